@@ -0,0 +1,88 @@
+use std::io;
+use std::path::PathBuf;
+
+use crate::agent::{AgentEvent, BackendKind, CodexAdapter};
+use crate::app::App;
+use crate::session_store::SessionStore;
+use crate::tasks_watch::external_edit_for;
+
+/// Answers chat questions while worker execution runs, with a separate master-configured
+/// adapter so the master's own session and tasks.json stay untouched.
+#[derive(Default)]
+pub struct AdvisorChat {
+    adapter: Option<CodexAdapter>,
+    /// Backend and session the adapter was built for; a change starts a fresh advisor.
+    built_for: Option<(BackendKind, PathBuf)>,
+    /// tasks.json as it was when the current question was sent.
+    tasks_snapshot: Option<String>,
+    project_info_sent: bool,
+}
+
+impl AdvisorChat {
+    pub fn is_in_flight(&self) -> bool {
+        self.tasks_snapshot.is_some()
+    }
+
+    /// Sends `prompt(include_project_info)` to the advisor, building its adapter with `build`
+    /// when there is none for `backend` and the store's session yet.
+    pub fn ask(
+        &mut self,
+        store: &SessionStore,
+        backend: BackendKind,
+        build: impl FnOnce() -> CodexAdapter,
+        prompt: impl FnOnce(bool) -> String,
+    ) {
+        let key = (backend, store.session_dir().to_path_buf());
+        if self.built_for.as_ref() != Some(&key) {
+            self.adapter = Some(build());
+            self.built_for = Some(key);
+            self.project_info_sent = false;
+        }
+        let Some(adapter) = self.adapter.as_ref() else {
+            return;
+        };
+        self.tasks_snapshot = Some(store.read_tasks_text().unwrap_or_default());
+        adapter.send_prompt(prompt(!self.project_info_sent));
+        self.project_info_sent = true;
+    }
+
+    pub fn drain_events(&self, max_events: usize) -> Vec<AgentEvent> {
+        self.adapter
+            .as_ref()
+            .map(|adapter| adapter.drain_events_limited(max_events))
+            .unwrap_or_default()
+    }
+
+    /// Ends the current question and returns tasks.json as it was when it was asked.
+    pub fn finish(&mut self) -> Option<String> {
+        self.tasks_snapshot.take()
+    }
+}
+
+/// Undoes a tasks.json edit made while the advisor ran. The file is rewritten from the app's
+/// task tree, or from `snapshot` when the user's own external edit is waiting for
+/// `/reload-tasks`. Returns whether anything was restored.
+pub fn reject_tasks_file_edits(
+    app: &App,
+    store: &SessionStore,
+    snapshot: &str,
+) -> io::Result<bool> {
+    let current = std::fs::read_to_string(store.tasks_file()).unwrap_or_default();
+    if current == snapshot {
+        return Ok(false);
+    }
+    if app.has_external_tasks_edit() {
+        store.write_tasks_text(snapshot)?;
+        return Ok(true);
+    }
+    if external_edit_for(app, store, current).is_none() {
+        // The app's own runtime status writes.
+        return Ok(false);
+    }
+    store.write_tasks(&app.planner_tasks_for_file())?;
+    Ok(true)
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/advisor_tests.rs"]
+mod tests;
@@ -1,9 +1,15 @@
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::mpsc::Receiver;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::audit_trail::{self, AuditEventFile};
+use crate::backend_metrics::{self, BackendCallFileEntry};
+use crate::telemetry::{self, SpanContext, SpanKind};
+use crate::wakeup::{self, Sender};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AgentEvent {
@@ -12,6 +18,36 @@ pub enum AgentEvent {
     Completed { success: bool, code: i32 },
 }
 
+/// Token counts and cost reported by JSON-mode agent runs (Codex `turn.completed`,
+/// Claude `result`). Plain-text runs do not report usage.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl TokenUsage {
+    pub fn add(&mut self, other: TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cost_usd += other.cost_usd;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == TokenUsage::default()
+    }
+}
+
+/// `950`, `12.3k`, `4.1M`.
+pub fn compact_count(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CodexCommandConfig {
     pub program: String,
@@ -21,6 +57,8 @@ pub struct CodexCommandConfig {
     pub skip_reader_join_after_wait: bool,
     pub model: Option<String>,
     pub model_reasoning_effort: Option<String>,
+    /// Directory the agent process starts in; `None` keeps the workspace root.
+    pub workdir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -42,6 +80,13 @@ impl BackendKind {
             Self::Codex
         }
     }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Codex => "codex",
+            Self::Claude => "claude",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,6 +113,7 @@ impl CodexCommandConfig {
                 skip_reader_join_after_wait: false,
                 model: None,
                 model_reasoning_effort: None,
+                workdir: None,
             },
             BackendKind::Claude => Self {
                 program: "claude".to_string(),
@@ -77,6 +123,7 @@ impl CodexCommandConfig {
                 skip_reader_join_after_wait: false,
                 model: None,
                 model_reasoning_effort: None,
+                workdir: None,
             },
         }
     }
@@ -84,6 +131,56 @@ impl CodexCommandConfig {
     pub fn backend_kind(&self) -> BackendKind {
         BackendKind::from_program(&self.program)
     }
+
+    /// Drops the flags that let the agent write and runs it in the backend's read-only
+    /// sandbox instead: Codex's `read-only` sandbox mode, Claude's plan permission mode.
+    pub fn make_read_only(&mut self) {
+        self.drop_bypass_flags();
+        match self.backend_kind() {
+            // A config override, unlike `--sandbox`, is also accepted by `codex exec resume`.
+            BackendKind::Codex => self
+                .args_prefix
+                .extend(["-c".to_string(), "sandbox_mode=\"read-only\"".to_string()]),
+            BackendKind::Claude => self
+                .args_prefix
+                .extend(["--permission-mode".to_string(), "plan".to_string()]),
+        }
+    }
+
+    /// Like [`make_read_only`](Self::make_read_only), but `writable_dir` can still be
+    /// written as an extra directory; the agent keeps running in its working directory.
+    /// Claude may only edit and write under `writable_dir`. Codex gets its `workspace-write`
+    /// sandbox with `writable_dir` as another writable root; that sandbox always includes the
+    /// working directory as well.
+    pub fn make_read_only_except(&mut self, writable_dir: &Path) {
+        self.drop_bypass_flags();
+        let dir = writable_dir.display().to_string();
+        match self.backend_kind() {
+            BackendKind::Codex => self.args_prefix.extend([
+                "-c".to_string(),
+                "sandbox_mode=\"workspace-write\"".to_string(),
+                "-c".to_string(),
+                format!(
+                    "sandbox_workspace_write.writable_roots=[{}]",
+                    toml::Value::String(dir)
+                ),
+            ]),
+            // Without the bypass flag, tools that are not allowed here are refused in print mode.
+            BackendKind::Claude => self.args_prefix.extend([
+                "--add-dir".to_string(),
+                dir.clone(),
+                "--allowedTools".to_string(),
+                format!("Edit(/{dir}/**),MultiEdit(/{dir}/**),Write(/{dir}/**)"),
+            ]),
+        }
+    }
+
+    fn drop_bypass_flags(&mut self) {
+        self.args_prefix.retain(|arg| {
+            arg != "--dangerously-bypass-approvals-and-sandbox"
+                && arg != "--dangerously-skip-permissions"
+        });
+    }
 }
 
 impl Default for CodexCommandConfig {
@@ -92,11 +189,51 @@ impl Default for CodexCommandConfig {
     }
 }
 
+/// What agents may write while `--safe-mode` is on: only the open session's directory.
+/// Every adapter of a run shares one, so switching sessions moves it for their next runs;
+/// without a session, agents cannot write at all.
+#[derive(Debug, Clone, Default)]
+pub struct SafeModeSandbox {
+    session_dir: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl SafeModeSandbox {
+    pub fn session_dir(&self) -> Option<PathBuf> {
+        self.session_dir.lock().ok().and_then(|dir| dir.clone())
+    }
+
+    pub fn set_session_dir(&self, dir: Option<PathBuf>) {
+        if let Ok(mut session_dir) = self.session_dir.lock() {
+            *session_dir = dir;
+        }
+    }
+
+    /// Sandboxes `config` for one run.
+    pub fn apply(&self, config: &mut CodexCommandConfig) {
+        match self.session_dir() {
+            Some(dir) => config.make_read_only_except(&dir),
+            None => config.make_read_only(),
+        }
+    }
+}
+
 pub struct CodexAdapter {
     config: CodexCommandConfig,
     event_tx: Sender<AgentEvent>,
     event_rx: Receiver<AgentEvent>,
     session_id: Arc<Mutex<Option<String>>>,
+    usage: Arc<Mutex<TokenUsage>>,
+    running_pid: Arc<Mutex<Option<u32>>>,
+    trace_parent: Mutex<Option<SpanContext>>,
+    /// Prompt of the latest run, kept so a run that failed for a transient reason can be
+    /// sent again unchanged.
+    last_prompt: Mutex<Option<String>>,
+    /// Agent slot runs are recorded under in the backend metrics; `None` records nothing.
+    agent_kind: Option<&'static str>,
+    /// Put before every prompt of this adapter, after the backend's global preamble.
+    prompt_preamble: Option<String>,
+    /// Applied to the config of every run while `--safe-mode` is on.
+    safe_mode: Option<SafeModeSandbox>,
 }
 
 const CODEX_GLOBAL_PROMPT_PREAMBLE: Option<&str> = Some(
@@ -142,23 +279,107 @@ impl CodexAdapter {
     }
 
     pub fn with_config(config: CodexCommandConfig) -> Self {
-        let (event_tx, event_rx) = mpsc::channel();
+        let (event_tx, event_rx) = wakeup::channel();
         Self {
             config,
             event_tx,
             event_rx,
             session_id: Arc::new(Mutex::new(None)),
+            usage: Arc::new(Mutex::new(TokenUsage::default())),
+            running_pid: Arc::new(Mutex::new(None)),
+            trace_parent: Mutex::new(None),
+            last_prompt: Mutex::new(None),
+            agent_kind: None,
+            prompt_preamble: None,
+            safe_mode: None,
+        }
+    }
+
+    /// Records this adapter's runs in the backend metrics under `agent_kind`.
+    pub fn with_agent_kind(mut self, agent_kind: &'static str) -> Self {
+        self.agent_kind = Some(agent_kind);
+        self
+    }
+
+    /// Puts `preamble` before every prompt this adapter sends.
+    pub fn with_prompt_preamble(mut self, preamble: String) -> Self {
+        self.prompt_preamble = Some(preamble);
+        self
+    }
+
+    /// Runs this adapter in `sandbox`.
+    pub fn with_safe_mode(mut self, sandbox: SafeModeSandbox) -> Self {
+        self.safe_mode = Some(sandbox);
+        self
+    }
+
+    /// The config the next run starts with: this adapter's, sandboxed in safe mode.
+    pub fn run_config(&self) -> CodexCommandConfig {
+        let mut config = self.config.clone();
+        if let Some(sandbox) = &self.safe_mode {
+            sandbox.apply(&mut config);
+        }
+        config
+    }
+
+    /// Span the next runs are traced under; `None` traces them under the run's root span.
+    pub fn set_trace_parent(&self, parent: Option<SpanContext>) {
+        if let Ok(mut trace_parent) = self.trace_parent.lock() {
+            *trace_parent = parent;
         }
     }
 
     pub fn send_prompt(&self, prompt: String) {
-        let config = self.config.clone();
+        self.start_run(prompt, false);
+    }
+
+    fn start_run(&self, prompt: String, retry: bool) {
+        if let Ok(mut last_prompt) = self.last_prompt.lock() {
+            *last_prompt = Some(prompt.clone());
+        }
+        let config = self.run_config();
         let program = config.program.clone();
         let tx = self.event_tx.clone();
         let session_id = self.session_id.clone();
+        let usage = self.usage.clone();
+        let running_pid = self.running_pid.clone();
         let session_id_snapshot = self.saved_session_id();
+        let trace_parent = self.trace_parent.lock().ok().and_then(|parent| *parent);
+        let agent_kind = self.agent_kind;
+        let prompt = match &self.prompt_preamble {
+            Some(preamble) => format!("{preamble}\n\n{prompt}"),
+            None => prompt,
+        };
         thread::spawn(move || {
+            let started = Instant::now();
+            let record_call = |exit_code: i32| {
+                if let Some(agent_kind) = agent_kind {
+                    backend_metrics::record(&BackendCallFileEntry::new(
+                        agent_kind,
+                        config.backend_kind().label(),
+                        config.model.as_deref(),
+                        started.elapsed().as_millis() as u64,
+                        exit_code,
+                        retry,
+                    ));
+                }
+            };
+            let mut span = telemetry::child_span("agent.run", SpanKind::Client, trace_parent);
+            span.set_attribute("agent.program", program.as_str());
+            if let Some(model) = config.model.as_deref() {
+                span.set_attribute("agent.model", model);
+            }
+            span.set_attribute(
+                "agent.resumed",
+                config.persistent_session && session_id_snapshot.is_some(),
+            );
+            span.set_attribute("agent.prompt_chars", prompt.chars().count());
             let prompt = apply_global_prompt_preamble(prompt, &config.program);
+            audit_trail::record(AuditEventFile::PromptSent {
+                program: program.clone(),
+                model: config.model.clone(),
+                prompt: prompt.clone(),
+            });
             let mut command = Command::new(&config.program);
             if config.persistent_session {
                 let known_session = session_id_snapshot;
@@ -172,11 +393,16 @@ impl CodexAdapter {
             } else {
                 command.args(build_new_session_args(&config)).arg(prompt);
             }
+            if let Some(workdir) = &config.workdir {
+                command.current_dir(workdir);
+            }
             command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
             let mut child = match command.spawn() {
                 Ok(child) => child,
                 Err(err) => {
+                    record_call(-1);
+                    span.set_error(format!("failed to start: {err}"));
                     let _ = tx.send(AgentEvent::System(format!(
                         "Adapter ({program}) failed to start: {err}"
                     )));
@@ -187,6 +413,7 @@ impl CodexAdapter {
                     return;
                 }
             };
+            set_running_pid(&running_pid, Some(child.id()));
 
             let mut readers = Vec::new();
 
@@ -196,6 +423,7 @@ impl CodexAdapter {
                     tx.clone(),
                     config.output_mode,
                     Some(session_id.clone()),
+                    usage.clone(),
                     config.backend_kind(),
                     false,
                 ));
@@ -206,12 +434,22 @@ impl CodexAdapter {
                     tx.clone(),
                     config.output_mode,
                     Some(session_id.clone()),
+                    usage.clone(),
                     config.backend_kind(),
                     true,
                 ));
             }
 
             let wait_result = child.wait();
+            set_running_pid(&running_pid, None);
+            record_call(
+                wait_result
+                    .as_ref()
+                    .ok()
+                    .and_then(|status| status.code())
+                    .unwrap_or(-1),
+            );
+            record_exit_in_span(span, &wait_result);
             let skip_reader_join_after_wait = (config.persistent_session
                 && matches!(config.output_mode, AdapterOutputMode::PlainText))
                 || (config.persistent_session && config.skip_reader_join_after_wait);
@@ -230,7 +468,25 @@ impl CodexAdapter {
         });
     }
 
-    #[cfg(test)]
+    /// Finishes a run without starting the CLI, for a result taken from the response cache:
+    /// emits `note` and a successful completion like a real run would.
+    pub fn complete_from_cache(&self, note: String) {
+        let _ = self.event_tx.send(AgentEvent::System(note));
+        let _ = self.event_tx.send(AgentEvent::Completed {
+            success: true,
+            code: 0,
+        });
+    }
+
+    /// Sends the latest prompt again. Returns false when nothing was sent yet.
+    pub fn resend_last_prompt(&self) -> bool {
+        let Some(prompt) = self.last_prompt.lock().ok().and_then(|lock| lock.clone()) else {
+            return false;
+        };
+        self.start_run(prompt, true);
+        true
+    }
+
     pub fn program(&self) -> &str {
         &self.config.program
     }
@@ -256,9 +512,38 @@ impl CodexAdapter {
             };
             events.push(event);
         }
+        if events.len() == max_events {
+            // More may be queued; keep the main loop from sleeping on them.
+            wakeup::notify();
+        }
         events
     }
 
+    /// Blocks until the adapter emits an event or `timeout` passes.
+    pub fn wait_event(&self, timeout: Duration) -> Option<AgentEvent> {
+        self.event_rx.recv_timeout(timeout).ok()
+    }
+
+    /// Usage reported since the last call.
+    pub fn take_usage(&self) -> TokenUsage {
+        self.usage
+            .lock()
+            .map(|mut lock| std::mem::take(&mut *lock))
+            .unwrap_or_default()
+    }
+
+    pub fn model(&self) -> Option<&str> {
+        self.config.model.as_deref()
+    }
+
+    pub fn model_reasoning_effort(&self) -> Option<&str> {
+        self.config.model_reasoning_effort.as_deref()
+    }
+
+    pub fn workdir(&self) -> Option<&Path> {
+        self.config.workdir.as_deref()
+    }
+
     pub fn reset_session(&self) {
         self.set_saved_session_id(None);
     }
@@ -272,6 +557,67 @@ impl CodexAdapter {
             *lock = session_id;
         }
     }
+
+    /// Sends SIGTERM to the running agent process. Returns false when nothing is running.
+    pub fn terminate(&self) -> bool {
+        terminate_running(&self.running_pid)
+    }
+}
+
+/// Lowercase fragments of the errors agent CLIs print when the run failed because of the
+/// process, network, or provider rather than the task.
+const TRANSIENT_FAILURE_MARKERS: [&str; 14] = [
+    "failed to start:",
+    "stream disconnected",
+    "connection reset",
+    "connection refused",
+    "connection closed",
+    "network error",
+    "error sending request",
+    "timed out",
+    "rate limit",
+    "too many requests",
+    "overloaded",
+    "service unavailable",
+    "bad gateway",
+    "internal server error",
+];
+
+/// Whether a system or stderr line reports a failure worth retrying unchanged, such as the
+/// process failing to start or the provider dropping the connection. Only lines outside the
+/// agent's own output count, since the agent may well write these words about the task.
+pub fn is_transient_failure_line(line: &str) -> bool {
+    let line = line.to_ascii_lowercase();
+    TRANSIENT_FAILURE_MARKERS
+        .iter()
+        .any(|marker| line.contains(marker))
+}
+
+pub(crate) fn set_running_pid(slot: &Mutex<Option<u32>>, pid: Option<u32>) {
+    if let Ok(mut lock) = slot.lock() {
+        *lock = pid;
+    }
+}
+
+/// Asks the process recorded in `slot` to exit; its completion event still arrives as usual.
+pub(crate) fn terminate_running(slot: &Mutex<Option<u32>>) -> bool {
+    let Some(pid) = slot.lock().ok().and_then(|lock| *lock) else {
+        return false;
+    };
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("taskkill");
+        command.args(["/T", "/F", "/PID", &pid.to_string()]);
+        command
+    } else {
+        let mut command = Command::new("kill");
+        command.args(["-TERM", &pid.to_string()]);
+        command
+    };
+    command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
 }
 
 fn spawn_reader<R: std::io::Read + Send + 'static>(
@@ -279,6 +625,7 @@ fn spawn_reader<R: std::io::Read + Send + 'static>(
     tx: Sender<AgentEvent>,
     output_mode: AdapterOutputMode,
     session_id: Option<Arc<Mutex<Option<String>>>>,
+    usage: Arc<Mutex<TokenUsage>>,
     backend_kind: BackendKind,
     is_stderr: bool,
 ) -> thread::JoinHandle<()> {
@@ -291,9 +638,20 @@ fn spawn_reader<R: std::io::Read + Send + 'static>(
             {
                 *lock = Some(found);
             }
+            if let Some(reported) = parse_usage_from_jsonl_line(&line)
+                && let Ok(mut lock) = usage.lock()
+            {
+                lock.add(reported);
+            }
             match output_mode {
                 AdapterOutputMode::PlainText => {
-                    let _ = tx.send(AgentEvent::Output(line));
+                    // Stderr reads as output here, apart from the failure lines the app checks
+                    // to decide whether a failed run is worth retrying.
+                    if is_stderr && is_transient_failure_line(&line) {
+                        let _ = tx.send(AgentEvent::System(line));
+                    } else {
+                        let _ = tx.send(AgentEvent::Output(line));
+                    }
                 }
                 AdapterOutputMode::JsonAssistantOnly => {
                     if let Some(text) = parse_agent_message_from_jsonl_line(&line) {
@@ -524,6 +882,13 @@ fn program_looks_like_claude(program: &str) -> bool {
         .contains("claude")
 }
 
+/// Arguments for a single non-persistent prompt, exactly as a fresh adapter would send it.
+pub fn one_shot_prompt_args(config: &CodexCommandConfig, prompt: &str) -> Vec<String> {
+    let mut args = build_new_session_args(config);
+    args.push(prompt.to_string());
+    args
+}
+
 fn build_new_session_args(config: &CodexCommandConfig) -> Vec<String> {
     match config.backend_kind() {
         BackendKind::Codex => {
@@ -620,6 +985,22 @@ fn append_claude_model_selection_args(args: &mut Vec<String>, model: Option<&str
     }
 }
 
+fn record_exit_in_span(
+    mut span: telemetry::Span,
+    wait_result: &std::io::Result<std::process::ExitStatus>,
+) {
+    match wait_result {
+        Ok(status) => {
+            let code = status.code().unwrap_or(-1);
+            span.set_attribute("process.exit_code", code);
+            if !status.success() {
+                span.set_error(format!("exited with status code {code}"));
+            }
+        }
+        Err(err) => span.set_error(format!("failed while waiting for process: {err}")),
+    }
+}
+
 fn emit_completion_event(
     tx: &Sender<AgentEvent>,
     program: &str,
@@ -650,6 +1031,47 @@ fn emit_completion_event(
     }
 }
 
+fn parse_usage_from_jsonl_line(line: &str) -> Option<TokenUsage> {
+    if !looks_like_json_line(line) {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    let count = |usage: &serde_json::Value, key: &str| {
+        usage
+            .get(key)
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0)
+    };
+    match value.get("type").and_then(|kind| kind.as_str())? {
+        // Codex counts cached input inside `input_tokens`.
+        "turn.completed" => {
+            let usage = value.get("usage")?;
+            Some(TokenUsage {
+                input_tokens: count(usage, "input_tokens"),
+                output_tokens: count(usage, "output_tokens"),
+                cost_usd: 0.0,
+            })
+        }
+        "result" => {
+            let usage = value.get("usage");
+            Some(TokenUsage {
+                input_tokens: usage.map_or(0, |usage| {
+                    count(usage, "input_tokens")
+                        + count(usage, "cache_creation_input_tokens")
+                        + count(usage, "cache_read_input_tokens")
+                }),
+                output_tokens: usage.map_or(0, |usage| count(usage, "output_tokens")),
+                cost_usd: value
+                    .get("total_cost_usd")
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.0),
+            })
+            .filter(|usage| !usage.is_empty())
+        }
+        _ => None,
+    }
+}
+
 fn parse_session_id_from_jsonl_line(line: &str, backend_kind: BackendKind) -> Option<String> {
     let value: serde_json::Value = serde_json::from_str(line).ok()?;
     if matches!(backend_kind, BackendKind::Claude) {
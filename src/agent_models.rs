@@ -1,17 +1,19 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io;
+use std::path::{Path, PathBuf};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::agent::{BackendKind, CodexCommandConfig};
-use crate::artifact_io::load_merged_metaagent_config_text;
+use crate::agent::{BackendKind, CodexCommandConfig, SafeModeSandbox};
+use crate::artifact_io::{is_workspace_subdir, load_merged_metaagent_config_text};
 use crate::default_config::DEFAULT_CONFIG_TOML;
 
 pub const DEFAULT_PROFILE_LABEL: &str = "large-smart";
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CodexModelProfile {
     pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub thinking_effort: Option<String>,
 }
 
@@ -30,6 +32,41 @@ impl CodexModelProfile {
             thinking_effort,
         })
     }
+
+    /// The model with its thinking effort, e.g. `gpt-5 (high)`.
+    pub fn describe(&self) -> String {
+        match &self.thinking_effort {
+            Some(effort) => format!("{} ({effort})", self.model),
+            None => self.model.clone(),
+        }
+    }
+}
+
+/// An agent slot whose configured model differs from the one a session recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelDrift {
+    pub agent: String,
+    pub recorded: CodexModelProfile,
+    pub configured: CodexModelProfile,
+}
+
+/// Slots in `recorded` that `configured` routes to another model or thinking effort, in
+/// slot-key order. Slots missing from either side are not drift.
+pub fn model_drift(
+    recorded: &BTreeMap<String, CodexModelProfile>,
+    configured: &BTreeMap<String, CodexModelProfile>,
+) -> Vec<ModelDrift> {
+    recorded
+        .iter()
+        .filter_map(|(agent, recorded)| {
+            let configured = configured.get(agent)?;
+            (configured != recorded).then(|| ModelDrift {
+                agent: agent.clone(),
+                recorded: recorded.clone(),
+                configured: configured.clone(),
+            })
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,11 +82,88 @@ pub enum CodexAgentKind {
     WorkerFinalAudit,
 }
 
+impl CodexAgentKind {
+    pub const ALL: [Self; 9] = [
+        Self::Master,
+        Self::MasterReport,
+        Self::ProjectInfo,
+        Self::DocsAttach,
+        Self::TaskCheck,
+        Self::WorkerImplementor,
+        Self::WorkerAuditor,
+        Self::WorkerTestWriter,
+        Self::WorkerFinalAudit,
+    ];
+
+    /// Key of this agent slot in `[codex.agent_profiles]` and `[codex.agent_workdirs]`.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            Self::Master => "master",
+            Self::MasterReport => "master_report",
+            Self::ProjectInfo => "project_info",
+            Self::DocsAttach => "docs_attach",
+            Self::TaskCheck => "task_check",
+            Self::WorkerImplementor => "worker_implementor",
+            Self::WorkerAuditor => "worker_auditor",
+            Self::WorkerTestWriter => "worker_test_writer",
+            Self::WorkerFinalAudit => "worker_final_audit",
+        }
+    }
+}
+
+/// A backend from the `[backend]` table, whether or not it is the selected one.
+#[derive(Debug, Clone)]
+pub struct ConfiguredBackend {
+    pub kind: BackendKind,
+    pub selected: bool,
+    pub command: CodexCommandConfig,
+}
+
+pub fn load_configured_backends() -> io::Result<Vec<ConfiguredBackend>> {
+    configured_backends_from_toml_str(&load_merged_metaagent_config_text()?)
+}
+
+/// Every configured backend, selected backend first.
+pub fn configured_backends_from_toml_str(text: &str) -> io::Result<Vec<ConfiguredBackend>> {
+    let backend = parse_config(DEFAULT_CONFIG_TOML)?
+        .backend
+        .merged_with(parse_config(text)?.backend);
+    let selected_kind = backend.selected_kind();
+    let mut backends = vec![
+        ConfiguredBackend {
+            kind: BackendKind::Codex,
+            selected: selected_kind == BackendKind::Codex,
+            command: backend.codex.into_runtime(BackendKind::Codex),
+        },
+        ConfiguredBackend {
+            kind: BackendKind::Claude,
+            selected: selected_kind == BackendKind::Claude,
+            command: backend.claude.into_runtime(BackendKind::Claude),
+        },
+    ];
+    backends.sort_by_key(|backend| !backend.selected);
+    Ok(backends)
+}
+
 #[derive(Debug, Clone)]
 pub struct CodexAgentModelRouting {
     profiles: HashMap<String, CodexModelProfile>,
     agent_profiles: AgentProfileAssignments,
+    /// Profiles from weakest to strongest that implementors climb after repeated audit
+    /// failures.
+    escalation_ladder: Vec<String>,
+    /// Workspace-relative directories from `[codex.agent_workdirs]`, by agent config key.
+    agent_workdirs: HashMap<String, PathBuf>,
     base_command: CodexCommandConfig,
+    /// Set by `--safe-mode`. Workers run in the backend's read-only sandbox, and the other
+    /// agents can only write in the open session's directory.
+    safe_mode: Option<SafeModeSandbox>,
+    /// Models a session pinned with `/pin-models`, by agent config key; they win over
+    /// `[codex.agent_profiles]`.
+    pinned_models: BTreeMap<String, CodexModelProfile>,
+    /// Workspace agents run in after `/resume` moved to another one; `None` runs them in
+    /// the process working directory.
+    workspace: Option<PathBuf>,
 }
 
 impl Default for CodexAgentModelRouting {
@@ -67,10 +181,17 @@ impl CodexAgentModelRouting {
     pub fn from_toml_str(text: &str) -> io::Result<Self> {
         let default_config = parse_config(DEFAULT_CONFIG_TOML)?;
         let override_config = parse_config(text)?;
-        Ok(Self::from_merged_config(default_config, override_config))
+        Self::from_merged_config(default_config, override_config)
     }
 
     pub fn profile_for(&self, kind: CodexAgentKind) -> CodexModelProfile {
+        if let Some(pinned) = self.pinned_models.get(kind.config_key()) {
+            return pinned.clone();
+        }
+        self.configured_profile_for(kind)
+    }
+
+    fn configured_profile_for(&self, kind: CodexAgentKind) -> CodexModelProfile {
         let label = self.agent_profiles.label_for(kind);
         self.profiles
             .get(label)
@@ -79,11 +200,108 @@ impl CodexAgentModelRouting {
             .unwrap_or_else(default_large_smart_profile)
     }
 
+    /// Profile named `label` in the config, if there is one.
+    pub fn profile_named(&self, label: &str) -> Option<CodexModelProfile> {
+        self.profiles.get(label).cloned()
+    }
+
+    /// Profile label routed to `kind` in `[codex.agent_profiles]`.
+    pub fn profile_label_for(&self, kind: CodexAgentKind) -> &str {
+        self.agent_profiles.label_for(kind)
+    }
+
+    /// Profile `steps` rungs above `current` on `[codex] escalation_ladder`, capped at the
+    /// top. `None` when `current` is not on the ladder or is already at the top.
+    pub fn escalated_profile(&self, current: &str, steps: u8) -> Option<String> {
+        let current = normalize_profile_label(current);
+        let index = self
+            .escalation_ladder
+            .iter()
+            .position(|label| *label == current)?;
+        let target = (index + usize::from(steps)).min(self.escalation_ladder.len() - 1);
+        (target > index).then(|| self.escalation_ladder[target].clone())
+    }
+
+    /// Names of every configured profile, sorted.
+    pub fn profile_labels(&self) -> Vec<String> {
+        let mut labels = self.profiles.keys().cloned().collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
+
+    /// Directory `kind` runs in when `[codex.agent_workdirs]` sets one; otherwise the
+    /// workspace root.
+    pub fn workdir_for(&self, kind: CodexAgentKind) -> Option<PathBuf> {
+        self.in_workspace(self.agent_workdirs.get(kind.config_key()).cloned())
+    }
+
+    /// `dir` resolved against the workspace from [`Self::set_workspace`], or that workspace
+    /// when `dir` is `None`. Without one, `dir` is kept as is.
+    pub fn in_workspace(&self, dir: Option<PathBuf>) -> Option<PathBuf> {
+        match (&self.workspace, dir) {
+            (Some(workspace), Some(dir)) => Some(workspace.join(dir)),
+            (Some(workspace), None) => Some(workspace.clone()),
+            (None, dir) => dir,
+        }
+    }
+
+    pub fn workspace(&self) -> Option<&Path> {
+        self.workspace.as_deref()
+    }
+
+    pub fn set_workspace(&mut self, workspace: Option<PathBuf>) {
+        self.workspace = workspace;
+    }
+
     pub fn base_command_config(&self) -> CodexCommandConfig {
         self.base_command.clone()
     }
 
-    fn from_merged_config(base: MetaAgentConfigFile, override_cfg: MetaAgentConfigFile) -> Self {
+    pub fn safe_mode(&self) -> Option<&SafeModeSandbox> {
+        self.safe_mode.as_ref()
+    }
+
+    pub fn set_safe_mode(&mut self, sandbox: Option<SafeModeSandbox>) {
+        self.safe_mode = sandbox;
+    }
+
+    pub fn pinned_models(&self) -> &BTreeMap<String, CodexModelProfile> {
+        &self.pinned_models
+    }
+
+    pub fn set_pinned_models(&mut self, pinned: BTreeMap<String, CodexModelProfile>) {
+        self.pinned_models = pinned;
+    }
+
+    /// The model config routes to each agent slot, by config key, ignoring pinned models.
+    pub fn configured_models(&self) -> BTreeMap<String, CodexModelProfile> {
+        CodexAgentKind::ALL
+            .into_iter()
+            .map(|kind| {
+                (
+                    kind.config_key().to_string(),
+                    self.configured_profile_for(kind),
+                )
+            })
+            .collect()
+    }
+
+    /// Distinct models routed to any agent slot, each with the slots that use it.
+    pub fn assigned_models(&self) -> BTreeMap<String, Vec<&'static str>> {
+        let mut models = BTreeMap::<String, Vec<&'static str>>::new();
+        for kind in CodexAgentKind::ALL {
+            models
+                .entry(self.profile_for(kind).model)
+                .or_default()
+                .push(kind.config_key());
+        }
+        models
+    }
+
+    fn from_merged_config(
+        base: MetaAgentConfigFile,
+        override_cfg: MetaAgentConfigFile,
+    ) -> io::Result<Self> {
         let merged_backend = base.backend.merged_with(override_cfg.backend);
         let merged_codex = base.codex.merged_with(override_cfg.codex);
         Self::from_merged_runtime_config(merged_backend, merged_codex)
@@ -92,7 +310,7 @@ impl CodexAgentModelRouting {
     fn from_merged_runtime_config(
         backend: BackendSelectionConfigFile,
         config: CodexModelConfigFile,
-    ) -> Self {
+    ) -> io::Result<Self> {
         let mut profiles = HashMap::new();
         for (label, profile) in config.model_profiles {
             let Some(parsed_profile) = CodexModelProfile::from_config(profile) else {
@@ -100,11 +318,23 @@ impl CodexAgentModelRouting {
             };
             profiles.insert(normalize_profile_label(&label), parsed_profile);
         }
-        Self {
+        let escalation_ladder = config
+            .escalation_ladder
+            .unwrap_or_default()
+            .iter()
+            .map(|label| normalize_profile_label(label))
+            .filter(|label| profiles.contains_key(label))
+            .collect();
+        Ok(Self {
             profiles,
             agent_profiles: config.agent_profiles.into_runtime(),
+            escalation_ladder,
+            agent_workdirs: parse_agent_workdirs(config.agent_workdirs)?,
             base_command: backend.into_runtime(),
-        }
+            safe_mode: None,
+            pinned_models: BTreeMap::new(),
+            workspace: None,
+        })
     }
 
     fn emergency_fallback() -> Self {
@@ -116,7 +346,12 @@ impl CodexAgentModelRouting {
         Self {
             profiles,
             agent_profiles: AgentProfileAssignments::default(),
+            escalation_ladder: Vec::new(),
+            agent_workdirs: HashMap::new(),
             base_command: CodexCommandConfig::default(),
+            safe_mode: None,
+            pinned_models: BTreeMap::new(),
+            workspace: None,
         }
     }
 }
@@ -145,18 +380,22 @@ impl BackendSelectionConfigFile {
         }
     }
 
-    fn into_runtime(self) -> CodexCommandConfig {
+    fn selected_kind(&self) -> BackendKind {
         let selected = self
             .selected
             .as_deref()
             .map(str::trim)
             .unwrap_or("codex")
             .to_ascii_lowercase();
-        let backend_kind = if selected == "claude" {
+        if selected == "claude" {
             BackendKind::Claude
         } else {
             BackendKind::Codex
-        };
+        }
+    }
+
+    fn into_runtime(self) -> CodexCommandConfig {
+        let backend_kind = self.selected_kind();
         let backend_config = match backend_kind {
             BackendKind::Codex => self.codex,
             BackendKind::Claude => self.claude,
@@ -206,6 +445,8 @@ impl BackendCommandConfigFile {
 struct CodexModelConfigFile {
     model_profiles: HashMap<String, CodexModelProfileConfig>,
     agent_profiles: AgentProfileAssignmentsConfig,
+    escalation_ladder: Option<Vec<String>>,
+    agent_workdirs: HashMap<String, String>,
 }
 
 impl CodexModelConfigFile {
@@ -214,9 +455,13 @@ impl CodexModelConfigFile {
         for (label, profile) in override_cfg.model_profiles {
             model_profiles.insert(label, profile);
         }
+        let mut agent_workdirs = self.agent_workdirs;
+        agent_workdirs.extend(override_cfg.agent_workdirs);
         Self {
             model_profiles,
             agent_profiles: self.agent_profiles.merged_with(override_cfg.agent_profiles),
+            escalation_ladder: override_cfg.escalation_ladder.or(self.escalation_ladder),
+            agent_workdirs,
         }
     }
 }
@@ -322,6 +567,38 @@ fn parse_config(text: &str) -> io::Result<MetaAgentConfigFile> {
         .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
 }
 
+/// Validates `[codex.agent_workdirs]`: keys must name an agent slot and values must stay
+/// inside the workspace. An empty value keeps that agent at the workspace root.
+fn parse_agent_workdirs(raw: HashMap<String, String>) -> io::Result<HashMap<String, PathBuf>> {
+    let mut workdirs = HashMap::new();
+    for (key, dir) in raw {
+        let key = normalize_profile_label(&key);
+        if !CodexAgentKind::ALL
+            .iter()
+            .any(|kind| kind.config_key() == key)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("[codex.agent_workdirs] names unknown agent \"{key}\""),
+            ));
+        }
+        let dir = dir.trim();
+        if dir.is_empty() {
+            continue;
+        }
+        if !is_workspace_subdir(dir) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "[codex.agent_workdirs] {key} = \"{dir}\" must be a relative path inside the workspace"
+                ),
+            ));
+        }
+        workdirs.insert(key, PathBuf::from(dir));
+    }
+    Ok(workdirs)
+}
+
 fn normalize_assignment(raw: Option<&str>) -> String {
     let normalized = normalize_profile_label(raw.unwrap_or(DEFAULT_PROFILE_LABEL));
     if normalized.is_empty() {
@@ -7,6 +7,7 @@ pub enum CapabilityId {
     AppPlannerStateSync,
     AppExecutionControl,
     EventPolling,
+    EventSubscription,
     WorkflowTaskGraphSync,
     WorkflowExecutionQueue,
     WorkflowContextProjection,
@@ -14,6 +15,9 @@ pub enum CapabilityId {
     SessionPlannerStorage,
     SessionFailureStorage,
     SessionProjectContextStorage,
+    SessionCodeSearch,
+    SessionAuditTrail,
+    SessionTaskEditing,
     SubagentPromptGeneration,
 }
 
@@ -76,10 +80,11 @@ pub const CAPABILITY_MATRIX: &[CapabilityDefinition] = &[
         id: CapabilityId::AppExecutionControl,
         domain: CapabilityDomain::App,
         operation: CapabilityOperation::CommandQuery,
-        request_contract: "AppRequest::{StartExecution,StartNextWorkerJob,WorkerOutput,WorkerCompleted,DrainWorkerFailures}",
+        request_contract: "AppRequest::{StartExecution,StartExecutionScoped,StartNextWorkerJob,WorkerOutput,WorkerCompleted,DrainWorkerFailures}",
         response_contract: "AppResponse::{ExecutionMessages,StartedWorkerJob,WorkerCompleted,WorkerFailures}",
         code_paths: &[
             "src/app.rs::start_execution",
+            "src/app.rs::start_execution_scoped",
             "src/app.rs::start_next_worker_job",
             "src/app.rs::on_worker_output",
             "src/app.rs::on_worker_completed",
@@ -96,6 +101,19 @@ pub const CAPABILITY_MATRIX: &[CapabilityDefinition] = &[
         code_paths: &["src/events.rs::next_event"],
         notes: "Maps terminal input/mouse transport into normalized app events.",
     },
+    CapabilityDefinition {
+        id: CapabilityId::EventSubscription,
+        domain: CapabilityDomain::Events,
+        operation: CapabilityOperation::Query,
+        request_contract: "EventsRequest::Subscribe",
+        response_contract: "EventsResponse::Batch",
+        code_paths: &[
+            "src/event_log.rs::last_event_seq",
+            "src/event_log.rs::wait_for_events",
+            "src/event_log.rs::SessionEventRecorder",
+        ],
+        notes: "Long-polls a session's append-only event log (task status, execution state, agent output) with cursor-based resumption.",
+    },
     CapabilityDefinition {
         id: CapabilityId::WorkflowTaskGraphSync,
         domain: CapabilityDomain::Workflow,
@@ -112,10 +130,11 @@ pub const CAPABILITY_MATRIX: &[CapabilityDefinition] = &[
         id: CapabilityId::WorkflowExecutionQueue,
         domain: CapabilityDomain::Workflow,
         operation: CapabilityOperation::CommandQuery,
-        request_contract: "WorkflowRequest::{StartExecution,StartNextJob,FinishActiveJob,DrainRecentFailures}",
+        request_contract: "WorkflowRequest::{StartExecution,StartExecutionScoped,StartNextJob,FinishActiveJob,DrainRecentFailures}",
         response_contract: "WorkflowResponse::{StartExecution,StartedJob,FinishActiveJob,RecentFailures}",
         code_paths: &[
             "src/workflow.rs::start_execution",
+            "src/workflow.rs::start_execution_scoped",
             "src/workflow.rs::start_next_job",
             "src/workflow.rs::finish_active_job",
             "src/workflow.rs::drain_recent_failures",
@@ -126,12 +145,13 @@ pub const CAPABILITY_MATRIX: &[CapabilityDefinition] = &[
         id: CapabilityId::WorkflowContextProjection,
         domain: CapabilityDomain::Workflow,
         operation: CapabilityOperation::CommandQuery,
-        request_contract: "WorkflowRequest::{RollingContextEntries,ReplaceRollingContextEntries,RightPaneBlockView}",
-        response_contract: "WorkflowResponse::{RollingContext,RightPaneBlock}",
+        request_contract: "WorkflowRequest::{RollingContextEntries,ReplaceRollingContextEntries,RightPaneBlockView,TaskGraphExport}",
+        response_contract: "WorkflowResponse::{RollingContext,RightPaneBlock,TaskGraph}",
         code_paths: &[
             "src/workflow.rs::rolling_context_entries",
             "src/workflow.rs::replace_rolling_context_entries",
             "src/workflow.rs::right_pane_block_view",
+            "src/task_graph.rs::render",
         ],
         notes: "Projects normalized context and pane representations for any adapter.",
     },
@@ -145,8 +165,9 @@ pub const CAPABILITY_MATRIX: &[CapabilityDefinition] = &[
             "src/session_store.rs::initialize",
             "src/session_store.rs::open_existing",
             "src/session_store.rs::list_sessions",
+            "src/session_template.rs::load_template",
         ],
-        notes: "Creates, resumes, and lists session storage roots without coupling to UI transport.",
+        notes: "Creates, resumes, and lists session storage roots without coupling to UI transport; init can seed a session from a template.",
     },
     CapabilityDefinition {
         id: CapabilityId::SessionPlannerStorage,
@@ -187,6 +208,42 @@ pub const CAPABILITY_MATRIX: &[CapabilityDefinition] = &[
         ],
         notes: "Manages project-context and session-meta documents consumed across subagents.",
     },
+    CapabilityDefinition {
+        id: CapabilityId::SessionCodeSearch,
+        domain: CapabilityDomain::Session,
+        operation: CapabilityOperation::Query,
+        request_contract: "SessionRequest::SearchCode",
+        response_contract: "SessionResponse::CodeSearchResults",
+        code_paths: &[
+            "src/code_index.rs::refresh_code_index",
+            "src/code_index.rs::search_code",
+        ],
+        notes: "Ranks workspace file chunks against a query using the session's embedding index.",
+    },
+    CapabilityDefinition {
+        id: CapabilityId::SessionAuditTrail,
+        domain: CapabilityDomain::Session,
+        operation: CapabilityOperation::Query,
+        request_contract: "SessionRequest::ExportAuditTrail",
+        response_contract: "SessionResponse::AuditTrail",
+        code_paths: &[
+            "src/audit_trail.rs::read_entries",
+            "src/audit_trail.rs::verify",
+        ],
+        notes: "Exports the session's hash-chained record of prompts, changed files, and approvals.",
+    },
+    CapabilityDefinition {
+        id: CapabilityId::SessionTaskEditing,
+        domain: CapabilityDomain::Session,
+        operation: CapabilityOperation::CommandQuery,
+        request_contract: "SessionRequest::{AddTask,UpdateTask,RemoveTask,ReorderTask}",
+        response_contract: "SessionResponse::Tasks",
+        code_paths: &[
+            "src/task_edits.rs::apply",
+            "src/session_store.rs::write_tasks_atomic",
+        ],
+        notes: "Edits one planner task at a time, validated like a tasks.json reload and written atomically.",
+    },
     CapabilityDefinition {
         id: CapabilityId::SubagentPromptGeneration,
         domain: CapabilityDomain::Subagent,
@@ -197,6 +254,8 @@ pub const CAPABILITY_MATRIX: &[CapabilityDefinition] = &[
             "src/subagents/master.rs::*",
             "src/subagents/project_info.rs::*",
             "src/subagents/task_check.rs::*",
+            "src/app.rs::prepare_attach_docs_prompt",
+            "src/workflow.rs::audit_prompt_for_planner_task",
         ],
         notes: "Builds deterministic prompt contracts used by worker and master adapters.",
     },
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -47,6 +49,9 @@ pub enum AppRequest {
         tasks_file: String,
     },
     StartExecution,
+    StartExecutionScoped {
+        task_id: String,
+    },
     StartNextWorkerJob,
     WorkerOutput {
         line: String,
@@ -119,12 +124,72 @@ pub struct StartedWorkerJobContract {
 #[serde(tag = "action", rename_all = "snake_case")]
 pub enum EventsRequest {
     NextEvent,
+    /// Long-polls a session's event log for records after the `after` cursor, waiting up to
+    /// `wait_ms` when none are available yet.
+    Subscribe {
+        #[serde(default)]
+        after: u64,
+        #[serde(default)]
+        limit: Option<usize>,
+        #[serde(default)]
+        wait_ms: u64,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum EventsResponse {
-    Event { event: AppEventContract },
+    Event {
+        event: AppEventContract,
+    },
+    /// `next_cursor` is the `after` value for the next subscribe call; it equals the request's
+    /// cursor when the batch is empty.
+    Batch {
+        events: Vec<SessionEventRecordContract>,
+        next_cursor: u64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionEventRecordContract {
+    pub seq: u64,
+    pub at_epoch_ms: u64,
+    pub event: SessionEventContract,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum SessionEventContract {
+    TaskStatusChanged {
+        task_id: String,
+        title: String,
+        status: PlannerTaskStatusContract,
+    },
+    ExecutionChanged {
+        enabled: bool,
+        busy: bool,
+    },
+    AgentOutput {
+        agent: String,
+        line: String,
+    },
+    AgentSystem {
+        agent: String,
+        line: String,
+    },
+    AgentCompleted {
+        agent: String,
+        success: bool,
+        exit_code: i32,
+    },
+    ManualIntervention {
+        task_id: String,
+        title: String,
+        action: String,
+    },
+    ChatMessage {
+        line: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -158,6 +223,9 @@ pub enum WorkflowRequest {
     },
     PlannerTasksForFile,
     StartExecution,
+    StartExecutionScoped {
+        task_id: String,
+    },
     StartNextJob,
     AppendActiveOutput {
         line: String,
@@ -172,6 +240,17 @@ pub enum WorkflowRequest {
         entries: Vec<String>,
     },
     RightPaneBlockView,
+    TaskGraphExport {
+        tasks: Vec<PlannerTaskEntryContract>,
+        format: TaskGraphFormatContract,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskGraphFormatContract {
+    Mermaid,
+    Dot,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -200,6 +279,10 @@ pub enum WorkflowResponse {
         lines: Vec<String>,
         toggles: Vec<RightPaneToggleContract>,
     },
+    TaskGraph {
+        format: TaskGraphFormatContract,
+        text: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -211,7 +294,11 @@ pub struct RightPaneToggleContract {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "action", rename_all = "snake_case")]
 pub enum SessionRequest {
-    Initialize { cwd: String },
+    Initialize {
+        cwd: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        template: Option<String>,
+    },
     OpenExisting { cwd: String, session_dir: String },
     ListSessions,
     ReadTasks,
@@ -223,6 +310,28 @@ pub enum SessionRequest {
     ReadProjectInfo,
     WriteProjectInfo { markdown: String },
     ReadSessionMeta,
+    SearchCode {
+        query: String,
+        limit: usize,
+    },
+    /// Verifies `audit-trail.jsonl` and, with `output`, copies it there unchanged.
+    ExportAuditTrail {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        output: Option<String>,
+    },
+    AddTask {
+        task: PlannerTaskEntryContract,
+    },
+    UpdateTask {
+        task: PlannerTaskEntryContract,
+    },
+    RemoveTask {
+        task_id: String,
+    },
+    ReorderTask {
+        task_id: String,
+        position: usize,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -252,9 +361,56 @@ pub enum SessionResponse {
     SessionMeta {
         meta: SessionMetaContract,
     },
+    CodeSearchResults {
+        results: Vec<CodeSearchHitContract>,
+    },
+    /// `broken_at` is the `seq` of the first entry whose hash chain does not verify.
+    AuditTrail {
+        entries: Vec<AuditTrailEntryContract>,
+        head_hash: String,
+        broken_at: Option<u64>,
+    },
     Ack,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditTrailEntryContract {
+    pub seq: u64,
+    pub at_epoch_ms: u64,
+    pub event: AuditEventContract,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum AuditEventContract {
+    PromptSent {
+        program: String,
+        model: Option<String>,
+        prompt: String,
+    },
+    FilesChanged {
+        task_id: String,
+        pass: u8,
+        files: Vec<String>,
+    },
+    ApprovalDecision {
+        subject: String,
+        decision: String,
+        detail: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CodeSearchHitContract {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub score: f32,
+    pub excerpt: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SessionStoreSnapshotContract {
     pub session_dir: String,
@@ -271,6 +427,8 @@ pub enum SubagentRequest {
     BuildMasterPrompt {
         tasks_file: String,
         workflow_prompt: String,
+        #[serde(default)]
+        tests_mode_enabled: Option<bool>,
     },
     BuildConvertPlanPrompt {
         planner_file: String,
@@ -307,6 +465,27 @@ pub enum SubagentRequest {
         project_info_file: String,
         session_meta_file: String,
     },
+    BuildAttachDocsPrompt {
+        tasks_file: String,
+    },
+    /// Builds the prompt for an auditor or final-audit task in `tasks`, as the workflow would
+    /// send it. Unset `pass` is 1 and unset `tests_mode_enabled` is on, matching a new session.
+    BuildAuditPrompt {
+        tasks: Vec<PlannerTaskEntryContract>,
+        task_id: String,
+        #[serde(default)]
+        worker_output: Option<String>,
+        #[serde(default)]
+        changed_files_summary: Option<String>,
+        #[serde(default)]
+        pass: Option<u8>,
+        #[serde(default)]
+        tests_mode_enabled: Option<bool>,
+        #[serde(default)]
+        audit_rubric: Option<String>,
+        #[serde(default)]
+        rolling_context: Vec<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -341,6 +520,18 @@ pub struct SessionMetaContract {
     pub stack_description: String,
     #[serde(default)]
     pub test_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frameworks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_command: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub personas: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -352,6 +543,7 @@ pub enum PlannerTaskKindContract {
     TestWriter,
     TestRunner,
     FinalAudit,
+    Plugin,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -361,6 +553,7 @@ pub enum PlannerTaskStatusContract {
     InProgress,
     NeedsChanges,
     Done,
+    Blocked,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -369,9 +562,11 @@ pub struct PlannerTaskDocContract {
     pub url: String,
     #[serde(default)]
     pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PlannerTaskEntryContract {
     pub id: String,
     pub title: String,
@@ -386,6 +581,22 @@ pub struct PlannerTaskEntryContract {
     #[serde(default)]
     pub parent_id: Option<String>,
     pub order: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_ref: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workdir: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acceptance_criteria: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_answers: Vec<String>,
 }
 
 impl Default for PlannerTaskKindContract {
@@ -405,6 +616,7 @@ impl Default for PlannerTaskStatusContract {
 pub enum WorkflowFailureKindContract {
     Audit,
     Test,
+    FlakyTest,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1,51 +1,55 @@
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-
-use crate::agent::BackendKind;
-use crate::session_store::PlannerTaskFileEntry;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::agent::{self, BackendKind, TokenUsage, compact_count};
+use crate::audit_citations::{self, AuditCitation};
+use crate::audit_trail::{self, AuditEventFile};
+use crate::change_review::{self, WorktreeSnapshot};
+use crate::clarification::{self, QuestionForm};
+use crate::code_context::{CodeContextPolicy, relevant_file_excerpts, render_code_context};
+use crate::command_args::{self, ArgContext, ArgHint};
+use crate::command_registry::{CommandRegistry, CommandSuggestion, UserCommand};
+use crate::edit_conflict::{ConflictChoice, ConflictFile, EditConflict};
+use crate::file_tree::FileTreeState;
+use crate::i18n::{self, LanguageSettings, Locale, Text};
+use crate::keybindings::{HelpOverlay, KeyContext};
+use crate::output_filters::{OutputFilter, OutputFilterConfig, PaneUpdate};
+use crate::plugins::{PluginCapability, PluginCommands, PluginRegistry, PluginRunRequest};
+use crate::project_info_scope::ProjectInfoScope;
+use crate::prompt_budget::{PromptBudget, PromptSection};
+use crate::prompt_context::{ContextSection, OMITTED_SECTION, PromptSections};
+use crate::prompt_editor::PromptEditor;
+use crate::protected_paths::ProtectedPaths;
+use crate::response_cache::ResponseCache;
+use crate::session_lock::{LockAction, LockOwner};
+use crate::session_store::{
+    PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile, SessionPreview,
+    SessionUiStateFile, TaskSummaryFileEntry,
+};
+use crate::shell_console::ShellConsole;
+use crate::shutdown::ShutdownMode;
+use crate::speed_profile::SpeedProfile;
 use crate::subagents;
-use crate::text_layout::{WrappedText, wrap_word_with_positions};
-use crate::workflow::{RightPaneBlockView, StartedJob, WorkerRole, Workflow, WorkflowFailure};
-
-#[cfg(not(test))]
-const COMMAND_INDEX: [(&str, &str); 15] = [
-    ("/start", "Start execution"),
-    ("/backend", "Choose backend"),
-    ("/toggle-tests", "Toggle global tests mode"),
-    ("/planner", "Show collaborative planner markdown"),
-    ("/convert", "Convert planner markdown to tasks"),
-    ("/skip-plan", "Show task list view"),
-    ("/quit", "Quit app"),
-    ("/exit", "Quit app"),
-    ("/attach-docs", "Attach docs to tasks"),
-    ("/newmaster", "Start a new master session"),
-    ("/resume", "Resume a prior session"),
-    ("/split-audits", "Split audits per concern"),
-    ("/merge-audits", "Merge audits"),
-    ("/add-final-audit", "Add final audit task"),
-    ("/remove-final-audit", "Remove final audit task"),
-];
-#[cfg(test)]
-const COMMAND_INDEX: [(&str, &str); 17] = [
-    ("/start", "Start execution"),
-    ("/backend", "Choose backend"),
-    ("/toggle-tests", "Toggle global tests mode"),
-    ("/planner", "Show collaborative planner markdown"),
-    ("/convert", "Convert planner markdown to tasks"),
-    ("/skip-plan", "Show task list view"),
-    ("/quit", "Quit app"),
-    ("/exit", "Quit app"),
-    ("/attach-docs", "Attach docs to tasks"),
-    ("/newmaster", "Start a new master session"),
-    ("/resume", "Resume a prior session"),
-    ("/split-audits", "Split audits per concern"),
-    ("/merge-audits", "Merge audits"),
-    ("/split-tests", "Split tests per concern"),
-    ("/merge-tests", "Merge tests"),
-    ("/add-final-audit", "Add final audit task"),
-    ("/remove-final-audit", "Remove final audit task"),
-];
+use crate::system_log;
+use crate::telemetry;
+use crate::terminal_graphics::{GraphicsProtocol, INLINE_IMAGE_ROWS};
+use crate::test_runners::TestRunReport;
+use crate::text_layout::{
+    WrappedText, char_to_byte_idx, nearest_index_for_line_col, wrap_word_with_positions,
+};
+use crate::time_format::{self, TimeZone};
+use crate::transcript::Transcript;
+use crate::worker_logs::WorkerLogEntry;
+use crate::workflow::{
+    ActiveJobMeta, CompletedTopTask, FinalAuditPolicy, JobRun, ManualIntervention,
+    ManualTaskAction, QueuedJobMeta, ReviewDecision, RightPaneBlockView, StartedJob,
+    TaskMenuTarget, VerifyGate, WorkerRole, Workflow, WorkflowFailure,
+};
+use crate::workspace_ignore::WorkspaceIgnore;
+
 const MAX_LEFT_TOP_LINES: usize = 2000;
 
 #[derive(Debug, Clone)]
@@ -62,10 +66,32 @@ struct WrappedInputCache {
     wrapped: Arc<WrappedText>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct CommandSuggestion {
-    pub command: &'static str,
-    pub description: &'static str,
+/// A task-splitter proposal waiting for `/approve-split` or `/reject-split`.
+#[derive(Debug, Clone)]
+pub struct PendingTaskSplit {
+    pub session_dir: PathBuf,
+    pub tasks: Vec<PlannerTaskFileEntry>,
+    /// tasks.json as it was when the splitter ran; approval is refused if it has changed since.
+    pub tasks_baseline: String,
+}
+
+/// tasks.json as it was before master writes waiting for `/accept-tasks` or `/reject-tasks`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTaskChanges {
+    pub session_dir: PathBuf,
+    /// The file text `/reject-tasks` writes back; later master writes keep the oldest one.
+    pub baseline: String,
+}
+
+/// A tasks.json edit made outside the app, waiting for `/reload-tasks` or `/keep-tasks`.
+/// While pending, runtime snapshots do not overwrite the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalTasksEdit {
+    pub session_dir: PathBuf,
+    /// The edited file as the watcher saw it, so a later master write can be told apart.
+    pub text: String,
+    /// Set when the edited file does not parse or validate, so it cannot be reloaded yet.
+    pub validation_error: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,10 +103,58 @@ pub struct ResumeSessionOption {
     pub last_used_epoch_secs: u64,
 }
 
+/// A session `/resume` or `/switch` found open in another instance; waits for `/lock`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedSession {
+    pub selection: ResumeSessionOption,
+    pub owner: LockOwner,
+}
+
 #[derive(Debug, Clone)]
 struct ResumePickerState {
     entries: Vec<ResumeSessionOption>,
+    /// Sessions of other workspaces, listed after `entries` while `all_workspaces` is on.
+    other_workspaces: Vec<ResumeSessionOption>,
+    all_workspaces: bool,
+    /// Session directory of an other-workspace entry waiting for a second Enter.
+    confirm_workspace_switch: Option<String>,
+    /// Index into the entries that match `filter`.
     selected: usize,
+    /// Reference point for the "2h ago" labels, fixed while the picker is open.
+    opened_at_epoch_secs: u64,
+    /// Preview of each entry by session directory.
+    previews: HashMap<String, SessionPreview>,
+    /// Typed query; each word must appear in an entry's title, workspace, or directory name.
+    filter: String,
+}
+
+impl ResumePickerState {
+    fn visible(&self) -> Vec<&ResumeSessionOption> {
+        let terms = self
+            .filter
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+        let others = if self.all_workspaces {
+            self.other_workspaces.as_slice()
+        } else {
+            &[]
+        };
+        self.entries
+            .iter()
+            .chain(others)
+            .filter(|option| {
+                let haystack = format!(
+                    "{} {} {}",
+                    option.title.as_deref().unwrap_or_default(),
+                    option.workspace,
+                    option.session_dir
+                )
+                .to_lowercase();
+                terms.iter().all(|term| haystack.contains(term.as_str()))
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -96,6 +170,77 @@ struct BackendPickerState {
     selected: usize,
 }
 
+#[derive(Debug, Clone)]
+struct LogPickerState {
+    entries: Vec<WorkerLogEntry>,
+    selected: usize,
+}
+
+/// One row of the menu opened by right-clicking (or long-pressing) a task in the right pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskMenuEntry {
+    ToggleDetails,
+    ViewLogs,
+    Retry,
+    Skip,
+    ChangeModel,
+    AttachDocs,
+    /// Show the chat lines about the task's top-level task.
+    ShowConversation,
+    /// A model profile offered after "Change model"; `None` goes back to agent routing.
+    ModelProfile(Option<String>),
+}
+
+impl TaskMenuEntry {
+    /// The slash command this entry runs, for entries that are shortcuts for one.
+    pub fn command(&self, target: &TaskMenuTarget) -> Option<String> {
+        match self {
+            TaskMenuEntry::ToggleDetails
+            | TaskMenuEntry::ChangeModel
+            | TaskMenuEntry::ShowConversation => None,
+            TaskMenuEntry::ViewLogs => Some(format!("/logs {}", target.top_task_id)),
+            TaskMenuEntry::Retry => Some(format!("/retry {}", target.task_id)),
+            TaskMenuEntry::Skip => Some(format!("/skip {}", target.task_id)),
+            TaskMenuEntry::AttachDocs => Some(format!("/attach-docs task:{}", target.task_id)),
+            TaskMenuEntry::ModelProfile(profile) => Some(format!(
+                "/task-model {} {}",
+                target.top_task_id,
+                profile.as_deref().unwrap_or("default")
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TaskMenuState {
+    target: TaskMenuTarget,
+    entries: Vec<TaskMenuEntry>,
+    selected: usize,
+}
+
+/// A menu command submitted through the chat input, and the draft it replaced there.
+#[derive(Debug, Clone)]
+struct StagedChatCommand {
+    command: String,
+    draft: String,
+}
+
+#[derive(Debug, Clone)]
+struct WorkerLogView {
+    label: String,
+    lines: Vec<String>,
+}
+
+/// A fully assembled master prompt held back for confirmation while `/preview` is on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MasterPromptPreview {
+    pub prompt: String,
+    pub user_message: String,
+    /// Whether building the prompt consumed the one-time session intro.
+    pub consumed_session_intro: bool,
+    pub scroll: u16,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Pane {
     LeftTop,
@@ -103,10 +248,191 @@ pub enum Pane {
     Right,
 }
 
+/// Panes whose content may have changed since the last draw. `ui::render_with_cache` renders
+/// these and copies the rest from the previous frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaneDamage {
+    left_top: bool,
+    left_bottom: bool,
+    right: bool,
+}
+
+impl PaneDamage {
+    pub const ALL: Self = Self {
+        left_top: true,
+        left_bottom: true,
+        right: true,
+    };
+    pub const NONE: Self = Self {
+        left_top: false,
+        left_bottom: false,
+        right: false,
+    };
+
+    pub fn contains(self, pane: Pane) -> bool {
+        match pane {
+            Pane::LeftTop => self.left_top,
+            Pane::LeftBottom => self.left_bottom,
+            Pane::Right => self.right,
+        }
+    }
+
+    pub fn insert(&mut self, pane: Pane) {
+        match pane {
+            Pane::LeftTop => self.left_top = true,
+            Pane::LeftBottom => self.left_bottom = true,
+            Pane::Right => self.right = true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeftTopMode {
+    WorkerOutput,
+    FileTree,
+    Stats,
+    Console,
+}
+
+/// What the chat pane shows; Ctrl+L switches between the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatView {
+    /// The conversation plus important system messages and replies to slash commands.
+    Chat,
+    /// Every system message.
+    SystemLog,
+    /// Chat lines, reports, and failures about one top-level task, by its id.
+    Task(u64),
+}
+
+/// `/review on|off` toggles the gate; the other forms resolve the pass it is holding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewCommand {
+    Toggle(bool),
+    Decide(ReviewDecision),
+    /// Bare `/review` or a malformed subcommand: report the gate state and usage.
+    Status,
+}
+
+/// `/jobs` lists the worker queue; `move` and `drop` edit it. Positions are 0-based here and
+/// 1-based in the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobsCommand {
+    /// Bare `/jobs` or a malformed subcommand: list the queue and usage.
+    List,
+    Move {
+        from: usize,
+        to: usize,
+    },
+    Drop(usize),
+}
+
+/// `/tasks done|reset|delete <numbers>` edits top-level tasks by their 1-based number in
+/// the task list. Numbers are comma-separated and may include ranges such as `5-7`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TasksCommand {
+    Done(Vec<usize>),
+    Reset(Vec<usize>),
+    Delete(Vec<usize>),
+    /// Bare `/tasks` or malformed arguments: show usage.
+    Usage,
+}
+
+/// What `/import-plan` does with a plan file found in the workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanImport {
+    /// Bare `/import-plan`: seed planner.md for editing.
+    Planner,
+    /// `/import-plan tasks`: seed planner.md and convert it, like `/convert`.
+    Tasks,
+}
+
+/// `/cancel context`, `/cancel docs`, and `/cancel taskcheck` stop the matching sub-agent run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelCommand {
+    Context,
+    Docs,
+    TaskCheck,
+    /// Bare `/cancel` or an unknown target: show usage.
+    Usage,
+}
+
+/// `/cache` shows response cache stats; `/cache purge` empties the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheCommand {
+    Stats,
+    Purge,
+    /// Unknown arguments: show usage.
+    Usage,
+}
+
+/// `/pin-models` keeps the models recorded in meta.json; `/pin-models off` follows config again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinModelsCommand {
+    Pin,
+    Unpin,
+    /// Unknown arguments: show usage.
+    Usage,
+}
+
+/// `/context` shows which sections the next master prompt includes; `/context <section>`
+/// toggles one and `/context reset` includes them all again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextCommand {
+    Show,
+    Toggle(ContextSection),
+    Reset,
+    /// Unknown arguments: show usage.
+    Usage,
+}
+
+/// `/plugin` lists the installed plugins; `/plugin <name> [input]` runs one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginCommand {
+    List,
+    Run { name: String, input: String },
+}
+
+/// `/console` shows or hides the console pane; the subcommands run, stop, keep, or clear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsoleCommand {
+    Toggle,
+    Run(String),
+    Stop,
+    /// Adds the last finished command's output to the rolling task context.
+    Keep,
+    Clear,
+    /// Unknown arguments: show usage.
+    Usage,
+}
+
+/// One-key answers to the status bar's "stalled?" prompt for a silent worker job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallAction {
+    /// Keep waiting; the prompt returns after another full stall period of silence.
+    Wait,
+    /// Stop the agent and resume its session with a follow-up prompt.
+    Nudge,
+    /// Stop the job; it completes as a failed run.
+    Abort,
+}
+
+/// A parsed `/attach-docs` command.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttachDocsRequest {
+    /// `task:<id>`: only this planner task and its subtasks.
+    pub task_id: Option<String>,
+    /// Also save sanitized text copies of the docs under the session's `docs/`.
+    pub snapshot: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RightPaneMode {
     TaskList,
     PlannerMarkdown,
+    WorkerLog,
+    /// `/summaries`: each done top-level task with the master report agent's summary of it.
+    TaskSummaries,
 }
 
 #[derive(Debug)]
@@ -115,17 +441,49 @@ pub struct App {
     pub ticks: u64,
     pub active_pane: Pane,
     right_pane_mode: RightPaneMode,
+    left_top_mode: LeftTopMode,
+    file_tree: FileTreeState,
+    workspace_root: Option<PathBuf>,
+    code_context_policy: CodeContextPolicy,
+    project_info_scope: ProjectInfoScope,
+    graphics_protocol: Option<GraphicsProtocol>,
+    stats_lines: Vec<String>,
+    stats_scroll: u16,
+    console: ShellConsole,
+    console_scroll: u16,
     left_top_lines: Vec<String>,
     left_top_generation: u64,
     left_top_wrap_cache: RefCell<Option<WrappedPaneCache>>,
-    chat_messages: Vec<String>,
-    chat_messages_generation: u64,
+    /// Every chat line in order, whichever view shows it; recorded to the session event log.
+    chat_transcript: Transcript,
+    chat_view: ChatView,
+    conversation: Transcript,
+    system_log: Transcript,
+    /// Chat lines that mention each top-level task, keyed by its id; see [`ChatView::Task`].
+    task_threads: HashMap<u64, Transcript>,
+    /// Set while a chat submission is handled, so Bob's direct replies show in the Chat view.
+    command_reply_open: bool,
+    /// Submissions made while master or task check was busy, sent in order once it finishes.
+    queued_messages: VecDeque<String>,
+    /// Steps of a `--send-file` batch script not sent yet, each sent once Bob is idle.
+    batch_steps: VecDeque<String>,
+    /// Number of steps in the batch script, for numbering the step being sent.
+    batch_step_total: usize,
+    /// Set by `/pin-models`, so the loop re-reads the session's pinned models.
+    session_models_changed: bool,
+    command_registry: CommandRegistry,
+    /// Sections `/context` left out of the next master prompt; reset once it is sent.
+    prompt_sections: PromptSections,
     right_lines: Vec<String>,
     planner_markdown: String,
+    /// planner.md as last read from or written to disk; edits beyond it are unsaved.
+    planner_saved_markdown: String,
     planner_cursor: usize,
     planner_cursor_goal_col: Option<u16>,
     left_top_scroll: u16,
-    chat_scroll: u16,
+    /// Display rows scrolled in the chat pane; wider than the other panes' offsets because
+    /// long transcripts exceed `u16::MAX` rows.
+    chat_scroll: usize,
     right_scroll: u16,
     chat_input: String,
     chat_input_generation: u64,
@@ -135,11 +493,79 @@ pub struct App {
     last_reported_context: Vec<String>,
     expanded_detail_keys: HashSet<String>,
     resume_picker: Option<ResumePickerState>,
+    /// Whether the first message of a new session should offer recent sessions first;
+    /// cleared once offered.
+    offer_recent_session: bool,
+    /// First message of a new session, held while the resume picker offers recent ones.
+    new_session_message: Option<String>,
     backend_picker: Option<BackendPickerState>,
+    log_picker: Option<LogPickerState>,
+    task_menu: Option<TaskMenuState>,
+    staged_chat_command: Option<StagedChatCommand>,
+    /// Model profiles pinned with `/task-model`, keyed by top-level task id.
+    task_models: BTreeMap<String, String>,
+    speed_profile: SpeedProfile,
+    worker_log_view: Option<WorkerLogView>,
+    /// Contents of the session's task-summaries.json.
+    task_summaries: Vec<TaskSummaryFileEntry>,
+    master_prompt_preview_enabled: bool,
+    master_prompt_preview: Option<MasterPromptPreview>,
+    question_form: Option<QuestionForm>,
+    rerun_editor: Option<PromptEditor>,
+    help_overlay: Option<HelpOverlay>,
+    pending_task_split: Option<PendingTaskSplit>,
+    external_tasks_edit: Option<ExternalTasksEdit>,
+    pending_task_changes: Option<PendingTaskChanges>,
+    /// Task ids a `/tasks delete` showed for confirmation; the same delete again removes them.
+    pending_tasks_delete: Option<Vec<String>>,
+    /// planner.md or tasks.json changed on both sides; waits for `/conflict`.
+    edit_conflict: Option<EditConflict>,
+    locked_session: Option<LockedSession>,
+    /// Session to follow read-only once the main loop exits, with the notice to show there.
+    observe_on_exit: Option<(PathBuf, String)>,
+    task_split_considered_titles: HashSet<String>,
     tests_mode_enabled: bool,
+    read_only: bool,
+    /// Launched with `--safe-mode`: workers are sandboxed read-only and master tasks.json
+    /// writes are staged for review.
+    safe_mode: bool,
     task_check_in_progress: bool,
     docs_attach_in_progress: bool,
+    /// tasks.json as it was when the running docs attach started, for the tasks changelog.
+    docs_attach_baseline: Option<String>,
     master_in_progress: bool,
+    runtime_backend: Option<String>,
+    active_job_model: Option<String>,
+    active_job_started_at: Option<Instant>,
+    /// Last output from the running job (or its start), for stall detection.
+    active_job_last_output_at: Option<Instant>,
+    /// First system or stderr line of the running job that looks like a transient backend
+    /// failure; only consulted when the run exits non-zero.
+    active_job_transient_failure: Option<String>,
+    /// Hides or collapses noisy agent output in the subagent pane (`[output_filters]`).
+    output_filter: OutputFilter,
+    /// Results of task-check and docs-attach runs, replayed for identical inputs.
+    response_cache: ResponseCache,
+    /// `/plugin` runs in flight; their output arrives as chat messages.
+    plugin_commands: PluginCommands,
+    /// `[workflow] stall_after_secs`; `None` never flags a job as stalled.
+    stall_after: Option<Duration>,
+    /// Trace span of the running worker job; ended when the job completes or is interrupted.
+    active_job_span: Option<telemetry::Span>,
+    token_usage: TokenUsage,
+    /// Workspace tree from before the running implementor pass, kept for the review gate
+    /// and the protected-path check.
+    review_baseline: Option<WorktreeSnapshot>,
+    locale: Locale,
+    prompt_language: Option<String>,
+    time_zone: TimeZone,
+    /// How a quit requested mid-execution should finish; no new jobs start while set.
+    shutdown: Option<ShutdownMode>,
+    /// Whether the wait/abort/detach choice was already shown for this quit request.
+    shutdown_prompted: bool,
+    /// Panes to re-render on the next draw. Streaming output and ticks mark their own pane;
+    /// the main loop marks every pane for input events and other state changes.
+    pane_damage: PaneDamage,
     workflow: Workflow,
 }
 
@@ -151,14 +577,34 @@ impl Default for App {
             ticks: 0,
             active_pane: Pane::LeftBottom,
             right_pane_mode: RightPaneMode::PlannerMarkdown,
+            left_top_mode: LeftTopMode::WorkerOutput,
+            file_tree: FileTreeState::default(),
+            workspace_root: None,
+            code_context_policy: CodeContextPolicy::default(),
+            project_info_scope: ProjectInfoScope::default(),
+            graphics_protocol: None,
+            stats_lines: Vec::new(),
+            stats_scroll: 0,
+            console: ShellConsole::default(),
+            console_scroll: 0,
             left_top_lines: vec![
                 "Sub-agent output stream.".to_string(),
                 "Implementor and auditor logs appear here.".to_string(),
             ],
             left_top_generation: 0,
             left_top_wrap_cache: RefCell::new(None),
-            chat_messages: Vec::new(),
-            chat_messages_generation: 0,
+            chat_transcript: Transcript::default(),
+            chat_view: ChatView::Chat,
+            conversation: Transcript::default(),
+            system_log: Transcript::default(),
+            task_threads: HashMap::new(),
+            command_reply_open: false,
+            queued_messages: VecDeque::new(),
+            batch_steps: VecDeque::new(),
+            batch_step_total: 0,
+            session_models_changed: false,
+            command_registry: CommandRegistry::default(),
+            prompt_sections: PromptSections::default(),
             right_lines: vec![
                 "# Collaborative Planner".to_string(),
                 String::new(),
@@ -167,6 +613,7 @@ impl Default for App {
                 "Use /convert when you're ready to implement from the plan.".to_string(),
             ],
             planner_markdown: String::new(),
+            planner_saved_markdown: String::new(),
             planner_cursor: 0,
             planner_cursor_goal_col: None,
             left_top_scroll: 0,
@@ -180,25 +627,124 @@ impl Default for App {
             last_reported_context: Vec::new(),
             expanded_detail_keys: HashSet::new(),
             resume_picker: None,
+            offer_recent_session: false,
+            new_session_message: None,
             backend_picker: None,
+            log_picker: None,
+            task_menu: None,
+            staged_chat_command: None,
+            task_models: BTreeMap::new(),
+            speed_profile: SpeedProfile::default(),
+            worker_log_view: None,
+            task_summaries: Vec::new(),
+            master_prompt_preview_enabled: false,
+            master_prompt_preview: None,
+            question_form: None,
+            rerun_editor: None,
+            help_overlay: None,
+            pending_task_split: None,
+            external_tasks_edit: None,
+            pending_task_changes: None,
+            pending_tasks_delete: None,
+            edit_conflict: None,
+            locked_session: None,
+            observe_on_exit: None,
+            task_split_considered_titles: HashSet::new(),
             tests_mode_enabled: true,
+            read_only: false,
+            safe_mode: false,
             task_check_in_progress: false,
             docs_attach_in_progress: false,
+            docs_attach_baseline: None,
             master_in_progress: false,
+            runtime_backend: None,
+            active_job_model: None,
+            active_job_started_at: None,
+            active_job_last_output_at: None,
+            active_job_transient_failure: None,
+            output_filter: OutputFilter::default(),
+            response_cache: ResponseCache::default(),
+            plugin_commands: PluginCommands::default(),
+            stall_after: Some(Duration::from_secs(300)),
+            active_job_span: None,
+            token_usage: TokenUsage::default(),
+            review_baseline: None,
+            locale: Locale::default(),
+            prompt_language: None,
+            time_zone: TimeZone::default(),
+            shutdown: None,
+            shutdown_prompted: false,
+            pane_damage: PaneDamage::ALL,
             workflow,
         }
     }
 }
 
 impl App {
+    /// Advances the chat title's working animation.
     pub fn on_tick(&mut self) {
         self.ticks = self.ticks.saturating_add(1);
+        if self.is_any_agent_in_progress() {
+            self.mark_pane_damaged(Pane::LeftBottom);
+        }
+    }
+
+    pub fn mark_pane_damaged(&mut self, pane: Pane) {
+        self.pane_damage.insert(pane);
+    }
+
+    pub fn mark_all_panes_damaged(&mut self) {
+        self.pane_damage = PaneDamage::ALL;
+    }
+
+    /// Panes damaged since the last call.
+    pub fn take_pane_damage(&mut self) -> PaneDamage {
+        std::mem::replace(&mut self.pane_damage, PaneDamage::NONE)
     }
 
     pub fn quit(&mut self) {
         self.running = false;
     }
 
+    /// Quits right away when no worker job is running. Otherwise records `mode`, or asks how
+    /// to finish when none was given; asking twice means abort.
+    pub fn request_shutdown(&mut self, mode: Option<ShutdownMode>) -> Option<String> {
+        if !self.is_execution_busy() {
+            self.quit();
+            return None;
+        }
+        let mode = match mode {
+            Some(mode) => mode,
+            None if self.shutdown_prompted => ShutdownMode::Abort,
+            None => {
+                self.shutdown_prompted = true;
+                return Some(self.system_text(Text::QuitWhileJobRunning, &[]));
+            }
+        };
+        self.shutdown = Some(mode);
+        (mode == ShutdownMode::Wait).then(|| self.system_text(Text::QuittingAfterJob, &[]))
+    }
+
+    pub fn shutdown_mode(&self) -> Option<ShutdownMode> {
+        self.shutdown
+    }
+
+    /// Whether a worker job is running or held for review.
+    pub fn has_active_worker_job(&self) -> bool {
+        self.workflow.active_job_meta().is_some() || self.workflow.pending_review().is_some()
+    }
+
+    /// Stops execution for shutdown; see [`Workflow::interrupt_execution`].
+    pub fn interrupt_execution(&mut self) -> Option<String> {
+        self.charge_active_job_elapsed();
+        self.end_job_span(None, Some("interrupted".to_string()));
+        let interrupted = self.workflow.interrupt_execution();
+        self.review_baseline = None;
+        self.active_job_model = None;
+        self.refresh_right_lines();
+        interrupted
+    }
+
     pub fn next_pane(&mut self) {
         self.active_pane = match self.active_pane {
             Pane::LeftTop => Pane::LeftBottom,
@@ -216,8 +762,23 @@ impl App {
     }
 
     pub fn scroll_up(&mut self) {
-        let scroll = self.scroll_mut(self.active_pane);
-        *scroll = scroll.saturating_sub(1);
+        if self.active_pane == Pane::LeftTop && self.is_file_tree_mode() {
+            self.file_tree.move_up();
+            return;
+        }
+        if self.active_pane == Pane::LeftTop && self.is_stats_mode() {
+            self.stats_scroll = self.stats_scroll.saturating_sub(1);
+            return;
+        }
+        if self.active_pane == Pane::LeftTop && self.is_console_mode() {
+            self.console_scroll = self.console_scroll.saturating_sub(1);
+            return;
+        }
+        match self.active_pane {
+            Pane::LeftTop => self.left_top_scroll = self.left_top_scroll.saturating_sub(1),
+            Pane::LeftBottom => self.scroll_chat_up(),
+            Pane::Right => self.right_scroll = self.right_scroll.saturating_sub(1),
+        }
     }
 
     pub fn input_char(&mut self, c: char) {
@@ -228,6 +789,19 @@ impl App {
         self.invalidate_chat_input_cache();
     }
 
+    /// Inserts pasted text at the cursor in one step; pasted newlines stay literal input.
+    pub fn paste_input(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        if text.is_empty() {
+            return;
+        }
+        let byte_idx = char_to_byte_idx(&self.chat_input, self.chat_cursor);
+        self.chat_input.insert_str(byte_idx, &text);
+        self.chat_cursor = self.chat_cursor.saturating_add(text.chars().count());
+        self.chat_cursor_goal_col = None;
+        self.invalidate_chat_input_cache();
+    }
+
     pub fn backspace_input(&mut self) {
         if self.chat_cursor == 0 {
             return;
@@ -316,7 +890,7 @@ impl App {
         self.chat_scroll = self.chat_scroll.saturating_sub(1);
     }
 
-    pub fn scroll_chat_down(&mut self, max_scroll: u16) {
+    pub fn scroll_chat_down(&mut self, max_scroll: usize) {
         self.chat_scroll = (self.chat_scroll + 1).min(max_scroll);
     }
 
@@ -329,6 +903,18 @@ impl App {
     }
 
     pub fn scroll_left_top_down(&mut self, max_scroll: u16) {
+        if self.is_file_tree_mode() {
+            self.file_tree.move_down(max_scroll);
+            return;
+        }
+        if self.is_stats_mode() {
+            self.stats_scroll = (self.stats_scroll + 1).min(max_scroll);
+            return;
+        }
+        if self.is_console_mode() {
+            self.console_scroll = (self.console_scroll + 1).min(max_scroll);
+            return;
+        }
         self.left_top_scroll = (self.left_top_scroll + 1).min(max_scroll);
     }
 
@@ -359,6 +945,87 @@ impl App {
         Some(message)
     }
 
+    /// Moves the chat input to the back of the message queue and shows it as queued. Returns
+    /// the queue length, or `None` when the input was empty.
+    pub fn queue_chat_input(&mut self) -> Option<usize> {
+        let message = self.consume_chat_input_trimmed()?;
+        self.push_chat_message_line(format!("You: [queued] {message}"));
+        self.queued_messages.push_back(message);
+        Some(self.queued_messages.len())
+    }
+
+    pub fn next_queued_message(&self) -> Option<&str> {
+        self.queued_messages.front().map(String::as_str)
+    }
+
+    pub fn dequeue_chat_message(&mut self) -> Option<String> {
+        self.queued_messages.pop_front()
+    }
+
+    pub fn queued_message_count(&self) -> usize {
+        self.queued_messages.len()
+    }
+
+    pub fn queued_messages(&self) -> Vec<String> {
+        self.queued_messages.iter().cloned().collect()
+    }
+
+    /// Queues the steps of a macro behind any messages already waiting.
+    pub fn queue_macro_steps(&mut self, steps: Vec<String>) {
+        for step in steps {
+            self.push_chat_message_line(format!("You: [queued] {step}"));
+            self.queued_messages.push_back(step);
+        }
+    }
+
+    /// Re-queues messages saved by an interrupted run.
+    pub fn restore_queued_messages(&mut self, messages: Vec<String>) {
+        for message in messages {
+            self.push_chat_message_line(format!("You: [queued] {message}"));
+            self.queued_messages.push_back(message);
+        }
+    }
+
+    pub fn clear_queued_messages(&mut self) -> usize {
+        let cleared = self.queued_messages.len();
+        self.queued_messages.clear();
+        cleared
+    }
+
+    /// Holds the steps of a batch script after the first, which is sent at startup.
+    pub fn load_batch_steps(&mut self, steps: Vec<String>) {
+        self.batch_step_total = steps.len() + 1;
+        self.batch_steps = steps.into();
+    }
+
+    pub fn has_batch_steps(&self) -> bool {
+        !self.batch_steps.is_empty()
+    }
+
+    /// The next batch step with its 1-based number among all steps of the script.
+    pub fn take_batch_step(&mut self) -> Option<(usize, String)> {
+        let step = self.batch_steps.pop_front()?;
+        Some((self.batch_step_total - self.batch_steps.len(), step))
+    }
+
+    pub fn batch_step_total(&self) -> usize {
+        self.batch_step_total
+    }
+
+    pub fn clear_batch_steps(&mut self) -> usize {
+        let cleared = self.batch_steps.len();
+        self.batch_steps.clear();
+        cleared
+    }
+
+    pub fn mark_session_models_changed(&mut self) {
+        self.session_models_changed = true;
+    }
+
+    pub fn take_session_models_changed(&mut self) -> bool {
+        std::mem::take(&mut self.session_models_changed)
+    }
+
     pub fn push_agent_message(&mut self, message: impl Into<String>) {
         self.push_chat_message_line(message.into());
     }
@@ -366,11 +1033,53 @@ impl App {
     pub fn prepare_master_prompt(&self, message: &str, tasks_file: &str) -> String {
         subagents::build_master_prompt(
             tasks_file,
-            &self.workflow.prepare_master_prompt(message),
+            &self
+                .workflow
+                .prepare_master_prompt(message, &self.prompt_sections),
             self.tests_mode_enabled,
         )
     }
 
+    /// Applies `/context` and returns the reply describing what the next master prompt includes.
+    pub fn apply_context_command(&mut self, command: ContextCommand) -> String {
+        let headline = match command {
+            ContextCommand::Show => i18n::text(self.locale, Text::ContextShown).to_string(),
+            ContextCommand::Toggle(section) => {
+                let state = if self.prompt_sections.toggle(section) {
+                    Text::ContextSectionIncluded
+                } else {
+                    Text::ContextSectionLeftOut
+                };
+                i18n::fill(self.locale, state, &[("section", &section.key())])
+            }
+            ContextCommand::Reset => {
+                self.prompt_sections = PromptSections::default();
+                i18n::text(self.locale, Text::ContextReset).to_string()
+            }
+            ContextCommand::Usage => {
+                let keys = ContextSection::ALL.map(ContextSection::key).join("|");
+                return self.system_text(Text::ContextUsage, &[("keys", &keys)]);
+            }
+        };
+        self.system_text(
+            Text::ContextSections,
+            &[
+                ("headline", &headline),
+                ("sections", &self.prompt_sections.render_lines().join("\n")),
+            ],
+        )
+    }
+
+    /// project-info.md as it should go into the session intro; `None` when `/context` left it out.
+    pub fn project_info_for_prompt<'a>(&self, project_info: Option<&'a str>) -> Option<&'a str> {
+        project_info.filter(|_| self.prompt_sections.includes(ContextSection::ProjectInfo))
+    }
+
+    /// Called once a master prompt is sent, so `/context` choices cover only that prompt.
+    pub fn reset_prompt_sections(&mut self) {
+        self.prompt_sections = PromptSections::default();
+    }
+
     pub fn prepare_context_report_prompt(&self, context_entries: &[String]) -> String {
         format!(
             "Rolling task context has new updates:\n{}\n\
@@ -405,6 +1114,10 @@ impl App {
         self.workflow.planner_tasks_for_file()
     }
 
+    pub fn estimate_comparisons(&self) -> Vec<String> {
+        self.workflow.estimate_comparisons()
+    }
+
     pub fn is_start_execution_command(message: &str) -> bool {
         let normalized = message.trim().to_lowercase();
         matches!(
@@ -413,88 +1126,495 @@ impl App {
         )
     }
 
-    pub fn is_planner_mode_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/planner")
+    /// Task id from `/start task:<id>`.
+    pub fn parse_start_task_command(message: &str) -> Option<String> {
+        let (command, rest) = message.trim().split_once(char::is_whitespace)?;
+        if !command.eq_ignore_ascii_case("/start") {
+            return None;
+        }
+        let rest = rest.trim();
+        let prefix = rest.get(..5)?;
+        if !prefix.eq_ignore_ascii_case("task:") {
+            return None;
+        }
+        let task_id = rest[5..].trim();
+        (!task_id.is_empty() && !task_id.contains(char::is_whitespace)).then(|| task_id.to_string())
     }
 
-    pub fn is_toggle_tests_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/toggle-tests")
+    /// Task id from `/skip <id>` or `/mark-done <id>`.
+    pub fn parse_manual_task_command(message: &str) -> Option<(ManualTaskAction, String)> {
+        let (command, task_id) = message.trim().split_once(char::is_whitespace)?;
+        let action = if command.eq_ignore_ascii_case("/skip") {
+            ManualTaskAction::Skip
+        } else if command.eq_ignore_ascii_case("/mark-done") {
+            ManualTaskAction::MarkDone
+        } else {
+            return None;
+        };
+        let task_id = task_id.trim();
+        (!task_id.is_empty() && !task_id.contains(char::is_whitespace))
+            .then(|| (action, task_id.to_string()))
     }
 
-    pub fn is_skip_plan_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/skip-plan")
+    /// `/speed` with an optional `eco`, `standard`, or `max`. Without a known profile the
+    /// current one is reported.
+    pub fn parse_speed_command(message: &str) -> Option<Option<SpeedProfile>> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/speed") {
+            return None;
+        }
+        let profile = parts.next().and_then(SpeedProfile::parse);
+        Some(profile.filter(|_| parts.next().is_none()))
     }
 
-    pub fn is_convert_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/convert")
+    pub fn parse_review_command(message: &str) -> Option<ReviewCommand> {
+        let trimmed = message.trim();
+        let (command, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        if !command.eq_ignore_ascii_case("/review") {
+            return None;
+        }
+        let rest = rest.trim();
+        let (action, feedback) = rest
+            .split_once(char::is_whitespace)
+            .map(|(action, feedback)| (action, feedback.trim()))
+            .unwrap_or((rest, ""));
+        Some(match action.to_ascii_lowercase().as_str() {
+            "on" if feedback.is_empty() => ReviewCommand::Toggle(true),
+            "off" if feedback.is_empty() => ReviewCommand::Toggle(false),
+            "approve" if feedback.is_empty() => ReviewCommand::Decide(ReviewDecision::Approve),
+            "revert" if feedback.is_empty() => ReviewCommand::Decide(ReviewDecision::Revert),
+            "changes" if !feedback.is_empty() => {
+                ReviewCommand::Decide(ReviewDecision::RequestChanges(feedback.to_string()))
+            }
+            _ => ReviewCommand::Status,
+        })
     }
 
-    pub fn is_attach_docs_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/attach-docs")
+    pub fn parse_jobs_command(message: &str) -> Option<JobsCommand> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/jobs") {
+            return None;
+        }
+        let args = parts.collect::<Vec<_>>();
+        let position = |arg: &str| arg.parse::<usize>().ok()?.checked_sub(1);
+        let command = match args.as_slice() {
+            [action, from, to] if action.eq_ignore_ascii_case("move") => position(from)
+                .zip(position(to))
+                .map(|(from, to)| JobsCommand::Move { from, to }),
+            [action, index] if action.eq_ignore_ascii_case("drop") => {
+                position(index).map(JobsCommand::Drop)
+            }
+            _ => None,
+        };
+        Some(command.unwrap_or(JobsCommand::List))
     }
 
-    pub fn is_quit_command(message: &str) -> bool {
-        let normalized = message.trim();
-        normalized.eq_ignore_ascii_case("/quit") || normalized.eq_ignore_ascii_case("/exit")
+    pub fn parse_tasks_command(message: &str) -> Option<TasksCommand> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/tasks") {
+            return None;
+        }
+        let action = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let positions = parse_task_positions(&parts.collect::<String>());
+        let command = match (action.as_str(), positions) {
+            ("done", Some(positions)) => TasksCommand::Done(positions),
+            ("reset", Some(positions)) => TasksCommand::Reset(positions),
+            ("delete", Some(positions)) => TasksCommand::Delete(positions),
+            _ => TasksCommand::Usage,
+        };
+        Some(command)
     }
 
-    pub fn is_new_master_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/newmaster")
+    pub fn parse_cancel_command(message: &str) -> Option<CancelCommand> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/cancel") {
+            return None;
+        }
+        let command = match (parts.next(), parts.next()) {
+            (Some(target), None) if target.eq_ignore_ascii_case("context") => {
+                CancelCommand::Context
+            }
+            (Some(target), None) if target.eq_ignore_ascii_case("docs") => CancelCommand::Docs,
+            (Some(target), None) if target.eq_ignore_ascii_case("taskcheck") => {
+                CancelCommand::TaskCheck
+            }
+            _ => CancelCommand::Usage,
+        };
+        Some(command)
     }
 
-    pub fn is_resume_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/resume")
+    pub fn is_planner_mode_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/planner")
     }
 
-    pub fn is_split_audits_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/split-audits")
+    pub fn is_toggle_tests_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/toggle-tests")
     }
 
-    pub fn is_merge_audits_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/merge-audits")
+    /// Returns the template name of `/new-from-template <name>`, empty when no name was given.
+    pub fn new_from_template_name(message: &str) -> Option<&str> {
+        let trimmed = message.trim();
+        let (command, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        command
+            .eq_ignore_ascii_case("/new-from-template")
+            .then(|| rest.trim())
     }
 
-    #[cfg(test)]
-    pub fn is_split_tests_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/split-tests")
+    pub fn is_preview_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/preview")
     }
 
-    #[cfg(test)]
-    pub fn is_merge_tests_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/merge-tests")
+    pub fn is_verbose_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/verbose")
     }
 
-    pub fn is_add_final_audit_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/add-final-audit")
+    pub fn parse_cache_command(message: &str) -> Option<CacheCommand> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/cache") {
+            return None;
+        }
+        let command = match (parts.next(), parts.next()) {
+            (None, _) => CacheCommand::Stats,
+            (Some(action), None) if action.eq_ignore_ascii_case("purge") => CacheCommand::Purge,
+            _ => CacheCommand::Usage,
+        };
+        Some(command)
     }
 
-    pub fn is_remove_final_audit_command(message: &str) -> bool {
-        message.trim().eq_ignore_ascii_case("/remove-final-audit")
+    pub fn parse_pin_models_command(message: &str) -> Option<PinModelsCommand> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/pin-models") {
+            return None;
+        }
+        let command = match (parts.next(), parts.next()) {
+            (None, _) => PinModelsCommand::Pin,
+            (Some(action), None) if action.eq_ignore_ascii_case("off") => PinModelsCommand::Unpin,
+            _ => PinModelsCommand::Usage,
+        };
+        Some(command)
     }
 
-    pub fn prepare_attach_docs_prompt(&self, tasks_file: &str) -> String {
-        format!(
-            "You are a docs-research sub-agent.\n\
-             Goal: update the planner task file with implementation documentation links.\n\
-             Read and edit this JSON file directly: {tasks_file}\n\
-             Requirements:\n\
-             - For every task/subtask where kind != \"test_runner\", populate or refresh a `docs` array.\n\
-             - Each docs item must include: title, url, summary.\n\
-             - Use the latest authoritative online docs relevant to implementing that task.\n\
-             - Keep existing task structure/order/status intact; only add/update docs.\n\
-             - Leave test_runner tasks with docs as-is (do not add docs there).\n\
-             - Save tasks.json, then output a short confirmation summary."
-        )
+    pub fn is_summaries_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/summaries")
     }
 
-    pub fn prepare_planner_prompt(
-        &self,
+    pub fn parse_context_command(message: &str) -> Option<ContextCommand> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/context") {
+            return None;
+        }
+        let command = match (parts.next(), parts.next()) {
+            (None, _) => ContextCommand::Show,
+            (Some(word), None) if word.eq_ignore_ascii_case("reset") => ContextCommand::Reset,
+            (Some(word), None) => {
+                ContextSection::parse(word).map_or(ContextCommand::Usage, ContextCommand::Toggle)
+            }
+            _ => ContextCommand::Usage,
+        };
+        Some(command)
+    }
+
+    pub fn parse_plugin_command(message: &str) -> Option<PluginCommand> {
+        let trimmed = message.trim();
+        let (command, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        if !command.eq_ignore_ascii_case("/plugin") {
+            return None;
+        }
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return Some(PluginCommand::List);
+        }
+        let (name, input) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        Some(PluginCommand::Run {
+            name: name.to_string(),
+            input: input.trim().to_string(),
+        })
+    }
+
+    pub fn parse_console_command(message: &str) -> Option<ConsoleCommand> {
+        let trimmed = message.trim();
+        let (command, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        if !command.eq_ignore_ascii_case("/console") {
+            return None;
+        }
+        let rest = rest.trim();
+        let (action, argument) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let argument = argument.trim();
+        let command = match action.to_ascii_lowercase().as_str() {
+            "" => ConsoleCommand::Toggle,
+            "run" if !argument.is_empty() => ConsoleCommand::Run(argument.to_string()),
+            "stop" if argument.is_empty() => ConsoleCommand::Stop,
+            "keep" if argument.is_empty() => ConsoleCommand::Keep,
+            "clear" if argument.is_empty() => ConsoleCommand::Clear,
+            _ => ConsoleCommand::Usage,
+        };
+        Some(command)
+    }
+
+    pub fn is_stats_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/stats")
+    }
+
+    pub fn is_logs_command(message: &str) -> bool {
+        Self::parse_logs_command(message).is_some()
+    }
+
+    /// `/logs` with an optional task id limiting the picker to that task's logs.
+    pub fn parse_logs_command(message: &str) -> Option<Option<String>> {
+        parse_optional_task_argument(message, "/logs", "")
+    }
+
+    /// Returns the instruction of `/direct <instruction>`, empty when none was given.
+    pub fn direct_instruction(message: &str) -> Option<&str> {
+        let trimmed = message.trim();
+        let (command, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        command.eq_ignore_ascii_case("/direct").then(|| rest.trim())
+    }
+
+    /// Task id from `/retry <id>`.
+    pub fn parse_retry_command(message: &str) -> Option<String> {
+        let (command, task_id) = message.trim().split_once(char::is_whitespace)?;
+        if !command.eq_ignore_ascii_case("/retry") {
+            return None;
+        }
+        let task_id = task_id.trim();
+        (!task_id.is_empty() && !task_id.contains(char::is_whitespace)).then(|| task_id.to_string())
+    }
+
+    /// `/unblock <id> <answer>`: the task id and the answer.
+    pub fn parse_unblock_command(message: &str) -> Option<(String, String)> {
+        let (command, rest) = message.trim().split_once(char::is_whitespace)?;
+        if !command.eq_ignore_ascii_case("/unblock") {
+            return None;
+        }
+        let (task_id, answer) = rest.trim().split_once(char::is_whitespace)?;
+        let answer = answer.trim();
+        (!answer.is_empty()).then(|| (task_id.to_string(), answer.to_string()))
+    }
+
+    /// Task id from `/rerun <id>`.
+    pub fn parse_rerun_command(message: &str) -> Option<String> {
+        let (command, task_id) = message.trim().split_once(char::is_whitespace)?;
+        if !command.eq_ignore_ascii_case("/rerun") {
+            return None;
+        }
+        let task_id = task_id.trim();
+        (!task_id.is_empty() && !task_id.contains(char::is_whitespace)).then(|| task_id.to_string())
+    }
+
+    /// `/task-model <id> [profile]`: the task id and the profile, if one was given.
+    pub fn parse_task_model_command(message: &str) -> Option<(String, Option<String>)> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/task-model") {
+            return None;
+        }
+        let task_id = parts.next()?.to_string();
+        let profile = parts.next().map(str::to_string);
+        parts.next().is_none().then_some((task_id, profile))
+    }
+
+    pub fn is_reload_tasks_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/reload-tasks")
+    }
+
+    pub fn is_keep_tasks_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/keep-tasks")
+    }
+
+    /// Parses `/conflict mine|theirs|merge`; a missing or unknown choice parses as `None`.
+    pub fn parse_conflict_command(message: &str) -> Option<Option<ConflictChoice>> {
+        let trimmed = message.trim();
+        let (command, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        command
+            .eq_ignore_ascii_case("/conflict")
+            .then(|| ConflictChoice::parse(rest))
+    }
+
+    pub fn parse_lock_command(message: &str) -> Option<Option<LockAction>> {
+        let trimmed = message.trim();
+        let (command, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        command
+            .eq_ignore_ascii_case("/lock")
+            .then(|| LockAction::parse(rest))
+    }
+
+    pub fn is_clear_queue_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/clear-queue")
+    }
+
+    pub fn is_files_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/files")
+    }
+
+    pub fn is_skip_plan_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/skip-plan")
+    }
+
+    pub fn is_convert_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/convert")
+    }
+
+    pub fn parse_import_plan_command(message: &str) -> Option<PlanImport> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/import-plan") {
+            return None;
+        }
+        match (parts.next(), parts.next()) {
+            (None, _) => Some(PlanImport::Planner),
+            (Some(target), None) if target.eq_ignore_ascii_case("tasks") => Some(PlanImport::Tasks),
+            _ => None,
+        }
+    }
+
+    pub fn is_attach_docs_command(message: &str) -> bool {
+        Self::parse_attach_docs_command(message).is_some()
+    }
+
+    /// `/attach-docs` with an optional `snapshot` (also save the docs as text in the session)
+    /// and an optional `task:<id>` limiting the research to one task, in either order.
+    pub fn parse_attach_docs_command(message: &str) -> Option<AttachDocsRequest> {
+        let mut parts = message.split_whitespace();
+        if !parts.next()?.eq_ignore_ascii_case("/attach-docs") {
+            return None;
+        }
+        let mut request = AttachDocsRequest::default();
+        for part in parts {
+            if part.eq_ignore_ascii_case("snapshot") && !request.snapshot {
+                request.snapshot = true;
+            } else if request.task_id.is_none()
+                && part.get(..5)?.eq_ignore_ascii_case("task:")
+                && part.len() > 5
+            {
+                request.task_id = Some(part[5..].to_string());
+            } else {
+                return None;
+            }
+        }
+        Some(request)
+    }
+
+    pub fn is_quit_command(message: &str) -> bool {
+        Self::parse_quit_command(message).is_some()
+    }
+
+    /// `/quit` or `/exit` with an optional `wait`, `abort`, or `detach`. An unknown argument
+    /// counts as no argument, so the choice is offered again.
+    pub fn parse_quit_command(message: &str) -> Option<Option<ShutdownMode>> {
+        let mut parts = message.split_whitespace();
+        let command = parts.next()?;
+        if !command.eq_ignore_ascii_case("/quit") && !command.eq_ignore_ascii_case("/exit") {
+            return None;
+        }
+        Some(parts.next().and_then(ShutdownMode::parse))
+    }
+
+    pub fn is_new_master_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/newmaster")
+    }
+
+    pub fn is_resume_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/resume")
+    }
+
+    pub fn is_switch_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/switch")
+    }
+
+    pub fn is_split_audits_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/split-audits")
+    }
+
+    pub fn is_merge_audits_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/merge-audits")
+    }
+
+    #[cfg(test)]
+    pub fn is_split_tests_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/split-tests")
+    }
+
+    #[cfg(test)]
+    pub fn is_merge_tests_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/merge-tests")
+    }
+
+    pub fn is_add_final_audit_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/add-final-audit")
+    }
+
+    pub fn is_remove_final_audit_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/remove-final-audit")
+    }
+
+    pub fn is_approve_split_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/approve-split")
+    }
+
+    pub fn is_reject_split_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/reject-split")
+    }
+
+    pub fn is_accept_tasks_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/accept-tasks")
+    }
+
+    pub fn is_reject_tasks_command(message: &str) -> bool {
+        message.trim().eq_ignore_ascii_case("/reject-tasks")
+    }
+
+    pub fn prepare_attach_docs_prompt(&self, tasks_file: &str) -> String {
+        attach_docs_prompt(
+            tasks_file,
+            "For every task/subtask where kind != \"test_runner\", populate or refresh a `docs` array.",
+        )
+    }
+
+    /// Like [`App::prepare_attach_docs_prompt`], limited to planner task `task_id` and its
+    /// subtasks.
+    pub fn prepare_attach_docs_prompt_for_task(&self, tasks_file: &str, task_id: &str) -> String {
+        attach_docs_prompt(
+            tasks_file,
+            &format!(
+                "Only for the task with id \"{task_id}\" and its subtasks where kind != \"test_runner\", populate or refresh a `docs` array. Leave the docs of every other task unchanged."
+            ),
+        )
+    }
+
+    pub fn worker_prompt_for_planner_task(
+        &self,
+        task_id: &str,
+        worker_output: Option<String>,
+        changed_files_summary: Option<String>,
+    ) -> Result<String, String> {
+        self.workflow
+            .worker_prompt_for_planner_task(task_id, worker_output, changed_files_summary)
+    }
+
+    pub fn prepare_planner_prompt(
+        &self,
         message: &str,
         planner_file: &str,
         project_info_file: &str,
     ) -> String {
+        let sections = &self.prompt_sections;
         let context_entries = self.workflow.rolling_context_entries();
-        let context_text = if context_entries.is_empty() {
+        let context_text = if !sections.includes(ContextSection::RollingContext) {
+            OMITTED_SECTION.to_string()
+        } else if context_entries.is_empty() {
             "(no rolling task context yet)".to_string()
         } else {
             context_entries
@@ -512,18 +1632,34 @@ impl App {
              - Do not plan or request creating/modifying test files, test-only setup work, or test execution."
                 .to_string()
         };
+        let questions_instructions = clarification::questions_prompt_instructions();
+        let planner_storage = if sections.includes(ContextSection::Planner) {
+            format!("- Read and update this markdown file directly: {planner_file}")
+        } else {
+            format!(
+                "- The user left {planner_file} out of this prompt: do not read it for this reply, answer from the conversation so far, and leave the file unchanged."
+            )
+        };
+        let project_info_brief = if sections.includes(ContextSection::ProjectInfo) {
+            format!(
+                "- Prefer grounding plan details using local repository files and this context brief: {project_info_file}"
+            )
+        } else {
+            "- Ground plan details using local repository files; the user left the project context brief out of this prompt.".to_string()
+        };
         format!(
             "You are the master Codex agent in planner mode.\n\
              Goal: collaboratively build a codebase-aware implementation plan before task generation.\n\
              Planner storage:\n\
-             - Read and update this markdown file directly: {planner_file}\n\
+             {planner_storage}\n\
              - Do not edit tasks.json while planner mode is active.\n\
              Codebase context:\n\
-             - Prefer grounding plan details using local repository files and this context brief: {project_info_file}\n\
+             {project_info_brief}\n\
              - Keep references concrete by naming likely files/modules when confident.\n\
              Clarification-first behavior:\n\
              - Do not generate or update planner markdown until you have asked follow-up questions that clarify scope, constraints, and success criteria.\n\
              - If key details are ambiguous, ask concise follow-up questions first and wait for answers before planning.\n\
+             {questions_instructions}\n\
              Tests-mode policy:\n\
              {tests_policy}\n\
              Plan formatting requirements:\n\
@@ -550,14 +1686,82 @@ impl App {
         )
     }
 
+    /// Starting execution accepts task changes still waiting for `/accept-tasks`.
     pub fn start_execution(&mut self) -> Vec<String> {
+        self.pending_task_changes = None;
         let messages = self.workflow.start_execution();
         self.prune_expanded_detail_keys();
         self.refresh_right_lines();
         messages
     }
 
-    #[cfg(test)]
+    pub fn start_execution_scoped(&mut self, task_id: &str) -> Result<Vec<String>, String> {
+        let messages = self.workflow.start_execution_scoped(task_id)?;
+        self.pending_task_changes = None;
+        self.prune_expanded_detail_keys();
+        self.refresh_right_lines();
+        Ok(messages)
+    }
+
+    pub fn retry_task(&mut self, task_id: &str) -> Result<Vec<String>, String> {
+        let messages = self.workflow.retry_task(task_id)?;
+        self.prune_expanded_detail_keys();
+        self.refresh_right_lines();
+        Ok(messages)
+    }
+
+    pub fn unblock_task(&mut self, task_id: &str, answer: &str) -> Result<Vec<String>, String> {
+        let messages = self.workflow.unblock_task(task_id, answer)?;
+        self.refresh_right_lines();
+        Ok(messages)
+    }
+
+    pub fn rerun_task_with_prompt(
+        &mut self,
+        task_id: &str,
+        prompt: String,
+    ) -> Result<Vec<String>, String> {
+        let messages = self.workflow.rerun_with_prompt(task_id, prompt)?;
+        self.prune_expanded_detail_keys();
+        self.refresh_right_lines();
+        Ok(messages)
+    }
+
+    pub fn resolve_task_manually(
+        &mut self,
+        task_id: &str,
+        action: ManualTaskAction,
+    ) -> Result<Vec<String>, String> {
+        let messages = self.workflow.resolve_task_manually(task_id, action)?;
+        self.prune_expanded_detail_keys();
+        self.refresh_right_lines();
+        Ok(messages)
+    }
+
+    pub fn queued_jobs(&self) -> Vec<QueuedJobMeta> {
+        self.workflow.queued_jobs()
+    }
+
+    pub fn move_queued_job(&mut self, from: usize, to: usize) -> Result<QueuedJobMeta, String> {
+        let moved = self.workflow.move_queued_job(from, to)?;
+        self.refresh_right_lines();
+        Ok(moved)
+    }
+
+    pub fn drop_queued_job(&mut self, index: usize) -> Result<QueuedJobMeta, String> {
+        let dropped = self.workflow.drop_queued_job(index)?;
+        self.refresh_right_lines();
+        Ok(dropped)
+    }
+
+    pub fn drain_manual_interventions(&mut self) -> Vec<ManualIntervention> {
+        self.workflow.drain_manual_interventions()
+    }
+
+    pub fn changed_files(&self) -> &BTreeSet<String> {
+        self.workflow.changed_files()
+    }
+
     pub fn is_execution_enabled(&self) -> bool {
         self.workflow.execution_enabled()
     }
@@ -567,279 +1771,1575 @@ impl App {
     }
 
     pub fn start_next_worker_job(&mut self) -> Option<StartedJob> {
-        let started = self.workflow.start_next_job();
-        if started.is_some() {
+        if self.shutdown.is_some() {
+            return None;
+        }
+        let mut started = self.workflow.start_next_job();
+        if let Some(job) = started.as_mut() {
+            job.model_profile = self
+                .workflow
+                .top_task_external_id_by_id(job.top_task_id)
+                .and_then(|top_task_id| self.task_models.get(&top_task_id).cloned())
+                .or_else(|| {
+                    self.speed_profile
+                        .worker_model_profile(job.role)
+                        .map(str::to_string)
+                });
+            self.active_job_started_at = Some(Instant::now());
+            self.active_job_last_output_at = self.active_job_started_at;
+            self.active_job_model = None;
+            self.active_job_transient_failure = None;
+            self.start_job_span(job);
+            self.snapshot_workspace_for_implementor(job.role);
+            if let JobRun::AgentPrompt(prompt) = &mut job.run {
+                let excerpts = self.code_context_for_active_job();
+                prompt.push_str(&excerpts);
+                self.workflow.record_active_prompt(prompt);
+                self.log_prompt_budget(job.role, prompt, &excerpts);
+            }
             self.prune_expanded_detail_keys();
             self.refresh_right_lines();
         }
         started
     }
 
-    pub fn on_worker_output(&mut self, line: String) {
-        if let Some(meta) = self.workflow.active_job_meta() {
-            let role = match meta.role {
-                WorkerRole::Implementor => "Impl",
-                WorkerRole::Auditor => "Audit",
-                WorkerRole::TestWriter => "Tests",
-                WorkerRole::TestRunner => "TestRun",
-                WorkerRole::FinalAudit => "FinalAudit",
-            };
-            self.append_left_top_line(format!("{role}#{}: {line}", meta.top_task_id));
-        } else {
-            self.append_left_top_line(format!("Worker: {line}"));
+    fn snapshot_workspace_for_implementor(&mut self, role: WorkerRole) {
+        self.review_baseline = None;
+        let watched =
+            self.workflow.review_changes_enabled() || !self.workflow.protected_paths().is_empty();
+        if !watched || role != WorkerRole::Implementor {
+            return;
+        }
+        let Some(root) = self.workspace_root.as_ref() else {
+            return;
+        };
+        match change_review::snapshot_worktree(root) {
+            Ok(snapshot) => self.review_baseline = Some(snapshot),
+            Err(err) => self.push_chat_message_line(
+                self.system_text(Text::WorkspaceSnapshotFailed, &[("err", &err)]),
+            ),
         }
-        self.workflow.append_active_output(line);
     }
 
-    pub fn on_worker_system_output(&mut self, line: String) {
-        self.append_left_top_line(format!("WorkerSystem: {line}"));
+    pub fn set_review_changes_enabled(&mut self, enabled: bool) {
+        self.workflow.set_review_changes_enabled(enabled);
     }
 
-    pub fn push_subagent_output(&mut self, line: impl Into<String>) {
-        self.append_left_top_line(line.into());
+    pub fn review_changes_enabled(&self) -> bool {
+        self.workflow.review_changes_enabled()
     }
 
-    pub fn on_worker_completed(&mut self, success: bool, code: i32) -> Vec<String> {
-        let messages = self.workflow.finish_active_job(success, code);
-        for message in messages {
-            self.push_chat_message_line(message);
-        }
-        self.prune_expanded_detail_keys();
-        self.refresh_right_lines();
-        let context_entries = self.workflow.rolling_context_entries();
-        let new_entries =
-            new_context_entries_since_snapshot(&self.last_reported_context, &context_entries);
-        self.last_reported_context = context_entries;
-        new_entries
+    pub fn set_protected_paths(&mut self, protected_paths: ProtectedPaths) {
+        self.workflow.set_protected_paths(protected_paths);
     }
 
-    pub fn drain_worker_failures(&mut self) -> Vec<WorkflowFailure> {
-        self.workflow.drain_recent_failures()
+    pub fn set_verify_gate(&mut self, verify_gate: VerifyGate) {
+        self.workflow.set_verify_gate(verify_gate);
     }
 
-    pub fn set_chat_scroll(&mut self, scroll: u16) {
-        self.chat_scroll = scroll;
+    /// Keeps ignored paths out of the changed files the file tree and code index track.
+    pub fn set_workspace_ignore(&mut self, workspace_ignore: WorkspaceIgnore) {
+        self.workflow.set_workspace_ignore(workspace_ignore);
     }
 
-    #[cfg(test)]
-    pub fn left_top_lines(&self) -> &[String] {
-        &self.left_top_lines
+    pub fn set_stall_after(&mut self, stall_after: Option<Duration>) {
+        self.stall_after = stall_after;
     }
 
-    pub fn left_top_wrapped_text(&self, width: u16) -> Arc<String> {
-        let width = width.max(1);
-        if let Some(cache) = self.left_top_wrap_cache.borrow().as_ref()
-            && cache.width == width
-            && cache.generation == self.left_top_generation
-        {
-            return Arc::clone(&cache.rendered);
-        }
-
-        let rendered =
-            Arc::new(wrap_word_with_positions(&self.left_top_lines.join("\n"), width).rendered);
-        *self.left_top_wrap_cache.borrow_mut() = Some(WrappedPaneCache {
-            width,
-            generation: self.left_top_generation,
-            rendered: Arc::clone(&rendered),
-        });
-        rendered
+    /// How long the running job has gone without output, once that reaches the stall period.
+    pub fn worker_stalled_for(&self, now: Instant) -> Option<Duration> {
+        let stall_after = self.stall_after?;
+        self.workflow.active_job_meta()?;
+        let silent = now.saturating_duration_since(self.active_job_last_output_at?);
+        (silent >= stall_after).then_some(silent)
     }
 
-    pub fn left_bottom_lines(&self) -> &[String] {
-        &self.chat_messages
+    /// The stall action bound to `c`, while the stall prompt shows and the chat input is empty
+    /// (so typing a message is never hijacked).
+    pub fn stall_action_for_key(&self, c: char, now: Instant) -> Option<StallAction> {
+        if !self.chat_input.is_empty() || self.worker_stalled_for(now).is_none() {
+            return None;
+        }
+        match c.to_ascii_lowercase() {
+            'w' => Some(StallAction::Wait),
+            'n' => Some(StallAction::Nudge),
+            'a' => Some(StallAction::Abort),
+            _ => None,
+        }
     }
 
-    pub fn right_block_lines(&self, width: u16) -> Vec<String> {
-        if self.right_pane_mode == RightPaneMode::PlannerMarkdown {
-            self.planner_block_lines(width)
-        } else {
-            self.workflow
-                .right_pane_block_view(width, &self.expanded_detail_keys)
-                .lines
+    /// Restarts the running job's silence clock, e.g. after choosing to keep waiting.
+    pub fn reset_stall_timer(&mut self, now: Instant) {
+        if self.active_job_last_output_at.is_some() {
+            self.active_job_last_output_at = Some(now);
         }
     }
 
-    pub fn right_block_view(&self, width: u16) -> RightPaneBlockView {
-        if self.right_pane_mode == RightPaneMode::PlannerMarkdown {
-            RightPaneBlockView {
-                lines: self.planner_block_lines(width),
-                toggles: Vec::new(),
+    /// Applies the user's verdict on the pass held by the review gate. A revert rolls the
+    /// workspace back to the snapshot taken when the pass started before queueing a retry.
+    pub fn resolve_review(&mut self, decision: ReviewDecision) -> Result<Vec<String>, String> {
+        let pending = self
+            .workflow
+            .pending_review()
+            .ok_or_else(|| "No implementor pass is awaiting review.".to_string())?;
+        let mut messages = Vec::new();
+        if decision == ReviewDecision::Revert {
+            let baseline = self.review_baseline.as_ref().ok_or_else(|| {
+                "No workspace snapshot was taken for this pass, so it cannot be reverted."
+                    .to_string()
+            })?;
+            let reverted = change_review::revert_to(baseline)
+                .map_err(|err| format!("Failed to revert the pass: {err}"))?;
+            messages.push(self.system_text(
+                Text::RevertedFiles,
+                &[
+                    ("reverted", &reverted),
+                    ("task", &pending.top_task_id),
+                    ("pass", &pending.pass),
+                ],
+            ));
+        }
+        let mut detail = format!("task #{} pass {}", pending.top_task_id, pending.pass);
+        let verdict = match &decision {
+            ReviewDecision::Approve => "approve",
+            ReviewDecision::RequestChanges(feedback) => {
+                detail.push_str(&format!(": {feedback}"));
+                "request_changes"
             }
-        } else {
-            self.workflow
-                .right_pane_block_view(width, &self.expanded_detail_keys)
+            ReviewDecision::Revert => "revert",
+        };
+        messages.extend(self.workflow.resolve_review(decision)?);
+        audit_trail::record(AuditEventFile::ApprovalDecision {
+            subject: "review".to_string(),
+            decision: verdict.to_string(),
+            detail,
+        });
+        self.review_baseline = None;
+        if self.is_worker_log_mode() {
+            self.close_worker_log();
         }
+        self.prune_expanded_detail_keys();
+        self.refresh_right_lines();
+        self.refresh_file_tree_if_visible();
+        Ok(messages)
     }
 
-    pub fn toggle_task_details(&mut self, task_key: &str) {
-        if self.expanded_detail_keys.contains(task_key) {
-            self.expanded_detail_keys.remove(task_key);
-        } else {
-            self.expanded_detail_keys.insert(task_key.to_string());
+    /// Workspace file excerpts appended to implementor and test-writer prompts.
+    /// Logs the worker prompt's size by section to the subagent pane, with a warning for every
+    /// section that was truncated or takes up most of the prompt.
+    fn log_prompt_budget(&mut self, role: WorkerRole, prompt: &str, excerpts: &str) {
+        let mut sections = self.workflow.active_prompt_sections();
+        if !excerpts.is_empty() {
+            sections.push(PromptSection::new("excerpts", excerpts));
+        }
+        let budget = PromptBudget::new(prompt, sections);
+        let pass = self.workflow.active_job_meta().map_or(1, |meta| meta.pass);
+        self.push_subagent_output(
+            budget.summary_line(&format!("{} pass {pass}", role.file_stem())),
+        );
+        for warning in budget.warnings() {
+            self.push_subagent_output(warning);
         }
     }
 
-    fn prune_expanded_detail_keys(&mut self) {
-        let valid = self.workflow.task_detail_keys();
-        self.expanded_detail_keys.retain(|key| valid.contains(key));
-    }
+    fn code_context_for_active_job(&self) -> String {
+        let (Some(root), Some(query)) = (
+            self.workspace_root.as_ref(),
+            self.workflow.active_code_context_query(),
+        ) else {
+            return String::new();
+        };
+        let excerpts = relevant_file_excerpts(root, &query, &self.code_context_policy);
+        if excerpts.is_empty() {
+            return String::new();
+        }
+        format!("\n\n{}", render_code_context(&excerpts))
+    }
+
+    pub fn set_code_context_policy(&mut self, policy: CodeContextPolicy) {
+        self.code_context_policy = policy;
+    }
+
+    pub fn set_project_info_scope(&mut self, scope: ProjectInfoScope) {
+        self.project_info_scope = scope;
+    }
+
+    pub fn project_info_scope(&self) -> &ProjectInfoScope {
+        &self.project_info_scope
+    }
+
+    pub fn set_graphics_protocol(&mut self, protocol: Option<GraphicsProtocol>) {
+        self.graphics_protocol = protocol;
+    }
+
+    pub fn graphics_protocol(&self) -> Option<GraphicsProtocol> {
+        self.graphics_protocol
+    }
+
+    /// Blank chat rows kept under each image fallback; zero when images are not drawn.
+    pub fn inline_image_rows(&self) -> u16 {
+        if self.graphics_protocol.is_some() {
+            INLINE_IMAGE_ROWS
+        } else {
+            0
+        }
+    }
+
+    pub fn set_language_settings(&mut self, settings: LanguageSettings) {
+        self.locale = settings.locale;
+        self.workflow.set_locale(settings.locale);
+        self.prompt_language = settings.prompt_language;
+        self.time_zone = settings.time_zone;
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Appends the `[ui] prompt_language` reply instruction to a master-facing prompt.
+    pub fn with_reply_language(&self, prompt: String) -> String {
+        match &self.prompt_language {
+            Some(language) => format!("{prompt}\n{}", i18n::reply_language_instruction(language)),
+            None => prompt,
+        }
+    }
+
+    /// `System:` chat line for a catalog message in the selected locale.
+    pub fn system_text(&self, key: Text, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        i18n::system(self.locale, key, args)
+    }
+
+    pub fn set_runtime_backend(&mut self, label: &str) {
+        self.runtime_backend = Some(label.to_string());
+    }
+
+    /// Records that the active implementor pass runs on the escalated profile `to`.
+    pub fn record_model_escalation(&mut self, from: &str, to: &str) {
+        if let Some(message) = self.workflow.record_model_escalation(from, to) {
+            self.push_agent_message(message);
+        }
+        self.refresh_right_lines();
+    }
+
+    /// Model the dispatched worker adapter runs with, when one is configured.
+    pub fn set_active_job_model(&mut self, model: Option<&str>) {
+        self.active_job_model = model.map(str::to_string);
+    }
+
+    /// Adds usage drained from the agent adapters; returns whether the totals changed.
+    pub fn record_token_usage(&mut self, usage: TokenUsage) -> bool {
+        if usage.is_empty() {
+            return false;
+        }
+        self.token_usage.add(usage);
+        true
+    }
+
+    /// Like [`Self::record_token_usage`], also charging the usage to the worker job's
+    /// top-level task for the task details view.
+    pub fn record_worker_token_usage(&mut self, usage: TokenUsage) -> bool {
+        if !self.record_token_usage(usage) {
+            return false;
+        }
+        self.workflow.record_worker_usage(usage);
+        self.mark_pane_damaged(Pane::Right);
+        true
+    }
+
+    /// Opens the trace span of a worker job; its agent or test-runner run is recorded under it.
+    fn start_job_span(&mut self, job: &StartedJob) {
+        let mut span = telemetry::span("workflow.job");
+        span.set_attribute("workflow.role", job.role.file_stem());
+        if let Some(meta) = self.workflow.active_job_meta() {
+            span.set_attribute("workflow.task_id", meta.top_task_key);
+            span.set_attribute("workflow.pass", meta.pass);
+        }
+        if let Some(title) = self.workflow.active_job_title() {
+            span.set_attribute("workflow.task_title", title);
+        }
+        if let Some(profile) = job.model_profile.as_deref() {
+            span.set_attribute("workflow.model_profile", profile);
+        }
+        telemetry::set_job_context(span.context());
+        self.active_job_span = Some(span);
+    }
+
+    /// Ends the running job's span; `error` marks a failed or unfinished job.
+    fn end_job_span(&mut self, exit_code: Option<i32>, error: Option<String>) {
+        let Some(mut span) = self.active_job_span.take() else {
+            return;
+        };
+        telemetry::set_job_context(None);
+        if let Some(code) = exit_code {
+            span.set_attribute("process.exit_code", code);
+        }
+        if let Some(error) = error {
+            span.set_error(error);
+        }
+    }
+
+    fn charge_active_job_elapsed(&mut self) {
+        self.active_job_last_output_at = None;
+        if let Some(started_at) = self.active_job_started_at.take() {
+            self.workflow.record_worker_elapsed(started_at.elapsed());
+        }
+    }
+
+    /// One-line execution summary for the status bar: active job, queue, backend, usage.
+    pub fn execution_status_text(&self, now: Instant) -> String {
+        let job = match (self.workflow.active_job_meta(), self.active_job_started_at) {
+            (Some(meta), started_at) => {
+                let elapsed =
+                    started_at.map_or(Duration::ZERO, |at| now.saturating_duration_since(at));
+                format!(
+                    "{} \"{}\" pass {} {}",
+                    worker_role_label(meta.role),
+                    self.workflow.active_job_title().unwrap_or_default(),
+                    meta.pass,
+                    time_format::format_duration(elapsed)
+                )
+            }
+            (None, _) => match self.workflow.pending_review() {
+                Some(pending) => format!(
+                    "Impl \"{}\" pass {} {}",
+                    pending.top_task_title,
+                    pending.pass,
+                    i18n::text(self.locale, Text::ExecAwaitingReview)
+                ),
+                None => i18n::text(self.locale, Text::ExecIdle).to_string(),
+            },
+        };
+        let mut parts = vec![
+            i18n::fill(self.locale, Text::ExecJob, &[("job", &job)]),
+            i18n::fill(
+                self.locale,
+                Text::ExecQueued,
+                &[("count", &self.workflow.queued_job_count())],
+            ),
+        ];
+        if let Some(silent) = self.worker_stalled_for(now) {
+            parts.insert(
+                1,
+                i18n::fill(
+                    self.locale,
+                    Text::ExecStalled,
+                    &[("silent", &time_format::format_duration(silent))],
+                ),
+            );
+        }
+        if let Some(backend) = &self.runtime_backend {
+            let backend = match &self.active_job_model {
+                Some(model) => format!("{backend}/{model}"),
+                None => backend.clone(),
+            };
+            parts.push(i18n::fill(
+                self.locale,
+                Text::ExecBackend,
+                &[("backend", &backend)],
+            ));
+        }
+        let usage = self.token_usage;
+        parts.push(i18n::fill(
+            self.locale,
+            Text::ExecTokens,
+            &[
+                ("input", &compact_count(usage.input_tokens)),
+                ("output", &compact_count(usage.output_tokens)),
+            ],
+        ));
+        if usage.cost_usd > 0.0 {
+            parts.push(i18n::fill(
+                self.locale,
+                Text::ExecCost,
+                &[("cost", &format!("{:.2}", usage.cost_usd))],
+            ));
+        }
+        parts.join(" | ")
+    }
+
+    pub fn on_worker_output(&mut self, line: String) {
+        self.reset_stall_timer(Instant::now());
+        if let Some(meta) = self.workflow.active_job_meta() {
+            let role = worker_role_label(meta.role);
+            let display = format!("{role}#{}: {line}", meta.top_task_id);
+            self.push_filtered_subagent_output(meta.role.file_stem(), &line, display);
+        } else {
+            self.push_filtered_subagent_output("worker", &line, format!("Worker: {line}"));
+        }
+        self.workflow.append_active_output(line);
+    }
+
+    pub fn active_worker_job(&self) -> Option<ActiveJobMeta> {
+        self.workflow.active_job_meta()
+    }
+
+    pub fn on_worker_system_output(&mut self, line: String) {
+        self.reset_stall_timer(Instant::now());
+        self.note_transient_failure(&line);
+        let role = self
+            .workflow
+            .active_job_meta()
+            .map_or("worker", |meta| meta.role.file_stem());
+        self.push_filtered_subagent_output(role, &line, format!("WorkerSystem: {line}"));
+    }
+
+    fn note_transient_failure(&mut self, line: &str) {
+        if self.active_job_transient_failure.is_none() && agent::is_transient_failure_line(line) {
+            self.active_job_transient_failure = Some(line.trim().to_string());
+        }
+    }
+
+    /// The transient backend failure the running job reported, if any, clearing it so a
+    /// retried run is judged on its own output.
+    pub fn take_active_job_transient_failure(&mut self) -> Option<String> {
+        self.active_job_transient_failure.take()
+    }
+
+    pub fn push_subagent_output(&mut self, line: impl Into<String>) {
+        self.append_left_top_line(line.into());
+    }
+
+    /// Adds agent output to the subagent pane through the `[output_filters]` rules for
+    /// `role`; `display` is the line as the pane shows it.
+    pub fn push_filtered_subagent_output(&mut self, role: &str, line: &str, display: String) {
+        match self
+            .output_filter
+            .apply(role, line, display, self.left_top_generation)
+        {
+            PaneUpdate::Append(display) => self.append_left_top_line(display),
+            PaneUpdate::ReplaceLast(display) => {
+                if let Some(last) = self.left_top_lines.last_mut() {
+                    *last = display;
+                }
+                self.left_top_generation = self.left_top_generation.saturating_add(1);
+                self.mark_pane_damaged(Pane::LeftTop);
+            }
+            PaneUpdate::Hide => {}
+        }
+    }
+
+    pub fn set_output_filters(&mut self, config: OutputFilterConfig) {
+        self.output_filter = OutputFilter::new(config);
+    }
+
+    /// Flips `/verbose`; returns whether every output line is now shown.
+    pub fn toggle_verbose_output(&mut self) -> bool {
+        let verbose = !self.output_filter.verbose();
+        self.output_filter.set_verbose(verbose);
+        verbose
+    }
+
+    /// Output lines the filters kept out of the subagent pane so far.
+    pub fn filtered_output_lines(&self) -> usize {
+        self.output_filter.hidden_lines()
+    }
+
+    pub fn set_response_cache(&mut self, cache: ResponseCache) {
+        self.response_cache = cache;
+    }
+
+    pub fn response_cache_mut(&mut self) -> &mut ResponseCache {
+        &mut self.response_cache
+    }
+
+    pub fn set_plugins(&mut self, plugins: PluginRegistry) {
+        self.workflow.set_plugins(plugins);
+    }
+
+    pub fn set_command_registry(&mut self, registry: CommandRegistry) {
+        self.command_registry = registry;
+    }
+
+    pub fn command_registry(&self) -> &CommandRegistry {
+        &self.command_registry
+    }
+
+    /// Replaces an alias at the start of the chat input with the command it stands for, so
+    /// the input is handled as if that had been typed. A macro is echoed and its steps
+    /// queued instead, clearing the input; returns true in that case.
+    pub fn expand_chat_input_command(&mut self) -> bool {
+        match self.command_registry.expand(&self.chat_input) {
+            None => false,
+            Some(UserCommand::Alias(command)) => {
+                self.chat_cursor = command.chars().count();
+                self.chat_input = command;
+                self.chat_cursor_goal_col = None;
+                self.invalidate_chat_input_cache();
+                false
+            }
+            Some(UserCommand::Macro(steps)) => {
+                let Some(name) = self.consume_chat_input_trimmed() else {
+                    return false;
+                };
+                self.push_chat_message_line(format!("You: {name}"));
+                self.queue_macro_steps(steps);
+                true
+            }
+        }
+    }
+
+    pub fn plugins(&self) -> &PluginRegistry {
+        self.workflow.plugins()
+    }
+
+    /// Starts command plugin `name` in the background with `input`; its output and verdict
+    /// arrive through [`Self::drain_plugin_messages`].
+    pub fn run_plugin_command(
+        &self,
+        name: &str,
+        input: &str,
+        session_dir: Option<String>,
+    ) -> Result<String, String> {
+        let plugin = self.plugins().get_for(name, PluginCapability::Command)?;
+        let request = PluginRunRequest {
+            invocation: PluginCapability::Command,
+            input: input.to_string(),
+            task: None,
+            workspace: self
+                .workspace_root
+                .as_ref()
+                .map(|root| root.display().to_string())
+                .unwrap_or_default(),
+            session_dir,
+        };
+        self.plugin_commands.start(plugin, request, self.locale);
+        Ok(self.system_text(Text::RunningPlugin, &[("name", &name)]))
+    }
+
+    pub fn drain_plugin_messages(&self) -> Vec<String> {
+        self.plugin_commands.drain_messages()
+    }
+
+    /// The `/plugin` listing: every installed plugin with what it can run as.
+    pub fn plugin_list_message(&self) -> String {
+        let plugins = self.plugins().plugins();
+        if plugins.is_empty() {
+            return self.system_text(Text::NoPluginsInstalled, &[]);
+        }
+        let mut message = self.system_text(Text::PluginsInstalled, &[("count", &plugins.len())]);
+        for plugin in plugins {
+            let capabilities = plugin
+                .manifest
+                .capabilities
+                .iter()
+                .map(|capability| match capability {
+                    PluginCapability::Command => "command",
+                    PluginCapability::Step => "step",
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            message.push_str(&format!("\n- {} [{capabilities}]", plugin.name()));
+            if !plugin.manifest.description.trim().is_empty() {
+                message.push_str(&format!(": {}", plugin.manifest.description.trim()));
+            }
+        }
+        message
+    }
+
+    /// See [`Workflow::mark_active_test_run_flaky`].
+    pub fn mark_active_test_run_flaky(&mut self) {
+        self.workflow.mark_active_test_run_flaky();
+    }
+
+    /// See [`Workflow::record_active_test_results`].
+    pub fn record_active_test_results(&mut self, report: TestRunReport) {
+        self.workflow.record_active_test_results(report);
+    }
+
+    pub fn on_worker_completed(&mut self, success: bool, code: i32) -> Vec<String> {
+        self.charge_active_job_elapsed();
+        self.end_job_span(
+            Some(code),
+            (!success).then(|| format!("job failed with code {code}")),
+        );
+        self.record_protected_path_changes();
+        self.record_audit_changed_files();
+        let citations = self.record_audit_citations();
+        let messages = self.workflow.finish_active_job(success, code);
+        self.active_job_model = None;
+        for message in messages {
+            self.push_chat_message_line(message);
+        }
+        if !citations.is_empty() {
+            self.append_left_top_line("Cited locations (click to open):".to_string());
+            for citation in &citations {
+                self.append_left_top_line(citation.describe());
+            }
+        }
+        if let Some(pending) = self.workflow.pending_review() {
+            let lines = match self.review_baseline.as_ref().map(change_review::diff_since) {
+                Some(Ok(lines)) => lines,
+                Some(Err(err)) => vec![format!("Failed to diff the workspace: {err}")],
+                None => vec!["No workspace snapshot was taken for this pass.".to_string()],
+            };
+            self.show_worker_log(
+                format!("Review: {} pass {}", pending.top_task_title, pending.pass),
+                lines,
+            );
+        } else {
+            self.review_baseline = None;
+        }
+        self.prune_expanded_detail_keys();
+        self.refresh_right_lines();
+        self.refresh_file_tree_if_visible();
+        let context_entries = self.workflow.rolling_context_entries();
+        let new_entries =
+            new_context_entries_since_snapshot(&self.last_reported_context, &context_entries);
+        self.last_reported_context = context_entries;
+        new_entries
+    }
+
+    /// Hands the workflow what git shows the finishing implementor pass changed, for the
+    /// protected-path check.
+    fn record_protected_path_changes(&mut self) {
+        if self.workflow.protected_paths().is_empty() {
+            return;
+        }
+        let Some(baseline) = self.review_baseline.as_ref() else {
+            return;
+        };
+        match change_review::changed_paths_since(baseline) {
+            Ok(paths) => self.workflow.record_active_worktree_changes(paths),
+            Err(err) => self.push_chat_message_line(
+                self.system_text(Text::ProtectedPathsListFailed, &[("err", &err)]),
+            ),
+        }
+    }
+
+    /// Adds the files the finishing implementor pass changed to the audit trail.
+    fn record_audit_changed_files(&self) {
+        let Some(job) = self
+            .workflow
+            .active_job_meta()
+            .filter(|job| job.role == WorkerRole::Implementor)
+        else {
+            return;
+        };
+        let files = self.workflow.active_changed_files();
+        if !files.is_empty() {
+            audit_trail::record(AuditEventFile::FilesChanged {
+                task_id: job.top_task_key,
+                pass: job.pass,
+                files,
+            });
+        }
+    }
+
+    /// Parses the finishing audit pass's file/line citations, checks them against the
+    /// workspace, and hands them to the workflow for the implementor's fix prompt.
+    fn record_audit_citations(&mut self) -> Vec<AuditCitation> {
+        let is_audit = self
+            .workflow
+            .active_job_meta()
+            .is_some_and(|job| matches!(job.role, WorkerRole::Auditor | WorkerRole::FinalAudit));
+        if !is_audit {
+            return Vec::new();
+        }
+        let mut citations = audit_citations::parse(self.workflow.active_transcript());
+        if let Some(root) = self.workspace_root.as_ref() {
+            audit_citations::verify(&mut citations, root);
+        }
+        self.workflow
+            .record_active_audit_citations(citations.clone());
+        citations
+    }
+
+    pub fn drain_worker_failures(&mut self) -> Vec<WorkflowFailure> {
+        self.workflow.drain_recent_failures()
+    }
+
+    pub fn drain_completed_top_tasks(&mut self) -> Vec<CompletedTopTask> {
+        self.workflow.drain_completed_top_tasks()
+    }
+
+    pub fn set_chat_scroll(&mut self, scroll: usize) {
+        if self.chat_scroll != scroll {
+            self.mark_pane_damaged(Pane::LeftBottom);
+        }
+        self.chat_scroll = scroll;
+    }
+
+    #[cfg(test)]
+    pub fn left_top_lines(&self) -> &[String] {
+        &self.left_top_lines
+    }
+
+    pub fn left_top_wrapped_text(&self, width: u16) -> Arc<String> {
+        let width = width.max(1);
+        if let Some(cache) = self.left_top_wrap_cache.borrow().as_ref()
+            && cache.width == width
+            && cache.generation == self.left_top_generation
+        {
+            return Arc::clone(&cache.rendered);
+        }
+
+        let rendered =
+            Arc::new(wrap_word_with_positions(&self.left_top_lines.join("\n"), width).rendered);
+        *self.left_top_wrap_cache.borrow_mut() = Some(WrappedPaneCache {
+            width,
+            generation: self.left_top_generation,
+            rendered: Arc::clone(&rendered),
+        });
+        rendered
+    }
+
+    /// The newest chat lines, the ones still in memory; see [`App::chat_transcript`].
+    #[cfg(test)]
+    pub fn left_bottom_lines(&self) -> &[String] {
+        self.chat_transcript.recent()
+    }
+
+    pub fn chat_transcript(&self) -> &Transcript {
+        &self.chat_transcript
+    }
+
+    /// The transcript of the current [`ChatView`], the one the chat pane draws.
+    pub fn displayed_chat_transcript(&self) -> &Transcript {
+        match self.chat_view {
+            ChatView::Chat => &self.conversation,
+            ChatView::SystemLog => &self.system_log,
+            ChatView::Task(top_task_id) => self
+                .task_threads
+                .get(&top_task_id)
+                .unwrap_or(&self.conversation),
+        }
+    }
+
+    pub fn chat_view(&self) -> ChatView {
+        self.chat_view
+    }
+
+    pub fn toggle_chat_view(&mut self) -> ChatView {
+        self.chat_view = match self.chat_view {
+            ChatView::Chat => ChatView::SystemLog,
+            ChatView::SystemLog | ChatView::Task(_) => ChatView::Chat,
+        };
+        self.chat_view
+    }
+
+    /// Switches the chat pane to the conversation about the top-level task containing
+    /// planner task `task_id`. Returns false when there is no such task.
+    pub fn show_task_thread(&mut self, task_id: &str) -> bool {
+        let Some(top_task_id) = self.workflow.top_task_id(task_id) else {
+            return false;
+        };
+        self.task_threads.entry(top_task_id).or_default();
+        self.chat_view = ChatView::Task(top_task_id);
+        self.mark_pane_damaged(Pane::LeftBottom);
+        true
+    }
+
+    /// Planner id the chat pane title shows for the thread of top-level task `top_task_id`.
+    pub fn task_thread_label(&self, top_task_id: u64) -> String {
+        self.workflow
+            .top_task_external_id_by_id(top_task_id)
+            .unwrap_or_else(|| format!("#{top_task_id}"))
+    }
+
+    /// Opens the window in which system messages count as replies to the user's input.
+    pub fn begin_command_reply(&mut self) {
+        self.command_reply_open = true;
+    }
+
+    pub fn end_command_reply(&mut self) {
+        self.command_reply_open = false;
+    }
+
+    pub fn right_block_lines(&self, width: u16) -> Vec<String> {
+        match self.right_pane_mode {
+            RightPaneMode::PlannerMarkdown => self.planner_block_lines(width),
+            RightPaneMode::WorkerLog => wrap_block_lines(&self.worker_log_raw_lines(), width),
+            RightPaneMode::TaskSummaries => wrap_block_lines(&self.task_summary_raw_lines(), width),
+            RightPaneMode::TaskList => {
+                self.workflow
+                    .right_pane_block_view(width, &self.expanded_detail_keys)
+                    .lines
+            }
+        }
+    }
+
+    pub fn right_block_view(&self, width: u16) -> RightPaneBlockView {
+        match self.right_pane_mode {
+            RightPaneMode::PlannerMarkdown => RightPaneBlockView {
+                lines: self.planner_block_lines(width),
+                toggles: Vec::new(),
+                task_spans: Vec::new(),
+            },
+            RightPaneMode::WorkerLog => RightPaneBlockView {
+                lines: wrap_block_lines(&self.worker_log_raw_lines(), width),
+                toggles: Vec::new(),
+                task_spans: Vec::new(),
+            },
+            RightPaneMode::TaskSummaries => RightPaneBlockView {
+                lines: wrap_block_lines(&self.task_summary_raw_lines(), width),
+                toggles: Vec::new(),
+                task_spans: Vec::new(),
+            },
+            RightPaneMode::TaskList => self
+                .workflow
+                .right_pane_block_view(width, &self.expanded_detail_keys),
+        }
+    }
+
+    pub fn toggle_task_details(&mut self, task_key: &str) {
+        if self.expanded_detail_keys.contains(task_key) {
+            self.expanded_detail_keys.remove(task_key);
+        } else {
+            self.expanded_detail_keys.insert(task_key.to_string());
+        }
+    }
+
+    /// Pane positions to save in the session being left.
+    pub fn session_ui_state(&self) -> SessionUiStateFile {
+        SessionUiStateFile {
+            left_top_scroll: self.left_top_scroll,
+            right_scroll: self.right_scroll,
+            planner_cursor: self.planner_cursor,
+            expanded_task_keys: self.expanded_detail_keys.iter().cloned().collect(),
+        }
+    }
+
+    /// Puts the panes back where [`App::session_ui_state`] left them. Call after the session's
+    /// tasks and planner are loaded so stale task keys and offsets are dropped.
+    pub fn restore_session_ui_state(&mut self, state: SessionUiStateFile) {
+        self.expanded_detail_keys = state.expanded_task_keys.into_iter().collect();
+        self.prune_expanded_detail_keys();
+        self.refresh_right_lines();
+        self.left_top_scroll = state.left_top_scroll.min(self.max_scroll(Pane::LeftTop));
+        self.right_scroll = state.right_scroll.min(self.max_scroll(Pane::Right));
+        self.set_planner_cursor(state.planner_cursor);
+    }
+
+    fn prune_expanded_detail_keys(&mut self) {
+        let valid = self.workflow.task_detail_keys();
+        self.expanded_detail_keys.retain(|key| valid.contains(key));
+    }
+
+    pub fn left_top_scroll(&self) -> u16 {
+        self.left_top_scroll
+    }
+
+    pub fn left_bottom_scroll(&self) -> usize {
+        self.chat_scroll
+    }
+
+    pub fn right_scroll(&self) -> u16 {
+        self.right_scroll
+    }
+
+    pub fn chat_input(&self) -> &str {
+        &self.chat_input
+    }
+
+    pub fn wrapped_chat_input_layout(&self, width: u16) -> Arc<WrappedText> {
+        let width = width.max(1);
+        if let Some(cache) = self.chat_input_wrap_cache.borrow().as_ref()
+            && cache.width == width
+            && cache.generation == self.chat_input_generation
+        {
+            return Arc::clone(&cache.wrapped);
+        }
+        let wrapped = Arc::new(wrap_word_with_positions(&self.chat_input, width));
+        *self.chat_input_wrap_cache.borrow_mut() = Some(WrappedInputCache {
+            width,
+            generation: self.chat_input_generation,
+            wrapped: Arc::clone(&wrapped),
+        });
+        wrapped
+    }
+
+    pub fn chat_input_line_count(&self, width: u16) -> u16 {
+        self.wrapped_chat_input_layout(width).line_count
+    }
+
+    pub fn chat_messages_generation(&self) -> u64 {
+        self.displayed_chat_transcript().generation()
+    }
+
+    pub fn consume_chat_input_trimmed(&mut self) -> Option<String> {
+        let message = self.chat_input.trim().to_string();
+        self.chat_input.clear();
+        self.chat_cursor = 0;
+        self.chat_cursor_goal_col = None;
+        self.invalidate_chat_input_cache();
+        if message.is_empty() {
+            None
+        } else {
+            Some(message)
+        }
+    }
+
+    pub fn chat_cursor_line_col(&self, width: u16) -> (u16, u16) {
+        let positions = &self.wrapped_chat_input_layout(width).positions;
+        positions[self.chat_cursor]
+    }
+
+    pub fn command_suggestions(&self) -> Vec<CommandSuggestion> {
+        let Some(query) = command_query(&self.chat_input) else {
+            return Vec::new();
+        };
+        self.command_registry.suggestions(query)
+    }
+
+    /// Usage and validation of the arguments typed after a slash command, with task ids
+    /// checked against the current plan.
+    pub fn command_arg_hint(&self) -> Option<ArgHint> {
+        if self.is_any_picker_open() || !self.chat_input.trim_start().starts_with('/') {
+            return None;
+        }
+        let task_ids = self
+            .planner_tasks_for_file()
+            .into_iter()
+            .map(|task| task.id)
+            .collect::<Vec<_>>();
+        // An alias gets the hint of the command it stands for.
+        let input = self.command_registry.expand_alias(&self.chat_input);
+        command_args::hint(
+            input.as_deref().unwrap_or(&self.chat_input),
+            ArgContext {
+                task_ids: &task_ids,
+            },
+        )
+    }
+
+    pub fn should_show_command_index(&self) -> bool {
+        !self.is_any_picker_open() && !self.command_suggestions().is_empty()
+    }
+
+    pub fn autocomplete_top_command(&mut self) -> bool {
+        if self.is_any_picker_open() {
+            return false;
+        }
+        let Some(top) = self.command_suggestions().into_iter().next() else {
+            return false;
+        };
+        self.chat_input = top.command;
+        self.chat_cursor = self.chat_input.chars().count();
+        self.chat_cursor_goal_col = None;
+        self.invalidate_chat_input_cache();
+        true
+    }
+
+    pub fn open_resume_picker(&mut self, entries: Vec<ResumeSessionOption>) {
+        self.open_resume_picker_with_other_workspaces(entries, Vec::new());
+    }
+
+    /// Opens the resume picker on this workspace's sessions; Tab adds `other_workspaces`,
+    /// which are listed from the start when this workspace has none.
+    pub fn open_resume_picker_with_other_workspaces(
+        &mut self,
+        entries: Vec<ResumeSessionOption>,
+        other_workspaces: Vec<ResumeSessionOption>,
+    ) {
+        self.new_session_message = None;
+        self.backend_picker = None;
+        self.log_picker = None;
+        self.task_menu = None;
+        if entries.is_empty() && other_workspaces.is_empty() {
+            self.resume_picker = None;
+        } else {
+            self.resume_picker = Some(ResumePickerState {
+                all_workspaces: entries.is_empty(),
+                entries,
+                other_workspaces,
+                confirm_workspace_switch: None,
+                selected: 0,
+                opened_at_epoch_secs: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_secs()),
+                previews: HashMap::new(),
+                filter: String::new(),
+            });
+        }
+    }
+
+    /// Previews for the open resume picker, keyed by session directory.
+    pub fn set_resume_picker_previews(&mut self, previews: HashMap<String, SessionPreview>) {
+        if let Some(state) = self.resume_picker.as_mut() {
+            state.previews = previews;
+        }
+    }
+
+    pub fn is_resume_picker_open(&self) -> bool {
+        self.resume_picker.is_some()
+    }
+
+    pub fn set_offer_recent_session(&mut self, enabled: bool) {
+        self.offer_recent_session = enabled;
+    }
+
+    pub fn offers_recent_session(&self) -> bool {
+        self.offer_recent_session
+    }
+
+    /// Opens the resume picker on the workspace's recent sessions while `message`, which
+    /// would start a new session, waits. Offered once per run.
+    pub fn offer_recent_sessions(&mut self, entries: Vec<ResumeSessionOption>, message: String) {
+        self.open_resume_picker(entries);
+        self.offer_recent_session = false;
+        if self.resume_picker.is_some() {
+            self.new_session_message = Some(message);
+        }
+    }
+
+    /// The message held by `offer_recent_sessions`, once the offer is declined.
+    pub fn take_new_session_message(&mut self) -> Option<String> {
+        self.new_session_message.take()
+    }
+
+    pub fn open_backend_picker(&mut self, entries: Vec<BackendOption>) {
+        self.resume_picker = None;
+        self.log_picker = None;
+        self.task_menu = None;
+        if entries.is_empty() {
+            self.backend_picker = None;
+        } else {
+            self.backend_picker = Some(BackendPickerState {
+                entries,
+                selected: 0,
+            });
+        }
+    }
+
+    pub fn is_backend_picker_open(&self) -> bool {
+        self.backend_picker.is_some()
+    }
+
+    pub fn backend_picker_options(&self) -> &[BackendOption] {
+        match self.backend_picker.as_ref() {
+            Some(state) => &state.entries,
+            None => &[],
+        }
+    }
+
+    pub fn backend_picker_selected_index(&self) -> usize {
+        self.backend_picker
+            .as_ref()
+            .map(|state| state.selected)
+            .unwrap_or(0)
+    }
+
+    pub fn backend_picker_move_up(&mut self) {
+        let Some(state) = self.backend_picker.as_mut() else {
+            return;
+        };
+        state.selected = state.selected.saturating_sub(1);
+    }
 
-    pub fn left_top_scroll(&self) -> u16 {
-        self.left_top_scroll
+    pub fn backend_picker_move_down(&mut self) {
+        let Some(state) = self.backend_picker.as_mut() else {
+            return;
+        };
+        if state.selected + 1 < state.entries.len() {
+            state.selected += 1;
+        }
     }
 
-    pub fn left_bottom_scroll(&self) -> u16 {
-        self.chat_scroll
+    pub fn select_backend_option(&mut self) -> Option<BackendOption> {
+        let state = self.backend_picker.take()?;
+        state.entries.get(state.selected).copied()
     }
 
-    pub fn right_scroll(&self) -> u16 {
-        self.right_scroll
+    pub fn open_log_picker(&mut self, entries: Vec<WorkerLogEntry>) {
+        self.resume_picker = None;
+        self.backend_picker = None;
+        self.task_menu = None;
+        if entries.is_empty() {
+            self.log_picker = None;
+        } else {
+            self.log_picker = Some(LogPickerState {
+                entries,
+                selected: 0,
+            });
+        }
     }
 
-    pub fn chat_input(&self) -> &str {
-        &self.chat_input
+    pub fn is_log_picker_open(&self) -> bool {
+        self.log_picker.is_some()
     }
 
-    pub fn wrapped_chat_input_layout(&self, width: u16) -> Arc<WrappedText> {
-        let width = width.max(1);
-        if let Some(cache) = self.chat_input_wrap_cache.borrow().as_ref()
-            && cache.width == width
-            && cache.generation == self.chat_input_generation
-        {
-            return Arc::clone(&cache.wrapped);
+    pub fn log_picker_options(&self) -> &[WorkerLogEntry] {
+        match self.log_picker.as_ref() {
+            Some(state) => &state.entries,
+            None => &[],
         }
-        let wrapped = Arc::new(wrap_word_with_positions(&self.chat_input, width));
-        *self.chat_input_wrap_cache.borrow_mut() = Some(WrappedInputCache {
-            width,
-            generation: self.chat_input_generation,
-            wrapped: Arc::clone(&wrapped),
-        });
-        wrapped
     }
 
-    pub fn chat_input_line_count(&self, width: u16) -> u16 {
-        self.wrapped_chat_input_layout(width).line_count
+    pub fn log_picker_selected_index(&self) -> usize {
+        self.log_picker
+            .as_ref()
+            .map(|state| state.selected)
+            .unwrap_or(0)
     }
 
-    pub fn chat_messages_generation(&self) -> u64 {
-        self.chat_messages_generation
+    pub fn log_picker_move_up(&mut self) {
+        let Some(state) = self.log_picker.as_mut() else {
+            return;
+        };
+        state.selected = state.selected.saturating_sub(1);
     }
 
-    pub fn consume_chat_input_trimmed(&mut self) -> Option<String> {
-        let message = self.chat_input.trim().to_string();
-        self.chat_input.clear();
-        self.chat_cursor = 0;
-        self.chat_cursor_goal_col = None;
-        self.invalidate_chat_input_cache();
-        if message.is_empty() {
-            None
-        } else {
-            Some(message)
+    pub fn log_picker_move_down(&mut self) {
+        let Some(state) = self.log_picker.as_mut() else {
+            return;
+        };
+        if state.selected + 1 < state.entries.len() {
+            state.selected += 1;
         }
     }
 
-    pub fn chat_cursor_line_col(&self, width: u16) -> (u16, u16) {
-        let positions = &self.wrapped_chat_input_layout(width).positions;
-        positions[self.chat_cursor]
+    pub fn select_worker_log(&mut self) -> Option<WorkerLogEntry> {
+        let state = self.log_picker.take()?;
+        state.entries.get(state.selected).cloned()
     }
 
-    pub fn command_suggestions(&self) -> Vec<CommandSuggestion> {
-        let Some(query) = command_query(&self.chat_input) else {
-            return Vec::new();
+    /// Opens the action menu for the task drawn under right-pane key `task_key`. Returns
+    /// false when that task has no planner id to act on.
+    pub fn open_task_menu(&mut self, task_key: &str) -> bool {
+        let Some(target) = self.workflow.task_menu_target(task_key) else {
+            return false;
         };
-        COMMAND_INDEX
+        let mut entries = Vec::new();
+        if target.collapsible_details {
+            entries.push(TaskMenuEntry::ToggleDetails);
+        }
+        entries.push(TaskMenuEntry::ViewLogs);
+        entries.push(TaskMenuEntry::ShowConversation);
+        entries.push(TaskMenuEntry::Retry);
+        if !target.done {
+            entries.push(TaskMenuEntry::Skip);
+        }
+        entries.push(TaskMenuEntry::ChangeModel);
+        if target.accepts_docs {
+            entries.push(TaskMenuEntry::AttachDocs);
+        }
+        self.show_task_menu(target, entries);
+        true
+    }
+
+    /// Replaces the menu's actions with a choice of model profiles for `target`.
+    pub fn open_task_model_menu(&mut self, target: TaskMenuTarget, profiles: Vec<String>) {
+        let current = self.task_models.get(&target.top_task_id).cloned();
+        let entries = std::iter::once(TaskMenuEntry::ModelProfile(None))
+            .chain(
+                profiles
+                    .into_iter()
+                    .map(|profile| TaskMenuEntry::ModelProfile(Some(profile))),
+            )
+            .collect::<Vec<_>>();
+        let selected = entries
             .iter()
-            .filter(|(command, _)| command.starts_with(query))
-            .map(|(command, description)| CommandSuggestion {
-                command,
-                description,
-            })
-            .collect()
+            .position(|entry| *entry == TaskMenuEntry::ModelProfile(current.clone()))
+            .unwrap_or(0);
+        self.show_task_menu(target, entries);
+        if let Some(state) = self.task_menu.as_mut() {
+            state.selected = selected;
+        }
     }
 
-    pub fn should_show_command_index(&self) -> bool {
-        !self.is_any_picker_open() && !self.command_suggestions().is_empty()
+    fn show_task_menu(&mut self, target: TaskMenuTarget, entries: Vec<TaskMenuEntry>) {
+        self.resume_picker = None;
+        self.backend_picker = None;
+        self.log_picker = None;
+        self.task_menu = Some(TaskMenuState {
+            target,
+            entries,
+            selected: 0,
+        });
     }
 
-    pub fn autocomplete_top_command(&mut self) -> bool {
-        if self.is_any_picker_open() {
-            return false;
+    pub fn is_task_menu_open(&self) -> bool {
+        self.task_menu.is_some()
+    }
+
+    pub fn close_task_menu(&mut self) {
+        self.task_menu = None;
+    }
+
+    pub fn task_menu_target(&self) -> Option<&TaskMenuTarget> {
+        self.task_menu.as_ref().map(|state| &state.target)
+    }
+
+    pub fn task_menu_entries(&self) -> &[TaskMenuEntry] {
+        match self.task_menu.as_ref() {
+            Some(state) => &state.entries,
+            None => &[],
         }
-        let Some(top) = self.command_suggestions().first().copied() else {
-            return false;
+    }
+
+    pub fn task_menu_selected_index(&self) -> usize {
+        self.task_menu
+            .as_ref()
+            .map(|state| state.selected)
+            .unwrap_or(0)
+    }
+
+    pub fn task_menu_move_up(&mut self) {
+        let Some(state) = self.task_menu.as_mut() else {
+            return;
+        };
+        state.selected = state.selected.saturating_sub(1);
+    }
+
+    pub fn task_menu_move_down(&mut self) {
+        let Some(state) = self.task_menu.as_mut() else {
+            return;
         };
-        self.chat_input = top.command.to_string();
+        if state.selected + 1 < state.entries.len() {
+            state.selected += 1;
+        }
+    }
+
+    pub fn set_task_menu_selected(&mut self, index: usize) {
+        let Some(state) = self.task_menu.as_mut() else {
+            return;
+        };
+        if index < state.entries.len() {
+            state.selected = index;
+        }
+    }
+
+    /// Closes the menu and returns the chosen entry with the task it applies to.
+    pub fn select_task_menu_entry(&mut self) -> Option<(TaskMenuTarget, TaskMenuEntry)> {
+        let state = self.task_menu.take()?;
+        let entry = state.entries.get(state.selected).cloned()?;
+        Some((state.target, entry))
+    }
+
+    pub fn is_task_details_expanded(&self, task_key: &str) -> bool {
+        self.expanded_detail_keys.contains(task_key)
+    }
+
+    /// Puts `command` in the chat input so the next submit runs it like a typed command. The
+    /// user's draft comes back with [`App::restore_staged_chat_draft`].
+    pub fn stage_chat_command(&mut self, command: String) {
+        let draft = std::mem::replace(&mut self.chat_input, command.clone());
+        self.staged_chat_command = Some(StagedChatCommand { command, draft });
         self.chat_cursor = self.chat_input.chars().count();
         self.chat_cursor_goal_col = None;
         self.invalidate_chat_input_cache();
-        true
     }
 
-    pub fn open_resume_picker(&mut self, entries: Vec<ResumeSessionOption>) {
+    /// Brings back the draft displaced by [`App::stage_chat_command`], dropping the staged
+    /// command if it was not submitted.
+    pub fn restore_staged_chat_draft(&mut self) {
+        let Some(staged) = self.staged_chat_command.take() else {
+            return;
+        };
+        if !self.chat_input.is_empty() && self.chat_input != staged.command {
+            return;
+        }
+        self.chat_input = staged.draft;
+        self.chat_cursor = self.chat_input.chars().count();
+        self.chat_cursor_goal_col = None;
+        self.invalidate_chat_input_cache();
+    }
+
+    pub fn task_model(&self, top_task_id: &str) -> Option<&str> {
+        self.task_models.get(top_task_id).map(String::as_str)
+    }
+
+    pub fn task_models(&self) -> &BTreeMap<String, String> {
+        &self.task_models
+    }
+
+    /// Pins `profile` to the worker jobs of top-level task `top_task_id`; `None` unpins it.
+    pub fn set_task_model(&mut self, top_task_id: &str, profile: Option<String>) {
+        match profile {
+            Some(profile) => {
+                self.task_models.insert(top_task_id.to_string(), profile);
+            }
+            None => {
+                self.task_models.remove(top_task_id);
+            }
+        }
+    }
+
+    pub fn replace_task_models(&mut self, task_models: BTreeMap<String, String>) {
+        self.task_models = task_models;
+    }
+
+    pub fn speed_profile(&self) -> SpeedProfile {
+        self.speed_profile
+    }
+
+    /// Applies a `/speed` profile to the worker models and audit settings of later jobs.
+    pub fn set_speed_profile(&mut self, speed: SpeedProfile) {
+        self.speed_profile = speed;
+        self.workflow.set_max_audit_retries(speed.audit_retries());
+        self.workflow
+            .set_test_writer_audits_enabled(speed.test_writer_audits());
+    }
+
+    /// Planner id and title of the top-level task containing planner task `task_id`.
+    pub fn top_level_task(&self, task_id: &str) -> Option<(String, String)> {
+        let top_id = self.workflow.top_task_external_id(task_id)?;
+        let target = self.workflow.task_menu_target(&top_id)?;
+        Some((top_id, target.title))
+    }
+
+    /// Shows a worker log read-only in the right pane until Backspace returns to the task list.
+    pub fn show_worker_log(&mut self, label: String, lines: Vec<String>) {
+        self.worker_log_view = Some(WorkerLogView { label, lines });
+        self.right_pane_mode = RightPaneMode::WorkerLog;
+        self.right_scroll = 0;
+        self.refresh_right_lines();
+    }
+
+    pub fn is_worker_log_mode(&self) -> bool {
+        self.right_pane_mode == RightPaneMode::WorkerLog
+    }
+
+    pub fn close_worker_log(&mut self) {
+        self.worker_log_view = None;
+        self.set_right_pane_mode(RightPaneMode::TaskList);
+    }
+
+    /// Replaces the task summaries with those read from a session's task-summaries.json.
+    pub fn load_task_summaries(&mut self, summaries: Vec<TaskSummaryFileEntry>) {
+        self.task_summaries = summaries;
+        self.refresh_right_lines();
+    }
+
+    /// Files the summary the master report agent wrote for a finished task.
+    pub fn record_task_summary(&mut self, entry: TaskSummaryFileEntry) {
+        self.task_summaries
+            .retain(|existing| existing.top_task_id != entry.top_task_id);
+        self.task_summaries.push(entry);
+        self.refresh_right_lines();
+    }
+
+    pub fn is_task_summaries_mode(&self) -> bool {
+        self.right_pane_mode == RightPaneMode::TaskSummaries
+    }
+
+    pub fn toggle_master_prompt_preview(&mut self) -> bool {
+        self.master_prompt_preview_enabled = !self.master_prompt_preview_enabled;
+        self.master_prompt_preview_enabled
+    }
+
+    pub fn is_master_prompt_preview_enabled(&self) -> bool {
+        self.master_prompt_preview_enabled
+    }
+
+    pub fn open_master_prompt_preview(
+        &mut self,
+        prompt: String,
+        user_message: String,
+        consumed_session_intro: bool,
+    ) {
+        self.resume_picker = None;
         self.backend_picker = None;
-        if entries.is_empty() {
-            self.resume_picker = None;
-        } else {
-            self.resume_picker = Some(ResumePickerState {
-                entries,
-                selected: 0,
-            });
+        self.log_picker = None;
+        self.task_menu = None;
+        self.master_prompt_preview = Some(MasterPromptPreview {
+            prompt,
+            user_message,
+            consumed_session_intro,
+            scroll: 0,
+        });
+    }
+
+    pub fn is_master_prompt_preview_open(&self) -> bool {
+        self.master_prompt_preview.is_some()
+    }
+
+    pub fn master_prompt_preview(&self) -> Option<&MasterPromptPreview> {
+        self.master_prompt_preview.as_ref()
+    }
+
+    pub fn master_prompt_preview_scroll_up(&mut self) {
+        if let Some(preview) = self.master_prompt_preview.as_mut() {
+            preview.scroll = preview.scroll.saturating_sub(1);
         }
     }
 
-    pub fn is_resume_picker_open(&self) -> bool {
-        self.resume_picker.is_some()
+    pub fn master_prompt_preview_scroll_down(&mut self, max_scroll: u16) {
+        if let Some(preview) = self.master_prompt_preview.as_mut() {
+            preview.scroll = preview.scroll.saturating_add(1).min(max_scroll);
+        }
     }
 
-    pub fn open_backend_picker(&mut self, entries: Vec<BackendOption>) {
+    pub fn take_master_prompt_preview(&mut self) -> Option<MasterPromptPreview> {
+        self.master_prompt_preview.take()
+    }
+
+    /// Opens the answer form for the questions in a master `QUESTIONS` block.
+    pub fn open_question_form(&mut self, form: QuestionForm) {
         self.resume_picker = None;
-        if entries.is_empty() {
-            self.backend_picker = None;
+        self.backend_picker = None;
+        self.log_picker = None;
+        self.task_menu = None;
+        self.question_form = Some(form);
+    }
+
+    pub fn is_question_form_open(&self) -> bool {
+        self.question_form.is_some()
+    }
+
+    pub fn question_form(&self) -> Option<&QuestionForm> {
+        self.question_form.as_ref()
+    }
+
+    pub fn question_form_mut(&mut self) -> Option<&mut QuestionForm> {
+        self.question_form.as_mut()
+    }
+
+    pub fn take_question_form(&mut self) -> Option<QuestionForm> {
+        self.question_form.take()
+    }
+
+    /// Opens the `/rerun` editor on task `task_id`'s last worker prompt.
+    pub fn open_rerun_editor(&mut self, task_id: &str) -> Result<(), String> {
+        let prompt = self.workflow.last_prompt_for(task_id)?;
+        self.resume_picker = None;
+        self.backend_picker = None;
+        self.log_picker = None;
+        self.task_menu = None;
+        self.rerun_editor = Some(PromptEditor::new(prompt));
+        Ok(())
+    }
+
+    pub fn is_rerun_editor_open(&self) -> bool {
+        self.rerun_editor.is_some()
+    }
+
+    pub fn rerun_editor(&self) -> Option<&PromptEditor> {
+        self.rerun_editor.as_ref()
+    }
+
+    pub fn rerun_editor_mut(&mut self) -> Option<&mut PromptEditor> {
+        self.rerun_editor.as_mut()
+    }
+
+    pub fn take_rerun_editor(&mut self) -> Option<PromptEditor> {
+        self.rerun_editor.take()
+    }
+
+    /// Opens the key binding help on the bindings of whatever has focus now.
+    pub fn open_help_overlay(&mut self) {
+        self.help_overlay = Some(HelpOverlay::new(self.focused_key_context()));
+    }
+
+    pub fn close_help_overlay(&mut self) {
+        self.help_overlay = None;
+    }
+
+    pub fn is_help_overlay_open(&self) -> bool {
+        self.help_overlay.is_some()
+    }
+
+    pub fn help_overlay(&self) -> Option<&HelpOverlay> {
+        self.help_overlay.as_ref()
+    }
+
+    pub fn help_overlay_mut(&mut self) -> Option<&mut HelpOverlay> {
+        self.help_overlay.as_mut()
+    }
+
+    /// The modal, picker, or pane mode that receives keys now.
+    pub fn focused_key_context(&self) -> KeyContext {
+        if self.rerun_editor.is_some() {
+            KeyContext::RerunEditor
+        } else if self.question_form.is_some() {
+            KeyContext::QuestionForm
+        } else if self.master_prompt_preview.is_some() {
+            KeyContext::PromptPreview
+        } else if self.task_menu.is_some() {
+            KeyContext::TaskMenu
+        } else if self.resume_picker.is_some()
+            || self.backend_picker.is_some()
+            || self.log_picker.is_some()
+        {
+            KeyContext::Pickers
         } else {
-            self.backend_picker = Some(BackendPickerState {
-                entries,
-                selected: 0,
-            });
+            match self.active_pane {
+                Pane::LeftBottom => KeyContext::ChatInput,
+                Pane::LeftTop if self.is_file_tree_mode() => KeyContext::FileTree,
+                Pane::LeftTop => KeyContext::WorkerOutput,
+                Pane::Right => match self.right_pane_mode {
+                    RightPaneMode::PlannerMarkdown => KeyContext::PlannerEditor,
+                    RightPaneMode::WorkerLog => KeyContext::WorkerLog,
+                    RightPaneMode::TaskSummaries => KeyContext::TaskSummaries,
+                    RightPaneMode::TaskList => KeyContext::TaskList,
+                },
+            }
         }
     }
 
-    pub fn is_backend_picker_open(&self) -> bool {
-        self.backend_picker.is_some()
+    /// Puts a cancelled message back into an empty chat input so it can be edited and resent.
+    pub fn restore_chat_input(&mut self, message: &str) {
+        if !self.chat_input.is_empty() {
+            return;
+        }
+        self.chat_input = message.to_string();
+        self.chat_cursor = self.chat_input.chars().count();
+        self.chat_cursor_goal_col = None;
+        self.invalidate_chat_input_cache();
+    }
+
+    pub fn set_pending_task_split(&mut self, proposal: PendingTaskSplit) {
+        self.pending_task_split = Some(proposal);
+    }
+
+    pub fn pending_task_split(&self) -> Option<&PendingTaskSplit> {
+        self.pending_task_split.as_ref()
+    }
+
+    pub fn take_pending_task_split(&mut self) -> Option<PendingTaskSplit> {
+        self.pending_task_split.take()
+    }
+
+    pub fn set_pending_task_changes(&mut self, changes: PendingTaskChanges) {
+        self.pending_task_changes = Some(changes);
+    }
+
+    pub fn pending_task_changes(&self) -> Option<&PendingTaskChanges> {
+        self.pending_task_changes.as_ref()
+    }
+
+    pub fn take_pending_task_changes(&mut self) -> Option<PendingTaskChanges> {
+        self.pending_task_changes.take()
     }
 
-    pub fn backend_picker_options(&self) -> &[BackendOption] {
-        match self.backend_picker.as_ref() {
-            Some(state) => &state.entries,
-            None => &[],
+    /// Whether `task_ids` repeat the `/tasks delete` last shown for confirmation. Otherwise
+    /// they are remembered, so sending the same delete again confirms it.
+    pub fn confirm_tasks_delete(&mut self, task_ids: Vec<String>) -> bool {
+        if self.pending_tasks_delete.as_ref() == Some(&task_ids) {
+            self.pending_tasks_delete = None;
+            return true;
         }
+        self.pending_tasks_delete = Some(task_ids);
+        false
     }
 
-    pub fn backend_picker_selected_index(&self) -> usize {
-        self.backend_picker
-            .as_ref()
-            .map(|state| state.selected)
-            .unwrap_or(0)
+    pub fn clear_pending_tasks_delete(&mut self) {
+        self.pending_tasks_delete = None;
     }
 
-    pub fn backend_picker_move_up(&mut self) {
-        let Some(state) = self.backend_picker.as_mut() else {
-            return;
-        };
-        state.selected = state.selected.saturating_sub(1);
+    pub fn set_external_tasks_edit(&mut self, edit: ExternalTasksEdit) {
+        self.external_tasks_edit = Some(edit);
     }
 
-    pub fn backend_picker_move_down(&mut self) {
-        let Some(state) = self.backend_picker.as_mut() else {
-            return;
-        };
-        if state.selected + 1 < state.entries.len() {
-            state.selected += 1;
-        }
+    pub fn external_tasks_edit(&self) -> Option<&ExternalTasksEdit> {
+        self.external_tasks_edit.as_ref()
     }
 
-    pub fn select_backend_option(&mut self) -> Option<BackendOption> {
-        let state = self.backend_picker.take()?;
-        state.entries.get(state.selected).copied()
+    pub fn has_external_tasks_edit(&self) -> bool {
+        self.external_tasks_edit.is_some()
+    }
+
+    pub fn take_external_tasks_edit(&mut self) -> Option<ExternalTasksEdit> {
+        self.external_tasks_edit.take()
+    }
+
+    /// Titles already sent to the task splitter in this session; they are not proposed again.
+    pub fn task_split_considered_titles(&self) -> &HashSet<String> {
+        &self.task_split_considered_titles
+    }
+
+    pub fn mark_task_split_considered(&mut self, titles: impl IntoIterator<Item = String>) {
+        self.task_split_considered_titles.extend(titles);
     }
 
     pub fn set_task_check_in_progress(&mut self, in_progress: bool) {
@@ -850,8 +3350,20 @@ impl App {
         self.task_check_in_progress
     }
 
+    /// Ending a docs attach also drops the tasks.json baseline it started from.
     pub fn set_docs_attach_in_progress(&mut self, in_progress: bool) {
         self.docs_attach_in_progress = in_progress;
+        if !in_progress {
+            self.docs_attach_baseline = None;
+        }
+    }
+
+    pub fn set_docs_attach_baseline(&mut self, baseline: Option<String>) {
+        self.docs_attach_baseline = baseline;
+    }
+
+    pub fn take_docs_attach_baseline(&mut self) -> Option<String> {
+        self.docs_attach_baseline.take()
     }
 
     pub fn is_docs_attach_in_progress(&self) -> bool {
@@ -867,6 +3379,40 @@ impl App {
         self.workflow.set_tests_mode_enabled(enabled);
     }
 
+    pub fn set_final_audit_policy(&mut self, policy: FinalAuditPolicy) {
+        self.workflow.set_final_audit_policy(policy);
+    }
+
+    pub fn final_audit_policy(&self) -> FinalAuditPolicy {
+        self.workflow.final_audit_policy()
+    }
+
+    pub fn set_audit_rubric(&mut self, rubric: Option<String>) {
+        self.workflow.set_audit_rubric(rubric);
+    }
+
+    /// Doc snapshot texts keyed by `snapshot` path, for the next worker prompts.
+    pub fn set_doc_snapshots(&mut self, snapshots: HashMap<String, String>) {
+        self.workflow.set_doc_snapshots(snapshots);
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_safe_mode(&mut self, safe_mode: bool) {
+        self.safe_mode = safe_mode;
+        self.workflow.set_safe_mode(safe_mode);
+    }
+
+    pub fn is_safe_mode(&self) -> bool {
+        self.safe_mode
+    }
+
     pub fn toggle_tests_mode(&mut self) -> bool {
         self.tests_mode_enabled = !self.tests_mode_enabled;
         self.workflow.set_tests_mode_enabled(self.tests_mode_enabled);
@@ -888,13 +3434,91 @@ impl App {
             || self.is_execution_busy()
     }
 
-    pub fn resume_picker_options(&self) -> &[ResumeSessionOption] {
-        match self.resume_picker.as_ref() {
-            Some(state) => &state.entries,
-            None => &[],
+    /// Resume picker entries matching the typed filter.
+    pub fn resume_picker_options(&self) -> Vec<&ResumeSessionOption> {
+        self.resume_picker
+            .as_ref()
+            .map(ResumePickerState::visible)
+            .unwrap_or_default()
+    }
+
+    pub fn resume_picker_filter(&self) -> &str {
+        self.resume_picker
+            .as_ref()
+            .map_or("", |state| state.filter.as_str())
+    }
+
+    /// The selected resume picker entry and its preview, if one was loaded.
+    pub fn resume_picker_preview(&self) -> Option<(&ResumeSessionOption, Option<&SessionPreview>)> {
+        let state = self.resume_picker.as_ref()?;
+        let option = *state.visible().get(state.selected)?;
+        Some((option, state.previews.get(&option.session_dir)))
+    }
+
+    pub fn resume_picker_filter_push(&mut self, ch: char) {
+        if let Some(state) = self.resume_picker.as_mut() {
+            state.filter.push(ch);
+            state.selected = 0;
+            state.confirm_workspace_switch = None;
+        }
+    }
+
+    pub fn resume_picker_shows_all_workspaces(&self) -> bool {
+        self.resume_picker
+            .as_ref()
+            .is_some_and(|state| state.all_workspaces)
+    }
+
+    /// Shows or hides other workspaces' sessions; false when there are none to show.
+    pub fn toggle_resume_picker_workspaces(&mut self) -> bool {
+        let Some(state) = self.resume_picker.as_mut() else {
+            return false;
+        };
+        if state.other_workspaces.is_empty() || state.entries.is_empty() {
+            return false;
+        }
+        state.all_workspaces = !state.all_workspaces;
+        state.selected = 0;
+        state.confirm_workspace_switch = None;
+        true
+    }
+
+    /// Drops the last filter character; false when the filter was already empty.
+    pub fn resume_picker_filter_pop(&mut self) -> bool {
+        let Some(state) = self.resume_picker.as_mut() else {
+            return false;
+        };
+        if state.filter.pop().is_none() {
+            return false;
+        }
+        state.selected = 0;
+        state.confirm_workspace_switch = None;
+        true
+    }
+
+    /// When a resume picker entry was last used, relative to when the picker opened; entries
+    /// without a recorded time show their meta.json creation date instead.
+    pub fn resume_option_when_label(&self, option: &ResumeSessionOption) -> String {
+        if option.last_used_epoch_secs > 0 {
+            return self.resume_picker_time_label(option.last_used_epoch_secs);
+        }
+        match option.created_at_label.as_deref() {
+            Some(label) => time_format::parse_rfc3339(label)
+                .map(|secs| time_format::format_date_time(self.locale, self.time_zone, secs))
+                .unwrap_or_else(|| label.to_string()),
+            None => i18n::text(self.locale, Text::UnknownDate).to_string(),
         }
     }
 
+    /// `epoch_secs` relative to when the resume picker opened.
+    pub fn resume_picker_time_label(&self, epoch_secs: u64) -> String {
+        let now = self
+            .resume_picker
+            .as_ref()
+            .map_or(epoch_secs, |state| state.opened_at_epoch_secs);
+        time_format::format_relative(self.locale, self.time_zone, epoch_secs, now)
+    }
+
     pub fn resume_picker_selected_index(&self) -> usize {
         self.resume_picker
             .as_ref()
@@ -907,24 +3531,57 @@ impl App {
             return;
         };
         state.selected = state.selected.saturating_sub(1);
+        state.confirm_workspace_switch = None;
     }
 
     pub fn resume_picker_move_down(&mut self) {
         let Some(state) = self.resume_picker.as_mut() else {
             return;
         };
-        if state.selected + 1 < state.entries.len() {
+        if state.selected + 1 < state.visible().len() {
             state.selected += 1;
         }
+        state.confirm_workspace_switch = None;
     }
 
+    /// Closes the picker with the selected entry; stays open while the filter matches nothing.
+    /// A session of another workspace is only returned on the second Enter, after a message
+    /// says the working directory will change.
     pub fn select_resume_session(&mut self) -> Option<ResumeSessionOption> {
-        let state = self.resume_picker.take()?;
-        state.entries.get(state.selected).cloned()
+        let state = self.resume_picker.as_ref()?;
+        let selection = state
+            .visible()
+            .get(state.selected)
+            .map(|option| (*option).clone())?;
+        if self.is_other_workspace(&selection)
+            && state.confirm_workspace_switch.as_deref() != Some(selection.session_dir.as_str())
+        {
+            self.push_agent_message(self.system_text(
+                Text::ResumeOtherWorkspace,
+                &[
+                    ("session", &selection.session_dir),
+                    ("workspace", &selection.workspace),
+                ],
+            ));
+            if let Some(state) = self.resume_picker.as_mut() {
+                state.confirm_workspace_switch = Some(selection.session_dir);
+            }
+            return None;
+        }
+        self.resume_picker = None;
+        if let Some(message) = self.new_session_message.take() {
+            self.restore_chat_input(&message);
+            self.push_agent_message(self.system_text(Text::MessageBackInInput, &[]));
+        }
+        Some(selection)
     }
 
     fn is_any_picker_open(&self) -> bool {
-        self.resume_picker.is_some() || self.backend_picker.is_some()
+        self.resume_picker.is_some()
+            || self.backend_picker.is_some()
+            || self.log_picker.is_some()
+            || self.task_menu.is_some()
+            || self.master_prompt_preview.is_some()
     }
 
     pub fn replace_rolling_context_entries(&mut self, entries: Vec<String>) {
@@ -935,8 +3592,270 @@ impl App {
     }
 
     pub fn reset_execution_for_session_switch(&mut self) {
+        self.pending_task_split = None;
+        self.pending_task_changes = None;
+        self.external_tasks_edit = None;
+        self.edit_conflict = None;
+        self.task_split_considered_titles.clear();
+        self.log_picker = None;
+        self.task_menu = None;
+        self.task_models.clear();
+        if self.is_worker_log_mode() {
+            self.close_worker_log();
+        }
         self.workflow.reset_execution_runtime();
+        self.workflow.clear_changed_files();
+        self.workflow.clear_task_usage();
+        self.workflow.set_review_changes_enabled(false);
+        self.set_speed_profile(SpeedProfile::default());
+        self.review_baseline = None;
+        self.active_job_started_at = None;
+        self.active_job_last_output_at = None;
+        self.active_job_model = None;
+        self.end_job_span(None, Some("session switched".to_string()));
         self.refresh_right_lines();
+        self.refresh_file_tree_if_visible();
+    }
+
+    pub fn workspace_root(&self) -> Option<&Path> {
+        self.workspace_root.as_deref()
+    }
+
+    /// Whether resuming `option` moves Bob out of the current workspace.
+    pub fn is_other_workspace(&self, option: &ResumeSessionOption) -> bool {
+        self.workspace_root.as_deref().is_some_and(|root| {
+            let workspace = option.workspace.trim();
+            !workspace.is_empty() && Path::new(workspace) != root
+        })
+    }
+
+    pub fn set_workspace_root(&mut self, root: PathBuf) {
+        self.workspace_root = Some(root.clone());
+        self.file_tree.set_root(root);
+        self.refresh_file_tree_if_visible();
+    }
+
+    pub fn is_file_tree_mode(&self) -> bool {
+        self.left_top_mode == LeftTopMode::FileTree
+    }
+
+    pub fn toggle_file_tree_mode(&mut self) -> bool {
+        self.left_top_mode = match self.left_top_mode {
+            LeftTopMode::WorkerOutput | LeftTopMode::Stats | LeftTopMode::Console => {
+                LeftTopMode::FileTree
+            }
+            LeftTopMode::FileTree => LeftTopMode::WorkerOutput,
+        };
+        if self.is_file_tree_mode() {
+            self.file_tree.refresh(self.workflow.changed_files());
+        } else {
+            self.left_top_scroll = self.max_scroll(Pane::LeftTop);
+        }
+        self.is_file_tree_mode()
+    }
+
+    pub fn left_top_title(&self) -> String {
+        match (self.left_top_mode, self.file_tree.viewer()) {
+            (LeftTopMode::WorkerOutput, _) => {
+                i18n::text(self.locale, Text::TabWorkerOutput).to_string()
+            }
+            (LeftTopMode::FileTree, None) => {
+                i18n::text(self.locale, Text::PaneFileTree).to_string()
+            }
+            (LeftTopMode::FileTree, Some(viewer)) => i18n::fill(
+                self.locale,
+                Text::PaneFileViewer,
+                &[("path", &viewer.relative_path)],
+            ),
+            (LeftTopMode::Stats, _) => i18n::text(self.locale, Text::PaneFailureStats).to_string(),
+            (LeftTopMode::Console, _) => i18n::text(self.locale, Text::PaneConsole).to_string(),
+        }
+    }
+
+    pub fn is_console_mode(&self) -> bool {
+        self.left_top_mode == LeftTopMode::Console
+    }
+
+    /// Shows or hides the console pane; returns whether it is now shown.
+    pub fn toggle_console_mode(&mut self) -> bool {
+        if self.is_console_mode() {
+            self.left_top_mode = LeftTopMode::WorkerOutput;
+            self.left_top_scroll = self.max_scroll(Pane::LeftTop);
+        } else {
+            self.left_top_mode = LeftTopMode::Console;
+            self.console_scroll = self.console.lines().len().saturating_sub(1) as u16;
+        }
+        self.mark_pane_damaged(Pane::LeftTop);
+        self.is_console_mode()
+    }
+
+    pub fn console_lines(&self) -> &[String] {
+        self.console.lines()
+    }
+
+    pub fn console_scroll(&self) -> u16 {
+        self.console_scroll
+    }
+
+    /// Runs a `/console` command with `cwd` as the shell's working directory and returns the
+    /// chat reply.
+    pub fn handle_console_command(&mut self, command: ConsoleCommand, cwd: &Path) -> String {
+        let last_command = self
+            .console
+            .last_run()
+            .map(|run| run.command.clone())
+            .unwrap_or_default();
+        match command {
+            ConsoleCommand::Toggle => {
+                if self.toggle_console_mode() {
+                    self.system_text(Text::ConsoleShown, &[])
+                } else {
+                    self.system_text(Text::ConsoleHidden, &[])
+                }
+            }
+            ConsoleCommand::Run(_) if self.safe_mode => {
+                self.system_text(Text::ConsoleSafeMode, &[])
+            }
+            ConsoleCommand::Run(command) => match self.console.run(&command, cwd) {
+                Ok(()) => {
+                    self.left_top_mode = LeftTopMode::Console;
+                    self.console_scroll = self.console.lines().len().saturating_sub(1) as u16;
+                    self.mark_pane_damaged(Pane::LeftTop);
+                    self.system_text(Text::ConsoleRunning, &[("command", &command)])
+                }
+                Err(err) => format!("System: {err}"),
+            },
+            ConsoleCommand::Stop => {
+                if self.console.is_running() && self.console.stop() {
+                    self.system_text(Text::ConsoleStopping, &[("last_command", &last_command)])
+                } else {
+                    self.system_text(Text::ConsoleNothingRunning, &[])
+                }
+            }
+            ConsoleCommand::Keep => match self.console.keep_entry() {
+                Some(entry) => {
+                    self.workflow.add_rolling_context(entry);
+                    self.refresh_right_lines();
+                    self.system_text(Text::ConsoleKept, &[("last_command", &last_command)])
+                }
+                None if self.console.is_running() => self.system_text(
+                    Text::ConsoleStillRunning,
+                    &[("last_command", &last_command)],
+                ),
+                None => self.system_text(Text::ConsoleNothingToKeep, &[]),
+            },
+            ConsoleCommand::Clear => {
+                self.console.clear();
+                self.console_scroll = 0;
+                self.mark_pane_damaged(Pane::LeftTop);
+                self.system_text(Text::ConsoleCleared, &[])
+            }
+            ConsoleCommand::Usage => self.system_text(Text::ConsoleUsage, &[]),
+        }
+    }
+
+    /// Moves new console output into the pane, following its tail, and offers to keep the
+    /// output of a command that just finished. Returns true when anything changed.
+    pub fn poll_console(&mut self) -> bool {
+        let was_running = self.console.is_running();
+        if !self.console.poll() {
+            return false;
+        }
+        self.console_scroll = self.console.lines().len().saturating_sub(1) as u16;
+        self.mark_pane_damaged(Pane::LeftTop);
+        if was_running
+            && !self.console.is_running()
+            && let Some(run) = self.console.last_run()
+        {
+            let status = match run.code {
+                Some(code) => format!("exit code {code}"),
+                None => "terminated".to_string(),
+            };
+            let message = self.system_text(
+                Text::ConsoleFinished,
+                &[("command", &run.command), ("status", &status)],
+            );
+            self.push_agent_message(message);
+        }
+        true
+    }
+
+    pub fn is_stats_mode(&self) -> bool {
+        self.left_top_mode == LeftTopMode::Stats
+    }
+
+    pub fn show_stats(&mut self, lines: Vec<String>) {
+        self.stats_lines = lines;
+        self.stats_scroll = 0;
+        self.left_top_mode = LeftTopMode::Stats;
+    }
+
+    pub fn hide_stats(&mut self) {
+        if self.is_stats_mode() {
+            self.left_top_mode = LeftTopMode::WorkerOutput;
+            self.left_top_scroll = self.max_scroll(Pane::LeftTop);
+        }
+    }
+
+    pub fn stats_lines(&self) -> &[String] {
+        &self.stats_lines
+    }
+
+    pub fn stats_scroll(&self) -> u16 {
+        self.stats_scroll
+    }
+
+    pub fn file_tree_lines(&self) -> Vec<String> {
+        self.file_tree.render_lines()
+    }
+
+    pub fn file_tree_selected_index(&self) -> usize {
+        self.file_tree.selected_index()
+    }
+
+    pub fn is_file_viewer_open(&self) -> bool {
+        self.file_tree.viewer().is_some()
+    }
+
+    pub fn file_viewer_scroll(&self) -> u16 {
+        self.file_tree.viewer().map(|viewer| viewer.scroll).unwrap_or(0)
+    }
+
+    pub fn file_viewer_highlight(&self) -> Option<(usize, usize)> {
+        self.file_tree.viewer().and_then(|viewer| viewer.highlight)
+    }
+
+    /// Opens a cited file in the file viewer at the cited lines. Returns false when the
+    /// citation names no file in the workspace.
+    pub fn open_citation(&mut self, citation: &AuditCitation) -> bool {
+        let mut citation = citation.clone();
+        let Some(root) = self.workspace_root.as_ref() else {
+            return false;
+        };
+        audit_citations::verify(std::slice::from_mut(&mut citation), root);
+        if !citation.exists {
+            return false;
+        }
+        self.left_top_mode = LeftTopMode::FileTree;
+        self.file_tree.refresh(self.workflow.changed_files());
+        self.file_tree
+            .open_file_at(&citation.path, citation.start_line, citation.end_line);
+        self.active_pane = Pane::LeftTop;
+        true
+    }
+
+    pub fn file_tree_activate(&mut self) {
+        self.file_tree.activate(self.workflow.changed_files());
+    }
+
+    pub fn file_tree_back(&mut self) -> bool {
+        self.file_tree.back(self.workflow.changed_files())
+    }
+
+    fn refresh_file_tree_if_visible(&mut self) {
+        if self.is_file_tree_mode() {
+            self.file_tree.refresh(self.workflow.changed_files());
+        }
     }
 
     pub fn is_planner_mode(&self) -> bool {
@@ -944,11 +3863,15 @@ impl App {
     }
 
     pub fn right_pane_title(&self) -> &'static str {
-        if self.is_planner_mode() {
-            "Planner Markdown"
-        } else {
-            "Task List"
-        }
+        i18n::text(
+            self.locale,
+            match self.right_pane_mode {
+                RightPaneMode::PlannerMarkdown => Text::PanePlannerMarkdown,
+                RightPaneMode::TaskList => Text::PaneTaskList,
+                RightPaneMode::WorkerLog => Text::PaneWorkerLog,
+                RightPaneMode::TaskSummaries => Text::PaneTaskSummaries,
+            },
+        )
     }
 
     pub fn set_right_pane_mode(&mut self, mode: RightPaneMode) {
@@ -972,6 +3895,102 @@ impl App {
         &self.planner_markdown
     }
 
+    /// Replaces the planner with planner.md as read from disk.
+    pub fn load_planner_markdown(&mut self, markdown: String) {
+        self.planner_saved_markdown = markdown.clone();
+        self.set_planner_markdown(markdown);
+    }
+
+    /// Records that planner.md on disk now matches the planner.
+    pub fn mark_planner_markdown_saved(&mut self) {
+        self.planner_saved_markdown = self.planner_markdown.clone();
+    }
+
+    pub fn planner_saved_markdown(&self) -> &str {
+        &self.planner_saved_markdown
+    }
+
+    /// Takes in planner.md as the master left it. Unsaved planner edits are kept when the file
+    /// did not change underneath them; when both changed, an edit conflict is opened instead of
+    /// overwriting either side. Returns whether a conflict was opened.
+    pub fn reconcile_planner_markdown(&mut self, disk: String) -> bool {
+        if disk == self.planner_markdown {
+            self.planner_saved_markdown = disk;
+            return false;
+        }
+        if self.planner_markdown == self.planner_saved_markdown {
+            self.load_planner_markdown(disk);
+            return false;
+        }
+        if disk == self.planner_saved_markdown {
+            return false;
+        }
+        // planner.md now holds the master's version; the planner keeps the user's until
+        // `/conflict`.
+        let base = std::mem::replace(&mut self.planner_saved_markdown, disk.clone());
+        self.open_edit_conflict(EditConflict {
+            file: ConflictFile::Planner,
+            base,
+            mine: self.planner_markdown.clone(),
+            theirs: disk,
+        });
+        true
+    }
+
+    /// Holds `conflict` for `/conflict` and tells the user about it.
+    pub fn open_edit_conflict(&mut self, conflict: EditConflict) {
+        self.push_agent_message(conflict.notice(self.locale));
+        self.edit_conflict = Some(conflict);
+    }
+
+    pub fn edit_conflict(&self) -> Option<&EditConflict> {
+        self.edit_conflict.as_ref()
+    }
+
+    pub fn take_edit_conflict(&mut self) -> Option<EditConflict> {
+        self.edit_conflict.take()
+    }
+
+    /// Holds a session another instance has open and offers `/lock takeover` or `/lock observe`.
+    pub fn offer_locked_session(&mut self, selection: ResumeSessionOption, owner: LockOwner) {
+        let since = time_format::format_date_time(
+            self.locale,
+            self.time_zone,
+            owner.acquired_at_epoch_secs,
+        );
+        let open_elsewhere = match owner.is_running() {
+            Some(_) => Text::SessionLockedElsewhere,
+            None => Text::SessionLockedOnOtherHost,
+        };
+        self.push_agent_message(self.system_text(
+            open_elsewhere,
+            &[
+                ("session", &selection.session_dir),
+                ("owner", &owner.describe()),
+                ("since", &since),
+            ],
+        ));
+        self.locked_session = Some(LockedSession { selection, owner });
+    }
+
+    pub fn locked_session(&self) -> Option<&LockedSession> {
+        self.locked_session.as_ref()
+    }
+
+    pub fn take_locked_session(&mut self) -> Option<LockedSession> {
+        self.locked_session.take()
+    }
+
+    /// Ends the interactive loop so the session in `session_dir` opens in observer mode.
+    pub fn observe_on_exit(&mut self, session_dir: PathBuf, notice: String) {
+        self.observe_on_exit = Some((session_dir, notice));
+        self.quit();
+    }
+
+    pub fn take_observe_on_exit(&mut self) -> Option<(PathBuf, String)> {
+        self.observe_on_exit.take()
+    }
+
     pub fn planner_input_char(&mut self, c: char) {
         let byte_idx = char_to_byte_idx(&self.planner_markdown, self.planner_cursor);
         self.planner_markdown.insert(byte_idx, c);
@@ -1104,23 +4123,58 @@ impl App {
     }
 
     fn planner_block_lines(&self, width: u16) -> Vec<String> {
-        let width = width.max(1);
-        let mut out = Vec::new();
-        for line in self.planner_raw_lines() {
-            let wrapped = wrap_word_with_positions(&line, width).rendered;
-            out.extend(wrapped.lines().map(|part| part.to_string()));
+        wrap_block_lines(&self.planner_raw_lines(), width)
+    }
+
+    /// Done top-level tasks in task-list order, each followed by its summary.
+    fn task_summary_raw_lines(&self) -> Vec<String> {
+        let done = self
+            .workflow
+            .planner_tasks_for_file()
+            .into_iter()
+            .filter(|task| {
+                task.parent_id.is_none()
+                    && task.kind == PlannerTaskKindFile::Task
+                    && task.status == PlannerTaskStatusFile::Done
+            })
+            .collect::<Vec<_>>();
+        if done.is_empty() {
+            return vec![
+                "No top-level task is done yet. Each one gets a summary here when it finishes."
+                    .to_string(),
+            ];
         }
-        if out.is_empty() {
-            out.push(String::new());
+        let mut lines = Vec::new();
+        for task in done {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("# {}", task.title));
+            let summary = self
+                .task_summaries
+                .iter()
+                .find(|entry| entry.top_task_id == task.id)
+                .map_or("(no summary yet)", |entry| entry.summary.as_str());
+            lines.push(summary.to_string());
         }
-        out
+        lines
+    }
+
+    fn worker_log_raw_lines(&self) -> Vec<String> {
+        let Some(view) = self.worker_log_view.as_ref() else {
+            return vec!["No worker log is open. Use /logs to pick one.".to_string()];
+        };
+        let mut lines = vec![format!("# {}", view.label), String::new()];
+        lines.extend(view.lines.iter().cloned());
+        lines
     }
 
     fn refresh_right_lines(&mut self) {
-        self.right_lines = if self.right_pane_mode == RightPaneMode::PlannerMarkdown {
-            self.planner_raw_lines()
-        } else {
-            self.workflow.right_pane_lines()
+        self.right_lines = match self.right_pane_mode {
+            RightPaneMode::PlannerMarkdown => self.planner_raw_lines(),
+            RightPaneMode::WorkerLog => self.worker_log_raw_lines(),
+            RightPaneMode::TaskSummaries => self.task_summary_raw_lines(),
+            RightPaneMode::TaskList => self.workflow.right_pane_lines(),
         };
         let max = self.max_scroll(Pane::Right);
         self.right_scroll = self.right_scroll.min(max);
@@ -1133,12 +4187,29 @@ impl App {
             self.left_top_lines.drain(0..overflow);
         }
         self.left_top_generation = self.left_top_generation.saturating_add(1);
+        self.mark_pane_damaged(Pane::LeftTop);
         self.left_top_scroll = self.max_scroll(Pane::LeftTop);
     }
 
+    /// Mirrors system lines into the System Log; the Chat view gets the rest, plus system
+    /// lines that are important or reply to the user's input.
     fn push_chat_message_line(&mut self, message: String) {
-        self.chat_messages.push(message);
-        self.chat_messages_generation = self.chat_messages_generation.saturating_add(1);
+        self.mark_pane_damaged(Pane::LeftBottom);
+        if !system_log::is_system_line(&message) {
+            self.conversation.push(message.clone());
+        } else {
+            if self.command_reply_open || system_log::is_important_system_line(&message) {
+                self.conversation.push(message.clone());
+            }
+            self.system_log.push(message.clone());
+        }
+        for top_task_id in self.workflow.top_tasks_mentioned(&message) {
+            self.task_threads
+                .entry(top_task_id)
+                .or_default()
+                .push(message.clone());
+        }
+        self.chat_transcript.push(message);
     }
 
     fn invalidate_chat_input_cache(&mut self) {
@@ -1146,55 +4217,103 @@ impl App {
         *self.chat_input_wrap_cache.borrow_mut() = None;
     }
 
-    fn scroll_mut(&mut self, pane: Pane) -> &mut u16 {
-        match pane {
-            Pane::LeftTop => &mut self.left_top_scroll,
-            Pane::LeftBottom => &mut self.chat_scroll,
-            Pane::Right => &mut self.right_scroll,
-        }
-    }
-
     fn max_scroll(&self, pane: Pane) -> u16 {
         let len = match pane {
             Pane::LeftTop => self.left_top_lines.len(),
-            Pane::LeftBottom => self.chat_messages.len(),
+            Pane::LeftBottom => self.displayed_chat_transcript().len(),
             Pane::Right => self.right_lines.len(),
         };
         len.saturating_sub(1) as u16
     }
 }
 
-fn char_to_byte_idx(s: &str, char_idx: usize) -> usize {
-    s.char_indices()
-        .nth(char_idx)
-        .map(|(byte_idx, _)| byte_idx)
-        .unwrap_or_else(|| s.len())
+fn worker_role_label(role: WorkerRole) -> &'static str {
+    match role {
+        WorkerRole::Implementor => "Impl",
+        WorkerRole::Auditor => "Audit",
+        WorkerRole::TestWriter => "Tests",
+        WorkerRole::TestRunner => "TestRun",
+        WorkerRole::FinalAudit => "FinalAudit",
+        WorkerRole::Plugin => "Plugin",
+    }
+}
+
+fn attach_docs_prompt(tasks_file: &str, scope: &str) -> String {
+    format!(
+        "You are a docs-research sub-agent.\n\
+         Goal: update the planner task file with implementation documentation links.\n\
+         Read and edit this JSON file directly: {tasks_file}\n\
+         Requirements:\n\
+         - {scope}\n\
+         - Each docs item must include: title, url, summary.\n\
+         - Use the latest authoritative online docs relevant to implementing that task.\n\
+         - Keep existing task structure/order/status intact; only add/update docs.\n\
+         - Leave test_runner tasks with docs as-is (do not add docs there).\n\
+         - Save tasks.json, then output a short confirmation summary."
+    )
 }
 
-fn nearest_index_for_line_col(positions: &[(u16, u16)], target_line: u16, goal_col: u16) -> usize {
-    let mut best: Option<(usize, u16)> = None;
-    let mut fallback: Option<usize> = None;
+/// `command` alone, or followed by one task id written as `<prefix><id>`.
+fn parse_optional_task_argument(
+    message: &str,
+    command: &str,
+    prefix: &str,
+) -> Option<Option<String>> {
+    let trimmed = message.trim();
+    let (head, rest) = trimmed
+        .split_once(char::is_whitespace)
+        .unwrap_or((trimmed, ""));
+    if !head.eq_ignore_ascii_case(command) {
+        return None;
+    }
+    let rest = rest.trim();
+    if rest.is_empty() {
+        return Some(None);
+    }
+    if !rest.get(..prefix.len())?.eq_ignore_ascii_case(prefix) {
+        return None;
+    }
+    let task_id = rest[prefix.len()..].trim();
+    (!task_id.is_empty() && !task_id.contains(char::is_whitespace))
+        .then(|| Some(task_id.to_string()))
+}
 
-    for (idx, (line, col)) in positions.iter().copied().enumerate() {
-        if line != target_line {
-            continue;
-        }
-        if fallback.is_none() {
-            fallback = Some(idx);
+/// Task numbers such as `3,5-7`, without duplicates, in the order given.
+fn parse_task_positions(list: &str) -> Option<Vec<usize>> {
+    /// Keeps a typo like `1-9999` from building a huge list.
+    const MAX_RANGE: usize = 1000;
+    let mut positions = Vec::new();
+    for item in list.split(',') {
+        let (start, end) = match item.split_once('-') {
+            Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+            None => {
+                let position = item.parse().ok()?;
+                (position, position)
+            }
+        };
+        if start == 0 || end < start || end - start >= MAX_RANGE {
+            return None;
         }
-        if col <= goal_col {
-            best = match best {
-                Some((_, best_col)) if best_col >= col => best,
-                _ => Some((idx, col)),
-            };
+        for position in start..=end {
+            if !positions.contains(&position) {
+                positions.push(position);
+            }
         }
     }
+    Some(positions)
+}
 
-    if let Some((idx, _)) = best {
-        idx
-    } else {
-        fallback.unwrap_or(positions.len().saturating_sub(1))
+fn wrap_block_lines(lines: &[String], width: u16) -> Vec<String> {
+    let width = width.max(1);
+    let mut out = Vec::new();
+    for line in lines {
+        let wrapped = wrap_word_with_positions(line, width).rendered;
+        out.extend(wrapped.lines().map(|part| part.to_string()));
+    }
+    if out.is_empty() {
+        out.push(String::new());
     }
+    out
 }
 
 fn command_query(input: &str) -> Option<&str> {
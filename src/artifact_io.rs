@@ -1,19 +1,47 @@
 use std::env;
 use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufReader, BufWriter, Read, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 #[cfg(test)]
 use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 #[cfg(unix)]
 use std::{fs::Permissions, os::unix::fs::PermissionsExt};
 
+use serde::Serialize;
+
 use crate::default_config::DEFAULT_CONFIG_TOML;
 
 const APP_DIR_NAME: &str = ".agentbob";
 const LEGACY_APP_DIR_NAME: &str = ".bob";
 const OLDEST_LEGACY_APP_DIR_NAME: &str = ".metaagent";
 const CONFIG_FILE_NAME: &str = "config.toml";
+const PROJECT_CONFIG_DIR_NAME: &str = ".metaagent";
+
+/// Where an effective config value came from, lowest precedence first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    Default,
+    Global,
+    Project,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectiveConfigEntry {
+    /// Dotted path of a leaf key, e.g. `workflow.final_audit`.
+    pub key: String,
+    pub value: toml::Value,
+    pub source: ConfigSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectiveConfig {
+    pub global_file: PathBuf,
+    /// Set only when the project has a `.metaagent/config.toml`.
+    pub project_file: Option<PathBuf>,
+    pub entries: Vec<EffectiveConfigEntry>,
+}
 
 pub fn read_text_file(path: &Path) -> io::Result<String> {
     let file = File::open(path)?;
@@ -23,6 +51,14 @@ pub fn read_text_file(path: &Path) -> io::Result<String> {
     Ok(text)
 }
 
+/// Whether `path` names a directory inside the workspace: relative and without `..`.
+pub fn is_workspace_subdir(path: &str) -> bool {
+    !path.trim().is_empty()
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
 pub fn write_text_file(path: &Path, text: &str) -> io::Result<()> {
     let file = File::create(path)?;
     let mut writer = BufWriter::new(file);
@@ -113,9 +149,104 @@ pub fn ensure_default_metaagent_config() -> io::Result<PathBuf> {
     Ok(config_file)
 }
 
+/// The global config overlaid with the current project's `.metaagent/config.toml`, if any.
 pub fn load_merged_metaagent_config_text() -> io::Result<String> {
+    load_merged_metaagent_config_text_in(&env::current_dir()?)
+}
+
+fn load_merged_metaagent_config_text_in(cwd: &Path) -> io::Result<String> {
     let config_file = ensure_default_metaagent_config()?;
-    read_text_file(&config_file)
+    let global_text = read_text_file(&config_file)?;
+    let Some((_, project_text)) = read_project_config(cwd)? else {
+        return Ok(global_text);
+    };
+    let mut merged = parse_toml_table(&global_text)?;
+    merge_toml_tables(&mut merged, parse_toml_table(&project_text)?);
+    toml::to_string_pretty(&merged).map_err(io::Error::other)
+}
+
+/// The project-local config overlay for `cwd`. The home directory never has one, because
+/// `~/.metaagent/config.toml` is a legacy global config location.
+pub fn project_config_file_path(cwd: &Path) -> Option<PathBuf> {
+    if home_dir().is_ok_and(|home| home == cwd) {
+        return None;
+    }
+    Some(cwd.join(PROJECT_CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+fn read_project_config(cwd: &Path) -> io::Result<Option<(PathBuf, String)>> {
+    let Some(path) = project_config_file_path(cwd) else {
+        return Ok(None);
+    };
+    match read_text_file(&path) {
+        Ok(text) => Ok(Some((path, text))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Every leaf key of the config, with the layer that set it. The project overlay for `cwd` is
+/// included when given; a global value equal to the embedded default is reported as `default`.
+pub fn effective_metaagent_config(cwd: Option<&Path>) -> io::Result<EffectiveConfig> {
+    let global_file = ensure_default_metaagent_config()?;
+    let defaults = flatten_toml(&default_config_value()?);
+    let project = match cwd {
+        Some(cwd) => read_project_config(cwd)?,
+        None => None,
+    };
+    let project_keys = match &project {
+        Some((_, text)) => flatten_toml(&parse_toml_table(text)?),
+        None => Vec::new(),
+    };
+    let merged = parse_toml_table(&match cwd {
+        Some(cwd) => load_merged_metaagent_config_text_in(cwd)?,
+        None => read_text_file(&global_file)?,
+    })?;
+    let entries = flatten_toml(&merged)
+        .into_iter()
+        .map(|(key, value)| {
+            let source = if project_keys
+                .iter()
+                .any(|(project_key, _)| *project_key == key)
+            {
+                ConfigSource::Project
+            } else if defaults
+                .iter()
+                .any(|(default_key, default)| *default_key == key && *default == value)
+            {
+                ConfigSource::Default
+            } else {
+                ConfigSource::Global
+            };
+            EffectiveConfigEntry { key, value, source }
+        })
+        .collect();
+    Ok(EffectiveConfig {
+        global_file,
+        project_file: project.map(|(path, _)| path),
+        entries,
+    })
+}
+
+fn flatten_toml(value: &toml::Value) -> Vec<(String, toml::Value)> {
+    fn walk(prefix: &str, value: &toml::Value, out: &mut Vec<(String, toml::Value)>) {
+        match value {
+            toml::Value::Table(table) => {
+                for (key, item) in table {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    walk(&path, item, out);
+                }
+            }
+            leaf => out.push((prefix.to_string(), leaf.clone())),
+        }
+    }
+    let mut out = Vec::new();
+    walk("", value, &mut out);
+    out
 }
 
 #[cfg(test)]
@@ -124,13 +255,18 @@ pub(crate) fn home_env_test_lock() -> &'static Mutex<()> {
     LOCK.get_or_init(|| Mutex::new(()))
 }
 
-fn merge_default_config_with_user_overrides(override_text: Option<&str>) -> io::Result<String> {
-    let mut merged = parse_toml_table(DEFAULT_CONFIG_TOML)?;
+fn default_config_value() -> io::Result<toml::Value> {
+    let mut defaults = parse_toml_table(DEFAULT_CONFIG_TOML)?;
     let config_dir = runtime_storage_dir()?;
     apply_default_storage_root_dir(
-        &mut merged,
+        &mut defaults,
         default_storage_root_for_config_dir(&config_dir),
     );
+    Ok(defaults)
+}
+
+fn merge_default_config_with_user_overrides(override_text: Option<&str>) -> io::Result<String> {
+    let mut merged = default_config_value()?;
     let override_value = parse_toml_table(override_text.unwrap_or_default())?;
     merge_toml_tables(&mut merged, override_value);
     toml::to_string_pretty(&merged).map_err(io::Error::other)
@@ -198,7 +334,13 @@ fn write_legacy_compat_config_if_missing(active_config_file: &Path, merged_text:
     }
 }
 
-fn write_text_file_atomic(path: &Path, text: &str) -> io::Result<()> {
+pub fn write_text_file_atomic(path: &Path, text: &str) -> io::Result<()> {
+    write_file_atomic(path, text.as_bytes())
+}
+
+/// Writes `bytes` to a temporary file next to `path` and renames it over `path`, so readers
+/// see either the old or the new contents.
+pub fn write_file_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
     let parent = path.parent().ok_or_else(|| {
         io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -221,7 +363,7 @@ fn write_text_file_atomic(path: &Path, text: &str) -> io::Result<()> {
             Ok(file) => {
                 ensure_owner_only_permissions(&tmp)?;
                 let mut writer = BufWriter::new(file);
-                writer.write_all(text.as_bytes())?;
+                writer.write_all(bytes)?;
                 writer.flush()?;
                 writer.get_ref().sync_all()?;
                 if let Err(err) = fs::rename(&tmp, path) {
@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use crate::artifact_io::is_workspace_subdir;
+
+/// A `path:line` or `path:start-end` reference an auditor gave for one of its findings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditCitation {
+    /// The citation as the auditor wrote it, passed on verbatim to the implementor.
+    pub text: String,
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Whether `path` names a file inside the workspace.
+    pub exists: bool,
+}
+
+impl AuditCitation {
+    /// Chat and pane line for this citation, flagging ones that point at no file.
+    pub fn describe(&self) -> String {
+        if self.exists {
+            format!("- {}", self.text)
+        } else {
+            format!("- {} (file not found in the workspace)", self.text)
+        }
+    }
+}
+
+/// Every distinct citation in `lines`, in order of first appearance. Nothing is checked
+/// against the workspace yet; see [`verify`].
+pub fn parse(lines: &[String]) -> Vec<AuditCitation> {
+    let mut citations: Vec<AuditCitation> = Vec::new();
+    for citation in lines.iter().flat_map(|line| parse_line(line)) {
+        if !citations.iter().any(|seen| seen.text == citation.text) {
+            citations.push(citation);
+        }
+    }
+    citations
+}
+
+/// Citations in one line of text, accepting `path:12`, `path:12-20`, `path:L12-L20`, and
+/// `path#L12-L20`, optionally wrapped in backticks, brackets, or quotes.
+pub fn parse_line(line: &str) -> Vec<AuditCitation> {
+    line.split_whitespace()
+        .filter_map(|token| {
+            let token = token
+                .trim_start_matches(['`', '"', '\'', '(', '['])
+                .trim_end_matches(['`', '"', '\'', ')', ']', '.', ',', ';', ':']);
+            parse_token(token)
+        })
+        .collect()
+}
+
+fn parse_token(token: &str) -> Option<AuditCitation> {
+    let (path, lines) = token.rsplit_once(['#', ':'])?;
+    let path = path.trim_start_matches("./");
+    if path.is_empty() || path.contains("://") || !(path.contains('.') || path.contains('/')) {
+        return None;
+    }
+    let line_number = |value: &str| {
+        value
+            .strip_prefix('L')
+            .unwrap_or(value)
+            .parse::<usize>()
+            .ok()
+            .filter(|line| *line > 0)
+    };
+    let (start_line, end_line) = match lines.split_once('-') {
+        Some((start, end)) => (line_number(start)?, line_number(end)?),
+        None => {
+            let line = line_number(lines)?;
+            (line, line)
+        }
+    };
+    Some(AuditCitation {
+        text: token.to_string(),
+        path: path.to_string(),
+        start_line: start_line.min(end_line),
+        end_line: start_line.max(end_line),
+        exists: false,
+    })
+}
+
+/// Marks which citations name a file under `root`. Absolute paths inside `root` are
+/// rewritten relative to it so they can be opened from the file viewer.
+pub fn verify(citations: &mut [AuditCitation], root: &Path) {
+    for citation in citations {
+        if let Ok(relative) = Path::new(&citation.path).strip_prefix(root)
+            && let Some(relative) = relative.to_str()
+        {
+            citation.path = relative.to_string();
+        }
+        citation.exists =
+            is_workspace_subdir(&citation.path) && root.join(&citation.path).is_file();
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/audit_citations_tests.rs"]
+mod tests;
@@ -0,0 +1,295 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// `prev_hash` of the first entry.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum AuditEventFile {
+    /// A prompt handed to an agent CLI; `model` is `None` when the CLI picks its default.
+    PromptSent {
+        program: String,
+        model: Option<String>,
+        prompt: String,
+    },
+    /// Files an implementor pass reported or left changed in the worktree.
+    FilesChanged {
+        task_id: String,
+        pass: u8,
+        files: Vec<String>,
+    },
+    /// A user verdict: `subject` is `review`, `task_split`, or `task_changes`.
+    ApprovalDecision {
+        subject: String,
+        decision: String,
+        detail: String,
+    },
+}
+
+/// One line of `audit-trail.jsonl`. `hash` is the SHA-256 of `prev_hash` and the entry's
+/// other fields, so editing, dropping, or reordering a line breaks every hash after it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditTrailFileEntry {
+    pub seq: u64,
+    pub at_epoch_ms: u64,
+    pub event: AuditEventFile,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// Result of re-computing a trail's hash chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditTrailVerification {
+    /// Hash of the last entry, or [`GENESIS_HASH`] for an empty trail.
+    pub head_hash: String,
+    /// `seq` of the first entry whose hash or link does not match; `None` when intact.
+    pub broken_at: Option<u64>,
+}
+
+#[derive(Debug)]
+struct OpenTrail {
+    path: PathBuf,
+    file: File,
+    next_seq: u64,
+    last_hash: String,
+}
+
+#[derive(Debug, Default)]
+struct ActiveTrail {
+    path: Option<PathBuf>,
+    open: Option<OpenTrail>,
+    failed_path: Option<PathBuf>,
+    error: Option<String>,
+}
+
+/// Points recording at the active session's trail, or stops recording with `None`.
+pub fn set_session(path: Option<PathBuf>) {
+    let mut active = active_trail().lock().unwrap_or_else(|err| err.into_inner());
+    active.path = path;
+}
+
+/// Appends `event` to the active session's trail. Nothing is recorded without an active
+/// session; after a write failure the same trail is not retried, and the error is kept for
+/// [`take_error`].
+pub fn record(event: AuditEventFile) {
+    let mut active = active_trail().lock().unwrap_or_else(|err| err.into_inner());
+    let Some(path) = active.path.clone() else {
+        return;
+    };
+    if active.failed_path.as_ref() == Some(&path) {
+        return;
+    }
+    if let Err(err) = append_to(&mut active.open, &path, event) {
+        active.open = None;
+        active.failed_path = Some(path);
+        active.error = Some(err.to_string());
+    }
+}
+
+/// The first write failure since the last call, so it is reported once.
+pub fn take_error() -> Option<String> {
+    active_trail()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .error
+        .take()
+}
+
+fn active_trail() -> &'static Mutex<ActiveTrail> {
+    static ACTIVE: OnceLock<Mutex<ActiveTrail>> = OnceLock::new();
+    ACTIVE.get_or_init(|| Mutex::new(ActiveTrail::default()))
+}
+
+fn append_to(open: &mut Option<OpenTrail>, path: &Path, event: AuditEventFile) -> io::Result<()> {
+    if open.as_ref().is_none_or(|trail| trail.path != path) {
+        *open = None;
+        let last = read_entries(path)?.pop();
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        *open = Some(OpenTrail {
+            path: path.to_path_buf(),
+            file,
+            next_seq: last.as_ref().map_or(1, |entry| entry.seq + 1),
+            last_hash: last.map_or_else(|| GENESIS_HASH.to_string(), |entry| entry.hash),
+        });
+    }
+    let Some(trail) = open.as_mut() else {
+        return Ok(());
+    };
+    let at_epoch_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    let entry = chain_entry(trail.next_seq, at_epoch_ms, event, &trail.last_hash)?;
+    let line = serde_json::to_string(&entry).map_err(io::Error::other)?;
+    writeln!(trail.file, "{line}")?;
+    trail.next_seq += 1;
+    trail.last_hash = entry.hash;
+    Ok(())
+}
+
+/// Builds the entry that follows `prev_hash`.
+pub fn chain_entry(
+    seq: u64,
+    at_epoch_ms: u64,
+    event: AuditEventFile,
+    prev_hash: &str,
+) -> io::Result<AuditTrailFileEntry> {
+    let hash = entry_hash(seq, at_epoch_ms, &event, prev_hash)?;
+    Ok(AuditTrailFileEntry {
+        seq,
+        at_epoch_ms,
+        event,
+        prev_hash: prev_hash.to_string(),
+        hash,
+    })
+}
+
+fn entry_hash(
+    seq: u64,
+    at_epoch_ms: u64,
+    event: &AuditEventFile,
+    prev_hash: &str,
+) -> io::Result<String> {
+    let event = serde_json::to_string(event).map_err(io::Error::other)?;
+    Ok(sha256_hex(
+        format!("{prev_hash}\n{seq}\n{at_epoch_ms}\n{event}").as_bytes(),
+    ))
+}
+
+/// Reads every entry, oldest first. A missing trail has no entries; a line that does not
+/// parse is an error, since a compliance export must not quietly drop records.
+pub fn read_entries(path: &Path) -> io::Result<Vec<AuditTrailFileEntry>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut entries = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str::<AuditTrailFileEntry>(&line).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {} is not an audit entry: {err}", index + 1),
+            )
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Re-computes the hash chain of `entries` from [`GENESIS_HASH`].
+pub fn verify(entries: &[AuditTrailFileEntry]) -> AuditTrailVerification {
+    let mut expected_prev = GENESIS_HASH.to_string();
+    for (index, entry) in entries.iter().enumerate() {
+        let intact = entry.seq == index as u64 + 1
+            && entry.prev_hash == expected_prev
+            && entry_hash(entry.seq, entry.at_epoch_ms, &entry.event, &entry.prev_hash)
+                .is_ok_and(|hash| hash == entry.hash);
+        if !intact {
+            return AuditTrailVerification {
+                head_hash: entries
+                    .last()
+                    .map_or(expected_prev, |last| last.hash.clone()),
+                broken_at: Some(entry.seq),
+            };
+        }
+        expected_prev = entry.hash.clone();
+    }
+    AuditTrailVerification {
+        head_hash: expected_prev,
+        broken_at: None,
+    }
+}
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 of `data` as lowercase hex.
+pub fn sha256_hex(data: &[u8]) -> String {
+    sha256(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// SHA-256 digest of `data`.
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_be_bytes());
+    for block in message.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (index, word) in block.chunks_exact(4).enumerate() {
+            w[index] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for index in 16..64 {
+            let s0 = w[index - 15].rotate_right(7)
+                ^ w[index - 15].rotate_right(18)
+                ^ (w[index - 15] >> 3);
+            let s1 = w[index - 2].rotate_right(17)
+                ^ w[index - 2].rotate_right(19)
+                ^ (w[index - 2] >> 10);
+            w[index] = w[index - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[index - 7])
+                .wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (k, word) in SHA256_K.iter().zip(w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let temp1 = h
+                .wrapping_add(s1)
+                .wrapping_add(choice)
+                .wrapping_add(*k)
+                .wrapping_add(word);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(majority);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+    let mut digest = [0u8; 32];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/audit_trail_tests.rs"]
+mod tests;
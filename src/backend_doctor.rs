@@ -0,0 +1,462 @@
+use std::io::{self, Read};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::{BackendKind, one_shot_prompt_args};
+use crate::agent_models::{CodexAgentModelRouting, ConfiguredBackend};
+
+const METADATA_PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+const PROMPT_PROBE_TIMEOUT: Duration = Duration::from_secs(120);
+const PROBE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const PROBE_PROMPT: &str = "Reply with the single word OK.";
+
+const MODEL_UNAVAILABLE_MARKERS: [&str; 9] = [
+    "not found",
+    "does not exist",
+    "not available",
+    "not supported",
+    "unsupported",
+    "do not have access",
+    "does not have access",
+    "invalid model",
+    "unknown model",
+];
+const UNAUTHENTICATED_MARKERS: [&str; 7] = [
+    "not logged in",
+    "please log in",
+    "login required",
+    "unauthorized",
+    "401",
+    "api key",
+    "authentication",
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DoctorOptions {
+    /// Skips the probe prompts that authenticate and check model access; they spend tokens.
+    pub quick: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackendDoctorReport {
+    pub backends: Vec<BackendDiagnosis>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendDiagnosis {
+    pub backend: String,
+    pub selected: bool,
+    pub program: String,
+    pub installed: bool,
+    pub version: Option<String>,
+    pub supports_json_mode: Option<bool>,
+    pub supports_resume: Option<bool>,
+    pub authenticated: Option<bool>,
+    pub models: Vec<ModelDiagnosis>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelDiagnosis {
+    pub model: String,
+    pub used_by: Vec<String>,
+    pub available: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ProbeOutput {
+    pub success: bool,
+    pub timed_out: bool,
+    /// Stdout followed by stderr.
+    pub text: String,
+}
+
+/// Runs `program args` with a timeout; swapped out in tests.
+pub(crate) type ProbeRunner = dyn Fn(&str, &[String], Duration) -> io::Result<ProbeOutput>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProbeFailure {
+    ModelUnavailable,
+    Unauthenticated,
+    Other,
+}
+
+/// Runs every probe against the real backend CLIs.
+pub fn diagnose_backends(
+    backends: &[ConfiguredBackend],
+    routing: &CodexAgentModelRouting,
+    options: DoctorOptions,
+) -> BackendDoctorReport {
+    BackendDoctorReport {
+        backends: backends
+            .iter()
+            .map(|backend| diagnose_backend(backend, routing, options, &run_probe))
+            .collect(),
+    }
+}
+
+impl BackendDoctorReport {
+    pub fn error_count(&self) -> usize {
+        self.backends
+            .iter()
+            .map(|backend| backend.errors.len())
+            .sum()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.backends
+            .iter()
+            .map(|backend| backend.warnings.len())
+            .sum()
+    }
+
+    pub fn render_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (idx, backend) in self.backends.iter().enumerate() {
+            if idx > 0 {
+                lines.push(String::new());
+            }
+            let selected = if backend.selected { " (selected)" } else { "" };
+            lines.push(format!(
+                "{}{selected}: `{}`",
+                backend.backend, backend.program
+            ));
+            if !backend.installed {
+                lines.push("  installed: no".to_string());
+            } else {
+                lines.push(format!(
+                    "  installed: yes, version {}",
+                    backend.version.as_deref().unwrap_or("unknown")
+                ));
+                lines.push(format!(
+                    "  json mode: {} | resume: {}",
+                    yes_no_unknown(backend.supports_json_mode),
+                    yes_no_unknown(backend.supports_resume)
+                ));
+                lines.push(format!(
+                    "  authenticated: {}",
+                    yes_no_unknown(backend.authenticated)
+                ));
+            }
+            for model in &backend.models {
+                let available = match model.available {
+                    Some(true) => "available",
+                    Some(false) => "NOT AVAILABLE",
+                    None => "not checked",
+                };
+                lines.push(format!(
+                    "  model {} ({}): {available}",
+                    model.model,
+                    model.used_by.join(", ")
+                ));
+            }
+            lines.extend(
+                backend
+                    .errors
+                    .iter()
+                    .map(|error| format!("  error: {error}")),
+            );
+            lines.extend(
+                backend
+                    .warnings
+                    .iter()
+                    .map(|warning| format!("  warning: {warning}")),
+            );
+        }
+        lines
+    }
+}
+
+pub(crate) fn diagnose_backend(
+    backend: &ConfiguredBackend,
+    routing: &CodexAgentModelRouting,
+    options: DoctorOptions,
+    run: &ProbeRunner,
+) -> BackendDiagnosis {
+    let program = backend.command.program.clone();
+    let mut diagnosis = BackendDiagnosis {
+        backend: backend_key(backend.kind).to_string(),
+        selected: backend.selected,
+        program: program.clone(),
+        installed: false,
+        version: None,
+        supports_json_mode: None,
+        supports_resume: None,
+        authenticated: None,
+        models: Vec::new(),
+        errors: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    match run(&program, &args(&["--version"]), METADATA_PROBE_TIMEOUT) {
+        Ok(output) => {
+            diagnosis.installed = true;
+            diagnosis.version = output
+                .success
+                .then(|| first_non_empty_line(&output.text))
+                .flatten();
+            if diagnosis.version.is_none() {
+                diagnosis
+                    .warnings
+                    .push(format!("`{program} --version` did not report a version"));
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            diagnosis
+                .errors
+                .push(format!("`{program}` is not installed or not on PATH"));
+            return diagnosis;
+        }
+        Err(err) => {
+            diagnosis
+                .errors
+                .push(format!("`{program}` could not be started: {err}"));
+            return diagnosis;
+        }
+    }
+
+    let help_args = match backend.kind {
+        BackendKind::Codex => args(&["exec", "--help"]),
+        BackendKind::Claude => args(&["--help"]),
+    };
+    match run(&program, &help_args, METADATA_PROBE_TIMEOUT) {
+        Ok(output) if output.success => {
+            let (json_mode, resume) = detect_supported_flags(backend.kind, &output.text);
+            diagnosis.supports_json_mode = Some(json_mode);
+            diagnosis.supports_resume = Some(resume);
+            if !json_mode {
+                diagnosis.warnings.push(
+                    "help output does not list a JSON output mode; JSON-streaming agents will fail"
+                        .to_string(),
+                );
+            }
+            if !resume {
+                diagnosis.warnings.push(
+                    "help output does not list session resume; persistent agents lose context between prompts"
+                        .to_string(),
+                );
+            }
+        }
+        _ => diagnosis.warnings.push(format!(
+            "`{program} {}` failed; supported flags are unknown",
+            help_args.join(" ")
+        )),
+    }
+
+    if backend.kind == BackendKind::Codex {
+        match run(
+            &program,
+            &args(&["login", "status"]),
+            METADATA_PROBE_TIMEOUT,
+        ) {
+            Ok(output) => {
+                diagnosis.authenticated = Some(output.success);
+                if !output.success {
+                    diagnosis
+                        .errors
+                        .push(format!("not logged in (run `{program} login`)"));
+                }
+            }
+            Err(err) => diagnosis
+                .warnings
+                .push(format!("`{program} login status` failed: {err}")),
+        }
+        diagnosis.models = routing
+            .assigned_models()
+            .into_iter()
+            .map(|(model, used_by)| ModelDiagnosis {
+                model,
+                used_by: used_by.into_iter().map(str::to_string).collect(),
+                available: None,
+            })
+            .collect();
+    }
+
+    if options.quick || diagnosis.authenticated == Some(false) {
+        return diagnosis;
+    }
+
+    if backend.kind == BackendKind::Claude {
+        let prompt_args = one_shot_prompt_args(&backend.command, PROBE_PROMPT);
+        match probe_prompt(run, &program, &prompt_args) {
+            Ok(()) => diagnosis.authenticated = Some(true),
+            Err((ProbeFailure::Unauthenticated, _)) => {
+                diagnosis.authenticated = Some(false);
+                diagnosis
+                    .errors
+                    .push(format!("not authenticated (run `{program}` to log in)"));
+            }
+            Err((_, detail)) => diagnosis
+                .warnings
+                .push(format!("probe prompt failed: {detail}")),
+        }
+        return diagnosis;
+    }
+
+    for idx in 0..diagnosis.models.len() {
+        let model = diagnosis.models[idx].model.clone();
+        let mut command = backend.command.clone();
+        command.model = Some(model.clone());
+        let prompt_args = one_shot_prompt_args(&command, PROBE_PROMPT);
+        match probe_prompt(run, &program, &prompt_args) {
+            Ok(()) => diagnosis.models[idx].available = Some(true),
+            Err((ProbeFailure::ModelUnavailable, _)) => {
+                diagnosis.models[idx].available = Some(false);
+                diagnosis.warnings.push(format!(
+                    "model `{model}` (used by {}) is not available to this account",
+                    diagnosis.models[idx].used_by.join(", ")
+                ));
+            }
+            Err((ProbeFailure::Unauthenticated, _)) => {
+                diagnosis.authenticated = Some(false);
+                diagnosis.errors.push(format!(
+                    "probe prompt was rejected as unauthenticated (run `{program} login`)"
+                ));
+                break;
+            }
+            Err((ProbeFailure::Other, detail)) => diagnosis
+                .warnings
+                .push(format!("probe prompt for model `{model}` failed: {detail}")),
+        }
+    }
+    diagnosis
+}
+
+fn probe_prompt(
+    run: &ProbeRunner,
+    program: &str,
+    prompt_args: &[String],
+) -> Result<(), (ProbeFailure, String)> {
+    let output = run(program, prompt_args, PROMPT_PROBE_TIMEOUT)
+        .map_err(|err| (ProbeFailure::Other, err.to_string()))?;
+    if output.timed_out {
+        return Err((
+            ProbeFailure::Other,
+            format!("timed out after {}s", PROMPT_PROBE_TIMEOUT.as_secs()),
+        ));
+    }
+    if output.success {
+        return Ok(());
+    }
+    let detail = output
+        .text
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("no output")
+        .to_string();
+    Err((classify_probe_failure(&output.text), detail))
+}
+
+fn classify_probe_failure(text: &str) -> ProbeFailure {
+    let lower = text.to_ascii_lowercase();
+    if lower.contains("model")
+        && MODEL_UNAVAILABLE_MARKERS
+            .iter()
+            .any(|marker| lower.contains(marker))
+    {
+        ProbeFailure::ModelUnavailable
+    } else if UNAUTHENTICATED_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker))
+    {
+        ProbeFailure::Unauthenticated
+    } else {
+        ProbeFailure::Other
+    }
+}
+
+/// Returns whether the help text advertises (JSON output mode, session resume).
+fn detect_supported_flags(kind: BackendKind, help: &str) -> (bool, bool) {
+    match kind {
+        BackendKind::Codex => (
+            help.contains("--json"),
+            help.lines()
+                .any(|line| line.trim_start().starts_with("resume")),
+        ),
+        BackendKind::Claude => (help.contains("--output-format"), help.contains("--resume")),
+    }
+}
+
+fn run_probe(program: &str, args: &[String], timeout: Duration) -> io::Result<ProbeOutput> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let readers = [
+        child.stdout.take().map(spawn_capture),
+        child.stderr.take().map(spawn_capture),
+    ];
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            let text = readers
+                .into_iter()
+                .flatten()
+                .filter_map(|reader| reader.join().ok())
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Ok(ProbeOutput {
+                success: status.success(),
+                timed_out: false,
+                text,
+            });
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            // Descendants may still hold the pipes open, so the readers are left detached.
+            return Ok(ProbeOutput {
+                success: false,
+                timed_out: true,
+                text: String::new(),
+            });
+        }
+        thread::sleep(PROBE_POLL_INTERVAL);
+    }
+}
+
+fn spawn_capture<R: Read + Send + 'static>(mut reader: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = reader.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+fn backend_key(kind: BackendKind) -> &'static str {
+    match kind {
+        BackendKind::Codex => "codex",
+        BackendKind::Claude => "claude",
+    }
+}
+
+fn first_non_empty_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+fn yes_no_unknown(value: Option<bool>) -> &'static str {
+    match value {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "unknown",
+    }
+}
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/backend_doctor_tests.rs"]
+mod tests;
@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::artifact_io::runtime_storage_dir;
+
+/// File under the runtime storage dir that every agent CLI call is appended to.
+pub const BACKEND_METRICS_FILE_NAME: &str = "backend-metrics.jsonl";
+
+static METRICS_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// One line of `backend-metrics.jsonl`: a single run of an agent CLI.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackendCallFileEntry {
+    pub at_epoch_ms: u64,
+    /// Agent slot that made the call, as named in `[codex.agent_profiles]`, e.g. `master`.
+    pub agent_kind: String,
+    /// `codex` or `claude`.
+    pub backend: String,
+    /// `None` when the CLI picked its default model.
+    pub model: Option<String>,
+    pub latency_ms: u64,
+    /// Exit code of the CLI; -1 when it failed to start or was killed by a signal.
+    pub exit_code: i32,
+    /// Whether the call resent a prompt after a transient failure.
+    pub retry: bool,
+}
+
+impl BackendCallFileEntry {
+    pub fn new(
+        agent_kind: &str,
+        backend: &str,
+        model: Option<&str>,
+        latency_ms: u64,
+        exit_code: i32,
+        retry: bool,
+    ) -> Self {
+        Self {
+            at_epoch_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as u64)
+                .unwrap_or_default(),
+            agent_kind: agent_kind.to_string(),
+            backend: backend.to_string(),
+            model: model.map(str::to_string),
+            latency_ms,
+            exit_code,
+            retry,
+        }
+    }
+}
+
+pub fn metrics_file_path() -> io::Result<PathBuf> {
+    Ok(runtime_storage_dir()?.join(BACKEND_METRICS_FILE_NAME))
+}
+
+/// Points recording at `path`, or stops recording with `None`. Nothing is recorded until the
+/// TUI sets a file, so tests and API commands leave the metrics alone.
+pub fn set_file(path: Option<PathBuf>) {
+    let mut file = METRICS_FILE.lock().unwrap_or_else(|err| err.into_inner());
+    *file = path;
+}
+
+/// Appends `entry` to the metrics file. Metrics only guide routing choices, so a failed
+/// write is dropped rather than reported in the middle of a run.
+pub fn record(entry: &BackendCallFileEntry) {
+    let path = METRICS_FILE
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone();
+    if let Some(path) = path {
+        let _ = append_entry(&path, entry);
+    }
+}
+
+pub(crate) fn append_entry(path: &Path, entry: &BackendCallFileEntry) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(entry).map_err(io::Error::other)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Entries in `path`; a missing file has none and unreadable lines are skipped.
+pub(crate) fn read_entries(path: &Path) -> io::Result<Vec<BackendCallFileEntry>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Reads the metrics file and summarizes it per agent kind and backend.
+pub fn collect_backend_stats() -> io::Result<BackendStats> {
+    Ok(BackendStats::from_entries(&read_entries(
+        &metrics_file_path()?,
+    )?))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackendStats {
+    pub total_calls: usize,
+    pub by_agent: Vec<BackendCallStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendCallStats {
+    pub agent_kind: String,
+    pub backend: String,
+    pub calls: usize,
+    pub failures: usize,
+    pub retries: usize,
+    pub p50_latency_ms: u64,
+    pub p95_latency_ms: u64,
+}
+
+impl BackendStats {
+    pub fn from_entries(entries: &[BackendCallFileEntry]) -> Self {
+        let mut groups: BTreeMap<(&str, &str), Vec<&BackendCallFileEntry>> = BTreeMap::new();
+        for entry in entries {
+            groups
+                .entry((entry.agent_kind.as_str(), entry.backend.as_str()))
+                .or_default()
+                .push(entry);
+        }
+        let by_agent = groups
+            .into_iter()
+            .map(|((agent_kind, backend), calls)| {
+                let mut latencies = calls.iter().map(|call| call.latency_ms).collect::<Vec<_>>();
+                latencies.sort_unstable();
+                BackendCallStats {
+                    agent_kind: agent_kind.to_string(),
+                    backend: backend.to_string(),
+                    calls: calls.len(),
+                    failures: calls.iter().filter(|call| call.exit_code != 0).count(),
+                    retries: calls.iter().filter(|call| call.retry).count(),
+                    p50_latency_ms: percentile(&latencies, 50),
+                    p95_latency_ms: percentile(&latencies, 95),
+                }
+            })
+            .collect();
+        Self {
+            total_calls: entries.len(),
+            by_agent,
+        }
+    }
+
+    pub fn render_lines(&self) -> Vec<String> {
+        if self.by_agent.is_empty() {
+            return vec!["No backend calls recorded yet.".to_string()];
+        }
+        let mut lines = vec![
+            format!("Backend calls: {}", self.total_calls),
+            String::new(),
+        ];
+        lines.push(format!(
+            "{:<20} {:<7} {:>6} {:>6} {:>7} {:>9} {:>9}",
+            "agent", "backend", "calls", "failed", "retries", "p50", "p95"
+        ));
+        for stats in &self.by_agent {
+            lines.push(format!(
+                "{:<20} {:<7} {:>6} {:>6} {:>7} {:>9} {:>9}",
+                stats.agent_kind,
+                stats.backend,
+                stats.calls,
+                stats.failures,
+                stats.retries,
+                format_latency(stats.p50_latency_ms),
+                format_latency(stats.p95_latency_ms)
+            ));
+        }
+        lines
+    }
+}
+
+/// Nearest-rank percentile of ascending `sorted`; 0 when empty.
+fn percentile(sorted: &[u64], percent: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn format_latency(ms: u64) -> String {
+    if ms < 1_000 {
+        format!("{ms}ms")
+    } else {
+        format!("{:.1}s", ms as f64 / 1_000.0)
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/backend_metrics_tests.rs"]
+mod tests;
@@ -0,0 +1,167 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Longest diff shown in the review pane; the rest is summarized in one line.
+const MAX_DIFF_LINES: usize = 4000;
+
+/// Git tree of the workspace taken before an implementor pass, so `/review on` can show
+/// and roll back exactly what the pass changed. Untracked files count; ignored ones do not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeSnapshot {
+    repo_root: PathBuf,
+    tree: String,
+}
+
+pub fn snapshot_worktree(root: &Path) -> io::Result<WorktreeSnapshot> {
+    let repo_root = PathBuf::from(git(root, &["rev-parse", "--show-toplevel"], None)?.trim());
+    let tree = write_tree(&repo_root)?;
+    Ok(WorktreeSnapshot { repo_root, tree })
+}
+
+/// `git diff` of the workspace against `snapshot`, ready for the right pane.
+pub fn diff_since(snapshot: &WorktreeSnapshot) -> io::Result<Vec<String>> {
+    let current = write_tree(&snapshot.repo_root)?;
+    let patch = git(
+        &snapshot.repo_root,
+        &[
+            "diff",
+            "--no-color",
+            "--no-renames",
+            "--stat",
+            "--patch",
+            &snapshot.tree,
+            &current,
+        ],
+        None,
+    )?;
+    let mut lines = patch.lines().map(str::to_string).collect::<Vec<_>>();
+    if lines.is_empty() {
+        lines.push("No workspace changes since the pass started.".to_string());
+    } else if lines.len() > MAX_DIFF_LINES {
+        let hidden = lines.len() - MAX_DIFF_LINES;
+        lines.truncate(MAX_DIFF_LINES);
+        lines.push(format!("... {hidden} more diff line(s) not shown."));
+    }
+    Ok(lines)
+}
+
+/// Repository-relative paths added, modified, or deleted since `snapshot`.
+pub fn changed_paths_since(snapshot: &WorktreeSnapshot) -> io::Result<Vec<String>> {
+    let current = write_tree(&snapshot.repo_root)?;
+    let changes = git(
+        &snapshot.repo_root,
+        &[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "-z",
+            &snapshot.tree,
+            &current,
+        ],
+        None,
+    )?;
+    Ok(changes
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Restores every file the pass touched to its state in `snapshot` and deletes files it
+/// added. Returns how many paths were rolled back.
+pub fn revert_to(snapshot: &WorktreeSnapshot) -> io::Result<usize> {
+    let current = write_tree(&snapshot.repo_root)?;
+    let changes = git(
+        &snapshot.repo_root,
+        &[
+            "diff",
+            "--name-status",
+            "--no-renames",
+            "-z",
+            &snapshot.tree,
+            &current,
+        ],
+        None,
+    )?;
+    let mut fields = changes.split('\0').filter(|field| !field.is_empty());
+    let mut restore = Vec::new();
+    let mut count = 0;
+    while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+        count += 1;
+        if status == "A" {
+            fs::remove_file(snapshot.repo_root.join(path))?;
+        } else {
+            restore.push(path.to_string());
+        }
+    }
+    if !restore.is_empty() {
+        let index = TempIndex::new();
+        git(
+            &snapshot.repo_root,
+            &["read-tree", &snapshot.tree],
+            Some(&index.path),
+        )?;
+        let mut args = vec!["checkout-index", "--force", "--"];
+        args.extend(restore.iter().map(String::as_str));
+        git(&snapshot.repo_root, &args, Some(&index.path))?;
+    }
+    Ok(count)
+}
+
+/// Tree object for the current workspace, staged through a throwaway index so the
+/// user's own index is left alone.
+fn write_tree(repo_root: &Path) -> io::Result<String> {
+    let index = TempIndex::new();
+    git(repo_root, &["add", "--all", "."], Some(&index.path))?;
+    Ok(git(repo_root, &["write-tree"], Some(&index.path))?
+        .trim()
+        .to_string())
+}
+
+fn git(dir: &Path, args: &[&str], index: Option<&Path>) -> io::Result<String> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(dir).args(args);
+    if let Some(index) = index {
+        command.env("GIT_INDEX_FILE", index);
+    }
+    let output = command
+        .output()
+        .map_err(|err| io::Error::other(format!("could not run git: {err}")))?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+struct TempIndex {
+    path: PathBuf,
+}
+
+impl TempIndex {
+    fn new() -> Self {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or_default();
+        Self {
+            path: std::env::temp_dir()
+                .join(format!("bob-review-index-{}-{now}", std::process::id())),
+        }
+    }
+}
+
+impl Drop for TempIndex {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/change_review_tests.rs"]
+mod tests;
@@ -0,0 +1,174 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::i18n::Locale;
+use crate::time_format::{TimeZone, format_date_time};
+
+/// Marks the block of clarification questions a master reply asks the user to answer.
+pub const QUESTIONS_BEGIN: &str = "QUESTIONS_BEGIN";
+pub const QUESTIONS_END: &str = "QUESTIONS_END";
+
+/// Prompt lines telling the master how to ask for clarification.
+pub fn questions_prompt_instructions() -> String {
+    format!(
+        "- When you need answers from the user, put each question on its own line between \
+         {QUESTIONS_BEGIN} and {QUESTIONS_END} lines; the user answers them in a form and the \
+         answers come back together in one message."
+    )
+}
+
+/// Every question in the `QUESTIONS_BEGIN`/`QUESTIONS_END` blocks of a master reply, without
+/// list markers.
+pub fn extract_questions(text: &str) -> Vec<String> {
+    let mut questions = Vec::new();
+    let mut in_block = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line == QUESTIONS_BEGIN {
+            in_block = true;
+        } else if line == QUESTIONS_END {
+            in_block = false;
+        } else if in_block {
+            let question = strip_list_marker(line);
+            if !question.is_empty() {
+                questions.push(question.to_string());
+            }
+        }
+    }
+    questions
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return rest.trim();
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = &line[digits..];
+    match rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+        Some(rest) if digits > 0 => rest.trim(),
+        _ => line,
+    }
+}
+
+/// What a key press did to the form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormStep {
+    /// Still editing.
+    Editing,
+    /// Enter on the last question: the answers are ready to send.
+    Submitted,
+}
+
+/// One answer field per master question; Enter moves to the next field and submits on the
+/// last one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestionForm {
+    questions: Vec<String>,
+    answers: Vec<String>,
+    selected: usize,
+}
+
+impl QuestionForm {
+    /// `None` when there is nothing to ask.
+    pub fn new(questions: Vec<String>) -> Option<Self> {
+        if questions.is_empty() {
+            return None;
+        }
+        let answers = vec![String::new(); questions.len()];
+        Some(Self {
+            questions,
+            answers,
+            selected: 0,
+        })
+    }
+
+    pub fn questions(&self) -> &[String] {
+        &self.questions
+    }
+
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.questions.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self
+            .selected
+            .checked_sub(1)
+            .unwrap_or(self.questions.len() - 1);
+    }
+
+    /// Adds typed or pasted text to the selected answer; answers stay on one line.
+    pub fn insert_text(&mut self, text: &str) {
+        let answer = &mut self.answers[self.selected];
+        for ch in text.chars() {
+            answer.push(if ch == '\n' || ch == '\r' { ' ' } else { ch });
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.answers[self.selected].pop();
+    }
+
+    pub fn submit_field(&mut self) -> FormStep {
+        if self.selected + 1 == self.questions.len() {
+            return FormStep::Submitted;
+        }
+        self.selected += 1;
+        FormStep::Editing
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.answers.iter().all(|answer| answer.trim().is_empty())
+    }
+
+    /// The message sent back to the master: every question with its answer, unanswered ones
+    /// marked as such.
+    pub fn combined_message(&self) -> String {
+        let mut out = String::from("Answers to your clarification questions:\n");
+        for (index, (question, answer)) in self.questions.iter().zip(&self.answers).enumerate() {
+            let answer = answer_or_placeholder(answer);
+            out.push_str(&format!("{}. {question}\n   Answer: {answer}\n", index + 1));
+        }
+        out
+    }
+}
+
+/// Appends the form's questions and answers to the session's `clarifications.md`, so they
+/// stay with the plan after the chat scrolls away.
+pub fn append_clarifications(path: &Path, form: &QuestionForm, epoch_secs: u64) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "# Clarifications\n")?;
+    }
+    let answered_at = format_date_time(Locale::En, TimeZone::UTC, epoch_secs);
+    let mut text = format!("## Answered {answered_at}\n\n");
+    for (question, answer) in form.questions.iter().zip(&form.answers) {
+        let answer = answer_or_placeholder(answer);
+        text.push_str(&format!("- Q: {question}\n  A: {answer}\n"));
+    }
+    text.push('\n');
+    file.write_all(text.as_bytes())
+}
+
+fn answer_or_placeholder(answer: &str) -> &str {
+    match answer.trim() {
+        "" => "(no answer)",
+        answer => answer,
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/clarification_tests.rs"]
+mod tests;
@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::file_tree::IGNORED_DIR_NAMES;
+use crate::workspace_ignore::WorkspaceIgnore;
+
+const MAX_WALK_DEPTH: usize = 12;
+const MAX_CANDIDATE_BYTES: u64 = 64 * 1024;
+/// Upper bound on file contents read per retrieval, so large workspaces stay cheap.
+const CONTENT_SCAN_BUDGET_BYTES: u64 = 8 * 1024 * 1024;
+const MIN_SCORE: u32 = 4;
+const MAX_EXCERPT_LINE_CHARS: usize = 200;
+/// Words too common in task details to say anything about which files matter.
+const STOP_WORDS: &str = "the and for with that this from into when then than should must will are was \
+     not but all any each use using add make new update support task tasks test tests implement \
+     file files code also only have has";
+
+/// How many workspace file excerpts implementor and test-writer prompts get
+/// (`[workflow] code_context_*`). Zero files disables retrieval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeContextPolicy {
+    pub max_files: usize,
+    pub max_excerpt_lines: usize,
+}
+
+impl Default for CodeContextPolicy {
+    fn default() -> Self {
+        Self {
+            max_files: 4,
+            max_excerpt_lines: 40,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileExcerpt {
+    pub relative_path: String,
+    /// 1-based line number of `lines[0]`.
+    pub start_line: usize,
+    pub lines: Vec<String>,
+}
+
+struct Candidate {
+    relative_path: String,
+    score: u32,
+    first_hit: Option<usize>,
+}
+
+/// Picks the workspace files that best match `query` (task titles and details): paths the
+/// query names outright, then files whose path or contents mention its keywords.
+pub fn relevant_file_excerpts(
+    root: &Path,
+    query: &str,
+    policy: &CodeContextPolicy,
+) -> Vec<FileExcerpt> {
+    if policy.max_files == 0 || policy.max_excerpt_lines == 0 {
+        return Vec::new();
+    }
+    let keywords = query_keywords(query);
+    let mentioned = mentioned_paths(query);
+    if keywords.is_empty() && mentioned.is_empty() {
+        return Vec::new();
+    }
+
+    let files = workspace_files(root);
+    let mut budget = CONTENT_SCAN_BUDGET_BYTES;
+    let mut candidates = files
+        .into_iter()
+        .filter_map(|relative_path| {
+            score_file(root, relative_path, &keywords, &mentioned, &mut budget)
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.relative_path.cmp(&b.relative_path))
+    });
+    candidates
+        .into_iter()
+        .take(policy.max_files)
+        .filter_map(|candidate| excerpt(root, candidate, policy.max_excerpt_lines))
+        .collect()
+}
+
+/// Prompt section listing `excerpts`; empty when there are none.
+pub fn render_code_context(excerpts: &[FileExcerpt]) -> String {
+    if excerpts.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from(
+        "Relevant workspace files (picked automatically from the task details; excerpts may be partial, so open a file before editing it):\n",
+    );
+    for excerpt in excerpts {
+        let end_line = excerpt.start_line + excerpt.lines.len().saturating_sub(1);
+        out.push_str(&format!(
+            "\n--- {} (lines {}-{}) ---\n",
+            excerpt.relative_path, excerpt.start_line, end_line
+        ));
+        for line in &excerpt.lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn query_keywords(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    query
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .flat_map(|word| {
+            let mut parts = vec![word.to_ascii_lowercase()];
+            if word.contains('_') {
+                parts.extend(word.split('_').map(str::to_ascii_lowercase));
+            }
+            parts
+        })
+        .filter(|word| {
+            word.len() >= 3
+                && !word.chars().all(|c| c.is_ascii_digit())
+                && !STOP_WORDS.split_whitespace().any(|stop| stop == word)
+        })
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
+}
+
+/// Path-like tokens such as `src/app.rs` or `main.rs`.
+fn mentioned_paths(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| c.is_whitespace() || matches!(c, '`' | '"' | '\'' | '(' | ')' | ','))
+        .map(|token| {
+            token
+                .trim_end_matches(['.', ':', ';'])
+                .trim_start_matches("./")
+        })
+        .filter(|token| {
+            token
+                .rsplit_once('.')
+                .is_some_and(|(stem, ext)| !stem.is_empty() && (1..=5).contains(&ext.len()))
+                && token.chars().any(|c| c.is_ascii_alphabetic())
+                && !token.contains("://")
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Relative paths of the workspace files worth searching, skipping hidden and build
+/// directories and whatever `.gitignore` or `.metaagentignore` ignores.
+pub(crate) fn workspace_files(root: &Path) -> Vec<String> {
+    let ignore = WorkspaceIgnore::load(root);
+    let mut files = Vec::new();
+    collect_files(root, "", 0, &ignore, &mut files);
+    files
+}
+
+fn collect_files(
+    dir: &Path,
+    relative_dir: &str,
+    depth: usize,
+    ignore: &WorkspaceIgnore,
+    out: &mut Vec<String>,
+) {
+    if depth > MAX_WALK_DEPTH {
+        return;
+    }
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut children = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            let file_type = entry.file_type().ok()?;
+            if name.starts_with('.') || file_type.is_symlink() {
+                return None;
+            }
+            if file_type.is_dir() && IGNORED_DIR_NAMES.contains(&name.as_str()) {
+                return None;
+            }
+            Some((name, file_type.is_dir()))
+        })
+        .collect::<Vec<_>>();
+    children.sort();
+    for (name, is_dir) in children {
+        let relative_path = if relative_dir.is_empty() {
+            name.clone()
+        } else {
+            format!("{relative_dir}/{name}")
+        };
+        if ignore.is_ignored(&relative_path, is_dir) {
+            continue;
+        }
+        if is_dir {
+            collect_files(&dir.join(&name), &relative_path, depth + 1, ignore, out);
+        } else {
+            out.push(relative_path);
+        }
+    }
+}
+
+fn score_file(
+    root: &Path,
+    relative_path: String,
+    keywords: &[String],
+    mentioned: &[String],
+    budget: &mut u64,
+) -> Option<Candidate> {
+    let mut score = 0u32;
+    if mentioned
+        .iter()
+        .any(|path| relative_path == *path || relative_path.ends_with(&format!("/{path}")))
+    {
+        score += 100;
+    }
+    let lower_path = relative_path.to_ascii_lowercase();
+    let stem = Path::new(&lower_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+        .to_string();
+    for keyword in keywords {
+        if stem == *keyword {
+            score += 8;
+        } else if lower_path.contains(keyword.as_str()) {
+            score += 3;
+        }
+    }
+
+    let mut first_hit = None;
+    let size = fs::metadata(root.join(&relative_path)).ok()?.len();
+    if size <= MAX_CANDIDATE_BYTES && size <= *budget {
+        *budget -= size;
+        let text = fs::read_to_string(root.join(&relative_path)).ok()?;
+        let lower = text.to_ascii_lowercase();
+        for keyword in keywords {
+            if let Some(offset) = lower.find(keyword.as_str()) {
+                score += if keyword.contains('_') { 4 } else { 1 };
+                let line = lower[..offset].matches('\n').count();
+                first_hit = Some(first_hit.map_or(line, |hit: usize| hit.min(line)));
+            }
+        }
+    }
+    (score >= MIN_SCORE).then_some(Candidate {
+        relative_path,
+        score,
+        first_hit,
+    })
+}
+
+fn excerpt(root: &Path, candidate: Candidate, max_lines: usize) -> Option<FileExcerpt> {
+    let text = fs::read_to_string(root.join(&candidate.relative_path)).ok()?;
+    let lines = text.lines().collect::<Vec<_>>();
+    if lines.is_empty() {
+        return None;
+    }
+    let start = candidate
+        .first_hit
+        .map(|hit| hit.saturating_sub(max_lines / 4))
+        .unwrap_or(0)
+        .min(lines.len().saturating_sub(max_lines));
+    Some(FileExcerpt {
+        relative_path: candidate.relative_path,
+        start_line: start + 1,
+        lines: lines
+            .iter()
+            .skip(start)
+            .take(max_lines)
+            .map(
+                |line| match line.char_indices().nth(MAX_EXCERPT_LINE_CHARS) {
+                    Some((cut, _)) => format!("{}...", &line[..cut]),
+                    None => line.to_string(),
+                },
+            )
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/code_context_tests.rs"]
+mod tests;
@@ -0,0 +1,480 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::artifact_io::{load_merged_metaagent_config_text, read_text_file, write_text_file};
+use crate::code_context::workspace_files;
+use crate::i18n::{self, Locale, Text};
+use crate::session_store::SessionStore;
+use crate::workspace_ignore::WorkspaceIgnore;
+
+const CHUNK_LINES: usize = 40;
+const MAX_INDEXED_FILE_BYTES: u64 = 256 * 1024;
+const DEFAULT_HASHED_DIMENSIONS: usize = 256;
+
+/// Turns text chunks into vectors (`[embeddings]` in config.toml).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmbeddingBackend {
+    /// Local feature hashing of identifiers and words; needs no model or network.
+    Hashed { dimensions: usize },
+    /// Runs `sh -c <command>` with `{"texts": [...]}` on stdin and expects
+    /// `{"vectors": [[...], ...]}` on stdout, one vector per text.
+    Command { command: String },
+}
+
+impl Default for EmbeddingBackend {
+    fn default() -> Self {
+        EmbeddingBackend::Hashed {
+            dimensions: DEFAULT_HASHED_DIMENSIONS,
+        }
+    }
+}
+
+impl EmbeddingBackend {
+    /// Recorded in the index so vectors from a different backend are never mixed.
+    pub fn label(&self) -> String {
+        match self {
+            EmbeddingBackend::Hashed { dimensions } => format!("hashed-{dimensions}"),
+            EmbeddingBackend::Command { command } => format!("command:{command}"),
+        }
+    }
+
+    pub fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        match self {
+            EmbeddingBackend::Hashed { dimensions } => Ok(texts
+                .iter()
+                .map(|text| hashed_embedding(text, *dimensions))
+                .collect()),
+            EmbeddingBackend::Command { command } => run_embedding_command(command, texts),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EmbeddingsConfigFile {
+    #[serde(default)]
+    embeddings: EmbeddingsSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EmbeddingsSection {
+    backend: Option<String>,
+    dimensions: Option<usize>,
+    command: Option<String>,
+}
+
+pub fn load_embedding_backend() -> io::Result<EmbeddingBackend> {
+    embedding_backend_from_toml(&load_merged_metaagent_config_text()?)
+}
+
+pub(crate) fn embedding_backend_from_toml(text: &str) -> io::Result<EmbeddingBackend> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let section = toml::from_str::<EmbeddingsConfigFile>(text)
+        .map_err(|err| invalid(err.to_string()))?
+        .embeddings;
+    match section.backend.as_deref().unwrap_or("hashed") {
+        "hashed" => {
+            let dimensions = section.dimensions.unwrap_or(DEFAULT_HASHED_DIMENSIONS);
+            if dimensions == 0 {
+                return Err(invalid(
+                    "embeddings.dimensions must be positive".to_string(),
+                ));
+            }
+            Ok(EmbeddingBackend::Hashed { dimensions })
+        }
+        "command" => match section.command.filter(|command| !command.trim().is_empty()) {
+            Some(command) => Ok(EmbeddingBackend::Command { command }),
+            None => Err(invalid(
+                "embeddings.command is required when embeddings.backend = \"command\"".to_string(),
+            )),
+        },
+        other => Err(invalid(format!(
+            "embeddings.backend must be \"hashed\" or \"command\" (got \"{other}\")"
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CodeIndexFile {
+    pub backend: String,
+    pub files: BTreeMap<String, IndexedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IndexedFile {
+    pub content_hash: u64,
+    pub chunks: Vec<IndexedChunk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IndexedChunk {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RefreshSummary {
+    pub indexed: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CodeSearchHit {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub score: f32,
+    pub excerpt: String,
+}
+
+/// Re-embeds workspace files whose contents changed since the last refresh and drops files
+/// that are gone or now ignored. `paths` limits the refresh to those files; the whole workspace is scanned
+/// when it is `None`, the index does not exist yet, or it was built with another backend.
+pub fn refresh_code_index(
+    root: &Path,
+    store: &SessionStore,
+    backend: &EmbeddingBackend,
+    paths: Option<&[String]>,
+) -> io::Result<RefreshSummary> {
+    let mut index = read_code_index(store)?.unwrap_or_default();
+    let label = backend.label();
+    let full = paths.is_none() || index.backend != label || index.files.is_empty();
+    if index.backend != label {
+        index = CodeIndexFile {
+            backend: label,
+            files: BTreeMap::new(),
+        };
+    }
+    let candidates = match paths {
+        Some(paths) if !full => paths.to_vec(),
+        _ => workspace_files(root),
+    };
+    let ignore = WorkspaceIgnore::load(root);
+
+    let mut summary = RefreshSummary::default();
+    if full {
+        let present = candidates.iter().collect::<BTreeSet<_>>();
+        let before = index.files.len();
+        index.files.retain(|path, _| present.contains(path));
+        summary.removed += before - index.files.len();
+    }
+    let mut pending = Vec::new();
+    for path in candidates {
+        let text = if ignore.is_ignored(&path, false) {
+            None
+        } else {
+            read_indexable_file(&root.join(&path))
+        };
+        let Some(text) = text else {
+            summary.removed += usize::from(index.files.remove(&path).is_some());
+            continue;
+        };
+        let content_hash = fnv1a(text.as_bytes());
+        if index
+            .files
+            .get(&path)
+            .is_some_and(|file| file.content_hash == content_hash)
+        {
+            summary.unchanged += 1;
+            continue;
+        }
+        pending.push((path, content_hash, chunk_lines(&text)));
+    }
+
+    let texts = pending
+        .iter()
+        .flat_map(|(_, _, chunks)| chunks.iter().map(|(_, _, text)| text.clone()))
+        .collect::<Vec<_>>();
+    let mut vectors = backend.embed(&texts).map_err(io::Error::other)?.into_iter();
+    for (path, content_hash, chunks) in pending {
+        let chunks = chunks
+            .into_iter()
+            .map(|(start_line, end_line, _)| IndexedChunk {
+                start_line,
+                end_line,
+                vector: vectors.next().unwrap_or_default(),
+            })
+            .collect();
+        index.files.insert(
+            path,
+            IndexedFile {
+                content_hash,
+                chunks,
+            },
+        );
+        summary.indexed += 1;
+    }
+    let text = serde_json::to_string(&index).map_err(io::Error::other)?;
+    write_text_file(&store.code_index_file(), &text)?;
+    Ok(summary)
+}
+
+pub fn read_code_index(store: &SessionStore) -> io::Result<Option<CodeIndexFile>> {
+    let text = match read_text_file(&store.code_index_file()) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Refreshes the index, then ranks its chunks by cosine similarity to `query`.
+pub fn search_code(
+    root: &Path,
+    store: &SessionStore,
+    backend: &EmbeddingBackend,
+    query: &str,
+    limit: usize,
+) -> io::Result<Vec<CodeSearchHit>> {
+    refresh_code_index(root, store, backend, None)?;
+    let index = read_code_index(store)?.unwrap_or_default();
+    let query_vector = backend
+        .embed(&[query.to_string()])
+        .map_err(io::Error::other)?
+        .pop()
+        .unwrap_or_default();
+    let mut scored = index
+        .files
+        .iter()
+        .flat_map(|(path, file)| {
+            let query_vector = &query_vector;
+            file.chunks
+                .iter()
+                .map(move |chunk| (path, chunk, cosine(query_vector, &chunk.vector)))
+        })
+        .filter(|(_, _, score)| *score > 0.0)
+        .collect::<Vec<_>>();
+    scored.sort_by(|a, b| {
+        b.2.total_cmp(&a.2)
+            .then_with(|| a.0.cmp(b.0))
+            .then_with(|| a.1.start_line.cmp(&b.1.start_line))
+    });
+    Ok(scored
+        .into_iter()
+        .take(limit)
+        .map(|(path, chunk, score)| CodeSearchHit {
+            path: path.clone(),
+            start_line: chunk.start_line,
+            end_line: chunk.end_line,
+            score,
+            excerpt: read_indexable_file(&root.join(path))
+                .map(|text| {
+                    text.lines()
+                        .skip(chunk.start_line - 1)
+                        .take(chunk.end_line + 1 - chunk.start_line)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Keeps the TUI session's index current: a full refresh when a session is first seen, then
+/// one for each batch of newly recorded changed files. Refreshes run on a background thread.
+#[derive(Debug)]
+pub struct CodeIndexRefresher {
+    tracked_session: Option<PathBuf>,
+    seen_changes: BTreeSet<String>,
+    pending: Option<Vec<String>>,
+    running: Option<JoinHandle<()>>,
+    results_tx: Sender<String>,
+    results_rx: Receiver<String>,
+}
+
+impl Default for CodeIndexRefresher {
+    fn default() -> Self {
+        let (results_tx, results_rx) = mpsc::channel();
+        Self {
+            tracked_session: None,
+            seen_changes: BTreeSet::new(),
+            pending: None,
+            running: None,
+            results_tx,
+            results_rx,
+        }
+    }
+}
+
+impl CodeIndexRefresher {
+    pub fn poll(
+        &mut self,
+        root: &Path,
+        store: &SessionStore,
+        changed_files: &BTreeSet<String>,
+        locale: Locale,
+    ) {
+        if self.tracked_session.as_deref() != Some(store.session_dir()) {
+            self.tracked_session = Some(store.session_dir().to_path_buf());
+            self.seen_changes = changed_files.clone();
+            self.pending = None;
+            self.spawn(root, store, None, locale);
+            return;
+        }
+        let new_paths = changed_files
+            .difference(&self.seen_changes)
+            .cloned()
+            .collect::<Vec<_>>();
+        self.seen_changes = changed_files.clone();
+        if !new_paths.is_empty() {
+            self.pending.get_or_insert_with(Vec::new).extend(new_paths);
+        }
+        if let Some(paths) = self.pending.take() {
+            if self
+                .running
+                .as_ref()
+                .is_some_and(|handle| !handle.is_finished())
+            {
+                self.pending = Some(paths);
+            } else {
+                self.spawn(root, store, Some(paths), locale);
+            }
+        }
+    }
+
+    pub fn drain_messages(&self) -> Vec<String> {
+        self.results_rx.try_iter().collect()
+    }
+
+    fn spawn(
+        &mut self,
+        root: &Path,
+        store: &SessionStore,
+        paths: Option<Vec<String>>,
+        locale: Locale,
+    ) {
+        let root = root.to_path_buf();
+        let store = store.clone();
+        let results_tx = self.results_tx.clone();
+        self.running = Some(thread::spawn(move || {
+            let result = load_embedding_backend()
+                .and_then(|backend| refresh_code_index(&root, &store, &backend, paths.as_deref()));
+            if let Err(err) = result {
+                let _ = results_tx.send(i18n::system(
+                    locale,
+                    Text::CodeIndexRefreshFailed,
+                    &[("err", &err)],
+                ));
+            }
+        }));
+    }
+}
+
+fn read_indexable_file(path: &Path) -> Option<String> {
+    let meta = fs::metadata(path).ok()?;
+    if !meta.is_file() || meta.len() > MAX_INDEXED_FILE_BYTES {
+        return None;
+    }
+    fs::read_to_string(path)
+        .ok()
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// `(start_line, end_line, text)` windows of [`CHUNK_LINES`] lines, 1-based and inclusive.
+fn chunk_lines(text: &str) -> Vec<(usize, usize, String)> {
+    let lines = text.lines().collect::<Vec<_>>();
+    lines
+        .chunks(CHUNK_LINES)
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let start = idx * CHUNK_LINES + 1;
+            (start, start + chunk.len() - 1, chunk.join("\n"))
+        })
+        .collect()
+}
+
+fn hashed_embedding(text: &str, dimensions: usize) -> Vec<f32> {
+    let mut vector = vec![0.0f32; dimensions];
+    let words = text
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|word| word.len() >= 2)
+        .map(str::to_ascii_lowercase);
+    for word in words {
+        let parts = word
+            .split('_')
+            .filter(|part| part.len() >= 2 && *part != word);
+        for token in std::iter::once(word.as_str()).chain(parts) {
+            let hash = fnv1a(token.as_bytes());
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            vector[(hash % dimensions as u64) as usize] += sign;
+        }
+    }
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+    vector
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot = a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+    let norm =
+        a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|y| y * y).sum::<f32>().sqrt();
+    if norm > 0.0 { dot / norm } else { 0.0 }
+}
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn run_embedding_command(command: &str, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("could not run embedding command: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(json!({ "texts": texts }).to_string().as_bytes())
+            .map_err(|err| format!("could not pass texts to the embedding command: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("embedding command failed: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "embedding command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    #[derive(Deserialize)]
+    struct EmbeddingOutput {
+        vectors: Vec<Vec<f32>>,
+    }
+    let parsed = serde_json::from_slice::<EmbeddingOutput>(&output.stdout)
+        .map_err(|err| format!("embedding command printed invalid JSON: {err}"))?;
+    if parsed.vectors.len() != texts.len() {
+        return Err(format!(
+            "embedding command returned {} vectors for {} texts",
+            parsed.vectors.len(),
+            texts.len()
+        ));
+    }
+    Ok(parsed.vectors)
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/code_index_tests.rs"]
+mod tests;
@@ -0,0 +1,393 @@
+/// What one argument of a slash command accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    /// One of a fixed set of words, matched case-insensitively.
+    Choice(&'static [&'static str]),
+    /// A task id from tasks.json, written right after `prefix` (e.g. `task:`).
+    TaskId { prefix: &'static str },
+    /// A 1-based position, as listed by `/jobs` or the task list.
+    Number,
+    /// Any single word, such as a template or plugin name.
+    Word,
+    /// Free text running to the end of the line.
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    /// Optional arguments only come after the required ones.
+    pub required: bool,
+}
+
+const fn required(name: &'static str, kind: ArgKind) -> ArgSpec {
+    ArgSpec {
+        name,
+        kind,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str, kind: ArgKind) -> ArgSpec {
+    ArgSpec {
+        name,
+        kind,
+        required: false,
+    }
+}
+
+const TASK: ArgKind = ArgKind::TaskId { prefix: "" };
+const PREFIXED_TASK: ArgKind = ArgKind::TaskId { prefix: "task:" };
+
+/// Each command's accepted argument lists. A command with several forms accepts any of them;
+/// commands without arguments are not listed.
+const COMMAND_ARGS: &[(&str, &[&[ArgSpec]])] = &[
+    ("/start", &[&[optional("id", PREFIXED_TASK)]]),
+    (
+        "/attach-docs",
+        &[
+            &[
+                optional("snapshot", ArgKind::Choice(&["snapshot"])),
+                optional("id", PREFIXED_TASK),
+            ],
+            &[
+                required("id", PREFIXED_TASK),
+                required("snapshot", ArgKind::Choice(&["snapshot"])),
+            ],
+        ],
+    ),
+    (
+        "/quit",
+        &[&[optional(
+            "mode",
+            ArgKind::Choice(&["wait", "abort", "detach"]),
+        )]],
+    ),
+    (
+        "/exit",
+        &[&[optional(
+            "mode",
+            ArgKind::Choice(&["wait", "abort", "detach"]),
+        )]],
+    ),
+    ("/new-from-template", &[&[optional("name", ArgKind::Word)]]),
+    ("/logs", &[&[optional("task-id", TASK)]]),
+    (
+        "/conflict",
+        &[&[required(
+            "choice",
+            ArgKind::Choice(&["mine", "theirs", "merge"]),
+        )]],
+    ),
+    (
+        "/lock",
+        &[&[required(
+            "action",
+            ArgKind::Choice(&["takeover", "observe"]),
+        )]],
+    ),
+    ("/skip", &[&[required("task-id", TASK)]]),
+    ("/mark-done", &[&[required("task-id", TASK)]]),
+    ("/retry", &[&[required("task-id", TASK)]]),
+    (
+        "/unblock",
+        &[&[required("task-id", TASK), required("answer", ArgKind::Text)]],
+    ),
+    ("/rerun", &[&[required("task-id", TASK)]]),
+    (
+        "/task-model",
+        &[&[
+            required("task-id", TASK),
+            optional("profile", ArgKind::Word),
+        ]],
+    ),
+    (
+        "/pin-models",
+        &[&[optional("off", ArgKind::Choice(&["off"]))]],
+    ),
+    (
+        "/context",
+        &[&[optional(
+            "section",
+            ArgKind::Choice(&["project", "rolling", "tasks", "planner", "reset"]),
+        )]],
+    ),
+    (
+        "/review",
+        &[
+            &[optional(
+                "action",
+                ArgKind::Choice(&["on", "off", "approve", "revert"]),
+            )],
+            &[
+                required("changes", ArgKind::Choice(&["changes"])),
+                required("feedback", ArgKind::Text),
+            ],
+        ],
+    ),
+    (
+        "/speed",
+        &[&[optional(
+            "profile",
+            ArgKind::Choice(&["eco", "standard", "max"]),
+        )]],
+    ),
+    ("/direct", &[&[required("instruction", ArgKind::Text)]]),
+    (
+        "/jobs",
+        &[
+            &[],
+            &[
+                required("move", ArgKind::Choice(&["move"])),
+                required("from", ArgKind::Number),
+                required("to", ArgKind::Number),
+            ],
+            &[
+                required("drop", ArgKind::Choice(&["drop"])),
+                required("n", ArgKind::Number),
+            ],
+        ],
+    ),
+    (
+        "/cancel",
+        &[&[required(
+            "target",
+            ArgKind::Choice(&["context", "docs", "taskcheck"]),
+        )]],
+    ),
+    (
+        "/import-plan",
+        &[&[optional("tasks", ArgKind::Choice(&["tasks"]))]],
+    ),
+    (
+        "/tasks",
+        &[&[
+            required("action", ArgKind::Choice(&["done", "reset", "delete"])),
+            required("numbers", ArgKind::Text),
+        ]],
+    ),
+    (
+        "/cache",
+        &[&[optional("purge", ArgKind::Choice(&["purge"]))]],
+    ),
+    (
+        "/plugin",
+        &[&[
+            optional("name", ArgKind::Word),
+            optional("input", ArgKind::Text),
+        ]],
+    ),
+    (
+        "/console",
+        &[
+            &[optional(
+                "action",
+                ArgKind::Choice(&["stop", "keep", "clear"]),
+            )],
+            &[
+                required("run", ArgKind::Choice(&["run"])),
+                required("command", ArgKind::Text),
+            ],
+        ],
+    ),
+];
+
+/// Values arguments are checked against.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArgContext<'a> {
+    pub task_ids: &'a [String],
+}
+
+/// What the input box shows under a slash command while its arguments are typed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgHint {
+    /// The forms still possible, e.g. `/speed [<eco|standard|max>]`.
+    pub usage: String,
+    /// Values the argument being typed can take, narrowed by what was typed so far.
+    pub candidates: Vec<String>,
+    /// Why the arguments typed so far cannot be accepted; `None` while they still can be.
+    pub problem: Option<String>,
+}
+
+/// The hint for `input` once a known command is followed by a space; `None` otherwise.
+pub fn hint(input: &str, context: ArgContext) -> Option<ArgHint> {
+    let trimmed = input.trim_start();
+    let (command, rest) = trimmed.split_once(char::is_whitespace)?;
+    let (name, forms) = COMMAND_ARGS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(command))?;
+    let tokens = rest.split_whitespace().collect::<Vec<_>>();
+    let typing_last = !rest.ends_with(char::is_whitespace) && !tokens.is_empty();
+    let matches = forms
+        .iter()
+        .map(|form| match_form(form, &tokens, typing_last, context))
+        .collect::<Vec<_>>();
+    let open = forms
+        .iter()
+        .zip(&matches)
+        .filter(|(_, matched)| matched.problem.is_none())
+        .collect::<Vec<_>>();
+    if open.is_empty() {
+        let (form, best) = forms
+            .iter()
+            .zip(&matches)
+            .max_by_key(|(_, matched)| matched.matched_tokens)?;
+        return Some(ArgHint {
+            usage: usage(name, form),
+            candidates: Vec::new(),
+            problem: best.problem.clone(),
+        });
+    }
+    let mut candidates = Vec::new();
+    for (_, matched) in &open {
+        for candidate in &matched.candidates {
+            if !candidates.contains(candidate) {
+                candidates.push(candidate.clone());
+            }
+        }
+    }
+    Some(ArgHint {
+        usage: open
+            .iter()
+            .map(|(form, _)| usage(name, form))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        candidates,
+        problem: None,
+    })
+}
+
+#[derive(Debug, Default)]
+struct FormMatch {
+    /// Tokens that fit this form before the first one that does not.
+    matched_tokens: usize,
+    candidates: Vec<String>,
+    problem: Option<String>,
+}
+
+fn match_form(
+    form: &[ArgSpec],
+    tokens: &[&str],
+    typing_last: bool,
+    context: ArgContext,
+) -> FormMatch {
+    let mut matched = FormMatch::default();
+    for (index, arg) in form.iter().enumerate() {
+        let Some(token) = tokens.get(index) else {
+            // The next argument to type.
+            matched.candidates = values(arg.kind, context);
+            return matched;
+        };
+        if arg.kind == ArgKind::Text {
+            matched.matched_tokens = tokens.len();
+            return matched;
+        }
+        let last = index + 1 == tokens.len();
+        if last && typing_last {
+            let candidates = values(arg.kind, context)
+                .into_iter()
+                .filter(|value| starts_with_ignore_case(value, token))
+                .collect::<Vec<_>>();
+            let accepted = match arg.kind {
+                ArgKind::Choice(_) | ArgKind::TaskId { .. } => !candidates.is_empty(),
+                ArgKind::Number => token.chars().all(|ch| ch.is_ascii_digit()),
+                ArgKind::Word | ArgKind::Text => true,
+            };
+            if !accepted {
+                matched.problem = Some(problem(arg, token));
+                return matched;
+            }
+            matched.matched_tokens = tokens.len();
+            matched.candidates = candidates;
+            return matched;
+        }
+        if !accepts(arg.kind, token, context) {
+            matched.problem = Some(problem(arg, token));
+            return matched;
+        }
+        matched.matched_tokens = index + 1;
+    }
+    if let Some(extra) = tokens.get(form.len()) {
+        matched.problem = Some(format!("unexpected `{extra}`"));
+    }
+    matched
+}
+
+fn accepts(kind: ArgKind, token: &str, context: ArgContext) -> bool {
+    match kind {
+        ArgKind::Choice(choices) => choices
+            .iter()
+            .any(|choice| choice.eq_ignore_ascii_case(token)),
+        ArgKind::TaskId { prefix } => token
+            .get(..prefix.len())
+            .filter(|head| head.eq_ignore_ascii_case(prefix))
+            .is_some_and(|_| {
+                context
+                    .task_ids
+                    .iter()
+                    .any(|id| *id == token[prefix.len()..])
+            }),
+        ArgKind::Number => token.parse::<usize>().is_ok_and(|number| number > 0),
+        ArgKind::Word | ArgKind::Text => true,
+    }
+}
+
+/// Every value `kind` can take, for the kinds with a known set.
+fn values(kind: ArgKind, context: ArgContext) -> Vec<String> {
+    match kind {
+        ArgKind::Choice(choices) => choices.iter().map(|choice| choice.to_string()).collect(),
+        ArgKind::TaskId { prefix } => context
+            .task_ids
+            .iter()
+            .map(|id| format!("{prefix}{id}"))
+            .collect(),
+        ArgKind::Number | ArgKind::Word | ArgKind::Text => Vec::new(),
+    }
+}
+
+fn problem(arg: &ArgSpec, token: &str) -> String {
+    match arg.kind {
+        ArgKind::Choice([choice]) => format!("expected `{choice}`, not `{token}`"),
+        ArgKind::Choice(choices) => {
+            format!("`{token}` is not one of {}", choices.join(", "))
+        }
+        ArgKind::TaskId { prefix } => match token.get(..prefix.len()) {
+            Some(head) if head.eq_ignore_ascii_case(prefix) => {
+                format!("no task with id `{}`", &token[prefix.len()..])
+            }
+            _ => format!("expected {prefix}<id>, not `{token}`"),
+        },
+        ArgKind::Number => format!("<{}> must be a position like 1 or 2", arg.name),
+        ArgKind::Word | ArgKind::Text => format!("unexpected `{token}`"),
+    }
+}
+
+fn usage(command: &str, form: &[ArgSpec]) -> String {
+    let mut usage = command.to_string();
+    for arg in form {
+        let text = match arg.kind {
+            ArgKind::Choice([choice]) => choice.to_string(),
+            ArgKind::Choice(choices) => format!("<{}>", choices.join("|")),
+            ArgKind::TaskId { prefix } => format!("{prefix}<{}>", arg.name),
+            ArgKind::Number | ArgKind::Word | ArgKind::Text => format!("<{}>", arg.name),
+        };
+        usage.push(' ');
+        if arg.required {
+            usage.push_str(&text);
+        } else {
+            usage.push_str(&format!("[{text}]"));
+        }
+    }
+    usage
+}
+
+fn starts_with_ignore_case(value: &str, prefix: &str) -> bool {
+    value
+        .get(..prefix.len())
+        .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/command_args_tests.rs"]
+mod tests;
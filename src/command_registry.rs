@@ -0,0 +1,326 @@
+use std::collections::BTreeMap;
+use std::io;
+
+use crate::artifact_io::load_merged_metaagent_config_text;
+
+#[cfg(not(test))]
+const BUILTIN_COMMANDS: [(&str, &str); 50] = [
+    ("/start", "Start execution; /start task:<id> runs one task"),
+    ("/backend", "Choose backend"),
+    ("/toggle-tests", "Toggle global tests mode"),
+    ("/planner", "Show collaborative planner markdown"),
+    ("/convert", "Convert planner markdown to tasks"),
+    ("/skip-plan", "Show task list view"),
+    ("/files", "Toggle workspace file tree"),
+    ("/stats", "Toggle failure analytics across sessions"),
+    ("/preview", "Toggle master prompt preview before sending"),
+    ("/quit", "Quit app (wait, abort, or detach a running job)"),
+    ("/exit", "Quit app"),
+    (
+        "/attach-docs",
+        "Attach docs to tasks; snapshot saves copies, task:<id> for one task",
+    ),
+    ("/newmaster", "Start a new master session"),
+    ("/new-from-template", "Start a session from a template"),
+    ("/resume", "Resume a prior session"),
+    ("/switch", "Switch to the last other session (Ctrl+Tab)"),
+    ("/split-audits", "Split audits per concern"),
+    ("/merge-audits", "Merge audits"),
+    ("/add-final-audit", "Add final audit task"),
+    ("/remove-final-audit", "Remove final audit task"),
+    ("/approve-split", "Apply the pending task split proposal"),
+    ("/reject-split", "Discard the pending task split proposal"),
+    (
+        "/accept-tasks",
+        "Keep the master's latest tasks.json changes",
+    ),
+    (
+        "/reject-tasks",
+        "Restore tasks.json from before the master's changes",
+    ),
+    ("/logs", "Open a worker output log; /logs <task-id> filters"),
+    ("/summaries", "Show a summary of each finished task"),
+    ("/reload-tasks", "Load tasks.json after an external edit"),
+    ("/keep-tasks", "Discard an external tasks.json edit"),
+    ("/conflict", "Settle an edit conflict with the master"),
+    ("/lock", "Take over or observe a session open elsewhere"),
+    ("/clear-queue", "Drop messages queued while agents are busy"),
+    ("/skip", "Skip a task you will handle yourself"),
+    ("/mark-done", "Mark a task done manually"),
+    ("/retry", "Reset a task to pending and run it again"),
+    ("/unblock", "Answer a blocked task and re-queue it"),
+    ("/rerun", "Edit and resend a task's last worker prompt"),
+    ("/task-model", "Pin a model profile to a task's worker jobs"),
+    ("/pin-models", "Keep this session's original models"),
+    ("/context", "Leave sections out of the next master prompt"),
+    ("/review", "Gate implementor passes behind a diff review"),
+    ("/speed", "Switch speed profile: eco, standard, or max"),
+    ("/direct", "Run one instruction as a task without planning"),
+    ("/jobs", "List, reorder, or drop queued worker jobs"),
+    ("/cancel", "Stop context gathering, docs, or task check"),
+    ("/import-plan", "Seed planner.md from PLAN.md or TODO.md"),
+    ("/tasks", "Mark done, reset, or delete tasks by number"),
+    ("/verbose", "Show all agent output without filters"),
+    ("/cache", "Response cache stats; /cache purge empties it"),
+    ("/plugin", "List plugins; /plugin <name> runs one"),
+    ("/console", "Toggle a workspace shell; /console run <cmd>"),
+];
+#[cfg(test)]
+const BUILTIN_COMMANDS: [(&str, &str); 52] = [
+    ("/start", "Start execution; /start task:<id> runs one task"),
+    ("/backend", "Choose backend"),
+    ("/toggle-tests", "Toggle global tests mode"),
+    ("/planner", "Show collaborative planner markdown"),
+    ("/convert", "Convert planner markdown to tasks"),
+    ("/skip-plan", "Show task list view"),
+    ("/files", "Toggle workspace file tree"),
+    ("/stats", "Toggle failure analytics across sessions"),
+    ("/preview", "Toggle master prompt preview before sending"),
+    ("/quit", "Quit app (wait, abort, or detach a running job)"),
+    ("/exit", "Quit app"),
+    (
+        "/attach-docs",
+        "Attach docs to tasks; snapshot saves copies, task:<id> for one task",
+    ),
+    ("/newmaster", "Start a new master session"),
+    ("/new-from-template", "Start a session from a template"),
+    ("/resume", "Resume a prior session"),
+    ("/switch", "Switch to the last other session (Ctrl+Tab)"),
+    ("/split-audits", "Split audits per concern"),
+    ("/merge-audits", "Merge audits"),
+    ("/split-tests", "Split tests per concern"),
+    ("/merge-tests", "Merge tests"),
+    ("/add-final-audit", "Add final audit task"),
+    ("/remove-final-audit", "Remove final audit task"),
+    ("/approve-split", "Apply the pending task split proposal"),
+    ("/reject-split", "Discard the pending task split proposal"),
+    (
+        "/accept-tasks",
+        "Keep the master's latest tasks.json changes",
+    ),
+    (
+        "/reject-tasks",
+        "Restore tasks.json from before the master's changes",
+    ),
+    ("/logs", "Open a worker output log; /logs <task-id> filters"),
+    ("/summaries", "Show a summary of each finished task"),
+    ("/reload-tasks", "Load tasks.json after an external edit"),
+    ("/keep-tasks", "Discard an external tasks.json edit"),
+    ("/conflict", "Settle an edit conflict with the master"),
+    ("/lock", "Take over or observe a session open elsewhere"),
+    ("/clear-queue", "Drop messages queued while agents are busy"),
+    ("/skip", "Skip a task you will handle yourself"),
+    ("/mark-done", "Mark a task done manually"),
+    ("/retry", "Reset a task to pending and run it again"),
+    ("/unblock", "Answer a blocked task and re-queue it"),
+    ("/rerun", "Edit and resend a task's last worker prompt"),
+    ("/task-model", "Pin a model profile to a task's worker jobs"),
+    ("/pin-models", "Keep this session's original models"),
+    ("/context", "Leave sections out of the next master prompt"),
+    ("/review", "Gate implementor passes behind a diff review"),
+    ("/speed", "Switch speed profile: eco, standard, or max"),
+    ("/direct", "Run one instruction as a task without planning"),
+    ("/jobs", "List, reorder, or drop queued worker jobs"),
+    ("/cancel", "Stop context gathering, docs, or task check"),
+    ("/import-plan", "Seed planner.md from PLAN.md or TODO.md"),
+    ("/tasks", "Mark done, reset, or delete tasks by number"),
+    ("/verbose", "Show all agent output without filters"),
+    ("/cache", "Response cache stats; /cache purge empties it"),
+    ("/plugin", "List plugins; /plugin <name> runs one"),
+    ("/console", "Toggle a workspace shell; /console run <cmd>"),
+];
+
+/// A command the `/` index offers for the typed prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandSuggestion {
+    pub command: String,
+    pub description: String,
+}
+
+/// What a command from the `[commands]` config table stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserCommand {
+    /// `[commands.aliases]`: a built-in command, optionally with arguments. Arguments typed
+    /// after the alias are appended.
+    Alias(String),
+    /// `[commands.macros]`: messages and commands sent in order, each once Bob can take it.
+    Macro(Vec<String>),
+}
+
+/// Built-in slash commands plus the aliases and macros the config defines.
+#[derive(Debug, Clone, Default)]
+pub struct CommandRegistry {
+    /// By lowercase name, including the leading `/`.
+    user: BTreeMap<String, UserCommand>,
+}
+
+impl CommandRegistry {
+    pub fn load() -> io::Result<Self> {
+        Self::from_toml_str(&load_merged_metaagent_config_text()?)
+    }
+
+    /// Reads `[commands.aliases]` (name to command) and `[commands.macros]` (name to a list
+    /// of messages and commands). Names may be written with or without the leading `/`.
+    pub fn from_toml_str(text: &str) -> io::Result<Self> {
+        let parsed = toml::from_str::<toml::Value>(text).map_err(invalid_data)?;
+        let Some(commands) = parsed.get("commands") else {
+            return Ok(Self::default());
+        };
+        let commands = commands
+            .as_table()
+            .ok_or_else(|| invalid_data("[commands] must be a table"))?;
+        let mut user = BTreeMap::new();
+        for (key, value) in table(commands, "aliases")? {
+            let name = command_name("aliases", key)?;
+            let target = value
+                .as_str()
+                .map(str::trim)
+                .filter(|target| target.starts_with('/'))
+                .ok_or_else(|| {
+                    invalid_data(format!(
+                        "commands.aliases.{key} must be a slash command such as \"/start\" (got {value})"
+                    ))
+                })?;
+            user.insert(name, UserCommand::Alias(target.to_string()));
+        }
+        for (key, value) in table(commands, "macros")? {
+            let name = command_name("macros", key)?;
+            let invalid = || {
+                invalid_data(format!(
+                    "commands.macros.{key} must be a non-empty list of messages and commands (got {value})"
+                ))
+            };
+            let steps = value
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(|step| {
+                    step.as_str()
+                        .map(str::trim)
+                        .filter(|step| !step.is_empty())
+                        .map(str::to_string)
+                        .ok_or_else(invalid)
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+            if steps.is_empty() {
+                return Err(invalid());
+            }
+            if user.contains_key(&name) {
+                return Err(invalid_data(format!(
+                    "{name} is defined both in commands.aliases and commands.macros"
+                )));
+            }
+            user.insert(name, UserCommand::Macro(steps));
+        }
+        // Expansion is one level deep, so user commands may only point at built-ins.
+        for (name, command) in &user {
+            let targets = match command {
+                UserCommand::Alias(target) => std::slice::from_ref(target),
+                UserCommand::Macro(steps) => steps.as_slice(),
+            };
+            if let Some(target) = targets
+                .iter()
+                .find(|target| user.contains_key(&command_word(target)))
+            {
+                return Err(invalid_data(format!(
+                    "{name} uses {target}, which is itself an alias or macro"
+                )));
+            }
+        }
+        Ok(Self { user })
+    }
+
+    /// Built-in commands starting with `query`, then aliases and macros.
+    pub fn suggestions(&self, query: &str) -> Vec<CommandSuggestion> {
+        let builtins = BUILTIN_COMMANDS
+            .iter()
+            .filter(|(command, _)| command.starts_with(query))
+            .map(|(command, description)| CommandSuggestion {
+                command: command.to_string(),
+                description: description.to_string(),
+            });
+        let user = self
+            .user
+            .iter()
+            .filter(|(name, _)| name.starts_with(query))
+            .map(|(name, command)| CommandSuggestion {
+                command: name.clone(),
+                description: match command {
+                    UserCommand::Alias(target) => format!("Alias for {target}"),
+                    UserCommand::Macro(steps) => format!("Macro: {}", steps.join("; ")),
+                },
+            });
+        builtins.chain(user).collect()
+    }
+
+    /// What `message` expands to when it starts with an alias or is a macro; `None` for
+    /// anything else, including a macro followed by arguments.
+    pub fn expand(&self, message: &str) -> Option<UserCommand> {
+        let message = message.trim();
+        match self.user.get(&command_word(message))? {
+            UserCommand::Alias(_) => self
+                .expand_alias(message)
+                .map(|command| UserCommand::Alias(command.trim_end().to_string())),
+            UserCommand::Macro(steps) => {
+                (message.split_whitespace().count() == 1).then(|| UserCommand::Macro(steps.clone()))
+            }
+        }
+    }
+
+    /// `input` with a leading alias replaced by its command, keeping what follows as typed.
+    pub fn expand_alias(&self, input: &str) -> Option<String> {
+        let input = input.trim_start();
+        let end = input.find(char::is_whitespace).unwrap_or(input.len());
+        match self.user.get(&input[..end].to_ascii_lowercase())? {
+            UserCommand::Alias(target) => Some(format!("{target}{}", &input[end..])),
+            UserCommand::Macro(_) => None,
+        }
+    }
+}
+
+/// Entries of the `[commands.<key>]` table; none when it is absent.
+fn table<'a>(
+    commands: &'a toml::Table,
+    key: &str,
+) -> io::Result<Vec<(&'a String, &'a toml::Value)>> {
+    let Some(value) = commands.get(key) else {
+        return Ok(Vec::new());
+    };
+    let entries = value
+        .as_table()
+        .ok_or_else(|| invalid_data(format!("commands.{key} must be a table")))?;
+    Ok(entries.iter().collect())
+}
+
+/// `/name` for a config key, rejecting names that are not one word or that would hide a
+/// built-in command.
+fn command_name(section: &str, key: &str) -> io::Result<String> {
+    let name = format!("/{}", key.trim().trim_start_matches('/')).to_ascii_lowercase();
+    if name.len() == 1 || name.contains(char::is_whitespace) {
+        return Err(invalid_data(format!(
+            "commands.{section}.{key} must be a single word"
+        )));
+    }
+    if BUILTIN_COMMANDS.iter().any(|(command, _)| *command == name) {
+        return Err(invalid_data(format!(
+            "commands.{section}.{key} would hide the built-in {name}"
+        )));
+    }
+    Ok(name)
+}
+
+fn command_word(message: &str) -> String {
+    message
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/command_registry_tests.rs"]
+mod tests;
@@ -0,0 +1,500 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::artifact_io::{read_text_file, runtime_storage_dir, write_text_file_atomic};
+use crate::audit_trail::sha256;
+
+/// Service name every secret is filed under in the OS keychain; the account is the
+/// credential's own service name (e.g. `linear`).
+const KEYCHAIN_SERVICE: &str = "agentbob";
+const STORE_FILE_NAME: &str = "credentials.json";
+const CHECK_TEXT: &str = "agentbob credentials";
+const KDF_ITERATIONS: u32 = 100_000;
+
+/// Passphrase of the encrypted credentials file, used when no keychain is available.
+pub const PASSPHRASE_ENV: &str = "AGENTBOB_CREDENTIALS_PASSPHRASE";
+
+/// Credential names read by the built-in integrations.
+pub const LINEAR_TOKEN: &str = "linear";
+pub const JIRA_TOKEN: &str = "jira";
+pub const SMTP_PASSWORD: &str = "smtp";
+pub const STORAGE_SECRET: &str = "storage";
+
+/// Where `bob auth set` stored a secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialLocation {
+    Keychain,
+    EncryptedFile,
+}
+
+impl CredentialLocation {
+    pub fn label(self) -> &'static str {
+        match self {
+            CredentialLocation::Keychain => "the OS keychain",
+            CredentialLocation::EncryptedFile => "the encrypted credentials file",
+        }
+    }
+}
+
+/// Service names use lower-case letters, digits, '-' or '_', like plugin names.
+pub fn validate_service(service: &str) -> Result<(), String> {
+    let valid = !service.is_empty()
+        && service
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "credential name \"{service}\" must use lower-case letters, digits, '-' or '_'"
+        ))
+    }
+}
+
+/// Stores `secret` in the OS keychain, or in the passphrase-encrypted credentials file when no
+/// keychain tool is installed or it refuses the write. Fails when neither is available.
+pub fn set_credential(service: &str, secret: &str) -> Result<CredentialLocation, String> {
+    validate_service(service)?;
+    if secret.is_empty() {
+        return Err(format!("No secret given for {service}"));
+    }
+    let store = CredentialFile::open_default()?;
+    if keychain_set(service, secret) {
+        let _ = store.remove(service);
+        return Ok(CredentialLocation::Keychain);
+    }
+    store.set(service, secret)?;
+    Ok(CredentialLocation::EncryptedFile)
+}
+
+/// Removes `service` from the keychain and the credentials file; false when neither had it.
+pub fn remove_credential(service: &str) -> Result<bool, String> {
+    validate_service(service)?;
+    let from_keychain = keychain_remove(service);
+    let from_file = CredentialFile::open_default()?.remove(service)?;
+    Ok(from_keychain || from_file)
+}
+
+/// Secret stored for `service`, looked up in the keychain first. Lookup failures count as
+/// not stored, so callers fall back to their own "not configured" errors.
+pub fn lookup_credential(service: &str) -> Option<String> {
+    if let Some(secret) = keychain_get(service) {
+        return Some(secret);
+    }
+    CredentialFile::open_default()
+        .and_then(|store| store.get(service))
+        .ok()
+        .flatten()
+}
+
+/// Fills an empty config secret from the credential store.
+pub fn fill_from_store(value: &mut String, service: &str) {
+    if value.trim().is_empty()
+        && let Some(secret) = lookup_credential(service)
+    {
+        *value = secret;
+    }
+}
+
+/// Runs a keychain tool; `None` when it is not installed or exits unsuccessfully, e.g. for a
+/// missing entry.
+fn run_keychain_tool(program: &str, args: &[&str], stdin: Option<&str>) -> Option<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    if let (Some(text), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(text.as_bytes()).ok()?;
+    }
+    let output = child.wait_with_output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Quotes one argument of a `security -i` command line.
+#[cfg(target_os = "macos")]
+fn security_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// macOS: the secret goes through `security -i` on stdin so it stays off the command line.
+#[cfg(target_os = "macos")]
+fn keychain_set(service: &str, secret: &str) -> bool {
+    let command = format!(
+        "add-generic-password -U -s {} -a {} -w {}\n",
+        security_quote(KEYCHAIN_SERVICE),
+        security_quote(service),
+        security_quote(secret)
+    );
+    run_keychain_tool("security", &["-i"], Some(&command)).is_some()
+}
+
+#[cfg(target_os = "macos")]
+fn keychain_get(service: &str) -> Option<String> {
+    run_keychain_tool(
+        "security",
+        &[
+            "find-generic-password",
+            "-s",
+            KEYCHAIN_SERVICE,
+            "-a",
+            service,
+            "-w",
+        ],
+        None,
+    )
+    .map(|secret| secret.trim_end_matches('\n').to_string())
+    .filter(|secret| !secret.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn keychain_remove(service: &str) -> bool {
+    run_keychain_tool(
+        "security",
+        &[
+            "delete-generic-password",
+            "-s",
+            KEYCHAIN_SERVICE,
+            "-a",
+            service,
+        ],
+        None,
+    )
+    .is_some()
+}
+
+/// Elsewhere: libsecret's `secret-tool`, which reads the secret from stdin.
+#[cfg(not(target_os = "macos"))]
+fn keychain_set(service: &str, secret: &str) -> bool {
+    let label = format!("{KEYCHAIN_SERVICE} {service}");
+    run_keychain_tool(
+        "secret-tool",
+        &[
+            "store",
+            "--label",
+            &label,
+            "service",
+            KEYCHAIN_SERVICE,
+            "account",
+            service,
+        ],
+        Some(secret),
+    )
+    .is_some()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn keychain_get(service: &str) -> Option<String> {
+    run_keychain_tool(
+        "secret-tool",
+        &["lookup", "service", KEYCHAIN_SERVICE, "account", service],
+        None,
+    )
+    .map(|secret| secret.trim_end_matches('\n').to_string())
+    .filter(|secret| !secret.is_empty())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn keychain_remove(service: &str) -> bool {
+    keychain_get(service).is_some()
+        && run_keychain_tool(
+            "secret-tool",
+            &["clear", "service", KEYCHAIN_SERVICE, "account", service],
+            None,
+        )
+        .is_some()
+}
+
+/// One secret in `credentials.json`: a SHA-256 keystream cipher with an HMAC over nonce and
+/// ciphertext, all hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SealedSecret {
+    nonce: String,
+    ciphertext: String,
+    mac: String,
+}
+
+/// The contents of `credentials.json`. The key is derived from the passphrase with
+/// PBKDF2-HMAC-SHA256 and never written down.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct StoreFile {
+    salt: String,
+    iterations: u32,
+    /// Seals `CHECK_TEXT`, so a wrong passphrase is caught before a secret is added under it.
+    check: SealedSecret,
+    secrets: BTreeMap<String, SealedSecret>,
+}
+
+/// `credentials.json` next to `config.toml`, for machines without a keychain. Secrets are
+/// encrypted with a key derived from the `AGENTBOB_CREDENTIALS_PASSPHRASE` passphrase; without
+/// one, nothing can be stored or read back.
+#[derive(Debug, Clone)]
+pub struct CredentialFile {
+    dir: PathBuf,
+    passphrase: Option<String>,
+    iterations: u32,
+}
+
+impl CredentialFile {
+    pub fn new(dir: &Path, passphrase: Option<String>) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            passphrase: passphrase.filter(|passphrase| !passphrase.is_empty()),
+            iterations: KDF_ITERATIONS,
+        }
+    }
+
+    fn open_default() -> Result<Self, String> {
+        runtime_storage_dir()
+            .map(|dir| Self::new(&dir, std::env::var(PASSPHRASE_ENV).ok()))
+            .map_err(|err| format!("Failed to locate the credentials file: {err}"))
+    }
+
+    pub fn get(&self, service: &str) -> Result<Option<String>, String> {
+        let Some(store) = self.read_store()? else {
+            return Ok(None);
+        };
+        let Some(sealed) = store.secrets.get(service) else {
+            return Ok(None);
+        };
+        let key = self.unlock(&store)?;
+        unseal(&key, sealed)
+            .map(Some)
+            .map_err(|err| format!("Failed to decrypt the {service} credential: {err}"))
+    }
+
+    pub fn set(&self, service: &str, secret: &str) -> Result<(), String> {
+        let (mut store, key) = match self.read_store()? {
+            Some(store) => {
+                let key = self.unlock(&store)?;
+                (store, key)
+            }
+            None => self.create_store()?,
+        };
+        store
+            .secrets
+            .insert(service.to_string(), seal(&key, secret)?);
+        self.write_store(&store)
+    }
+
+    /// False when `service` was not stored. Needs no passphrase.
+    pub fn remove(&self, service: &str) -> Result<bool, String> {
+        let Some(mut store) = self.read_store()? else {
+            return Ok(false);
+        };
+        if store.secrets.remove(service).is_none() {
+            return Ok(false);
+        }
+        self.write_store(&store)?;
+        Ok(true)
+    }
+
+    fn passphrase(&self) -> Result<&str, String> {
+        self.passphrase.as_deref().ok_or_else(|| {
+            format!(
+                "No OS keychain is available; set {PASSPHRASE_ENV} to use the encrypted credentials file"
+            )
+        })
+    }
+
+    /// Derives the key and checks it against the store's check entry. A store asking for
+    /// another round count is refused rather than derived: fewer rounds weaken the key, and a
+    /// huge count would hang every read.
+    fn unlock(&self, store: &StoreFile) -> Result<[u8; 32], String> {
+        if store.iterations != self.iterations {
+            return Err(format!(
+                "{STORE_FILE_NAME} uses {} key derivation rounds; only {} are supported",
+                store.iterations, self.iterations
+            ));
+        }
+        let salt = decode_hex(&store.salt)
+            .ok_or_else(|| format!("{STORE_FILE_NAME} has an invalid salt"))?;
+        let key = cached_derive_key(self.passphrase()?, &salt, store.iterations);
+        match unseal(&key, &store.check) {
+            Ok(text) if text == CHECK_TEXT => Ok(key),
+            _ => Err(format!(
+                "{PASSPHRASE_ENV} does not unlock {STORE_FILE_NAME}"
+            )),
+        }
+    }
+
+    fn create_store(&self) -> Result<(StoreFile, [u8; 32]), String> {
+        let passphrase = self.passphrase()?;
+        let salt = random_bytes::<16>()?;
+        let key = derive_key(passphrase, &salt, self.iterations);
+        let store = StoreFile {
+            salt: encode_hex(&salt),
+            iterations: self.iterations,
+            check: seal(&key, CHECK_TEXT)?,
+            secrets: BTreeMap::new(),
+        };
+        Ok((store, key))
+    }
+
+    fn read_store(&self) -> Result<Option<StoreFile>, String> {
+        let path = self.dir.join(STORE_FILE_NAME);
+        match read_text_file(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map(Some)
+                .map_err(|err| format!("Failed to parse {}: {err}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("Failed to read {}: {err}", path.display())),
+        }
+    }
+
+    fn write_store(&self, store: &StoreFile) -> Result<(), String> {
+        let path = self.dir.join(STORE_FILE_NAME);
+        let text = serde_json::to_string_pretty(store)
+            .map_err(|err| format!("Failed to serialize credentials: {err}"))?;
+        write_text_file_atomic(&path, &text)
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+    }
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let hashed;
+    let key = if key.len() > 64 {
+        hashed = sha256(key);
+        &hashed[..]
+    } else {
+        key
+    };
+    let mut inner = vec![0x36u8; 64];
+    let mut outer = vec![0x5cu8; 64];
+    for (index, byte) in key.iter().enumerate() {
+        inner[index] ^= byte;
+        outer[index] ^= byte;
+    }
+    inner.extend_from_slice(message);
+    outer.extend_from_slice(&sha256(&inner));
+    sha256(&outer)
+}
+
+/// PBKDF2-HMAC-SHA256 with a single 32-byte output block.
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut input = salt.to_vec();
+    input.extend_from_slice(&1u32.to_be_bytes());
+    let mut block = hmac_sha256(passphrase.as_bytes(), &input);
+    let mut key = block;
+    for _ in 1..iterations {
+        block = hmac_sha256(passphrase.as_bytes(), &block);
+        for (byte, next) in key.iter_mut().zip(block) {
+            *byte ^= next;
+        }
+    }
+    key
+}
+
+/// `derive_key` remembering its last result, since every lookup of a stored secret unlocks the
+/// file again.
+fn cached_derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    static LAST: Mutex<Option<([u8; 32], [u8; 32])>> = Mutex::new(None);
+    let mut input = sha256(passphrase.as_bytes()).to_vec();
+    input.extend_from_slice(salt);
+    input.extend_from_slice(&iterations.to_be_bytes());
+    let id = sha256(&input);
+    let mut last = LAST.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((cached_id, key)) = *last
+        && cached_id == id
+    {
+        return key;
+    }
+    let key = derive_key(passphrase, salt, iterations);
+    *last = Some((id, key));
+    key
+}
+
+/// XORs `data` with HMAC-SHA256(encryption key, nonce || block counter) blocks.
+fn apply_keystream(key: &[u8; 32], nonce: &[u8], data: &mut [u8]) {
+    let encryption_key = hmac_sha256(key, b"agentbob credentials encryption");
+    for (counter, chunk) in data.chunks_mut(32).enumerate() {
+        let mut input = nonce.to_vec();
+        input.extend_from_slice(&(counter as u64).to_be_bytes());
+        let block = hmac_sha256(&encryption_key, &input);
+        for (byte, pad) in chunk.iter_mut().zip(block) {
+            *byte ^= pad;
+        }
+    }
+}
+
+fn secret_mac(key: &[u8; 32], nonce: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    let mac_key = hmac_sha256(key, b"agentbob credentials authentication");
+    let mut input = nonce.to_vec();
+    input.extend_from_slice(ciphertext);
+    hmac_sha256(&mac_key, &input)
+}
+
+fn seal(key: &[u8; 32], secret: &str) -> Result<SealedSecret, String> {
+    let nonce = random_bytes::<16>()?;
+    let mut ciphertext = secret.as_bytes().to_vec();
+    apply_keystream(key, &nonce, &mut ciphertext);
+    Ok(SealedSecret {
+        nonce: encode_hex(&nonce),
+        mac: encode_hex(&secret_mac(key, &nonce, &ciphertext)),
+        ciphertext: encode_hex(&ciphertext),
+    })
+}
+
+fn unseal(key: &[u8; 32], sealed: &SealedSecret) -> Result<String, String> {
+    let (Some(nonce), Some(mut data), Some(mac)) = (
+        decode_hex(&sealed.nonce),
+        decode_hex(&sealed.ciphertext),
+        decode_hex(&sealed.mac),
+    ) else {
+        return Err("entry is not valid hex".to_string());
+    };
+    let expected = secret_mac(key, &nonce, &data);
+    let mismatch = mac.len() != expected.len()
+        || mac
+            .iter()
+            .zip(expected)
+            .fold(0u8, |acc, (left, right)| acc | (left ^ right))
+            != 0;
+    if mismatch {
+        return Err("entry was modified or encrypted with another key".to_string());
+    }
+    apply_keystream(key, &nonce, &mut data);
+    String::from_utf8(data).map_err(|_| "entry is not UTF-8".to_string())
+}
+
+/// Bytes from the OS random source. There is no fallback: a guessable salt or nonce would
+/// weaken every secret sealed with it.
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut bytes = [0u8; N];
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut file| file.read_exact(&mut bytes))
+        .map_err(|err| format!("Failed to read random bytes from /dev/urandom: {err}"))?;
+    Ok(bytes)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/credentials_tests.rs"]
+mod tests;
@@ -1,9 +1,19 @@
 use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
 use std::thread;
 
-use crate::agent::AgentEvent;
+use crate::agent::{AgentEvent, set_running_pid, terminate_running};
+use crate::i18n::Locale;
+use crate::plugins::{self, PluginRunRequest};
+use crate::telemetry::{self, SpanKind};
+use crate::test_runners::{
+    DeterministicRunner, RunnerCommand, RunnerRegistry, TestRunReport, TestRunnerSpec,
+};
+use crate::wakeup::{self, Sender};
 
 #[derive(Debug, Clone)]
 pub struct TestRunnerConfig {
@@ -23,55 +33,153 @@ impl Default for TestRunnerConfig {
 pub struct TestRunnerAdapter {
     #[cfg(test)]
     config: TestRunnerConfig,
+    registry: RunnerRegistry,
     event_tx: Sender<AgentEvent>,
     event_rx: Receiver<AgentEvent>,
+    running_pid: Arc<Mutex<Option<u32>>>,
+    /// Re-run a failing test command once to tell flaky failures from real ones.
+    rerun_failures: bool,
+    /// Set before `Completed` when the re-run's result differed from the first run's.
+    last_run_flaky: Arc<AtomicBool>,
+    /// Test cases the runner recognized in the reported run, set before `Completed`.
+    last_report: Arc<Mutex<Option<TestRunReport>>>,
+    /// Directory test commands run in; `None` runs them in the process working directory.
+    workdir: Option<PathBuf>,
+}
+
+/// Exit of one test command run; `code` is negative when the command could not run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RunExit {
+    success: bool,
+    code: i32,
 }
 
 impl TestRunnerAdapter {
     pub fn new() -> Self {
-        let (event_tx, event_rx) = mpsc::channel();
+        let (event_tx, event_rx) = wakeup::channel();
         Self {
             #[cfg(test)]
             config: TestRunnerConfig::default(),
+            registry: RunnerRegistry::register_defaults(),
             event_tx,
             event_rx,
+            running_pid: Arc::new(Mutex::new(None)),
+            rerun_failures: false,
+            last_run_flaky: Arc::new(AtomicBool::new(false)),
+            last_report: Arc::new(Mutex::new(None)),
+            workdir: None,
         }
     }
 
     #[cfg(test)]
     pub fn with_config(config: TestRunnerConfig) -> Self {
-        let (event_tx, event_rx) = mpsc::channel();
+        let (event_tx, event_rx) = wakeup::channel();
         Self {
             config,
+            registry: RunnerRegistry::register_defaults(),
             event_tx,
             event_rx,
+            running_pid: Arc::new(Mutex::new(None)),
+            rerun_failures: false,
+            last_run_flaky: Arc::new(AtomicBool::new(false)),
+            last_report: Arc::new(Mutex::new(None)),
+            workdir: None,
         }
     }
 
+    pub fn set_rerun_failures(&mut self, enabled: bool) {
+        self.rerun_failures = enabled;
+    }
+
+    pub fn set_workdir(&mut self, workdir: Option<PathBuf>) {
+        self.workdir = workdir;
+    }
+
+    /// Whether the last finished run failed and then gave a different result on its re-run.
+    /// Clears the flag.
+    pub fn take_last_run_flaky(&self) -> bool {
+        self.last_run_flaky.swap(false, Ordering::SeqCst)
+    }
+
+    /// Test cases recognized in the last finished run, if the runner found any. Clears them.
+    pub fn take_last_report(&self) -> Option<TestRunReport> {
+        self.last_report
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take()
+    }
+
     #[cfg(test)]
     pub fn run_tests(&self) {
-        Self::spawn_run(self.config.clone(), self.event_tx.clone());
+        let spec = TestRunnerSpec::shell("");
+        let runner = self.registry.get(&spec.kind).expect("shell runner");
+        let command = RunnerCommand {
+            config: self.config.clone(),
+            env: Vec::new(),
+            report_file: None,
+            cleanup: None,
+        };
+        self.spawn_run(runner, spec, command, self.event_tx.clone());
     }
 
+    #[cfg(test)]
     pub fn run_tests_with_command(&self, command: Option<&str>) {
+        let spec = command
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(TestRunnerSpec::shell);
+        self.run_tests_with_spec(spec.as_ref());
+    }
+
+    /// Runs the session's tests with the registered runner for `spec`'s kind.
+    pub fn run_tests_with_spec(&self, spec: Option<&TestRunnerSpec>) {
         let tx = self.event_tx.clone();
-        let normalized = command.map(str::trim).filter(|value| !value.is_empty());
-        if let Some(command_line) = normalized {
-            let config = TestRunnerConfig {
-                program: "bash".to_string(),
-                args: vec!["-lc".to_string(), command_line.to_string()],
-            };
-            Self::spawn_run(config, tx);
-        } else {
-            let _ = tx.send(AgentEvent::System(
-                "Deterministic test runner failed: no test command configured in meta.json."
-                    .to_string(),
-            ));
+        let Some(spec) = spec else {
+            send_setup_failure(&tx, "no test command configured in meta.json".to_string());
+            return;
+        };
+        match self.registry.resolve(spec) {
+            Ok((runner, command)) => self.spawn_run(runner, spec.clone(), command, tx),
+            Err(err) => send_setup_failure(&tx, err),
+        }
+    }
+
+    /// Runs a workflow-step plugin. Its output and findings are streamed as output lines and
+    /// its verdict completes the job like a test run; `/stop` terminates it the same way.
+    pub fn run_plugin_step(&self, name: &str, program: Option<&Path>, request: PluginRunRequest) {
+        let tx = self.event_tx.clone();
+        let Some(program) = program else {
+            let _ = tx.send(AgentEvent::Output(format!(
+                "Plugin {name} is not installed or cannot run as a workflow step."
+            )));
             let _ = tx.send(AgentEvent::Completed {
                 success: false,
                 code: -2,
             });
-        }
+            return;
+        };
+        let name = name.to_string();
+        let program = program.to_path_buf();
+        let running_pid = self.running_pid.clone();
+        thread::spawn(move || {
+            let mut on_line = |line: String| {
+                let _ = tx.send(AgentEvent::Output(line));
+            };
+            let (success, code) = match plugins::run(&program, &request, &running_pid, &mut on_line)
+            {
+                Ok(response) => {
+                    for line in plugins::response_message(Locale::En, &name, &response).lines() {
+                        let _ = tx.send(AgentEvent::Output(line.to_string()));
+                    }
+                    (response.passed(), if response.passed() { 0 } else { 1 })
+                }
+                Err(err) => {
+                    let _ = tx.send(AgentEvent::Output(format!("Plugin {name} failed: {err}.")));
+                    (false, -1)
+                }
+            };
+            let _ = tx.send(AgentEvent::Completed { success, code });
+        });
     }
 
     #[cfg(test)]
@@ -90,79 +198,207 @@ impl TestRunnerAdapter {
             };
             events.push(event);
         }
+        if events.len() == max_events {
+            wakeup::notify();
+        }
         events
     }
 
-    fn spawn_run(config: TestRunnerConfig, tx: Sender<AgentEvent>) {
+    /// Sends SIGTERM to a running test command. Returns false when nothing is running.
+    pub fn terminate(&self) -> bool {
+        terminate_running(&self.running_pid)
+    }
+
+    fn spawn_run(
+        &self,
+        runner: Arc<dyn DeterministicRunner>,
+        spec: TestRunnerSpec,
+        command: RunnerCommand,
+        tx: Sender<AgentEvent>,
+    ) {
+        let running_pid = self.running_pid.clone();
+        let rerun_failures = self.rerun_failures;
+        let last_run_flaky = self.last_run_flaky.clone();
+        last_run_flaky.store(false, Ordering::SeqCst);
+        let last_report = self.last_report.clone();
+        *last_report.lock().unwrap_or_else(|err| err.into_inner()) = None;
+        let trace_parent = telemetry::job_context();
+        let workdir = self.workdir.clone();
         thread::spawn(move || {
-            let mut command = Command::new(&config.program);
-            command
-                .args(&config.args)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
-
-            let mut child = match command.spawn() {
-                Ok(child) => child,
-                Err(err) => {
+            let mut span = telemetry::child_span("test_runner.run", SpanKind::Client, trace_parent);
+            span.set_attribute("test_runner.kind", runner.kind());
+            span.set_attribute("test_runner.command", command.config.args.join(" "));
+            let (mut exit, mut report) = run_once(
+                &*runner,
+                &spec,
+                &command,
+                workdir.as_deref(),
+                &tx,
+                &running_pid,
+            );
+            if rerun_failures && !exit.success && exit.code >= 0 {
+                let _ = tx.send(AgentEvent::System(format!(
+                    "Deterministic test runner exited with status code {}; re-running once to check for flaky tests.",
+                    exit.code
+                )));
+                let (rerun, rerun_report) = run_once(
+                    &*runner,
+                    &spec,
+                    &command,
+                    workdir.as_deref(),
+                    &tx,
+                    &running_pid,
+                );
+                span.set_attribute("test_runner.rerun_exit_code", rerun.code);
+                if rerun != exit && rerun.code >= 0 {
+                    span.set_attribute("test_runner.flaky", true);
+                    last_run_flaky.store(true, Ordering::SeqCst);
                     let _ = tx.send(AgentEvent::System(format!(
-                        "Deterministic test runner failed to start: {err}"
+                        "Deterministic test re-run exited with status code {}; classifying the failure as flaky.",
+                        rerun.code
                     )));
-                    let _ = tx.send(AgentEvent::Completed {
-                        success: false,
-                        code: -1,
-                    });
-                    return;
+                } else {
+                    exit = rerun;
+                    report = rerun_report;
                 }
-            };
-
-            let mut readers = Vec::new();
-            if let Some(stdout) = child.stdout.take() {
-                readers.push(spawn_reader(stdout, tx.clone()));
             }
-            if let Some(stderr) = child.stderr.take() {
-                readers.push(spawn_reader(stderr, tx.clone()));
+            if let Some(cleanup) = command.cleanup.as_ref() {
+                run_cleanup(cleanup, workdir.as_deref());
             }
-
-            let wait_result = child.wait();
-            for reader in readers {
-                let _ = reader.join();
+            if !report.cases.is_empty() {
+                let _ = tx.send(AgentEvent::System(format!(
+                    "Deterministic test results ({}).",
+                    report.summary()
+                )));
+                *last_report.lock().unwrap_or_else(|err| err.into_inner()) = Some(report);
             }
-            match wait_result {
-                Ok(status) => {
-                    let code = status.code().unwrap_or(-1);
-                    let _ = tx.send(AgentEvent::Completed {
-                        success: status.success(),
-                        code,
-                    });
-                    if !status.success() {
-                        let _ = tx.send(AgentEvent::System(format!(
-                            "Deterministic test runner exited with status code {code}"
-                        )));
-                    }
-                }
-                Err(err) => {
-                    let _ = tx.send(AgentEvent::System(format!(
-                        "Deterministic test runner wait failed: {err}"
-                    )));
-                    let _ = tx.send(AgentEvent::Completed {
-                        success: false,
-                        code: -1,
-                    });
-                }
+            span.set_attribute("process.exit_code", exit.code);
+            if !exit.success {
+                span.set_error(format!("exited with status code {}", exit.code));
+            }
+            span.end();
+            // A flaky run still reports the first failure, since the suite needs fixing.
+            let _ = tx.send(AgentEvent::Completed {
+                success: exit.success,
+                code: exit.code,
+            });
+            if !exit.success && exit.code >= 0 {
+                let _ = tx.send(AgentEvent::System(format!(
+                    "Deterministic test runner exited with status code {}",
+                    exit.code
+                )));
             }
         });
     }
 }
 
-fn spawn_reader<R: std::io::Read + Send + 'static>(
+fn send_setup_failure(tx: &Sender<AgentEvent>, reason: String) {
+    let _ = tx.send(AgentEvent::System(format!(
+        "Deterministic test runner failed: {reason}."
+    )));
+    let _ = tx.send(AgentEvent::Completed {
+        success: false,
+        code: -2,
+    });
+}
+
+/// Runs the test command to completion, streaming the output lines `runner` shows as
+/// events, and returns its exit with the test cases the runner recognized.
+fn run_once(
+    runner: &dyn DeterministicRunner,
+    spec: &TestRunnerSpec,
+    runner_command: &RunnerCommand,
+    workdir: Option<&Path>,
+    tx: &Sender<AgentEvent>,
+    running_pid: &Arc<Mutex<Option<u32>>>,
+) -> (RunExit, TestRunReport) {
+    let config = &runner_command.config;
+    let mut command = Command::new(&config.program);
+    command
+        .args(&config.args)
+        .envs(runner_command.env.iter().map(|(key, value)| (key, value)))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(workdir) = workdir {
+        command.current_dir(workdir);
+    }
+
+    let report = Arc::new(Mutex::new(TestRunReport::new(runner.kind())));
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            let _ = tx.send(AgentEvent::System(format!(
+                "Deterministic test runner failed to start: {err}"
+            )));
+            let exit = RunExit {
+                success: false,
+                code: -1,
+            };
+            return (exit, TestRunReport::new(runner.kind()));
+        }
+    };
+    set_running_pid(running_pid, Some(child.id()));
+
+    // Readers parse on their own threads; scoped so they can borrow the runner.
+    let wait_result = thread::scope(|scope| {
+        if let Some(stdout) = child.stdout.take() {
+            spawn_reader(scope, stdout, runner, &report, tx.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_reader(scope, stderr, runner, &report, tx.clone());
+        }
+        let wait_result = child.wait();
+        set_running_pid(running_pid, None);
+        wait_result
+    });
+    let exit = match wait_result {
+        Ok(status) => RunExit {
+            success: status.success(),
+            code: status.code().unwrap_or(-1),
+        },
+        Err(err) => {
+            let _ = tx.send(AgentEvent::System(format!(
+                "Deterministic test runner wait failed: {err}"
+            )));
+            RunExit {
+                success: false,
+                code: -1,
+            }
+        }
+    };
+    let mut report = report.lock().unwrap_or_else(|err| err.into_inner()).clone();
+    runner.finish(spec, runner_command, exit.success, &mut report);
+    (exit, report)
+}
+
+fn spawn_reader<'scope, R: std::io::Read + Send + 'scope>(
+    scope: &'scope thread::Scope<'scope, '_>,
     reader: R,
+    runner: &'scope dyn DeterministicRunner,
+    report: &'scope Mutex<TestRunReport>,
     tx: Sender<AgentEvent>,
-) -> thread::JoinHandle<()> {
-    thread::spawn(move || {
+) {
+    scope.spawn(move || {
         for line in BufReader::new(reader).lines().map_while(Result::ok) {
-            let _ = tx.send(AgentEvent::Output(line));
+            let mut report = report.lock().unwrap_or_else(|err| err.into_inner());
+            for shown in runner.parse_line(&line, &mut report) {
+                let _ = tx.send(AgentEvent::Output(shown));
+            }
         }
-    })
+    });
+}
+
+/// Best-effort teardown such as `docker compose down`; its output is not shown.
+fn run_cleanup(cleanup: &TestRunnerConfig, workdir: Option<&Path>) {
+    let mut command = Command::new(&cleanup.program);
+    command
+        .args(&cleanup.args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    if let Some(workdir) = workdir {
+        command.current_dir(workdir);
+    }
+    let _ = command.status();
 }
 
 #[cfg(test)]
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::session_store::{PlannerTaskFileEntry, SessionStore};
+
+/// Session subdirectory holding one folder of snapshots per task.
+pub const SNAPSHOTS_DIR: &str = "docs";
+/// Longest snapshot injected into a worker prompt; the rest is cut with a note.
+pub const MAX_SNAPSHOT_PROMPT_CHARS: usize = 20_000;
+
+/// What [`sanitize_snapshots`] did to the `snapshot` fields of a task list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotSweep {
+    pub kept: usize,
+    /// Fields cleared because the path left `docs/` or the file was missing or empty.
+    pub dropped: usize,
+}
+
+/// Extra requirements for an `/attach-docs snapshot` run.
+pub fn snapshot_prompt_instructions(session_dir: &Path) -> String {
+    let session_dir = session_dir.display();
+    format!(
+        "Snapshot requirements:\n\
+         - Also download every doc you attach and save a readable plain-text copy of it: the documentation text and code samples only, without HTML markup, scripts, navigation, or ads.\n\
+         - Save each copy under {session_dir}/{SNAPSHOTS_DIR}/<task id>/ with a short file name ending in .txt, one file per doc.\n\
+         - Set the doc item's `snapshot` field to the path relative to {session_dir}, e.g. \"{SNAPSHOTS_DIR}/<task id>/serde-derive.txt\".\n\
+         - Workers will not have network access, so keep the parts needed to implement the task."
+    )
+}
+
+/// Makes snapshot text safe to paste into a prompt: strips HTML markup (when the file is
+/// HTML), terminal escapes, and control characters, and collapses runs of blank lines.
+pub fn sanitize_snapshot_text(raw: &str) -> String {
+    let text = if looks_like_html(raw) {
+        strip_html(raw)
+    } else {
+        raw.to_string()
+    };
+    let mut out = String::new();
+    let mut previous_blank = true;
+    for line in text.lines() {
+        let line = strip_control(line);
+        let line = line.trim_end();
+        let blank = line.trim().is_empty();
+        if blank && previous_blank {
+            continue;
+        }
+        out.push_str(if blank { "" } else { line });
+        out.push('\n');
+        previous_blank = blank;
+    }
+    out.trim_end().to_string()
+}
+
+/// Sanitizes every snapshot file the tasks point at, rewriting it in place, and clears
+/// `snapshot` fields that cannot be used.
+pub fn sanitize_snapshots(
+    store: &SessionStore,
+    tasks: &mut [PlannerTaskFileEntry],
+) -> io::Result<SnapshotSweep> {
+    let mut sweep = SnapshotSweep::default();
+    for doc in tasks.iter_mut().flat_map(|task| task.docs.iter_mut()) {
+        let Some(relative) = doc.snapshot.as_deref() else {
+            continue;
+        };
+        let sanitized = match resolve_snapshot_path(store, relative) {
+            Some(path) => match std::fs::read(&path) {
+                Ok(bytes) => {
+                    let sanitized = sanitize_snapshot_text(&String::from_utf8_lossy(&bytes));
+                    if !sanitized.is_empty() && sanitized.as_bytes() != bytes {
+                        std::fs::write(&path, &sanitized)?;
+                    }
+                    sanitized
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+                Err(err) => return Err(err),
+            },
+            None => String::new(),
+        };
+        if sanitized.is_empty() {
+            doc.snapshot = None;
+            sweep.dropped += 1;
+        } else {
+            sweep.kept += 1;
+        }
+    }
+    Ok(sweep)
+}
+
+/// Snapshot texts keyed by their `snapshot` path, capped at
+/// [`MAX_SNAPSHOT_PROMPT_CHARS`]. Unreadable snapshots are left out, so their docs fall back
+/// to the URL.
+pub fn read_snapshots(
+    store: &SessionStore,
+    tasks: &[PlannerTaskFileEntry],
+) -> HashMap<String, String> {
+    let mut snapshots = HashMap::new();
+    for relative in tasks
+        .iter()
+        .flat_map(|task| task.docs.iter())
+        .filter_map(|doc| doc.snapshot.as_deref())
+    {
+        let Some(path) = resolve_snapshot_path(store, relative) else {
+            continue;
+        };
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let text = match text.char_indices().nth(MAX_SNAPSHOT_PROMPT_CHARS) {
+            Some((cut, _)) => format!("{}\n{}", &text[..cut], truncation_note()),
+            None => text.to_string(),
+        };
+        snapshots.insert(relative.to_string(), text);
+    }
+    snapshots
+}
+
+/// Whether [`read_snapshots`] cut `text` down to [`MAX_SNAPSHOT_PROMPT_CHARS`].
+pub fn is_truncated_snapshot(text: &str) -> bool {
+    text.ends_with(&truncation_note())
+}
+
+fn truncation_note() -> String {
+    format!("[snapshot truncated at {MAX_SNAPSHOT_PROMPT_CHARS} characters]")
+}
+
+/// The snapshot file for a relative `snapshot` path, or `None` when the path is absolute or
+/// leaves the session's `docs/` directory.
+fn resolve_snapshot_path(store: &SessionStore, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative.trim());
+    let mut components = relative.components();
+    if components.next() != Some(Component::Normal(SNAPSHOTS_DIR.as_ref())) {
+        return None;
+    }
+    let mut depth = 0;
+    for component in components {
+        match component {
+            Component::Normal(_) => depth += 1,
+            _ => return None,
+        }
+    }
+    (depth > 0).then(|| store.session_dir().join(relative))
+}
+
+fn looks_like_html(text: &str) -> bool {
+    let head = text.trim_start().get(..512).unwrap_or(text.trim_start());
+    let head = head.to_ascii_lowercase();
+    head.starts_with("<!doctype html") || head.contains("<html") || head.contains("<body")
+}
+
+/// Drops tags, `<script>`/`<style>`/`<nav>` blocks and comments, turns block-level tags into
+/// line breaks, and decodes the common entities.
+fn strip_html(html: &str) -> String {
+    const SKIPPED_BLOCKS: [&str; 5] = ["script", "style", "nav", "noscript", "svg"];
+    const BREAKING_TAGS: [&str; 14] = [
+        "p", "div", "br", "li", "tr", "h1", "h2", "h3", "h4", "h5", "h6", "pre", "section",
+        "article",
+    ];
+    let lower = html.to_ascii_lowercase();
+    let mut out = String::new();
+    let mut pos = 0;
+    while pos < html.len() {
+        let Some(offset) = html[pos..].find('<') else {
+            out.push_str(&html[pos..]);
+            break;
+        };
+        out.push_str(&html[pos..pos + offset]);
+        let tag_start = pos + offset;
+        if lower[tag_start..].starts_with("<!--") {
+            pos = lower[tag_start..]
+                .find("-->")
+                .map_or(html.len(), |end| tag_start + end + 3);
+            continue;
+        }
+        let Some(tag_len) = html[tag_start..].find('>') else {
+            break;
+        };
+        let tag_end = tag_start + tag_len + 1;
+        let name = lower[tag_start + 1..tag_end - 1]
+            .trim_start_matches('/')
+            .split(|ch: char| ch.is_whitespace() || ch == '/')
+            .next()
+            .unwrap_or_default();
+        pos = tag_end;
+        if SKIPPED_BLOCKS.contains(&name) && !lower[tag_start + 1..].starts_with('/') {
+            let closing = format!("</{name}");
+            pos = lower[pos..]
+                .find(&closing)
+                .and_then(|close| {
+                    lower[pos + close..]
+                        .find('>')
+                        .map(|end| pos + close + end + 1)
+                })
+                .unwrap_or(html.len());
+        } else if BREAKING_TAGS.contains(&name) {
+            out.push('\n');
+        }
+    }
+    decode_entities(&out)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Removes ANSI escape sequences and every control character except tab.
+fn strip_control(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\u{1b}' {
+            if chars.next_if_eq(&'[').is_some() {
+                while chars.next().is_some_and(|next| !next.is_ascii_alphabetic()) {}
+            }
+            continue;
+        }
+        if ch == '\t' || !ch.is_control() {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/doc_snapshots_tests.rs"]
+mod tests;
@@ -0,0 +1,163 @@
+use crate::i18n::{self, Locale, Text};
+
+/// Session file an agent rewrote while the user still had edits of their own in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictFile {
+    /// Unsaved planner-mode edits to planner.md.
+    Planner,
+    /// A hand edit to tasks.json that was waiting for `/reload-tasks`.
+    Tasks,
+}
+
+impl ConflictFile {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Planner => "planner.md",
+            Self::Tasks => "tasks.json",
+        }
+    }
+}
+
+/// How `/conflict` settles an open conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictChoice {
+    /// Keep the user's version and drop the master's changes.
+    Mine,
+    /// Keep the master's version and drop the user's edits.
+    Theirs,
+    /// Combine both; overlapping changes are kept between conflict markers.
+    Merge,
+}
+
+impl ConflictChoice {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "mine" => Some(Self::Mine),
+            "theirs" => Some(Self::Theirs),
+            "merge" => Some(Self::Merge),
+            _ => None,
+        }
+    }
+}
+
+/// The three versions of a file both sides changed, held until the user picks one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditConflict {
+    pub file: ConflictFile,
+    /// The file as both sides last agreed on it.
+    pub base: String,
+    /// The user's version.
+    pub mine: String,
+    /// The master's version, which is what the file on disk holds.
+    pub theirs: String,
+}
+
+impl EditConflict {
+    /// Text the file should hold after `choice`, and whether it still has conflict markers.
+    pub fn resolve(&self, choice: ConflictChoice) -> (String, bool) {
+        match choice {
+            ConflictChoice::Mine => (self.mine.clone(), false),
+            ConflictChoice::Theirs => (self.theirs.clone(), false),
+            ConflictChoice::Merge => merge_three_way(&self.base, &self.mine, &self.theirs),
+        }
+    }
+
+    /// Chat notice offering the `/conflict` choices.
+    pub fn notice(&self, locale: Locale) -> String {
+        i18n::system(
+            locale,
+            Text::EditConflictNotice,
+            &[("file_name", &self.file.file_name())],
+        )
+    }
+}
+
+pub const CONFLICT_MINE_MARKER: &str = "<<<<<<< yours";
+pub const CONFLICT_SEPARATOR: &str = "=======";
+pub const CONFLICT_THEIRS_MARKER: &str = ">>>>>>> master";
+
+/// Line-based three-way merge. Regions only one side changed take that side; regions both
+/// changed the same way are kept once; anything else is wrapped in conflict markers. Returns
+/// the merged text and whether it contains conflicts.
+pub fn merge_three_way(base: &str, mine: &str, theirs: &str) -> (String, bool) {
+    let base_lines = base.lines().collect::<Vec<_>>();
+    let mine_lines = mine.lines().collect::<Vec<_>>();
+    let theirs_lines = theirs.lines().collect::<Vec<_>>();
+    let mine_matches = matched_base_lines(&base_lines, &mine_lines);
+    let theirs_matches = matched_base_lines(&base_lines, &theirs_lines);
+
+    let mut merged = Vec::<&str>::new();
+    let mut conflicted = false;
+    let (mut base_at, mut mine_at, mut theirs_at) = (0, 0, 0);
+    // Base lines both sides kept unchanged anchor the merge; the sentinel flushes the tail.
+    let anchors = (0..base_lines.len())
+        .filter_map(|idx| Some((idx, mine_matches[idx]?, theirs_matches[idx]?)))
+        .chain(std::iter::once((
+            base_lines.len(),
+            mine_lines.len(),
+            theirs_lines.len(),
+        )));
+    for (base_end, mine_end, theirs_end) in anchors {
+        let base_chunk = &base_lines[base_at..base_end];
+        let mine_chunk = &mine_lines[mine_at..mine_end];
+        let theirs_chunk = &theirs_lines[theirs_at..theirs_end];
+        if mine_chunk == base_chunk || mine_chunk == theirs_chunk {
+            merged.extend_from_slice(theirs_chunk);
+        } else if theirs_chunk == base_chunk {
+            merged.extend_from_slice(mine_chunk);
+        } else {
+            conflicted = true;
+            merged.push(CONFLICT_MINE_MARKER);
+            merged.extend_from_slice(mine_chunk);
+            merged.push(CONFLICT_SEPARATOR);
+            merged.extend_from_slice(theirs_chunk);
+            merged.push(CONFLICT_THEIRS_MARKER);
+        }
+        if base_end < base_lines.len() {
+            merged.push(base_lines[base_end]);
+        }
+        (base_at, mine_at, theirs_at) = (base_end + 1, mine_end + 1, theirs_end + 1);
+    }
+
+    let mut text = merged.join("\n");
+    if [mine, theirs].iter().any(|side| side.ends_with('\n')) && !text.is_empty() {
+        text.push('\n');
+    }
+    (text, conflicted)
+}
+
+/// For each base line, the index of the `other` line it is matched to by a longest common
+/// subsequence, if any.
+fn matched_base_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let (rows, cols) = (base.len(), other.len());
+    // lcs[i][j]: LCS length of base[i..] and other[j..].
+    let mut lcs = vec![0u32; (rows + 1) * (cols + 1)];
+    let at = |i: usize, j: usize| i * (cols + 1) + j;
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            lcs[at(i, j)] = if base[i] == other[j] {
+                lcs[at(i + 1, j + 1)] + 1
+            } else {
+                lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+            };
+        }
+    }
+    let mut matches = vec![None; rows];
+    let (mut i, mut j) = (0, 0);
+    while i < rows && j < cols {
+        if base[i] == other[j] {
+            matches[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if lcs[at(i + 1, j)] >= lcs[at(i, j + 1)] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/edit_conflict_tests.rs"]
+mod tests;
@@ -0,0 +1,417 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+
+use crate::artifact_io::load_merged_metaagent_config_text;
+use crate::credentials;
+use crate::i18n::{self, Locale, Text};
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskStatusFile, SessionStore};
+use crate::ticket_sync::curl_quote;
+
+const SMTP_TIMEOUT: Duration = Duration::from_secs(60);
+/// Reporting-agent summaries kept for one digest; older ones are dropped.
+const MAX_SUMMARIES: usize = 20;
+/// Longest failure reason quoted in a digest.
+const MAX_REASON_CHARS: usize = 200;
+
+/// The `[email_digest]` table; an empty `smtp_url` or `to` keeps digests off.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmailDigestConfig {
+    /// `smtps://host:465`, or `smtp://host:587` upgraded with STARTTLS.
+    pub smtp_url: String,
+    pub username: String,
+    /// Falls back to the `smtp` credential from `bob auth set smtp` when empty.
+    pub password: String,
+    pub from: String,
+    pub to: Vec<String>,
+    /// Minutes between progress digests while execution runs.
+    pub interval_minutes: u64,
+    /// Fails the send instead of falling back to plain text when `smtp://` has no STARTTLS.
+    pub require_tls: bool,
+}
+
+impl Default for EmailDigestConfig {
+    fn default() -> Self {
+        Self {
+            smtp_url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            from: String::new(),
+            to: Vec::new(),
+            interval_minutes: 60,
+            require_tls: true,
+        }
+    }
+}
+
+impl EmailDigestConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.smtp_url.trim().is_empty() && !self.to.is_empty()
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_minutes.saturating_mul(60))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EmailDigestConfigFile {
+    email_digest: EmailDigestConfig,
+}
+
+pub fn load_email_digest_config() -> io::Result<EmailDigestConfig> {
+    let mut config = email_digest_config_from_toml(&load_merged_metaagent_config_text()?)?;
+    if config.is_enabled() && !config.username.trim().is_empty() {
+        credentials::fill_from_store(&mut config.password, credentials::SMTP_PASSWORD);
+    }
+    Ok(config)
+}
+
+pub(crate) fn email_digest_config_from_toml(text: &str) -> io::Result<EmailDigestConfig> {
+    let config = toml::from_str::<EmailDigestConfigFile>(text)
+        .map(|file| file.email_digest)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    if config.interval_minutes == 0 {
+        return Err(invalid("email_digest.interval_minutes must be at least 1"));
+    }
+    if config.is_enabled() && config.from.trim().is_empty() {
+        return Err(invalid(
+            "email_digest.from is required when smtp_url is set",
+        ));
+    }
+    Ok(config)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestKind {
+    /// Sent every `interval_minutes` while execution runs.
+    Progress,
+    /// Sent once when execution stops.
+    Final,
+}
+
+/// What one digest e-mail reports; everything but `remaining` covers the time since the
+/// previous digest of the same run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestReport {
+    pub kind: DigestKind,
+    pub session_title: String,
+    pub elapsed: Duration,
+    pub completed: Vec<String>,
+    pub failures: Vec<String>,
+    pub remaining: Vec<String>,
+    pub summaries: Vec<String>,
+    /// Estimate against worker time per top-level task; final reports only.
+    pub estimates: Vec<String>,
+}
+
+impl DigestReport {
+    pub fn subject(&self) -> String {
+        let title = if self.session_title.trim().is_empty() {
+            "Bob session"
+        } else {
+            self.session_title.trim()
+        };
+        match self.kind {
+            DigestKind::Progress => format!(
+                "[bob] {title}: {} done, {} remaining",
+                self.completed.len(),
+                self.remaining.len()
+            ),
+            DigestKind::Final if self.remaining.is_empty() => {
+                format!("[bob] {title}: execution finished")
+            }
+            DigestKind::Final => format!(
+                "[bob] {title}: execution stopped with {} task(s) remaining",
+                self.remaining.len()
+            ),
+        }
+    }
+
+    pub fn body(&self) -> String {
+        let minutes = self.elapsed.as_secs() / 60;
+        let mut lines = vec![match self.kind {
+            DigestKind::Progress => format!(
+                "Execution has been running for {}h {:02}m.",
+                minutes / 60,
+                minutes % 60
+            ),
+            DigestKind::Final => format!(
+                "Final report: execution ran for {}h {:02}m.",
+                minutes / 60,
+                minutes % 60
+            ),
+        }];
+        let mut sections = vec![
+            ("Completed", &self.completed),
+            ("Failures", &self.failures),
+            ("Remaining", &self.remaining),
+            ("Reporter summaries", &self.summaries),
+        ];
+        if !self.estimates.is_empty() {
+            sections.push(("Estimates vs actuals", &self.estimates));
+        }
+        for (heading, items) in sections {
+            lines.push(String::new());
+            lines.push(format!("{heading} ({}):", items.len()));
+            if items.is_empty() {
+                lines.push("- none".to_string());
+            }
+            lines.extend(items.iter().map(|item| format!("- {item}")));
+        }
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug)]
+struct DigestRun {
+    session_dir: PathBuf,
+    started_at: Instant,
+    last_sent_at: Instant,
+    statuses: HashMap<String, PlannerTaskStatusFile>,
+    fails_seen: usize,
+    summaries: Vec<String>,
+}
+
+/// Mails `[email_digest]` progress digests while execution runs, and a final report when it
+/// stops. Sends happen on a background thread; their outcome comes back as chat messages.
+pub struct EmailDigest {
+    config: EmailDigestConfig,
+    run: Option<DigestRun>,
+    results_tx: Sender<String>,
+    results_rx: Receiver<String>,
+}
+
+impl EmailDigest {
+    pub fn new(config: EmailDigestConfig) -> Self {
+        let (results_tx, results_rx) = mpsc::channel();
+        Self {
+            config,
+            run: None,
+            results_tx,
+            results_rx,
+        }
+    }
+
+    /// Keeps a reporting-agent summary for the next digest of the running execution.
+    pub fn note_summary(&mut self, summary: &str) {
+        if let Some(run) = self.run.as_mut() {
+            run.summaries.push(summary.trim().to_string());
+            let overflow = run.summaries.len().saturating_sub(MAX_SUMMARIES);
+            run.summaries.drain(..overflow);
+        }
+    }
+
+    /// Sends a digest when one is due; see [`EmailDigest::due_report`].
+    pub fn poll(
+        &mut self,
+        now: Instant,
+        store: &SessionStore,
+        tasks: &[PlannerTaskFileEntry],
+        estimates: &[String],
+        running: bool,
+        locale: Locale,
+    ) {
+        let Some(report) = self.due_report(now, store, tasks, estimates, running) else {
+            return;
+        };
+        let config = self.config.clone();
+        let results_tx = self.results_tx.clone();
+        thread::spawn(move || {
+            let (sent, failed) = match report.kind {
+                DigestKind::Progress => (Text::ProgressDigestSent, Text::ProgressDigestFailed),
+                DigestKind::Final => (Text::FinalReportSent, Text::FinalReportFailed),
+            };
+            let message = match send_mail(&config, &report.subject(), &report.body()) {
+                Ok(()) => i18n::system(locale, sent, &[("to", &config.to.join(", "))]),
+                Err(err) => i18n::system(locale, failed, &[("err", &err)]),
+            };
+            let _ = results_tx.send(message);
+        });
+    }
+
+    pub fn drain_messages(&self) -> Vec<String> {
+        self.results_rx.try_iter().collect()
+    }
+
+    /// Starts tracking when execution starts, then returns a progress report every
+    /// `interval_minutes` and a final report once execution stops, which also lists
+    /// `estimates`. Switching sessions drops the run without a report.
+    pub fn due_report(
+        &mut self,
+        now: Instant,
+        store: &SessionStore,
+        tasks: &[PlannerTaskFileEntry],
+        estimates: &[String],
+        running: bool,
+    ) -> Option<DigestReport> {
+        if !self.config.is_enabled() {
+            return None;
+        }
+        if self
+            .run
+            .as_ref()
+            .is_some_and(|run| run.session_dir != store.session_dir())
+        {
+            self.run = None;
+        }
+        let fails = store.read_task_fails().unwrap_or_default();
+        let Some(run) = self.run.as_mut() else {
+            if running {
+                self.run = Some(DigestRun {
+                    session_dir: store.session_dir().to_path_buf(),
+                    started_at: now,
+                    last_sent_at: now,
+                    statuses: task_statuses(tasks),
+                    fails_seen: fails.len(),
+                    summaries: Vec::new(),
+                });
+            }
+            return None;
+        };
+        let kind = if !running {
+            DigestKind::Final
+        } else if now.saturating_duration_since(run.last_sent_at) >= self.config.interval() {
+            DigestKind::Progress
+        } else {
+            return None;
+        };
+        let top_level = || tasks.iter().filter(|task| task.parent_id.is_none());
+        let report = DigestReport {
+            kind,
+            session_title: store
+                .read_session_meta()
+                .map(|meta| meta.title)
+                .unwrap_or_default(),
+            elapsed: now.saturating_duration_since(run.started_at),
+            completed: top_level()
+                .filter(|task| {
+                    task.status == PlannerTaskStatusFile::Done
+                        && run.statuses.get(&task.id) != Some(&PlannerTaskStatusFile::Done)
+                })
+                .map(|task| task.title.trim().to_string())
+                .collect(),
+            failures: fails
+                .iter()
+                .skip(run.fails_seen)
+                .map(|fail| {
+                    let reason = fail.reason.lines().next().unwrap_or_default().trim();
+                    format!(
+                        "{} ({}, attempt {}): {}",
+                        fail.top_task_title.trim(),
+                        fail.kind,
+                        fail.attempts,
+                        reason.chars().take(MAX_REASON_CHARS).collect::<String>()
+                    )
+                })
+                .collect(),
+            remaining: top_level()
+                .filter(|task| task.status != PlannerTaskStatusFile::Done)
+                .map(|task| task.title.trim().to_string())
+                .collect(),
+            summaries: std::mem::take(&mut run.summaries),
+            estimates: match kind {
+                DigestKind::Progress => Vec::new(),
+                DigestKind::Final => estimates.to_vec(),
+            },
+        };
+        run.last_sent_at = now;
+        run.statuses = task_statuses(tasks);
+        run.fails_seen = fails.len();
+        if kind == DigestKind::Final {
+            self.run = None;
+        }
+        Some(report)
+    }
+}
+
+fn task_statuses(tasks: &[PlannerTaskFileEntry]) -> HashMap<String, PlannerTaskStatusFile> {
+    tasks
+        .iter()
+        .map(|task| (task.id.clone(), task.status))
+        .collect()
+}
+
+/// Sends a plain-text message through `curl`'s SMTP support. The curl config goes over
+/// stdin so the password stays off the command line; the message goes through a temporary
+/// file.
+fn send_mail(config: &EmailDigestConfig, subject: &str, body: &str) -> Result<(), String> {
+    let message = format!(
+        "From: {}\nTo: {}\nSubject: {}\nMIME-Version: 1.0\nContent-Type: text/plain; charset=utf-8\n\n{body}\n",
+        config.from.trim(),
+        config.to.join(", "),
+        subject.replace(['\r', '\n'], " ")
+    );
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    let message_path = std::env::temp_dir().join(format!(
+        "agentbob-digest-{}-{nanos}.eml",
+        std::process::id()
+    ));
+    fs::write(&message_path, message)
+        .map_err(|err| format!("could not write the message file: {err}"))?;
+
+    let mut curl_config = format!(
+        "silent\nshow-error\ncrlf\nmax-time = {}\nurl = {}\nmail-from = {}\nupload-file = {}\n",
+        SMTP_TIMEOUT.as_secs(),
+        curl_quote(config.smtp_url.trim()),
+        curl_quote(config.from.trim()),
+        curl_quote(&message_path.display().to_string())
+    );
+    for recipient in &config.to {
+        curl_config.push_str(&format!("mail-rcpt = {}\n", curl_quote(recipient.trim())));
+    }
+    if !config.username.trim().is_empty() {
+        curl_config.push_str(&format!(
+            "user = {}\n",
+            curl_quote(&format!("{}:{}", config.username.trim(), config.password))
+        ));
+    }
+    if config.require_tls {
+        curl_config.push_str("ssl-reqd\n");
+    }
+    let result = run_curl_config(&curl_config);
+    let _ = fs::remove_file(&message_path);
+    result
+}
+
+fn run_curl_config(curl_config: &str) -> Result<(), String> {
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("could not run curl: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(curl_config.as_bytes())
+            .map_err(|err| format!("could not pass the message to curl: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("curl failed: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "curl exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/email_digest_tests.rs"]
+mod tests;
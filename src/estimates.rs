@@ -0,0 +1,118 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::time_format::format_duration;
+
+/// Size or hour count the master gives a top-level task in the `estimate` field of
+/// `tasks.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Estimate {
+    Small,
+    Medium,
+    Large,
+    /// Tenths of an hour, so `1.5h` is 15.
+    Hours(u32),
+}
+
+impl Estimate {
+    /// Parses `S`, `M`, or `L` in any case, or a positive hour count such as `4h` or `1.5h`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let invalid = || format!("estimate \"{value}\" must be S, M, L, or hours such as \"4h\"");
+        match value.to_ascii_uppercase().as_str() {
+            "S" => return Ok(Estimate::Small),
+            "M" => return Ok(Estimate::Medium),
+            "L" => return Ok(Estimate::Large),
+            _ => {}
+        }
+        let hours = value
+            .strip_suffix(['h', 'H'])
+            .map(str::trim)
+            .and_then(|hours| hours.parse::<f64>().ok())
+            .filter(|hours| hours.is_finite() && *hours > 0.0 && *hours <= 10_000.0)
+            .ok_or_else(invalid)?;
+        Ok(Estimate::Hours(((hours * 10.0).round() as u32).max(1)))
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Estimate::Small => write!(f, "S"),
+            Estimate::Medium => write!(f, "M"),
+            Estimate::Large => write!(f, "L"),
+            Estimate::Hours(tenths) => write_hours(f, *tenths),
+        }
+    }
+}
+
+fn write_hours(f: &mut impl fmt::Write, tenths: u32) -> fmt::Result {
+    if tenths.is_multiple_of(10) {
+        write!(f, "{}h", tenths / 10)
+    } else {
+        write!(f, "{}.{}h", tenths / 10, tenths % 10)
+    }
+}
+
+/// Plan-level total of top-level task estimates, e.g. `2 S, 1 L + 6.5h (1 task not
+/// estimated)`. Sizes are counted rather than converted to hours. `None` when no task has
+/// an estimate.
+pub fn plan_total(estimates: &[Option<Estimate>]) -> Option<String> {
+    if estimates.iter().all(Option::is_none) {
+        return None;
+    }
+    let count = |size: Estimate| estimates.iter().filter(|e| **e == Some(size)).count();
+    let mut parts = [
+        (count(Estimate::Small), "S"),
+        (count(Estimate::Medium), "M"),
+        (count(Estimate::Large), "L"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, size)| format!("{count} {size}"))
+    .collect::<Vec<_>>()
+    .join(", ");
+    let hours = estimates
+        .iter()
+        .filter_map(|estimate| match estimate {
+            Some(Estimate::Hours(tenths)) => Some(*tenths),
+            _ => None,
+        })
+        .sum::<u32>();
+    if hours > 0 {
+        if !parts.is_empty() {
+            parts.push_str(" + ");
+        }
+        let _ = write_hours(&mut parts, hours);
+    }
+    let missing = estimates.iter().filter(|e| e.is_none()).count();
+    if missing > 0 {
+        parts.push_str(&format!(
+            " ({missing} {} not estimated)",
+            if missing == 1 { "task" } else { "tasks" }
+        ));
+    }
+    Some(parts)
+}
+
+/// Estimate and actual worker time of one top-level task, e.g. `estimated 2h, took 3h 00m
+/// (+50%)`. Only hour estimates get a percentage.
+pub fn comparison(estimate: Option<Estimate>, actual: Duration) -> String {
+    let took = format_duration(actual);
+    match estimate {
+        None => format!("not estimated, took {took}"),
+        Some(Estimate::Hours(tenths)) => {
+            let estimated_secs = f64::from(tenths) * 360.0;
+            let delta = (actual.as_secs_f64() - estimated_secs) / estimated_secs * 100.0;
+            format!(
+                "estimated {}, took {took} ({delta:+.0}%)",
+                Estimate::Hours(tenths)
+            )
+        }
+        Some(size) => format!("estimated {size}, took {took}"),
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/estimates_tests.rs"]
+mod tests;
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentEvent;
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskStatusFile, SessionStore};
+use crate::transcript::Transcript;
+use crate::workflow::ManualIntervention;
+
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const TAIL_CHUNK_BYTES: u64 = 16 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum SessionEventKindFile {
+    TaskStatusChanged {
+        task_id: String,
+        title: String,
+        status: PlannerTaskStatusFile,
+    },
+    ExecutionChanged {
+        enabled: bool,
+        busy: bool,
+    },
+    AgentOutput {
+        agent: String,
+        line: String,
+    },
+    AgentSystem {
+        agent: String,
+        line: String,
+    },
+    AgentCompleted {
+        agent: String,
+        success: bool,
+        exit_code: i32,
+    },
+    /// `/skip` or `/mark-done`; `action` is `skipped` or `marked_done`.
+    ManualIntervention {
+        task_id: String,
+        title: String,
+        action: String,
+    },
+    /// One line of the TUI chat, with its `You:`/`Agent:`/`System:` prefix.
+    ChatMessage {
+        line: String,
+    },
+}
+
+/// One line of `events.jsonl`; `seq` starts at 1 and is the cursor subscribers resume from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionEventFileEntry {
+    pub seq: u64,
+    pub at_epoch_ms: u64,
+    pub event: SessionEventKindFile,
+}
+
+#[derive(Debug)]
+struct OpenEventLog {
+    path: PathBuf,
+    file: File,
+    next_seq: u64,
+}
+
+/// Appends workflow state changes and agent output to the active session's event log.
+#[derive(Debug, Default)]
+pub struct SessionEventRecorder {
+    current: Option<OpenEventLog>,
+    failed_path: Option<PathBuf>,
+    tracked_session: Option<PathBuf>,
+    task_statuses: HashMap<String, PlannerTaskStatusFile>,
+    execution: (bool, bool),
+    chat_session: Option<PathBuf>,
+    chat_lines_recorded: usize,
+}
+
+impl SessionEventRecorder {
+    /// Appends one event. After a failure the same log is not retried, so callers can report
+    /// the first error without repeating it for every event.
+    pub fn record(&mut self, store: &SessionStore, event: SessionEventKindFile) -> io::Result<()> {
+        let path = store.events_file();
+        if self.failed_path.as_ref() == Some(&path) {
+            return Ok(());
+        }
+        let result = self.append_to(path.clone(), event);
+        if result.is_err() {
+            self.current = None;
+            self.failed_path = Some(path);
+        }
+        result
+    }
+
+    pub fn record_agent_event(
+        &mut self,
+        store: &SessionStore,
+        agent: &str,
+        event: &AgentEvent,
+    ) -> io::Result<()> {
+        let agent = agent.to_string();
+        let event = match event {
+            AgentEvent::Output(line) => SessionEventKindFile::AgentOutput {
+                agent,
+                line: line.clone(),
+            },
+            AgentEvent::System(line) => SessionEventKindFile::AgentSystem {
+                agent,
+                line: line.clone(),
+            },
+            AgentEvent::Completed { success, code } => SessionEventKindFile::AgentCompleted {
+                agent,
+                success: *success,
+                exit_code: *code,
+            },
+        };
+        self.record(store, event)
+    }
+
+    pub fn record_manual_intervention(
+        &mut self,
+        store: &SessionStore,
+        intervention: &ManualIntervention,
+    ) -> io::Result<()> {
+        self.record(
+            store,
+            SessionEventKindFile::ManualIntervention {
+                task_id: intervention.task_id.clone(),
+                title: intervention.title.clone(),
+                action: intervention.action.label().to_string(),
+            },
+        )
+    }
+
+    /// Records task status and execution state changes since the previous call. The first call
+    /// for a session only takes a baseline, and tasks that were not seen before (e.g. a fresh
+    /// plan) are not reported until their status changes.
+    pub fn record_workflow_changes(
+        &mut self,
+        store: &SessionStore,
+        tasks: &[PlannerTaskFileEntry],
+        execution_enabled: bool,
+        execution_busy: bool,
+    ) -> io::Result<()> {
+        let previous = std::mem::replace(
+            &mut self.task_statuses,
+            tasks
+                .iter()
+                .map(|task| (task.id.clone(), task.status))
+                .collect(),
+        );
+        let execution = std::mem::replace(&mut self.execution, (execution_enabled, execution_busy));
+        if self.tracked_session.as_deref() != Some(store.session_dir()) {
+            self.tracked_session = Some(store.session_dir().to_path_buf());
+            return Ok(());
+        }
+        for task in tasks {
+            if previous
+                .get(&task.id)
+                .is_some_and(|status| *status != task.status)
+            {
+                self.record(
+                    store,
+                    SessionEventKindFile::TaskStatusChanged {
+                        task_id: task.id.clone(),
+                        title: task.title.clone(),
+                        status: task.status,
+                    },
+                )?;
+            }
+        }
+        if execution != self.execution {
+            self.record(
+                store,
+                SessionEventKindFile::ExecutionChanged {
+                    enabled: execution_enabled,
+                    busy: execution_busy,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Records chat lines appended since the previous call. Lines from before a session
+    /// switch stay with the previous session; the first session also gets the lines shown
+    /// before it was created, such as the message that started it.
+    pub fn record_chat_lines(
+        &mut self,
+        store: &SessionStore,
+        transcript: &Transcript,
+    ) -> io::Result<()> {
+        if self.chat_session.as_deref() != Some(store.session_dir()) {
+            let switched = self.chat_session.is_some();
+            self.chat_session = Some(store.session_dir().to_path_buf());
+            self.chat_lines_recorded = if switched { transcript.len() } else { 0 };
+        }
+        for index in self.chat_lines_recorded.min(transcript.len())..transcript.len() {
+            let line = transcript.line(index).unwrap_or_default().into_owned();
+            self.record(store, SessionEventKindFile::ChatMessage { line })?;
+            self.chat_lines_recorded = index + 1;
+        }
+        Ok(())
+    }
+
+    fn append_to(&mut self, path: PathBuf, event: SessionEventKindFile) -> io::Result<()> {
+        if self.current.as_ref().is_none_or(|open| open.path != path) {
+            self.current = None;
+            let next_seq = last_event_seq(&path)? + 1;
+            let file = OpenOptions::new().create(true).append(true).open(&path)?;
+            self.current = Some(OpenEventLog {
+                path,
+                file,
+                next_seq,
+            });
+        }
+        let Some(open) = self.current.as_mut() else {
+            return Ok(());
+        };
+        let entry = SessionEventFileEntry {
+            seq: open.next_seq,
+            at_epoch_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            event,
+        };
+        let line = serde_json::to_string(&entry).map_err(io::Error::other)?;
+        writeln!(open.file, "{line}")?;
+        open.next_seq += 1;
+        Ok(())
+    }
+}
+
+/// Sequence number of the newest event in the log, 0 when it has none. Reads backwards from the
+/// end, so a long log is not parsed in full.
+pub fn last_event_seq(path: &Path) -> io::Result<u64> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err),
+    };
+    let mut end = file.metadata()?.len();
+    let mut tail = Vec::new();
+    while end > 0 {
+        let start = end.saturating_sub(TAIL_CHUNK_BYTES);
+        let mut chunk = vec![0; (end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        end = start;
+        // The first line may continue before `start`, and the last one may still be written.
+        let whole = if start == 0 {
+            &tail[..]
+        } else {
+            match tail.iter().position(|byte| *byte == b'\n') {
+                Some(newline) => &tail[newline + 1..],
+                None => continue,
+            }
+        };
+        let newest = whole
+            .split(|byte| *byte == b'\n')
+            .rev()
+            .skip(1)
+            .find_map(|line| serde_json::from_slice::<SessionEventFileEntry>(line).ok());
+        if let Some(entry) = newest {
+            return Ok(entry.seq);
+        }
+    }
+    Ok(0)
+}
+
+/// Returns up to `limit` events with `seq > after`, oldest first, waiting until events arrive
+/// or `wait` elapses, whichever comes first. A missing log has no events; lines that do not
+/// parse (such as one still being written) are skipped. Each poll only parses the lines
+/// appended since the previous one.
+pub fn wait_for_events(
+    path: &Path,
+    after: u64,
+    limit: usize,
+    wait: Duration,
+) -> io::Result<Vec<SessionEventFileEntry>> {
+    let deadline = Instant::now() + wait;
+    let mut cursor = EventLogCursor::default();
+    loop {
+        let events = cursor.read_after(path, after, limit)?;
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if !events.is_empty() || remaining.is_zero() {
+            return Ok(events);
+        }
+        std::thread::sleep(remaining.min(WAIT_POLL_INTERVAL));
+    }
+}
+
+/// Byte offset of the first line of an event log not read yet; only whole lines are consumed.
+#[derive(Debug, Default)]
+struct EventLogCursor {
+    offset: u64,
+}
+
+impl EventLogCursor {
+    fn read_after(
+        &mut self,
+        path: &Path,
+        after: u64,
+        limit: usize,
+    ) -> io::Result<Vec<SessionEventFileEntry>> {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        if file.metadata()?.len() < self.offset {
+            // Rewritten from scratch, e.g. by `session pull`.
+            self.offset = 0;
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut reader = BufReader::new(file);
+        let mut events = Vec::new();
+        let mut line = Vec::new();
+        while events.len() < limit {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if line.last() != Some(&b'\n') {
+                // End of the log, or a line still being written that a later read picks up.
+                break;
+            }
+            self.offset += read as u64;
+            let Ok(entry) = serde_json::from_slice::<SessionEventFileEntry>(&line) else {
+                continue;
+            };
+            if entry.seq > after {
+                events.push(entry);
+            }
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/event_log_tests.rs"]
+mod tests;
@@ -1,16 +1,26 @@
+use std::collections::VecDeque;
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
-use std::time::Duration;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use crossterm::event::{
     self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEventKind,
 };
 
-const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(1);
+use crate::wakeup;
+
+/// How often the input reader thread checks whether it was asked to stop.
+const READER_STOP_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+/// A left press held this long without dragging opens the context menu, for terminals and
+/// touch setups that have no right button.
+const LONG_PRESS_THRESHOLD: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Default)]
 struct LeftMouseState {
     press_position: Option<(u16, u16)>,
+    pressed_at: Option<Instant>,
     dragged_since_press: bool,
 }
 
@@ -19,6 +29,27 @@ fn left_mouse_state() -> &'static Mutex<LeftMouseState> {
     LEFT_MOUSE_STATE.get_or_init(|| Mutex::new(LeftMouseState::default()))
 }
 
+static READER_RUNNING: AtomicBool = AtomicBool::new(false);
+static READER_STOP: AtomicBool = AtomicBool::new(false);
+
+fn reader_thread() -> &'static Mutex<Option<JoinHandle<()>>> {
+    static READER_THREAD: OnceLock<Mutex<Option<JoinHandle<()>>>> = OnceLock::new();
+    READER_THREAD.get_or_init(|| Mutex::new(None))
+}
+
+/// Terminal events read by the input reader thread and not yet handed to the main loop.
+fn input_queue() -> &'static Mutex<VecDeque<io::Result<Event>>> {
+    static INPUT_QUEUE: OnceLock<Mutex<VecDeque<io::Result<Event>>>> = OnceLock::new();
+    INPUT_QUEUE.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+fn pop_input() -> Option<io::Result<Event>> {
+    input_queue()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .pop_front()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AppEvent {
     Tick,
@@ -43,6 +74,18 @@ pub enum AppEvent {
     MouseScrollUp,
     MouseScrollDown,
     MouseLeftClick(u16, u16),
+    MouseContextMenu(u16, u16),
+    /// Ctrl+Tab: jump back to the most recently used other session.
+    QuickSwitch,
+    /// Ctrl+L: switch the chat pane between Chat and System Log.
+    ToggleChatView,
+    /// The terminal was resized; nothing to handle beyond a redraw.
+    Resize,
+    /// F1, or `?` where it is not typed as text: open or close the key binding help.
+    ShowHelp,
+    /// The terminal window gained or lost focus; only changes how often the loop ticks.
+    FocusGained,
+    FocusLost,
 }
 
 fn map_key_event(key_event: KeyEvent) -> AppEvent {
@@ -59,10 +102,17 @@ fn map_key_event(key_event: KeyEvent) -> AppEvent {
     if key_event.code == KeyCode::Char('d') && key_event.modifiers.contains(KeyModifiers::CONTROL) {
         return AppEvent::ScrollRightDownGlobal;
     }
+    if key_event.code == KeyCode::Char('l') && key_event.modifiers.contains(KeyModifiers::CONTROL) {
+        return AppEvent::ToggleChatView;
+    }
 
     match key_event.code {
+        KeyCode::Tab if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            AppEvent::QuickSwitch
+        }
         KeyCode::Tab => AppEvent::NextPane,
         KeyCode::BackTab => AppEvent::PrevPane,
+        KeyCode::F(1) => AppEvent::ShowHelp,
         KeyCode::Up
             if key_event.modifiers.contains(KeyModifiers::SHIFT)
                 || key_event.modifiers.contains(KeyModifiers::CONTROL) =>
@@ -113,6 +163,9 @@ fn map_mouse_event_kind(kind: MouseEventKind) -> AppEvent {
         MouseEventKind::ScrollUp => AppEvent::MouseScrollUp,
         MouseEventKind::ScrollDown => AppEvent::MouseScrollDown,
         MouseEventKind::Down(crossterm::event::MouseButton::Left) => AppEvent::MouseLeftClick(0, 0),
+        MouseEventKind::Down(crossterm::event::MouseButton::Right) => {
+            AppEvent::MouseContextMenu(0, 0)
+        }
         _ => AppEvent::Tick,
     }
 }
@@ -122,9 +175,13 @@ fn map_mouse_event(mouse_event: crossterm::event::MouseEvent) -> AppEvent {
         MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
             let mut state = left_mouse_state().lock().unwrap_or_else(|e| e.into_inner());
             state.press_position = Some((mouse_event.column, mouse_event.row));
+            state.pressed_at = Some(Instant::now());
             state.dragged_since_press = false;
             AppEvent::Tick
         }
+        MouseEventKind::Down(crossterm::event::MouseButton::Right) => {
+            AppEvent::MouseContextMenu(mouse_event.column, mouse_event.row)
+        }
         MouseEventKind::Drag(crossterm::event::MouseButton::Left) => {
             let mut state = left_mouse_state().lock().unwrap_or_else(|e| e.into_inner());
             if state.press_position.is_some() {
@@ -135,8 +192,14 @@ fn map_mouse_event(mouse_event: crossterm::event::MouseEvent) -> AppEvent {
         MouseEventKind::Up(crossterm::event::MouseButton::Left) => {
             let mut state = left_mouse_state().lock().unwrap_or_else(|e| e.into_inner());
             let should_emit_click = state.press_position.take().is_some() && !state.dragged_since_press;
+            let long_press = state
+                .pressed_at
+                .take()
+                .is_some_and(|pressed_at| pressed_at.elapsed() >= LONG_PRESS_THRESHOLD);
             state.dragged_since_press = false;
-            if should_emit_click {
+            if should_emit_click && long_press {
+                AppEvent::MouseContextMenu(mouse_event.column, mouse_event.row)
+            } else if should_emit_click {
                 AppEvent::MouseLeftClick(mouse_event.column, mouse_event.row)
             } else {
                 AppEvent::Tick
@@ -150,29 +213,98 @@ fn map_mouse_event(mouse_event: crossterm::event::MouseEvent) -> AppEvent {
 fn reset_left_mouse_state_for_tests() {
     let mut state = left_mouse_state().lock().unwrap_or_else(|e| e.into_inner());
     state.press_position = None;
+    state.pressed_at = None;
     state.dragged_since_press = false;
 }
 
-pub fn next_event() -> io::Result<AppEvent> {
-    if event::poll(EVENT_POLL_INTERVAL)? {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                return Ok(map_key_event(key_event));
-            }
-            Event::Paste(content) => {
-                return Ok(AppEvent::Paste(content));
-            }
-            Event::Mouse(mouse_event) => {
-                return Ok(map_mouse_event(mouse_event));
+#[cfg(test)]
+fn backdate_left_press_for_tests(held_for: Duration) {
+    let mut state = left_mouse_state().lock().unwrap_or_else(|e| e.into_inner());
+    state.pressed_at = state
+        .pressed_at
+        .and_then(|pressed_at| pressed_at.checked_sub(held_for));
+}
+
+fn map_event(event: Event) -> AppEvent {
+    match event {
+        Event::Key(key_event) if key_event.kind == KeyEventKind::Press => map_key_event(key_event),
+        Event::Paste(content) => AppEvent::Paste(content),
+        Event::Mouse(mouse_event) => map_mouse_event(mouse_event),
+        Event::Resize(_, _) => AppEvent::Resize,
+        Event::FocusGained => AppEvent::FocusGained,
+        Event::FocusLost => AppEvent::FocusLost,
+        _ => AppEvent::Tick,
+    }
+}
+
+/// Moves terminal reads onto a background thread that queues events and wakes the main loop,
+/// so [`next_event`] can sleep on agent output and keystrokes alike.
+pub fn start_input_reader() {
+    let mut reader = reader_thread().lock().unwrap_or_else(|e| e.into_inner());
+    if reader.is_some() {
+        return;
+    }
+    READER_STOP.store(false, Ordering::SeqCst);
+    READER_RUNNING.store(true, Ordering::SeqCst);
+    *reader = Some(thread::spawn(|| {
+        while !READER_STOP.load(Ordering::SeqCst) {
+            let read = match event::poll(READER_STOP_CHECK_INTERVAL) {
+                Ok(false) => continue,
+                Ok(true) => event::read(),
+                Err(err) => Err(err),
+            };
+            let failed = read.is_err();
+            input_queue()
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push_back(read);
+            wakeup::notify();
+            if failed {
+                break;
             }
-            _ => {}
         }
+    }));
+}
+
+/// Stops the input reader, e.g. before giving the terminal back on `/quit detach`, and waits
+/// for its thread so it cannot read past this point or overlap a restarted reader. Events it
+/// already queued are still returned by [`next_event`].
+pub fn stop_input_reader() {
+    let reader = reader_thread()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    READER_STOP.store(true, Ordering::SeqCst);
+    if let Some(reader) = reader {
+        let _ = reader.join();
     }
+    READER_RUNNING.store(false, Ordering::SeqCst);
+}
 
-    Ok(AppEvent::Tick)
+/// Returns the next terminal event, or `Tick` once `timeout` passes or something else woke
+/// the loop through [`wakeup::notify`].
+pub fn next_event(timeout: Duration) -> io::Result<AppEvent> {
+    if let Some(read) = pop_input() {
+        return read.map(map_event);
+    }
+    if !READER_RUNNING.load(Ordering::SeqCst) {
+        if !event::poll(timeout)? {
+            return Ok(AppEvent::Tick);
+        }
+        return event::read().map(map_event);
+    }
+    wakeup::wait(timeout);
+    pop_input().map_or(Ok(AppEvent::Tick), |read| read.map(map_event))
 }
 
 pub fn has_pending_input() -> io::Result<bool> {
+    let queued = !input_queue()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_empty();
+    if queued || READER_RUNNING.load(Ordering::SeqCst) {
+        return Ok(queued);
+    }
     event::poll(Duration::from_millis(0))
 }
 
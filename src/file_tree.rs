@@ -0,0 +1,287 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) const IGNORED_DIR_NAMES: [&str; 3] = [".git", "target", "node_modules"];
+const MAX_VIEWER_BYTES: u64 = 512 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTreeEntry {
+    pub relative_path: String,
+    pub name: String,
+    pub depth: usize,
+    pub is_dir: bool,
+    pub expanded: bool,
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileViewer {
+    pub relative_path: String,
+    pub lines: Vec<String>,
+    pub scroll: u16,
+    /// 1-based inclusive line range to highlight, set when opened from an audit citation.
+    pub highlight: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Default)]
+pub struct FileTreeState {
+    root: Option<PathBuf>,
+    expanded_dirs: HashSet<String>,
+    entries: Vec<FileTreeEntry>,
+    selected: usize,
+    viewer: Option<FileViewer>,
+}
+
+impl FileTreeState {
+    pub fn set_root(&mut self, root: PathBuf) {
+        self.root = Some(root);
+        self.expanded_dirs.clear();
+        self.entries.clear();
+        self.selected = 0;
+        self.viewer = None;
+    }
+
+    pub fn refresh(&mut self, changed_files: &BTreeSet<String>) {
+        let Some(root) = self.root.clone() else {
+            self.entries.clear();
+            self.selected = 0;
+            return;
+        };
+        let mut entries = Vec::new();
+        collect_entries(
+            &root,
+            "",
+            0,
+            &self.expanded_dirs,
+            changed_files,
+            &mut entries,
+        );
+        self.entries = entries;
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+
+    #[cfg(test)]
+    pub fn entries(&self) -> &[FileTreeEntry] {
+        &self.entries
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    pub fn viewer(&self) -> Option<&FileViewer> {
+        self.viewer.as_ref()
+    }
+
+    pub fn move_up(&mut self) {
+        if let Some(viewer) = self.viewer.as_mut() {
+            viewer.scroll = viewer.scroll.saturating_sub(1);
+            return;
+        }
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self, max_viewer_scroll: u16) {
+        if let Some(viewer) = self.viewer.as_mut() {
+            viewer.scroll = viewer.scroll.saturating_add(1).min(max_viewer_scroll);
+            return;
+        }
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Toggles the selected directory or opens the selected file in the read-only viewer.
+    pub fn activate(&mut self, changed_files: &BTreeSet<String>) {
+        if self.viewer.is_some() {
+            return;
+        }
+        let Some(entry) = self.entries.get(self.selected).cloned() else {
+            return;
+        };
+        if entry.is_dir {
+            if !self.expanded_dirs.remove(&entry.relative_path) {
+                self.expanded_dirs.insert(entry.relative_path);
+            }
+            self.refresh(changed_files);
+            return;
+        }
+        let Some(root) = self.root.as_ref() else {
+            return;
+        };
+        self.viewer = Some(FileViewer {
+            lines: read_viewer_lines(&root.join(&entry.relative_path)),
+            relative_path: entry.relative_path,
+            scroll: 0,
+            highlight: None,
+        });
+    }
+
+    /// Opens `relative_path` in the viewer scrolled to `start_line` with lines
+    /// `start_line..=end_line` highlighted.
+    pub fn open_file_at(&mut self, relative_path: &str, start_line: usize, end_line: usize) {
+        let Some(root) = self.root.as_ref() else {
+            return;
+        };
+        let lines = read_viewer_lines(&root.join(relative_path));
+        let top = start_line
+            .saturating_sub(1)
+            .min(lines.len().saturating_sub(1));
+        self.viewer = Some(FileViewer {
+            lines,
+            relative_path: relative_path.to_string(),
+            scroll: u16::try_from(top).unwrap_or(u16::MAX),
+            highlight: Some((start_line, end_line)),
+        });
+    }
+
+    /// Closes the viewer, or collapses the selected directory. Returns false when nothing changed.
+    pub fn back(&mut self, changed_files: &BTreeSet<String>) -> bool {
+        if self.viewer.take().is_some() {
+            return true;
+        }
+        let Some(entry) = self.entries.get(self.selected) else {
+            return false;
+        };
+        if entry.is_dir && self.expanded_dirs.remove(&entry.relative_path) {
+            self.refresh(changed_files);
+            return true;
+        }
+        false
+    }
+
+    pub fn render_lines(&self) -> Vec<String> {
+        if let Some(viewer) = self.viewer.as_ref() {
+            let number_width = viewer.lines.len().max(1).to_string().len();
+            return viewer
+                .lines
+                .iter()
+                .enumerate()
+                .map(|(idx, line)| format!("{:>number_width$} │ {line}", idx + 1))
+                .collect();
+        }
+        if self.entries.is_empty() {
+            return vec!["(workspace is empty or unreadable)".to_string()];
+        }
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let cursor = if idx == self.selected { ">" } else { " " };
+                let indent = "  ".repeat(entry.depth);
+                let icon = match (entry.is_dir, entry.expanded) {
+                    (true, true) => "[-]",
+                    (true, false) => "[+]",
+                    (false, _) => "   ",
+                };
+                let marker = if entry.changed { " *" } else { "" };
+                let slash = if entry.is_dir { "/" } else { "" };
+                format!("{cursor} {indent}{icon} {}{slash}{marker}", entry.name)
+            })
+            .collect()
+    }
+}
+
+/// Extracts file paths from a FILES_CHANGED summary (`- path: description` bullets).
+pub fn parse_changed_file_paths(summary: &str) -> Vec<String> {
+    summary
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let line = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .unwrap_or(line);
+            let path = line.split_once(": ").map(|(path, _)| path).unwrap_or(line);
+            let path = path.trim().trim_matches('`').trim_start_matches("./");
+            if path.is_empty() || path.contains(char::is_whitespace) {
+                None
+            } else {
+                Some(path.to_string())
+            }
+        })
+        .collect()
+}
+
+fn collect_entries(
+    dir: &Path,
+    relative_dir: &str,
+    depth: usize,
+    expanded_dirs: &HashSet<String>,
+    changed_files: &BTreeSet<String>,
+    out: &mut Vec<FileTreeEntry>,
+) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut children = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            let is_dir = entry.file_type().ok()?.is_dir();
+            if is_dir && IGNORED_DIR_NAMES.contains(&name.as_str()) {
+                return None;
+            }
+            Some((name, is_dir))
+        })
+        .collect::<Vec<_>>();
+    children.sort_by(|(a_name, a_dir), (b_name, b_dir)| {
+        b_dir.cmp(a_dir).then_with(|| a_name.cmp(b_name))
+    });
+
+    for (name, is_dir) in children {
+        let relative_path = if relative_dir.is_empty() {
+            name.clone()
+        } else {
+            format!("{relative_dir}/{name}")
+        };
+        let expanded = is_dir && expanded_dirs.contains(&relative_path);
+        let changed = if is_dir {
+            let prefix = format!("{relative_path}/");
+            changed_files.iter().any(|path| path.starts_with(&prefix))
+        } else {
+            changed_files.contains(&relative_path)
+        };
+        out.push(FileTreeEntry {
+            relative_path: relative_path.clone(),
+            name: name.clone(),
+            depth,
+            is_dir,
+            expanded,
+            changed,
+        });
+        if expanded {
+            collect_entries(
+                &dir.join(&name),
+                &relative_path,
+                depth + 1,
+                expanded_dirs,
+                changed_files,
+                out,
+            );
+        }
+    }
+}
+
+fn read_viewer_lines(path: &Path) -> Vec<String> {
+    match fs::metadata(path) {
+        Ok(meta) if meta.len() > MAX_VIEWER_BYTES => {
+            return vec![format!(
+                "(file is {} bytes; viewer limit is {MAX_VIEWER_BYTES} bytes)",
+                meta.len()
+            )];
+        }
+        Ok(_) => {}
+        Err(err) => return vec![format!("(failed to read file: {err})")],
+    }
+    match fs::read_to_string(path) {
+        Ok(text) if text.is_empty() => vec!["(empty file)".to_string()],
+        Ok(text) => text.lines().map(|line| line.replace('\t', "    ")).collect(),
+        Err(err) => vec![format!("(binary or unreadable file: {err})")],
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/file_tree_tests.rs"]
+mod tests;
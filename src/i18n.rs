@@ -0,0 +1,3313 @@
+use std::fmt::Display;
+use std::io;
+
+use serde::Deserialize;
+
+use crate::artifact_io::load_merged_metaagent_config_text;
+use crate::time_format::TimeZone;
+
+/// Language of the TUI's own strings (`[ui] locale`). Agent prompts stay English.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Es];
+
+    /// Accepts `en`/`es` and region-tagged forms such as `es-MX` or `en_US.UTF-8`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let language = value
+            .trim()
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+}
+
+/// `[ui]` settings: the catalog locale, the language agents should reply in, and the time zone
+/// dates are shown in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageSettings {
+    pub locale: Locale,
+    /// Natural-language name such as "Spanish"; `None` leaves agent replies in English.
+    pub prompt_language: Option<String>,
+    pub time_zone: TimeZone,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct UiConfigFile {
+    #[serde(default)]
+    ui: UiSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct UiSection {
+    locale: Option<String>,
+    prompt_language: Option<String>,
+    time_zone: Option<String>,
+}
+
+pub fn load_language_settings() -> io::Result<LanguageSettings> {
+    language_settings_from_toml(&load_merged_metaagent_config_text()?)
+}
+
+pub(crate) fn language_settings_from_toml(text: &str) -> io::Result<LanguageSettings> {
+    let section = toml::from_str::<UiConfigFile>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        .ui;
+    let locale = match section.locale.as_deref() {
+        None => Locale::default(),
+        Some(value) => Locale::parse(value).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "ui.locale must be one of {} (got \"{value}\")",
+                    Locale::ALL.map(Locale::code).join(", ")
+                ),
+            )
+        })?,
+    };
+    let time_zone = match section.time_zone.as_deref() {
+        None => TimeZone::default(),
+        Some(value) => TimeZone::parse(value).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("ui.time_zone must be UTC or an offset such as +02:00 (got \"{value}\")"),
+            )
+        })?,
+    };
+    Ok(LanguageSettings {
+        locale,
+        prompt_language: section
+            .prompt_language
+            .map(|language| language.trim().to_string())
+            .filter(|language| !language.is_empty()),
+        time_zone,
+    })
+}
+
+/// Prompt line asking an agent to answer the user in `language`.
+pub fn reply_language_instruction(language: &str) -> String {
+    format!(
+        "Write every user-facing reply in {language}. Keep file contents, task ids, commands, and code in their original form."
+    )
+}
+
+/// Keys of the UI message catalog. Placeholders are written `{name}` and filled by [`fill`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    StatusHelpNarrow,
+    StatusHelpWide,
+    TestsOn,
+    TestsOff,
+    AttachingDocs,
+    CheckingTasks,
+    TabFiles,
+    TabStats,
+    TabConsole,
+    TabWorkerOutput,
+    TabAgentChat,
+    TabShortChat,
+    TabShortTasks,
+    TabShortOutput,
+    TabSystemLog,
+    TabTaskThread,
+    ChatObserving,
+    ChatWorking,
+    ChatQueued,
+    PaneTaskList,
+    PanePlannerMarkdown,
+    PaneWorkerLog,
+    PaneTaskSummaries,
+    PaneFileTree,
+    PaneFileViewer,
+    PaneFailureStats,
+    PaneConsole,
+    ResumeTitle,
+    ResumeHint,
+    ResumeAllWorkspaces,
+    ResumeFilter,
+    ResumeNoMatches,
+    ResumePreviewWorkspace,
+    ResumePreviewTasks,
+    ResumePreviewActivity,
+    ResumePreviewNoPlanner,
+    UnknownDate,
+    JustNow,
+    MinutesAgo,
+    HoursAgo,
+    DaysAgo,
+    PreviewTitle,
+    PreviewHint,
+    QuestionsTitle,
+    QuestionsHint,
+    RerunTitle,
+    RerunHint,
+    HelpTitle,
+    HelpHint,
+    HelpSearch,
+    HelpFocused,
+    HelpNoMatches,
+    BackendTitle,
+    BackendHint,
+    LogsTitle,
+    LogsHint,
+    LogSize,
+    TaskMenuTitle,
+    TaskMenuHint,
+    TaskMenuExpandDetails,
+    TaskMenuCollapseDetails,
+    TaskMenuShowConversation,
+    TaskMenuViewLogs,
+    TaskMenuRetry,
+    TaskMenuSkip,
+    TaskMenuChangeModel,
+    TaskMenuAttachDocs,
+    TaskMenuDefaultModel,
+    ExecJob,
+    ExecIdle,
+    ExecAwaitingReview,
+    ExecQueued,
+    ExecBackend,
+    ExecTokens,
+    ExecCost,
+    ExecStalled,
+    NoActiveSession,
+    SessionDirInitialized,
+    ProjectInfoBusy,
+    MasterBusyQueued,
+    TaskCheckQueued,
+    ExecutionBusyBlocked,
+    AdvisorAsked,
+    AdvisorBusy,
+    StartingJob,
+    NoQueuedMessages,
+    DroppedQueuedMessages,
+    ReviewOn,
+    ReviewOff,
+    ReviewStatusOn,
+    ReviewStatusOff,
+    QuitWhileJobRunning,
+    QuittingAfterJob,
+    ContextUsage,
+    WorkspaceSnapshotFailed,
+    RevertedFiles,
+    RunningPlugin,
+    NoPluginsInstalled,
+    PluginsInstalled,
+    PluginsSafeMode,
+    ProtectedPathsListFailed,
+    ResumeOtherWorkspace,
+    MessageBackInInput,
+    ConsoleShown,
+    ConsoleHidden,
+    ConsoleRunning,
+    ConsoleStopping,
+    ConsoleNothingRunning,
+    ConsoleKept,
+    ConsoleStillRunning,
+    ConsoleNothingToKeep,
+    ConsoleCleared,
+    ConsoleUsage,
+    ConsoleFinished,
+    ConsoleSafeMode,
+    ObservingSession,
+    ObserverReadOnly,
+    ProjectInfoTrimmed,
+    ProjectInfoTrimWriteFailed,
+    ModelProfileConfigLoadFailed,
+    SafeModeOn,
+    TestsModeLoadFailed,
+    FinalAuditPolicyLoadFailed,
+    StallAfterLoadFailed,
+    TaskSplitLimitsLoadFailed,
+    WorkerLogSettingsLoadFailed,
+    FailoverThresholdLoadFailed,
+    RerunFailedTestsLoadFailed,
+    DocsUpdateTaskLoadFailed,
+    ProtectedPathsLoadFailed,
+    VerifyLoadFailed,
+    CommandsLoadFailed,
+    PluginLoadFailed,
+    PluginsDirUnavailable,
+    OutputFiltersLoadFailed,
+    ResponseCacheOff,
+    OfferRecentSessionLoadFailed,
+    ExportingTraces,
+    TelemetryLoadFailed,
+    CodeContextLoadFailed,
+    ProjectInfoScopeLoadFailed,
+    GraphicsLoadFailed,
+    BackendMetricsOff,
+    NarrowWidthLoadFailed,
+    LanguageSettingsLoadFailed,
+    BatchScriptStarted,
+    EmailDigestLoadFailed,
+    TaskSummariesReadFailed,
+    DeliveringLeftoverReports,
+    ReportQueueReadFailed,
+    DocsPolicyEnforceFailed,
+    RemovedMasterDocs,
+    AppendedTask,
+    AutomaticTasksWriteFailed,
+    TaskTreeRefreshFailed,
+    TasksReadAfterMasterFailed,
+    TasksCorrectionRequested,
+    TasksCorrectionRetriesExceeded,
+    TaskStatusPersistFailed,
+    RetryingWorkerJob,
+    TransientRetryScheduled,
+    TransientRetriesExhausted,
+    AdvisorEditRejected,
+    AdvisorEditUndoFailed,
+    ProjectInfoReadFailed,
+    ProjectContextAttached,
+    ProjectInfoFallbackWriteFailed,
+    ProjectContextEmpty,
+    ProjectContextExited,
+    SessionModelsRecordFailed,
+    SessionMetadataSaved,
+    SessionMetadataWritten,
+    SessionMetadataExited,
+    DocsAttached,
+    DocsAttachExited,
+    DocsAttachRefreshFailed,
+    DocsAttachReadFailed,
+    TaskCheckerFixed,
+    TaskCheckerRefreshFailed,
+    TaskCheckerReadFailed,
+    SessionTakenOver,
+    EventLogWriteFailed,
+    TraceExportFailed,
+    AuditTrailWriteFailed,
+    SendingQueuedMessage,
+    SendingQueuedMessageRemaining,
+    BatchStep,
+    MasterPromptSent,
+    RerunCancelled,
+    PlannerEditSessionInitFailed,
+    StartingNewSession,
+    ResumePickerCancelled,
+    BackendPickerCancelled,
+    LogPickerCancelled,
+    MasterStillProcessing,
+    DocsAttachBlocksSwitch,
+    DroppedBatchSteps,
+    TasksEditedBeforeSkip,
+    TasksWriteAfterUpdateFailed,
+    TasksEditedBeforeUnblock,
+    TasksEditedBeforeEdit,
+    SpeedProfileSet,
+    SpeedProfileSaveFailed,
+    SpeedProfileStatus,
+    ReviewGateSaveFailed,
+    TasksWriteAfterReviewFailed,
+    NewSessionBlocked,
+    StartedNewSession,
+    TemplateSessionBlocked,
+    StartedTemplateSession,
+    TemplateSessionFailed,
+    DocsAttachAlreadyRunning,
+    AttachDocsSafeMode,
+    NoTaskWithId,
+    DocsAttachStarted,
+    NoSavedSessions,
+    NoOtherSessionsToResume,
+    ResumeOtherWorkspacesOnly,
+    ResumePickerHint,
+    ResumePickerHintWithOthers,
+    ListSessionsFailed,
+    NoOtherSessionToSwitch,
+    LockTakeoverFailed,
+    TookOverSession,
+    FollowingLockedSession,
+    NoLockedSessionWaiting,
+    LockUsage,
+    BackendPickerHint,
+    TestsModeSaved,
+    TestsModeSaveFailed,
+    PlannerModeEnabled,
+    RollingContextPersistFailed,
+    TaskModelStatus,
+    UnknownModelProfile,
+    TaskModelSet,
+    TaskModelReset,
+    PromptPreviewOn,
+    PromptPreviewOff,
+    ResponseCacheStats,
+    ResponseCachePurged,
+    ResponseCachePurgeFailed,
+    CacheUsage,
+    SummariesHidden,
+    SummariesShown,
+    VerboseOn,
+    VerboseOff,
+    SkipPlanEnabled,
+    ConvertNeedsPlanner,
+    ConvertPlannerConflict,
+    ConvertPersistFailed,
+    NoPlanToImport,
+    ImportedPlan,
+    ImportedPlanHint,
+    ImportPlanConflict,
+    ImportPlanWriteFailed,
+    UnknownCommand,
+    GatheringProjectContext,
+    ProjectContextStillGathering,
+    StartedExecution,
+    ClearedStaleLock,
+    LockWriteFailed,
+    ResumeWorkspaceMissing,
+    ResumeSessionFailed,
+    ResumedSession,
+    WorkingDirectoryChanged,
+    TemplateUsageNoTemplates,
+    TemplateUsage,
+    ListTemplatesFailed,
+    ResumedTaskTreeRefreshFailed,
+    UiStateSaveFailed,
+    InterruptedDuringJob,
+    InterruptedDuringExecution,
+    RestoredQueuedMessages,
+    InterruptedRunClearFailed,
+    RecentSessionsOffer,
+    TaskSplitStaleRemoveFailed,
+    TaskSplitKept,
+    TaskSplitReadFailed,
+    TaskSplitDiscarded,
+    TaskSplitProposed,
+    NoTaskSplitPending,
+    TaskSplitOtherSession,
+    TaskSplitBlocked,
+    TaskSplitRejected,
+    TaskSplitOutdated,
+    TaskSplitApplied,
+    TaskSplitWriteFailed,
+    TaskSplitInvalid,
+    SafeModeRestoredTasks,
+    SafeModeStagedTasks,
+    MasterChangedTasks,
+    NoTaskChangesPending,
+    TaskChangesOtherSession,
+    KeptMasterTaskChanges,
+    RestoreTasksBlocked,
+    PreviousTasksReadFailed,
+    RejectedMasterTaskChanges,
+    RestoreTasksWriteFailed,
+    PreviousTasksInvalid,
+    DiscardedStagedTasks,
+    RemoveFileFailed,
+    StagedTasksBlocked,
+    StagedTasksReadFailed,
+    StagedTasksInvalid,
+    AppliedStagedTasks,
+    StagedTasksWriteFailed,
+    AppendFileFailed,
+    NoEditConflict,
+    WriteFileFailed,
+    NoExternalTasksEdit,
+    ExternalTasksEditOtherSession,
+    ExternalTasksEditDiscarded,
+    RestoreTasksFailed,
+    ReloadTasksBlocked,
+    ReloadTasksFailed,
+    ReloadedTasksResuming,
+    ReloadedTasks,
+    ReloadTasksInvalid,
+    FinalAuditBlocked,
+    FinalAuditDisabled,
+    FinalAuditReadFailed,
+    FinalAuditAdded,
+    FinalAuditAddWriteFailed,
+    FinalAuditRefreshFailed,
+    FinalAuditMissing,
+    FinalAuditRemoved,
+    FinalAuditRemoveWriteFailed,
+    NoQueuedJobs,
+    QueuedJobs,
+    MovedJob,
+    DroppedJob,
+    BulkTasksUsage,
+    BulkTasksUnchanged,
+    BulkTasksWriteFailed,
+    BulkTasksRefreshFailed,
+    CancelUsage,
+    NoProjectContextRunning,
+    ProjectContextCancelledSending,
+    ProjectContextCancelled,
+    NoDocsAttachRunning,
+    DocsAttachCancelled,
+    TaskSplitterCancelled,
+    NoTaskCheckRunning,
+    TaskCheckCancelled,
+    TaskCheckCancelledUnchanged,
+    TaskCheckCancelledRestored,
+    TaskCheckCancelRestoreFailed,
+    DirectUsage,
+    DirectRefreshFailed,
+    DirectWriteFailed,
+    DirectTaskAdded,
+    ConvertingPlanner,
+    PlannerWriteFailed,
+    ReportQueuePersistFailed,
+    SavedProjectMemory,
+    ProjectMemoryUpdateFailed,
+    MasterAskedQuestions,
+    QuestionsDismissed,
+    RecordAnswersFailed,
+    RerunNeedsSession,
+    FileTreeShown,
+    FileTreeHidden,
+    PreviewingPrompt,
+    PreviewCancelled,
+    StallWaiting,
+    StallNudging,
+    StallCannotNudge,
+    StallAborting,
+    StallAlreadyStopped,
+    FailureStatsHidden,
+    FailureStatsShown,
+    FailureStatsFailed,
+    WorkerLogsNeedSession,
+    NoWorkerLogsForTask,
+    NoWorkerLogs,
+    ListWorkerLogsFailed,
+    WorkerLogWriteFailed,
+    PinModelsUsage,
+    UpdateFileFailed,
+    UnpinnedModels,
+    PinnedModelsUnchanged,
+    PinnedModels,
+    PinnedModelDrift,
+    ModelDrift,
+    BackendUnchanged,
+    BackendSaved,
+    BackendSaveFailed,
+    BackendModelReloadFailed,
+    BackendFailover,
+    TasksReadFailed,
+    FoundPlanFile,
+    SplittingAudits,
+    MergingAudits,
+    CachedResultApplyFailed,
+    CachedResultSaveFailed,
+    ReportNoSummary,
+    TaskSummariesWriteFailed,
+    TaskSummarized,
+    ExecutionAlreadyRunning,
+    ResumedUnfinishedTasks,
+    ExecutionAlreadyEnabled,
+    ExecutionEnabled,
+    ExecutionEnabledScoped,
+    RerunningEditedPrompt,
+    PassReadyForReview,
+    FinalAuditPassed,
+    FinalAuditRetriesExhausted,
+    FinalAuditNotPassedRetry,
+    FinalAuditFailedRetry,
+    ScopedRunDone,
+    TaskSkipped,
+    TaskMarkedDone,
+    TaskResetForRetry,
+    TaskUnblocked,
+    UnblockedTaskQueued,
+    UnblockedTaskWaitsForStart,
+    PluginStepQueued,
+    ExistingTestsQueued,
+    ImplementationAuditsPassed,
+    AuditQueued,
+    PluginStepSkipped,
+    TestsOffSkippingTestWriter,
+    TestWriterAuditSkipped,
+    TestWriterAuditQueued,
+    TestRunnerQueueFailed,
+    TestRunQueued,
+    TaskBlockedOnUser,
+    TaskCompletedWithTests,
+    TaskCompleted,
+    AuditPassedWithoutCriteria,
+    AuditRetriesExhausted,
+    AuditRequestedFixes,
+    ImplementationResumedAudit,
+    ImplementationFailedRetry,
+    ProtectedPathsModified,
+    ReviewSentBack,
+    PluginStepPassed,
+    PluginStepRetriesExhausted,
+    PluginStepFailed,
+    TestsOffSkippingTestAuditRetries,
+    TestAuditRetriesExhausted,
+    TestAuditRequestedFixes,
+    TestAuditPassComplete,
+    DeterministicTestsPassed,
+    TestsOffSkippingTestRunnerRetries,
+    TestsRetriesExhaustedCleanup,
+    ExistingTestsPassed,
+    TestsOffSkippingExistingTestRetries,
+    ExistingTestsRetriesExhausted,
+    ExistingTestsFailed,
+    FailingTestsRemoved,
+    TestWriterResumedAudit,
+    TestsOffSkippingTestWriterRetries,
+    TestWriterRetriesExhausted,
+    TestWriterFailedRetry,
+    VerifyQueued,
+    VerifySkippedSafeMode,
+    VerifyPassed,
+    VerifyRetriesExhausted,
+    VerifyFailed,
+    DocSnapshotsDropped,
+    DocSnapshotsClearFailed,
+    DocSnapshotsSaved,
+    DocSnapshotsSanitizeFailed,
+    LockHeldHere,
+    ConflictKeptMine,
+    ConflictKeptTheirs,
+    ConflictMergedWithMarkers,
+    ConflictMerged,
+    ConflictReloadHint,
+    BulkTasksMarkedDone,
+    BulkTasksReset,
+    BulkTasksDeleted,
+    BulkTasksConfirmDelete,
+    ContextShown,
+    ContextSectionIncluded,
+    ContextSectionLeftOut,
+    ContextReset,
+    ContextSections,
+    SessionLockedElsewhere,
+    SessionLockedOnOtherHost,
+    ScopedRunContains,
+    CodeIndexRefreshFailed,
+    EditConflictNotice,
+    ProgressDigestSent,
+    ProgressDigestFailed,
+    FinalReportSent,
+    FinalReportFailed,
+    PluginFailed,
+    RemotePushFailed,
+    TicketSyncConfigFailed,
+    TicketMoved,
+    TicketUpdateFailed,
+    TasksEditedExternally,
+    TasksEditedExternallyInvalid,
+    ObservedTasksApplyFailed,
+    ObservedTasksParseFailed,
+    ObservedTaskSkipped,
+    ObservedTaskMarkedDone,
+    ObservedTaskFailed,
+    TestsFlakyWriterQueued,
+    TestsFailedWriterQueued,
+    ManualTaskFailed,
+    UnblockFailed,
+    ReviewDecisionFailed,
+    RerunUnavailable,
+    RerunFailed,
+    StartExecutionFailed,
+    MoveJobFailed,
+    DropJobFailed,
+    PluginRunFailed,
+    PluginOutputLine,
+    PluginPassed,
+    PluginPassedSummary,
+    PluginFailedVerdict,
+    PluginFailedSummary,
+    RemotePushed,
+    ModelEscalated,
+}
+
+impl Text {
+    /// Every key, so tests can check that each locale covers the whole catalog.
+    #[cfg(test)]
+    pub const ALL: [Text; 518] = [
+        Text::StatusHelpNarrow,
+        Text::StatusHelpWide,
+        Text::TestsOn,
+        Text::TestsOff,
+        Text::AttachingDocs,
+        Text::CheckingTasks,
+        Text::TabFiles,
+        Text::TabStats,
+        Text::TabConsole,
+        Text::TabWorkerOutput,
+        Text::TabAgentChat,
+        Text::TabShortChat,
+        Text::TabShortTasks,
+        Text::TabShortOutput,
+        Text::TabSystemLog,
+        Text::TabTaskThread,
+        Text::ChatObserving,
+        Text::ChatWorking,
+        Text::ChatQueued,
+        Text::PaneTaskList,
+        Text::PanePlannerMarkdown,
+        Text::PaneWorkerLog,
+        Text::PaneTaskSummaries,
+        Text::PaneFileTree,
+        Text::PaneFileViewer,
+        Text::PaneFailureStats,
+        Text::PaneConsole,
+        Text::ResumeTitle,
+        Text::ResumeHint,
+        Text::ResumeAllWorkspaces,
+        Text::ResumeFilter,
+        Text::ResumeNoMatches,
+        Text::ResumePreviewWorkspace,
+        Text::ResumePreviewTasks,
+        Text::ResumePreviewActivity,
+        Text::ResumePreviewNoPlanner,
+        Text::UnknownDate,
+        Text::JustNow,
+        Text::MinutesAgo,
+        Text::HoursAgo,
+        Text::DaysAgo,
+        Text::PreviewTitle,
+        Text::PreviewHint,
+        Text::QuestionsTitle,
+        Text::QuestionsHint,
+        Text::RerunTitle,
+        Text::RerunHint,
+        Text::HelpTitle,
+        Text::HelpHint,
+        Text::HelpSearch,
+        Text::HelpFocused,
+        Text::HelpNoMatches,
+        Text::BackendTitle,
+        Text::BackendHint,
+        Text::LogsTitle,
+        Text::LogsHint,
+        Text::LogSize,
+        Text::TaskMenuTitle,
+        Text::TaskMenuHint,
+        Text::TaskMenuExpandDetails,
+        Text::TaskMenuCollapseDetails,
+        Text::TaskMenuShowConversation,
+        Text::TaskMenuViewLogs,
+        Text::TaskMenuRetry,
+        Text::TaskMenuSkip,
+        Text::TaskMenuChangeModel,
+        Text::TaskMenuAttachDocs,
+        Text::TaskMenuDefaultModel,
+        Text::ExecJob,
+        Text::ExecIdle,
+        Text::ExecAwaitingReview,
+        Text::ExecQueued,
+        Text::ExecBackend,
+        Text::ExecTokens,
+        Text::ExecCost,
+        Text::ExecStalled,
+        Text::NoActiveSession,
+        Text::SessionDirInitialized,
+        Text::ProjectInfoBusy,
+        Text::MasterBusyQueued,
+        Text::TaskCheckQueued,
+        Text::ExecutionBusyBlocked,
+        Text::AdvisorAsked,
+        Text::AdvisorBusy,
+        Text::StartingJob,
+        Text::NoQueuedMessages,
+        Text::DroppedQueuedMessages,
+        Text::ReviewOn,
+        Text::ReviewOff,
+        Text::ReviewStatusOn,
+        Text::ReviewStatusOff,
+        Text::QuitWhileJobRunning,
+        Text::QuittingAfterJob,
+        Text::ContextUsage,
+        Text::WorkspaceSnapshotFailed,
+        Text::RevertedFiles,
+        Text::RunningPlugin,
+        Text::NoPluginsInstalled,
+        Text::PluginsInstalled,
+        Text::PluginsSafeMode,
+        Text::ProtectedPathsListFailed,
+        Text::ResumeOtherWorkspace,
+        Text::MessageBackInInput,
+        Text::ConsoleShown,
+        Text::ConsoleHidden,
+        Text::ConsoleRunning,
+        Text::ConsoleStopping,
+        Text::ConsoleNothingRunning,
+        Text::ConsoleKept,
+        Text::ConsoleStillRunning,
+        Text::ConsoleNothingToKeep,
+        Text::ConsoleCleared,
+        Text::ConsoleUsage,
+        Text::ConsoleFinished,
+        Text::ConsoleSafeMode,
+        Text::ObservingSession,
+        Text::ObserverReadOnly,
+        Text::ProjectInfoTrimmed,
+        Text::ProjectInfoTrimWriteFailed,
+        Text::ModelProfileConfigLoadFailed,
+        Text::SafeModeOn,
+        Text::TestsModeLoadFailed,
+        Text::FinalAuditPolicyLoadFailed,
+        Text::StallAfterLoadFailed,
+        Text::TaskSplitLimitsLoadFailed,
+        Text::WorkerLogSettingsLoadFailed,
+        Text::FailoverThresholdLoadFailed,
+        Text::RerunFailedTestsLoadFailed,
+        Text::DocsUpdateTaskLoadFailed,
+        Text::ProtectedPathsLoadFailed,
+        Text::VerifyLoadFailed,
+        Text::CommandsLoadFailed,
+        Text::PluginLoadFailed,
+        Text::PluginsDirUnavailable,
+        Text::OutputFiltersLoadFailed,
+        Text::ResponseCacheOff,
+        Text::OfferRecentSessionLoadFailed,
+        Text::ExportingTraces,
+        Text::TelemetryLoadFailed,
+        Text::CodeContextLoadFailed,
+        Text::ProjectInfoScopeLoadFailed,
+        Text::GraphicsLoadFailed,
+        Text::BackendMetricsOff,
+        Text::NarrowWidthLoadFailed,
+        Text::LanguageSettingsLoadFailed,
+        Text::BatchScriptStarted,
+        Text::EmailDigestLoadFailed,
+        Text::TaskSummariesReadFailed,
+        Text::DeliveringLeftoverReports,
+        Text::ReportQueueReadFailed,
+        Text::DocsPolicyEnforceFailed,
+        Text::RemovedMasterDocs,
+        Text::AppendedTask,
+        Text::AutomaticTasksWriteFailed,
+        Text::TaskTreeRefreshFailed,
+        Text::TasksReadAfterMasterFailed,
+        Text::TasksCorrectionRequested,
+        Text::TasksCorrectionRetriesExceeded,
+        Text::TaskStatusPersistFailed,
+        Text::RetryingWorkerJob,
+        Text::TransientRetryScheduled,
+        Text::TransientRetriesExhausted,
+        Text::AdvisorEditRejected,
+        Text::AdvisorEditUndoFailed,
+        Text::ProjectInfoReadFailed,
+        Text::ProjectContextAttached,
+        Text::ProjectInfoFallbackWriteFailed,
+        Text::ProjectContextEmpty,
+        Text::ProjectContextExited,
+        Text::SessionModelsRecordFailed,
+        Text::SessionMetadataSaved,
+        Text::SessionMetadataWritten,
+        Text::SessionMetadataExited,
+        Text::DocsAttached,
+        Text::DocsAttachExited,
+        Text::DocsAttachRefreshFailed,
+        Text::DocsAttachReadFailed,
+        Text::TaskCheckerFixed,
+        Text::TaskCheckerRefreshFailed,
+        Text::TaskCheckerReadFailed,
+        Text::SessionTakenOver,
+        Text::EventLogWriteFailed,
+        Text::TraceExportFailed,
+        Text::AuditTrailWriteFailed,
+        Text::SendingQueuedMessage,
+        Text::SendingQueuedMessageRemaining,
+        Text::BatchStep,
+        Text::MasterPromptSent,
+        Text::RerunCancelled,
+        Text::PlannerEditSessionInitFailed,
+        Text::StartingNewSession,
+        Text::ResumePickerCancelled,
+        Text::BackendPickerCancelled,
+        Text::LogPickerCancelled,
+        Text::MasterStillProcessing,
+        Text::DocsAttachBlocksSwitch,
+        Text::DroppedBatchSteps,
+        Text::TasksEditedBeforeSkip,
+        Text::TasksWriteAfterUpdateFailed,
+        Text::TasksEditedBeforeUnblock,
+        Text::TasksEditedBeforeEdit,
+        Text::SpeedProfileSet,
+        Text::SpeedProfileSaveFailed,
+        Text::SpeedProfileStatus,
+        Text::ReviewGateSaveFailed,
+        Text::TasksWriteAfterReviewFailed,
+        Text::NewSessionBlocked,
+        Text::StartedNewSession,
+        Text::TemplateSessionBlocked,
+        Text::StartedTemplateSession,
+        Text::TemplateSessionFailed,
+        Text::DocsAttachAlreadyRunning,
+        Text::AttachDocsSafeMode,
+        Text::NoTaskWithId,
+        Text::DocsAttachStarted,
+        Text::NoSavedSessions,
+        Text::NoOtherSessionsToResume,
+        Text::ResumeOtherWorkspacesOnly,
+        Text::ResumePickerHint,
+        Text::ResumePickerHintWithOthers,
+        Text::ListSessionsFailed,
+        Text::NoOtherSessionToSwitch,
+        Text::LockTakeoverFailed,
+        Text::TookOverSession,
+        Text::FollowingLockedSession,
+        Text::NoLockedSessionWaiting,
+        Text::LockUsage,
+        Text::BackendPickerHint,
+        Text::TestsModeSaved,
+        Text::TestsModeSaveFailed,
+        Text::PlannerModeEnabled,
+        Text::RollingContextPersistFailed,
+        Text::TaskModelStatus,
+        Text::UnknownModelProfile,
+        Text::TaskModelSet,
+        Text::TaskModelReset,
+        Text::PromptPreviewOn,
+        Text::PromptPreviewOff,
+        Text::ResponseCacheStats,
+        Text::ResponseCachePurged,
+        Text::ResponseCachePurgeFailed,
+        Text::CacheUsage,
+        Text::SummariesHidden,
+        Text::SummariesShown,
+        Text::VerboseOn,
+        Text::VerboseOff,
+        Text::SkipPlanEnabled,
+        Text::ConvertNeedsPlanner,
+        Text::ConvertPlannerConflict,
+        Text::ConvertPersistFailed,
+        Text::NoPlanToImport,
+        Text::ImportedPlan,
+        Text::ImportedPlanHint,
+        Text::ImportPlanConflict,
+        Text::ImportPlanWriteFailed,
+        Text::UnknownCommand,
+        Text::GatheringProjectContext,
+        Text::ProjectContextStillGathering,
+        Text::StartedExecution,
+        Text::ClearedStaleLock,
+        Text::LockWriteFailed,
+        Text::ResumeWorkspaceMissing,
+        Text::ResumeSessionFailed,
+        Text::ResumedSession,
+        Text::WorkingDirectoryChanged,
+        Text::TemplateUsageNoTemplates,
+        Text::TemplateUsage,
+        Text::ListTemplatesFailed,
+        Text::ResumedTaskTreeRefreshFailed,
+        Text::UiStateSaveFailed,
+        Text::InterruptedDuringJob,
+        Text::InterruptedDuringExecution,
+        Text::RestoredQueuedMessages,
+        Text::InterruptedRunClearFailed,
+        Text::RecentSessionsOffer,
+        Text::TaskSplitStaleRemoveFailed,
+        Text::TaskSplitKept,
+        Text::TaskSplitReadFailed,
+        Text::TaskSplitDiscarded,
+        Text::TaskSplitProposed,
+        Text::NoTaskSplitPending,
+        Text::TaskSplitOtherSession,
+        Text::TaskSplitBlocked,
+        Text::TaskSplitRejected,
+        Text::TaskSplitOutdated,
+        Text::TaskSplitApplied,
+        Text::TaskSplitWriteFailed,
+        Text::TaskSplitInvalid,
+        Text::SafeModeRestoredTasks,
+        Text::SafeModeStagedTasks,
+        Text::MasterChangedTasks,
+        Text::NoTaskChangesPending,
+        Text::TaskChangesOtherSession,
+        Text::KeptMasterTaskChanges,
+        Text::RestoreTasksBlocked,
+        Text::PreviousTasksReadFailed,
+        Text::RejectedMasterTaskChanges,
+        Text::RestoreTasksWriteFailed,
+        Text::PreviousTasksInvalid,
+        Text::DiscardedStagedTasks,
+        Text::RemoveFileFailed,
+        Text::StagedTasksBlocked,
+        Text::StagedTasksReadFailed,
+        Text::StagedTasksInvalid,
+        Text::AppliedStagedTasks,
+        Text::StagedTasksWriteFailed,
+        Text::AppendFileFailed,
+        Text::NoEditConflict,
+        Text::WriteFileFailed,
+        Text::NoExternalTasksEdit,
+        Text::ExternalTasksEditOtherSession,
+        Text::ExternalTasksEditDiscarded,
+        Text::RestoreTasksFailed,
+        Text::ReloadTasksBlocked,
+        Text::ReloadTasksFailed,
+        Text::ReloadedTasksResuming,
+        Text::ReloadedTasks,
+        Text::ReloadTasksInvalid,
+        Text::FinalAuditBlocked,
+        Text::FinalAuditDisabled,
+        Text::FinalAuditReadFailed,
+        Text::FinalAuditAdded,
+        Text::FinalAuditAddWriteFailed,
+        Text::FinalAuditRefreshFailed,
+        Text::FinalAuditMissing,
+        Text::FinalAuditRemoved,
+        Text::FinalAuditRemoveWriteFailed,
+        Text::NoQueuedJobs,
+        Text::QueuedJobs,
+        Text::MovedJob,
+        Text::DroppedJob,
+        Text::BulkTasksUsage,
+        Text::BulkTasksUnchanged,
+        Text::BulkTasksWriteFailed,
+        Text::BulkTasksRefreshFailed,
+        Text::CancelUsage,
+        Text::NoProjectContextRunning,
+        Text::ProjectContextCancelledSending,
+        Text::ProjectContextCancelled,
+        Text::NoDocsAttachRunning,
+        Text::DocsAttachCancelled,
+        Text::TaskSplitterCancelled,
+        Text::NoTaskCheckRunning,
+        Text::TaskCheckCancelled,
+        Text::TaskCheckCancelledUnchanged,
+        Text::TaskCheckCancelledRestored,
+        Text::TaskCheckCancelRestoreFailed,
+        Text::DirectUsage,
+        Text::DirectRefreshFailed,
+        Text::DirectWriteFailed,
+        Text::DirectTaskAdded,
+        Text::ConvertingPlanner,
+        Text::PlannerWriteFailed,
+        Text::ReportQueuePersistFailed,
+        Text::SavedProjectMemory,
+        Text::ProjectMemoryUpdateFailed,
+        Text::MasterAskedQuestions,
+        Text::QuestionsDismissed,
+        Text::RecordAnswersFailed,
+        Text::RerunNeedsSession,
+        Text::FileTreeShown,
+        Text::FileTreeHidden,
+        Text::PreviewingPrompt,
+        Text::PreviewCancelled,
+        Text::StallWaiting,
+        Text::StallNudging,
+        Text::StallCannotNudge,
+        Text::StallAborting,
+        Text::StallAlreadyStopped,
+        Text::FailureStatsHidden,
+        Text::FailureStatsShown,
+        Text::FailureStatsFailed,
+        Text::WorkerLogsNeedSession,
+        Text::NoWorkerLogsForTask,
+        Text::NoWorkerLogs,
+        Text::ListWorkerLogsFailed,
+        Text::WorkerLogWriteFailed,
+        Text::PinModelsUsage,
+        Text::UpdateFileFailed,
+        Text::UnpinnedModels,
+        Text::PinnedModelsUnchanged,
+        Text::PinnedModels,
+        Text::PinnedModelDrift,
+        Text::ModelDrift,
+        Text::BackendUnchanged,
+        Text::BackendSaved,
+        Text::BackendSaveFailed,
+        Text::BackendModelReloadFailed,
+        Text::BackendFailover,
+        Text::TasksReadFailed,
+        Text::FoundPlanFile,
+        Text::SplittingAudits,
+        Text::MergingAudits,
+        Text::CachedResultApplyFailed,
+        Text::CachedResultSaveFailed,
+        Text::ReportNoSummary,
+        Text::TaskSummariesWriteFailed,
+        Text::TaskSummarized,
+        Text::ExecutionAlreadyRunning,
+        Text::ResumedUnfinishedTasks,
+        Text::ExecutionAlreadyEnabled,
+        Text::ExecutionEnabled,
+        Text::ExecutionEnabledScoped,
+        Text::RerunningEditedPrompt,
+        Text::PassReadyForReview,
+        Text::FinalAuditPassed,
+        Text::FinalAuditRetriesExhausted,
+        Text::FinalAuditNotPassedRetry,
+        Text::FinalAuditFailedRetry,
+        Text::ScopedRunDone,
+        Text::TaskSkipped,
+        Text::TaskMarkedDone,
+        Text::TaskResetForRetry,
+        Text::TaskUnblocked,
+        Text::UnblockedTaskQueued,
+        Text::UnblockedTaskWaitsForStart,
+        Text::PluginStepQueued,
+        Text::ExistingTestsQueued,
+        Text::ImplementationAuditsPassed,
+        Text::AuditQueued,
+        Text::PluginStepSkipped,
+        Text::TestsOffSkippingTestWriter,
+        Text::TestWriterAuditSkipped,
+        Text::TestWriterAuditQueued,
+        Text::TestRunnerQueueFailed,
+        Text::TestRunQueued,
+        Text::TaskBlockedOnUser,
+        Text::TaskCompletedWithTests,
+        Text::TaskCompleted,
+        Text::AuditPassedWithoutCriteria,
+        Text::AuditRetriesExhausted,
+        Text::AuditRequestedFixes,
+        Text::ImplementationResumedAudit,
+        Text::ImplementationFailedRetry,
+        Text::ProtectedPathsModified,
+        Text::ReviewSentBack,
+        Text::PluginStepPassed,
+        Text::PluginStepRetriesExhausted,
+        Text::PluginStepFailed,
+        Text::TestsOffSkippingTestAuditRetries,
+        Text::TestAuditRetriesExhausted,
+        Text::TestAuditRequestedFixes,
+        Text::TestAuditPassComplete,
+        Text::DeterministicTestsPassed,
+        Text::TestsOffSkippingTestRunnerRetries,
+        Text::TestsRetriesExhaustedCleanup,
+        Text::ExistingTestsPassed,
+        Text::TestsOffSkippingExistingTestRetries,
+        Text::ExistingTestsRetriesExhausted,
+        Text::ExistingTestsFailed,
+        Text::FailingTestsRemoved,
+        Text::TestWriterResumedAudit,
+        Text::TestsOffSkippingTestWriterRetries,
+        Text::TestWriterRetriesExhausted,
+        Text::TestWriterFailedRetry,
+        Text::VerifyQueued,
+        Text::VerifySkippedSafeMode,
+        Text::VerifyPassed,
+        Text::VerifyRetriesExhausted,
+        Text::VerifyFailed,
+        Text::DocSnapshotsDropped,
+        Text::DocSnapshotsClearFailed,
+        Text::DocSnapshotsSaved,
+        Text::DocSnapshotsSanitizeFailed,
+        Text::LockHeldHere,
+        Text::ConflictKeptMine,
+        Text::ConflictKeptTheirs,
+        Text::ConflictMergedWithMarkers,
+        Text::ConflictMerged,
+        Text::ConflictReloadHint,
+        Text::BulkTasksMarkedDone,
+        Text::BulkTasksReset,
+        Text::BulkTasksDeleted,
+        Text::BulkTasksConfirmDelete,
+        Text::ContextShown,
+        Text::ContextSectionIncluded,
+        Text::ContextSectionLeftOut,
+        Text::ContextReset,
+        Text::ContextSections,
+        Text::SessionLockedElsewhere,
+        Text::SessionLockedOnOtherHost,
+        Text::ScopedRunContains,
+        Text::CodeIndexRefreshFailed,
+        Text::EditConflictNotice,
+        Text::ProgressDigestSent,
+        Text::ProgressDigestFailed,
+        Text::FinalReportSent,
+        Text::FinalReportFailed,
+        Text::PluginFailed,
+        Text::RemotePushFailed,
+        Text::TicketSyncConfigFailed,
+        Text::TicketMoved,
+        Text::TicketUpdateFailed,
+        Text::TasksEditedExternally,
+        Text::TasksEditedExternallyInvalid,
+        Text::ObservedTasksApplyFailed,
+        Text::ObservedTasksParseFailed,
+        Text::ObservedTaskSkipped,
+        Text::ObservedTaskMarkedDone,
+        Text::ObservedTaskFailed,
+        Text::TestsFlakyWriterQueued,
+        Text::TestsFailedWriterQueued,
+        Text::ManualTaskFailed,
+        Text::UnblockFailed,
+        Text::ReviewDecisionFailed,
+        Text::RerunUnavailable,
+        Text::RerunFailed,
+        Text::StartExecutionFailed,
+        Text::MoveJobFailed,
+        Text::DropJobFailed,
+        Text::PluginRunFailed,
+        Text::PluginOutputLine,
+        Text::PluginPassed,
+        Text::PluginPassedSummary,
+        Text::PluginFailedVerdict,
+        Text::PluginFailedSummary,
+        Text::RemotePushed,
+        Text::ModelEscalated,
+    ];
+}
+
+pub fn text(locale: Locale, key: Text) -> &'static str {
+    match locale {
+        Locale::En => english(key),
+        Locale::Es => spanish(key),
+    }
+}
+
+/// Catalog entry for `key` with each `{name}` placeholder replaced by its value.
+pub fn fill(locale: Locale, key: Text, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = text(locale, key).to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    out
+}
+
+/// `System:` chat line for a catalog entry; the prefix itself is not translated.
+pub fn system(locale: Locale, key: Text, args: &[(&str, &dyn Display)]) -> String {
+    format!("System: {}", fill(locale, key, args))
+}
+
+fn english(key: Text) -> &'static str {
+    match key {
+        Text::StatusHelpNarrow => {
+            "Tab/Shift+Tab switch views | Click tabs at top to switch | Click [^]/[v] buttons on right to scroll active tab by half-page | Ctrl+U/Ctrl+D or PgUp/PgDn scroll main right pane | Ctrl+L chat/system log | F1/? all keys"
+        }
+        Text::StatusHelpWide => {
+            "Tab/Shift+Tab focus | Ctrl+L chat/log | Ctrl+U/Ctrl+D or PgUp/PgDn scroll right pane | F1/? keys"
+        }
+        Text::TestsOn => "TESTS: ON",
+        Text::TestsOff => "TESTS: OFF",
+        Text::AttachingDocs => "Attaching Documentation...",
+        Text::CheckingTasks => "Checking Tasks...",
+        Text::TabFiles => "Files",
+        Text::TabStats => "Stats",
+        Text::TabConsole => "Console",
+        Text::TabWorkerOutput => "Worker Output",
+        Text::TabAgentChat => "Agent Chat",
+        Text::TabShortChat => "Chat",
+        Text::TabShortTasks => "Tasks",
+        Text::TabShortOutput => "Output",
+        Text::TabSystemLog => "System Log",
+        Text::TabTaskThread => "Task {task} Chat",
+        Text::ChatObserving => "Observing (read-only)",
+        Text::ChatWorking => "Working",
+        Text::ChatQueued => "{count} queued",
+        Text::PaneTaskList => "Task List",
+        Text::PanePlannerMarkdown => "Planner Markdown",
+        Text::PaneWorkerLog => "Worker Log (read-only, Backspace closes)",
+        Text::PaneTaskSummaries => "Task Summaries (Backspace returns to tasks)",
+        Text::PaneFileTree => "Files (* changed this session)",
+        Text::PaneFileViewer => "{path} (read-only, Backspace closes)",
+        Text::PaneFailureStats => "Failure Stats (all sessions)",
+        Text::PaneConsole => "Console (workspace shell)",
+        Text::ResumeTitle => "Resume Session",
+        Text::ResumeHint => {
+            "(type to filter, Up/Down select, Enter/Space resume, Tab other workspaces)"
+        }
+        Text::ResumeAllWorkspaces => "All workspaces",
+        Text::ResumeFilter => "Filter: {query}",
+        Text::ResumeNoMatches => "No sessions match the filter",
+        Text::ResumePreviewWorkspace => "Workspace: {path}",
+        Text::ResumePreviewTasks => "Tasks: {done}/{total} done",
+        Text::ResumePreviewActivity => "Last activity: {when}",
+        Text::ResumePreviewNoPlanner => "planner.md is empty",
+        Text::UnknownDate => "unknown date",
+        Text::JustNow => "just now",
+        Text::MinutesAgo => "{count}m ago",
+        Text::HoursAgo => "{count}h ago",
+        Text::DaysAgo => "{count}d ago",
+        Text::PreviewTitle => "Master Prompt Preview",
+        Text::PreviewHint => "({chars} chars; Enter send, Backspace cancel, Up/Down scroll)",
+        Text::QuestionsTitle => "Master Questions",
+        Text::QuestionsHint => "(Enter next/send, Up/Down or Tab switch; send blank to dismiss)",
+        Text::RerunTitle => "Re-run {task}: {label}",
+        Text::RerunHint => "(Enter re-run, Shift+Enter newline, Shift+Tab cancel)",
+        Text::HelpTitle => "Key Bindings",
+        Text::HelpHint => "(type to search, Up/Down scroll, Enter or Backspace closes)",
+        Text::HelpSearch => "Search: {query} (Backspace deletes, Enter closes)",
+        Text::HelpFocused => "(focused)",
+        Text::HelpNoMatches => "No key bindings match the search",
+        Text::BackendTitle => "Select Backend",
+        Text::BackendHint => "(Up/Down select, Enter/Space choose)",
+        Text::LogsTitle => "Worker Logs",
+        Text::LogsHint => "(newest first; Up/Down select, Enter/Space open)",
+        Text::LogSize => "({bytes} bytes)",
+        Text::TaskMenuTitle => "Task {id}: {title}",
+        Text::TaskMenuHint => "(Up/Down select, Enter/Space choose, Backspace close)",
+        Text::TaskMenuExpandDetails => "Expand details",
+        Text::TaskMenuCollapseDetails => "Collapse details",
+        Text::TaskMenuShowConversation => "Show conversation",
+        Text::TaskMenuViewLogs => "View logs",
+        Text::TaskMenuRetry => "Retry",
+        Text::TaskMenuSkip => "Skip (I will handle it)",
+        Text::TaskMenuChangeModel => "Change model",
+        Text::TaskMenuAttachDocs => "Attach docs to this task",
+        Text::TaskMenuDefaultModel => "Default (agent routing)",
+        Text::ExecJob => "Job: {job}",
+        Text::ExecIdle => "idle",
+        Text::ExecAwaitingReview => "awaiting review",
+        Text::ExecQueued => "Queued: {count}",
+        Text::ExecBackend => "Backend: {backend}",
+        Text::ExecTokens => "Tokens: {input} in / {output} out",
+        Text::ExecCost => "Cost: ${cost}",
+        Text::ExecStalled => "Stalled? {silent} without output (w wait, n nudge, a abort)",
+        Text::NoActiveSession => {
+            "No active session yet. Enter a normal message to start one, or use /resume to select an existing session."
+        }
+        Text::SessionDirInitialized => "Session dir initialized at {path}",
+        Text::ProjectInfoBusy => {
+            "Project context gathering is in progress. Enter/Return submissions are temporarily disabled until it completes; /cancel context skips it."
+        }
+        Text::MasterBusyQueued => {
+            "Master is still processing your previous request. Queued as #{position}; it is sent when that finishes. Use /clear-queue to drop queued messages."
+        }
+        Text::TaskCheckQueued => {
+            "Task checking is in progress. Queued as #{position}; it is sent when that finishes. Use /clear-queue to drop queued messages or /cancel taskcheck to stop the check."
+        }
+        Text::ExecutionBusyBlocked => {
+            "Execution is currently running. Master/task editing commands are blocked until active worker jobs finish."
+        }
+        Text::AdvisorAsked => {
+            "Execution is running, so a read-only advisor answers instead of the master. It cannot change tasks.json; send plan changes once execution finishes."
+        }
+        Text::AdvisorBusy => {
+            "The advisor is still answering your previous question; send this again when it finishes."
+        }
+        Text::StartingJob => "Starting {role} for task #{task}.",
+        Text::NoQueuedMessages => "No queued messages.",
+        Text::DroppedQueuedMessages => "Dropped {count} queued message(s); they will not be sent.",
+        Text::ReviewOn => {
+            "Review gate ON. Each implementor pass waits for /review approve, /review changes <feedback>, or /review revert before its audit."
+        }
+        Text::ReviewOff => "Review gate OFF. Implementor passes go straight to audit.",
+        Text::ReviewStatusOn => {
+            "Review gate is ON. Usage: /review on|off|approve|revert, or /review changes <feedback>."
+        }
+        Text::ReviewStatusOff => {
+            "Review gate is OFF. Usage: /review on|off|approve|revert, or /review changes <feedback>."
+        }
+        Text::QuitWhileJobRunning => {
+            "A worker job is still running. /quit wait lets it finish and then quits, /quit abort stops it now and resets its task to pending, and /quit detach closes the TUI while the job finishes in the background. Quit again to abort."
+        }
+        Text::QuittingAfterJob => "Quitting once the active job finishes; no new jobs will start.",
+        Text::ContextUsage => "Usage: /context [{keys}|reset]",
+        Text::WorkspaceSnapshotFailed => {
+            "Could not snapshot the workspace ({err}); the review diff, /review revert, and the git check of protected paths are unavailable for this pass."
+        }
+        Text::RevertedFiles => "Reverted {reverted} file(s) changed by task #{task} pass {pass}.",
+        Text::RunningPlugin => "Running plugin {name}...",
+        Text::NoPluginsInstalled => {
+            "No plugins are installed. Put plugin executables in ~/.agentbob/plugins/ and restart Bob."
+        }
+        Text::PluginsInstalled => "{count} plugin(s) installed:",
+        Text::PluginsSafeMode => {
+            "Plugins run programs outside the sandbox, so they are off in safe mode."
+        }
+        Text::ProtectedPathsListFailed => {
+            "Could not list workspace changes for the protected-path check ({err}); only FILES_CHANGED is checked for this pass."
+        }
+        Text::ResumeOtherWorkspace => {
+            "Session {session} belongs to {workspace}. Press Enter again to change the working directory there and resume it; agents restart in that workspace."
+        }
+        Text::MessageBackInInput => {
+            "Your message is back in the input; press Enter to send it to the resumed session."
+        }
+        Text::ConsoleShown => {
+            "Console shown in the top-left pane. Run a command in the workspace with /console run <command>; /console again hides it."
+        }
+        Text::ConsoleHidden => "Console hidden; the top-left pane shows worker output again.",
+        Text::ConsoleRunning => "Running `{command}` in the console.",
+        Text::ConsoleStopping => "Stopping `{last_command}`.",
+        Text::ConsoleNothingRunning => "No console command is running.",
+        Text::ConsoleKept => "Added the output of `{last_command}` to the rolling task context.",
+        Text::ConsoleStillRunning => {
+            "`{last_command}` is still running; keep its output once it finishes."
+        }
+        Text::ConsoleNothingToKeep => "No finished console command to keep.",
+        Text::ConsoleCleared => "Console cleared.",
+        Text::ConsoleUsage => "Usage: /console [run <command>|stop|keep|clear]",
+        Text::ConsoleFinished => {
+            "Console command `{command}` finished ({status}). /console keep adds its output to the rolling task context."
+        }
+        Text::ConsoleSafeMode => "/console run is disabled in safe mode.",
+        Text::ObservingSession => {
+            "Observing session {session} (read-only). Prompts cannot be submitted; use /quit to exit."
+        }
+        Text::ObserverReadOnly => {
+            "Observer mode is read-only. Only /files, /stats, /planner, /skip-plan, and /quit are available."
+        }
+        Text::ProjectInfoTrimmed => {
+            "project-info.md was over project_info.max_tokens ({max_tokens}); trimmed to fit."
+        }
+        Text::ProjectInfoTrimWriteFailed => "Failed to write the trimmed project-info.md: {err}",
+        Text::ModelProfileConfigLoadFailed => {
+            "Failed to load model profile config from ~/.agentbob/config.toml (legacy fallbacks: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}. Using defaults."
+        }
+        Text::SafeModeOn => {
+            "Safe mode is on. Agents run in the backend's read-only sandbox and can only write session files. The master's task changes are staged for /accept-tasks or /reject-tasks, and plugins, verify commands, and /console are off."
+        }
+        Text::TestsModeLoadFailed => {
+            "Failed to load global tests mode from config.toml; defaulting to ON: {err}"
+        }
+        Text::FinalAuditPolicyLoadFailed => {
+            "Failed to load final audit policy from config.toml; defaulting to manual: {err}"
+        }
+        Text::StallAfterLoadFailed => {
+            "Failed to load workflow.stall_after_secs from config.toml; using 300 seconds: {err}"
+        }
+        Text::TaskSplitLimitsLoadFailed => {
+            "Failed to load task split limits from config.toml; using defaults: {err}"
+        }
+        Text::WorkerLogSettingsLoadFailed => {
+            "Failed to load worker log settings from config.toml; using defaults: {err}"
+        }
+        Text::FailoverThresholdLoadFailed => {
+            "Failed to load backend failover threshold from config.toml; using {threshold}: {err}"
+        }
+        Text::RerunFailedTestsLoadFailed => {
+            "Failed to load workflow.rerun_failed_tests from config.toml; re-runs are off: {err}"
+        }
+        Text::DocsUpdateTaskLoadFailed => {
+            "Failed to load workflow.docs_update_task from config.toml; no docs task will be added: {err}"
+        }
+        Text::ProtectedPathsLoadFailed => {
+            "Failed to load workflow.protected_paths from config.toml; no paths are protected: {err}"
+        }
+        Text::VerifyLoadFailed => {
+            "Failed to load workflow.verify from config.toml; no verify command runs: {err}"
+        }
+        Text::CommandsLoadFailed => {
+            "Failed to load [commands] from config.toml; no aliases or macros are defined: {err}"
+        }
+        Text::PluginLoadFailed => "Failed to load plugin {err}",
+        Text::PluginsDirUnavailable => {
+            "Plugins are off; the plugins directory could not be located: {err}"
+        }
+        Text::OutputFiltersLoadFailed => {
+            "Failed to load output_filters from config.toml; using the default filters: {err}"
+        }
+        Text::ResponseCacheOff => {
+            "Response cache is off; task checks and docs attach always call the model: {err}"
+        }
+        Text::OfferRecentSessionLoadFailed => {
+            "Failed to load storage.offer_recent_session from config.toml; recent sessions will not be offered: {err}"
+        }
+        Text::ExportingTraces => "Exporting traces to {url}.",
+        Text::TelemetryLoadFailed => {
+            "Failed to load telemetry settings from config.toml; tracing is off: {err}"
+        }
+        Text::CodeContextLoadFailed => {
+            "Failed to load code context settings from config.toml; using defaults: {err}"
+        }
+        Text::ProjectInfoScopeLoadFailed => {
+            "Failed to load [project_info] from config.toml; project info explores without limits: {err}"
+        }
+        Text::GraphicsLoadFailed => {
+            "Failed to load [ui] graphics from config.toml; images show as text: {err}"
+        }
+        Text::BackendMetricsOff => "Backend call metrics are off; no storage directory: {err}",
+        Text::NarrowWidthLoadFailed => {
+            "Failed to load [ui] narrow_width from config.toml; using {width}: {err}"
+        }
+        Text::LanguageSettingsLoadFailed => {
+            "Failed to load [ui] language settings from config.toml; using English: {err}"
+        }
+        Text::BatchScriptStarted => {
+            "Running a batch script of {steps} steps. Each step is sent once everything the previous one started has finished; /clear-queue drops the rest."
+        }
+        Text::EmailDigestLoadFailed => {
+            "Failed to load email_digest from config.toml; e-mail digests are off: {err}"
+        }
+        Text::TaskSummariesReadFailed => "Failed to read task-summaries.json: {err}",
+        Text::DeliveringLeftoverReports => {
+            "Delivering {restored} master report(s) left over from the last run."
+        }
+        Text::ReportQueueReadFailed => "Failed to read master-report-queue.json: {err}",
+        Text::DocsPolicyEnforceFailed => "Failed to enforce docs policy in tasks.json: {err}",
+        Text::RemovedMasterDocs => {
+            "Removed master-written docs entries; use /attach-docs to populate docs."
+        }
+        Text::AppendedTask => "Appended {task}.",
+        Text::AutomaticTasksWriteFailed => "Failed to write automatic tasks to tasks.json: {err}",
+        Text::TaskTreeRefreshFailed => "Failed to refresh task tree from tasks.json: {err}",
+        Text::TasksReadAfterMasterFailed => "Failed to read tasks.json after master update: {err}",
+        Text::TasksCorrectionRequested => {
+            "Requested tasks.json correction from master (attempt {attempt})."
+        }
+        Text::TasksCorrectionRetriesExceeded => {
+            "tasks.json correction retries exceeded. Waiting for next user input."
+        }
+        Text::TaskStatusPersistFailed => {
+            "Failed to persist runtime task status to tasks.json: {err}"
+        }
+        Text::RetryingWorkerJob => "Retrying the worker job now.",
+        Text::TransientRetryScheduled => {
+            "The worker agent hit a transient backend failure ({reason}); sending the same job again in {delay}s (retry {retry} of {max}). This does not use the task's audit or test retries."
+        }
+        Text::TransientRetriesExhausted => {
+            "The worker agent still fails after {max} retries ({reason}); counting the run as failed."
+        }
+        Text::AdvisorEditRejected => {
+            "The advisor edited tasks.json; the edit was rejected and the file restored."
+        }
+        Text::AdvisorEditUndoFailed => "Failed to undo the advisor's tasks.json edit: {err}",
+        Text::ProjectInfoReadFailed => {
+            "Project info run succeeded but reading project-info.md failed: {err}"
+        }
+        Text::ProjectContextAttached => "Project context gathered and attached for this session.",
+        Text::ProjectInfoFallbackWriteFailed => {
+            "Failed to persist project-info.md fallback output: {err}"
+        }
+        Text::ProjectContextEmpty => {
+            "Project context run returned no content; proceeding without attachment."
+        }
+        Text::ProjectContextExited => {
+            "Project context gather exited with code {code}; proceeding without attachment."
+        }
+        Text::SessionModelsRecordFailed => {
+            "Failed to record this session's models in meta.json: {err}"
+        }
+        Text::SessionMetadataSaved => "Session metadata saved: \"{title}\" ({created_at})",
+        Text::SessionMetadataWritten => "Session metadata write completed.",
+        Text::SessionMetadataExited => {
+            "Session metadata write exited with code {code}; continuing."
+        }
+        Text::DocsAttached => "Documentation has been attached to planner tasks.{snapshot_note}",
+        Text::DocsAttachExited => "Documentation attach run exited with code {code}.",
+        Text::DocsAttachRefreshFailed => "Docs attach completed but task refresh failed: {err}",
+        Text::DocsAttachReadFailed => "Docs attach completed but reading tasks.json failed: {err}",
+        Text::TaskCheckerFixed => "Task checker applied fixes to tasks.json.",
+        Text::TaskCheckerRefreshFailed => "Task checker completed but task refresh failed: {err}",
+        Text::TaskCheckerReadFailed => "Task checker completed but tasks.json could not be read.",
+        Text::SessionTakenOver => {
+            "{owner} took over this session with /lock takeover. Its jobs were stopped here and you are now following it read-only."
+        }
+        Text::EventLogWriteFailed => "Failed to write session event log: {err}",
+        Text::TraceExportFailed => "Trace export failed: {err}",
+        Text::AuditTrailWriteFailed => {
+            "Failed to write audit-trail.jsonl; later entries are not recorded: {err}"
+        }
+        Text::SendingQueuedMessage => "Sending queued message: {message}",
+        Text::SendingQueuedMessageRemaining => {
+            "Sending queued message ({remaining} still queued): {message}"
+        }
+        Text::BatchStep => "Batch step {number}/{total}.",
+        Text::MasterPromptSent => "Master prompt sent.",
+        Text::RerunCancelled => "Re-run cancelled.",
+        Text::PlannerEditSessionInitFailed => {
+            "Failed to initialize session for planner edit: {err}"
+        }
+        Text::StartingNewSession => "Starting a new session.",
+        Text::ResumePickerCancelled => "Resume picker cancelled.",
+        Text::BackendPickerCancelled => "Backend picker cancelled.",
+        Text::LogPickerCancelled => "Log picker cancelled.",
+        Text::MasterStillProcessing => {
+            "Master is still processing your previous request. Please wait for completion before sending another message."
+        }
+        Text::DocsAttachBlocksSwitch => {
+            "Documentation attach is still running. Wait for it to finish or use /cancel docs before switching sessions."
+        }
+        Text::DroppedBatchSteps => "Dropped the {batch_steps} remaining batch script steps.",
+        Text::TasksEditedBeforeSkip => {
+            "tasks.json was edited outside the app. Use /reload-tasks or /keep-tasks before skipping or completing tasks."
+        }
+        Text::TasksWriteAfterUpdateFailed => {
+            "Failed to write tasks file after updating {task_id}: {err}"
+        }
+        Text::TasksEditedBeforeUnblock => {
+            "tasks.json was edited outside the app. Use /reload-tasks or /keep-tasks before unblocking tasks."
+        }
+        Text::TasksEditedBeforeEdit => {
+            "tasks.json was edited outside the app. Use /reload-tasks or /keep-tasks before editing tasks."
+        }
+        Text::SpeedProfileSet => {
+            "Speed profile set to {profile} ({summary}). Jobs started from now on use it."
+        }
+        Text::SpeedProfileSaveFailed => {
+            "Speed profile set to {profile} for this run, but saving it to the session failed: {err}"
+        }
+        Text::SpeedProfileStatus => {
+            "Speed profile is {profile}. Use /speed eco|standard|max: {options}."
+        }
+        Text::ReviewGateSaveFailed => {
+            "Review gate set for this run, but saving it to the session failed: {err}"
+        }
+        Text::TasksWriteAfterReviewFailed => "Failed to write tasks file after review: {err}",
+        Text::NewSessionBlocked => {
+            "Cannot start a new master session while worker execution is running. Wait for active jobs to finish first."
+        }
+        Text::StartedNewSession => "Started new master session",
+        Text::TemplateSessionBlocked => {
+            "Cannot start a session from a template while worker execution is running. Wait for active jobs to finish first."
+        }
+        Text::StartedTemplateSession => {
+            "Started session {session} from template `{template_name}`."
+        }
+        Text::TemplateSessionFailed => {
+            "Failed to start a session from template `{template_name}`: {err}"
+        }
+        Text::DocsAttachAlreadyRunning => {
+            "Docs attach is already running. Please wait for completion."
+        }
+        Text::AttachDocsSafeMode => {
+            "/attach-docs rewrites tasks.json, so it is disabled in safe mode."
+        }
+        Text::NoTaskWithId => "No task with id `{task_id}`.",
+        Text::DocsAttachStarted => "Started documentation attach sub-agent.",
+        Text::NoSavedSessions => "No saved sessions found.",
+        Text::NoOtherSessionsToResume => "No other saved sessions found to resume.",
+        Text::ResumeOtherWorkspacesOnly => {
+            "This workspace has no other sessions; the resume picker lists other workspaces'. Picking one changes the working directory."
+        }
+        Text::ResumePickerHint => {
+            "Select a session in the resume picker and press Enter or Space; type to filter."
+        }
+        Text::ResumePickerHintWithOthers => {
+            "Select a session in the resume picker and press Enter or Space; type to filter, or press Tab to list other workspaces' sessions."
+        }
+        Text::ListSessionsFailed => "Failed to list sessions: {err}",
+        Text::NoOtherSessionToSwitch => "No other saved session in this workspace to switch to.",
+        Text::LockTakeoverFailed => "Failed to take over the lock on {session}: {err}",
+        Text::TookOverSession => {
+            "Took over session {session} from {owner}; that instance stops its jobs and switches to read-only when it notices."
+        }
+        Text::FollowingLockedSession => {
+            "{owner} has this session open; you are following it read-only."
+        }
+        Text::NoLockedSessionWaiting => {
+            "No locked session is waiting; /lock applies after /resume or /switch finds a session open in another instance."
+        }
+        Text::LockUsage => {
+            "Usage: /lock takeover|observe, after /resume or /switch finds a session open in another instance.{held}"
+        }
+        Text::BackendPickerHint => {
+            "Select a backend in the picker and press Enter or Space (Backspace cancels)."
+        }
+        Text::TestsModeSaved => "Tests mode is now {mode}. Saved to {path}.",
+        Text::TestsModeSaveFailed => {
+            "Tests mode is now {mode} for this run, but persistence to config.toml failed (default path is ~/.agentbob/config.toml; legacy fallbacks: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}."
+        }
+        Text::PlannerModeEnabled => {
+            "Planner mode enabled. The right pane now shows planner.md for collaborative markdown planning."
+        }
+        Text::RollingContextPersistFailed => "Failed to persist rolling_context.json: {err}",
+        Text::TaskModelStatus => "\"{title}\" ({top_id}) uses {model}. Profiles: {profiles}.",
+        Text::UnknownModelProfile => "Unknown model profile `{profile}`. Profiles: {profiles}.",
+        Text::TaskModelSet => {
+            "Worker jobs for \"{title}\" ({top_id}) now use model profile {profile}, starting with its next job."
+        }
+        Text::TaskModelReset => {
+            "Worker jobs for \"{title}\" ({top_id}) use the default agent routing again."
+        }
+        Text::PromptPreviewOn => {
+            "Master prompt preview enabled. Each master prompt is shown for confirmation before it is sent."
+        }
+        Text::PromptPreviewOff => {
+            "Master prompt preview disabled. Master prompts are sent immediately."
+        }
+        Text::ResponseCacheStats => {
+            "Response cache: {hits} hit(s) and {misses} miss(es) since startup; {entries} cached result(s) ({kb} KB) on disk. /cache purge empties it."
+        }
+        Text::ResponseCachePurged => "Removed {removed} cached result(s) from the response cache.",
+        Text::ResponseCachePurgeFailed => "Failed to purge the response cache: {err}",
+        Text::CacheUsage => "Usage: /cache or /cache purge",
+        Text::SummariesHidden => "The right pane shows the task list again.",
+        Text::SummariesShown => {
+            "The right pane lists each done top-level task with its summary. /summaries or Backspace returns to the task list."
+        }
+        Text::VerboseOn => {
+            "Verbose output on. The subagent pane shows every agent line; {filtered} line(s) filtered before now are in the worker logs (/logs)."
+        }
+        Text::VerboseOff => "Verbose output off. The subagent pane filters agent output again.",
+        Text::SkipPlanEnabled => {
+            "Skip-plan mode enabled. The right pane now shows planner tasks for execution."
+        }
+        Text::ConvertNeedsPlanner => {
+            "/convert is only available in planner mode. Use /planner first."
+        }
+        Text::ConvertPlannerConflict => {
+            "planner.md has an open edit conflict; settle it with /conflict before /convert."
+        }
+        Text::ConvertPersistFailed => {
+            "Could not persist planner.md before /convert; conversion aborted."
+        }
+        Text::NoPlanToImport => {
+            "No PLAN.md, TODO.md, or planner.md with content in the workspace root."
+        }
+        Text::ImportedPlan => "Imported {path} into planner.md.",
+        Text::ImportedPlanHint => "Review it in the planner, then /convert turns it into tasks.",
+        Text::ImportPlanConflict => {
+            "planner.md has an open edit conflict; settle it with /conflict, then run /import-plan again."
+        }
+        Text::ImportPlanWriteFailed => "Could not write planner.md; the plan was not imported.",
+        Text::UnknownCommand => "Unknown command `{command}`. Type `/` to see available commands.",
+        Text::GatheringProjectContext => "Gathering project context before contacting master.",
+        Text::ProjectContextStillGathering => {
+            "Project context is still gathering; your message will be sent when ready."
+        }
+        Text::StartedExecution => "Started execution",
+        Text::ClearedStaleLock => {
+            "Cleared a stale lock on {session} left by {owner}, which is no longer running."
+        }
+        Text::LockWriteFailed => {
+            "Failed to write the session lock file; another instance could open this session too: {err}"
+        }
+        Text::ResumeWorkspaceMissing => {
+            "Failed to resume session {session}: cannot change the working directory to {workspace}: not a directory"
+        }
+        Text::ResumeSessionFailed => "Failed to resume session {session}: {err}",
+        Text::ResumedSession => "Resumed session {session}",
+        Text::WorkingDirectoryChanged => {
+            "Working directory is now {path}; agents run there from the next job. Project config overrides load at the next start."
+        }
+        Text::TemplateUsageNoTemplates => {
+            "Usage: /new-from-template <name>. No templates found; add <name>.toml files to {dir}."
+        }
+        Text::TemplateUsage => "Usage: /new-from-template <name>. Available templates: {names}",
+        Text::ListTemplatesFailed => "Failed to list templates in {dir}: {err}",
+        Text::ResumedTaskTreeRefreshFailed => {
+            "Failed to refresh task tree from resumed tasks.json: {err}"
+        }
+        Text::UiStateSaveFailed => "Failed to save ui-state.json: {err}",
+        Text::InterruptedDuringJob => {
+            "The previous run was stopped during {job}; that job was reset to pending. Use /start to continue."
+        }
+        Text::InterruptedDuringExecution => {
+            "The previous run was stopped while execution was running. Use /start to continue."
+        }
+        Text::RestoredQueuedMessages => "Restored {count} queued message(s) from the previous run.",
+        Text::InterruptedRunClearFailed => "Failed to clear interrupted-run.json: {err}",
+        Text::RecentSessionsOffer => {
+            "This workspace has {count} session(s) used in the last 7 days. Continue the most recent one instead? Enter resumes the selected session; Backspace starts a new session."
+        }
+        Text::TaskSplitStaleRemoveFailed => {
+            "Skipped task splitting; stale proposal could not be removed: {err}"
+        }
+        Text::TaskSplitKept => "Task splitter kept the oversized tasks as they are.",
+        Text::TaskSplitReadFailed => "Task split proposal could not be read: {err}",
+        Text::TaskSplitDiscarded => "Discarded task split proposal because {reason}.",
+        Text::TaskSplitProposed => {
+            "Task splitter proposes smaller tasks.\n{summary}\nType /approve-split to rewrite tasks.json or /reject-split to keep the current tasks."
+        }
+        Text::NoTaskSplitPending => "No task split proposal is pending.",
+        Text::TaskSplitOtherSession => "Discarded a task split proposal from another session.",
+        Text::TaskSplitBlocked => {
+            "Cannot apply a task split while worker execution is running. Wait for active jobs to finish first."
+        }
+        Text::TaskSplitRejected => "Task split proposal rejected; tasks.json is unchanged.",
+        Text::TaskSplitOutdated => {
+            "tasks.json changed after the split was proposed; discarded the proposal."
+        }
+        Text::TaskSplitApplied => "Applied task split proposal to tasks.json.",
+        Text::TaskSplitWriteFailed => "Failed to write tasks.json while applying task split: {err}",
+        Text::TaskSplitInvalid => "Task split proposal no longer validates: {err}",
+        Text::SafeModeRestoredTasks => {
+            "The master wrote tasks.json in safe mode; restored the previous tasks. Only changes in tasks.staged.json are offered for review."
+        }
+        Text::SafeModeStagedTasks => {
+            "The master staged task changes in {path}:\n{diff}\nType /accept-tasks to apply them to tasks.json or /reject-tasks to discard them."
+        }
+        Text::MasterChangedTasks => {
+            "Master changed tasks.json:\n{diff}\nType /accept-tasks to keep these changes or /reject-tasks to restore the previous tasks."
+        }
+        Text::NoTaskChangesPending => "No task changes are waiting for approval.",
+        Text::TaskChangesOtherSession => "Discarded task changes from another session.",
+        Text::KeptMasterTaskChanges => "Kept the master's task changes.",
+        Text::RestoreTasksBlocked => {
+            "Cannot restore tasks.json while worker execution is running. Wait for active jobs to finish first."
+        }
+        Text::PreviousTasksReadFailed => {
+            "Could not read the previous tasks.json to restore it: {err}"
+        }
+        Text::RejectedMasterTaskChanges => {
+            "Rejected the master's task changes; restored the previous tasks.json."
+        }
+        Text::RestoreTasksWriteFailed => {
+            "Failed to write tasks.json while restoring the previous tasks: {err}"
+        }
+        Text::PreviousTasksInvalid => "The previous tasks.json no longer validates: {err}",
+        Text::DiscardedStagedTasks => "Discarded the master's staged task changes.",
+        Text::RemoveFileFailed => "Failed to remove {path}: {err}",
+        Text::StagedTasksBlocked => {
+            "Cannot apply staged tasks while worker execution is running. Wait for active jobs to finish first."
+        }
+        Text::StagedTasksReadFailed => "Could not read the staged tasks from {path}: {err}",
+        Text::StagedTasksInvalid => "The staged tasks no longer validate: {err}",
+        Text::AppliedStagedTasks => "Applied the master's staged task changes to tasks.json.",
+        Text::StagedTasksWriteFailed => {
+            "Failed to write tasks.json while applying the staged tasks: {err}"
+        }
+        Text::AppendFileFailed => "Failed to append to {path}: {err}",
+        Text::NoEditConflict => "No edit conflict is open.",
+        Text::WriteFileFailed => "Failed to write {file_name}: {err}",
+        Text::NoExternalTasksEdit => "No external tasks.json edit is pending.",
+        Text::ExternalTasksEditOtherSession => {
+            "Discarded a pending tasks.json edit from another session."
+        }
+        Text::ExternalTasksEditDiscarded => {
+            "Discarded the external edit; tasks.json was restored from the current task tree."
+        }
+        Text::RestoreTasksFailed => "Failed to restore tasks.json: {err}",
+        Text::ReloadTasksBlocked => {
+            "Cannot reload tasks.json while worker execution is running. Wait for active jobs to finish first."
+        }
+        Text::ReloadTasksFailed => {
+            "tasks.json cannot be reloaded: {err}. Fix the file and try again, or type /keep-tasks."
+        }
+        Text::ReloadedTasksResuming => {
+            "Reloaded tasks.json; resuming execution with the updated dependencies."
+        }
+        Text::ReloadedTasks => "Reloaded tasks.json.",
+        Text::ReloadTasksInvalid => {
+            "tasks.json does not validate: {err}. Fix the file and try again, or type /keep-tasks."
+        }
+        Text::FinalAuditBlocked => {
+            "Cannot modify final-audit tasks while worker execution is running. Wait for active jobs to finish first."
+        }
+        Text::FinalAuditDisabled => {
+            "Final audit tasks are disabled by config (final_audit = \"never\")."
+        }
+        Text::FinalAuditReadFailed => {
+            "Could not read tasks file; final audit command aborted: {err}"
+        }
+        Text::FinalAuditAdded => "Added final audit task.",
+        Text::FinalAuditAddWriteFailed => {
+            "Failed to write tasks file while adding final audit task: {err}"
+        }
+        Text::FinalAuditRefreshFailed => {
+            "Final audit command aborted; task tree refresh failed before write: {err}"
+        }
+        Text::FinalAuditMissing => "No final audit task was present.",
+        Text::FinalAuditRemoved => "Removed final audit task.",
+        Text::FinalAuditRemoveWriteFailed => {
+            "Failed to write tasks file while removing final audit task: {err}"
+        }
+        Text::NoQueuedJobs => "No worker jobs are queued.",
+        Text::QueuedJobs => {
+            "Queued worker jobs:\n{lines}\nUse /jobs move <from> <to> or /jobs drop <n>."
+        }
+        Text::MovedJob => "Moved {job} to queue position {position}.",
+        Text::DroppedJob => {
+            "Dropped queued {job}; the task stays unfinished and /retry {task} runs it again once execution is idle."
+        }
+        Text::BulkTasksUsage => {
+            "Usage: /tasks done|reset|delete <numbers>, with task list numbers such as 3,5-7."
+        }
+        Text::BulkTasksUnchanged => "No tasks were changed: {err}",
+        Text::BulkTasksWriteFailed => "No tasks were changed. Failed to write tasks.json: {err}",
+        Text::BulkTasksRefreshFailed => {
+            "Saved tasks.json but refreshing the task list failed: {err}"
+        }
+        Text::CancelUsage => "Usage: /cancel context | /cancel docs | /cancel taskcheck",
+        Text::NoProjectContextRunning => "No project context gather is running.",
+        Text::ProjectContextCancelledSending => {
+            "Cancelled project context gathering; sending your message to the master without it."
+        }
+        Text::ProjectContextCancelled => {
+            "Cancelled project context gathering; proceeding without attachment."
+        }
+        Text::NoDocsAttachRunning => "No docs attach is running.",
+        Text::DocsAttachCancelled => {
+            "Cancelled docs attach. Docs it had already attached were kept."
+        }
+        Text::TaskSplitterCancelled => "Cancelled the task splitter.",
+        Text::NoTaskCheckRunning => "No task check is running.",
+        Text::TaskCheckCancelled => "Cancelled the task check.",
+        Text::TaskCheckCancelledUnchanged => {
+            "Cancelled the task check; tasks.json was not changed."
+        }
+        Text::TaskCheckCancelledRestored => {
+            "Cancelled the task check and restored tasks.json from before it ran."
+        }
+        Text::TaskCheckCancelRestoreFailed => {
+            "Cancelled the task check but could not restore tasks.json: {err}"
+        }
+        Text::DirectUsage => "Usage: /direct <instruction> runs one change without planning.",
+        Text::DirectRefreshFailed => "/direct aborted; task tree refresh failed: {err}",
+        Text::DirectWriteFailed => "Failed to write direct task to tasks.json: {err}",
+        Text::DirectTaskAdded => "Added direct task {task_id}; running it without planning.",
+        Text::ConvertingPlanner => "Converting planner.md into tasks.json in task mode...",
+        Text::PlannerWriteFailed => "Failed to write planner markdown to planner.md: {err}",
+        Text::ReportQueuePersistFailed => "Failed to persist master-report-queue.json: {err}",
+        Text::SavedProjectMemory => "Saved {added} fact(s) to project memory ({path}).",
+        Text::ProjectMemoryUpdateFailed => "Failed to update project memory: {err}",
+        Text::MasterAskedQuestions => {
+            "The master asked {count} question(s); answer them in the form."
+        }
+        Text::QuestionsDismissed => "Clarification questions dismissed.",
+        Text::RecordAnswersFailed => "Failed to record answers in {path}: {err}",
+        Text::RerunNeedsSession => "/rerun requires an active session.",
+        Text::FileTreeShown => {
+            "File tree enabled in the top-left pane. Up/Down select, Enter expands or opens a read-only view, Backspace goes back. Files marked * changed this session."
+        }
+        Text::FileTreeHidden => "File tree hidden; the top-left pane shows worker output again.",
+        Text::PreviewingPrompt => {
+            "Previewing the assembled master prompt. Enter sends it; Backspace cancels and returns your message to the input for editing."
+        }
+        Text::PreviewCancelled => "Master prompt preview cancelled; nothing was sent.",
+        Text::StallWaiting => {
+            "Still waiting; the job is flagged again if it stays silent for another stall period."
+        }
+        Text::StallNudging => {
+            "Nudging the stalled agent; its run was stopped and its session resumes with a follow-up prompt."
+        }
+        Text::StallCannotNudge => {
+            "The running job has no agent session to resume, so it cannot be nudged. Press w to keep waiting or a to abort it."
+        }
+        Text::StallAborting => "Aborting the stalled job; it finishes as a failed run.",
+        Text::StallAlreadyStopped => "The stalled job already stopped; waiting for it to finish.",
+        Text::FailureStatsHidden => {
+            "Failure stats hidden; the top-left pane shows worker output again."
+        }
+        Text::FailureStatsShown => {
+            "Failure stats for {sessions} session(s) shown in the top-left pane. Run /stats again to hide them."
+        }
+        Text::FailureStatsFailed => "Failed to collect failure stats: {err}",
+        Text::WorkerLogsNeedSession => "No active session; worker logs are stored per session.",
+        Text::NoWorkerLogsForTask => "No worker logs for task `{task_id}` yet.",
+        Text::NoWorkerLogs => "No worker logs in this session yet.",
+        Text::ListWorkerLogsFailed => "Failed to list worker logs: {err}",
+        Text::WorkerLogWriteFailed => "Failed to write worker log {path}: {err}",
+        Text::PinModelsUsage => "Usage: /pin-models or /pin-models off",
+        Text::UpdateFileFailed => "Failed to update {path}: {err}",
+        Text::UnpinnedModels => {
+            "Unpinned this session's models; agents started from now on follow config.toml."
+        }
+        Text::PinnedModelsUnchanged => {
+            "Pinned this session's models. They match config.toml today and stay as they are if it changes; /pin-models off follows config.toml again."
+        }
+        Text::PinnedModels => {
+            "Pinned this session's models; agents started from now on use them instead of config.toml: {drift}. /pin-models off follows config.toml again."
+        }
+        Text::PinnedModelDrift => {
+            "This session pins the models it was created with, which config.toml now routes differently: {drift}. /pin-models off follows config.toml instead."
+        }
+        Text::ModelDrift => {
+            "config.toml now routes this session's agents to other models than it was created with: {drift}. Run /pin-models to keep the original models for the rest of this plan."
+        }
+        Text::BackendUnchanged => "Backend remains {backend}.",
+        Text::BackendSaved => {
+            "Backend set to {backend}. Saved to {path}. New adapters will use this backend."
+        }
+        Text::BackendSaveFailed => {
+            "Backend set to {backend} for this run, but persistence to config.toml failed (default path is ~/.agentbob/config.toml; legacy fallbacks: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}. New adapters in this run will still use this backend."
+        }
+        Text::BackendModelReloadFailed => {
+            "Backend switched to {label} in memory, but model config reload failed: {err}. Using fallback defaults for future adapters."
+        }
+        Text::BackendFailover => {
+            "{failures} worker jobs in a row failed on {current}; failing over to {fallback} for subsequent jobs. This is not saved to config.toml; use /backend to switch back."
+        }
+        Text::TasksReadFailed => "Failed to read tasks.json: {err}",
+        Text::FoundPlanFile => {
+            "Found {file} in the workspace. /import-plan copies it into planner.md; /import-plan tasks has the master turn it into tasks."
+        }
+        Text::SplittingAudits => "Splitting audits...",
+        Text::MergingAudits => "Merging audits...",
+        Text::CachedResultApplyFailed => {
+            "Failed to apply the cached {call} result, calling the model instead: {err}"
+        }
+        Text::CachedResultSaveFailed => {
+            "Failed to save the {call} result to the response cache: {err}"
+        }
+        Text::ReportNoSummary => {
+            "The master report agent returned no summary for task \"{top_task_title}\"."
+        }
+        Text::TaskSummariesWriteFailed => "Failed to write task-summaries.json: {err}",
+        Text::TaskSummarized => "Task \"{title}\" is summarized in /summaries.",
+        Text::ExecutionAlreadyRunning => "Execution is already running; continuing current task.",
+        Text::ResumedUnfinishedTasks => {
+            "Resumed from last unfinished task(s). Queued {queued} task job(s)."
+        }
+        Text::ExecutionAlreadyEnabled => {
+            "Execution is already enabled. No unfinished tasks to resume."
+        }
+        Text::ExecutionEnabled => "Execution enabled. Queued {queued} task job(s).",
+        Text::ExecutionEnabledScoped => {
+            "Execution enabled for \"{top_title}\"{via} only. Queued {queued} task job(s)."
+        }
+        Text::RerunningEditedPrompt => {
+            "Re-running the {label} of \"{title}\" ({task_id}) with the edited prompt."
+        }
+        Text::PassReadyForReview => {
+            "Task #{task} implementation pass {pass} is ready for review. Use /review approve, /review changes <feedback>, or /review revert."
+        }
+        Text::FinalAuditPassed => "Final audit task #{task} completed on pass {pass}.",
+        Text::FinalAuditRetriesExhausted => {
+            "Final audit task #{task} still failed at pass {pass}. Max retries ({max}) reached; no further final-audit retries queued."
+        }
+        Text::FinalAuditNotPassedRetry => {
+            "Final audit task #{task} did not explicitly pass; retry queued."
+        }
+        Text::FinalAuditFailedRetry => {
+            "Final audit task #{task} failed (code {code}); retry queued."
+        }
+        Text::ScopedRunDone => {
+            "Scoped run of \"{title}\" is done; other pending tasks were left untouched. Use /start to continue the plan."
+        }
+        Text::TaskSkipped => "Skipped \"{title}\" ({task_id}).",
+        Text::TaskMarkedDone => "Marked \"{title}\" ({task_id}) done.",
+        Text::TaskResetForRetry => "Reset \"{title}\" ({task_id}) to pending for a retry.",
+        Text::TaskUnblocked => "Unblocked \"{title}\" ({task_id}) with your answer.",
+        Text::UnblockedTaskQueued => "Queued the unblocked task.",
+        Text::UnblockedTaskWaitsForStart => "It runs when execution starts (/start).",
+        Text::PluginStepQueued => "Task #{task} all audits complete; plugin step queued.",
+        Text::ExistingTestsQueued => {
+            "Task #{task} all audits complete; existing-test runner queued."
+        }
+        Text::ImplementationAuditsPassed => "Task #{task} implementation branch passed all audits.",
+        Text::AuditQueued => "Task #{task} audit queued (audit pass {pass}).",
+        Text::PluginStepSkipped => "Warning: skipped plugin step \"{step}\". {err}",
+        Text::TestsOffSkippingTestWriter => {
+            "Task #{task} tests mode is OFF; skipping test-writer follow-up steps."
+        }
+        Text::TestWriterAuditSkipped => {
+            "Task #{task} test-writer audit skipped; test-writer audits are off for this speed profile."
+        }
+        Text::TestWriterAuditQueued => {
+            "Task #{task} test-writer pass {pass} complete; test-writer audit queued."
+        }
+        Text::TestRunnerQueueFailed => {
+            "Task #{task} could not queue deterministic test runner; test branch blocked."
+        }
+        Text::TestRunQueued => {
+            "Task #{task} test-writer pass {pass} complete; deterministic test run queued."
+        }
+        Text::TaskBlockedOnUser => {
+            "Task #{top_task_id} is blocked on you: {reason}\nAnswer with /unblock {task_id} <answer>; other tasks keep running."
+        }
+        Text::TaskCompletedWithTests => {
+            "Task #{task} completed after implementation and testing branches converged."
+        }
+        Text::TaskCompleted => {
+            "Task #{task} completed after implementation branch checks converged."
+        }
+        Text::AuditPassedWithoutCriteria => {
+            "Task #{task} audit passed without confirming every acceptance criterion as met; treating it as a failed audit."
+        }
+        Text::AuditRetriesExhausted => {
+            "Task #{task} audit still found critical blockers at pass {pass}. Max retries ({max}) reached; proceeding to next audit/step."
+        }
+        Text::AuditRequestedFixes => {
+            "Task #{task} audit requested fixes; implementor pass {pass} queued."
+        }
+        Text::ImplementationResumedAudit => {
+            "Task #{task} implementation pass {pass} complete; resumed audit queued."
+        }
+        Text::ImplementationFailedRetry => {
+            "Task #{task} implementation failed (code {code}); retry queued."
+        }
+        Text::ProtectedPathsModified => {
+            "Task #{task} implementation pass {pass} modified protected paths: {paths}. Audit failed with a protected-path violation."
+        }
+        Text::ReviewSentBack => {
+            "Task #{task} implementation pass {pass} sent back by review; pass {next_pass} queued."
+        }
+        Text::PluginStepPassed => "Task #{top_task_id} plugin step {name} passed on run {pass}.",
+        Text::PluginStepRetriesExhausted => {
+            "Task #{top_task_id} plugin step {name} still failing at pass {pass}. Max retries ({max}) reached; proceeding to next step."
+        }
+        Text::PluginStepFailed => {
+            "Task #{top_task_id} plugin step {name} failed; implementor pass {next_pass} queued."
+        }
+        Text::TestsOffSkippingTestAuditRetries => {
+            "Task #{task} tests mode is OFF; skipping test-writer audit retries."
+        }
+        Text::TestAuditRetriesExhausted => {
+            "Task #{task} test-writer audit still found critical blockers at pass {pass}. Max retries ({max}) reached; proceeding to deterministic tests."
+        }
+        Text::TestAuditRequestedFixes => {
+            "Task #{task} test-writer audit requested fixes; test-writer pass {pass} queued."
+        }
+        Text::TestAuditPassComplete => "Task #{task} test-writer audit pass {pass} complete.",
+        Text::DeterministicTestsPassed => "Task #{task} deterministic tests passed on run {pass}.",
+        Text::TestsOffSkippingTestRunnerRetries => {
+            "Task #{task} tests mode is OFF; skipping deterministic test-runner retries."
+        }
+        Text::TestsRetriesExhaustedCleanup => {
+            "Task #{task} tests still failing at pass {pass}. Max retries ({max}) reached; queued cleanup removal pass."
+        }
+        Text::ExistingTestsPassed => {
+            "Task #{task} existing-test runner passed on run {pass}; implementor branch complete."
+        }
+        Text::TestsOffSkippingExistingTestRetries => {
+            "Task #{task} tests mode is OFF; skipping existing-test runner retries."
+        }
+        Text::ExistingTestsRetriesExhausted => {
+            "Task #{task} existing tests still failing at pass {pass}. Max retries ({max}) reached; proceeding to next step."
+        }
+        Text::ExistingTestsFailed => {
+            "Task #{task} existing tests failed; implementor pass {pass} queued."
+        }
+        Text::FailingTestsRemoved => {
+            "Task #{task} removed failing tests after retries and proceeded."
+        }
+        Text::TestWriterResumedAudit => {
+            "Task #{task} test-writer pass {pass} complete; resumed audit queued."
+        }
+        Text::TestsOffSkippingTestWriterRetries => {
+            "Task #{task} tests mode is OFF; skipping test-writer retries."
+        }
+        Text::TestWriterRetriesExhausted => {
+            "Task #{task} test-writer still failing at pass {pass}. Max retries ({max}) reached; proceeding to next step."
+        }
+        Text::TestWriterFailedRetry => {
+            "Task #{task} test-writer failed (code {code}); retry queued."
+        }
+        Text::VerifyQueued => {
+            "Task #{top_task_id} tests complete; verify command queued (attempt {attempt})."
+        }
+        Text::VerifySkippedSafeMode => {
+            "Task #{top_task_id} tests complete; skipped its verify command in safe mode."
+        }
+        Text::VerifyPassed => {
+            "Task #{top_task_id} verify command passed on attempt {attempt}; implementor branch complete."
+        }
+        Text::VerifyRetriesExhausted => {
+            "Task #{top_task_id} verify command still failing at attempt {attempt}. Max retries ({max_retries}) reached; proceeding to next step."
+        }
+        Text::VerifyFailed => {
+            "Task #{top_task_id} verify command failed; implementor pass {pass} queued."
+        }
+        Text::DocSnapshotsDropped => {
+            " Kept {kept} doc snapshot(s); dropped {dropped} that were missing or outside docs/."
+        }
+        Text::DocSnapshotsClearFailed => {
+            " Failed to clear unusable doc snapshots from tasks.json: {err}"
+        }
+        Text::DocSnapshotsSaved => " Saved {kept} doc snapshot(s).",
+        Text::DocSnapshotsSanitizeFailed => " Failed to sanitize doc snapshots: {err}",
+        Text::LockHeldHere => " This instance holds the lock on {session}.",
+        Text::ConflictKeptMine => {
+            "Resolved the edit conflict: kept your {file_name} and dropped the master's changes.{follow_up}"
+        }
+        Text::ConflictKeptTheirs => {
+            "Resolved the edit conflict: kept the master's {file_name} and dropped your edits.{follow_up}"
+        }
+        Text::ConflictMergedWithMarkers => {
+            "Resolved the edit conflict: merged both versions of {file_name}; overlapping changes sit between <<<<<<< yours and >>>>>>> master markers for you to edit.{follow_up}"
+        }
+        Text::ConflictMerged => {
+            "Resolved the edit conflict: merged both versions of {file_name}.{follow_up}"
+        }
+        Text::ConflictReloadHint => " Type /reload-tasks to load it or /keep-tasks to drop it.",
+        Text::BulkTasksMarkedDone => "Marked done {count} task(s) with their subtasks:\n{titles}",
+        Text::BulkTasksReset => "Reset to pending {count} task(s) with their subtasks:\n{titles}",
+        Text::BulkTasksDeleted => "Deleted {count} task(s) with their subtasks:\n{titles}",
+        Text::BulkTasksConfirmDelete => {
+            "This deletes {count} task(s) with their subtasks:\n{titles}\nSend the same /tasks delete again to confirm."
+        }
+        Text::ContextShown => "Sections for the next master prompt:",
+        Text::ContextSectionIncluded => "`{section}` is now included in the next master prompt:",
+        Text::ContextSectionLeftOut => "`{section}` is now left out of the next master prompt:",
+        Text::ContextReset => "Every section is included in the next master prompt again:",
+        Text::ContextSections => {
+            "{headline}\n{sections}\nToggle a section with /context <section>. The choice applies to the next master prompt only."
+        }
+        Text::SessionLockedElsewhere => {
+            "Session {session} is open in another instance ({owner}, since {since}). Type /lock takeover to open it here anyway, or /lock observe to follow it read-only."
+        }
+        Text::SessionLockedOnOtherHost => {
+            "Session {session} is open in another instance ({owner}, since {since}) on another host, so Bob cannot tell whether it is still running. Type /lock takeover to open it here anyway, or /lock observe to follow it read-only."
+        }
+        Text::ScopedRunContains => " (contains {task_id})",
+        Text::CodeIndexRefreshFailed => "Failed to refresh the code search index: {err}",
+        Text::EditConflictNotice => {
+            "The master changed {file_name} while you had edits of your own; neither version was overwritten. Type /conflict mine to keep yours, /conflict theirs to keep the master's, or /conflict merge to combine them (overlapping changes get conflict markers)."
+        }
+        Text::ProgressDigestSent => "E-mailed the progress digest to {to}.",
+        Text::ProgressDigestFailed => "Failed to e-mail the progress digest: {err}",
+        Text::FinalReportSent => "E-mailed the final report to {to}.",
+        Text::FinalReportFailed => "Failed to e-mail the final report: {err}",
+        Text::PluginFailed => "Plugin {name} failed: {err}.",
+        Text::RemotePushFailed => "Failed to push session {session} to remote storage: {err}",
+        Text::TicketSyncConfigFailed => "Ticket sync skipped; failed to load [sync] config: {err}",
+        Text::TicketMoved => "Moved {reference} to {state}.",
+        Text::TicketUpdateFailed => "Failed to update {reference}: {err}",
+        Text::TasksEditedExternally => {
+            "tasks.json was edited outside the app. Runtime updates will not overwrite it; type /reload-tasks to load it or /keep-tasks to restore the current task tree."
+        }
+        Text::TasksEditedExternallyInvalid => {
+            "tasks.json was edited outside the app but does not validate: {err}. Fix the file, or type /keep-tasks to restore the current task tree."
+        }
+        Text::ObservedTasksApplyFailed => "Observed tasks.json update could not be applied: {err}",
+        Text::ObservedTasksParseFailed => "Observed tasks.json update could not be parsed: {err}",
+        Text::ObservedTaskSkipped => {
+            "Task {task_id} ({title}) was skipped by the running instance."
+        }
+        Text::ObservedTaskMarkedDone => {
+            "Task {task_id} ({title}) was marked done by the running instance."
+        }
+        Text::ObservedTaskFailed => {
+            "{kind} failed for task #{task} ({title}) after {attempts} attempt(s): {reason}"
+        }
+        Text::TestsFlakyWriterQueued => {
+            "Task #{task} tests are flaky; test-writer pass {pass} queued."
+        }
+        Text::TestsFailedWriterQueued => {
+            "Task #{task} tests failed; test-writer pass {pass} queued."
+        }
+        Text::ManualTaskFailed => "Could not update task {task_id}: {err}",
+        Text::UnblockFailed => "Could not unblock task {task_id}: {err}",
+        Text::ReviewDecisionFailed => "Could not apply the review decision: {err}",
+        Text::RerunUnavailable => "Cannot re-run task {task_id}: {err}",
+        Text::RerunFailed => "Could not re-run task {task_id}: {err}",
+        Text::StartExecutionFailed => "Could not start execution: {err}",
+        Text::MoveJobFailed => "Could not move the job: {err}",
+        Text::DropJobFailed => "Could not drop the job: {err}",
+        Text::PluginRunFailed => "Could not run plugin {name}: {err}",
+        Text::PluginOutputLine => "[{name}] {line}",
+        Text::PluginPassed => "Plugin {name} passed.",
+        Text::PluginPassedSummary => "Plugin {name} passed: {summary}",
+        Text::PluginFailedVerdict => "Plugin {name} failed.",
+        Text::PluginFailedSummary => "Plugin {name} failed: {summary}",
+        Text::RemotePushed => "Pushed session {session} to {remote} ({count} files).",
+        Text::ModelEscalated => {
+            "Task #{task} implementor pass {pass} escalated from model profile {from} to {to} after repeated audit failures."
+        }
+    }
+}
+
+fn spanish(key: Text) -> &'static str {
+    match key {
+        Text::StatusHelpNarrow => {
+            "Tab/Shift+Tab cambia de vista | Haz clic en las pestañas superiores para cambiar | Los botones [^]/[v] de la derecha desplazan media página | Ctrl+U/Ctrl+D o RePág/AvPág desplazan el panel derecho | Ctrl+L chat/registro del sistema | F1/? todos los atajos"
+        }
+        Text::StatusHelpWide => {
+            "Tab/Shift+Tab cambia el foco | Ctrl+L chat/registro | Ctrl+U/Ctrl+D o RePág/AvPág desplazan el panel derecho | F1/? atajos"
+        }
+        Text::TestsOn => "PRUEBAS: SÍ",
+        Text::TestsOff => "PRUEBAS: NO",
+        Text::AttachingDocs => "Adjuntando documentación...",
+        Text::CheckingTasks => "Revisando tareas...",
+        Text::TabFiles => "Archivos",
+        Text::TabStats => "Estadísticas",
+        Text::TabConsole => "Consola",
+        Text::TabWorkerOutput => "Salida de agentes",
+        Text::TabAgentChat => "Chat con agentes",
+        Text::TabShortChat => "Chat",
+        Text::TabShortTasks => "Tareas",
+        Text::TabShortOutput => "Salida",
+        Text::TabSystemLog => "Registro del sistema",
+        Text::TabTaskThread => "Chat de la tarea {task}",
+        Text::ChatObserving => "Observando (solo lectura)",
+        Text::ChatWorking => "Trabajando",
+        Text::ChatQueued => "{count} en cola",
+        Text::PaneTaskList => "Lista de tareas",
+        Text::PanePlannerMarkdown => "Plan en Markdown",
+        Text::PaneWorkerLog => "Registro de agente (solo lectura, Retroceso cierra)",
+        Text::PaneTaskSummaries => "Resúmenes de tareas (Retroceso vuelve a las tareas)",
+        Text::PaneFileTree => "Archivos (* cambiados en esta sesión)",
+        Text::PaneFileViewer => "{path} (solo lectura, Retroceso cierra)",
+        Text::PaneFailureStats => "Estadísticas de fallos (todas las sesiones)",
+        Text::PaneConsole => "Consola (shell del espacio de trabajo)",
+        Text::ResumeTitle => "Reanudar sesión",
+        Text::ResumeHint => {
+            "(escribe para filtrar, Arriba/Abajo elige, Intro/Espacio reanuda, Tab otros espacios)"
+        }
+        Text::ResumeAllWorkspaces => "Todos los espacios de trabajo",
+        Text::ResumeFilter => "Filtro: {query}",
+        Text::ResumeNoMatches => "Ninguna sesión coincide con el filtro",
+        Text::ResumePreviewWorkspace => "Espacio de trabajo: {path}",
+        Text::ResumePreviewTasks => "Tareas: {done}/{total} hechas",
+        Text::ResumePreviewActivity => "Última actividad: {when}",
+        Text::ResumePreviewNoPlanner => "planner.md está vacío",
+        Text::UnknownDate => "fecha desconocida",
+        Text::JustNow => "ahora mismo",
+        Text::MinutesAgo => "hace {count} min",
+        Text::HoursAgo => "hace {count} h",
+        Text::DaysAgo => "hace {count} d",
+        Text::PreviewTitle => "Vista previa del prompt del maestro",
+        Text::PreviewHint => {
+            "({chars} caracteres; Intro envía, Retroceso cancela, Arriba/Abajo desplaza)"
+        }
+        Text::QuestionsTitle => "Preguntas del maestro",
+        Text::QuestionsHint => {
+            "(Intro siguiente/envía, Arriba/Abajo o Tab cambia; envía en blanco para descartar)"
+        }
+        Text::RerunTitle => "Volver a ejecutar {task}: {label}",
+        Text::RerunHint => "(Intro ejecuta, Mayús+Intro nueva línea, Mayús+Tab cancela)",
+        Text::HelpTitle => "Atajos de teclado",
+        Text::HelpHint => "(escribe para buscar, Arriba/Abajo desplaza, Intro o Retroceso cierra)",
+        Text::HelpSearch => "Buscar: {query} (Retroceso borra, Intro cierra)",
+        Text::HelpFocused => "(enfocado)",
+        Text::HelpNoMatches => "Ningún atajo coincide con la búsqueda",
+        Text::BackendTitle => "Elegir backend",
+        Text::BackendHint => "(Arriba/Abajo elige, Intro/Espacio confirma)",
+        Text::LogsTitle => "Registros de agentes",
+        Text::LogsHint => "(más recientes primero; Arriba/Abajo elige, Intro/Espacio abre)",
+        Text::LogSize => "({bytes} bytes)",
+        Text::TaskMenuTitle => "Tarea {id}: {title}",
+        Text::TaskMenuHint => "(Arriba/Abajo elige, Intro/Espacio confirma, Retroceso cierra)",
+        Text::TaskMenuExpandDetails => "Mostrar detalles",
+        Text::TaskMenuCollapseDetails => "Ocultar detalles",
+        Text::TaskMenuShowConversation => "Ver conversación",
+        Text::TaskMenuViewLogs => "Ver registros",
+        Text::TaskMenuRetry => "Reintentar",
+        Text::TaskMenuSkip => "Omitir (la haré yo)",
+        Text::TaskMenuChangeModel => "Cambiar modelo",
+        Text::TaskMenuAttachDocs => "Adjuntar documentación a esta tarea",
+        Text::TaskMenuDefaultModel => "Predeterminado (enrutado del agente)",
+        Text::ExecJob => "Trabajo: {job}",
+        Text::ExecIdle => "inactivo",
+        Text::ExecAwaitingReview => "esperando revisión",
+        Text::ExecQueued => "En cola: {count}",
+        Text::ExecBackend => "Backend: {backend}",
+        Text::ExecTokens => "Tokens: {input} entrada / {output} salida",
+        Text::ExecCost => "Coste: ${cost}",
+        Text::ExecStalled => "¿Atascado? {silent} sin salida (w esperar, n insistir, a abortar)",
+        Text::NoActiveSession => {
+            "Todavía no hay una sesión activa. Escribe un mensaje normal para iniciar una o usa /resume para elegir una sesión existente."
+        }
+        Text::SessionDirInitialized => "Directorio de sesión creado en {path}",
+        Text::ProjectInfoBusy => {
+            "Se está recopilando el contexto del proyecto. Los envíos con Intro están desactivados hasta que termine; /cancel context lo omite."
+        }
+        Text::MasterBusyQueued => {
+            "El maestro sigue procesando tu petición anterior. En cola como #{position}; se enviará cuando termine. Usa /clear-queue para descartar los mensajes en cola."
+        }
+        Text::TaskCheckQueued => {
+            "La revisión de tareas está en curso. En cola como #{position}; se enviará cuando termine. Usa /clear-queue para descartar los mensajes en cola o /cancel taskcheck para detener la revisión."
+        }
+        Text::ExecutionBusyBlocked => {
+            "La ejecución está en marcha. Los comandos del maestro y de edición de tareas están bloqueados hasta que terminen los trabajos activos."
+        }
+        Text::AdvisorAsked => {
+            "La ejecución está en marcha, así que responde un asesor de solo lectura en lugar del maestro. No puede cambiar tasks.json; envía los cambios del plan cuando termine la ejecución."
+        }
+        Text::AdvisorBusy => {
+            "El asesor sigue respondiendo tu pregunta anterior; vuelve a enviarla cuando termine."
+        }
+        Text::StartingJob => "Iniciando {role} para la tarea #{task}.",
+        Text::NoQueuedMessages => "No hay mensajes en cola.",
+        Text::DroppedQueuedMessages => "Se descartaron {count} mensaje(s) en cola; no se enviarán.",
+        Text::ReviewOn => {
+            "Revisión ACTIVADA. Cada pasada del implementador espera /review approve, /review changes <comentarios> o /review revert antes de su auditoría."
+        }
+        Text::ReviewOff => {
+            "Revisión DESACTIVADA. Las pasadas del implementador van directamente a auditoría."
+        }
+        Text::ReviewStatusOn => {
+            "La revisión está ACTIVADA. Uso: /review on|off|approve|revert, o /review changes <comentarios>."
+        }
+        Text::ReviewStatusOff => {
+            "La revisión está DESACTIVADA. Uso: /review on|off|approve|revert, o /review changes <comentarios>."
+        }
+        Text::QuitWhileJobRunning => {
+            "Un trabajo de agente sigue en curso. /quit wait deja que termine y luego sale, /quit abort lo detiene ahora y devuelve su tarea a pendiente, y /quit detach cierra la TUI mientras el trabajo termina en segundo plano. Vuelve a salir para abortar."
+        }
+        Text::QuittingAfterJob => {
+            "Se saldrá cuando termine el trabajo activo; no empezarán trabajos nuevos."
+        }
+        Text::ContextUsage => "Uso: /context [{keys}|reset]",
+        Text::WorkspaceSnapshotFailed => {
+            "No se pudo tomar una instantánea del espacio de trabajo ({err}); el diff de revisión, /review revert y la comprobación git de rutas protegidas no están disponibles en esta pasada."
+        }
+        Text::RevertedFiles => {
+            "Se revirtieron {reverted} archivo(s) cambiados por la tarea #{task} en la pasada {pass}."
+        }
+        Text::RunningPlugin => "Ejecutando el plugin {name}...",
+        Text::NoPluginsInstalled => {
+            "No hay plugins instalados. Pon ejecutables de plugins en ~/.agentbob/plugins/ y reinicia Bob."
+        }
+        Text::PluginsInstalled => "{count} plugin(s) instalado(s):",
+        Text::PluginsSafeMode => {
+            "Los plugins ejecutan programas fuera del sandbox, así que están desactivados en modo seguro."
+        }
+        Text::ProtectedPathsListFailed => {
+            "No se pudieron listar los cambios del espacio de trabajo para la comprobación de rutas protegidas ({err}); en esta pasada solo se comprueba FILES_CHANGED."
+        }
+        Text::ResumeOtherWorkspace => {
+            "La sesión {session} pertenece a {workspace}. Pulsa Intro otra vez para cambiar allí el directorio de trabajo y reanudarla; los agentes se reinician en ese espacio de trabajo."
+        }
+        Text::MessageBackInInput => {
+            "Tu mensaje vuelve a estar en la entrada; pulsa Intro para enviarlo a la sesión reanudada."
+        }
+        Text::ConsoleShown => {
+            "Consola mostrada en el panel superior izquierdo. Ejecuta un comando en el espacio de trabajo con /console run <comando>; /console de nuevo la oculta."
+        }
+        Text::ConsoleHidden => {
+            "Consola oculta; el panel superior izquierdo vuelve a mostrar la salida de los agentes."
+        }
+        Text::ConsoleRunning => "Ejecutando `{command}` en la consola.",
+        Text::ConsoleStopping => "Deteniendo `{last_command}`.",
+        Text::ConsoleNothingRunning => "No hay ningún comando de consola en ejecución.",
+        Text::ConsoleKept => {
+            "Se añadió la salida de `{last_command}` al contexto acumulado de tareas."
+        }
+        Text::ConsoleStillRunning => {
+            "`{last_command}` sigue en ejecución; guarda su salida cuando termine."
+        }
+        Text::ConsoleNothingToKeep => "No hay ningún comando de consola terminado que guardar.",
+        Text::ConsoleCleared => "Consola limpiada.",
+        Text::ConsoleUsage => "Uso: /console [run <comando>|stop|keep|clear]",
+        Text::ConsoleFinished => {
+            "El comando de consola `{command}` terminó ({status}). /console keep añade su salida al contexto acumulado de tareas."
+        }
+        Text::ConsoleSafeMode => "/console run está desactivado en modo seguro.",
+        Text::ObservingSession => {
+            "Observando la sesión {session} (solo lectura). No se pueden enviar prompts; usa /quit para salir."
+        }
+        Text::ObserverReadOnly => {
+            "El modo observador es de solo lectura. Solo están disponibles /files, /stats, /planner, /skip-plan y /quit."
+        }
+        Text::ProjectInfoTrimmed => {
+            "project-info.md superaba project_info.max_tokens ({max_tokens}); se recortó para que quepa."
+        }
+        Text::ProjectInfoTrimWriteFailed => {
+            "No se pudo escribir el project-info.md recortado: {err}"
+        }
+        Text::ModelProfileConfigLoadFailed => {
+            "No se pudo cargar la configuración de perfiles de modelo de ~/.agentbob/config.toml (alternativas heredadas: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}. Se usan los valores predeterminados."
+        }
+        Text::SafeModeOn => {
+            "El modo seguro está activado. Los agentes trabajan en el sandbox de solo lectura del backend y solo pueden escribir archivos de la sesión. Los cambios de tareas del maestro quedan preparados para /accept-tasks o /reject-tasks, y los plugins, los comandos verify y /console están desactivados."
+        }
+        Text::TestsModeLoadFailed => {
+            "No se pudo cargar el modo de pruebas global de config.toml; se usa ACTIVADO: {err}"
+        }
+        Text::FinalAuditPolicyLoadFailed => {
+            "No se pudo cargar la política de auditoría final de config.toml; se usa manual: {err}"
+        }
+        Text::StallAfterLoadFailed => {
+            "No se pudo cargar workflow.stall_after_secs de config.toml; se usan 300 segundos: {err}"
+        }
+        Text::TaskSplitLimitsLoadFailed => {
+            "No se pudieron cargar los límites de división de tareas de config.toml; se usan los predeterminados: {err}"
+        }
+        Text::WorkerLogSettingsLoadFailed => {
+            "No se pudo cargar la configuración de registros de agentes de config.toml; se usan los valores predeterminados: {err}"
+        }
+        Text::FailoverThresholdLoadFailed => {
+            "No se pudo cargar el umbral de cambio de backend de config.toml; se usa {threshold}: {err}"
+        }
+        Text::RerunFailedTestsLoadFailed => {
+            "No se pudo cargar workflow.rerun_failed_tests de config.toml; las repeticiones están desactivadas: {err}"
+        }
+        Text::DocsUpdateTaskLoadFailed => {
+            "No se pudo cargar workflow.docs_update_task de config.toml; no se añadirá tarea de documentación: {err}"
+        }
+        Text::ProtectedPathsLoadFailed => {
+            "No se pudo cargar workflow.protected_paths de config.toml; ninguna ruta está protegida: {err}"
+        }
+        Text::VerifyLoadFailed => {
+            "No se pudo cargar workflow.verify de config.toml; no se ejecuta ningún comando de verificación: {err}"
+        }
+        Text::CommandsLoadFailed => {
+            "No se pudo cargar [commands] de config.toml; no hay alias ni macros definidos: {err}"
+        }
+        Text::PluginLoadFailed => "No se pudo cargar el plugin {err}",
+        Text::PluginsDirUnavailable => {
+            "Los plugins están desactivados; no se encontró el directorio de plugins: {err}"
+        }
+        Text::OutputFiltersLoadFailed => {
+            "No se pudo cargar output_filters de config.toml; se usan los filtros predeterminados: {err}"
+        }
+        Text::ResponseCacheOff => {
+            "La caché de respuestas está desactivada; las revisiones de tareas y la adjunción de documentación siempre llaman al modelo: {err}"
+        }
+        Text::OfferRecentSessionLoadFailed => {
+            "No se pudo cargar storage.offer_recent_session de config.toml; no se ofrecerán sesiones recientes: {err}"
+        }
+        Text::ExportingTraces => "Exportando trazas a {url}.",
+        Text::TelemetryLoadFailed => {
+            "No se pudo cargar la configuración de telemetría de config.toml; el trazado está desactivado: {err}"
+        }
+        Text::CodeContextLoadFailed => {
+            "No se pudo cargar la configuración de contexto de código de config.toml; se usan los valores predeterminados: {err}"
+        }
+        Text::ProjectInfoScopeLoadFailed => {
+            "No se pudo cargar [project_info] de config.toml; la exploración del proyecto no tiene límites: {err}"
+        }
+        Text::GraphicsLoadFailed => {
+            "No se pudo cargar [ui] graphics de config.toml; las imágenes se muestran como texto: {err}"
+        }
+        Text::BackendMetricsOff => {
+            "Las métricas de llamadas al backend están desactivadas; no hay directorio de almacenamiento: {err}"
+        }
+        Text::NarrowWidthLoadFailed => {
+            "No se pudo cargar [ui] narrow_width de config.toml; se usa {width}: {err}"
+        }
+        Text::LanguageSettingsLoadFailed => {
+            "No se pudo cargar la configuración de idioma [ui] de config.toml; se usa inglés: {err}"
+        }
+        Text::BatchScriptStarted => {
+            "Ejecutando un script por lotes de {steps} pasos. Cada paso se envía cuando termina todo lo que inició el anterior; /clear-queue descarta el resto."
+        }
+        Text::EmailDigestLoadFailed => {
+            "No se pudo cargar email_digest de config.toml; los resúmenes por correo están desactivados: {err}"
+        }
+        Text::TaskSummariesReadFailed => "No se pudo leer task-summaries.json: {err}",
+        Text::DeliveringLeftoverReports => {
+            "Entregando {restored} informe(s) del maestro pendientes de la ejecución anterior."
+        }
+        Text::ReportQueueReadFailed => "No se pudo leer master-report-queue.json: {err}",
+        Text::DocsPolicyEnforceFailed => {
+            "No se pudo aplicar la política de documentación en tasks.json: {err}"
+        }
+        Text::RemovedMasterDocs => {
+            "Se eliminaron las entradas de documentación escritas por el maestro; usa /attach-docs para rellenarlas."
+        }
+        Text::AppendedTask => "Se añadió {task}.",
+        Text::AutomaticTasksWriteFailed => {
+            "No se pudieron escribir las tareas automáticas en tasks.json: {err}"
+        }
+        Text::TaskTreeRefreshFailed => {
+            "No se pudo actualizar el árbol de tareas desde tasks.json: {err}"
+        }
+        Text::TasksReadAfterMasterFailed => {
+            "No se pudo leer tasks.json tras la actualización del maestro: {err}"
+        }
+        Text::TasksCorrectionRequested => {
+            "Se pidió al maestro que corrija tasks.json (intento {attempt})."
+        }
+        Text::TasksCorrectionRetriesExceeded => {
+            "Se agotaron los reintentos de corrección de tasks.json. Esperando la próxima entrada del usuario."
+        }
+        Text::TaskStatusPersistFailed => {
+            "No se pudo guardar el estado de las tareas en tasks.json: {err}"
+        }
+        Text::RetryingWorkerJob => "Reintentando ahora el trabajo del agente.",
+        Text::TransientRetryScheduled => {
+            "El agente sufrió un fallo transitorio del backend ({reason}); se reenviará el mismo trabajo en {delay}s (reintento {retry} de {max}). Esto no consume los reintentos de auditoría ni de pruebas de la tarea."
+        }
+        Text::TransientRetriesExhausted => {
+            "El agente sigue fallando tras {max} reintentos ({reason}); la ejecución cuenta como fallida."
+        }
+        Text::AdvisorEditRejected => {
+            "El asesor editó tasks.json; la edición se rechazó y se restauró el archivo."
+        }
+        Text::AdvisorEditUndoFailed => {
+            "No se pudo deshacer la edición del asesor en tasks.json: {err}"
+        }
+        Text::ProjectInfoReadFailed => {
+            "La ejecución de información del proyecto terminó bien, pero no se pudo leer project-info.md: {err}"
+        }
+        Text::ProjectContextAttached => {
+            "Se recopiló y adjuntó el contexto del proyecto para esta sesión."
+        }
+        Text::ProjectInfoFallbackWriteFailed => {
+            "No se pudo guardar la salida alternativa de project-info.md: {err}"
+        }
+        Text::ProjectContextEmpty => {
+            "La recopilación del contexto del proyecto no devolvió contenido; se continúa sin adjuntarlo."
+        }
+        Text::ProjectContextExited => {
+            "La recopilación del contexto del proyecto terminó con código {code}; se continúa sin adjuntarlo."
+        }
+        Text::SessionModelsRecordFailed => {
+            "No se pudieron registrar los modelos de esta sesión en meta.json: {err}"
+        }
+        Text::SessionMetadataSaved => {
+            "Metadatos de la sesión guardados: \"{title}\" ({created_at})"
+        }
+        Text::SessionMetadataWritten => "Escritura de metadatos de la sesión completada.",
+        Text::SessionMetadataExited => {
+            "La escritura de metadatos de la sesión terminó con código {code}; se continúa."
+        }
+        Text::DocsAttached => "Se adjuntó la documentación a las tareas del plan.{snapshot_note}",
+        Text::DocsAttachExited => "La adjunción de documentación terminó con código {code}.",
+        Text::DocsAttachRefreshFailed => {
+            "La adjunción de documentación terminó, pero no se pudieron actualizar las tareas: {err}"
+        }
+        Text::DocsAttachReadFailed => {
+            "La adjunción de documentación terminó, pero no se pudo leer tasks.json: {err}"
+        }
+        Text::TaskCheckerFixed => "El revisor de tareas aplicó correcciones a tasks.json.",
+        Text::TaskCheckerRefreshFailed => {
+            "El revisor de tareas terminó, pero no se pudieron actualizar las tareas: {err}"
+        }
+        Text::TaskCheckerReadFailed => {
+            "El revisor de tareas terminó, pero no se pudo leer tasks.json."
+        }
+        Text::SessionTakenOver => {
+            "{owner} tomó el control de esta sesión con /lock takeover. Sus trabajos se detuvieron aquí y ahora la sigues en solo lectura."
+        }
+        Text::EventLogWriteFailed => {
+            "No se pudo escribir el registro de eventos de la sesión: {err}"
+        }
+        Text::TraceExportFailed => "Falló la exportación de trazas: {err}",
+        Text::AuditTrailWriteFailed => {
+            "No se pudo escribir audit-trail.jsonl; las entradas posteriores no se registran: {err}"
+        }
+        Text::SendingQueuedMessage => "Enviando mensaje en cola: {message}",
+        Text::SendingQueuedMessageRemaining => {
+            "Enviando mensaje en cola ({remaining} aún en cola): {message}"
+        }
+        Text::BatchStep => "Paso {number}/{total} del lote.",
+        Text::MasterPromptSent => "Prompt del maestro enviado.",
+        Text::RerunCancelled => "Repetición cancelada.",
+        Text::PlannerEditSessionInitFailed => {
+            "No se pudo inicializar la sesión para editar el plan: {err}"
+        }
+        Text::StartingNewSession => "Iniciando una nueva sesión.",
+        Text::ResumePickerCancelled => "Selector de sesiones cancelado.",
+        Text::BackendPickerCancelled => "Selector de backend cancelado.",
+        Text::LogPickerCancelled => "Selector de registros cancelado.",
+        Text::MasterStillProcessing => {
+            "El maestro sigue procesando tu petición anterior. Espera a que termine antes de enviar otro mensaje."
+        }
+        Text::DocsAttachBlocksSwitch => {
+            "La adjunción de documentación sigue en curso. Espera a que termine o usa /cancel docs antes de cambiar de sesión."
+        }
+        Text::DroppedBatchSteps => {
+            "Se descartaron los {batch_steps} pasos restantes del script por lotes."
+        }
+        Text::TasksEditedBeforeSkip => {
+            "tasks.json se editó fuera de la aplicación. Usa /reload-tasks o /keep-tasks antes de omitir o completar tareas."
+        }
+        Text::TasksWriteAfterUpdateFailed => {
+            "No se pudo escribir el archivo de tareas tras actualizar {task_id}: {err}"
+        }
+        Text::TasksEditedBeforeUnblock => {
+            "tasks.json se editó fuera de la aplicación. Usa /reload-tasks o /keep-tasks antes de desbloquear tareas."
+        }
+        Text::TasksEditedBeforeEdit => {
+            "tasks.json se editó fuera de la aplicación. Usa /reload-tasks o /keep-tasks antes de editar tareas."
+        }
+        Text::SpeedProfileSet => {
+            "Perfil de velocidad establecido en {profile} ({summary}). Los trabajos que empiecen a partir de ahora lo usan."
+        }
+        Text::SpeedProfileSaveFailed => {
+            "Perfil de velocidad establecido en {profile} para esta ejecución, pero no se pudo guardar en la sesión: {err}"
+        }
+        Text::SpeedProfileStatus => {
+            "El perfil de velocidad es {profile}. Usa /speed eco|standard|max: {options}."
+        }
+        Text::ReviewGateSaveFailed => {
+            "Revisión configurada para esta ejecución, pero no se pudo guardar en la sesión: {err}"
+        }
+        Text::TasksWriteAfterReviewFailed => {
+            "No se pudo escribir el archivo de tareas tras la revisión: {err}"
+        }
+        Text::NewSessionBlocked => {
+            "No se puede iniciar una nueva sesión del maestro mientras la ejecución está en marcha. Espera a que terminen los trabajos activos."
+        }
+        Text::StartedNewSession => "Nueva sesión del maestro iniciada",
+        Text::TemplateSessionBlocked => {
+            "No se puede iniciar una sesión desde una plantilla mientras la ejecución está en marcha. Espera a que terminen los trabajos activos."
+        }
+        Text::StartedTemplateSession => {
+            "Sesión {session} iniciada desde la plantilla `{template_name}`."
+        }
+        Text::TemplateSessionFailed => {
+            "No se pudo iniciar una sesión desde la plantilla `{template_name}`: {err}"
+        }
+        Text::DocsAttachAlreadyRunning => {
+            "La adjunción de documentación ya está en curso. Espera a que termine."
+        }
+        Text::AttachDocsSafeMode => {
+            "/attach-docs reescribe tasks.json, así que está desactivado en modo seguro."
+        }
+        Text::NoTaskWithId => "No hay ninguna tarea con id `{task_id}`.",
+        Text::DocsAttachStarted => "Subagente de adjunción de documentación iniciado.",
+        Text::NoSavedSessions => "No se encontraron sesiones guardadas.",
+        Text::NoOtherSessionsToResume => {
+            "No se encontraron otras sesiones guardadas para reanudar."
+        }
+        Text::ResumeOtherWorkspacesOnly => {
+            "Este espacio de trabajo no tiene otras sesiones; el selector muestra las de otros espacios. Elegir una cambia el directorio de trabajo."
+        }
+        Text::ResumePickerHint => {
+            "Elige una sesión en el selector y pulsa Intro o Espacio; escribe para filtrar."
+        }
+        Text::ResumePickerHintWithOthers => {
+            "Elige una sesión en el selector y pulsa Intro o Espacio; escribe para filtrar o pulsa Tab para ver las sesiones de otros espacios de trabajo."
+        }
+        Text::ListSessionsFailed => "No se pudieron listar las sesiones: {err}",
+        Text::NoOtherSessionToSwitch => {
+            "No hay otra sesión guardada en este espacio de trabajo a la que cambiar."
+        }
+        Text::LockTakeoverFailed => "No se pudo tomar el bloqueo de {session}: {err}",
+        Text::TookOverSession => {
+            "Se tomó el control de la sesión {session} de {owner}; esa instancia detendrá sus trabajos y pasará a solo lectura cuando lo detecte."
+        }
+        Text::FollowingLockedSession => {
+            "{owner} tiene esta sesión abierta; la sigues en solo lectura."
+        }
+        Text::NoLockedSessionWaiting => {
+            "No hay ninguna sesión bloqueada en espera; /lock se aplica cuando /resume o /switch encuentran una sesión abierta en otra instancia."
+        }
+        Text::LockUsage => {
+            "Uso: /lock takeover|observe, después de que /resume o /switch encuentren una sesión abierta en otra instancia.{held}"
+        }
+        Text::BackendPickerHint => {
+            "Elige un backend en el selector y pulsa Intro o Espacio (Retroceso cancela)."
+        }
+        Text::TestsModeSaved => "El modo de pruebas ahora es {mode}. Guardado en {path}.",
+        Text::TestsModeSaveFailed => {
+            "El modo de pruebas ahora es {mode} para esta ejecución, pero no se pudo guardar en config.toml (ruta predeterminada ~/.agentbob/config.toml; alternativas heredadas: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}."
+        }
+        Text::PlannerModeEnabled => {
+            "Modo planificador activado. El panel derecho muestra ahora planner.md para planificar en Markdown de forma colaborativa."
+        }
+        Text::RollingContextPersistFailed => "No se pudo guardar rolling_context.json: {err}",
+        Text::TaskModelStatus => "\"{title}\" ({top_id}) usa {model}. Perfiles: {profiles}.",
+        Text::UnknownModelProfile => {
+            "Perfil de modelo desconocido `{profile}`. Perfiles: {profiles}."
+        }
+        Text::TaskModelSet => {
+            "Los trabajos de \"{title}\" ({top_id}) usan ahora el perfil de modelo {profile}, a partir de su próximo trabajo."
+        }
+        Text::TaskModelReset => {
+            "Los trabajos de \"{title}\" ({top_id}) vuelven a usar el enrutado predeterminado de agentes."
+        }
+        Text::PromptPreviewOn => {
+            "Vista previa del prompt del maestro activada. Cada prompt del maestro se muestra para confirmarlo antes de enviarlo."
+        }
+        Text::PromptPreviewOff => {
+            "Vista previa del prompt del maestro desactivada. Los prompts del maestro se envían de inmediato."
+        }
+        Text::ResponseCacheStats => {
+            "Caché de respuestas: {hits} acierto(s) y {misses} fallo(s) desde el inicio; {entries} resultado(s) en caché ({kb} KB) en disco. /cache purge la vacía."
+        }
+        Text::ResponseCachePurged => {
+            "Se eliminaron {removed} resultado(s) de la caché de respuestas."
+        }
+        Text::ResponseCachePurgeFailed => "No se pudo vaciar la caché de respuestas: {err}",
+        Text::CacheUsage => "Uso: /cache o /cache purge",
+        Text::SummariesHidden => "El panel derecho vuelve a mostrar la lista de tareas.",
+        Text::SummariesShown => {
+            "El panel derecho muestra cada tarea principal terminada con su resumen. /summaries o Retroceso vuelven a la lista de tareas."
+        }
+        Text::VerboseOn => {
+            "Salida detallada activada. El panel de subagentes muestra cada línea de los agentes; las {filtered} línea(s) filtradas hasta ahora están en los registros de agentes (/logs)."
+        }
+        Text::VerboseOff => {
+            "Salida detallada desactivada. El panel de subagentes vuelve a filtrar la salida de los agentes."
+        }
+        Text::SkipPlanEnabled => {
+            "Modo sin plan activado. El panel derecho muestra ahora las tareas del plan para ejecutarlas."
+        }
+        Text::ConvertNeedsPlanner => {
+            "/convert solo está disponible en modo planificador. Usa /planner primero."
+        }
+        Text::ConvertPlannerConflict => {
+            "planner.md tiene un conflicto de edición abierto; resuélvelo con /conflict antes de /convert."
+        }
+        Text::ConvertPersistFailed => {
+            "No se pudo guardar planner.md antes de /convert; conversión cancelada."
+        }
+        Text::NoPlanToImport => {
+            "No hay PLAN.md, TODO.md ni planner.md con contenido en la raíz del espacio de trabajo."
+        }
+        Text::ImportedPlan => "Se importó {path} en planner.md.",
+        Text::ImportedPlanHint => {
+            "Revísalo en el planificador; después /convert lo convierte en tareas."
+        }
+        Text::ImportPlanConflict => {
+            "planner.md tiene un conflicto de edición abierto; resuélvelo con /conflict y vuelve a ejecutar /import-plan."
+        }
+        Text::ImportPlanWriteFailed => "No se pudo escribir planner.md; el plan no se importó.",
+        Text::UnknownCommand => {
+            "Comando desconocido `{command}`. Escribe `/` para ver los comandos disponibles."
+        }
+        Text::GatheringProjectContext => {
+            "Recopilando el contexto del proyecto antes de contactar con el maestro."
+        }
+        Text::ProjectContextStillGathering => {
+            "El contexto del proyecto se sigue recopilando; tu mensaje se enviará cuando esté listo."
+        }
+        Text::StartedExecution => "Ejecución iniciada",
+        Text::ClearedStaleLock => {
+            "Se eliminó un bloqueo obsoleto de {session} dejado por {owner}, que ya no está en ejecución."
+        }
+        Text::LockWriteFailed => {
+            "No se pudo escribir el archivo de bloqueo de la sesión; otra instancia también podría abrirla: {err}"
+        }
+        Text::ResumeWorkspaceMissing => {
+            "No se pudo reanudar la sesión {session}: no se puede cambiar el directorio de trabajo a {workspace}: no es un directorio"
+        }
+        Text::ResumeSessionFailed => "No se pudo reanudar la sesión {session}: {err}",
+        Text::ResumedSession => "Sesión {session} reanudada",
+        Text::WorkingDirectoryChanged => {
+            "El directorio de trabajo ahora es {path}; los agentes trabajan allí desde el próximo trabajo. La configuración del proyecto se carga en el próximo inicio."
+        }
+        Text::TemplateUsageNoTemplates => {
+            "Uso: /new-from-template <nombre>. No se encontraron plantillas; añade archivos <nombre>.toml a {dir}."
+        }
+        Text::TemplateUsage => "Uso: /new-from-template <nombre>. Plantillas disponibles: {names}",
+        Text::ListTemplatesFailed => "No se pudieron listar las plantillas de {dir}: {err}",
+        Text::ResumedTaskTreeRefreshFailed => {
+            "No se pudo actualizar el árbol de tareas desde el tasks.json reanudado: {err}"
+        }
+        Text::UiStateSaveFailed => "No se pudo guardar ui-state.json: {err}",
+        Text::InterruptedDuringJob => {
+            "La ejecución anterior se detuvo durante {job}; ese trabajo volvió a pendiente. Usa /start para continuar."
+        }
+        Text::InterruptedDuringExecution => {
+            "La ejecución anterior se detuvo mientras la ejecución estaba en marcha. Usa /start para continuar."
+        }
+        Text::RestoredQueuedMessages => {
+            "Se restauraron {count} mensaje(s) en cola de la ejecución anterior."
+        }
+        Text::InterruptedRunClearFailed => "No se pudo borrar interrupted-run.json: {err}",
+        Text::RecentSessionsOffer => {
+            "Este espacio de trabajo tiene {count} sesión(es) usadas en los últimos 7 días. ¿Continuar la más reciente? Intro reanuda la sesión elegida; Retroceso inicia una nueva."
+        }
+        Text::TaskSplitStaleRemoveFailed => {
+            "Se omitió la división de tareas; no se pudo eliminar la propuesta obsoleta: {err}"
+        }
+        Text::TaskSplitKept => "El divisor de tareas mantuvo las tareas grandes tal como están.",
+        Text::TaskSplitReadFailed => "No se pudo leer la propuesta de división de tareas: {err}",
+        Text::TaskSplitDiscarded => {
+            "Se descartó la propuesta de división de tareas porque {reason}."
+        }
+        Text::TaskSplitProposed => {
+            "El divisor de tareas propone tareas más pequeñas.\n{summary}\nEscribe /approve-split para reescribir tasks.json o /reject-split para mantener las tareas actuales."
+        }
+        Text::NoTaskSplitPending => "No hay ninguna propuesta de división de tareas pendiente.",
+        Text::TaskSplitOtherSession => {
+            "Se descartó una propuesta de división de tareas de otra sesión."
+        }
+        Text::TaskSplitBlocked => {
+            "No se puede aplicar una división de tareas mientras la ejecución está en marcha. Espera a que terminen los trabajos activos."
+        }
+        Text::TaskSplitRejected => "Propuesta de división rechazada; tasks.json no cambia.",
+        Text::TaskSplitOutdated => {
+            "tasks.json cambió después de proponer la división; se descartó la propuesta."
+        }
+        Text::TaskSplitApplied => "Se aplicó la propuesta de división de tareas a tasks.json.",
+        Text::TaskSplitWriteFailed => {
+            "No se pudo escribir tasks.json al aplicar la división de tareas: {err}"
+        }
+        Text::TaskSplitInvalid => "La propuesta de división de tareas ya no es válida: {err}",
+        Text::SafeModeRestoredTasks => {
+            "El maestro escribió tasks.json en modo seguro; se restauraron las tareas anteriores. Solo se ofrecen para revisión los cambios en tasks.staged.json."
+        }
+        Text::SafeModeStagedTasks => {
+            "El maestro preparó cambios de tareas en {path}:\n{diff}\nEscribe /accept-tasks para aplicarlos a tasks.json o /reject-tasks para descartarlos."
+        }
+        Text::MasterChangedTasks => {
+            "El maestro cambió tasks.json:\n{diff}\nEscribe /accept-tasks para mantener estos cambios o /reject-tasks para restaurar las tareas anteriores."
+        }
+        Text::NoTaskChangesPending => "No hay cambios de tareas esperando aprobación.",
+        Text::TaskChangesOtherSession => "Se descartaron cambios de tareas de otra sesión.",
+        Text::KeptMasterTaskChanges => "Se mantuvieron los cambios de tareas del maestro.",
+        Text::RestoreTasksBlocked => {
+            "No se puede restaurar tasks.json mientras la ejecución está en marcha. Espera a que terminen los trabajos activos."
+        }
+        Text::PreviousTasksReadFailed => {
+            "No se pudo leer el tasks.json anterior para restaurarlo: {err}"
+        }
+        Text::RejectedMasterTaskChanges => {
+            "Se rechazaron los cambios de tareas del maestro; se restauró el tasks.json anterior."
+        }
+        Text::RestoreTasksWriteFailed => {
+            "No se pudo escribir tasks.json al restaurar las tareas anteriores: {err}"
+        }
+        Text::PreviousTasksInvalid => "El tasks.json anterior ya no es válido: {err}",
+        Text::DiscardedStagedTasks => {
+            "Se descartaron los cambios de tareas preparados del maestro."
+        }
+        Text::RemoveFileFailed => "No se pudo eliminar {path}: {err}",
+        Text::StagedTasksBlocked => {
+            "No se pueden aplicar las tareas preparadas mientras la ejecución está en marcha. Espera a que terminen los trabajos activos."
+        }
+        Text::StagedTasksReadFailed => "No se pudieron leer las tareas preparadas de {path}: {err}",
+        Text::StagedTasksInvalid => "Las tareas preparadas ya no son válidas: {err}",
+        Text::AppliedStagedTasks => {
+            "Se aplicaron a tasks.json los cambios de tareas preparados del maestro."
+        }
+        Text::StagedTasksWriteFailed => {
+            "No se pudo escribir tasks.json al aplicar las tareas preparadas: {err}"
+        }
+        Text::AppendFileFailed => "No se pudo añadir a {path}: {err}",
+        Text::NoEditConflict => "No hay ningún conflicto de edición abierto.",
+        Text::WriteFileFailed => "No se pudo escribir {file_name}: {err}",
+        Text::NoExternalTasksEdit => "No hay ninguna edición externa de tasks.json pendiente.",
+        Text::ExternalTasksEditOtherSession => {
+            "Se descartó una edición pendiente de tasks.json de otra sesión."
+        }
+        Text::ExternalTasksEditDiscarded => {
+            "Se descartó la edición externa; tasks.json se restauró desde el árbol de tareas actual."
+        }
+        Text::RestoreTasksFailed => "No se pudo restaurar tasks.json: {err}",
+        Text::ReloadTasksBlocked => {
+            "No se puede recargar tasks.json mientras la ejecución está en marcha. Espera a que terminen los trabajos activos."
+        }
+        Text::ReloadTasksFailed => {
+            "No se puede recargar tasks.json: {err}. Corrige el archivo y vuelve a intentarlo o escribe /keep-tasks."
+        }
+        Text::ReloadedTasksResuming => {
+            "Se recargó tasks.json; la ejecución continúa con las dependencias actualizadas."
+        }
+        Text::ReloadedTasks => "Se recargó tasks.json.",
+        Text::ReloadTasksInvalid => {
+            "tasks.json no es válido: {err}. Corrige el archivo y vuelve a intentarlo o escribe /keep-tasks."
+        }
+        Text::FinalAuditBlocked => {
+            "No se pueden modificar las tareas de auditoría final mientras la ejecución está en marcha. Espera a que terminen los trabajos activos."
+        }
+        Text::FinalAuditDisabled => {
+            "Las tareas de auditoría final están desactivadas por configuración (final_audit = \"never\")."
+        }
+        Text::FinalAuditReadFailed => {
+            "No se pudo leer el archivo de tareas; se canceló el comando de auditoría final: {err}"
+        }
+        Text::FinalAuditAdded => "Tarea de auditoría final añadida.",
+        Text::FinalAuditAddWriteFailed => {
+            "No se pudo escribir el archivo de tareas al añadir la tarea de auditoría final: {err}"
+        }
+        Text::FinalAuditRefreshFailed => {
+            "Se canceló el comando de auditoría final; no se pudo actualizar el árbol de tareas antes de escribir: {err}"
+        }
+        Text::FinalAuditMissing => "No había ninguna tarea de auditoría final.",
+        Text::FinalAuditRemoved => "Tarea de auditoría final eliminada.",
+        Text::FinalAuditRemoveWriteFailed => {
+            "No se pudo escribir el archivo de tareas al eliminar la tarea de auditoría final: {err}"
+        }
+        Text::NoQueuedJobs => "No hay trabajos de agentes en cola.",
+        Text::QueuedJobs => {
+            "Trabajos de agentes en cola:\n{lines}\nUsa /jobs move <desde> <hasta> o /jobs drop <n>."
+        }
+        Text::MovedJob => "Se movió {job} a la posición {position} de la cola.",
+        Text::DroppedJob => {
+            "Se descartó {job} de la cola; la tarea sigue sin terminar y /retry {task} la vuelve a ejecutar cuando la ejecución esté inactiva."
+        }
+        Text::BulkTasksUsage => {
+            "Uso: /tasks done|reset|delete <números>, con números de la lista de tareas como 3,5-7."
+        }
+        Text::BulkTasksUnchanged => "No se cambió ninguna tarea: {err}",
+        Text::BulkTasksWriteFailed => {
+            "No se cambió ninguna tarea. No se pudo escribir tasks.json: {err}"
+        }
+        Text::BulkTasksRefreshFailed => {
+            "Se guardó tasks.json, pero no se pudo actualizar la lista de tareas: {err}"
+        }
+        Text::CancelUsage => "Uso: /cancel context | /cancel docs | /cancel taskcheck",
+        Text::NoProjectContextRunning => "No se está recopilando el contexto del proyecto.",
+        Text::ProjectContextCancelledSending => {
+            "Se canceló la recopilación del contexto del proyecto; tu mensaje se envía al maestro sin él."
+        }
+        Text::ProjectContextCancelled => {
+            "Se canceló la recopilación del contexto del proyecto; se continúa sin adjuntarlo."
+        }
+        Text::NoDocsAttachRunning => "No hay ninguna adjunción de documentación en curso.",
+        Text::DocsAttachCancelled => {
+            "Se canceló la adjunción de documentación. Se conservó la documentación ya adjuntada."
+        }
+        Text::TaskSplitterCancelled => "Se canceló el divisor de tareas.",
+        Text::NoTaskCheckRunning => "No hay ninguna revisión de tareas en curso.",
+        Text::TaskCheckCancelled => "Se canceló la revisión de tareas.",
+        Text::TaskCheckCancelledUnchanged => {
+            "Se canceló la revisión de tareas; tasks.json no cambió."
+        }
+        Text::TaskCheckCancelledRestored => {
+            "Se canceló la revisión de tareas y se restauró el tasks.json de antes de ejecutarla."
+        }
+        Text::TaskCheckCancelRestoreFailed => {
+            "Se canceló la revisión de tareas, pero no se pudo restaurar tasks.json: {err}"
+        }
+        Text::DirectUsage => "Uso: /direct <instrucción> ejecuta un cambio sin planificar.",
+        Text::DirectRefreshFailed => {
+            "Se canceló /direct; no se pudo actualizar el árbol de tareas: {err}"
+        }
+        Text::DirectWriteFailed => "No se pudo escribir la tarea directa en tasks.json: {err}",
+        Text::DirectTaskAdded => "Se añadió la tarea directa {task_id}; se ejecuta sin planificar.",
+        Text::ConvertingPlanner => "Convirtiendo planner.md en tasks.json en modo tareas...",
+        Text::PlannerWriteFailed => "No se pudo escribir el plan en planner.md: {err}",
+        Text::ReportQueuePersistFailed => "No se pudo guardar master-report-queue.json: {err}",
+        Text::SavedProjectMemory => {
+            "Se guardaron {added} dato(s) en la memoria del proyecto ({path})."
+        }
+        Text::ProjectMemoryUpdateFailed => "No se pudo actualizar la memoria del proyecto: {err}",
+        Text::MasterAskedQuestions => {
+            "El maestro hizo {count} pregunta(s); respóndelas en el formulario."
+        }
+        Text::QuestionsDismissed => "Preguntas de aclaración descartadas.",
+        Text::RecordAnswersFailed => "No se pudieron registrar las respuestas en {path}: {err}",
+        Text::RerunNeedsSession => "/rerun necesita una sesión activa.",
+        Text::FileTreeShown => {
+            "Árbol de archivos activado en el panel superior izquierdo. Arriba/Abajo elige, Intro expande o abre una vista de solo lectura, Retroceso vuelve. Los archivos marcados con * cambiaron en esta sesión."
+        }
+        Text::FileTreeHidden => {
+            "Árbol de archivos oculto; el panel superior izquierdo vuelve a mostrar la salida de los agentes."
+        }
+        Text::PreviewingPrompt => {
+            "Vista previa del prompt del maestro. Intro lo envía; Retroceso cancela y devuelve tu mensaje a la entrada para editarlo."
+        }
+        Text::PreviewCancelled => "Vista previa cancelada; no se envió nada.",
+        Text::StallWaiting => {
+            "Se sigue esperando; el trabajo se marcará de nuevo si sigue sin salida otro periodo."
+        }
+        Text::StallNudging => {
+            "Insistiendo al agente atascado; su ejecución se detuvo y su sesión se reanuda con un prompt de seguimiento."
+        }
+        Text::StallCannotNudge => {
+            "El trabajo en curso no tiene una sesión de agente que reanudar, así que no se le puede insistir. Pulsa w para seguir esperando o a para abortarlo."
+        }
+        Text::StallAborting => "Abortando el trabajo atascado; terminará como ejecución fallida.",
+        Text::StallAlreadyStopped => "El trabajo atascado ya se detuvo; esperando a que termine.",
+        Text::FailureStatsHidden => {
+            "Estadísticas de fallos ocultas; el panel superior izquierdo vuelve a mostrar la salida de los agentes."
+        }
+        Text::FailureStatsShown => {
+            "Estadísticas de fallos de {sessions} sesión(es) en el panel superior izquierdo. Ejecuta /stats de nuevo para ocultarlas."
+        }
+        Text::FailureStatsFailed => "No se pudieron recopilar las estadísticas de fallos: {err}",
+        Text::WorkerLogsNeedSession => {
+            "No hay sesión activa; los registros de agentes se guardan por sesión."
+        }
+        Text::NoWorkerLogsForTask => {
+            "Todavía no hay registros de agentes para la tarea `{task_id}`."
+        }
+        Text::NoWorkerLogs => "Todavía no hay registros de agentes en esta sesión.",
+        Text::ListWorkerLogsFailed => "No se pudieron listar los registros de agentes: {err}",
+        Text::WorkerLogWriteFailed => "No se pudo escribir el registro de agente {path}: {err}",
+        Text::PinModelsUsage => "Uso: /pin-models o /pin-models off",
+        Text::UpdateFileFailed => "No se pudo actualizar {path}: {err}",
+        Text::UnpinnedModels => {
+            "Se liberaron los modelos de esta sesión; los agentes que empiecen a partir de ahora siguen config.toml."
+        }
+        Text::PinnedModelsUnchanged => {
+            "Se fijaron los modelos de esta sesión. Coinciden hoy con config.toml y se mantienen si cambia; /pin-models off vuelve a seguir config.toml."
+        }
+        Text::PinnedModels => {
+            "Se fijaron los modelos de esta sesión; los agentes que empiecen a partir de ahora los usan en lugar de config.toml: {drift}. /pin-models off vuelve a seguir config.toml."
+        }
+        Text::PinnedModelDrift => {
+            "Esta sesión fija los modelos con los que se creó, que config.toml ahora enruta de otra forma: {drift}. /pin-models off sigue config.toml en su lugar."
+        }
+        Text::ModelDrift => {
+            "config.toml enruta ahora los agentes de esta sesión a modelos distintos de los de su creación: {drift}. Ejecuta /pin-models para mantener los modelos originales durante el resto del plan."
+        }
+        Text::BackendUnchanged => "El backend sigue siendo {backend}.",
+        Text::BackendSaved => {
+            "Backend establecido en {backend}. Guardado en {path}. Los nuevos adaptadores usarán este backend."
+        }
+        Text::BackendSaveFailed => {
+            "Backend establecido en {backend} para esta ejecución, pero no se pudo guardar en config.toml (ruta predeterminada ~/.agentbob/config.toml; alternativas heredadas: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}. Los nuevos adaptadores de esta ejecución usarán igualmente este backend."
+        }
+        Text::BackendModelReloadFailed => {
+            "Backend cambiado a {label} en memoria, pero no se pudo recargar la configuración de modelos: {err}. Los próximos adaptadores usan los valores predeterminados."
+        }
+        Text::BackendFailover => {
+            "{failures} trabajos seguidos fallaron en {current}; se cambia a {fallback} para los siguientes trabajos. Esto no se guarda en config.toml; usa /backend para volver."
+        }
+        Text::TasksReadFailed => "No se pudo leer tasks.json: {err}",
+        Text::FoundPlanFile => {
+            "Se encontró {file} en el espacio de trabajo. /import-plan lo copia en planner.md; /import-plan tasks hace que el maestro lo convierta en tareas."
+        }
+        Text::SplittingAudits => "Dividiendo auditorías...",
+        Text::MergingAudits => "Combinando auditorías...",
+        Text::CachedResultApplyFailed => {
+            "No se pudo aplicar el resultado en caché de {call}; se llama al modelo: {err}"
+        }
+        Text::CachedResultSaveFailed => {
+            "No se pudo guardar el resultado de {call} en la caché de respuestas: {err}"
+        }
+        Text::ReportNoSummary => {
+            "El agente de informes del maestro no devolvió un resumen para la tarea \"{top_task_title}\"."
+        }
+        Text::TaskSummariesWriteFailed => "No se pudo escribir task-summaries.json: {err}",
+        Text::TaskSummarized => "La tarea \"{title}\" está resumida en /summaries.",
+        Text::ExecutionAlreadyRunning => {
+            "La ejecución ya está en marcha; se continúa con la tarea actual."
+        }
+        Text::ResumedUnfinishedTasks => {
+            "Se reanudó desde la(s) última(s) tarea(s) sin terminar. Se pusieron en cola {queued} trabajo(s) de tareas."
+        }
+        Text::ExecutionAlreadyEnabled => {
+            "La ejecución ya está activada. No hay tareas sin terminar que reanudar."
+        }
+        Text::ExecutionEnabled => {
+            "Ejecución activada. Se pusieron en cola {queued} trabajo(s) de tareas."
+        }
+        Text::ExecutionEnabledScoped => {
+            "Ejecución activada solo para \"{top_title}\"{via}. Se pusieron en cola {queued} trabajo(s) de tareas."
+        }
+        Text::RerunningEditedPrompt => {
+            "Volviendo a ejecutar {label} de \"{title}\" ({task_id}) con el prompt editado."
+        }
+        Text::PassReadyForReview => {
+            "La pasada {pass} de implementación de la tarea #{task} está lista para revisión. Usa /review approve, /review changes <comentarios> o /review revert."
+        }
+        Text::FinalAuditPassed => {
+            "La tarea de auditoría final #{task} terminó en la pasada {pass}."
+        }
+        Text::FinalAuditRetriesExhausted => {
+            "La tarea de auditoría final #{task} siguió fallando en la pasada {pass}. Se alcanzó el máximo de reintentos ({max}); no se ponen en cola más reintentos de auditoría final."
+        }
+        Text::FinalAuditNotPassedRetry => {
+            "La tarea de auditoría final #{task} no aprobó explícitamente; reintento en cola."
+        }
+        Text::FinalAuditFailedRetry => {
+            "La tarea de auditoría final #{task} falló (código {code}); reintento en cola."
+        }
+        Text::ScopedRunDone => {
+            "La ejecución acotada de \"{title}\" terminó; las demás tareas pendientes no se tocaron. Usa /start para continuar el plan."
+        }
+        Text::TaskSkipped => "Se omitió \"{title}\" ({task_id}).",
+        Text::TaskMarkedDone => "Se marcó \"{title}\" ({task_id}) como hecha.",
+        Text::TaskResetForRetry => {
+            "Se devolvió \"{title}\" ({task_id}) a pendiente para reintentarla."
+        }
+        Text::TaskUnblocked => "Se desbloqueó \"{title}\" ({task_id}) con tu respuesta.",
+        Text::UnblockedTaskQueued => "Se puso en cola la tarea desbloqueada.",
+        Text::UnblockedTaskWaitsForStart => "Se ejecutará cuando empiece la ejecución (/start).",
+        Text::PluginStepQueued => {
+            "La tarea #{task} completó todas las auditorías; paso de plugin en cola."
+        }
+        Text::ExistingTestsQueued => {
+            "La tarea #{task} completó todas las auditorías; ejecutor de pruebas existentes en cola."
+        }
+        Text::ImplementationAuditsPassed => {
+            "La rama de implementación de la tarea #{task} superó todas las auditorías."
+        }
+        Text::AuditQueued => "Auditoría de la tarea #{task} en cola (pasada de auditoría {pass}).",
+        Text::PluginStepSkipped => "Aviso: se omitió el paso de plugin \"{step}\". {err}",
+        Text::TestsOffSkippingTestWriter => {
+            "La tarea #{task} tiene el modo de pruebas DESACTIVADO; se omiten los pasos del escritor de pruebas."
+        }
+        Text::TestWriterAuditSkipped => {
+            "Se omitió la auditoría del escritor de pruebas de la tarea #{task}; las auditorías del escritor de pruebas están desactivadas en este perfil de velocidad."
+        }
+        Text::TestWriterAuditQueued => {
+            "La pasada {pass} del escritor de pruebas de la tarea #{task} terminó; auditoría del escritor de pruebas en cola."
+        }
+        Text::TestRunnerQueueFailed => {
+            "La tarea #{task} no pudo poner en cola el ejecutor de pruebas determinista; la rama de pruebas queda bloqueada."
+        }
+        Text::TestRunQueued => {
+            "La pasada {pass} del escritor de pruebas de la tarea #{task} terminó; ejecución determinista de pruebas en cola."
+        }
+        Text::TaskBlockedOnUser => {
+            "La tarea #{top_task_id} está bloqueada esperándote: {reason}\nResponde con /unblock {task_id} <respuesta>; las demás tareas siguen ejecutándose."
+        }
+        Text::TaskCompletedWithTests => {
+            "La tarea #{task} terminó después de que convergieran las ramas de implementación y de pruebas."
+        }
+        Text::TaskCompleted => {
+            "La tarea #{task} terminó después de que convergieran las comprobaciones de la rama de implementación."
+        }
+        Text::AuditPassedWithoutCriteria => {
+            "La auditoría de la tarea #{task} aprobó sin confirmar que se cumplen todos los criterios de aceptación; se trata como auditoría fallida."
+        }
+        Text::AuditRetriesExhausted => {
+            "La auditoría de la tarea #{task} siguió encontrando bloqueos críticos en la pasada {pass}. Se alcanzó el máximo de reintentos ({max}); se pasa a la siguiente auditoría o paso."
+        }
+        Text::AuditRequestedFixes => {
+            "La auditoría de la tarea #{task} pidió correcciones; pasada {pass} del implementador en cola."
+        }
+        Text::ImplementationResumedAudit => {
+            "La pasada {pass} de implementación de la tarea #{task} terminó; auditoría reanudada en cola."
+        }
+        Text::ImplementationFailedRetry => {
+            "La implementación de la tarea #{task} falló (código {code}); reintento en cola."
+        }
+        Text::ProtectedPathsModified => {
+            "La pasada {pass} de implementación de la tarea #{task} modificó rutas protegidas: {paths}. La auditoría falló por infringir una ruta protegida."
+        }
+        Text::ReviewSentBack => {
+            "La revisión devolvió la pasada {pass} de implementación de la tarea #{task}; pasada {next_pass} en cola."
+        }
+        Text::PluginStepPassed => {
+            "El paso de plugin {name} de la tarea #{top_task_id} pasó en la ejecución {pass}."
+        }
+        Text::PluginStepRetriesExhausted => {
+            "El paso de plugin {name} de la tarea #{top_task_id} siguió fallando en la pasada {pass}. Se alcanzó el máximo de reintentos ({max}); se pasa al siguiente paso."
+        }
+        Text::PluginStepFailed => {
+            "El paso de plugin {name} de la tarea #{top_task_id} falló; pasada {next_pass} del implementador en cola."
+        }
+        Text::TestsOffSkippingTestAuditRetries => {
+            "La tarea #{task} tiene el modo de pruebas DESACTIVADO; se omiten los reintentos de auditoría del escritor de pruebas."
+        }
+        Text::TestAuditRetriesExhausted => {
+            "La auditoría del escritor de pruebas de la tarea #{task} siguió encontrando bloqueos críticos en la pasada {pass}. Se alcanzó el máximo de reintentos ({max}); se pasa a las pruebas deterministas."
+        }
+        Text::TestAuditRequestedFixes => {
+            "La auditoría del escritor de pruebas de la tarea #{task} pidió correcciones; pasada {pass} del escritor de pruebas en cola."
+        }
+        Text::TestAuditPassComplete => {
+            "La pasada {pass} de auditoría del escritor de pruebas de la tarea #{task} terminó."
+        }
+        Text::DeterministicTestsPassed => {
+            "Las pruebas deterministas de la tarea #{task} pasaron en la ejecución {pass}."
+        }
+        Text::TestsOffSkippingTestRunnerRetries => {
+            "La tarea #{task} tiene el modo de pruebas DESACTIVADO; se omiten los reintentos del ejecutor de pruebas deterministas."
+        }
+        Text::TestsRetriesExhaustedCleanup => {
+            "Las pruebas de la tarea #{task} siguieron fallando en la pasada {pass}. Se alcanzó el máximo de reintentos ({max}); pasada de limpieza en cola."
+        }
+        Text::ExistingTestsPassed => {
+            "El ejecutor de pruebas existentes de la tarea #{task} pasó en la ejecución {pass}; la rama del implementador terminó."
+        }
+        Text::TestsOffSkippingExistingTestRetries => {
+            "La tarea #{task} tiene el modo de pruebas DESACTIVADO; se omiten los reintentos del ejecutor de pruebas existentes."
+        }
+        Text::ExistingTestsRetriesExhausted => {
+            "Las pruebas existentes de la tarea #{task} siguieron fallando en la pasada {pass}. Se alcanzó el máximo de reintentos ({max}); se pasa al siguiente paso."
+        }
+        Text::ExistingTestsFailed => {
+            "Las pruebas existentes de la tarea #{task} fallaron; pasada {pass} del implementador en cola."
+        }
+        Text::FailingTestsRemoved => {
+            "La tarea #{task} eliminó las pruebas que fallaban tras los reintentos y continuó."
+        }
+        Text::TestWriterResumedAudit => {
+            "La pasada {pass} del escritor de pruebas de la tarea #{task} terminó; auditoría reanudada en cola."
+        }
+        Text::TestsOffSkippingTestWriterRetries => {
+            "La tarea #{task} tiene el modo de pruebas DESACTIVADO; se omiten los reintentos del escritor de pruebas."
+        }
+        Text::TestWriterRetriesExhausted => {
+            "El escritor de pruebas de la tarea #{task} siguió fallando en la pasada {pass}. Se alcanzó el máximo de reintentos ({max}); se pasa al siguiente paso."
+        }
+        Text::TestWriterFailedRetry => {
+            "El escritor de pruebas de la tarea #{task} falló (código {code}); reintento en cola."
+        }
+        Text::VerifyQueued => {
+            "Las pruebas de la tarea #{top_task_id} terminaron; comando de verificación en cola (intento {attempt})."
+        }
+        Text::VerifySkippedSafeMode => {
+            "Las pruebas de la tarea #{top_task_id} terminaron; se omitió su comando de verificación en modo seguro."
+        }
+        Text::VerifyPassed => {
+            "El comando de verificación de la tarea #{top_task_id} pasó en el intento {attempt}; la rama del implementador terminó."
+        }
+        Text::VerifyRetriesExhausted => {
+            "El comando de verificación de la tarea #{top_task_id} siguió fallando en el intento {attempt}. Se alcanzó el máximo de reintentos ({max_retries}); se pasa al siguiente paso."
+        }
+        Text::VerifyFailed => {
+            "El comando de verificación de la tarea #{top_task_id} falló; pasada {pass} del implementador en cola."
+        }
+        Text::DocSnapshotsDropped => {
+            " Se conservaron {kept} instantánea(s) de documentación; se descartaron {dropped} que faltaban o estaban fuera de docs/."
+        }
+        Text::DocSnapshotsClearFailed => {
+            " No se pudieron quitar de tasks.json las instantáneas de documentación inservibles: {err}"
+        }
+        Text::DocSnapshotsSaved => " Se guardaron {kept} instantánea(s) de documentación.",
+        Text::DocSnapshotsSanitizeFailed => {
+            " No se pudieron limpiar las instantáneas de documentación: {err}"
+        }
+        Text::LockHeldHere => " Esta instancia tiene el bloqueo de {session}.",
+        Text::ConflictKeptMine => {
+            "Conflicto de edición resuelto: se conservó tu {file_name} y se descartaron los cambios del maestro.{follow_up}"
+        }
+        Text::ConflictKeptTheirs => {
+            "Conflicto de edición resuelto: se conservó el {file_name} del maestro y se descartaron tus ediciones.{follow_up}"
+        }
+        Text::ConflictMergedWithMarkers => {
+            "Conflicto de edición resuelto: se combinaron ambas versiones de {file_name}; los cambios superpuestos quedan entre los marcadores <<<<<<< yours y >>>>>>> master para que los edites.{follow_up}"
+        }
+        Text::ConflictMerged => {
+            "Conflicto de edición resuelto: se combinaron ambas versiones de {file_name}.{follow_up}"
+        }
+        Text::ConflictReloadHint => {
+            " Escribe /reload-tasks para cargarlo o /keep-tasks para descartarlo."
+        }
+        Text::BulkTasksMarkedDone => {
+            "Se marcaron como hechas {count} tarea(s) con sus subtareas:\n{titles}"
+        }
+        Text::BulkTasksReset => {
+            "Se devolvieron a pendiente {count} tarea(s) con sus subtareas:\n{titles}"
+        }
+        Text::BulkTasksDeleted => "Se eliminaron {count} tarea(s) con sus subtareas:\n{titles}",
+        Text::BulkTasksConfirmDelete => {
+            "Esto elimina {count} tarea(s) con sus subtareas:\n{titles}\nEnvía de nuevo el mismo /tasks delete para confirmar."
+        }
+        Text::ContextShown => "Secciones del próximo prompt del maestro:",
+        Text::ContextSectionIncluded => {
+            "`{section}` se incluye ahora en el próximo prompt del maestro:"
+        }
+        Text::ContextSectionLeftOut => {
+            "`{section}` queda ahora fuera del próximo prompt del maestro:"
+        }
+        Text::ContextReset => {
+            "Todas las secciones vuelven a incluirse en el próximo prompt del maestro:"
+        }
+        Text::ContextSections => {
+            "{headline}\n{sections}\nActiva o desactiva una sección con /context <sección>. La elección solo se aplica al próximo prompt del maestro."
+        }
+        Text::SessionLockedElsewhere => {
+            "La sesión {session} está abierta en otra instancia ({owner}, desde {since}). Escribe /lock takeover para abrirla aquí de todos modos o /lock observe para seguirla en solo lectura."
+        }
+        Text::SessionLockedOnOtherHost => {
+            "La sesión {session} está abierta en otra instancia ({owner}, desde {since}) en otro equipo, así que Bob no puede saber si sigue en ejecución. Escribe /lock takeover para abrirla aquí de todos modos o /lock observe para seguirla en solo lectura."
+        }
+        Text::ScopedRunContains => " (contiene {task_id})",
+        Text::CodeIndexRefreshFailed => {
+            "No se pudo actualizar el índice de búsqueda de código: {err}"
+        }
+        Text::EditConflictNotice => {
+            "El maestro cambió {file_name} mientras tenías ediciones propias; no se sobrescribió ninguna versión. Escribe /conflict mine para conservar la tuya, /conflict theirs para conservar la del maestro o /conflict merge para combinarlas (los cambios superpuestos llevan marcadores de conflicto)."
+        }
+        Text::ProgressDigestSent => "Se envió por correo el resumen de progreso a {to}.",
+        Text::ProgressDigestFailed => "No se pudo enviar por correo el resumen de progreso: {err}",
+        Text::FinalReportSent => "Se envió por correo el informe final a {to}.",
+        Text::FinalReportFailed => "No se pudo enviar por correo el informe final: {err}",
+        Text::PluginFailed => "El plugin {name} falló: {err}.",
+        Text::RemotePushFailed => {
+            "No se pudo subir la sesión {session} al almacenamiento remoto: {err}"
+        }
+        Text::TicketSyncConfigFailed => {
+            "Se omitió la sincronización de tickets; no se pudo cargar la configuración [sync]: {err}"
+        }
+        Text::TicketMoved => "Se movió {reference} a {state}.",
+        Text::TicketUpdateFailed => "No se pudo actualizar {reference}: {err}",
+        Text::TasksEditedExternally => {
+            "tasks.json se editó fuera de la aplicación. Las actualizaciones en ejecución no lo sobrescribirán; escribe /reload-tasks para cargarlo o /keep-tasks para restaurar el árbol de tareas actual."
+        }
+        Text::TasksEditedExternallyInvalid => {
+            "tasks.json se editó fuera de la aplicación, pero no es válido: {err}. Corrige el archivo o escribe /keep-tasks para restaurar el árbol de tareas actual."
+        }
+        Text::ObservedTasksApplyFailed => {
+            "No se pudo aplicar la actualización observada de tasks.json: {err}"
+        }
+        Text::ObservedTasksParseFailed => {
+            "No se pudo interpretar la actualización observada de tasks.json: {err}"
+        }
+        Text::ObservedTaskSkipped => {
+            "La instancia en ejecución omitió la tarea {task_id} ({title})."
+        }
+        Text::ObservedTaskMarkedDone => {
+            "La instancia en ejecución marcó como hecha la tarea {task_id} ({title})."
+        }
+        Text::ObservedTaskFailed => {
+            "{kind} falló en la tarea #{task} ({title}) tras {attempts} intento(s): {reason}"
+        }
+        Text::TestsFlakyWriterQueued => {
+            "Las pruebas de la tarea #{task} son inestables; pasada {pass} del escritor de pruebas en cola."
+        }
+        Text::TestsFailedWriterQueued => {
+            "Las pruebas de la tarea #{task} fallaron; pasada {pass} del escritor de pruebas en cola."
+        }
+        Text::ManualTaskFailed => "No se pudo actualizar la tarea {task_id}: {err}",
+        Text::UnblockFailed => "No se pudo desbloquear la tarea {task_id}: {err}",
+        Text::ReviewDecisionFailed => "No se pudo aplicar la decisión de revisión: {err}",
+        Text::RerunUnavailable => "No se puede volver a ejecutar la tarea {task_id}: {err}",
+        Text::RerunFailed => "No se pudo volver a ejecutar la tarea {task_id}: {err}",
+        Text::StartExecutionFailed => "No se pudo iniciar la ejecución: {err}",
+        Text::MoveJobFailed => "No se pudo mover el trabajo: {err}",
+        Text::DropJobFailed => "No se pudo quitar el trabajo: {err}",
+        Text::PluginRunFailed => "No se pudo ejecutar el plugin {name}: {err}",
+        Text::PluginOutputLine => "[{name}] {line}",
+        Text::PluginPassed => "El plugin {name} pasó.",
+        Text::PluginPassedSummary => "El plugin {name} pasó: {summary}",
+        Text::PluginFailedVerdict => "El plugin {name} falló.",
+        Text::PluginFailedSummary => "El plugin {name} falló: {summary}",
+        Text::RemotePushed => "Sesión {session} subida a {remote} ({count} archivos).",
+        Text::ModelEscalated => {
+            "La pasada {pass} del implementador de la tarea #{task} escaló del perfil de modelo {from} a {to} tras fallos repetidos de auditoría."
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/i18n_tests.rs"]
+mod tests;
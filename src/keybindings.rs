@@ -0,0 +1,373 @@
+/// Where a key binding applies. The help overlay lists the focused context first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Global,
+    ChatInput,
+    WorkerOutput,
+    FileTree,
+    TaskList,
+    PlannerEditor,
+    WorkerLog,
+    TaskSummaries,
+    Pickers,
+    TaskMenu,
+    PromptPreview,
+    QuestionForm,
+    RerunEditor,
+}
+
+impl KeyContext {
+    /// Section order after the focused context and [`KeyContext::Global`].
+    const ALL: [KeyContext; 13] = [
+        KeyContext::Global,
+        KeyContext::ChatInput,
+        KeyContext::WorkerOutput,
+        KeyContext::FileTree,
+        KeyContext::TaskList,
+        KeyContext::PlannerEditor,
+        KeyContext::WorkerLog,
+        KeyContext::TaskSummaries,
+        KeyContext::Pickers,
+        KeyContext::TaskMenu,
+        KeyContext::PromptPreview,
+        KeyContext::QuestionForm,
+        KeyContext::RerunEditor,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            KeyContext::Global => "Everywhere",
+            KeyContext::ChatInput => "Chat input",
+            KeyContext::WorkerOutput => "Worker output",
+            KeyContext::FileTree => "File tree",
+            KeyContext::TaskList => "Task list",
+            KeyContext::PlannerEditor => "Planner editor",
+            KeyContext::WorkerLog => "Worker log",
+            KeyContext::TaskSummaries => "Task summaries",
+            KeyContext::Pickers => "Resume, backend, and log pickers",
+            KeyContext::TaskMenu => "Task menu",
+            KeyContext::PromptPreview => "Master prompt preview",
+            KeyContext::QuestionForm => "Question form",
+            KeyContext::RerunEditor => "Re-run editor",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub context: KeyContext,
+    pub keys: &'static str,
+    pub action: &'static str,
+}
+
+const fn bind(context: KeyContext, keys: &'static str, action: &'static str) -> KeyBinding {
+    KeyBinding {
+        context,
+        keys,
+        action,
+    }
+}
+
+/// Every key the main loop handles, in the order the help overlay lists them.
+pub const KEY_BINDINGS: [KeyBinding; 58] = [
+    bind(
+        KeyContext::Global,
+        "F1, ?",
+        "Show this help; ? works outside text fields and on an empty chat input",
+    ),
+    bind(
+        KeyContext::Global,
+        "Enter, Backspace",
+        "Close this help; Backspace first clears the search",
+    ),
+    bind(
+        KeyContext::Global,
+        "Type",
+        "Search this help by key or action",
+    ),
+    bind(KeyContext::Global, "Up / Down", "Scroll this help"),
+    bind(
+        KeyContext::Global,
+        "Tab / Shift+Tab",
+        "Focus the next / previous pane",
+    ),
+    bind(
+        KeyContext::Global,
+        "Ctrl+Tab",
+        "Switch to the most recently used other session",
+    ),
+    bind(
+        KeyContext::Global,
+        "Ctrl+L",
+        "Switch the chat pane between Chat and System Log",
+    ),
+    bind(
+        KeyContext::Global,
+        "Ctrl+U, PgUp",
+        "Scroll the right pane up",
+    ),
+    bind(
+        KeyContext::Global,
+        "Ctrl+D, PgDn",
+        "Scroll the right pane down",
+    ),
+    bind(KeyContext::Global, "Mouse wheel", "Scroll the focused pane"),
+    bind(
+        KeyContext::Global,
+        "Click",
+        "Focus a pane; its [^]/[v] buttons scroll half a page",
+    ),
+    bind(
+        KeyContext::Global,
+        "Ctrl+C",
+        "Quit; while a job runs, choose how with /quit",
+    ),
+    bind(
+        KeyContext::ChatInput,
+        "Enter",
+        "Send the message or slash command",
+    ),
+    bind(
+        KeyContext::ChatInput,
+        "Shift/Ctrl/Alt+Enter",
+        "Insert a newline",
+    ),
+    bind(
+        KeyContext::ChatInput,
+        "Tab",
+        "Complete the suggested slash command",
+    ),
+    bind(KeyContext::ChatInput, "Left / Right", "Move the cursor"),
+    bind(
+        KeyContext::ChatInput,
+        "Ctrl/Alt+Left/Right",
+        "Move the cursor by word",
+    ),
+    bind(
+        KeyContext::ChatInput,
+        "Up / Down",
+        "Move the cursor between lines",
+    ),
+    bind(
+        KeyContext::ChatInput,
+        "Shift/Ctrl+Up/Down",
+        "Scroll the chat",
+    ),
+    bind(
+        KeyContext::ChatInput,
+        "Backspace",
+        "Delete the character before the cursor",
+    ),
+    bind(
+        KeyContext::ChatInput,
+        "W / N / A",
+        "With a stalled job and an empty input: wait, nudge, or abort it",
+    ),
+    bind(
+        KeyContext::WorkerOutput,
+        "Up / Down, k / j",
+        "Scroll the output",
+    ),
+    bind(
+        KeyContext::WorkerOutput,
+        "Click a citation",
+        "Open the cited file in the file viewer",
+    ),
+    bind(KeyContext::FileTree, "Up / Down", "Move the selection"),
+    bind(
+        KeyContext::FileTree,
+        "Enter",
+        "Expand a folder or open a file read-only",
+    ),
+    bind(
+        KeyContext::FileTree,
+        "Backspace",
+        "Close the file viewer or go up a folder",
+    ),
+    bind(
+        KeyContext::TaskList,
+        "Up / Down, k / j",
+        "Scroll the task list",
+    ),
+    bind(
+        KeyContext::TaskList,
+        "Click a task",
+        "Show or hide its details",
+    ),
+    bind(
+        KeyContext::TaskList,
+        "Right-click, long press",
+        "Open the task menu",
+    ),
+    bind(
+        KeyContext::PlannerEditor,
+        "Type",
+        "Edit planner.md; edits are saved after a pause",
+    ),
+    bind(KeyContext::PlannerEditor, "Enter", "Insert a newline"),
+    bind(KeyContext::PlannerEditor, "Arrows", "Move the cursor"),
+    bind(
+        KeyContext::PlannerEditor,
+        "Backspace",
+        "Delete the character before the cursor",
+    ),
+    bind(KeyContext::PlannerEditor, "Click", "Place the cursor"),
+    bind(KeyContext::WorkerLog, "Up / Down, k / j", "Scroll the log"),
+    bind(KeyContext::WorkerLog, "Backspace", "Close the log"),
+    bind(
+        KeyContext::TaskSummaries,
+        "Up / Down, k / j",
+        "Scroll the summaries",
+    ),
+    bind(
+        KeyContext::TaskSummaries,
+        "Backspace",
+        "Return to the task list",
+    ),
+    bind(KeyContext::Pickers, "Up / Down", "Move the selection"),
+    bind(
+        KeyContext::Pickers,
+        "Enter, Space",
+        "Choose the selected entry",
+    ),
+    bind(KeyContext::Pickers, "Type", "Filter the resume picker"),
+    bind(
+        KeyContext::Pickers,
+        "Backspace",
+        "Delete a filter character, or cancel",
+    ),
+    bind(KeyContext::TaskMenu, "Up / Down", "Move the selection"),
+    bind(
+        KeyContext::TaskMenu,
+        "Enter, Space, click",
+        "Choose the selected entry",
+    ),
+    bind(
+        KeyContext::TaskMenu,
+        "Backspace, right-click",
+        "Close the menu",
+    ),
+    bind(KeyContext::PromptPreview, "Enter", "Send the prompt"),
+    bind(KeyContext::PromptPreview, "Backspace", "Cancel"),
+    bind(KeyContext::PromptPreview, "Up / Down", "Scroll the prompt"),
+    bind(
+        KeyContext::QuestionForm,
+        "Type",
+        "Answer the selected question",
+    ),
+    bind(
+        KeyContext::QuestionForm,
+        "Enter",
+        "Next field; on the last field, send every answer",
+    ),
+    bind(KeyContext::QuestionForm, "Up / Down, Tab", "Switch fields"),
+    bind(
+        KeyContext::QuestionForm,
+        "Backspace",
+        "Delete the character before the cursor",
+    ),
+    bind(KeyContext::RerunEditor, "Type", "Edit the prompt"),
+    bind(
+        KeyContext::RerunEditor,
+        "Enter",
+        "Re-run the job with the edited prompt",
+    ),
+    bind(
+        KeyContext::RerunEditor,
+        "Shift/Ctrl/Alt+Enter",
+        "Insert a newline",
+    ),
+    bind(KeyContext::RerunEditor, "Arrows", "Move the cursor"),
+    bind(
+        KeyContext::RerunEditor,
+        "Backspace",
+        "Delete the character before the cursor",
+    ),
+    bind(KeyContext::RerunEditor, "Shift+Tab", "Cancel"),
+];
+
+/// The `?` overlay: the key bindings of every context, the focused one first, narrowed by a
+/// search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpOverlay {
+    focused: KeyContext,
+    query: String,
+    scroll: u16,
+}
+
+impl HelpOverlay {
+    pub fn new(focused: KeyContext) -> Self {
+        Self {
+            focused,
+            query: String::new(),
+            scroll: 0,
+        }
+    }
+
+    pub fn focused(&self) -> KeyContext {
+        self.focused
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn scroll(&self) -> u16 {
+        self.scroll
+    }
+
+    pub fn push_query(&mut self, text: &str) {
+        self.query.push_str(text);
+        self.scroll = 0;
+    }
+
+    /// Removes the last query character; false when the query was already empty.
+    pub fn pop_query(&mut self) -> bool {
+        let popped = self.query.pop().is_some();
+        self.scroll = 0;
+        popped
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self, max_scroll: u16) {
+        self.scroll = self.scroll.saturating_add(1).min(max_scroll);
+    }
+
+    /// Sections to show: the focused context, then [`KeyContext::Global`], then the rest.
+    /// Each keeps the bindings whose keys or action contain every word of the query, and
+    /// sections left empty are dropped.
+    pub fn sections(&self) -> Vec<(KeyContext, Vec<&'static KeyBinding>)> {
+        let words = self
+            .query
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+        let mut order = vec![self.focused];
+        order.extend(
+            KeyContext::ALL
+                .into_iter()
+                .filter(|context| *context != self.focused),
+        );
+        order
+            .into_iter()
+            .filter_map(|context| {
+                let bindings = KEY_BINDINGS
+                    .iter()
+                    .filter(|binding| binding.context == context)
+                    .filter(|binding| {
+                        let text = format!("{} {}", binding.keys, binding.action).to_lowercase();
+                        words.iter().all(|word| text.contains(word.as_str()))
+                    })
+                    .collect::<Vec<_>>();
+                (!bindings.is_empty()).then_some((context, bindings))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/keybindings_tests.rs"]
+mod tests;
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::io;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
@@ -6,7 +6,8 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use crossterm::cursor::SetCursorStyle;
 use crossterm::event::{
-    DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags,
     PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
 };
 use crossterm::execute;
@@ -17,38 +18,128 @@ use ratatui::prelude::*;
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::{Value, json};
 
+mod advisor;
 mod agent;
 mod agent_models;
 mod api;
 mod app;
 mod artifact_io;
+mod audit_citations;
+mod audit_trail;
+mod backend_doctor;
+mod backend_metrics;
+mod change_review;
+mod clarification;
+mod code_context;
+mod code_index;
+mod command_args;
+mod command_registry;
+mod credentials;
 mod default_config;
 mod deterministic;
+mod doc_snapshots;
+mod edit_conflict;
+mod email_digest;
+mod estimates;
+mod event_log;
 mod events;
+mod file_tree;
+mod i18n;
+mod keybindings;
+mod observer;
+mod output_filters;
+mod plan_import;
+mod plan_pipeline;
+mod plugins;
+mod project_info_scope;
+mod project_memory;
+mod prompt_budget;
+mod prompt_context;
+mod prompt_editor;
+mod prompt_snapshots;
+mod protected_paths;
+mod response_cache;
 mod services;
+mod session_lock;
+mod session_storage;
 mod session_store;
+mod session_template;
+mod shell_console;
+mod shutdown;
+mod speed_profile;
+mod stats;
 mod subagents;
+mod system_log;
+mod task_diff;
+mod task_edits;
+mod task_graph;
+mod tasks_changelog;
+mod tasks_watch;
+mod telemetry;
+mod terminal_graphics;
+mod test_runners;
 mod text_layout;
 mod theme;
+mod ticket_sync;
+mod time_format;
+mod transcript;
 mod ui;
+mod wakeup;
+mod web_ui;
+mod worker_logs;
 mod workflow;
+mod workspace_ignore;
 
-use agent::{AdapterOutputMode, AgentEvent, BackendKind, CodexAdapter, CodexCommandConfig};
+use advisor::AdvisorChat;
+use agent::{
+    AdapterOutputMode, AgentEvent, BackendKind, CodexAdapter, CodexCommandConfig, SafeModeSandbox,
+    TokenUsage,
+};
 use agent_models::{CodexAgentKind, CodexAgentModelRouting, CodexModelProfile};
-use app::{App, BackendOption, Pane, ResumeSessionOption, RightPaneMode};
-use artifact_io::{ensure_default_metaagent_config, load_merged_metaagent_config_text};
+use app::{
+    App, BackendOption, CacheCommand, CancelCommand, ConsoleCommand, JobsCommand, Pane,
+    PendingTaskChanges, PendingTaskSplit, PinModelsCommand, PlanImport, PluginCommand,
+    ResumeSessionOption, ReviewCommand, RightPaneMode, StallAction, TaskMenuEntry, TasksCommand,
+};
+use artifact_io::{
+    ensure_default_metaagent_config, load_merged_metaagent_config_text, runtime_storage_dir,
+};
+use clarification::{FormStep, QuestionForm};
+use command_registry::{CommandRegistry, UserCommand};
 use deterministic::TestRunnerAdapter;
+use edit_conflict::{ConflictChoice, ConflictFile, EditConflict};
+use event_log::SessionEventRecorder;
 use events::AppEvent;
+use i18n::Text;
+use observer::SessionObserver;
+use plugins::PluginRegistry;
+use prompt_editor::PromptEditor;
+use response_cache::{CachedCall, RESPONSE_CACHE_DIR_NAME, ResponseCache};
 use services::{
-    CoreOrchestrationService, DefaultCoreOrchestrationService, DefaultUiPromptService,
-    TaskWriteBaseline, UiPromptService,
+    BackendFailoverTracker, CoreOrchestrationService, DefaultCoreOrchestrationService,
+    DefaultUiPromptService, TaskWriteBaseline, TransientRetryQueue, UiPromptService,
 };
+use session_lock::{LockAction, LockOwner, LockState};
 use session_store::{
-    PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile, SessionListEntry,
-    SessionStore, TaskFailFileEntry, load_global_tests_mode_enabled,
-    persist_global_tests_mode_enabled,
+    InterruptedRunFile, MasterReportQueueFile, PlannerTaskFileEntry, PlannerTaskKindFile,
+    PlannerTaskStatusFile, SessionListEntry, SessionPreview, SessionSettingsFile, SessionStore,
+    SessionUiStateFile, TaskFailFileEntry, TaskSummaryFileEntry, load_global_backend_failover_threshold,
+    load_global_code_context_policy, load_global_docs_update_task, load_global_final_audit_policy,
+    load_global_offer_recent_session, load_global_output_filters, load_global_project_info_scope,
+    load_global_protected_paths, load_global_rerun_failed_tests, load_global_stall_after,
+    load_global_task_split_limits, load_global_tests_mode_enabled, load_global_verify_gate,
+    load_global_worker_log_policy, persist_global_tests_mode_enabled,
 };
-use theme::Theme;
+use shutdown::ShutdownMode;
+use speed_profile::SpeedProfile;
+use task_edits::{TaskEdit, TaskEditError};
+use tasks_changelog::TasksChangeAgent;
+use tasks_watch::TasksFileWatcher;
+use terminal_graphics::{InlineImages, find_placements, load_graphics_protocol};
+use theme::{ColorDepth, Theme};
+use worker_logs::{WorkerLogPolicy, WorkerLogWriter};
+use workflow::{DOCS_UPDATE_TASK_ID, FinalAuditPolicy, QueuedJobMeta, TaskSplitLimits, Workflow};
+use workspace_ignore::WorkspaceIgnore;
 #[cfg(test)]
 use workflow::JobRun;
 
@@ -68,6 +159,14 @@ enum SilentMasterCommand {
     MergeTests,
 }
 
+/// A task-splitter run on the task-check adapter, started right after a task check completes.
+#[derive(Debug, Clone)]
+struct TaskSplitRun {
+    session_dir: PathBuf,
+    tasks_baseline: String,
+    tasks_before: Vec<PlannerTaskFileEntry>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SubmitBlockReason {
     ProjectInfoGathering,
@@ -79,7 +178,25 @@ enum SubmitBlockReason {
 const GLOBAL_RIGHT_SCROLL_LINES: u16 = 5;
 const MAX_ADAPTER_EVENTS_PER_LOOP: usize = 32;
 const UI_TICK_INTERVAL: Duration = Duration::from_millis(120);
+/// UI tick while the user is away; spinners advance but the terminal is redrawn far less.
+const AWAY_UI_TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// With no key or mouse input for this long the user counts as away even if the terminal
+/// still has focus.
+const INPUT_IDLE_AFTER: Duration = Duration::from_secs(5 * 60);
+const OBSERVER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Longest the main loop sleeps without being woken; bounds how late state that is only
+/// checked once per iteration, such as trace export errors, is surfaced.
+const IDLE_WAIT_CAP: Duration = Duration::from_secs(5);
+const TASKS_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+const AWAY_TASKS_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_EVENT_BATCH_LIMIT: usize = 500;
 const PLANNER_AUTOSAVE_DEBOUNCE: Duration = Duration::from_millis(1_000);
+const RESUME_PREVIEW_PLANNER_LINES: usize = 6;
+/// Sessions used this recently are offered before a new session starts in their workspace.
+const RECENT_SESSION_WINDOW_SECS: u64 = 7 * 24 * 60 * 60;
+const TASKS_FILE_FIX_PROMPT: &str = "tasks.json failed to parse/validate. Fix tasks.json immediately and retry. \
+     Ensure id and parent_id are valid values and hierarchy is valid. \
+     Do not ask the user to start execution yet.";
 const PLANNER_PREFILL_INIT_PROMPT: &str = "Planner.md has been prefilled by user.";
 #[cfg(test)]
 type PendingTaskWriteBaseline = TaskWriteBaseline;
@@ -116,7 +233,7 @@ fn build_codex_adapter(
     selected_backend: BackendKind,
     output_mode: AdapterOutputMode,
     persistent_session: bool,
-    profile: &CodexModelProfile,
+    kind: CodexAgentKind,
 ) -> CodexAdapter {
     let mut config = base_command_config_for_backend(model_routing, selected_backend);
     if matches!(output_mode, AdapterOutputMode::JsonAssistantOnly)
@@ -126,8 +243,22 @@ fn build_codex_adapter(
     }
     config.output_mode = output_mode;
     config.persistent_session = persistent_session;
-    apply_codex_profile(&mut config, profile);
+    config.workdir = model_routing.workdir_for(kind);
+    apply_codex_profile(&mut config, &model_routing.profile_for(kind));
+    let Some(sandbox) = model_routing.safe_mode() else {
+        return CodexAdapter::with_config(config).with_agent_kind(kind.config_key());
+    };
+    let workspace = config
+        .workdir
+        .clone()
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
     CodexAdapter::with_config(config)
+        .with_agent_kind(kind.config_key())
+        .with_safe_mode(sandbox.clone())
+        .with_prompt_preamble(subagents::safe_mode_preamble(
+            &workspace.display().to_string(),
+        ))
 }
 
 fn build_json_persistent_adapter(
@@ -135,13 +266,12 @@ fn build_json_persistent_adapter(
     selected_backend: BackendKind,
     kind: CodexAgentKind,
 ) -> CodexAdapter {
-    let profile = model_routing.profile_for(kind);
     build_codex_adapter(
         model_routing,
         selected_backend,
         AdapterOutputMode::JsonAssistantOnly,
         true,
-        &profile,
+        kind,
     )
 }
 
@@ -151,29 +281,66 @@ fn build_plain_adapter(
     kind: CodexAgentKind,
     persistent_session: bool,
 ) -> CodexAdapter {
-    let profile = model_routing.profile_for(kind);
     build_codex_adapter(
         model_routing,
         selected_backend,
         AdapterOutputMode::PlainText,
         persistent_session,
-        &profile,
+        kind,
     )
 }
 
 fn main() -> io::Result<()> {
     let launch_options = parse_launch_options(std::env::args().skip(1))?;
+    if let Some(RootCommand::Observe(observe)) = launch_options.command {
+        let store = SessionStore::open_read_only(&observe.session_dir)?;
+        return run_in_terminal(|terminal, theme| {
+            run_observer_app(terminal, App::default(), theme, SessionObserver::new(store))
+        });
+    }
+    if let Some(RootCommand::ServeUi(serve)) = launch_options.command {
+        let store = SessionStore::open_read_only(&serve.session_dir)?;
+        let server = web_ui::DashboardServer::bind(&store, serve.port)?;
+        println!(
+            "Serving the dashboard for {} at http://{}/ (Ctrl+C stops it).",
+            store.session_dir().display(),
+            server.local_addr()?
+        );
+        return server.serve();
+    }
     if let Some(command) = launch_options.command {
         let exit_code =
             run_cli_command(command, launch_options.output_mode, launch_options.verbose);
         std::process::exit(exit_code);
     }
-    let startup_message = if let Some(path) = launch_options.send_file {
-        Some(std::fs::read_to_string(path)?)
-    } else {
-        None
+    let mut batch_steps = match launch_options.send_file {
+        Some(path) => parse_batch_steps(&std::fs::read_to_string(path)?),
+        None => Vec::new(),
     };
+    let startup_message = (!batch_steps.is_empty()).then(|| batch_steps.remove(0));
+    let cwd = std::env::current_dir()?;
+    let mut app = App::default();
+    app.set_safe_mode(launch_options.safe_mode);
+    app.load_batch_steps(batch_steps);
+    let exit_session = run_in_terminal(|terminal, theme| {
+        run_app(terminal, app, theme, cwd, startup_message.as_deref())
+    })?;
+    if let Some(session_dir) = exit_session {
+        match session_storage::push_on_exit(&session_dir) {
+            Ok(Some(summary)) => println!("{summary}."),
+            Ok(None) => {}
+            Err(err) => eprintln!(
+                "Failed to push session {} to remote storage: {err}",
+                session_dir.display()
+            ),
+        }
+    }
+    Ok(())
+}
 
+fn run_in_terminal<T>(
+    run: impl FnOnce(&mut Terminal<CrosstermBackend<io::Stdout>>, &Theme) -> io::Result<T>,
+) -> io::Result<T> {
     enable_raw_mode()?;
 
     let mut stdout = io::stdout();
@@ -181,6 +348,8 @@ fn main() -> io::Result<()> {
         stdout,
         EnterAlternateScreen,
         EnableMouseCapture,
+        EnableBracketedPaste,
+        EnableFocusChange,
         SetCursorStyle::SteadyBar
     )?;
     let keyboard_enhancements_enabled = execute!(
@@ -192,15 +361,10 @@ fn main() -> io::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
-    let theme = Theme::load_or_default("theme.toml");
-    let cwd = std::env::current_dir()?;
-    let result = run_app(
-        &mut terminal,
-        App::default(),
-        &theme,
-        cwd,
-        startup_message.as_deref(),
-    );
+    let theme = Theme::load_or_default("theme.toml").with_color_depth(ColorDepth::detect());
+    events::start_input_reader();
+    let result = run(&mut terminal, &theme);
+    events::stop_input_reader();
 
     if keyboard_enhancements_enabled {
         let _ = execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags);
@@ -209,35 +373,229 @@ fn main() -> io::Result<()> {
     execute!(
         terminal.backend_mut(),
         SetCursorStyle::DefaultUserShape,
+        DisableBracketedPaste,
+        DisableFocusChange,
         DisableMouseCapture,
         LeaveAlternateScreen
     )?;
     terminal.show_cursor()?;
+    telemetry::shutdown();
 
     result
 }
 
+/// Read-only TUI loop for `observe`: tails another process's session files and never writes them.
+fn run_observer_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    mut app: App,
+    theme: &Theme,
+    mut observer: SessionObserver,
+) -> io::Result<()> {
+    load_language_settings(&mut app);
+    app.set_read_only(true);
+    app.push_agent_message(app.system_text(
+        Text::ObservingSession,
+        &[("session", &observer.session_dir().display())],
+    ));
+    observer.poll(&mut app);
+
+    let mut needs_draw = true;
+    let mut last_poll = Instant::now();
+    while app.running {
+        if last_poll.elapsed() >= OBSERVER_POLL_INTERVAL {
+            if observer.poll(&mut app) {
+                let size = terminal.size()?;
+                let screen = Rect::new(0, 0, size.width, size.height);
+                let max_scroll = ui::chat_max_scroll(screen, &app);
+                app.set_chat_scroll(max_scroll);
+                needs_draw = true;
+            }
+            last_poll = Instant::now();
+        }
+
+        let wait = loop_wait_timeout(Instant::now(), [Some(last_poll + OBSERVER_POLL_INTERVAL)]);
+        let mut app_event = events::next_event(wait)?;
+        if matches!(&app_event, AppEvent::InsertNewline) && app.active_pane != Pane::LeftBottom {
+            app_event = AppEvent::Submit;
+        }
+        if !matches!(&app_event, AppEvent::Tick) {
+            needs_draw = true;
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        if app.is_help_overlay_open() && !matches!(&app_event, AppEvent::Tick | AppEvent::Quit) {
+            handle_help_overlay_event(&mut app, screen, &app_event);
+            app_event = AppEvent::Tick;
+        } else if help_requested(&app, &app_event) {
+            app.open_help_overlay();
+            app_event = AppEvent::Tick;
+        }
+        match app_event {
+            AppEvent::Tick | AppEvent::Resize | AppEvent::FocusGained | AppEvent::FocusLost => {}
+            AppEvent::Quit => app.quit(),
+            AppEvent::NextPane => {
+                if !(app.active_pane == Pane::LeftBottom && app.autocomplete_top_command()) {
+                    app.next_pane();
+                }
+            }
+            AppEvent::PrevPane => app.prev_pane(),
+            AppEvent::MoveUp | AppEvent::ScrollChatUp | AppEvent::MouseScrollUp => {
+                match app.active_pane {
+                    Pane::LeftBottom => app.scroll_chat_up(),
+                    Pane::Right => app.scroll_right_up(),
+                    Pane::LeftTop => app.scroll_up(),
+                }
+            }
+            AppEvent::MoveDown | AppEvent::ScrollChatDown | AppEvent::MouseScrollDown => {
+                match app.active_pane {
+                    Pane::LeftBottom => app.scroll_chat_down(ui::chat_max_scroll(screen, &app)),
+                    Pane::Right => app.scroll_right_down(ui::right_max_scroll(screen, &app)),
+                    Pane::LeftTop => {
+                        app.scroll_left_top_down(ui::left_top_max_scroll(screen, &app))
+                    }
+                }
+            }
+            AppEvent::ScrollRightUpGlobal => scroll_right_up_global(&mut app),
+            AppEvent::ScrollRightDownGlobal => {
+                let max_scroll = ui::right_max_scroll(screen, &app);
+                scroll_right_down_global(&mut app, max_scroll);
+            }
+            AppEvent::CursorLeft if app.active_pane == Pane::LeftBottom => app.move_cursor_left(),
+            AppEvent::CursorLeftWord if app.active_pane == Pane::LeftBottom => {
+                app.move_cursor_left_word()
+            }
+            AppEvent::CursorRight if app.active_pane == Pane::LeftBottom => app.move_cursor_right(),
+            AppEvent::CursorRightWord if app.active_pane == Pane::LeftBottom => {
+                app.move_cursor_right_word()
+            }
+            AppEvent::CursorLeft
+            | AppEvent::CursorLeftWord
+            | AppEvent::CursorRight
+            | AppEvent::CursorRightWord => {}
+            AppEvent::InputChar(c) => {
+                if app.active_pane == Pane::LeftBottom {
+                    app.input_char(c);
+                }
+            }
+            AppEvent::Paste(content) => {
+                if app.active_pane == Pane::LeftBottom {
+                    app.paste_input(&content);
+                }
+            }
+            AppEvent::Backspace => {
+                if app.active_pane == Pane::LeftBottom {
+                    app.backspace_input();
+                } else if app.active_pane == Pane::LeftTop && app.is_file_tree_mode() {
+                    app.file_tree_back();
+                }
+            }
+            AppEvent::InsertNewline => {}
+            AppEvent::Submit => {
+                if app.active_pane == Pane::LeftTop && app.is_file_tree_mode() {
+                    app.file_tree_activate();
+                } else if app.active_pane == Pane::LeftBottom
+                    && let Some(message) = app.submit_chat_message()
+                {
+                    app.begin_command_reply();
+                    submit_observer_message(&mut app, &message);
+                    app.end_command_reply();
+                    let max_scroll = ui::chat_max_scroll(screen, &app);
+                    app.set_chat_scroll(max_scroll);
+                }
+            }
+            AppEvent::MouseLeftClick(column, row) => {
+                handle_mouse_left_click(&mut app, screen, column, row);
+            }
+            AppEvent::ToggleChatView => toggle_chat_view(&mut app, screen),
+            AppEvent::MouseContextMenu(_, _) | AppEvent::QuickSwitch | AppEvent::ShowHelp => {}
+        }
+
+        if needs_draw && !events::has_pending_input()? {
+            terminal.draw(|frame| ui::render(frame, &app, theme))?;
+            needs_draw = false;
+        }
+    }
+
+    Ok(())
+}
+
+/// Switches the chat pane between Chat and System Log, showing the newest lines.
+fn toggle_chat_view(app: &mut App, screen: Rect) {
+    app.toggle_chat_view();
+    let max_scroll = ui::chat_max_scroll(screen, app);
+    app.set_chat_scroll(max_scroll);
+}
+
+/// Handles the view-only slash commands available while observing; everything else is refused.
+fn submit_observer_message(app: &mut App, message: &str) {
+    if App::is_quit_command(message) {
+        app.quit();
+    } else if App::is_files_command(message) {
+        toggle_file_tree_view(app);
+    } else if App::is_stats_command(message) {
+        toggle_stats_view(app);
+    } else if App::is_planner_mode_command(message) {
+        app.set_right_pane_mode(RightPaneMode::PlannerMarkdown);
+    } else if App::is_skip_plan_command(message) {
+        app.set_right_pane_mode(RightPaneMode::TaskList);
+    } else {
+        app.push_agent_message(app.system_text(Text::ObserverReadOnly, &[]));
+    }
+}
+
+fn load_language_settings(app: &mut App) {
+    match i18n::load_language_settings() {
+        Ok(settings) => app.set_language_settings(settings),
+        Err(err) => app.push_agent_message(
+            app.system_text(Text::LanguageSettingsLoadFailed, &[("err", &err)]),
+        ),
+    }
+}
+
+/// Cuts a gathered project-info.md that is over `[project_info] max_tokens` and rewrites it.
+fn fit_project_info_to_budget(app: &mut App, store: &SessionStore, markdown: String) -> String {
+    let max_tokens = app.project_info_scope().max_tokens;
+    let Some(trimmed) = app.project_info_scope().fit_to_budget(&markdown) else {
+        return markdown;
+    };
+    match store.write_project_info(&trimmed) {
+        Ok(()) => app.push_agent_message(
+            app.system_text(Text::ProjectInfoTrimmed, &[("max_tokens", &max_tokens)]),
+        ),
+        Err(err) => app.push_agent_message(
+            app.system_text(Text::ProjectInfoTrimWriteFailed, &[("err", &err)]),
+        ),
+    }
+    trimmed
+}
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     mut app: App,
     theme: &Theme,
-    cwd: PathBuf,
+    mut cwd: PathBuf,
     startup_message: Option<&str>,
-) -> io::Result<()> {
+) -> io::Result<Option<PathBuf>> {
+    // First, so the config warnings below are already in the user's language.
+    load_language_settings(&mut app);
     let orchestration_service = DefaultCoreOrchestrationService;
-    let prompt_service = DefaultUiPromptService;
 
     let mut session_store: Option<SessionStore> = None;
     let mut model_routing = match CodexAgentModelRouting::load_from_metaagent_config() {
         Ok(config) => config,
         Err(err) => {
-            app.push_agent_message(format!(
-                "System: Failed to load model profile config from ~/.agentbob/config.toml (legacy fallbacks: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}. Using defaults."
-            ));
+            app.push_agent_message(
+                app.system_text(Text::ModelProfileConfigLoadFailed, &[("err", &err)]),
+            );
             CodexAgentModelRouting::default()
         }
     };
+    if app.is_safe_mode() {
+        model_routing.set_safe_mode(Some(SafeModeSandbox::default()));
+        app.push_agent_message(app.system_text(Text::SafeModeOn, &[]));
+    }
     let mut selected_backend = model_routing.base_command_config().backend_kind();
+    app.set_runtime_backend(backend_label(selected_backend));
     let mut master_adapter =
         build_json_persistent_adapter(&model_routing, selected_backend, CodexAgentKind::Master);
     let mut master_report_adapter = build_json_persistent_adapter(
@@ -264,17 +622,23 @@ fn run_app(
         CodexAgentKind::TaskCheck,
         false,
     );
-    let test_runner_adapter = TestRunnerAdapter::new();
+    let mut test_runner_adapter = TestRunnerAdapter::new();
     let mut master_transcript: Vec<String> = Vec::new();
     let mut master_report_transcript: Vec<String> = Vec::new();
+    let mut advisor = AdvisorChat::default();
     let mut master_report_in_flight = false;
     let mut pending_master_report_prompts: VecDeque<String> = VecDeque::new();
+    let mut master_report_in_flight_prompt: Option<String> = None;
+    let mut master_report_queue_session: Option<PathBuf> = None;
     let mut project_info_transcript: Vec<String> = Vec::new();
     let mut pending_task_write_baseline: Option<TaskWriteBaseline> = None;
+    // Follow-up for a nudged worker; sent when its stopped run completes.
+    let mut pending_stall_nudge: Option<String> = None;
     let mut task_file_fix_retry_count: u8 = 0;
     let mut docs_attach_in_flight = false;
     let mut task_check_in_flight = false;
     let mut task_check_baseline: Option<String> = None;
+    let mut task_split_run: Option<TaskSplitRun> = None;
     let mut master_session_intro_needed = true;
     let mut master_report_session_intro_needed = true;
     let mut pending_master_message_after_project_info: Option<String> = None;
@@ -284,14 +648,156 @@ fn run_app(
     let tests_mode_enabled = match load_global_tests_mode_enabled() {
         Ok(enabled) => enabled,
         Err(err) => {
-            app.push_agent_message(format!(
-                "System: Failed to load global tests mode from config.toml; defaulting to ON: {err}"
-            ));
+            app.push_agent_message(app.system_text(Text::TestsModeLoadFailed, &[("err", &err)]));
             true
         }
     };
     app.set_tests_mode_enabled(tests_mode_enabled);
+    match load_global_final_audit_policy() {
+        Ok(policy) => app.set_final_audit_policy(policy),
+        Err(err) => app.push_agent_message(
+            app.system_text(Text::FinalAuditPolicyLoadFailed, &[("err", &err)]),
+        ),
+    }
+    match load_global_stall_after() {
+        Ok(stall_after) => app.set_stall_after(stall_after),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::StallAfterLoadFailed, &[("err", &err)]))
+        }
+    }
+    let task_split_limits = load_global_task_split_limits().unwrap_or_else(|err| {
+        app.push_agent_message(app.system_text(Text::TaskSplitLimitsLoadFailed, &[("err", &err)]));
+        TaskSplitLimits::default()
+    });
+    let mut worker_log_writer =
+        WorkerLogWriter::new(load_global_worker_log_policy().unwrap_or_else(|err| {
+            app.push_agent_message(
+                app.system_text(Text::WorkerLogSettingsLoadFailed, &[("err", &err)]),
+            );
+            WorkerLogPolicy::default()
+        }));
+    let mut backend_failover = BackendFailoverTracker::new(
+        load_global_backend_failover_threshold().unwrap_or_else(|err| {
+            app.push_agent_message(app.system_text(
+                Text::FailoverThresholdLoadFailed,
+                &[
+                    ("threshold", &BackendFailoverTracker::DEFAULT_THRESHOLD),
+                    ("err", &err),
+                ],
+            ));
+            BackendFailoverTracker::DEFAULT_THRESHOLD
+        }),
+    );
+    let mut worker_retry = TransientRetryQueue::default();
+    match load_global_rerun_failed_tests() {
+        Ok(enabled) => test_runner_adapter.set_rerun_failures(enabled),
+        Err(err) => app.push_agent_message(
+            app.system_text(Text::RerunFailedTestsLoadFailed, &[("err", &err)]),
+        ),
+    }
+    let docs_update_task = load_global_docs_update_task().unwrap_or_else(|err| {
+        app.push_agent_message(app.system_text(Text::DocsUpdateTaskLoadFailed, &[("err", &err)]));
+        false
+    });
+    match load_global_protected_paths() {
+        Ok(protected_paths) => app.set_protected_paths(protected_paths),
+        Err(err) => app
+            .push_agent_message(app.system_text(Text::ProtectedPathsLoadFailed, &[("err", &err)])),
+    }
+    match load_global_verify_gate() {
+        Ok(verify_gate) => app.set_verify_gate(verify_gate),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::VerifyLoadFailed, &[("err", &err)]))
+        }
+    }
+    app.set_workspace_ignore(WorkspaceIgnore::load(&cwd));
+    match CommandRegistry::load() {
+        Ok(registry) => app.set_command_registry(registry),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::CommandsLoadFailed, &[("err", &err)]))
+        }
+    }
+    // Discovery already runs each plugin program, so safe mode skips it.
+    match PluginRegistry::plugins_dir() {
+        _ if app.is_safe_mode() => {}
+        Ok(dir) => {
+            let plugins = PluginRegistry::discover(&dir);
+            for err in plugins.load_errors() {
+                app.push_agent_message(app.system_text(Text::PluginLoadFailed, &[("err", &err)]));
+            }
+            app.set_plugins(plugins);
+        }
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::PluginsDirUnavailable, &[("err", &err)]))
+        }
+    }
+    match load_global_output_filters() {
+        Ok(config) => app.set_output_filters(config),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::OutputFiltersLoadFailed, &[("err", &err)]))
+        }
+    }
+    match runtime_storage_dir() {
+        Ok(dir) => app.set_response_cache(ResponseCache::new(dir.join(RESPONSE_CACHE_DIR_NAME))),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::ResponseCacheOff, &[("err", &err)]))
+        }
+    }
+    match load_global_offer_recent_session() {
+        Ok(enabled) => app.set_offer_recent_session(enabled),
+        Err(err) => app.push_agent_message(
+            app.system_text(Text::OfferRecentSessionLoadFailed, &[("err", &err)]),
+        ),
+    }
+    match telemetry::load_telemetry_config() {
+        Ok(config) => {
+            if let Some(url) = telemetry::init(&config) {
+                app.push_agent_message(app.system_text(Text::ExportingTraces, &[("url", &url)]));
+            }
+        }
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::TelemetryLoadFailed, &[("err", &err)]))
+        }
+    }
+    match load_global_code_context_policy() {
+        Ok(policy) => app.set_code_context_policy(policy),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::CodeContextLoadFailed, &[("err", &err)]))
+        }
+    }
+    match load_global_project_info_scope() {
+        Ok(scope) => app.set_project_info_scope(scope),
+        Err(err) => app.push_agent_message(
+            app.system_text(Text::ProjectInfoScopeLoadFailed, &[("err", &err)]),
+        ),
+    }
+    match load_graphics_protocol() {
+        Ok(protocol) => app.set_graphics_protocol(protocol),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::GraphicsLoadFailed, &[("err", &err)]))
+        }
+    }
+    match backend_metrics::metrics_file_path() {
+        Ok(path) => backend_metrics::set_file(Some(path)),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::BackendMetricsOff, &[("err", &err)]))
+        }
+    }
+    match ui::load_narrow_width() {
+        Ok(width) => ui::set_narrow_width(width),
+        Err(err) => app.push_agent_message(app.system_text(
+            Text::NarrowWidthLoadFailed,
+            &[("width", &ui::DEFAULT_NARROW_SCREEN_WIDTH), ("err", &err)],
+        )),
+    }
+    app.set_workspace_root(cwd.clone());
     app.push_agent_message("Agent: What can I help you build?".to_string());
+    if app.has_batch_steps() {
+        app.push_agent_message(app.system_text(
+            Text::BatchScriptStarted,
+            &[("steps", &app.batch_step_total())],
+        ));
+    }
 
     if let Some(message) = startup_message
         && let Some(message) = app.submit_direct_message(message)
@@ -328,24 +834,173 @@ fn run_app(
     }
 
     let mut needs_draw = true;
+    // Session whose pinned models `model_routing` carries.
+    let mut models_session_dir: Option<PathBuf> = None;
+    let mut pane_render_cache = ui::PaneRenderCache::default();
+    let mut inline_images = InlineImages::default();
     let mut last_ui_tick = Instant::now();
+    let mut tasks_watcher = TasksFileWatcher::default();
+    let mut last_tasks_watch = Instant::now();
+    // tasks.json and session.lock wake the loop through this when edited from outside.
+    let mut session_file_watch = wakeup::FileWatch::default();
+    let mut tasks_watch_pending = true;
+    let mut presence = UserPresence::new(Instant::now());
+    let mut handled_event = true;
+    let mut session_event_recorder = SessionEventRecorder::default();
+    let mut ticket_status_sync = ticket_sync::TicketStatusSync::default();
+    let remote_mirror = session_storage::RemoteMirror::default();
+    let mut email_digest =
+        email_digest::EmailDigest::new(match email_digest::load_email_digest_config() {
+            Ok(config) => config,
+            Err(err) => {
+                app.push_agent_message(
+                    app.system_text(Text::EmailDigestLoadFailed, &[("err", &err)]),
+                );
+                email_digest::EmailDigestConfig::default()
+            }
+        });
+    let mut code_index_refresher = code_index::CodeIndexRefresher::default();
     let mut planner_manual_edit_dirty = false;
     let mut planner_last_keystroke_at: Option<Instant> = None;
+    let mut detached = false;
+    shutdown::install_sigterm_handler();
     while app.running {
-        let input_pending = events::has_pending_input()?;
+        app.end_command_reply();
+        if shutdown::take_sigterm()
+            && let Some(message) = app.request_shutdown(None)
+        {
+            app.push_agent_message(message);
+            needs_draw = true;
+        }
+        match app.shutdown_mode() {
+            Some(ShutdownMode::Abort) => {
+                for adapter in worker_agent_adapters.values() {
+                    adapter.terminate();
+                }
+                test_runner_adapter.terminate();
+                app.quit();
+                break;
+            }
+            Some(ShutdownMode::Detach) if !detached => {
+                detach_from_terminal(terminal, session_store.as_ref())?;
+                detached = true;
+            }
+            _ => {}
+        }
+        if app.shutdown_mode().is_some() && !app.has_active_worker_job() {
+            app.quit();
+            break;
+        }
+        let input_pending = !detached && events::has_pending_input()?;
+        let away = presence.is_away(Instant::now());
+        let (ui_tick_interval, tasks_watch_interval) = if away {
+            (AWAY_UI_TICK_INTERVAL, AWAY_TASKS_WATCH_INTERVAL)
+        } else {
+            (UI_TICK_INTERVAL, TASKS_WATCH_INTERVAL)
+        };
         let mut chat_updated = false;
+        // Agent output lines mark only the pane they land in, unlike `chat_updated`.
+        let mut output_streamed = false;
+        let mut usage = TokenUsage::default();
+        for adapter in [
+            &master_adapter,
+            &master_report_adapter,
+            &project_info_adapter,
+            &docs_attach_adapter,
+            &task_check_adapter,
+        ] {
+            usage.add(adapter.take_usage());
+        }
+        needs_draw |= app.record_token_usage(usage);
+        let mut worker_usage = TokenUsage::default();
+        for adapter in worker_agent_adapters.values() {
+            worker_usage.add(adapter.take_usage());
+        }
+        needs_draw |= app.record_worker_token_usage(worker_usage);
+
+        let session_dir = session_store
+            .as_ref()
+            .map(|store| store.session_dir().to_path_buf());
+        if session_dir != master_report_queue_session {
+            if let Some(previous) = master_report_queue_session.as_deref() {
+                remote_mirror.push_in_background(previous, app.locale());
+            }
+            master_report_queue_session = session_dir;
+            let summaries = match session_store
+                .as_ref()
+                .map(SessionStore::read_task_summaries)
+            {
+                Some(Ok(summaries)) => summaries,
+                Some(Err(err)) => {
+                    app.push_agent_message(
+                        app.system_text(Text::TaskSummariesReadFailed, &[("err", &err)]),
+                    );
+                    Vec::new()
+                }
+                None => Vec::new(),
+            };
+            app.load_task_summaries(summaries);
+            if !master_report_in_flight {
+                master_report_in_flight_prompt = None;
+            }
+            if let Some(active_session) = session_store.as_ref() {
+                match active_session.read_master_report_queue() {
+                    Ok(queue) => {
+                        let restored =
+                            restore_master_report_queue(queue, &mut pending_master_report_prompts);
+                        if restored > 0 {
+                            app.push_agent_message(app.system_text(
+                                Text::DeliveringLeftoverReports,
+                                &[("restored", &restored)],
+                            ));
+                            if !master_report_in_flight
+                                && let Some(prompt) = pending_master_report_prompts.pop_front()
+                            {
+                                let prompt = with_master_report_session_intro(
+                                    prompt,
+                                    active_session,
+                                    project_info_text.as_deref(),
+                                    &mut master_report_session_intro_needed,
+                                );
+                                master_report_in_flight = true;
+                                master_report_transcript.clear();
+                                master_report_in_flight_prompt = Some(prompt.clone());
+                                master_report_adapter.send_prompt(prompt);
+                            }
+                            persist_master_report_queue(
+                                &mut app,
+                                Some(active_session),
+                                master_report_in_flight_prompt.as_deref(),
+                                &pending_master_report_prompts,
+                            );
+                            chat_updated = true;
+                        }
+                    }
+                    Err(err) => app.push_agent_message(
+                        app.system_text(Text::ReportQueueReadFailed, &[("err", &err)]),
+                    ),
+                }
+            }
+        }
 
         if !input_pending {
             for event in master_adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP) {
+                record_session_agent_event(
+                    &mut app,
+                    &mut session_event_recorder,
+                    session_store.as_ref(),
+                    "master",
+                    &event,
+                );
                 match event {
                     AgentEvent::Output(line) => {
                         master_transcript.push(line.clone());
                         app.push_agent_message(format!("Agent: {line}"));
-                        chat_updated = true;
+                        output_streamed = true;
                     }
                     AgentEvent::System(line) => {
                         app.push_agent_message(format!("System: {line}"));
-                        chat_updated = true;
+                        output_streamed = true;
                     }
                     AgentEvent::Completed { .. } => {
                         let Some(active_session) = session_store.as_ref() else {
@@ -355,15 +1010,26 @@ fn run_app(
                             continue;
                         };
                         app.set_master_in_progress(false);
-                        let _transcript = master_transcript.join("\n");
+                        let transcript = master_transcript.join("\n");
                         master_transcript.clear();
+                        record_project_memory(&mut app, active_session, &transcript);
+                        open_master_questions(&mut app, &transcript);
                         let baseline_tasks_text = pending_task_write_baseline
                             .as_ref()
                             .map(|b| b.tasks_json.clone());
                         let mut tasks_refresh_ok = false;
                         let mut requested_task_file_retry = false;
+                        detect_tasks_edit_conflict(&mut app, active_session);
+                        if app.is_safe_mode() {
+                            restore_tasks_after_safe_mode_master(
+                                &mut app,
+                                active_session,
+                                baseline_tasks_text.as_deref(),
+                            );
+                        }
+                        let master_tasks_file = master_edited_tasks_file(&app, active_session);
                         if should_process_master_task_file_updates(app.is_execution_busy()) {
-                            match active_session.read_tasks() {
+                            match read_master_tasks(&app, active_session) {
                                 Ok(mut tasks) => {
                                     let docs_sanitized = sanitize_master_docs_fields(
                                         &mut tasks,
@@ -372,46 +1038,70 @@ fn run_app(
                                             .map(|b| b.tasks_json.as_str()),
                                     );
                                     if docs_sanitized {
-                                        match serde_json::to_string_pretty(&tasks) {
-                                        Ok(text) => {
-                                            if let Err(err) =
-                                                std::fs::write(active_session.tasks_file(), text)
-                                            {
-                                                app.push_agent_message(format!(
-                                                    "System: Failed to enforce docs policy in tasks.json: {err}"
-                                                ));
-                                            } else {
-                                                app.push_agent_message(
-                                                    "System: Removed master-written docs entries; use /attach-docs to populate docs."
-                                                        .to_string(),
-                                                );
-                                            }
+                                        match write_master_tasks(&app, active_session, &tasks) {
+                                            Ok(()) => app.push_agent_message(
+                                                app.system_text(Text::RemovedMasterDocs, &[]),
+                                            ),
+                                            Err(err) => app.push_agent_message(app.system_text(
+                                                Text::DocsPolicyEnforceFailed,
+                                                &[("err", &err)],
+                                            )),
                                         }
-                                        Err(err) => app.push_agent_message(format!(
-                                            "System: Failed to serialize tasks.json after docs sanitization: {err}"
-                                        )),
                                     }
+                                    let plan_was_empty = app.planner_tasks_for_file().is_empty();
+                                    let mut appended = Vec::new();
+                                    if append_docs_update_task_if_needed(
+                                        docs_update_task,
+                                        plan_was_empty,
+                                        &mut tasks,
+                                    ) {
+                                        appended.push("README/CHANGELOG update task (docs_update_task = true)");
+                                    }
+                                    if append_auto_final_audit_if_needed(
+                                        app.final_audit_policy(),
+                                        plan_was_empty,
+                                        &mut tasks,
+                                    ) {
+                                        appended.push("final audit task (final_audit = \"auto\")");
+                                    }
+                                    if !appended.is_empty() {
+                                        match write_master_tasks(&app, active_session, &tasks) {
+                                            Ok(()) => {
+                                                for task in appended {
+                                                    app.push_agent_message(app.system_text(
+                                                        Text::AppendedTask,
+                                                        &[("task", &task)],
+                                                    ));
+                                                }
+                                            }
+                                            Err(err) => app.push_agent_message(app.system_text(
+                                                Text::AutomaticTasksWriteFailed,
+                                                &[("err", &err)],
+                                            )),
+                                        }
                                     }
-                                    match app.sync_planner_tasks_from_file(tasks) {
+                                    match load_master_tasks(&mut app, tasks) {
                                         Ok(()) => {
                                             tasks_refresh_ok = true;
                                             task_file_fix_retry_count = 0;
                                         }
                                         Err(err) => {
-                                            app.push_agent_message(format!(
-                                            "System: Failed to refresh task tree from tasks.json: {err}"
-                                        ));
+                                            app.push_agent_message(app.system_text(
+                                                Text::TaskTreeRefreshFailed,
+                                                &[("err", &err)],
+                                            ));
                                         }
                                     }
                                 }
                                 Err(err) => {
-                                    app.push_agent_message(format!(
-                                    "System: Failed to read tasks.json after master update: {err}"
-                                ));
+                                    app.push_agent_message(app.system_text(
+                                        Text::TasksReadAfterMasterFailed,
+                                        &[("err", &err)],
+                                    ));
                                 }
                             }
                             if let Ok(markdown) = active_session.read_planner_markdown() {
-                                app.set_planner_markdown(markdown);
+                                app.reconcile_planner_markdown(markdown);
                             }
 
                             if !tasks_refresh_ok {
@@ -421,9 +1111,7 @@ fn run_app(
                                     requested_task_file_retry = true;
                                     master_adapter.send_prompt(
                                     subagents::build_session_intro_if_needed(
-                                        "tasks.json failed to parse/validate. Fix tasks.json immediately and retry. \
-                                     Ensure id and parent_id are valid values and hierarchy is valid. \
-                                     Do not ask the user to start execution yet.",
+                                        &tasks_file_fix_prompt(&app),
                                         active_session
                                             .session_dir()
                                             .display()
@@ -431,19 +1119,22 @@ fn run_app(
                                             .as_str(),
                                         &active_session.session_meta_file().display().to_string(),
                                         project_info_text.as_deref(),
+                                        project_memory::session_memory_section(active_session).as_deref(),
+                                        active_session
+                                            .agent_persona(CodexAgentKind::Master)
+                                            .as_deref(),
                                         &mut master_session_intro_needed,
                                     ),
                                 );
                                     app.set_master_in_progress(true);
-                                    app.push_agent_message(format!(
-                                    "System: Requested tasks.json correction from master (attempt {}).",
-                                    task_file_fix_retry_count
-                                ));
+                                    app.push_agent_message(app.system_text(
+                                        Text::TasksCorrectionRequested,
+                                        &[("attempt", &task_file_fix_retry_count)],
+                                    ));
                                 } else {
                                     app.push_agent_message(
-                                    "System: tasks.json correction retries exceeded. Waiting for next user input."
-                                        .to_string(),
-                                );
+                                        app.system_text(Text::TasksCorrectionRetriesExceeded, &[]),
+                                    );
                                     task_file_fix_retry_count = 0;
                                 }
                             }
@@ -451,23 +1142,35 @@ fn run_app(
                         let changed_tasks = if tasks_refresh_ok {
                             tasks_changed_since_baseline(
                                 baseline_tasks_text.as_deref(),
-                                std::fs::read_to_string(active_session.tasks_file())
-                                    .ok()
-                                    .as_deref(),
+                                std::fs::read_to_string(&master_tasks_file).ok().as_deref(),
                             )
                         } else {
                             false
                         };
-                        if should_clear_task_write_baseline(
-                            tasks_refresh_ok,
-                            requested_task_file_retry,
-                        ) {
-                            pending_task_write_baseline = None;
+                        // In safe mode the change is recorded when it is accepted.
+                        if changed_tasks && !app.is_safe_mode() {
+                            record_tasks_change(
+                                &mut app,
+                                active_session,
+                                TasksChangeAgent::Master,
+                                baseline_tasks_text.as_deref(),
+                            );
+                        }
+                        if changed_tasks && let Some(baseline) = baseline_tasks_text.as_deref() {
+                            offer_task_changes(&mut app, active_session, baseline);
+                        }
+                        if should_clear_task_write_baseline(
+                            tasks_refresh_ok,
+                            requested_task_file_retry,
+                        ) {
+                            pending_task_write_baseline = None;
                         }
 
                         if tasks_refresh_ok {
+                            // In safe mode the changes are staged, and a task check could
+                            // rewrite tasks.json behind the review.
                             if should_start_task_check(
-                                changed_tasks,
+                                changed_tasks && !app.is_safe_mode(),
                                 task_check_in_flight,
                                 docs_attach_in_flight,
                             ) {
@@ -478,29 +1181,42 @@ fn run_app(
                                 app.push_subagent_output(
                                     "TaskCheckSystem: Checking updated tasks.json".to_string(),
                                 );
-                                task_check_adapter.send_prompt(subagents::build_task_check_prompt(
+                                let prompt = subagents::build_task_check_prompt(
                                     &active_session.tasks_file().display().to_string(),
                                     &active_session.project_info_file().display().to_string(),
                                     &active_session.session_meta_file().display().to_string(),
-                                ));
+                                );
+                                send_cacheable_tasks_prompt(
+                                    &mut app,
+                                    &task_check_adapter,
+                                    CachedCall::TaskCheck,
+                                    prompt,
+                                    active_session,
+                                );
                             }
                             match orchestration_service.start_next_worker_job_if_any(
-                            &mut app,
-                            &mut worker_agent_adapters,
-                            &mut active_worker_context_key,
-                            &test_runner_adapter,
-                            active_session,
-                            &model_routing,
-                        ) {
-                            Ok(Some(job)) => app.push_agent_message(format!(
-                                "System: Starting {:?} for task #{}.",
-                                job.role, job.top_task_id
-                            )),
-                            Ok(None) => {}
-                            Err(err) => app.push_agent_message(format!(
-                                "System: Failed to persist runtime task status to tasks.json: {err}"
-                            )),
-                        }
+                                &mut app,
+                                &mut worker_agent_adapters,
+                                &mut active_worker_context_key,
+                                &test_runner_adapter,
+                                active_session,
+                                &model_routing,
+                            ) {
+                                Ok(Some(job)) => app.push_agent_message(app.system_text(
+                                    Text::StartingJob,
+                                    &[
+                                        ("role", &format!("{:?}", job.role)),
+                                        ("task", &job.top_task_id),
+                                    ],
+                                )),
+                                Ok(None) => {}
+                                Err(err) => {
+                                    app.push_agent_message(app.system_text(
+                                        Text::TaskStatusPersistFailed,
+                                        &[("err", &err)],
+                                    ))
+                                }
+                            }
                         }
                         chat_updated = true;
                     }
@@ -508,6 +1224,17 @@ fn run_app(
             }
         }
 
+        if let Some(key) = worker_retry.take_due(Instant::now())
+            && active_worker_context_key.as_ref() == Some(&key)
+            && app.active_worker_job().is_some()
+            && let Some(adapter) = worker_agent_adapters.get(&key)
+            && adapter.resend_last_prompt()
+        {
+            app.reset_stall_timer(Instant::now());
+            app.push_agent_message(app.system_text(Text::RetryingWorkerJob, &[]));
+            chat_updated = true;
+        }
+
         if !input_pending {
             let worker_events = active_worker_context_key
                 .as_ref()
@@ -515,16 +1242,41 @@ fn run_app(
                 .map(|adapter| adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP))
                 .unwrap_or_default();
             for event in worker_events {
+                if !matches!(event, AgentEvent::Completed { .. }) {
+                    record_worker_log_event(
+                        &mut app,
+                        &mut worker_log_writer,
+                        session_store.as_ref(),
+                        &event,
+                    );
+                    record_session_agent_event(
+                        &mut app,
+                        &mut session_event_recorder,
+                        session_store.as_ref(),
+                        "worker",
+                        &event,
+                    );
+                }
                 match event {
                     AgentEvent::Output(line) => {
                         app.on_worker_output(line);
-                        chat_updated = true;
+                        output_streamed = true;
                     }
                     AgentEvent::System(line) => {
                         app.on_worker_system_output(line);
-                        chat_updated = true;
+                        output_streamed = true;
                     }
                     AgentEvent::Completed { success, code } => {
+                        if let Some(prompt) = pending_stall_nudge.take()
+                            && let Some(adapter) = active_worker_context_key
+                                .as_ref()
+                                .and_then(|key| worker_agent_adapters.get(key))
+                        {
+                            adapter.send_prompt(prompt);
+                            app.reset_stall_timer(Instant::now());
+                            chat_updated = true;
+                            continue;
+                        }
                         let Some(active_session) = session_store.as_ref() else {
                             active_worker_context_key = None;
                             let _ = app.on_worker_completed(success, code);
@@ -535,6 +1287,19 @@ fn run_app(
                             && let Some(adapter) = worker_agent_adapters.get(key)
                         {
                             for tail_event in drain_post_completion_worker_events(adapter) {
+                                record_session_agent_event(
+                                    &mut app,
+                                    &mut session_event_recorder,
+                                    session_store.as_ref(),
+                                    "worker",
+                                    &tail_event,
+                                );
+                                record_worker_log_event(
+                                    &mut app,
+                                    &mut worker_log_writer,
+                                    session_store.as_ref(),
+                                    &tail_event,
+                                );
                                 match tail_event {
                                     AgentEvent::Output(line) => {
                                         app.on_worker_output(line);
@@ -546,7 +1311,63 @@ fn run_app(
                                 }
                             }
                         }
+                        record_worker_log_event(
+                            &mut app,
+                            &mut worker_log_writer,
+                            Some(active_session),
+                            &AgentEvent::Completed { success, code },
+                        );
+                        record_session_agent_event(
+                            &mut app,
+                            &mut session_event_recorder,
+                            Some(active_session),
+                            "worker",
+                            &AgentEvent::Completed { success, code },
+                        );
+                        let transient_failure = app.take_active_job_transient_failure();
+                        if !success
+                            && let Some(reason) = transient_failure
+                            && let Some(key) = active_worker_context_key.clone()
+                        {
+                            if let Some((retry, delay)) = worker_retry.schedule(key, Instant::now())
+                            {
+                                app.push_agent_message(app.system_text(
+                                    Text::TransientRetryScheduled,
+                                    &[
+                                        ("reason", &reason),
+                                        ("delay", &delay.as_secs()),
+                                        ("retry", &retry),
+                                        ("max", &TransientRetryQueue::MAX_RETRIES),
+                                    ],
+                                ));
+                                chat_updated = true;
+                                continue;
+                            }
+                            app.push_agent_message(app.system_text(
+                                Text::TransientRetriesExhausted,
+                                &[
+                                    ("max", &TransientRetryQueue::MAX_RETRIES),
+                                    ("reason", &reason),
+                                ],
+                            ));
+                        }
+                        worker_retry.reset();
                         active_worker_context_key = None;
+                        if let Some(failures) = backend_failover.record_worker_result(success) {
+                            fail_over_backend(
+                                &mut app,
+                                failures,
+                                &mut selected_backend,
+                                &mut model_routing,
+                                &mut active_worker_context_key,
+                                &mut worker_agent_adapters,
+                                &mut master_adapter,
+                                &mut master_report_adapter,
+                                &mut project_info_adapter,
+                                &mut docs_attach_adapter,
+                                &mut task_check_adapter,
+                            );
+                        }
                         let outcome = orchestration_service.complete_worker_cycle_and_start_next(
                             &mut app,
                             success,
@@ -562,23 +1383,38 @@ fn run_app(
                         for warning in outcome.warnings {
                             app.push_agent_message(format!("System: {warning}"));
                         }
+                        let mut report_queue_changed = false;
                         for prompt in [outcome.failure_report_prompt, outcome.context_report_prompt]
                             .into_iter()
                             .flatten()
+                            .chain(outcome.task_summary_prompts)
                         {
+                            report_queue_changed = true;
                             if let Some(prompt_to_send) = enqueue_or_dispatch_master_report_prompt(
                                 prompt,
                                 &mut master_report_in_flight,
                                 &mut pending_master_report_prompts,
                             ) {
                                 master_report_transcript.clear();
+                                master_report_in_flight_prompt = Some(prompt_to_send.clone());
                                 master_report_adapter.send_prompt(prompt_to_send);
                             }
                         }
+                        if report_queue_changed {
+                            persist_master_report_queue(
+                                &mut app,
+                                Some(active_session),
+                                master_report_in_flight_prompt.as_deref(),
+                                &pending_master_report_prompts,
+                            );
+                        }
                         if let Some(job) = outcome.started_job {
-                            app.push_agent_message(format!(
-                                "System: Starting {:?} for task #{}.",
-                                job.role, job.top_task_id
+                            app.push_agent_message(app.system_text(
+                                Text::StartingJob,
+                                &[
+                                    ("role", &format!("{:?}", job.role)),
+                                    ("task", &job.top_task_id),
+                                ],
                             ));
                         }
                         chat_updated = true;
@@ -589,16 +1425,35 @@ fn run_app(
 
         if !input_pending {
             for event in test_runner_adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP) {
+                record_session_agent_event(
+                    &mut app,
+                    &mut session_event_recorder,
+                    session_store.as_ref(),
+                    "test_runner",
+                    &event,
+                );
+                record_worker_log_event(
+                    &mut app,
+                    &mut worker_log_writer,
+                    session_store.as_ref(),
+                    &event,
+                );
                 match event {
                     AgentEvent::Output(line) => {
                         app.on_worker_output(line);
-                        chat_updated = true;
+                        output_streamed = true;
                     }
                     AgentEvent::System(line) => {
                         app.on_worker_system_output(line);
-                        chat_updated = true;
+                        output_streamed = true;
                     }
                     AgentEvent::Completed { success, code } => {
+                        if test_runner_adapter.take_last_run_flaky() {
+                            app.mark_active_test_run_flaky();
+                        }
+                        if let Some(report) = test_runner_adapter.take_last_report() {
+                            app.record_active_test_results(report);
+                        }
                         let Some(active_session) = session_store.as_ref() else {
                             let _ = app.on_worker_completed(success, code);
                             chat_updated = true;
@@ -619,23 +1474,38 @@ fn run_app(
                         for warning in outcome.warnings {
                             app.push_agent_message(format!("System: {warning}"));
                         }
+                        let mut report_queue_changed = false;
                         for prompt in [outcome.failure_report_prompt, outcome.context_report_prompt]
                             .into_iter()
                             .flatten()
+                            .chain(outcome.task_summary_prompts)
                         {
+                            report_queue_changed = true;
                             if let Some(prompt_to_send) = enqueue_or_dispatch_master_report_prompt(
                                 prompt,
                                 &mut master_report_in_flight,
                                 &mut pending_master_report_prompts,
                             ) {
                                 master_report_transcript.clear();
+                                master_report_in_flight_prompt = Some(prompt_to_send.clone());
                                 master_report_adapter.send_prompt(prompt_to_send);
                             }
                         }
+                        if report_queue_changed {
+                            persist_master_report_queue(
+                                &mut app,
+                                Some(active_session),
+                                master_report_in_flight_prompt.as_deref(),
+                                &pending_master_report_prompts,
+                            );
+                        }
                         if let Some(job) = outcome.started_job {
-                            app.push_agent_message(format!(
-                                "System: Starting {:?} for task #{}.",
-                                job.role, job.top_task_id
+                            app.push_agent_message(app.system_text(
+                                Text::StartingJob,
+                                &[
+                                    ("role", &format!("{:?}", job.role)),
+                                    ("task", &job.top_task_id),
+                                ],
                             ));
                         }
                         chat_updated = true;
@@ -646,45 +1516,128 @@ fn run_app(
 
         if !input_pending {
             for event in master_report_adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP) {
+                record_session_agent_event(
+                    &mut app,
+                    &mut session_event_recorder,
+                    session_store.as_ref(),
+                    "master_report",
+                    &event,
+                );
                 match event {
                     AgentEvent::Output(line) => {
                         master_report_transcript.push(line);
                     }
                     AgentEvent::System(_line) => {}
                     AgentEvent::Completed { .. } => {
-                        let summary = master_report_transcript
-                            .iter()
-                            .rev()
-                            .find(|line| !line.trim().is_empty())
-                            .map(|line| format_internal_master_update(line))
-                            .unwrap_or_else(|| {
-                                "A sub-agent completed work.".to_string()
-                            });
-                        app.push_agent_message(format!("Agent: {summary}"));
+                        if let Some((task_id, task_title)) = master_report_in_flight_prompt
+                            .as_deref()
+                            .and_then(subagents::task_summary_target)
+                        {
+                            record_task_summary(
+                                &mut app,
+                                session_store.as_ref(),
+                                task_id,
+                                task_title,
+                                &master_report_transcript,
+                            );
+                        } else {
+                            let summary = master_report_transcript
+                                .iter()
+                                .rev()
+                                .find(|line| !line.trim().is_empty())
+                                .map(|line| format_internal_master_update(line))
+                                .unwrap_or_else(|| "A sub-agent completed work.".to_string());
+                            email_digest.note_summary(&summary);
+                            app.push_agent_message(format!("Agent: {summary}"));
+                        }
                         master_report_transcript.clear();
-                        if let Some(prompt_to_send) = complete_and_next_master_report_prompt(
+                        master_report_in_flight_prompt = complete_and_next_master_report_prompt(
                             &mut master_report_in_flight,
                             &mut pending_master_report_prompts,
-                        ) {
+                        );
+                        if let Some(prompt_to_send) = master_report_in_flight_prompt.clone() {
                             master_report_transcript.clear();
                             master_report_adapter.send_prompt(prompt_to_send);
                         }
+                        persist_master_report_queue(
+                            &mut app,
+                            session_store.as_ref(),
+                            master_report_in_flight_prompt.as_deref(),
+                            &pending_master_report_prompts,
+                        );
                         chat_updated = true;
                     }
                 }
             }
         }
 
+        if !input_pending {
+            for event in advisor.drain_events(MAX_ADAPTER_EVENTS_PER_LOOP) {
+                record_session_agent_event(
+                    &mut app,
+                    &mut session_event_recorder,
+                    session_store.as_ref(),
+                    "advisor",
+                    &event,
+                );
+                match event {
+                    AgentEvent::Output(line) => {
+                        app.push_agent_message(format!("Agent: [advisor] {line}"));
+                    }
+                    AgentEvent::System(line) => {
+                        app.push_agent_message(format!("System: {line}"));
+                    }
+                    AgentEvent::Completed { .. } => {
+                        if let Some(snapshot) = advisor.finish()
+                            && let Some(active_session) = session_store.as_ref()
+                        {
+                            match advisor::reject_tasks_file_edits(&app, active_session, &snapshot)
+                            {
+                                Ok(true) => app.push_agent_message(
+                                    app.system_text(Text::AdvisorEditRejected, &[]),
+                                ),
+                                Ok(false) => {}
+                                Err(err) => app.push_agent_message(
+                                    app.system_text(Text::AdvisorEditUndoFailed, &[("err", &err)]),
+                                ),
+                            }
+                        }
+                    }
+                }
+                chat_updated = true;
+            }
+        }
+
         if !input_pending {
             for event in project_info_adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP) {
+                record_session_agent_event(
+                    &mut app,
+                    &mut session_event_recorder,
+                    session_store.as_ref(),
+                    "project_info",
+                    &event,
+                );
                 match event {
                     AgentEvent::Output(line) => {
                         project_info_transcript.push(line.clone());
-                        app.push_subagent_output(format!("ProjectInfo: {line}"));
+                        app.push_filtered_subagent_output(
+                            "project_info",
+                            &line,
+                            format!("ProjectInfo: {line}"),
+                        );
                         chat_updated = true;
                     }
                     AgentEvent::System(line) => {
-                        app.push_subagent_output(format!("ProjectInfoSystem: {line}"));
+                        app.push_filtered_subagent_output(
+                            "project_info",
+                            &line,
+                            format!("ProjectInfoSystem: {line}"),
+                        );
+                        chat_updated = true;
+                    }
+                    AgentEvent::Completed { .. } if !project_info_in_flight => {
+                        // Cancelled with /cancel context; the flags are already cleared.
+                        project_info_transcript.clear();
                         chat_updated = true;
                     }
                     AgentEvent::Completed { success, code } => {
@@ -704,38 +1657,45 @@ fn run_app(
                                         }
                                         Ok(_) => None,
                                         Err(err) => {
-                                            app.push_agent_message(format!(
-                                            "System: Project info run succeeded but reading project-info.md failed: {err}"
-                                        ));
+                                            app.push_agent_message(app.system_text(
+                                                Text::ProjectInfoReadFailed,
+                                                &[("err", &err)],
+                                            ));
                                             None
                                         }
                                     };
                                     if let Some(markdown) = gathered {
-                                        project_info_text = Some(markdown);
+                                        project_info_text = Some(fit_project_info_to_budget(
+                                            &mut app,
+                                            active_session,
+                                            markdown,
+                                        ));
                                         app.push_agent_message(
-                                        "System: Project context gathered and attached for this session."
-                                            .to_string(),
-                                    );
+                                            app.system_text(Text::ProjectContextAttached, &[]),
+                                        );
                                     } else if !project_info_transcript.is_empty() {
                                         let markdown = project_info_transcript.join("\n");
+                                        let markdown = app
+                                            .project_info_scope()
+                                            .fit_to_budget(&markdown)
+                                            .unwrap_or(markdown);
                                         if let Err(err) =
                                             active_session.write_project_info(&markdown)
                                         {
-                                            app.push_agent_message(format!(
-                                            "System: Failed to persist project-info.md fallback output: {err}"
-                                        ));
+                                            app.push_agent_message(app.system_text(
+                                                Text::ProjectInfoFallbackWriteFailed,
+                                                &[("err", &err)],
+                                            ));
                                         } else {
                                             project_info_text = Some(markdown);
                                             app.push_agent_message(
-                                            "System: Project context gathered and attached for this session."
-                                                .to_string(),
-                                        );
+                                                app.system_text(Text::ProjectContextAttached, &[]),
+                                            );
                                         }
                                     } else {
                                         app.push_agent_message(
-                                        "System: Project context run returned no content; proceeding without attachment."
-                                            .to_string(),
-                                    );
+                                            app.system_text(Text::ProjectContextEmpty, &[]),
+                                        );
                                     }
 
                                     if let Some(original_prompt) =
@@ -760,27 +1720,40 @@ fn run_app(
                                         continue;
                                     }
                                 } else {
-                                    app.push_agent_message(format!(
-                                    "System: Project context gather exited with code {code}; proceeding without attachment."
-                                ));
+                                    app.push_agent_message(app.system_text(
+                                        Text::ProjectContextExited,
+                                        &[("code", &code)],
+                                    ));
                                 }
                             }
                             Some(ProjectInfoStage::WritingSessionMeta) => {
                                 if success {
+                                    if let Err(err) = active_session
+                                        .record_session_models(&model_routing.configured_models())
+                                    {
+                                        app.push_agent_message(app.system_text(
+                                            Text::SessionModelsRecordFailed,
+                                            &[("err", &err)],
+                                        ));
+                                    }
                                     if let Ok(meta) = active_session.read_session_meta() {
-                                        app.push_agent_message(format!(
-                                            "System: Session metadata saved: \"{}\" ({})",
-                                            meta.title, meta.created_at
+                                        app.push_agent_message(app.system_text(
+                                            Text::SessionMetadataSaved,
+                                            &[
+                                                ("title", &meta.title),
+                                                ("created_at", &meta.created_at),
+                                            ],
                                         ));
                                     } else {
                                         app.push_agent_message(
-                                            "System: Session metadata write completed.".to_string(),
+                                            app.system_text(Text::SessionMetadataWritten, &[]),
                                         );
                                     }
                                 } else {
-                                    app.push_agent_message(format!(
-                                    "System: Session metadata write exited with code {code}; continuing."
-                                ));
+                                    app.push_agent_message(app.system_text(
+                                        Text::SessionMetadataExited,
+                                        &[("code", &code)],
+                                    ));
                                 }
                             }
                             None => {}
@@ -792,17 +1765,15 @@ fn run_app(
                         if let Some(pending_message) =
                             pending_master_message_after_project_info.take()
                         {
-                            let with_intro = prompt_service.build_master_prompt_for_message(
-                                &app,
-                                &pending_message,
+                            send_held_master_message(
+                                &mut app,
+                                &master_adapter,
                                 active_session,
+                                &pending_message,
                                 project_info_text.as_deref(),
                                 &mut master_session_intro_needed,
+                                &mut pending_task_write_baseline,
                             );
-                            master_adapter.send_prompt(with_intro);
-                            app.set_master_in_progress(true);
-                            pending_task_write_baseline =
-                                orchestration_service.capture_tasks_baseline(active_session);
                         }
 
                         project_info_transcript.clear();
@@ -814,44 +1785,109 @@ fn run_app(
 
         if !input_pending {
             for event in docs_attach_adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP) {
+                record_session_agent_event(
+                    &mut app,
+                    &mut session_event_recorder,
+                    session_store.as_ref(),
+                    "docs_attach",
+                    &event,
+                );
                 match event {
                     AgentEvent::Output(line) => {
-                        app.push_subagent_output(format!("Docs: {line}"));
+                        app.push_filtered_subagent_output("docs", &line, format!("Docs: {line}"));
                         chat_updated = true;
                     }
                     AgentEvent::System(line) => {
-                        app.push_subagent_output(format!("DocsSystem: {line}"));
+                        app.push_filtered_subagent_output(
+                            "docs",
+                            &line,
+                            format!("DocsSystem: {line}"),
+                        );
+                        chat_updated = true;
+                    }
+                    AgentEvent::Completed { .. } if !docs_attach_in_flight => {
+                        // Cancelled with /cancel docs; the flags are already cleared.
                         chat_updated = true;
                     }
                     AgentEvent::Completed { success, code } => {
                         docs_attach_in_flight = false;
+                        let docs_attach_baseline = app.take_docs_attach_baseline();
                         app.set_docs_attach_in_progress(false);
                         let Some(active_session) = session_store.as_ref() else {
                             chat_updated = true;
                             continue;
                         };
+                        let tasks_text = std::fs::read_to_string(active_session.tasks_file()).ok();
+                        store_cached_response(
+                            &mut app,
+                            CachedCall::DocsAttach,
+                            tasks_text.as_deref().filter(|_| success),
+                        );
                         match active_session.read_tasks() {
-                            Ok(tasks) => match app.sync_planner_tasks_from_file(tasks) {
-                                Ok(_) => {
-                                    if success {
-                                        app.push_agent_message(
-                                        "System: Documentation has been attached to planner tasks."
-                                            .to_string(),
-                                    );
-                                    } else {
-                                        app.push_agent_message(format!(
-                                        "System: Documentation attach run exited with code {code}."
-                                    ));
+                            Ok(mut tasks) => {
+                                let snapshot_note = match doc_snapshots::sanitize_snapshots(
+                                    active_session,
+                                    &mut tasks,
+                                ) {
+                                    Ok(sweep) if sweep.dropped > 0 => {
+                                        match active_session.write_tasks(&tasks) {
+                                            Ok(()) => i18n::fill(
+                                                app.locale(),
+                                                Text::DocSnapshotsDropped,
+                                                &[
+                                                    ("kept", &sweep.kept),
+                                                    ("dropped", &sweep.dropped),
+                                                ],
+                                            ),
+                                            Err(err) => i18n::fill(
+                                                app.locale(),
+                                                Text::DocSnapshotsClearFailed,
+                                                &[("err", &err)],
+                                            ),
+                                        }
+                                    }
+                                    Ok(sweep) if sweep.kept > 0 => i18n::fill(
+                                        app.locale(),
+                                        Text::DocSnapshotsSaved,
+                                        &[("kept", &sweep.kept)],
+                                    ),
+                                    Ok(_) => String::new(),
+                                    Err(err) => i18n::fill(
+                                        app.locale(),
+                                        Text::DocSnapshotsSanitizeFailed,
+                                        &[("err", &err)],
+                                    ),
+                                };
+                                match app.sync_planner_tasks_from_file(tasks) {
+                                    Ok(_) => {
+                                        if success {
+                                            app.push_agent_message(app.system_text(
+                                                Text::DocsAttached,
+                                                &[("snapshot_note", &snapshot_note)],
+                                            ));
+                                        } else {
+                                            app.push_agent_message(app.system_text(
+                                                Text::DocsAttachExited,
+                                                &[("code", &code)],
+                                            ));
+                                        }
                                     }
+                                    Err(err) => app.push_agent_message(app.system_text(
+                                        Text::DocsAttachRefreshFailed,
+                                        &[("err", &err)],
+                                    )),
                                 }
-                                Err(err) => app.push_agent_message(format!(
-                                    "System: Docs attach completed but task refresh failed: {err}"
-                                )),
-                            },
-                            Err(err) => app.push_agent_message(format!(
-                                "System: Docs attach completed but reading tasks.json failed: {err}"
-                            )),
+                            }
+                            Err(err) => app.push_agent_message(
+                                app.system_text(Text::DocsAttachReadFailed, &[("err", &err)]),
+                            ),
                         }
+                        record_tasks_change(
+                            &mut app,
+                            active_session,
+                            TasksChangeAgent::DocsAttach,
+                            docs_attach_baseline.as_deref(),
+                        );
                         chat_updated = true;
                     }
                 }
@@ -859,13 +1895,60 @@ fn run_app(
         }
         if !input_pending {
             for event in task_check_adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP) {
+                record_session_agent_event(
+                    &mut app,
+                    &mut session_event_recorder,
+                    session_store.as_ref(),
+                    "task_check",
+                    &event,
+                );
+                if task_split_run.is_some() {
+                    match event {
+                        AgentEvent::Output(line) => {
+                            app.push_filtered_subagent_output(
+                                "task_split",
+                                &line,
+                                format!("TaskSplit: {line}"),
+                            );
+                        }
+                        AgentEvent::System(line) => {
+                            app.push_filtered_subagent_output(
+                                "task_split",
+                                &line,
+                                format!("TaskSplitSystem: {line}"),
+                            );
+                        }
+                        AgentEvent::Completed { .. } => {
+                            app.set_task_check_in_progress(false);
+                            if let (Some(run), Some(active_session)) =
+                                (task_split_run.take(), session_store.as_ref())
+                            {
+                                finish_task_split_run(&mut app, active_session, run);
+                            }
+                        }
+                    }
+                    chat_updated = true;
+                    continue;
+                }
                 match event {
                     AgentEvent::Output(line) => {
-                        app.push_subagent_output(format!("TaskCheck: {line}"));
+                        app.push_filtered_subagent_output(
+                            "task_check",
+                            &line,
+                            format!("TaskCheck: {line}"),
+                        );
                         chat_updated = true;
                     }
                     AgentEvent::System(line) => {
-                        app.push_subagent_output(format!("TaskCheckSystem: {line}"));
+                        app.push_filtered_subagent_output(
+                            "task_check",
+                            &line,
+                            format!("TaskCheckSystem: {line}"),
+                        );
+                        chat_updated = true;
+                    }
+                    AgentEvent::Completed { .. } if !task_check_in_flight => {
+                        // Cancelled with /cancel taskcheck; the flags are already cleared.
                         chat_updated = true;
                     }
                     AgentEvent::Completed { success, code } => {
@@ -877,30 +1960,44 @@ fn run_app(
                             continue;
                         };
                         let after_text = std::fs::read_to_string(active_session.tasks_file()).ok();
+                        store_cached_response(
+                            &mut app,
+                            CachedCall::TaskCheck,
+                            after_text.as_deref().filter(|_| success),
+                        );
                         let changed = match (task_check_baseline.as_deref(), after_text.as_deref())
                         {
                             (Some(before), Some(after)) => before != after,
                             _ => false,
                         };
+                        if changed {
+                            record_tasks_change(
+                                &mut app,
+                                active_session,
+                                TasksChangeAgent::TaskCheck,
+                                task_check_baseline.as_deref(),
+                            );
+                        }
                         task_check_baseline = None;
                         if let Ok(tasks) = active_session.read_tasks() {
                             match app.sync_planner_tasks_from_file(tasks) {
                                 Ok(()) => {
                                     if changed {
                                         app.push_agent_message(
-                                            "System: Task checker applied fixes to tasks.json."
-                                                .to_string(),
+                                            app.system_text(Text::TaskCheckerFixed, &[]),
                                         );
                                     }
                                 }
-                                Err(err) => app.push_agent_message(format!(
-                                    "System: Task checker completed but task refresh failed: {err}"
-                                )),
+                                Err(err) => {
+                                    app.push_agent_message(app.system_text(
+                                        Text::TaskCheckerRefreshFailed,
+                                        &[("err", &err)],
+                                    ))
+                                }
                             }
                         } else {
                             app.push_agent_message(
-                                "System: Task checker completed but tasks.json could not be read."
-                                    .to_string(),
+                                app.system_text(Text::TaskCheckerReadFailed, &[]),
                             );
                         }
                         if success {
@@ -912,12 +2009,260 @@ fn run_app(
                                 "TaskCheckSystem: Task check exited with code {code}."
                             ));
                         }
+                        task_split_run = start_task_split_if_needed(
+                            &mut app,
+                            active_session,
+                            &task_check_adapter,
+                            task_split_limits,
+                        );
                         chat_updated = true;
                     }
                 }
             }
         }
+        // Resuming, switching, and takeovers change the session between iterations.
+        sync_safe_mode_session(&model_routing, session_store.as_ref());
+        session_file_watch.watch(&session_store.as_ref().map_or_else(Vec::new, |store| {
+            vec![
+                store.tasks_file().to_path_buf(),
+                session_lock::lock_file(store.session_dir()),
+            ]
+        }));
+        tasks_watch_pending |= session_file_watch.take_changed();
+        if !input_pending
+            && last_tasks_watch.elapsed() >= tasks_watch_interval
+            && let Some(owner) = session_lock::taken_over_by()
+            && let Some(active_session) = session_store.take()
+        {
+            // Another instance owns the files now; stop writing them and follow along.
+            audit_trail::set_session(None);
+            for adapter in worker_agent_adapters.values() {
+                adapter.terminate();
+            }
+            test_runner_adapter.terminate();
+            app.observe_on_exit(
+                active_session.session_dir().to_path_buf(),
+                app.system_text(Text::SessionTakenOver, &[("owner", &owner.describe())]),
+            );
+            break;
+        }
+        if !input_pending
+            && last_tasks_watch.elapsed() >= tasks_watch_interval
+            && let Some(active_session) = session_store.as_ref()
+        {
+            last_tasks_watch = Instant::now();
+            tasks_watch_pending = false;
+            let agent_may_write = app.is_master_in_progress()
+                || app.is_task_check_in_progress()
+                || app.is_docs_attach_in_progress()
+                || advisor.is_in_flight();
+            chat_updated |= tasks_watcher.poll(&mut app, active_session, agent_may_write);
+            let interventions = app.drain_manual_interventions();
+            if let Err(err) = interventions
+                .iter()
+                .try_for_each(|intervention| {
+                    session_event_recorder.record_manual_intervention(active_session, intervention)
+                })
+                .and_then(|()| {
+                    session_event_recorder.record_workflow_changes(
+                        active_session,
+                        &app.planner_tasks_for_file(),
+                        app.is_execution_enabled(),
+                        app.is_execution_busy(),
+                    )
+                })
+                .and_then(|()| {
+                    session_event_recorder
+                        .record_chat_lines(active_session, app.chat_transcript())
+                })
+            {
+                app.push_agent_message(
+                    app.system_text(Text::EventLogWriteFailed, &[("err", &err)]),
+                );
+                chat_updated = true;
+            }
+            ticket_status_sync.poll(active_session, &app.planner_tasks_for_file(), app.locale());
+            for message in ticket_status_sync.drain_messages() {
+                app.push_agent_message(message);
+                chat_updated = true;
+            }
+            for message in remote_mirror.drain_messages() {
+                app.push_agent_message(message);
+                chat_updated = true;
+            }
+            poll_digest_and_code_index(
+                &app,
+                away,
+                &cwd,
+                active_session,
+                &mut email_digest,
+                &mut code_index_refresher,
+            );
+            for message in email_digest.drain_messages() {
+                app.push_agent_message(message);
+                chat_updated = true;
+            }
+            for message in code_index_refresher.drain_messages() {
+                app.push_agent_message(message);
+                chat_updated = true;
+            }
+        }
+        for message in app.drain_plugin_messages() {
+            app.push_agent_message(message);
+            chat_updated = true;
+        }
+        // Console output marks only the top-left pane.
+        output_streamed |= app.poll_console();
+        if let Some(err) = telemetry::take_export_error() {
+            app.push_agent_message(app.system_text(Text::TraceExportFailed, &[("err", &err)]));
+            chat_updated = true;
+        }
+        if let Some(err) = audit_trail::take_error() {
+            app.push_agent_message(app.system_text(Text::AuditTrailWriteFailed, &[("err", &err)]));
+            chat_updated = true;
+        }
+        if !input_pending
+            && !app.is_master_prompt_preview_open()
+            && !app.is_question_form_open()
+            && !app.is_rerun_editor_open()
+            && app.shutdown_mode().is_none()
+            && app.next_queued_message().is_some_and(|message| {
+                queued_message_ready(
+                    project_info_in_flight,
+                    app.is_master_in_progress(),
+                    app.is_task_check_in_progress(),
+                    message,
+                )
+            })
+            && let Some(message) = app.dequeue_chat_message()
+        {
+            let remaining = app.queued_message_count();
+            app.push_agent_message(if remaining == 0 {
+                app.system_text(Text::SendingQueuedMessage, &[("message", &message)])
+            } else {
+                app.system_text(
+                    Text::SendingQueuedMessageRemaining,
+                    &[("remaining", &remaining), ("message", &message)],
+                )
+            });
+            submit_user_message_with_runtime(
+                &mut app,
+                message,
+                &master_adapter,
+                &master_report_adapter,
+                &project_info_adapter,
+                &mut worker_agent_adapters,
+                &mut active_worker_context_key,
+                &docs_attach_adapter,
+                &test_runner_adapter,
+                &mut master_report_in_flight,
+                &mut pending_master_report_prompts,
+                &mut master_report_transcript,
+                &mut task_check_in_flight,
+                &mut task_check_baseline,
+                &mut session_store,
+                &cwd,
+                terminal,
+                &mut pending_task_write_baseline,
+                &mut docs_attach_in_flight,
+                &mut master_session_intro_needed,
+                &mut master_report_session_intro_needed,
+                &mut pending_master_message_after_project_info,
+                &mut project_info_in_flight,
+                &mut project_info_stage,
+                &mut project_info_text,
+                &mut model_routing,
+                &mut selected_backend,
+            )?;
+            chat_updated = true;
+        }
+        if !input_pending
+            && app.has_batch_steps()
+            && !app.is_master_prompt_preview_open()
+            && !app.is_question_form_open()
+            && !app.is_rerun_editor_open()
+            && app.shutdown_mode().is_none()
+            && batch_step_ready(
+                project_info_in_flight,
+                app.is_master_in_progress(),
+                app.is_task_check_in_progress(),
+                docs_attach_in_flight,
+                app.is_execution_busy(),
+                app.queued_message_count(),
+            )
+            && let Some((number, step)) = app.take_batch_step()
+            && let Some(message) = app.submit_direct_message(&step)
+        {
+            app.push_agent_message(app.system_text(
+                Text::BatchStep,
+                &[("number", &number), ("total", &app.batch_step_total())],
+            ));
+            submit_user_message_with_runtime(
+                &mut app,
+                message,
+                &master_adapter,
+                &master_report_adapter,
+                &project_info_adapter,
+                &mut worker_agent_adapters,
+                &mut active_worker_context_key,
+                &docs_attach_adapter,
+                &test_runner_adapter,
+                &mut master_report_in_flight,
+                &mut pending_master_report_prompts,
+                &mut master_report_transcript,
+                &mut task_check_in_flight,
+                &mut task_check_baseline,
+                &mut session_store,
+                &cwd,
+                terminal,
+                &mut pending_task_write_baseline,
+                &mut docs_attach_in_flight,
+                &mut master_session_intro_needed,
+                &mut master_report_session_intro_needed,
+                &mut pending_master_message_after_project_info,
+                &mut project_info_in_flight,
+                &mut project_info_stage,
+                &mut project_info_text,
+                &mut model_routing,
+                &mut selected_backend,
+            )?;
+            chat_updated = true;
+        }
+        let session_dir = session_store
+            .as_ref()
+            .map(|store| store.session_dir().to_path_buf());
+        // Adapters are rebuilt for new pins, so wait until none of them is running.
+        if (session_dir != models_session_dir || app.take_session_models_changed())
+            && batch_step_ready(
+                project_info_in_flight,
+                app.is_master_in_progress(),
+                app.is_task_check_in_progress(),
+                docs_attach_in_flight || master_report_in_flight,
+                app.is_execution_busy(),
+                0,
+            )
+        {
+            let opened = session_dir != models_session_dir;
+            models_session_dir = session_dir;
+            if sync_session_models(&mut app, session_store.as_ref(), &mut model_routing, opened) {
+                rebuild_runtime_adapters(
+                    &model_routing,
+                    selected_backend,
+                    &mut master_adapter,
+                    &mut master_report_adapter,
+                    &mut project_info_adapter,
+                    &mut docs_attach_adapter,
+                    &mut task_check_adapter,
+                    &mut active_worker_context_key,
+                    &mut worker_agent_adapters,
+                );
+            }
+            chat_updated = true;
+        }
         if chat_updated {
+            app.mark_all_panes_damaged();
+        }
+        if chat_updated || output_streamed {
             let size = terminal.size()?;
             let screen = Rect::new(0, 0, size.width, size.height);
             let max_scroll = ui::chat_max_scroll(screen, &app);
@@ -925,32 +2270,250 @@ fn run_app(
             needs_draw = true;
         }
 
-        let mut app_event = events::next_event()?;
+        app.restore_staged_chat_draft();
+        // Handling an event can leave work for the top of the loop, so only sleep after a
+        // pass that had nothing to handle.
+        let wait = if handled_event {
+            Duration::ZERO
+        } else {
+            loop_wait_timeout(
+                Instant::now(),
+                [
+                    app.is_any_agent_in_progress()
+                        .then(|| last_ui_tick + ui_tick_interval),
+                    // Between runs only user actions and outside edits need a tasks pass.
+                    (session_store.is_some()
+                        && (tasks_watch_pending
+                            || app.is_any_agent_in_progress()
+                            || app.is_execution_busy()))
+                    .then(|| last_tasks_watch + tasks_watch_interval),
+                    planner_last_keystroke_at
+                        .filter(|_| planner_manual_edit_dirty)
+                        .map(|at| at + PLANNER_AUTOSAVE_DEBOUNCE),
+                    worker_retry.next_due(),
+                ],
+            )
+        };
+        let mut app_event = if detached {
+            wakeup::wait(wait);
+            AppEvent::Tick
+        } else {
+            events::next_event(wait)?
+        };
+        if presence.on_event(&app_event, Instant::now()) {
+            // Back from away: tick and run the deferred background work on this pass.
+            let now = Instant::now();
+            last_ui_tick = now.checked_sub(ui_tick_interval).unwrap_or(now);
+            last_tasks_watch = now.checked_sub(tasks_watch_interval).unwrap_or(now);
+            needs_draw = true;
+        }
+        if matches!(&app_event, AppEvent::FocusGained | AppEvent::FocusLost) {
+            app_event = AppEvent::Tick;
+        }
         if matches!(&app_event, AppEvent::InsertNewline)
             && (app.active_pane != Pane::LeftBottom || is_picker_open(&app))
+            && !app.is_rerun_editor_open()
         {
             app_event = AppEvent::Submit;
         }
-        if !matches!(&app_event, AppEvent::Tick) {
+        handled_event = !matches!(&app_event, AppEvent::Tick);
+        tasks_watch_pending |= handled_event;
+        if handled_event {
+            app.mark_all_panes_damaged();
             needs_draw = true;
         }
-        match app_event {
-            AppEvent::Tick => {
-                flush_debounced_planner_autosave_if_due(
-                    &mut app,
-                    session_store.as_ref(),
-                    &mut planner_manual_edit_dirty,
-                    &mut planner_last_keystroke_at,
-                );
-                if last_ui_tick.elapsed() >= UI_TICK_INTERVAL {
-                    app.on_tick();
-                    last_ui_tick = Instant::now();
-                    needs_draw = true;
-                }
-            }
-            AppEvent::Quit => app.quit(),
+        if app.is_help_overlay_open() && !matches!(&app_event, AppEvent::Tick | AppEvent::Quit) {
+            let size = terminal.size()?;
+            handle_help_overlay_event(
+                &mut app,
+                Rect::new(0, 0, size.width, size.height),
+                &app_event,
+            );
+            app_event = AppEvent::Tick;
+        } else if help_requested(&app, &app_event) {
+            app.open_help_overlay();
+            app_event = AppEvent::Tick;
+        }
+        if app.is_master_prompt_preview_open()
+            && !matches!(&app_event, AppEvent::Tick | AppEvent::Quit)
+        {
+            let size = terminal.size()?;
+            let screen = Rect::new(0, 0, size.width, size.height);
+            match app_event {
+                AppEvent::Submit => {
+                    if let Some(preview) = app.take_master_prompt_preview() {
+                        master_adapter.send_prompt(preview.prompt);
+                        app.set_master_in_progress(true);
+                        app.reset_prompt_sections();
+                        pending_task_write_baseline = session_store
+                            .as_ref()
+                            .and_then(|store| orchestration_service.capture_tasks_baseline(store));
+                        app.push_agent_message(app.system_text(Text::MasterPromptSent, &[]));
+                    }
+                }
+                AppEvent::Backspace => {
+                    cancel_master_prompt_preview(&mut app, &mut master_session_intro_needed);
+                }
+                AppEvent::MoveUp | AppEvent::ScrollChatUp | AppEvent::MouseScrollUp => {
+                    app.master_prompt_preview_scroll_up();
+                }
+                AppEvent::MoveDown | AppEvent::ScrollChatDown | AppEvent::MouseScrollDown => {
+                    let max_scroll = ui::master_prompt_preview_max_scroll(screen, &app);
+                    app.master_prompt_preview_scroll_down(max_scroll);
+                }
+                _ => {}
+            }
+            let max_scroll = ui::chat_max_scroll(screen, &app);
+            app.set_chat_scroll(max_scroll);
+            app_event = AppEvent::Tick;
+        }
+        if app.is_question_form_open() && !matches!(&app_event, AppEvent::Tick | AppEvent::Quit) {
+            let mut submitted = false;
+            if let Some(form) = app.question_form_mut() {
+                match &app_event {
+                    AppEvent::Submit => submitted = form.submit_field() == FormStep::Submitted,
+                    AppEvent::InputChar(c) => form.insert_text(&c.to_string()),
+                    AppEvent::Paste(content) => form.insert_text(content),
+                    AppEvent::Backspace => form.backspace(),
+                    AppEvent::MoveUp | AppEvent::PrevPane => form.select_previous(),
+                    AppEvent::MoveDown | AppEvent::NextPane => form.select_next(),
+                    _ => {}
+                }
+            }
+            app_event = AppEvent::Tick;
+            if submitted
+                && let Some(form) = app.take_question_form()
+                && stage_question_answers(&mut app, session_store.as_ref(), &form)
+            {
+                app.active_pane = Pane::LeftBottom;
+                app_event = AppEvent::Submit;
+            }
+        }
+        if app.is_rerun_editor_open() && !matches!(&app_event, AppEvent::Tick | AppEvent::Quit) {
+            let size = terminal.size()?;
+            let (width, visible_lines) =
+                ui::rerun_editor_metrics(Rect::new(0, 0, size.width, size.height));
+            let mut send = false;
+            let mut cancel = false;
+            if let Some(editor) = app.rerun_editor_mut() {
+                match &app_event {
+                    AppEvent::Submit => send = true,
+                    AppEvent::PrevPane => cancel = true,
+                    AppEvent::InsertNewline => editor.insert_text("\n"),
+                    AppEvent::InputChar(c) => editor.insert_text(&c.to_string()),
+                    AppEvent::Paste(content) => editor.insert_text(content),
+                    AppEvent::Backspace => editor.backspace(),
+                    AppEvent::CursorLeft => editor.move_left(),
+                    AppEvent::CursorRight => editor.move_right(),
+                    AppEvent::MoveUp => editor.move_up(width),
+                    AppEvent::MoveDown => editor.move_down(width),
+                    _ => {}
+                }
+                editor.ensure_cursor_visible(width, visible_lines);
+            }
+            if cancel && app.take_rerun_editor().is_some() {
+                app.push_agent_message(app.system_text(Text::RerunCancelled, &[]));
+            }
+            if send && let Some(editor) = app.take_rerun_editor() {
+                pending_task_write_baseline = None;
+                start_edited_rerun(
+                    &mut app,
+                    editor,
+                    &mut worker_agent_adapters,
+                    &mut active_worker_context_key,
+                    &test_runner_adapter,
+                    session_store.as_ref(),
+                    &model_routing,
+                );
+            }
+            let size = terminal.size()?;
+            let screen = Rect::new(0, 0, size.width, size.height);
+            let max_scroll = ui::chat_max_scroll(screen, &app);
+            app.set_chat_scroll(max_scroll);
+            app_event = AppEvent::Tick;
+        }
+        if app.is_task_menu_open() && !matches!(&app_event, AppEvent::Tick | AppEvent::Quit) {
+            let size = terminal.size()?;
+            let screen = Rect::new(0, 0, size.width, size.height);
+            let mut choose = false;
+            match app_event {
+                AppEvent::Submit | AppEvent::InputChar(' ') => choose = true,
+                AppEvent::MoveUp | AppEvent::ScrollChatUp | AppEvent::MouseScrollUp => {
+                    app.task_menu_move_up();
+                }
+                AppEvent::MoveDown | AppEvent::ScrollChatDown | AppEvent::MouseScrollDown => {
+                    app.task_menu_move_down();
+                }
+                AppEvent::MouseLeftClick(column, row) => {
+                    if let Some(index) = ui::task_menu_hit_test(screen, &app, column, row) {
+                        app.set_task_menu_selected(index);
+                        choose = true;
+                    } else if !ui::task_menu_contains(screen, &app, column, row) {
+                        app.close_task_menu();
+                    }
+                }
+                AppEvent::Backspace | AppEvent::MouseContextMenu(_, _) => app.close_task_menu(),
+                _ => {}
+            }
+            app_event = AppEvent::Tick;
+            if choose && let Some(command) = activate_task_menu_entry(&mut app, &model_routing) {
+                app.stage_chat_command(command);
+                app.active_pane = Pane::LeftBottom;
+                app_event = AppEvent::Submit;
+            }
+        }
+        if let AppEvent::InputChar(c) = app_event
+            && app.active_pane == Pane::LeftBottom
+            && !is_picker_open(&app)
+            && let Some(action) = app.stall_action_for_key(c, Instant::now())
+        {
+            handle_stall_action(
+                &mut app,
+                action,
+                active_worker_context_key
+                    .as_ref()
+                    .and_then(|key| worker_agent_adapters.get(key)),
+                &test_runner_adapter,
+                &mut pending_stall_nudge,
+                Instant::now(),
+            );
+            let size = terminal.size()?;
+            let max_scroll = ui::chat_max_scroll(Rect::new(0, 0, size.width, size.height), &app);
+            app.set_chat_scroll(max_scroll);
+            app_event = AppEvent::Tick;
+        }
+        if app_event == AppEvent::QuickSwitch {
+            app_event = if is_picker_open(&app) {
+                AppEvent::Tick
+            } else {
+                app.stage_chat_command("/switch".to_string());
+                app.active_pane = Pane::LeftBottom;
+                AppEvent::Submit
+            };
+        }
+        match app_event {
+            AppEvent::Tick => {
+                flush_debounced_planner_autosave_if_due(
+                    &mut app,
+                    session_store.as_ref(),
+                    &mut planner_manual_edit_dirty,
+                    &mut planner_last_keystroke_at,
+                );
+                if app.is_any_agent_in_progress() && last_ui_tick.elapsed() >= ui_tick_interval {
+                    app.on_tick();
+                    last_ui_tick = Instant::now();
+                    needs_draw = true;
+                }
+            }
+            AppEvent::Quit => {
+                if let Some(message) = app.request_shutdown(None) {
+                    app.push_agent_message(message);
+                }
+            }
             AppEvent::NextPane => {
-                if is_picker_open(&app) {
+                if app.is_resume_picker_open() {
+                    app.toggle_resume_picker_workspaces();
+                } else if is_picker_open(&app) {
                     // ignore pane focus changes while a picker is open
                 } else if app.active_pane == Pane::LeftBottom && app.autocomplete_top_command() {
                     // keep focus in input when command autocomplete is applied
@@ -968,6 +2531,8 @@ fn run_app(
                     app.resume_picker_move_up();
                 } else if app.is_backend_picker_open() {
                     app.backend_picker_move_up();
+                } else if app.is_log_picker_open() {
+                    app.log_picker_move_up();
                 } else if app.active_pane == Pane::LeftBottom {
                     let size = terminal.size()?;
                     let width = ui::chat_input_text_width(Rect::new(0, 0, size.width, size.height));
@@ -992,6 +2557,8 @@ fn run_app(
                     app.resume_picker_move_down();
                 } else if app.is_backend_picker_open() {
                     app.backend_picker_move_down();
+                } else if app.is_log_picker_open() {
+                    app.log_picker_move_down();
                 } else if app.active_pane == Pane::LeftBottom {
                     let size = terminal.size()?;
                     let width = ui::chat_input_text_width(Rect::new(0, 0, size.width, size.height));
@@ -1076,6 +2643,8 @@ fn run_app(
                     app.resume_picker_move_up();
                 } else if app.is_backend_picker_open() {
                     app.backend_picker_move_up();
+                } else if app.is_log_picker_open() {
+                    app.log_picker_move_up();
                 } else if app.active_pane == Pane::LeftBottom {
                     app.scroll_chat_up();
                 } else if app.active_pane == Pane::Right {
@@ -1089,6 +2658,8 @@ fn run_app(
                     app.resume_picker_move_down();
                 } else if app.is_backend_picker_open() {
                     app.backend_picker_move_down();
+                } else if app.is_log_picker_open() {
+                    app.log_picker_move_down();
                 } else if app.active_pane == Pane::LeftBottom {
                     let size = terminal.size()?;
                     let screen = Rect::new(0, 0, size.width, size.height);
@@ -1117,9 +2688,9 @@ fn run_app(
             }
             AppEvent::InputChar(c) => {
                 if app.is_resume_picker_open() {
-                    if c == ' '
-                        && let Some(selection) = app.select_resume_session()
-                    {
+                    if c != ' ' || !app.resume_picker_filter().is_empty() {
+                        app.resume_picker_filter_push(c);
+                    } else if let Some(selection) = app.select_resume_session() {
                         resume_session(
                             &mut app,
                             &mut session_store,
@@ -1162,6 +2733,11 @@ fn run_app(
                             &mut docs_attach_adapter,
                             &mut task_check_adapter,
                         );
+                        backend_failover.rearm();
+                    }
+                } else if app.is_log_picker_open() {
+                    if c == ' ' {
+                        open_selected_worker_log(&mut app);
                     }
                 } else if app.active_pane == Pane::LeftBottom {
                     app.input_char(c);
@@ -1174,10 +2750,14 @@ fn run_app(
                             &mut project_info_text,
                             &project_info_adapter,
                             &master_adapter,
+                            &model_routing,
                         ) {
-                            app.push_agent_message(format!(
-                                "System: Failed to initialize session for planner edit: {err}"
-                            ));
+                            app.push_agent_message(
+                                app.system_text(
+                                    Text::PlannerEditSessionInitFailed,
+                                    &[("err", &err)],
+                                ),
+                            );
                         }
                     }
 
@@ -1213,13 +2793,60 @@ fn run_app(
             }
             AppEvent::Backspace => {
                 if app.is_resume_picker_open() {
-                    app.open_resume_picker(Vec::new());
-                    app.push_agent_message("System: Resume picker cancelled.".to_string());
+                    if !app.resume_picker_filter_pop() {
+                        let new_session_message = app.take_new_session_message();
+                        app.open_resume_picker(Vec::new());
+                        if let Some(message) = new_session_message {
+                            app.push_agent_message(app.system_text(Text::StartingNewSession, &[]));
+                            submit_user_message_with_runtime(
+                                &mut app,
+                                message,
+                                &master_adapter,
+                                &master_report_adapter,
+                                &project_info_adapter,
+                                &mut worker_agent_adapters,
+                                &mut active_worker_context_key,
+                                &docs_attach_adapter,
+                                &test_runner_adapter,
+                                &mut master_report_in_flight,
+                                &mut pending_master_report_prompts,
+                                &mut master_report_transcript,
+                                &mut task_check_in_flight,
+                                &mut task_check_baseline,
+                                &mut session_store,
+                                &cwd,
+                                terminal,
+                                &mut pending_task_write_baseline,
+                                &mut docs_attach_in_flight,
+                                &mut master_session_intro_needed,
+                                &mut master_report_session_intro_needed,
+                                &mut pending_master_message_after_project_info,
+                                &mut project_info_in_flight,
+                                &mut project_info_stage,
+                                &mut project_info_text,
+                                &mut model_routing,
+                                &mut selected_backend,
+                            )?;
+                        } else {
+                            app.push_agent_message(
+                                app.system_text(Text::ResumePickerCancelled, &[]),
+                            );
+                        }
+                    }
                 } else if app.is_backend_picker_open() {
                     app.open_backend_picker(Vec::new());
-                    app.push_agent_message("System: Backend picker cancelled.".to_string());
+                    app.push_agent_message(app.system_text(Text::BackendPickerCancelled, &[]));
+                } else if app.is_log_picker_open() {
+                    app.open_log_picker(Vec::new());
+                    app.push_agent_message(app.system_text(Text::LogPickerCancelled, &[]));
                 } else if app.active_pane == Pane::LeftBottom {
                     app.backspace_input();
+                } else if app.active_pane == Pane::LeftTop && app.is_file_tree_mode() {
+                    app.file_tree_back();
+                } else if app.active_pane == Pane::Right && app.is_worker_log_mode() {
+                    app.close_worker_log();
+                } else if app.active_pane == Pane::Right && app.is_task_summaries_mode() {
+                    app.set_right_pane_mode(RightPaneMode::TaskList);
                 } else if app.active_pane == Pane::Right && app.is_planner_mode() {
                     if session_store.is_none() {
                         if let Err(err) = initialize_session_for_planner_edit_if_needed(
@@ -1229,10 +2856,14 @@ fn run_app(
                             &mut project_info_text,
                             &project_info_adapter,
                             &master_adapter,
+                            &model_routing,
                         ) {
-                            app.push_agent_message(format!(
-                                "System: Failed to initialize session for planner edit: {err}"
-                            ));
+                            app.push_agent_message(
+                                app.system_text(
+                                    Text::PlannerEditSessionInitFailed,
+                                    &[("err", &err)],
+                                ),
+                            );
                         }
                     }
 
@@ -1252,6 +2883,7 @@ fn run_app(
                 app.insert_chat_newline();
             }
             AppEvent::Submit => {
+                app.begin_command_reply();
                 if app.is_resume_picker_open() {
                     if let Some(selection) = app.select_resume_session() {
                         resume_session(
@@ -1294,7 +2926,12 @@ fn run_app(
                             &mut docs_attach_adapter,
                             &mut task_check_adapter,
                         );
+                        backend_failover.rearm();
                     }
+                } else if app.is_log_picker_open() {
+                    open_selected_worker_log(&mut app);
+                } else if app.active_pane == Pane::LeftTop && app.is_file_tree_mode() {
+                    app.file_tree_activate();
                 } else if app.active_pane == Pane::Right && app.is_planner_mode() {
                     if session_store.is_none() {
                         if let Err(err) = initialize_session_for_planner_edit_if_needed(
@@ -1304,10 +2941,14 @@ fn run_app(
                             &mut project_info_text,
                             &project_info_adapter,
                             &master_adapter,
+                            &model_routing,
                         ) {
-                            app.push_agent_message(format!(
-                                "System: Failed to initialize session for planner edit: {err}"
-                            ));
+                            app.push_agent_message(
+                                app.system_text(
+                                    Text::PlannerEditSessionInitFailed,
+                                    &[("err", &err)],
+                                ),
+                            );
                         }
                     }
 
@@ -1322,6 +2963,9 @@ fn run_app(
                         &mut planner_last_keystroke_at,
                     );
                 } else if app.active_pane == Pane::LeftBottom {
+                    if app.expand_chat_input_command() {
+                        continue;
+                    }
                     let pending = app.chat_input().trim().to_string();
                     match submit_block_reason(
                         project_info_in_flight,
@@ -1331,35 +2975,99 @@ fn run_app(
                         &pending,
                     ) {
                         Some(SubmitBlockReason::ProjectInfoGathering) => {
-                            app.push_agent_message(
-                                "System: Project context gathering is in progress. Enter/Return submissions are temporarily disabled until it completes.".to_string(),
-                            );
-                            continue;
-                        }
-                        Some(SubmitBlockReason::MasterBusy) => {
-                            app.push_agent_message(
-                                "System: Master is still processing your previous request. Enter/Return submissions are temporarily disabled until it completes.".to_string(),
-                            );
+                            app.push_agent_message(app.system_text(Text::ProjectInfoBusy, &[]));
                             continue;
                         }
-                        Some(SubmitBlockReason::TaskCheck) => {
-                            app.push_agent_message(
-                                "System: Task checking is in progress. Message and slash commands are temporarily blocked (except /quit and /exit).".to_string(),
-                            );
+                        Some(
+                            reason @ (SubmitBlockReason::MasterBusy | SubmitBlockReason::TaskCheck),
+                        ) => {
+                            if let Some(queued) = app.queue_chat_input() {
+                                let key = if reason == SubmitBlockReason::MasterBusy {
+                                    Text::MasterBusyQueued
+                                } else {
+                                    Text::TaskCheckQueued
+                                };
+                                app.push_agent_message(
+                                    app.system_text(key, &[("position", &queued)]),
+                                );
+                            }
                             continue;
                         }
                         Some(SubmitBlockReason::ExecutionBusy) => {
-                            app.push_agent_message(
-                                "System: Execution is currently running. Master/task editing commands are blocked until active worker jobs finish."
-                                    .to_string(),
-                            );
+                            if should_send_to_master(&pending)
+                                && let Some(active_session) = session_store.as_ref()
+                            {
+                                if advisor.is_in_flight() {
+                                    app.push_agent_message(app.system_text(Text::AdvisorBusy, &[]));
+                                } else if let Some(question) = app.submit_chat_message() {
+                                    app.push_agent_message(
+                                        app.system_text(Text::AdvisorAsked, &[]),
+                                    );
+                                    ask_advisor(
+                                        &app,
+                                        &mut advisor,
+                                        active_session,
+                                        &question,
+                                        &model_routing,
+                                        selected_backend,
+                                        project_info_text.as_deref(),
+                                    );
+                                }
+                            } else {
+                                app.push_agent_message(
+                                    app.system_text(Text::ExecutionBusyBlocked, &[]),
+                                );
+                            }
                             continue;
                         }
                         None => {}
                     }
+                    if let Some(command) = App::parse_cancel_command(&pending) {
+                        let _ = app.consume_chat_input_trimmed();
+                        let held_message = handle_cancel_command(
+                            &mut app,
+                            command,
+                            &project_info_adapter,
+                            &docs_attach_adapter,
+                            &task_check_adapter,
+                            &mut project_info_in_flight,
+                            &mut project_info_stage,
+                            &mut pending_master_message_after_project_info,
+                            &mut docs_attach_in_flight,
+                            &mut task_check_in_flight,
+                            &mut task_check_baseline,
+                            &mut task_split_run,
+                            session_store.as_ref(),
+                        );
+                        if let (Some(message), Some(active_session)) =
+                            (held_message, session_store.as_ref())
+                        {
+                            send_held_master_message(
+                                &mut app,
+                                &master_adapter,
+                                active_session,
+                                &message,
+                                project_info_text.as_deref(),
+                                &mut master_session_intro_needed,
+                                &mut pending_task_write_baseline,
+                            );
+                        }
+                        let size = terminal.size()?;
+                        let max_scroll =
+                            ui::chat_max_scroll(Rect::new(0, 0, size.width, size.height), &app);
+                        app.set_chat_scroll(max_scroll);
+                        continue;
+                    }
                     if parse_silent_master_command(&pending).is_some()
                         || App::is_add_final_audit_command(&pending)
                         || App::is_remove_final_audit_command(&pending)
+                        || App::is_approve_split_command(&pending)
+                        || App::is_reject_split_command(&pending)
+                        || App::is_accept_tasks_command(&pending)
+                        || App::is_reject_tasks_command(&pending)
+                        || App::is_reload_tasks_command(&pending)
+                        || App::is_keep_tasks_command(&pending)
+                        || App::parse_conflict_command(&pending).is_some()
                     {
                         if let Some(message) = app.consume_chat_input_trimmed() {
                             submit_user_message_with_runtime(
@@ -1427,9 +3135,7 @@ fn run_app(
             }
             AppEvent::Paste(content) => {
                 if app.active_pane == Pane::LeftBottom {
-                    for c in content.chars() {
-                        app.input_char(c);
-                    }
+                    app.paste_input(&content);
                 } else if app.active_pane == Pane::Right && app.is_planner_mode() {
                     let started_without_session = session_store.is_none();
                     if started_without_session {
@@ -1440,10 +3146,14 @@ fn run_app(
                             &mut project_info_text,
                             &project_info_adapter,
                             &master_adapter,
+                            &model_routing,
                         ) {
-                            app.push_agent_message(format!(
-                                "System: Failed to initialize session for planner edit: {err}"
-                            ));
+                            app.push_agent_message(
+                                app.system_text(
+                                    Text::PlannerEditSessionInitFailed,
+                                    &[("err", &err)],
+                                ),
+                            );
                         }
                     }
 
@@ -1460,7 +3170,9 @@ fn run_app(
                         );
                     } else {
                         match persist_planner_markdown_if_changed(&mut app, session_store.as_ref()) {
-                        PlannerPersistResult::Persisted | PlannerPersistResult::Unchanged => {
+                        PlannerPersistResult::Persisted
+                        | PlannerPersistResult::Unchanged
+                        | PlannerPersistResult::Conflicted => {
                             planner_manual_edit_dirty = false;
                             planner_last_keystroke_at = None;
                         }
@@ -1479,6 +3191,8 @@ fn run_app(
                     app.resume_picker_move_up();
                 } else if app.is_backend_picker_open() {
                     app.backend_picker_move_up();
+                } else if app.is_log_picker_open() {
+                    app.log_picker_move_up();
                 } else if app.active_pane == Pane::LeftBottom {
                     app.scroll_chat_up();
                 } else if app.active_pane == Pane::Right {
@@ -1492,6 +3206,8 @@ fn run_app(
                     app.resume_picker_move_down();
                 } else if app.is_backend_picker_open() {
                     app.backend_picker_move_down();
+                } else if app.is_log_picker_open() {
+                    app.log_picker_move_down();
                 } else if app.active_pane == Pane::LeftBottom {
                     let size = terminal.size()?;
                     let screen = Rect::new(0, 0, size.width, size.height);
@@ -1514,14 +3230,133 @@ fn run_app(
                 let screen = Rect::new(0, 0, size.width, size.height);
                 handle_mouse_left_click(&mut app, screen, column, row);
             }
+            AppEvent::MouseContextMenu(column, row) => {
+                let size = terminal.size()?;
+                let screen = Rect::new(0, 0, size.width, size.height);
+                handle_mouse_context_menu(&mut app, screen, column, row);
+            }
+            // Staged as a `/switch` submit above.
+            AppEvent::QuickSwitch => {}
+            AppEvent::ToggleChatView => {
+                let size = terminal.size()?;
+                toggle_chat_view(&mut app, Rect::new(0, 0, size.width, size.height));
+            }
+            // Handled before the modals above.
+            AppEvent::ShowHelp => {}
+            AppEvent::Resize | AppEvent::FocusGained | AppEvent::FocusLost => {}
         }
 
-        if needs_draw && !events::has_pending_input()? {
-            terminal.draw(|frame| ui::render(frame, &app, theme))?;
+        // `/resume` may have moved to another workspace.
+        if let Some(root) = app.workspace_root()
+            && root != cwd
+        {
+            cwd = root.to_path_buf();
+            app.set_workspace_ignore(WorkspaceIgnore::load(&cwd));
+            // Spawned processes get the workspace as their directory; the process keeps its own.
+            model_routing.set_workspace(Some(cwd.clone()));
+            test_runner_adapter.set_workdir(Some(cwd.clone()));
+            rebuild_runtime_adapters(
+                &model_routing,
+                selected_backend,
+                &mut master_adapter,
+                &mut master_report_adapter,
+                &mut project_info_adapter,
+                &mut docs_attach_adapter,
+                &mut task_check_adapter,
+                &mut active_worker_context_key,
+                &mut worker_agent_adapters,
+            );
+        }
+
+        if needs_draw && !detached && !events::has_pending_input()? {
+            let damage = app.take_pane_damage();
+            let frame = terminal.draw(|frame| {
+                ui::render_with_cache(frame, &app, theme, &mut pane_render_cache, damage)
+            })?;
             needs_draw = false;
+            if let Some(protocol) = app.graphics_protocol() {
+                let placements = find_placements(frame.buffer, app.inline_image_rows());
+                if inline_images.update(terminal.backend_mut(), protocol, placements, &cwd)? {
+                    // iTerm2 images live in the cells, so moved ones need a full repaint.
+                    terminal.clear()?;
+                    app.mark_all_panes_damaged();
+                    needs_draw = true;
+                }
+            }
         }
     }
 
+    let observe = app.take_observe_on_exit();
+    persist_run_state_on_exit(&mut app, session_store.as_ref())?;
+    session_lock::release();
+    let Some((session_dir, notice)) = observe else {
+        return Ok(session_store.map(|store| store.session_dir().to_path_buf()));
+    };
+    let store = SessionStore::open_read_only(session_dir)?;
+    let mut observer_app = App::default();
+    observer_app.push_agent_message(notice);
+    // The session now belongs to the instance that took it over, so it pushes it.
+    run_observer_app(terminal, observer_app, theme, SessionObserver::new(store))?;
+    Ok(None)
+}
+
+/// Hands the terminal back for `/quit detach`. The loop keeps running without input or
+/// drawing until the active job finishes.
+fn detach_from_terminal(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    session_store: Option<&SessionStore>,
+) -> io::Result<()> {
+    shutdown::ignore_hangup();
+    events::stop_input_reader();
+    let _ = execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags);
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        SetCursorStyle::DefaultUserShape,
+        DisableBracketedPaste,
+        DisableFocusChange,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
+    terminal.show_cursor()?;
+    let observe_hint = session_store
+        .map(|store| {
+            format!(
+                " Follow it with `agentbob observe --session-dir {}`.",
+                store.session_dir().display()
+            )
+        })
+        .unwrap_or_default();
+    println!(
+        "Detached: the active job keeps running in this process (pid {}) and it exits when the job finishes. Press Ctrl+Z and run `bg` to get your shell back.{observe_hint}",
+        std::process::id()
+    );
+    Ok(())
+}
+
+/// Resets a run cut short by quitting so `/resume` restarts its job cleanly, and saves what
+/// the next activation of the session should replay, pane positions included.
+fn persist_run_state_on_exit(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+) -> io::Result<()> {
+    let execution_running = app.is_execution_busy();
+    let interrupted_job = app.interrupt_execution();
+    let Some(store) = session_store else {
+        return Ok(());
+    };
+    store.write_ui_state(&app.session_ui_state())?;
+    if execution_running && !app.has_external_tasks_edit() {
+        store.write_tasks(&app.planner_tasks_for_file())?;
+    }
+    let run = InterruptedRunFile {
+        execution_running,
+        interrupted_job,
+        queued_messages: app.queued_messages(),
+    };
+    if run != InterruptedRunFile::default() {
+        store.write_interrupted_run(&run)?;
+    }
     Ok(())
 }
 
@@ -1557,11 +3392,30 @@ fn submit_user_message_with_runtime<B: Backend>(
     let orchestration_service = DefaultCoreOrchestrationService;
     let prompt_service = DefaultUiPromptService;
 
+    // Startup, batch, and queued messages reach here without passing the chat input.
+    let message = match app.command_registry().expand(&message) {
+        Some(UserCommand::Alias(command)) => command,
+        Some(UserCommand::Macro(steps)) => {
+            app.queue_macro_steps(steps);
+            let size = terminal.size()?;
+            let screen = Rect::new(0, 0, size.width, size.height);
+            let max_scroll = ui::chat_max_scroll(screen, app);
+            app.set_chat_scroll(max_scroll);
+            return Ok(());
+        }
+        None => message,
+    };
+
     if should_send_to_master(&message) && app.is_master_in_progress() {
-        app.push_agent_message(
-            "System: Master is still processing your previous request. Please wait for completion before sending another message."
-                .to_string(),
-        );
+        app.push_agent_message(app.system_text(Text::MasterStillProcessing, &[]));
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if offer_recent_sessions_if_needed(app, &message, cwd, session_store.is_some()) {
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1569,13 +3423,17 @@ fn submit_user_message_with_runtime<B: Backend>(
         return Ok(());
     }
 
-    initialize_session_for_message_if_needed(app, &message, cwd, session_store, project_info_text)?;
+    initialize_session_for_message_if_needed(
+        app,
+        &message,
+        cwd,
+        session_store,
+        project_info_text,
+        model_routing,
+    )?;
 
     if command_requires_active_session(&message) && session_store.is_none() {
-        app.push_agent_message(
-            "System: No active session yet. Enter a normal message to start one, or use /resume to select an existing session."
-                .to_string(),
-        );
+        app.push_agent_message(app.system_text(Text::NoActiveSession, &[]));
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1584,10 +3442,7 @@ fn submit_user_message_with_runtime<B: Backend>(
     }
 
     if app.is_execution_busy() && conflicts_with_running_execution(&message) {
-        app.push_agent_message(
-            "System: Execution is currently running. Master/task editing commands are blocked until active worker jobs finish."
-                .to_string(),
-        );
+        app.push_agent_message(app.system_text(Text::ExecutionBusyBlocked, &[]));
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1596,12 +3451,12 @@ fn submit_user_message_with_runtime<B: Backend>(
     }
 
     if *docs_attach_in_flight
-        && (App::is_new_master_command(&message) || App::is_resume_command(&message))
+        && (App::is_new_master_command(&message)
+            || App::is_resume_command(&message)
+            || App::is_switch_command(&message)
+            || App::new_from_template_name(&message).is_some())
     {
-        app.push_agent_message(
-            "System: Documentation attach is still running. Wait for it to finish before switching sessions."
-                .to_string(),
-        );
+        app.push_agent_message(app.system_text(Text::DocsAttachBlocksSwitch, &[]));
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1609,17 +3464,332 @@ fn submit_user_message_with_runtime<B: Backend>(
         return Ok(());
     }
 
-    if App::is_add_final_audit_command(&message) || App::is_remove_final_audit_command(&message) {
-        let active_session = session_store
-            .as_ref()
-            .expect("final-audit commands require an active session");
-        if handle_final_audit_command(app, &message, active_session, terminal)? {
-            return Ok(());
-        }
+    if App::is_approve_split_command(&message) || App::is_reject_split_command(&message) {
+        handle_task_split_decision(app, &message, session_store.as_ref());
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
     }
 
-    if parse_silent_master_command(&message).is_some() {
-        let active_session = session_store
+    if App::is_accept_tasks_command(&message) || App::is_reject_tasks_command(&message) {
+        handle_task_changes_decision(app, &message, session_store.as_ref());
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_clear_queue_command(&message) {
+        let batch_steps = app.clear_batch_steps();
+        match app.clear_queued_messages() {
+            0 if batch_steps > 0 => {}
+            0 => app.push_agent_message(app.system_text(Text::NoQueuedMessages, &[])),
+            cleared => app.push_agent_message(
+                app.system_text(Text::DroppedQueuedMessages, &[("count", &cleared)]),
+            ),
+        }
+        if batch_steps > 0 {
+            app.push_agent_message(
+                app.system_text(Text::DroppedBatchSteps, &[("batch_steps", &batch_steps)]),
+            );
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_reload_tasks_command(&message) || App::is_keep_tasks_command(&message) {
+        let resume_execution =
+            handle_external_tasks_decision(app, &message, session_store.as_ref());
+        if resume_execution && let Some(active_session) = session_store.as_ref() {
+            *pending_task_write_baseline = None;
+            for system_message in app.start_execution() {
+                app.push_agent_message(system_message);
+            }
+            match orchestration_service.start_next_worker_job_if_any(
+                app,
+                worker_agent_adapters,
+                active_worker_context_key,
+                test_runner_adapter,
+                active_session,
+                model_routing,
+            ) {
+                Ok(Some(job)) => app.push_agent_message(app.system_text(
+                    Text::StartingJob,
+                    &[
+                        ("role", &format!("{:?}", job.role)),
+                        ("task", &job.top_task_id),
+                    ],
+                )),
+                Ok(None) => {}
+                Err(err) => app.push_agent_message(
+                    app.system_text(Text::TaskStatusPersistFailed, &[("err", &err)]),
+                ),
+            }
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_add_final_audit_command(&message) || App::is_remove_final_audit_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("final-audit commands require an active session");
+        if handle_final_audit_command(app, &message, active_session, terminal)? {
+            return Ok(());
+        }
+    }
+
+    if let Some((action, task_id)) = App::parse_manual_task_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("manual task commands require an active session");
+        if app.has_external_tasks_edit() {
+            app.push_agent_message(app.system_text(Text::TasksEditedBeforeSkip, &[]));
+        } else {
+            match app.resolve_task_manually(&task_id, action) {
+                Ok(system_messages) => {
+                    for system_message in system_messages {
+                        app.push_agent_message(system_message);
+                    }
+                    if let Err(err) = active_session.write_tasks(&app.planner_tasks_for_file()) {
+                        app.push_agent_message(app.system_text(
+                            Text::TasksWriteAfterUpdateFailed,
+                            &[("task_id", &task_id), ("err", &err)],
+                        ));
+                    }
+                    match orchestration_service.start_next_worker_job_if_any(
+                        app,
+                        worker_agent_adapters,
+                        active_worker_context_key,
+                        test_runner_adapter,
+                        active_session,
+                        model_routing,
+                    ) {
+                        Ok(Some(job)) => app.push_agent_message(app.system_text(
+                            Text::StartingJob,
+                            &[
+                                ("role", &format!("{:?}", job.role)),
+                                ("task", &job.top_task_id),
+                            ],
+                        )),
+                        Ok(None) => {}
+                        Err(err) => app.push_agent_message(
+                            app.system_text(Text::TaskStatusPersistFailed, &[("err", &err)]),
+                        ),
+                    }
+                }
+                Err(err) => app.push_agent_message(app.system_text(
+                    Text::ManualTaskFailed,
+                    &[("task_id", &task_id), ("err", &err)],
+                )),
+            }
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some((task_id, answer)) = App::parse_unblock_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("/unblock requires an active session");
+        if app.has_external_tasks_edit() {
+            app.push_agent_message(app.system_text(Text::TasksEditedBeforeUnblock, &[]));
+        } else {
+            match app.unblock_task(&task_id, &answer) {
+                Ok(system_messages) => {
+                    for system_message in system_messages {
+                        app.push_agent_message(system_message);
+                    }
+                    if let Err(err) = active_session.write_tasks(&app.planner_tasks_for_file()) {
+                        app.push_agent_message(app.system_text(
+                            Text::TasksWriteAfterUpdateFailed,
+                            &[("task_id", &task_id), ("err", &err)],
+                        ));
+                    }
+                    match orchestration_service.start_next_worker_job_if_any(
+                        app,
+                        worker_agent_adapters,
+                        active_worker_context_key,
+                        test_runner_adapter,
+                        active_session,
+                        model_routing,
+                    ) {
+                        Ok(Some(job)) => app.push_agent_message(app.system_text(
+                            Text::StartingJob,
+                            &[
+                                ("role", &format!("{:?}", job.role)),
+                                ("task", &job.top_task_id),
+                            ],
+                        )),
+                        Ok(None) => {}
+                        Err(err) => app.push_agent_message(
+                            app.system_text(Text::TaskStatusPersistFailed, &[("err", &err)]),
+                        ),
+                    }
+                }
+                Err(err) => app.push_agent_message(
+                    app.system_text(Text::UnblockFailed, &[("task_id", &task_id), ("err", &err)]),
+                ),
+            }
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(command) = App::parse_tasks_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("/tasks requires an active session");
+        let summary = if app.has_external_tasks_edit() {
+            app.system_text(Text::TasksEditedBeforeEdit, &[])
+        } else {
+            apply_tasks_command(app, command, active_session)
+        };
+        app.push_agent_message(summary);
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(speed) = App::parse_speed_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("/speed requires an active session");
+        match speed {
+            Some(speed) => {
+                app.set_speed_profile(speed);
+                app.push_agent_message(match write_session_settings(app, active_session) {
+                    Ok(()) => app.system_text(
+                        Text::SpeedProfileSet,
+                        &[("profile", &speed.label()), ("summary", &speed.summary())],
+                    ),
+                    Err(err) => app.system_text(
+                        Text::SpeedProfileSaveFailed,
+                        &[("profile", &speed.label()), ("err", &err)],
+                    ),
+                });
+            }
+            None => {
+                let current = app.speed_profile();
+                let options = SpeedProfile::ALL
+                    .iter()
+                    .map(|profile| format!("{} ({})", profile.label(), profile.summary()))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                app.push_agent_message(app.system_text(
+                    Text::SpeedProfileStatus,
+                    &[("profile", &current.label()), ("options", &options)],
+                ));
+            }
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(choice) = App::parse_conflict_command(&message) {
+        apply_conflict_command(app, choice, session_store.as_ref());
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(command) = App::parse_jobs_command(&message) {
+        apply_jobs_command(app, command);
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(command) = App::parse_review_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("review commands require an active session");
+        match command {
+            ReviewCommand::Toggle(enabled) => {
+                app.set_review_changes_enabled(enabled);
+                app.push_agent_message(match write_session_settings(app, active_session) {
+                    Ok(()) if enabled => app.system_text(Text::ReviewOn, &[]),
+                    Ok(()) => app.system_text(Text::ReviewOff, &[]),
+                    Err(err) => app.system_text(Text::ReviewGateSaveFailed, &[("err", &err)]),
+                });
+            }
+            ReviewCommand::Status => app.push_agent_message(app.system_text(
+                if app.review_changes_enabled() {
+                    Text::ReviewStatusOn
+                } else {
+                    Text::ReviewStatusOff
+                },
+                &[],
+            )),
+            ReviewCommand::Decide(decision) => match app.resolve_review(decision) {
+                Ok(system_messages) => {
+                    for system_message in system_messages {
+                        app.push_agent_message(system_message);
+                    }
+                    if let Err(err) = active_session.write_tasks(&app.planner_tasks_for_file()) {
+                        app.push_agent_message(
+                            app.system_text(Text::TasksWriteAfterReviewFailed, &[("err", &err)]),
+                        );
+                    }
+                    match orchestration_service.start_next_worker_job_if_any(
+                        app,
+                        worker_agent_adapters,
+                        active_worker_context_key,
+                        test_runner_adapter,
+                        active_session,
+                        model_routing,
+                    ) {
+                        Ok(Some(job)) => app.push_agent_message(app.system_text(
+                            Text::StartingJob,
+                            &[
+                                ("role", &format!("{:?}", job.role)),
+                                ("task", &job.top_task_id),
+                            ],
+                        )),
+                        Ok(None) => {}
+                        Err(err) => app.push_agent_message(
+                            app.system_text(Text::TaskStatusPersistFailed, &[("err", &err)]),
+                        ),
+                    }
+                }
+                Err(err) => app.push_agent_message(
+                    app.system_text(Text::ReviewDecisionFailed, &[("err", &err)]),
+                ),
+            },
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if parse_silent_master_command(&message).is_some() {
+        let active_session = session_store
             .as_ref()
             .expect("silent master commands require an active session");
         if handle_silent_master_command(
@@ -1638,10 +3808,7 @@ fn submit_user_message_with_runtime<B: Backend>(
 
     if App::is_new_master_command(&message) {
         if app.is_execution_busy() {
-            app.push_agent_message(
-                "System: Cannot start a new master session while worker execution is running. Wait for active jobs to finish first."
-                    .to_string(),
-            );
+            app.push_agent_message(app.system_text(Text::NewSessionBlocked, &[]));
             let size = terminal.size()?;
             let screen = Rect::new(0, 0, size.width, size.height);
             let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1670,7 +3837,61 @@ fn submit_user_message_with_runtime<B: Backend>(
         app.set_task_check_in_progress(false);
         app.set_master_in_progress(false);
         app.reset_execution_for_session_switch();
-        app.push_agent_message("System: Started new master session".to_string());
+        app.push_agent_message(app.system_text(Text::StartedNewSession, &[]));
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(template_name) = App::new_from_template_name(&message) {
+        if app.is_execution_busy() {
+            app.push_agent_message(app.system_text(Text::TemplateSessionBlocked, &[]));
+        } else if template_name.is_empty() {
+            app.push_agent_message(available_templates_message(app));
+        } else {
+            match start_session_from_template(cwd, template_name) {
+                Ok(prepared) => {
+                    activate_prepared_session(
+                        app,
+                        session_store,
+                        prepared,
+                        master_adapter,
+                        master_report_adapter,
+                        project_info_adapter,
+                        worker_agent_adapters,
+                        active_worker_context_key,
+                        pending_task_write_baseline,
+                        docs_attach_in_flight,
+                        master_session_intro_needed,
+                        master_report_session_intro_needed,
+                        pending_master_message_after_project_info,
+                        project_info_in_flight,
+                        project_info_stage,
+                        project_info_text,
+                        master_report_in_flight,
+                        pending_master_report_prompts,
+                        master_report_transcript,
+                        task_check_in_flight,
+                        task_check_baseline,
+                    );
+                    if let Some(active_session) = session_store.as_ref() {
+                        app.push_agent_message(app.system_text(
+                            Text::StartedTemplateSession,
+                            &[
+                                ("session", &active_session.session_dir().display()),
+                                ("template_name", &template_name),
+                            ],
+                        ));
+                    }
+                }
+                Err(err) => app.push_agent_message(app.system_text(
+                    Text::TemplateSessionFailed,
+                    &[("template_name", &template_name), ("err", &err)],
+                )),
+            }
+        }
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1678,21 +3899,45 @@ fn submit_user_message_with_runtime<B: Backend>(
         return Ok(());
     }
 
-    if App::is_attach_docs_command(&message) {
+    if let Some(request) = App::parse_attach_docs_command(&message) {
         let active_session = session_store
             .as_ref()
             .expect("/attach-docs requires an active session");
+        let tasks_file = active_session.tasks_file().display().to_string();
         if *docs_attach_in_flight {
-            app.push_agent_message(
-                "System: Docs attach is already running. Please wait for completion.".to_string(),
-            );
+            app.push_agent_message(app.system_text(Text::DocsAttachAlreadyRunning, &[]));
+        } else if app.is_safe_mode() {
+            app.push_agent_message(app.system_text(Text::AttachDocsSafeMode, &[]));
+        } else if let Some(task_id) = request.task_id.as_deref()
+            && app.top_level_task(task_id).is_none()
+        {
+            app.push_agent_message(app.system_text(Text::NoTaskWithId, &[("task_id", &task_id)]));
         } else {
-            let prompt =
-                app.prepare_attach_docs_prompt(&active_session.tasks_file().display().to_string());
-            docs_attach_adapter.send_prompt(prompt);
+            let mut prompt = match request.task_id.as_deref() {
+                Some(task_id) => app.prepare_attach_docs_prompt_for_task(&tasks_file, task_id),
+                None => app.prepare_attach_docs_prompt(&tasks_file),
+            };
+            app.set_docs_attach_baseline(std::fs::read_to_string(active_session.tasks_file()).ok());
+            if request.snapshot {
+                // Snapshots are files in the session, so the run must really happen.
+                prompt.push('\n');
+                prompt.push_str(&doc_snapshots::snapshot_prompt_instructions(
+                    active_session.session_dir(),
+                ));
+                app.response_cache_mut().forget(CachedCall::DocsAttach);
+                docs_attach_adapter.send_prompt(prompt);
+            } else {
+                send_cacheable_tasks_prompt(
+                    app,
+                    docs_attach_adapter,
+                    CachedCall::DocsAttach,
+                    prompt,
+                    active_session,
+                );
+            }
             *docs_attach_in_flight = true;
             app.set_docs_attach_in_progress(true);
-            app.push_agent_message("System: Started documentation attach sub-agent.".to_string());
+            app.push_agent_message(app.system_text(Text::DocsAttachStarted, &[]));
         }
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
@@ -1701,33 +3946,175 @@ fn submit_user_message_with_runtime<B: Backend>(
         return Ok(());
     }
 
-    if App::is_quit_command(&message) {
-        app.quit();
+    if let Some(mode) = App::parse_quit_command(&message) {
+        if let Some(message) = app.request_shutdown(mode) {
+            app.push_agent_message(message);
+        }
         return Ok(());
     }
 
     if App::is_resume_command(&message) {
         match SessionStore::list_sessions() {
             Ok(sessions) if sessions.is_empty() => {
-                app.push_agent_message("System: No saved sessions found.".to_string());
+                app.push_agent_message(app.system_text(Text::NoSavedSessions, &[]));
             }
             Ok(sessions) => {
                 let current_session_dir = session_store.as_ref().map(SessionStore::session_dir);
-                let options = build_resume_options(sessions, current_session_dir, Some(cwd));
-                if options.is_empty() {
-                    app.push_agent_message(
-                        "System: No other saved sessions found to resume.".to_string(),
-                    );
+                let (options, other_workspaces): (Vec<_>, Vec<_>) =
+                    build_resume_options(sessions, current_session_dir, None)
+                        .into_iter()
+                        .partition(|option| Path::new(option.workspace.trim()) == cwd);
+                if options.is_empty() && other_workspaces.is_empty() {
+                    app.push_agent_message(app.system_text(Text::NoOtherSessionsToResume, &[]));
                 } else {
-                    app.open_resume_picker(options);
-                    app.push_agent_message(
-                        "System: Select a session in the resume picker and press Enter or Space."
-                            .to_string(),
-                    );
+                    let previews = resume_previews(options.iter().chain(&other_workspaces));
+                    let message = if options.is_empty() {
+                        app.system_text(Text::ResumeOtherWorkspacesOnly, &[])
+                    } else if other_workspaces.is_empty() {
+                        app.system_text(Text::ResumePickerHint, &[])
+                    } else {
+                        app.system_text(Text::ResumePickerHintWithOthers, &[])
+                    };
+                    app.open_resume_picker_with_other_workspaces(options, other_workspaces);
+                    app.set_resume_picker_previews(previews);
+                    app.push_agent_message(message.to_string());
                 }
             }
             Err(err) => {
-                app.push_agent_message(format!("System: Failed to list sessions: {err}"));
+                app.push_agent_message(app.system_text(Text::ListSessionsFailed, &[("err", &err)]));
+            }
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_switch_command(&message) {
+        let current_session_dir = session_store.as_ref().map(SessionStore::session_dir);
+        match SessionStore::list_sessions().map(|sessions| {
+            build_resume_options(sessions, current_session_dir, Some(cwd))
+                .into_iter()
+                .next()
+        }) {
+            Ok(Some(selection)) => {
+                return resume_session(
+                    app,
+                    session_store,
+                    selection,
+                    master_adapter,
+                    master_report_adapter,
+                    project_info_adapter,
+                    worker_agent_adapters,
+                    active_worker_context_key,
+                    pending_task_write_baseline,
+                    docs_attach_in_flight,
+                    master_session_intro_needed,
+                    master_report_session_intro_needed,
+                    pending_master_message_after_project_info,
+                    project_info_in_flight,
+                    project_info_stage,
+                    project_info_text,
+                    master_report_in_flight,
+                    pending_master_report_prompts,
+                    master_report_transcript,
+                    task_check_in_flight,
+                    task_check_baseline,
+                    terminal,
+                );
+            }
+            Ok(None) => app.push_agent_message(app.system_text(Text::NoOtherSessionToSwitch, &[])),
+            Err(err) => {
+                app.push_agent_message(app.system_text(Text::ListSessionsFailed, &[("err", &err)]));
+            }
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(action) = App::parse_lock_command(&message) {
+        match (action, app.locked_session().is_some()) {
+            (Some(LockAction::Takeover), true) => {
+                let locked = app
+                    .take_locked_session()
+                    .expect("locked session was just checked");
+                let session_dir = PathBuf::from(&locked.selection.session_dir);
+                let still_running = match session_lock::inspect(&session_dir) {
+                    LockState::Held(owner) => owner.is_running() != Some(false),
+                    LockState::Free | LockState::Stale(_) => false,
+                };
+                if let Err(err) = session_lock::write_owner(&session_dir, &LockOwner::current()) {
+                    app.push_agent_message(app.system_text(
+                        Text::LockTakeoverFailed,
+                        &[("session", &session_dir.display()), ("err", &err)],
+                    ));
+                } else {
+                    if still_running {
+                        app.push_agent_message(app.system_text(
+                            Text::TookOverSession,
+                            &[
+                                ("session", &session_dir.display()),
+                                ("owner", &locked.owner.describe()),
+                            ],
+                        ));
+                    }
+                    return resume_session(
+                        app,
+                        session_store,
+                        locked.selection,
+                        master_adapter,
+                        master_report_adapter,
+                        project_info_adapter,
+                        worker_agent_adapters,
+                        active_worker_context_key,
+                        pending_task_write_baseline,
+                        docs_attach_in_flight,
+                        master_session_intro_needed,
+                        master_report_session_intro_needed,
+                        pending_master_message_after_project_info,
+                        project_info_in_flight,
+                        project_info_stage,
+                        project_info_text,
+                        master_report_in_flight,
+                        pending_master_report_prompts,
+                        master_report_transcript,
+                        task_check_in_flight,
+                        task_check_baseline,
+                        terminal,
+                    );
+                }
+            }
+            (Some(LockAction::Observe), true) => {
+                let locked = app
+                    .take_locked_session()
+                    .expect("locked session was just checked");
+                app.observe_on_exit(
+                    PathBuf::from(&locked.selection.session_dir),
+                    app.system_text(
+                        Text::FollowingLockedSession,
+                        &[("owner", &locked.owner.describe())],
+                    ),
+                );
+            }
+            (Some(_), false) => {
+                app.push_agent_message(app.system_text(Text::NoLockedSessionWaiting, &[]))
+            }
+            (None, _) => {
+                let held = session_store
+                    .as_ref()
+                    .map(|store| {
+                        i18n::fill(
+                            app.locale(),
+                            Text::LockHeldHere,
+                            &[("session", &store.session_dir().display())],
+                        )
+                    })
+                    .unwrap_or_default();
+                app.push_agent_message(app.system_text(Text::LockUsage, &[("held", &held)]));
             }
         }
         let size = terminal.size()?;
@@ -1740,10 +4127,7 @@ fn submit_user_message_with_runtime<B: Backend>(
     if is_backend_command(&message) {
         let options = backend_picker_options(*selected_backend);
         app.open_backend_picker(options);
-        app.push_agent_message(
-            "System: Select a backend in the picker and press Enter or Space (Backspace cancels)."
-                .to_string(),
-        );
+        app.push_agent_message(app.system_text(Text::BackendPickerHint, &[]));
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1754,14 +4138,16 @@ fn submit_user_message_with_runtime<B: Backend>(
     if App::is_toggle_tests_command(&message) {
         let enabled = app.toggle_tests_mode();
         match persist_global_tests_mode_enabled(enabled) {
-            Ok(config_file) => app.push_agent_message(format!(
-                "System: Tests mode is now {}. Saved to {}.",
-                if enabled { "ON" } else { "OFF" },
-                config_file.display()
+            Ok(config_file) => app.push_agent_message(app.system_text(
+                Text::TestsModeSaved,
+                &[
+                    ("mode", &if enabled { "ON" } else { "OFF" }),
+                    ("path", &config_file.display()),
+                ],
             )),
-            Err(err) => app.push_agent_message(format!(
-                "System: Tests mode is now {} for this run, but persistence to config.toml failed (default path is ~/.agentbob/config.toml; legacy fallbacks: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}.",
-                if enabled { "ON" } else { "OFF" }
+            Err(err) => app.push_agent_message(app.system_text(
+                Text::TestsModeSaveFailed,
+                &[("mode", &if enabled { "ON" } else { "OFF" }), ("err", &err)],
             )),
         }
         let size = terminal.size()?;
@@ -1776,13 +4162,10 @@ fn submit_user_message_with_runtime<B: Backend>(
             .as_ref()
             .expect("/planner requires an active session");
         if let Ok(markdown) = active_session.read_planner_markdown() {
-            app.set_planner_markdown(markdown);
+            app.reconcile_planner_markdown(markdown);
         }
         app.set_right_pane_mode(RightPaneMode::PlannerMarkdown);
-        app.push_agent_message(
-            "System: Planner mode enabled. The right pane now shows planner.md for collaborative markdown planning."
-                .to_string(),
-        );
+        app.push_agent_message(app.system_text(Text::PlannerModeEnabled, &[]));
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1790,12 +4173,8 @@ fn submit_user_message_with_runtime<B: Backend>(
         return Ok(());
     }
 
-    if App::is_skip_plan_command(&message) {
-        app.set_right_pane_mode(RightPaneMode::TaskList);
-        app.push_agent_message(
-            "System: Skip-plan mode enabled. The right pane now shows planner tasks for execution."
-                .to_string(),
-        );
+    if App::is_files_command(&message) {
+        toggle_file_tree_view(app);
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1803,51 +4182,292 @@ fn submit_user_message_with_runtime<B: Backend>(
         return Ok(());
     }
 
-    if App::is_convert_command(&message) {
-        let active_session = session_store
-            .as_ref()
-            .expect("/convert requires an active session");
-        if !app.is_planner_mode() {
+    if let Some(command) = App::parse_console_command(&message) {
+        let keep = command == ConsoleCommand::Keep;
+        let reply = app.handle_console_command(command, cwd);
+        app.push_agent_message(reply);
+        if keep
+            && let Some(active_session) = session_store.as_ref()
+            && let Err(err) = active_session.write_rolling_context(&app.rolling_context_entries())
+        {
             app.push_agent_message(
-                "System: /convert is only available in planner mode. Use /planner first."
-                    .to_string(),
+                app.system_text(Text::RollingContextPersistFailed, &[("err", &err)]),
             );
-            let size = terminal.size()?;
-            let screen = Rect::new(0, 0, size.width, size.height);
-            let max_scroll = ui::chat_max_scroll(screen, app);
-            app.set_chat_scroll(max_scroll);
-            return Ok(());
-        }
-
-        match persist_planner_markdown_if_changed(app, Some(active_session)) {
-            PlannerPersistResult::Persisted | PlannerPersistResult::Unchanged => {}
-            PlannerPersistResult::Deferred => {
-                app.push_agent_message(
-                    "System: Could not persist planner.md before /convert; conversion aborted."
-                        .to_string(),
-                );
-                let size = terminal.size()?;
-                let screen = Rect::new(0, 0, size.width, size.height);
-                let max_scroll = ui::chat_max_scroll(screen, app);
-                app.set_chat_scroll(max_scroll);
-                return Ok(());
-            }
         }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
 
-        app.set_right_pane_mode(RightPaneMode::TaskList);
-        app.push_agent_message(
-            "System: Converting planner.md into tasks.json in task mode...".to_string(),
-        );
+    if App::is_stats_command(&message) {
+        toggle_stats_view(app);
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some((task_id, profile)) = App::parse_task_model_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("/task-model requires an active session");
+        let profiles = model_routing.profile_labels();
+        match app.top_level_task(&task_id) {
+            None => app
+                .push_agent_message(app.system_text(Text::NoTaskWithId, &[("task_id", &task_id)])),
+            Some((top_id, title)) => match profile.as_deref() {
+                None => app.push_agent_message(
+                    app.system_text(
+                        Text::TaskModelStatus,
+                        &[
+                            ("title", &title),
+                            ("top_id", &top_id),
+                            (
+                                "model",
+                                &app.task_model(&top_id)
+                                    .unwrap_or("the default agent routing"),
+                            ),
+                            ("profiles", &profiles.join(", ")),
+                        ],
+                    ),
+                ),
+                Some(profile)
+                    if !profile.eq_ignore_ascii_case("default")
+                        && !profiles.iter().any(|label| label == profile) =>
+                {
+                    app.push_agent_message(app.system_text(
+                        Text::UnknownModelProfile,
+                        &[("profile", &profile), ("profiles", &profiles.join(", "))],
+                    ))
+                }
+                Some(profile) => {
+                    let profile =
+                        (!profile.eq_ignore_ascii_case("default")).then(|| profile.to_string());
+                    let summary = match profile.as_deref() {
+                        Some(profile) => app.system_text(
+                            Text::TaskModelSet,
+                            &[
+                                ("title", &title),
+                                ("top_id", &top_id),
+                                ("profile", &profile),
+                            ],
+                        ),
+                        None => app.system_text(
+                            Text::TaskModelReset,
+                            &[("title", &title), ("top_id", &top_id)],
+                        ),
+                    };
+                    app.set_task_model(&top_id, profile);
+                    app.push_agent_message(match write_session_settings(app, active_session) {
+                        Ok(()) => summary,
+                        Err(err) => format!("{summary} Saving it to the session failed: {err}"),
+                    });
+                }
+            },
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(task_id) = App::parse_logs_command(&message) {
+        open_worker_log_picker(app, session_store.as_ref(), task_id.as_deref());
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(task_id) = App::parse_rerun_command(&message) {
+        if let Err(err) = app.open_rerun_editor(&task_id) {
+            app.push_agent_message(app.system_text(
+                Text::RerunUnavailable,
+                &[("task_id", &task_id), ("err", &err)],
+            ));
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_preview_command(&message) {
+        let enabled = app.toggle_master_prompt_preview();
+        app.push_agent_message(if enabled {
+            app.system_text(Text::PromptPreviewOn, &[])
+        } else {
+            app.system_text(Text::PromptPreviewOff, &[])
+        });
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(command) = App::parse_cache_command(&message) {
+        let cache = app.response_cache_mut();
+        let reply = match command {
+            CacheCommand::Stats => {
+                let stats = cache.stats();
+                app.system_text(
+                    Text::ResponseCacheStats,
+                    &[
+                        ("hits", &stats.hits),
+                        ("misses", &stats.misses),
+                        ("entries", &stats.entries),
+                        ("kb", &stats.bytes.div_ceil(1024)),
+                    ],
+                )
+            }
+            CacheCommand::Purge => match cache.purge() {
+                Ok(removed) => app.system_text(Text::ResponseCachePurged, &[("removed", &removed)]),
+                Err(err) => app.system_text(Text::ResponseCachePurgeFailed, &[("err", &err)]),
+            },
+            CacheCommand::Usage => app.system_text(Text::CacheUsage, &[]),
+        };
+        app.push_agent_message(reply);
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(command) = App::parse_pin_models_command(&message)
+        && let Some(active_session) = session_store.as_ref()
+    {
+        let reply = pin_session_models(app, active_session, model_routing, command);
+        app.push_agent_message(reply);
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(command) = App::parse_plugin_command(&message) {
+        let reply = match command {
+            _ if app.is_safe_mode() => app.system_text(Text::PluginsSafeMode, &[]),
+            PluginCommand::List => app.plugin_list_message(),
+            PluginCommand::Run { name, input } => {
+                let session_dir = session_store
+                    .as_ref()
+                    .map(|store| store.session_dir().display().to_string());
+                app.run_plugin_command(&name, &input, session_dir)
+                    .unwrap_or_else(|err| {
+                        app.system_text(Text::PluginRunFailed, &[("name", &name), ("err", &err)])
+                    })
+            }
+        };
+        app.push_agent_message(reply);
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_summaries_command(&message) {
+        let reply = if app.is_task_summaries_mode() {
+            app.set_right_pane_mode(RightPaneMode::TaskList);
+            app.system_text(Text::SummariesHidden, &[])
+        } else {
+            app.set_right_pane_mode(RightPaneMode::TaskSummaries);
+            app.system_text(Text::SummariesShown, &[])
+        };
+        app.push_agent_message(reply.to_string());
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if let Some(command) = App::parse_context_command(&message) {
+        let reply = app.apply_context_command(command);
+        app.push_agent_message(reply);
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_verbose_command(&message) {
+        let verbose = app.toggle_verbose_output();
+        app.push_agent_message(if verbose {
+            app.system_text(
+                Text::VerboseOn,
+                &[("filtered", &app.filtered_output_lines())],
+            )
+        } else {
+            app.system_text(Text::VerboseOff, &[])
+        });
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_skip_plan_command(&message) {
+        app.set_right_pane_mode(RightPaneMode::TaskList);
+        app.push_agent_message(app.system_text(Text::SkipPlanEnabled, &[]));
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
+    if App::is_convert_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("/convert requires an active session");
+        if !app.is_planner_mode() {
+            app.push_agent_message(app.system_text(Text::ConvertNeedsPlanner, &[]));
+            let size = terminal.size()?;
+            let screen = Rect::new(0, 0, size.width, size.height);
+            let max_scroll = ui::chat_max_scroll(screen, app);
+            app.set_chat_scroll(max_scroll);
+            return Ok(());
+        }
+
+        match persist_planner_markdown_if_changed(app, Some(active_session)) {
+            PlannerPersistResult::Persisted | PlannerPersistResult::Unchanged => {}
+            result @ (PlannerPersistResult::Deferred | PlannerPersistResult::Conflicted) => {
+                app.push_agent_message(
+                    if matches!(result, PlannerPersistResult::Conflicted) {
+                        app.system_text(Text::ConvertPlannerConflict, &[])
+                    } else {
+                        app.system_text(Text::ConvertPersistFailed, &[])
+                    }
+                    .to_string(),
+                );
+                let size = terminal.size()?;
+                let screen = Rect::new(0, 0, size.width, size.height);
+                let max_scroll = ui::chat_max_scroll(screen, app);
+                app.set_chat_scroll(max_scroll);
+                return Ok(());
+            }
+        }
 
-        let with_intro = prompt_service.build_convert_master_prompt(
+        send_convert_prompt(
             app,
             active_session,
+            master_adapter,
             project_info_text.as_deref(),
             master_session_intro_needed,
+            pending_task_write_baseline,
         );
-        master_adapter.send_prompt(with_intro);
-        app.set_master_in_progress(true);
-        *pending_task_write_baseline = orchestration_service.capture_tasks_baseline(active_session);
 
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
@@ -1856,11 +4476,54 @@ fn submit_user_message_with_runtime<B: Backend>(
         return Ok(());
     }
 
+    if let Some(import) = App::parse_import_plan_command(&message) {
+        let active_session = session_store
+            .as_ref()
+            .expect("/import-plan requires an active session");
+        match plan_import::find(cwd) {
+            None => app.push_agent_message(app.system_text(Text::NoPlanToImport, &[])),
+            Some(plan) => {
+                let merged = plan_import::merge_into_planner(app.planner_markdown(), &plan);
+                app.set_planner_markdown(merged);
+                match persist_planner_markdown_if_changed(app, Some(active_session)) {
+                    PlannerPersistResult::Persisted | PlannerPersistResult::Unchanged => {
+                        app.push_agent_message(
+                            app.system_text(Text::ImportedPlan, &[("path", &plan.path.display())]),
+                        );
+                        if import == PlanImport::Tasks {
+                            send_convert_prompt(
+                                app,
+                                active_session,
+                                master_adapter,
+                                project_info_text.as_deref(),
+                                master_session_intro_needed,
+                                pending_task_write_baseline,
+                            );
+                        } else {
+                            app.set_right_pane_mode(RightPaneMode::PlannerMarkdown);
+                            app.push_agent_message(app.system_text(Text::ImportedPlanHint, &[]));
+                        }
+                    }
+                    PlannerPersistResult::Conflicted => {
+                        app.push_agent_message(app.system_text(Text::ImportPlanConflict, &[]))
+                    }
+                    PlannerPersistResult::Deferred => {
+                        app.push_agent_message(app.system_text(Text::ImportPlanWriteFailed, &[]))
+                    }
+                }
+            }
+        }
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
+
     if message.trim().starts_with('/') && !is_known_slash_command(&message) {
-        app.push_agent_message(format!(
-            "System: Unknown command `{}`. Type `/` to see available commands.",
-            message.trim()
-        ));
+        app.push_agent_message(
+            app.system_text(Text::UnknownCommand, &[("command", &message.trim())]),
+        );
         let size = terminal.size()?;
         let screen = Rect::new(0, 0, size.width, size.height);
         let max_scroll = ui::chat_max_scroll(screen, app);
@@ -1878,22 +4541,20 @@ fn submit_user_message_with_runtime<B: Backend>(
                     &cwd.display().to_string(),
                     &message,
                     &active_session.project_info_file().display().to_string(),
+                    WorkspaceIgnore::load(cwd).metaagent_patterns(),
+                    app.project_info_scope(),
                 );
                 project_info_adapter.send_prompt(prompt);
                 *project_info_in_flight = true;
                 *project_info_stage = Some(ProjectInfoStage::GatheringInfo);
                 *pending_master_message_after_project_info = Some(message.clone());
-                app.push_agent_message(
-                    "System: Gathering project context before contacting master.".to_string(),
-                );
+                app.push_agent_message(app.system_text(Text::GatheringProjectContext, &[]));
             } else {
                 *pending_master_message_after_project_info = Some(message.clone());
-                app.push_agent_message(
-                    "System: Project context is still gathering; your message will be sent when ready."
-                        .to_string(),
-                );
+                app.push_agent_message(app.system_text(Text::ProjectContextStillGathering, &[]));
             }
         } else {
+            let intro_was_needed = *master_session_intro_needed;
             let with_intro = prompt_service.build_master_prompt_for_message(
                 app,
                 &message,
@@ -1901,37 +4562,69 @@ fn submit_user_message_with_runtime<B: Backend>(
                 project_info_text.as_deref(),
                 master_session_intro_needed,
             );
-            master_adapter.send_prompt(with_intro);
-            app.set_master_in_progress(true);
+            send_or_preview_master_prompt(
+                app,
+                master_adapter,
+                with_intro,
+                &message,
+                intro_was_needed && !*master_session_intro_needed,
+            );
         }
     }
-    if App::is_start_execution_command(&message) {
+    let direct_task = App::direct_instruction(&message).and_then(|instruction| {
+        let active_session = session_store
+            .as_ref()
+            .expect("/direct requires an active session");
+        add_direct_task(app, instruction, active_session)
+    });
+    let scoped_start_task = App::parse_start_task_command(&message).or(direct_task);
+    let retry_task = App::parse_retry_command(&message);
+    if scoped_start_task.is_some()
+        || retry_task.is_some()
+        || App::is_start_execution_command(&message)
+    {
         let active_session = session_store
             .as_ref()
             .expect("start execution requires an active session");
-        if is_slash_start_command(&message) {
-            app.push_agent_message("System: Started execution".to_string());
-        }
         *pending_task_write_baseline = None;
-        for system_message in app.start_execution() {
-            app.push_agent_message(system_message);
-        }
-        match orchestration_service.start_next_worker_job_if_any(
-            app,
-            worker_agent_adapters,
-            active_worker_context_key,
-            test_runner_adapter,
-            active_session,
-            model_routing,
-        ) {
-            Ok(Some(job)) => app.push_agent_message(format!(
-                "System: Starting {:?} for task #{}.",
-                job.role, job.top_task_id
-            )),
-            Ok(None) => {}
-            Err(err) => app.push_agent_message(format!(
-                "System: Failed to persist runtime task status to tasks.json: {err}"
-            )),
+        let started = match (scoped_start_task, retry_task) {
+            (Some(task_id), _) => app.start_execution_scoped(&task_id),
+            (None, Some(task_id)) => app.retry_task(&task_id),
+            (None, None) => {
+                if is_slash_start_command(&message) {
+                    app.push_agent_message(app.system_text(Text::StartedExecution, &[]));
+                }
+                Ok(app.start_execution())
+            }
+        };
+        match started {
+            Ok(system_messages) => {
+                for system_message in system_messages {
+                    app.push_agent_message(system_message);
+                }
+                match orchestration_service.start_next_worker_job_if_any(
+                    app,
+                    worker_agent_adapters,
+                    active_worker_context_key,
+                    test_runner_adapter,
+                    active_session,
+                    model_routing,
+                ) {
+                    Ok(Some(job)) => app.push_agent_message(app.system_text(
+                        Text::StartingJob,
+                        &[
+                            ("role", &format!("{:?}", job.role)),
+                            ("task", &job.top_task_id),
+                        ],
+                    )),
+                    Ok(None) => {}
+                    Err(err) => app.push_agent_message(
+                        app.system_text(Text::TaskStatusPersistFailed, &[("err", &err)]),
+                    ),
+                }
+            }
+            Err(err) => app
+                .push_agent_message(app.system_text(Text::StartExecutionFailed, &[("err", &err)])),
         }
     } else {
         *pending_task_write_baseline = session_store
@@ -2024,6 +4717,12 @@ struct PreparedResumeSession {
     planner_markdown: String,
     rolling_context: Vec<String>,
     project_info_text: Option<String>,
+    audit_rubric: Option<String>,
+    review_changes: bool,
+    task_models: BTreeMap<String, String>,
+    speed: SpeedProfile,
+    interrupted_run: Option<InterruptedRunFile>,
+    ui_state: SessionUiStateFile,
 }
 
 fn prepare_resumed_session(
@@ -2032,6 +4731,10 @@ fn prepare_resumed_session(
 ) -> io::Result<PreparedResumeSession> {
     let selected_path = PathBuf::from(&selection.session_dir);
     let store = SessionStore::open_existing(cwd, &selected_path)?;
+    prepare_session_for_activation(store)
+}
+
+fn prepare_session_for_activation(store: SessionStore) -> io::Result<PreparedResumeSession> {
     let tasks = store.read_tasks().map_err(|err| {
         io::Error::new(
             io::ErrorKind::InvalidData,
@@ -2057,6 +4760,25 @@ fn prepare_resumed_session(
             .ok()
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty()),
+        audit_rubric: store
+            .read_session_template()
+            .ok()
+            .flatten()
+            .map(|template| template.audit_rubric),
+        review_changes: store
+            .read_session_settings()
+            .map(|settings| settings.review_changes)
+            .unwrap_or_default(),
+        task_models: store
+            .read_session_settings()
+            .map(|settings| settings.task_models)
+            .unwrap_or_default(),
+        speed: store
+            .read_session_settings()
+            .map(|settings| settings.speed)
+            .unwrap_or_default(),
+        interrupted_run: store.read_interrupted_run().ok().flatten(),
+        ui_state: store.read_ui_state().unwrap_or_default(),
         store,
         tasks,
     })
@@ -2087,13 +4809,59 @@ fn resume_session<B: Backend>(
     task_check_baseline: &mut Option<String>,
     terminal: &mut Terminal<B>,
 ) -> io::Result<()> {
-    let cwd = std::env::current_dir()?;
+    match session_lock::acquire(Path::new(&selection.session_dir)) {
+        Ok(LockState::Held(owner)) => {
+            app.offer_locked_session(selection, owner);
+            let size = terminal.size()?;
+            let screen = Rect::new(0, 0, size.width, size.height);
+            let max_scroll = ui::chat_max_scroll(screen, app);
+            app.set_chat_scroll(max_scroll);
+            return Ok(());
+        }
+        Ok(LockState::Stale(owner)) => app.push_agent_message(app.system_text(
+            Text::ClearedStaleLock,
+            &[
+                ("session", &selection.session_dir),
+                ("owner", &owner.describe()),
+            ],
+        )),
+        Ok(LockState::Free) => {}
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::LockWriteFailed, &[("err", &err)]))
+        }
+    }
+    let changes_workspace = app.is_other_workspace(&selection);
+    let cwd = if changes_workspace {
+        PathBuf::from(selection.workspace.trim())
+    } else if let Some(root) = app.workspace_root() {
+        root.to_path_buf()
+    } else {
+        std::env::current_dir()?
+    };
+    // The process working directory stays where Bob started; the run loop points agents
+    // and test runs at the new workspace once `set_workspace_root` below records it.
+    if changes_workspace && !cwd.is_dir() {
+        session_lock::abandon(Path::new(&selection.session_dir));
+        app.push_agent_message(app.system_text(
+            Text::ResumeWorkspaceMissing,
+            &[
+                ("session", &selection.session_dir),
+                ("workspace", &cwd.display()),
+            ],
+        ));
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let max_scroll = ui::chat_max_scroll(screen, app);
+        app.set_chat_scroll(max_scroll);
+        return Ok(());
+    }
     let prepared = match prepare_resumed_session(&cwd, &selection) {
         Ok(prepared) => prepared,
         Err(err) => {
-            app.push_agent_message(format!(
-                "System: Failed to resume session {}: {err}",
-                selection.session_dir
+            session_lock::abandon(Path::new(&selection.session_dir));
+            app.push_agent_message(app.system_text(
+                Text::ResumeSessionFailed,
+                &[("session", &selection.session_dir), ("err", &err)],
             ));
             let size = terminal.size()?;
             let screen = Rect::new(0, 0, size.width, size.height);
@@ -2103,59 +4871,259 @@ fn resume_session<B: Backend>(
         }
     };
 
-    *session_store = Some(prepared.store);
-    let active_session = session_store
-        .as_ref()
-        .expect("resumed session should be available");
-
-    master_adapter.reset_session();
-    master_report_adapter.reset_session();
-    project_info_adapter.reset_session();
-    worker_agent_adapters.clear();
-    *active_worker_context_key = None;
-    *master_session_intro_needed = true;
-    *master_report_session_intro_needed = true;
-    *pending_task_write_baseline = None;
-    *pending_master_message_after_project_info = None;
-    *docs_attach_in_flight = false;
-    *project_info_in_flight = false;
-    *project_info_stage = None;
-    reset_master_report_runtime(
+    activate_prepared_session(
+        app,
+        session_store,
+        prepared,
+        master_adapter,
+        master_report_adapter,
+        project_info_adapter,
+        worker_agent_adapters,
+        active_worker_context_key,
+        pending_task_write_baseline,
+        docs_attach_in_flight,
+        master_session_intro_needed,
+        master_report_session_intro_needed,
+        pending_master_message_after_project_info,
+        project_info_in_flight,
+        project_info_stage,
+        project_info_text,
         master_report_in_flight,
         pending_master_report_prompts,
         master_report_transcript,
+        task_check_in_flight,
+        task_check_baseline,
     );
-    reset_task_check_runtime(task_check_in_flight, task_check_baseline);
-
-    app.reset_execution_for_session_switch();
-    app.set_task_check_in_progress(false);
-    app.set_docs_attach_in_progress(false);
-    app.set_master_in_progress(false);
-
+    let active_session = session_store
+        .as_ref()
+        .expect("resumed session should be available");
+    app.push_agent_message(app.system_text(
+        Text::ResumedSession,
+        &[("session", &active_session.session_dir().display())],
+    ));
+    if changes_workspace {
+        app.set_workspace_root(cwd.clone());
+        app.push_agent_message(
+            app.system_text(Text::WorkingDirectoryChanged, &[("path", &cwd.display())]),
+        );
+    }
+
+    let size = terminal.size()?;
+    let screen = Rect::new(0, 0, size.width, size.height);
+    let max_scroll = ui::chat_max_scroll(screen, app);
+    app.set_chat_scroll(max_scroll);
+    Ok(())
+}
+
+/// Creates a new session seeded from a template. The template is validated before any
+/// session directory is created.
+fn start_session_from_template(cwd: &Path, name: &str) -> io::Result<PreparedResumeSession> {
+    let template = session_template::load_template(name)?;
+    let store = SessionStore::initialize(cwd)?;
+    template.apply_to_session(name, &store)?;
+    prepare_session_for_activation(store)
+}
+
+fn available_templates_message(app: &App) -> String {
+    let dir = session_template::templates_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|_| "~/.agentbob/templates".to_string());
+    match session_template::list_templates() {
+        Ok(names) if names.is_empty() => {
+            app.system_text(Text::TemplateUsageNoTemplates, &[("dir", &dir)])
+        }
+        Ok(names) => app.system_text(Text::TemplateUsage, &[("names", &names.join(", "))]),
+        Err(err) => app.system_text(Text::ListTemplatesFailed, &[("dir", &dir), ("err", &err)]),
+    }
+}
+
+/// Switches the app and all per-session runtime state over to a prepared session.
+#[allow(clippy::too_many_arguments)]
+fn activate_prepared_session(
+    app: &mut App,
+    session_store: &mut Option<SessionStore>,
+    prepared: PreparedResumeSession,
+    master_adapter: &CodexAdapter,
+    master_report_adapter: &CodexAdapter,
+    project_info_adapter: &CodexAdapter,
+    worker_agent_adapters: &mut HashMap<String, CodexAdapter>,
+    active_worker_context_key: &mut Option<String>,
+    pending_task_write_baseline: &mut Option<TaskWriteBaseline>,
+    docs_attach_in_flight: &mut bool,
+    master_session_intro_needed: &mut bool,
+    master_report_session_intro_needed: &mut bool,
+    pending_master_message_after_project_info: &mut Option<String>,
+    project_info_in_flight: &mut bool,
+    project_info_stage: &mut Option<ProjectInfoStage>,
+    project_info_text: &mut Option<String>,
+    master_report_in_flight: &mut bool,
+    pending_master_report_prompts: &mut VecDeque<String>,
+    master_report_transcript: &mut Vec<String>,
+    task_check_in_flight: &mut bool,
+    task_check_baseline: &mut Option<String>,
+) {
+    if let Some(previous) = session_store.as_ref() {
+        save_session_ui_state(app, previous);
+    }
+    hold_session_lock(app, &prepared.store);
+    audit_trail::set_session(Some(prepared.store.audit_trail_file()));
+    *session_store = Some(prepared.store);
+
+    master_adapter.reset_session();
+    master_report_adapter.reset_session();
+    project_info_adapter.reset_session();
+    worker_agent_adapters.clear();
+    *active_worker_context_key = None;
+    *master_session_intro_needed = true;
+    *master_report_session_intro_needed = true;
+    *pending_task_write_baseline = None;
+    *pending_master_message_after_project_info = None;
+    *docs_attach_in_flight = false;
+    *project_info_in_flight = false;
+    *project_info_stage = None;
+    reset_master_report_runtime(
+        master_report_in_flight,
+        pending_master_report_prompts,
+        master_report_transcript,
+    );
+    reset_task_check_runtime(task_check_in_flight, task_check_baseline);
+
+    app.reset_execution_for_session_switch();
+    app.set_task_check_in_progress(false);
+    app.set_docs_attach_in_progress(false);
+    app.set_master_in_progress(false);
+
     app.replace_rolling_context_entries(prepared.rolling_context);
 
     match app.sync_planner_tasks_from_file(prepared.tasks) {
         Ok(()) => {
             app.set_right_pane_mode(prepared.pane_mode);
         }
-        Err(err) => app.push_agent_message(format!(
-            "System: Failed to refresh task tree from resumed tasks.json: {err}"
-        )),
+        Err(err) => app.push_agent_message(
+            app.system_text(Text::ResumedTaskTreeRefreshFailed, &[("err", &err)]),
+        ),
+    }
+    app.load_planner_markdown(prepared.planner_markdown);
+    app.set_audit_rubric(prepared.audit_rubric);
+    app.set_review_changes_enabled(prepared.review_changes);
+    app.replace_task_models(prepared.task_models);
+    app.set_speed_profile(prepared.speed);
+    app.restore_session_ui_state(prepared.ui_state);
+    if let Some(run) = prepared.interrupted_run {
+        replay_interrupted_run(app, session_store.as_ref(), run);
     }
-    app.set_planner_markdown(prepared.planner_markdown);
 
     *project_info_text = prepared.project_info_text;
+}
 
-    app.push_agent_message(format!(
-        "System: Resumed session {}",
-        active_session.session_dir().display()
-    ));
+/// Points safe mode's writable directory at the open session; without one, agents cannot
+/// write at all.
+fn sync_safe_mode_session(
+    model_routing: &CodexAgentModelRouting,
+    session_store: Option<&SessionStore>,
+) {
+    if let Some(sandbox) = model_routing.safe_mode() {
+        sandbox.set_session_dir(session_store.map(|store| store.session_dir().to_path_buf()));
+    }
+}
 
-    let size = terminal.size()?;
-    let screen = Rect::new(0, 0, size.width, size.height);
-    let max_scroll = ui::chat_max_scroll(screen, app);
-    app.set_chat_scroll(max_scroll);
-    Ok(())
+/// Marks `store` as open in this instance; a failure only loses the protection against a
+/// second instance opening it.
+fn hold_session_lock(app: &mut App, store: &SessionStore) {
+    if let Err(err) = session_lock::hold(store.session_dir()) {
+        app.push_agent_message(app.system_text(Text::LockWriteFailed, &[("err", &err)]));
+    }
+}
+
+/// Saves where the panes are in the session being left; a failure only costs the positions.
+fn save_session_ui_state(app: &mut App, store: &SessionStore) {
+    if let Err(err) = store.write_ui_state(&app.session_ui_state()) {
+        app.push_agent_message(app.system_text(Text::UiStateSaveFailed, &[("err", &err)]));
+    }
+}
+
+/// Reports a run that was quit mid-execution and puts its unsent messages back in the queue.
+fn replay_interrupted_run(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+    run: InterruptedRunFile,
+) {
+    if let Some(job) = run.interrupted_job {
+        app.push_agent_message(app.system_text(Text::InterruptedDuringJob, &[("job", &job)]));
+    } else if run.execution_running {
+        app.push_agent_message(app.system_text(Text::InterruptedDuringExecution, &[]));
+    }
+    if !run.queued_messages.is_empty() {
+        app.push_agent_message(app.system_text(
+            Text::RestoredQueuedMessages,
+            &[("count", &run.queued_messages.len())],
+        ));
+        app.restore_queued_messages(run.queued_messages);
+    }
+    if let Some(store) = session_store
+        && let Err(err) = store.clear_interrupted_run()
+    {
+        app.push_agent_message(app.system_text(Text::InterruptedRunClearFailed, &[("err", &err)]));
+    }
+}
+
+fn resume_previews<'a>(
+    options: impl IntoIterator<Item = &'a ResumeSessionOption>,
+) -> HashMap<String, SessionPreview> {
+    options
+        .into_iter()
+        .map(|option| {
+            let preview = SessionStore::read_preview(
+                Path::new(&option.session_dir),
+                RESUME_PREVIEW_PLANNER_LINES,
+            );
+            (option.session_dir.clone(), preview)
+        })
+        .collect()
+}
+
+/// Sessions of `workspace` last used within [`RECENT_SESSION_WINDOW_SECS`] of `now_secs`,
+/// most recent first.
+fn recent_session_options(
+    sessions: Vec<SessionListEntry>,
+    workspace: &Path,
+    now_secs: u64,
+) -> Vec<ResumeSessionOption> {
+    build_resume_options(sessions, None, Some(workspace))
+        .into_iter()
+        .filter(|option| {
+            now_secs.saturating_sub(option.last_used_epoch_secs) <= RECENT_SESSION_WINDOW_SECS
+        })
+        .collect()
+}
+
+/// Before `message` starts a new session, offers the workspace's recent sessions in the
+/// resume picker; the message waits until a session is picked or the offer is declined.
+fn offer_recent_sessions_if_needed(
+    app: &mut App,
+    message: &str,
+    cwd: &Path,
+    has_session: bool,
+) -> bool {
+    if has_session
+        || !app.offers_recent_session()
+        || !should_initialize_session_for_message(message)
+    {
+        return false;
+    }
+    let options = match SessionStore::list_sessions() {
+        Ok(sessions) => recent_session_options(sessions, cwd, current_epoch_secs()),
+        Err(_) => Vec::new(),
+    };
+    if options.is_empty() {
+        return false;
+    }
+    let count = options.len();
+    let previews = resume_previews(&options);
+    app.offer_recent_sessions(options, message.to_string());
+    app.set_resume_picker_previews(previews);
+    app.push_agent_message(app.system_text(Text::RecentSessionsOffer, &[("count", &count)]));
+    true
 }
 
 fn build_resume_options(
@@ -2185,6 +5153,541 @@ fn build_resume_options(
         .collect()
 }
 
+/// Starts the task splitter when the checked tasks contain oversized top-level tasks.
+fn start_task_split_if_needed(
+    app: &mut App,
+    session_store: &SessionStore,
+    task_check_adapter: &CodexAdapter,
+    limits: TaskSplitLimits,
+) -> Option<TaskSplitRun> {
+    if app.pending_task_split().is_some() || app.is_execution_busy() {
+        return None;
+    }
+    let tasks_baseline = std::fs::read_to_string(session_store.tasks_file()).ok()?;
+    let tasks_before = session_store.read_tasks().ok()?;
+    let oversized = subagents::find_oversized_top_tasks(
+        &tasks_before,
+        limits,
+        app.task_split_considered_titles(),
+    );
+    if oversized.is_empty() {
+        return None;
+    }
+    if let Err(err) = session_store.clear_task_split_proposal() {
+        app.push_agent_message(app.system_text(Text::TaskSplitStaleRemoveFailed, &[("err", &err)]));
+        return None;
+    }
+    app.mark_task_split_considered(oversized.iter().map(|task| task.title.clone()));
+    app.set_task_check_in_progress(true);
+    app.push_subagent_output(format!(
+        "TaskSplitSystem: Proposing splits for {} oversized task(s)",
+        oversized.len()
+    ));
+    task_check_adapter.send_prompt(subagents::build_task_split_prompt(
+        &session_store.tasks_file().display().to_string(),
+        &session_store.task_split_proposal_file().display().to_string(),
+        &session_store.project_info_file().display().to_string(),
+        &oversized,
+    ));
+    Some(TaskSplitRun {
+        session_dir: session_store.session_dir().to_path_buf(),
+        tasks_baseline,
+        tasks_before,
+    })
+}
+
+/// Turns the splitter's proposal file into a pending proposal, or reports why there is none.
+fn finish_task_split_run(app: &mut App, session_store: &SessionStore, run: TaskSplitRun) {
+    if session_store.session_dir() != run.session_dir {
+        return;
+    }
+    let proposal = match session_store.read_task_split_proposal() {
+        Ok(Some(tasks)) => tasks,
+        Ok(None) => {
+            app.push_agent_message(app.system_text(Text::TaskSplitKept, &[]));
+            return;
+        }
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::TaskSplitReadFailed, &[("err", &err)]));
+            let _ = session_store.clear_task_split_proposal();
+            return;
+        }
+    };
+    let summary = subagents::summarize_task_split(&run.tasks_before, &proposal);
+    let mut validation = Workflow::default();
+    validation.set_final_audit_policy(app.final_audit_policy());
+    let rejection = if summary.is_empty() {
+        Some("it does not change any top-level task".to_string())
+    } else {
+        validation
+            .sync_planner_tasks_from_file(proposal.clone())
+            .err()
+            .map(|err| err.to_string())
+    };
+    if let Some(reason) = rejection {
+        app.push_agent_message(app.system_text(Text::TaskSplitDiscarded, &[("reason", &reason)]));
+        let _ = session_store.clear_task_split_proposal();
+        return;
+    }
+    app.push_agent_message(
+        app.system_text(Text::TaskSplitProposed, &[("summary", &summary.join("\n"))]),
+    );
+    app.set_pending_task_split(PendingTaskSplit {
+        session_dir: run.session_dir,
+        tasks: proposal,
+        tasks_baseline: run.tasks_baseline,
+    });
+}
+
+fn handle_task_split_decision(app: &mut App, message: &str, session_store: Option<&SessionStore>) {
+    let Some(proposal) = app.take_pending_task_split() else {
+        app.push_agent_message(app.system_text(Text::NoTaskSplitPending, &[]));
+        return;
+    };
+    let Some(active_session) =
+        session_store.filter(|store| store.session_dir() == proposal.session_dir)
+    else {
+        app.push_agent_message(app.system_text(Text::TaskSplitOtherSession, &[]));
+        return;
+    };
+    if App::is_approve_split_command(message) && app.is_execution_busy() {
+        app.push_agent_message(app.system_text(Text::TaskSplitBlocked, &[]));
+        app.set_pending_task_split(proposal);
+        return;
+    }
+    let _ = active_session.clear_task_split_proposal();
+    if App::is_reject_split_command(message) {
+        record_approval_decision("task_split", "reject", proposal.tasks.len());
+        app.push_agent_message(app.system_text(Text::TaskSplitRejected, &[]));
+        return;
+    }
+    let current = std::fs::read_to_string(active_session.tasks_file()).ok();
+    if current.as_deref() != Some(proposal.tasks_baseline.as_str()) {
+        app.push_agent_message(app.system_text(Text::TaskSplitOutdated, &[]));
+        return;
+    }
+    match app.sync_planner_tasks_from_file(proposal.tasks.clone()) {
+        Ok(()) => match active_session.write_tasks(&proposal.tasks) {
+            Ok(()) => {
+                record_approval_decision("task_split", "approve", proposal.tasks.len());
+                app.push_agent_message(app.system_text(Text::TaskSplitApplied, &[]));
+            }
+            Err(err) => app
+                .push_agent_message(app.system_text(Text::TaskSplitWriteFailed, &[("err", &err)])),
+        },
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::TaskSplitInvalid, &[("err", &err)]))
+        }
+    }
+}
+
+/// Adds a `/approve-split`-style verdict on a tasks.json rewrite to the audit trail.
+fn record_approval_decision(subject: &str, decision: &str, task_count: usize) {
+    audit_trail::record(audit_trail::AuditEventFile::ApprovalDecision {
+        subject: subject.to_string(),
+        decision: decision.to_string(),
+        detail: format!("tasks.json with {task_count} task(s)"),
+    });
+}
+
+/// Shows what a master write changed in tasks.json and holds the tasks.json from before it
+/// for `/accept-tasks` or `/reject-tasks`. While earlier changes are still undecided, the
+/// diff and the baseline kept are those from before the earliest of them.
+fn offer_task_changes(app: &mut App, store: &SessionStore, baseline: &str) {
+    let baseline = match app.pending_task_changes() {
+        Some(pending) if pending.session_dir == store.session_dir() => pending.baseline.clone(),
+        _ => baseline.to_string(),
+    };
+    let (Ok(before), Ok(after)) = (
+        serde_json::from_str::<Vec<PlannerTaskFileEntry>>(&baseline),
+        read_master_tasks(app, store),
+    ) else {
+        return;
+    };
+    let changes = task_diff::diff(&before, &after);
+    if changes.is_empty() {
+        app.take_pending_task_changes();
+        return;
+    }
+    app.set_pending_task_changes(PendingTaskChanges {
+        session_dir: store.session_dir().to_path_buf(),
+        baseline,
+    });
+    if app.is_safe_mode() {
+        app.push_agent_message(app.system_text(
+            Text::SafeModeStagedTasks,
+            &[
+                ("path", &store.staged_tasks_file().display()),
+                ("diff", &task_diff::render(&changes)),
+            ],
+        ));
+        return;
+    }
+    app.push_agent_message(app.system_text(
+        Text::MasterChangedTasks,
+        &[("diff", &task_diff::render(&changes))],
+    ));
+}
+
+/// The tasks file the master edits: `tasks.staged.json` in safe mode, otherwise tasks.json.
+fn master_edited_tasks_file(app: &App, store: &SessionStore) -> PathBuf {
+    if app.is_safe_mode() {
+        store.staged_tasks_file()
+    } else {
+        store.tasks_file().to_path_buf()
+    }
+}
+
+fn read_master_tasks(app: &App, store: &SessionStore) -> io::Result<Vec<PlannerTaskFileEntry>> {
+    if app.is_safe_mode() {
+        store.read_staged_tasks()
+    } else {
+        store.read_tasks()
+    }
+}
+
+fn write_master_tasks(
+    app: &App,
+    store: &SessionStore,
+    tasks: &[PlannerTaskFileEntry],
+) -> io::Result<()> {
+    if app.is_safe_mode() {
+        store.write_staged_tasks(tasks)
+    } else {
+        store.write_tasks(tasks)
+    }
+}
+
+/// Loads the master's tasks into the task tree. In safe mode they are only validated, and the
+/// tree keeps the tasks from tasks.json until `/accept-tasks`.
+fn load_master_tasks(app: &mut App, tasks: Vec<PlannerTaskFileEntry>) -> Result<(), String> {
+    if !app.is_safe_mode() {
+        return app.sync_planner_tasks_from_file(tasks);
+    }
+    let current = app.planner_tasks_for_file();
+    app.sync_planner_tasks_from_file(tasks)?;
+    app.sync_planner_tasks_from_file(current)
+}
+
+/// Puts tasks.json back when a master in safe mode wrote it instead of the staged file.
+fn restore_tasks_after_safe_mode_master(
+    app: &mut App,
+    store: &SessionStore,
+    baseline: Option<&str>,
+) {
+    let Some(baseline) = baseline else {
+        return;
+    };
+    if store.read_tasks_text().is_ok_and(|text| text == baseline) {
+        return;
+    }
+    match store.write_tasks_text(baseline) {
+        Ok(()) => app.push_agent_message(app.system_text(Text::SafeModeRestoredTasks, &[])),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::RestoreTasksWriteFailed, &[("err", &err)]))
+        }
+    }
+}
+
+/// [`TASKS_FILE_FIX_PROMPT`] naming the file the master edits.
+fn tasks_file_fix_prompt(app: &App) -> String {
+    if app.is_safe_mode() {
+        TASKS_FILE_FIX_PROMPT.replace("tasks.json", "tasks.staged.json")
+    } else {
+        TASKS_FILE_FIX_PROMPT.to_string()
+    }
+}
+
+fn handle_task_changes_decision(
+    app: &mut App,
+    message: &str,
+    session_store: Option<&SessionStore>,
+) {
+    let Some(pending) = app.take_pending_task_changes() else {
+        app.push_agent_message(app.system_text(Text::NoTaskChangesPending, &[]));
+        return;
+    };
+    let Some(active_session) =
+        session_store.filter(|store| store.session_dir() == pending.session_dir)
+    else {
+        app.push_agent_message(app.system_text(Text::TaskChangesOtherSession, &[]));
+        return;
+    };
+    if app.is_safe_mode() {
+        resolve_staged_task_changes(app, message, active_session, pending);
+        return;
+    }
+    if App::is_accept_tasks_command(message) {
+        let tasks = active_session.read_tasks().map_or(0, |tasks| tasks.len());
+        record_approval_decision("task_changes", "accept", tasks);
+        app.push_agent_message(app.system_text(Text::KeptMasterTaskChanges, &[]));
+        return;
+    }
+    if app.is_execution_busy() {
+        app.push_agent_message(app.system_text(Text::RestoreTasksBlocked, &[]));
+        app.set_pending_task_changes(pending);
+        return;
+    }
+    let tasks = match serde_json::from_str::<Vec<PlannerTaskFileEntry>>(&pending.baseline) {
+        Ok(tasks) => tasks,
+        Err(err) => {
+            app.push_agent_message(
+                app.system_text(Text::PreviousTasksReadFailed, &[("err", &err)]),
+            );
+            return;
+        }
+    };
+    match app.sync_planner_tasks_from_file(tasks.clone()) {
+        Ok(()) => match active_session.write_tasks(&tasks) {
+            Ok(()) => {
+                record_approval_decision("task_changes", "reject", tasks.len());
+                app.push_agent_message(app.system_text(Text::RejectedMasterTaskChanges, &[]));
+            }
+            Err(err) => app.push_agent_message(
+                app.system_text(Text::RestoreTasksWriteFailed, &[("err", &err)]),
+            ),
+        },
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::PreviousTasksInvalid, &[("err", &err)]))
+        }
+    }
+}
+
+/// Moves the master's tasks.json to the staged file and puts `before` back, so nothing the
+/// master planned runs until `/accept-tasks`.
+fn resolve_staged_task_changes(
+    app: &mut App,
+    message: &str,
+    active_session: &SessionStore,
+    pending: PendingTaskChanges,
+) {
+    if !App::is_accept_tasks_command(message) {
+        let tasks = active_session
+            .read_staged_tasks()
+            .map_or(0, |tasks| tasks.len());
+        match active_session.clear_staged_tasks() {
+            Ok(()) => {
+                record_approval_decision("task_changes", "reject", tasks);
+                app.push_agent_message(app.system_text(Text::DiscardedStagedTasks, &[]));
+            }
+            Err(err) => app.push_agent_message(app.system_text(
+                Text::RemoveFileFailed,
+                &[
+                    ("path", &active_session.staged_tasks_file().display()),
+                    ("err", &err),
+                ],
+            )),
+        }
+        return;
+    }
+    if app.is_execution_busy() {
+        app.push_agent_message(app.system_text(Text::StagedTasksBlocked, &[]));
+        app.set_pending_task_changes(pending);
+        return;
+    }
+    let tasks = match active_session.read_staged_tasks() {
+        Ok(tasks) => tasks,
+        Err(err) => {
+            app.push_agent_message(app.system_text(
+                Text::StagedTasksReadFailed,
+                &[
+                    ("path", &active_session.staged_tasks_file().display()),
+                    ("err", &err),
+                ],
+            ));
+            return;
+        }
+    };
+    if let Err(err) = app.sync_planner_tasks_from_file(tasks.clone()) {
+        app.push_agent_message(app.system_text(Text::StagedTasksInvalid, &[("err", &err)]));
+        return;
+    }
+    match active_session.write_tasks(&tasks) {
+        Ok(()) => {
+            let _ = active_session.clear_staged_tasks();
+            record_tasks_change(
+                app,
+                active_session,
+                TasksChangeAgent::Master,
+                Some(&pending.baseline),
+            );
+            record_approval_decision("task_changes", "accept", tasks.len());
+            app.push_agent_message(app.system_text(Text::AppliedStagedTasks, &[]));
+        }
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::StagedTasksWriteFailed, &[("err", &err)]))
+        }
+    }
+}
+
+/// Appends the tasks.json changes `agent` made since `before` to the session's changelog.
+fn record_tasks_change(
+    app: &mut App,
+    store: &SessionStore,
+    agent: TasksChangeAgent,
+    before: Option<&str>,
+) {
+    let after = std::fs::read_to_string(store.tasks_file()).ok();
+    let path = store.tasks_changelog_file();
+    if let Err(err) = tasks_changelog::record(&path, agent, before, after.as_deref()) {
+        app.push_agent_message(app.system_text(
+            Text::AppendFileFailed,
+            &[("path", &path.display()), ("err", &err)],
+        ));
+    }
+}
+
+/// Opens an edit conflict when the master rewrote tasks.json over a hand edit that was still
+/// waiting for `/reload-tasks`. Call before the master's tasks are synced into the app.
+fn detect_tasks_edit_conflict(app: &mut App, store: &SessionStore) {
+    if app
+        .external_tasks_edit()
+        .is_none_or(|edit| edit.session_dir != store.session_dir())
+    {
+        return;
+    }
+    let Ok(theirs) = std::fs::read_to_string(store.tasks_file()) else {
+        return;
+    };
+    if app
+        .external_tasks_edit()
+        .is_some_and(|edit| edit.text == theirs)
+    {
+        return;
+    }
+    let Some(edit) = app.take_external_tasks_edit() else {
+        return;
+    };
+    let base = serde_json::to_string_pretty(&app.planner_tasks_for_file()).unwrap_or_default();
+    app.open_edit_conflict(EditConflict {
+        file: ConflictFile::Tasks,
+        base,
+        mine: edit.text,
+        theirs,
+    });
+}
+
+/// Settles the open planner.md or tasks.json edit conflict for `/conflict`. Your version or a
+/// merge of tasks.json goes back to being a hand edit waiting for `/reload-tasks`.
+fn apply_conflict_command(
+    app: &mut App,
+    choice: Option<ConflictChoice>,
+    session_store: Option<&SessionStore>,
+) {
+    let Some(mut conflict) = app.take_edit_conflict() else {
+        app.push_agent_message(app.system_text(Text::NoEditConflict, &[]));
+        return;
+    };
+    if conflict.file == ConflictFile::Planner {
+        // Typing continues in the planner while the conflict is open.
+        conflict.mine = app.planner_markdown().to_string();
+    }
+    let (Some(choice), Some(store)) = (choice, session_store) else {
+        app.open_edit_conflict(conflict);
+        return;
+    };
+    let file_name = conflict.file.file_name();
+    let (text, conflicted) = conflict.resolve(choice);
+    let written = match (conflict.file, choice) {
+        (ConflictFile::Planner, ConflictChoice::Theirs) => {
+            app.load_planner_markdown(text);
+            Ok(())
+        }
+        (ConflictFile::Planner, _) => store
+            .write_planner_markdown(&text)
+            .map(|()| app.load_planner_markdown(text)),
+        (ConflictFile::Tasks, ConflictChoice::Theirs) => Ok(()),
+        (ConflictFile::Tasks, _) => store.write_tasks_text(&text).map(|()| {
+            if let Some(edit) = tasks_watch::external_edit_for(app, store, text) {
+                app.set_external_tasks_edit(edit);
+            }
+        }),
+    };
+    if let Err(err) = written {
+        app.push_agent_message(app.system_text(
+            Text::WriteFileFailed,
+            &[("file_name", &file_name), ("err", &err)],
+        ));
+        app.open_edit_conflict(conflict);
+        return;
+    }
+    let resolved = match choice {
+        ConflictChoice::Mine => Text::ConflictKeptMine,
+        ConflictChoice::Theirs => Text::ConflictKeptTheirs,
+        ConflictChoice::Merge if conflicted => Text::ConflictMergedWithMarkers,
+        ConflictChoice::Merge => Text::ConflictMerged,
+    };
+    let follow_up = if app.has_external_tasks_edit() && conflict.file == ConflictFile::Tasks {
+        i18n::text(app.locale(), Text::ConflictReloadHint)
+    } else {
+        ""
+    };
+    app.push_agent_message(app.system_text(
+        resolved,
+        &[("file_name", &file_name), ("follow_up", &follow_up)],
+    ));
+}
+
+/// Applies `/reload-tasks` or `/keep-tasks` to a pending external tasks.json edit. Returns
+/// true when execution was running before the reload and should resume on the new task tree.
+fn handle_external_tasks_decision(
+    app: &mut App,
+    message: &str,
+    session_store: Option<&SessionStore>,
+) -> bool {
+    let Some(edit) = app.take_external_tasks_edit() else {
+        app.push_agent_message(app.system_text(Text::NoExternalTasksEdit, &[]));
+        return false;
+    };
+    let Some(active_session) =
+        session_store.filter(|store| store.session_dir() == edit.session_dir)
+    else {
+        app.push_agent_message(app.system_text(Text::ExternalTasksEditOtherSession, &[]));
+        return false;
+    };
+    if App::is_keep_tasks_command(message) {
+        match active_session.write_tasks(&app.planner_tasks_for_file()) {
+            Ok(()) => {
+                app.push_agent_message(app.system_text(Text::ExternalTasksEditDiscarded, &[]))
+            }
+            Err(err) => {
+                app.push_agent_message(app.system_text(Text::RestoreTasksFailed, &[("err", &err)]));
+                app.set_external_tasks_edit(edit);
+            }
+        }
+        return false;
+    }
+    if app.is_execution_busy() {
+        app.push_agent_message(app.system_text(Text::ReloadTasksBlocked, &[]));
+        app.set_external_tasks_edit(edit);
+        return false;
+    }
+    let tasks = match active_session.read_tasks() {
+        Ok(tasks) => tasks,
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::ReloadTasksFailed, &[("err", &err)]));
+            app.set_external_tasks_edit(edit);
+            return false;
+        }
+    };
+    let execution_was_enabled = app.is_execution_enabled();
+    match app.sync_planner_tasks_from_file(tasks) {
+        Ok(()) => {
+            app.push_agent_message(if execution_was_enabled {
+                app.system_text(Text::ReloadedTasksResuming, &[])
+            } else {
+                app.system_text(Text::ReloadedTasks, &[])
+            });
+            execution_was_enabled
+        }
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::ReloadTasksInvalid, &[("err", &err)]));
+            app.set_external_tasks_edit(edit);
+            false
+        }
+    }
+}
+
 fn handle_final_audit_command<B: Backend>(
     app: &mut App,
     message: &str,
@@ -2209,10 +5712,14 @@ fn handle_final_audit_tasks_command(
     if (App::is_add_final_audit_command(message) || App::is_remove_final_audit_command(message))
         && app.is_execution_busy()
     {
-        app.push_agent_message(
-            "System: Cannot modify final-audit tasks while worker execution is running. Wait for active jobs to finish first."
-                .to_string(),
-        );
+        app.push_agent_message(app.system_text(Text::FinalAuditBlocked, &[]));
+        return Ok(true);
+    }
+
+    if App::is_add_final_audit_command(message)
+        && app.final_audit_policy() == FinalAuditPolicy::Never
+    {
+        app.push_agent_message(app.system_text(Text::FinalAuditDisabled, &[]));
         return Ok(true);
     }
 
@@ -2220,27 +5727,24 @@ fn handle_final_audit_tasks_command(
         let mut tasks = match session_store.read_tasks() {
             Ok(tasks) => tasks,
             Err(err) => {
-                app.push_agent_message(format!(
-                    "System: Could not read tasks file; final audit command aborted: {err}"
-                ));
+                app.push_agent_message(
+                    app.system_text(Text::FinalAuditReadFailed, &[("err", &err)]),
+                );
                 return Ok(true);
             }
         };
         ensure_final_audit_task(&mut tasks);
         normalize_root_orders_with_final_last(&mut tasks);
         match app.sync_planner_tasks_from_file(tasks.clone()) {
-            Ok(_) => match serde_json::to_string_pretty(&tasks)
-                .map_err(io::Error::other)
-                .and_then(|text| std::fs::write(session_store.tasks_file(), text))
-            {
-                Ok(()) => app.push_agent_message("System: Added final audit task.".to_string()),
-                Err(err) => app.push_agent_message(format!(
-                    "System: Failed to write tasks file while adding final audit task: {err}"
-                )),
+            Ok(_) => match session_store.write_tasks(&tasks) {
+                Ok(()) => app.push_agent_message(app.system_text(Text::FinalAuditAdded, &[])),
+                Err(err) => app.push_agent_message(
+                    app.system_text(Text::FinalAuditAddWriteFailed, &[("err", &err)]),
+                ),
             },
-            Err(err) => app.push_agent_message(format!(
-                "System: Final audit command aborted; task tree refresh failed before write: {err}"
-            )),
+            Err(err) => app.push_agent_message(
+                app.system_text(Text::FinalAuditRefreshFailed, &[("err", &err)]),
+            ),
         }
         return Ok(true);
     }
@@ -2249,9 +5753,9 @@ fn handle_final_audit_tasks_command(
         let mut tasks = match session_store.read_tasks() {
             Ok(tasks) => tasks,
             Err(err) => {
-                app.push_agent_message(format!(
-                    "System: Could not read tasks file; final audit command aborted: {err}"
-                ));
+                app.push_agent_message(
+                    app.system_text(Text::FinalAuditReadFailed, &[("err", &err)]),
+                );
                 return Ok(true);
             }
         };
@@ -2262,26 +5766,21 @@ fn handle_final_audit_tasks_command(
         tasks.retain(|task| task.kind != PlannerTaskKindFile::FinalAudit);
         normalize_root_orders_with_final_last(&mut tasks);
         match app.sync_planner_tasks_from_file(tasks.clone()) {
-            Ok(_) => match serde_json::to_string_pretty(&tasks)
-                .map_err(io::Error::other)
-                .and_then(|text| std::fs::write(session_store.tasks_file(), text))
-            {
+            Ok(_) => match session_store.write_tasks(&tasks) {
                 Ok(()) => {
                     if final_audit_count == 0 {
-                        app.push_agent_message(
-                            "System: No final audit task was present.".to_string(),
-                        );
+                        app.push_agent_message(app.system_text(Text::FinalAuditMissing, &[]));
                     } else {
-                        app.push_agent_message("System: Removed final audit task.".to_string());
+                        app.push_agent_message(app.system_text(Text::FinalAuditRemoved, &[]));
                     }
                 }
-                Err(err) => app.push_agent_message(format!(
-                    "System: Failed to write tasks file while removing final audit task: {err}"
-                )),
+                Err(err) => app.push_agent_message(
+                    app.system_text(Text::FinalAuditRemoveWriteFailed, &[("err", &err)]),
+                ),
             },
-            Err(err) => app.push_agent_message(format!(
-                "System: Final audit command aborted; task tree refresh failed before write: {err}"
-            )),
+            Err(err) => app.push_agent_message(
+                app.system_text(Text::FinalAuditRefreshFailed, &[("err", &err)]),
+            ),
         }
         return Ok(true);
     }
@@ -2289,6 +5788,91 @@ fn handle_final_audit_tasks_command(
     Ok(false)
 }
 
+/// In `auto` mode, appends a final audit the first time a plan materializes in an empty workflow.
+fn append_auto_final_audit_if_needed(
+    policy: FinalAuditPolicy,
+    plan_was_empty: bool,
+    tasks: &mut Vec<PlannerTaskFileEntry>,
+) -> bool {
+    if policy != FinalAuditPolicy::Auto
+        || !plan_was_empty
+        || tasks.is_empty()
+        || tasks
+            .iter()
+            .any(|task| task.kind == PlannerTaskKindFile::FinalAudit)
+    {
+        return false;
+    }
+    ensure_final_audit_task(tasks);
+    normalize_root_orders_with_final_last(tasks);
+    true
+}
+
+/// With `docs_update_task` on, appends the README/CHANGELOG task the first time a plan
+/// materializes in an empty workflow. It is ordered after the planned tasks and before any
+/// final audit, and its auditor reviews the docs edits like any other implementation.
+fn append_docs_update_task_if_needed(
+    enabled: bool,
+    plan_was_empty: bool,
+    tasks: &mut Vec<PlannerTaskFileEntry>,
+) -> bool {
+    if !enabled
+        || !plan_was_empty
+        || tasks.is_empty()
+        || tasks.iter().any(|task| task.id == DOCS_UPDATE_TASK_ID)
+    {
+        return false;
+    }
+    let entry = |id: String, title: &str, details: &str, kind, parent_id: Option<String>| {
+        PlannerTaskFileEntry {
+            id,
+            title: title.to_string(),
+            details: details.to_string(),
+            docs: Vec::new(),
+            kind,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id,
+            order: Some(0),
+            ..Default::default()
+        }
+    };
+    let implementor_id = format!("{DOCS_UPDATE_TASK_ID}-write");
+    tasks.extend([
+        PlannerTaskFileEntry {
+            order: Some(u32::MAX),
+            ..entry(
+                DOCS_UPDATE_TASK_ID.to_string(),
+                "Update README and CHANGELOG",
+                "Document the user-visible changes made by the other tasks in this plan.",
+                PlannerTaskKindFile::Task,
+                None,
+            )
+        },
+        entry(
+            implementor_id.clone(),
+            "Write docs updates",
+            "Act as a docs writer. Using the session tasks and changed files listed in your \
+             prompt, update the README feature list (and any usage sections the changes \
+             affect) and add entries to CHANGELOG.md under an \"Unreleased\" heading, creating \
+             the file if it does not exist. Describe only user-visible behavior that was \
+             actually implemented; skip tasks that are not finished. Change documentation \
+             files only.",
+            PlannerTaskKindFile::Implementor,
+            Some(DOCS_UPDATE_TASK_ID.to_string()),
+        ),
+        entry(
+            format!("{DOCS_UPDATE_TASK_ID}-audit"),
+            "Audit docs updates",
+            "Check that the README and CHANGELOG edits match the changes made this session, \
+             claim nothing that was not implemented, and touch no files besides documentation.",
+            PlannerTaskKindFile::Auditor,
+            Some(implementor_id),
+        ),
+    ]);
+    normalize_root_orders_with_final_last(tasks);
+    true
+}
+
 fn ensure_final_audit_task(tasks: &mut Vec<PlannerTaskFileEntry>) {
     if let Some(existing) = tasks
         .iter_mut()
@@ -2308,22 +5892,395 @@ fn ensure_final_audit_task(tasks: &mut Vec<PlannerTaskFileEntry>) {
     }
 
     let mut suffix = 1usize;
-    let mut id = "final-audit".to_string();
-    while tasks.iter().any(|task| task.id == id) {
+    let mut id = "final-audit".to_string();
+    while tasks.iter().any(|task| task.id == id) {
+        suffix = suffix.saturating_add(1);
+        id = format!("final-audit-{suffix}");
+    }
+    tasks.push(PlannerTaskFileEntry {
+        id,
+        title: "Final Audit".to_string(),
+        details: "Perform a final holistic audit after all implementation and test tasks complete."
+            .to_string(),
+        docs: Vec::new(),
+        kind: PlannerTaskKindFile::FinalAudit,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: None,
+        order: Some(u32::MAX),
+        ..Default::default()
+    });
+}
+
+/// Sends a chat message to the read-only advisor while worker execution runs.
+fn ask_advisor(
+    app: &App,
+    advisor: &mut AdvisorChat,
+    store: &SessionStore,
+    question: &str,
+    model_routing: &CodexAgentModelRouting,
+    selected_backend: BackendKind,
+    project_info: Option<&str>,
+) {
+    let queued_jobs = app
+        .queued_jobs()
+        .iter()
+        .map(QueuedJobMeta::label)
+        .collect::<Vec<_>>();
+    advisor.ask(
+        store,
+        selected_backend,
+        || build_json_persistent_adapter(model_routing, selected_backend, CodexAgentKind::Master),
+        |include_project_info| {
+            subagents::build_advisor_prompt(
+                question,
+                &store.session_dir().display().to_string(),
+                &store.tasks_file().display().to_string(),
+                project_info.filter(|_| include_project_info),
+                &queued_jobs,
+            )
+        },
+    );
+}
+
+/// Lists, reorders, or drops queued worker jobs for `/jobs`.
+fn apply_jobs_command(app: &mut App, command: JobsCommand) {
+    let message = match command {
+        JobsCommand::List => {
+            let jobs = app.queued_jobs();
+            if jobs.is_empty() {
+                app.system_text(Text::NoQueuedJobs, &[])
+            } else {
+                let lines = jobs
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, job)| format!("{}. {}", idx + 1, job.label()))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                app.system_text(Text::QueuedJobs, &[("lines", &lines)])
+            }
+        }
+        JobsCommand::Move { from, to } => match app.move_queued_job(from, to) {
+            Ok(job) => app.system_text(
+                Text::MovedJob,
+                &[("job", &job.label()), ("position", &(to + 1))],
+            ),
+            Err(err) => app.system_text(Text::MoveJobFailed, &[("err", &err)]),
+        },
+        JobsCommand::Drop(index) => match app.drop_queued_job(index) {
+            Ok(job) => app.system_text(
+                Text::DroppedJob,
+                &[("job", &job.label()), ("task", &job.task_key)],
+            ),
+            Err(err) => app.system_text(Text::DropJobFailed, &[("err", &err)]),
+        },
+    };
+    app.push_agent_message(message);
+}
+
+/// Applies a `/tasks` bulk edit through the same checks as `api session` task edits, saves
+/// tasks.json, and returns the summary for chat. Nothing is changed if any edit fails, and a
+/// delete only goes through when the same command is sent again for the same tasks.
+fn apply_tasks_command(
+    app: &mut App,
+    command: TasksCommand,
+    session_store: &SessionStore,
+) -> String {
+    let (status, positions) = match command {
+        TasksCommand::Done(positions) => (Some(PlannerTaskStatusFile::Done), positions),
+        TasksCommand::Reset(positions) => (Some(PlannerTaskStatusFile::Pending), positions),
+        TasksCommand::Delete(positions) => (None, positions),
+        TasksCommand::Usage => {
+            app.clear_pending_tasks_delete();
+            return app.system_text(Text::BulkTasksUsage, &[]);
+        }
+    };
+    let tasks = app.planner_tasks_for_file();
+    let final_audit_policy = app.final_audit_policy();
+    let result = task_edits::top_level_ids(&tasks, &positions).and_then(|ids| {
+        let titles = ids
+            .iter()
+            .zip(&positions)
+            .filter_map(|(id, position)| {
+                let task = tasks.iter().find(|task| &task.id == id)?;
+                Some(format!("- {position}. {}", task.title))
+            })
+            .collect::<Vec<_>>();
+        let edited = ids.iter().try_fold(tasks.clone(), |tasks, task_id| {
+            let task_id = task_id.clone();
+            let edit = match status {
+                Some(status) => TaskEdit::SetStatus { task_id, status },
+                None => TaskEdit::Remove { task_id },
+            };
+            task_edits::apply(tasks, edit, final_audit_policy)
+        })?;
+        Ok((ids, edited, titles))
+    });
+    let (ids, edited, titles) = match result {
+        Ok(result) => result,
+        Err(err) => {
+            app.clear_pending_tasks_delete();
+            return app.system_text(Text::BulkTasksUnchanged, &[("err", &err)]);
+        }
+    };
+    if status.is_some() {
+        app.clear_pending_tasks_delete();
+    } else if !app.confirm_tasks_delete(ids) {
+        return app.system_text(
+            Text::BulkTasksConfirmDelete,
+            &[("count", &titles.len()), ("titles", &titles.join("\n"))],
+        );
+    }
+    if let Err(err) = session_store.write_tasks_atomic(&edited) {
+        return app.system_text(Text::BulkTasksWriteFailed, &[("err", &err)]);
+    }
+    if let Err(err) = app.sync_planner_tasks_from_file(edited) {
+        return app.system_text(Text::BulkTasksRefreshFailed, &[("err", &err)]);
+    }
+    let changed = match status {
+        Some(PlannerTaskStatusFile::Done) => Text::BulkTasksMarkedDone,
+        Some(_) => Text::BulkTasksReset,
+        None => Text::BulkTasksDeleted,
+    };
+    app.system_text(
+        changed,
+        &[("count", &titles.len()), ("titles", &titles.join("\n"))],
+    )
+}
+
+/// Stops the sub-agent named by `/cancel`. A cancelled task check has its tasks.json edits
+/// undone by restoring the file it started from, and a cancelled task split drops its
+/// proposal; docs attach keeps the docs it already wrote. A cancelled project-info gather
+/// returns the master message it was holding, to be sent without project context. The
+/// run's completion event is ignored once the in-flight flag is cleared here.
+#[allow(clippy::too_many_arguments)]
+fn handle_cancel_command(
+    app: &mut App,
+    command: CancelCommand,
+    project_info_adapter: &CodexAdapter,
+    docs_attach_adapter: &CodexAdapter,
+    task_check_adapter: &CodexAdapter,
+    project_info_in_flight: &mut bool,
+    project_info_stage: &mut Option<ProjectInfoStage>,
+    pending_master_message_after_project_info: &mut Option<String>,
+    docs_attach_in_flight: &mut bool,
+    task_check_in_flight: &mut bool,
+    task_check_baseline: &mut Option<String>,
+    task_split_run: &mut Option<TaskSplitRun>,
+    session_store: Option<&SessionStore>,
+) -> Option<String> {
+    match command {
+        CancelCommand::Usage => {
+            app.push_agent_message(app.system_text(Text::CancelUsage, &[]));
+        }
+        CancelCommand::Context => {
+            if !*project_info_in_flight {
+                app.push_agent_message(app.system_text(Text::NoProjectContextRunning, &[]));
+                return None;
+            }
+            project_info_adapter.terminate();
+            *project_info_in_flight = false;
+            *project_info_stage = None;
+            let held = pending_master_message_after_project_info.take();
+            app.push_agent_message(if held.is_some() {
+                app.system_text(Text::ProjectContextCancelledSending, &[])
+            } else {
+                app.system_text(Text::ProjectContextCancelled, &[])
+            });
+            return held;
+        }
+        CancelCommand::Docs => {
+            if !*docs_attach_in_flight {
+                app.push_agent_message(app.system_text(Text::NoDocsAttachRunning, &[]));
+                return None;
+            }
+            docs_attach_adapter.terminate();
+            *docs_attach_in_flight = false;
+            let baseline = app.take_docs_attach_baseline();
+            app.set_docs_attach_in_progress(false);
+            if let Some(store) = session_store {
+                record_tasks_change(
+                    app,
+                    store,
+                    TasksChangeAgent::DocsAttach,
+                    baseline.as_deref(),
+                );
+                if let Ok(tasks) = store.read_tasks() {
+                    let _ = app.sync_planner_tasks_from_file(tasks);
+                }
+            }
+            app.push_agent_message(app.system_text(Text::DocsAttachCancelled, &[]));
+        }
+        CancelCommand::TaskCheck => {
+            if let Some(run) = task_split_run.take() {
+                task_check_adapter.terminate();
+                app.set_task_check_in_progress(false);
+                if let Some(store) =
+                    session_store.filter(|store| store.session_dir() == run.session_dir)
+                {
+                    let _ = store.clear_task_split_proposal();
+                }
+                app.push_agent_message(app.system_text(Text::TaskSplitterCancelled, &[]));
+                return None;
+            }
+            if !*task_check_in_flight {
+                app.push_agent_message(app.system_text(Text::NoTaskCheckRunning, &[]));
+                return None;
+            }
+            task_check_adapter.terminate();
+            let baseline = task_check_baseline.take();
+            reset_task_check_runtime(task_check_in_flight, task_check_baseline);
+            app.set_task_check_in_progress(false);
+            let Some(active_session) = session_store else {
+                app.push_agent_message(app.system_text(Text::TaskCheckCancelled, &[]));
+                return None;
+            };
+            let current = std::fs::read_to_string(active_session.tasks_file()).ok();
+            let Some(baseline) = baseline.filter(|baseline| current.as_ref() != Some(baseline))
+            else {
+                app.push_agent_message(app.system_text(Text::TaskCheckCancelledUnchanged, &[]));
+                return None;
+            };
+            let restored = serde_json::from_str::<Vec<PlannerTaskFileEntry>>(&baseline)
+                .map_err(|err| err.to_string())
+                .and_then(|tasks| {
+                    active_session
+                        .write_tasks(&tasks)
+                        .map_err(|err| err.to_string())?;
+                    app.sync_planner_tasks_from_file(tasks)
+                });
+            app.push_agent_message(match restored {
+                Ok(()) => app.system_text(Text::TaskCheckCancelledRestored, &[]),
+                Err(err) => app.system_text(Text::TaskCheckCancelRestoreFailed, &[("err", &err)]),
+            });
+        }
+    }
+    None
+}
+
+/// Sends a master message that was held back while project context was gathered.
+fn send_held_master_message(
+    app: &mut App,
+    master_adapter: &CodexAdapter,
+    active_session: &SessionStore,
+    message: &str,
+    project_info_text: Option<&str>,
+    master_session_intro_needed: &mut bool,
+    pending_task_write_baseline: &mut Option<TaskWriteBaseline>,
+) {
+    let intro_was_needed = *master_session_intro_needed;
+    let with_intro = DefaultUiPromptService.build_master_prompt_for_message(
+        app,
+        message,
+        active_session,
+        project_info_text,
+        master_session_intro_needed,
+    );
+    if send_or_preview_master_prompt(
+        app,
+        master_adapter,
+        with_intro,
+        message,
+        intro_was_needed && !*master_session_intro_needed,
+    ) {
+        *pending_task_write_baseline =
+            DefaultCoreOrchestrationService.capture_tasks_baseline(active_session);
+    }
+}
+
+/// Appends `/direct <instruction>` to the task tree as a top-level task with one implementor,
+/// its audit, and a test run, and saves tasks.json. Returns the new task's id.
+fn add_direct_task(
+    app: &mut App,
+    instruction: &str,
+    session_store: &SessionStore,
+) -> Option<String> {
+    if instruction.is_empty() {
+        app.push_agent_message(app.system_text(Text::DirectUsage, &[]));
+        return None;
+    }
+    let mut tasks = app.planner_tasks_for_file();
+    let task_id = append_direct_task(&mut tasks, instruction);
+    normalize_root_orders_with_final_last(&mut tasks);
+    if let Err(err) = app.sync_planner_tasks_from_file(tasks.clone()) {
+        app.push_agent_message(app.system_text(Text::DirectRefreshFailed, &[("err", &err)]));
+        return None;
+    }
+    if let Err(err) = session_store.write_tasks(&tasks) {
+        app.push_agent_message(app.system_text(Text::DirectWriteFailed, &[("err", &err)]));
+        return None;
+    }
+    app.set_right_pane_mode(RightPaneMode::TaskList);
+    app.push_agent_message(app.system_text(Text::DirectTaskAdded, &[("task_id", &task_id)]));
+    Some(task_id)
+}
+
+fn append_direct_task(tasks: &mut Vec<PlannerTaskFileEntry>, instruction: &str) -> String {
+    let mut suffix = 1usize;
+    let mut id = "direct-1".to_string();
+    while tasks
+        .iter()
+        .any(|task| task.id == id || task.id.starts_with(&format!("{id}-")))
+    {
         suffix = suffix.saturating_add(1);
-        id = format!("final-audit-{suffix}");
+        id = format!("direct-{suffix}");
     }
-    tasks.push(PlannerTaskFileEntry {
-        id,
-        title: "Final Audit".to_string(),
-        details: "Perform a final holistic audit after all implementation and test tasks complete."
-            .to_string(),
-        docs: Vec::new(),
-        kind: PlannerTaskKindFile::FinalAudit,
-        status: PlannerTaskStatusFile::Pending,
-        parent_id: None,
-        order: Some(u32::MAX),
-    });
+    let first_line = instruction.lines().next().unwrap_or_default().trim();
+    let title = if first_line.chars().count() > 60 {
+        format!("{}...", first_line.chars().take(57).collect::<String>())
+    } else {
+        first_line.to_string()
+    };
+    let entry = |id: String, title: &str, details: String, kind, parent_id: Option<&str>| {
+        PlannerTaskFileEntry {
+            id,
+            title: title.to_string(),
+            details,
+            docs: Vec::new(),
+            kind,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: parent_id.map(str::to_string),
+            order: Some(0),
+            ..Default::default()
+        }
+    };
+    let implementor_id = format!("{id}-impl");
+    tasks.extend([
+        PlannerTaskFileEntry {
+            order: Some(u32::MAX - 1),
+            ..entry(
+                id.clone(),
+                &format!("Direct: {title}"),
+                instruction.to_string(),
+                PlannerTaskKindFile::Task,
+                None,
+            )
+        },
+        entry(
+            implementor_id.clone(),
+            "Implement",
+            format!("Make this change directly; there is no separate plan:\n{instruction}"),
+            PlannerTaskKindFile::Implementor,
+            Some(&id),
+        ),
+        entry(
+            format!("{id}-audit"),
+            "Audit",
+            format!("Check that the change does exactly what was asked, without regressions:\n{instruction}"),
+            PlannerTaskKindFile::Auditor,
+            Some(&implementor_id),
+        ),
+        PlannerTaskFileEntry {
+            order: Some(1),
+            ..entry(
+                format!("{id}-tests"),
+                "Run tests",
+                "Run the existing tests to catch regressions from this change.".to_string(),
+                PlannerTaskKindFile::TestRunner,
+                Some(&implementor_id),
+            )
+        },
+    ]);
+    id
 }
 
 fn normalize_root_orders_with_final_last(tasks: &mut [PlannerTaskFileEntry]) {
@@ -2393,9 +6350,7 @@ fn sanitize_master_docs_fields(
 
 #[cfg(test)]
 fn persist_runtime_tasks_snapshot(app: &App, session_store: &SessionStore) -> io::Result<()> {
-    let tasks = app.planner_tasks_for_file();
-    let text = serde_json::to_string_pretty(&tasks).map_err(io::Error::other)?;
-    std::fs::write(session_store.tasks_file(), text)
+    session_store.write_tasks(&app.planner_tasks_for_file())
 }
 
 #[cfg(test)]
@@ -2405,106 +6360,647 @@ fn claim_next_worker_job_and_persist_snapshot(
 ) -> Option<workflow::StartedJob> {
     let job = app.start_next_worker_job();
     if let Err(err) = persist_runtime_tasks_snapshot(app, session_store) {
-        app.push_agent_message(format!(
-            "System: Failed to persist runtime task status to tasks.json: {err}"
-        ));
+        app.push_agent_message(app.system_text(Text::TaskStatusPersistFailed, &[("err", &err)]));
+    }
+    job
+}
+
+enum PlannerPersistResult {
+    Persisted,
+    Unchanged,
+    Deferred,
+    /// planner.md changed on disk and in the planner; nothing is written until `/conflict`.
+    Conflicted,
+}
+
+/// Asks the master to turn planner.md into tasks.json and shows the task list, for
+/// `/convert` and `/import-plan tasks`.
+fn send_convert_prompt(
+    app: &mut App,
+    active_session: &SessionStore,
+    master_adapter: &CodexAdapter,
+    project_info_text: Option<&str>,
+    master_session_intro_needed: &mut bool,
+    pending_task_write_baseline: &mut Option<TaskWriteBaseline>,
+) {
+    app.set_right_pane_mode(RightPaneMode::TaskList);
+    app.push_agent_message(app.system_text(Text::ConvertingPlanner, &[]));
+    let with_intro = DefaultUiPromptService.build_convert_master_prompt(
+        app,
+        active_session,
+        project_info_text,
+        master_session_intro_needed,
+    );
+    master_adapter.send_prompt(with_intro);
+    app.set_master_in_progress(true);
+    app.reset_prompt_sections();
+    *pending_task_write_baseline =
+        DefaultCoreOrchestrationService.capture_tasks_baseline(active_session);
+}
+
+fn persist_planner_markdown_if_changed(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+) -> PlannerPersistResult {
+    let Some(session_store) = session_store else {
+        return PlannerPersistResult::Deferred;
+    };
+    if app
+        .edit_conflict()
+        .is_some_and(|conflict| conflict.file == ConflictFile::Planner)
+    {
+        return PlannerPersistResult::Conflicted;
+    }
+    if let Ok(existing) = session_store.read_planner_markdown() {
+        if existing == app.planner_markdown() {
+            app.mark_planner_markdown_saved();
+            return PlannerPersistResult::Unchanged;
+        }
+        if existing != app.planner_saved_markdown() {
+            // The master rewrote planner.md since the planner last read or saved it.
+            return if app.reconcile_planner_markdown(existing) {
+                PlannerPersistResult::Conflicted
+            } else {
+                PlannerPersistResult::Unchanged
+            };
+        }
+    }
+    if let Err(err) = session_store.write_planner_markdown(app.planner_markdown()) {
+        app.push_agent_message(app.system_text(Text::PlannerWriteFailed, &[("err", &err)]));
+        return PlannerPersistResult::Deferred;
+    }
+    app.mark_planner_markdown_saved();
+    PlannerPersistResult::Persisted
+}
+
+fn mark_planner_manual_edit(dirty: &mut bool, last_keystroke_at: &mut Option<Instant>) {
+    *dirty = true;
+    *last_keystroke_at = Some(Instant::now());
+}
+
+fn flush_debounced_planner_autosave_if_due(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+    dirty: &mut bool,
+    last_keystroke_at: &mut Option<Instant>,
+) {
+    if !*dirty {
+        return;
+    }
+    let Some(last_input) = *last_keystroke_at else {
+        return;
+    };
+    if last_input.elapsed() < PLANNER_AUTOSAVE_DEBOUNCE {
+        return;
+    }
+    match persist_planner_markdown_if_changed(app, session_store) {
+        PlannerPersistResult::Persisted
+        | PlannerPersistResult::Unchanged
+        | PlannerPersistResult::Conflicted => {
+            *dirty = false;
+            *last_keystroke_at = None;
+        }
+        PlannerPersistResult::Deferred => {
+            *last_keystroke_at = Some(Instant::now());
+        }
+    }
+}
+
+fn tasks_changed_since_baseline(before: Option<&str>, after: Option<&str>) -> bool {
+    match (before, after) {
+        (Some(before), Some(after)) => before != after,
+        (None, Some(after)) => !after.trim().is_empty(),
+        _ => false,
+    }
+}
+
+fn should_clear_task_write_baseline(
+    tasks_refresh_ok: bool,
+    requested_task_file_retry: bool,
+) -> bool {
+    tasks_refresh_ok || !requested_task_file_retry
+}
+
+/// Queues a session's unanswered master reports, the in-flight one first since its reply
+/// may never have arrived. Returns how many were queued.
+fn restore_master_report_queue(
+    queue: MasterReportQueueFile,
+    pending_master_report_prompts: &mut VecDeque<String>,
+) -> usize {
+    let restored = queue
+        .in_flight
+        .into_iter()
+        .chain(queue.pending)
+        .filter(|prompt| !prompt.trim().is_empty())
+        .collect::<Vec<_>>();
+    let count = restored.len();
+    pending_master_report_prompts.extend(restored);
+    count
+}
+
+/// Saves the master report queue so reports still waiting are delivered after a restart.
+fn persist_master_report_queue(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+    in_flight_prompt: Option<&str>,
+    pending_master_report_prompts: &VecDeque<String>,
+) {
+    let Some(active_session) = session_store else {
+        return;
+    };
+    let queue = MasterReportQueueFile {
+        in_flight: in_flight_prompt.map(str::to_string),
+        pending: pending_master_report_prompts.iter().cloned().collect(),
+    };
+    if let Err(err) = active_session.write_master_report_queue(&queue) {
+        app.push_agent_message(app.system_text(Text::ReportQueuePersistFailed, &[("err", &err)]));
+    }
+}
+
+/// Appends the facts in the master's `PROJECT_MEMORY` blocks to the workspace memory file.
+fn record_project_memory(app: &mut App, session_store: &SessionStore, transcript: &str) {
+    let facts = project_memory::extract_memory_facts(transcript);
+    if facts.is_empty() {
+        return;
+    }
+    match project_memory::record_session_facts(session_store, &facts) {
+        Ok((_, 0)) => {}
+        Ok((path, added)) => app.push_agent_message(app.system_text(
+            Text::SavedProjectMemory,
+            &[("added", &added), ("path", &path.display())],
+        )),
+        Err(err) => app
+            .push_agent_message(app.system_text(Text::ProjectMemoryUpdateFailed, &[("err", &err)])),
+    }
+}
+
+/// Opens the answer form for the questions in the master's `QUESTIONS` blocks.
+fn open_master_questions(app: &mut App, transcript: &str) {
+    let Some(form) = QuestionForm::new(clarification::extract_questions(transcript)) else {
+        return;
+    };
+    app.push_agent_message(app.system_text(
+        Text::MasterAskedQuestions,
+        &[("count", &form.questions().len())],
+    ));
+    app.open_question_form(form);
+}
+
+/// Saves the answers to the session's clarifications file and stages them as one chat
+/// message. Returns false when every answer was left blank, which dismisses the form.
+fn stage_question_answers(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+    form: &QuestionForm,
+) -> bool {
+    if form.is_blank() {
+        app.push_agent_message(app.system_text(Text::QuestionsDismissed, &[]));
+        return false;
+    }
+    if let Some(store) = session_store {
+        let path = store.clarifications_file();
+        if let Err(err) = clarification::append_clarifications(&path, form, current_epoch_secs()) {
+            app.push_agent_message(app.system_text(
+                Text::RecordAnswersFailed,
+                &[("path", &path.display()), ("err", &err)],
+            ));
+        }
+    }
+    app.stage_chat_command(form.combined_message().trim_end().to_string());
+    true
+}
+
+/// Queues the task's last worker job with the prompt edited in the `/rerun` editor and starts
+/// it right away.
+fn start_edited_rerun(
+    app: &mut App,
+    editor: PromptEditor,
+    worker_agent_adapters: &mut HashMap<String, CodexAdapter>,
+    active_worker_context_key: &mut Option<String>,
+    test_runner_adapter: &TestRunnerAdapter,
+    session_store: Option<&SessionStore>,
+    model_routing: &CodexAgentModelRouting,
+) {
+    let Some(active_session) = session_store else {
+        app.push_agent_message(app.system_text(Text::RerunNeedsSession, &[]));
+        return;
+    };
+    let (task_id, prompt) = editor.into_parts();
+    let messages = match app.rerun_task_with_prompt(&task_id, prompt) {
+        Ok(messages) => messages,
+        Err(err) => {
+            app.push_agent_message(
+                app.system_text(Text::RerunFailed, &[("task_id", &task_id), ("err", &err)]),
+            );
+            return;
+        }
+    };
+    for message in messages {
+        app.push_agent_message(message);
+    }
+    match DefaultCoreOrchestrationService.start_next_worker_job_if_any(
+        app,
+        worker_agent_adapters,
+        active_worker_context_key,
+        test_runner_adapter,
+        active_session,
+        model_routing,
+    ) {
+        Ok(Some(job)) => app.push_agent_message(app.system_text(
+            Text::StartingJob,
+            &[
+                ("role", &format!("{:?}", job.role)),
+                ("task", &job.top_task_id),
+            ],
+        )),
+        Ok(None) => {}
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::TaskStatusPersistFailed, &[("err", &err)]))
+        }
+    }
+}
+
+/// Restored prompts may have been built after the previous run's session intro was sent,
+/// so the restarted agent gets one unless the prompt already carries it.
+fn with_master_report_session_intro(
+    prompt: String,
+    session_store: &SessionStore,
+    project_info_text: Option<&str>,
+    master_report_session_intro_needed: &mut bool,
+) -> String {
+    if prompt.starts_with("Meta-agent session working directory:") {
+        *master_report_session_intro_needed = false;
+        return prompt;
+    }
+    subagents::build_session_intro_if_needed(
+        &prompt,
+        session_store.session_dir().display().to_string().as_str(),
+        &session_store.session_meta_file().display().to_string(),
+        project_info_text,
+        None,
+        session_store
+            .agent_persona(CodexAgentKind::MasterReport)
+            .as_deref(),
+        master_report_session_intro_needed,
+    )
+}
+
+fn reset_master_report_runtime(
+    master_report_in_flight: &mut bool,
+    pending_master_report_prompts: &mut VecDeque<String>,
+    master_report_transcript: &mut Vec<String>,
+) {
+    *master_report_in_flight = false;
+    pending_master_report_prompts.clear();
+    master_report_transcript.clear();
+}
+
+fn reset_task_check_runtime(
+    task_check_in_flight: &mut bool,
+    task_check_baseline: &mut Option<String>,
+) {
+    *task_check_in_flight = false;
+    *task_check_baseline = None;
+}
+
+fn toggle_file_tree_view(app: &mut App) {
+    if app.toggle_file_tree_mode() {
+        app.active_pane = Pane::LeftTop;
+        app.push_agent_message(app.system_text(Text::FileTreeShown, &[]));
+    } else {
+        app.push_agent_message(app.system_text(Text::FileTreeHidden, &[]));
+    }
+}
+
+/// Sends a master prompt, or holds it in the preview modal while `/preview` is on.
+/// Returns true when the prompt was dispatched.
+fn send_or_preview_master_prompt(
+    app: &mut App,
+    master_adapter: &CodexAdapter,
+    prompt: String,
+    user_message: &str,
+    consumed_session_intro: bool,
+) -> bool {
+    if app.is_master_prompt_preview_enabled() {
+        app.open_master_prompt_preview(prompt, user_message.to_string(), consumed_session_intro);
+        app.push_agent_message(app.system_text(Text::PreviewingPrompt, &[]));
+        return false;
+    }
+    master_adapter.send_prompt(prompt);
+    app.set_master_in_progress(true);
+    app.reset_prompt_sections();
+    true
+}
+
+/// Cancels a held master prompt; the session intro is re-armed if building the prompt consumed it.
+fn cancel_master_prompt_preview(app: &mut App, master_session_intro_needed: &mut bool) {
+    let Some(preview) = app.take_master_prompt_preview() else {
+        return;
+    };
+    if preview.consumed_session_intro {
+        *master_session_intro_needed = true;
+    }
+    app.restore_chat_input(&preview.user_message);
+    app.push_agent_message(app.system_text(Text::PreviewCancelled, &[]));
+}
+
+/// Follow-up sent to a worker agent the user nudged after it went quiet.
+const STALL_NUDGE_PROMPT: &str = "You have produced no output for a while. If you are stuck or waiting on something, say what it is; otherwise continue the task and report progress as you go.";
+
+/// Applies the answer to the "stalled?" prompt. A nudge stops the agent and leaves the
+/// follow-up in `pending_nudge`; the worker's completion then resumes its session with it
+/// instead of finishing the job. `worker_adapter` is `None` while tests run.
+fn handle_stall_action(
+    app: &mut App,
+    action: StallAction,
+    worker_adapter: Option<&CodexAdapter>,
+    test_runner_adapter: &TestRunnerAdapter,
+    pending_nudge: &mut Option<String>,
+    now: Instant,
+) {
+    match action {
+        StallAction::Wait => {
+            app.reset_stall_timer(now);
+            app.push_agent_message(app.system_text(Text::StallWaiting, &[]));
+        }
+        StallAction::Nudge => match worker_adapter {
+            Some(adapter) if adapter.saved_session_id().is_some() && adapter.terminate() => {
+                *pending_nudge = Some(STALL_NUDGE_PROMPT.to_string());
+                app.reset_stall_timer(now);
+                app.push_agent_message(app.system_text(Text::StallNudging, &[]));
+            }
+            _ => app.push_agent_message(app.system_text(Text::StallCannotNudge, &[])),
+        },
+        StallAction::Abort => {
+            *pending_nudge = None;
+            let stopped = match worker_adapter {
+                Some(adapter) => adapter.terminate(),
+                None => test_runner_adapter.terminate(),
+            };
+            app.reset_stall_timer(now);
+            app.push_agent_message(if stopped {
+                app.system_text(Text::StallAborting, &[])
+            } else {
+                app.system_text(Text::StallAlreadyStopped, &[])
+            });
+        }
+    }
+}
+
+fn toggle_stats_view(app: &mut App) {
+    if app.is_stats_mode() {
+        app.hide_stats();
+        app.push_agent_message(app.system_text(Text::FailureStatsHidden, &[]));
+        return;
+    }
+    match stats::collect_failure_stats() {
+        Ok(stats) => {
+            app.show_stats(stats.render_lines());
+            app.active_pane = Pane::LeftTop;
+            app.push_agent_message(app.system_text(
+                Text::FailureStatsShown,
+                &[("sessions", &stats.sessions_scanned)],
+            ));
+        }
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::FailureStatsFailed, &[("err", &err)]));
+        }
+    }
+}
+
+/// Opens the log picker, limited to the logs of the top-level task containing `task_id`
+/// when one is given.
+fn open_worker_log_picker(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+    task_id: Option<&str>,
+) {
+    let Some(active_session) = session_store else {
+        app.push_agent_message(app.system_text(Text::WorkerLogsNeedSession, &[]));
+        return;
+    };
+    let task_dir = match task_id {
+        Some(task_id) => match app.top_level_task(task_id) {
+            Some((top_id, _)) => Some(format!("{}/", worker_logs::task_log_dir_name(&top_id))),
+            None => {
+                app.push_agent_message(
+                    app.system_text(Text::NoTaskWithId, &[("task_id", &task_id)]),
+                );
+                return;
+            }
+        },
+        None => None,
+    };
+    let listed = worker_logs::list_worker_logs(&active_session.logs_dir()).map(|entries| {
+        entries
+            .into_iter()
+            .filter(|entry| {
+                task_dir
+                    .as_deref()
+                    .is_none_or(|dir| entry.label.starts_with(dir))
+            })
+            .collect::<Vec<_>>()
+    });
+    match listed {
+        Ok(entries) if entries.is_empty() && task_id.is_some() => {
+            app.push_agent_message(app.system_text(
+                Text::NoWorkerLogsForTask,
+                &[("task_id", &task_id.unwrap_or_default())],
+            ))
+        }
+        Ok(entries) if entries.is_empty() => {
+            app.push_agent_message(app.system_text(Text::NoWorkerLogs, &[]))
+        }
+        Ok(entries) => app.open_log_picker(entries),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::ListWorkerLogsFailed, &[("err", &err)]))
+        }
     }
-    job
 }
 
-enum PlannerPersistResult {
-    Persisted,
-    Unchanged,
-    Deferred,
+fn open_selected_worker_log(app: &mut App) {
+    if let Some(entry) = app.select_worker_log() {
+        app.show_worker_log(entry.label, worker_logs::read_worker_log_lines(&entry.path));
+        app.active_pane = Pane::Right;
+    }
 }
 
-fn persist_planner_markdown_if_changed(
+/// Mirrors a worker event into the active job's log file; `Completed` closes the file.
+fn record_worker_log_event(
     app: &mut App,
+    writer: &mut WorkerLogWriter,
     session_store: Option<&SessionStore>,
-) -> PlannerPersistResult {
-    let Some(session_store) = session_store else {
-        return PlannerPersistResult::Deferred;
+    event: &AgentEvent,
+) {
+    let line = match event {
+        AgentEvent::Output(line) => line.clone(),
+        AgentEvent::System(line) => format!("[system] {line}"),
+        AgentEvent::Completed { success, code } => {
+            if let (Some(active_session), Some(meta)) = (session_store, app.active_worker_job()) {
+                let _ = writer.append(
+                    &active_session.logs_dir(),
+                    &worker_logs::worker_log_relative_path(&meta),
+                    &format!("[system] completed success={success} code={code}"),
+                );
+            }
+            writer.close();
+            return;
+        }
     };
-    if let Ok(existing) = session_store.read_planner_markdown()
-        && existing == app.planner_markdown()
-    {
-        return PlannerPersistResult::Unchanged;
-    }
-    if let Err(err) = session_store.write_planner_markdown(app.planner_markdown()) {
-        app.push_agent_message(format!(
-            "System: Failed to write planner markdown to planner.md: {err}"
+    let (Some(active_session), Some(meta)) = (session_store, app.active_worker_job()) else {
+        return;
+    };
+    let relative = worker_logs::worker_log_relative_path(&meta);
+    if let Err(err) = writer.append(&active_session.logs_dir(), &relative, &line) {
+        app.push_agent_message(app.system_text(
+            Text::WorkerLogWriteFailed,
+            &[("path", &relative.display()), ("err", &err)],
         ));
-        return PlannerPersistResult::Deferred;
     }
-    PlannerPersistResult::Persisted
 }
 
-fn mark_planner_manual_edit(dirty: &mut bool, last_keystroke_at: &mut Option<Instant>) {
-    *dirty = true;
-    *last_keystroke_at = Some(Instant::now());
+/// Mirrors an agent event into the session's event log for `api events subscribe`.
+/// The digest is polled even while the user is away, since mail is how they hear about a
+/// run they are not watching; only the code index refresh waits until they are back.
+fn poll_digest_and_code_index(
+    app: &App,
+    away: bool,
+    cwd: &Path,
+    active_session: &SessionStore,
+    email_digest: &mut email_digest::EmailDigest,
+    code_index_refresher: &mut code_index::CodeIndexRefresher,
+) {
+    email_digest.poll(
+        Instant::now(),
+        active_session,
+        &app.planner_tasks_for_file(),
+        &app.estimate_comparisons(),
+        app.is_execution_busy(),
+        app.locale(),
+    );
+    if !away {
+        code_index_refresher.poll(cwd, active_session, app.changed_files(), app.locale());
+    }
 }
 
-fn flush_debounced_planner_autosave_if_due(
+fn record_session_agent_event(
     app: &mut App,
+    recorder: &mut SessionEventRecorder,
     session_store: Option<&SessionStore>,
-    dirty: &mut bool,
-    last_keystroke_at: &mut Option<Instant>,
+    agent: &str,
+    event: &AgentEvent,
 ) {
-    if !*dirty {
-        return;
-    }
-    let Some(last_input) = *last_keystroke_at else {
+    let Some(active_session) = session_store else {
         return;
     };
-    if last_input.elapsed() < PLANNER_AUTOSAVE_DEBOUNCE {
-        return;
-    }
-    match persist_planner_markdown_if_changed(app, session_store) {
-        PlannerPersistResult::Persisted | PlannerPersistResult::Unchanged => {
-            *dirty = false;
-            *last_keystroke_at = None;
-        }
-        PlannerPersistResult::Deferred => {
-            *last_keystroke_at = Some(Instant::now());
-        }
+    if let Err(err) = recorder.record_agent_event(active_session, agent, event) {
+        app.push_agent_message(app.system_text(Text::EventLogWriteFailed, &[("err", &err)]));
     }
 }
 
-fn tasks_changed_since_baseline(before: Option<&str>, after: Option<&str>) -> bool {
-    match (before, after) {
-        (Some(before), Some(after)) => before != after,
-        (None, Some(after)) => !after.trim().is_empty(),
+fn is_picker_open(app: &App) -> bool {
+    app.is_resume_picker_open()
+        || app.is_backend_picker_open()
+        || app.is_log_picker_open()
+        || app.is_task_menu_open()
+        || app.is_master_prompt_preview_open()
+        || app.is_question_form_open()
+        || app.is_rerun_editor_open()
+}
+
+/// Right click or long press: opens the action menu for the task under the pointer.
+/// F1 anywhere, or `?` where it would not be typed as text: outside pickers and modals, the
+/// planner editor, and a chat input that already holds a draft.
+fn help_requested(app: &App, event: &AppEvent) -> bool {
+    match event {
+        AppEvent::ShowHelp => true,
+        AppEvent::InputChar('?') if !is_picker_open(app) => match app.active_pane {
+            Pane::LeftTop => true,
+            Pane::LeftBottom => app.chat_input().is_empty(),
+            Pane::Right => !app.is_planner_mode(),
+        },
         _ => false,
     }
 }
 
-fn should_clear_task_write_baseline(
-    tasks_refresh_ok: bool,
-    requested_task_file_retry: bool,
-) -> bool {
-    tasks_refresh_ok || !requested_task_file_retry
+/// Keys while the help overlay is open: typing searches, Up/Down scroll, and Enter, F1, or
+/// `?` on an empty search close it. Backspace clears the search before closing.
+fn handle_help_overlay_event(app: &mut App, screen: Rect, event: &AppEvent) {
+    let max_scroll = ui::help_overlay_max_scroll(screen, app);
+    let Some(help) = app.help_overlay_mut() else {
+        return;
+    };
+    let close = match event {
+        AppEvent::Submit | AppEvent::ShowHelp => true,
+        AppEvent::InputChar('?') if help.query().is_empty() => true,
+        AppEvent::InputChar(c) => {
+            help.push_query(&c.to_string());
+            false
+        }
+        AppEvent::Paste(content) => {
+            help.push_query(content);
+            false
+        }
+        AppEvent::Backspace => !help.pop_query(),
+        AppEvent::MoveUp | AppEvent::ScrollChatUp | AppEvent::MouseScrollUp => {
+            help.scroll_up();
+            false
+        }
+        AppEvent::MoveDown | AppEvent::ScrollChatDown | AppEvent::MouseScrollDown => {
+            help.scroll_down(max_scroll);
+            false
+        }
+        _ => false,
+    };
+    if close {
+        app.close_help_overlay();
+    }
 }
 
-fn reset_master_report_runtime(
-    master_report_in_flight: &mut bool,
-    pending_master_report_prompts: &mut VecDeque<String>,
-    master_report_transcript: &mut Vec<String>,
-) {
-    *master_report_in_flight = false;
-    pending_master_report_prompts.clear();
-    master_report_transcript.clear();
+fn handle_mouse_context_menu(app: &mut App, screen: Rect, column: u16, row: u16) {
+    if is_picker_open(app) {
+        return;
+    }
+    if ui::pane_hit_test(screen, column, row) != Some(Pane::Right) {
+        return;
+    }
+    app.active_pane = Pane::Right;
+    if let Some(task_key) = ui::right_pane_task_hit_test(screen, app, column, row) {
+        app.open_task_menu(&task_key);
+    }
 }
 
-fn reset_task_check_runtime(
-    task_check_in_flight: &mut bool,
-    task_check_baseline: &mut Option<String>,
-) {
-    *task_check_in_flight = false;
-    *task_check_baseline = None;
+/// Acts on the chosen task menu entry. Returns the slash command to submit for entries that
+/// are shortcuts for one.
+fn activate_task_menu_entry(
+    app: &mut App,
+    model_routing: &CodexAgentModelRouting,
+) -> Option<String> {
+    let (target, entry) = app.select_task_menu_entry()?;
+    match entry {
+        TaskMenuEntry::ToggleDetails => {
+            app.toggle_task_details(&target.task_id);
+            None
+        }
+        TaskMenuEntry::ChangeModel => {
+            app.open_task_model_menu(target, model_routing.profile_labels());
+            None
+        }
+        TaskMenuEntry::ShowConversation => {
+            app.show_task_thread(&target.task_id);
+            app.active_pane = Pane::LeftBottom;
+            None
+        }
+        entry => entry.command(&target),
+    }
 }
 
-fn is_picker_open(app: &App) -> bool {
-    app.is_resume_picker_open() || app.is_backend_picker_open()
+fn write_session_settings(app: &App, session_store: &SessionStore) -> io::Result<()> {
+    session_store.write_session_settings(&SessionSettingsFile {
+        review_changes: app.review_changes_enabled(),
+        task_models: app.task_models().clone(),
+        speed: app.speed_profile(),
+    })
 }
 
 fn handle_mouse_left_click(app: &mut App, screen: Rect, column: u16, row: u16) {
@@ -2521,6 +7017,13 @@ fn handle_mouse_left_click(app: &mut App, screen: Rect, column: u16, row: u16) {
         app.active_pane = pane;
     }
 
+    if app.active_pane == Pane::LeftTop
+        && let Some(citation) = ui::left_top_citation_hit_test(screen, app, column, row)
+    {
+        app.open_citation(&citation);
+        return;
+    }
+
     if app.is_planner_mode() && app.active_pane == Pane::Right {
         if let Some(cursor) = ui::planner_cursor_hit_test(screen, app, column, row) {
             app.set_planner_cursor(cursor);
@@ -2557,30 +7060,26 @@ fn apply_half_page_scroll(app: &mut App, screen: Rect, button: ui::ScrollButton)
                 }
             }
         },
-        ui::ScrollButton::Down => {
-            let max_scroll = match pane {
-                Pane::LeftTop => ui::left_top_max_scroll(screen, app),
-                Pane::LeftBottom => ui::chat_max_scroll(screen, app),
-                Pane::Right => ui::right_max_scroll(screen, app),
-            };
-            match pane {
-                Pane::LeftTop => {
-                    for _ in 0..delta {
-                        app.scroll_left_top_down(max_scroll);
-                    }
+        ui::ScrollButton::Down => match pane {
+            Pane::LeftTop => {
+                let max_scroll = ui::left_top_max_scroll(screen, app);
+                for _ in 0..delta {
+                    app.scroll_left_top_down(max_scroll);
                 }
-                Pane::LeftBottom => {
-                    for _ in 0..delta {
-                        app.scroll_chat_down(max_scroll);
-                    }
+            }
+            Pane::LeftBottom => {
+                let max_scroll = ui::chat_max_scroll(screen, app);
+                for _ in 0..delta {
+                    app.scroll_chat_down(max_scroll);
                 }
-                Pane::Right => {
-                    for _ in 0..delta {
-                        app.scroll_right_down(max_scroll);
-                    }
+            }
+            Pane::Right => {
+                let max_scroll = ui::right_max_scroll(screen, app);
+                for _ in 0..delta {
+                    app.scroll_right_down(max_scroll);
                 }
             }
-        }
+        },
     }
 }
 
@@ -2650,6 +7149,113 @@ fn persist_backend_selection(selected_backend: BackendKind) -> io::Result<std::p
     Ok(config_file)
 }
 
+/// Handles `/pin-models`: records the session's models first when meta.json lacks them, then
+/// sets or clears `models_pinned`. The loop applies the change once agents are idle.
+fn pin_session_models(
+    app: &mut App,
+    session_store: &SessionStore,
+    model_routing: &CodexAgentModelRouting,
+    command: PinModelsCommand,
+) -> String {
+    let configured = model_routing.configured_models();
+    let result = match command {
+        PinModelsCommand::Usage => {
+            return app.system_text(Text::PinModelsUsage, &[]);
+        }
+        PinModelsCommand::Pin => session_store
+            .record_session_models(&configured)
+            .and_then(|_| session_store.set_session_models_pinned(true)),
+        PinModelsCommand::Unpin => session_store.set_session_models_pinned(false),
+    };
+    if let Err(err) = result {
+        return app.system_text(
+            Text::UpdateFileFailed,
+            &[
+                ("path", &session_store.session_meta_file().display()),
+                ("err", &err),
+            ],
+        );
+    }
+    app.mark_session_models_changed();
+    if command == PinModelsCommand::Unpin {
+        return app.system_text(Text::UnpinnedModels, &[]);
+    }
+    let drift = session_store
+        .read_session_meta()
+        .map(|meta| agent_models::model_drift(&meta.models, &configured))
+        .unwrap_or_default();
+    if drift.is_empty() {
+        app.system_text(Text::PinnedModelsUnchanged, &[])
+    } else {
+        app.system_text(
+            Text::PinnedModels,
+            &[("drift", &describe_model_drift(&drift))],
+        )
+    }
+}
+
+/// Applies the models the active session pins to `model_routing`, clearing those of the
+/// previous session. When the session was just opened, also records its models if meta.json
+/// has none yet, or warns when config.toml now routes its agents elsewhere. Returns whether
+/// the pinned models changed, so the adapters need rebuilding.
+fn sync_session_models(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+    model_routing: &mut CodexAgentModelRouting,
+    opened: bool,
+) -> bool {
+    let meta = session_store.and_then(|store| store.read_session_meta().ok());
+    let pinned = meta
+        .as_ref()
+        .filter(|meta| meta.models_pinned)
+        .map(|meta| meta.models.clone())
+        .unwrap_or_default();
+    if opened && let (Some(store), Some(meta)) = (session_store, meta.as_ref()) {
+        let configured = model_routing.configured_models();
+        if meta.models.is_empty() {
+            // Sessions from before models were recorded start tracking them from here.
+            if let Err(err) = store.record_session_models(&configured) {
+                app.push_agent_message(
+                    app.system_text(Text::SessionModelsRecordFailed, &[("err", &err)]),
+                );
+            }
+        } else {
+            let drift = agent_models::model_drift(&meta.models, &configured);
+            if !drift.is_empty() && meta.models_pinned {
+                app.push_agent_message(app.system_text(
+                    Text::PinnedModelDrift,
+                    &[("drift", &describe_model_drift(&drift))],
+                ));
+            } else if !drift.is_empty() {
+                app.push_agent_message(app.system_text(
+                    Text::ModelDrift,
+                    &[("drift", &describe_model_drift(&drift))],
+                ));
+            }
+        }
+    }
+    if *model_routing.pinned_models() == pinned {
+        return false;
+    }
+    model_routing.set_pinned_models(pinned);
+    true
+}
+
+fn describe_model_drift(drift: &[agent_models::ModelDrift]) -> String {
+    drift
+        .iter()
+        .map(|drift| {
+            format!(
+                "{} was {}, now {}",
+                drift.agent,
+                drift.recorded.describe(),
+                drift.configured.describe()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 fn rebuild_runtime_adapters(
     model_routing: &CodexAgentModelRouting,
     selected_backend: BackendKind,
@@ -2692,7 +7298,13 @@ fn rebuild_model_routing_with_backend_selection(
 ) -> io::Result<()> {
     let merged = load_merged_metaagent_config_text().unwrap_or_default();
     let updated = update_backend_selected_in_toml(&merged, selected_backend)?;
+    let safe_mode = model_routing.safe_mode().cloned();
+    let pinned_models = model_routing.pinned_models().clone();
+    let workspace = model_routing.workspace().map(Path::to_path_buf);
     *model_routing = CodexAgentModelRouting::from_toml_str(&updated)?;
+    model_routing.set_safe_mode(safe_mode);
+    model_routing.set_pinned_models(pinned_models);
+    model_routing.set_workspace(workspace);
     Ok(())
 }
 
@@ -2712,21 +7324,73 @@ fn apply_backend_selection(
     let target = selected.kind;
     let was = *selected_backend;
     if was == target {
-        app.push_agent_message(format!("System: Backend remains {}.", selected.label));
+        app.push_agent_message(
+            app.system_text(Text::BackendUnchanged, &[("backend", &selected.label)]),
+        );
         return;
     }
     *selected_backend = target;
+    switch_runtime_backend(
+        app,
+        target,
+        selected.label,
+        model_routing,
+        active_worker_context_key,
+        worker_agent_adapters,
+        master_adapter,
+        master_report_adapter,
+        project_info_adapter,
+        docs_attach_adapter,
+        task_check_adapter,
+    );
+
+    match persist_backend_selection(target) {
+        Ok(config_file) => app.push_agent_message(app.system_text(
+            Text::BackendSaved,
+            &[
+                ("backend", &selected.label),
+                ("path", &config_file.display()),
+            ],
+        )),
+        Err(err) => app.push_agent_message(app.system_text(
+            Text::BackendSaveFailed,
+            &[("backend", &selected.label), ("err", &err)],
+        )),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn switch_runtime_backend(
+    app: &mut App,
+    target: BackendKind,
+    label: &str,
+    model_routing: &mut CodexAgentModelRouting,
+    active_worker_context_key: &mut Option<String>,
+    worker_agent_adapters: &mut HashMap<String, CodexAdapter>,
+    master_adapter: &mut CodexAdapter,
+    master_report_adapter: &mut CodexAdapter,
+    project_info_adapter: &mut CodexAdapter,
+    docs_attach_adapter: &mut CodexAdapter,
+    task_check_adapter: &mut CodexAdapter,
+) {
     if let Err(err) = rebuild_model_routing_with_backend_selection(model_routing, target) {
-        app.push_agent_message(format!(
-            "System: Backend switched to {} in memory, but model config reload failed: {err}. Using fallback defaults for future adapters.",
-            selected.label
+        app.push_agent_message(app.system_text(
+            Text::BackendModelReloadFailed,
+            &[("label", &label), ("err", &err)],
         ));
+        let safe_mode = model_routing.safe_mode().cloned();
+        let pinned_models = model_routing.pinned_models().clone();
+        let workspace = model_routing.workspace().map(Path::to_path_buf);
         *model_routing = CodexAgentModelRouting::from_toml_str(&format!(
             "[backend]\nselected = \"{}\"\n",
             backend_label(target)
         ))
         .unwrap_or_default();
+        model_routing.set_safe_mode(safe_mode);
+        model_routing.set_pinned_models(pinned_models);
+        model_routing.set_workspace(workspace);
     }
+    app.set_runtime_backend(backend_label(target));
     rebuild_runtime_adapters(
         model_routing,
         target,
@@ -2738,18 +7402,51 @@ fn apply_backend_selection(
         active_worker_context_key,
         worker_agent_adapters,
     );
+}
 
-    match persist_backend_selection(target) {
-        Ok(config_file) => app.push_agent_message(format!(
-            "System: Backend set to {}. Saved to {}. New adapters will use this backend.",
-            selected.label,
-            config_file.display()
-        )),
-        Err(err) => app.push_agent_message(format!(
-            "System: Backend set to {} for this run, but persistence to config.toml failed (default path is ~/.agentbob/config.toml; legacy fallbacks: ~/.bob/config.toml, ~/.metaagent/config.toml): {err}. New adapters in this run will still use this backend.",
-            selected.label
-        )),
-    }
+/// Moves this run to the other backend after `failures` worker jobs failed in a row. Unlike
+/// `/backend`, the switch is not persisted, so the next launch starts on the configured one.
+#[allow(clippy::too_many_arguments)]
+fn fail_over_backend(
+    app: &mut App,
+    failures: u64,
+    selected_backend: &mut BackendKind,
+    model_routing: &mut CodexAgentModelRouting,
+    active_worker_context_key: &mut Option<String>,
+    worker_agent_adapters: &mut HashMap<String, CodexAdapter>,
+    master_adapter: &mut CodexAdapter,
+    master_report_adapter: &mut CodexAdapter,
+    project_info_adapter: &mut CodexAdapter,
+    docs_attach_adapter: &mut CodexAdapter,
+    task_check_adapter: &mut CodexAdapter,
+) {
+    // The picker lists the selected backend first.
+    let options = backend_picker_options(*selected_backend);
+    let [current, fallback] = options.as_slice() else {
+        return;
+    };
+    *selected_backend = fallback.kind;
+    switch_runtime_backend(
+        app,
+        fallback.kind,
+        fallback.label,
+        model_routing,
+        active_worker_context_key,
+        worker_agent_adapters,
+        master_adapter,
+        master_report_adapter,
+        project_info_adapter,
+        docs_attach_adapter,
+        task_check_adapter,
+    );
+    app.push_agent_message(app.system_text(
+        Text::BackendFailover,
+        &[
+            ("failures", &failures),
+            ("current", &current.label),
+            ("fallback", &fallback.label),
+        ],
+    ));
 }
 
 fn scroll_right_up_global(app: &mut App) {
@@ -2772,6 +7469,8 @@ fn should_send_to_master(message: &str) -> bool {
 fn should_initialize_session_for_message(message: &str) -> bool {
     let trimmed = message.trim();
     !trimmed.starts_with('/')
+        || App::direct_instruction(trimmed).is_some_and(|i| !i.is_empty())
+        || App::parse_import_plan_command(trimmed).is_some()
 }
 
 fn command_requires_active_session(message: &str) -> bool {
@@ -2780,8 +7479,20 @@ fn command_requires_active_session(message: &str) -> bool {
         return false;
     }
     App::is_start_execution_command(trimmed)
+        || App::parse_start_task_command(trimmed).is_some()
+        || App::parse_manual_task_command(trimmed).is_some()
+        || App::parse_review_command(trimmed).is_some()
+        || App::parse_retry_command(trimmed).is_some()
+        || App::parse_unblock_command(trimmed).is_some()
+        || App::parse_rerun_command(trimmed).is_some()
+        || App::direct_instruction(trimmed).is_some()
+        || App::parse_task_model_command(trimmed).is_some()
+        || App::parse_pin_models_command(trimmed).is_some()
+        || App::parse_speed_command(trimmed).is_some()
         || App::is_planner_mode_command(trimmed)
         || App::is_convert_command(trimmed)
+        || App::parse_import_plan_command(trimmed).is_some()
+        || App::parse_tasks_command(trimmed).is_some()
         || App::is_attach_docs_command(trimmed)
         || parse_silent_master_command(trimmed).is_some()
         || App::is_add_final_audit_command(trimmed)
@@ -2794,33 +7505,45 @@ fn initialize_session_for_message_if_needed(
     cwd: &Path,
     session_store: &mut Option<SessionStore>,
     project_info_text: &mut Option<String>,
+    model_routing: &CodexAgentModelRouting,
 ) -> io::Result<()> {
     if !should_initialize_session_for_message(message) || session_store.is_some() {
         return Ok(());
     }
 
     let store = SessionStore::initialize(cwd)?;
-    app.push_agent_message(format!(
-        "System: Session dir initialized at {}",
-        store.session_dir().display()
+    app.push_agent_message(app.system_text(
+        Text::SessionDirInitialized,
+        &[("path", &store.session_dir().display())],
     ));
     match store.read_tasks() {
         Ok(tasks) => match app.sync_planner_tasks_from_file(tasks) {
             Ok(()) => {}
-            Err(err) => app.push_agent_message(format!(
-                "System: Failed to refresh task tree from tasks.json: {err}"
-            )),
+            Err(err) => app
+                .push_agent_message(app.system_text(Text::TaskTreeRefreshFailed, &[("err", &err)])),
         },
-        Err(err) => app.push_agent_message(format!("System: Failed to read tasks.json: {err}")),
+        Err(err) => {
+            app.push_agent_message(app.system_text(Text::TasksReadFailed, &[("err", &err)]))
+        }
     }
     if let Ok(markdown) = store.read_planner_markdown() {
-        app.set_planner_markdown(markdown);
+        app.load_planner_markdown(markdown);
+    }
+    if App::parse_import_plan_command(message).is_none()
+        && app.planner_markdown().trim().is_empty()
+        && let Some(plan) = plan_import::find(cwd)
+    {
+        app.push_agent_message(app.system_text(Text::FoundPlanFile, &[("file", &plan.file_name)]));
     }
     *project_info_text = store
         .read_project_info()
         .ok()
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty());
+    hold_session_lock(app, &store);
+    audit_trail::set_session(Some(store.audit_trail_file()));
+    // Before the message that opened the session reaches an agent.
+    sync_safe_mode_session(model_routing, Some(&store));
     *session_store = Some(store);
     Ok(())
 }
@@ -2832,6 +7555,7 @@ fn initialize_session_for_planner_edit_if_needed(
     project_info_text: &mut Option<String>,
     project_info_adapter: &CodexAdapter,
     master_adapter: &CodexAdapter,
+    model_routing: &CodexAgentModelRouting,
 ) -> io::Result<bool> {
     if session_store.is_some() {
         return Ok(false);
@@ -2843,6 +7567,7 @@ fn initialize_session_for_planner_edit_if_needed(
         cwd,
         session_store,
         project_info_text,
+        model_routing,
     )?;
     let prompt = PLANNER_PREFILL_INIT_PROMPT.to_string();
     project_info_adapter.send_prompt(prompt.clone());
@@ -2858,11 +7583,23 @@ fn conflicts_with_running_execution(message: &str) -> bool {
     should_send_to_master(message)
         || App::is_new_master_command(message)
         || App::is_resume_command(message)
+        || App::is_switch_command(message)
+        || matches!(App::parse_lock_command(message), Some(Some(_)))
         || App::is_convert_command(message)
+        || App::parse_import_plan_command(message).is_some()
+        || App::parse_tasks_command(message).is_some()
         || App::is_attach_docs_command(message)
         || parse_silent_master_command(message).is_some()
         || App::is_add_final_audit_command(message)
         || App::is_remove_final_audit_command(message)
+        || App::is_approve_split_command(message)
+        || App::is_reject_tasks_command(message)
+        || App::is_reload_tasks_command(message)
+        || App::parse_start_task_command(message).is_some()
+        || App::parse_retry_command(message).is_some()
+        || App::parse_rerun_command(message).is_some()
+        || App::direct_instruction(message).is_some()
+        || App::parse_pin_models_command(message).is_some()
 }
 
 fn submit_block_reason(
@@ -2875,10 +7612,49 @@ fn submit_block_reason(
     if App::is_quit_command(message) {
         return None;
     }
-    if is_backend_command(message) {
+    if is_backend_command(message) {
+        return None;
+    }
+    if App::is_toggle_tests_command(message) {
+        return None;
+    }
+    if App::is_files_command(message) {
+        return None;
+    }
+    if App::is_stats_command(message) {
+        return None;
+    }
+    if App::parse_console_command(message).is_some() {
+        return None;
+    }
+    if App::is_logs_command(message) {
+        return None;
+    }
+    if App::parse_task_model_command(message).is_some() {
         return None;
     }
-    if App::is_toggle_tests_command(message) {
+    if App::parse_speed_command(message).is_some() {
+        return None;
+    }
+    if App::parse_jobs_command(message).is_some() {
+        return None;
+    }
+    if App::is_preview_command(message) || App::is_verbose_command(message) {
+        return None;
+    }
+    if App::parse_cache_command(message).is_some() {
+        return None;
+    }
+    if App::parse_plugin_command(message).is_some() {
+        return None;
+    }
+    if App::parse_context_command(message).is_some() || App::is_summaries_command(message) {
+        return None;
+    }
+    if App::is_clear_queue_command(message) {
+        return None;
+    }
+    if App::parse_cancel_command(message).is_some() {
         return None;
     }
     if project_info_in_flight {
@@ -2896,6 +7672,42 @@ fn submit_block_reason(
     None
 }
 
+/// A queued message goes out once the stage that queued it is done. Execution conflicts are
+/// not waited on; the normal submit path reports them when the message is sent.
+fn queued_message_ready(
+    project_info_in_flight: bool,
+    master_in_progress: bool,
+    task_check_in_progress: bool,
+    message: &str,
+) -> bool {
+    submit_block_reason(
+        project_info_in_flight,
+        master_in_progress,
+        task_check_in_progress,
+        false,
+        message,
+    )
+    .is_none()
+}
+
+/// A batch step goes out once everything the previous step started has finished, execution
+/// and queued messages included, so a script can plan, convert, and start in sequence.
+fn batch_step_ready(
+    project_info_in_flight: bool,
+    master_in_progress: bool,
+    task_check_in_progress: bool,
+    docs_attach_in_flight: bool,
+    execution_busy: bool,
+    queued_messages: usize,
+) -> bool {
+    !project_info_in_flight
+        && !master_in_progress
+        && !task_check_in_progress
+        && !docs_attach_in_flight
+        && !execution_busy
+        && queued_messages == 0
+}
+
 fn enqueue_or_dispatch_master_report_prompt(
     prompt: String,
     master_report_in_flight: &mut bool,
@@ -2931,25 +7743,70 @@ fn should_start_task_check(
     changed_tasks && !task_check_in_flight && !docs_attach_in_flight
 }
 
+/// Collects output a worker's reader threads still flush after its completion, waiting on
+/// the worker's own event channel until it stays quiet for a moment.
 fn drain_post_completion_worker_events(adapter: &CodexAdapter) -> Vec<AgentEvent> {
-    const MAX_TAIL_POLLS: usize = 24;
-    const MAX_IDLE_POLLS: usize = 8;
+    const QUIET_PERIOD: Duration = Duration::from_millis(80);
+    const MAX_TAIL_WAIT: Duration = Duration::from_millis(250);
+    const MAX_TAIL_EVENTS: usize = 24 * MAX_ADAPTER_EVENTS_PER_LOOP;
+    let deadline = Instant::now() + MAX_TAIL_WAIT;
     let mut out = Vec::new();
-    let mut idle_polls = 0usize;
-    for _ in 0..MAX_TAIL_POLLS {
-        let batch = adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP);
-        if batch.is_empty() {
-            idle_polls = idle_polls.saturating_add(1);
-            if idle_polls >= MAX_IDLE_POLLS {
-                break;
+    while out.len() < MAX_TAIL_EVENTS {
+        let wait = QUIET_PERIOD.min(deadline.saturating_duration_since(Instant::now()));
+        let Some(event) = adapter.wait_event(wait) else {
+            break;
+        };
+        out.push(event);
+    }
+    out
+}
+
+/// Whether the user is at the terminal: it has focus and key or mouse input arrived within
+/// [`INPUT_IDLE_AFTER`]. While away the main loop ticks slower and defers optional work.
+#[derive(Debug, Clone, Copy)]
+struct UserPresence {
+    focused: bool,
+    last_input_at: Instant,
+}
+
+impl UserPresence {
+    fn new(now: Instant) -> Self {
+        Self {
+            focused: true,
+            last_input_at: now,
+        }
+    }
+
+    fn is_away(&self, now: Instant) -> bool {
+        !self.focused || now.saturating_duration_since(self.last_input_at) >= INPUT_IDLE_AFTER
+    }
+
+    /// Records `event`; returns true when it brings an away user back.
+    fn on_event(&mut self, event: &AppEvent, now: Instant) -> bool {
+        let was_away = self.is_away(now);
+        match event {
+            AppEvent::Tick | AppEvent::Resize => return false,
+            AppEvent::FocusLost => self.focused = false,
+            AppEvent::FocusGained => {
+                self.focused = true;
+                self.last_input_at = now;
             }
-            std::thread::sleep(Duration::from_millis(10));
-            continue;
+            _ => self.last_input_at = now,
         }
-        idle_polls = 0;
-        out.extend(batch);
+        was_away && !self.is_away(now)
     }
-    out
+}
+
+/// How long the loop may wait for input or agent output before the earliest of `deadlines`.
+fn loop_wait_timeout(
+    now: Instant,
+    deadlines: impl IntoIterator<Item = Option<Instant>>,
+) -> Duration {
+    deadlines
+        .into_iter()
+        .flatten()
+        .map(|deadline| deadline.saturating_duration_since(now))
+        .fold(IDLE_WAIT_CAP, Duration::min)
 }
 
 fn should_process_master_task_file_updates(execution_busy: bool) -> bool {
@@ -2993,11 +7850,11 @@ fn handle_silent_master_command<B: Backend>(
 
     let (status_message, command_prompt) = match command {
         SilentMasterCommand::SplitAudits => (
-            "System: Splitting audits...".to_string(),
+            app.system_text(Text::SplittingAudits, &[]),
             subagents::split_audits_command_prompt(),
         ),
         SilentMasterCommand::MergeAudits => (
-            "System: Merging audits...".to_string(),
+            app.system_text(Text::MergingAudits, &[]),
             subagents::merge_audits_command_prompt(),
         ),
         #[cfg(test)]
@@ -3007,17 +7864,24 @@ fn handle_silent_master_command<B: Backend>(
     app.push_agent_message(status_message);
     let master_prompt = app.prepare_master_prompt(
         &command_prompt,
-        &session_store.tasks_file().display().to_string(),
+        &services::master_tasks_file(app, session_store)
+            .display()
+            .to_string(),
     );
     let with_intro = subagents::build_session_intro_if_needed(
         &master_prompt,
         session_store.session_dir().display().to_string().as_str(),
         &session_store.session_meta_file().display().to_string(),
-        project_info_text,
+        app.project_info_for_prompt(project_info_text),
+        project_memory::session_memory_section(session_store).as_deref(),
+        session_store
+            .agent_persona(CodexAgentKind::Master)
+            .as_deref(),
         master_session_intro_needed,
     );
     master_adapter.send_prompt(with_intro);
     app.set_master_in_progress(true);
+    app.reset_prompt_sections();
     *pending_task_write_baseline = orchestration_service.capture_tasks_baseline(session_store);
 
     let size = terminal.size()?;
@@ -3027,6 +7891,57 @@ fn handle_silent_master_command<B: Backend>(
     Ok(true)
 }
 
+/// Sends a task-check or docs-attach prompt, or replays the result of an identical earlier
+/// run from the response cache. The key covers the prompt, the adapter's program and model,
+/// and the session files the sub-agent reads; a hit writes the cached tasks.json back.
+fn send_cacheable_tasks_prompt(
+    app: &mut App,
+    adapter: &CodexAdapter,
+    call: CachedCall,
+    prompt: String,
+    session: &SessionStore,
+) {
+    let read = |path: &Path| std::fs::read_to_string(path).unwrap_or_default();
+    let key = ResponseCache::key(
+        call,
+        &[
+            &prompt,
+            adapter.program(),
+            adapter.model().unwrap_or_default(),
+            &read(session.tasks_file()),
+            &read(session.project_info_file()),
+            &read(session.session_meta_file()),
+        ],
+    );
+    let Some(output) = app.response_cache_mut().lookup(call, key) else {
+        adapter.send_prompt(prompt);
+        return;
+    };
+    match session.write_tasks_text(&output) {
+        Ok(()) => adapter.complete_from_cache(format!(
+            "Reused the cached {} result for identical inputs; no model call was made.",
+            call.label()
+        )),
+        Err(err) => {
+            app.push_agent_message(app.system_text(
+                Text::CachedResultApplyFailed,
+                &[("call", &call.label()), ("err", &err)],
+            ));
+            adapter.send_prompt(prompt);
+        }
+    }
+}
+
+/// Saves a finished run's tasks.json under the key its cache miss left; `None` for a failed run.
+fn store_cached_response(app: &mut App, call: CachedCall, output: Option<&str>) {
+    if let Err(err) = app.response_cache_mut().complete(call, output) {
+        app.push_agent_message(app.system_text(
+            Text::CachedResultSaveFailed,
+            &[("call", &call.label()), ("err", &err)],
+        ));
+    }
+}
+
 fn is_slash_start_command(message: &str) -> bool {
     let trimmed = message.trim();
     trimmed.starts_with('/') && App::is_start_execution_command(trimmed)
@@ -3042,19 +7957,54 @@ fn is_known_slash_command(message: &str) -> bool {
         return false;
     }
     App::is_start_execution_command(trimmed)
+        || App::parse_start_task_command(trimmed).is_some()
         || is_backend_command(trimmed)
         || App::is_toggle_tests_command(trimmed)
         || App::is_planner_mode_command(trimmed)
         || App::is_skip_plan_command(trimmed)
+        || App::is_files_command(trimmed)
+        || App::is_stats_command(trimmed)
+        || App::parse_console_command(trimmed).is_some()
+        || App::is_logs_command(trimmed)
+        || App::is_preview_command(trimmed)
+        || App::is_verbose_command(trimmed)
+        || App::parse_cache_command(trimmed).is_some()
+        || App::parse_pin_models_command(trimmed).is_some()
+        || App::parse_context_command(trimmed).is_some()
+        || App::is_summaries_command(trimmed)
+        || App::parse_plugin_command(trimmed).is_some()
         || App::is_convert_command(trimmed)
         || App::is_quit_command(trimmed)
         || App::is_attach_docs_command(trimmed)
         || App::is_new_master_command(trimmed)
+        || App::new_from_template_name(trimmed).is_some()
         || App::is_resume_command(trimmed)
+        || App::is_switch_command(trimmed)
         || App::is_split_audits_command(trimmed)
         || App::is_merge_audits_command(trimmed)
         || App::is_add_final_audit_command(trimmed)
         || App::is_remove_final_audit_command(trimmed)
+        || App::is_approve_split_command(trimmed)
+        || App::is_reject_split_command(trimmed)
+        || App::is_accept_tasks_command(trimmed)
+        || App::is_reject_tasks_command(trimmed)
+        || App::is_reload_tasks_command(trimmed)
+        || App::is_keep_tasks_command(trimmed)
+        || App::parse_conflict_command(trimmed).is_some()
+        || App::parse_lock_command(trimmed).is_some()
+        || App::is_clear_queue_command(trimmed)
+        || App::parse_manual_task_command(trimmed).is_some()
+        || App::parse_review_command(trimmed).is_some()
+        || App::parse_retry_command(trimmed).is_some()
+        || App::parse_unblock_command(trimmed).is_some()
+        || App::parse_rerun_command(trimmed).is_some()
+        || App::parse_task_model_command(trimmed).is_some()
+        || App::parse_speed_command(trimmed).is_some()
+        || App::direct_instruction(trimmed).is_some()
+        || App::parse_jobs_command(trimmed).is_some()
+        || App::parse_cancel_command(trimmed).is_some()
+        || App::parse_import_plan_command(trimmed).is_some()
+        || App::parse_tasks_command(trimmed).is_some()
 }
 
 #[allow(dead_code)]
@@ -3092,6 +8042,13 @@ enum CliOutputMode {
     Json,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "snake_case")]
+enum CliGraphFormat {
+    Mermaid,
+    Dot,
+}
+
 impl Default for CliOutputMode {
     fn default() -> Self {
         Self::Human
@@ -3106,6 +8063,10 @@ struct LaunchCli {
     output_mode: CliOutputMode,
     #[arg(long = "verbose", default_value_t = false)]
     verbose: bool,
+    /// Run workers in the backend's read-only sandbox and stage master tasks.json writes
+    /// for review.
+    #[arg(long = "safe-mode", default_value_t = false)]
+    safe_mode: bool,
     #[command(subcommand)]
     command: Option<RootCommand>,
 }
@@ -3113,6 +8074,107 @@ struct LaunchCli {
 #[derive(Debug, Clone, Subcommand)]
 enum RootCommand {
     Api(ApiRootCommand),
+    Observe(ObserveCommand),
+    /// Serve a read-only web dashboard of a session on localhost.
+    ServeUi(ServeUiCommand),
+    /// Aggregate failure analytics across all stored sessions.
+    Stats(StatsRootCommand),
+    /// Inspect the global and project-local configuration.
+    Config(ConfigRootCommand),
+    /// Plan a spec file into a ready-to-execute tasks.json without the TUI.
+    Plan(PlanCommand),
+    /// Store integration secrets in the OS keychain or the encrypted credentials file.
+    Auth(AuthRootCommand),
+    /// Move sessions between the local storage root and the `[storage.remote]` bucket.
+    Storage(StorageRootCommand),
+}
+
+#[derive(Debug, Clone, Args)]
+struct StatsRootCommand {
+    #[command(subcommand)]
+    action: Option<StatsCommand>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum StatsCommand {
+    /// Latency percentiles, failures, and retries of agent CLI calls per agent and backend.
+    Backends,
+}
+
+#[derive(Debug, Clone, Args)]
+struct AuthRootCommand {
+    #[command(subcommand)]
+    action: AuthCommand,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum AuthCommand {
+    /// Store the secret for a service (`linear`, `jira`, `smtp`, `storage`), read from stdin.
+    Set { service: String },
+    /// Remove the stored secret for a service.
+    Remove { service: String },
+}
+
+#[derive(Debug, Clone, Args)]
+struct StorageRootCommand {
+    #[command(subcommand)]
+    action: StorageCommand,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum StorageCommand {
+    /// List the sessions in remote storage and whether each is also stored locally.
+    List,
+    /// Upload a session, given by directory name or path, to remote storage.
+    Push { session: String },
+    /// Download a session into the local storage root so `/resume` can open it.
+    Pull {
+        session: String,
+        /// Workspace the pulled session runs in; defaults to its recorded workspace when that
+        /// exists here, else the current directory.
+        #[arg(long)]
+        workspace: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Args)]
+struct PlanCommand {
+    /// Markdown spec describing what to build; sent to the master as the first message.
+    #[arg(long)]
+    spec: PathBuf,
+    /// Where to write the validated tasks.json.
+    #[arg(long)]
+    out: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+struct ConfigRootCommand {
+    #[command(subcommand)]
+    action: ConfigCommand,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum ConfigCommand {
+    /// Show the global config, or with `--effective` the merged config with each key's source.
+    Show {
+        #[arg(long, default_value_t = false)]
+        effective: bool,
+    },
+}
+
+#[derive(Debug, Clone, Args)]
+struct ObserveCommand {
+    #[arg(long)]
+    session_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+struct ServeUiCommand {
+    #[arg(long)]
+    session_dir: PathBuf,
+    /// Port on 127.0.0.1; 0 picks a free one.
+    #[arg(long, default_value_t = web_ui::DEFAULT_PORT)]
+    port: u16,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -3139,6 +8201,77 @@ enum ApiResourceCommand {
         #[command(subcommand)]
         action: SessionCommand,
     },
+    Events {
+        #[command(subcommand)]
+        action: EventsCommand,
+    },
+    Subagent {
+        #[command(subcommand)]
+        action: SubagentCommand,
+    },
+    Backend {
+        #[command(subcommand)]
+        action: BackendCommand,
+    },
+    Sync {
+        #[command(subcommand)]
+        action: SyncCommand,
+    },
+    Prompts {
+        #[command(subcommand)]
+        action: PromptsCommand,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum PromptsCommand {
+    /// Render every subagent and worker prompt against fixture task trees and diff them with
+    /// golden files.
+    Snapshot {
+        /// Directory of golden `<name>.txt` files.
+        #[arg(long)]
+        golden_dir: PathBuf,
+        /// Directory of `*.json` task trees to use instead of the built-in fixture.
+        #[arg(long)]
+        fixtures_dir: Option<PathBuf>,
+        /// Rewrite changed and missing golden files and delete stale ones.
+        #[arg(long, default_value_t = false)]
+        update: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum SyncCommand {
+    /// Import Linear or Jira tickets into the session's tasks.json.
+    Import {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+        /// Ticket to import, e.g. `linear:ENG-123` or `jira:PROJ-42`; repeatable.
+        #[arg(long = "ticket", required = true)]
+        tickets: Vec<String>,
+    },
+    /// Push the current InProgress/Done status of every linked task to its ticket.
+    Push {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum BackendCommand {
+    /// Check that each configured backend CLI is installed, authenticated, and can use its models.
+    Doctor {
+        /// Only check this backend (`codex` or `claude`).
+        #[arg(long)]
+        backend: Option<String>,
+        /// Skip the probe prompts that verify authentication and model access.
+        #[arg(long, default_value_t = false)]
+        quick: bool,
+    },
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -3184,6 +8317,105 @@ enum WorkflowCommand {
         #[arg(long, default_value_t = 100)]
         width: u16,
     },
+    /// Render the task tree as a Mermaid or Graphviz diagram colored by task status.
+    ExportGraph {
+        #[arg(long)]
+        tasks_file: PathBuf,
+        #[arg(long, value_enum, default_value_t = CliGraphFormat::Mermaid)]
+        format: CliGraphFormat,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum SubagentCommand {
+    #[command(name = "project-info-prompt")]
+    ProjectInfo {
+        #[arg(long)]
+        cwd: String,
+        #[arg(long)]
+        question: String,
+        #[arg(long)]
+        output_path: String,
+    },
+    #[command(name = "session-meta-prompt")]
+    SessionMeta {
+        #[arg(long)]
+        user_prompt: String,
+        #[arg(long)]
+        output_path: String,
+    },
+    #[command(name = "task-check-prompt")]
+    TaskCheck {
+        #[arg(long)]
+        tasks_file: String,
+        #[arg(long)]
+        project_info_file: String,
+        #[arg(long)]
+        session_meta_file: String,
+    },
+    #[command(name = "attach-docs-prompt")]
+    AttachDocs {
+        #[arg(long)]
+        tasks_file: String,
+    },
+    #[command(name = "convert-plan-prompt")]
+    ConvertPlan {
+        #[arg(long)]
+        planner_file: String,
+        #[arg(long)]
+        tasks_file: String,
+    },
+    #[command(name = "failure-report-prompt")]
+    FailureReport {
+        #[arg(long)]
+        task_fails_file: String,
+        /// JSON array of the failures to report.
+        #[arg(long)]
+        entries_file: PathBuf,
+        #[arg(long, default_value_t = false)]
+        has_test_failure: bool,
+    },
+    /// Build the prompt an auditor or final-audit task in `--tasks-file` would receive.
+    #[command(name = "audit-prompt")]
+    Audit {
+        #[arg(long)]
+        tasks_file: PathBuf,
+        #[arg(long)]
+        task_id: String,
+        /// Implementor or test-writer output under audit (previous feedback for a final audit).
+        #[arg(long)]
+        worker_output_file: Option<PathBuf>,
+        #[arg(long)]
+        changed_files_file: Option<PathBuf>,
+        #[arg(long)]
+        pass: Option<u8>,
+        #[arg(long, default_value_t = false)]
+        tests_off: bool,
+        #[arg(long)]
+        rubric_file: Option<PathBuf>,
+        /// JSON array of rolling-context entries.
+        #[arg(long)]
+        context_file: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum EventsCommand {
+    /// Wait for session events after a cursor; pass the returned `next_cursor` as `--after`
+    /// on the next call to resume without gaps.
+    Subscribe {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+        #[arg(long, default_value_t = 0)]
+        after: u64,
+        #[arg(long)]
+        limit: Option<usize>,
+        /// How long to wait for new events before returning an empty batch.
+        #[arg(long, default_value_t = 0)]
+        wait_ms: u64,
+    },
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -3191,6 +8423,9 @@ enum SessionCommand {
     Init {
         #[arg(long)]
         cwd: Option<PathBuf>,
+        /// Seed the session from `templates/<name>.toml` in the config directory.
+        #[arg(long)]
+        template: Option<String>,
     },
     Open {
         #[arg(long)]
@@ -3259,6 +8494,64 @@ enum SessionCommand {
         #[arg(long)]
         session_dir: PathBuf,
     },
+    /// Rank workspace file chunks against a query using the session's embedding index.
+    SearchCode {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+        #[arg(long)]
+        query: String,
+        #[arg(long, default_value_t = 5)]
+        limit: usize,
+    },
+    /// Verify the session's hash-chained audit trail, optionally copying it to a file.
+    ExportAuditTrail {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Add one planner task from a JSON file; the id must be new.
+    AddTask {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+        #[arg(long)]
+        task_file: PathBuf,
+    },
+    /// Replace the planner task with the id given in a JSON file.
+    UpdateTask {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+        #[arg(long)]
+        task_file: PathBuf,
+    },
+    /// Remove a planner task and its subtasks.
+    RemoveTask {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+        #[arg(long)]
+        task_id: String,
+    },
+    /// Move a planner task to a zero-based position among its siblings.
+    ReorderTask {
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        #[arg(long)]
+        session_dir: PathBuf,
+        #[arg(long)]
+        task_id: String,
+        #[arg(long)]
+        position: usize,
+    },
 }
 
 #[derive(Debug, Serialize)]
@@ -3324,8 +8617,87 @@ impl TransportAdapterRegistry {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
-struct CliTransportAdapter;
+#[derive(Debug, Clone, Copy, Default)]
+struct CliTransportAdapter;
+
+/// Runs `plan --spec` stages on the configured backend, one blocking run at a time.
+struct AdapterPlanRunner {
+    project_info: CodexAdapter,
+    master: CodexAdapter,
+    task_check: CodexAdapter,
+    docs_attach: CodexAdapter,
+}
+
+impl plan_pipeline::PlanStageRunner for AdapterPlanRunner {
+    fn run(
+        &mut self,
+        stage: plan_pipeline::PlanStage,
+        prompt: String,
+    ) -> Result<Vec<String>, String> {
+        let adapter = match stage {
+            plan_pipeline::PlanStage::ProjectInfo | plan_pipeline::PlanStage::SessionMeta => {
+                &self.project_info
+            }
+            plan_pipeline::PlanStage::Master => &self.master,
+            plan_pipeline::PlanStage::TaskCheck => &self.task_check,
+            plan_pipeline::PlanStage::DocsAttach => &self.docs_attach,
+        };
+        adapter.send_prompt(prompt);
+        let mut transcript = Vec::new();
+        loop {
+            for event in adapter.drain_events_limited(MAX_ADAPTER_EVENTS_PER_LOOP) {
+                match event {
+                    AgentEvent::Output(line) => transcript.push(line),
+                    AgentEvent::System(_) => {}
+                    AgentEvent::Completed { success: true, .. } => return Ok(transcript),
+                    AgentEvent::Completed { code, .. } => {
+                        return Err(format!("agent exited with code {code}"));
+                    }
+                }
+            }
+            std::thread::sleep(OBSERVER_POLL_INTERVAL / 10);
+        }
+    }
+}
+
+/// Reads a secret for `bob auth set`: the whole of piped stdin, or a hidden line typed at the
+/// terminal.
+fn read_secret(service: &str) -> io::Result<String> {
+    use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+    use std::io::{IsTerminal, Read, Write};
+
+    let mut stdin = io::stdin();
+    if !stdin.is_terminal() {
+        let mut secret = String::new();
+        stdin.read_to_string(&mut secret)?;
+        return Ok(secret.trim_end_matches(['\r', '\n']).to_string());
+    }
+    eprint!("Secret for {service} (input hidden): ");
+    io::stderr().flush()?;
+    enable_raw_mode()?;
+    let mut secret = String::new();
+    let result = loop {
+        match crossterm::event::read() {
+            Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled"));
+                }
+                KeyCode::Char(ch) => secret.push(ch),
+                KeyCode::Backspace => {
+                    secret.pop();
+                }
+                _ => {}
+            },
+            Ok(Event::Paste(text)) => secret.push_str(&text),
+            Ok(_) => {}
+            Err(err) => break Err(err),
+        }
+    };
+    disable_raw_mode()?;
+    eprintln!();
+    result.map(|()| secret)
+}
 
 #[derive(Debug)]
 struct CliContractInvocation {
@@ -3340,17 +8712,286 @@ impl TransportAdapter for CliTransportAdapter {
     fn execute(&self, command: RootCommand) -> Result<CliCommandOutput, CliCommandError> {
         match command {
             RootCommand::Api(api_command) => self.execute_api_command(api_command),
+            RootCommand::Observe(_) => Err(CliCommandError::new(
+                api::ApiErrorCode::Unsupported,
+                "`observe` opens the read-only TUI and is not an API command",
+            )),
+            RootCommand::ServeUi(_) => Err(CliCommandError::new(
+                api::ApiErrorCode::Unsupported,
+                "`serve-ui` runs the dashboard server and is not an API command",
+            )),
+            RootCommand::Stats(stats) => match stats.action {
+                None => self.execute_stats_command(),
+                Some(StatsCommand::Backends) => self.execute_backend_stats_command(),
+            },
+            RootCommand::Config(config) => self.execute_config_command(config.action),
+            RootCommand::Plan(plan) => self.execute_plan_command(plan),
+            RootCommand::Auth(auth) => self.execute_auth_command(auth.action),
+            RootCommand::Storage(storage) => self.execute_storage_command(storage.action),
         }
     }
 }
 
 impl CliTransportAdapter {
+    fn execute_stats_command(&self) -> Result<CliCommandOutput, CliCommandError> {
+        let stats = stats::collect_failure_stats().map_err(|err| {
+            CliCommandError::new(
+                api::ApiErrorCode::IoFailure,
+                format!("Failed to collect failure stats: {err}"),
+            )
+        })?;
+        Ok(CliCommandOutput {
+            summary: format!(
+                "Aggregated {} failure(s) across {} session(s)",
+                stats.total_failures, stats.sessions_scanned
+            ),
+            data: serde_json::to_value(&stats).map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::Internal,
+                    format!("Failed to serialize failure stats: {err}"),
+                )
+            })?,
+        })
+    }
+
+    fn execute_backend_stats_command(&self) -> Result<CliCommandOutput, CliCommandError> {
+        let stats = backend_metrics::collect_backend_stats().map_err(|err| {
+            CliCommandError::new(
+                api::ApiErrorCode::IoFailure,
+                format!("Failed to read backend metrics: {err}"),
+            )
+        })?;
+        Ok(CliCommandOutput {
+            summary: format!(
+                "Summarized {} backend call(s) across {} agent/backend pair(s)",
+                stats.total_calls,
+                stats.by_agent.len()
+            ),
+            data: serde_json::to_value(&stats).map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::Internal,
+                    format!("Failed to serialize backend stats: {err}"),
+                )
+            })?,
+        })
+    }
+
+    fn execute_plan_command(
+        &self,
+        command: PlanCommand,
+    ) -> Result<CliCommandOutput, CliCommandError> {
+        let io_error = |context: &str, err: io::Error| {
+            CliCommandError::new(api::ApiErrorCode::IoFailure, format!("{context}: {err}"))
+        };
+        let spec = std::fs::read_to_string(&command.spec)
+            .map_err(|err| io_error("Failed to read spec file", err))?;
+        if spec.trim().is_empty() {
+            return Err(CliCommandError::new(
+                api::ApiErrorCode::InvalidRequest,
+                format!("Spec file {} is empty", command.spec.display()),
+            ));
+        }
+        let cwd = std::env::current_dir().map_err(|err| io_error("Failed to resolve cwd", err))?;
+        let model_routing = CodexAgentModelRouting::load_from_metaagent_config()
+            .map_err(|err| io_error("Failed to load model routing", err))?;
+        let selected_backend = model_routing.base_command_config().backend_kind();
+        let store = SessionStore::initialize(&cwd)
+            .map_err(|err| io_error("Failed to create planning session", err))?;
+        let mut app = App::default();
+        app.set_tests_mode_enabled(
+            load_global_tests_mode_enabled()
+                .map_err(|err| io_error("Failed to load tests mode", err))?,
+        );
+        app.set_final_audit_policy(
+            load_global_final_audit_policy()
+                .map_err(|err| io_error("Failed to load final audit policy", err))?,
+        );
+        let mut runner = AdapterPlanRunner {
+            project_info: build_json_persistent_adapter(
+                &model_routing,
+                selected_backend,
+                CodexAgentKind::ProjectInfo,
+            ),
+            master: build_json_persistent_adapter(
+                &model_routing,
+                selected_backend,
+                CodexAgentKind::Master,
+            ),
+            task_check: build_plain_adapter(
+                &model_routing,
+                selected_backend,
+                CodexAgentKind::TaskCheck,
+                false,
+            ),
+            docs_attach: build_plain_adapter(
+                &model_routing,
+                selected_backend,
+                CodexAgentKind::DocsAttach,
+                false,
+            ),
+        };
+        let outcome = plan_pipeline::run_plan_pipeline(&mut app, &store, &cwd, &spec, &mut runner)
+            .map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::Internal,
+                    format!("{err} (session: {})", store.session_dir().display()),
+                )
+            })?;
+        let text = serde_json::to_string_pretty(&outcome.tasks).map_err(|err| {
+            CliCommandError::new(
+                api::ApiErrorCode::Internal,
+                format!("Failed to serialize tasks: {err}"),
+            )
+        })?;
+        std::fs::write(&command.out, text)
+            .map_err(|err| io_error("Failed to write --out file", err))?;
+        Ok(CliCommandOutput {
+            summary: format!(
+                "Planned {} task(s) into {} ({} stage note(s))",
+                outcome.tasks.len(),
+                command.out.display(),
+                outcome.notes.len()
+            ),
+            data: json!({
+                "out": command.out,
+                "session_dir": store.session_dir(),
+                "task_count": outcome.tasks.len(),
+                "notes": outcome.notes,
+            }),
+        })
+    }
+
+    fn execute_auth_command(
+        &self,
+        action: AuthCommand,
+    ) -> Result<CliCommandOutput, CliCommandError> {
+        let invalid =
+            |message: String| CliCommandError::new(api::ApiErrorCode::InvalidRequest, message);
+        let io_failure =
+            |message: String| CliCommandError::new(api::ApiErrorCode::IoFailure, message);
+        match action {
+            AuthCommand::Set { service } => {
+                credentials::validate_service(&service).map_err(invalid)?;
+                let secret = read_secret(&service)
+                    .map_err(|err| io_failure(format!("Failed to read the secret: {err}")))?;
+                let location =
+                    credentials::set_credential(&service, &secret).map_err(io_failure)?;
+                Ok(CliCommandOutput {
+                    summary: format!("Stored the {service} secret in {}", location.label()),
+                    data: json!({ "service": service, "location": location.label() }),
+                })
+            }
+            AuthCommand::Remove { service } => {
+                credentials::validate_service(&service).map_err(invalid)?;
+                let removed = credentials::remove_credential(&service).map_err(io_failure)?;
+                let summary = if removed {
+                    format!("Removed the stored {service} secret")
+                } else {
+                    format!("No {service} secret was stored")
+                };
+                Ok(CliCommandOutput {
+                    summary,
+                    data: json!({ "service": service, "removed": removed }),
+                })
+            }
+        }
+    }
+
+    fn execute_storage_command(
+        &self,
+        action: StorageCommand,
+    ) -> Result<CliCommandOutput, CliCommandError> {
+        let storage_error = |err: io::Error| {
+            let code = match err.kind() {
+                io::ErrorKind::NotFound => api::ApiErrorCode::NotFound,
+                io::ErrorKind::InvalidInput => api::ApiErrorCode::InvalidRequest,
+                io::ErrorKind::AlreadyExists => api::ApiErrorCode::Conflict,
+                _ => api::ApiErrorCode::ExternalFailure,
+            };
+            CliCommandError::new(code, err.to_string())
+        };
+        match action {
+            StorageCommand::List => {
+                let (remote, sessions) =
+                    session_storage::list_remote_sessions().map_err(storage_error)?;
+                Ok(CliCommandOutput {
+                    summary: format!("{} session(s) in {remote}", sessions.len()),
+                    data: json!({ "remote": remote, "sessions": sessions }),
+                })
+            }
+            StorageCommand::Push { session } => {
+                let summary = session_storage::push_session(&session).map_err(storage_error)?;
+                Ok(CliCommandOutput {
+                    summary,
+                    data: json!({ "session": session, "direction": "push" }),
+                })
+            }
+            StorageCommand::Pull { session, workspace } => {
+                let cwd = std::env::current_dir().map_err(storage_error)?;
+                let summary = session_storage::pull_session(&session, workspace.as_deref(), &cwd)
+                    .map_err(storage_error)?;
+                Ok(CliCommandOutput {
+                    summary,
+                    data: json!({ "session": session, "direction": "pull" }),
+                })
+            }
+        }
+    }
+
+    fn execute_config_command(
+        &self,
+        action: ConfigCommand,
+    ) -> Result<CliCommandOutput, CliCommandError> {
+        let ConfigCommand::Show { effective } = action;
+        let config_error = |err: io::Error| {
+            CliCommandError::new(
+                api::ApiErrorCode::IoFailure,
+                format!("Failed to load config: {err}"),
+            )
+        };
+        let cwd = std::env::current_dir().map_err(config_error)?;
+        let config = artifact_io::effective_metaagent_config(effective.then_some(cwd.as_path()))
+            .map_err(config_error)?;
+        let summary = if !effective {
+            format!("Global config: {}", config.global_file.display())
+        } else if let Some(path) = &config.project_file {
+            let overridden = config
+                .entries
+                .iter()
+                .filter(|entry| entry.source == artifact_io::ConfigSource::Project)
+                .count();
+            format!(
+                "Effective config: {} key(s), {overridden} set by {}",
+                config.entries.len(),
+                path.display()
+            )
+        } else {
+            format!(
+                "Effective config: {} key(s), no project config in {}",
+                config.entries.len(),
+                cwd.display()
+            )
+        };
+        Ok(CliCommandOutput {
+            summary,
+            data: serde_json::to_value(&config).map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::Internal,
+                    format!("Failed to serialize config: {err}"),
+                )
+            })?,
+        })
+    }
+
     fn execute_api_command(
         &self,
         command: ApiRootCommand,
     ) -> Result<CliCommandOutput, CliCommandError> {
         match command.resource {
             ApiResourceCommand::Capability { action } => self.execute_capability_action(action),
+            ApiResourceCommand::Backend { action } => self.execute_backend_action(action),
+            ApiResourceCommand::Sync { action } => self.execute_sync_action(action),
+            ApiResourceCommand::Prompts { action } => self.execute_prompts_action(action),
             resource => {
                 let invocation = self.map_resource_to_contract(resource)?;
                 let response = execute_core_api_contract(invocation.request)?;
@@ -3399,6 +9040,201 @@ impl CliTransportAdapter {
         }
     }
 
+    fn execute_backend_action(
+        &self,
+        action: BackendCommand,
+    ) -> Result<CliCommandOutput, CliCommandError> {
+        let BackendCommand::Doctor { backend, quick } = action;
+        let config_error = |err: io::Error| {
+            CliCommandError::new(
+                api::ApiErrorCode::IoFailure,
+                format!("Failed to load backend config: {err}"),
+            )
+        };
+        let mut backends = agent_models::load_configured_backends().map_err(config_error)?;
+        let routing = CodexAgentModelRouting::load_from_metaagent_config().map_err(config_error)?;
+        if let Some(requested) = backend.as_deref().map(str::trim) {
+            backends.retain(|configured| backend_label(configured.kind) == requested);
+            if backends.is_empty() {
+                return Err(CliCommandError::new(
+                    api::ApiErrorCode::InvalidRequest,
+                    format!("Unknown backend `{requested}` (expected `codex` or `claude`)"),
+                ));
+            }
+        }
+        let report = backend_doctor::diagnose_backends(
+            &backends,
+            &routing,
+            backend_doctor::DoctorOptions { quick },
+        );
+        Ok(CliCommandOutput {
+            summary: format!(
+                "Checked {} backend(s): {} error(s), {} warning(s)",
+                report.backends.len(),
+                report.error_count(),
+                report.warning_count()
+            ),
+            data: serde_json::to_value(&report).map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::Internal,
+                    format!("Failed to serialize backend report: {err}"),
+                )
+            })?,
+        })
+    }
+
+    fn execute_prompts_action(
+        &self,
+        action: PromptsCommand,
+    ) -> Result<CliCommandOutput, CliCommandError> {
+        let PromptsCommand::Snapshot {
+            golden_dir,
+            fixtures_dir,
+            update,
+        } = action;
+        let io_error = |context: &str, err: io::Error| {
+            CliCommandError::new(api::ApiErrorCode::IoFailure, format!("{context}: {err}"))
+        };
+        let fixtures = match fixtures_dir {
+            Some(dir) => prompt_snapshots::load_fixtures(&dir)
+                .map_err(|err| io_error("Failed to load prompt fixtures", err))?,
+            None => prompt_snapshots::builtin_fixtures(),
+        };
+        let snapshots = prompt_snapshots::render_prompt_snapshots(&fixtures)
+            .map_err(|err| CliCommandError::new(api::ApiErrorCode::ValidationFailed, err))?;
+        let report = prompt_snapshots::compare_with_golden(&snapshots, &golden_dir, update)
+            .map_err(|err| io_error("Failed to compare golden files", err))?;
+        let data = serde_json::to_value(&report).map_err(|err| {
+            CliCommandError::new(
+                api::ApiErrorCode::Internal,
+                format!("Failed to serialize snapshot report: {err}"),
+            )
+        })?;
+        let drift = report.changed.len() + report.missing.len() + report.stale.len();
+        if !report.is_clean() {
+            return Err(CliCommandError::new(
+                api::ApiErrorCode::ValidationFailed,
+                format!(
+                    "{drift} prompt snapshot(s) differ from golden files; rerun with --update to accept"
+                ),
+            )
+            .with_details(data));
+        }
+        Ok(CliCommandOutput {
+            summary: if update {
+                format!(
+                    "Rendered {} prompt(s); updated {drift} golden file(s)",
+                    snapshots.len()
+                )
+            } else {
+                format!("All {} prompt(s) match golden files", snapshots.len())
+            },
+            data,
+        })
+    }
+
+    fn execute_sync_action(
+        &self,
+        action: SyncCommand,
+    ) -> Result<CliCommandOutput, CliCommandError> {
+        let config = ticket_sync::load_ticket_sync_config().map_err(|err| {
+            CliCommandError::new(
+                api::ApiErrorCode::IoFailure,
+                format!("Failed to load [sync] config: {err}"),
+            )
+        })?;
+        let open_session = |cwd: Option<PathBuf>, session_dir: PathBuf| {
+            let cwd = resolve_cli_cwd(cwd)?;
+            SessionStore::open_existing(&cwd, &session_dir).map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::NotFound,
+                    format!("Failed to open session: {err}"),
+                )
+                .with_details(json!({ "session_dir": session_dir.display().to_string() }))
+            })
+        };
+        let read_tasks = |store: &SessionStore| {
+            store.read_tasks().map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::IoFailure,
+                    format!("Failed to read tasks.json: {err}"),
+                )
+            })
+        };
+        match action {
+            SyncCommand::Import {
+                cwd,
+                session_dir,
+                tickets,
+            } => {
+                let references = tickets
+                    .iter()
+                    .map(|ticket| ticket_sync::ExternalRef::parse(ticket))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| CliCommandError::new(api::ApiErrorCode::InvalidRequest, err))?;
+                let store = open_session(cwd, session_dir)?;
+                let mut tasks = read_tasks(&store)?;
+                let fetched = references
+                    .iter()
+                    .map(|reference| ticket_sync::fetch_ticket(&config, reference))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| CliCommandError::new(api::ApiErrorCode::ExternalFailure, err))?;
+                let added = ticket_sync::append_ticket_tasks(&mut tasks, &fetched);
+                App::default()
+                    .sync_planner_tasks_from_file(tasks.clone())
+                    .map_err(|err| {
+                        CliCommandError::new(
+                            api::ApiErrorCode::ValidationFailed,
+                            format!("Imported tasks are invalid: {err}"),
+                        )
+                    })?;
+                store.write_tasks(&tasks).map_err(|err| {
+                    CliCommandError::new(
+                        api::ApiErrorCode::IoFailure,
+                        format!("Failed to write tasks.json: {err}"),
+                    )
+                })?;
+                Ok(CliCommandOutput {
+                    summary: format!(
+                        "Imported {added} of {} ticket(s) into tasks.json",
+                        fetched.len()
+                    ),
+                    data: json!({ "added": added, "tickets": fetched }),
+                })
+            }
+            SyncCommand::Push { cwd, session_dir } => {
+                let store = open_session(cwd, session_dir)?;
+                let mut pushed = Vec::new();
+                let mut failed = Vec::new();
+                for task in read_tasks(&store)? {
+                    let Some(reference) = task.external_ref.as_deref() else {
+                        continue;
+                    };
+                    let result = ticket_sync::ExternalRef::parse(reference).and_then(|parsed| {
+                        ticket_sync::push_ticket_status(&config, &parsed, task.status)
+                    });
+                    match result {
+                        Ok(Some(state)) => pushed.push(
+                            json!({ "task_id": task.id, "external_ref": reference, "state": state }),
+                        ),
+                        Ok(None) => {}
+                        Err(err) => failed.push(
+                            json!({ "task_id": task.id, "external_ref": reference, "error": err }),
+                        ),
+                    }
+                }
+                Ok(CliCommandOutput {
+                    summary: format!(
+                        "Pushed {} ticket status(es), {} failed",
+                        pushed.len(),
+                        failed.len()
+                    ),
+                    data: json!({ "pushed": pushed, "failed": failed }),
+                })
+            }
+        }
+    }
+
     fn map_resource_to_contract(
         &self,
         resource: ApiResourceCommand,
@@ -3463,13 +9299,30 @@ impl CliTransportAdapter {
                         )?,
                     }
                 }
+                WorkflowCommand::ExportGraph { tasks_file, format } => {
+                    let tasks = read_cli_tasks_contract(&tasks_file)?;
+                    let format = match format {
+                        CliGraphFormat::Mermaid => api::TaskGraphFormatContract::Mermaid,
+                        CliGraphFormat::Dot => api::TaskGraphFormatContract::Dot,
+                    };
+                    CliContractInvocation {
+                        request: build_cli_envelope(
+                            api::CapabilityId::WorkflowContextProjection,
+                            api::ApiRequestContract::Workflow(
+                                api::WorkflowRequest::TaskGraphExport { tasks, format },
+                            ),
+                            self.id(),
+                        ),
+                    }
+                }
             },
             ApiResourceCommand::Session { action } => {
                 let payload = match action {
-                    SessionCommand::Init { cwd } => {
+                    SessionCommand::Init { cwd, template } => {
                         let cwd = resolve_cli_cwd(cwd)?;
                         api::SessionRequest::Initialize {
                             cwd: cwd.to_string_lossy().to_string(),
+                            template,
                         }
                     }
                     SessionCommand::Open { cwd, session_dir } => {
@@ -3592,21 +9445,128 @@ impl CliTransportAdapter {
                         })?;
                         return Ok(CliContractInvocation {
                             request: build_cli_envelope_with_actor(
-                                api::CapabilityId::SessionProjectContextStorage,
-                                api::ApiRequestContract::Session(
-                                    api::SessionRequest::WriteProjectInfo { markdown },
-                                ),
+                                api::CapabilityId::SessionProjectContextStorage,
+                                api::ApiRequestContract::Session(
+                                    api::SessionRequest::WriteProjectInfo { markdown },
+                                ),
+                                self.id(),
+                                json!(resolve_session_lookup_context(cwd, session_dir)?),
+                            )?,
+                        });
+                    }
+                    SessionCommand::ReadSessionMeta { cwd, session_dir } => {
+                        return Ok(CliContractInvocation {
+                            request: build_cli_envelope_with_actor(
+                                api::CapabilityId::SessionProjectContextStorage,
+                                api::ApiRequestContract::Session(
+                                    api::SessionRequest::ReadSessionMeta,
+                                ),
+                                self.id(),
+                                json!(resolve_session_lookup_context(cwd, session_dir)?),
+                            )?,
+                        });
+                    }
+                    SessionCommand::SearchCode {
+                        cwd,
+                        session_dir,
+                        query,
+                        limit,
+                    } => {
+                        return Ok(CliContractInvocation {
+                            request: build_cli_envelope_with_actor(
+                                api::CapabilityId::SessionCodeSearch,
+                                api::ApiRequestContract::Session(api::SessionRequest::SearchCode {
+                                    query,
+                                    limit,
+                                }),
+                                self.id(),
+                                json!(resolve_session_lookup_context(cwd, session_dir)?),
+                            )?,
+                        });
+                    }
+                    SessionCommand::ExportAuditTrail {
+                        cwd,
+                        session_dir,
+                        output,
+                    } => {
+                        return Ok(CliContractInvocation {
+                            request: build_cli_envelope_with_actor(
+                                api::CapabilityId::SessionAuditTrail,
+                                api::ApiRequestContract::Session(
+                                    api::SessionRequest::ExportAuditTrail {
+                                        output: output.map(|path| path.display().to_string()),
+                                    },
+                                ),
+                                self.id(),
+                                json!(resolve_session_lookup_context(cwd, session_dir)?),
+                            )?,
+                        });
+                    }
+                    SessionCommand::AddTask {
+                        cwd,
+                        session_dir,
+                        task_file,
+                    } => {
+                        let task = read_json_from_file::<api::PlannerTaskEntryContract>(
+                            &task_file, "task",
+                        )?;
+                        return Ok(CliContractInvocation {
+                            request: build_cli_envelope_with_actor(
+                                api::CapabilityId::SessionTaskEditing,
+                                api::ApiRequestContract::Session(api::SessionRequest::AddTask {
+                                    task,
+                                }),
+                                self.id(),
+                                json!(resolve_session_lookup_context(cwd, session_dir)?),
+                            )?,
+                        });
+                    }
+                    SessionCommand::UpdateTask {
+                        cwd,
+                        session_dir,
+                        task_file,
+                    } => {
+                        let task = read_json_from_file::<api::PlannerTaskEntryContract>(
+                            &task_file, "task",
+                        )?;
+                        return Ok(CliContractInvocation {
+                            request: build_cli_envelope_with_actor(
+                                api::CapabilityId::SessionTaskEditing,
+                                api::ApiRequestContract::Session(api::SessionRequest::UpdateTask {
+                                    task,
+                                }),
+                                self.id(),
+                                json!(resolve_session_lookup_context(cwd, session_dir)?),
+                            )?,
+                        });
+                    }
+                    SessionCommand::RemoveTask {
+                        cwd,
+                        session_dir,
+                        task_id,
+                    } => {
+                        return Ok(CliContractInvocation {
+                            request: build_cli_envelope_with_actor(
+                                api::CapabilityId::SessionTaskEditing,
+                                api::ApiRequestContract::Session(api::SessionRequest::RemoveTask {
+                                    task_id,
+                                }),
                                 self.id(),
                                 json!(resolve_session_lookup_context(cwd, session_dir)?),
                             )?,
                         });
                     }
-                    SessionCommand::ReadSessionMeta { cwd, session_dir } => {
+                    SessionCommand::ReorderTask {
+                        cwd,
+                        session_dir,
+                        task_id,
+                        position,
+                    } => {
                         return Ok(CliContractInvocation {
                             request: build_cli_envelope_with_actor(
-                                api::CapabilityId::SessionProjectContextStorage,
+                                api::CapabilityId::SessionTaskEditing,
                                 api::ApiRequestContract::Session(
-                                    api::SessionRequest::ReadSessionMeta,
+                                    api::SessionRequest::ReorderTask { task_id, position },
                                 ),
                                 self.id(),
                                 json!(resolve_session_lookup_context(cwd, session_dir)?),
@@ -3629,12 +9589,130 @@ impl CliTransportAdapter {
                     ),
                 }
             }
+            ApiResourceCommand::Events {
+                action:
+                    EventsCommand::Subscribe {
+                        cwd,
+                        session_dir,
+                        after,
+                        limit,
+                        wait_ms,
+                    },
+            } => CliContractInvocation {
+                request: build_cli_envelope_with_actor(
+                    api::CapabilityId::EventSubscription,
+                    api::ApiRequestContract::Events(api::EventsRequest::Subscribe {
+                        after,
+                        limit,
+                        wait_ms,
+                    }),
+                    self.id(),
+                    resolve_session_lookup_context(cwd, session_dir)?,
+                )?,
+            },
+            ApiResourceCommand::Subagent { action } => {
+                let payload = match action {
+                    SubagentCommand::ProjectInfo {
+                        cwd,
+                        question,
+                        output_path,
+                    } => api::SubagentRequest::BuildProjectInfoPrompt {
+                        cwd,
+                        question,
+                        output_path,
+                    },
+                    SubagentCommand::SessionMeta {
+                        user_prompt,
+                        output_path,
+                    } => api::SubagentRequest::BuildSessionMetaPrompt {
+                        user_prompt,
+                        output_path,
+                    },
+                    SubagentCommand::TaskCheck {
+                        tasks_file,
+                        project_info_file,
+                        session_meta_file,
+                    } => api::SubagentRequest::BuildTaskCheckPrompt {
+                        tasks_file,
+                        project_info_file,
+                        session_meta_file,
+                    },
+                    SubagentCommand::AttachDocs { tasks_file } => {
+                        api::SubagentRequest::BuildAttachDocsPrompt { tasks_file }
+                    }
+                    SubagentCommand::ConvertPlan {
+                        planner_file,
+                        tasks_file,
+                    } => api::SubagentRequest::BuildConvertPlanPrompt {
+                        planner_file,
+                        tasks_file,
+                    },
+                    SubagentCommand::FailureReport {
+                        task_fails_file,
+                        entries_file,
+                        has_test_failure,
+                    } => api::SubagentRequest::BuildFailureReportPrompt {
+                        task_fails_file,
+                        failed_this_cycle: read_json_from_file(&entries_file, "entries")?,
+                        has_test_failure,
+                    },
+                    SubagentCommand::Audit {
+                        tasks_file,
+                        task_id,
+                        worker_output_file,
+                        changed_files_file,
+                        pass,
+                        tests_off,
+                        rubric_file,
+                        context_file,
+                    } => api::SubagentRequest::BuildAuditPrompt {
+                        tasks: read_cli_tasks_contract(&tasks_file)?,
+                        task_id,
+                        worker_output: read_optional_cli_text(worker_output_file.as_deref())?,
+                        changed_files_summary: read_optional_cli_text(
+                            changed_files_file.as_deref(),
+                        )?,
+                        pass,
+                        tests_mode_enabled: Some(!tests_off),
+                        audit_rubric: read_optional_cli_text(rubric_file.as_deref())?,
+                        rolling_context: context_file
+                            .map(|path| read_json_from_file(&path, "context"))
+                            .transpose()?
+                            .unwrap_or_default(),
+                    },
+                };
+                CliContractInvocation {
+                    request: build_cli_envelope(
+                        api::CapabilityId::SubagentPromptGeneration,
+                        api::ApiRequestContract::Subagent(payload),
+                        self.id(),
+                    ),
+                }
+            }
             ApiResourceCommand::Capability { .. } => {
                 return Err(CliCommandError::new(
                     api::ApiErrorCode::Unsupported,
                     "Capability commands are handled directly by the CLI adapter",
                 ));
             }
+            ApiResourceCommand::Backend { .. } => {
+                return Err(CliCommandError::new(
+                    api::ApiErrorCode::Unsupported,
+                    "Backend commands are handled directly by the CLI adapter",
+                ));
+            }
+            ApiResourceCommand::Sync { .. } => {
+                return Err(CliCommandError::new(
+                    api::ApiErrorCode::Unsupported,
+                    "Sync commands are handled directly by the CLI adapter",
+                ));
+            }
+            ApiResourceCommand::Prompts { .. } => {
+                return Err(CliCommandError::new(
+                    api::ApiErrorCode::Unsupported,
+                    "Prompts commands are handled directly by the CLI adapter",
+                ));
+            }
         };
         Ok(request)
     }
@@ -3680,6 +9758,13 @@ impl CliTransportAdapter {
                 summary: "Rendered right-pane workflow block".to_string(),
                 data: json!({ "lines": lines, "toggles": toggles }),
             }),
+            api::ApiResponseContract::Workflow(api::WorkflowResponse::TaskGraph {
+                format,
+                text,
+            }) => Ok(CliCommandOutput {
+                summary: "Rendered task graph".to_string(),
+                data: json!({ "format": format, "graph": text }),
+            }),
             api::ApiResponseContract::Session(api::SessionResponse::Initialized { session }) => {
                 Ok(CliCommandOutput {
                     summary: "Initialized session".to_string(),
@@ -3735,10 +9820,57 @@ impl CliTransportAdapter {
                     data: json!({ "meta": meta }),
                 })
             }
+            api::ApiResponseContract::Session(api::SessionResponse::CodeSearchResults {
+                results,
+            }) => Ok(CliCommandOutput {
+                summary: format!("Found {} matching code chunk(s)", results.len()),
+                data: json!({ "results": results }),
+            }),
+            api::ApiResponseContract::Session(api::SessionResponse::AuditTrail {
+                entries,
+                head_hash,
+                broken_at,
+            }) => Ok(CliCommandOutput {
+                summary: match broken_at {
+                    Some(seq) => format!(
+                        "Read {} audit entrie(s); the hash chain breaks at entry {seq}",
+                        entries.len()
+                    ),
+                    None => format!(
+                        "Read {} audit entrie(s); hash chain verified",
+                        entries.len()
+                    ),
+                },
+                data: json!({
+                    "entries": entries,
+                    "head_hash": head_hash,
+                    "broken_at": broken_at,
+                }),
+            }),
             api::ApiResponseContract::Session(api::SessionResponse::Ack) => Ok(CliCommandOutput {
                 summary: "Completed session operation".to_string(),
                 data: json!({}),
             }),
+            api::ApiResponseContract::Subagent(api::SubagentResponse::Prompt { text }) => {
+                Ok(CliCommandOutput {
+                    summary: "Built subagent prompt".to_string(),
+                    data: json!({ "prompt": text }),
+                })
+            }
+            api::ApiResponseContract::Subagent(api::SubagentResponse::IntroPrompt {
+                text,
+                intro_needed_after,
+            }) => Ok(CliCommandOutput {
+                summary: "Built subagent prompt".to_string(),
+                data: json!({ "prompt": text, "intro_needed_after": intro_needed_after }),
+            }),
+            api::ApiResponseContract::Events(api::EventsResponse::Batch {
+                events,
+                next_cursor,
+            }) => Ok(CliCommandOutput {
+                summary: format!("Read {} event(s); next cursor {next_cursor}", events.len()),
+                data: json!({ "events": events, "next_cursor": next_cursor }),
+            }),
             other => Err(CliCommandError::new(
                 api::ApiErrorCode::Unsupported,
                 format!("CLI transport does not support response contract: {other:?}"),
@@ -3825,6 +9957,19 @@ where
     })
 }
 
+fn read_optional_cli_text(path: Option<&Path>) -> Result<Option<String>, CliCommandError> {
+    path.map(|path| {
+        std::fs::read_to_string(path).map_err(|err| {
+            CliCommandError::new(
+                api::ApiErrorCode::IoFailure,
+                format!("Failed to read file: {err}"),
+            )
+            .with_details(json!({ "path": path }))
+        })
+    })
+    .transpose()
+}
+
 fn resolve_cli_cwd(cwd: Option<PathBuf>) -> Result<PathBuf, CliCommandError> {
     Ok(cwd.unwrap_or(std::env::current_dir().map_err(|err| {
         CliCommandError::new(
@@ -3886,11 +10031,11 @@ fn execute_core_api_contract(
         api::ApiRequestContract::Session(session_request) => api::ApiResponseContract::Session(
             execute_core_session_request(session_request, metadata)?,
         ),
-        api::ApiRequestContract::Events(_) | api::ApiRequestContract::Subagent(_) => {
-            return Err(CliCommandError::new(
-                api::ApiErrorCode::Unsupported,
-                "CLI transport does not expose this API contract domain",
-            ));
+        api::ApiRequestContract::Events(events_request) => {
+            api::ApiResponseContract::Events(execute_core_events_request(events_request, metadata)?)
+        }
+        api::ApiRequestContract::Subagent(subagent_request) => {
+            api::ApiResponseContract::Subagent(execute_core_subagent_request(subagent_request)?)
         }
     };
     Ok(api::ResponseEnvelope {
@@ -3991,6 +10136,22 @@ fn execute_core_workflow_request(
                     .collect(),
             })
         }
+        api::WorkflowRequest::TaskGraphExport { tasks, format } => {
+            let mut workflow = workflow::Workflow::default();
+            workflow
+                .sync_planner_tasks_from_file(
+                    tasks.into_iter().map(contract_task_to_file_task).collect(),
+                )
+                .map_err(|err| CliCommandError::new(api::ApiErrorCode::ValidationFailed, err))?;
+            let graph_format = match format {
+                api::TaskGraphFormatContract::Mermaid => task_graph::GraphFormat::Mermaid,
+                api::TaskGraphFormatContract::Dot => task_graph::GraphFormat::Dot,
+            };
+            Ok(api::WorkflowResponse::TaskGraph {
+                format,
+                text: task_graph::render(&workflow.planner_tasks_for_file(), graph_format),
+            })
+        }
         _ => Err(CliCommandError::new(
             api::ApiErrorCode::Unsupported,
             "Workflow request is not available in CLI transport mode",
@@ -3998,6 +10159,174 @@ fn execute_core_workflow_request(
     }
 }
 
+fn execute_core_events_request(
+    request: api::EventsRequest,
+    metadata: api::RequestMetadata,
+) -> Result<api::EventsResponse, CliCommandError> {
+    let api::EventsRequest::Subscribe {
+        after,
+        limit,
+        wait_ms,
+    } = request
+    else {
+        return Err(CliCommandError::new(
+            api::ApiErrorCode::Unsupported,
+            "Events request is not available in CLI transport mode",
+        ));
+    };
+    let actor = decode_actor_json(metadata.actor)?;
+    let cwd = actor_pathbuf(&actor, "cwd")?;
+    let session_dir = actor_pathbuf(&actor, "session_dir")?;
+    let store = SessionStore::open_existing(&cwd, &session_dir).map_err(|err| {
+        CliCommandError::new(
+            api::ApiErrorCode::NotFound,
+            format!("Failed to open session: {err}"),
+        )
+        .with_details(json!({ "cwd": cwd, "session_dir": session_dir }))
+    })?;
+    let events = event_log::wait_for_events(
+        &store.events_file(),
+        after,
+        limit.unwrap_or(DEFAULT_EVENT_BATCH_LIMIT).max(1),
+        Duration::from_millis(wait_ms),
+    )
+    .map_err(|err| {
+        CliCommandError::new(
+            api::ApiErrorCode::IoFailure,
+            format!("Failed to read session event log: {err}"),
+        )
+    })?;
+    let next_cursor = events.last().map_or(after, |entry| entry.seq);
+    Ok(api::EventsResponse::Batch {
+        events: events
+            .into_iter()
+            .map(file_event_to_contract_event)
+            .collect(),
+        next_cursor,
+    })
+}
+
+fn execute_core_subagent_request(
+    request: api::SubagentRequest,
+) -> Result<api::SubagentResponse, CliCommandError> {
+    let text = match request {
+        api::SubagentRequest::BuildMasterPrompt {
+            tasks_file,
+            workflow_prompt,
+            tests_mode_enabled,
+        } => subagents::build_master_prompt(
+            &tasks_file,
+            &workflow_prompt,
+            tests_mode_enabled.unwrap_or(true),
+        ),
+        api::SubagentRequest::BuildConvertPlanPrompt {
+            planner_file,
+            tasks_file,
+        } => subagents::build_convert_plan_prompt(&planner_file, &tasks_file),
+        api::SubagentRequest::BuildSessionIntroIfNeeded {
+            prompt,
+            session_dir,
+            session_meta_file,
+            project_info,
+            intro_needed,
+        } => {
+            let mut intro_needed_after = intro_needed;
+            let text = subagents::build_session_intro_if_needed(
+                &prompt,
+                &session_dir,
+                &session_meta_file,
+                project_info.as_deref(),
+                None,
+                None,
+                &mut intro_needed_after,
+            );
+            return Ok(api::SubagentResponse::IntroPrompt {
+                text,
+                intro_needed_after,
+            });
+        }
+        api::SubagentRequest::BuildFailureReportPrompt {
+            task_fails_file,
+            failed_this_cycle,
+            has_test_failure,
+        } => {
+            let entries = failed_this_cycle
+                .into_iter()
+                .map(|entry| contract_task_fail_to_file(entry, 0))
+                .collect::<Vec<_>>();
+            subagents::build_failure_report_prompt(&task_fails_file, &entries, has_test_failure)
+        }
+        api::SubagentRequest::SplitAuditsCommandPrompt => subagents::split_audits_command_prompt(),
+        api::SubagentRequest::MergeAuditsCommandPrompt => subagents::merge_audits_command_prompt(),
+        api::SubagentRequest::SplitTestsCommandPrompt
+        | api::SubagentRequest::MergeTestsCommandPrompt => {
+            return Err(CliCommandError::new(
+                api::ApiErrorCode::Unsupported,
+                "Test split/merge commands are disabled in this build",
+            ));
+        }
+        api::SubagentRequest::BuildProjectInfoPrompt {
+            cwd,
+            question,
+            output_path,
+        } => subagents::build_project_info_prompt(
+            &cwd,
+            &question,
+            &output_path,
+            WorkspaceIgnore::load(Path::new(&cwd)).metaagent_patterns(),
+            &load_global_project_info_scope().map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::InvalidRequest,
+                    format!("Invalid [project_info] in config.toml: {err}"),
+                )
+            })?,
+        ),
+        api::SubagentRequest::BuildSessionMetaPrompt {
+            user_prompt,
+            output_path,
+        } => subagents::build_session_meta_prompt(&user_prompt, &output_path),
+        api::SubagentRequest::BuildTaskCheckPrompt {
+            tasks_file,
+            project_info_file,
+            session_meta_file,
+        } => {
+            subagents::build_task_check_prompt(&tasks_file, &project_info_file, &session_meta_file)
+        }
+        api::SubagentRequest::BuildAttachDocsPrompt { tasks_file } => {
+            App::default().prepare_attach_docs_prompt(&tasks_file)
+        }
+        api::SubagentRequest::BuildAuditPrompt {
+            tasks,
+            task_id,
+            worker_output,
+            changed_files_summary,
+            pass,
+            tests_mode_enabled,
+            audit_rubric,
+            rolling_context,
+        } => {
+            let mut workflow = workflow::Workflow::default();
+            workflow.set_tests_mode_enabled(tests_mode_enabled.unwrap_or(true));
+            workflow.set_audit_rubric(audit_rubric);
+            workflow.replace_rolling_context_entries(rolling_context);
+            workflow
+                .sync_planner_tasks_from_file(
+                    tasks.into_iter().map(contract_task_to_file_task).collect(),
+                )
+                .map_err(|err| CliCommandError::new(api::ApiErrorCode::ValidationFailed, err))?;
+            workflow
+                .audit_prompt_for_planner_task(
+                    &task_id,
+                    worker_output,
+                    changed_files_summary,
+                    pass.unwrap_or(1),
+                )
+                .map_err(|err| CliCommandError::new(api::ApiErrorCode::InvalidRequest, err))?
+        }
+    };
+    Ok(api::SubagentResponse::Prompt { text })
+}
+
 fn execute_core_session_request(
     request: api::SessionRequest,
     metadata: api::RequestMetadata,
@@ -4016,8 +10345,27 @@ fn execute_core_session_request(
     };
 
     match request {
-        api::SessionRequest::Initialize { cwd } => {
+        api::SessionRequest::Initialize { cwd, template } => {
             let cwd = PathBuf::from(cwd);
+            let template = template
+                .map(|name| {
+                    session_template::load_template(&name)
+                        .map(|template| (name.clone(), template))
+                        .map_err(|err| {
+                            CliCommandError::new(
+                                match err.kind() {
+                                    io::ErrorKind::NotFound => api::ApiErrorCode::NotFound,
+                                    io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => {
+                                        api::ApiErrorCode::ValidationFailed
+                                    }
+                                    _ => api::ApiErrorCode::IoFailure,
+                                },
+                                format!("Failed to load session template: {err}"),
+                            )
+                            .with_details(json!({ "template": name }))
+                        })
+                })
+                .transpose()?;
             let session = SessionStore::initialize(&cwd).map_err(|err| {
                 CliCommandError::new(
                     api::ApiErrorCode::IoFailure,
@@ -4025,6 +10373,15 @@ fn execute_core_session_request(
                 )
                 .with_details(json!({ "cwd": cwd }))
             })?;
+            if let Some((name, template)) = template {
+                template.apply_to_session(&name, &session).map_err(|err| {
+                    CliCommandError::new(
+                        api::ApiErrorCode::IoFailure,
+                        format!("Failed to apply session template: {err}"),
+                    )
+                    .with_details(json!({ "template": name }))
+                })?;
+            }
             Ok(api::SessionResponse::Initialized {
                 session: api::SessionStoreSnapshotContract {
                     session_dir: session.session_dir().display().to_string(),
@@ -4181,9 +10538,127 @@ fn execute_core_session_request(
                 meta: file_session_meta_to_contract(meta),
             })
         }
+        api::SessionRequest::SearchCode { query, limit } => {
+            if query.trim().is_empty() {
+                return Err(CliCommandError::new(
+                    api::ApiErrorCode::ValidationFailed,
+                    "Search query must not be empty",
+                ));
+            }
+            let session = open_actor_session()?;
+            let root = actor_pathbuf(&decode_actor_json(metadata.actor.clone())?, "cwd")?;
+            let backend = code_index::load_embedding_backend().map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::ValidationFailed,
+                    format!("Failed to load embedding settings from config.toml: {err}"),
+                )
+            })?;
+            let hits = code_index::search_code(&root, &session, &backend, &query, limit).map_err(
+                |err| {
+                    CliCommandError::new(
+                        api::ApiErrorCode::IoFailure,
+                        format!("Failed to search the code index: {err}"),
+                    )
+                },
+            )?;
+            Ok(api::SessionResponse::CodeSearchResults {
+                results: hits
+                    .into_iter()
+                    .map(|hit| api::CodeSearchHitContract {
+                        path: hit.path,
+                        start_line: hit.start_line,
+                        end_line: hit.end_line,
+                        score: hit.score,
+                        excerpt: hit.excerpt,
+                    })
+                    .collect(),
+            })
+        }
+        api::SessionRequest::ExportAuditTrail { output } => {
+            let session = open_actor_session()?;
+            let path = session.audit_trail_file();
+            let entries = audit_trail::read_entries(&path).map_err(|err| {
+                CliCommandError::new(
+                    api::ApiErrorCode::IoFailure,
+                    format!("Failed to read audit trail: {err}"),
+                )
+            })?;
+            if let Some(output) = output {
+                let copied = if path.exists() {
+                    std::fs::copy(&path, &output).map(|_| ())
+                } else {
+                    std::fs::write(&output, "")
+                };
+                copied.map_err(|err| {
+                    CliCommandError::new(
+                        api::ApiErrorCode::IoFailure,
+                        format!("Failed to write audit trail export: {err}"),
+                    )
+                    .with_details(json!({ "output": output }))
+                })?;
+            }
+            let verification = audit_trail::verify(&entries);
+            Ok(api::SessionResponse::AuditTrail {
+                entries: entries
+                    .into_iter()
+                    .map(file_audit_entry_to_contract)
+                    .collect(),
+                head_hash: verification.head_hash,
+                broken_at: verification.broken_at,
+            })
+        }
+        api::SessionRequest::AddTask { task } => apply_session_task_edit(
+            open_actor_session()?,
+            TaskEdit::Add(contract_task_to_file_task(task)),
+        ),
+        api::SessionRequest::UpdateTask { task } => apply_session_task_edit(
+            open_actor_session()?,
+            TaskEdit::Update(contract_task_to_file_task(task)),
+        ),
+        api::SessionRequest::RemoveTask { task_id } => {
+            apply_session_task_edit(open_actor_session()?, TaskEdit::Remove { task_id })
+        }
+        api::SessionRequest::ReorderTask { task_id, position } => apply_session_task_edit(
+            open_actor_session()?,
+            TaskEdit::Reorder { task_id, position },
+        ),
     }
 }
 
+fn apply_session_task_edit(
+    session: SessionStore,
+    edit: TaskEdit,
+) -> Result<api::SessionResponse, CliCommandError> {
+    let tasks = session.read_tasks().map_err(|err| {
+        CliCommandError::new(
+            api::ApiErrorCode::IoFailure,
+            format!("Failed to read tasks file: {err}"),
+        )
+    })?;
+    let final_audit_policy = load_global_final_audit_policy().map_err(|err| {
+        CliCommandError::new(
+            api::ApiErrorCode::InvalidRequest,
+            format!("Invalid [workflow] final_audit in config.toml: {err}"),
+        )
+    })?;
+    let tasks = task_edits::apply(tasks, edit, final_audit_policy).map_err(|err| {
+        let code = match err {
+            TaskEditError::NotFound(_) => api::ApiErrorCode::NotFound,
+            TaskEditError::Invalid(_) => api::ApiErrorCode::ValidationFailed,
+        };
+        CliCommandError::new(code, err.to_string())
+    })?;
+    session.write_tasks_atomic(&tasks).map_err(|err| {
+        CliCommandError::new(
+            api::ApiErrorCode::IoFailure,
+            format!("Failed to write tasks file: {err}"),
+        )
+    })?;
+    Ok(api::SessionResponse::Tasks {
+        tasks: tasks.into_iter().map(file_task_to_contract_task).collect(),
+    })
+}
+
 fn file_task_to_contract_task(task: PlannerTaskFileEntry) -> api::PlannerTaskEntryContract {
     api::PlannerTaskEntryContract {
         id: task.id,
@@ -4198,6 +10673,102 @@ fn file_task_to_contract_task(task: PlannerTaskFileEntry) -> api::PlannerTaskEnt
         status: file_status_to_contract_status(task.status),
         parent_id: task.parent_id,
         order: task.order,
+        external_ref: task.external_ref,
+        workdir: task.workdir,
+        acceptance_criteria: task.acceptance_criteria,
+        plugin: task.plugin,
+        estimate: task.estimate,
+        verify: task.verify,
+        blocked_reason: task.blocked_reason,
+        user_answers: task.user_answers,
+    }
+}
+
+fn file_event_to_contract_event(
+    entry: event_log::SessionEventFileEntry,
+) -> api::SessionEventRecordContract {
+    use event_log::SessionEventKindFile as Kind;
+    let event = match entry.event {
+        Kind::TaskStatusChanged {
+            task_id,
+            title,
+            status,
+        } => api::SessionEventContract::TaskStatusChanged {
+            task_id,
+            title,
+            status: file_status_to_contract_status(status),
+        },
+        Kind::ExecutionChanged { enabled, busy } => {
+            api::SessionEventContract::ExecutionChanged { enabled, busy }
+        }
+        Kind::AgentOutput { agent, line } => api::SessionEventContract::AgentOutput { agent, line },
+        Kind::AgentSystem { agent, line } => api::SessionEventContract::AgentSystem { agent, line },
+        Kind::AgentCompleted {
+            agent,
+            success,
+            exit_code,
+        } => api::SessionEventContract::AgentCompleted {
+            agent,
+            success,
+            exit_code,
+        },
+        Kind::ManualIntervention {
+            task_id,
+            title,
+            action,
+        } => api::SessionEventContract::ManualIntervention {
+            task_id,
+            title,
+            action,
+        },
+        Kind::ChatMessage { line } => api::SessionEventContract::ChatMessage { line },
+    };
+    api::SessionEventRecordContract {
+        seq: entry.seq,
+        at_epoch_ms: entry.at_epoch_ms,
+        event,
+    }
+}
+
+fn file_audit_entry_to_contract(
+    entry: audit_trail::AuditTrailFileEntry,
+) -> api::AuditTrailEntryContract {
+    use audit_trail::AuditEventFile as Kind;
+    let event = match entry.event {
+        Kind::PromptSent {
+            program,
+            model,
+            prompt,
+        } => api::AuditEventContract::PromptSent {
+            program,
+            model,
+            prompt,
+        },
+        Kind::FilesChanged {
+            task_id,
+            pass,
+            files,
+        } => api::AuditEventContract::FilesChanged {
+            task_id,
+            pass,
+            files,
+        },
+        Kind::ApprovalDecision {
+            subject,
+            decision,
+            detail,
+        } => api::AuditEventContract::ApprovalDecision {
+            subject,
+            decision,
+            detail,
+        },
+    };
+    api::AuditTrailEntryContract {
+        seq: entry.seq,
+        at_epoch_ms: entry.at_epoch_ms,
+        event,
+        prev_hash: entry.prev_hash,
+        hash: entry.hash,
     }
 }
 
@@ -4215,6 +10786,14 @@ fn contract_task_to_file_task(task: api::PlannerTaskEntryContract) -> PlannerTas
         status: contract_status_to_file_status(task.status),
         parent_id: task.parent_id,
         order: task.order,
+        external_ref: task.external_ref,
+        workdir: task.workdir,
+        acceptance_criteria: task.acceptance_criteria,
+        plugin: task.plugin,
+        estimate: task.estimate,
+        verify: task.verify,
+        blocked_reason: task.blocked_reason,
+        user_answers: task.user_answers,
     }
 }
 
@@ -4225,6 +10804,7 @@ fn file_doc_to_contract_doc(
         title: doc.title,
         url: doc.url,
         summary: doc.summary,
+        snapshot: doc.snapshot,
     }
 }
 
@@ -4235,6 +10815,7 @@ fn contract_doc_to_file_doc(
         title: doc.title,
         url: doc.url,
         summary: doc.summary,
+        snapshot: doc.snapshot,
     }
 }
 
@@ -4244,6 +10825,7 @@ fn file_task_fail_to_contract(
     let kind = match entry.kind.as_str() {
         "audit" => api::WorkflowFailureKindContract::Audit,
         "test" => api::WorkflowFailureKindContract::Test,
+        "flaky_test" => api::WorkflowFailureKindContract::FlakyTest,
         other => {
             return Err(CliCommandError::new(
                 api::ApiErrorCode::ValidationFailed,
@@ -4269,6 +10851,7 @@ fn contract_task_fail_to_file(
         kind: match entry.kind {
             api::WorkflowFailureKindContract::Audit => "audit".to_string(),
             api::WorkflowFailureKindContract::Test => "test".to_string(),
+            api::WorkflowFailureKindContract::FlakyTest => "flaky_test".to_string(),
         },
         top_task_id: entry.top_task_id,
         top_task_title: entry.top_task_title,
@@ -4285,7 +10868,51 @@ fn file_session_meta_to_contract(meta: session_store::SessionMetaFile) -> api::S
         created_at: meta.created_at,
         stack_description: meta.stack_description,
         test_command: meta.test_command,
+        languages: meta.languages,
+        frameworks: meta.frameworks,
+        package_manager: meta.package_manager,
+        build_command: meta.build_command,
+        run_command: meta.run_command,
+        personas: meta.personas,
+    }
+}
+
+/// Files the master report agent's reply to a task summary prompt, joined into one paragraph.
+fn record_task_summary(
+    app: &mut App,
+    session_store: Option<&SessionStore>,
+    top_task_id: String,
+    top_task_title: String,
+    transcript: &[String],
+) {
+    let summary = transcript
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if summary.is_empty() {
+        app.push_agent_message(app.system_text(
+            Text::ReportNoSummary,
+            &[("top_task_title", &top_task_title)],
+        ));
+        return;
+    }
+    let entry = TaskSummaryFileEntry {
+        top_task_id,
+        top_task_title,
+        summary,
+        created_at_epoch_secs: current_epoch_secs(),
+    };
+    if let Some(store) = session_store
+        && let Err(err) = store.upsert_task_summary(entry.clone())
+    {
+        app.push_agent_message(app.system_text(Text::TaskSummariesWriteFailed, &[("err", &err)]));
     }
+    app.push_agent_message(
+        app.system_text(Text::TaskSummarized, &[("title", &entry.top_task_title)]),
+    );
+    app.record_task_summary(entry);
 }
 
 fn current_epoch_secs() -> u64 {
@@ -4303,6 +10930,7 @@ fn file_kind_to_contract_kind(kind: PlannerTaskKindFile) -> api::PlannerTaskKind
         PlannerTaskKindFile::TestWriter => api::PlannerTaskKindContract::TestWriter,
         PlannerTaskKindFile::TestRunner => api::PlannerTaskKindContract::TestRunner,
         PlannerTaskKindFile::FinalAudit => api::PlannerTaskKindContract::FinalAudit,
+        PlannerTaskKindFile::Plugin => api::PlannerTaskKindContract::Plugin,
     }
 }
 
@@ -4314,6 +10942,7 @@ fn contract_kind_to_file_kind(kind: api::PlannerTaskKindContract) -> PlannerTask
         api::PlannerTaskKindContract::TestWriter => PlannerTaskKindFile::TestWriter,
         api::PlannerTaskKindContract::TestRunner => PlannerTaskKindFile::TestRunner,
         api::PlannerTaskKindContract::FinalAudit => PlannerTaskKindFile::FinalAudit,
+        api::PlannerTaskKindContract::Plugin => PlannerTaskKindFile::Plugin,
     }
 }
 
@@ -4323,6 +10952,7 @@ fn file_status_to_contract_status(status: PlannerTaskStatusFile) -> api::Planner
         PlannerTaskStatusFile::InProgress => api::PlannerTaskStatusContract::InProgress,
         PlannerTaskStatusFile::NeedsChanges => api::PlannerTaskStatusContract::NeedsChanges,
         PlannerTaskStatusFile::Done => api::PlannerTaskStatusContract::Done,
+        PlannerTaskStatusFile::Blocked => api::PlannerTaskStatusContract::Blocked,
     }
 }
 
@@ -4332,6 +10962,7 @@ fn contract_status_to_file_status(status: api::PlannerTaskStatusContract) -> Pla
         api::PlannerTaskStatusContract::InProgress => PlannerTaskStatusFile::InProgress,
         api::PlannerTaskStatusContract::NeedsChanges => PlannerTaskStatusFile::NeedsChanges,
         api::PlannerTaskStatusContract::Done => PlannerTaskStatusFile::Done,
+        api::PlannerTaskStatusContract::Blocked => PlannerTaskStatusFile::Blocked,
     }
 }
 
@@ -4412,6 +11043,33 @@ fn render_cli_payload_human(payload: &Value, verbose: bool) {
         }
         return;
     }
+    if let Ok(report) =
+        serde_json::from_value::<backend_doctor::BackendDoctorReport>(payload.clone())
+    {
+        for line in report.render_lines() {
+            println!("{line}");
+        }
+        return;
+    }
+    if let Ok(stats) = serde_json::from_value::<backend_metrics::BackendStats>(payload.clone()) {
+        for line in stats.render_lines() {
+            println!("{line}");
+        }
+        return;
+    }
+    if let Ok(stats) = serde_json::from_value::<stats::FailureStats>(payload.clone()) {
+        for line in stats.render_lines() {
+            println!("{line}");
+        }
+        return;
+    }
+    if let (Some(graph), Some(_)) = (
+        payload.get("graph").and_then(Value::as_str),
+        payload.get("format"),
+    ) {
+        println!("{graph}");
+        return;
+    }
 
     if verbose {
         let text = match serde_json::to_string_pretty(payload) {
@@ -4627,11 +11285,35 @@ impl<'a> From<&'a api::capabilities::CapabilityDefinition> for CapabilityDefinit
     }
 }
 
+/// Line that separates the steps of a `--send-file` batch script.
+const BATCH_STEP_DELIMITER: &str = "---";
+
+/// Splits a `--send-file` script into the messages and commands it sends, in order. Steps are
+/// separated by lines holding only `---` and blank steps are dropped, so a file without a
+/// delimiter is a single message.
+fn parse_batch_steps(text: &str) -> Vec<String> {
+    let mut steps = vec![String::new()];
+    for line in text.lines() {
+        if line.trim() == BATCH_STEP_DELIMITER {
+            steps.push(String::new());
+        } else if let Some(step) = steps.last_mut() {
+            step.push_str(line);
+            step.push('\n');
+        }
+    }
+    steps
+        .into_iter()
+        .map(|step| step.trim().to_string())
+        .filter(|step| !step.is_empty())
+        .collect()
+}
+
 #[derive(Debug, Default)]
 struct LaunchOptions {
     send_file: Option<PathBuf>,
     output_mode: CliOutputMode,
     verbose: bool,
+    safe_mode: bool,
     command: Option<RootCommand>,
 }
 
@@ -4673,6 +11355,7 @@ where
         send_file: parsed.send_file,
         output_mode: parsed.output_mode,
         verbose: parsed.verbose,
+        safe_mode: parsed.safe_mode,
         command: parsed.command,
     })
 }
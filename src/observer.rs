@@ -0,0 +1,212 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::app::App;
+use crate::event_log::{SessionEventFileEntry, SessionEventKindFile};
+use crate::i18n::Text;
+use crate::session_store::{SessionStore, TaskFailFileEntry};
+use crate::workflow::ManualTaskAction;
+
+/// Tails the files of a session that is driven by another process and mirrors them into `App`.
+#[derive(Debug)]
+pub struct SessionObserver {
+    store: SessionStore,
+    tasks_snapshot: Option<String>,
+    planner_snapshot: Option<String>,
+    context_snapshot: Vec<String>,
+    task_fails_seen: usize,
+    /// Bytes of `events.jsonl` already applied; only whole lines are consumed.
+    events_offset: u64,
+}
+
+impl SessionObserver {
+    pub fn new(store: SessionStore) -> Self {
+        Self {
+            store,
+            tasks_snapshot: None,
+            planner_snapshot: None,
+            context_snapshot: Vec::new(),
+            task_fails_seen: 0,
+            events_offset: 0,
+        }
+    }
+
+    pub fn session_dir(&self) -> &Path {
+        self.store.session_dir()
+    }
+
+    /// Applies any session file changes since the last poll. Returns true when the app changed.
+    pub fn poll(&mut self, app: &mut App) -> bool {
+        let mut changed = self.poll_tasks(app);
+        changed |= self.poll_planner(app);
+        changed |= self.poll_rolling_context(app);
+        changed |= self.poll_task_fails(app);
+        changed |= self.poll_events(app);
+        changed
+    }
+
+    /// Replays the chat lines, agent output, and manual interventions appended to the event
+    /// log since the last poll. Task and execution state come from the files polled above.
+    fn poll_events(&mut self, app: &mut App) -> bool {
+        let Ok(mut file) = File::open(self.store.events_file()) else {
+            return false;
+        };
+        let Ok(len) = file.metadata().map(|meta| meta.len()) else {
+            return false;
+        };
+        if len < self.events_offset {
+            // Rewritten from scratch, e.g. by `session pull`.
+            self.events_offset = 0;
+        }
+        let mut appended = Vec::new();
+        if file.seek(SeekFrom::Start(self.events_offset)).is_err()
+            || file.read_to_end(&mut appended).is_err()
+        {
+            return false;
+        }
+        // A line still being written is picked up by a later poll.
+        let Some(end) = appended.iter().rposition(|byte| *byte == b'\n') else {
+            return false;
+        };
+        self.events_offset += end as u64 + 1;
+        let mut changed = false;
+        for line in appended[..end].split(|byte| *byte == b'\n') {
+            let Ok(entry) = serde_json::from_slice::<SessionEventFileEntry>(line) else {
+                continue;
+            };
+            changed |= apply_observed_event(app, entry.event);
+        }
+        changed
+    }
+
+    fn poll_tasks(&mut self, app: &mut App) -> bool {
+        let Ok(text) = std::fs::read_to_string(self.store.tasks_file()) else {
+            return false;
+        };
+        if self.tasks_snapshot.as_deref() == Some(text.as_str()) {
+            return false;
+        }
+        self.tasks_snapshot = Some(text);
+        match self.store.read_tasks() {
+            Ok(tasks) => {
+                if let Err(err) = app.sync_planner_tasks_from_file(tasks) {
+                    app.push_agent_message(
+                        app.system_text(Text::ObservedTasksApplyFailed, &[("err", &err)]),
+                    );
+                }
+            }
+            Err(err) => app.push_agent_message(
+                app.system_text(Text::ObservedTasksParseFailed, &[("err", &err)]),
+            ),
+        }
+        true
+    }
+
+    fn poll_planner(&mut self, app: &mut App) -> bool {
+        let Ok(markdown) = self.store.read_planner_markdown() else {
+            return false;
+        };
+        if self.planner_snapshot.as_deref() == Some(markdown.as_str()) {
+            return false;
+        }
+        self.planner_snapshot = Some(markdown.clone());
+        app.set_planner_markdown(markdown);
+        true
+    }
+
+    fn poll_rolling_context(&mut self, app: &mut App) -> bool {
+        let Ok(entries) = self.store.read_rolling_context() else {
+            return false;
+        };
+        let new_entries = unseen_context_entries(&self.context_snapshot, &entries);
+        let changed = !new_entries.is_empty();
+        for entry in new_entries {
+            app.push_subagent_output(format!("Context: {entry}"));
+        }
+        self.context_snapshot = entries;
+        changed
+    }
+
+    fn poll_task_fails(&mut self, app: &mut App) -> bool {
+        let Ok(entries) = self.store.read_task_fails() else {
+            return false;
+        };
+        if entries.len() < self.task_fails_seen {
+            self.task_fails_seen = 0;
+        }
+        let new_entries = &entries[self.task_fails_seen..];
+        if new_entries.is_empty() {
+            return false;
+        }
+        for entry in new_entries {
+            app.push_agent_message(format_observed_task_fail(app, entry));
+        }
+        self.task_fails_seen = entries.len();
+        true
+    }
+}
+
+/// The rolling context is a capped window, so new entries are whatever follows the longest
+/// overlap between the end of the previous window and the start of the current one.
+fn unseen_context_entries<'a>(previous: &[String], current: &'a [String]) -> &'a [String] {
+    let max_overlap = previous.len().min(current.len());
+    let overlap = (0..=max_overlap)
+        .rev()
+        .find(|&len| previous[previous.len() - len..] == current[..len])
+        .unwrap_or(0);
+    &current[overlap..]
+}
+
+fn apply_observed_event(app: &mut App, event: SessionEventKindFile) -> bool {
+    match event {
+        SessionEventKindFile::ChatMessage { line } => app.push_agent_message(line),
+        SessionEventKindFile::AgentOutput { agent, line }
+        | SessionEventKindFile::AgentSystem { agent, line } => {
+            app.push_subagent_output(format!("{agent}: {line}"))
+        }
+        SessionEventKindFile::AgentCompleted {
+            agent,
+            success,
+            exit_code,
+        } => app.push_subagent_output(if success {
+            format!("{agent}: finished")
+        } else {
+            format!("{agent}: failed with exit code {exit_code}")
+        }),
+        SessionEventKindFile::ManualIntervention {
+            task_id,
+            title,
+            action,
+        } => {
+            let observed = if action == ManualTaskAction::Skip.label() {
+                Text::ObservedTaskSkipped
+            } else {
+                Text::ObservedTaskMarkedDone
+            };
+            app.push_agent_message(
+                app.system_text(observed, &[("task_id", &task_id), ("title", &title)]),
+            )
+        }
+        SessionEventKindFile::TaskStatusChanged { .. }
+        | SessionEventKindFile::ExecutionChanged { .. } => return false,
+    }
+    true
+}
+
+fn format_observed_task_fail(app: &App, entry: &TaskFailFileEntry) -> String {
+    app.system_text(
+        Text::ObservedTaskFailed,
+        &[
+            ("kind", &entry.kind),
+            ("task", &entry.top_task_id),
+            ("title", &entry.top_task_title),
+            ("attempts", &entry.attempts),
+            ("reason", &entry.reason),
+        ],
+    )
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/observer_tests.rs"]
+mod tests;
@@ -0,0 +1,217 @@
+use serde::Deserialize;
+
+/// What a rule does with the lines it matches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    #[default]
+    Hide,
+    /// Shows the first line of a run of matching lines with a count of the rest.
+    Collapse,
+}
+
+/// One `[[output_filters.rules]]` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputFilterRule {
+    /// Text a line must contain. `*` matches any text, and a leading `^` or trailing `$`
+    /// ties the pattern to the start or end of the line.
+    pub pattern: String,
+    /// Roles the rule applies to (`implementor`, `auditor`, `test_writer`, `test_runner`,
+    /// `final_audit`, `project_info`, `docs`, `task_split`, `task_check`); empty means all.
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub action: FilterAction,
+}
+
+/// `[output_filters]` in config.toml: which agent output lines the subagent pane hides or
+/// collapses. Worker logs always get every line.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputFilterConfig {
+    /// Hides spinner frames such as `⠙ Thinking`.
+    pub hide_spinners: bool,
+    /// Collapses a line repeated back to back into one line with a count.
+    pub collapse_repeats: bool,
+    pub rules: Vec<OutputFilterRule>,
+}
+
+impl Default for OutputFilterConfig {
+    fn default() -> Self {
+        Self {
+            hide_spinners: true,
+            collapse_repeats: true,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// How the subagent pane should take one output line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaneUpdate {
+    Append(String),
+    /// Replaces the pane's last line, which holds the run this line was collapsed into.
+    ReplaceLast(String),
+    Hide,
+}
+
+#[derive(Debug, Clone)]
+struct CollapsedRun {
+    /// The role plus the matching rule's pattern, or plus the repeated line itself.
+    key: String,
+    first_line: String,
+    hidden: usize,
+    /// Pane generation right after the run's line was last written; any other pane write
+    /// ends the run.
+    pane_generation: u64,
+}
+
+/// Applies an [`OutputFilterConfig`] to agent output on its way to the subagent pane.
+#[derive(Debug, Clone, Default)]
+pub struct OutputFilter {
+    config: OutputFilterConfig,
+    verbose: bool,
+    hidden_lines: usize,
+    run: Option<CollapsedRun>,
+}
+
+impl OutputFilter {
+    pub fn new(config: OutputFilterConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    /// Shows every line from now on (`/verbose`) or goes back to filtering.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+        self.run = None;
+    }
+
+    /// Lines hidden or collapsed so far.
+    pub fn hidden_lines(&self) -> usize {
+        self.hidden_lines
+    }
+
+    /// Decides what to do with `line`, shown as `display`, from an agent in `role`.
+    /// `pane_generation` is the pane's write counter, which is expected to go up by one
+    /// when the update is applied.
+    pub fn apply(
+        &mut self,
+        role: &str,
+        line: &str,
+        display: String,
+        pane_generation: u64,
+    ) -> PaneUpdate {
+        if self.verbose {
+            return PaneUpdate::Append(display);
+        }
+        if self.config.hide_spinners && is_spinner_frame(line) {
+            self.hidden_lines += 1;
+            return PaneUpdate::Hide;
+        }
+        let rule = self.config.rules.iter().find(|rule| {
+            (rule.roles.is_empty() || rule.roles.iter().any(|allowed| allowed == role))
+                && pattern_matches(&rule.pattern, line)
+        });
+        let key = match rule {
+            Some(rule) if rule.action == FilterAction::Hide => {
+                self.hidden_lines += 1;
+                return PaneUpdate::Hide;
+            }
+            Some(rule) => Some(format!("{role}\n{}", rule.pattern)),
+            None => self
+                .config
+                .collapse_repeats
+                .then(|| format!("{role}\n{}", line.trim())),
+        };
+        let Some(key) = key else {
+            self.run = None;
+            return PaneUpdate::Append(display);
+        };
+        let next_generation = pane_generation.saturating_add(1);
+        if let Some(run) = self.run.as_mut()
+            && run.key == key
+            && run.pane_generation == pane_generation
+        {
+            run.hidden += 1;
+            run.pane_generation = next_generation;
+            self.hidden_lines += 1;
+            return PaneUpdate::ReplaceLast(format!(
+                "{} (+{} similar)",
+                run.first_line, run.hidden
+            ));
+        }
+        self.run = Some(CollapsedRun {
+            key,
+            first_line: display.clone(),
+            hidden: 0,
+            pane_generation: next_generation,
+        });
+        PaneUpdate::Append(display)
+    }
+}
+
+/// A terminal spinner frame: a braille-pattern glyph first, or a lone `|`, `/`, `-`, `\`.
+pub fn is_spinner_frame(line: &str) -> bool {
+    let line = line.trim();
+    line.chars()
+        .next()
+        .is_some_and(|first| ('\u{2801}'..='\u{28FF}').contains(&first))
+        || matches!(line, "|" | "/" | "-" | "\\")
+}
+
+/// Whether `line` matches a rule pattern; see [`OutputFilterRule::pattern`].
+pub fn pattern_matches(pattern: &str, line: &str) -> bool {
+    let (anchored_start, pattern) = match pattern.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    let (anchored_end, pattern) = match pattern.strip_suffix('$') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    let parts = pattern.split('*').collect::<Vec<_>>();
+    let (first, rest) = parts.split_first().expect("split yields at least one part");
+    if rest.is_empty() {
+        return match (anchored_start, anchored_end) {
+            (true, true) => line == *first,
+            (true, false) => line.starts_with(first),
+            (false, true) => line.ends_with(first),
+            (false, false) => line.contains(first),
+        };
+    }
+    let mut remaining = line;
+    if anchored_start {
+        let Some(after) = remaining.strip_prefix(first) else {
+            return false;
+        };
+        remaining = after;
+    } else if let Some(index) = remaining.find(first) {
+        remaining = &remaining[index + first.len()..];
+    } else {
+        return false;
+    }
+    let (last, middle) = rest.split_last().expect("rest is not empty");
+    for part in middle {
+        let Some(index) = remaining.find(part) else {
+            return false;
+        };
+        remaining = &remaining[index + part.len()..];
+    }
+    if anchored_end {
+        remaining.ends_with(last)
+    } else {
+        remaining.contains(last)
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/output_filters_tests.rs"]
+mod tests;
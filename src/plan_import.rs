@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Plan files looked for in the workspace root, most specific first.
+const PLAN_FILE_NAMES: [&str; 3] = ["PLAN.md", "TODO.md", "planner.md"];
+/// Larger files are more likely generated output than a plan someone wrote.
+const MAX_PLAN_BYTES: u64 = 256 * 1024;
+
+/// A plan written outside Bob, found in the workspace root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspacePlan {
+    pub file_name: &'static str,
+    pub path: PathBuf,
+    pub markdown: String,
+}
+
+/// The first non-empty plan file in `cwd`, if any.
+pub fn find(cwd: &Path) -> Option<WorkspacePlan> {
+    PLAN_FILE_NAMES.iter().find_map(|file_name| {
+        let path = cwd.join(file_name);
+        let metadata = fs::metadata(&path).ok()?;
+        if !metadata.is_file() || metadata.len() > MAX_PLAN_BYTES {
+            return None;
+        }
+        let markdown = fs::read_to_string(&path).ok()?;
+        (!markdown.trim().is_empty()).then(|| WorkspacePlan {
+            file_name,
+            path,
+            markdown: markdown.trim().to_string(),
+        })
+    })
+}
+
+/// Planner text with `plan` imported: the plan itself when the planner is empty, otherwise the
+/// planner followed by the plan under an "Imported from" heading. Importing the same plan again
+/// changes nothing.
+pub fn merge_into_planner(planner: &str, plan: &WorkspacePlan) -> String {
+    let planner = planner.trim_end();
+    if planner.trim().is_empty() {
+        return format!("{}\n", plan.markdown);
+    }
+    if planner.contains(&plan.markdown) {
+        return format!("{planner}\n");
+    }
+    format!(
+        "{planner}\n\n## Imported from {}\n\n{}\n",
+        plan.file_name, plan.markdown
+    )
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/plan_import_tests.rs"]
+mod tests;
@@ -0,0 +1,243 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::agent_models::CodexAgentKind;
+use crate::app::App;
+use crate::project_info_scope::ProjectInfoScope;
+use crate::project_memory;
+use crate::services::{DefaultUiPromptService, UiPromptService};
+use crate::session_store::{PlannerTaskFileEntry, SessionStore};
+use crate::subagents;
+use crate::tasks_changelog::{self, TasksChangeAgent};
+use crate::workspace_ignore::WorkspaceIgnore;
+
+const MAX_TASK_FILE_FIX_ATTEMPTS: usize = 2;
+
+/// One headless sub-agent run of `plan --spec`, in pipeline order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanStage {
+    ProjectInfo,
+    SessionMeta,
+    Master,
+    TaskCheck,
+    DocsAttach,
+}
+
+/// Sends a stage's prompt to its agent and blocks until the run finishes, returning the
+/// agent's output lines, or an error when the run fails.
+pub trait PlanStageRunner {
+    fn run(&mut self, stage: PlanStage, prompt: String) -> Result<Vec<String>, String>;
+}
+
+/// A non-fatal problem in one stage; the pipeline carried on without that stage's result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanStageNote {
+    pub stage: PlanStage,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanOutcome {
+    pub tasks: Vec<PlannerTaskFileEntry>,
+    pub notes: Vec<PlanStageNote>,
+}
+
+/// Plans `spec` into the session's tasks.json without the TUI: project info, master planning
+/// (with the same correction retries as the TUI), task check, then docs attach. Only master
+/// failures are fatal; a later stage that leaves tasks.json invalid is rolled back.
+pub fn run_plan_pipeline(
+    app: &mut App,
+    store: &SessionStore,
+    cwd: &Path,
+    spec: &str,
+    runner: &mut impl PlanStageRunner,
+) -> Result<PlanOutcome, String> {
+    let mut notes = Vec::new();
+    let mut note = |stage, note: String| notes.push(PlanStageNote { stage, note });
+
+    let project_info_text = gather_project_info(store, cwd, spec, app.project_info_scope(), runner)
+        .unwrap_or_else(|err| {
+            note(PlanStage::ProjectInfo, err);
+            None
+        });
+    let meta_prompt = subagents::build_session_meta_prompt(
+        spec,
+        &store.session_meta_file().display().to_string(),
+    );
+    if let Err(err) = runner.run(PlanStage::SessionMeta, meta_prompt) {
+        note(PlanStage::SessionMeta, err);
+    }
+
+    let baseline = std::fs::read_to_string(store.tasks_file()).ok();
+    let mut intro_needed = true;
+    let mut prompt = DefaultUiPromptService.build_master_prompt_for_message(
+        app,
+        spec,
+        store,
+        project_info_text.as_deref(),
+        &mut intro_needed,
+    );
+    let mut attempt = 0;
+    loop {
+        runner
+            .run(PlanStage::Master, prompt)
+            .map_err(|err| format!("Master planning failed: {err}"))?;
+        let err = match load_master_tasks(app, store, baseline.as_deref()) {
+            Ok(()) => break,
+            Err(err) => err,
+        };
+        if attempt == MAX_TASK_FILE_FIX_ATTEMPTS {
+            return Err(format!(
+                "tasks.json still does not validate after {attempt} correction attempt(s): {err}"
+            ));
+        }
+        attempt += 1;
+        prompt = subagents::build_session_intro_if_needed(
+            crate::TASKS_FILE_FIX_PROMPT,
+            &store.session_dir().display().to_string(),
+            &store.session_meta_file().display().to_string(),
+            project_info_text.as_deref(),
+            project_memory::session_memory_section(store).as_deref(),
+            store.agent_persona(CodexAgentKind::Master).as_deref(),
+            &mut intro_needed,
+        );
+    }
+    if let Err(err) = record_tasks_change(store, TasksChangeAgent::Master, baseline.as_deref()) {
+        note(PlanStage::Master, err);
+    }
+
+    let tasks_file = store.tasks_file().display().to_string();
+    let task_check_prompt = subagents::build_task_check_prompt(
+        &tasks_file,
+        &store.project_info_file().display().to_string(),
+        &store.session_meta_file().display().to_string(),
+    );
+    for (stage, agent, prompt) in [
+        (
+            PlanStage::TaskCheck,
+            TasksChangeAgent::TaskCheck,
+            task_check_prompt,
+        ),
+        (
+            PlanStage::DocsAttach,
+            TasksChangeAgent::DocsAttach,
+            app.prepare_attach_docs_prompt(&tasks_file),
+        ),
+    ] {
+        let before = std::fs::read_to_string(store.tasks_file()).ok();
+        if let Err(err) = runner.run(stage, prompt) {
+            note(stage, err);
+        }
+        if let Err(err) = reload_or_restore_tasks(app, store) {
+            note(stage, err);
+        }
+        if let Err(err) = record_tasks_change(store, agent, before.as_deref()) {
+            note(stage, err);
+        }
+    }
+
+    Ok(PlanOutcome {
+        tasks: app.planner_tasks_for_file(),
+        notes,
+    })
+}
+
+fn gather_project_info(
+    store: &SessionStore,
+    cwd: &Path,
+    spec: &str,
+    scope: &ProjectInfoScope,
+    runner: &mut impl PlanStageRunner,
+) -> Result<Option<String>, String> {
+    let prompt = subagents::build_project_info_prompt(
+        &cwd.display().to_string(),
+        spec,
+        &store.project_info_file().display().to_string(),
+        WorkspaceIgnore::load(cwd).metaagent_patterns(),
+        scope,
+    );
+    let transcript = runner.run(PlanStage::ProjectInfo, prompt)?;
+    match store.read_project_info() {
+        Ok(text) if !text.trim().is_empty() => {
+            let Some(trimmed) = scope.fit_to_budget(&text) else {
+                return Ok(Some(text));
+            };
+            store
+                .write_project_info(&trimmed)
+                .map_err(|err| format!("failed to persist trimmed project-info.md: {err}"))?;
+            return Ok(Some(trimmed));
+        }
+        Ok(_) => {}
+        Err(err) => return Err(format!("reading project-info.md failed: {err}")),
+    }
+    if transcript.is_empty() {
+        return Ok(None);
+    }
+    let markdown = transcript.join("\n");
+    let markdown = scope.fit_to_budget(&markdown).unwrap_or(markdown);
+    store
+        .write_project_info(&markdown)
+        .map_err(|err| format!("failed to persist project-info.md: {err}"))?;
+    Ok(Some(markdown))
+}
+
+/// Applies the TUI's post-master rules (docs stripped, auto final audit) and loads the result.
+fn load_master_tasks(
+    app: &mut App,
+    store: &SessionStore,
+    baseline: Option<&str>,
+) -> Result<(), String> {
+    let mut tasks = store
+        .read_tasks()
+        .map_err(|err| format!("reading tasks.json failed: {err}"))?;
+    let sanitized = crate::sanitize_master_docs_fields(&mut tasks, baseline);
+    let appended =
+        crate::append_auto_final_audit_if_needed(app.final_audit_policy(), true, &mut tasks);
+    app.sync_planner_tasks_from_file(tasks.clone())?;
+    if sanitized || appended {
+        store
+            .write_tasks(&tasks)
+            .map_err(|err| format!("writing tasks.json failed: {err}"))?;
+    }
+    Ok(())
+}
+
+fn record_tasks_change(
+    store: &SessionStore,
+    agent: TasksChangeAgent,
+    before: Option<&str>,
+) -> Result<(), String> {
+    let after = std::fs::read_to_string(store.tasks_file()).ok();
+    tasks_changelog::record(
+        &store.tasks_changelog_file(),
+        agent,
+        before,
+        after.as_deref(),
+    )
+    .map(|_| ())
+    .map_err(|err| format!("appending to tasks-changelog.jsonl failed: {err}"))
+}
+
+fn reload_or_restore_tasks(app: &mut App, store: &SessionStore) -> Result<(), String> {
+    let reloaded = store
+        .read_tasks()
+        .map_err(|err| err.to_string())
+        .and_then(|tasks| app.sync_planner_tasks_from_file(tasks));
+    let Err(err) = reloaded else {
+        return Ok(());
+    };
+    store
+        .write_tasks(&app.planner_tasks_for_file())
+        .map_err(|write_err| {
+            format!("tasks.json became invalid ({err}) and restoring it failed: {write_err}")
+        })?;
+    Err(format!(
+        "tasks.json became invalid ({err}); restored the previous plan"
+    ))
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/plan_pipeline_tests.rs"]
+mod tests;
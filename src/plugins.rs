@@ -0,0 +1,414 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::set_running_pid;
+use crate::artifact_io::runtime_storage_dir;
+use crate::i18n::{self, Locale, Text};
+use crate::wakeup;
+
+/// Directory under the runtime storage dir (`~/.agentbob`) that holds plugin executables.
+pub const PLUGINS_DIR_NAME: &str = "plugins";
+/// Version sent in every request; plugins should refuse versions they do not know.
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+const DESCRIBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest a plugin step or command may run before it is killed and counted as failed.
+pub const RUN_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Where a plugin can be invoked from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginCapability {
+    /// `/plugin <name> [input]`.
+    Command,
+    /// A `plugin` task in tasks.json, run after its implementor's audits.
+    Step,
+}
+
+/// A plugin's answer to the `describe` request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub capabilities: Vec<PluginCapability>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    pub manifest: PluginManifest,
+    pub program: PathBuf,
+}
+
+impl Plugin {
+    pub fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    pub fn supports(&self, capability: PluginCapability) -> bool {
+        self.manifest.capabilities.contains(&capability)
+    }
+}
+
+/// Plugins found in the plugins directory at startup, with why any executable was not
+/// loaded.
+#[derive(Debug, Clone, Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Plugin>,
+    load_errors: Vec<String>,
+}
+
+impl PluginRegistry {
+    pub fn plugins_dir() -> io::Result<PathBuf> {
+        Ok(runtime_storage_dir()?.join(PLUGINS_DIR_NAME))
+    }
+
+    /// Asks every executable in `dir` to describe itself. Hidden files and subdirectories
+    /// are ignored; a missing directory means no plugins.
+    pub fn discover(dir: &Path) -> Self {
+        let mut registry = Self::default();
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return registry;
+        };
+        let mut programs = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| !name.starts_with('.'))
+                    && is_executable(path)
+            })
+            .collect::<Vec<_>>();
+        programs.sort();
+        for program in programs {
+            let file_name = program
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            match describe(&program) {
+                Ok(manifest) if !is_valid_name(&manifest.name) => {
+                    registry.load_errors.push(format!(
+                        "{file_name}: plugin name \"{}\" must use lower-case letters, digits, '-' or '_'",
+                        manifest.name
+                    ));
+                }
+                Ok(manifest) if registry.get(&manifest.name).is_some() => {
+                    registry.load_errors.push(format!(
+                        "{file_name}: plugin name \"{}\" is already taken",
+                        manifest.name
+                    ));
+                }
+                Ok(manifest) => registry.plugins.push(Plugin { manifest, program }),
+                Err(err) => registry.load_errors.push(format!("{file_name}: {err}")),
+            }
+        }
+        registry.plugins.sort_by(|a, b| a.name().cmp(b.name()));
+        registry
+    }
+
+    pub fn plugins(&self) -> &[Plugin] {
+        &self.plugins
+    }
+
+    pub fn load_errors(&self) -> &[String] {
+        &self.load_errors
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Plugin> {
+        self.plugins.iter().find(|plugin| plugin.name() == name)
+    }
+
+    /// The plugin named `name` when it can be invoked as `capability`.
+    pub fn get_for(&self, name: &str, capability: PluginCapability) -> Result<&Plugin, String> {
+        let plugin = self
+            .get(name)
+            .ok_or_else(|| format!("No plugin named \"{name}\" is installed."))?;
+        if !plugin.supports(capability) {
+            let role = match capability {
+                PluginCapability::Command => "a command",
+                PluginCapability::Step => "a workflow step",
+            };
+            return Err(format!("Plugin \"{name}\" cannot run as {role}."));
+        }
+        Ok(plugin)
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit())
+        && chars.all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_')
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// The planner task a step invocation runs for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginTask {
+    pub id: String,
+    pub title: String,
+    pub details: String,
+    /// Title of the top-level task the step belongs to.
+    pub top_task_title: String,
+    /// Files the session's workers changed so far.
+    pub changed_files: Vec<String>,
+}
+
+/// The `run` request: what to do, for which task, and where.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginRunRequest {
+    pub invocation: PluginCapability,
+    /// Step: the `plugin` task's details. Command: the text after `/plugin <name>`.
+    pub input: String,
+    pub task: Option<PluginTask>,
+    pub workspace: String,
+    pub session_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginStatus {
+    Pass,
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PluginRunResponse {
+    pub status: PluginStatus,
+    #[serde(default)]
+    pub summary: String,
+    /// Findings shown under the summary; a failed step hands them to the implementor.
+    #[serde(default)]
+    pub details: Vec<String>,
+}
+
+impl PluginRunResponse {
+    pub fn passed(&self) -> bool {
+        self.status == PluginStatus::Pass
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "request", rename_all = "snake_case")]
+enum PluginRequest<'a> {
+    Describe {
+        protocol: u32,
+    },
+    Run {
+        protocol: u32,
+        #[serde(flatten)]
+        run: &'a PluginRunRequest,
+    },
+}
+
+/// Sends the `describe` request and parses the manifest.
+pub fn describe(program: &Path) -> Result<PluginManifest, String> {
+    let request = PluginRequest::Describe {
+        protocol: PLUGIN_PROTOCOL_VERSION,
+    };
+    let response = exchange(
+        program,
+        &request,
+        DESCRIBE_TIMEOUT,
+        &Mutex::new(None),
+        &mut |_| {},
+    )?;
+    serde_json::from_str(&response).map_err(|err| format!("invalid describe response: {err}"))
+}
+
+/// Sends a `run` request, passing every line the plugin prints before its response to
+/// `on_line`. The pid of the running plugin is kept in `running_pid` so it can be stopped.
+pub fn run(
+    program: &Path,
+    request: &PluginRunRequest,
+    running_pid: &Mutex<Option<u32>>,
+    on_line: &mut dyn FnMut(String),
+) -> Result<PluginRunResponse, String> {
+    let request = PluginRequest::Run {
+        protocol: PLUGIN_PROTOCOL_VERSION,
+        run: request,
+    };
+    let response = exchange(program, &request, RUN_TIMEOUT, running_pid, on_line)?;
+    serde_json::from_str(&response).map_err(|err| format!("invalid run response: {err}"))
+}
+
+/// Writes `request` as one JSON line to the plugin's stdin and returns the last non-empty
+/// line it prints to stdout. Earlier stdout lines and all of stderr go to `on_line`.
+fn exchange(
+    program: &Path,
+    request: &PluginRequest<'_>,
+    timeout: Duration,
+    running_pid: &Mutex<Option<u32>>,
+    on_line: &mut dyn FnMut(String),
+) -> Result<String, String> {
+    let mut command = Command::new(program);
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let PluginRequest::Run { run, .. } = request
+        && !run.workspace.is_empty()
+    {
+        command.current_dir(&run.workspace);
+    }
+    let request = serde_json::to_string(request).map_err(|err| err.to_string())?;
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("failed to start: {err}"))?;
+    set_running_pid(running_pid, Some(child.id()));
+    if let Some(mut stdin) = child.stdin.take() {
+        // A plugin that exits without reading its request is reported by its exit status.
+        let _ = writeln!(stdin, "{request}");
+    }
+    let (line_tx, line_rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        spawn_line_reader(stdout, true, line_tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_line_reader(stderr, false, line_tx);
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut response = None::<String>;
+    loop {
+        let wait = deadline.saturating_duration_since(Instant::now());
+        match line_rx.recv_timeout(wait) {
+            Ok((true, line)) if line.trim().is_empty() => {}
+            Ok((true, line)) => {
+                if let Some(previous) = response.replace(line) {
+                    on_line(previous);
+                }
+            }
+            Ok((false, line)) => on_line(line),
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                set_running_pid(running_pid, None);
+                // Descendants may still hold the pipes open, so the readers are left detached.
+                return Err(format!("timed out after {}s", timeout.as_secs()));
+            }
+        }
+    }
+    let status = child.wait().map_err(|err| err.to_string());
+    set_running_pid(running_pid, None);
+    let status = status?;
+    match response {
+        Some(response) if status.success() => Ok(response),
+        Some(response) => {
+            on_line(response);
+            Err(exit_error(status.code()))
+        }
+        None if status.success() => Err("printed no response".to_string()),
+        None => Err(exit_error(status.code())),
+    }
+}
+
+fn exit_error(code: Option<i32>) -> String {
+    match code {
+        Some(code) => format!("exited with status code {code}"),
+        None => "was terminated".to_string(),
+    }
+}
+
+fn spawn_line_reader<R: io::Read + Send + 'static>(
+    reader: R,
+    stdout: bool,
+    tx: Sender<(bool, String)>,
+) {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            if tx.send((stdout, line)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Runs `/plugin` commands in the background and collects their chat messages.
+#[derive(Debug)]
+pub struct PluginCommands {
+    results_tx: wakeup::Sender<String>,
+    results_rx: Receiver<String>,
+}
+
+impl Default for PluginCommands {
+    fn default() -> Self {
+        let (results_tx, results_rx) = wakeup::channel();
+        Self {
+            results_tx,
+            results_rx,
+        }
+    }
+}
+
+impl PluginCommands {
+    pub fn start(&self, plugin: &Plugin, request: PluginRunRequest, locale: Locale) {
+        let name = plugin.name().to_string();
+        let program = plugin.program.clone();
+        let results_tx = self.results_tx.clone();
+        thread::spawn(move || {
+            let running_pid = Mutex::new(None);
+            let mut on_line = |line: String| {
+                let _ = results_tx.send(i18n::system(
+                    locale,
+                    Text::PluginOutputLine,
+                    &[("name", &name), ("line", &line)],
+                ));
+            };
+            let message = match run(&program, &request, &running_pid, &mut on_line) {
+                Ok(response) => format!("System: {}", response_message(locale, &name, &response)),
+                Err(err) => i18n::system(
+                    locale,
+                    Text::PluginFailed,
+                    &[("name", &name), ("err", &err)],
+                ),
+            };
+            let _ = results_tx.send(message);
+        });
+    }
+
+    pub fn drain_messages(&self) -> Vec<String> {
+        self.results_rx.try_iter().collect()
+    }
+}
+
+/// Text for a finished plugin run: its verdict and summary, then one line per detail.
+pub fn response_message(locale: Locale, name: &str, response: &PluginRunResponse) -> String {
+    let summary = response.summary.trim();
+    let key = match (response.passed(), summary.is_empty()) {
+        (true, true) => Text::PluginPassed,
+        (true, false) => Text::PluginPassedSummary,
+        (false, true) => Text::PluginFailedVerdict,
+        (false, false) => Text::PluginFailedSummary,
+    };
+    let mut message = i18n::fill(locale, key, &[("name", &name), ("summary", &summary)]);
+    for detail in &response.details {
+        message.push_str(&format!("\n- {detail}"));
+    }
+    message
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/plugins_tests.rs"]
+mod tests;
@@ -0,0 +1,122 @@
+use serde::Deserialize;
+
+use crate::artifact_io::is_workspace_subdir;
+use crate::prompt_budget::estimated_tokens;
+
+/// Files fast mode lets the project-info agent read: package manifests, build files, and the
+/// top-level README.
+const MANIFEST_FILES: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "requirements.txt",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "Gemfile",
+    "composer.json",
+    "Makefile",
+    "CMakeLists.txt",
+    "README.md",
+];
+
+/// `[project_info]` in config.toml: how far the project-info agent explores the workspace
+/// before the first master prompt. Zero `max_depth` or `max_tokens` means no limit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectInfoScope {
+    /// Directory levels below the workspace root the agent may list or read.
+    pub max_depth: usize,
+    /// Workspace-relative directories to explore; empty explores the whole workspace.
+    pub include: Vec<String>,
+    /// Workspace-relative directories the agent must not explore.
+    pub exclude: Vec<String>,
+    /// Token budget for project-info.md; a longer brief is cut to fit.
+    pub max_tokens: usize,
+    /// Read only manifest files instead of exploring the source tree.
+    pub fast: bool,
+}
+
+impl ProjectInfoScope {
+    /// The first include or exclude entry that is not a directory inside the workspace.
+    pub fn invalid_directory(&self) -> Option<&str> {
+        self.include
+            .iter()
+            .chain(&self.exclude)
+            .map(String::as_str)
+            .find(|dir| !is_workspace_subdir(dir))
+    }
+
+    /// Requirement lines for the project-info prompt, each ending in a newline; empty when
+    /// the scope limits nothing.
+    pub fn prompt_requirements(&self) -> String {
+        let mut lines = String::new();
+        if self.fast {
+            lines.push_str(&format!(
+                "- Fast mode: read only these files where they exist, plus the top-level directory listing; do not open source files, and state unknown for anything they do not answer: {}\n",
+                backticked(MANIFEST_FILES.iter().copied())
+            ));
+        }
+        if self.max_depth > 0 {
+            lines.push_str(&format!(
+                "- Do not list or read anything more than {} directory level(s) below the repository root.\n",
+                self.max_depth
+            ));
+        }
+        if !self.include.is_empty() {
+            lines.push_str(&format!(
+                "- Only explore these directories: {}\n",
+                backticked(self.include.iter().map(String::as_str))
+            ));
+        }
+        if !self.exclude.is_empty() {
+            lines.push_str(&format!(
+                "- Do not explore these directories: {}\n",
+                backticked(self.exclude.iter().map(String::as_str))
+            ));
+        }
+        if self.max_tokens > 0 {
+            lines.push_str(&format!(
+                "- Keep the brief under about {} tokens; it is cut at that length.\n",
+                self.max_tokens
+            ));
+        }
+        lines
+    }
+
+    /// `markdown` cut after the last whole line within `max_tokens`, with a note saying so;
+    /// `None` when it already fits or there is no budget.
+    pub fn fit_to_budget(&self, markdown: &str) -> Option<String> {
+        if self.max_tokens == 0 || estimated_tokens(markdown.len()) <= self.max_tokens {
+            return None;
+        }
+        let note = format!(
+            "_Trimmed to the project_info.max_tokens budget of {} tokens._",
+            self.max_tokens
+        );
+        let mut kept = String::new();
+        for line in markdown.lines() {
+            if estimated_tokens(kept.len() + line.len() + 1 + note.len()) > self.max_tokens {
+                break;
+            }
+            kept.push_str(line);
+            kept.push('\n');
+        }
+        kept.push_str(&note);
+        kept.push('\n');
+        Some(kept)
+    }
+}
+
+fn backticked<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    items
+        .map(|item| format!("`{item}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/project_info_scope_tests.rs"]
+mod tests;
@@ -0,0 +1,161 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::artifact_io::{read_text_file, runtime_storage_dir};
+use crate::code_index::fnv1a;
+use crate::session_store::SessionStore;
+
+/// Marks the block of durable facts the master appends to the project memory.
+pub const MEMORY_BEGIN: &str = "PROJECT_MEMORY_BEGIN";
+pub const MEMORY_END: &str = "PROJECT_MEMORY_END";
+/// Directory under the runtime storage dir holding one memory file per workspace.
+const MEMORY_DIR_NAME: &str = "memory";
+/// Longest memory injected into a session intro; older facts are cut first.
+const MAX_MEMORY_PROMPT_CHARS: usize = 12_000;
+
+/// `<storage>/memory/<workspace-hash>.md`; the hash keeps one file per workspace path.
+pub fn memory_file_in(storage_dir: &Path, workspace: &Path) -> PathBuf {
+    let hash = fnv1a(workspace.to_string_lossy().as_bytes());
+    storage_dir
+        .join(MEMORY_DIR_NAME)
+        .join(format!("{hash:016x}.md"))
+}
+
+/// Memory file for the workspace the session was created in.
+pub fn memory_file(store: &SessionStore) -> io::Result<PathBuf> {
+    Ok(memory_file_in(
+        &runtime_storage_dir()?,
+        &session_workspace(store)?,
+    ))
+}
+
+/// Appends `facts` to the session workspace's memory; returns the file and how many facts
+/// were new.
+pub fn record_session_facts(
+    store: &SessionStore,
+    facts: &[String],
+) -> io::Result<(PathBuf, usize)> {
+    let workspace = session_workspace(store)?;
+    let path = memory_file_in(&runtime_storage_dir()?, &workspace);
+    let added = append_memory_facts(&path, &workspace, facts)?;
+    Ok((path, added))
+}
+
+fn session_workspace(store: &SessionStore) -> io::Result<PathBuf> {
+    store
+        .workspace_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "session has no recorded workspace"))
+}
+
+/// Facts recorded so far, one per line, or `None` when there are none.
+pub fn read_memory_facts(path: &Path) -> Option<Vec<String>> {
+    let text = read_text_file(path).ok()?;
+    let facts = text
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("- "))
+        .map(|fact| fact.trim().to_string())
+        .filter(|fact| !fact.is_empty())
+        .collect::<Vec<_>>();
+    (!facts.is_empty()).then_some(facts)
+}
+
+/// Every fact in the `PROJECT_MEMORY_BEGIN`/`PROJECT_MEMORY_END` blocks of a master reply.
+pub fn extract_memory_facts(text: &str) -> Vec<String> {
+    let mut facts = Vec::new();
+    let mut in_block = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line == MEMORY_BEGIN {
+            in_block = true;
+        } else if line == MEMORY_END {
+            in_block = false;
+        } else if in_block {
+            let fact = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .unwrap_or(line)
+                .trim();
+            if !fact.is_empty() {
+                facts.push(fact.to_string());
+            }
+        }
+    }
+    facts
+}
+
+/// Appends the facts that are not in the file yet, creating it with a header for
+/// `workspace`. Returns how many were added.
+pub fn append_memory_facts(path: &Path, workspace: &Path, facts: &[String]) -> io::Result<usize> {
+    let mut known = read_memory_facts(path)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|fact| fact.to_lowercase())
+        .collect::<Vec<_>>();
+    let mut text = String::new();
+    for fact in facts {
+        let fact = fact.split_whitespace().collect::<Vec<_>>().join(" ");
+        if fact.is_empty() || known.contains(&fact.to_lowercase()) {
+            continue;
+        }
+        text.push_str(&format!("- {fact}\n"));
+        known.push(fact.to_lowercase());
+    }
+    if text.is_empty() {
+        return Ok(0);
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(
+            file,
+            "# Project memory\n\nWorkspace: {}\n",
+            workspace.display()
+        )?;
+    }
+    file.write_all(text.as_bytes())?;
+    Ok(text.lines().count())
+}
+
+/// Session intro section with the recorded facts and how to add more.
+pub fn memory_prompt_section(path: &Path) -> String {
+    let mut out = String::from("Project memory (shared by every session in this workspace):\n");
+    match read_memory_facts(path) {
+        Some(facts) => {
+            let mut lines = Vec::new();
+            let mut used = 0;
+            for fact in facts.iter().rev() {
+                used += fact.len() + 3;
+                if used > MAX_MEMORY_PROMPT_CHARS {
+                    lines.push("- (older facts omitted)".to_string());
+                    break;
+                }
+                lines.push(format!("- {fact}"));
+            }
+            lines.reverse();
+            out.push_str(&lines.join("\n"));
+        }
+        None => out.push_str("(no facts recorded yet)"),
+    }
+    out.push_str(&format!(
+        "\nTo remember a durable fact for future sessions (an architecture decision, a \
+         convention, a gotcha), end your reply with one fact per line between \
+         {MEMORY_BEGIN} and {MEMORY_END} lines. Record only facts that stay true beyond this \
+         session; do not repeat facts listed above."
+    ));
+    out
+}
+
+/// [`memory_prompt_section`] for the session's workspace, or `None` when its memory file
+/// cannot be located.
+pub fn session_memory_section(store: &SessionStore) -> Option<String> {
+    memory_file(store)
+        .ok()
+        .map(|path| memory_prompt_section(&path))
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/project_memory_tests.rs"]
+mod tests;
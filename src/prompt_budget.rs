@@ -0,0 +1,120 @@
+use crate::agent::compact_count;
+
+/// Rough bytes-per-token ratio for English prose and code; good enough to spot outliers.
+const BYTES_PER_TOKEN: usize = 4;
+/// A section above this share of the prompt is reported as dominating it.
+const DOMINANT_SHARE_PERCENT: usize = 60;
+/// Prompts smaller than this are cheap enough that no section is worth warning about.
+const DOMINANCE_MIN_PROMPT_BYTES: usize = 8 * 1024;
+/// Name of the section covering whatever the measured sections leave over.
+pub const REMAINDER_SECTION: &str = "task";
+
+/// One measured part of a worker prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptSection {
+    pub name: &'static str,
+    pub bytes: usize,
+    /// Why the section is shorter than its source, when something was cut or dropped.
+    pub truncated: Option<String>,
+}
+
+impl PromptSection {
+    pub fn new(name: &'static str, text: &str) -> Self {
+        Self {
+            name,
+            bytes: text.len(),
+            truncated: None,
+        }
+    }
+
+    pub fn truncated(mut self, reason: impl Into<String>) -> Self {
+        self.truncated = Some(reason.into());
+        self
+    }
+}
+
+/// Size breakdown of a worker prompt, with the bytes no section claims folded into
+/// [`REMAINDER_SECTION`] (task titles, details, and fixed instructions).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptBudget {
+    pub total_bytes: usize,
+    pub sections: Vec<PromptSection>,
+}
+
+impl PromptBudget {
+    pub fn new(prompt: &str, mut sections: Vec<PromptSection>) -> Self {
+        let measured = sections.iter().map(|section| section.bytes).sum::<usize>();
+        sections.push(PromptSection {
+            name: REMAINDER_SECTION,
+            bytes: prompt.len().saturating_sub(measured),
+            truncated: None,
+        });
+        Self {
+            total_bytes: prompt.len(),
+            sections,
+        }
+    }
+
+    /// `[prompt] Implementor pass 2: 14.2k bytes (~3.6k tokens) | docs 9.1k (64%) | ...`
+    pub fn summary_line(&self, job_label: &str) -> String {
+        let mut parts = vec![format!(
+            "[prompt] {job_label}: {} bytes (~{} tokens)",
+            compact_count(self.total_bytes as u64),
+            compact_count(estimated_tokens(self.total_bytes) as u64)
+        )];
+        parts.extend(self.sections.iter().map(|section| {
+            format!(
+                "{} {} ({}%)",
+                section.name,
+                compact_count(section.bytes as u64),
+                self.share_percent(section)
+            )
+        }));
+        parts.join(" | ")
+    }
+
+    /// One line per truncated section and per section that dominates a large prompt.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = self
+            .sections
+            .iter()
+            .filter_map(|section| {
+                let reason = section.truncated.as_deref()?;
+                Some(format!(
+                    "[prompt] warning: {} section was truncated: {reason}",
+                    section.name
+                ))
+            })
+            .collect::<Vec<_>>();
+        if self.total_bytes >= DOMINANCE_MIN_PROMPT_BYTES {
+            warnings.extend(
+                self.sections
+                    .iter()
+                    .filter(|section| self.share_percent(section) > DOMINANT_SHARE_PERCENT)
+                    .map(|section| {
+                        format!(
+                            "[prompt] warning: {} section is {}% of the prompt (~{} tokens)",
+                            section.name,
+                            self.share_percent(section),
+                            compact_count(estimated_tokens(section.bytes) as u64)
+                        )
+                    }),
+            );
+        }
+        warnings
+    }
+
+    fn share_percent(&self, section: &PromptSection) -> usize {
+        (section.bytes * 100)
+            .checked_div(self.total_bytes)
+            .unwrap_or_default()
+    }
+}
+
+pub fn estimated_tokens(bytes: usize) -> usize {
+    bytes.div_ceil(BYTES_PER_TOKEN)
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/prompt_budget_tests.rs"]
+mod tests;
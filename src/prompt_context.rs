@@ -0,0 +1,85 @@
+use std::collections::BTreeSet;
+
+/// A part of the master prompt that `/context` can leave out of the next prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContextSection {
+    /// project-info.md, sent with the session intro and referenced by planner prompts.
+    ProjectInfo,
+    RollingContext,
+    TaskTree,
+    /// The planner-mode instruction to read planner.md before replying.
+    Planner,
+}
+
+impl ContextSection {
+    pub const ALL: [ContextSection; 4] = [
+        ContextSection::ProjectInfo,
+        ContextSection::RollingContext,
+        ContextSection::TaskTree,
+        ContextSection::Planner,
+    ];
+
+    /// The word `/context <section>` accepts.
+    pub fn key(self) -> &'static str {
+        match self {
+            ContextSection::ProjectInfo => "project",
+            ContextSection::RollingContext => "rolling",
+            ContextSection::TaskTree => "tasks",
+            ContextSection::Planner => "planner",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ContextSection::ProjectInfo => "project info (project-info.md)",
+            ContextSection::RollingContext => "rolling task context",
+            ContextSection::TaskTree => "full task tree",
+            ContextSection::Planner => "planner.md (planner mode only)",
+        }
+    }
+
+    pub fn parse(word: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|section| section.key().eq_ignore_ascii_case(word))
+    }
+}
+
+/// Stands in for a section the user left out, so the master knows it was dropped on purpose.
+pub const OMITTED_SECTION: &str = "(left out of this prompt by the user via /context)";
+
+/// Which sections go into the next master prompt. Everything is included by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptSections {
+    excluded: BTreeSet<ContextSection>,
+}
+
+impl PromptSections {
+    pub fn includes(&self, section: ContextSection) -> bool {
+        !self.excluded.contains(&section)
+    }
+
+    /// Flips one section and returns whether it is now included.
+    pub fn toggle(&mut self, section: ContextSection) -> bool {
+        if self.excluded.remove(&section) {
+            return true;
+        }
+        self.excluded.insert(section);
+        false
+    }
+
+    /// One checkbox line per section, for the `/context` panel.
+    pub fn render_lines(&self) -> Vec<String> {
+        ContextSection::ALL
+            .into_iter()
+            .map(|section| {
+                let mark = if self.includes(section) { "x" } else { " " };
+                format!("[{mark}] {:<8} {}", section.key(), section.label())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/prompt_context_tests.rs"]
+mod tests;
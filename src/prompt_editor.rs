@@ -0,0 +1,119 @@
+use crate::text_layout::{char_to_byte_idx, nearest_index_for_line_col, wrap_word_with_positions};
+use crate::workflow::LastWorkerPrompt;
+
+/// Multi-line editor `/rerun` opens on a task's last worker prompt. Cursor movement follows
+/// the planner editor: up and down keep the column they started from across short lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptEditor {
+    task_id: String,
+    label: String,
+    text: String,
+    /// Character index into `text`.
+    cursor: usize,
+    goal_col: Option<u16>,
+    scroll: u16,
+}
+
+impl PromptEditor {
+    pub fn new(prompt: LastWorkerPrompt) -> Self {
+        Self {
+            task_id: prompt.task_id,
+            label: prompt.label,
+            text: prompt.prompt,
+            cursor: 0,
+            goal_col: None,
+            scroll: 0,
+        }
+    }
+
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn scroll(&self) -> u16 {
+        self.scroll
+    }
+
+    pub fn insert_text(&mut self, text: &str) {
+        let byte_idx = char_to_byte_idx(&self.text, self.cursor);
+        self.text.insert_str(byte_idx, text);
+        self.cursor += text.chars().count();
+        self.goal_col = None;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        let start = char_to_byte_idx(&self.text, self.cursor - 1);
+        let end = char_to_byte_idx(&self.text, self.cursor);
+        self.text.drain(start..end);
+        self.cursor -= 1;
+        self.goal_col = None;
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+        self.goal_col = None;
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.text.chars().count());
+        self.goal_col = None;
+    }
+
+    pub fn move_up(&mut self, width: u16) {
+        let positions = wrap_word_with_positions(&self.text, width.max(1)).positions;
+        let (line, col) = positions[self.cursor];
+        if line == 0 {
+            return;
+        }
+        let goal_col = self.goal_col.unwrap_or(col);
+        self.cursor = nearest_index_for_line_col(&positions, line - 1, goal_col);
+        self.goal_col = Some(goal_col);
+    }
+
+    pub fn move_down(&mut self, width: u16) {
+        let wrapped = wrap_word_with_positions(&self.text, width.max(1));
+        let (line, col) = wrapped.positions[self.cursor];
+        if line + 1 >= wrapped.line_count {
+            return;
+        }
+        let goal_col = self.goal_col.unwrap_or(col);
+        self.cursor = nearest_index_for_line_col(&wrapped.positions, line + 1, goal_col);
+        self.goal_col = Some(goal_col);
+    }
+
+    /// Wrapped line and column of the cursor at `width`.
+    pub fn cursor_line_col(&self, width: u16) -> (u16, u16) {
+        wrap_word_with_positions(&self.text, width.max(1)).positions[self.cursor]
+    }
+
+    /// Scrolls just enough to keep the cursor line among `visible_lines` rows.
+    pub fn ensure_cursor_visible(&mut self, width: u16, visible_lines: u16) {
+        let visible_lines = visible_lines.max(1);
+        let (line, _) = self.cursor_line_col(width);
+        if line < self.scroll {
+            self.scroll = line;
+        } else if line >= self.scroll + visible_lines {
+            self.scroll = line + 1 - visible_lines;
+        }
+    }
+
+    /// The task id and the edited prompt.
+    pub fn into_parts(self) -> (String, String) {
+        (self.task_id, self.text)
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/prompt_editor_tests.rs"]
+mod tests;
@@ -0,0 +1,426 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::app::App;
+use crate::project_info_scope::ProjectInfoScope;
+use crate::session_store::{
+    PlannerTaskDocFileEntry, PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile,
+    TaskFailFileEntry,
+};
+use crate::subagents;
+
+const GOLDEN_EXTENSION: &str = "txt";
+// Placeholder arguments keep snapshots independent of the machine and session they run in.
+const TASKS_FILE: &str = "<session>/tasks.json";
+const PLANNER_FILE: &str = "<session>/planner.md";
+const PROJECT_INFO_FILE: &str = "<session>/project-info.md";
+const SESSION_META_FILE: &str = "<session>/meta.json";
+const WORKER_OUTPUT: &str = "<worker report under audit>";
+const CHANGED_FILES: &str = "- src/lib.rs: <changed-files summary>";
+
+/// A named task tree that worker and master prompts are rendered against.
+#[derive(Debug, Clone)]
+pub struct PromptFixture {
+    pub name: String,
+    pub tasks: Vec<PlannerTaskFileEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptSnapshot {
+    /// Path of the golden file relative to the golden directory, without extension.
+    pub name: String,
+    pub prompt: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnapshotDiff {
+    pub name: String,
+    /// Changed lines only, prefixed with `-` (golden) or `+` (rendered).
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SnapshotReport {
+    pub matched: Vec<String>,
+    pub changed: Vec<SnapshotDiff>,
+    /// Rendered prompts with no golden file yet.
+    pub missing: Vec<String>,
+    /// Golden files no prompt was rendered for.
+    pub stale: Vec<String>,
+    pub updated: bool,
+}
+
+impl SnapshotReport {
+    pub fn is_clean(&self) -> bool {
+        self.updated
+            || (self.changed.is_empty() && self.missing.is_empty() && self.stale.is_empty())
+    }
+}
+
+/// The fixture used when no fixtures directory is given: one top-level task exercising every
+/// agent-backed task kind, task docs, and a final audit.
+pub fn builtin_fixtures() -> Vec<PromptFixture> {
+    let task = |id: &str, title: &str, details: &str, kind, parent_id: Option<&str>| {
+        PlannerTaskFileEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            details: details.to_string(),
+            docs: Vec::new(),
+            kind,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: parent_id.map(str::to_string),
+            order: Some(0),
+            ..Default::default()
+        }
+    };
+    let mut implementor = task(
+        "export-impl",
+        "Implement CSV export",
+        "Add `export_csv` to src/export.rs writing one row per record.",
+        PlannerTaskKindFile::Implementor,
+        Some("export"),
+    );
+    implementor.docs.push(PlannerTaskDocFileEntry {
+        title: "csv crate".to_string(),
+        url: "https://docs.rs/csv".to_string(),
+        summary: "Writer API for CSV output.".to_string(),
+        snapshot: None,
+    });
+    let mut final_audit = task(
+        "final-audit",
+        "Final audit",
+        "Review the export feature end to end.",
+        PlannerTaskKindFile::FinalAudit,
+        None,
+    );
+    final_audit.order = Some(1);
+    vec![PromptFixture {
+        name: "export".to_string(),
+        tasks: vec![
+            task(
+                "export",
+                "CSV export",
+                "Let users export records as CSV.",
+                PlannerTaskKindFile::Task,
+                None,
+            ),
+            implementor,
+            task(
+                "export-audit",
+                "Audit CSV export",
+                "Check quoting, headers, and empty input.",
+                PlannerTaskKindFile::Auditor,
+                Some("export-impl"),
+            ),
+            task(
+                "export-tests",
+                "Test CSV export",
+                "Cover quoting and empty input in tests/export.rs.",
+                PlannerTaskKindFile::TestWriter,
+                Some("export"),
+            ),
+            task(
+                "export-tests-audit",
+                "Audit CSV export tests",
+                "Check the tests fail without the implementation.",
+                PlannerTaskKindFile::Auditor,
+                Some("export-tests"),
+            ),
+            task(
+                "export-tests-run",
+                "Run CSV export tests",
+                "Run the test suite.",
+                PlannerTaskKindFile::TestRunner,
+                Some("export-tests"),
+            ),
+            final_audit,
+        ],
+    }]
+}
+
+/// Loads every `*.json` task file in `dir` as a fixture named after the file stem.
+pub fn load_fixtures(dir: &Path) -> io::Result<Vec<PromptFixture>> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let text = fs::read_to_string(&path)?;
+            let tasks = serde_json::from_str(&text).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {err}", path.display()),
+                )
+            })?;
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Ok(PromptFixture { name, tasks })
+        })
+        .collect()
+}
+
+/// Renders every subagent prompt once, and the master, planner, and worker prompts for each
+/// fixture with tests mode on and off. Snapshots are sorted by name.
+pub fn render_prompt_snapshots(fixtures: &[PromptFixture]) -> Result<Vec<PromptSnapshot>, String> {
+    let mut snapshots = subagent_snapshots();
+    for fixture in fixtures {
+        for tests_mode in [true, false] {
+            let mut app = App::default();
+            app.set_tests_mode_enabled(tests_mode);
+            app.sync_planner_tasks_from_file(fixture.tasks.clone())
+                .map_err(|err| format!("Fixture `{}` is invalid: {err}", fixture.name))?;
+            let prefix = format!(
+                "{}/tests-{}",
+                fixture.name,
+                if tests_mode { "on" } else { "off" }
+            );
+            snapshots.push(PromptSnapshot {
+                name: format!("{prefix}/master"),
+                prompt: app.prepare_master_prompt("Add a CSV export.", TASKS_FILE),
+            });
+            snapshots.push(PromptSnapshot {
+                name: format!("{prefix}/planner"),
+                prompt: app.prepare_planner_prompt(
+                    "Add a CSV export.",
+                    PLANNER_FILE,
+                    PROJECT_INFO_FILE,
+                ),
+            });
+            for task in &fixture.tasks {
+                if matches!(
+                    task.kind,
+                    PlannerTaskKindFile::Task | PlannerTaskKindFile::TestRunner
+                ) {
+                    continue;
+                }
+                let prompt = app.worker_prompt_for_planner_task(
+                    &task.id,
+                    Some(WORKER_OUTPUT.to_string()),
+                    Some(CHANGED_FILES.to_string()),
+                )?;
+                snapshots.push(PromptSnapshot {
+                    name: format!("{prefix}/{}", task.id),
+                    prompt,
+                });
+            }
+        }
+    }
+    snapshots.sort_by(|left, right| left.name.cmp(&right.name));
+    Ok(snapshots)
+}
+
+fn subagent_snapshots() -> Vec<PromptSnapshot> {
+    let failures = [TaskFailFileEntry {
+        kind: "test".to_string(),
+        top_task_id: 1,
+        top_task_title: "CSV export".to_string(),
+        attempts: 3,
+        reason: "tests kept failing".to_string(),
+        action_taken: "skipped".to_string(),
+        created_at_epoch_secs: 0,
+    }];
+    let oversized = [subagents::task_split::OversizedTask {
+        id: "export".to_string(),
+        title: "CSV export".to_string(),
+        reason: "9 subtasks (limit 8)".to_string(),
+    }];
+    let app = App::default();
+    [
+        ("attach-docs", app.prepare_attach_docs_prompt(TASKS_FILE)),
+        (
+            "context-report",
+            app.prepare_context_report_prompt(&["Implementor finished CSV export.".to_string()]),
+        ),
+        (
+            "convert-plan",
+            subagents::build_convert_plan_prompt(PLANNER_FILE, TASKS_FILE),
+        ),
+        (
+            "failure-report",
+            subagents::build_failure_report_prompt("<session>/task-fails.json", &failures, true),
+        ),
+        ("merge-audits", subagents::merge_audits_command_prompt()),
+        (
+            "project-info",
+            subagents::build_project_info_prompt(
+                "<cwd>",
+                "Add a CSV export.",
+                PROJECT_INFO_FILE,
+                &[],
+                &ProjectInfoScope::default(),
+            ),
+        ),
+        (
+            "session-intro",
+            subagents::build_session_intro_if_needed(
+                "<master prompt>",
+                "<session>",
+                SESSION_META_FILE,
+                Some("# Project\nA Rust CLI."),
+                None,
+                None,
+                &mut true,
+            ),
+        ),
+        (
+            "session-meta",
+            subagents::build_session_meta_prompt("Add a CSV export.", SESSION_META_FILE),
+        ),
+        ("split-audits", subagents::split_audits_command_prompt()),
+        (
+            "task-summary",
+            subagents::build_task_summary_prompt(
+                "export",
+                "CSV export",
+                &["<session>/logs/export/implementor-pass2.log".to_string()],
+            ),
+        ),
+        (
+            "task-check",
+            subagents::build_task_check_prompt(TASKS_FILE, PROJECT_INFO_FILE, SESSION_META_FILE),
+        ),
+        (
+            "task-split",
+            subagents::build_task_split_prompt(
+                TASKS_FILE,
+                "<session>/task-split.json",
+                PROJECT_INFO_FILE,
+                &oversized,
+            ),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, prompt)| PromptSnapshot {
+        name: format!("subagents/{name}"),
+        prompt,
+    })
+    .collect()
+}
+
+/// Compares snapshots with `<golden_dir>/<name>.txt`. With `update`, rewrites changed and
+/// missing golden files and deletes stale ones instead of only reporting them.
+pub fn compare_with_golden(
+    snapshots: &[PromptSnapshot],
+    golden_dir: &Path,
+    update: bool,
+) -> io::Result<SnapshotReport> {
+    let mut report = SnapshotReport {
+        updated: update,
+        ..SnapshotReport::default()
+    };
+    let mut rendered = BTreeSet::new();
+    for snapshot in snapshots {
+        rendered.insert(snapshot.name.clone());
+        let path = golden_path(golden_dir, &snapshot.name);
+        match fs::read_to_string(&path) {
+            Ok(golden) if golden == snapshot.prompt => {
+                report.matched.push(snapshot.name.clone());
+                continue;
+            }
+            Ok(golden) => report.changed.push(SnapshotDiff {
+                name: snapshot.name.clone(),
+                lines: diff_lines(&golden, &snapshot.prompt),
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                report.missing.push(snapshot.name.clone())
+            }
+            Err(err) => return Err(err),
+        }
+        if update {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, &snapshot.prompt)?;
+        }
+    }
+    for name in golden_names(golden_dir)? {
+        if rendered.contains(&name) {
+            continue;
+        }
+        if update {
+            fs::remove_file(golden_path(golden_dir, &name))?;
+        }
+        report.stale.push(name);
+    }
+    Ok(report)
+}
+
+fn golden_path(golden_dir: &Path, name: &str) -> PathBuf {
+    golden_dir.join(format!("{name}.{GOLDEN_EXTENSION}"))
+}
+
+fn golden_names(golden_dir: &Path) -> io::Result<Vec<String>> {
+    let mut names = Vec::new();
+    let mut pending = vec![golden_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == GOLDEN_EXTENSION)
+                && let Ok(relative) = path.with_extension("").strip_prefix(golden_dir)
+            {
+                let parts = relative
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>();
+                names.push(parts.join("/"));
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Line diff via longest common subsequence; prompts are small enough for the quadratic table.
+fn diff_lines(golden: &str, rendered: &str) -> Vec<String> {
+    let old = golden.lines().collect::<Vec<_>>();
+    let new = rendered.lines().collect::<Vec<_>>();
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || common[i][j + 1] >= common[i + 1][j]) {
+            lines.push(format!("+{}", new[j]));
+            j += 1;
+        } else {
+            lines.push(format!("-{}", old[i]));
+            i += 1;
+        }
+    }
+    if lines.is_empty() {
+        // Same lines, so the difference is line endings or a trailing newline.
+        lines.push("~line endings differ".to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/prompt_snapshots_tests.rs"]
+mod tests;
@@ -0,0 +1,104 @@
+/// Globs from `[workflow] protected_paths` naming files workers may not modify.
+///
+/// `**` matches any number of path segments, `*` and `?` stay within one segment, and a
+/// pattern without a `/` matches a file or directory name at any depth, so `*.lock`
+/// covers `Cargo.lock` and `web/yarn.lock`. A trailing `/` protects everything below.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtectedPaths {
+    patterns: Vec<String>,
+}
+
+/// A changed path together with the first pattern that protects it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub path: String,
+    pub pattern: String,
+}
+
+impl ProtectedPaths {
+    pub fn new(patterns: impl IntoIterator<Item = String>) -> Self {
+        let patterns = patterns
+            .into_iter()
+            .map(|pattern| {
+                let pattern = normalize(&pattern);
+                match pattern.strip_suffix('/') {
+                    Some(dir) => format!("{dir}/**"),
+                    None => pattern,
+                }
+            })
+            .filter(|pattern| !pattern.is_empty())
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Protected entries of `paths`, deduplicated and in the order given.
+    pub fn violations<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = Vec::new();
+        for path in paths {
+            let path = normalize(path);
+            if violations.iter().any(|violation| violation.path == path) {
+                continue;
+            }
+            if let Some(pattern) = self.patterns.iter().find(|pattern| matches(pattern, &path)) {
+                violations.push(Violation {
+                    path,
+                    pattern: pattern.clone(),
+                });
+            }
+        }
+        violations
+    }
+}
+
+fn normalize(path: &str) -> String {
+    let path = path.trim().replace('\\', "/");
+    path.trim_start_matches("./")
+        .trim_start_matches('/')
+        .to_string()
+}
+
+fn matches(pattern: &str, path: &str) -> bool {
+    let path = path.split('/').collect::<Vec<_>>();
+    if pattern.contains('/') {
+        let pattern = pattern.split('/').collect::<Vec<_>>();
+        return segments_match(&pattern, &path);
+    }
+    path.iter().any(|segment| segment_matches(pattern, segment))
+}
+
+pub(crate) fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path_rest)| {
+            segment_matches(segment, name) && segments_match(rest, path_rest)
+        }),
+    }
+}
+
+pub(crate) fn segment_matches(pattern: &str, name: &str) -> bool {
+    fn chars_match(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| chars_match(rest, &name[skip..])),
+            Some((&expected, rest)) => name.split_first().is_some_and(|(&actual, name_rest)| {
+                (expected == '?' || expected == actual) && chars_match(rest, name_rest)
+            }),
+        }
+    }
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    chars_match(&pattern, &name)
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/protected_paths_tests.rs"]
+mod tests;
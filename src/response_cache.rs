@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::artifact_io::write_text_file_atomic;
+use crate::audit_trail::sha256_hex;
+
+/// Directory under the runtime storage dir that holds cached responses.
+pub const RESPONSE_CACHE_DIR_NAME: &str = "response-cache";
+
+/// Sub-agent calls whose result only depends on their inputs, so a repeat can be answered
+/// from the cache instead of the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CachedCall {
+    /// The task checker's rewrite of tasks.json.
+    TaskCheck,
+    /// The docs attach sub-agent's tasks.json with docs filled in.
+    DocsAttach,
+}
+
+impl CachedCall {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TaskCheck => "task_check",
+            Self::DocsAttach => "docs_attach",
+        }
+    }
+}
+
+/// One `<key>.json` file in the cache directory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CachedResponseFile {
+    pub call: String,
+    pub created_at_epoch_secs: u64,
+    pub output: String,
+}
+
+/// Hits and misses since startup, plus what is on disk now.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+/// Content-addressed store of sub-agent results shared by every session. A miss leaves the
+/// call's key pending until the run completes and its output is stored under that key.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseCache {
+    /// `None` turns caching off, e.g. when HOME is not set.
+    dir: Option<PathBuf>,
+    pending: HashMap<CachedCall, String>,
+    hits: u64,
+    misses: u64,
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir: Some(dir),
+            ..Self::default()
+        }
+    }
+
+    /// SHA-256 over the call and every input, each prefixed with its length so inputs
+    /// cannot run into one another.
+    pub fn key(call: CachedCall, inputs: &[&str]) -> String {
+        let mut material = String::from(call.label());
+        for input in inputs {
+            material.push('\n');
+            material.push_str(&input.len().to_string());
+            material.push('\n');
+            material.push_str(input);
+        }
+        sha256_hex(material.as_bytes())
+    }
+
+    /// Returns the output stored under `key`. On a miss the key waits for
+    /// [`Self::complete`]; an unreadable entry counts as a miss.
+    pub fn lookup(&mut self, call: CachedCall, key: String) -> Option<String> {
+        let dir = self.dir.as_ref()?;
+        let cached = fs::read_to_string(dir.join(format!("{key}.json")))
+            .ok()
+            .and_then(|text| serde_json::from_str::<CachedResponseFile>(&text).ok())
+            .filter(|entry| entry.call == call.label());
+        match cached {
+            Some(entry) => {
+                self.hits += 1;
+                self.pending.remove(&call);
+                Some(entry.output)
+            }
+            None => {
+                self.misses += 1;
+                self.pending.insert(call, key);
+                None
+            }
+        }
+    }
+
+    /// Drops the pending key of `call`, for a run whose result must not be cached.
+    pub fn forget(&mut self, call: CachedCall) {
+        self.pending.remove(&call);
+    }
+
+    /// Stores `output` under the key left by the last miss of `call`; `None` (a failed run)
+    /// stores nothing. Without a pending key this does nothing.
+    pub fn complete(&mut self, call: CachedCall, output: Option<&str>) -> io::Result<()> {
+        let Some(key) = self.pending.remove(&call) else {
+            return Ok(());
+        };
+        let (Some(dir), Some(output)) = (self.dir.as_ref(), output) else {
+            return Ok(());
+        };
+        let entry = CachedResponseFile {
+            call: call.label().to_string(),
+            created_at_epoch_secs: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            output: output.to_string(),
+        };
+        let text = serde_json::to_string_pretty(&entry).map_err(io::Error::other)?;
+        write_text_file_atomic(&dir.join(format!("{key}.json")), &text)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            ..CacheStats::default()
+        };
+        for path in self.entry_paths() {
+            stats.entries += 1;
+            stats.bytes += fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        }
+        stats
+    }
+
+    /// Deletes every cached response; returns how many were removed.
+    pub fn purge(&mut self) -> io::Result<usize> {
+        let mut removed = 0;
+        for path in self.entry_paths() {
+            fs::remove_file(path)?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn entry_paths(&self) -> Vec<PathBuf> {
+        let Some(entries) = self.dir.as_ref().and_then(|dir| fs::read_dir(dir).ok()) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/response_cache_tests.rs"]
+mod tests;
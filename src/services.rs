@@ -1,14 +1,21 @@
 use std::collections::HashMap;
 use std::io;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::agent::{BackendKind, CodexAdapter};
 use crate::agent_models::{CodexAgentKind, CodexAgentModelRouting};
 use crate::app::App;
-use crate::artifact_io::{read_text_file, write_text_file};
+use crate::artifact_io::read_text_file;
 use crate::deterministic::TestRunnerAdapter;
+use crate::doc_snapshots;
+use crate::plugins::{PluginCapability, PluginRunRequest};
+use crate::project_memory;
 use crate::session_store::{SessionStore, TaskFailFileEntry};
 use crate::subagents;
+use crate::telemetry;
+use crate::test_runners::TestRunnerSpec;
+use crate::worker_logs;
 use crate::workflow::{JobRun, StartedJob, WorkerRole, WorkflowFailure, WorkflowFailureKind};
 
 #[derive(Debug, Clone)]
@@ -20,10 +27,105 @@ pub struct TaskWriteBaseline {
 pub struct WorkerCompletionOutcome {
     pub failure_report_prompt: Option<String>,
     pub context_report_prompt: Option<String>,
+    /// One prompt per top-level task this completion finished, asking for its summary.
+    pub task_summary_prompts: Vec<String>,
     pub started_job: Option<StartedJob>,
     pub warnings: Vec<String>,
 }
 
+/// Counts consecutive failed worker-agent jobs so a broken backend can be swapped out instead
+/// of burning every retry against it. Fires once, then stays quiet until [`Self::rearm`].
+#[derive(Debug, Clone, Copy)]
+pub struct BackendFailoverTracker {
+    threshold: u64,
+    consecutive_failures: u64,
+    armed: bool,
+}
+
+impl BackendFailoverTracker {
+    pub const DEFAULT_THRESHOLD: u64 = 3;
+
+    /// A `threshold` of 0 disables failover.
+    pub fn new(threshold: u64) -> Self {
+        Self {
+            threshold,
+            consecutive_failures: 0,
+            armed: true,
+        }
+    }
+
+    /// Returns the failure streak when it has just reached the threshold.
+    pub fn record_worker_result(&mut self, success: bool) -> Option<u64> {
+        if success {
+            self.consecutive_failures = 0;
+            return None;
+        }
+        self.consecutive_failures += 1;
+        if !self.armed || self.threshold == 0 || self.consecutive_failures < self.threshold {
+            return None;
+        }
+        self.armed = false;
+        Some(std::mem::take(&mut self.consecutive_failures))
+    }
+
+    /// Re-enables failover, e.g. after the user picks a backend with `/backend`.
+    pub fn rearm(&mut self) {
+        self.consecutive_failures = 0;
+        self.armed = true;
+    }
+}
+
+/// Holds a worker job whose agent run failed for a transient reason (it did not start, or
+/// the provider dropped or refused the request) until its prompt is sent again. The wait
+/// doubles with each retry of the same job. Retries leave the job active, so they use up
+/// no audit or test retry budget; once [`Self::MAX_RETRIES`] are spent, the failure is
+/// handled like any other.
+#[derive(Debug, Clone, Default)]
+pub struct TransientRetryQueue {
+    retries: u32,
+    /// Worker adapter key to resend on, and when.
+    due: Option<(String, Instant)>,
+}
+
+impl TransientRetryQueue {
+    pub const MAX_RETRIES: u32 = 4;
+    const FIRST_DELAY: Duration = Duration::from_secs(2);
+    const MAX_DELAY: Duration = Duration::from_secs(60);
+
+    /// Queues a retry on the adapter `key` and returns its number and delay, or `None` when
+    /// the job has no retries left.
+    pub fn schedule(&mut self, key: String, now: Instant) -> Option<(u32, Duration)> {
+        if self.retries >= Self::MAX_RETRIES {
+            self.reset();
+            return None;
+        }
+        let delay = Self::FIRST_DELAY
+            .saturating_mul(1 << self.retries)
+            .min(Self::MAX_DELAY);
+        self.retries += 1;
+        self.due = Some((key, now + delay));
+        Some((self.retries, delay))
+    }
+
+    /// When the queued retry is due, for the main loop's sleep.
+    pub fn next_due(&self) -> Option<Instant> {
+        self.due.as_ref().map(|(_, at)| *at)
+    }
+
+    /// The adapter key of the queued retry once it is due, removing it from the queue.
+    pub fn take_due(&mut self, now: Instant) -> Option<String> {
+        if self.next_due().is_none_or(|at| at > now) {
+            return None;
+        }
+        self.due.take().map(|(key, _)| key)
+    }
+
+    /// Forgets the queued retry and the retry count, e.g. once the job finishes.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
 pub trait CoreOrchestrationService {
     fn claim_next_worker_job_and_persist_snapshot(
         &self,
@@ -108,6 +210,10 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
         app: &mut App,
         session_store: &SessionStore,
     ) -> io::Result<Option<StartedJob>> {
+        app.set_doc_snapshots(doc_snapshots::read_snapshots(
+            session_store,
+            &app.planner_tasks_for_file(),
+        ));
         let job = app.start_next_worker_job();
         self.persist_runtime_tasks_snapshot(app, session_store)?;
         Ok(job)
@@ -128,19 +234,68 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
                     .parent_context_key
                     .clone()
                     .unwrap_or_else(|| format!("top:{}", job.top_task_id));
-                worker_agent_adapters
-                    .entry(key.clone())
-                    .or_insert_with(|| build_worker_adapter(model_routing, job.role));
+                let adapter = build_worker_adapter(
+                    model_routing,
+                    job.role,
+                    job.model_profile.as_deref(),
+                    job.workdir.as_deref(),
+                );
+                // A /task-model change or escalation takes effect on the task's next job.
+                // Adapters without a model (Claude) ignore profiles, so they are kept.
+                let pinned_model_changed =
+                    worker_agent_adapters.get(&key).is_some_and(|existing| {
+                        existing.model().is_some()
+                            && (existing.model() != adapter.model()
+                                || existing.model_reasoning_effort()
+                                    != adapter.model_reasoning_effort())
+                    });
+                let workdir_changed = worker_agent_adapters
+                    .get(&key)
+                    .is_some_and(|existing| existing.workdir() != adapter.workdir());
+                if pinned_model_changed || workdir_changed {
+                    worker_agent_adapters.insert(key.clone(), adapter);
+                } else {
+                    worker_agent_adapters.entry(key.clone()).or_insert(adapter);
+                }
                 let adapter = worker_agent_adapters
                     .get(&key)
                     .expect("worker adapter should be present after insertion");
-                adapter.send_prompt(prompt.clone());
+                let persona = session_store.agent_persona(worker_role_agent_kind(job.role));
+                let stack = session_store.project_stack();
+                let prompt = subagents::with_project_stack(prompt, stack.as_deref());
+                adapter.set_trace_parent(telemetry::job_context());
+                adapter.send_prompt(subagents::with_worker_persona(&prompt, persona.as_deref()));
                 *active_worker_context_key = Some(key);
             }
             JobRun::DeterministicTestRun => {
                 *active_worker_context_key = None;
-                let test_command = session_test_command(session_store);
-                test_runner_adapter.run_tests_with_command(test_command.as_deref());
+                let test_runner = session_test_runner(session_store);
+                test_runner_adapter.run_tests_with_spec(test_runner.as_ref());
+            }
+            JobRun::PluginStep {
+                plugin,
+                program,
+                input,
+                task,
+            } => {
+                *active_worker_context_key = None;
+                let request = PluginRunRequest {
+                    invocation: PluginCapability::Step,
+                    input: input.clone(),
+                    task: Some(task.clone()),
+                    workspace: model_routing
+                        .workspace()
+                        .map(Path::to_path_buf)
+                        .or_else(|| std::env::current_dir().ok())
+                        .map(|dir| dir.display().to_string())
+                        .unwrap_or_default(),
+                    session_dir: Some(session_store.session_dir().display().to_string()),
+                };
+                test_runner_adapter.run_plugin_step(plugin, program.as_deref(), request);
+            }
+            JobRun::Verify { command } => {
+                *active_worker_context_key = None;
+                test_runner_adapter.run_tests_with_spec(Some(&TestRunnerSpec::shell(command)));
             }
         }
     }
@@ -155,7 +310,8 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
         model_routing: &CodexAgentModelRouting,
     ) -> io::Result<Option<StartedJob>> {
         loop {
-            let Some(job) = self.claim_next_worker_job_and_persist_snapshot(app, session_store)?
+            let Some(mut job) =
+                self.claim_next_worker_job_and_persist_snapshot(app, session_store)?
             else {
                 return Ok(None);
             };
@@ -165,6 +321,7 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
                 self.persist_runtime_tasks_snapshot(app, session_store)?;
                 continue;
             }
+            escalate_job_model(app, &mut job, model_routing);
             self.dispatch_worker_job(
                 &job,
                 worker_agent_adapters,
@@ -173,6 +330,12 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
                 session_store,
                 model_routing,
             );
+            if let Some(adapter) = active_worker_context_key
+                .as_ref()
+                .and_then(|key| worker_agent_adapters.get(key))
+            {
+                app.set_active_job_model(adapter.model());
+            }
             return Ok(Some(job));
         }
     }
@@ -202,6 +365,7 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
                 kind: match failure.kind {
                     WorkflowFailureKind::Audit => "audit".to_string(),
                     WorkflowFailureKind::Test => "test".to_string(),
+                    WorkflowFailureKind::FlakyTest => "flaky_test".to_string(),
                 },
                 top_task_id: failure.top_task_id,
                 top_task_title: failure.top_task_title.clone(),
@@ -212,10 +376,19 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
             })
             .collect();
         session_store.append_task_fails(&fail_entries)?;
-        let has_test_failure = fail_entries.iter().any(|entry| entry.kind == "test");
+        // Flaky runs are logged, but their retries are not exhausted, so there is nothing to
+        // report yet.
+        let exhausted = fail_entries
+            .into_iter()
+            .filter(|entry| entry.kind != "flaky_test")
+            .collect::<Vec<_>>();
+        if exhausted.is_empty() {
+            return Ok(None);
+        }
+        let has_test_failure = exhausted.iter().any(|entry| entry.kind == "test");
         let prompt = subagents::build_failure_report_prompt(
             &session_store.task_fails_file().display().to_string(),
-            &fail_entries,
+            &exhausted,
             has_test_failure,
         );
         Ok(Some(subagents::build_session_intro_if_needed(
@@ -223,6 +396,10 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
             session_store.session_dir().display().to_string().as_str(),
             &session_store.session_meta_file().display().to_string(),
             project_info_text,
+            None,
+            session_store
+                .agent_persona(CodexAgentKind::MasterReport)
+                .as_deref(),
             master_report_session_intro_needed,
         )))
     }
@@ -245,6 +422,30 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
         let mut context_report_prompt = None;
 
         let new_context_entries = app.on_worker_completed(success, code);
+        let logs_dir = session_store.logs_dir();
+        let task_summary_prompts = app
+            .drain_completed_top_tasks()
+            .into_iter()
+            .map(|task| {
+                let final_logs = worker_logs::final_pass_logs(&logs_dir, &task.key)
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>();
+                let prompt =
+                    subagents::build_task_summary_prompt(&task.key, &task.title, &final_logs);
+                app.with_reply_language(subagents::build_session_intro_if_needed(
+                    &prompt,
+                    session_store.session_dir().display().to_string().as_str(),
+                    &session_store.session_meta_file().display().to_string(),
+                    project_info_text,
+                    None,
+                    session_store
+                        .agent_persona(CodexAgentKind::MasterReport)
+                        .as_deref(),
+                    master_report_session_intro_needed,
+                ))
+            })
+            .collect();
         let exhausted_failures = app.drain_worker_failures();
         if !exhausted_failures.is_empty() {
             match self.build_exhausted_loop_failures_prompt(
@@ -254,7 +455,7 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
                 exhausted_failures,
             ) {
                 Ok(prompt) => {
-                    failure_report_prompt = prompt;
+                    failure_report_prompt = prompt.map(|prompt| app.with_reply_language(prompt));
                 }
                 Err(err) => warnings.push(format!("Failed to append task-fails.json: {err}")),
             }
@@ -265,13 +466,19 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
                 warnings.push(format!("Failed to persist rolling_context.json: {err}"));
             }
             let prompt = app.prepare_context_report_prompt(&new_context_entries);
-            context_report_prompt = Some(subagents::build_session_intro_if_needed(
-                &prompt,
-                session_store.session_dir().display().to_string().as_str(),
-                &session_store.session_meta_file().display().to_string(),
-                project_info_text,
-                master_report_session_intro_needed,
-            ));
+            context_report_prompt = Some(
+                app.with_reply_language(subagents::build_session_intro_if_needed(
+                    &prompt,
+                    session_store.session_dir().display().to_string().as_str(),
+                    &session_store.session_meta_file().display().to_string(),
+                    project_info_text,
+                    None,
+                    session_store
+                        .agent_persona(CodexAgentKind::MasterReport)
+                        .as_deref(),
+                    master_report_session_intro_needed,
+                )),
+            );
         }
 
         let started_job = match self.start_next_worker_job_if_any(
@@ -294,6 +501,7 @@ impl CoreOrchestrationService for DefaultCoreOrchestrationService {
         WorkerCompletionOutcome {
             failure_report_prompt,
             context_report_prompt,
+            task_summary_prompts,
             started_job,
             warnings,
         }
@@ -311,7 +519,7 @@ impl UiPromptService for DefaultUiPromptService {
     ) -> String {
         let session_dir = session_store.session_dir().display().to_string();
         let session_meta_file = session_store.session_meta_file().display().to_string();
-        let tasks_file = session_store.tasks_file().display().to_string();
+        let tasks_file = master_tasks_file(app, session_store).display().to_string();
         let planner_file = session_store.planner_file().display().to_string();
         let project_info_file = session_store.project_info_file().display().to_string();
         let prompt = if app.is_planner_mode() {
@@ -319,13 +527,17 @@ impl UiPromptService for DefaultUiPromptService {
         } else {
             app.prepare_master_prompt(message, &tasks_file)
         };
-        subagents::build_session_intro_if_needed(
+        app.with_reply_language(subagents::build_session_intro_if_needed(
             &prompt,
             session_dir.as_str(),
             &session_meta_file,
-            project_info_text,
+            app.project_info_for_prompt(project_info_text),
+            project_memory::session_memory_section(session_store).as_deref(),
+            session_store
+                .agent_persona(CodexAgentKind::Master)
+                .as_deref(),
             master_session_intro_needed,
-        )
+        ))
     }
 
     fn build_convert_master_prompt(
@@ -337,17 +549,21 @@ impl UiPromptService for DefaultUiPromptService {
     ) -> String {
         let session_dir = session_store.session_dir().display().to_string();
         let session_meta_file = session_store.session_meta_file().display().to_string();
-        let tasks_file = session_store.tasks_file().display().to_string();
+        let tasks_file = master_tasks_file(app, session_store).display().to_string();
         let planner_file = session_store.planner_file().display().to_string();
         let command_prompt = subagents::build_convert_plan_prompt(&planner_file, &tasks_file);
         let master_prompt = app.prepare_master_prompt(&command_prompt, &tasks_file);
-        subagents::build_session_intro_if_needed(
+        app.with_reply_language(subagents::build_session_intro_if_needed(
             &master_prompt,
             session_dir.as_str(),
             &session_meta_file,
-            project_info_text,
+            app.project_info_for_prompt(project_info_text),
+            project_memory::session_memory_section(session_store).as_deref(),
+            session_store
+                .agent_persona(CodexAgentKind::Master)
+                .as_deref(),
             master_session_intro_needed,
-        )
+        ))
     }
 }
 
@@ -357,23 +573,42 @@ impl DefaultCoreOrchestrationService {
         app: &App,
         session_store: &SessionStore,
     ) -> io::Result<()> {
-        let tasks = app.planner_tasks_for_file();
-        let text = serde_json::to_string_pretty(&tasks).map_err(io::Error::other)?;
-        write_text_file(session_store.tasks_file(), &text)
+        if app.has_external_tasks_edit() {
+            // Leave the user's hand edit in place until /reload-tasks or /keep-tasks.
+            return Ok(());
+        }
+        session_store.write_tasks(&app.planner_tasks_for_file())
     }
 }
 
+/// The tasks file master prompts name. In safe mode that is `tasks.staged.json`, so tasks.json
+/// only changes on `/accept-tasks`.
+pub(crate) fn master_tasks_file(app: &App, session_store: &SessionStore) -> PathBuf {
+    if !app.is_safe_mode() {
+        return session_store.tasks_file().to_path_buf();
+    }
+    // Without the copy the master writes the staged file from scratch, and the review that
+    // follows still shows every change against tasks.json.
+    let _ = session_store.begin_staged_tasks();
+    session_store.staged_tasks_file()
+}
+
 fn worker_role_agent_kind(role: WorkerRole) -> CodexAgentKind {
     match role {
         WorkerRole::Implementor => CodexAgentKind::WorkerImplementor,
         WorkerRole::Auditor => CodexAgentKind::WorkerAuditor,
         WorkerRole::TestWriter => CodexAgentKind::WorkerTestWriter,
         WorkerRole::FinalAudit => CodexAgentKind::WorkerFinalAudit,
-        WorkerRole::TestRunner => CodexAgentKind::WorkerTestWriter,
+        WorkerRole::TestRunner | WorkerRole::Plugin => CodexAgentKind::WorkerTestWriter,
     }
 }
 
-fn build_worker_adapter(model_routing: &CodexAgentModelRouting, role: WorkerRole) -> CodexAdapter {
+fn build_worker_adapter(
+    model_routing: &CodexAgentModelRouting,
+    role: WorkerRole,
+    model_profile: Option<&str>,
+    workdir: Option<&str>,
+) -> CodexAdapter {
     let mut config = model_routing.base_command_config();
     config.output_mode = if matches!(config.backend_kind(), BackendKind::Claude) {
         crate::agent::AdapterOutputMode::JsonAssistantOnly
@@ -382,7 +617,17 @@ fn build_worker_adapter(model_routing: &CodexAgentModelRouting, role: WorkerRole
     };
     config.persistent_session = true;
     config.skip_reader_join_after_wait = true;
-    let profile = model_routing.profile_for(worker_role_agent_kind(role));
+    if model_routing.safe_mode().is_some() {
+        config.make_read_only();
+    }
+    // A task's own workdir wins over the per-kind one from `[codex.agent_workdirs]`.
+    config.workdir = match workdir {
+        Some(workdir) => model_routing.in_workspace(Some(PathBuf::from(workdir))),
+        None => model_routing.workdir_for(worker_role_agent_kind(role)),
+    };
+    let profile = model_profile
+        .and_then(|label| model_routing.profile_named(label))
+        .unwrap_or_else(|| model_routing.profile_for(worker_role_agent_kind(role)));
     if matches!(config.backend_kind(), BackendKind::Codex) {
         config.model = Some(profile.model.clone());
         config.model_reasoning_effort = profile.thinking_effort;
@@ -390,14 +635,46 @@ fn build_worker_adapter(model_routing: &CodexAgentModelRouting, role: WorkerRole
         config.model = None;
         config.model_reasoning_effort = None;
     }
-    CodexAdapter::with_config(config)
+    CodexAdapter::with_config(config).with_agent_kind(worker_role_agent_kind(role).config_key())
 }
 
-fn session_test_command(session_store: &SessionStore) -> Option<String> {
-    session_store
-        .read_session_meta()
+/// Moves a repeatedly failing implementor pass up the escalation ladder. Claude adapters
+/// ignore model profiles, so nothing is escalated there.
+fn escalate_job_model(app: &mut App, job: &mut StartedJob, model_routing: &CodexAgentModelRouting) {
+    if job.escalation == 0
+        || !matches!(
+            model_routing.base_command_config().backend_kind(),
+            BackendKind::Codex
+        )
+    {
+        return;
+    }
+    let from = job.model_profile.clone().unwrap_or_else(|| {
+        model_routing
+            .profile_label_for(worker_role_agent_kind(job.role))
+            .to_string()
+    });
+    if let Some(to) = model_routing.escalated_profile(&from, job.escalation) {
+        app.record_model_escalation(&from, &to);
+        job.model_profile = Some(to);
+    }
+}
+
+/// A typed `test_runner` in meta.json wins; otherwise a test command pinned by the session
+/// template, then the one inferred into meta.json, runs through the shell runner.
+fn session_test_runner(session_store: &SessionStore) -> Option<TestRunnerSpec> {
+    let meta = session_store.read_session_meta().ok();
+    if let Some(spec) = meta.as_ref().and_then(|meta| meta.test_runner.clone()) {
+        return Some(spec);
+    }
+    let template_command = session_store
+        .read_session_template()
         .ok()
-        .and_then(|meta| normalize_test_command(meta.test_command))
+        .flatten()
+        .and_then(|template| normalize_test_command(template.test_command));
+    template_command
+        .or_else(|| meta.and_then(|meta| normalize_test_command(meta.test_command)))
+        .map(|command| TestRunnerSpec::shell(&command))
 }
 
 fn normalize_test_command(value: Option<String>) -> Option<String> {
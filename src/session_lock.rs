@@ -0,0 +1,281 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::artifact_io::{read_text_file, write_text_file, write_text_file_if_missing};
+
+/// Lock file in each session directory naming the instance that has the session open.
+pub const LOCK_FILE_NAME: &str = "session.lock";
+
+/// The instance recorded in a session's lock file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockOwner {
+    pub pid: u32,
+    pub host: String,
+    #[serde(default)]
+    pub acquired_at_epoch_secs: u64,
+}
+
+impl LockOwner {
+    pub fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            host: current_host(),
+            acquired_at_epoch_secs: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
+    pub fn is_current_process(&self) -> bool {
+        self.pid == std::process::id() && self.host == current_host()
+    }
+
+    fn is_same_instance(&self, other: &LockOwner) -> bool {
+        self.pid == other.pid && self.host == other.host
+    }
+
+    /// Whether the owner's process still runs. `None` when it ran on another host, where
+    /// liveness cannot be checked.
+    pub fn is_running(&self) -> Option<bool> {
+        (self.host == current_host()).then(|| process_is_running(self.pid))
+    }
+
+    /// `pid 4242 on devbox`.
+    pub fn describe(&self) -> String {
+        format!("pid {} on {}", self.pid, self.host)
+    }
+}
+
+/// How `/lock` settles a session another instance has open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockAction {
+    /// Open the session here; the other instance drops to read-only when it notices.
+    Takeover,
+    /// Follow the session read-only, as `agentbob observe` does.
+    Observe,
+}
+
+impl LockAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "takeover" => Some(Self::Takeover),
+            "observe" => Some(Self::Observe),
+            _ => None,
+        }
+    }
+}
+
+/// What a session's lock file says about opening it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockState {
+    /// No lock, an unreadable one, or one this process already holds.
+    Free,
+    /// Left behind by a process on this host that is no longer running.
+    Stale(LockOwner),
+    /// Held by a running instance, or by one on another host.
+    Held(LockOwner),
+}
+
+pub fn lock_file(session_dir: &Path) -> PathBuf {
+    session_dir.join(LOCK_FILE_NAME)
+}
+
+pub fn read_owner(session_dir: &Path) -> Option<LockOwner> {
+    let text = read_text_file(&lock_file(session_dir)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+pub fn inspect(session_dir: &Path) -> LockState {
+    state_for(read_owner(session_dir), &LockOwner::current())
+}
+
+fn state_for(owner: Option<LockOwner>, me: &LockOwner) -> LockState {
+    let Some(owner) = owner else {
+        return LockState::Free;
+    };
+    if owner.is_same_instance(me) {
+        LockState::Free
+    } else if owner.is_running() == Some(false) {
+        LockState::Stale(owner)
+    } else {
+        LockState::Held(owner)
+    }
+}
+
+/// Records `owner` over whatever the lock file says; only `/lock takeover` replaces a lock
+/// another instance holds this way.
+pub fn write_owner(session_dir: &Path, owner: &LockOwner) -> io::Result<()> {
+    let text = serde_json::to_string_pretty(owner).map_err(io::Error::other)?;
+    write_text_file(&lock_file(session_dir), &text)
+}
+
+/// Creates `session_dir`'s lock file for this process. The file is only created when
+/// missing, so of two instances opening a session at once just one gets it. A lock this
+/// process holds is kept, and a stale or unreadable one is replaced; returns `Held` without
+/// touching a lock another instance holds.
+pub fn acquire(session_dir: &Path) -> io::Result<LockState> {
+    acquire_as(session_dir, &LockOwner::current())
+}
+
+/// [`acquire`] on behalf of `me`, so tests can stand in for several instances.
+fn acquire_as(session_dir: &Path, me: &LockOwner) -> io::Result<LockState> {
+    let path = lock_file(session_dir);
+    let text = serde_json::to_string_pretty(me).map_err(io::Error::other)?;
+    if write_text_file_if_missing(&path, &text)? {
+        return confirm_owner(session_dir, me, LockState::Free);
+    }
+    let found = read_owner(session_dir);
+    let state = state_for(found.clone(), me);
+    let owned = found
+        .as_ref()
+        .is_some_and(|owner| owner.is_same_instance(me));
+    if owned || matches!(state, LockState::Held(_)) {
+        return Ok(state);
+    }
+    // Instances clearing the same stale lock race here. The rename moves the file to a name
+    // only this instance uses, so at most one of them removes it; a lock that is no longer
+    // the one inspected above was just created by the winner and goes back in place.
+    let aside = session_dir.join(format!("{LOCK_FILE_NAME}.{}.replaced", me.pid));
+    match std::fs::rename(&path, &aside) {
+        Ok(()) => {
+            let moved = read_text_file(&aside)
+                .ok()
+                .and_then(|text| serde_json::from_str::<LockOwner>(&text).ok());
+            if moved != found {
+                let _ = std::fs::hard_link(&aside, &path);
+                let _ = std::fs::remove_file(&aside);
+                return contested(session_dir, me);
+            }
+            std::fs::remove_file(&aside)?;
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    if !write_text_file_if_missing(&path, &text)? {
+        return contested(session_dir, me);
+    }
+    confirm_owner(session_dir, me, state)
+}
+
+/// Reads the lock back after creating it: a racing instance that removed it in between has
+/// won, and this one reports the session as held.
+fn confirm_owner(session_dir: &Path, me: &LockOwner, state: LockState) -> io::Result<LockState> {
+    if read_owner(session_dir).is_some_and(|owner| owner.is_same_instance(me)) {
+        Ok(state)
+    } else {
+        contested(session_dir, me)
+    }
+}
+
+fn contested(session_dir: &Path, me: &LockOwner) -> io::Result<LockState> {
+    match read_owner(session_dir) {
+        Some(owner) if !owner.is_same_instance(me) => Ok(LockState::Held(owner)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "another instance opened the session at the same time",
+        )),
+    }
+}
+
+/// Acquires `session_dir` for this process and releases the session it held before. Fails
+/// with `AlreadyExists` while another instance holds it.
+pub fn hold(session_dir: &Path) -> io::Result<()> {
+    if let LockState::Held(owner) = acquire(session_dir)? {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("the session is open in {}", owner.describe()),
+        ));
+    }
+    let mut held = held_session().lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(previous) = held.replace(session_dir.to_path_buf())
+        && previous != session_dir
+    {
+        remove_if_owned(&previous);
+    }
+    Ok(())
+}
+
+/// Drops the lock [`acquire`] took on a session that then failed to open, unless it is the
+/// session this process holds.
+pub fn abandon(session_dir: &Path) {
+    let held = held_session().lock().unwrap_or_else(|err| err.into_inner());
+    if held.as_deref() != Some(session_dir) {
+        remove_if_owned(session_dir);
+    }
+}
+
+/// Removes the held session's lock file unless another instance has taken it over.
+pub fn release() {
+    let mut held = held_session().lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(session_dir) = held.take() {
+        remove_if_owned(&session_dir);
+    }
+}
+
+/// The instance that took over the held session, once its lock file names someone else.
+pub fn taken_over_by() -> Option<LockOwner> {
+    let held = held_session().lock().unwrap_or_else(|err| err.into_inner());
+    read_owner(held.as_deref()?).filter(|owner| !owner.is_current_process())
+}
+
+fn held_session() -> &'static Mutex<Option<PathBuf>> {
+    static HELD: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+    HELD.get_or_init(|| Mutex::new(None))
+}
+
+fn remove_if_owned(session_dir: &Path) {
+    if read_owner(session_dir).is_some_and(|owner| owner.is_current_process()) {
+        let _ = std::fs::remove_file(lock_file(session_dir));
+    }
+}
+
+fn current_host() -> String {
+    static HOST: OnceLock<String> = OnceLock::new();
+    HOST.get_or_init(|| {
+        std::env::var("HOSTNAME")
+            .ok()
+            .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .map(|host| host.trim().to_string())
+            .filter(|host| !host.is_empty())
+            .unwrap_or_else(|| "localhost".to_string())
+    })
+    .clone()
+}
+
+/// Looks the process up in `/proc` where there is one. Elsewhere signal 0 checks that it
+/// exists without touching it; only "no such process" counts as gone, since a process owned
+/// by another user refuses the signal while still running. Windows has no equivalent here,
+/// so a recorded process is assumed to be running.
+fn process_is_running(pid: u32) -> bool {
+    if cfg!(windows) {
+        return true;
+    }
+    if Path::new("/proc/self").exists() {
+        return Path::new("/proc").join(pid.to_string()).exists();
+    }
+    match Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+    {
+        Ok(output) => {
+            output.status.success()
+                || !String::from_utf8_lossy(&output.stderr)
+                    .to_ascii_lowercase()
+                    .contains("no such process")
+        }
+        Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/session_lock_tests.rs"]
+mod tests;
@@ -0,0 +1,808 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::artifact_io::write_file_atomic;
+use crate::audit_trail::sha256_hex;
+use crate::credentials;
+use crate::i18n::{self, Locale, Text};
+use crate::session_lock::{self, LOCK_FILE_NAME, LockState};
+use crate::session_store::{SessionStore, load_global_remote_storage, storage_root_dir};
+use crate::ticket_sync::curl_quote;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A place session directories live in: the local storage root, or a bucket they are mirrored
+/// to. `SessionStore` reads and writes every session file through the local one, since agents
+/// edit the same files by path; the bucket only receives copies on push and pull. Sessions are
+/// named by their directory name; files by `/`-separated paths inside it.
+pub trait SessionStorage: fmt::Debug {
+    /// `/home/dev/.agentbob/sessions` or `s3://bucket/prefix`, for messages.
+    fn describe(&self) -> String;
+    /// Names of the stored sessions, sorted.
+    fn list_sessions(&self) -> io::Result<Vec<String>>;
+    /// Files of one session, sorted; empty when the session is not stored here.
+    fn list_files(&self, session: &str) -> io::Result<Vec<String>>;
+    fn read_file(&self, session: &str, path: &str) -> io::Result<Vec<u8>>;
+    fn write_file(&self, session: &str, path: &str, bytes: &[u8]) -> io::Result<()>;
+    /// Like [`Self::write_file`], but a reader sees either the old or the new contents, never
+    /// part of them.
+    fn replace_file(&self, session: &str, path: &str, bytes: &[u8]) -> io::Result<()>;
+    /// Deletes one file; a file that is not there counts as deleted.
+    fn remove_file(&self, session: &str, path: &str) -> io::Result<()>;
+}
+
+/// Session directories under a local root, as `SessionStore` creates them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalDiskStorage {
+    root: PathBuf,
+}
+
+impl LocalDiskStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The `[storage] root_dir` new sessions are created under.
+    pub fn from_config() -> io::Result<Self> {
+        Ok(Self::new(storage_root_dir()?))
+    }
+
+    pub fn session_dir(&self, session: &str) -> PathBuf {
+        self.root.join(session)
+    }
+}
+
+impl SessionStorage for LocalDiskStorage {
+    fn describe(&self) -> String {
+        self.root.display().to_string()
+    }
+
+    fn list_sessions(&self) -> io::Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut sessions = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                sessions.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        sessions.sort();
+        Ok(sessions)
+    }
+
+    fn list_files(&self, session: &str) -> io::Result<Vec<String>> {
+        let session_dir = self.session_dir(checked_session_name(session)?);
+        let mut files = Vec::new();
+        if session_dir.is_dir() {
+            collect_files(&session_dir, "", &mut files)?;
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn read_file(&self, session: &str, path: &str) -> io::Result<Vec<u8>> {
+        fs::read(
+            self.session_dir(checked_session_name(session)?)
+                .join(checked_file_path(path)?),
+        )
+    }
+
+    fn write_file(&self, session: &str, path: &str, bytes: &[u8]) -> io::Result<()> {
+        let target = self
+            .session_dir(checked_session_name(session)?)
+            .join(checked_file_path(path)?);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, bytes)
+    }
+
+    fn replace_file(&self, session: &str, path: &str, bytes: &[u8]) -> io::Result<()> {
+        write_file_atomic(
+            &self
+                .session_dir(checked_session_name(session)?)
+                .join(checked_file_path(path)?),
+            bytes,
+        )
+    }
+
+    fn remove_file(&self, session: &str, path: &str) -> io::Result<()> {
+        match fs::remove_file(
+            self.session_dir(checked_session_name(session)?)
+                .join(checked_file_path(path)?),
+        ) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn collect_files(dir: &Path, relative: &str, files: &mut Vec<String>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = if relative.is_empty() {
+            name
+        } else {
+            format!("{relative}/{name}")
+        };
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), &path, files)?;
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The `[storage.remote]` table. An empty `endpoint` keeps sessions on this machine only.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RemoteStorageConfig {
+    /// S3 API base URL: `https://s3.us-east-1.amazonaws.com`, `https://storage.googleapis.com`
+    /// with GCS HMAC keys, or a MinIO/R2 URL. Buckets are addressed path-style.
+    pub endpoint: String,
+    pub bucket: String,
+    /// Key prefix every session directory is stored under.
+    pub prefix: String,
+    /// SigV4 signing region; GCS and R2 accept `auto`.
+    pub region: String,
+    pub access_key_id: String,
+    /// Falls back to the `storage` credential from `bob auth set storage` when empty.
+    pub secret_access_key: String,
+    /// Upload the open session when bob exits or another session is opened.
+    pub push_on_exit: bool,
+}
+
+impl Default for RemoteStorageConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            bucket: String::new(),
+            prefix: "agentbob/sessions".to_string(),
+            region: "us-east-1".to_string(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            push_on_exit: true,
+        }
+    }
+}
+
+impl RemoteStorageConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.endpoint.trim().is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ObjectRequest {
+    pub method: &'static str,
+    pub url: String,
+    pub body: Option<Vec<u8>>,
+}
+
+/// Sends one signed request and returns the response body; swapped out in tests.
+pub(crate) type ObjectRunner = dyn Fn(&ObjectRequest) -> Result<Vec<u8>, String>;
+
+/// Sessions mirrored to an S3-compatible bucket, one object per session file.
+pub struct RemoteObjectStorage {
+    config: RemoteStorageConfig,
+    runner: Box<ObjectRunner>,
+}
+
+/// Shows where the bucket is, never the keys.
+impl fmt::Debug for RemoteObjectStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RemoteObjectStorage")
+            .field(&self.describe())
+            .finish()
+    }
+}
+
+impl RemoteObjectStorage {
+    /// The configured remote store, or `None` when `[storage.remote]` has no endpoint.
+    pub fn from_config() -> io::Result<Option<Self>> {
+        let mut config = load_global_remote_storage()?;
+        if !config.is_enabled() {
+            return Ok(None);
+        }
+        credentials::fill_from_store(&mut config.secret_access_key, credentials::STORAGE_SECRET);
+        if config.bucket.trim().is_empty()
+            || config.access_key_id.trim().is_empty()
+            || config.secret_access_key.trim().is_empty()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "storage.remote needs bucket, access_key_id, and a secret_access_key (or `bob auth set storage`)",
+            ));
+        }
+        let user = format!(
+            "{}:{}",
+            config.access_key_id.trim(),
+            config.secret_access_key.trim()
+        );
+        let region = config.region.trim().to_string();
+        Ok(Some(Self::with_runner(
+            config,
+            Box::new(move |request| run_signed_curl(&user, &region, request)),
+        )))
+    }
+
+    pub(crate) fn with_runner(config: RemoteStorageConfig, runner: Box<ObjectRunner>) -> Self {
+        Self { config, runner }
+    }
+
+    fn bucket_url(&self) -> String {
+        format!(
+            "{}/{}",
+            self.config.endpoint.trim().trim_end_matches('/'),
+            percent_encode(self.config.bucket.trim(), false)
+        )
+    }
+
+    /// `prefix/` with surrounding slashes normalized, or empty for the bucket root.
+    fn base_key(&self) -> String {
+        let prefix = self.config.prefix.trim().trim_matches('/');
+        if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{prefix}/")
+        }
+    }
+
+    fn object_url(&self, session: &str, path: &str) -> io::Result<String> {
+        let key = format!(
+            "{}{}/{}",
+            self.base_key(),
+            checked_session_name(session)?,
+            checked_file_path(path)?
+        );
+        Ok(format!(
+            "{}/{}",
+            self.bucket_url(),
+            percent_encode(&key, true)
+        ))
+    }
+
+    fn send(&self, request: ObjectRequest) -> io::Result<Vec<u8>> {
+        (self.runner)(&request).map_err(io::Error::other)
+    }
+
+    /// Pages through ListObjectsV2 under `prefix`, returning the object keys and, with a
+    /// delimiter, the common prefixes one level down.
+    fn list_objects(&self, prefix: &str, delimited: bool) -> io::Result<ListedObjects> {
+        let mut listed = ListedObjects::default();
+        let mut continuation: Option<String> = None;
+        loop {
+            let mut url = format!(
+                "{}?list-type=2&prefix={}",
+                self.bucket_url(),
+                percent_encode(prefix, false)
+            );
+            if delimited {
+                url.push_str("&delimiter=%2F");
+            }
+            if let Some(token) = &continuation {
+                url.push_str(&format!(
+                    "&continuation-token={}",
+                    percent_encode(token, false)
+                ));
+            }
+            let body = self.send(ObjectRequest {
+                method: "GET",
+                url,
+                body: None,
+            })?;
+            let xml = String::from_utf8_lossy(&body);
+            for contents in xml_elements(&xml, "Contents") {
+                listed
+                    .keys
+                    .extend(xml_elements(contents, "Key").into_iter().map(xml_text));
+            }
+            for common in xml_elements(&xml, "CommonPrefixes") {
+                listed
+                    .prefixes
+                    .extend(xml_elements(common, "Prefix").into_iter().map(xml_text));
+            }
+            let truncated = xml_elements(&xml, "IsTruncated").first() == Some(&"true");
+            continuation = xml_elements(&xml, "NextContinuationToken")
+                .pop()
+                .map(xml_text);
+            if !truncated || continuation.is_none() {
+                return Ok(listed);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ListedObjects {
+    keys: Vec<String>,
+    prefixes: Vec<String>,
+}
+
+impl SessionStorage for RemoteObjectStorage {
+    fn describe(&self) -> String {
+        format!(
+            "s3://{}/{}",
+            self.config.bucket.trim(),
+            self.config.prefix.trim().trim_matches('/')
+        )
+        .trim_end_matches('/')
+        .to_string()
+    }
+
+    fn list_sessions(&self) -> io::Result<Vec<String>> {
+        let base = self.base_key();
+        let mut sessions = self
+            .list_objects(&base, true)?
+            .prefixes
+            .into_iter()
+            .filter_map(|prefix| {
+                let name = prefix.strip_prefix(&base)?.trim_end_matches('/');
+                (!name.is_empty()).then(|| name.to_string())
+            })
+            .collect::<Vec<_>>();
+        sessions.sort();
+        Ok(sessions)
+    }
+
+    fn list_files(&self, session: &str) -> io::Result<Vec<String>> {
+        let session_prefix = format!("{}{}/", self.base_key(), checked_session_name(session)?);
+        let mut files = self
+            .list_objects(&session_prefix, false)?
+            .keys
+            .into_iter()
+            .filter_map(|key| {
+                let path = key.strip_prefix(&session_prefix)?;
+                checked_file_path(path).ok().map(str::to_string)
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        Ok(files)
+    }
+
+    fn read_file(&self, session: &str, path: &str) -> io::Result<Vec<u8>> {
+        self.send(ObjectRequest {
+            method: "GET",
+            url: self.object_url(session, path)?,
+            body: None,
+        })
+    }
+
+    fn write_file(&self, session: &str, path: &str, bytes: &[u8]) -> io::Result<()> {
+        self.send(ObjectRequest {
+            method: "PUT",
+            url: self.object_url(session, path)?,
+            body: Some(bytes.to_vec()),
+        })
+        .map(|_| ())
+    }
+
+    /// A PUT replaces the whole object at once.
+    fn replace_file(&self, session: &str, path: &str, bytes: &[u8]) -> io::Result<()> {
+        self.write_file(session, path, bytes)
+    }
+
+    fn remove_file(&self, session: &str, path: &str) -> io::Result<()> {
+        self.send(ObjectRequest {
+            method: "DELETE",
+            url: self.object_url(session, path)?,
+            body: None,
+        })
+        .map(|_| ())
+    }
+}
+
+/// Copies every file of `session` from one storage to the other, except the lock file, which
+/// only means something on the machine that wrote it. Returns the number of files copied.
+pub fn copy_session(
+    from: &dyn SessionStorage,
+    to: &dyn SessionStorage,
+    session: &str,
+) -> io::Result<usize> {
+    let files = from
+        .list_files(session)?
+        .into_iter()
+        .filter(|path| path != LOCK_FILE_NAME)
+        .collect::<Vec<_>>();
+    if files.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no session named {session} in {}", from.describe()),
+        ));
+    }
+    for path in &files {
+        let bytes = from.read_file(session, path)?;
+        to.write_file(session, path, &bytes)?;
+    }
+    Ok(files.len())
+}
+
+/// One row of `bob storage list`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RemoteSessionEntry {
+    pub session: String,
+    /// Whether the local storage root has a session of the same name.
+    pub local: bool,
+}
+
+pub fn list_remote_sessions() -> io::Result<(String, Vec<RemoteSessionEntry>)> {
+    let remote = configured_remote()?;
+    let local = LocalDiskStorage::from_config()?.list_sessions()?;
+    let sessions = remote
+        .list_sessions()?
+        .into_iter()
+        .map(|session| RemoteSessionEntry {
+            local: local.contains(&session),
+            session,
+        })
+        .collect();
+    Ok((remote.describe(), sessions))
+}
+
+/// What a push uploaded; displays as the English summary line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushedSession {
+    pub name: String,
+    pub remote: String,
+    pub files: usize,
+}
+
+impl fmt::Display for PushedSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Pushed session {} to {} ({} files)",
+            self.name, self.remote, self.files
+        )
+    }
+}
+
+/// Uploads a session given by directory name or path; returns a summary line.
+pub fn push_session(session: &str) -> io::Result<String> {
+    let remote = configured_remote()?;
+    let (local, name) = local_session(session)?;
+    let files = copy_session(&local, &remote, &name)?;
+    let pushed = PushedSession {
+        name,
+        remote: remote.describe(),
+        files,
+    };
+    Ok(pushed.to_string())
+}
+
+/// Downloads a session into the local storage root and points it at `workspace`. Without one,
+/// a recorded workspace that exists on this machine is kept and anything else becomes `cwd`.
+pub fn pull_session(session: &str, workspace: Option<&Path>, cwd: &Path) -> io::Result<String> {
+    let remote = configured_remote()?;
+    let local = LocalDiskStorage::from_config()?;
+    let session_dir = local.session_dir(checked_session_name(session)?);
+    if let LockState::Held(owner) = session_lock::inspect(&session_dir) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "session {session} is open in {}; quit it before pulling",
+                owner.describe()
+            ),
+        ));
+    }
+    let copied = copy_session(&remote, &local, session)?;
+    let store = SessionStore::open_read_only(&session_dir)?;
+    let workspace = match workspace {
+        Some(workspace) => workspace.to_path_buf(),
+        None => store
+            .workspace_dir()
+            .filter(|recorded| recorded.is_dir())
+            .unwrap_or_else(|| cwd.to_path_buf()),
+    };
+    store.set_workspace(&workspace)?;
+    Ok(format!(
+        "Pulled session {session} from {} ({copied} files) into {}; /resume it from {}",
+        remote.describe(),
+        session_dir.display(),
+        workspace.display()
+    ))
+}
+
+/// Pushes `session_dir` when a remote store is configured with `push_on_exit`; `Ok(None)` when
+/// it is not.
+pub fn push_on_exit(session_dir: &Path) -> io::Result<Option<PushedSession>> {
+    if !load_global_remote_storage()?.push_on_exit {
+        return Ok(None);
+    }
+    let Some(remote) = RemoteObjectStorage::from_config()? else {
+        return Ok(None);
+    };
+    let (local, name) = local_session(&session_dir.to_string_lossy())?;
+    let files = copy_session(&local, &remote, &name)?;
+    Ok(Some(PushedSession {
+        name,
+        remote: remote.describe(),
+        files,
+    }))
+}
+
+/// Pushes sessions the user moved away from in the background, reporting each result as a
+/// chat line.
+#[derive(Debug)]
+pub struct RemoteMirror {
+    results_tx: Sender<String>,
+    results_rx: Receiver<String>,
+}
+
+impl Default for RemoteMirror {
+    fn default() -> Self {
+        let (results_tx, results_rx) = mpsc::channel();
+        Self {
+            results_tx,
+            results_rx,
+        }
+    }
+}
+
+impl RemoteMirror {
+    pub fn push_in_background(&self, session_dir: &Path, locale: Locale) {
+        let session_dir = session_dir.to_path_buf();
+        let results_tx = self.results_tx.clone();
+        thread::spawn(move || {
+            let message = match push_on_exit(&session_dir) {
+                Ok(Some(pushed)) => i18n::system(
+                    locale,
+                    Text::RemotePushed,
+                    &[
+                        ("session", &pushed.name),
+                        ("remote", &pushed.remote),
+                        ("count", &pushed.files),
+                    ],
+                ),
+                Ok(None) => return,
+                Err(err) => i18n::system(
+                    locale,
+                    Text::RemotePushFailed,
+                    &[("session", &session_dir.display()), ("err", &err)],
+                ),
+            };
+            let _ = results_tx.send(message);
+        });
+    }
+
+    pub fn drain_messages(&self) -> Vec<String> {
+        self.results_rx.try_iter().collect()
+    }
+}
+
+fn configured_remote() -> io::Result<RemoteObjectStorage> {
+    RemoteObjectStorage::from_config()?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "remote storage is not configured (set [storage.remote] endpoint in config.toml)",
+        )
+    })
+}
+
+/// A session path names its own root; a bare name is looked up under the storage root.
+fn local_session(session: &str) -> io::Result<(LocalDiskStorage, String)> {
+    let path = Path::new(session);
+    if path.is_dir()
+        && let (Some(parent), Some(name)) = (path.parent(), path.file_name())
+        && !parent.as_os_str().is_empty()
+    {
+        return Ok((
+            LocalDiskStorage::new(parent),
+            name.to_string_lossy().into_owned(),
+        ));
+    }
+    Ok((
+        LocalDiskStorage::from_config()?,
+        checked_session_name(session)?.to_string(),
+    ))
+}
+
+fn checked_session_name(session: &str) -> io::Result<&str> {
+    if session.contains('/') {
+        return Err(invalid_path(session));
+    }
+    checked_file_path(session)
+}
+
+/// Keeps names from a remote listing inside the session directory.
+fn checked_file_path(path: &str) -> io::Result<&str> {
+    let valid = !path.is_empty()
+        && !path.contains('\\')
+        && path.split('/').all(|part| !part.is_empty())
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if valid {
+        Ok(path)
+    } else {
+        Err(invalid_path(path))
+    }
+}
+
+fn invalid_path(path: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("`{path}` is not a valid session or file name"),
+    )
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters, keeping `/` in object keys.
+fn percent_encode(text: &str, keep_slash: bool) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric()
+            || matches!(byte, b'-' | b'_' | b'.' | b'~')
+            || (keep_slash && byte == b'/')
+        {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Raw contents of every `<tag>...</tag>` element. Enough for ListObjectsV2 responses, which
+/// have no attributes or CDATA on the elements read here.
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut values = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after_open = &rest[start + open.len()..];
+        let Some(end) = after_open.find(&close) else {
+            break;
+        };
+        values.push(&after_open[..end]);
+        rest = &after_open[end + close.len()..];
+    }
+    values
+}
+
+/// Decodes XML's predefined entities; `&amp;` goes last so `&amp;lt;` stays `&lt;`.
+fn xml_text(raw: &str) -> String {
+    raw.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Runs `curl` with SigV4 signing. The config goes over stdin so the keys stay off the command
+/// line; an upload goes through a file only the user can read, in a fresh private directory.
+fn run_signed_curl(user: &str, region: &str, request: &ObjectRequest) -> Result<Vec<u8>, String> {
+    let payload = request.body.as_deref().unwrap_or_default();
+    let mut config = format!(
+        "silent\nshow-error\nfail-with-body\nmax-time = {}\nrequest = {}\nurl = {}\nuser = {}\naws-sigv4 = {}\nheader = {}\n",
+        HTTP_TIMEOUT.as_secs(),
+        curl_quote(request.method),
+        curl_quote(&request.url),
+        curl_quote(user),
+        curl_quote(&format!("aws:amz:{region}:s3")),
+        curl_quote(&format!("x-amz-content-sha256: {}", sha256_hex(payload)))
+    );
+    let upload_dir = match &request.body {
+        Some(body) => {
+            let dir = write_upload_file(body)
+                .map_err(|err| format!("could not write the upload file: {err}"))?;
+            config.push_str(&format!(
+                "upload-file = {}\n",
+                curl_quote(&dir.join(UPLOAD_FILE_NAME).display().to_string())
+            ));
+            Some(dir)
+        }
+        None => None,
+    };
+    let result = run_curl_config(&config);
+    if let Some(dir) = upload_dir {
+        let _ = fs::remove_dir_all(dir);
+    }
+    result.map_err(|detail| format!("{} {} failed: {detail}", request.method, request.url))
+}
+
+const UPLOAD_FILE_NAME: &str = "body";
+
+/// Writes `body` to [`UPLOAD_FILE_NAME`] in a new owner-only directory under the temp dir and
+/// returns the directory. Both are created fresh, so nothing planted in the shared temp dir
+/// beforehand is written through or read back.
+fn write_upload_file(body: &[u8]) -> io::Result<PathBuf> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    for attempt in 0..16u8 {
+        let dir = std::env::temp_dir().join(format!(
+            "agentbob-upload-{}-{nanos}-{attempt}",
+            std::process::id()
+        ));
+        match create_private_dir(&dir) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+        let written = open_private_file(&dir.join(UPLOAD_FILE_NAME))
+            .and_then(|mut file| file.write_all(body));
+        if let Err(err) = written {
+            let _ = fs::remove_dir_all(&dir);
+            return Err(err);
+        }
+        return Ok(dir);
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "failed to allocate a temporary upload directory",
+    ))
+}
+
+#[cfg(unix)]
+fn create_private_dir(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    fs::DirBuilder::new().mode(0o700).create(path)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(path: &Path) -> io::Result<()> {
+    fs::create_dir(path)
+}
+
+#[cfg(unix)]
+fn open_private_file(path: &Path) -> io::Result<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn open_private_file(path: &Path) -> io::Result<fs::File> {
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+fn run_curl_config(config: &str) -> Result<Vec<u8>, String> {
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("could not run curl: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|err| format!("could not pass the request to curl: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("curl failed: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let body = String::from_utf8_lossy(&output.stdout);
+        return Err([stderr.trim(), body.trim()]
+            .into_iter()
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(": "));
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/session_storage_tests.rs"]
+mod tests;
@@ -1,14 +1,28 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::io;
-use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 
+use crate::agent_models::{CodexAgentKind, CodexModelProfile};
 use crate::artifact_io::{
-    ensure_default_metaagent_config, home_dir, read_text_file, write_text_file,
-    write_text_file_if_missing,
+    ensure_default_metaagent_config, home_dir, load_merged_metaagent_config_text, read_text_file,
+    write_text_file,
 };
+use crate::code_context::CodeContextPolicy;
+use crate::output_filters::OutputFilterConfig;
+use crate::project_info_scope::ProjectInfoScope;
+use crate::protected_paths::ProtectedPaths;
+use crate::services::BackendFailoverTracker;
+use crate::session_storage::{LocalDiskStorage, RemoteStorageConfig, SessionStorage};
+use crate::speed_profile::SpeedProfile;
+use crate::telemetry;
+use crate::test_runners::TestRunnerSpec;
+use crate::workflow::{FinalAuditPolicy, TaskSplitLimits, VerifyGate};
+use crate::worker_logs::WorkerLogPolicy;
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -20,6 +34,9 @@ pub struct MetaAgentConfig {
 #[serde(default)]
 pub struct StorageConfig {
     pub root_dir: String,
+    /// Offer to continue a recent session of the workspace before starting a new one.
+    pub offer_recent_session: bool,
+    pub remote: RemoteStorageConfig,
 }
 
 impl Default for MetaAgentConfig {
@@ -34,11 +51,13 @@ impl Default for StorageConfig {
     fn default() -> Self {
         Self {
             root_dir: "~/.agentbob/sessions".to_string(),
+            offer_recent_session: true,
+            remote: RemoteStorageConfig::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PlannerTaskFileEntry {
     #[serde(deserialize_with = "deserialize_id_to_string")]
     pub id: String,
@@ -54,6 +73,32 @@ pub struct PlannerTaskFileEntry {
     #[serde(default, deserialize_with = "deserialize_optional_id_to_string")]
     pub parent_id: Option<String>,
     pub order: Option<u32>,
+    /// Linked tracker ticket, e.g. `linear:ENG-123` or `jira:PROJ-42`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_ref: Option<String>,
+    /// Workspace-relative directory the worker agents of this top-level task run in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workdir: Option<String>,
+    /// Verifiable statements of done for a top-level task. Its implementor addresses each one
+    /// and its auditor must report each one as met before the audit can pass.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acceptance_criteria: Vec<String>,
+    /// Installed plugin a `plugin` task runs as a workflow step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+    /// Size (`S`, `M`, `L`) or hours (e.g. `4h`) the master estimates for a top-level task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<String>,
+    /// Command a top-level task runs after its tests pass, e.g. `make e2e`; overrides
+    /// `[workflow] verify`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify: Option<String>,
+    /// What the user must answer before a `blocked` top-level task can run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+    /// Answers given with `/unblock`, shown to the task's workers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_answers: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -66,6 +111,7 @@ pub enum PlannerTaskKindFile {
     TestWriter,
     TestRunner,
     FinalAudit,
+    Plugin,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -76,6 +122,8 @@ pub enum PlannerTaskStatusFile {
     InProgress,
     NeedsChanges,
     Done,
+    /// Waiting for the user to answer `blocked_reason` with `/unblock`.
+    Blocked,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -84,6 +132,10 @@ pub struct PlannerTaskDocFileEntry {
     pub url: String,
     #[serde(default)]
     pub summary: String,
+    /// Sanitized text copy of the page, relative to the session directory
+    /// (`docs/<task-id>/...`); workers get it verbatim instead of the link.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,6 +166,18 @@ pub struct SessionListEntry {
     pub last_used_epoch_secs: u64,
 }
 
+/// What the resume picker shows about a session without opening it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionPreview {
+    /// Top-level tasks marked done, out of `tasks_total`.
+    pub tasks_done: usize,
+    pub tasks_total: usize,
+    /// Latest change to the session's tasks, planner, or event log.
+    pub last_activity_epoch_secs: Option<u64>,
+    /// Leading non-blank lines of planner.md.
+    pub planner_head: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SessionMetaFile {
     pub title: String,
@@ -122,6 +186,101 @@ pub struct SessionMetaFile {
     pub stack_description: String,
     #[serde(default)]
     pub test_command: Option<String>,
+    /// Stack details the project-info agent fills in next to `stack_description`; workers
+    /// get them as a list instead of having to read project-info.md.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frameworks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_command: Option<String>,
+    /// Typed deterministic runner used instead of running `test_command` through bash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_runner: Option<TestRunnerSpec>,
+    /// Style preambles keyed by agent slot (`master`, `worker_implementor`, ...), added to
+    /// that agent's session intro or worker prompts.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub personas: BTreeMap<String, String>,
+    /// Model each agent slot was routed to when the session was created, by config key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, CodexModelProfile>,
+    /// Set by `/pin-models`: agents keep `models` even when config.toml now routes elsewhere.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub models_pinned: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// Per-session settings carried over from the template a session was created from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SessionTemplateFile {
+    pub name: String,
+    pub test_command: Option<String>,
+    pub audit_rubric: String,
+}
+
+/// Toggles the user changes from inside a session, such as `/review on`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SessionSettingsFile {
+    pub review_changes: bool,
+    /// Model profiles pinned with `/task-model`, keyed by top-level task id.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub task_models: BTreeMap<String, String>,
+    /// Profile chosen with `/speed`.
+    #[serde(skip_serializing_if = "SpeedProfile::is_standard")]
+    pub speed: SpeedProfile,
+}
+
+/// Where the user left the panes in a session, restored when switching back to it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SessionUiStateFile {
+    pub left_top_scroll: u16,
+    pub right_scroll: u16,
+    /// Character offset of the planner editor cursor.
+    pub planner_cursor: usize,
+    /// Tasks whose details were expanded in the task list.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub expanded_task_keys: BTreeSet<String>,
+}
+
+/// Master report prompts not yet answered, kept so they are delivered after a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MasterReportQueueFile {
+    /// Prompt sent to the master report agent whose reply has not arrived yet.
+    pub in_flight: Option<String>,
+    pub pending: Vec<String>,
+}
+
+/// Paragraph the master report agent wrote about a finished top-level task, shown by
+/// `/summaries`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskSummaryFileEntry {
+    pub top_task_id: String,
+    pub top_task_title: String,
+    pub summary: String,
+    pub created_at_epoch_secs: u64,
+}
+
+/// Run state left by a `/quit` or SIGTERM mid-execution, replayed by the next `/resume`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct InterruptedRunFile {
+    /// Execution was enabled with work running or queued.
+    pub execution_running: bool,
+    /// The worker job that was stopped and reset to pending, if one was running.
+    pub interrupted_job: Option<String>,
+    /// Chat messages still queued behind the master or task check.
+    pub queued_messages: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -144,13 +303,17 @@ pub struct SessionStore {
     task_fails_file: PathBuf,
     project_info_file: PathBuf,
     session_meta_file: PathBuf,
+    template_file: PathBuf,
+    task_split_proposal_file: PathBuf,
     metadata_file: PathBuf,
+    /// Where the session's files are read and written; `session` names the directory in it.
+    storage: Arc<dyn SessionStorage + Send + Sync>,
+    session: String,
 }
 
 impl SessionStore {
     pub fn initialize(cwd: &Path) -> io::Result<Self> {
-        let config = load_config()?;
-        let root_dir = expand_home(&config.storage.root_dir)?;
+        let root_dir = storage_root_dir()?;
         fs::create_dir_all(&root_dir)?;
 
         let workspace_name = cwd
@@ -163,16 +326,7 @@ impl SessionStore {
             .unwrap_or(0);
         let session_dir = create_unique_session_dir(&root_dir, now_secs, workspace_name)?;
 
-        let store = Self {
-            tasks_file: session_dir.join("tasks.json"),
-            planner_file: session_dir.join("planner.md"),
-            context_file: session_dir.join("rolling_context.json"),
-            task_fails_file: session_dir.join("task-fails.json"),
-            project_info_file: session_dir.join("project-info.md"),
-            session_meta_file: session_dir.join("meta.json"),
-            metadata_file: session_dir.join("metadata.json"),
-            session_dir,
-        };
+        let store = Self::from_session_dir(session_dir);
         store.bootstrap_files(cwd, now_secs)?;
         store.touch_last_used(now_secs)?;
         Ok(store)
@@ -191,13 +345,72 @@ impl SessionStore {
         Ok(store)
     }
 
+    /// Opens a session for observation without creating, bootstrapping, or touching any files.
+    pub fn open_read_only(session_dir: impl AsRef<Path>) -> io::Result<Self> {
+        let session_dir = session_dir.as_ref().to_path_buf();
+        if !session_dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("session directory not found: {}", session_dir.display()),
+            ));
+        }
+        let store = Self::from_session_dir(session_dir);
+        if !store.tasks_file.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "not a session directory (missing tasks.json): {}",
+                    store.session_dir.display()
+                ),
+            ));
+        }
+        Ok(store)
+    }
+
     pub fn list_sessions() -> io::Result<Vec<SessionListEntry>> {
-        let config = load_config()?;
-        let root_dir = expand_home(&config.storage.root_dir)?;
+        let root_dir = storage_root_dir()?;
         fs::create_dir_all(&root_dir)?;
         list_sessions_in_root(&root_dir)
     }
 
+    /// Reads a listed session's preview; missing or unreadable files leave their part empty.
+    pub fn read_preview(session_dir: &Path, planner_lines: usize) -> SessionPreview {
+        let store = Self::from_session_dir(session_dir.to_path_buf());
+        let top_tasks = store
+            .read_tasks()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|task| task.parent_id.is_none())
+            .collect::<Vec<_>>();
+        let last_activity_epoch_secs = [
+            store.tasks_file.clone(),
+            store.planner_file.clone(),
+            store.events_file(),
+        ]
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .filter_map(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|since| since.as_secs())
+        .max();
+        SessionPreview {
+            tasks_done: top_tasks
+                .iter()
+                .filter(|task| task.status == PlannerTaskStatusFile::Done)
+                .count(),
+            tasks_total: top_tasks.len(),
+            last_activity_epoch_secs,
+            planner_head: store
+                .read_planner_markdown()
+                .unwrap_or_default()
+                .lines()
+                .map(str::trim_end)
+                .filter(|line| !line.trim().is_empty())
+                .take(planner_lines)
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
     pub fn session_dir(&self) -> &Path {
         &self.session_dir
     }
@@ -207,7 +420,7 @@ impl SessionStore {
     }
 
     pub fn read_tasks(&self) -> io::Result<Vec<PlannerTaskFileEntry>> {
-        let text = read_text_file(&self.tasks_file)?;
+        let text = self.read_session_file(&self.tasks_file)?;
         let parsed = serde_json::from_str::<Vec<PlannerTaskFileEntry>>(&text)
             .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
         Ok(parsed)
@@ -218,20 +431,20 @@ impl SessionStore {
     }
 
     pub fn read_planner_markdown(&self) -> io::Result<String> {
-        read_text_file(&self.planner_file)
+        self.read_session_file(&self.planner_file)
     }
 
     pub fn write_planner_markdown(&self, markdown: &str) -> io::Result<()> {
-        fs::write(&self.planner_file, markdown)
+        self.write_session_file(&self.planner_file, markdown)
     }
 
     pub fn write_rolling_context(&self, entries: &[String]) -> io::Result<()> {
         let text = serde_json::to_string_pretty(entries).map_err(io::Error::other)?;
-        write_text_file(&self.context_file, &text)
+        self.write_session_file(&self.context_file, &text)
     }
 
     pub fn read_rolling_context(&self) -> io::Result<Vec<String>> {
-        let text = read_text_file(&self.context_file)?;
+        let text = self.read_session_file(&self.context_file)?;
         let parsed = serde_json::from_str::<Vec<String>>(&text)
             .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
         Ok(parsed)
@@ -242,7 +455,7 @@ impl SessionStore {
     }
 
     pub fn read_task_fails(&self) -> io::Result<Vec<TaskFailFileEntry>> {
-        let text = read_text_file(&self.task_fails_file)?;
+        let text = self.read_session_file(&self.task_fails_file)?;
         let parsed = serde_json::from_str::<Vec<TaskFailFileEntry>>(&text)
             .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
         Ok(parsed)
@@ -255,7 +468,7 @@ impl SessionStore {
         let mut existing = self.read_task_fails().unwrap_or_default();
         existing.extend_from_slice(entries);
         let text = serde_json::to_string_pretty(&existing).map_err(io::Error::other)?;
-        write_text_file(&self.task_fails_file, &text)
+        self.write_session_file(&self.task_fails_file, &text)
     }
 
     pub fn project_info_file(&self) -> &Path {
@@ -267,52 +480,393 @@ impl SessionStore {
     }
 
     pub fn read_project_info(&self) -> io::Result<String> {
-        read_text_file(&self.project_info_file)
+        self.read_session_file(&self.project_info_file)
     }
 
     pub fn write_project_info(&self, markdown: &str) -> io::Result<()> {
-        write_text_file(&self.project_info_file, markdown)
+        self.write_session_file(&self.project_info_file, markdown)
     }
 
     pub fn read_session_meta(&self) -> io::Result<SessionMetaFile> {
-        let text = read_text_file(&self.session_meta_file)?;
+        let text = self.read_session_file(&self.session_meta_file)?;
         serde_json::from_str::<SessionMetaFile>(&text)
             .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
     }
 
+    /// Records `models` in meta.json unless it already lists the session's models. Returns
+    /// false without writing when it does or meta.json has not been written yet.
+    pub fn record_session_models(
+        &self,
+        models: &BTreeMap<String, CodexModelProfile>,
+    ) -> io::Result<bool> {
+        let meta = match self.read_session_meta() {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        if !meta.models.is_empty() {
+            return Ok(false);
+        }
+        let models = serde_json::to_value(models).map_err(io::Error::other)?;
+        self.update_session_meta(|fields| {
+            fields.insert("models".to_string(), models);
+        })?;
+        Ok(true)
+    }
+
+    pub fn set_session_models_pinned(&self, pinned: bool) -> io::Result<()> {
+        self.update_session_meta(|fields| {
+            if pinned {
+                fields.insert("models_pinned".to_string(), serde_json::Value::Bool(true));
+            } else {
+                fields.remove("models_pinned");
+            }
+        })
+    }
+
+    /// Rewrites fields of meta.json in place, keeping any the meta agent wrote that
+    /// [`SessionMetaFile`] does not know.
+    fn update_session_meta(
+        &self,
+        update: impl FnOnce(&mut serde_json::Map<String, serde_json::Value>),
+    ) -> io::Result<()> {
+        let text = self.read_session_file(&self.session_meta_file)?;
+        let mut fields = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        update(&mut fields);
+        let text = serde_json::to_string_pretty(&fields).map_err(io::Error::other)?;
+        self.write_session_file(&self.session_meta_file, &text)
+    }
+
+    /// Workspace the session was created in, from `metadata.json`.
+    pub fn workspace_dir(&self) -> Option<PathBuf> {
+        self.read_metadata()
+            .ok()
+            .map(|metadata| metadata.workspace)
+            .filter(|workspace| !workspace.trim().is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Points the session at another workspace, e.g. after pulling it onto a machine where the
+    /// project is checked out elsewhere.
+    pub fn set_workspace(&self, workspace: &Path) -> io::Result<()> {
+        let mut metadata = self.read_metadata().unwrap_or_default();
+        metadata.workspace = workspace.to_string_lossy().to_string();
+        let text = serde_json::to_string_pretty(&metadata).map_err(io::Error::other)?;
+        self.write_session_file(&self.metadata_file, &text)
+    }
+
+    /// Persona the session meta sets for `kind`, if any. A missing or unreadable meta file
+    /// means no persona.
+    pub fn agent_persona(&self, kind: CodexAgentKind) -> Option<String> {
+        let mut meta = self.read_session_meta().ok()?;
+        meta.personas
+            .remove(kind.config_key())
+            .map(|persona| persona.trim().to_string())
+            .filter(|persona| !persona.is_empty())
+    }
+
+    /// The session's stack as one `- Field: value` line per field the meta sets, or `None`
+    /// when it sets none of them.
+    pub fn project_stack(&self) -> Option<String> {
+        let meta = self.read_session_meta().ok()?;
+        let list = |values: &[String]| {
+            let values = values
+                .iter()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .collect::<Vec<_>>();
+            (!values.is_empty()).then(|| values.join(", "))
+        };
+        let text = |value: Option<&str>| {
+            value
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let command =
+            |value: &Option<String>| text(value.as_deref()).map(|value| format!("`{value}`"));
+        let lines = [
+            ("Stack", text(Some(&meta.stack_description))),
+            ("Languages", list(&meta.languages)),
+            ("Frameworks", list(&meta.frameworks)),
+            ("Package manager", text(meta.package_manager.as_deref())),
+            ("Build command", command(&meta.build_command)),
+            ("Run command", command(&meta.run_command)),
+            ("Test command", command(&meta.test_command)),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| Some(format!("- {field}: {}", value?)))
+        .collect::<Vec<_>>();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    pub fn write_tasks(&self, tasks: &[PlannerTaskFileEntry]) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(tasks).map_err(io::Error::other)?;
+        self.write_session_file(&self.tasks_file, &text)
+    }
+
+    /// tasks.json as it is on disk, for comparing with and restoring an earlier version.
+    pub fn read_tasks_text(&self) -> io::Result<String> {
+        self.read_session_file(&self.tasks_file)
+    }
+
+    /// Replaces tasks.json with `text` unchanged, e.g. to put back what
+    /// [`Self::read_tasks_text`] returned; readers never see a half-written file.
+    pub fn write_tasks_text(&self, text: &str) -> io::Result<()> {
+        self.replace_session_file(&self.tasks_file, text)
+    }
+
+    /// Like [`Self::write_tasks`], but a reader never sees a half-written tasks.json.
+    pub fn write_tasks_atomic(&self, tasks: &[PlannerTaskFileEntry]) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(tasks).map_err(io::Error::other)?;
+        self.replace_session_file(&self.tasks_file, &text)
+    }
+
+    /// Reads the template settings; sessions not created from a template have none.
+    pub fn read_session_template(&self) -> io::Result<Option<SessionTemplateFile>> {
+        let text = match self.read_session_file(&self.template_file) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str::<SessionTemplateFile>(&text)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn write_session_template(&self, template: &SessionTemplateFile) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(template).map_err(io::Error::other)?;
+        self.write_session_file(&self.template_file, &text)
+    }
+
+    /// Reads `settings.json`; a session that never changed a setting gets the defaults.
+    pub fn read_session_settings(&self) -> io::Result<SessionSettingsFile> {
+        let text = match self.read_session_file(&self.session_dir.join("settings.json")) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(SessionSettingsFile::default());
+            }
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str::<SessionSettingsFile>(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn write_session_settings(&self, settings: &SessionSettingsFile) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(settings).map_err(io::Error::other)?;
+        self.write_session_file(&self.session_dir.join("settings.json"), &text)
+    }
+
+    /// Reads `ui-state.json`; a session never switched away from starts at the defaults.
+    pub fn read_ui_state(&self) -> io::Result<SessionUiStateFile> {
+        let text = match self.read_session_file(&self.session_dir.join("ui-state.json")) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(SessionUiStateFile::default());
+            }
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str::<SessionUiStateFile>(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn write_ui_state(&self, state: &SessionUiStateFile) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(state).map_err(io::Error::other)?;
+        self.write_session_file(&self.session_dir.join("ui-state.json"), &text)
+    }
+
+    /// Reads `master-report-queue.json`; sessions with nothing queued have no file.
+    pub fn read_master_report_queue(&self) -> io::Result<MasterReportQueueFile> {
+        let text = match self.read_session_file(&self.session_dir.join("master-report-queue.json"))
+        {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(MasterReportQueueFile::default());
+            }
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str::<MasterReportQueueFile>(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn write_master_report_queue(&self, queue: &MasterReportQueueFile) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(queue).map_err(io::Error::other)?;
+        self.write_session_file(&self.session_dir.join("master-report-queue.json"), &text)
+    }
+
+    /// Reads `task-summaries.json`; sessions without a finished task have no file.
+    pub fn read_task_summaries(&self) -> io::Result<Vec<TaskSummaryFileEntry>> {
+        let text = match self.read_session_file(&self.session_dir.join("task-summaries.json")) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str::<Vec<TaskSummaryFileEntry>>(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Saves a task's summary, replacing the one written when it last finished.
+    pub fn upsert_task_summary(&self, entry: TaskSummaryFileEntry) -> io::Result<()> {
+        let mut summaries = self.read_task_summaries()?;
+        summaries.retain(|existing| existing.top_task_id != entry.top_task_id);
+        summaries.push(entry);
+        let text = serde_json::to_string_pretty(&summaries).map_err(io::Error::other)?;
+        self.write_session_file(&self.session_dir.join("task-summaries.json"), &text)
+    }
+
+    /// Reads `interrupted-run.json`, present only after a shutdown mid-execution.
+    pub fn read_interrupted_run(&self) -> io::Result<Option<InterruptedRunFile>> {
+        let text = match self.read_session_file(&self.session_dir.join("interrupted-run.json")) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str::<InterruptedRunFile>(&text)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn write_interrupted_run(&self, run: &InterruptedRunFile) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(run).map_err(io::Error::other)?;
+        self.write_session_file(&self.session_dir.join("interrupted-run.json"), &text)
+    }
+
+    pub fn clear_interrupted_run(&self) -> io::Result<()> {
+        self.remove_session_file(&self.session_dir.join("interrupted-run.json"))
+    }
+
+    /// Copy of tasks.json the master edits in safe mode until `/accept-tasks` applies it.
+    pub fn staged_tasks_file(&self) -> PathBuf {
+        self.session_dir.join("tasks.staged.json")
+    }
+
+    pub fn read_staged_tasks(&self) -> io::Result<Vec<PlannerTaskFileEntry>> {
+        let text = self.read_session_file(&self.staged_tasks_file())?;
+        serde_json::from_str::<Vec<PlannerTaskFileEntry>>(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn write_staged_tasks(&self, tasks: &[PlannerTaskFileEntry]) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(tasks).map_err(io::Error::other)?;
+        self.write_session_file(&self.staged_tasks_file(), &text)
+    }
+
+    /// Starts `tasks.staged.json` from tasks.json unless changes are already staged, so the
+    /// master edits the staged copy in safe mode.
+    pub fn begin_staged_tasks(&self) -> io::Result<()> {
+        if self.staged_tasks_file().exists() {
+            return Ok(());
+        }
+        let text = self.read_session_file(self.tasks_file())?;
+        self.write_session_file(&self.staged_tasks_file(), &text)
+    }
+
+    pub fn clear_staged_tasks(&self) -> io::Result<()> {
+        self.remove_session_file(&self.staged_tasks_file())
+    }
+
+    /// Per-job worker output logs (`logs/<task-id>/<role>-pass<n>.log`).
+    pub fn logs_dir(&self) -> PathBuf {
+        self.session_dir.join("logs")
+    }
+
+    /// Embedding index of workspace file chunks searched by `api session search-code`.
+    pub fn code_index_file(&self) -> PathBuf {
+        self.session_dir.join("code-index.json")
+    }
+
+    /// Append-only `events.jsonl` read by `api events subscribe`.
+    pub fn events_file(&self) -> PathBuf {
+        self.session_dir.join("events.jsonl")
+    }
+
+    /// Hash-chained `audit-trail.jsonl` written by `audit_trail::record`.
+    pub fn audit_trail_file(&self) -> PathBuf {
+        self.session_dir.join("audit-trail.jsonl")
+    }
+
+    /// `tasks-changelog.jsonl`: each agent run's changes to tasks.json, written by
+    /// `tasks_changelog::record`.
+    pub fn tasks_changelog_file(&self) -> PathBuf {
+        self.session_dir.join("tasks-changelog.jsonl")
+    }
+
+    /// Questions from master `QUESTIONS` blocks and the user's answers to them.
+    pub fn clarifications_file(&self) -> PathBuf {
+        self.session_dir.join("clarifications.md")
+    }
+
+    pub fn task_split_proposal_file(&self) -> &Path {
+        &self.task_split_proposal_file
+    }
+
+    /// Reads the task list proposed by the task splitter, if it wrote one.
+    pub fn read_task_split_proposal(&self) -> io::Result<Option<Vec<PlannerTaskFileEntry>>> {
+        let text = match self.read_session_file(&self.task_split_proposal_file) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str::<Vec<PlannerTaskFileEntry>>(&text)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn clear_task_split_proposal(&self) -> io::Result<()> {
+        self.remove_session_file(&self.task_split_proposal_file)
+    }
+
     fn bootstrap_files(&self, cwd: &Path, now_secs: u64) -> io::Result<()> {
-        write_text_file_if_missing(&self.tasks_file, "[]\n")?;
-        write_text_file_if_missing(&self.planner_file, "")?;
-        write_text_file_if_missing(&self.context_file, "[]\n")?;
-        write_text_file_if_missing(&self.task_fails_file, "[]\n")?;
-        write_text_file_if_missing(&self.project_info_file, "")?;
-        if write_text_file_if_missing(&self.metadata_file, "")? {
+        self.write_session_file_if_missing(&self.tasks_file, "[]\n")?;
+        self.write_session_file_if_missing(&self.planner_file, "")?;
+        self.write_session_file_if_missing(&self.context_file, "[]\n")?;
+        self.write_session_file_if_missing(&self.task_fails_file, "[]\n")?;
+        self.write_session_file_if_missing(&self.project_info_file, "")?;
+        if self.write_session_file_if_missing(&self.metadata_file, "")? {
             let metadata = SessionMetadata {
                 workspace: cwd.to_string_lossy().to_string(),
                 created_at_epoch_secs: now_secs,
                 last_used_epoch_secs: now_secs,
             };
             let text = serde_json::to_string_pretty(&metadata).map_err(io::Error::other)?;
-            write_text_file(&self.metadata_file, &text)?;
+            self.write_session_file(&self.metadata_file, &text)?;
         }
         Ok(())
     }
 
     fn from_session_dir(session_dir: PathBuf) -> Self {
+        // A bare `.` has no name of its own; its absolute form does.
+        let named_dir = match session_dir.file_name() {
+            Some(_) => session_dir.clone(),
+            None => std::path::absolute(&session_dir).unwrap_or_else(|_| session_dir.clone()),
+        };
+        let root = named_dir
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let session = named_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
         Self {
+            storage: Arc::new(LocalDiskStorage::new(root)),
+            session,
             tasks_file: session_dir.join("tasks.json"),
             planner_file: session_dir.join("planner.md"),
             context_file: session_dir.join("rolling_context.json"),
             task_fails_file: session_dir.join("task-fails.json"),
             project_info_file: session_dir.join("project-info.md"),
             session_meta_file: session_dir.join("meta.json"),
+            template_file: session_dir.join("template.json"),
+            task_split_proposal_file: session_dir.join("task-split-proposal.json"),
             metadata_file: session_dir.join("metadata.json"),
             session_dir,
         }
     }
 
     fn touch_last_used(&self, now_secs: u64) -> io::Result<()> {
-        let mut metadata = read_metadata_file(&self.metadata_file).unwrap_or_default();
+        let mut metadata = self.read_metadata().unwrap_or_default();
         if metadata.workspace.is_empty() {
             metadata.workspace = self.session_dir.to_string_lossy().to_string();
         }
@@ -321,13 +875,118 @@ impl SessionStore {
         }
         metadata.last_used_epoch_secs = now_secs;
         let text = serde_json::to_string_pretty(&metadata).map_err(io::Error::other)?;
-        write_text_file(&self.metadata_file, &text)
+        self.write_session_file(&self.metadata_file, &text)
+    }
+
+    fn read_metadata(&self) -> io::Result<SessionMetadata> {
+        let text = self.read_session_file(&self.metadata_file)?;
+        serde_json::from_str::<SessionMetadata>(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// The `/`-separated name `storage` knows a file under the session directory by.
+    fn storage_path(&self, path: &Path) -> io::Result<String> {
+        let relative = path.strip_prefix(&self.session_dir).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is outside the session directory", path.display()),
+            )
+        })?;
+        Ok(relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+
+    /// Reads a session artifact under a `session.read` trace span.
+    fn read_session_file(&self, path: &Path) -> io::Result<String> {
+        let mut span = telemetry::span("session.read");
+        span.set_attribute("file.name", file_name_attribute(path));
+        let result = self
+            .storage_path(path)
+            .and_then(|name| self.storage.read_file(&self.session, &name))
+            .and_then(|bytes| {
+                String::from_utf8(bytes)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            });
+        match &result {
+            Ok(text) => span.set_attribute("file.bytes", text.len()),
+            Err(err) => span.set_error(err.to_string()),
+        }
+        result
+    }
+
+    /// Writes a session artifact under a `session.write` trace span.
+    fn write_session_file(&self, path: &Path, text: &str) -> io::Result<()> {
+        self.traced_write(path, text, |name| {
+            self.storage
+                .write_file(&self.session, name, text.as_bytes())
+        })
+    }
+
+    /// Like [`Self::write_session_file`], but through the storage's atomic replace.
+    fn replace_session_file(&self, path: &Path, text: &str) -> io::Result<()> {
+        self.traced_write(path, text, |name| {
+            self.storage
+                .replace_file(&self.session, name, text.as_bytes())
+        })
+    }
+
+    fn traced_write(
+        &self,
+        path: &Path,
+        text: &str,
+        write: impl FnOnce(&str) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut span = telemetry::span("session.write");
+        span.set_attribute("file.name", file_name_attribute(path));
+        span.set_attribute("file.bytes", text.len());
+        let result = self.storage_path(path).and_then(|name| write(&name));
+        if let Err(err) = &result {
+            span.set_error(err.to_string());
+        }
+        result
+    }
+
+    /// Writes `text` unless the file is already there; returns whether it wrote.
+    fn write_session_file_if_missing(&self, path: &Path, text: &str) -> io::Result<bool> {
+        let name = self.storage_path(path)?;
+        match self.storage.read_file(&self.session, &name) {
+            Ok(_) => Ok(false),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                self.write_session_file(path, text).map(|()| true)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn remove_session_file(&self, path: &Path) -> io::Result<()> {
+        self.storage
+            .remove_file(&self.session, &self.storage_path(path)?)
     }
 }
 
+/// `[storage] offer_recent_session`. On by default.
+pub fn load_global_offer_recent_session() -> io::Result<bool> {
+    Ok(load_config()?.storage.offer_recent_session)
+}
+
+/// `[storage.remote]`; its endpoint is empty unless sessions are mirrored to a bucket.
+pub fn load_global_remote_storage() -> io::Result<RemoteStorageConfig> {
+    Ok(load_config()?.storage.remote)
+}
+
+/// `[storage] root_dir` with `~` expanded.
+pub fn storage_root_dir() -> io::Result<PathBuf> {
+    expand_home(&load_config()?.storage.root_dir)
+}
+
 pub fn load_global_tests_mode_enabled() -> io::Result<bool> {
-    let config_file = ensure_default_metaagent_config()?;
-    let text = read_text_file(&config_file)?;
+    let text = load_merged_metaagent_config_text()?;
     tests_mode_enabled_from_toml(&text)
 }
 
@@ -339,6 +998,341 @@ pub fn persist_global_tests_mode_enabled(enabled: bool) -> io::Result<PathBuf> {
     Ok(config_file)
 }
 
+pub fn load_global_final_audit_policy() -> io::Result<FinalAuditPolicy> {
+    let text = load_merged_metaagent_config_text()?;
+    final_audit_policy_from_toml(&text)
+}
+
+fn final_audit_policy_from_toml(text: &str) -> io::Result<FinalAuditPolicy> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let Some(value) = parsed
+        .get("workflow")
+        .and_then(toml::Value::as_table)
+        .and_then(|table| table.get("final_audit"))
+    else {
+        return Ok(FinalAuditPolicy::default());
+    };
+    value
+        .as_str()
+        .and_then(FinalAuditPolicy::parse)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "workflow.final_audit must be \"auto\", \"manual\", or \"never\" (got {value})"
+                ),
+            )
+        })
+}
+
+/// `[workflow] rerun_failed_tests`: re-run a failing deterministic test command once to
+/// detect flaky tests. Off by default.
+pub fn load_global_rerun_failed_tests() -> io::Result<bool> {
+    let text = load_merged_metaagent_config_text()?;
+    rerun_failed_tests_from_toml(&text)
+}
+
+fn rerun_failed_tests_from_toml(text: &str) -> io::Result<bool> {
+    workflow_flag_from_toml(text, "rerun_failed_tests")
+}
+
+/// `[workflow] docs_update_task`: append a README/CHANGELOG update task when a plan first
+/// materializes. Off by default.
+pub fn load_global_docs_update_task() -> io::Result<bool> {
+    let text = load_merged_metaagent_config_text()?;
+    docs_update_task_from_toml(&text)
+}
+
+fn docs_update_task_from_toml(text: &str) -> io::Result<bool> {
+    workflow_flag_from_toml(text, "docs_update_task")
+}
+
+/// `[workflow] protected_paths`: globs implementor passes may not touch. Empty by default.
+pub fn load_global_protected_paths() -> io::Result<ProtectedPaths> {
+    let text = load_merged_metaagent_config_text()?;
+    protected_paths_from_toml(&text)
+}
+
+fn protected_paths_from_toml(text: &str) -> io::Result<ProtectedPaths> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let Some(value) = parsed
+        .get("workflow")
+        .and_then(toml::Value::as_table)
+        .and_then(|table| table.get("protected_paths"))
+    else {
+        return Ok(ProtectedPaths::default());
+    };
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("workflow.protected_paths must be a list of glob strings (got {value})"),
+        )
+    };
+    let patterns = value
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|pattern| pattern.as_str().map(str::to_string).ok_or_else(invalid))
+        .collect::<io::Result<Vec<_>>>()?;
+    Ok(ProtectedPaths::new(patterns))
+}
+
+/// Boolean `[workflow]` key; `false` when it is absent.
+fn workflow_flag_from_toml(text: &str, key: &str) -> io::Result<bool> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let Some(value) = parsed
+        .get("workflow")
+        .and_then(toml::Value::as_table)
+        .and_then(|table| table.get(key))
+    else {
+        return Ok(false);
+    };
+    value.as_bool().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("workflow.{key} must be true or false (got {value})"),
+        )
+    })
+}
+
+/// `[workflow] verify` and `verify_retries`: the command run after an implementation branch's
+/// tests, and how many failed runs go back to the implementor. No command by default.
+pub fn load_global_verify_gate() -> io::Result<VerifyGate> {
+    let text = load_merged_metaagent_config_text()?;
+    verify_gate_from_toml(&text)
+}
+
+fn verify_gate_from_toml(text: &str) -> io::Result<VerifyGate> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let defaults = VerifyGate::default();
+    let command = match parsed
+        .get("workflow")
+        .and_then(toml::Value::as_table)
+        .and_then(|table| table.get("verify"))
+    {
+        None => None,
+        Some(value) => {
+            let command = value.as_str().map(str::trim).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("workflow.verify must be a command string (got {value})"),
+                )
+            })?;
+            (!command.is_empty()).then(|| command.to_string())
+        }
+    };
+    let retries = non_negative_config_integer(
+        &parsed,
+        "workflow",
+        "verify_retries",
+        u64::from(defaults.max_retries),
+    )?;
+    let max_retries = u8::try_from(retries).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("workflow.verify_retries must be at most 255 (got {retries})"),
+        )
+    })?;
+    Ok(VerifyGate {
+        command,
+        max_retries,
+    })
+}
+
+/// `[workflow] stall_after_secs`: how long a running job may go without output before the
+/// status bar flags it as possibly stalled. 0 turns the check off.
+pub fn load_global_stall_after() -> io::Result<Option<Duration>> {
+    let text = load_merged_metaagent_config_text()?;
+    stall_after_from_toml(&text)
+}
+
+fn stall_after_from_toml(text: &str) -> io::Result<Option<Duration>> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let secs = non_negative_config_integer(&parsed, "workflow", "stall_after_secs", 300)?;
+    Ok((secs > 0).then(|| Duration::from_secs(secs)))
+}
+
+pub fn load_global_task_split_limits() -> io::Result<TaskSplitLimits> {
+    let text = load_merged_metaagent_config_text()?;
+    task_split_limits_from_toml(&text)
+}
+
+fn task_split_limits_from_toml(text: &str) -> io::Result<TaskSplitLimits> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let defaults = TaskSplitLimits::default();
+    let limit = |key: &str, default: usize| {
+        non_negative_config_integer(&parsed, "workflow", key, default as u64)
+            .map(|limit| limit as usize)
+    };
+    Ok(TaskSplitLimits {
+        max_details_chars: limit("task_split_max_details_chars", defaults.max_details_chars)?,
+        max_subtasks: limit("task_split_max_subtasks", defaults.max_subtasks)?,
+    })
+}
+
+pub fn load_global_code_context_policy() -> io::Result<CodeContextPolicy> {
+    let text = load_merged_metaagent_config_text()?;
+    code_context_policy_from_toml(&text)
+}
+
+fn code_context_policy_from_toml(text: &str) -> io::Result<CodeContextPolicy> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let defaults = CodeContextPolicy::default();
+    let limit = |key: &str, default: usize| {
+        non_negative_config_integer(&parsed, "workflow", key, default as u64)
+            .map(|limit| limit as usize)
+    };
+    Ok(CodeContextPolicy {
+        max_files: limit("code_context_files", defaults.max_files)?,
+        max_excerpt_lines: limit("code_context_excerpt_lines", defaults.max_excerpt_lines)?,
+    })
+}
+
+pub fn load_global_worker_log_policy() -> io::Result<WorkerLogPolicy> {
+    let text = load_merged_metaagent_config_text()?;
+    worker_log_policy_from_toml(&text)
+}
+
+fn worker_log_policy_from_toml(text: &str) -> io::Result<WorkerLogPolicy> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let defaults = WorkerLogPolicy::default();
+    Ok(WorkerLogPolicy {
+        max_file_bytes: non_negative_config_integer(
+            &parsed,
+            "logs",
+            "max_file_bytes",
+            defaults.max_file_bytes,
+        )?,
+        max_rotated_files: non_negative_config_integer(
+            &parsed,
+            "logs",
+            "max_rotated_files",
+            defaults.max_rotated_files as u64,
+        )? as usize,
+        retention_days: non_negative_config_integer(
+            &parsed,
+            "logs",
+            "retention_days",
+            defaults.retention_days,
+        )?,
+    })
+}
+
+/// `[project_info]`: scan depth, directories, brief token budget, and fast mode for the
+/// project-info agent.
+pub fn load_global_project_info_scope() -> io::Result<ProjectInfoScope> {
+    let text = load_merged_metaagent_config_text()?;
+    project_info_scope_from_toml(&text)
+}
+
+fn project_info_scope_from_toml(text: &str) -> io::Result<ProjectInfoScope> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let Some(value) = parsed.get("project_info") else {
+        return Ok(ProjectInfoScope::default());
+    };
+    let scope = value
+        .clone()
+        .try_into::<ProjectInfoScope>()
+        .map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid [project_info]: {err}"),
+            )
+        })?;
+    if let Some(dir) = scope.invalid_directory() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "project_info include and exclude entries must be relative paths inside the workspace (got \"{dir}\")"
+            ),
+        ));
+    }
+    Ok(scope)
+}
+
+/// `[output_filters]`: agent output the subagent pane hides or collapses.
+pub fn load_global_output_filters() -> io::Result<OutputFilterConfig> {
+    let text = load_merged_metaagent_config_text()?;
+    output_filters_from_toml(&text)
+}
+
+fn output_filters_from_toml(text: &str) -> io::Result<OutputFilterConfig> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let Some(value) = parsed.get("output_filters") else {
+        return Ok(OutputFilterConfig::default());
+    };
+    let config = value
+        .clone()
+        .try_into::<OutputFilterConfig>()
+        .map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid [output_filters]: {err}"),
+            )
+        })?;
+    if let Some(index) = config
+        .rules
+        .iter()
+        .position(|rule| rule.pattern.trim().is_empty())
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("output_filters.rules[{index}].pattern must not be empty"),
+        ));
+    }
+    Ok(config)
+}
+
+pub fn load_global_backend_failover_threshold() -> io::Result<u64> {
+    let text = load_merged_metaagent_config_text()?;
+    backend_failover_threshold_from_toml(&text)
+}
+
+fn backend_failover_threshold_from_toml(text: &str) -> io::Result<u64> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    non_negative_config_integer(
+        &parsed,
+        "backend",
+        "failover_after_failures",
+        BackendFailoverTracker::DEFAULT_THRESHOLD,
+    )
+}
+
+fn non_negative_config_integer(
+    parsed: &toml::Value,
+    section: &str,
+    key: &str,
+    default: u64,
+) -> io::Result<u64> {
+    let Some(value) = parsed
+        .get(section)
+        .and_then(toml::Value::as_table)
+        .and_then(|table| table.get(key))
+    else {
+        return Ok(default);
+    };
+    value
+        .as_integer()
+        .and_then(|number| u64::try_from(number).ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{section}.{key} must be a non-negative integer (got {value})"),
+            )
+        })
+}
+
 fn tests_mode_enabled_from_toml(text: &str) -> io::Result<bool> {
     let parsed = toml::from_str::<toml::Value>(text)
         .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
@@ -400,6 +1394,12 @@ fn create_unique_session_dir(
     ))
 }
 
+fn file_name_attribute(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
 fn read_metadata_file(path: &Path) -> io::Result<SessionMetadata> {
     let text = read_text_file(path)?;
     let metadata = serde_json::from_str::<SessionMetadata>(&text)
@@ -450,8 +1450,7 @@ fn list_sessions_in_root(root_dir: &Path) -> io::Result<Vec<SessionListEntry>> {
 }
 
 fn load_config() -> io::Result<MetaAgentConfig> {
-    let config_file = ensure_default_metaagent_config()?;
-    let text = read_text_file(&config_file)?;
+    let text = load_merged_metaagent_config_text()?;
     let parsed = toml::from_str::<MetaAgentConfig>(&text)
         .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
     Ok(parsed)
@@ -536,6 +1535,7 @@ fn strings_to_docs(items: Vec<String>) -> Vec<PlannerTaskDocFileEntry> {
                     title: piece.to_string(),
                     url: piece.to_string(),
                     summary: String::new(),
+                    snapshot: None,
                 });
             }
         } else {
@@ -543,6 +1543,7 @@ fn strings_to_docs(items: Vec<String>) -> Vec<PlannerTaskDocFileEntry> {
                 title: trimmed.to_string(),
                 url: trimmed.to_string(),
                 summary: String::new(),
+                snapshot: None,
             });
         }
     }
@@ -0,0 +1,118 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::artifact_io::{read_text_file, runtime_storage_dir};
+use crate::session_store::{PlannerTaskFileEntry, SessionStore, SessionTemplateFile};
+use crate::workflow::Workflow;
+
+const TEMPLATES_DIR_NAME: &str = "templates";
+const TEMPLATE_FILE_EXTENSION: &str = "toml";
+
+/// A reusable project archetype stored as `templates/<name>.toml` next to the global config.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SessionTemplate {
+    pub description: String,
+    pub planner: String,
+    pub test_command: Option<String>,
+    pub audit_rubric: String,
+    pub tasks: Vec<PlannerTaskFileEntry>,
+}
+
+impl SessionTemplate {
+    /// Seeds a freshly initialized session with the template's planner, tasks, and settings.
+    pub fn apply_to_session(&self, name: &str, store: &SessionStore) -> io::Result<()> {
+        store.write_planner_markdown(&self.planner)?;
+        store.write_tasks(&self.tasks)?;
+        store.write_session_template(&SessionTemplateFile {
+            name: name.to_string(),
+            test_command: self
+                .test_command
+                .as_deref()
+                .map(str::trim)
+                .filter(|command| !command.is_empty())
+                .map(str::to_string),
+            audit_rubric: self.audit_rubric.trim().to_string(),
+        })
+    }
+}
+
+pub fn templates_dir() -> io::Result<PathBuf> {
+    Ok(runtime_storage_dir()?.join(TEMPLATES_DIR_NAME))
+}
+
+pub fn list_templates() -> io::Result<Vec<String>> {
+    list_templates_in_dir(&templates_dir()?)
+}
+
+pub fn load_template(name: &str) -> io::Result<SessionTemplate> {
+    load_template_from_dir(&templates_dir()?, name)
+}
+
+fn list_templates_in_dir(dir: &Path) -> io::Result<Vec<String>> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut names = read_dir
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == TEMPLATE_FILE_EXTENSION)
+        })
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect::<Vec<_>>();
+    names.sort();
+    Ok(names)
+}
+
+fn load_template_from_dir(dir: &Path, name: &str) -> io::Result<SessionTemplate> {
+    if !is_valid_template_name(name) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid template name `{name}` (use letters, digits, `-`, or `_`)"),
+        ));
+    }
+    let path = dir.join(format!("{name}.{TEMPLATE_FILE_EXTENSION}"));
+    let text = read_text_file(&path).map_err(|err| {
+        if err.kind() == io::ErrorKind::NotFound {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("template `{name}` not found at {}", path.display()),
+            )
+        } else {
+            err
+        }
+    })?;
+    parse_template(&text)
+}
+
+fn parse_template(text: &str) -> io::Result<SessionTemplate> {
+    let template = toml::from_str::<SessionTemplate>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Workflow::default()
+        .sync_planner_tasks_from_file(template.tasks.clone())
+        .map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("template tasks are invalid: {err}"),
+            )
+        })?;
+    Ok(template)
+}
+
+fn is_valid_template_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/session_template_tests.rs"]
+mod tests;
@@ -0,0 +1,226 @@
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::agent::{set_running_pid, terminate_running};
+use crate::wakeup::{self, Sender};
+
+/// Lines the console pane keeps; older output scrolls away.
+const MAX_CONSOLE_LINES: usize = 2_000;
+/// Most output lines `/console keep` copies into the rolling task context.
+const MAX_KEPT_LINES: usize = 20;
+/// How long a finished command's output readers get to drain once the shell exits; a
+/// background process still holding the pipes is left detached.
+const READER_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug)]
+enum ConsoleEvent {
+    Line(String),
+    Exited(Option<i32>),
+}
+
+/// The last command run in the console and the output it printed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleRun {
+    pub command: String,
+    pub output: Vec<String>,
+    /// Exit code once the command finished; `None` while it runs or when it was killed.
+    pub code: Option<i32>,
+    pub finished: bool,
+}
+
+/// Shell commands the user runs in the workspace from the console pane. Their output is
+/// captured into the pane instead of going to the raw terminal.
+#[derive(Debug, Default)]
+pub struct ShellConsole {
+    lines: Vec<String>,
+    events: Option<Receiver<ConsoleEvent>>,
+    running_pid: Arc<Mutex<Option<u32>>>,
+    last_run: Option<ConsoleRun>,
+}
+
+impl ShellConsole {
+    /// Starts `command` in the platform shell with `cwd` as its working directory.
+    pub fn run(&mut self, command: &str, cwd: &Path) -> Result<(), String> {
+        let command = command.trim();
+        if command.is_empty() {
+            return Err("Usage: /console run <command>".to_string());
+        }
+        if self.is_running() {
+            return Err(format!(
+                "`{}` is still running; /console stop ends it.",
+                self.last_run
+                    .as_ref()
+                    .map(|run| run.command.as_str())
+                    .unwrap_or_default()
+            ));
+        }
+        let mut child = shell_command(command)
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("Failed to start the shell: {err}"))?;
+        set_running_pid(&self.running_pid, Some(child.id()));
+        let (tx, rx) = wakeup::channel();
+        let mut readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(spawn_line_reader(stdout, tx.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(spawn_line_reader(stderr, tx.clone()));
+        }
+        let running_pid = Arc::clone(&self.running_pid);
+        thread::spawn(move || {
+            let code = child.wait().ok().and_then(|status| status.code());
+            set_running_pid(&running_pid, None);
+            let deadline = Instant::now() + READER_DRAIN_TIMEOUT;
+            while !readers.iter().all(thread::JoinHandle::is_finished) && Instant::now() < deadline
+            {
+                thread::sleep(Duration::from_millis(10));
+            }
+            let _ = tx.send(ConsoleEvent::Exited(code));
+        });
+
+        self.push_line(format!("$ {command}"));
+        self.events = Some(rx);
+        self.last_run = Some(ConsoleRun {
+            command: command.to_string(),
+            output: Vec::new(),
+            code: None,
+            finished: false,
+        });
+        Ok(())
+    }
+
+    /// Moves output that arrived since the last call into the pane. Returns true when
+    /// anything changed.
+    pub fn poll(&mut self) -> bool {
+        let Some(events) = &self.events else {
+            return false;
+        };
+        let received = events.try_iter().collect::<Vec<_>>();
+        for event in &received {
+            match event {
+                ConsoleEvent::Line(line) => {
+                    if let Some(run) = &mut self.last_run {
+                        run.output.push(line.clone());
+                    }
+                    self.push_line(line.clone());
+                }
+                ConsoleEvent::Exited(code) => {
+                    self.events = None;
+                    if let Some(run) = &mut self.last_run {
+                        run.code = *code;
+                        run.finished = true;
+                    }
+                    self.push_line(match code {
+                        Some(code) => format!("[exit code {code}]"),
+                        None => "[terminated]".to_string(),
+                    });
+                    self.push_line(String::new());
+                }
+            }
+        }
+        !received.is_empty()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.events.is_some()
+    }
+
+    /// Asks the running command to exit; its exit still shows up through [`Self::poll`].
+    pub fn stop(&self) -> bool {
+        terminate_running(&self.running_pid)
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    pub fn last_run(&self) -> Option<&ConsoleRun> {
+        self.last_run.as_ref()
+    }
+
+    /// Rolling-context entry for the last finished command and the tail of its output.
+    pub fn keep_entry(&self) -> Option<String> {
+        let run = self.last_run.as_ref().filter(|run| run.finished)?;
+        let status = match run.code {
+            Some(code) => format!("exit code {code}"),
+            None => "terminated".to_string(),
+        };
+        let output = run
+            .output
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let skipped = output.len().saturating_sub(MAX_KEPT_LINES);
+        let tail = match (output.is_empty(), skipped) {
+            (true, _) => "no output".to_string(),
+            (false, 0) => format!("output: {}", output.join(" | ")),
+            (false, _) => format!(
+                "last {MAX_KEPT_LINES} output lines: {}",
+                output[skipped..].join(" | ")
+            ),
+        };
+        Some(format!(
+            "User ran `{}` in the workspace console ({status}); {tail}",
+            run.command
+        ))
+    }
+
+    fn push_line(&mut self, line: String) {
+        self.lines.push(line);
+        if self.lines.len() > MAX_CONSOLE_LINES {
+            let overflow = self.lines.len() - MAX_CONSOLE_LINES;
+            self.lines.drain(0..overflow);
+        }
+    }
+}
+
+impl Drop for ShellConsole {
+    fn drop(&mut self) {
+        if self.is_running() {
+            self.stop();
+        }
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+fn spawn_line_reader<R: Read + Send + 'static>(
+    reader: R,
+    tx: Sender<ConsoleEvent>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            if tx.send(ConsoleEvent::Line(line)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/shell_console_tests.rs"]
+mod tests;
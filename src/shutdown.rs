@@ -0,0 +1,78 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// How `/quit` ends a run while a worker job is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownMode {
+    /// Let the active job finish, start nothing new, then quit.
+    Wait,
+    /// Stop the active job now and reset it to pending.
+    Abort,
+    /// Close the TUI and keep running headless until the active job finishes.
+    Detach,
+}
+
+impl ShutdownMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "wait" => Some(Self::Wait),
+            "abort" => Some(Self::Abort),
+            "detach" => Some(Self::Detach),
+            _ => None,
+        }
+    }
+}
+
+static SIGTERM_RECEIVED: AtomicBool = AtomicBool::new(false);
+
+/// Routes SIGTERM to [`take_sigterm`] so the main loop can shut down like `/quit` does.
+pub fn install_sigterm_handler() {
+    #[cfg(unix)]
+    unix::install_sigterm_handler();
+}
+
+/// True once per SIGTERM received since the last call.
+pub fn take_sigterm() -> bool {
+    SIGTERM_RECEIVED.swap(false, Ordering::SeqCst)
+}
+
+/// Keeps a detached run alive when its terminal closes.
+pub fn ignore_hangup() {
+    #[cfg(unix)]
+    unix::ignore_hangup();
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::sync::atomic::Ordering;
+
+    const SIGHUP: i32 = 1;
+    const SIGTERM: i32 = 15;
+    const SIG_IGN: usize = 1;
+
+    unsafe extern "C" {
+        fn signal(signum: i32, handler: usize) -> usize;
+    }
+
+    extern "C" fn on_sigterm(_signum: i32) {
+        super::SIGTERM_RECEIVED.store(true, Ordering::SeqCst);
+    }
+
+    pub(super) fn install_sigterm_handler() {
+        let handler: extern "C" fn(i32) = on_sigterm;
+        // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+        unsafe {
+            signal(SIGTERM, handler as usize);
+        }
+    }
+
+    pub(super) fn ignore_hangup() {
+        // SAFETY: SIG_IGN installs no handler code.
+        unsafe {
+            signal(SIGHUP, SIG_IGN);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/shutdown_tests.rs"]
+mod tests;
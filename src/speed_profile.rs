@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use crate::workflow::WorkerRole;
+
+/// Bundled trade-off between cost, quality, and turnaround, switched with `/speed`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeedProfile {
+    /// Small models, two audit passes, no test-writer audits.
+    Eco,
+    /// Configured models and the default retry limits.
+    #[default]
+    Standard,
+    /// Large high-effort models and more audit passes.
+    Max,
+}
+
+impl SpeedProfile {
+    pub const ALL: [Self; 3] = [Self::Eco, Self::Standard, Self::Max];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "eco" => Some(Self::Eco),
+            "standard" => Some(Self::Standard),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Eco => "eco",
+            Self::Standard => "standard",
+            Self::Max => "max",
+        }
+    }
+
+    pub fn is_standard(&self) -> bool {
+        *self == Self::Standard
+    }
+
+    /// Model profile for a worker role, or `None` to follow `[codex.agent_profiles]`. A
+    /// `/task-model` pin still wins.
+    pub fn worker_model_profile(self, role: WorkerRole) -> Option<&'static str> {
+        match (self, role) {
+            (Self::Standard, _) | (_, WorkerRole::TestRunner | WorkerRole::Plugin) => None,
+            (Self::Eco, WorkerRole::Implementor | WorkerRole::FinalAudit) => Some("small-smart"),
+            (Self::Eco, WorkerRole::Auditor | WorkerRole::TestWriter) => Some("small-dumb"),
+            (Self::Max, WorkerRole::TestWriter) => Some("large-smart"),
+            (Self::Max, _) => Some("large-genius"),
+        }
+    }
+
+    /// Implementor and test-writer audit passes before the run moves on.
+    pub fn audit_retries(self) -> u8 {
+        match self {
+            Self::Eco => 2,
+            Self::Standard => 4,
+            Self::Max => 6,
+        }
+    }
+
+    pub fn test_writer_audits(self) -> bool {
+        self != Self::Eco
+    }
+
+    pub fn summary(self) -> String {
+        let models = match self {
+            Self::Eco => "small models for workers",
+            Self::Standard => "configured worker models",
+            Self::Max => "large high-effort models for workers",
+        };
+        format!(
+            "{models}, up to {} audit passes, test-writer audits {}",
+            self.audit_retries(),
+            if self.test_writer_audits() {
+                "on"
+            } else {
+                "off"
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/speed_profile_tests.rs"]
+mod tests;
@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::session_store::{
+    PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile, SessionStore,
+    TaskFailFileEntry,
+};
+
+const MAX_EXPENSIVE_TASKS: usize = 5;
+
+/// Failure ledger and task list of one session, as read from disk.
+#[derive(Debug, Clone)]
+pub struct SessionFailureSample {
+    pub session_dir: PathBuf,
+    pub tasks: Vec<PlannerTaskFileEntry>,
+    pub fails: Vec<TaskFailFileEntry>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FailureStats {
+    pub sessions_scanned: usize,
+    pub top_tasks: usize,
+    pub done_tasks: usize,
+    pub total_failures: usize,
+    pub by_kind: Vec<FailureKindStats>,
+    pub most_expensive_tasks: Vec<ExpensiveTaskStats>,
+    /// Recorded failed passes plus the final pass, averaged over done top-level tasks.
+    pub average_passes_to_done: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailureKindStats {
+    pub kind: String,
+    pub failures: usize,
+    pub tasks_affected: usize,
+    pub failure_rate: f64,
+    pub total_attempts: u64,
+    pub average_attempts: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpensiveTaskStats {
+    pub session_dir: String,
+    pub task_title: String,
+    pub failures: usize,
+    pub attempts: u64,
+}
+
+/// Reads every listed session without touching its files and aggregates the failure ledgers.
+pub fn collect_failure_stats() -> io::Result<FailureStats> {
+    let samples = SessionStore::list_sessions()?
+        .into_iter()
+        .filter_map(|entry| SessionStore::open_read_only(&entry.session_dir).ok())
+        .map(|store| SessionFailureSample {
+            session_dir: store.session_dir().to_path_buf(),
+            tasks: store.read_tasks().unwrap_or_default(),
+            fails: store.read_task_fails().unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    Ok(FailureStats::from_samples(&samples))
+}
+
+impl FailureStats {
+    pub fn from_samples(samples: &[SessionFailureSample]) -> Self {
+        let mut stats = Self {
+            sessions_scanned: samples.len(),
+            ..Self::default()
+        };
+        let mut kinds = BTreeMap::<String, (usize, HashSet<(usize, String)>, u64)>::new();
+        let mut expensive = Vec::new();
+        let mut passes_to_done = Vec::new();
+
+        for (session_idx, sample) in samples.iter().enumerate() {
+            // Runtime task ids are reallocated on every sync, so failures are matched by title.
+            let mut per_task = HashMap::<&str, (usize, u64)>::new();
+            for fail in &sample.fails {
+                stats.total_failures += 1;
+                let attempts = u64::from(fail.attempts);
+                let kind = kinds.entry(fail.kind.clone()).or_default();
+                kind.0 += 1;
+                kind.1.insert((session_idx, fail.top_task_title.clone()));
+                kind.2 += attempts;
+                let task = per_task.entry(fail.top_task_title.as_str()).or_default();
+                task.0 += 1;
+                task.1 += attempts;
+            }
+
+            for task in sample
+                .tasks
+                .iter()
+                .filter(|task| task.parent_id.is_none() && task.kind == PlannerTaskKindFile::Task)
+            {
+                stats.top_tasks += 1;
+                if task.status == PlannerTaskStatusFile::Done {
+                    stats.done_tasks += 1;
+                    let failed_passes = per_task
+                        .get(task.title.as_str())
+                        .map(|(_, attempts)| *attempts)
+                        .unwrap_or(0);
+                    passes_to_done.push(failed_passes + 1);
+                }
+            }
+
+            let session_dir = sample.session_dir.display().to_string();
+            expensive.extend(per_task.into_iter().map(|(title, (failures, attempts))| {
+                ExpensiveTaskStats {
+                    session_dir: session_dir.clone(),
+                    task_title: title.to_string(),
+                    failures,
+                    attempts,
+                }
+            }));
+        }
+
+        stats.by_kind = kinds
+            .into_iter()
+            .map(
+                |(kind, (failures, tasks, total_attempts))| FailureKindStats {
+                    kind,
+                    failures,
+                    tasks_affected: tasks.len(),
+                    failure_rate: ratio(tasks.len() as f64, stats.top_tasks as f64),
+                    total_attempts,
+                    average_attempts: ratio(total_attempts as f64, failures as f64),
+                },
+            )
+            .collect();
+        expensive.sort_by(|a, b| {
+            b.attempts
+                .cmp(&a.attempts)
+                .then_with(|| b.failures.cmp(&a.failures))
+                .then_with(|| a.task_title.cmp(&b.task_title))
+        });
+        expensive.truncate(MAX_EXPENSIVE_TASKS);
+        stats.most_expensive_tasks = expensive;
+        stats.average_passes_to_done = (!passes_to_done.is_empty())
+            .then(|| passes_to_done.iter().sum::<u64>() as f64 / passes_to_done.len() as f64);
+        stats
+    }
+
+    pub fn render_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "Sessions: {} | Top-level tasks: {} ({} done) | Failures: {}",
+                self.sessions_scanned, self.top_tasks, self.done_tasks, self.total_failures
+            ),
+            format!(
+                "Average passes to done: {}",
+                self.average_passes_to_done
+                    .map(|value| format!("{value:.2}"))
+                    .unwrap_or_else(|| "n/a".to_string())
+            ),
+            String::new(),
+            "Failures by kind:".to_string(),
+        ];
+        if self.by_kind.is_empty() {
+            lines.push("  (no recorded failures)".to_string());
+        }
+        for kind in &self.by_kind {
+            lines.push(format!(
+                "  {}: {} failure(s), {} task(s) affected ({:.0}% of tasks), avg {:.1} attempt(s)",
+                kind.kind,
+                kind.failures,
+                kind.tasks_affected,
+                kind.failure_rate * 100.0,
+                kind.average_attempts
+            ));
+        }
+        lines.push(String::new());
+        lines.push("Most expensive tasks:".to_string());
+        if self.most_expensive_tasks.is_empty() {
+            lines.push("  (none)".to_string());
+        }
+        for (idx, task) in self.most_expensive_tasks.iter().enumerate() {
+            lines.push(format!(
+                "  {}. {} — {} attempt(s) over {} failure(s)",
+                idx + 1,
+                task.task_title,
+                task.attempts,
+                task.failures
+            ));
+            lines.push(format!("     {}", task.session_dir));
+        }
+        lines
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        0.0
+    } else {
+        numerator / denominator
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/stats_tests.rs"]
+mod tests;
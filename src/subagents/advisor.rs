@@ -0,0 +1,36 @@
+pub(crate) fn build_advisor_prompt(
+    question: &str,
+    session_dir: &str,
+    tasks_file: &str,
+    project_info: Option<&str>,
+    queued_jobs: &[String],
+) -> String {
+    let project_context = project_info
+        .filter(|info| !info.trim().is_empty())
+        .map(|info| format!("Project context (project-info.md):\n{info}\n"))
+        .unwrap_or_default();
+    let queue = if queued_jobs.is_empty() {
+        "- (no jobs queued)".to_string()
+    } else {
+        queued_jobs
+            .iter()
+            .map(|job| format!("- {job}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    format!(
+        "You are the planning master's read-only advisor. Worker agents are executing the plan right now, so you may only answer questions.\n\
+         Meta-agent session working directory: {session_dir}\n\
+         Current task list with runtime statuses (read only): {tasks_file}\n\
+         {project_context}\
+         Worker jobs waiting to start:\n\
+         {queue}\n\
+         Rules:\n\
+         - Read any file you need, but do not create, edit, or delete files: not tasks.json, not planner or session files, not workspace files.\n\
+         - Edits to tasks.json are rejected: the file is restored as soon as you finish.\n\
+         - If the user asks to change the plan, explain what you would change and ask them to send the request again once execution finishes.\n\
+         - Answer concisely.\n\
+         User question:\n\
+         {question}"
+    )
+}
@@ -1,3 +1,5 @@
+use crate::clarification::questions_prompt_instructions;
+
 pub(crate) fn build_master_prompt(
     tasks_file: &str,
     workflow_prompt: &str,
@@ -44,6 +46,12 @@ pub(crate) fn build_master_prompt(
          - `/start` always resumes from the last unfinished task.\n\
          - File schema: array of objects with fields id, title, details, docs, kind, status, parent_id, order\n\
          - kind values: task, final_audit, implementor, auditor, test_writer, test_runner\n\
+         - Optional `external_ref` (e.g. `linear:ENG-123`) links a task to a tracker ticket; keep it unchanged on existing tasks.\n\
+         - Optional `workdir` on a top-level task (workspace-relative, e.g. `backend`) runs its workers in that directory; omit it to use the workspace root.\n\
+         - Give every new top-level task an `acceptance_criteria` list of short, verifiable statements of done (e.g. [\"`bob --version` prints the crate version\"]). Its implementor addresses each one and its auditor must confirm each one as met. Only top-level tasks may have them.\n\
+         - Give every new top-level task an `estimate` of its size: \"S\", \"M\", \"L\", or hours such as \"4h\" or \"1.5h\". Only top-level tasks may have one.\n\
+         - Optional `verify` on a top-level task is a shell command (e.g. `make e2e`) that must pass after its tests; only set it when the user asks for one.\n\
+         - Set a top-level task's status to \"blocked\" with a `blocked_reason` question when it cannot run without the user's input (credentials, a design decision); the user answers with `/unblock`. Keep `user_answers` unchanged.\n\
          - `docs` is reserved for `/attach-docs`. Do not populate or modify `docs` in master edits.\n\
          - For new tasks created by master, set `docs` to [] and leave it empty.\n\
          - Every task and sub-task must include a non-empty details field with concrete implementation/audit/test intent.\n\
@@ -52,10 +60,13 @@ pub(crate) fn build_master_prompt(
          {}\n\
          - Update tasks.json only when task state should change.\n\
          - Conversational answers that do not change task state do not require tasks.json edits.\n\
+         {}\n\
          - Do not ask the user to start execution until task updates are ready.\n\
+         - When a reply concerns specific tasks, name each as `task <id>` with its tasks.json id, so it shows in that task's conversation.\n\
          - After updating tasks.json, explain to the user what changed.",
         workflow_prompt,
-        tests_policy
+        tests_policy,
+        questions_prompt_instructions()
     )
 }
 
@@ -79,6 +90,8 @@ pub(crate) fn build_session_intro_if_needed(
     session_dir: &str,
     session_meta_file: &str,
     project_info: Option<&str>,
+    project_memory: Option<&str>,
+    persona: Option<&str>,
     intro_needed: &mut bool,
 ) -> String {
     if !*intro_needed {
@@ -102,10 +115,52 @@ pub(crate) fn build_session_intro_if_needed(
         out.push_str(info);
         out.push_str("\n\n");
     }
+    if let Some(memory) = project_memory {
+        out.push_str(memory);
+        out.push_str("\n\n");
+    }
+    if let Some(persona) = persona {
+        out.push_str(&persona_section(persona));
+        out.push_str("\n\n");
+    }
     out.push_str(prompt);
     out
 }
 
+/// Preamble for the master and the other session agents while `--safe-mode` is on.
+pub(crate) fn safe_mode_preamble(workspace: &str) -> String {
+    format!(
+        "Safe mode is on:\n\
+         - The workspace at {workspace} is read-only; do not change its files.\n\
+         - Files can only be written in the session directory Bob names in its prompts.\n\
+         - Edit tasks in the session's tasks.staged.json instead of tasks.json. Bob shows the changes to the user and applies them to tasks.json when they are accepted."
+    )
+}
+
+/// Appends the session persona for a worker agent to its prompt.
+pub(crate) fn with_worker_persona(prompt: &str, persona: Option<&str>) -> String {
+    match persona {
+        Some(persona) => format!("{prompt}\n\n{}", persona_section(persona)),
+        None => prompt.to_string(),
+    }
+}
+
+/// Appends the stack details from meta.json, when the session has any, to a worker prompt.
+pub(crate) fn with_project_stack(prompt: &str, stack: Option<&str>) -> String {
+    match stack {
+        Some(stack) => format!(
+            "{prompt}\n\nProject stack (from meta.json; use these commands rather than guessing them):\n{stack}"
+        ),
+        None => prompt.to_string(),
+    }
+}
+
+fn persona_section(persona: &str) -> String {
+    format!(
+        "Session persona (from meta.json; follow it unless it conflicts with the rules above):\n{persona}"
+    )
+}
+
 pub(crate) fn build_failure_report_prompt(
     task_fails_file: &str,
     failed_this_cycle: &[crate::session_store::TaskFailFileEntry],
@@ -145,6 +200,41 @@ pub(crate) fn build_failure_report_prompt(
     prompt
 }
 
+/// Line of a task summary prompt naming the task; the reply is filed under it.
+const TASK_SUMMARY_HEADER: &str = "Summarize finished top-level task";
+
+pub(crate) fn build_task_summary_prompt(
+    task_id: &str,
+    task_title: &str,
+    final_logs: &[String],
+) -> String {
+    let logs = if final_logs.is_empty() {
+        "- (no worker logs were kept; rely on the rolling task context)".to_string()
+    } else {
+        final_logs
+            .iter()
+            .map(|path| format!("- {path}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    format!(
+        "Internal update from execution engine:\n\
+         {TASK_SUMMARY_HEADER} {task_id}: {task_title}\n\
+         Worker log of the last pass of each role that worked on it:\n\
+         {logs}\n\
+         Read them and reply with exactly one paragraph for the session's changelog: what was implemented, what the final audit concluded, and what the tests cover, for the roles that ran.\n\
+         Do not emit task operations and do not modify files.\n"
+    )
+}
+
+/// Task id and title of a prompt built by [`build_task_summary_prompt`]; `None` for other prompts.
+pub(crate) fn task_summary_target(prompt: &str) -> Option<(String, String)> {
+    prompt.lines().find_map(|line| {
+        let (task_id, title) = line.strip_prefix(TASK_SUMMARY_HEADER)?.split_once(": ")?;
+        Some((task_id.trim().to_string(), title.to_string()))
+    })
+}
+
 pub(crate) fn split_audits_command_prompt() -> String {
     "Update tasks.json now by splitting audit tasks into more granular audit tasks mapped per concern.\n\
      Concern examples to map across relevant work: correctness, edge cases, tests/coverage, security, performance, and UX.\n\
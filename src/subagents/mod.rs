@@ -1,13 +1,21 @@
+pub(crate) mod advisor;
 pub(crate) mod master;
 pub(crate) mod project_info;
 pub(crate) mod task_check;
+pub(crate) mod task_split;
 
+pub(crate) use advisor::build_advisor_prompt;
 pub(crate) use master::{
     build_convert_plan_prompt, build_failure_report_prompt, build_master_prompt,
-    build_session_intro_if_needed, merge_audits_command_prompt, split_audits_command_prompt,
+    build_session_intro_if_needed, build_task_summary_prompt, merge_audits_command_prompt,
+    safe_mode_preamble, split_audits_command_prompt, task_summary_target, with_project_stack,
+    with_worker_persona,
 };
 #[cfg(test)]
 #[allow(unused_imports)]
 pub(crate) use master::{merge_tests_command_prompt, split_tests_command_prompt};
 pub(crate) use project_info::{build_project_info_prompt, build_session_meta_prompt};
 pub(crate) use task_check::build_task_check_prompt;
+pub(crate) use task_split::{
+    build_task_split_prompt, find_oversized_top_tasks, summarize_task_split,
+};
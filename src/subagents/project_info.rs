@@ -1,4 +1,27 @@
-pub(crate) fn build_project_info_prompt(cwd: &str, question: &str, output_path: &str) -> String {
+use crate::project_info_scope::ProjectInfoScope;
+
+/// `ignored_patterns` are the workspace's `.metaagentignore` lines, which the agent must not
+/// read or describe; `scope` is `[project_info]`'s depth, directory, and budget limits.
+pub(crate) fn build_project_info_prompt(
+    cwd: &str,
+    question: &str,
+    output_path: &str,
+    ignored_patterns: &[String],
+    scope: &ProjectInfoScope,
+) -> String {
+    let scope = scope.prompt_requirements();
+    let ignored = if ignored_patterns.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "- Skip paths matching these .metaagentignore patterns (.gitignore syntax); do not read, list, or summarize them: {}\n",
+            ignored_patterns
+                .iter()
+                .map(|pattern| format!("`{pattern}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
     format!(
         "You are a project-context discovery sub-agent.\n\
          Analyze the repository and gather concise project context for the user question.\n\
@@ -7,7 +30,7 @@ pub(crate) fn build_project_info_prompt(cwd: &str, question: &str, output_path:
          {question}\n\
          Requirements:\n\
          - Inspect only local files in the repository to understand structure, tech stack, and constraints.\n\
-         - Do not browse the web, call external tools/services, or include internet-sourced references.\n\
+         {ignored}{scope}- Do not browse the web, call external tools/services, or include internet-sourced references.\n\
          - Write a concise Markdown brief to this exact path: {output_path}\n\
          - Include sections: \"Project Overview\", \"Language & Tech Stack\", \"File Structure\", \"Relevant Code Areas\", \"Constraints & Conventions\", \"Testing Setup\".\n\
          - In \"Testing Setup\", explicitly state whether tests currently exist, where they are, and the best command to run the project's tests end-to-end.\n\
@@ -25,12 +48,17 @@ pub(crate) fn build_session_meta_prompt(user_prompt: &str, output_path: &str) ->
         "Using the same session context and project info you already gathered, create session metadata.\n\
          Write valid JSON to this exact path: {output_path}\n\
          JSON schema:\n\
-         {{\"title\":\"...\",\"created_at\":\"...\",\"stack_description\":\"...\",\"test_command\":\"...\"}}\n\
+         {{\"title\":\"...\",\"created_at\":\"...\",\"stack_description\":\"...\",\"languages\":[\"...\"],\"frameworks\":[\"...\"],\"package_manager\":\"...\",\"build_command\":\"...\",\"run_command\":\"...\",\"test_command\":\"...\"}}\n\
          Requirements:\n\
          - title: a concise 4-10 word title derived from the user's original request.\n\
          - created_at: current date-time in ISO-8601 UTC format (example: 2026-02-16T20:14:00Z).\n\
          - stack_description: a concise 1-2 sentence description of the project's language/technology stack based on gathered project info.\n\
          - If stack details are uncertain, state that clearly rather than guessing.\n\
+         - languages: the main programming languages, most used first (for example: [\"Rust\", \"TypeScript\"]).\n\
+         - frameworks: the main frameworks and libraries the code is built on; use [] if there are none.\n\
+         - package_manager: the tool that installs dependencies (for example: \"cargo\", \"npm\", \"poetry\"), or JSON null if there is none.\n\
+         - build_command and run_command: like test_command below, one exact bash command run from the repository root to build or to start the project, or JSON null if there is none or it is unknown.\n\
+         - Only fill in languages, frameworks, package_manager, build_command, and run_command from what the repository's files show.\n\
          - test_command: the best command to run the project's tests end-to-end.\n\
          - test_command must be one exact command string runnable in bash as-is from the repository root (for example: \"cargo test\", \"go test ./...\", \"npm test\").\n\
          - Do not describe the command or wrap it in markdown/backticks; provide only the raw command string value.\n\
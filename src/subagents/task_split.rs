@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskKindFile};
+use crate::workflow::TaskSplitLimits;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OversizedTask {
+    pub id: String,
+    pub title: String,
+    pub reason: String,
+}
+
+/// Top-level tasks whose details or subtask count exceed the configured limits.
+pub(crate) fn find_oversized_top_tasks(
+    tasks: &[PlannerTaskFileEntry],
+    limits: TaskSplitLimits,
+    skip_titles: &HashSet<String>,
+) -> Vec<OversizedTask> {
+    tasks
+        .iter()
+        .filter(|task| task.parent_id.is_none() && task.kind == PlannerTaskKindFile::Task)
+        .filter(|task| !skip_titles.contains(&task.title))
+        .filter_map(|task| {
+            let details_chars = task.details.chars().count();
+            let subtasks = count_descendants(tasks, &task.id);
+            let reason = if limits.max_details_chars > 0 && details_chars > limits.max_details_chars
+            {
+                format!(
+                    "details are {details_chars} chars (limit {})",
+                    limits.max_details_chars
+                )
+            } else if limits.max_subtasks > 0 && subtasks > limits.max_subtasks {
+                format!("{subtasks} subtasks (limit {})", limits.max_subtasks)
+            } else {
+                return None;
+            };
+            Some(OversizedTask {
+                id: task.id.clone(),
+                title: task.title.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn build_task_split_prompt(
+    tasks_file: &str,
+    proposal_file: &str,
+    project_info_file: &str,
+    oversized: &[OversizedTask],
+) -> String {
+    let oversized_lines = oversized
+        .iter()
+        .map(|task| format!("- id {}: \"{}\" ({})", task.id, task.title, task.reason))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "You are a task-splitting sub-agent.\n\
+         Read the planner JSON file at: {tasks_file}\n\
+         You may also read project context at: {project_info_file}\n\
+         These top-level tasks exceed the size heuristics:\n\
+         {oversized_lines}\n\
+         Requirements:\n\
+         - Do NOT edit tasks.json. Write your proposal to: {proposal_file}\n\
+         - The proposal is the complete task list in the same JSON schema as tasks.json.\n\
+         - Replace each listed task with two or more smaller, independently executable top-level tasks, in order, at the original position.\n\
+         - Give every new top-level task its own implementor/auditor subtasks (and test_writer/test_runner subtasks where the original had them), following the same hierarchy guardrails as tasks.json.\n\
+         - Distribute the original details so each new task is self-contained; do not drop requirements.\n\
+         - Keep every other task exactly as it is, including ids, status, and order.\n\
+         - If a listed task is cohesive and should not be split, keep it unchanged; if nothing should be split, do not write the proposal file.\n\
+         - Return \"PROPOSED\" or \"NO_SPLIT\" on the first line, followed by one line per split task.\n\
+         Then exit."
+    )
+}
+
+/// Describes a proposal as the top-level tasks it removes and the ones it adds.
+pub(crate) fn summarize_task_split(
+    before: &[PlannerTaskFileEntry],
+    after: &[PlannerTaskFileEntry],
+) -> Vec<String> {
+    let before_titles = top_level_titles(before);
+    let after_titles = top_level_titles(after);
+    let removed = before_titles
+        .iter()
+        .filter(|title| !after_titles.contains(title))
+        .map(|title| format!("\"{title}\""))
+        .collect::<Vec<_>>();
+    let added = after_titles
+        .iter()
+        .filter(|title| !before_titles.contains(title))
+        .map(|title| format!("\"{title}\""))
+        .collect::<Vec<_>>();
+    if removed.is_empty() && added.is_empty() {
+        return Vec::new();
+    }
+    vec![
+        format!("Split: {}", removed.join(", ")),
+        format!("Into: {}", added.join(", ")),
+    ]
+}
+
+fn top_level_titles(tasks: &[PlannerTaskFileEntry]) -> Vec<&str> {
+    tasks
+        .iter()
+        .filter(|task| task.parent_id.is_none() && task.kind == PlannerTaskKindFile::Task)
+        .map(|task| task.title.as_str())
+        .collect()
+}
+
+fn count_descendants(tasks: &[PlannerTaskFileEntry], root_id: &str) -> usize {
+    let mut frontier = vec![root_id];
+    let mut count = 0;
+    while let Some(parent_id) = frontier.pop() {
+        for child in tasks
+            .iter()
+            .filter(|task| task.parent_id.as_deref() == Some(parent_id))
+        {
+            count += 1;
+            frontier.push(&child.id);
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/subagents_task_split_tests.rs"]
+mod tests;
@@ -0,0 +1,37 @@
+/// Prefix of the status lines Bob writes to the chat pane.
+pub const SYSTEM_PREFIX: &str = "System:";
+
+/// Lower-case fragments that surface a system message in the Chat view: failures,
+/// warnings, and anything waiting on the user.
+const IMPORTANT_MARKERS: [&str; 16] = [
+    "fail",
+    "error",
+    "cannot",
+    "could not",
+    "not validate",
+    "no longer validates",
+    "conflict",
+    "exceeded",
+    "rejected",
+    "aborted",
+    "stalled",
+    "unknown",
+    "usage:",
+    "edited outside",
+    "warning",
+    "answer them",
+];
+
+pub fn is_system_line(line: &str) -> bool {
+    line.starts_with(SYSTEM_PREFIX)
+}
+
+/// Whether a system line belongs in the Chat view as well as the System Log.
+pub fn is_important_system_line(line: &str) -> bool {
+    let line = line.to_lowercase();
+    IMPORTANT_MARKERS.iter().any(|marker| line.contains(marker))
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/system_log_tests.rs"]
+mod tests;
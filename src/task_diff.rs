@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskStatusFile};
+
+/// Most change lines shown for one master write; the rest are counted in a final line.
+const MAX_SHOWN_CHANGES: usize = 20;
+
+/// One difference between two versions of tasks.json, matched by task id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum TaskChange {
+    Added {
+        id: String,
+        title: String,
+    },
+    Removed {
+        id: String,
+        title: String,
+    },
+    Retitled {
+        id: String,
+        from: String,
+        to: String,
+    },
+    StatusChanged {
+        id: String,
+        title: String,
+        from: PlannerTaskStatusFile,
+        to: PlannerTaskStatusFile,
+    },
+    DetailsEdited {
+        id: String,
+        title: String,
+    },
+    DocsChanged {
+        id: String,
+        title: String,
+    },
+}
+
+impl TaskChange {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Added { id, title } => format!("+ added {id}: {title}"),
+            Self::Removed { id, title } => format!("- removed {id}: {title}"),
+            Self::Retitled { id, from, to } => format!("~ retitled {id}: \"{from}\" -> \"{to}\""),
+            Self::StatusChanged {
+                id,
+                title,
+                from,
+                to,
+            } => format!(
+                "* {id} ({title}): {} -> {}",
+                status_label(*from),
+                status_label(*to)
+            ),
+            Self::DetailsEdited { id, title } => format!("~ edited details of {id}: {title}"),
+            Self::DocsChanged { id, title } => format!("~ changed docs of {id}: {title}"),
+        }
+    }
+}
+
+/// Removed tasks in `before` order, then the changes to each task of `after` in its order.
+pub fn diff(before: &[PlannerTaskFileEntry], after: &[PlannerTaskFileEntry]) -> Vec<TaskChange> {
+    let find =
+        |tasks: &[PlannerTaskFileEntry], id: &str| tasks.iter().find(|task| task.id == id).cloned();
+    let mut changes = before
+        .iter()
+        .filter(|task| find(after, &task.id).is_none())
+        .map(|task| TaskChange::Removed {
+            id: task.id.clone(),
+            title: task.title.clone(),
+        })
+        .collect::<Vec<_>>();
+    for task in after {
+        let Some(old) = find(before, &task.id) else {
+            changes.push(TaskChange::Added {
+                id: task.id.clone(),
+                title: task.title.clone(),
+            });
+            continue;
+        };
+        if old.title != task.title {
+            changes.push(TaskChange::Retitled {
+                id: task.id.clone(),
+                from: old.title.clone(),
+                to: task.title.clone(),
+            });
+        }
+        if old.status != task.status {
+            changes.push(TaskChange::StatusChanged {
+                id: task.id.clone(),
+                title: task.title.clone(),
+                from: old.status,
+                to: task.status,
+            });
+        }
+        if old.details.trim() != task.details.trim() {
+            changes.push(TaskChange::DetailsEdited {
+                id: task.id.clone(),
+                title: task.title.clone(),
+            });
+        }
+        if old.docs != task.docs {
+            changes.push(TaskChange::DocsChanged {
+                id: task.id.clone(),
+                title: task.title.clone(),
+            });
+        }
+    }
+    changes
+}
+
+/// One line per change, capped at [`MAX_SHOWN_CHANGES`].
+pub fn render(changes: &[TaskChange]) -> String {
+    let mut lines = changes
+        .iter()
+        .take(MAX_SHOWN_CHANGES)
+        .map(TaskChange::describe)
+        .collect::<Vec<_>>();
+    if changes.len() > MAX_SHOWN_CHANGES {
+        lines.push(format!(
+            "... and {} more change(s)",
+            changes.len() - MAX_SHOWN_CHANGES
+        ));
+    }
+    lines.join("\n")
+}
+
+fn status_label(status: PlannerTaskStatusFile) -> &'static str {
+    match status {
+        PlannerTaskStatusFile::Pending => "pending",
+        PlannerTaskStatusFile::InProgress => "in progress",
+        PlannerTaskStatusFile::NeedsChanges => "needs changes",
+        PlannerTaskStatusFile::Done => "done",
+        PlannerTaskStatusFile::Blocked => "blocked on user",
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/task_diff_tests.rs"]
+mod tests;
@@ -0,0 +1,161 @@
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskStatusFile};
+use crate::workflow::{FinalAuditPolicy, Workflow};
+
+/// One change to a session's tasks.json, as requested through `api session` or `/tasks`.
+#[derive(Debug, Clone)]
+pub enum TaskEdit {
+    /// Adds a task with a new id; without an `order` it goes after its siblings.
+    Add(PlannerTaskFileEntry),
+    /// Replaces the task with the same id.
+    Update(PlannerTaskFileEntry),
+    /// Removes a task together with its subtasks.
+    Remove { task_id: String },
+    /// Moves a task to a zero-based position among its siblings and renumbers them.
+    Reorder { task_id: String, position: usize },
+    /// Sets the status of a task and all of its subtasks.
+    SetStatus {
+        task_id: String,
+        status: PlannerTaskStatusFile,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskEditError {
+    NotFound(String),
+    Invalid(String),
+}
+
+impl std::fmt::Display for TaskEditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(message) | Self::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+/// Applies `edit` and checks the result the same way a `/reload-tasks` of tasks.json
+/// would under the `[workflow] final_audit` policy, so a rejected edit never reaches the file.
+pub fn apply(
+    mut tasks: Vec<PlannerTaskFileEntry>,
+    edit: TaskEdit,
+    final_audit_policy: FinalAuditPolicy,
+) -> Result<Vec<PlannerTaskFileEntry>, TaskEditError> {
+    match edit {
+        TaskEdit::Add(mut task) => {
+            if tasks.iter().any(|entry| entry.id == task.id) {
+                return Err(TaskEditError::Invalid(format!(
+                    "Planner task {} already exists",
+                    task.id
+                )));
+            }
+            if task.order.is_none() {
+                task.order = Some(
+                    tasks
+                        .iter()
+                        .filter(|entry| entry.parent_id == task.parent_id)
+                        .filter_map(|entry| entry.order)
+                        .max()
+                        .map_or(0, |order| order.saturating_add(1)),
+                );
+            }
+            tasks.push(task);
+        }
+        TaskEdit::Update(task) => {
+            let index = find_task(&tasks, &task.id)?;
+            tasks[index] = task;
+        }
+        TaskEdit::Remove { task_id } => {
+            let removed = subtree_ids(&tasks, task_id)?;
+            tasks.retain(|entry| !removed.contains(&entry.id));
+        }
+        TaskEdit::Reorder { task_id, position } => {
+            let index = find_task(&tasks, &task_id)?;
+            let parent_id = tasks[index].parent_id.clone();
+            let mut siblings = (0..tasks.len())
+                .filter(|&idx| tasks[idx].parent_id == parent_id)
+                .collect::<Vec<_>>();
+            siblings.sort_by_key(|&idx| tasks[idx].order.unwrap_or(u32::MAX));
+            if position >= siblings.len() {
+                return Err(TaskEditError::Invalid(format!(
+                    "Position {position} is out of range; planner task {task_id} has {} sibling position(s)",
+                    siblings.len()
+                )));
+            }
+            siblings.retain(|&idx| idx != index);
+            siblings.insert(position, index);
+            for (order, idx) in siblings.into_iter().enumerate() {
+                tasks[idx].order = Some(order as u32);
+            }
+        }
+        TaskEdit::SetStatus { task_id, status } => {
+            let updated = subtree_ids(&tasks, task_id)?;
+            for entry in tasks.iter_mut().filter(|entry| updated.contains(&entry.id)) {
+                entry.status = status;
+            }
+        }
+    }
+    let mut validation = Workflow::default();
+    validation.set_final_audit_policy(final_audit_policy);
+    validation
+        .sync_planner_tasks_from_file(tasks.clone())
+        .map_err(TaskEditError::Invalid)?;
+    Ok(tasks)
+}
+
+/// Ids of the top-level tasks at the 1-based `positions` of the task list, which numbers
+/// them by `order`.
+pub fn top_level_ids(
+    tasks: &[PlannerTaskFileEntry],
+    positions: &[usize],
+) -> Result<Vec<String>, TaskEditError> {
+    let mut roots = tasks
+        .iter()
+        .filter(|entry| entry.parent_id.is_none())
+        .collect::<Vec<_>>();
+    roots.sort_by_key(|entry| entry.order.unwrap_or(u32::MAX));
+    positions
+        .iter()
+        .map(|&position| {
+            position
+                .checked_sub(1)
+                .and_then(|index| roots.get(index))
+                .map(|entry| entry.id.clone())
+                .ok_or_else(|| {
+                    TaskEditError::NotFound(format!(
+                        "No task #{position}; the task list has {} top-level task(s)",
+                        roots.len()
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// `task_id` followed by the ids of all of its subtasks.
+fn subtree_ids(
+    tasks: &[PlannerTaskFileEntry],
+    task_id: String,
+) -> Result<Vec<String>, TaskEditError> {
+    find_task(tasks, &task_id)?;
+    let mut ids = vec![task_id];
+    let mut next = 0;
+    while let Some(parent) = ids.get(next).cloned() {
+        for entry in tasks {
+            if entry.parent_id.as_ref() == Some(&parent) && !ids.contains(&entry.id) {
+                ids.push(entry.id.clone());
+            }
+        }
+        next += 1;
+    }
+    Ok(ids)
+}
+
+fn find_task(tasks: &[PlannerTaskFileEntry], task_id: &str) -> Result<usize, TaskEditError> {
+    tasks
+        .iter()
+        .position(|entry| entry.id == task_id)
+        .ok_or_else(|| TaskEditError::NotFound(format!("Planner task {task_id} not found")))
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/task_edits_tests.rs"]
+mod tests;
@@ -0,0 +1,130 @@
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile};
+
+/// Diagram languages `api workflow export-graph` can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Mermaid,
+    Dot,
+}
+
+/// Fill and border colors per task status, shared by both formats.
+const STATUS_COLORS: [(PlannerTaskStatusFile, &str, &str); 5] = [
+    (PlannerTaskStatusFile::Pending, "#eeeeee", "#999999"),
+    (PlannerTaskStatusFile::InProgress, "#fff3b0", "#d4a017"),
+    (PlannerTaskStatusFile::NeedsChanges, "#ffd6d6", "#d9534f"),
+    (PlannerTaskStatusFile::Done, "#d4f7d4", "#3c9d3c"),
+    (PlannerTaskStatusFile::Blocked, "#e3d7ff", "#7a52c7"),
+];
+
+/// Renders the task tree of a validated `tasks.json` as a flowchart: solid edges run from
+/// each task to its subtasks, dashed edges from each top-level task to the one that runs
+/// after it, and nodes are colored by status. `tasks` must be in file order, as returned by
+/// `Workflow::planner_tasks_for_file`.
+pub fn render(tasks: &[PlannerTaskFileEntry], format: GraphFormat) -> String {
+    let node_id = |id: &str| {
+        let index = tasks.iter().position(|task| task.id == id).unwrap_or(0);
+        format!("t{index}")
+    };
+    let hierarchy = tasks
+        .iter()
+        .filter_map(|task| Some((node_id(task.parent_id.as_deref()?), node_id(&task.id))))
+        .collect::<Vec<_>>();
+    let top_level = tasks
+        .iter()
+        .filter(|task| task.parent_id.is_none())
+        .map(|task| node_id(&task.id))
+        .collect::<Vec<_>>();
+    let sequence = top_level.windows(2).map(|pair| (&pair[0], &pair[1]));
+
+    let mut lines = Vec::new();
+    match format {
+        GraphFormat::Mermaid => {
+            lines.push("flowchart TD".to_string());
+            for (status, fill, stroke) in STATUS_COLORS {
+                lines.push(format!(
+                    "  classDef {} fill:{fill},stroke:{stroke}",
+                    status_name(status)
+                ));
+            }
+            for task in tasks {
+                lines.push(format!(
+                    "  {}[\"{}\"]:::{}",
+                    node_id(&task.id),
+                    label(task).replace('"', "#quot;").replace('\n', "<br/>"),
+                    status_name(task.status)
+                ));
+            }
+            lines.extend(
+                hierarchy
+                    .iter()
+                    .map(|(parent, child)| format!("  {parent} --> {child}")),
+            );
+            lines.extend(sequence.map(|(before, after)| format!("  {before} -.-> {after}")));
+        }
+        GraphFormat::Dot => {
+            lines.push("digraph tasks {".to_string());
+            lines.push("  rankdir=TB;".to_string());
+            lines.push("  node [shape=box, style=\"rounded,filled\"];".to_string());
+            for task in tasks {
+                let (fill, stroke) = STATUS_COLORS
+                    .iter()
+                    .find(|(status, _, _)| *status == task.status)
+                    .map_or(("#ffffff", "#000000"), |(_, fill, stroke)| (*fill, *stroke));
+                lines.push(format!(
+                    "  {} [label=\"{}\", fillcolor=\"{fill}\", color=\"{stroke}\"];",
+                    node_id(&task.id),
+                    label(task)
+                        .replace('\\', "\\\\")
+                        .replace('"', "\\\"")
+                        .replace('\n', "\\n")
+                ));
+            }
+            lines.extend(
+                hierarchy
+                    .iter()
+                    .map(|(parent, child)| format!("  {parent} -> {child};")),
+            );
+            lines.extend(
+                sequence.map(|(before, after)| format!("  {before} -> {after} [style=dashed];")),
+            );
+            lines.push("}".to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+/// Title, then kind and status, on two lines.
+fn label(task: &PlannerTaskFileEntry) -> String {
+    format!(
+        "{}\n{} · {}",
+        task.title.trim(),
+        kind_name(task.kind),
+        status_name(task.status).replace('_', " ")
+    )
+}
+
+fn kind_name(kind: PlannerTaskKindFile) -> &'static str {
+    match kind {
+        PlannerTaskKindFile::Task => "task",
+        PlannerTaskKindFile::Implementor => "implementor",
+        PlannerTaskKindFile::Auditor => "auditor",
+        PlannerTaskKindFile::TestWriter => "test_writer",
+        PlannerTaskKindFile::TestRunner => "test_runner",
+        PlannerTaskKindFile::FinalAudit => "final_audit",
+        PlannerTaskKindFile::Plugin => "plugin",
+    }
+}
+
+fn status_name(status: PlannerTaskStatusFile) -> &'static str {
+    match status {
+        PlannerTaskStatusFile::Pending => "pending",
+        PlannerTaskStatusFile::InProgress => "in_progress",
+        PlannerTaskStatusFile::NeedsChanges => "needs_changes",
+        PlannerTaskStatusFile::Done => "done",
+        PlannerTaskStatusFile::Blocked => "blocked",
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/task_graph_tests.rs"]
+mod tests;
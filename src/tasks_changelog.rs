@@ -0,0 +1,95 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::session_store::PlannerTaskFileEntry;
+use crate::task_diff::{self, TaskChange};
+
+/// Agent whose run rewrote tasks.json.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TasksChangeAgent {
+    Master,
+    TaskCheck,
+    DocsAttach,
+}
+
+/// One line of a session's `tasks-changelog.jsonl`: what one agent run changed in tasks.json.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TasksChangelogEntry {
+    pub at_epoch_ms: u64,
+    pub agent: TasksChangeAgent,
+    pub changes: Vec<TaskChange>,
+    /// tasks.json as the run left it, so the plan can be shown at any point of its history.
+    pub tasks: Vec<PlannerTaskFileEntry>,
+}
+
+/// Appends the changes `agent` made from the tasks.json text `before` to `after` to the
+/// changelog at `path`. A missing or unreadable `before` counts as an empty plan. Returns
+/// false without writing when `after` does not parse or nothing changed.
+pub fn record(
+    path: &Path,
+    agent: TasksChangeAgent,
+    before: Option<&str>,
+    after: Option<&str>,
+) -> io::Result<bool> {
+    let Some(tasks) = after.and_then(parse_tasks) else {
+        return Ok(false);
+    };
+    let before = before.and_then(parse_tasks).unwrap_or_default();
+    let changes = task_diff::diff(&before, &tasks);
+    if changes.is_empty() {
+        return Ok(false);
+    }
+    append_entry(
+        path,
+        &TasksChangelogEntry {
+            at_epoch_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as u64)
+                .unwrap_or_default(),
+            agent,
+            changes,
+            tasks,
+        },
+    )?;
+    Ok(true)
+}
+
+fn parse_tasks(text: &str) -> Option<Vec<PlannerTaskFileEntry>> {
+    serde_json::from_str(text).ok()
+}
+
+fn append_entry(path: &Path, entry: &TasksChangelogEntry) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(entry).map_err(io::Error::other)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Entries in `path`, oldest first; a missing file has none and unreadable lines are skipped.
+#[cfg(test)]
+pub(crate) fn read_entries(path: &Path) -> io::Result<Vec<TasksChangelogEntry>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/tasks_changelog_tests.rs"]
+mod tests;
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use crate::app::{App, ExternalTasksEdit};
+use crate::i18n::Text;
+use crate::session_store::{PlannerTaskFileEntry, SessionStore};
+use crate::workflow::{FinalAuditPolicy, Workflow};
+
+/// Polls the active session's tasks.json and flags edits that did not come from the app or
+/// one of its agents, so they are offered for reload instead of being overwritten.
+#[derive(Debug, Default)]
+pub struct TasksFileWatcher {
+    session_dir: Option<PathBuf>,
+    known_text: Option<String>,
+    agent_was_writing: bool,
+}
+
+impl TasksFileWatcher {
+    /// `agent_may_write` is true while the master, task checker, or docs attacher can be
+    /// editing tasks.json; changes seen during (or right after) such a run are theirs.
+    /// Returns true when a new external edit was flagged.
+    pub fn poll(&mut self, app: &mut App, store: &SessionStore, agent_may_write: bool) -> bool {
+        let agent_was_writing = std::mem::replace(&mut self.agent_was_writing, agent_may_write);
+        let Ok(text) = std::fs::read_to_string(store.tasks_file()) else {
+            return false;
+        };
+        if self.session_dir.as_deref() != Some(store.session_dir()) {
+            self.session_dir = Some(store.session_dir().to_path_buf());
+            self.known_text = Some(text);
+            return false;
+        }
+        if self.known_text.as_deref() == Some(text.as_str()) {
+            return false;
+        }
+        self.known_text = Some(text.clone());
+        if agent_may_write || agent_was_writing {
+            return false;
+        }
+
+        let Some(edit) = external_edit_for(app, store, text) else {
+            // The app wrote this itself, or the user reverted their edit.
+            app.take_external_tasks_edit();
+            return false;
+        };
+        if app.external_tasks_edit() == Some(&edit) {
+            // Already pending, e.g. put back by `/conflict`.
+            return false;
+        }
+        app.push_agent_message(match edit.validation_error.as_deref() {
+            None => app.system_text(Text::TasksEditedExternally, &[]),
+            Some(err) => app.system_text(Text::TasksEditedExternallyInvalid, &[("err", &err)]),
+        });
+        app.set_external_tasks_edit(edit);
+        true
+    }
+}
+
+/// The pending edit `text` amounts to, or `None` when it holds the app's own task tree.
+pub(crate) fn external_edit_for(
+    app: &App,
+    store: &SessionStore,
+    text: String,
+) -> Option<ExternalTasksEdit> {
+    let policy = app.final_audit_policy();
+    let validation = serde_json::from_str::<Vec<PlannerTaskFileEntry>>(&text)
+        .map_err(|err| format!("invalid JSON: {err}"))
+        .and_then(|tasks| canonical_tasks(tasks, policy));
+    let matches_app = validation.as_ref().is_ok_and(|file| {
+        canonical_tasks(app.planner_tasks_for_file(), policy).as_ref() == Ok(file)
+    });
+    (!matches_app).then(|| ExternalTasksEdit {
+        session_dir: store.session_dir().to_path_buf(),
+        text,
+        validation_error: validation.err(),
+    })
+}
+
+/// Normalizes a task list through the same validation the app applies, so formatting and
+/// order numbering differences do not count as edits.
+fn canonical_tasks(
+    tasks: Vec<PlannerTaskFileEntry>,
+    policy: FinalAuditPolicy,
+) -> Result<serde_json::Value, String> {
+    let mut workflow = Workflow::default();
+    workflow.set_final_audit_policy(policy);
+    workflow.sync_planner_tasks_from_file(tasks)?;
+    serde_json::to_value(workflow.planner_tasks_for_file()).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/tasks_watch_tests.rs"]
+mod tests;
@@ -0,0 +1,488 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::artifact_io::load_merged_metaagent_config_text;
+use crate::ticket_sync::{HttpRequest, run_curl};
+
+/// Standard OTLP variable, used when `[telemetry] otlp_endpoint` is empty.
+const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+/// Spans are sent in batches of this size, or sooner once the oldest has waited this long.
+const EXPORT_BATCH_SIZE: usize = 64;
+const EXPORT_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+const EXPORT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// How long shutdown waits for the last batch to be delivered.
+const SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The `[telemetry]` table; an empty endpoint keeps tracing off.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    pub otlp_endpoint: String,
+    pub otlp_headers: Vec<String>,
+    pub service_name: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: String::new(),
+            otlp_headers: Vec::new(),
+            service_name: "agentbob".to_string(),
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// `<endpoint>/v1/traces`, or `None` when tracing is off.
+    pub fn traces_url(&self) -> Option<String> {
+        let endpoint = self.otlp_endpoint.trim().trim_end_matches('/');
+        if endpoint.is_empty() {
+            return None;
+        }
+        Some(if endpoint.ends_with(OTLP_TRACES_PATH) {
+            endpoint.to_string()
+        } else {
+            format!("{endpoint}{OTLP_TRACES_PATH}")
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TelemetryConfigFile {
+    telemetry: TelemetryConfig,
+}
+
+pub fn load_telemetry_config() -> io::Result<TelemetryConfig> {
+    let mut config = telemetry_config_from_toml(&load_merged_metaagent_config_text()?)?;
+    if config.otlp_endpoint.trim().is_empty()
+        && let Ok(endpoint) = std::env::var(OTLP_ENDPOINT_ENV)
+    {
+        config.otlp_endpoint = endpoint;
+    }
+    Ok(config)
+}
+
+pub(crate) fn telemetry_config_from_toml(text: &str) -> io::Result<TelemetryConfig> {
+    toml::from_str::<TelemetryConfigFile>(text)
+        .map(|file| file.telemetry)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Identifies a span so that work started elsewhere, like an adapter thread, can be
+/// recorded as its child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanContext {
+    pub trace_id: u128,
+    pub span_id: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    Internal,
+    /// A call out to another process: an agent CLI or the test command.
+    Client,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeValue {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<u8> for AttributeValue {
+    fn from(value: u8) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<usize> for AttributeValue {
+    fn from(value: usize) -> Self {
+        Self::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+/// A finished span as handed to the exporter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanRecord {
+    pub name: String,
+    pub context: SpanContext,
+    pub parent_span_id: Option<u64>,
+    pub kind: SpanKind,
+    pub start_unix_nanos: u64,
+    pub end_unix_nanos: u64,
+    pub attributes: Vec<(String, AttributeValue)>,
+    /// Set when the traced work failed; the span is exported with an error status.
+    pub error: Option<String>,
+}
+
+/// An open span; it is recorded when ended or dropped. Spans started while tracing is off
+/// record nothing.
+#[derive(Debug)]
+pub struct Span {
+    inner: Option<(SpanRecord, Sender<SpanRecord>)>,
+}
+
+impl Span {
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    pub fn context(&self) -> Option<SpanContext> {
+        self.inner.as_ref().map(|(record, _)| record.context)
+    }
+
+    pub fn set_attribute(&mut self, key: &str, value: impl Into<AttributeValue>) {
+        if let Some((record, _)) = self.inner.as_mut() {
+            record.attributes.push((key.to_string(), value.into()));
+        }
+    }
+
+    pub fn set_error(&mut self, message: impl Into<String>) {
+        if let Some((record, _)) = self.inner.as_mut() {
+            record.error = Some(message.into());
+        }
+    }
+
+    pub fn end(self) {}
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some((mut record, sink)) = self.inner.take() {
+            record.end_unix_nanos = unix_nanos().max(record.start_unix_nanos);
+            let _ = sink.send(record);
+        }
+    }
+}
+
+/// Starts spans for one trace and sends them to `sink` when they end.
+#[derive(Debug)]
+pub struct Tracer {
+    trace_id: u128,
+    sink: Sender<SpanRecord>,
+    /// The worker job running now; worker adapters and the test runner record their runs
+    /// under it.
+    job: Mutex<Option<SpanContext>>,
+}
+
+impl Tracer {
+    pub fn new(sink: Sender<SpanRecord>) -> Self {
+        Self {
+            trace_id: (u128::from(random_id()) << 64) | u128::from(random_id()),
+            sink,
+            job: Mutex::new(None),
+        }
+    }
+
+    pub fn start_span(&self, name: &str, kind: SpanKind, parent: Option<SpanContext>) -> Span {
+        Span {
+            inner: Some((
+                SpanRecord {
+                    name: name.to_string(),
+                    context: SpanContext {
+                        trace_id: parent.map_or(self.trace_id, |parent| parent.trace_id),
+                        span_id: random_id(),
+                    },
+                    parent_span_id: parent.map(|parent| parent.span_id),
+                    kind,
+                    start_unix_nanos: unix_nanos(),
+                    end_unix_nanos: 0,
+                    attributes: Vec::new(),
+                    error: None,
+                },
+                self.sink.clone(),
+            )),
+        }
+    }
+
+    pub fn set_job_context(&self, context: Option<SpanContext>) {
+        if let Ok(mut job) = self.job.lock() {
+            *job = context;
+        }
+    }
+
+    pub fn job_context(&self) -> Option<SpanContext> {
+        self.job.lock().ok().and_then(|job| *job)
+    }
+}
+
+struct GlobalTelemetry {
+    tracer: Tracer,
+    root: Mutex<Option<Span>>,
+    /// Asks the exporter to send what it holds now; it acknowledges on the given sender.
+    flush: Sender<Sender<()>>,
+    export_error: Mutex<Option<String>>,
+}
+
+static TELEMETRY: OnceLock<GlobalTelemetry> = OnceLock::new();
+
+/// Starts the OTLP exporter and the run's root span. Returns the traces URL, or `None` when
+/// tracing is off; calling it again keeps the first exporter.
+pub fn init(config: &TelemetryConfig) -> Option<String> {
+    let url = config.traces_url()?;
+    let (span_tx, span_rx) = mpsc::channel();
+    let (flush_tx, flush_rx) = mpsc::channel();
+    let telemetry = GlobalTelemetry {
+        tracer: Tracer::new(span_tx),
+        root: Mutex::new(None),
+        flush: flush_tx,
+        export_error: Mutex::new(None),
+    };
+    if TELEMETRY.set(telemetry).is_err() {
+        return Some(url);
+    }
+    let exporter = OtlpExporter {
+        url: url.clone(),
+        headers: config.otlp_headers.clone(),
+        service_name: config.service_name.clone(),
+    };
+    thread::spawn(move || exporter.run(span_rx, flush_rx));
+    if let Some(telemetry) = TELEMETRY.get() {
+        let mut root = telemetry
+            .tracer
+            .start_span("agentbob.run", SpanKind::Internal, None);
+        root.set_attribute("process.pid", i64::from(std::process::id()));
+        if let Ok(mut slot) = telemetry.root.lock() {
+            *slot = Some(root);
+        }
+    }
+    Some(url)
+}
+
+/// Ends the root span and waits briefly for the remaining spans to be exported.
+pub fn shutdown() {
+    let Some(telemetry) = TELEMETRY.get() else {
+        return;
+    };
+    if let Ok(mut root) = telemetry.root.lock() {
+        root.take();
+    }
+    let (ack_tx, ack_rx) = mpsc::channel();
+    if telemetry.flush.send(ack_tx).is_ok() {
+        let _ = ack_rx.recv_timeout(SHUTDOWN_FLUSH_TIMEOUT);
+    }
+}
+
+/// A span under the run's root span; inert while tracing is off.
+pub fn span(name: &str) -> Span {
+    child_span(name, SpanKind::Internal, None)
+}
+
+/// A span under `parent`, or under the run's root span when `parent` is `None`.
+pub fn child_span(name: &str, kind: SpanKind, parent: Option<SpanContext>) -> Span {
+    let Some(telemetry) = TELEMETRY.get() else {
+        return Span::disabled();
+    };
+    let parent = parent.or_else(|| {
+        telemetry
+            .root
+            .lock()
+            .ok()
+            .and_then(|root| root.as_ref().and_then(Span::context))
+    });
+    telemetry.tracer.start_span(name, kind, parent)
+}
+
+pub fn set_job_context(context: Option<SpanContext>) {
+    if let Some(telemetry) = TELEMETRY.get() {
+        telemetry.tracer.set_job_context(context);
+    }
+}
+
+pub fn job_context() -> Option<SpanContext> {
+    TELEMETRY
+        .get()
+        .and_then(|telemetry| telemetry.tracer.job_context())
+}
+
+/// The first export failure since the last call, so it is reported once.
+pub fn take_export_error() -> Option<String> {
+    TELEMETRY
+        .get()
+        .and_then(|telemetry| telemetry.export_error.lock().ok()?.take())
+}
+
+struct OtlpExporter {
+    url: String,
+    headers: Vec<String>,
+    service_name: String,
+}
+
+impl OtlpExporter {
+    fn run(self, spans: Receiver<SpanRecord>, flushes: Receiver<Sender<()>>) {
+        let mut batch = Vec::new();
+        let mut batch_started = Instant::now();
+        let mut failing = false;
+        loop {
+            let disconnected = match spans.recv_timeout(EXPORT_POLL_INTERVAL) {
+                Ok(record) => {
+                    if batch.is_empty() {
+                        batch_started = Instant::now();
+                    }
+                    batch.push(record);
+                    false
+                }
+                Err(RecvTimeoutError::Timeout) => false,
+                Err(RecvTimeoutError::Disconnected) => true,
+            };
+            let ack = flushes.try_recv().ok();
+            if ack.is_some() {
+                batch.extend(spans.try_iter());
+            }
+            let due = ack.is_some()
+                || disconnected
+                || batch.len() >= EXPORT_BATCH_SIZE
+                || batch_started.elapsed() >= EXPORT_FLUSH_INTERVAL;
+            if due && !batch.is_empty() {
+                let result = run_curl(&HttpRequest {
+                    method: "POST",
+                    url: self.url.clone(),
+                    headers: self.headers.clone(),
+                    user: None,
+                    body: Some(otlp_traces_json(&self.service_name, &batch)),
+                });
+                batch.clear();
+                match result {
+                    // Only the first failure of a streak is reported.
+                    Ok(_) => failing = false,
+                    Err(err) if !failing => {
+                        failing = true;
+                        report_export_error(err);
+                    }
+                    Err(_) => {}
+                }
+            }
+            if let Some(ack) = ack {
+                let _ = ack.send(());
+            }
+            if disconnected {
+                break;
+            }
+        }
+    }
+}
+
+fn report_export_error(err: String) {
+    if let Some(telemetry) = TELEMETRY.get()
+        && let Ok(mut slot) = telemetry.export_error.lock()
+    {
+        *slot = Some(err);
+    }
+}
+
+/// OTLP/HTTP JSON body (`ExportTraceServiceRequest`) for one batch of spans.
+pub fn otlp_traces_json(service_name: &str, spans: &[SpanRecord]) -> Value {
+    let spans = spans
+        .iter()
+        .map(|span| {
+            let mut value = json!({
+                "traceId": format!("{:032x}", span.context.trace_id),
+                "spanId": format!("{:016x}", span.context.span_id),
+                "name": span.name,
+                "kind": match span.kind {
+                    SpanKind::Internal => 1,
+                    SpanKind::Client => 3,
+                },
+                "startTimeUnixNano": span.start_unix_nanos.to_string(),
+                "endTimeUnixNano": span.end_unix_nanos.to_string(),
+                "attributes": span
+                    .attributes
+                    .iter()
+                    .map(|(key, value)| otlp_attribute(key, value))
+                    .collect::<Vec<_>>(),
+                "status": match &span.error {
+                    Some(message) => json!({ "code": 2, "message": message }),
+                    None => json!({ "code": 1 }),
+                },
+            });
+            if let Some(parent) = span.parent_span_id {
+                value["parentSpanId"] = json!(format!("{parent:016x}"));
+            }
+            value
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [otlp_attribute("service.name", &AttributeValue::from(service_name))],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "agentbob", "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+fn otlp_attribute(key: &str, value: &AttributeValue) -> Value {
+    let value = match value {
+        AttributeValue::Str(text) => json!({ "stringValue": text }),
+        // OTLP JSON encodes 64-bit integers as strings.
+        AttributeValue::Int(number) => json!({ "intValue": number.to_string() }),
+        AttributeValue::Bool(flag) => json!({ "boolValue": flag }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+fn unix_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
+
+/// Non-zero random id; `RandomState` is seeded per instance, and the counter keeps ids
+/// distinct even if two seeds collide.
+fn random_id() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.write_u64(unix_nanos());
+    hasher.finish().max(1)
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/telemetry_tests.rs"]
+mod tests;
@@ -0,0 +1,318 @@
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use ratatui::buffer::Buffer;
+
+use crate::artifact_io::load_merged_metaagent_config_text;
+
+/// Chat rows reserved under an image's text fallback when the terminal can draw it.
+pub const INLINE_IMAGE_ROWS: u16 = 12;
+/// Widest an inline image is drawn, in cells.
+const MAX_IMAGE_COLS: u16 = 64;
+/// Start of the text an image reference is shown as: `[image: <path>] <alt>`.
+pub const IMAGE_MARKER: &str = "[image: ";
+/// Largest payload an iTerm2 image is sent with; bigger files keep their text fallback.
+const MAX_ITERM2_IMAGE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// `[ui] graphics`: whether images referenced in chat are drawn in terminals that support it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphicsMode {
+    #[default]
+    Auto,
+    Off,
+}
+
+impl GraphicsMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+}
+
+/// Inline image protocol the terminal speaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+}
+
+impl GraphicsProtocol {
+    /// The protocol of the terminal described by `env`; `None` inside tmux or screen, whose
+    /// passthrough is not set up, and in terminals without one.
+    pub fn detect(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if env("TMUX").is_some() || env("STY").is_some() {
+            return None;
+        }
+        let term = env("TERM").unwrap_or_default();
+        let program = env("TERM_PROGRAM").unwrap_or_default();
+        if term == "xterm-kitty" || env("KITTY_WINDOW_ID").is_some() || program == "ghostty" {
+            return Some(Self::Kitty);
+        }
+        if matches!(program.as_str(), "iTerm.app" | "WezTerm") {
+            return Some(Self::Iterm2);
+        }
+        None
+    }
+}
+
+/// Loads `[ui] graphics` and resolves it against this terminal.
+pub fn load_graphics_protocol() -> io::Result<Option<GraphicsProtocol>> {
+    let mode = graphics_mode_from_toml(&load_merged_metaagent_config_text()?)?;
+    Ok(match mode {
+        GraphicsMode::Auto => GraphicsProtocol::detect(|name| std::env::var(name).ok()),
+        GraphicsMode::Off => None,
+    })
+}
+
+pub(crate) fn graphics_mode_from_toml(text: &str) -> io::Result<GraphicsMode> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let Some(value) = parsed
+        .get("ui")
+        .and_then(toml::Value::as_table)
+        .and_then(|table| table.get("graphics"))
+    else {
+        return Ok(GraphicsMode::default());
+    };
+    value.as_str().and_then(GraphicsMode::parse).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("ui.graphics must be \"auto\" or \"off\" (got {value})"),
+        )
+    })
+}
+
+/// `text` with each Markdown reference to a PNG, `![alt](path.png)`, replaced by its text
+/// fallback `[image: path.png] alt`.
+pub fn with_image_fallbacks(text: &str) -> Cow<'_, str> {
+    if !text.contains("![") {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("![") {
+        let (before, candidate) = rest.split_at(start);
+        out.push_str(before);
+        match parse_image_ref(candidate) {
+            Some((alt, path, len)) => {
+                out.push_str(IMAGE_MARKER);
+                out.push_str(path);
+                out.push(']');
+                if !alt.is_empty() {
+                    out.push(' ');
+                    out.push_str(alt);
+                }
+                rest = &candidate[len..];
+            }
+            None => {
+                out.push_str("![");
+                rest = &candidate[2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// Alt text, target, and byte length of the `![alt](target.png)` at the start of `text`.
+fn parse_image_ref(text: &str) -> Option<(&str, &str, usize)> {
+    let after_bang = text.strip_prefix("![")?;
+    let alt_end = after_bang.find(']')?;
+    let alt = &after_bang[..alt_end];
+    let target_part = after_bang[alt_end + 1..].strip_prefix('(')?;
+    let target_end = target_part.find(')')?;
+    let target = &target_part[..target_end];
+    if alt.contains('\n')
+        || target.is_empty()
+        || target.contains(char::is_whitespace)
+        || !target.to_ascii_lowercase().ends_with(".png")
+    {
+        return None;
+    }
+    Some((alt.trim(), target, 2 + alt_end + 2 + target_end + 1))
+}
+
+/// An image fallback found on screen and the blank cells under it the image is drawn in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImagePlacement {
+    pub path: String,
+    pub x: u16,
+    /// First row below the fallback line.
+    pub y: u16,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+/// Image fallbacks in a rendered frame. Each is drawn in the blank cells below it that share
+/// its background, up to `max_rows` rows; a fallback wrapped over two lines is skipped.
+pub fn find_placements(buffer: &Buffer, max_rows: u16) -> Vec<ImagePlacement> {
+    let area = buffer.area;
+    let marker: Vec<char> = IMAGE_MARKER.chars().collect();
+    let mut placements = Vec::new();
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x + marker.len() as u16 <= area.right() {
+            let matches = marker.iter().enumerate().all(|(offset, ch)| {
+                buffer[(x + offset as u16, y)].symbol() == ch.encode_utf8(&mut [0; 4])
+            });
+            if !matches {
+                x += 1;
+                continue;
+            }
+            let mut path = String::new();
+            let mut end = x + marker.len() as u16;
+            let mut closed = false;
+            while end < area.right() {
+                let symbol = buffer[(end, y)].symbol();
+                end += 1;
+                if symbol == "]" {
+                    closed = true;
+                    break;
+                }
+                path.push_str(symbol);
+            }
+            if closed && let Some(placement) = placement_below(buffer, x, y, path, max_rows) {
+                placements.push(placement);
+            }
+            x = end;
+        }
+    }
+    placements
+}
+
+fn placement_below(
+    buffer: &Buffer,
+    x: u16,
+    y: u16,
+    path: String,
+    max_rows: u16,
+) -> Option<ImagePlacement> {
+    let area = buffer.area;
+    let background = buffer[(x, y)].bg;
+    let blank = |cx: u16, cy: u16| {
+        let cell = &buffer[(cx, cy)];
+        cell.symbol() == " " && cell.bg == background
+    };
+    let below = y.checked_add(1).filter(|row| *row < area.bottom())?;
+    let cols = (x..area.right())
+        .take_while(|cx| blank(*cx, below))
+        .count()
+        .min(MAX_IMAGE_COLS as usize) as u16;
+    if cols == 0 {
+        return None;
+    }
+    let rows = (below..area.bottom())
+        .take(max_rows as usize)
+        .take_while(|cy| (x..x + cols).all(|cx| blank(cx, *cy)))
+        .count() as u16;
+    (rows > 0).then_some(ImagePlacement {
+        path,
+        x,
+        y: below,
+        cols,
+        rows,
+    })
+}
+
+/// Images drawn over the last frame, so they are only redrawn when the screen moves them.
+#[derive(Debug, Default)]
+pub struct InlineImages {
+    shown: Vec<ImagePlacement>,
+}
+
+impl InlineImages {
+    /// Draws `placements` when they differ from the ones on screen, resolving relative
+    /// paths against `root`. Returns true when stale iTerm2 images are still in the cells,
+    /// so the caller should clear the terminal and draw again.
+    pub fn update(
+        &mut self,
+        out: &mut impl Write,
+        protocol: GraphicsProtocol,
+        placements: Vec<ImagePlacement>,
+        root: &Path,
+    ) -> io::Result<bool> {
+        if placements == self.shown {
+            return Ok(false);
+        }
+        if protocol == GraphicsProtocol::Iterm2 && !self.shown.is_empty() {
+            self.shown.clear();
+            return Ok(true);
+        }
+        let mut sequence = String::from("\x1b7");
+        if protocol == GraphicsProtocol::Kitty && !self.shown.is_empty() {
+            sequence.push_str(KITTY_DELETE_ALL);
+        }
+        for placement in &placements {
+            let path = root.join(&placement.path);
+            let Some(image) = encode_image(protocol, &path, placement.cols, placement.rows) else {
+                continue;
+            };
+            sequence.push_str(&format!("\x1b[{};{}H", placement.y + 1, placement.x + 1));
+            sequence.push_str(&image);
+        }
+        sequence.push_str("\x1b8");
+        out.write_all(sequence.as_bytes())?;
+        out.flush()?;
+        self.shown = placements;
+        Ok(false)
+    }
+}
+
+/// Deletes every kitty image placement on screen.
+const KITTY_DELETE_ALL: &str = "\x1b_Ga=d,d=A,q=2\x1b\\";
+
+/// The escape sequence that draws the PNG at `path` in a `cols` x `rows` cell box at the
+/// cursor; `None` when the file is missing or, for iTerm2, too large to send inline.
+pub fn encode_image(
+    protocol: GraphicsProtocol,
+    path: &Path,
+    cols: u16,
+    rows: u16,
+) -> Option<String> {
+    let metadata = fs::metadata(path).ok().filter(|meta| meta.is_file())?;
+    match protocol {
+        // The terminal reads the file itself, so only its path is sent.
+        GraphicsProtocol::Kitty => Some(format!(
+            "\x1b_Ga=T,f=100,t=f,c={cols},r={rows},C=1,q=2;{}\x1b\\",
+            base64(path.to_string_lossy().as_bytes())
+        )),
+        GraphicsProtocol::Iterm2 => {
+            if metadata.len() > MAX_ITERM2_IMAGE_BYTES {
+                return None;
+            }
+            let data = fs::read(path).ok()?;
+            Some(format!(
+                "\x1b]1337;File=inline=1;size={};width={cols};height={rows};preserveAspectRatio=1:{}\x07",
+                data.len(),
+                base64(&data)
+            ))
+        }
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk.iter().enumerate().fold(0u32, |acc, (index, byte)| {
+            acc | (u32::from(*byte) << (16 - 8 * index))
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                out.push(ALPHABET[(triple >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/terminal_graphics_tests.rs"]
+mod tests;
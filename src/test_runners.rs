@@ -0,0 +1,533 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::deterministic::TestRunnerConfig;
+
+/// Longest failure message kept per test case; the rest is summarized in one line.
+const MAX_MESSAGE_LINES: usize = 20;
+
+/// The runner named by a session's meta.json `test_runner`, for example
+/// `{"kind": "cargo", "args": ["--workspace"]}`. Without one, `test_command` runs as a
+/// `shell` runner.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TestRunnerSpec {
+    /// `shell`, `cargo`, `pytest`, or `docker_compose`.
+    pub kind: String,
+    /// Command line for the `shell` runner.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Extra arguments: after `cargo test`, after `pytest`, or after the compose service.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Compose file for `docker_compose`; compose's own lookup applies when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Compose service that runs the tests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+}
+
+impl TestRunnerSpec {
+    pub fn shell(command: &str) -> Self {
+        Self {
+            kind: "shell".to_string(),
+            command: Some(command.to_string()),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCaseResult {
+    pub name: String,
+    pub outcome: TestOutcome,
+    /// `path:line[:column]` where the failure was raised.
+    pub location: Option<String>,
+    /// Assertion or error message of a failure.
+    pub message: Option<String>,
+}
+
+/// Test cases a runner recognized in one run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestRunReport {
+    pub runner: String,
+    pub cases: Vec<TestCaseResult>,
+    /// Whether the cases came from the runner's structured output (cargo JSON events or a
+    /// JUnit report), so failure feedback can be built from them instead of the output.
+    pub detailed: bool,
+}
+
+impl TestRunReport {
+    pub fn new(runner: &str) -> Self {
+        Self {
+            runner: runner.to_string(),
+            cases: Vec::new(),
+            detailed: false,
+        }
+    }
+
+    pub fn count(&self, outcome: TestOutcome) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.outcome == outcome)
+            .count()
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &TestCaseResult> {
+        self.cases
+            .iter()
+            .filter(|case| case.outcome == TestOutcome::Failed)
+    }
+
+    /// `cargo: 12 passed, 2 failed, 1 skipped`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {} passed, {} failed, {} skipped",
+            self.runner,
+            self.count(TestOutcome::Passed),
+            self.count(TestOutcome::Failed),
+            self.count(TestOutcome::Skipped)
+        )
+    }
+
+    fn push(
+        &mut self,
+        name: &str,
+        outcome: TestOutcome,
+        location: Option<String>,
+        message: Option<String>,
+    ) {
+        self.cases.push(TestCaseResult {
+            name: name.to_string(),
+            outcome,
+            location,
+            message: message
+                .map(|message| cap_lines(message.trim()))
+                .filter(|message| !message.is_empty()),
+        });
+    }
+}
+
+/// What to execute for one deterministic run.
+#[derive(Debug, Clone)]
+pub struct RunnerCommand {
+    pub config: TestRunnerConfig,
+    pub env: Vec<(String, String)>,
+    /// Report the command writes and the runner reads once it exits.
+    pub report_file: Option<PathBuf>,
+    /// Run after the last run, whatever its result, with output discarded.
+    pub cleanup: Option<TestRunnerConfig>,
+}
+
+impl RunnerCommand {
+    fn new(program: &str, args: Vec<String>) -> Self {
+        Self {
+            config: TestRunnerConfig {
+                program: program.to_string(),
+                args,
+            },
+            env: Vec::new(),
+            report_file: None,
+            cleanup: None,
+        }
+    }
+}
+
+/// A typed way of running a session's deterministic tests and reading their results.
+pub trait DeterministicRunner: Send + Sync {
+    fn kind(&self) -> &'static str;
+
+    fn command(&self, spec: &TestRunnerSpec) -> Result<RunnerCommand, String>;
+
+    /// Records any test result on an output line and returns the lines to show for it.
+    fn parse_line(&self, line: &str, report: &mut TestRunReport) -> Vec<String> {
+        parse_text_result_line(line, report);
+        vec![line.to_string()]
+    }
+
+    /// Completes `report` once the command has exited.
+    fn finish(
+        &self,
+        _spec: &TestRunnerSpec,
+        _command: &RunnerCommand,
+        _success: bool,
+        _report: &mut TestRunReport,
+    ) {
+    }
+}
+
+/// Runners by kind. `TestRunnerAdapter` looks runners up here for each test job.
+pub struct RunnerRegistry {
+    runners: Vec<Arc<dyn DeterministicRunner>>,
+}
+
+impl RunnerRegistry {
+    pub fn register_defaults() -> Self {
+        Self {
+            runners: vec![
+                Arc::new(ShellRunner),
+                Arc::new(CargoRunner),
+                Arc::new(PytestRunner),
+                Arc::new(DockerComposeRunner),
+            ],
+        }
+    }
+
+    pub fn get(&self, kind: &str) -> Option<Arc<dyn DeterministicRunner>> {
+        let kind = kind.trim().to_ascii_lowercase().replace('-', "_");
+        self.runners
+            .iter()
+            .find(|runner| runner.kind() == kind)
+            .cloned()
+    }
+
+    pub fn kinds(&self) -> Vec<&'static str> {
+        self.runners.iter().map(|runner| runner.kind()).collect()
+    }
+
+    /// The runner for `spec` and the command it runs.
+    pub fn resolve(
+        &self,
+        spec: &TestRunnerSpec,
+    ) -> Result<(Arc<dyn DeterministicRunner>, RunnerCommand), String> {
+        let runner = self.get(&spec.kind).ok_or_else(|| {
+            format!(
+                "unknown test_runner kind {:?} (expected one of: {})",
+                spec.kind,
+                self.kinds().join(", ")
+            )
+        })?;
+        let command = runner.command(spec)?;
+        Ok((runner, command))
+    }
+}
+
+struct ShellRunner;
+
+impl DeterministicRunner for ShellRunner {
+    fn kind(&self) -> &'static str {
+        "shell"
+    }
+
+    fn command(&self, spec: &TestRunnerSpec) -> Result<RunnerCommand, String> {
+        let command = spec
+            .command
+            .as_deref()
+            .map(str::trim)
+            .filter(|command| !command.is_empty())
+            .ok_or_else(|| "the shell test runner needs a command".to_string())?;
+        Ok(RunnerCommand::new(
+            "bash",
+            vec!["-lc".to_string(), command.to_string()],
+        ))
+    }
+}
+
+/// `cargo test` with libtest's JSON event stream, which stable toolchains only emit with
+/// `RUSTC_BOOTSTRAP=1`.
+struct CargoRunner;
+
+impl DeterministicRunner for CargoRunner {
+    fn kind(&self) -> &'static str {
+        "cargo"
+    }
+
+    fn command(&self, spec: &TestRunnerSpec) -> Result<RunnerCommand, String> {
+        let mut args = vec!["test".to_string()];
+        args.extend(spec.args.iter().cloned());
+        if !spec.args.iter().any(|arg| arg == "--") {
+            args.push("--".to_string());
+        }
+        args.extend(["-Z", "unstable-options", "--format", "json"].map(str::to_string));
+        let mut command = RunnerCommand::new("cargo", args);
+        command
+            .env
+            .push(("RUSTC_BOOTSTRAP".to_string(), "1".to_string()));
+        Ok(command)
+    }
+
+    fn parse_line(&self, line: &str, report: &mut TestRunReport) -> Vec<String> {
+        let Ok(event) = serde_json::from_str::<Value>(line.trim()) else {
+            return vec![line.to_string()];
+        };
+        let field = |key: &str| event.get(key).and_then(Value::as_str).unwrap_or_default();
+        match (field("type"), field("event")) {
+            (_, "started") => Vec::new(),
+            ("test", result) => {
+                let name = field("name");
+                let (outcome, label) = match result {
+                    "ok" => (TestOutcome::Passed, "ok"),
+                    "ignored" => (TestOutcome::Skipped, "ignored"),
+                    _ => (TestOutcome::Failed, "FAILED"),
+                };
+                let output = event.get("stdout").and_then(Value::as_str);
+                let (location, message) = match output {
+                    Some(output) if outcome == TestOutcome::Failed => parse_panic(output),
+                    _ => (None, None),
+                };
+                report.detailed = true;
+                report.push(name, outcome, location, message);
+                let mut shown = vec![format!("test {name} ... {label}")];
+                if outcome == TestOutcome::Failed {
+                    shown.extend(output.unwrap_or_default().lines().map(str::to_string));
+                }
+                shown
+            }
+            ("suite", result) => vec![format!(
+                "test result: {}. {} passed; {} failed; {} ignored",
+                if result == "ok" { "ok" } else { "FAILED" },
+                event.get("passed").and_then(Value::as_u64).unwrap_or(0),
+                event.get("failed").and_then(Value::as_u64).unwrap_or(0),
+                event.get("ignored").and_then(Value::as_u64).unwrap_or(0)
+            )],
+            _ => vec![line.to_string()],
+        }
+    }
+}
+
+/// pytest writing a JUnit XML report that is read after the run.
+struct PytestRunner;
+
+impl DeterministicRunner for PytestRunner {
+    fn kind(&self) -> &'static str {
+        "pytest"
+    }
+
+    fn command(&self, spec: &TestRunnerSpec) -> Result<RunnerCommand, String> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or_default();
+        let report_file = std::env::temp_dir().join(format!(
+            "agentbob-pytest-{}-{nanos}.xml",
+            std::process::id()
+        ));
+        let mut args = spec.args.clone();
+        args.push(format!("--junitxml={}", report_file.display()));
+        let mut command = RunnerCommand::new("pytest", args);
+        command.report_file = Some(report_file);
+        Ok(command)
+    }
+
+    fn parse_line(&self, line: &str, _report: &mut TestRunReport) -> Vec<String> {
+        vec![line.to_string()]
+    }
+
+    fn finish(
+        &self,
+        _spec: &TestRunnerSpec,
+        command: &RunnerCommand,
+        _success: bool,
+        report: &mut TestRunReport,
+    ) {
+        let Some(path) = command.report_file.as_ref() else {
+            return;
+        };
+        if let Ok(xml) = std::fs::read_to_string(path) {
+            parse_junit_xml(&xml, report);
+            report.detailed = true;
+        }
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// `docker compose run` of one service, with the project torn down afterwards.
+struct DockerComposeRunner;
+
+impl DeterministicRunner for DockerComposeRunner {
+    fn kind(&self) -> &'static str {
+        "docker_compose"
+    }
+
+    fn command(&self, spec: &TestRunnerSpec) -> Result<RunnerCommand, String> {
+        let service = spec
+            .service
+            .as_deref()
+            .map(str::trim)
+            .filter(|service| !service.is_empty())
+            .ok_or_else(|| "the docker_compose test runner needs a service".to_string())?;
+        let mut compose = vec!["compose".to_string()];
+        if let Some(file) = spec.file.as_deref().filter(|file| !file.trim().is_empty()) {
+            compose.extend(["-f".to_string(), file.trim().to_string()]);
+        }
+        let mut args = compose.clone();
+        args.extend(["run", "--rm", "--build", service].map(str::to_string));
+        args.extend(spec.args.iter().cloned());
+        let mut command = RunnerCommand::new("docker", args);
+        compose.extend(["down", "--remove-orphans"].map(str::to_string));
+        command.cleanup = Some(TestRunnerConfig {
+            program: "docker".to_string(),
+            args: compose,
+        });
+        Ok(command)
+    }
+
+    /// Without recognizable per-test lines, the service's exit is the one test case.
+    fn finish(
+        &self,
+        spec: &TestRunnerSpec,
+        _command: &RunnerCommand,
+        success: bool,
+        report: &mut TestRunReport,
+    ) {
+        if !report.cases.is_empty() {
+            return;
+        }
+        let service = spec.service.as_deref().unwrap_or_default().trim();
+        let outcome = if success {
+            TestOutcome::Passed
+        } else {
+            TestOutcome::Failed
+        };
+        report.push(service, outcome, None, None);
+    }
+}
+
+/// Records `test name ... ok` (libtest) and `path::name PASSED` (pytest -v) lines.
+fn parse_text_result_line(line: &str, report: &mut TestRunReport) {
+    let line = line.trim();
+    if let Some((name, result)) = line
+        .strip_prefix("test ")
+        .and_then(|rest| rest.rsplit_once(" ... "))
+    {
+        let outcome = match result.split(',').next().unwrap_or_default() {
+            "ok" => TestOutcome::Passed,
+            "FAILED" => TestOutcome::Failed,
+            "ignored" => TestOutcome::Skipped,
+            _ => return,
+        };
+        report.push(name, outcome, None, None);
+    } else if let Some((name, result)) = line.split_once(' ')
+        && name.contains("::")
+    {
+        let outcome = match result.split_whitespace().next().unwrap_or_default() {
+            "PASSED" | "XFAIL" => TestOutcome::Passed,
+            "FAILED" | "ERROR" | "XPASS" => TestOutcome::Failed,
+            "SKIPPED" => TestOutcome::Skipped,
+            _ => return,
+        };
+        report.push(name, outcome, None, None);
+    }
+}
+
+/// Location and message of the panic in a failed libtest case's captured output, for
+/// both `panicked at src/lib.rs:9:5:` followed by the message and the older
+/// `panicked at 'message', src/lib.rs:9:5`.
+fn parse_panic(output: &str) -> (Option<String>, Option<String>) {
+    let mut lines = output.lines();
+    let Some(header) = lines.by_ref().find(|line| line.contains("panicked at ")) else {
+        return (None, Some(output.to_string()));
+    };
+    let after = &header[header.find("panicked at ").unwrap_or(0) + "panicked at ".len()..];
+    if let Some(quoted) = after.strip_prefix('\'')
+        && let Some((message, location)) = quoted.rsplit_once("', ")
+    {
+        return (Some(location.to_string()), Some(message.to_string()));
+    }
+    let message = lines
+        .take_while(|line| !line.starts_with("note: ") && !line.starts_with("stack backtrace:"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (Some(after.trim_end_matches(':').to_string()), Some(message))
+}
+
+fn cap_lines(text: &str) -> String {
+    let lines = text.lines().collect::<Vec<_>>();
+    if lines.len() <= MAX_MESSAGE_LINES {
+        return text.to_string();
+    }
+    format!(
+        "{}\n... {} more line(s)",
+        lines[..MAX_MESSAGE_LINES].join("\n"),
+        lines.len() - MAX_MESSAGE_LINES
+    )
+}
+
+/// Reads `<testcase>` elements of a JUnit XML report, with `<failure>` and `<error>`
+/// marking failures and `<skipped>` skips.
+fn parse_junit_xml(xml: &str, report: &mut TestRunReport) {
+    let mut rest = xml;
+    while let Some(start) = rest.find("<testcase") {
+        rest = &rest[start + "<testcase".len()..];
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let attributes = &rest[..tag_end];
+        let self_closing = attributes.ends_with('/');
+        let body = if self_closing {
+            ""
+        } else {
+            let body_end = rest.find("</testcase>").unwrap_or(rest.len());
+            &rest[tag_end + 1..body_end]
+        };
+        let class_name = xml_attribute(attributes, "classname").unwrap_or_default();
+        let name = xml_attribute(attributes, "name").unwrap_or_default();
+        let name = if class_name.is_empty() {
+            name
+        } else {
+            format!("{class_name}::{name}")
+        };
+        let failure = ["<failure", "<error"]
+            .iter()
+            .find_map(|tag| body.find(tag).map(|at| &body[at..]));
+        if let Some(failure) = failure {
+            let tag_end = failure.find('>').unwrap_or(failure.len());
+            let message = xml_attribute(&failure[..tag_end], "message");
+            let trace = if failure[..tag_end].ends_with('/') {
+                String::new()
+            } else {
+                let text = &failure[(tag_end + 1).min(failure.len())..];
+                xml_unescape(&text[..text.find("</").unwrap_or(text.len())])
+            };
+            let location = traceback_location(&trace)
+                .or_else(|| xml_attribute(attributes, "file").filter(|file| !file.is_empty()));
+            report.push(&name, TestOutcome::Failed, location, message);
+        } else if body.contains("<skipped") {
+            report.push(&name, TestOutcome::Skipped, None, None);
+        } else {
+            report.push(&name, TestOutcome::Passed, None, None);
+        }
+        rest = &rest[tag_end..];
+    }
+}
+
+/// The `tests/test_api.py:14` of the last `tests/test_api.py:14: AssertionError` line of a
+/// pytest traceback.
+fn traceback_location(trace: &str) -> Option<String> {
+    trace.lines().rev().find_map(|line| {
+        let (location, _) = line.trim().split_once(": ")?;
+        let (path, line_number) = location.rsplit_once(':')?;
+        (path.ends_with(".py") && line_number.parse::<u32>().is_ok()).then(|| location.to_string())
+    })
+}
+
+fn xml_attribute(attributes: &str, key: &str) -> Option<String> {
+    let pattern = format!(" {key}=\"");
+    let start = attributes.find(&pattern)? + pattern.len();
+    let end = attributes[start..].find('"')? + start;
+    Some(xml_unescape(&attributes[start..end]))
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/test_runners_tests.rs"]
+mod tests;
@@ -61,6 +61,46 @@ pub fn wrap_word_with_positions(text: &str, width: u16) -> WrappedText {
     }
 }
 
+/// Byte offset of the `char_idx`-th character, or the string's length past its end.
+pub fn char_to_byte_idx(s: &str, char_idx: usize) -> usize {
+    s.char_indices()
+        .nth(char_idx)
+        .map(|(byte_idx, _)| byte_idx)
+        .unwrap_or_else(|| s.len())
+}
+
+/// Text index on wrapped line `target_line` closest to, without passing, column `goal_col`;
+/// takes `positions` from [`wrap_word_with_positions`].
+pub fn nearest_index_for_line_col(
+    positions: &[(u16, u16)],
+    target_line: u16,
+    goal_col: u16,
+) -> usize {
+    let mut best: Option<(usize, u16)> = None;
+    let mut fallback: Option<usize> = None;
+
+    for (idx, (line, col)) in positions.iter().copied().enumerate() {
+        if line != target_line {
+            continue;
+        }
+        if fallback.is_none() {
+            fallback = Some(idx);
+        }
+        if col <= goal_col {
+            best = match best {
+                Some((_, best_col)) if best_col >= col => best,
+                _ => Some((idx, col)),
+            };
+        }
+    }
+
+    if let Some((idx, _)) = best {
+        idx
+    } else {
+        fallback.unwrap_or(positions.len().saturating_sub(1))
+    }
+}
+
 fn word_lengths_from_each_index(chars: &[char]) -> Vec<usize> {
     let mut out = vec![0usize; chars.len()];
     let mut run = 0usize;
@@ -5,6 +5,122 @@ use serde::Deserialize;
 
 use crate::artifact_io::read_text_file;
 
+/// How many colors the terminal can show, from none to full RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    Monochrome,
+    Ansi16,
+    Indexed256,
+    TrueColor,
+}
+
+/// xterm's default RGB values for the 16 named colors, used to pick the nearest one.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+/// Channel values of the 6x6x6 cube in the 256-color palette (indices 16-231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorDepth {
+    /// Reads the terminal's color support from the environment.
+    pub fn detect() -> Self {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// A non-empty `NO_COLOR` (see no-color.org) or `TERM=dumb` turns colors off;
+    /// `COLORTERM=truecolor|24bit` means full RGB; a `TERM` naming 256 colors means the
+    /// 256-color palette; anything else gets the 16 named colors.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty()) || term == "dumb" {
+            return Self::Monochrome;
+        }
+        let colorterm = var("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Indexed256
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    /// The closest color this depth can show. Monochrome leaves every color to the
+    /// terminal's defaults.
+    pub fn downgrade(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::TrueColor, _) | (_, Color::Reset) => color,
+            (Self::Monochrome, _) => Color::Reset,
+            (Self::Indexed256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed((r, g, b))),
+            (Self::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi16((r, g, b)),
+            (Self::Ansi16, Color::Indexed(index)) => nearest_ansi16(indexed_rgb(index)),
+            _ => color,
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| (i32::from(a) - i32::from(b)).unsigned_abs().pow(2);
+    channel(r1, r2) + channel(g1, g2) + channel(b1, b2)
+}
+
+fn nearest_ansi16(rgb: (u8, u8, u8)) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, candidate)| distance(rgb, *candidate))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// The closer of the nearest cube color and the nearest gray ramp entry.
+fn nearest_indexed((r, g, b): (u8, u8, u8)) -> u8 {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|index| CUBE_LEVELS[*index].abs_diff(value))
+            .unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+    let average = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23);
+    if distance((r, g, b), indexed_rgb(gray)) < distance((r, g, b), indexed_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[usize::from(index)].1,
+        16..=231 => {
+            let cube = index - 16;
+            (
+                CUBE_LEVELS[usize::from(cube / 36)],
+                CUBE_LEVELS[usize::from(cube / 6 % 6)],
+                CUBE_LEVELS[usize::from(cube % 6)],
+            )
+        }
+        232..=255 => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Theme {
     pub left_top_bg: Color,
@@ -15,6 +131,8 @@ pub struct Theme {
     pub text_fg: Color,
     pub muted_fg: Color,
     pub active_fg: Color,
+    /// Every color drawn is downgraded to this depth before it reaches the terminal.
+    pub color_depth: ColorDepth,
 }
 
 impl Default for Theme {
@@ -28,11 +146,17 @@ impl Default for Theme {
             text_fg: Color::Rgb(225, 225, 225),
             muted_fg: Color::Rgb(185, 185, 185),
             active_fg: Color::Rgb(255, 255, 255),
+            color_depth: ColorDepth::TrueColor,
         }
     }
 }
 
 impl Theme {
+    pub fn with_color_depth(mut self, color_depth: ColorDepth) -> Self {
+        self.color_depth = color_depth;
+        self
+    }
+
     pub fn load_or_default(path: impl AsRef<Path>) -> Self {
         let path_ref = path.as_ref();
         match read_text_file(path_ref) {
@@ -67,6 +191,7 @@ impl Theme {
             text_fg: cfg.colors.text_fg.to_color(),
             muted_fg: cfg.colors.muted_fg.to_color(),
             active_fg: cfg.colors.active_fg.to_color(),
+            color_depth: ColorDepth::TrueColor,
         })
     }
 }
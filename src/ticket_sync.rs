@@ -0,0 +1,607 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::artifact_io::load_merged_metaagent_config_text;
+use crate::credentials;
+use crate::i18n::{self, Locale, Text};
+use crate::session_store::{
+    PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile, SessionStore,
+};
+
+const LINEAR_GRAPHQL_URL: &str = "https://api.linear.app/graphql";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketProvider {
+    Linear,
+    Jira,
+}
+
+impl TicketProvider {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::Jira => "jira",
+        }
+    }
+}
+
+/// A task's `external_ref`: `linear:ENG-123` or `jira:PROJ-42`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalRef {
+    pub provider: TicketProvider,
+    pub key: String,
+}
+
+impl ExternalRef {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (provider, key) = text
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| format!("`{text}` is not `linear:<KEY>` or `jira:<KEY>`"))?;
+        let provider = match provider.trim().to_ascii_lowercase().as_str() {
+            "linear" => TicketProvider::Linear,
+            "jira" => TicketProvider::Jira,
+            other => return Err(format!("Unknown ticket provider `{other}` in `{text}`")),
+        };
+        let key = key.trim().to_ascii_uppercase();
+        // Keys end up in URLs and queries, so only the usual ticket key characters are allowed.
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("Invalid ticket key in `{text}`"));
+        }
+        Ok(Self { provider, key })
+    }
+}
+
+impl std::fmt::Display for ExternalRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.provider.label(), self.key)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LinearConfig {
+    /// Falls back to the `linear` credential from `bob auth set linear` when empty.
+    #[serde(default)]
+    pub api_token: String,
+    #[serde(default = "default_in_progress_name")]
+    pub in_progress_state: String,
+    #[serde(default = "default_done_name")]
+    pub done_state: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct JiraConfig {
+    pub base_url: String,
+    pub email: String,
+    /// Falls back to the `jira` credential from `bob auth set jira` when empty.
+    #[serde(default)]
+    pub api_token: String,
+    #[serde(default = "default_in_progress_name")]
+    pub in_progress_transition: String,
+    #[serde(default = "default_done_name")]
+    pub done_transition: String,
+}
+
+/// The `[sync.linear]` and `[sync.jira]` tables; a provider without a table is not configured.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TicketSyncConfig {
+    pub linear: Option<LinearConfig>,
+    pub jira: Option<JiraConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TicketSyncConfigFile {
+    sync: TicketSyncConfig,
+}
+
+fn default_in_progress_name() -> String {
+    "In Progress".to_string()
+}
+
+fn default_done_name() -> String {
+    "Done".to_string()
+}
+
+pub fn load_ticket_sync_config() -> io::Result<TicketSyncConfig> {
+    let mut config = ticket_sync_config_from_toml(&load_merged_metaagent_config_text()?)?;
+    if let Some(linear) = config.linear.as_mut() {
+        credentials::fill_from_store(&mut linear.api_token, credentials::LINEAR_TOKEN);
+    }
+    if let Some(jira) = config.jira.as_mut() {
+        credentials::fill_from_store(&mut jira.api_token, credentials::JIRA_TOKEN);
+    }
+    Ok(config)
+}
+
+pub(crate) fn ticket_sync_config_from_toml(text: &str) -> io::Result<TicketSyncConfig> {
+    toml::from_str::<TicketSyncConfigFile>(text)
+        .map(|file| file.sync)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Ticket {
+    pub external_ref: String,
+    pub title: String,
+    pub description: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HttpRequest {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<String>,
+    /// `user:password` for basic auth.
+    pub user: Option<String>,
+    pub body: Option<Value>,
+}
+
+/// Sends one request and returns the parsed JSON body (`Null` when empty); swapped out in tests.
+pub(crate) type HttpRunner<'a> = dyn Fn(&HttpRequest) -> Result<Value, String> + 'a;
+
+pub fn fetch_ticket(config: &TicketSyncConfig, reference: &ExternalRef) -> Result<Ticket, String> {
+    fetch_ticket_with(config, reference, &run_curl)
+}
+
+pub(crate) fn fetch_ticket_with(
+    config: &TicketSyncConfig,
+    reference: &ExternalRef,
+    http: &HttpRunner<'_>,
+) -> Result<Ticket, String> {
+    match reference.provider {
+        TicketProvider::Linear => {
+            let linear = linear_config(config)?;
+            let data = linear_graphql(
+                linear,
+                "query($id: String!) { issue(id: $id) { identifier title description url } }",
+                json!({ "id": reference.key }),
+                http,
+            )?;
+            let issue = &data["issue"];
+            Ok(Ticket {
+                external_ref: reference.to_string(),
+                title: json_text(&issue["title"]),
+                description: json_text(&issue["description"]),
+                url: json_text(&issue["url"]),
+            })
+        }
+        TicketProvider::Jira => {
+            let jira = jira_config(config)?;
+            let issue = http(&jira_request(
+                jira,
+                "GET",
+                format!("issue/{}?fields=summary,description", reference.key),
+                None,
+            ))?;
+            Ok(Ticket {
+                external_ref: reference.to_string(),
+                title: json_text(&issue["fields"]["summary"]),
+                description: json_text(&issue["fields"]["description"]),
+                url: format!("{}/browse/{}", jira_base(jira), reference.key),
+            })
+        }
+    }
+}
+
+/// Moves the ticket to the state configured for `status`. Returns the state name, or `None`
+/// for statuses that are not synced (only InProgress and Done are).
+pub fn push_ticket_status(
+    config: &TicketSyncConfig,
+    reference: &ExternalRef,
+    status: PlannerTaskStatusFile,
+) -> Result<Option<String>, String> {
+    push_ticket_status_with(config, reference, status, &run_curl)
+}
+
+pub(crate) fn push_ticket_status_with(
+    config: &TicketSyncConfig,
+    reference: &ExternalRef,
+    status: PlannerTaskStatusFile,
+    http: &HttpRunner<'_>,
+) -> Result<Option<String>, String> {
+    if !matches!(
+        status,
+        PlannerTaskStatusFile::InProgress | PlannerTaskStatusFile::Done
+    ) {
+        return Ok(None);
+    }
+    let done = status == PlannerTaskStatusFile::Done;
+    match reference.provider {
+        TicketProvider::Linear => {
+            let linear = linear_config(config)?;
+            let target = if done {
+                &linear.done_state
+            } else {
+                &linear.in_progress_state
+            };
+            let data = linear_graphql(
+                linear,
+                "query($id: String!) { issue(id: $id) { id team { states { nodes { id name } } } } }",
+                json!({ "id": reference.key }),
+                http,
+            )?;
+            let issue = &data["issue"];
+            let state_id = issue["team"]["states"]["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|state| json_text(&state["name"]).eq_ignore_ascii_case(target))
+                .map(|state| json_text(&state["id"]))
+                .ok_or_else(|| format!("{reference} has no workflow state named `{target}`"))?;
+            linear_graphql(
+                linear,
+                "mutation($id: String!, $stateId: String!) { issueUpdate(id: $id, input: { stateId: $stateId }) { success } }",
+                json!({ "id": json_text(&issue["id"]), "stateId": state_id }),
+                http,
+            )?;
+            Ok(Some(target.clone()))
+        }
+        TicketProvider::Jira => {
+            let jira = jira_config(config)?;
+            let target = if done {
+                &jira.done_transition
+            } else {
+                &jira.in_progress_transition
+            };
+            let path = format!("issue/{}/transitions", reference.key);
+            let available = http(&jira_request(jira, "GET", path.clone(), None))?;
+            let transition_id = available["transitions"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|transition| {
+                    json_text(&transition["name"]).eq_ignore_ascii_case(target)
+                        || json_text(&transition["to"]["name"]).eq_ignore_ascii_case(target)
+                })
+                .map(|transition| json_text(&transition["id"]))
+                .ok_or_else(|| format!("{reference} has no available transition to `{target}`"))?;
+            http(&jira_request(
+                jira,
+                "POST",
+                path,
+                Some(json!({ "transition": { "id": transition_id } })),
+            ))?;
+            Ok(Some(target.clone()))
+        }
+    }
+}
+
+/// Appends each ticket not already linked by a task as a top-level task with an implementor
+/// and auditor, ahead of any final audit. Returns the number of tickets added.
+pub fn append_ticket_tasks(tasks: &mut Vec<PlannerTaskFileEntry>, tickets: &[Ticket]) -> usize {
+    let mut next_order = tasks
+        .iter()
+        .filter(|task| task.parent_id.is_none() && task.kind != PlannerTaskKindFile::FinalAudit)
+        .filter_map(|task| task.order)
+        .max()
+        .map_or(0, |order| order.saturating_add(1));
+    let mut added = 0;
+    for ticket in tickets {
+        if tasks
+            .iter()
+            .any(|task| task.external_ref.as_deref() == Some(ticket.external_ref.as_str()))
+        {
+            continue;
+        }
+        let key = ticket
+            .external_ref
+            .split_once(':')
+            .map_or(ticket.external_ref.as_str(), |(_, key)| key);
+        let base_id = key.to_ascii_lowercase();
+        let mut id = base_id.clone();
+        let mut suffix = 1usize;
+        while tasks.iter().any(|task| task.id == id) {
+            suffix += 1;
+            id = format!("{base_id}-{suffix}");
+        }
+        let mut details = ticket.description.trim().to_string();
+        if details.is_empty() {
+            details = ticket.title.trim().to_string();
+        }
+        if !ticket.url.is_empty() {
+            details.push_str(&format!("\n\nSource ticket: {}", ticket.url));
+        }
+        let entry = |id: String, title: String, details: String, kind, parent_id: Option<&str>| {
+            PlannerTaskFileEntry {
+                id,
+                title,
+                details,
+                docs: Vec::new(),
+                kind,
+                status: PlannerTaskStatusFile::Pending,
+                parent_id: parent_id.map(str::to_string),
+                order: Some(0),
+                ..Default::default()
+            }
+        };
+        let mut top = entry(
+            id.clone(),
+            format!("{key}: {}", ticket.title.trim()),
+            details,
+            PlannerTaskKindFile::Task,
+            None,
+        );
+        top.order = Some(next_order);
+        top.external_ref = Some(ticket.external_ref.clone());
+        tasks.push(top);
+        tasks.push(entry(
+            format!("{id}-impl"),
+            format!("Implement {key}"),
+            format!("Implement ticket {key} as described in the parent task's details."),
+            PlannerTaskKindFile::Implementor,
+            Some(&id),
+        ));
+        tasks.push(entry(
+            format!("{id}-audit"),
+            format!("Audit {key}"),
+            format!("Audit the {key} implementation against the parent task's details."),
+            PlannerTaskKindFile::Auditor,
+            Some(&format!("{id}-impl")),
+        ));
+        next_order = next_order.saturating_add(1);
+        added += 1;
+    }
+    added
+}
+
+/// Pushes linked tasks' status transitions to their tickets in the background, reporting each
+/// result as a chat line.
+#[derive(Debug)]
+pub struct TicketStatusSync {
+    tracked_session: Option<PathBuf>,
+    statuses: HashMap<String, PlannerTaskStatusFile>,
+    results_tx: Sender<String>,
+    results_rx: Receiver<String>,
+}
+
+impl Default for TicketStatusSync {
+    fn default() -> Self {
+        let (results_tx, results_rx) = mpsc::channel();
+        Self {
+            tracked_session: None,
+            statuses: HashMap::new(),
+            results_tx,
+            results_rx,
+        }
+    }
+}
+
+impl TicketStatusSync {
+    /// Starts a push for every linked task whose status changed since the previous call. Like
+    /// the session event log, the first call for a session only takes a baseline.
+    pub fn poll(&mut self, store: &SessionStore, tasks: &[PlannerTaskFileEntry], locale: Locale) {
+        let changed = self.changed_links(store, tasks);
+        if changed.is_empty() {
+            return;
+        }
+        let results_tx = self.results_tx.clone();
+        thread::spawn(move || {
+            let config = match load_ticket_sync_config() {
+                Ok(config) => config,
+                Err(err) => {
+                    let _ = results_tx.send(i18n::system(
+                        locale,
+                        Text::TicketSyncConfigFailed,
+                        &[("err", &err)],
+                    ));
+                    return;
+                }
+            };
+            for (reference, status) in changed {
+                let message = match push_ticket_status(&config, &reference, status) {
+                    Ok(Some(state)) => i18n::system(
+                        locale,
+                        Text::TicketMoved,
+                        &[("reference", &reference), ("state", &state)],
+                    ),
+                    Ok(None) => continue,
+                    Err(err) => i18n::system(
+                        locale,
+                        Text::TicketUpdateFailed,
+                        &[("reference", &reference), ("err", &err)],
+                    ),
+                };
+                let _ = results_tx.send(message);
+            }
+        });
+    }
+
+    pub fn drain_messages(&self) -> Vec<String> {
+        self.results_rx.try_iter().collect()
+    }
+
+    fn changed_links(
+        &mut self,
+        store: &SessionStore,
+        tasks: &[PlannerTaskFileEntry],
+    ) -> Vec<(ExternalRef, PlannerTaskStatusFile)> {
+        let previous = std::mem::replace(
+            &mut self.statuses,
+            tasks
+                .iter()
+                .filter(|task| task.external_ref.is_some())
+                .map(|task| (task.id.clone(), task.status))
+                .collect(),
+        );
+        if self.tracked_session.as_deref() != Some(store.session_dir()) {
+            self.tracked_session = Some(store.session_dir().to_path_buf());
+            return Vec::new();
+        }
+        tasks
+            .iter()
+            .filter(|task| {
+                previous
+                    .get(&task.id)
+                    .is_some_and(|status| *status != task.status)
+                    && matches!(
+                        task.status,
+                        PlannerTaskStatusFile::InProgress | PlannerTaskStatusFile::Done
+                    )
+            })
+            .filter_map(|task| {
+                let reference = ExternalRef::parse(task.external_ref.as_deref()?).ok()?;
+                Some((reference, task.status))
+            })
+            .collect()
+    }
+}
+
+fn linear_config(config: &TicketSyncConfig) -> Result<&LinearConfig, String> {
+    config
+        .linear
+        .as_ref()
+        .filter(|linear| !linear.api_token.trim().is_empty())
+        .ok_or_else(|| {
+            "Linear sync is not configured (add [sync.linear] and run `bob auth set linear`)"
+                .to_string()
+        })
+}
+
+fn jira_config(config: &TicketSyncConfig) -> Result<&JiraConfig, String> {
+    config
+        .jira
+        .as_ref()
+        .filter(|jira| !jira.api_token.trim().is_empty())
+        .ok_or_else(|| {
+            "Jira sync is not configured (add [sync.jira] base_url and email, and run `bob auth set jira`)"
+                .to_string()
+        })
+}
+
+fn linear_graphql(
+    linear: &LinearConfig,
+    query: &str,
+    variables: Value,
+    http: &HttpRunner<'_>,
+) -> Result<Value, String> {
+    let response = http(&HttpRequest {
+        method: "POST",
+        url: LINEAR_GRAPHQL_URL.to_string(),
+        headers: vec![format!("Authorization: {}", linear.api_token.trim())],
+        user: None,
+        body: Some(json!({ "query": query, "variables": variables })),
+    })?;
+    if let Some(message) = response["errors"]
+        .as_array()
+        .and_then(|errors| errors.first())
+        .map(|error| json_text(&error["message"]))
+    {
+        return Err(format!("Linear API error: {message}"));
+    }
+    Ok(response["data"].clone())
+}
+
+fn jira_base(jira: &JiraConfig) -> &str {
+    jira.base_url.trim().trim_end_matches('/')
+}
+
+fn jira_request(
+    jira: &JiraConfig,
+    method: &'static str,
+    path: String,
+    body: Option<Value>,
+) -> HttpRequest {
+    HttpRequest {
+        method,
+        url: format!("{}/rest/api/2/{path}", jira_base(jira)),
+        headers: vec!["Accept: application/json".to_string()],
+        user: Some(format!("{}:{}", jira.email.trim(), jira.api_token.trim())),
+        body,
+    }
+}
+
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Runs `curl`, passing the request as a config file on stdin so tokens stay off the
+/// command line.
+pub(crate) fn run_curl(request: &HttpRequest) -> Result<Value, String> {
+    let mut config = format!(
+        "silent\nshow-error\nfail-with-body\nmax-time = {}\nrequest = {}\nurl = {}\n",
+        HTTP_TIMEOUT.as_secs(),
+        curl_quote(request.method),
+        curl_quote(&request.url)
+    );
+    for header in &request.headers {
+        config.push_str(&format!("header = {}\n", curl_quote(header)));
+    }
+    if let Some(user) = &request.user {
+        config.push_str(&format!("user = {}\n", curl_quote(user)));
+    }
+    if let Some(body) = &request.body {
+        config.push_str("header = \"Content-Type: application/json\"\n");
+        config.push_str(&format!(
+            "data-binary = {}\n",
+            curl_quote(&body.to_string())
+        ));
+    }
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("could not run curl: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|err| format!("could not pass the request to curl: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("curl failed: {err}"))?;
+    let body = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = [stderr.trim(), body.trim()]
+            .into_iter()
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(": ");
+        return Err(format!(
+            "{} {} failed: {detail}",
+            request.method, request.url
+        ));
+    }
+    if body.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(&body)
+        .map_err(|err| format!("{} returned invalid JSON: {err}", request.url))
+}
+
+pub(crate) fn curl_quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/ticket_sync_tests.rs"]
+mod tests;
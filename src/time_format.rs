@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use crate::i18n::{self, Locale, Text};
+
+const SECS_PER_MINUTE: u64 = 60;
+const SECS_PER_HOUR: u64 = 3_600;
+const SECS_PER_DAY: u64 = 86_400;
+/// Older timestamps show as a date instead of "Nd ago".
+const RELATIVE_LIMIT_SECS: u64 = 7 * SECS_PER_DAY;
+
+/// Fixed UTC offset that dates are shown in (`[ui] time_zone`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeZone {
+    offset_minutes: i32,
+}
+
+impl TimeZone {
+    pub const UTC: TimeZone = TimeZone { offset_minutes: 0 };
+
+    /// Accepts `UTC`/`Z` and offsets such as `+02:00`, `-0530`, `+9`, or `UTC+1`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let offset = match value.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("utc") => &value[3..],
+            _ => value,
+        };
+        if offset.is_empty() || offset.eq_ignore_ascii_case("z") {
+            return Some(Self::UTC);
+        }
+        let (sign, rest) = match offset.as_bytes()[0] {
+            b'+' => (1, &offset[1..]),
+            b'-' => (-1, &offset[1..]),
+            _ => return None,
+        };
+        if rest.is_empty() || !rest.chars().all(|ch| ch.is_ascii_digit() || ch == ':') {
+            return None;
+        }
+        let (hours, minutes) = match rest.split_once(':') {
+            Some(parts) => parts,
+            None if rest.len() == 4 => rest.split_at(2),
+            None => (rest, "0"),
+        };
+        let hours = hours.parse::<i32>().ok()?;
+        let minutes = minutes.parse::<i32>().ok()?;
+        if hours > 14 || minutes >= 60 {
+            return None;
+        }
+        Some(Self {
+            offset_minutes: sign * (hours * 60 + minutes),
+        })
+    }
+
+    /// `UTC` or `UTC+05:30`.
+    pub fn label(self) -> String {
+        if self.offset_minutes == 0 {
+            return "UTC".to_string();
+        }
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        let minutes = self.offset_minutes.unsigned_abs();
+        format!("UTC{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+
+    fn local_secs(self, epoch_secs: u64) -> i64 {
+        epoch_secs as i64 + i64::from(self.offset_minutes) * 60
+    }
+}
+
+/// `just now`, `5m ago`, `2h ago`, `3d ago`; a week or more back falls back to [`format_date`].
+pub fn format_relative(locale: Locale, zone: TimeZone, then_secs: u64, now_secs: u64) -> String {
+    let ago = now_secs.saturating_sub(then_secs);
+    match ago {
+        0..SECS_PER_MINUTE => i18n::text(locale, Text::JustNow).to_string(),
+        SECS_PER_MINUTE..SECS_PER_HOUR => i18n::fill(
+            locale,
+            Text::MinutesAgo,
+            &[("count", &(ago / SECS_PER_MINUTE))],
+        ),
+        SECS_PER_HOUR..SECS_PER_DAY => {
+            i18n::fill(locale, Text::HoursAgo, &[("count", &(ago / SECS_PER_HOUR))])
+        }
+        SECS_PER_DAY..RELATIVE_LIMIT_SECS => {
+            i18n::fill(locale, Text::DaysAgo, &[("count", &(ago / SECS_PER_DAY))])
+        }
+        _ => format_date(locale, zone, then_secs),
+    }
+}
+
+/// `Feb 16, 2026` in English, `16 feb 2026` in Spanish.
+pub fn format_date(locale: Locale, zone: TimeZone, epoch_secs: u64) -> String {
+    let (year, month, day) =
+        civil_date(zone.local_secs(epoch_secs).div_euclid(SECS_PER_DAY as i64));
+    let month_name = month_abbreviation(locale, month);
+    match locale {
+        Locale::En => format!("{month_name} {day}, {year}"),
+        Locale::Es => format!("{day} {month_name} {year}"),
+    }
+}
+
+/// [`format_date`] plus a 24-hour clock time and the zone, e.g. `Feb 16, 2026 14:05 UTC`.
+pub fn format_date_time(locale: Locale, zone: TimeZone, epoch_secs: u64) -> String {
+    let secs_of_day = zone.local_secs(epoch_secs).rem_euclid(SECS_PER_DAY as i64) as u64;
+    format!(
+        "{} {:02}:{:02} {}",
+        format_date(locale, zone, epoch_secs),
+        secs_of_day / SECS_PER_HOUR,
+        secs_of_day % SECS_PER_HOUR / SECS_PER_MINUTE,
+        zone.label()
+    )
+}
+
+/// `42s`, `4m 05s`, `1h 03m`.
+pub fn format_duration(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        0..SECS_PER_MINUTE => format!("{secs}s"),
+        SECS_PER_MINUTE..SECS_PER_HOUR => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3_600, secs % 3_600 / 60),
+    }
+}
+
+/// Epoch seconds of an RFC 3339 timestamp such as `2026-02-16T12:00:00Z` or
+/// `2026-02-16T12:00:00.5+02:00`. A bare date is read as midnight UTC.
+pub fn parse_rfc3339(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (date, time) = match value.split_once(['T', 't', ' ']) {
+        Some(parts) => parts,
+        None => (value, "00:00:00Z"),
+    };
+    let mut date_parts = date.splitn(3, '-');
+    let year = date_parts.next()?.parse::<i64>().ok()?;
+    let month = date_parts.next()?.parse::<u32>().ok()?;
+    let day = date_parts.next()?.parse::<u32>().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let zone_at = time.find(['Z', 'z', '+', '-']).unwrap_or(time.len());
+    let (clock, zone) = time.split_at(zone_at);
+    let zone = TimeZone::parse(zone)?;
+    let clock = clock.split('.').next().unwrap_or_default();
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour = clock_parts.next()?.parse::<i64>().ok()?;
+    let minute = clock_parts.next()?.parse::<i64>().ok()?;
+    let second = clock_parts
+        .next()
+        .map_or(Some(0), |s| s.parse::<i64>().ok())?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let local = days_from_civil(year, month, day) * SECS_PER_DAY as i64
+        + hour * SECS_PER_HOUR as i64
+        + minute * SECS_PER_MINUTE as i64
+        + second;
+    u64::try_from(local - i64::from(zone.offset_minutes) * 60).ok()
+}
+
+fn month_abbreviation(locale: Locale, month: u32) -> &'static str {
+    const EN: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    const ES: [&str; 12] = [
+        "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+    ];
+    let names = match locale {
+        Locale::En => &EN,
+        Locale::Es => &ES,
+    };
+    names[(month as usize).clamp(1, 12) - 1]
+}
+
+/// Proleptic Gregorian (year, month, day) of a day count since 1970-01-01.
+fn civil_date(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Inverse of [`civil_date`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/time_format_tests.rs"]
+mod tests;
@@ -0,0 +1,154 @@
+use std::borrow::Cow;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Chat lines kept in memory before the oldest are moved to the spill file.
+const DEFAULT_MEMORY_LINES: usize = 5_000;
+/// Lines moved per spill, so a long session writes in batches rather than on every push.
+const SPILL_BATCH_LINES: usize = 1_000;
+
+/// Append-only chat transcript. The newest lines stay in memory; older ones are written to a
+/// temporary spill file and read back by index, so memory stays bounded in very long sessions.
+#[derive(Debug)]
+pub struct Transcript {
+    /// Unique per transcript, so caches of one never apply to another.
+    id: usize,
+    recent: Vec<String>,
+    /// Lines in the spill file, all older than `recent`.
+    spilled: usize,
+    spill: Option<SpillFile>,
+    memory_lines: usize,
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct SpillFile {
+    path: PathBuf,
+    file: File,
+    /// Start offset of each spilled line, followed by the end of the last one.
+    offsets: Vec<u64>,
+}
+
+impl Default for Transcript {
+    fn default() -> Self {
+        Self::with_memory_lines(DEFAULT_MEMORY_LINES)
+    }
+}
+
+impl Transcript {
+    pub fn with_memory_lines(memory_lines: usize) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            recent: Vec::new(),
+            spilled: 0,
+            spill: None,
+            memory_lines: memory_lines.max(1),
+            generation: 0,
+        }
+    }
+
+    pub fn push(&mut self, line: String) {
+        self.recent.push(line);
+        self.generation = self.generation.saturating_add(1);
+        let batch = SPILL_BATCH_LINES.min(self.memory_lines);
+        if self.recent.len() >= self.memory_lines + batch && self.spill_oldest(batch).is_err() {
+            // Without a spill file the lines simply stay in memory.
+            self.memory_lines = self.memory_lines.saturating_mul(2);
+        }
+    }
+
+    /// Total number of lines, spilled ones included.
+    pub fn len(&self) -> usize {
+        self.spilled + self.recent.len()
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Bumped on every push; render caches key on it.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The newest lines, still held in memory.
+    #[cfg(test)]
+    pub fn recent(&self) -> &[String] {
+        &self.recent
+    }
+
+    /// Line `index` counted from the start of the transcript, read from disk when spilled.
+    pub fn line(&self, index: usize) -> Option<Cow<'_, str>> {
+        if index >= self.spilled {
+            return self
+                .recent
+                .get(index - self.spilled)
+                .map(|line| line.into());
+        }
+        let spill = self.spill.as_ref()?;
+        let (start, end) = (spill.offsets[index], spill.offsets[index + 1]);
+        let mut bytes = vec![0; usize::try_from(end - start).ok()?];
+        let mut file = &spill.file;
+        file.seek(SeekFrom::Start(start)).ok()?;
+        file.read_exact(&mut bytes).ok()?;
+        Some(String::from_utf8_lossy(&bytes).into_owned().into())
+    }
+
+    fn spill_oldest(&mut self, count: usize) -> io::Result<()> {
+        if self.spill.is_none() {
+            self.spill = Some(SpillFile::create(self.id)?);
+        }
+        let Some(spill) = self.spill.as_mut() else {
+            return Ok(());
+        };
+        let start = spill.offsets.last().copied().unwrap_or(0);
+        let mut bytes = Vec::new();
+        let mut offsets = Vec::with_capacity(count);
+        for line in &self.recent[..count] {
+            bytes.extend_from_slice(line.as_bytes());
+            offsets.push(start + bytes.len() as u64);
+        }
+        spill.file.seek(SeekFrom::Start(start))?;
+        spill.file.write_all(&bytes)?;
+        if spill.offsets.is_empty() {
+            spill.offsets.push(0);
+        }
+        spill.offsets.extend(offsets);
+        self.recent.drain(..count);
+        self.spilled += count;
+        Ok(())
+    }
+}
+
+impl SpillFile {
+    fn create(transcript_id: usize) -> io::Result<Self> {
+        let path = std::env::temp_dir().join(format!(
+            "agentbob-chat-{}-{transcript_id}.spill",
+            std::process::id()
+        ));
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&path)?;
+        Ok(Self {
+            path,
+            file,
+            offsets: Vec::new(),
+        })
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/transcript_tests.rs"]
+mod tests;
@@ -1,6 +1,7 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::io;
 use std::path::Path;
-use std::sync::Arc;
+use std::time::Instant;
 
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span, Text};
@@ -10,27 +11,38 @@ use ratatui_core::style::{Color as CoreColor, Modifier as CoreModifier, Style as
 use ratatui_core::text::{Line as CoreLine, Span as CoreSpan, Text as CoreText};
 use tui_markdown::from_str;
 
-use crate::app::{App, CommandSuggestion, Pane};
+use crate::app::{App, ChatView, Pane, PaneDamage, TaskMenuEntry};
+use crate::artifact_io::load_merged_metaagent_config_text;
+use crate::audit_citations::{self, AuditCitation};
+use crate::command_args::ArgHint;
+use crate::command_registry::CommandSuggestion;
+use crate::i18n::{self, Locale, Text as UiText};
+use crate::terminal_graphics::{IMAGE_MARKER, with_image_fallbacks};
 use crate::text_layout::wrap_word_with_positions;
-use crate::theme::Theme;
+use crate::theme::{ColorDepth, Theme};
+use crate::workflow::{RightPaneBlockView, TaskMenuTarget};
 
-const MAX_INPUT_TEXT_LINES: u16 = 5;
+const MIN_INPUT_TEXT_LINES_CAP: u16 = 5;
 const TEXT_PADDING: u16 = 1;
-const STATUS_HEIGHT: u16 = 3;
+const STATUS_HEIGHT: u16 = 4;
 const TITLE_BAR_HEIGHT: u16 = 3;
 const TAB_BAR_HEIGHT: u16 = 3;
+/// Screens shorter than this get a one-row tab bar in the narrow layout.
+const COMPACT_TAB_BAR_SCREEN_HEIGHT: u16 = 20;
 const CHAT_INPUT_PREFIX: &str = "▸ ";
-const NARROW_SCREEN_WIDTH: u16 = 100;
+/// Default `[ui] narrow_width`.
+pub const DEFAULT_NARROW_SCREEN_WIDTH: u16 = 100;
 const ACTIVE_TITLE_BG: Color = Color::Rgb(90, 145, 200);
 const ACTIVE_TITLE_FG: Color = Color::Black;
 const LEFT_TOP_PANE_PERCENT: u16 = 30;
 const LEFT_BOTTOM_PANE_PERCENT: u16 = 70;
-const STATUS_HELP_TEXT_NARROW: &str =
-    "Tab/Shift+Tab switch views | Click tabs at top to switch | Click [^]/[v] buttons on right to scroll active tab by half-page | Ctrl+U/Ctrl+D or PgUp/PgDn scroll main right pane | Wheel scrolls focused pane";
-const STATUS_HELP_TEXT_WIDE: &str =
-    "Tab/Shift+Tab focus | Ctrl+U/Ctrl+D or PgUp/PgDn scroll main right pane | Wheel scrolls focused pane";
 const SCROLL_BUTTON_WIDTH: u16 = 3;
 const CHAT_INPUT_PREFIX_WIDTH: u16 = 2;
+const RESUME_PICKER_MAX_WIDTH: u16 = 120;
+/// Narrower resume pickers drop the preview pane and show the workspace in each row instead.
+const RESUME_PREVIEW_MIN_WIDTH: u16 = 80;
+/// Column the key binding help starts each action at, past the indented keys.
+const HELP_KEYS_WIDTH: usize = 28;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScrollButton {
@@ -38,15 +50,24 @@ pub enum ScrollButton {
     Down,
 }
 
+/// Where each chat message's rows end at one width. New messages only extend it, so a long
+/// transcript is wrapped once per width and rendering wraps just the messages in view.
 #[derive(Debug, Clone)]
-struct ChatLinesCache {
+struct ChatLayoutCache {
+    transcript_id: usize,
     width: u16,
+    /// Blank rows reserved under each inline image fallback.
+    image_rows: u16,
     generation: u64,
-    lines: Arc<Vec<ChatDisplayLine>>,
+    /// Row after the last display row of each message; a separator row follows every
+    /// message but the last.
+    row_ends: Vec<usize>,
 }
 
 thread_local! {
-    static CHAT_LINES_CACHE: RefCell<Option<ChatLinesCache>> = const { RefCell::new(None) };
+    static CHAT_LAYOUT_CACHE: RefCell<Option<ChatLayoutCache>> = const { RefCell::new(None) };
+    /// `[ui] narrow_width` used by this thread's renders and hit tests.
+    static NARROW_SCREEN_WIDTH: Cell<u16> = const { Cell::new(DEFAULT_NARROW_SCREEN_WIDTH) };
 }
 
 fn split_body_and_status(screen: Rect) -> (Rect, Rect) {
@@ -64,9 +85,49 @@ fn pane_tab_rects(tab_bar: Rect) -> [Rect; 3] {
     .areas(tab_bar)
 }
 
+/// Sets the terminal width below which panes are shown one at a time under a tab bar; 0
+/// always shows all three panes.
+pub fn set_narrow_width(width: u16) {
+    NARROW_SCREEN_WIDTH.set(width);
+}
+
+/// Loads `[ui] narrow_width` from config.toml.
+pub fn load_narrow_width() -> io::Result<u16> {
+    narrow_width_from_toml(&load_merged_metaagent_config_text()?)
+}
+
+pub(crate) fn narrow_width_from_toml(text: &str) -> io::Result<u16> {
+    let parsed = toml::from_str::<toml::Value>(text)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let Some(value) = parsed
+        .get("ui")
+        .and_then(toml::Value::as_table)
+        .and_then(|table| table.get("narrow_width"))
+    else {
+        return Ok(DEFAULT_NARROW_SCREEN_WIDTH);
+    };
+    value
+        .as_integer()
+        .and_then(|width| u16::try_from(width).ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("ui.narrow_width must be a column count from 0 to 65535 (got {value})"),
+            )
+        })
+}
+
+fn tab_bar_height(screen: Rect) -> u16 {
+    if screen.height < COMPACT_TAB_BAR_SCREEN_HEIGHT {
+        1
+    } else {
+        TAB_BAR_HEIGHT
+    }
+}
+
 fn is_narrow_layout(screen: Rect) -> bool {
     let (body, _status) = split_body_and_status(screen);
-    body.width < NARROW_SCREEN_WIDTH
+    body.width < NARROW_SCREEN_WIDTH.get()
 }
 
 fn narrow_main_and_scroll_strip(screen: Rect) -> Option<(Rect, Rect)> {
@@ -99,7 +160,7 @@ fn pane_areas(screen: Rect) -> ([Rect; 3], Option<Rect>) {
     let (body, _status) = split_body_and_status(screen);
     if let Some((main, _scroll_strip)) = narrow_main_and_scroll_strip(screen) {
         let [tab_bar, content] = Layout::vertical([
-            Constraint::Length(TAB_BAR_HEIGHT),
+            Constraint::Length(tab_bar_height(screen)),
             Constraint::Min(0),
         ])
         .areas(main);
@@ -158,7 +219,7 @@ fn chat_input_text_width_inner(content_width: u16) -> u16 {
         .max(1)
 }
 
-pub fn chat_max_scroll(screen: Rect, app: &App) -> u16 {
+pub fn chat_max_scroll(screen: Rect, app: &App) -> usize {
     let left_bottom = pane_area(screen, Pane::LeftBottom);
     let [_title_bar, content] =
         Layout::vertical([Constraint::Length(TITLE_BAR_HEIGHT), Constraint::Min(0)])
@@ -177,9 +238,8 @@ pub fn chat_max_scroll(screen: Rect, app: &App) -> u16 {
 
     let visible_message_lines = messages_area.height.saturating_sub(TEXT_PADDING * 2);
     let total_message_lines =
-        cached_chat_display_lines(app, content.width.saturating_sub(TEXT_PADDING * 2).max(1)).len()
-            as u16;
-    total_message_lines.saturating_sub(visible_message_lines)
+        chat_row_count(app, content.width.saturating_sub(TEXT_PADDING * 2).max(1));
+    total_message_lines.saturating_sub(visible_message_lines.into())
 }
 
 pub fn left_top_max_scroll(screen: Rect, app: &App) -> u16 {
@@ -190,9 +250,22 @@ pub fn left_top_max_scroll(screen: Rect, app: &App) -> u16 {
     if content.width < 1 || content.height < 1 {
         return 0;
     }
+    let visible_lines = content.height.saturating_sub(TEXT_PADDING * 2);
+    if app.is_file_tree_mode() {
+        if !app.is_file_viewer_open() {
+            return 0;
+        }
+        let total_lines = app.file_tree_lines().len() as u16;
+        return total_lines.saturating_sub(visible_lines);
+    }
+    if app.is_stats_mode() {
+        return (app.stats_lines().len() as u16).saturating_sub(visible_lines);
+    }
+    if app.is_console_mode() {
+        return (app.console_lines().len() as u16).saturating_sub(visible_lines);
+    }
     let text_width = content.width.saturating_sub(TEXT_PADDING * 2).max(1);
     let total_lines = app.left_top_wrapped_text(text_width).lines().count() as u16;
-    let visible_lines = content.height.saturating_sub(TEXT_PADDING * 2);
     total_lines.saturating_sub(visible_lines)
 }
 
@@ -291,12 +364,23 @@ pub fn pane_scroll_button_page_delta(screen: Rect, pane: Pane, app: &App) -> u16
     }
 }
 
-fn pane_scroll_state(screen: Rect, pane: Pane, app: &App) -> (u16, u16) {
-    match pane {
+fn pane_scroll_state(screen: Rect, pane: Pane, app: &App) -> (usize, usize) {
+    let (position, max) = match pane {
+        Pane::LeftTop if app.is_file_tree_mode() => {
+            (app.file_viewer_scroll(), left_top_max_scroll(screen, app))
+        }
+        Pane::LeftTop if app.is_stats_mode() => {
+            (app.stats_scroll(), left_top_max_scroll(screen, app))
+        }
+        Pane::LeftTop if app.is_console_mode() => {
+            let max = left_top_max_scroll(screen, app);
+            (app.console_scroll().min(max), max)
+        }
         Pane::LeftTop => (app.left_top_scroll(), left_top_max_scroll(screen, app)),
-        Pane::LeftBottom => (app.left_bottom_scroll(), chat_max_scroll(screen, app)),
+        Pane::LeftBottom => return (app.left_bottom_scroll(), chat_max_scroll(screen, app)),
         Pane::Right => (app.right_scroll(), right_max_scroll(screen, app)),
-    }
+    };
+    (position.into(), max.into())
 }
 
 fn pane_scroll_button_areas(screen: Rect, _pane: Pane) -> Option<[Rect; 2]> {
@@ -320,49 +404,98 @@ fn pane_scroll_button_areas(screen: Rect, _pane: Pane) -> Option<[Rect; 2]> {
     Some([up_area, down_area])
 }
 
+/// Cells each pane drew last frame, before overlays. [`render_with_cache`] copies an
+/// undamaged pane from here instead of rendering it again.
+#[derive(Debug, Default)]
+pub struct PaneRenderCache {
+    panes: [Option<Buffer>; 3],
+}
+
+impl PaneRenderCache {
+    fn slot(pane: Pane) -> usize {
+        match pane {
+            Pane::LeftTop => 0,
+            Pane::LeftBottom => 1,
+            Pane::Right => 2,
+        }
+    }
+}
+
 pub fn render(frame: &mut Frame, app: &App, theme: &Theme) {
+    render_with_cache(
+        frame,
+        app,
+        theme,
+        &mut PaneRenderCache::default(),
+        PaneDamage::ALL,
+    );
+}
+
+/// Renders the panes in `damage`, or whose area changed, and copies the others from `cache`.
+/// The tab bar, status line, and overlays are always drawn.
+pub fn render_with_cache(
+    frame: &mut Frame,
+    app: &App,
+    theme: &Theme,
+    cache: &mut PaneRenderCache,
+    damage: PaneDamage,
+) {
     let [_, status] = Layout::vertical([Constraint::Min(0), Constraint::Length(STATUS_HEIGHT)])
         .areas(frame.area());
     let (panes, tab_bar) = pane_areas(frame.area());
     let [left_top, left_bottom, right] = panes;
 
+    let mut draw_pane = |frame: &mut Frame, pane: Pane, area: Rect, active: bool| {
+        let slot = &mut cache.panes[PaneRenderCache::slot(pane)];
+        if !damage.contains(pane)
+            && let Some(cached) = slot.as_ref().filter(|cached| cached.area == area)
+        {
+            frame.buffer_mut().merge(cached);
+            return;
+        }
+        match pane {
+            Pane::LeftTop => render_worker_output_pane(frame, area, active, app, theme),
+            Pane::LeftBottom => render_chat_pane(frame, area, app, active, theme),
+            Pane::Right => render_right_task_pane(frame, area, app, active, theme),
+        }
+        let mut snapshot = Buffer::empty(area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                snapshot[(x, y)] = frame.buffer_mut()[(x, y)].clone();
+            }
+        }
+        *slot = Some(snapshot);
+    };
     if let Some(tab_bar) = tab_bar {
         render_tab_bar(frame, tab_bar, app.active_pane, app, theme);
-        match app.active_pane {
-            Pane::LeftTop => render_worker_output_pane(frame, left_top, true, app, theme),
-            Pane::LeftBottom => render_chat_pane(frame, left_bottom, app, true, theme),
-            Pane::Right => render_right_task_pane(frame, right, app, true, theme),
-        }
+        let area = match app.active_pane {
+            Pane::LeftTop => left_top,
+            Pane::LeftBottom => left_bottom,
+            Pane::Right => right,
+        };
+        draw_pane(frame, app.active_pane, area, true);
     } else {
-        render_worker_output_pane(
-            frame,
-            left_top,
-            app.active_pane == Pane::LeftTop,
-            app,
-            theme,
-        );
-        render_chat_pane(
-            frame,
-            left_bottom,
-            app,
-            app.active_pane == Pane::LeftBottom,
-            theme,
-        );
-        render_right_task_pane(frame, right, app, app.active_pane == Pane::Right, theme);
+        for (pane, area) in [
+            (Pane::LeftTop, left_top),
+            (Pane::LeftBottom, left_bottom),
+            (Pane::Right, right),
+        ] {
+            draw_pane(frame, pane, area, app.active_pane == pane);
+        }
     }
 
     if app.is_docs_attach_in_progress() {
         render_center_overlay(
             frame,
             overlay_target_area(frame.area(), app.active_pane),
-            "Attaching Documentation...",
+            i18n::text(app.locale(), UiText::AttachingDocs),
         );
     }
     if app.is_task_check_in_progress() {
         render_center_overlay(
             frame,
             overlay_target_area(frame.area(), app.active_pane),
-            "Checking Tasks...",
+            i18n::text(app.locale(), UiText::CheckingTasks),
         );
     }
     render_scroll_buttons(frame, frame.area(), app, theme);
@@ -371,19 +504,55 @@ pub fn render(frame: &mut Frame, app: &App, theme: &Theme) {
         Block::default().style(Style::default().bg(theme.status_bg)),
         status,
     );
-    let help = Paragraph::new(status_line_text(frame.area(), app.tests_mode_enabled()))
-        .style(Style::default().bg(theme.status_bg).fg(theme.muted_fg))
-        .block(
-            Block::default()
-                .style(Style::default().bg(theme.status_bg))
-                .padding(Padding::uniform(TEXT_PADDING)),
-        );
+    let help = Paragraph::new(vec![
+        Line::from(app.execution_status_text(Instant::now()))
+            .style(Style::default().fg(theme.text_fg)),
+        Line::from(status_line_text(
+            frame.area(),
+            app.tests_mode_enabled(),
+            app.locale(),
+        )),
+    ])
+    .style(Style::default().bg(theme.status_bg).fg(theme.muted_fg))
+    .block(
+        Block::default()
+            .style(Style::default().bg(theme.status_bg))
+            .padding(Padding::uniform(TEXT_PADDING)),
+    );
     frame.render_widget(help, status);
 
     if app.is_resume_picker_open() {
         render_resume_picker(frame, app, theme);
     } else if app.is_backend_picker_open() {
         render_backend_picker(frame, app, theme);
+    } else if app.is_log_picker_open() {
+        render_log_picker(frame, app, theme);
+    } else if app.is_task_menu_open() {
+        render_task_menu(frame, app, theme);
+    } else if app.is_master_prompt_preview_open() {
+        render_master_prompt_preview(frame, app, theme);
+    } else if app.is_question_form_open() {
+        render_question_form(frame, app, theme);
+    } else if app.is_rerun_editor_open() {
+        render_rerun_editor(frame, app, theme);
+    }
+    if app.is_help_overlay_open() {
+        render_help_overlay(frame, app, theme);
+    }
+    if theme.color_depth != ColorDepth::TrueColor {
+        downgrade_colors(frame.buffer_mut(), theme.color_depth);
+    }
+}
+
+/// Maps every drawn color to one the terminal can show. Without colors, the active pane's
+/// title bar, otherwise marked only by its background, is drawn in reverse video.
+fn downgrade_colors(buffer: &mut Buffer, depth: ColorDepth) {
+    for cell in &mut buffer.content {
+        if depth == ColorDepth::Monochrome && cell.bg == ACTIVE_TITLE_BG {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = depth.downgrade(cell.fg);
+        cell.bg = depth.downgrade(cell.bg);
     }
 }
 
@@ -393,13 +562,43 @@ fn render_tab_bar(frame: &mut Frame, area: Rect, active: Pane, app: &App, theme:
     }
 
     let [worker_tab, chat_tab, right_tab] = pane_tab_rects(area);
+    let left_top_title = if app.is_file_tree_mode() {
+        UiText::TabFiles
+    } else if app.is_stats_mode() {
+        UiText::TabStats
+    } else if app.is_console_mode() {
+        UiText::TabConsole
+    } else {
+        UiText::TabWorkerOutput
+    };
     let tabs = [
-        (Pane::LeftTop, "Worker Output", worker_tab),
-        (Pane::LeftBottom, "Agent Chat", chat_tab),
-        (Pane::Right, app.right_pane_title(), right_tab),
+        (
+            Pane::LeftTop,
+            i18n::text(app.locale(), left_top_title).to_string(),
+            UiText::TabShortOutput,
+            worker_tab,
+        ),
+        (
+            Pane::LeftBottom,
+            chat_view_title(app),
+            UiText::TabShortChat,
+            chat_tab,
+        ),
+        (
+            Pane::Right,
+            app.right_pane_title().to_string(),
+            UiText::TabShortTasks,
+            right_tab,
+        ),
     ];
 
-    for (pane, title, tab_area) in tabs {
+    for (pane, title, short_title, tab_area) in tabs {
+        // Tabs too narrow for the pane's title fall back to Output / Chat / Tasks.
+        let title = if title.chars().count() > usize::from(tab_area.width) {
+            i18n::text(app.locale(), short_title).to_string()
+        } else {
+            title
+        };
         let active = pane == active;
         let tab_bg = if active { ACTIVE_TITLE_BG } else { theme.status_bg };
         let tab_fg = if active { ACTIVE_TITLE_FG } else { theme.muted_fg };
@@ -493,7 +692,7 @@ fn render_worker_output_pane(
         title_area,
     );
     frame.render_widget(
-        Paragraph::new("Worker Output")
+        Paragraph::new(app.left_top_title())
             .style(Style::default().bg(title_bg).fg(header_fg))
             .block(
                 Block::default()
@@ -503,6 +702,57 @@ fn render_worker_output_pane(
         title_area,
     );
 
+    if app.is_file_tree_mode() {
+        render_file_tree_content(frame, content_area, app, theme);
+        return;
+    }
+    if app.is_stats_mode() {
+        frame.render_widget(
+            Paragraph::new(app.stats_lines().join("\n"))
+                .style(Style::default().bg(theme.left_top_bg).fg(theme.text_fg))
+                .scroll((app.stats_scroll(), 0))
+                .block(
+                    Block::default()
+                        .style(Style::default().bg(theme.left_top_bg))
+                        .padding(Padding::uniform(TEXT_PADDING)),
+                ),
+            content_area,
+        );
+        return;
+    }
+    if app.is_console_mode() {
+        let lines = app.console_lines();
+        let visible_lines = content_area.height.saturating_sub(TEXT_PADDING * 2);
+        let max_scroll = (lines.len() as u16).saturating_sub(visible_lines);
+        let text = Text::from(
+            lines
+                .iter()
+                .map(|line| {
+                    if line.starts_with("$ ") {
+                        Line::from(Span::styled(
+                            line.as_str(),
+                            Style::default().fg(theme.active_fg),
+                        ))
+                    } else {
+                        Line::from(line.as_str())
+                    }
+                })
+                .collect::<Vec<_>>(),
+        );
+        frame.render_widget(
+            Paragraph::new(text)
+                .style(Style::default().bg(theme.left_top_bg).fg(theme.text_fg))
+                .scroll((app.console_scroll().min(max_scroll), 0))
+                .block(
+                    Block::default()
+                        .style(Style::default().bg(theme.left_top_bg))
+                        .padding(Padding::uniform(TEXT_PADDING)),
+                ),
+            content_area,
+        );
+        return;
+    }
+
     let width = content_area.width.saturating_sub(TEXT_PADDING * 2).max(1);
     let content = app.left_top_wrapped_text(width);
     frame.render_widget(
@@ -518,18 +768,66 @@ fn render_worker_output_pane(
     );
 }
 
-fn status_line_text(screen: Rect, tests_mode_enabled: bool) -> String {
+fn render_file_tree_content(frame: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let lines = app.file_tree_lines();
+    let visible_lines = area.height.saturating_sub(TEXT_PADDING * 2).max(1);
+    let scroll = if app.is_file_viewer_open() {
+        app.file_viewer_scroll()
+    } else {
+        file_tree_scroll_for_selection(app.file_tree_selected_index(), visible_lines)
+    };
+    let selected = (!app.is_file_viewer_open()).then(|| app.file_tree_selected_index());
+    let highlight = app.file_viewer_highlight();
+    let text = Text::from(
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                if Some(idx) == selected
+                    || highlight.is_some_and(|(start, end)| (start..=end).contains(&(idx + 1)))
+                {
+                    Line::from(Span::styled(line, Style::default().fg(theme.active_fg)))
+                } else if line.ends_with(" *") {
+                    Line::from(Span::styled(line, Style::default().fg(Color::Rgb(230, 150, 60))))
+                } else {
+                    Line::from(line)
+                }
+            })
+            .collect::<Vec<_>>(),
+    );
+    frame.render_widget(
+        Paragraph::new(text)
+            .style(Style::default().bg(theme.left_top_bg).fg(theme.text_fg))
+            .scroll((scroll, 0))
+            .block(
+                Block::default()
+                    .style(Style::default().bg(theme.left_top_bg))
+                    .padding(Padding::uniform(TEXT_PADDING)),
+            ),
+        area,
+    );
+}
+
+fn file_tree_scroll_for_selection(selected: usize, visible_lines: u16) -> u16 {
+    (selected as u16).saturating_sub(visible_lines.saturating_sub(1))
+}
+
+fn status_line_text(screen: Rect, tests_mode_enabled: bool, locale: Locale) -> String {
     let base = if is_narrow_layout(screen) {
-        STATUS_HELP_TEXT_NARROW
+        UiText::StatusHelpNarrow
     } else {
-        STATUS_HELP_TEXT_WIDE
+        UiText::StatusHelpWide
     };
     let tests_indicator = if tests_mode_enabled {
-        "TESTS: ON"
+        UiText::TestsOn
     } else {
-        "TESTS: OFF"
+        UiText::TestsOff
     };
-    format!("{base} | {tests_indicator}")
+    format!(
+        "{} | {}",
+        i18n::text(locale, base),
+        i18n::text(locale, tests_indicator)
+    )
 }
 
 fn master_working_dots(ticks: u64) -> &'static str {
@@ -602,17 +900,15 @@ fn render_chat_pane(frame: &mut Frame, area: Rect, app: &App, active: bool, them
     let [messages_area, input_area] =
         Layout::vertical([Constraint::Min(1), Constraint::Length(input_height)]).areas(content);
 
-    let message_lines = cached_chat_display_lines(
+    let message_lines = visible_chat_display_lines(
         app,
         messages_area.width.saturating_sub(TEXT_PADDING * 2).max(1),
+        app.left_bottom_scroll()
+            .min(chat_max_scroll(frame.area(), app)),
+        messages_area.height.saturating_sub(TEXT_PADDING * 2).into(),
     );
-    let message_text = chat_text(message_lines.as_ref(), theme);
+    let message_text = chat_text(&message_lines, theme);
     let messages = Paragraph::new(message_text)
-        .scroll((
-            app.left_bottom_scroll()
-                .min(chat_max_scroll(frame.area(), app)),
-            0,
-        ))
         .style(Style::default().bg(theme.chat_bg).fg(theme.text_fg))
         .block(
             Block::default()
@@ -630,6 +926,9 @@ fn render_chat_pane(frame: &mut Frame, area: Rect, app: &App, active: bool, them
         .style(Style::default().bg(theme.input_bg).fg(theme.text_fg))
         .scroll((input_scroll, 0));
     frame.render_widget(input, input_area);
+    if let Some(hint) = app.command_arg_hint() {
+        render_command_arg_hint(frame, &hint, input_area, theme);
+    }
     if app.should_show_command_index() {
         render_command_index(
             frame,
@@ -672,6 +971,41 @@ fn render_chat_pane(frame: &mut Frame, area: Rect, app: &App, active: bool, them
     }
 }
 
+/// Draws `hint` on the input box's bottom padding row, under the text being typed.
+fn render_command_arg_hint(frame: &mut Frame, hint: &ArgHint, input_area: Rect, theme: &Theme) {
+    let indent = TEXT_PADDING + CHAT_INPUT_PREFIX_WIDTH;
+    if input_area.height < TEXT_PADDING * 2 + 1 || input_area.width <= indent + TEXT_PADDING {
+        return;
+    }
+    let row = Rect::new(
+        input_area.x + indent,
+        input_area.bottom() - 1,
+        input_area.width - indent - TEXT_PADDING,
+        1,
+    );
+    let mut spans = vec![Span::styled(
+        hint.usage.clone(),
+        Style::default().fg(theme.muted_fg),
+    )];
+    if let Some(problem) = &hint.problem {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            problem.clone(),
+            Style::default().fg(Color::Rgb(230, 150, 60)),
+        ));
+    } else if !hint.candidates.is_empty() {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            hint.candidates.join(" "),
+            Style::default().fg(theme.active_fg),
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.input_bg)),
+        row,
+    );
+}
+
 fn render_command_index(
     frame: &mut Frame,
     suggestions: Vec<CommandSuggestion>,
@@ -723,25 +1057,66 @@ fn render_command_index(
     );
 }
 
+fn chat_view_title(app: &App) -> String {
+    let locale = app.locale();
+    match app.chat_view() {
+        ChatView::Chat => i18n::text(locale, UiText::TabAgentChat).to_string(),
+        ChatView::SystemLog => i18n::text(locale, UiText::TabSystemLog).to_string(),
+        ChatView::Task(top_task_id) => i18n::fill(
+            locale,
+            UiText::TabTaskThread,
+            &[("task", &app.task_thread_label(top_task_id))],
+        ),
+    }
+}
+
 fn chat_title_text(app: &App) -> String {
-    if app.is_any_agent_in_progress() {
-        format!("Agent Chat | Working {}", master_working_dots(app.ticks))
+    let locale = app.locale();
+    let chat = chat_view_title(app);
+    let title = if app.is_read_only() {
+        format!("{chat} | {}", i18n::text(locale, UiText::ChatObserving))
+    } else if app.is_any_agent_in_progress() {
+        format!(
+            "{chat} | {} {}",
+            i18n::text(locale, UiText::ChatWorking),
+            master_working_dots(app.ticks)
+        )
     } else {
-        "Agent Chat".to_string()
+        chat.to_string()
+    };
+    match app.queued_message_count() {
+        0 => title,
+        queued => format!(
+            "{title} | {}",
+            i18n::fill(locale, UiText::ChatQueued, &[("count", &queued)])
+        ),
     }
 }
 
 fn render_resume_picker(frame: &mut Frame, app: &App, theme: &Theme) {
-    let entries = app.resume_picker_options();
-    if entries.is_empty() {
+    if !app.is_resume_picker_open() {
         return;
     }
+    let entries = app.resume_picker_options();
+    let filter = app.resume_picker_filter();
 
-    let width = frame.area().width.min(90).max(40);
+    let width = frame
+        .area()
+        .width
+        .clamp(40, RESUME_PICKER_MAX_WIDTH)
+        .min(frame.area().width);
+    let show_preview = width >= RESUME_PREVIEW_MIN_WIDTH;
+    let preview = if show_preview {
+        resume_preview_lines(app, theme)
+    } else {
+        Vec::new()
+    };
+    let header_rows = if filter.is_empty() { 1 } else { 2 };
     let max_rows = frame.area().height.saturating_sub(8).max(3);
-    let shown_count = (entries.len() as u16).min(max_rows.saturating_sub(2).max(1));
-    let height = shown_count
-        .saturating_add(4)
+    let shown_count = (entries.len().max(1) as u16).min(max_rows.saturating_sub(2).max(1));
+    let body_rows = shown_count.max(preview.len() as u16).min(max_rows);
+    let height = (body_rows + header_rows)
+        .saturating_add(3)
         .min(frame.area().height.max(3));
     let x = frame
         .area()
@@ -762,20 +1137,43 @@ fn render_resume_picker(frame: &mut Frame, app: &App, theme: &Theme) {
         .take(shown_count as usize)
         .collect::<Vec<_>>();
 
-    let mut lines = Vec::with_capacity(shown.len() + 1);
-    lines.push(Line::from(vec![
+    let mut header = vec![Line::from(vec![
         Span::styled(
-            "Resume Session",
+            i18n::text(app.locale(), UiText::ResumeTitle),
             Style::default()
                 .fg(theme.active_fg)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
         Span::styled(
-            "(Up/Down select, Enter/Space resume)",
+            i18n::text(app.locale(), UiText::ResumeHint),
             Style::default().fg(theme.muted_fg),
         ),
-    ]));
+    ])];
+    if app.resume_picker_shows_all_workspaces() {
+        header[0].push_span(Span::raw(" "));
+        header[0].push_span(Span::styled(
+            format!(
+                "[{}]",
+                i18n::text(app.locale(), UiText::ResumeAllWorkspaces)
+            ),
+            Style::default().fg(theme.active_fg),
+        ));
+    }
+    if !filter.is_empty() {
+        header.push(Line::styled(
+            i18n::fill(app.locale(), UiText::ResumeFilter, &[("query", &filter)]),
+            Style::default().fg(theme.text_fg),
+        ));
+    }
+
+    let mut lines = Vec::with_capacity(shown.len().max(1));
+    if shown.is_empty() {
+        lines.push(Line::styled(
+            i18n::text(app.locale(), UiText::ResumeNoMatches),
+            Style::default().fg(theme.muted_fg),
+        ));
+    }
     for (idx, item) in shown.iter().enumerate() {
         let absolute_idx = start + idx;
         let selected = absolute_idx == app.resume_picker_selected_index();
@@ -786,96 +1184,491 @@ fn render_resume_picker(frame: &mut Frame, app: &App, theme: &Theme) {
         } else {
             Style::default().fg(theme.text_fg)
         };
-        let name = Path::new(&item.session_dir)
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or(&item.session_dir);
-        let display_title = item.title.as_deref().unwrap_or(name);
-        let when = item.created_at_label.as_deref().unwrap_or("unknown date");
-        lines.push(Line::from(vec![
+        let when = app.resume_option_when_label(item);
+        let mut spans = vec![
             Span::styled(
                 if selected { ">" } else { " " }.to_string(),
                 Style::default().fg(theme.muted_fg),
             ),
             Span::raw(" "),
-            Span::styled(display_title.to_string(), style),
+            Span::styled(resume_option_title(item).to_string(), style),
             Span::raw(" "),
-            Span::styled(
-                format!("({when} | {})", item.workspace),
-                Style::default().fg(theme.muted_fg),
-            ),
-        ]));
+        ];
+        // The preview pane shows the workspace, so the list only needs the time.
+        spans.push(Span::styled(
+            if show_preview {
+                format!("({when})")
+            } else {
+                format!("({when} | {})", item.workspace)
+            },
+            Style::default().fg(theme.muted_fg),
+        ));
+        lines.push(Line::from(spans));
     }
 
+    let block = Block::default()
+        .style(Style::default().bg(theme.input_bg))
+        .padding(Padding::uniform(TEXT_PADDING));
+    let inner = block.inner(overlay);
     frame.render_widget(Clear, overlay);
-    frame.render_widget(
-        Paragraph::new(lines)
-            .style(Style::default().bg(theme.input_bg))
-            .block(
-                Block::default()
-                    .style(Style::default().bg(theme.input_bg))
-                    .padding(Padding::uniform(TEXT_PADDING)),
+    frame.render_widget(block, overlay);
+    let [header_area, body_area] =
+        Layout::vertical([Constraint::Length(header_rows), Constraint::Fill(1)]).areas(inner);
+    frame.render_widget(Paragraph::new(header), header_area);
+    if !show_preview {
+        frame.render_widget(Paragraph::new(lines), body_area);
+        return;
+    }
+    let [list_area, _, preview_area] = Layout::horizontal([
+        Constraint::Percentage(50),
+        Constraint::Length(2),
+        Constraint::Fill(1),
+    ])
+    .areas(body_area);
+    frame.render_widget(Paragraph::new(lines), list_area);
+    frame.render_widget(Paragraph::new(preview), preview_area);
+}
+
+fn resume_option_title(option: &crate::app::ResumeSessionOption) -> &str {
+    let name = Path::new(&option.session_dir)
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or(&option.session_dir);
+    option.title.as_deref().unwrap_or(name)
+}
+
+/// Preview of the selected resume picker entry: title, workspace, task completion, last
+/// activity, and the start of planner.md.
+fn resume_preview_lines(app: &App, theme: &Theme) -> Vec<Line<'static>> {
+    let Some((option, preview)) = app.resume_picker_preview() else {
+        return Vec::new();
+    };
+    let locale = app.locale();
+    let muted = Style::default().fg(theme.muted_fg);
+    let text = Style::default().fg(theme.text_fg);
+    let mut lines = vec![
+        Line::styled(
+            resume_option_title(option).to_string(),
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::styled(
+            i18n::fill(
+                locale,
+                UiText::ResumePreviewWorkspace,
+                &[("path", &option.workspace)],
             ),
-        overlay,
+            text,
+        ),
+    ];
+    let Some(preview) = preview else {
+        return lines;
+    };
+    lines.push(Line::styled(
+        i18n::fill(
+            locale,
+            UiText::ResumePreviewTasks,
+            &[
+                ("done", &preview.tasks_done),
+                ("total", &preview.tasks_total),
+            ],
+        ),
+        text,
+    ));
+    if let Some(secs) = preview.last_activity_epoch_secs {
+        lines.push(Line::styled(
+            i18n::fill(
+                locale,
+                UiText::ResumePreviewActivity,
+                &[("when", &app.resume_picker_time_label(secs))],
+            ),
+            text,
+        ));
+    }
+    lines.push(Line::default());
+    if preview.planner_head.is_empty() {
+        lines.push(Line::styled(
+            i18n::text(locale, UiText::ResumePreviewNoPlanner),
+            muted,
+        ));
+    }
+    lines.extend(
+        preview
+            .planner_head
+            .iter()
+            .map(|line| Line::styled(line.clone(), muted)),
     );
+    lines
 }
 
-fn render_backend_picker(frame: &mut Frame, app: &App, theme: &Theme) {
-    let entries = app.backend_picker_options();
-    if entries.is_empty() {
-        return;
-    }
+fn master_prompt_preview_area(screen: Rect) -> Rect {
+    let width = (screen.width.saturating_mul(9) / 10).max(40).min(screen.width);
+    let height = (screen.height.saturating_mul(8) / 10).max(6).min(screen.height);
+    let x = screen.x.saturating_add(screen.width.saturating_sub(width) / 2);
+    let y = screen.y.saturating_add(screen.height.saturating_sub(height) / 2);
+    Rect::new(x, y, width, height)
+}
 
-    let width = frame.area().width.min(90).max(40);
-    let max_rows = frame.area().height.saturating_sub(8).max(3);
-    let shown_count = (entries.len() as u16).min(max_rows.saturating_sub(2).max(1));
-    let height = shown_count
-        .saturating_add(4)
-        .min(frame.area().height.max(3));
-    let x = frame
-        .area()
-        .x
-        .saturating_add(frame.area().width.saturating_sub(width) / 2);
-    let y = frame
-        .area()
-        .y
-        .saturating_add(frame.area().height.saturating_sub(height) / 2);
-    let overlay = Rect::new(x, y, width, height);
+/// Inner text width and visible body lines of the preview modal (below its one-line header).
+fn master_prompt_preview_metrics(screen: Rect) -> (u16, u16) {
+    let area = master_prompt_preview_area(screen);
+    let width = area.width.saturating_sub(TEXT_PADDING * 2).max(1);
+    let visible_lines = area.height.saturating_sub(TEXT_PADDING * 2 + 1).max(1);
+    (width, visible_lines)
+}
 
-    let start = app
-        .backend_picker_selected_index()
-        .saturating_sub((shown_count as usize).saturating_sub(1));
-    let shown = entries
-        .iter()
-        .skip(start)
-        .take(shown_count as usize)
-        .collect::<Vec<_>>();
+pub fn master_prompt_preview_max_scroll(screen: Rect, app: &App) -> u16 {
+    let Some(preview) = app.master_prompt_preview() else {
+        return 0;
+    };
+    let (width, visible_lines) = master_prompt_preview_metrics(screen);
+    wrap_word_with_positions(&preview.prompt, width)
+        .line_count
+        .saturating_sub(visible_lines)
+}
 
-    let mut lines = Vec::with_capacity(shown.len() + 1);
-    lines.push(Line::from(vec![
+fn render_master_prompt_preview(frame: &mut Frame, app: &App, theme: &Theme) {
+    let Some(preview) = app.master_prompt_preview() else {
+        return;
+    };
+    let overlay = master_prompt_preview_area(frame.area());
+    let (width, _) = master_prompt_preview_metrics(frame.area());
+    let [header_area, body_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(1)])
+        .areas(Rect::new(
+            overlay.x.saturating_add(TEXT_PADDING),
+            overlay.y.saturating_add(TEXT_PADDING),
+            width,
+            overlay.height.saturating_sub(TEXT_PADDING * 2),
+        ));
+    let header = Line::from(vec![
         Span::styled(
-            "Select Backend",
+            i18n::text(app.locale(), UiText::PreviewTitle),
             Style::default()
                 .fg(theme.active_fg)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
         Span::styled(
-            "(Up/Down select, Enter/Space choose)",
+            i18n::fill(
+                app.locale(),
+                UiText::PreviewHint,
+                &[("chars", &preview.prompt.chars().count())],
+            ),
             Style::default().fg(theme.muted_fg),
         ),
-    ]));
-    for (idx, item) in shown.iter().enumerate() {
-        let absolute_idx = start + idx;
-        let selected = absolute_idx == app.backend_picker_selected_index();
-        let style = if selected {
+    ]);
+
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(
+        Block::default().style(Style::default().bg(theme.input_bg)),
+        overlay,
+    );
+    frame.render_widget(Paragraph::new(header), header_area);
+    frame.render_widget(
+        Paragraph::new(wrap_word_with_positions(&preview.prompt, width).rendered)
+            .style(Style::default().fg(theme.text_fg))
+            .scroll((preview.scroll, 0)),
+        body_area,
+    );
+}
+
+/// One numbered question per field with its answer below; the view scrolls to keep the
+/// selected answer on screen and puts the cursor at its end.
+fn render_question_form(frame: &mut Frame, app: &App, theme: &Theme) {
+    let Some(form) = app.question_form() else {
+        return;
+    };
+    let overlay = master_prompt_preview_area(frame.area());
+    let (width, visible_lines) = master_prompt_preview_metrics(frame.area());
+    let [header_area, body_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(1)])
+        .areas(Rect::new(
+            overlay.x.saturating_add(TEXT_PADDING),
+            overlay.y.saturating_add(TEXT_PADDING),
+            width,
+            overlay.height.saturating_sub(TEXT_PADDING * 2),
+        ));
+    let header = Line::from(vec![
+        Span::styled(
+            i18n::text(app.locale(), UiText::QuestionsTitle),
             Style::default()
                 .fg(theme.active_fg)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(theme.text_fg)
-        };
-        lines.push(Line::from(vec![
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            i18n::text(app.locale(), UiText::QuestionsHint),
+            Style::default().fg(theme.muted_fg),
+        ),
+    ]);
+
+    let answer_width = usize::from(width.saturating_sub(2).max(1));
+    let mut lines = Vec::new();
+    let mut cursor = (0, 0);
+    for (index, (question, answer)) in form.questions().iter().zip(form.answers()).enumerate() {
+        let selected = index == form.selected();
+        let style = if selected {
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_fg)
+        };
+        let question = format!("{}. {question}", index + 1);
+        for row in wrap_word_with_positions(&question, width).rendered.lines() {
+            lines.push(Line::from(Span::styled(row.to_string(), style)));
+        }
+        // Long answers show their tail so the end being typed stays visible.
+        let skip = answer.chars().count().saturating_sub(answer_width - 1);
+        let shown = answer.chars().skip(skip).collect::<String>();
+        if selected {
+            cursor = (2 + shown.chars().count() as u16, lines.len() as u16);
+        }
+        lines.push(Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.muted_fg)),
+            Span::styled(shown, Style::default().fg(theme.text_fg)),
+        ]));
+        lines.push(Line::default());
+    }
+    let scroll = (cursor.1 + 1).saturating_sub(visible_lines);
+
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(
+        Block::default().style(Style::default().bg(theme.input_bg)),
+        overlay,
+    );
+    frame.render_widget(Paragraph::new(header), header_area);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), body_area);
+    frame.set_cursor_position((
+        body_area.x.saturating_add(cursor.0),
+        body_area.y.saturating_add(cursor.1 - scroll),
+    ));
+}
+
+/// Text width and visible lines of the `/rerun` editor, which shares the preview's modal.
+pub fn rerun_editor_metrics(screen: Rect) -> (u16, u16) {
+    master_prompt_preview_metrics(screen)
+}
+
+fn render_rerun_editor(frame: &mut Frame, app: &App, theme: &Theme) {
+    let Some(editor) = app.rerun_editor() else {
+        return;
+    };
+    let overlay = master_prompt_preview_area(frame.area());
+    let (width, _) = rerun_editor_metrics(frame.area());
+    let [header_area, body_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(1)])
+        .areas(Rect::new(
+            overlay.x.saturating_add(TEXT_PADDING),
+            overlay.y.saturating_add(TEXT_PADDING),
+            width,
+            overlay.height.saturating_sub(TEXT_PADDING * 2),
+        ));
+    let header = Line::from(vec![
+        Span::styled(
+            i18n::fill(
+                app.locale(),
+                UiText::RerunTitle,
+                &[("task", &editor.task_id()), ("label", &editor.label())],
+            ),
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            i18n::text(app.locale(), UiText::RerunHint),
+            Style::default().fg(theme.muted_fg),
+        ),
+    ]);
+    let wrapped = wrap_word_with_positions(editor.text(), width);
+    let (line, col) = editor.cursor_line_col(width);
+    let row = line.saturating_sub(editor.scroll());
+
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(
+        Block::default().style(Style::default().bg(theme.input_bg)),
+        overlay,
+    );
+    frame.render_widget(Paragraph::new(header), header_area);
+    frame.render_widget(
+        Paragraph::new(wrapped.rendered)
+            .style(Style::default().fg(theme.text_fg))
+            .scroll((editor.scroll(), 0)),
+        body_area,
+    );
+    frame.set_cursor_position((
+        body_area.x.saturating_add(col),
+        body_area.y.saturating_add(row),
+    ));
+}
+
+/// One row of the key binding help: a section title (marked when it is the focused
+/// context), a binding with its action wrapped past the keys column, or a gap.
+enum HelpRow {
+    Section(&'static str, bool),
+    Binding(String, String),
+    Blank,
+}
+
+fn help_overlay_rows(app: &App, width: u16) -> Vec<HelpRow> {
+    let Some(help) = app.help_overlay() else {
+        return Vec::new();
+    };
+    let action_width = width.saturating_sub(HELP_KEYS_WIDTH as u16).max(12);
+    let mut rows = Vec::new();
+    for (context, bindings) in help.sections() {
+        if !rows.is_empty() {
+            rows.push(HelpRow::Blank);
+        }
+        rows.push(HelpRow::Section(context.title(), context == help.focused()));
+        for binding in bindings {
+            let wrapped = wrap_word_with_positions(binding.action, action_width).rendered;
+            for (index, action) in wrapped.lines().enumerate() {
+                let keys = if index == 0 { binding.keys } else { "" };
+                rows.push(HelpRow::Binding(
+                    format!("  {keys:<width$}", width = HELP_KEYS_WIDTH - 2),
+                    action.to_string(),
+                ));
+            }
+        }
+    }
+    rows
+}
+
+pub fn help_overlay_max_scroll(screen: Rect, app: &App) -> u16 {
+    let (width, visible_lines) = master_prompt_preview_metrics(screen);
+    (help_overlay_rows(app, width).len() as u16).saturating_sub(visible_lines)
+}
+
+fn render_help_overlay(frame: &mut Frame, app: &App, theme: &Theme) {
+    let Some(help) = app.help_overlay() else {
+        return;
+    };
+    let overlay = master_prompt_preview_area(frame.area());
+    let (width, _) = master_prompt_preview_metrics(frame.area());
+    let [header_area, body_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(1)])
+        .areas(Rect::new(
+            overlay.x.saturating_add(TEXT_PADDING),
+            overlay.y.saturating_add(TEXT_PADDING),
+            width,
+            overlay.height.saturating_sub(TEXT_PADDING * 2),
+        ));
+    let hint = if help.query().is_empty() {
+        i18n::text(app.locale(), UiText::HelpHint).to_string()
+    } else {
+        i18n::fill(
+            app.locale(),
+            UiText::HelpSearch,
+            &[("query", &help.query())],
+        )
+    };
+    let header = Line::from(vec![
+        Span::styled(
+            i18n::text(app.locale(), UiText::HelpTitle),
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(hint, Style::default().fg(theme.muted_fg)),
+    ]);
+    let mut lines = help_overlay_rows(app, width)
+        .into_iter()
+        .map(|row| match row {
+            HelpRow::Section(title, focused) => {
+                let mut spans = vec![Span::styled(
+                    title,
+                    Style::default()
+                        .fg(theme.active_fg)
+                        .add_modifier(Modifier::BOLD),
+                )];
+                if focused {
+                    spans.push(Span::styled(
+                        format!(" {}", i18n::text(app.locale(), UiText::HelpFocused)),
+                        Style::default().fg(theme.muted_fg),
+                    ));
+                }
+                Line::from(spans)
+            }
+            HelpRow::Binding(keys, action) => Line::from(vec![
+                Span::styled(keys, Style::default().fg(theme.active_fg)),
+                Span::styled(action, Style::default().fg(theme.text_fg)),
+            ]),
+            HelpRow::Blank => Line::default(),
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            i18n::text(app.locale(), UiText::HelpNoMatches),
+            Style::default().fg(theme.muted_fg),
+        )));
+    }
+
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(
+        Block::default().style(Style::default().bg(theme.input_bg)),
+        overlay,
+    );
+    frame.render_widget(Paragraph::new(header), header_area);
+    frame.render_widget(Paragraph::new(lines).scroll((help.scroll(), 0)), body_area);
+}
+
+fn render_backend_picker(frame: &mut Frame, app: &App, theme: &Theme) {
+    let entries = app.backend_picker_options();
+    if entries.is_empty() {
+        return;
+    }
+
+    let width = frame.area().width.min(90).max(40);
+    let max_rows = frame.area().height.saturating_sub(8).max(3);
+    let shown_count = (entries.len() as u16).min(max_rows.saturating_sub(2).max(1));
+    let height = shown_count
+        .saturating_add(4)
+        .min(frame.area().height.max(3));
+    let x = frame
+        .area()
+        .x
+        .saturating_add(frame.area().width.saturating_sub(width) / 2);
+    let y = frame
+        .area()
+        .y
+        .saturating_add(frame.area().height.saturating_sub(height) / 2);
+    let overlay = Rect::new(x, y, width, height);
+
+    let start = app
+        .backend_picker_selected_index()
+        .saturating_sub((shown_count as usize).saturating_sub(1));
+    let shown = entries
+        .iter()
+        .skip(start)
+        .take(shown_count as usize)
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::with_capacity(shown.len() + 1);
+    lines.push(Line::from(vec![
+        Span::styled(
+            i18n::text(app.locale(), UiText::BackendTitle),
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            i18n::text(app.locale(), UiText::BackendHint),
+            Style::default().fg(theme.muted_fg),
+        ),
+    ]));
+    for (idx, item) in shown.iter().enumerate() {
+        let absolute_idx = start + idx;
+        let selected = absolute_idx == app.backend_picker_selected_index();
+        let style = if selected {
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_fg)
+        };
+        lines.push(Line::from(vec![
             Span::styled(
                 if selected { ">" } else { " " }.to_string(),
                 Style::default().fg(theme.muted_fg),
@@ -903,6 +1696,201 @@ fn render_backend_picker(frame: &mut Frame, app: &App, theme: &Theme) {
     );
 }
 
+fn render_log_picker(frame: &mut Frame, app: &App, theme: &Theme) {
+    let entries = app.log_picker_options();
+    if entries.is_empty() {
+        return;
+    }
+
+    let width = frame.area().width.min(90).max(40);
+    let max_rows = frame.area().height.saturating_sub(8).max(3);
+    let shown_count = (entries.len() as u16).min(max_rows.saturating_sub(2).max(1));
+    let height = shown_count
+        .saturating_add(4)
+        .min(frame.area().height.max(3));
+    let x = frame
+        .area()
+        .x
+        .saturating_add(frame.area().width.saturating_sub(width) / 2);
+    let y = frame
+        .area()
+        .y
+        .saturating_add(frame.area().height.saturating_sub(height) / 2);
+    let overlay = Rect::new(x, y, width, height);
+
+    let start = app
+        .log_picker_selected_index()
+        .saturating_sub((shown_count as usize).saturating_sub(1));
+    let shown = entries
+        .iter()
+        .skip(start)
+        .take(shown_count as usize)
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::with_capacity(shown.len() + 1);
+    lines.push(Line::from(vec![
+        Span::styled(
+            i18n::text(app.locale(), UiText::LogsTitle),
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            i18n::text(app.locale(), UiText::LogsHint),
+            Style::default().fg(theme.muted_fg),
+        ),
+    ]));
+    for (idx, item) in shown.iter().enumerate() {
+        let absolute_idx = start + idx;
+        let selected = absolute_idx == app.log_picker_selected_index();
+        let style = if selected {
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_fg)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                if selected { ">" } else { " " }.to_string(),
+                Style::default().fg(theme.muted_fg),
+            ),
+            Span::raw(" "),
+            Span::styled(item.label.clone(), style),
+            Span::raw(" "),
+            Span::styled(
+                i18n::fill(
+                    app.locale(),
+                    UiText::LogSize,
+                    &[("bytes", &item.size_bytes)],
+                ),
+                Style::default().fg(theme.muted_fg),
+            ),
+        ]));
+    }
+
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().bg(theme.input_bg))
+            .block(
+                Block::default()
+                    .style(Style::default().bg(theme.input_bg))
+                    .padding(Padding::uniform(TEXT_PADDING)),
+            ),
+        overlay,
+    );
+}
+
+fn task_menu_area(screen: Rect, entry_count: usize) -> Rect {
+    let width = screen.width.clamp(30, 60);
+    let height = (entry_count as u16)
+        .saturating_add(4)
+        .min(screen.height.max(3));
+    let x = screen
+        .x
+        .saturating_add(screen.width.saturating_sub(width) / 2);
+    let y = screen
+        .y
+        .saturating_add(screen.height.saturating_sub(height) / 2);
+    Rect::new(x, y, width, height)
+}
+
+/// Index of the task menu entry at `(x, y)`, or `None` outside the entry rows.
+pub fn task_menu_hit_test(screen: Rect, app: &App, x: u16, y: u16) -> Option<usize> {
+    let entries = app.task_menu_entries();
+    let overlay = task_menu_area(screen, entries.len());
+    if x < overlay.x || x >= overlay.x.saturating_add(overlay.width) {
+        return None;
+    }
+    // Padding, then the title and hint rows, then one row per entry.
+    let first_entry_row = overlay.y.saturating_add(TEXT_PADDING + 2);
+    let index = y.checked_sub(first_entry_row)? as usize;
+    (index < entries.len()).then_some(index)
+}
+
+/// Whether `(x, y)` falls inside the open task menu.
+pub fn task_menu_contains(screen: Rect, app: &App, x: u16, y: u16) -> bool {
+    let overlay = task_menu_area(screen, app.task_menu_entries().len());
+    x >= overlay.x
+        && x < overlay.x.saturating_add(overlay.width)
+        && y >= overlay.y
+        && y < overlay.y.saturating_add(overlay.height)
+}
+
+fn task_menu_entry_label(app: &App, target: &TaskMenuTarget, entry: &TaskMenuEntry) -> String {
+    let key = match entry {
+        TaskMenuEntry::ToggleDetails if app.is_task_details_expanded(&target.task_id) => {
+            UiText::TaskMenuCollapseDetails
+        }
+        TaskMenuEntry::ToggleDetails => UiText::TaskMenuExpandDetails,
+        TaskMenuEntry::ShowConversation => UiText::TaskMenuShowConversation,
+        TaskMenuEntry::ViewLogs => UiText::TaskMenuViewLogs,
+        TaskMenuEntry::Retry => UiText::TaskMenuRetry,
+        TaskMenuEntry::Skip => UiText::TaskMenuSkip,
+        TaskMenuEntry::ChangeModel => UiText::TaskMenuChangeModel,
+        TaskMenuEntry::AttachDocs => UiText::TaskMenuAttachDocs,
+        TaskMenuEntry::ModelProfile(None) => UiText::TaskMenuDefaultModel,
+        TaskMenuEntry::ModelProfile(Some(profile)) => return profile.clone(),
+    };
+    i18n::text(app.locale(), key).to_string()
+}
+
+fn render_task_menu(frame: &mut Frame, app: &App, theme: &Theme) {
+    let Some(target) = app.task_menu_target() else {
+        return;
+    };
+    let entries = app.task_menu_entries();
+    let overlay = task_menu_area(frame.area(), entries.len());
+
+    let mut lines = Vec::with_capacity(entries.len() + 2);
+    lines.push(Line::from(Span::styled(
+        i18n::fill(
+            app.locale(),
+            UiText::TaskMenuTitle,
+            &[("id", &target.task_id), ("title", &target.title)],
+        ),
+        Style::default()
+            .fg(theme.active_fg)
+            .add_modifier(Modifier::BOLD),
+    )));
+    lines.push(Line::from(Span::styled(
+        i18n::text(app.locale(), UiText::TaskMenuHint),
+        Style::default().fg(theme.muted_fg),
+    )));
+    for (idx, entry) in entries.iter().enumerate() {
+        let selected = idx == app.task_menu_selected_index();
+        let style = if selected {
+            Style::default()
+                .fg(theme.active_fg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_fg)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                if selected { ">" } else { " " }.to_string(),
+                Style::default().fg(theme.muted_fg),
+            ),
+            Span::raw(" "),
+            Span::styled(task_menu_entry_label(app, target, entry), style),
+        ]));
+    }
+
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().bg(theme.input_bg))
+            .block(
+                Block::default()
+                    .style(Style::default().bg(theme.input_bg))
+                    .padding(Padding::uniform(TEXT_PADDING)),
+            ),
+        overlay,
+    );
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ChatPrefix {
     You,
@@ -918,78 +1906,178 @@ struct ChatDisplayLine {
     is_separator: bool,
 }
 
-fn cached_chat_display_lines(app: &App, width: u16) -> Arc<Vec<ChatDisplayLine>> {
+/// Runs `f` with the row ends of every chat message at `width`, wrapping only the messages
+/// added since the last call at that width.
+fn with_chat_row_ends<T>(app: &App, width: u16, f: impl FnOnce(&[usize]) -> T) -> T {
     let width = width.max(1);
+    let image_rows = app.inline_image_rows();
     let generation = app.chat_messages_generation();
-    CHAT_LINES_CACHE.with(|cache_cell| {
-        if let Some(cache) = cache_cell.borrow().as_ref()
-            && cache.width == width
-            && cache.generation == generation
-        {
-            return Arc::clone(&cache.lines);
+    let transcript = app.displayed_chat_transcript();
+    CHAT_LAYOUT_CACHE.with(|cache_cell| {
+        let mut cache = cache_cell.borrow_mut();
+        let reusable = cache.as_ref().is_some_and(|cache| {
+            cache.transcript_id == transcript.id()
+                && cache.width == width
+                && cache.image_rows == image_rows
+        });
+        if !reusable {
+            *cache = Some(ChatLayoutCache {
+                transcript_id: transcript.id(),
+                width,
+                image_rows,
+                generation: 0,
+                row_ends: Vec::new(),
+            });
+        }
+        let Some(cache) = cache.as_mut() else {
+            return f(&[]);
+        };
+        if cache.generation != generation {
+            for index in cache.row_ends.len()..transcript.len() {
+                let start = cache.row_ends.last().map_or(0, |end| end + 1);
+                let mut lines = Vec::new();
+                push_message_display_lines(
+                    &mut lines,
+                    &transcript.line(index).unwrap_or_default(),
+                    width,
+                    image_rows,
+                );
+                cache.row_ends.push(start + lines.len());
+            }
+            cache.generation = generation;
         }
+        f(&cache.row_ends)
+    })
+}
 
-        let lines = Arc::new(chat_display_lines(app.left_bottom_lines(), width));
-        *cache_cell.borrow_mut() = Some(ChatLinesCache {
-            width,
-            generation,
-            lines: Arc::clone(&lines),
-        });
-        lines
+fn chat_row_count(app: &App, width: u16) -> usize {
+    with_chat_row_ends(app, width, |row_ends| row_ends.last().copied().unwrap_or(0))
+}
+
+/// The `height` display rows starting at row `scroll`, wrapping only the messages they show.
+fn visible_chat_display_lines(
+    app: &App,
+    width: u16,
+    scroll: usize,
+    height: usize,
+) -> Vec<ChatDisplayLine> {
+    let width = width.max(1);
+    let image_rows = app.inline_image_rows();
+    let transcript = app.displayed_chat_transcript();
+    with_chat_row_ends(app, width, |row_ends| {
+        // Messages whose rows and separator all lie above the view are skipped unwrapped.
+        let first = row_ends.partition_point(|end| *end < scroll);
+        let first_row = first
+            .checked_sub(1)
+            .map_or(0, |previous| row_ends[previous] + 1);
+        let mut out = Vec::new();
+        let mut row = first_row;
+        for index in first..row_ends.len() {
+            if row >= scroll + height {
+                break;
+            }
+            push_message_display_lines(
+                &mut out,
+                &transcript.line(index).unwrap_or_default(),
+                width,
+                image_rows,
+            );
+            if index + 1 < row_ends.len() {
+                out.push(chat_separator_line(width));
+            }
+            row = row_ends[index] + 1;
+        }
+        out.into_iter()
+            .skip(scroll - first_row)
+            .take(height)
+            .collect()
     })
 }
 
+#[cfg(test)]
 fn chat_display_lines(messages: &[String], width: u16) -> Vec<ChatDisplayLine> {
     let width = width.max(1);
     let mut out = Vec::new();
     for (idx, message) in messages.iter().enumerate() {
-        let (prefix, body) = parse_chat_prefix_and_body(message);
-        if let Some(prefix) = prefix {
-            let label = match prefix {
-                ChatPrefix::You => "You:",
-                ChatPrefix::Agent => "Agent:",
-                ChatPrefix::System => "System:",
-            };
-            let prefix_width = label.chars().count() + 1;
-            let body_width = (width as usize).saturating_sub(prefix_width).max(1) as u16;
-            let wrapped = wrap_text_lines(body, body_width);
-            if let Some((first, rest)) = wrapped.split_first() {
-                out.push(ChatDisplayLine {
-                    prefix: Some(prefix),
-                    body: first.clone(),
-                    show_label: true,
-                    is_separator: false,
-                });
-                for line in rest {
-                    out.push(ChatDisplayLine {
-                        prefix: Some(prefix),
-                        body: line.clone(),
-                        show_label: false,
-                        is_separator: false,
-                    });
-                }
-            }
-        } else {
-            for line in wrap_text_lines(body, width) {
+        push_message_display_lines(&mut out, message, width, 0);
+        if idx + 1 < messages.len() {
+            out.push(chat_separator_line(width));
+        }
+    }
+    out
+}
+
+fn chat_separator_line(width: u16) -> ChatDisplayLine {
+    ChatDisplayLine {
+        prefix: None,
+        body: "─".repeat(width as usize),
+        show_label: false,
+        is_separator: true,
+    }
+}
+
+/// Wraps one chat message. PNG references show as their text fallback; with `image_rows`,
+/// that many blank rows follow each fallback line for the terminal to draw the image in.
+fn push_message_display_lines(
+    out: &mut Vec<ChatDisplayLine>,
+    message: &str,
+    width: u16,
+    image_rows: u16,
+) {
+    let (prefix, body) = parse_chat_prefix_and_body(message);
+    let body = with_image_fallbacks(body);
+    let Some(prefix) = prefix else {
+        for line in wrap_text_lines(&body, width) {
+            let reserve = image_rows_after(&line, image_rows);
+            out.push(ChatDisplayLine {
+                prefix: None,
+                body: line,
+                show_label: false,
+                is_separator: false,
+            });
+            for _ in 0..reserve {
                 out.push(ChatDisplayLine {
                     prefix: None,
-                    body: line,
+                    body: String::new(),
                     show_label: false,
                     is_separator: false,
                 });
             }
         }
-
-        if idx + 1 < messages.len() {
+        return;
+    };
+    let label = match prefix {
+        ChatPrefix::You => "You:",
+        ChatPrefix::Agent => "Agent:",
+        ChatPrefix::System => "System:",
+    };
+    let prefix_width = label.chars().count() + 1;
+    let body_width = (width as usize).saturating_sub(prefix_width).max(1) as u16;
+    for (index, line) in wrap_text_lines(&body, body_width).into_iter().enumerate() {
+        let reserve = image_rows_after(&line, image_rows);
+        out.push(ChatDisplayLine {
+            prefix: Some(prefix),
+            body: line,
+            show_label: index == 0,
+            is_separator: false,
+        });
+        for _ in 0..reserve {
             out.push(ChatDisplayLine {
-                prefix: None,
-                body: "─".repeat(width as usize),
+                prefix: Some(prefix),
+                body: String::new(),
                 show_label: false,
-                is_separator: true,
+                is_separator: false,
             });
         }
     }
-    out
+}
+
+fn image_rows_after(line: &str, image_rows: u16) -> u16 {
+    if line.contains(IMAGE_MARKER) {
+        image_rows
+    } else {
+        0
+    }
 }
 
 fn chat_text(lines: &[ChatDisplayLine], theme: &Theme) -> Text<'static> {
@@ -1163,8 +2251,13 @@ fn render_right_task_pane(frame: &mut Frame, area: Rect, app: &App, active: bool
             );
         }
     } else {
+        let right_text = if app.is_worker_log_mode() || app.is_task_summaries_mode() {
+            Text::from(view.lines.join("\n"))
+        } else {
+            right_pane_text(&view.lines)
+        };
         frame.render_widget(
-            Paragraph::new(right_pane_text(&view.lines))
+            Paragraph::new(right_text)
                 .style(Style::default().bg(theme.right_bg).fg(theme.text_fg))
                 .scroll((app.right_scroll(), 0))
                 .block(
@@ -1226,6 +2319,58 @@ pub fn planner_cursor_hit_test(screen: Rect, app: &App, x: u16, y: u16) -> Optio
 }
 
 pub fn right_pane_toggle_hit_test(screen: Rect, app: &App, x: u16, y: u16) -> Option<String> {
+    let (view, line_index) = right_pane_line_hit_test(screen, app, x, y)?;
+    let line = &view.lines[line_index];
+    if !(line.contains("[+]") || line.contains("[-]")) {
+        return None;
+    }
+    view.toggles
+        .iter()
+        .find(|toggle| toggle.line_index == line_index)
+        .map(|toggle| toggle.task_key.clone())
+}
+
+/// First file/line citation on the worker output row at `(x, y)`, so clicking an audit
+/// finding opens the cited lines.
+pub fn left_top_citation_hit_test(
+    screen: Rect,
+    app: &App,
+    x: u16,
+    y: u16,
+) -> Option<AuditCitation> {
+    if app.is_file_tree_mode() || app.is_stats_mode() || app.is_console_mode() {
+        return None;
+    }
+    let [_title_area, content_area] =
+        Layout::vertical([Constraint::Length(TITLE_BAR_HEIGHT), Constraint::Min(0)])
+            .areas(pane_area(screen, Pane::LeftTop));
+    if !point_in_rect(content_area, x, y) {
+        return None;
+    }
+    let inner_y = y.saturating_sub(content_area.y);
+    if inner_y < TEXT_PADDING {
+        return None;
+    }
+    let width = content_area.width.saturating_sub(TEXT_PADDING * 2).max(1);
+    let row = usize::from(app.left_top_scroll()) + usize::from(inner_y - TEXT_PADDING);
+    let text = app.left_top_wrapped_text(width);
+    audit_citations::parse_line(text.lines().nth(row)?)
+        .into_iter()
+        .next()
+}
+
+/// Planner id of the task drawn at `(x, y)` in the right pane, for the task action menu.
+pub fn right_pane_task_hit_test(screen: Rect, app: &App, x: u16, y: u16) -> Option<String> {
+    let (view, line_index) = right_pane_line_hit_test(screen, app, x, y)?;
+    view.task_key_at(line_index).map(str::to_string)
+}
+
+fn right_pane_line_hit_test(
+    screen: Rect,
+    app: &App,
+    x: u16,
+    y: u16,
+) -> Option<(RightPaneBlockView, usize)> {
     if app.is_planner_mode() {
         return None;
     }
@@ -1250,14 +2395,7 @@ pub fn right_pane_toggle_hit_test(screen: Rect, app: &App, x: u16, y: u16) -> Op
     if line_index >= view.lines.len() {
         return None;
     }
-    let line = &view.lines[line_index];
-    if !(line.contains("[+]") || line.contains("[-]")) {
-        return None;
-    }
-    view.toggles
-        .iter()
-        .find(|toggle| toggle.line_index == line_index)
-        .map(|toggle| toggle.task_key.clone())
+    Some((view, line_index))
 }
 
 fn right_pane_text(lines: &[String]) -> Text<'static> {
@@ -1335,6 +2473,19 @@ fn right_pane_text(lines: &[String]) -> Text<'static> {
             details_continuation = false;
         }
 
+        if in_task_section && line.contains("[?]") {
+            let style = Style::default()
+                .fg(Color::Rgb(170, 130, 230))
+                .add_modifier(Modifier::BOLD);
+            if let Some((prefix, body, suffix)) = split_box_line_content(line) {
+                out.push(Line::from(styled_box_body_preserving_borders(
+                    prefix, body, suffix, style,
+                )));
+            } else {
+                out.push(Line::from(Span::styled(line.clone(), style)));
+            }
+            continue;
+        }
         if in_task_section && line.contains("[~]") {
             let style = Style::default().fg(Color::Rgb(230, 150, 60));
             if let Some((prefix, body, suffix)) = split_box_line_content(line) {
@@ -1587,6 +2738,7 @@ fn is_detail_continuation_line(line: &str) -> bool {
     if line.contains("[ ]")
         || line.contains("[~]")
         || line.contains("[!]")
+        || line.contains("[?]")
         || line.contains("[x]")
         || line.starts_with("Execution")
         || line.starts_with("Rolling Task Context")
@@ -1606,7 +2758,9 @@ fn is_numbered_top_task_line(line: &str) -> bool {
 }
 
 fn input_box_metrics(input_text_lines: u16, cursor_line: u16, max_input_height: u16) -> (u16, u16) {
-    let capped_text_lines = input_text_lines.clamp(1, MAX_INPUT_TEXT_LINES);
+    // Long pastes may grow the box to half the chat pane; beyond that the input scrolls.
+    let max_text_lines = MIN_INPUT_TEXT_LINES_CAP.max(max_input_height / 2);
+    let capped_text_lines = input_text_lines.clamp(1, max_text_lines);
     let desired_height = capped_text_lines.saturating_add(TEXT_PADDING * 2);
     let input_height = desired_height.clamp(1, max_input_height.max(1));
     let visible_text_lines = input_height.saturating_sub(TEXT_PADDING * 2).max(1);
@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SendError};
+use std::sync::{Arc, Condvar, Mutex, OnceLock, Weak};
+use std::time::Duration;
+
+/// How often a [`FileWatch`] thread checks its files.
+const FILE_WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Process-wide signal the main loop sleeps on between iterations. Terminal input and agent
+/// and test-runner output notify it, so an idle loop only wakes when there is something to
+/// handle or one of its timers is due.
+struct Signal {
+    pending: Mutex<bool>,
+    condvar: Condvar,
+}
+
+fn signal() -> &'static Signal {
+    static SIGNAL: OnceLock<Signal> = OnceLock::new();
+    SIGNAL.get_or_init(|| Signal {
+        pending: Mutex::new(false),
+        condvar: Condvar::new(),
+    })
+}
+
+pub fn notify() {
+    let signal = signal();
+    let mut pending = signal.pending.lock().unwrap_or_else(|e| e.into_inner());
+    *pending = true;
+    signal.condvar.notify_all();
+}
+
+/// Sleeps until [`notify`] or `timeout` and returns whether it was notified. A notification
+/// sent while nobody was waiting ends the next wait right away.
+pub fn wait(timeout: Duration) -> bool {
+    let signal = signal();
+    let pending = signal.pending.lock().unwrap_or_else(|e| e.into_inner());
+    let (mut pending, _) = signal
+        .condvar
+        .wait_timeout_while(pending, timeout, |pending| !*pending)
+        .unwrap_or_else(|e| e.into_inner());
+    std::mem::take(&mut *pending)
+}
+
+/// An `mpsc` sender that calls [`notify`] after every send.
+#[derive(Debug)]
+pub struct Sender<T>(mpsc::Sender<T>);
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Sender<T> {
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let sent = self.0.send(value);
+        notify();
+        sent
+    }
+}
+
+/// Like `mpsc::channel`, with a sender that wakes the main loop.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    (Sender(tx), rx)
+}
+
+#[derive(Debug, Default)]
+struct WatchedFiles {
+    paths: Mutex<Vec<PathBuf>>,
+    changed: AtomicBool,
+}
+
+/// Checks a few files from a background thread and calls [`notify`] when one of them is
+/// created, removed, or modified, so the main loop does not have to wake up to poll them.
+/// The thread stops once the watch is dropped.
+#[derive(Debug, Default)]
+pub struct FileWatch {
+    files: Option<Arc<WatchedFiles>>,
+}
+
+impl FileWatch {
+    /// Replaces the watched files; an empty list watches nothing.
+    pub fn watch(&mut self, paths: &[PathBuf]) {
+        let files = self.files.get_or_insert_with(|| {
+            let files = Arc::new(WatchedFiles::default());
+            let weak = Arc::downgrade(&files);
+            std::thread::spawn(move || watch_files(weak));
+            files
+        });
+        let mut watched = files.paths.lock().unwrap_or_else(|e| e.into_inner());
+        if watched.as_slice() != paths {
+            *watched = paths.to_vec();
+        }
+    }
+
+    /// Whether a watched file changed since the last call.
+    pub fn take_changed(&self) -> bool {
+        self.files
+            .as_ref()
+            .is_some_and(|files| files.changed.swap(false, Ordering::SeqCst))
+    }
+}
+
+fn watch_files(files: Weak<WatchedFiles>) {
+    let mut seen_paths = Vec::new();
+    let mut seen_stamps = Vec::new();
+    loop {
+        std::thread::sleep(FILE_WATCH_INTERVAL);
+        let Some(files) = files.upgrade() else {
+            return;
+        };
+        let paths = files
+            .paths
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let stamps = paths
+            .iter()
+            .map(|path| {
+                let meta = std::fs::metadata(path).ok()?;
+                Some((meta.modified().ok()?, meta.len()))
+            })
+            .collect::<Vec<_>>();
+        if seen_paths == paths && seen_stamps != stamps {
+            files.changed.store(true, Ordering::SeqCst);
+            notify();
+        }
+        seen_paths = paths;
+        seen_stamps = stamps;
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/wakeup_tests.rs"]
+mod tests;
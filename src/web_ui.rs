@@ -0,0 +1,242 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::event_log::{self, SessionEventFileEntry};
+use crate::session_store::{PlannerTaskFileEntry, SessionStore};
+
+pub const DEFAULT_PORT: u16 = 8787;
+
+const DASHBOARD_HTML: &str = include_str!("web_ui.html");
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+const EVENT_BATCH_LIMIT: usize = 500;
+/// How long a connection may take to send its request head.
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long an event stream waits for new events before sending a keep-alive comment, which
+/// is also how a closed browser tab is noticed.
+const EVENT_STREAM_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// The parts of an HTTP request line and headers the dashboard routes on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestHead {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    pub host: Option<String>,
+    /// `Last-Event-ID`, sent by a reconnecting `EventSource`.
+    pub last_event_id: Option<u64>,
+}
+
+/// State the page loads once; everything after it arrives over the event stream.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardSnapshot {
+    pub session_dir: String,
+    pub tasks: Vec<PlannerTaskFileEntry>,
+    pub rolling_context: Vec<String>,
+    /// Sequence number of the newest event in `events.jsonl`, 0 when there are none.
+    pub last_event_seq: u64,
+}
+
+/// Serves the dashboard for one session on a loopback port. It only reads session files, so
+/// it can run next to the TUI that drives the session.
+#[derive(Debug)]
+pub struct DashboardServer {
+    listener: TcpListener,
+    store: SessionStore,
+}
+
+impl DashboardServer {
+    /// Binds `127.0.0.1:<port>`; port 0 picks a free one.
+    pub fn bind(store: &SessionStore, port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))?;
+        Ok(Self {
+            listener,
+            store: store.clone(),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections until the process exits, one thread per connection so event
+    /// streams do not hold up page and snapshot requests.
+    pub fn serve(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let store = self.store.clone();
+            std::thread::spawn(move || {
+                let _ = handle_connection(stream, &store);
+            });
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_request_head(text: &str) -> Option<RequestHead> {
+    let mut request_line = text.lines().next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    if !request_line.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    Some(RequestHead {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        host: header(text, "host").map(str::to_string),
+        last_event_id: header(text, "last-event-id").and_then(|value| value.parse().ok()),
+    })
+}
+
+fn header<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
+
+/// Whether `Host` names the dashboard's own loopback address. Anything else is a page on
+/// another site reaching the port through a rebound DNS name, and is refused.
+pub fn is_dashboard_host(host: Option<&str>, port: u16) -> bool {
+    host.is_some_and(|host| {
+        host == format!("127.0.0.1:{port}")
+            || host.eq_ignore_ascii_case(&format!("localhost:{port}"))
+    })
+}
+
+pub fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+pub fn read_snapshot(store: &SessionStore) -> io::Result<DashboardSnapshot> {
+    Ok(DashboardSnapshot {
+        session_dir: store.session_dir().display().to_string(),
+        tasks: store.read_tasks()?,
+        rolling_context: store.read_rolling_context().unwrap_or_default(),
+        last_event_seq: event_log::last_event_seq(&store.events_file())?,
+    })
+}
+
+/// One server-sent event; the event name is the entry's `type`, so the page can listen per
+/// kind, and the id lets a reconnecting browser resume after it.
+pub fn format_server_sent_event(entry: &SessionEventFileEntry) -> String {
+    let data = serde_json::to_value(entry).unwrap_or_default();
+    let name = data
+        .pointer("/event/type")
+        .and_then(|value| value.as_str())
+        .unwrap_or("message")
+        .to_string();
+    format!("id: {}\nevent: {name}\ndata: {data}\n\n", entry.seq)
+}
+
+fn handle_connection(stream: TcpStream, store: &SessionStore) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut head = String::new();
+    loop {
+        let read = reader.read_line(&mut head)?;
+        if read == 0 || head.ends_with("\r\n\r\n") || head.ends_with("\n\n") {
+            break;
+        }
+        if head.len() > MAX_REQUEST_HEAD_BYTES {
+            return write_response(
+                stream,
+                "431 Request Header Fields Too Large",
+                "text/plain",
+                "",
+            );
+        }
+    }
+    let Some(request) = parse_request_head(&head) else {
+        return write_response(stream, "400 Bad Request", "text/plain", "bad request");
+    };
+    if !is_dashboard_host(request.host.as_deref(), stream.local_addr()?.port()) {
+        return write_response(stream, "403 Forbidden", "text/plain", "unknown host");
+    }
+    if request.method != "GET" {
+        return write_response(stream, "405 Method Not Allowed", "text/plain", "GET only");
+    }
+    match request.path.as_str() {
+        "/" | "/index.html" => {
+            write_response(stream, "200 OK", "text/html; charset=utf-8", DASHBOARD_HTML)
+        }
+        "/api/snapshot" => match read_snapshot(store) {
+            Ok(snapshot) => write_response(
+                stream,
+                "200 OK",
+                "application/json",
+                &serde_json::to_string(&snapshot).map_err(io::Error::other)?,
+            ),
+            Err(err) => write_response(
+                stream,
+                "500 Internal Server Error",
+                "application/json",
+                &serde_json::json!({ "error": err.to_string() }).to_string(),
+            ),
+        },
+        "/api/events" => {
+            let after = request.last_event_id.unwrap_or_else(|| {
+                query_param(&request.query, "after")
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(0)
+            });
+            stream_events(stream, &store.events_file(), after)
+        }
+        _ => write_response(stream, "404 Not Found", "text/plain", "not found"),
+    }
+}
+
+fn write_response(
+    mut stream: TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+/// Tails `events.jsonl` as `text/event-stream` until the browser goes away.
+fn stream_events(mut stream: TcpStream, events_file: &Path, after: u64) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\n"
+    )?;
+    let mut cursor = after;
+    loop {
+        let events = event_log::wait_for_events(
+            events_file,
+            cursor,
+            EVENT_BATCH_LIMIT,
+            EVENT_STREAM_KEEP_ALIVE,
+        )?;
+        if events.is_empty() {
+            stream.write_all(b": keep-alive\n\n")?;
+        }
+        for entry in &events {
+            stream.write_all(format_server_sent_event(entry).as_bytes())?;
+            cursor = entry.seq;
+        }
+        stream.flush()?;
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/web_ui_tests.rs"]
+mod tests;
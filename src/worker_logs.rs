@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::workflow::ActiveJobMeta;
+
+const LOG_FILE_EXTENSION: &str = "log";
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Size-based rotation and age-based retention for worker logs (`[logs]` in config.toml).
+/// Zero disables rotation or retention respectively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerLogPolicy {
+    pub max_file_bytes: u64,
+    pub max_rotated_files: usize,
+    pub retention_days: u64,
+}
+
+impl Default for WorkerLogPolicy {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 1024 * 1024,
+            max_rotated_files: 3,
+            retention_days: 14,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerLogEntry {
+    pub path: PathBuf,
+    /// Path relative to the logs dir, e.g. `task-1/implementor-pass2.log`.
+    pub label: String,
+    pub size_bytes: u64,
+    pub modified: SystemTime,
+}
+
+/// `<task-id>/<role>-pass<n>.log`, relative to the session's logs dir.
+pub fn worker_log_relative_path(meta: &ActiveJobMeta) -> PathBuf {
+    PathBuf::from(task_log_dir_name(&meta.top_task_key)).join(format!(
+        "{}-pass{}.{LOG_FILE_EXTENSION}",
+        meta.role.file_stem(),
+        meta.pass
+    ))
+}
+
+/// Directory under the logs dir holding the logs of top-level task `top_task_key`.
+pub fn task_log_dir_name(top_task_key: &str) -> String {
+    top_task_key
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct OpenLog {
+    path: PathBuf,
+    file: File,
+    bytes: u64,
+}
+
+/// Appends worker output to the active job's log file, rotating it once it grows past the limit.
+#[derive(Debug)]
+pub struct WorkerLogWriter {
+    policy: WorkerLogPolicy,
+    current: Option<OpenLog>,
+    failed_path: Option<PathBuf>,
+}
+
+impl WorkerLogWriter {
+    pub fn new(policy: WorkerLogPolicy) -> Self {
+        Self {
+            policy,
+            current: None,
+            failed_path: None,
+        }
+    }
+
+    /// Writes one line to `logs_dir/relative`. After a failure the same file is not retried,
+    /// so callers can report the first error without repeating it for every line.
+    pub fn append(&mut self, logs_dir: &Path, relative: &Path, line: &str) -> io::Result<()> {
+        let path = logs_dir.join(relative);
+        if self.failed_path.as_ref() == Some(&path) {
+            return Ok(());
+        }
+        let result = self.append_to(logs_dir, path.clone(), line);
+        if result.is_err() {
+            self.current = None;
+            self.failed_path = Some(path);
+        }
+        result
+    }
+
+    pub fn close(&mut self) {
+        self.current = None;
+    }
+
+    fn append_to(&mut self, logs_dir: &Path, path: PathBuf, line: &str) -> io::Result<()> {
+        if self.current.as_ref().is_none_or(|open| open.path != path) {
+            self.current = None;
+            prune_expired_logs(logs_dir, self.policy.retention_days, SystemTime::now())?;
+            self.current = Some(open_log(path)?);
+        }
+        let line_bytes = line.len() as u64 + 1;
+        let needs_rotation = self.current.as_ref().is_some_and(|open| {
+            self.policy.max_file_bytes > 0
+                && open.bytes > 0
+                && open.bytes + line_bytes > self.policy.max_file_bytes
+        });
+        if needs_rotation && let Some(open) = self.current.take() {
+            drop(open.file);
+            rotate_log(&open.path, self.policy.max_rotated_files)?;
+            self.current = Some(open_log(open.path)?);
+        }
+        let Some(open) = self.current.as_mut() else {
+            return Ok(());
+        };
+        writeln!(open.file, "{line}")?;
+        open.bytes += line_bytes;
+        Ok(())
+    }
+}
+
+fn open_log(path: PathBuf) -> io::Result<OpenLog> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    let bytes = file.metadata()?.len();
+    Ok(OpenLog { path, file, bytes })
+}
+
+/// Shifts `x.log` to `x.log.1`, `x.log.1` to `x.log.2`, and so on, dropping the oldest.
+fn rotate_log(path: &Path, max_rotated_files: usize) -> io::Result<()> {
+    let rotated = |index: usize| {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    };
+    if max_rotated_files == 0 {
+        return fs::remove_file(path);
+    }
+    remove_if_exists(&rotated(max_rotated_files))?;
+    for index in (1..max_rotated_files).rev() {
+        let from = rotated(index);
+        if from.exists() {
+            fs::rename(&from, rotated(index + 1))?;
+        }
+    }
+    fs::rename(path, rotated(1))
+}
+
+/// Removes log files older than `retention_days`, then any task dirs left empty.
+pub fn prune_expired_logs(logs_dir: &Path, retention_days: u64, now: SystemTime) -> io::Result<()> {
+    if retention_days == 0 {
+        return Ok(());
+    }
+    let cutoff = now
+        .checked_sub(Duration::from_secs(
+            retention_days.saturating_mul(SECS_PER_DAY),
+        ))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    for entry in list_worker_logs(logs_dir)? {
+        if entry.modified < cutoff {
+            remove_if_exists(&entry.path)?;
+            if let Some(parent) = entry.path.parent()
+                && fs::read_dir(parent)?.next().is_none()
+            {
+                fs::remove_dir(parent)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// All worker logs in the session, rotated files included, newest first.
+pub fn list_worker_logs(logs_dir: &Path) -> io::Result<Vec<WorkerLogEntry>> {
+    let task_dirs = match fs::read_dir(logs_dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut entries = Vec::new();
+    for task_dir in task_dirs.filter_map(Result::ok) {
+        if !task_dir.file_type()?.is_dir() {
+            continue;
+        }
+        for file in fs::read_dir(task_dir.path())?.filter_map(Result::ok) {
+            let name = file.file_name().to_string_lossy().into_owned();
+            if !is_log_file_name(&name) {
+                continue;
+            }
+            let meta = file.metadata()?;
+            entries.push(WorkerLogEntry {
+                path: file.path(),
+                label: format!("{}/{name}", task_dir.file_name().to_string_lossy()),
+                size_bytes: meta.len(),
+                modified: meta.modified()?,
+            });
+        }
+    }
+    entries.sort_by(|a, b| b.modified.cmp(&a.modified).then(a.label.cmp(&b.label)));
+    Ok(entries)
+}
+
+/// The log of each role's last pass on top-level task `top_task_key`, ordered by role name.
+/// Rotated copies are skipped: the current file holds the end of the pass.
+pub fn final_pass_logs(logs_dir: &Path, top_task_key: &str) -> Vec<PathBuf> {
+    let Ok(files) = fs::read_dir(logs_dir.join(task_log_dir_name(top_task_key))) else {
+        return Vec::new();
+    };
+    let mut last_passes: BTreeMap<String, (u32, PathBuf)> = BTreeMap::new();
+    for file in files.filter_map(Result::ok) {
+        let name = file.file_name().to_string_lossy().into_owned();
+        let Some((role, pass)) = name
+            .strip_suffix(&format!(".{LOG_FILE_EXTENSION}"))
+            .and_then(|stem| stem.rsplit_once("-pass"))
+        else {
+            continue;
+        };
+        let Ok(pass) = pass.parse::<u32>() else {
+            continue;
+        };
+        if last_passes
+            .get(role)
+            .is_none_or(|(last_pass, _)| pass > *last_pass)
+        {
+            last_passes.insert(role.to_string(), (pass, file.path()));
+        }
+    }
+    last_passes.into_values().map(|(_, path)| path).collect()
+}
+
+pub fn read_worker_log_lines(path: &Path) -> Vec<String> {
+    match fs::read(path) {
+        Ok(bytes) if bytes.is_empty() => vec!["(empty log)".to_string()],
+        Ok(bytes) => String::from_utf8_lossy(&bytes)
+            .lines()
+            .map(|line| line.replace('\t', "    "))
+            .collect(),
+        Err(err) => vec![format!("(failed to read log: {err})")],
+    }
+}
+
+fn is_log_file_name(name: &str) -> bool {
+    let base = match name.rsplit_once('.') {
+        Some((base, suffix)) if suffix.chars().all(|ch| ch.is_ascii_digit()) => base,
+        _ => name,
+    };
+    base.ends_with(&format!(".{LOG_FILE_EXTENSION}"))
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/worker_logs_tests.rs"]
+mod tests;
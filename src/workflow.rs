@@ -1,14 +1,32 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::time::Duration;
 
 mod implementation_auditor;
 mod implementor;
+mod plugin_step;
 mod test_auditor;
 mod test_runner;
 mod test_writer;
-
+mod verify_step;
+
+use crate::agent::{TokenUsage, compact_count};
+use crate::artifact_io::is_workspace_subdir;
+use crate::audit_citations::AuditCitation;
+use crate::doc_snapshots::{MAX_SNAPSHOT_PROMPT_CHARS, is_truncated_snapshot};
+use crate::estimates::{self, Estimate};
+use crate::file_tree::parse_changed_file_paths;
+use crate::i18n::{self, Locale, Text};
+use crate::plugins::{PluginCapability, PluginRegistry, PluginTask};
+use crate::prompt_budget::PromptSection;
+use crate::prompt_context::{ContextSection, OMITTED_SECTION, PromptSections};
+use crate::protected_paths::ProtectedPaths;
 use crate::session_store::{
     PlannerTaskDocFileEntry, PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile,
 };
+use crate::test_runners::TestRunReport;
+use crate::time_format::format_duration;
+use crate::workspace_ignore::WorkspaceIgnore;
 
 const FILES_CHANGED_BEGIN: &str = "FILES_CHANGED_BEGIN";
 const FILES_CHANGED_END: &str = "FILES_CHANGED_END";
@@ -16,6 +34,8 @@ const MAX_AUDIT_RETRIES: u8 = 4;
 const MAX_TEST_RETRIES: u8 = 5;
 const MAX_FINAL_AUDIT_RETRIES: u8 = 4;
 const ENFORCE_TESTS_MODE_RUNTIME_GATING: bool = !cfg!(test);
+/// Planner id of the README/CHANGELOG task appended when `[workflow] docs_update_task` is on.
+pub const DOCS_UPDATE_TASK_ID: &str = "docs-update";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskStatus {
@@ -23,6 +43,78 @@ pub enum TaskStatus {
     InProgress,
     NeedsChanges,
     Done,
+    /// Waiting for an answer from the user; the scheduler skips it until `/unblock`.
+    Blocked,
+}
+
+/// Config-driven policy (`[workflow] final_audit`) for how final-audit tasks enter the plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FinalAuditPolicy {
+    Auto,
+    #[default]
+    Manual,
+    Never,
+}
+
+impl FinalAuditPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "manual" => Some(Self::Manual),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    fn master_prompt_note(self) -> &'static str {
+        match self {
+            Self::Auto => {
+                "Final audit policy is auto: a final_audit task is appended automatically; do not add one yourself."
+            }
+            Self::Manual => {
+                "Final audit policy is manual: only add a final_audit task when the user asks for one."
+            }
+            Self::Never => {
+                "Final audit policy is never: final_audit tasks are rejected; do not create them."
+            }
+        }
+    }
+}
+
+/// Config-driven size heuristics (`[workflow] task_split_*`) that trigger the task splitter.
+/// A limit of zero disables that check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskSplitLimits {
+    pub max_details_chars: usize,
+    pub max_subtasks: usize,
+}
+
+impl Default for TaskSplitLimits {
+    fn default() -> Self {
+        Self {
+            max_details_chars: 2000,
+            max_subtasks: 12,
+        }
+    }
+}
+
+/// Config-driven verify gate (`[workflow] verify`): a command run after an implementation
+/// branch's tests, whose failures go back to the implementor. A top-level task's own
+/// `verify` overrides the command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyGate {
+    pub command: Option<String>,
+    /// Failed verify runs sent back to the implementor before the gate gives up.
+    pub max_retries: u8,
+}
+
+impl Default for VerifyGate {
+    fn default() -> Self {
+        Self {
+            command: None,
+            max_retries: 3,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -32,12 +124,38 @@ pub enum WorkerRole {
     TestWriter,
     TestRunner,
     FinalAudit,
+    Plugin,
+}
+
+impl WorkerRole {
+    pub fn file_stem(self) -> &'static str {
+        match self {
+            WorkerRole::Implementor => "implementor",
+            WorkerRole::Auditor => "auditor",
+            WorkerRole::TestWriter => "test_writer",
+            WorkerRole::TestRunner => "test_runner",
+            WorkerRole::FinalAudit => "final_audit",
+            WorkerRole::Plugin => "plugin",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum JobRun {
     AgentPrompt(String),
     DeterministicTestRun,
+    /// Runs an installed plugin for a `plugin` task; `input` is the task's details.
+    PluginStep {
+        plugin: String,
+        /// `None` when no step plugin of that name is installed.
+        program: Option<PathBuf>,
+        input: String,
+        task: PluginTask,
+    },
+    /// Runs a top-level task's verify command through `sh` in the workspace.
+    Verify {
+        command: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -46,18 +164,114 @@ pub struct StartedJob {
     pub role: WorkerRole,
     pub top_task_id: u64,
     pub parent_context_key: Option<String>,
+    /// Model profile pinned to the top-level task with `/task-model`; `None` follows agent
+    /// routing.
+    pub model_profile: Option<String>,
+    /// Rungs up `[codex] escalation_ladder` this implementor pass should run after its audit
+    /// failed repeatedly; 0 keeps `model_profile`.
+    pub escalation: u8,
+    /// The top task's `workdir`, which overrides `[codex.agent_workdirs]` for its workers.
+    pub workdir: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ActiveJobMeta {
     pub role: WorkerRole,
     pub top_task_id: u64,
+    /// The top task's `tasks.json` id, or `internal-<id>` for tasks without one.
+    pub top_task_key: String,
+    pub pass: u8,
+}
+
+/// A job waiting in the worker queue, as listed in the Execution section and by `/jobs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedJobMeta {
+    pub role: WorkerRole,
+    /// `tasks.json` id of the node the job works on, or `internal-<id>`.
+    pub task_key: String,
+    pub top_task_title: String,
+    pub pass: u8,
+}
+
+impl QueuedJobMeta {
+    pub fn label(&self) -> String {
+        format!(
+            "{} task:{} pass {} ({})",
+            self.role.file_stem(),
+            self.task_key,
+            self.pass,
+            self.top_task_title
+        )
+    }
+}
+
+/// Worker time, passes, and usage charged to one top-level task.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TaskUsageStats {
+    /// Wall-clock time of the task's finished worker jobs.
+    pub elapsed: Duration,
+    /// Worker jobs started for the task, across every role.
+    pub passes: u32,
+    /// Implementor passes that ran on an escalated model profile.
+    pub escalations: u32,
+    pub usage: TokenUsage,
+}
+
+/// How the user resolves an implementor pass held by the `/review on` gate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewDecision {
+    Approve,
+    RequestChanges(String),
+    /// The pass's edits were already rolled back in the workspace.
+    Revert,
+}
+
+/// Implementor pass waiting on `/review approve`, `/review changes` or `/review revert`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingReviewMeta {
+    pub top_task_id: u64,
+    pub top_task_title: String,
+    pub pass: u8,
+}
+
+/// How `/skip` and `/mark-done` resolve a task the user takes over from the workers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManualTaskAction {
+    Skip,
+    MarkDone,
+}
+
+impl ManualTaskAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            ManualTaskAction::Skip => "skipped",
+            ManualTaskAction::MarkDone => "marked_done",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManualIntervention {
+    pub task_id: String,
+    pub title: String,
+    pub action: ManualTaskAction,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WorkflowFailureKind {
     Audit,
     Test,
+    /// A failing test run gave a different result when re-run; recorded without
+    /// exhausting any retries.
+    FlakyTest,
+}
+
+/// A top-level task whose worker branches all finished, waiting for its summary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletedTopTask {
+    /// Planner id, as in tasks.json and the worker log directory name.
+    pub key: String,
+    pub title: String,
 }
 
 #[derive(Debug, Clone)]
@@ -78,6 +292,7 @@ enum TaskKind {
     Auditor,
     TestWriter,
     TestRunner,
+    Plugin,
 }
 
 #[derive(Debug, Clone)]
@@ -87,6 +302,18 @@ struct TaskNode {
     title: String,
     details: String,
     docs: Vec<PlannerTaskDocFileEntry>,
+    external_ref: Option<String>,
+    workdir: Option<String>,
+    acceptance_criteria: Vec<String>,
+    /// Installed plugin a `Plugin` node runs.
+    plugin: Option<String>,
+    estimate: Option<Estimate>,
+    /// Command a top-level task runs after its tests pass; overrides `[workflow] verify`.
+    verify: Option<String>,
+    /// Question a blocked top-level task is waiting on the user for.
+    blocked_reason: Option<String>,
+    /// Answers given with `/unblock`, as `question -> answer`.
+    user_answers: Vec<String>,
     status: TaskStatus,
     kind: TaskKind,
     children: Vec<TaskNode>,
@@ -98,10 +325,43 @@ pub struct RightPaneToggleLine {
     pub task_key: String,
 }
 
+/// Lines `start..end` of the right pane render task `task_key` or its subtasks.
+#[derive(Debug, Clone)]
+pub struct RightPaneTaskSpan {
+    pub start: usize,
+    pub end: usize,
+    pub task_key: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct RightPaneBlockView {
     pub lines: Vec<String>,
     pub toggles: Vec<RightPaneToggleLine>,
+    pub task_spans: Vec<RightPaneTaskSpan>,
+}
+
+impl RightPaneBlockView {
+    /// Key of the innermost task drawn on `line_index`.
+    pub fn task_key_at(&self, line_index: usize) -> Option<&str> {
+        self.task_spans
+            .iter()
+            .filter(|span| (span.start..span.end).contains(&line_index))
+            .min_by_key(|span| span.end - span.start)
+            .map(|span| span.task_key.as_str())
+    }
+}
+
+/// The task a right-pane context menu was opened on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskMenuTarget {
+    pub task_id: String,
+    /// Id of the top-level task containing it; logs and model overrides are per top-level task.
+    pub top_task_id: String,
+    pub title: String,
+    pub done: bool,
+    /// Subtask boxes collapse their details; top-level tasks always show them.
+    pub collapsible_details: bool,
+    pub accepts_docs: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -149,6 +409,17 @@ enum WorkerJobKind {
         pass: u8,
         feedback: Option<String>,
     },
+    PluginStep {
+        implementor_id: u64,
+        plugin_step_id: u64,
+        pass: u8,
+    },
+    /// The verify gate after the implementor's tests; `attempt` counts its own retries.
+    Verify {
+        implementor_id: u64,
+        pass: u8,
+        attempt: u8,
+    },
 }
 
 impl WorkerJobKind {
@@ -161,6 +432,42 @@ impl WorkerJobKind {
             WorkerJobKind::TestRunner { .. } => WorkerRole::TestRunner,
             WorkerJobKind::ImplementorTestRunner { .. } => WorkerRole::TestRunner,
             WorkerJobKind::FinalAudit { .. } => WorkerRole::FinalAudit,
+            WorkerJobKind::PluginStep { .. } => WorkerRole::Plugin,
+            WorkerJobKind::Verify { .. } => WorkerRole::TestRunner,
+        }
+    }
+
+    fn pass(&self) -> u8 {
+        match self {
+            WorkerJobKind::Implementor { pass, .. }
+            | WorkerJobKind::Auditor { pass, .. }
+            | WorkerJobKind::TestWriterAuditor { pass, .. }
+            | WorkerJobKind::TestWriter { pass, .. }
+            | WorkerJobKind::TestRunner { pass, .. }
+            | WorkerJobKind::ImplementorTestRunner { pass, .. }
+            | WorkerJobKind::FinalAudit { pass, .. }
+            | WorkerJobKind::PluginStep { pass, .. }
+            | WorkerJobKind::Verify { pass, .. } => *pass,
+        }
+    }
+
+    /// Role and pass for messages, e.g. `Implementor pass 2`.
+    fn pass_label(&self) -> String {
+        format!("{:?} pass {}", self.role(), self.pass())
+    }
+
+    /// The task node this job works on.
+    fn target_node_id(&self) -> u64 {
+        match self {
+            WorkerJobKind::Implementor { implementor_id, .. }
+            | WorkerJobKind::Verify { implementor_id, .. } => *implementor_id,
+            WorkerJobKind::Auditor { auditor_id, .. }
+            | WorkerJobKind::TestWriterAuditor { auditor_id, .. } => *auditor_id,
+            WorkerJobKind::TestWriter { test_writer_id, .. } => *test_writer_id,
+            WorkerJobKind::TestRunner { test_runner_id, .. }
+            | WorkerJobKind::ImplementorTestRunner { test_runner_id, .. } => *test_runner_id,
+            WorkerJobKind::FinalAudit { final_audit_id, .. } => *final_audit_id,
+            WorkerJobKind::PluginStep { plugin_step_id, .. } => *plugin_step_id,
         }
     }
 
@@ -185,6 +492,7 @@ impl WorkerJobKind {
             WorkerJobKind::FinalAudit { final_audit_id, .. } => {
                 Some(format!("final_audit:{final_audit_id}"))
             }
+            WorkerJobKind::PluginStep { .. } | WorkerJobKind::Verify { .. } => None,
         }
     }
 }
@@ -199,6 +507,34 @@ struct WorkerJob {
 struct ActiveJob {
     job: WorkerJob,
     transcript: Vec<String>,
+    /// The deterministic test run failed and then gave a different result on its re-run.
+    flaky: bool,
+    /// Paths git shows the implementor pass changed, checked against protected paths.
+    worktree_changes: Vec<String>,
+    /// Test cases the deterministic runner recognized in its output or report.
+    test_results: Option<TestRunReport>,
+    /// File/line citations of an audit pass, checked against the workspace.
+    audit_citations: Vec<AuditCitation>,
+    /// Started by `/rerun` with a user-edited prompt.
+    edited_prompt: bool,
+}
+
+/// Worker prompt a job was last dispatched with, kept for `/rerun`.
+#[derive(Debug, Clone)]
+struct RecordedPrompt {
+    job: WorkerJob,
+    prompt: String,
+    /// Dispatch order, so the latest prompt within a subtree wins.
+    seq: u64,
+}
+
+/// The prompt `/rerun` opens in its editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastWorkerPrompt {
+    pub task_id: String,
+    /// Role and pass of the job, e.g. `Implementor pass 2`.
+    pub label: String,
+    pub prompt: String,
 }
 
 #[derive(Debug)]
@@ -206,13 +542,56 @@ pub struct Workflow {
     tasks: Vec<TaskNode>,
     queue: VecDeque<WorkerJob>,
     active: Option<ActiveJob>,
+    /// Finished implementor pass held back by the review gate.
+    pending_review: Option<ActiveJob>,
+    review_changes_enabled: bool,
+    protected_paths: ProtectedPaths,
+    /// Implementor and test-writer audit passes before moving on; `/speed` changes it.
+    max_audit_retries: u8,
+    test_writer_audits_enabled: bool,
     rolling_context: VecDeque<String>,
     max_context_entries: usize,
+    /// Rolling context entries pushed out by newer ones this session.
+    rolling_context_dropped: usize,
     next_id: u64,
     execution_enabled: bool,
+    /// Top-level task that `/start task:<id>` limited execution to.
+    execution_scope: Option<u64>,
     tests_mode_enabled: bool,
+    final_audit_policy: FinalAuditPolicy,
+    audit_rubric: Option<String>,
+    /// Doc snapshot texts keyed by the doc's `snapshot` path, inlined into worker prompts.
+    doc_snapshots: HashMap<String, String>,
     recent_failures: Vec<WorkflowFailure>,
+    /// Top-level tasks finished by their workers since the last drain.
+    completed_top_tasks: Vec<CompletedTopTask>,
+    manual_interventions: Vec<ManualIntervention>,
     exhausted_final_audits: HashSet<u64>,
+    /// Escalation steps for an implementor's next pass, keyed by implementor id; set once
+    /// the same audit fails it twice.
+    implementor_escalations: HashMap<u64, u8>,
+    changed_files: BTreeSet<String>,
+    /// Paths left out of `changed_files` (`.gitignore` and `.metaagentignore`).
+    workspace_ignore: WorkspaceIgnore,
+    /// Usage per top-level task, keyed like the task's details toggle.
+    task_usage: HashMap<String, TaskUsageStats>,
+    /// Top-level task worker usage is charged to: the running job's, or the last job's, so
+    /// usage reported just before a worker exits still lands on its task.
+    usage_task_key: Option<String>,
+    /// Last dispatched prompt per task node, this run of Bob only.
+    recorded_prompts: HashMap<u64, RecordedPrompt>,
+    prompt_seq: u64,
+    /// Edited prompt for the job `/rerun` put at the front of the queue.
+    rerun_prompt: Option<String>,
+    /// Plugins discovered at startup; `plugin` tasks run the step-capable ones.
+    plugins: PluginRegistry,
+    verify_gate: VerifyGate,
+    /// Verify runs queued per implementor id, counted against `verify_gate.max_retries`.
+    verify_attempts: HashMap<u64, u8>,
+    /// Set by `--safe-mode`: verify commands are skipped.
+    safe_mode: bool,
+    /// Language of the `System:` messages returned to the chat.
+    locale: Locale,
 }
 
 impl Default for Workflow {
@@ -221,19 +600,51 @@ impl Default for Workflow {
             tasks: Vec::new(),
             queue: VecDeque::new(),
             active: None,
+            pending_review: None,
+            review_changes_enabled: false,
+            protected_paths: ProtectedPaths::default(),
+            max_audit_retries: MAX_AUDIT_RETRIES,
+            test_writer_audits_enabled: true,
             rolling_context: VecDeque::new(),
             max_context_entries: 16,
+            rolling_context_dropped: 0,
             next_id: 1,
             execution_enabled: false,
+            execution_scope: None,
             tests_mode_enabled: true,
+            final_audit_policy: FinalAuditPolicy::default(),
+            audit_rubric: None,
+            doc_snapshots: HashMap::new(),
             recent_failures: Vec::new(),
+            completed_top_tasks: Vec::new(),
+            manual_interventions: Vec::new(),
             exhausted_final_audits: HashSet::new(),
+            implementor_escalations: HashMap::new(),
+            changed_files: BTreeSet::new(),
+            workspace_ignore: WorkspaceIgnore::default(),
+            task_usage: HashMap::new(),
+            usage_task_key: None,
+            recorded_prompts: HashMap::new(),
+            prompt_seq: 0,
+            rerun_prompt: None,
+            plugins: PluginRegistry::default(),
+            verify_gate: VerifyGate::default(),
+            verify_attempts: HashMap::new(),
+            safe_mode: false,
+            locale: Locale::default(),
         }
     }
 }
 
 impl Workflow {
-    pub fn prepare_master_prompt(&self, user_message: &str) -> String {
+    /// Builds the master prompt, leaving out the rolling context or task tree when `/context`
+    /// turned them off.
+    pub fn prepare_master_prompt(&self, user_message: &str, sections: &PromptSections) -> String {
+        let final_audit_note = format!(
+            "{}{}",
+            self.final_audit_policy.master_prompt_note(),
+            self.step_plugins_note()
+        );
         format!(
             "You are the master Codex agent in a TUI.\n\
              Primary responsibilities:\n\
@@ -244,6 +655,7 @@ impl Workflow {
              Execution is currently {}. Only start execution when user explicitly asks to start.\n\
              `/start` always resumes from the last unfinished task.\n\
              Tests mode is currently {}.\n\
+             {}\n\
              Rolling task context:\n{}\n\
              Current task tree:\n{}\n\
              User message:\n{}\n\
@@ -254,12 +666,46 @@ impl Workflow {
                 "disabled"
             },
             if self.tests_mode_enabled { "ON" } else { "OFF" },
-            self.context_block(),
-            self.task_tree_compact(),
+            final_audit_note,
+            if sections.includes(ContextSection::RollingContext) {
+                self.context_block()
+            } else {
+                OMITTED_SECTION.to_string()
+            },
+            if sections.includes(ContextSection::TaskTree) {
+                self.task_tree_compact()
+            } else {
+                OMITTED_SECTION.to_string()
+            },
             user_message
         )
     }
 
+    /// Master prompt lines naming the installed step plugins; empty when there are none.
+    fn step_plugins_note(&self) -> String {
+        let plugins = self
+            .plugins
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.supports(PluginCapability::Step))
+            .map(|plugin| match plugin.manifest.description.trim() {
+                "" => plugin.name().to_string(),
+                description => format!("{} ({description})", plugin.name()),
+            })
+            .collect::<Vec<_>>();
+        if plugins.is_empty() {
+            return String::new();
+        }
+        format!(
+            "\nInstalled step plugins: {}. To run one on an implementation, add a child task of its implementor with kind \"plugin\" and \"plugin\": \"<name>\", after its auditors; the task details are passed to the plugin.",
+            plugins.join(", ")
+        )
+    }
+
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+
     pub fn set_tests_mode_enabled(&mut self, enabled: bool) {
         self.tests_mode_enabled = enabled;
         if ENFORCE_TESTS_MODE_RUNTIME_GATING && !enabled {
@@ -272,13 +718,95 @@ impl Workflow {
         self.tests_mode_enabled
     }
 
+    pub fn set_review_changes_enabled(&mut self, enabled: bool) {
+        self.review_changes_enabled = enabled;
+    }
+
+    pub fn review_changes_enabled(&self) -> bool {
+        self.review_changes_enabled
+    }
+
+    pub fn set_protected_paths(&mut self, protected_paths: ProtectedPaths) {
+        self.protected_paths = protected_paths;
+    }
+
+    pub fn protected_paths(&self) -> &ProtectedPaths {
+        &self.protected_paths
+    }
+
+    pub fn set_verify_gate(&mut self, verify_gate: VerifyGate) {
+        self.verify_gate = verify_gate;
+    }
+
+    pub fn set_safe_mode(&mut self, safe_mode: bool) {
+        self.safe_mode = safe_mode;
+    }
+
+    pub fn set_plugins(&mut self, plugins: PluginRegistry) {
+        self.plugins = plugins;
+    }
+
+    pub fn plugins(&self) -> &PluginRegistry {
+        &self.plugins
+    }
+
+    pub fn set_workspace_ignore(&mut self, workspace_ignore: WorkspaceIgnore) {
+        self.workspace_ignore = workspace_ignore;
+    }
+
+    pub fn set_max_audit_retries(&mut self, retries: u8) {
+        self.max_audit_retries = retries.max(1);
+    }
+
+    pub fn max_audit_retries(&self) -> u8 {
+        self.max_audit_retries
+    }
+
+    /// Whether a test-writer pass is audited before the deterministic test run. Off marks
+    /// the test-writer auditor tasks done unrun.
+    pub fn set_test_writer_audits_enabled(&mut self, enabled: bool) {
+        self.test_writer_audits_enabled = enabled;
+    }
+
+    pub fn set_final_audit_policy(&mut self, policy: FinalAuditPolicy) {
+        self.final_audit_policy = policy;
+    }
+
+    pub fn final_audit_policy(&self) -> FinalAuditPolicy {
+        self.final_audit_policy
+    }
+
+    pub fn set_doc_snapshots(&mut self, snapshots: HashMap<String, String>) {
+        self.doc_snapshots = snapshots;
+    }
+
+    pub fn set_audit_rubric(&mut self, rubric: Option<String>) {
+        self.audit_rubric = rubric
+            .map(|rubric| rubric.trim().to_string())
+            .filter(|rubric| !rubric.is_empty());
+    }
+
+    /// Session-template rubric lines for audit prompts; empty when the session has none.
+    pub(crate) fn audit_rubric_block(&self) -> String {
+        match self.audit_rubric.as_deref() {
+            Some(rubric) => format!("Project audit rubric (apply in addition to the checks above):\n{rubric}\n"),
+            None => String::new(),
+        }
+    }
+
     pub fn rolling_context_entries(&self) -> Vec<String> {
         self.rolling_context.iter().cloned().collect()
     }
 
+    /// Adds an entry the user chose to share with later agents, such as console output.
+    pub fn add_rolling_context(&mut self, entry: String) {
+        self.push_context(entry);
+    }
+
     pub fn replace_rolling_context_entries(&mut self, entries: Vec<String>) {
         self.rolling_context.clear();
         let keep = entries.len().saturating_sub(self.max_context_entries);
+        self.rolling_context_dropped += keep;
         for entry in entries.into_iter().skip(keep) {
             self.rolling_context.push_back(entry);
         }
@@ -307,6 +835,14 @@ impl Workflow {
                 status: task_status_to_file(node.status),
                 parent_id: parent_id.map(ToString::to_string),
                 order: Some(order),
+                external_ref: node.external_ref.clone(),
+                workdir: node.workdir.clone(),
+                acceptance_criteria: node.acceptance_criteria.clone(),
+                plugin: node.plugin.clone(),
+                estimate: node.estimate.map(|estimate| estimate.to_string()),
+                verify: node.verify.clone(),
+                blocked_reason: node.blocked_reason.clone(),
+                user_answers: node.user_answers.clone(),
             });
             for (idx, child) in node.children.iter().enumerate() {
                 collect(child, Some(&node_id), idx as u32, out);
@@ -320,12 +856,62 @@ impl Workflow {
         out
     }
 
+    pub fn changed_files(&self) -> &BTreeSet<String> {
+        &self.changed_files
+    }
+
+    pub fn clear_changed_files(&mut self) {
+        self.changed_files.clear();
+    }
+
+    fn record_changed_files(&mut self, changed_files_summary: &str) {
+        let workspace_ignore = &self.workspace_ignore;
+        self.changed_files.extend(
+            parse_changed_file_paths(changed_files_summary)
+                .into_iter()
+                .filter(|path| !workspace_ignore.is_ignored(path, false)),
+        );
+    }
+
     pub fn reset_execution_runtime(&mut self) {
         self.execution_enabled = false;
+        self.execution_scope = None;
         self.queue.clear();
         self.active = None;
+        self.pending_review = None;
         self.recent_failures.clear();
+        self.completed_top_tasks.clear();
         self.exhausted_final_audits.clear();
+        self.implementor_escalations.clear();
+    }
+
+    /// Stops execution for a shutdown mid-run. The running or review-held job's task and any
+    /// in-progress top-level task go back to pending so the next `/start` reruns the job from
+    /// scratch instead of skipping ahead. Describes the interrupted job, if there was one.
+    pub fn interrupt_execution(&mut self) -> Option<String> {
+        let interrupted = self
+            .active
+            .take()
+            .or_else(|| self.pending_review.take())
+            .map(|active| active.job);
+        if let Some(job) = &interrupted {
+            self.set_status(job.kind.target_node_id(), TaskStatus::Pending);
+        }
+        for top in &mut self.tasks {
+            if top.status == TaskStatus::InProgress {
+                top.status = TaskStatus::Pending;
+            }
+        }
+        self.reset_execution_runtime();
+        interrupted.map(|job| {
+            format!(
+                "{:?} pass {} for task #{} ({})",
+                job.kind.role(),
+                job.kind.pass(),
+                job.top_task_id,
+                self.task_title(job.top_task_id)
+            )
+        })
     }
 
     pub fn sync_planner_tasks_from_file(
@@ -353,6 +939,14 @@ impl Workflow {
                     entry.id
                 ));
             }
+            if self.final_audit_policy == FinalAuditPolicy::Never
+                && entry.kind == PlannerTaskKindFile::FinalAudit
+            {
+                return Err(format!(
+                    "Planner task {} has kind \"final_audit\" but final audits are disabled (final_audit = \"never\")",
+                    entry.id
+                ));
+            }
             if id_to_num.contains_key(&entry.id) {
                 return Err(format!("Duplicate planner task id {}", entry.id));
             }
@@ -416,6 +1010,93 @@ impl Workflow {
                             entry.id
                         ));
                     }
+                    let workdir = entry
+                        .workdir
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|dir| !dir.is_empty());
+                    if let Some(dir) = workdir
+                        && !is_workspace_subdir(dir)
+                    {
+                        return Err(format!(
+                            "Planner task {} workdir \"{dir}\" must be a relative path inside the workspace",
+                            entry.id
+                        ));
+                    }
+                    let workdir = workdir.map(str::to_string);
+                    let acceptance_criteria = entry
+                        .acceptance_criteria
+                        .iter()
+                        .map(|criterion| criterion.trim())
+                        .filter(|criterion| !criterion.is_empty())
+                        .map(str::to_string)
+                        .collect::<Vec<_>>();
+                    if !acceptance_criteria.is_empty() && kind != TaskKind::Top {
+                        return Err(format!(
+                            "Planner task {} has acceptance_criteria; only top-level tasks of kind \"task\" may have them",
+                            entry.id
+                        ));
+                    }
+                    let plugin = entry
+                        .plugin
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(str::to_string);
+                    match (kind, &plugin) {
+                        (TaskKind::Plugin, None) => {
+                            return Err(format!(
+                                "Planner task {} has kind \"plugin\" but names no plugin",
+                                entry.id
+                            ));
+                        }
+                        (TaskKind::Plugin, Some(_)) | (_, None) => {}
+                        (_, Some(_)) => {
+                            return Err(format!(
+                                "Planner task {} names a plugin; only tasks of kind \"plugin\" may",
+                                entry.id
+                            ));
+                        }
+                    }
+                    let estimate = entry
+                        .estimate
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|estimate| !estimate.is_empty())
+                        .map(Estimate::parse)
+                        .transpose()
+                        .map_err(|err| format!("Planner task {} has an invalid {err}", entry.id))?;
+                    if estimate.is_some() && kind != TaskKind::Top {
+                        return Err(format!(
+                            "Planner task {} has an estimate; only top-level tasks of kind \"task\" may have one",
+                            entry.id
+                        ));
+                    }
+                    let verify = entry
+                        .verify
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|command| !command.is_empty())
+                        .map(str::to_string);
+                    if verify.is_some() && kind != TaskKind::Top {
+                        return Err(format!(
+                            "Planner task {} has a verify command; only top-level tasks of kind \"task\" may have one",
+                            entry.id
+                        ));
+                    }
+                    let blocked = entry.status == PlannerTaskStatusFile::Blocked;
+                    let blocked_reason = entry
+                        .blocked_reason
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|reason| blocked && !reason.is_empty())
+                        .map(str::to_string);
+                    if blocked && (kind != TaskKind::Top || blocked_reason.is_none()) {
+                        return Err(format!(
+                            "Planner task {} is blocked; only top-level tasks of kind \"task\" may be, with a blocked_reason",
+                            entry.id
+                        ));
+                    }
                     out.push(TaskNode {
                         id: *id_to_num
                             .get(&entry.id)
@@ -424,14 +1105,28 @@ impl Workflow {
                         title: entry.title.trim().to_string(),
                         details: entry.details.trim().to_string(),
                         docs: entry.docs.clone(),
+                        external_ref: entry
+                            .external_ref
+                            .as_deref()
+                            .map(str::trim)
+                            .filter(|reference| !reference.is_empty())
+                            .map(str::to_string),
+                        workdir,
+                        acceptance_criteria,
+                        plugin,
                         status: match entry.status {
                             PlannerTaskStatusFile::Pending => TaskStatus::Pending,
                             PlannerTaskStatusFile::InProgress => TaskStatus::InProgress,
                             PlannerTaskStatusFile::NeedsChanges => TaskStatus::NeedsChanges,
                             PlannerTaskStatusFile::Done => TaskStatus::Done,
+                            PlannerTaskStatusFile::Blocked => TaskStatus::Blocked,
                         },
                         kind,
                         children: child_nodes,
+                        estimate,
+                        verify,
+                        blocked_reason,
+                        user_answers: entry.user_answers.clone(),
                     });
                 }
             }
@@ -456,8 +1151,11 @@ impl Workflow {
         self.tasks = root_nodes;
         self.queue.clear();
         self.active = None;
+        self.pending_review = None;
         self.recent_failures.clear();
+        self.completed_top_tasks.clear();
         self.exhausted_final_audits.clear();
+        self.implementor_escalations.clear();
         if ENFORCE_TESTS_MODE_RUNTIME_GATING && !self.tests_mode_enabled {
             self.mark_tests_disabled_state();
         }
@@ -465,22 +1163,29 @@ impl Workflow {
     }
 
     pub fn start_execution(&mut self) -> Vec<String> {
+        // A plain start widens a scoped run back to the whole plan.
+        self.execution_scope = None;
         if self.execution_enabled {
             if self.active.is_some() {
-                return vec![
-                    "System: Execution is already running; continuing current task.".to_string(),
-                ];
+                return vec![i18n::system(
+                    self.locale,
+                    Text::ExecutionAlreadyRunning,
+                    &[],
+                )];
             }
             let queued = self.enqueue_ready_top_tasks();
             if queued > 0 {
-                return vec![format!(
-                    "System: Resumed from last unfinished task(s). Queued {} task job(s).",
-                    queued
+                return vec![i18n::system(
+                    self.locale,
+                    Text::ResumedUnfinishedTasks,
+                    &[("queued", &queued)],
                 )];
             }
-            return vec![
-                "System: Execution is already enabled. No unfinished tasks to resume.".to_string(),
-            ];
+            return vec![i18n::system(
+                self.locale,
+                Text::ExecutionAlreadyEnabled,
+                &[],
+            )];
         }
 
         self.execution_enabled = true;
@@ -489,23 +1194,79 @@ impl Workflow {
             self.mark_tests_disabled_state();
         }
         let queued = self.enqueue_ready_top_tasks();
-        vec![format!(
-            "System: Execution enabled. Queued {} task job(s).",
-            queued
+        vec![i18n::system(
+            self.locale,
+            Text::ExecutionEnabled,
+            &[("queued", &queued)],
         )]
     }
 
-    #[cfg(test)]
+    /// Enables execution for the top-level task containing planner task `task_id` only; other
+    /// pending tasks and the final audit are left untouched. Execution returns to planning mode
+    /// once that task is done.
+    pub fn start_execution_scoped(&mut self, task_id: &str) -> Result<Vec<String>, String> {
+        if self.active.is_some() || !self.queue.is_empty() {
+            return Err(
+                "Execution is already running; wait for the current job before scoping to a task."
+                    .to_string(),
+            );
+        }
+        let path = find_path_by_external_id(&self.tasks, task_id)
+            .ok_or_else(|| format!("Planner task {task_id} not found"))?;
+        let top = path[0];
+        if top.kind == TaskKind::FinalAudit {
+            return Err("The final audit runs after every task; use /start instead.".to_string());
+        }
+        if top.status == TaskStatus::Done {
+            return Err(format!("Task \"{}\" is already done.", top.title));
+        }
+        let (top_id, top_title) = (top.id, top.title.clone());
+        let via = if path.len() > 1 {
+            i18n::fill(
+                self.locale,
+                Text::ScopedRunContains,
+                &[("task_id", &task_id)],
+            )
+        } else {
+            String::new()
+        };
+
+        self.execution_enabled = true;
+        self.execution_scope = Some(top_id);
+        if ENFORCE_TESTS_MODE_RUNTIME_GATING && !self.tests_mode_enabled {
+            self.drop_queued_test_jobs_when_disabled();
+            self.mark_tests_disabled_state();
+        }
+        let queued = self.enqueue_ready_top_tasks();
+        Ok(vec![i18n::system(
+            self.locale,
+            Text::ExecutionEnabledScoped,
+            &[
+                ("top_title", &top_title),
+                ("via", &via),
+                ("queued", &queued),
+            ],
+        )])
+    }
+
     pub fn execution_enabled(&self) -> bool {
         self.execution_enabled
     }
 
+    /// Planner id of the top-level task a scoped run is limited to.
+    pub fn execution_scope_task_id(&self) -> Option<String> {
+        self.execution_scope
+            .and_then(|id| find_node(&self.tasks, id))
+            .and_then(|node| node.external_id.clone())
+    }
+
     pub fn execution_busy(&self) -> bool {
-        self.execution_enabled && (self.active.is_some() || !self.queue.is_empty())
+        self.execution_enabled
+            && (self.active.is_some() || self.pending_review.is_some() || !self.queue.is_empty())
     }
 
     pub fn start_next_job(&mut self) -> Option<StartedJob> {
-        if !self.execution_enabled || self.active.is_some() {
+        if !self.execution_enabled || self.active.is_some() || self.pending_review.is_some() {
             return None;
         }
         let job = loop {
@@ -516,31 +1277,396 @@ impl Workflow {
             {
                 break next;
             }
+            self.rerun_prompt = None;
             self.mark_skipped_test_job_done(&next);
         };
         self.mark_job_started(&job);
+        self.usage_task_key = find_node(&self.tasks, job.top_task_id).map(task_detail_key);
+        if let Some(key) = &self.usage_task_key {
+            self.task_usage.entry(key.clone()).or_default().passes += 1;
+        }
         let role = job.kind.role();
-        let run = self.run_for_job(&job);
+        let edited_prompt = self.rerun_prompt.is_some();
+        let run = match self.rerun_prompt.take() {
+            Some(prompt) => JobRun::AgentPrompt(prompt),
+            None => self.run_for_job(&job),
+        };
+        let escalation = match &job.kind {
+            WorkerJobKind::Implementor { implementor_id, .. } => self
+                .implementor_escalations
+                .get(implementor_id)
+                .copied()
+                .unwrap_or(0),
+            _ => 0,
+        };
         let started = StartedJob {
             run,
             role,
             top_task_id: job.top_task_id,
             parent_context_key: job.kind.parent_context_key(),
+            model_profile: None,
+            escalation,
+            workdir: find_node(&self.tasks, job.top_task_id).and_then(|node| node.workdir.clone()),
         };
         self.active = Some(ActiveJob {
             job,
             transcript: Vec::new(),
+            flaky: false,
+            worktree_changes: Vec::new(),
+            test_results: None,
+            audit_citations: Vec::new(),
+            edited_prompt,
         });
         Some(started)
     }
 
+    /// Keeps the prompt the active job is dispatched with, for `/rerun`.
+    pub fn record_active_prompt(&mut self, prompt: &str) {
+        let Some(active) = self.active.as_ref() else {
+            return;
+        };
+        self.prompt_seq += 1;
+        self.recorded_prompts.insert(
+            active.job.kind.target_node_id(),
+            RecordedPrompt {
+                job: active.job.clone(),
+                prompt: prompt.to_string(),
+                seq: self.prompt_seq,
+            },
+        );
+    }
+
+    /// The prompt last dispatched for planner task `task_id` or any task below it.
+    pub fn last_prompt_for(&self, task_id: &str) -> Result<LastWorkerPrompt, String> {
+        let recorded = self.last_recorded_prompt(task_id)?;
+        Ok(LastWorkerPrompt {
+            task_id: task_id.to_string(),
+            label: recorded.job.kind.pass_label(),
+            prompt: recorded.prompt.clone(),
+        })
+    }
+
+    fn last_recorded_prompt(&self, task_id: &str) -> Result<&RecordedPrompt, String> {
+        let path = find_path_by_external_id(&self.tasks, task_id)
+            .ok_or_else(|| format!("Planner task {task_id} not found"))?;
+        let mut ids = Vec::new();
+        collect_subtree_ids(path[path.len() - 1], &mut ids);
+        ids.iter()
+            .filter_map(|id| self.recorded_prompts.get(id))
+            .max_by_key(|recorded| recorded.seq)
+            .ok_or_else(|| {
+                format!("No worker prompt has been sent for task {task_id} since Bob started.")
+            })
+    }
+
+    /// Queues the job behind planner task `task_id`'s last prompt again with `prompt`, in a
+    /// scoped run of its top-level task. Pass counters and task statuses are kept.
+    pub fn rerun_with_prompt(
+        &mut self,
+        task_id: &str,
+        prompt: String,
+    ) -> Result<Vec<String>, String> {
+        if self.active.is_some() || self.pending_review.is_some() || !self.queue.is_empty() {
+            return Err(
+                "Execution is already running; wait for the current job before re-running a task."
+                    .to_string(),
+            );
+        }
+        let job = self.last_recorded_prompt(task_id)?.job.clone();
+        if prompt.trim().is_empty() {
+            return Err("The edited prompt is empty; nothing was re-run.".to_string());
+        }
+        if ENFORCE_TESTS_MODE_RUNTIME_GATING
+            && !self.tests_mode_enabled
+            && job.kind.is_test_flow_job()
+        {
+            return Err("Tests mode is OFF; turn it on to re-run a test job.".to_string());
+        }
+        let label = job.kind.pass_label();
+        let title = self.task_title(job.top_task_id);
+        self.push_context(format!(
+            "User re-ran the {label} of \"{title}\" ({task_id}) with an edited prompt."
+        ));
+        self.execution_enabled = true;
+        self.execution_scope = Some(job.top_task_id);
+        self.queue.push_front(job);
+        self.rerun_prompt = Some(prompt);
+        Ok(vec![i18n::system(
+            self.locale,
+            Text::RerunningEditedPrompt,
+            &[("label", &label), ("title", &title), ("task_id", &task_id)],
+        )])
+    }
+
+    /// Task text used to pick workspace file excerpts for the active implementor or
+    /// test-writer job; other roles get none.
+    pub fn active_code_context_query(&self) -> Option<String> {
+        let active = self.active.as_ref()?;
+        if active.edited_prompt {
+            // The edited prompt already carries the excerpts it was first sent with.
+            return None;
+        }
+        let job = &active.job;
+        let node_id = match &job.kind {
+            WorkerJobKind::Implementor { implementor_id, .. } => *implementor_id,
+            WorkerJobKind::TestWriter { test_writer_id, .. } => *test_writer_id,
+            _ => return None,
+        };
+        let mut parts = Vec::new();
+        for id in [job.top_task_id, node_id] {
+            if let Some(node) = find_node(&self.tasks, id) {
+                parts.push(node.title.clone());
+                parts.push(node.details.clone());
+            }
+        }
+        Some(parts.join("\n"))
+    }
+
     pub fn active_job_meta(&self) -> Option<ActiveJobMeta> {
         self.active.as_ref().map(|active| ActiveJobMeta {
             role: active.job.kind.role(),
             top_task_id: active.job.top_task_id,
+            top_task_key: find_node(&self.tasks, active.job.top_task_id)
+                .and_then(|node| node.external_id.clone())
+                .unwrap_or_else(|| format!("internal-{}", active.job.top_task_id)),
+            pass: active.job.kind.pass(),
         })
     }
 
+    /// Sizes of the measurable parts of the active job's prompt: the task docs prefix, the
+    /// rolling context, and the feedback or report it was handed.
+    pub fn active_prompt_sections(&self) -> Vec<PromptSection> {
+        let Some(active) = self.active.as_ref() else {
+            return Vec::new();
+        };
+        let kind = &active.job.kind;
+        let mut sections = Vec::new();
+
+        let docs = self.task_docs_prefix(kind.target_node_id());
+        if !docs.is_empty() {
+            let mut section = PromptSection::new("docs", &docs);
+            let cut = find_node(&self.tasks, kind.target_node_id())
+                .into_iter()
+                .flat_map(|node| node.docs.iter())
+                .filter_map(|doc| doc.snapshot.as_deref())
+                .filter(|path| {
+                    self.doc_snapshots
+                        .get(*path)
+                        .is_some_and(|text| is_truncated_snapshot(text))
+                })
+                .collect::<Vec<_>>();
+            if !cut.is_empty() {
+                section = section.truncated(format!(
+                    "{} cut at {MAX_SNAPSHOT_PROMPT_CHARS} characters",
+                    cut.join(", ")
+                ));
+            }
+            sections.push(section);
+        }
+
+        let mut context = PromptSection::new("context", &self.context_block());
+        if self.rolling_context_dropped > 0 {
+            context = context.truncated(format!(
+                "{} older entries dropped (keeps the last {})",
+                self.rolling_context_dropped, self.max_context_entries
+            ));
+        }
+        sections.push(context);
+
+        let (name, handoff) = match kind {
+            WorkerJobKind::Implementor { feedback, .. }
+            | WorkerJobKind::TestWriter { feedback, .. }
+            | WorkerJobKind::FinalAudit { feedback, .. } => ("feedback", feedback),
+            WorkerJobKind::Auditor {
+                implementation_report,
+                ..
+            } => ("report", implementation_report),
+            WorkerJobKind::TestWriterAuditor { test_report, .. } => ("report", test_report),
+            WorkerJobKind::TestRunner { .. }
+            | WorkerJobKind::ImplementorTestRunner { .. }
+            | WorkerJobKind::PluginStep { .. }
+            | WorkerJobKind::Verify { .. } => {
+                return sections;
+            }
+        };
+        if let Some(text) = handoff {
+            sections.push(PromptSection::new(name, text));
+        }
+        sections
+    }
+
+    /// Title of the top-level task the active job belongs to.
+    pub fn active_job_title(&self) -> Option<String> {
+        let active = self.active.as_ref()?;
+        find_node(&self.tasks, active.job.top_task_id).map(|node| node.title.clone())
+    }
+
+    pub fn queued_job_count(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Queued jobs in the order they will start.
+    pub fn queued_jobs(&self) -> Vec<QueuedJobMeta> {
+        self.queue
+            .iter()
+            .map(|job| QueuedJobMeta {
+                role: job.kind.role(),
+                task_key: find_node(&self.tasks, job.kind.target_node_id())
+                    .and_then(|node| node.external_id.clone())
+                    .unwrap_or_else(|| format!("internal-{}", job.kind.target_node_id())),
+                top_task_title: self.task_title(job.top_task_id),
+                pass: job.kind.pass(),
+            })
+            .collect()
+    }
+
+    /// Moves the queued job at `from` to `to` (0-based). Final audits stay behind every
+    /// other job, so they cannot be moved and nothing can be moved behind them.
+    pub fn move_queued_job(&mut self, from: usize, to: usize) -> Result<QueuedJobMeta, String> {
+        let len = self.queue.len();
+        if from >= len || to >= len {
+            return Err(format!(
+                "Queue position out of range; there are {len} queued job(s)."
+            ));
+        }
+        let first_final = self
+            .queue
+            .iter()
+            .position(|job| matches!(job.kind, WorkerJobKind::FinalAudit { .. }))
+            .unwrap_or(len);
+        if from >= first_final || to >= first_final {
+            return Err("Final audit jobs stay at the end of the queue.".to_string());
+        }
+        let meta = self.queued_jobs().swap_remove(from);
+        if let Some(job) = self.queue.remove(from) {
+            self.queue.insert(to, job);
+        }
+        Ok(meta)
+    }
+
+    /// Removes the queued job at `index` (0-based) without running it. Its task is left
+    /// unfinished; `/retry` runs it again once execution is idle.
+    pub fn drop_queued_job(&mut self, index: usize) -> Result<QueuedJobMeta, String> {
+        let len = self.queue.len();
+        if index >= len {
+            return Err(format!(
+                "Queue position out of range; there are {len} queued job(s)."
+            ));
+        }
+        let meta = self.queued_jobs().swap_remove(index);
+        self.queue.remove(index);
+        Ok(meta)
+    }
+
+    /// Charges worker usage to the top-level task of the running or last job; returns whether
+    /// there was one.
+    pub fn record_worker_usage(&mut self, usage: TokenUsage) -> bool {
+        let Some(key) = &self.usage_task_key else {
+            return false;
+        };
+        self.task_usage
+            .entry(key.clone())
+            .or_default()
+            .usage
+            .add(usage);
+        true
+    }
+
+    /// Notes in rolling context and the task's usage that the active implementor pass runs on
+    /// `to` instead of `from`. Returns the chat message describing it.
+    pub fn record_model_escalation(&mut self, from: &str, to: &str) -> Option<String> {
+        let meta = self.active_job_meta()?;
+        let entry = format!(
+            "Task #{} implementor pass {} escalated from model profile {from} to {to} after repeated audit failures.",
+            meta.top_task_id, meta.pass
+        );
+        self.push_context(entry.clone());
+        if let Some(key) = &self.usage_task_key {
+            self.task_usage.entry(key.clone()).or_default().escalations += 1;
+        }
+        Some(i18n::system(
+            self.locale,
+            Text::ModelEscalated,
+            &[
+                ("task", &meta.top_task_id),
+                ("pass", &meta.pass),
+                ("from", &from),
+                ("to", &to),
+            ],
+        ))
+    }
+
+    /// Adds the wall-clock time of a finished or interrupted worker job to its top-level task.
+    pub fn record_worker_elapsed(&mut self, elapsed: Duration) {
+        if let Some(key) = &self.usage_task_key {
+            self.task_usage.entry(key.clone()).or_default().elapsed += elapsed;
+        }
+    }
+
+    pub fn task_usage_stats(&self, task_key: &str) -> Option<TaskUsageStats> {
+        self.task_usage.get(task_key).copied()
+    }
+
+    pub fn clear_task_usage(&mut self) {
+        self.task_usage.clear();
+        self.usage_task_key = None;
+    }
+
+    /// Marks the running test-runner job's failure as flaky before it finishes.
+    pub fn mark_active_test_run_flaky(&mut self) {
+        if let Some(active) = self.active.as_mut()
+            && matches!(
+                active.job.kind,
+                WorkerJobKind::TestRunner { .. } | WorkerJobKind::ImplementorTestRunner { .. }
+            )
+        {
+            active.flaky = true;
+        }
+    }
+
+    /// Attaches the running test-runner job's structured results before it finishes.
+    pub fn record_active_test_results(&mut self, report: TestRunReport) {
+        if let Some(active) = self.active.as_mut() {
+            active.test_results = Some(report);
+        }
+    }
+
+    /// Records what the running implementor pass changed in the workspace, as seen by git.
+    pub fn record_active_worktree_changes(&mut self, paths: Vec<String>) {
+        if let Some(active) = self.active.as_mut() {
+            active.worktree_changes = paths;
+        }
+    }
+
+    /// Files the running job reported under FILES_CHANGED, plus those git saw it change when
+    /// the worktree was checked; sorted and without duplicates.
+    pub fn active_changed_files(&self) -> Vec<String> {
+        let Some(active) = self.active.as_ref() else {
+            return Vec::new();
+        };
+        parse_changed_file_paths(&extract_changed_files_summary(&active.transcript))
+            .into_iter()
+            .chain(active.worktree_changes.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Records the citations the running audit pass gave for its findings.
+    pub fn record_active_audit_citations(&mut self, citations: Vec<AuditCitation>) {
+        if let Some(active) = self.active.as_mut() {
+            active.audit_citations = citations;
+        }
+    }
+
+    /// Output of the running job so far.
+    pub fn active_transcript(&self) -> &[String] {
+        self.active
+            .as_ref()
+            .map(|active| active.transcript.as_slice())
+            .unwrap_or_default()
+    }
+
     pub fn append_active_output(&mut self, line: String) {
         if let Some(active) = self.active.as_mut() {
             active.transcript.push(line);
@@ -551,9 +1677,64 @@ impl Workflow {
         let Some(active) = self.active.take() else {
             return Vec::new();
         };
+        if self.review_changes_enabled
+            && success
+            && matches!(active.job.kind, WorkerJobKind::Implementor { .. })
+        {
+            let message = i18n::system(
+                self.locale,
+                Text::PassReadyForReview,
+                &[
+                    ("task", &active.job.top_task_id),
+                    ("pass", &active.job.kind.pass()),
+                ],
+            );
+            self.pending_review = Some(active);
+            return vec![message];
+        }
+        self.complete_job(active, success, code)
+    }
+
+    pub fn pending_review(&self) -> Option<PendingReviewMeta> {
+        self.pending_review
+            .as_ref()
+            .map(|pending| PendingReviewMeta {
+                top_task_id: pending.job.top_task_id,
+                top_task_title: self.task_title(pending.job.top_task_id),
+                pass: pending.job.kind.pass(),
+            })
+    }
+
+    /// Applies the user's verdict on the held implementor pass. Approving hands it to the
+    /// auditor as usual; requesting changes or reverting queues another implementor pass
+    /// with the user's feedback in place of audit feedback.
+    pub fn resolve_review(&mut self, decision: ReviewDecision) -> Result<Vec<String>, String> {
+        let pending = self
+            .pending_review
+            .take()
+            .ok_or_else(|| "No implementor pass is awaiting review.".to_string())?;
+        let feedback = match decision {
+            ReviewDecision::Approve => return Ok(self.complete_job(pending, true, 0)),
+            ReviewDecision::RequestChanges(text) => {
+                format!("User review requested changes:\n{}", text.trim())
+            }
+            ReviewDecision::Revert => "The user reviewed your previous pass and reverted all of its changes. Start again from the current workspace state and take a different approach.".to_string(),
+        };
+        let mut messages = Vec::new();
+        implementor::on_review_rejected(self, pending.job, feedback, &mut messages);
+        if self.execution_enabled {
+            let _ = self.enqueue_ready_top_tasks();
+            self.finish_scoped_run_if_done(&mut messages);
+        }
+        Ok(messages)
+    }
 
+    fn complete_job(&mut self, active: ActiveJob, success: bool, code: i32) -> Vec<String> {
         let job = active.job;
         let transcript = active.transcript;
+        let worktree_changes = active.worktree_changes;
+        let test_results = active.test_results;
+        let citations = active.audit_citations;
         let mut messages = Vec::new();
 
         match job.kind {
@@ -572,6 +1753,7 @@ impl Workflow {
                     resume_auditor_id,
                     resume_audit_pass,
                     &transcript,
+                    &worktree_changes,
                     success,
                     code,
                     &mut messages,
@@ -594,6 +1776,7 @@ impl Workflow {
                     implementation_report,
                     changed_files_summary,
                     &transcript,
+                    &citations,
                     success,
                     code,
                     &mut messages,
@@ -635,6 +1818,7 @@ impl Workflow {
                     pass,
                     test_report,
                     &transcript,
+                    &citations,
                     success,
                     code,
                     &mut messages,
@@ -652,8 +1836,10 @@ impl Workflow {
                     test_runner_id,
                     pass,
                     &transcript,
+                    test_results.as_ref(),
                     success,
                     code,
+                    active.flaky,
                     &mut messages,
                 );
             }
@@ -669,6 +1855,42 @@ impl Workflow {
                     test_runner_id,
                     pass,
                     &transcript,
+                    test_results.as_ref(),
+                    success,
+                    code,
+                    active.flaky,
+                    &mut messages,
+                );
+            }
+            WorkerJobKind::PluginStep {
+                implementor_id,
+                plugin_step_id,
+                pass,
+            } => {
+                plugin_step::on_completion(
+                    self,
+                    job.top_task_id,
+                    implementor_id,
+                    plugin_step_id,
+                    pass,
+                    &transcript,
+                    success,
+                    code,
+                    &mut messages,
+                );
+            }
+            WorkerJobKind::Verify {
+                implementor_id,
+                pass,
+                attempt,
+            } => {
+                verify_step::on_completion(
+                    self,
+                    job.top_task_id,
+                    implementor_id,
+                    pass,
+                    attempt,
+                    &transcript,
                     success,
                     code,
                     &mut messages,
@@ -687,13 +1909,14 @@ impl Workflow {
                 if explicit_pass {
                     self.exhausted_final_audits.remove(&final_audit_id);
                     self.set_status(final_audit_id, TaskStatus::Done);
-                    messages.push(format!(
-                        "System: Final audit task #{} completed on pass {}.",
-                        job.top_task_id, pass
+                    messages.push(i18n::system(
+                        self.locale,
+                        Text::FinalAuditPassed,
+                        &[("task", &job.top_task_id), ("pass", &pass)],
                     ));
                 } else {
                     self.set_status(final_audit_id, TaskStatus::NeedsChanges);
-                    let feedback = audit_feedback(&transcript, code, success);
+                    let feedback = audit_feedback(&transcript, &citations, code, success);
                     if pass >= MAX_FINAL_AUDIT_RETRIES {
                         self.exhausted_final_audits.insert(final_audit_id);
                         self.recent_failures.push(WorkflowFailure {
@@ -707,9 +1930,14 @@ impl Workflow {
                                 pass
                             ),
                         });
-                        messages.push(format!(
-                            "System: Final audit task #{} still failed at pass {}. Max retries ({}) reached; no further final-audit retries queued.",
-                            job.top_task_id, pass, MAX_FINAL_AUDIT_RETRIES
+                        messages.push(i18n::system(
+                            self.locale,
+                            Text::FinalAuditRetriesExhausted,
+                            &[
+                                ("task", &job.top_task_id),
+                                ("pass", &pass),
+                                ("max", &MAX_FINAL_AUDIT_RETRIES),
+                            ],
                         ));
                     } else {
                         self.queue.push_back(WorkerJob {
@@ -721,14 +1949,16 @@ impl Workflow {
                             },
                         });
                         messages.push(if success {
-                            format!(
-                                "System: Final audit task #{} did not explicitly pass; retry queued.",
-                                job.top_task_id
+                            i18n::system(
+                                self.locale,
+                                Text::FinalAuditNotPassedRetry,
+                                &[("task", &job.top_task_id)],
                             )
                         } else {
-                            format!(
-                                "System: Final audit task #{} failed (code {}); retry queued.",
-                                job.top_task_id, code
+                            i18n::system(
+                                self.locale,
+                                Text::FinalAuditFailedRetry,
+                                &[("task", &job.top_task_id), ("code", &code)],
                             )
                         });
                     }
@@ -736,14 +1966,300 @@ impl Workflow {
             }
         }
 
-        if self.execution_enabled {
-            let _ = self.enqueue_ready_top_tasks();
+        if self.execution_enabled {
+            let _ = self.enqueue_ready_top_tasks();
+            self.finish_scoped_run_if_done(&mut messages);
+        }
+        messages
+    }
+
+    /// Execution-section lines listing the queued jobs by their `/jobs` position.
+    fn queue_lines(&self) -> Vec<String> {
+        let jobs = self.queued_jobs();
+        if jobs.is_empty() {
+            return vec!["- queue: empty".to_string()];
+        }
+        let mut lines = vec![format!(
+            "- queue: {} job(s); /jobs move <from> <to> or /jobs drop <n>",
+            jobs.len()
+        )];
+        lines.extend(
+            jobs.iter()
+                .enumerate()
+                .map(|(idx, job)| format!("  {}. {}", idx + 1, job.label())),
+        );
+        lines
+    }
+
+    fn execution_status_label(&self) -> String {
+        match (self.execution_enabled, self.execution_scope_task_id()) {
+            (true, Some(task_id)) => format!("running (task:{task_id} only)"),
+            (true, None) => "running".to_string(),
+            (false, _) => "planning".to_string(),
+        }
+    }
+
+    fn finish_scoped_run_if_done(&mut self, messages: &mut Vec<String>) {
+        let Some(scope) = self.execution_scope else {
+            return;
+        };
+        if self.active.is_some() || !self.queue.is_empty() {
+            return;
+        }
+        let Some(top) = find_node(&self.tasks, scope) else {
+            return;
+        };
+        if top.status != TaskStatus::Done {
+            return;
+        }
+        messages.push(i18n::system(
+            self.locale,
+            Text::ScopedRunDone,
+            &[("title", &top.title)],
+        ));
+        self.execution_enabled = false;
+        self.execution_scope = None;
+    }
+
+    pub fn drain_recent_failures(&mut self) -> Vec<WorkflowFailure> {
+        std::mem::take(&mut self.recent_failures)
+    }
+
+    pub fn drain_completed_top_tasks(&mut self) -> Vec<CompletedTopTask> {
+        std::mem::take(&mut self.completed_top_tasks)
+    }
+
+    /// Marks planner task `task_id` and its whole subtree done on the user's behalf, drops its
+    /// queued jobs and lets execution move on to the next task.
+    pub fn resolve_task_manually(
+        &mut self,
+        task_id: &str,
+        action: ManualTaskAction,
+    ) -> Result<Vec<String>, String> {
+        let path = find_path_by_external_id(&self.tasks, task_id)
+            .ok_or_else(|| format!("Planner task {task_id} not found"))?;
+        let node = path[path.len() - 1];
+        if Self::subtree_done(node) {
+            return Err(format!("Task \"{}\" is already done.", node.title));
+        }
+        let (top_id, node_id, title) = (path[0].id, node.id, node.title.clone());
+        let mut subtree = Vec::new();
+        collect_subtree_ids(node, &mut subtree);
+        if let Some(active) = &self.active
+            && subtree.contains(&active.job.kind.target_node_id())
+        {
+            return Err(format!(
+                "Task \"{title}\" has a running job; wait for it to finish first."
+            ));
+        }
+        if let Some(pending) = &self.pending_review
+            && subtree.contains(&pending.job.kind.target_node_id())
+        {
+            return Err(format!(
+                "Task \"{title}\" has a pass awaiting review; resolve it with /review first."
+            ));
+        }
+
+        for id in &subtree {
+            self.set_status(*id, TaskStatus::Done);
+        }
+        self.queue
+            .retain(|job| !subtree.contains(&job.kind.target_node_id()));
+        let mut messages = vec![match action {
+            ManualTaskAction::Skip => i18n::system(
+                self.locale,
+                Text::TaskSkipped,
+                &[("title", &title), ("task_id", &task_id)],
+            ),
+            ManualTaskAction::MarkDone => i18n::system(
+                self.locale,
+                Text::TaskMarkedDone,
+                &[("title", &title), ("task_id", &task_id)],
+            ),
+        }];
+        self.push_context(match action {
+            ManualTaskAction::Skip => format!(
+                "User skipped task \"{title}\" ({task_id}); no agent completed it, so do not assume it is implemented."
+            ),
+            ManualTaskAction::MarkDone => format!(
+                "User marked task \"{title}\" ({task_id}) done after handling it manually outside the agent workflow."
+            ),
+        });
+        self.manual_interventions.push(ManualIntervention {
+            task_id: task_id.to_string(),
+            title,
+            action,
+        });
+        if node_id != top_id {
+            self.try_mark_top_done(top_id, &mut messages);
+        }
+        if self.execution_enabled && self.active.is_none() && self.pending_review.is_none() {
+            let _ = self.enqueue_ready_top_tasks();
+            self.finish_scoped_run_if_done(&mut messages);
+        }
+        Ok(messages)
+    }
+
+    /// Resets planner task `task_id` and its subtree to pending, reopens its ancestors, and
+    /// starts a scoped run for its top-level task so the work is redone.
+    pub fn retry_task(&mut self, task_id: &str) -> Result<Vec<String>, String> {
+        if self.active.is_some() || self.pending_review.is_some() || !self.queue.is_empty() {
+            return Err(
+                "Execution is already running; wait for the current job before retrying a task."
+                    .to_string(),
+            );
+        }
+        let path = find_path_by_external_id(&self.tasks, task_id)
+            .ok_or_else(|| format!("Planner task {task_id} not found"))?;
+        if path[0].kind == TaskKind::FinalAudit {
+            return Err("The final audit runs after every task; use /start instead.".to_string());
+        }
+        let node = path[path.len() - 1];
+        let title = node.title.clone();
+        let mut reset = path[..path.len() - 1]
+            .iter()
+            .map(|ancestor| ancestor.id)
+            .collect::<Vec<_>>();
+        collect_subtree_ids(node, &mut reset);
+        for id in reset {
+            self.set_status(id, TaskStatus::Pending);
+        }
+        self.push_context(format!(
+            "User asked to retry task \"{title}\" ({task_id}); redo it even if earlier passes finished."
+        ));
+        let mut messages = vec![i18n::system(
+            self.locale,
+            Text::TaskResetForRetry,
+            &[("title", &title), ("task_id", &task_id)],
+        )];
+        messages.extend(self.start_execution_scoped(task_id)?);
+        Ok(messages)
+    }
+
+    /// Records `answer` to the question blocked planner task `task_id` is waiting on and puts
+    /// the task back in the queue; its workers see the answer in their prompts.
+    pub fn unblock_task(&mut self, task_id: &str, answer: &str) -> Result<Vec<String>, String> {
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Err("Usage: /unblock <task-id> <answer>".to_string());
+        }
+        let path = find_path_by_external_id(&self.tasks, task_id)
+            .ok_or_else(|| format!("Planner task {task_id} not found"))?;
+        let node = path[path.len() - 1];
+        if node.status != TaskStatus::Blocked {
+            return Err(format!("Task \"{}\" is not blocked.", node.title));
+        }
+        let (node_id, title) = (node.id, node.title.clone());
+        if let Some(node) = find_node_mut(&mut self.tasks, node_id) {
+            let question = node.blocked_reason.take().unwrap_or_default();
+            node.user_answers.push(format!("{question} -> {answer}"));
+            node.status = TaskStatus::Pending;
+        }
+        self.push_context(format!(
+            "User answered blocked task \"{title}\" ({task_id}): {answer}"
+        ));
+        let mut messages = vec![i18n::system(
+            self.locale,
+            Text::TaskUnblocked,
+            &[("title", &title), ("task_id", &task_id)],
+        )];
+        if self.execution_enabled && self.active.is_none() && self.pending_review.is_none() {
+            if self.enqueue_ready_top_tasks() > 0 {
+                messages.push(i18n::system(self.locale, Text::UnblockedTaskQueued, &[]));
+            }
+        } else if !self.execution_enabled {
+            messages.push(i18n::system(
+                self.locale,
+                Text::UnblockedTaskWaitsForStart,
+                &[],
+            ));
+        }
+        Ok(messages)
+    }
+
+    /// Describes the task drawn under right-pane key `task_key`; tasks without a planner id
+    /// have no menu.
+    pub fn task_menu_target(&self, task_key: &str) -> Option<TaskMenuTarget> {
+        let path = find_path_by_external_id(&self.tasks, task_key)?;
+        let top = path[0];
+        let node = path[path.len() - 1];
+        Some(TaskMenuTarget {
+            task_id: task_key.to_string(),
+            top_task_id: top.external_id.clone()?,
+            title: node.title.clone(),
+            done: Self::subtree_done(node),
+            collapsible_details: path.len() > 1,
+            accepts_docs: node.kind != TaskKind::TestRunner,
+        })
+    }
+
+    /// Top-level tasks a chat line is about: those it names as `#<id>`, `task <planner id>`,
+    /// `task:<planner id>`, or a backticked planner id, themselves or through a subtask.
+    pub fn top_tasks_mentioned(&self, line: &str) -> Vec<u64> {
+        let mut tops = Vec::new();
+        for reference in task_references(line) {
+            let top = match reference {
+                TaskReference::Internal(id) => self
+                    .tasks
+                    .iter()
+                    .find(|top| find_node(std::slice::from_ref(*top), id).is_some()),
+                TaskReference::Planner(key) => {
+                    find_path_by_external_id(&self.tasks, key).map(|path| path[0])
+                }
+            };
+            if let Some(top) = top
+                && !tops.contains(&top.id)
+            {
+                tops.push(top.id);
+            }
         }
-        messages
+        tops
     }
 
-    pub fn drain_recent_failures(&mut self) -> Vec<WorkflowFailure> {
-        std::mem::take(&mut self.recent_failures)
+    /// Planner id of the top-level task containing planner task `task_id`.
+    pub fn top_task_external_id(&self, task_id: &str) -> Option<String> {
+        find_path_by_external_id(&self.tasks, task_id)?[0]
+            .external_id
+            .clone()
+    }
+
+    /// Id of the top-level task containing planner task `task_id`.
+    pub fn top_task_id(&self, task_id: &str) -> Option<u64> {
+        find_path_by_external_id(&self.tasks, task_id).map(|path| path[0].id)
+    }
+
+    /// Planner id of top-level task `top_task_id`.
+    pub fn top_task_external_id_by_id(&self, top_task_id: u64) -> Option<String> {
+        find_node(&self.tasks, top_task_id).and_then(|node| node.external_id.clone())
+    }
+
+    /// Total of the top-level task estimates; `None` when no task has one.
+    pub fn plan_estimate_total(&self) -> Option<String> {
+        let estimates = self
+            .tasks
+            .iter()
+            .filter(|task| task.kind == TaskKind::Top)
+            .map(|task| task.estimate)
+            .collect::<Vec<_>>();
+        estimates::plan_total(&estimates)
+    }
+
+    /// Estimate and worker time of every top-level task that has run, in plan order, for the
+    /// final report.
+    pub fn estimate_comparisons(&self) -> Vec<String> {
+        self.ordered_root_nodes()
+            .into_iter()
+            .filter(|task| task.kind == TaskKind::Top)
+            .filter_map(|task| {
+                let stats = self.task_usage_stats(&task_detail_key(task))?;
+                let comparison = estimates::comparison(task.estimate, stats.elapsed);
+                Some(format!("{}: {comparison}", task.title))
+            })
+            .collect()
+    }
+
+    pub fn drain_manual_interventions(&mut self) -> Vec<ManualIntervention> {
+        std::mem::take(&mut self.manual_interventions)
     }
 
     pub fn right_pane_lines(&self) -> Vec<String> {
@@ -756,16 +2272,16 @@ impl Workflow {
             }
         }
 
+        if let Some(total) = self.plan_estimate_total() {
+            lines.push(String::new());
+            lines.push("Estimates".to_string());
+            lines.push(format!("- total: {total}"));
+        }
+
         lines.push(String::new());
         lines.push("Execution".to_string());
-        lines.push(format!(
-            "- status: {}",
-            if self.execution_enabled {
-                "running"
-            } else {
-                "planning"
-            }
-        ));
+        lines.push(format!("- status: {}", self.execution_status_label()));
+        lines.extend(self.queue_lines());
 
         lines.push(String::new());
         lines.push("Rolling Task Context".to_string());
@@ -805,6 +2321,7 @@ impl Workflow {
     ) -> RightPaneBlockView {
         let mut lines = Vec::new();
         let mut toggles = Vec::new();
+        let mut task_spans = Vec::new();
         if self.tasks.is_empty() {
             lines.push("  (no tasks queued)".to_string());
         } else {
@@ -817,7 +2334,22 @@ impl Workflow {
                     lines.push(section_divider.clone());
                     lines.push(String::new());
                 }
-                lines.push(format!("  {}. {}", idx + 1, task.title));
+                let task_start = lines.len();
+                match task.estimate {
+                    Some(estimate) => {
+                        lines.push(format!("  {}. {} [est. {estimate}]", idx + 1, task.title))
+                    }
+                    None => lines.push(format!("  {}. {}", idx + 1, task.title)),
+                }
+                if task.status == TaskStatus::Blocked {
+                    let question = format!(
+                        "[?] blocked on you: {}",
+                        task.blocked_reason.as_deref().unwrap_or_default()
+                    );
+                    for segment in wrap_words(&question, width.saturating_sub(2).max(1)) {
+                        lines.push(format!("  {segment}"));
+                    }
+                }
                 lines.push(String::new());
                 lines.extend(render_detail_lines(&task.details, width, false, 2, false));
                 if task.kind != TaskKind::TestRunner && !task.docs.is_empty() {
@@ -837,6 +2369,22 @@ impl Workflow {
                         lines.extend(render_docs_lines(&task.docs, width, 4));
                     }
                 }
+                let task_key = task_detail_key(task);
+                if let Some(stats) = self.task_usage_stats(&task_key) {
+                    let collapsed = !expanded_detail_keys.contains(&task_key);
+                    toggles.push(RightPaneToggleLine {
+                        line_index: lines.len(),
+                        task_key: task_key.clone(),
+                    });
+                    lines.push(format!(
+                        "  [time & usage] {} {}",
+                        usage_headline(&stats),
+                        detail_toggle_label(collapsed)
+                    ));
+                    if !collapsed {
+                        lines.extend(render_usage_lines(&stats, width, 4));
+                    }
+                }
                 lines.push(String::new());
                 for child in &task.children {
                     render_subtree_box(
@@ -844,23 +2392,23 @@ impl Workflow {
                         width.saturating_sub(2).max(8),
                         &mut lines,
                         &mut toggles,
+                        &mut task_spans,
                         expanded_detail_keys,
                         2,
                     );
                 }
+                task_spans.push(RightPaneTaskSpan {
+                    start: task_start,
+                    end: lines.len(),
+                    task_key: task_detail_key(task),
+                });
             }
         }
 
         lines.push(String::new());
         lines.push("Execution".to_string());
-        lines.push(format!(
-            "- status: {}",
-            if self.execution_enabled {
-                "running"
-            } else {
-                "planning"
-            }
-        ));
+        lines.push(format!("- status: {}", self.execution_status_label()));
+        lines.extend(self.queue_lines());
 
         lines.push(String::new());
         lines.push("Rolling Task Context".to_string());
@@ -872,7 +2420,88 @@ impl Workflow {
             }
         }
 
-        RightPaneBlockView { lines, toggles }
+        RightPaneBlockView {
+            lines,
+            toggles,
+            task_spans,
+        }
+    }
+
+    /// Builds the prompt an audit job would send for the planner task `task_id` (an auditor or
+    /// final audit) without queueing anything. `worker_output` is the implementor or test-writer
+    /// report under audit; for a final audit it is the previous audit's feedback.
+    pub fn audit_prompt_for_planner_task(
+        &self,
+        task_id: &str,
+        worker_output: Option<String>,
+        changed_files_summary: Option<String>,
+        pass: u8,
+    ) -> Result<String, String> {
+        let path = find_path_by_external_id(&self.tasks, task_id)
+            .ok_or_else(|| format!("Planner task {task_id} not found"))?;
+        let audit = path[path.len() - 1];
+        let prompt = match (audit.kind, path.len()) {
+            (TaskKind::FinalAudit, _) => self.final_audit_prompt(worker_output.as_deref()),
+            (TaskKind::Auditor, len) if len >= 3 => {
+                let parent = path[len - 2];
+                match parent.kind {
+                    TaskKind::Implementor => implementation_auditor::build_prompt(
+                        self,
+                        path[0].id,
+                        parent.id,
+                        audit.id,
+                        &worker_output,
+                        &changed_files_summary,
+                        pass,
+                    ),
+                    TaskKind::TestWriter => test_auditor::build_prompt(
+                        self,
+                        path[0].id,
+                        parent.id,
+                        audit.id,
+                        &worker_output,
+                        pass,
+                    ),
+                    _ => {
+                        return Err(format!(
+                            "Auditor {task_id} must be a child of an implementor or test_writer"
+                        ));
+                    }
+                }
+            }
+            _ => return Err(format!("Planner task {task_id} is not an audit task")),
+        };
+        Ok(self.prepend_task_docs_to_prompt(audit.id, prompt))
+    }
+
+    /// Builds the first-pass prompt for any planner task that runs an agent (implementor,
+    /// test writer, auditor, or final audit). Audit arguments are passed through to
+    /// `audit_prompt_for_planner_task`.
+    pub fn worker_prompt_for_planner_task(
+        &self,
+        task_id: &str,
+        worker_output: Option<String>,
+        changed_files_summary: Option<String>,
+    ) -> Result<String, String> {
+        let path = find_path_by_external_id(&self.tasks, task_id)
+            .ok_or_else(|| format!("Planner task {task_id} not found"))?;
+        let node = path[path.len() - 1];
+        let prompt = match node.kind {
+            TaskKind::Implementor => implementor::build_prompt(self, path[0].id, node.id, None),
+            TaskKind::TestWriter => {
+                test_writer::build_prompt(self, path[0].id, node.id, None, false)
+            }
+            TaskKind::Auditor | TaskKind::FinalAudit => {
+                return self.audit_prompt_for_planner_task(
+                    task_id,
+                    worker_output,
+                    changed_files_summary,
+                    1,
+                );
+            }
+            _ => return Err(format!("Planner task {task_id} does not run an agent")),
+        };
+        Ok(self.prepend_task_docs_to_prompt(node.id, prompt))
     }
 
     fn run_for_job(&self, job: &WorkerJob) -> JobRun {
@@ -943,43 +2572,55 @@ impl Workflow {
             }
             WorkerJobKind::TestRunner { .. } => JobRun::DeterministicTestRun,
             WorkerJobKind::ImplementorTestRunner { .. } => JobRun::DeterministicTestRun,
+            WorkerJobKind::PluginStep {
+                implementor_id,
+                plugin_step_id,
+                ..
+            } => plugin_step::job_run(self, job.top_task_id, *implementor_id, *plugin_step_id),
+            WorkerJobKind::Verify { .. } => JobRun::Verify {
+                command: self.verify_command(job.top_task_id).unwrap_or_default(),
+            },
             WorkerJobKind::FinalAudit {
                 final_audit_id,
                 feedback,
                 ..
             } => {
-                let tests_policy = if self.tests_mode_enabled {
-                    "Tests mode policy (ON): include cross-task test adequacy in holistic risk assessment when relevant."
-                } else {
-                    "Tests mode policy (OFF): do not fail solely for missing new tests; treat test additions/changes as out of scope."
-                };
-                let prompt = format!(
-                    "You are a final audit sub-agent.\n\
-                 Perform a holistic audit across all completed tasks and their outcomes.\n\
-                 Focus on cross-task correctness, missing edge cases, integration risk, and overall quality gaps.\n\
-                 Rolling task context:\n{}\n\
-                 Current task tree:\n{}\n\
-                 {}\n\
-                 {}\n\
-                 Response protocol (required):\n\
-                 - First line must be exactly one of:\n\
-                   PASS\n\
-                   FAIL\n\
-                 - PASS: no additional text after the token.\n\
-                 - FAIL: include one or more lines of findings and rationale after the token.",
-                    self.context_block(),
-                    self.task_tree_compact(),
-                    tests_policy,
-                    feedback
-                        .as_deref()
-                        .map(|f| format!("Previous final-audit feedback to address:\n{f}"))
-                        .unwrap_or_else(|| "No prior final-audit feedback.".to_string())
-                );
+                let prompt = self.final_audit_prompt(feedback.as_deref());
                 JobRun::AgentPrompt(self.prepend_task_docs_to_prompt(*final_audit_id, prompt))
             }
         }
     }
 
+    fn final_audit_prompt(&self, feedback: Option<&str>) -> String {
+        let tests_policy = if self.tests_mode_enabled {
+            "Tests mode policy (ON): include cross-task test adequacy in holistic risk assessment when relevant."
+        } else {
+            "Tests mode policy (OFF): do not fail solely for missing new tests; treat test additions/changes as out of scope."
+        };
+        format!(
+            "You are a final audit sub-agent.\n\
+             Perform a holistic audit across all completed tasks and their outcomes.\n\
+             Focus on cross-task correctness, missing edge cases, integration risk, and overall quality gaps.\n\
+             Rolling task context:\n{}\n\
+             Current task tree:\n{}\n\
+             {}\n\
+             {}\n\
+             Response protocol (required):\n\
+             - First line must be exactly one of:\n\
+               PASS\n\
+               FAIL\n\
+             - PASS: no additional text after the token.\n\
+             - FAIL: include one or more lines of findings and rationale after the token.\n\
+             - Each FAIL finding must cite the code it is about as a workspace-relative `path:line` or `path:start-end` (e.g. `src/app.rs:120-134`).",
+            self.context_block(),
+            self.task_tree_compact(),
+            tests_policy,
+            feedback
+                .map(|f| format!("Previous final-audit feedback to address:\n{f}"))
+                .unwrap_or_else(|| "No prior final-audit feedback.".to_string())
+        )
+    }
+
     fn prepend_task_docs_to_prompt(&self, task_id: u64, prompt: String) -> String {
         let prefix = self.task_docs_prefix(task_id);
         if prefix.is_empty() {
@@ -997,9 +2638,26 @@ impl Workflow {
             return String::new();
         }
 
+        let snapshot_of = |doc: &PlannerTaskDocFileEntry| {
+            doc.snapshot
+                .as_deref()
+                .and_then(|path| self.doc_snapshots.get(path))
+        };
+        let snapshots = node
+            .docs
+            .iter()
+            .filter(|doc| snapshot_of(doc).is_some())
+            .count();
+        let read_instruction = if snapshots == 0 {
+            "- Before starting this task, read every linked document from the web."
+        } else if snapshots == node.docs.len() {
+            "- Every linked document is included below as a local snapshot; read those instead of fetching the web."
+        } else {
+            "- Before starting this task, read every linked document; the ones with a snapshot below are included verbatim, fetch the rest from the web."
+        };
         let mut lines = vec![
             "Task documentation requirements:".to_string(),
-            "- Before starting this task, read every linked document from the web.".to_string(),
+            read_instruction.to_string(),
             "- Use these docs as primary references while completing this task.".to_string(),
             "Task docs:".to_string(),
         ];
@@ -1010,6 +2668,12 @@ impl Workflow {
             if !doc.summary.trim().is_empty() {
                 lines.push(format!("   Summary: {}", doc.summary.trim()));
             }
+            if let Some(text) = snapshot_of(doc) {
+                lines.push("   Snapshot:".to_string());
+                lines.push("<<<".to_string());
+                lines.push(text.clone());
+                lines.push(">>>".to_string());
+            }
         }
         lines.push(String::new());
         lines.join("\n")
@@ -1032,9 +2696,17 @@ impl Workflow {
                 title: title.to_string(),
                 details: default_generated_details(kind).to_string(),
                 docs: Vec::new(),
+                external_ref: None,
+                workdir: None,
+                acceptance_criteria: Vec::new(),
+                plugin: None,
                 status: TaskStatus::Pending,
                 kind,
                 children: Vec::new(),
+                estimate: None,
+                verify: None,
+                blocked_reason: None,
+                user_answers: Vec::new(),
             });
         }
         Some(id)
@@ -1062,9 +2734,17 @@ impl Workflow {
                 title: title.to_string(),
                 details: default_generated_details(kind).to_string(),
                 docs: Vec::new(),
+                external_ref: None,
+                workdir: None,
+                acceptance_criteria: Vec::new(),
+                plugin: None,
                 status: TaskStatus::Pending,
                 kind,
                 children: Vec::new(),
+                estimate: None,
+                verify: None,
+                blocked_reason: None,
+                user_answers: Vec::new(),
             });
         }
         Some(id)
@@ -1097,6 +2777,22 @@ impl Workflow {
     ) -> bool {
         let Some(auditor_id) = self.find_next_pending_child_kind(implementor_id, TaskKind::Auditor)
         else {
+            if let Some(plugin_step_id) = self.next_runnable_plugin_step(implementor_id, messages) {
+                self.queue.push_back(WorkerJob {
+                    top_task_id,
+                    kind: WorkerJobKind::PluginStep {
+                        implementor_id,
+                        plugin_step_id,
+                        pass,
+                    },
+                });
+                messages.push(i18n::system(
+                    self.locale,
+                    Text::PluginStepQueued,
+                    &[("task", &top_task_id)],
+                ));
+                return true;
+            }
             if (!ENFORCE_TESTS_MODE_RUNTIME_GATING || self.tests_mode_enabled)
                 && let Some(test_runner_id) =
                 self.find_next_pending_child_kind(implementor_id, TaskKind::TestRunner)
@@ -1109,16 +2805,21 @@ impl Workflow {
                         pass,
                     },
                 });
-                messages.push(format!(
-                    "System: Task #{} all audits complete; existing-test runner queued.",
-                    top_task_id
+                messages.push(i18n::system(
+                    self.locale,
+                    Text::ExistingTestsQueued,
+                    &[("task", &top_task_id)],
                 ));
                 return true;
             }
+            if verify_step::queue_if_configured(self, top_task_id, implementor_id, pass, messages) {
+                return true;
+            }
             self.set_status(implementor_id, TaskStatus::Done);
-            messages.push(format!(
-                "System: Task #{} implementation branch passed all audits.",
-                top_task_id
+            messages.push(i18n::system(
+                self.locale,
+                Text::ImplementationAuditsPassed,
+                &[("task", &top_task_id)],
             ));
             self.try_mark_top_done(top_task_id, messages);
             return false;
@@ -1133,18 +2834,54 @@ impl Workflow {
                 changed_files_summary,
             },
         });
-        messages.push(format!(
-            "System: Task #{} audit queued (audit pass {}).",
-            top_task_id, pass
+        messages.push(i18n::system(
+            self.locale,
+            Text::AuditQueued,
+            &[("task", &top_task_id), ("pass", &pass)],
         ));
         true
     }
 
+    /// The implementor's next unfinished plugin step whose plugin is installed. Steps naming
+    /// a missing plugin, and every step in safe mode, are marked done with a warning so the
+    /// branch is not blocked.
+    fn next_runnable_plugin_step(
+        &mut self,
+        implementor_id: u64,
+        messages: &mut Vec<String>,
+    ) -> Option<u64> {
+        loop {
+            let step_id = self.find_next_pending_child_kind(implementor_id, TaskKind::Plugin)?;
+            let name = find_node(&self.tasks, step_id)
+                .and_then(|node| node.plugin.clone())
+                .unwrap_or_default();
+            let plugin = if self.safe_mode {
+                Err(i18n::text(self.locale, Text::PluginsSafeMode).to_string())
+            } else {
+                self.plugins
+                    .get_for(&name, PluginCapability::Step)
+                    .map(|_| ())
+            };
+            match plugin {
+                Ok(()) => return Some(step_id),
+                Err(err) => {
+                    self.set_status(step_id, TaskStatus::Done);
+                    messages.push(i18n::system(
+                        self.locale,
+                        Text::PluginStepSkipped,
+                        &[("step", &self.node_title(step_id, &name)), ("err", &err)],
+                    ));
+                }
+            }
+        }
+    }
+
     fn enqueue_ready_top_tasks(&mut self) -> usize {
         let root_ids: Vec<u64> = self
             .ordered_root_nodes()
             .iter()
             .filter(|node| matches!(node.kind, TaskKind::Top | TaskKind::FinalAudit))
+            .filter(|node| self.execution_scope.is_none_or(|scope| scope == node.id))
             .map(|node| node.id)
             .collect();
 
@@ -1173,7 +2910,8 @@ impl Workflow {
                 .iter()
                 .any(|child| child.kind == TaskKind::TestWriter);
 
-            if top_status == TaskStatus::Done {
+            // Blocked tasks wait for `/unblock`; later tasks still run.
+            if matches!(top_status, TaskStatus::Done | TaskStatus::Blocked) {
                 continue;
             }
 
@@ -1350,14 +3088,32 @@ impl Workflow {
     ) -> bool {
         if ENFORCE_TESTS_MODE_RUNTIME_GATING && !self.tests_mode_enabled {
             self.set_status(test_writer_id, TaskStatus::Done);
-            messages.push(format!(
-                "System: Task #{} tests mode is OFF; skipping test-writer follow-up steps.",
-                top_task_id
+            messages.push(i18n::system(
+                self.locale,
+                Text::TestsOffSkippingTestWriter,
+                &[("task", &top_task_id)],
             ));
             self.try_mark_top_done(top_task_id, messages);
             return false;
         }
 
+        if !self.test_writer_audits_enabled {
+            let mut skipped = false;
+            while let Some(auditor_id) =
+                self.find_next_pending_child_kind(test_writer_id, TaskKind::Auditor)
+            {
+                self.set_status(auditor_id, TaskStatus::Done);
+                skipped = true;
+            }
+            if skipped {
+                messages.push(i18n::system(
+                    self.locale,
+                    Text::TestWriterAuditSkipped,
+                    &[("task", &top_task_id)],
+                ));
+            }
+        }
+
         if allow_test_writer_auditor
             && let Some(auditor_id) =
                 self.find_next_pending_child_kind(test_writer_id, TaskKind::Auditor)
@@ -1371,9 +3127,10 @@ impl Workflow {
                     test_report,
                 },
             });
-            messages.push(format!(
-                "System: Task #{} test-writer pass {} complete; test-writer audit queued.",
-                top_task_id, pass
+            messages.push(i18n::system(
+                self.locale,
+                Text::TestWriterAuditQueued,
+                &[("task", &top_task_id), ("pass", &pass)],
             ));
             return true;
         }
@@ -1383,9 +3140,10 @@ impl Workflow {
             TaskKind::TestRunner,
             "Deterministic Test Run",
         ) else {
-            messages.push(format!(
-                "System: Task #{} could not queue deterministic test runner; test branch blocked.",
-                top_task_id
+            messages.push(i18n::system(
+                self.locale,
+                Text::TestRunnerQueueFailed,
+                &[("task", &top_task_id)],
             ));
             return false;
         };
@@ -1397,9 +3155,10 @@ impl Workflow {
                 pass,
             },
         });
-        messages.push(format!(
-            "System: Task #{} test-writer pass {} complete; deterministic test run queued.",
-            top_task_id, pass
+        messages.push(i18n::system(
+            self.locale,
+            Text::TestRunQueued,
+            &[("task", &top_task_id), ("pass", &pass)],
         ));
         true
     }
@@ -1412,6 +3171,8 @@ impl Workflow {
                     WorkerJobKind::Implementor { .. }
                         | WorkerJobKind::ImplementorTestRunner { .. }
                         | WorkerJobKind::Auditor { .. }
+                        | WorkerJobKind::PluginStep { .. }
+                        | WorkerJobKind::Verify { .. }
                 )
             }
             TaskKind::TestWriter => {
@@ -1462,6 +3223,12 @@ impl Workflow {
             WorkerJobKind::FinalAudit { final_audit_id, .. } => {
                 self.set_status(*final_audit_id, TaskStatus::InProgress)
             }
+            WorkerJobKind::PluginStep { plugin_step_id, .. } => {
+                self.set_status(*plugin_step_id, TaskStatus::InProgress)
+            }
+            WorkerJobKind::Verify { implementor_id, .. } => {
+                self.set_status(*implementor_id, TaskStatus::InProgress)
+            }
         }
     }
 
@@ -1481,6 +3248,13 @@ impl Workflow {
             .unwrap_or_else(|| format!("Task #{top_task_id}"))
     }
 
+    /// The top-level task's own verify command, else `[workflow] verify`.
+    fn verify_command(&self, top_task_id: u64) -> Option<String> {
+        find_node(&self.tasks, top_task_id)
+            .and_then(|node| node.verify.clone())
+            .or_else(|| self.verify_gate.command.clone())
+    }
+
     fn node_title(&self, node_id: u64, fallback: &str) -> String {
         find_node(&self.tasks, node_id)
             .map(|node| node.title.clone())
@@ -1488,6 +3262,84 @@ impl Workflow {
             .unwrap_or_else(|| fallback.to_string())
     }
 
+    /// The top task's acceptance criteria numbered `AC1: ...`, one per line, or `None` when
+    /// it has none.
+    /// Answers the user gave with `/unblock` for top-level task `top_task_id`, as a prompt
+    /// block ending in a newline; empty when there are none.
+    fn user_answers_block(&self, top_task_id: u64) -> String {
+        let Some(top) = find_node(&self.tasks, top_task_id) else {
+            return String::new();
+        };
+        if top.user_answers.is_empty() {
+            return String::new();
+        }
+        format!(
+            "User answers for this task (follow them):\n- {}\n",
+            top.user_answers.join("\n- ")
+        )
+    }
+
+    /// Parks top-level task `top_task_id` until the user answers `reason` with `/unblock`.
+    fn block_top_task(&mut self, top_task_id: u64, reason: String, messages: &mut Vec<String>) {
+        let Some(top) = find_node_mut(&mut self.tasks, top_task_id) else {
+            return;
+        };
+        top.status = TaskStatus::Blocked;
+        top.blocked_reason = Some(reason.clone());
+        let task_id = top
+            .external_id
+            .clone()
+            .unwrap_or_else(|| top_task_id.to_string());
+        self.queue.retain(|job| job.top_task_id != top_task_id);
+        messages.push(i18n::system(
+            self.locale,
+            Text::TaskBlockedOnUser,
+            &[
+                ("top_task_id", &top_task_id),
+                ("reason", &reason),
+                ("task_id", &task_id),
+            ],
+        ));
+    }
+
+    fn numbered_acceptance_criteria(&self, top_task_id: u64) -> Option<String> {
+        let criteria = &find_node(&self.tasks, top_task_id)?.acceptance_criteria;
+        if criteria.is_empty() {
+            return None;
+        }
+        Some(
+            criteria
+                .iter()
+                .enumerate()
+                .map(|(index, criterion)| format!("AC{}: {criterion}", index + 1))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    /// Criteria of the top task the audit in `transcript` did not report as met.
+    fn unmet_acceptance_criteria(&self, top_task_id: u64, transcript: &[String]) -> Vec<String> {
+        let Some(top) = find_node(&self.tasks, top_task_id) else {
+            return Vec::new();
+        };
+        let mut met = vec![false; top.acceptance_criteria.len()];
+        for (number, verdict) in transcript
+            .iter()
+            .flat_map(|line| line.lines())
+            .filter_map(acceptance_verdict)
+        {
+            if let Some(slot) = met.get_mut(number - 1) {
+                *slot = verdict;
+            }
+        }
+        top.acceptance_criteria
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !met[*index])
+            .map(|(index, criterion)| format!("AC{}: {criterion}", index + 1))
+            .collect()
+    }
+
     fn node_details(&self, node_id: u64) -> String {
         find_node(&self.tasks, node_id)
             .map(|node| node.details.trim().to_string())
@@ -1529,18 +3381,26 @@ impl Workflow {
         if impl_done && test_done && !already_done {
             self.set_status(top_task_id, TaskStatus::Done);
             let task_title = self.task_title(top_task_id);
+            self.completed_top_tasks.push(CompletedTopTask {
+                key: find_node(&self.tasks, top_task_id)
+                    .and_then(|node| node.external_id.clone())
+                    .unwrap_or_else(|| format!("internal-{top_task_id}")),
+                title: task_title.clone(),
+            });
             self.push_context(format!(
                 "Task \"{task_title}\" records what was changed and why this branch can continue as context for downstream work."
             ));
             if self.tests_mode_enabled && requires_test_writer {
-                messages.push(format!(
-                    "System: Task #{} completed after implementation and testing branches converged.",
-                    top_task_id
+                messages.push(i18n::system(
+                    self.locale,
+                    Text::TaskCompletedWithTests,
+                    &[("task", &top_task_id)],
                 ));
             } else {
-                messages.push(format!(
-                    "System: Task #{} completed after implementation branch checks converged.",
-                    top_task_id
+                messages.push(i18n::system(
+                    self.locale,
+                    Text::TaskCompleted,
+                    &[("task", &top_task_id)],
                 ));
             }
         }
@@ -1556,6 +3416,7 @@ impl Workflow {
     fn push_context(&mut self, entry: String) {
         if self.rolling_context.len() >= self.max_context_entries {
             self.rolling_context.pop_front();
+            self.rolling_context_dropped += 1;
         }
         self.rolling_context.push_back(entry);
     }
@@ -1728,6 +3589,59 @@ fn find_node(nodes: &[TaskNode], id: u64) -> Option<&TaskNode> {
     None
 }
 
+/// The chain of nodes from a top-level task down to the node with planner id `external_id`.
+fn find_path_by_external_id<'a>(
+    nodes: &'a [TaskNode],
+    external_id: &str,
+) -> Option<Vec<&'a TaskNode>> {
+    for node in nodes {
+        if node.external_id.as_deref() == Some(external_id) {
+            return Some(vec![node]);
+        }
+        if let Some(mut path) = find_path_by_external_id(&node.children, external_id) {
+            path.insert(0, node);
+            return Some(path);
+        }
+    }
+    None
+}
+
+enum TaskReference<'a> {
+    Internal(u64),
+    Planner(&'a str),
+}
+
+fn task_references(line: &str) -> Vec<TaskReference<'_>> {
+    let mut references = Vec::new();
+    let mut after_task_word = false;
+    for word in line.split_whitespace() {
+        let word = word
+            .trim_start_matches(['(', '['])
+            .trim_end_matches(['.', ',', ';', ':', ')', ']', '!', '?']);
+        if let Some(id) = word.strip_prefix('#').and_then(|id| id.parse().ok()) {
+            references.push(TaskReference::Internal(id));
+        } else if let Some(key) = word.strip_prefix("task:") {
+            references.push(TaskReference::Planner(key));
+        } else if let Some(key) = word
+            .strip_prefix('`')
+            .and_then(|rest| rest.strip_suffix('`'))
+        {
+            references.push(TaskReference::Planner(key));
+        } else if after_task_word {
+            references.push(TaskReference::Planner(word));
+        }
+        after_task_word = word.eq_ignore_ascii_case("task");
+    }
+    references
+}
+
+fn collect_subtree_ids(node: &TaskNode, ids: &mut Vec<u64>) {
+    ids.push(node.id);
+    for child in &node.children {
+        collect_subtree_ids(child, ids);
+    }
+}
+
 fn find_node_mut(nodes: &mut [TaskNode], id: u64) -> Option<&mut TaskNode> {
     for node in nodes {
         if node.id == id {
@@ -1749,6 +3663,7 @@ fn render_tree(node: &TaskNode, depth: usize, lines: &mut Vec<String>) {
         TaskKind::Auditor => "Audit",
         TaskKind::TestWriter => "Tests",
         TaskKind::TestRunner => "TestRun",
+        TaskKind::Plugin => "Plugin",
     };
     lines.push(format!(
         "{indent}- {} {}: {}",
@@ -1766,10 +3681,12 @@ fn render_subtree_box(
     width: usize,
     out: &mut Vec<String>,
     toggles: &mut Vec<RightPaneToggleLine>,
+    task_spans: &mut Vec<RightPaneTaskSpan>,
     expanded_detail_keys: &HashSet<String>,
     left_indent: usize,
 ) {
     let width = width.max(8);
+    let box_start = out.len();
     out.push(format!(
         "{}┌{}┐",
         " ".repeat(left_indent),
@@ -1846,11 +3763,13 @@ fn render_subtree_box(
         for (idx, child) in node.children.iter().enumerate() {
             let mut child_lines = Vec::new();
             let mut child_toggles = Vec::new();
+            let mut child_spans = Vec::new();
             render_subtree_box(
                 child,
                 child_width,
                 &mut child_lines,
                 &mut child_toggles,
+                &mut child_spans,
                 expanded_detail_keys,
                 0,
             );
@@ -1868,6 +3787,13 @@ fn render_subtree_box(
                     task_key: toggle.task_key,
                 });
             }
+            for span in child_spans {
+                task_spans.push(RightPaneTaskSpan {
+                    start: line_offset + span.start,
+                    end: line_offset + span.end,
+                    task_key: span.task_key,
+                });
+            }
             if idx + 1 < node.children.len() {
                 out.push(format!(
                     "{}│ {} │",
@@ -1883,6 +3809,11 @@ fn render_subtree_box(
         " ".repeat(left_indent),
         "─".repeat(width.saturating_sub(2))
     ));
+    task_spans.push(RightPaneTaskSpan {
+        start: box_start,
+        end: out.len(),
+        task_key: key,
+    });
 }
 
 fn kind_label(kind: TaskKind) -> &'static str {
@@ -1893,6 +3824,7 @@ fn kind_label(kind: TaskKind) -> &'static str {
         TaskKind::Auditor => "Audit",
         TaskKind::TestWriter => "Tests",
         TaskKind::TestRunner => "TestRun",
+        TaskKind::Plugin => "Plugin",
     }
 }
 
@@ -1940,6 +3872,12 @@ fn validate_required_subtask_structure(nodes: &[TaskNode]) -> Result<(), String>
                         node_label(node)
                     ));
                 }
+                if child.kind == TaskKind::Plugin && idx <= last_audit_idx {
+                    return Err(format!(
+                        "Implementor task \"{}\" has a plugin step before audit; plugin steps must come after audit",
+                        node_label(node)
+                    ));
+                }
             }
             let test_runner_count = node
                 .children
@@ -1954,6 +3892,13 @@ fn validate_required_subtask_structure(nodes: &[TaskNode]) -> Result<(), String>
             }
         }
 
+        if node.kind == TaskKind::Plugin && parent_kind != Some(TaskKind::Implementor) {
+            return Err(format!(
+                "Plugin task \"{}\" must be a child of an implementor",
+                node_label(node)
+            ));
+        }
+
         if node.kind == TaskKind::TestWriter && parent_kind != Some(TaskKind::Top) {
             return Err(format!(
                 "Test-writer task \"{}\" must be a direct child of a top-level task (no nested test_writer groups)",
@@ -2175,6 +4120,44 @@ fn render_docs_lines(
     out
 }
 
+fn usage_headline(stats: &TaskUsageStats) -> String {
+    format!(
+        "{} {}, {}",
+        stats.passes,
+        if stats.passes == 1 { "pass" } else { "passes" },
+        format_duration(stats.elapsed)
+    )
+}
+
+fn render_usage_lines(stats: &TaskUsageStats, width: usize, base_indent: usize) -> Vec<String> {
+    let usage = stats.usage;
+    let cost = if usage.cost_usd > 0.0 {
+        format!("${:.2}", usage.cost_usd)
+    } else {
+        "not reported by the backend".to_string()
+    };
+    let mut fields = vec![
+        ("wall-clock", format_duration(stats.elapsed)),
+        ("passes", stats.passes.to_string()),
+        (
+            "tokens",
+            format!(
+                "{} in / {} out",
+                compact_count(usage.input_tokens),
+                compact_count(usage.output_tokens)
+            ),
+        ),
+        ("est. cost", cost),
+    ];
+    if stats.escalations > 0 {
+        fields.insert(2, ("escalations", stats.escalations.to_string()));
+    }
+    fields
+        .iter()
+        .flat_map(|(label, value)| render_doc_field_lines(label, value, width, base_indent, true))
+        .collect()
+}
+
 fn render_doc_field_lines(
     label: &str,
     value: &str,
@@ -2224,6 +4207,7 @@ fn status_label(status: TaskStatus) -> &'static str {
         TaskStatus::InProgress => "[~]",
         TaskStatus::NeedsChanges => "[!]",
         TaskStatus::Done => "[x]",
+        TaskStatus::Blocked => "[?]",
     }
 }
 
@@ -2235,6 +4219,7 @@ fn task_kind_from_file(kind: PlannerTaskKindFile) -> TaskKind {
         PlannerTaskKindFile::Auditor => TaskKind::Auditor,
         PlannerTaskKindFile::TestWriter => TaskKind::TestWriter,
         PlannerTaskKindFile::TestRunner => TaskKind::TestRunner,
+        PlannerTaskKindFile::Plugin => TaskKind::Plugin,
     }
 }
 
@@ -2246,6 +4231,7 @@ fn task_kind_to_file(kind: TaskKind) -> PlannerTaskKindFile {
         TaskKind::Auditor => PlannerTaskKindFile::Auditor,
         TaskKind::TestWriter => PlannerTaskKindFile::TestWriter,
         TaskKind::TestRunner => PlannerTaskKindFile::TestRunner,
+        TaskKind::Plugin => PlannerTaskKindFile::Plugin,
     }
 }
 
@@ -2255,6 +4241,7 @@ fn task_status_to_file(status: TaskStatus) -> PlannerTaskStatusFile {
         TaskStatus::InProgress => PlannerTaskStatusFile::InProgress,
         TaskStatus::NeedsChanges => PlannerTaskStatusFile::NeedsChanges,
         TaskStatus::Done => PlannerTaskStatusFile::Done,
+        TaskStatus::Blocked => PlannerTaskStatusFile::Blocked,
     }
 }
 
@@ -2316,6 +4303,7 @@ fn audit_detects_issues(transcript: &[String]) -> bool {
 enum AuditResultToken {
     Pass,
     Fail,
+    Blocked,
     InvalidProtocol,
 }
 
@@ -2332,7 +4320,11 @@ fn parse_audit_result_token(transcript: &[String]) -> Option<AuditResultToken> {
 
     match first.to_ascii_uppercase().as_str() {
         "PASS" => {
-            if lines.len() == 1 {
+            // Acceptance criteria verdicts are the only lines allowed after PASS.
+            if lines[1..]
+                .iter()
+                .all(|line| acceptance_verdict(line).is_some())
+            {
                 Some(AuditResultToken::Pass)
             } else {
                 Some(AuditResultToken::InvalidProtocol)
@@ -2345,10 +4337,47 @@ fn parse_audit_result_token(transcript: &[String]) -> Option<AuditResultToken> {
                 Some(AuditResultToken::InvalidProtocol)
             }
         }
+        "BLOCKED" => {
+            if lines.len() > 1 {
+                Some(AuditResultToken::Blocked)
+            } else {
+                Some(AuditResultToken::InvalidProtocol)
+            }
+        }
         _ => None,
     }
 }
 
+/// The question a `BLOCKED` audit asks the user, from the lines after the token.
+fn audit_blocked_question(transcript: &[String]) -> Option<String> {
+    if parse_audit_result_token(transcript) != Some(AuditResultToken::Blocked) {
+        return None;
+    }
+    let lines = transcript
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .skip(1)
+        .collect::<Vec<_>>();
+    Some(lines.join(" "))
+}
+
+/// An audit's `AC<n>: MET` or `AC<n>: NOT MET - ...` line, as the criterion number and
+/// whether it was met.
+fn acceptance_verdict(line: &str) -> Option<(usize, bool)> {
+    let line = line.trim().trim_start_matches(['-', '*', '`', ' ']);
+    let rest = line
+        .strip_prefix("AC")
+        .or_else(|| line.strip_prefix("ac"))?;
+    let (number, verdict) = rest.split_once(':')?;
+    let number = number.trim().parse::<usize>().ok().filter(|n| *n > 0)?;
+    let verdict = verdict.trim().trim_start_matches('`').to_ascii_uppercase();
+    let met = verdict
+        .strip_prefix("MET")
+        .is_some_and(|tail| !tail.starts_with(|c: char| c.is_ascii_alphanumeric()));
+    (met || verdict.starts_with("NOT MET")).then_some((number, met))
+}
+
 fn make_context_summary(
     role: &str,
     task_title: &str,
@@ -2386,31 +4415,92 @@ fn default_generated_details(kind: TaskKind) -> &'static str {
         TaskKind::FinalAudit => {
             "Perform a final cross-task audit after all implementation and testing complete."
         }
+        TaskKind::Plugin => "Run the named plugin on this implementation branch.",
         TaskKind::Top => "Top-level task scope and expected outcome.",
     }
 }
 
-fn audit_feedback(transcript: &[String], code: i32, success: bool) -> String {
+fn audit_feedback(
+    transcript: &[String],
+    citations: &[AuditCitation],
+    code: i32,
+    success: bool,
+) -> String {
     if !success {
         return format!(
             "Audit process exited with code {code}; re-run implementation and validate."
         );
     }
     let merged = transcript.join(" ");
-    if merged.trim().is_empty() {
+    let feedback = if merged.trim().is_empty() {
         "Audit requested fixes without detailed notes; review implementation against requirements."
             .to_string()
     } else {
         format!("Audit feedback: {merged}")
+    };
+    if citations.is_empty() {
+        return feedback;
     }
+    let cited = citations
+        .iter()
+        .map(AuditCitation::describe)
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{feedback}\nCited locations:\n{cited}")
 }
 
-fn test_runner_feedback(transcript: &[String], code: i32) -> String {
+fn test_runner_feedback(
+    transcript: &[String],
+    code: i32,
+    results: Option<&TestRunReport>,
+) -> String {
     let merged = transcript.join("\n");
-    if merged.trim().is_empty() {
-        return format!("Deterministic test run failed with code {code} and no output.");
+    let Some(results) = results else {
+        if merged.trim().is_empty() {
+            return format!("Deterministic test run failed with code {code} and no output.");
+        }
+        return format!("Deterministic test run failed with code {code}. Output:\n{merged}");
+    };
+    let mut feedback = format!(
+        "Deterministic test run failed with code {code} ({}).",
+        results.summary()
+    );
+    let failed = results.failed().collect::<Vec<_>>();
+    if results.detailed && !failed.is_empty() {
+        // Structured failures replace the raw output, which mostly repeats them.
+        feedback.push_str("\nFailing tests:");
+        for case in failed {
+            feedback.push_str(&format!("\n- {}", case.name));
+            if let Some(location) = case.location.as_deref() {
+                feedback.push_str(&format!(" at {location}"));
+            }
+            for line in case.message.iter().flat_map(|message| message.lines()) {
+                feedback.push_str(&format!("\n  {line}"));
+            }
+        }
+        return feedback;
+    }
+    if !failed.is_empty() {
+        let names = failed
+            .iter()
+            .map(|case| format!("- {}", case.name))
+            .collect::<Vec<_>>();
+        feedback.push_str(&format!("\nFailing tests:\n{}", names.join("\n")));
+    }
+    if !merged.trim().is_empty() {
+        feedback.push_str(&format!("\nOutput:\n{merged}"));
     }
-    format!("Deterministic test run failed with code {code}. Output:\n{merged}")
+    feedback
+}
+
+fn flaky_test_feedback(transcript: &[String], code: i32) -> String {
+    format!(
+        "The deterministic test run failed with code {code}, then gave a different result when it was re-run, so the tests are flaky.\n\
+         Make them deterministic: remove dependence on timing, sleeps, test order, shared global or filesystem state, randomness, and the network.\n\
+         Do not hide the flakiness by retrying, loosening assertions, or deleting the tests.\n\
+         Output of both runs:\n{}",
+        transcript.join("\n")
+    )
 }
 
 #[cfg(test)]
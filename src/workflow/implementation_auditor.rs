@@ -1,8 +1,8 @@
 use super::WorkerJob;
 use super::Workflow;
-use super::{
-    MAX_AUDIT_RETRIES, TaskStatus, audit_detects_issues, audit_feedback,
-};
+use super::{TaskStatus, audit_blocked_question, audit_detects_issues, audit_feedback};
+use crate::audit_citations::AuditCitation;
+use crate::i18n::{self, Text};
 
 pub(crate) fn build_prompt(
     workflow: &Workflow,
@@ -13,6 +13,19 @@ pub(crate) fn build_prompt(
     changed_files_summary: &Option<String>,
     pass: u8,
 ) -> String {
+    let criteria = workflow.numbered_acceptance_criteria(top_task_id);
+    let (criteria_block, pass_rule, criteria_rule) = match &criteria {
+        Some(criteria) => (
+            format!("Acceptance criteria to verify:\n{criteria}\n"),
+            "- PASS: no additional text after the token except the acceptance criteria lines.\n",
+            "- After the first line, report every acceptance criterion on its own line as `AC<n>: MET` or `AC<n>: NOT MET - <finding>`. PASS requires every criterion MET; a missing criterion counts as NOT MET.\n",
+        ),
+        None => (
+            String::new(),
+            "- PASS: no additional text after the token.\n",
+            "",
+        ),
+    };
     let tests_policy = if workflow.tests_mode_enabled() {
         "Tests mode policy (ON): do not audit test quality/coverage or request test changes in this pass; limit findings to implementation concerns only."
     } else {
@@ -24,11 +37,14 @@ pub(crate) fn build_prompt(
          Parent implementor task: {}\n\
          Parent implementor details:\n{}\n\
          Audit subtask details:\n{}\n\
+         {}\
+         {}\
          Audit pass: {} of {}\n\
          Rolling task context:\n{}\n\
          Implementor changed-files summary:\n{}\n\
          Implementation output to audit:\n{}\n\
          {}\n\
+         {}\
          Scope lock (required): audit only the parent implementor task/details above. Do not audit unrelated tasks, broader roadmap items, or unrelated files.\n\
          Execution guardrail: do not run tests and do not execute/check shell commands. Command/test execution is handled by a subsequent dedicated agent.\n\
          Strictness policy for this audit pass:\n{}\n\
@@ -36,15 +52,21 @@ pub(crate) fn build_prompt(
          - First line must be exactly one of:\n\
            PASS\n\
            FAIL\n\
-         - PASS: no additional text after the token.\n\
+           BLOCKED\n\
+         {}\
          - FAIL: include one or more lines of findings and rationale after the token.\n\
+         - BLOCKED: only when the work cannot go on without input only the user can give (credentials, a design decision); follow the token with the question for the user. The task waits for their answer.\n\
+         - Each FAIL finding must cite the code it is about as a workspace-relative `path:line` or `path:start-end` (e.g. `src/app.rs:120-134`).\n\
+         {}\
          - On pass 4, only FAIL for truly critical blockers that would prevent the broader plan from running.",
         workflow.task_title(top_task_id),
         workflow.node_title(implementor_id, "Implementation"),
         workflow.node_details(implementor_id),
         workflow.node_details(auditor_id),
+        criteria_block,
+        workflow.user_answers_block(top_task_id),
         pass,
-        MAX_AUDIT_RETRIES,
+        workflow.max_audit_retries(),
         workflow.context_block(),
         changed_files_summary
             .as_deref()
@@ -53,7 +75,10 @@ pub(crate) fn build_prompt(
             .as_deref()
             .unwrap_or("(no implementation output captured)"),
         tests_policy,
-        audit_strictness_policy(pass)
+        workflow.audit_rubric_block(),
+        audit_strictness_policy(pass),
+        pass_rule,
+        criteria_rule
     )
 }
 
@@ -66,21 +91,53 @@ pub(crate) fn on_completion(
     implementation_report: Option<String>,
     changed_files_summary: Option<String>,
     transcript: &[String],
+    citations: &[AuditCitation],
     success: bool,
     code: i32,
     messages: &mut Vec<String>,
 ) {
-    let issues = !success || audit_detects_issues(transcript);
-    if issues {
+    if success && let Some(question) = audit_blocked_question(transcript) {
+        workflow.implementor_escalations.remove(&implementor_id);
         workflow.set_status(implementor_id, TaskStatus::NeedsChanges);
-        if pass >= MAX_AUDIT_RETRIES {
+        workflow.set_status(auditor_id, TaskStatus::NeedsChanges);
+        workflow.block_top_task(top_task_id, question, messages);
+        return;
+    }
+    let unmet_criteria = if success {
+        workflow.unmet_acceptance_criteria(top_task_id, transcript)
+    } else {
+        Vec::new()
+    };
+    let audit_failed = !success || audit_detects_issues(transcript);
+    if !audit_failed && !unmet_criteria.is_empty() {
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::AuditPassedWithoutCriteria,
+            &[("task", &top_task_id)],
+        ));
+    }
+    let feedback = || {
+        let feedback = audit_feedback(transcript, citations, code, success);
+        if unmet_criteria.is_empty() {
+            return feedback;
+        }
+        format!(
+            "{feedback}\nAcceptance criteria not confirmed as met:\n- {}",
+            unmet_criteria.join("\n- ")
+        )
+    };
+    if audit_failed || !unmet_criteria.is_empty() {
+        workflow.set_status(implementor_id, TaskStatus::NeedsChanges);
+        let max_retries = workflow.max_audit_retries();
+        if pass >= max_retries {
+            workflow.implementor_escalations.remove(&implementor_id);
             workflow.set_status(auditor_id, TaskStatus::Done);
             workflow.recent_failures.push(super::WorkflowFailure {
                 kind: super::WorkflowFailureKind::Audit,
                 top_task_id,
                 top_task_title: workflow.task_title(top_task_id),
                 attempts: pass,
-                reason: audit_feedback(transcript, code, success),
+                reason: feedback(),
                 action_taken: "Audit retries exhausted; continued execution to next audit/step."
                     .to_string(),
             });
@@ -92,29 +149,41 @@ pub(crate) fn on_completion(
                 changed_files_summary,
                 messages,
             );
-            messages.push(format!(
-                "System: Task #{} audit still found critical blockers at pass {}. Max retries ({}) reached; proceeding to next audit/step.",
-                top_task_id, pass, MAX_AUDIT_RETRIES
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::AuditRetriesExhausted,
+                &[
+                    ("task", &top_task_id),
+                    ("pass", &pass),
+                    ("max", &max_retries),
+                ],
             ));
         } else {
             workflow.set_status(auditor_id, TaskStatus::NeedsChanges);
+            if pass >= 2 {
+                // The same audit failed this implementor again: climb the model ladder.
+                workflow
+                    .implementor_escalations
+                    .insert(implementor_id, pass - 1);
+            }
             workflow.queue.push_back(WorkerJob {
                 top_task_id,
                 kind: super::WorkerJobKind::Implementor {
                     implementor_id,
                     pass: pass.saturating_add(1),
-                    feedback: Some(audit_feedback(transcript, code, success)),
+                    feedback: Some(feedback()),
                     resume_auditor_id: Some(auditor_id),
                     resume_audit_pass: Some(pass.saturating_add(1)),
                 },
             });
-            messages.push(format!(
-                "System: Task #{} audit requested fixes; implementor pass {} queued.",
-                top_task_id,
-                pass.saturating_add(1)
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::AuditRequestedFixes,
+                &[("task", &top_task_id), ("pass", &pass.saturating_add(1))],
             ));
         }
     } else {
+        workflow.implementor_escalations.remove(&implementor_id);
         workflow.set_status(auditor_id, TaskStatus::Done);
         let _ = workflow.queue_next_implementor_audit(
             top_task_id,
@@ -1,7 +1,9 @@
-use super::Workflow;
+use super::{DOCS_UPDATE_TASK_ID, TaskKind, Workflow, implementation_auditor};
 use super::{
     TaskStatus, WorkerJob, WorkerJobKind, extract_changed_files_summary, make_context_summary,
+    parse_changed_file_paths,
 };
+use crate::i18n::{self, Text};
 
 pub(crate) fn build_prompt(
     workflow: &Workflow,
@@ -19,6 +21,10 @@ pub(crate) fn build_prompt(
          Top-level task: {}\n\
          Implementation subtask: {}\n\
          Implementation details:\n{}\n\
+         {}\
+         {}\
+         {}\
+         {}\
          Rolling task context:\n{}\n\
          {}\n\
          {}\n\
@@ -31,6 +37,10 @@ pub(crate) fn build_prompt(
         workflow.task_title(top_task_id),
         workflow.node_title(implementor_id, "Implementation"),
         workflow.node_details(implementor_id),
+        acceptance_criteria_block(workflow, top_task_id),
+        workflow.user_answers_block(top_task_id),
+        docs_update_block(workflow, top_task_id),
+        protected_paths_block(workflow),
         workflow.context_block(),
         feedback
             .as_ref()
@@ -40,6 +50,65 @@ pub(crate) fn build_prompt(
     )
 }
 
+fn acceptance_criteria_block(workflow: &Workflow, top_task_id: u64) -> String {
+    let Some(criteria) = workflow.numbered_acceptance_criteria(top_task_id) else {
+        return String::new();
+    };
+    format!(
+        "Acceptance criteria (address every one; the audit checks each):\n{criteria}\n\
+         Before the changed-files summary, add one line per criterion: `AC<n>: <how the change meets it>`, or why it cannot be met.\n"
+    )
+}
+
+fn protected_paths_block(workflow: &Workflow) -> String {
+    let patterns = workflow.protected_paths().patterns();
+    if patterns.is_empty() {
+        return String::new();
+    }
+    format!(
+        "Protected paths (do not create, modify, or delete matching files; doing so fails the audit): {}\n",
+        patterns.join(", ")
+    )
+}
+
+/// For the docs-update task, the other top-level tasks and every file changed this session,
+/// so the README and CHANGELOG edits cover what actually happened.
+fn docs_update_block(workflow: &Workflow, top_task_id: u64) -> String {
+    if workflow.top_task_external_id_by_id(top_task_id).as_deref() != Some(DOCS_UPDATE_TASK_ID) {
+        return String::new();
+    }
+    let tasks = workflow
+        .tasks
+        .iter()
+        .filter(|task| task.kind == TaskKind::Top && task.id != top_task_id)
+        .map(|task| {
+            let status = if task.status == TaskStatus::Done {
+                "done"
+            } else {
+                "not finished"
+            };
+            format!("- {} ({status})", task.title)
+        })
+        .collect::<Vec<_>>();
+    let files = workflow
+        .changed_files()
+        .iter()
+        .map(|path| format!("- {path}"))
+        .collect::<Vec<_>>();
+    let list = |lines: Vec<String>, empty: &str| {
+        if lines.is_empty() {
+            empty.to_string()
+        } else {
+            lines.join("\n")
+        }
+    };
+    format!(
+        "Session tasks:\n{}\nFiles changed this session:\n{}\n",
+        list(tasks, "- (no other tasks)"),
+        list(files, "- (no changed files were reported)")
+    )
+}
+
 pub(crate) fn on_completion(
     workflow: &mut Workflow,
     top_task_id: u64,
@@ -48,6 +117,7 @@ pub(crate) fn on_completion(
     resume_auditor_id: Option<u64>,
     resume_audit_pass: Option<u8>,
     transcript: &[String],
+    worktree_changes: &[String],
     success: bool,
     code: i32,
     messages: &mut Vec<String>,
@@ -60,10 +130,24 @@ pub(crate) fn on_completion(
     ));
 
     if success {
+        workflow.record_changed_files(&extract_changed_files_summary(transcript));
         // Mark implementation pass complete before moving into audit. If an audit fails,
         // status is set back to NeedsChanges and implementor retries.
         workflow.set_status(implementor_id, TaskStatus::Done);
-        if let Some(auditor_id) = resume_auditor_id {
+        let violations = protected_path_violations(workflow, transcript, worktree_changes);
+        if !violations.is_empty() {
+            fail_audit_for_protected_paths(
+                workflow,
+                top_task_id,
+                implementor_id,
+                pass,
+                resume_auditor_id,
+                resume_audit_pass,
+                transcript,
+                &violations,
+                messages,
+            );
+        } else if let Some(auditor_id) = resume_auditor_id {
             workflow.queue.push_back(WorkerJob {
                 top_task_id,
                 kind: WorkerJobKind::Auditor {
@@ -74,9 +158,10 @@ pub(crate) fn on_completion(
                     changed_files_summary: Some(extract_changed_files_summary(transcript)),
                 },
             });
-            messages.push(format!(
-                "System: Task #{} implementation pass {} complete; resumed audit queued.",
-                top_task_id, pass
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::ImplementationResumedAudit,
+                &[("task", &top_task_id), ("pass", &pass)],
             ));
         } else {
             if workflow
@@ -110,9 +195,126 @@ pub(crate) fn on_completion(
                 resume_audit_pass: None,
             },
         });
-        messages.push(format!(
-            "System: Task #{} implementation failed (code {}); retry queued.",
-            top_task_id, code
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::ImplementationFailedRetry,
+            &[("task", &top_task_id), ("code", &code)],
         ));
     }
 }
+
+/// Protected files the pass listed under FILES_CHANGED or that git shows it touched.
+fn protected_path_violations(
+    workflow: &Workflow,
+    transcript: &[String],
+    worktree_changes: &[String],
+) -> Vec<String> {
+    let reported = parse_changed_file_paths(&extract_changed_files_summary(transcript));
+    workflow
+        .protected_paths()
+        .violations(reported.iter().chain(worktree_changes).map(String::as_str))
+        .into_iter()
+        .map(|violation| format!("{} ({})", violation.path, violation.pattern))
+        .collect()
+}
+
+/// Fails the pass's audit without running the auditor, so the retry, escalation, and
+/// retry-limit handling of a failed audit apply to protected-path violations too.
+#[allow(clippy::too_many_arguments)]
+fn fail_audit_for_protected_paths(
+    workflow: &mut Workflow,
+    top_task_id: u64,
+    implementor_id: u64,
+    pass: u8,
+    resume_auditor_id: Option<u64>,
+    resume_audit_pass: Option<u8>,
+    transcript: &[String],
+    violations: &[String],
+    messages: &mut Vec<String>,
+) {
+    let (auditor_id, audit_pass) = match resume_auditor_id {
+        Some(auditor_id) => (Some(auditor_id), resume_audit_pass.unwrap_or(1)),
+        None => (
+            workflow
+                .find_next_pending_child_kind(implementor_id, TaskKind::Auditor)
+                .or_else(|| {
+                    workflow.find_or_create_child_kind(implementor_id, TaskKind::Auditor, "Audit")
+                }),
+            pass,
+        ),
+    };
+    let Some(auditor_id) = auditor_id else {
+        return;
+    };
+    let paths = violations.join(", ");
+    messages.push(i18n::system(
+        workflow.locale,
+        Text::ProtectedPathsModified,
+        &[("task", &top_task_id), ("pass", &pass), ("paths", &paths)],
+    ));
+    let verdict = [
+        "FAIL".to_string(),
+        format!(
+            "Protected-path violation: this pass modified {paths}, which workers may not change. Revert those changes and complete the task without touching protected paths."
+        ),
+    ];
+    implementation_auditor::on_completion(
+        workflow,
+        top_task_id,
+        implementor_id,
+        auditor_id,
+        audit_pass,
+        Some(transcript.join("\n")),
+        Some(extract_changed_files_summary(transcript)),
+        &verdict,
+        &[],
+        true,
+        0,
+        messages,
+    );
+}
+
+/// Queues the next pass after the user rejected or reverted a reviewed implementor pass.
+pub(crate) fn on_review_rejected(
+    workflow: &mut Workflow,
+    job: WorkerJob,
+    feedback: String,
+    messages: &mut Vec<String>,
+) {
+    let top_task_id = job.top_task_id;
+    let WorkerJobKind::Implementor {
+        implementor_id,
+        pass,
+        resume_auditor_id,
+        resume_audit_pass,
+        ..
+    } = job.kind
+    else {
+        return;
+    };
+    workflow.push_context(format!(
+        "Implementor worked on \"{}\" but the user sent pass {} back in review.",
+        workflow.task_title(top_task_id),
+        pass
+    ));
+    workflow.set_status(implementor_id, TaskStatus::NeedsChanges);
+    workflow.queue.push_back(WorkerJob {
+        top_task_id,
+        kind: WorkerJobKind::Implementor {
+            implementor_id,
+            pass: pass.saturating_add(1),
+            feedback: Some(feedback),
+            resume_auditor_id,
+            resume_audit_pass,
+        },
+    });
+    messages.push(i18n::system(
+        workflow.locale,
+        Text::ReviewSentBack,
+        &[
+            ("task", &top_task_id),
+            ("pass", &pass),
+            ("next_pass", &pass.saturating_add(1)),
+        ],
+    ));
+}
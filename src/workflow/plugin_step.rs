@@ -0,0 +1,141 @@
+use super::{JobRun, TaskStatus, WorkerJob, WorkerJobKind, Workflow, find_node};
+use crate::i18n::{self, Text};
+use crate::plugins::{PluginCapability, PluginTask};
+
+/// The plugin run for a `plugin` task: its details as input, and the implementation branch
+/// it checks as the task.
+pub(crate) fn job_run(
+    workflow: &Workflow,
+    top_task_id: u64,
+    implementor_id: u64,
+    plugin_step_id: u64,
+) -> JobRun {
+    let step = find_node(&workflow.tasks, plugin_step_id);
+    let plugin = step
+        .and_then(|node| node.plugin.clone())
+        .unwrap_or_default();
+    let implementor = find_node(&workflow.tasks, implementor_id);
+    JobRun::PluginStep {
+        program: workflow
+            .plugins
+            .get_for(&plugin, PluginCapability::Step)
+            .ok()
+            .map(|installed| installed.program.clone()),
+        plugin,
+        input: step.map(|node| node.details.clone()).unwrap_or_default(),
+        task: PluginTask {
+            id: implementor
+                .and_then(|node| node.external_id.clone())
+                .unwrap_or_else(|| format!("internal-{implementor_id}")),
+            title: workflow.node_title(implementor_id, "Implementation"),
+            details: implementor
+                .map(|node| node.details.clone())
+                .unwrap_or_default(),
+            top_task_title: workflow.task_title(top_task_id),
+            changed_files: workflow.changed_files().iter().cloned().collect(),
+        },
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn on_completion(
+    workflow: &mut Workflow,
+    top_task_id: u64,
+    implementor_id: u64,
+    plugin_step_id: u64,
+    pass: u8,
+    transcript: &[String],
+    success: bool,
+    code: i32,
+    messages: &mut Vec<String>,
+) {
+    let name = find_node(&workflow.tasks, plugin_step_id)
+        .and_then(|node| node.plugin.clone())
+        .unwrap_or_default();
+    if success {
+        workflow.set_status(plugin_step_id, TaskStatus::Done);
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::PluginStepPassed,
+            &[
+                ("top_task_id", &top_task_id),
+                ("name", &name),
+                ("pass", &pass),
+            ],
+        ));
+        let _ = workflow.queue_next_implementor_audit(
+            top_task_id,
+            implementor_id,
+            pass,
+            None,
+            None,
+            messages,
+        );
+        return;
+    }
+
+    let feedback = plugin_feedback(&name, transcript, code);
+    if pass >= super::MAX_TEST_RETRIES {
+        workflow.set_status(plugin_step_id, TaskStatus::Done);
+        workflow.recent_failures.push(super::WorkflowFailure {
+            kind: super::WorkflowFailureKind::Audit,
+            top_task_id,
+            top_task_title: workflow.task_title(top_task_id),
+            attempts: pass,
+            reason: feedback,
+            action_taken: format!("Plugin {name} retries exhausted; continued to next step."),
+        });
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::PluginStepRetriesExhausted,
+            &[
+                ("top_task_id", &top_task_id),
+                ("name", &name),
+                ("pass", &pass),
+                ("max", &super::MAX_TEST_RETRIES),
+            ],
+        ));
+        let _ = workflow.queue_next_implementor_audit(
+            top_task_id,
+            implementor_id,
+            pass,
+            None,
+            None,
+            messages,
+        );
+        return;
+    }
+    workflow.set_status(plugin_step_id, TaskStatus::NeedsChanges);
+    workflow.set_status(implementor_id, TaskStatus::NeedsChanges);
+    workflow.queue.push_back(WorkerJob {
+        top_task_id,
+        kind: WorkerJobKind::Implementor {
+            implementor_id,
+            pass: pass.saturating_add(1),
+            feedback: Some(feedback),
+            resume_auditor_id: None,
+            resume_audit_pass: None,
+        },
+    });
+    messages.push(i18n::system(
+        workflow.locale,
+        Text::PluginStepFailed,
+        &[
+            ("top_task_id", &top_task_id),
+            ("name", &name),
+            ("next_pass", &pass.saturating_add(1)),
+        ],
+    ));
+}
+
+fn plugin_feedback(name: &str, transcript: &[String], code: i32) -> String {
+    let merged = transcript.join("\n");
+    if merged.trim().is_empty() {
+        return format!("Plugin {name} failed with code {code} and no output.");
+    }
+    format!("Plugin {name} failed with code {code}. Output:\n{merged}")
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/plugin_step_tests.rs"]
+mod tests;
@@ -1,6 +1,8 @@
 use super::WorkerJob;
 use super::Workflow;
 use super::{TaskStatus, audit_detects_issues, audit_feedback};
+use crate::audit_citations::AuditCitation;
+use crate::i18n::{self, Text};
 
 pub(crate) fn build_prompt(
     workflow: &Workflow,
@@ -25,6 +27,7 @@ pub(crate) fn build_prompt(
          Rolling task context:\n{}\n\
          Test-writer output to audit:\n{}\n\
          {}\n\
+         {}\
          Execution guardrail: do not run tests and do not execute/check shell commands. Command/test execution is handled by a subsequent dedicated agent.\n\
          Strictness policy for this audit pass:\n{}\n\
          Response protocol (required):\n\
@@ -33,18 +36,20 @@ pub(crate) fn build_prompt(
            FAIL\n\
          - PASS: no additional text after the token.\n\
          - FAIL: include one or more lines of findings and rationale after the token.\n\
+         - Each FAIL finding must cite the code it is about as a workspace-relative `path:line` or `path:start-end` (e.g. `src/app.rs:120-134`).\n\
          - On pass 4, only FAIL for truly critical blockers that would prevent the broader plan from running.",
         workflow.task_title(top_task_id),
         workflow.node_title(test_writer_id, "Test Writing"),
         workflow.node_details(test_writer_id),
         workflow.node_details(auditor_id),
         pass,
-        super::MAX_AUDIT_RETRIES,
+        workflow.max_audit_retries(),
         workflow.context_block(),
         test_report
             .as_deref()
             .unwrap_or("(no test-writer output captured)"),
         tests_policy,
+        workflow.audit_rubric_block(),
         audit_strictness_policy(pass),
     )
 }
@@ -57,6 +62,7 @@ pub(crate) fn on_completion(
     pass: u8,
     test_report: Option<String>,
     transcript: &[String],
+    citations: &[AuditCitation],
     success: bool,
     code: i32,
     messages: &mut Vec<String>,
@@ -66,22 +72,24 @@ pub(crate) fn on_completion(
         if super::ENFORCE_TESTS_MODE_RUNTIME_GATING && !workflow.tests_mode_enabled() {
             workflow.set_status(auditor_id, TaskStatus::Done);
             workflow.set_status(test_writer_id, TaskStatus::Done);
-            messages.push(format!(
-                "System: Task #{} tests mode is OFF; skipping test-writer audit retries.",
-                top_task_id
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestsOffSkippingTestAuditRetries,
+                &[("task", &top_task_id)],
             ));
             workflow.try_mark_top_done(top_task_id, messages);
             return;
         }
         workflow.set_status(test_writer_id, TaskStatus::NeedsChanges);
-        if pass >= super::MAX_AUDIT_RETRIES {
+        let max_retries = workflow.max_audit_retries();
+        if pass >= max_retries {
             workflow.set_status(auditor_id, TaskStatus::Done);
             workflow.recent_failures.push(super::WorkflowFailure {
                 kind: super::WorkflowFailureKind::Audit,
                 top_task_id,
                 top_task_title: workflow.task_title(top_task_id),
                 attempts: pass,
-                reason: audit_feedback(transcript, code, success),
+                reason: audit_feedback(transcript, citations, code, success),
                 action_taken:
                     "Test-writer audit retries exhausted; continued to deterministic test run."
                         .to_string(),
@@ -94,9 +102,14 @@ pub(crate) fn on_completion(
                 test_report,
                 messages,
             );
-            messages.push(format!(
-                "System: Task #{} test-writer audit still found critical blockers at pass {}. Max retries ({}) reached; proceeding to deterministic tests.",
-                top_task_id, pass, super::MAX_AUDIT_RETRIES
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestAuditRetriesExhausted,
+                &[
+                    ("task", &top_task_id),
+                    ("pass", &pass),
+                    ("max", &max_retries),
+                ],
             ));
         } else {
             workflow.set_status(auditor_id, TaskStatus::NeedsChanges);
@@ -105,16 +118,16 @@ pub(crate) fn on_completion(
                 kind: super::WorkerJobKind::TestWriter {
                     test_writer_id,
                     pass: pass.saturating_add(1),
-                    feedback: Some(audit_feedback(transcript, code, success)),
+                    feedback: Some(audit_feedback(transcript, citations, code, success)),
                     skip_test_runner_on_success: false,
                     resume_auditor_id: Some(auditor_id),
                     resume_audit_pass: Some(pass.saturating_add(1)),
                 },
             });
-            messages.push(format!(
-                "System: Task #{} test-writer audit requested fixes; test-writer pass {} queued.",
-                top_task_id,
-                pass.saturating_add(1)
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestAuditRequestedFixes,
+                &[("task", &top_task_id), ("pass", &pass.saturating_add(1))],
             ));
         }
     } else {
@@ -127,9 +140,10 @@ pub(crate) fn on_completion(
             test_report,
             messages,
         );
-        messages.push(format!(
-            "System: Task #{} test-writer audit pass {} complete.",
-            top_task_id, pass
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::TestAuditPassComplete,
+            &[("task", &top_task_id), ("pass", &pass)],
         ));
     }
 }
@@ -1,5 +1,7 @@
-use super::Workflow;
-use super::{TaskStatus, test_runner_feedback};
+use super::{TaskStatus, flaky_test_feedback, test_runner_feedback};
+use super::{Workflow, verify_step};
+use crate::i18n::{self, Text};
+use crate::test_runners::TestRunReport;
 
 pub(crate) fn on_writer_completion(
     workflow: &mut Workflow,
@@ -8,33 +10,48 @@ pub(crate) fn on_writer_completion(
     test_runner_id: u64,
     pass: u8,
     transcript: &[String],
+    results: Option<&TestRunReport>,
     success: bool,
     code: i32,
+    flaky: bool,
     messages: &mut Vec<String>,
 ) {
     workflow.set_status(test_runner_id, TaskStatus::Done);
 
     if success {
         workflow.set_status(test_writer_id, TaskStatus::Done);
-        messages.push(format!(
-            "System: Task #{} deterministic tests passed on run {}.",
-            top_task_id, pass
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::DeterministicTestsPassed,
+            &[("task", &top_task_id), ("pass", &pass)],
         ));
         workflow.try_mark_top_done(top_task_id, messages);
     } else {
         if super::ENFORCE_TESTS_MODE_RUNTIME_GATING && !workflow.tests_mode_enabled() {
             workflow.set_status(test_runner_id, TaskStatus::Done);
             workflow.set_status(test_writer_id, TaskStatus::Done);
-            messages.push(format!(
-                "System: Task #{} tests mode is OFF; skipping deterministic test-runner retries.",
-                top_task_id
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestsOffSkippingTestRunnerRetries,
+                &[("task", &top_task_id)],
             ));
             workflow.try_mark_top_done(top_task_id, messages);
             return;
         }
         workflow.set_status(test_writer_id, TaskStatus::NeedsChanges);
+        if flaky {
+            record_flaky_failure(
+                workflow,
+                top_task_id,
+                pass,
+                transcript,
+                results,
+                code,
+                "Sent the test writer flaky-test feedback.",
+            );
+        }
         if pass >= super::MAX_TEST_RETRIES {
-            let failure_reason = test_runner_feedback(transcript, code);
+            let failure_reason = test_runner_feedback(transcript, code, results);
             workflow.recent_failures.push(super::WorkflowFailure {
                 kind: super::WorkflowFailureKind::Test,
                 top_task_id,
@@ -62,26 +79,41 @@ pub(crate) fn on_writer_completion(
                     resume_audit_pass: None,
                 },
             });
-            messages.push(format!(
-                "System: Task #{} tests still failing at pass {}. Max retries ({}) reached; queued cleanup removal pass.",
-                top_task_id, pass, super::MAX_TEST_RETRIES
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestsRetriesExhaustedCleanup,
+                &[
+                    ("task", &top_task_id),
+                    ("pass", &pass),
+                    ("max", &super::MAX_TEST_RETRIES),
+                ],
             ));
         } else {
+            let feedback = if flaky {
+                flaky_test_feedback(transcript, code)
+            } else {
+                test_runner_feedback(transcript, code, results)
+            };
             workflow.queue.push_back(super::WorkerJob {
                 top_task_id,
                 kind: super::WorkerJobKind::TestWriter {
                     test_writer_id,
                     pass: pass.saturating_add(1),
-                    feedback: Some(test_runner_feedback(transcript, code)),
+                    feedback: Some(feedback),
                     skip_test_runner_on_success: false,
                     resume_auditor_id: None,
                     resume_audit_pass: None,
                 },
             });
-            messages.push(format!(
-                "System: Task #{} tests failed; test-writer pass {} queued.",
-                top_task_id,
-                pass.saturating_add(1)
+            let key = if flaky {
+                Text::TestsFlakyWriterQueued
+            } else {
+                Text::TestsFailedWriterQueued
+            };
+            messages.push(i18n::system(
+                workflow.locale,
+                key,
+                &[("task", &top_task_id), ("pass", &pass.saturating_add(1))],
             ));
         }
     }
@@ -94,30 +126,48 @@ pub(crate) fn on_implementor_completion(
     test_runner_id: u64,
     pass: u8,
     transcript: &[String],
+    results: Option<&TestRunReport>,
     success: bool,
     code: i32,
+    flaky: bool,
     messages: &mut Vec<String>,
 ) {
     if success {
         workflow.set_status(test_runner_id, TaskStatus::Done);
+        if verify_step::queue_if_configured(workflow, top_task_id, implementor_id, pass, messages) {
+            return;
+        }
         workflow.set_status(implementor_id, TaskStatus::Done);
         workflow.try_mark_top_done(top_task_id, messages);
-        messages.push(format!(
-            "System: Task #{} existing-test runner passed on run {}; implementor branch complete.",
-            top_task_id, pass
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::ExistingTestsPassed,
+            &[("task", &top_task_id), ("pass", &pass)],
         ));
     } else {
         if super::ENFORCE_TESTS_MODE_RUNTIME_GATING && !workflow.tests_mode_enabled() {
             workflow.set_status(test_runner_id, TaskStatus::Done);
             workflow.set_status(implementor_id, TaskStatus::Done);
-            messages.push(format!(
-                "System: Task #{} tests mode is OFF; skipping existing-test runner retries.",
-                top_task_id
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestsOffSkippingExistingTestRetries,
+                &[("task", &top_task_id)],
             ));
             workflow.try_mark_top_done(top_task_id, messages);
             return;
         }
         workflow.set_status(test_runner_id, TaskStatus::NeedsChanges);
+        if flaky {
+            record_flaky_failure(
+                workflow,
+                top_task_id,
+                pass,
+                transcript,
+                results,
+                code,
+                "Existing tests are flaky; handled like a failed run.",
+            );
+        }
         if pass >= super::MAX_TEST_RETRIES {
             workflow.set_status(test_runner_id, TaskStatus::Done);
             workflow.recent_failures.push(super::WorkflowFailure {
@@ -125,15 +175,29 @@ pub(crate) fn on_implementor_completion(
                 top_task_id,
                 top_task_title: workflow.task_title(top_task_id),
                 attempts: pass,
-                reason: test_runner_feedback(transcript, code),
+                reason: test_runner_feedback(transcript, code, results),
                 action_taken: "Existing-tests runner retries exhausted; continued to next step."
                     .to_string(),
             });
+            if verify_step::queue_if_configured(
+                workflow,
+                top_task_id,
+                implementor_id,
+                pass,
+                messages,
+            ) {
+                return;
+            }
             workflow.set_status(implementor_id, TaskStatus::Done);
             workflow.try_mark_top_done(top_task_id, messages);
-            messages.push(format!(
-                "System: Task #{} existing tests still failing at pass {}. Max retries ({}) reached; proceeding to next step.",
-                top_task_id, pass, super::MAX_TEST_RETRIES
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::ExistingTestsRetriesExhausted,
+                &[
+                    ("task", &top_task_id),
+                    ("pass", &pass),
+                    ("max", &super::MAX_TEST_RETRIES),
+                ],
             ));
         } else {
             workflow.set_status(implementor_id, TaskStatus::NeedsChanges);
@@ -142,16 +206,36 @@ pub(crate) fn on_implementor_completion(
                 kind: super::WorkerJobKind::Implementor {
                     implementor_id,
                     pass: pass.saturating_add(1),
-                    feedback: Some(test_runner_feedback(transcript, code)),
+                    feedback: Some(test_runner_feedback(transcript, code, results)),
                     resume_auditor_id: None,
                     resume_audit_pass: None,
                 },
             });
-            messages.push(format!(
-                "System: Task #{} existing tests failed; implementor pass {} queued.",
-                top_task_id,
-                pass.saturating_add(1)
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::ExistingTestsFailed,
+                &[("task", &top_task_id), ("pass", &pass.saturating_add(1))],
             ));
         }
     }
 }
+
+/// Logs a flaky run to `task-fails.json` apart from exhausted test retries.
+fn record_flaky_failure(
+    workflow: &mut Workflow,
+    top_task_id: u64,
+    pass: u8,
+    transcript: &[String],
+    results: Option<&TestRunReport>,
+    code: i32,
+    action_taken: &str,
+) {
+    workflow.recent_failures.push(super::WorkflowFailure {
+        kind: super::WorkflowFailureKind::FlakyTest,
+        top_task_id,
+        top_task_title: workflow.task_title(top_task_id),
+        attempts: pass,
+        reason: test_runner_feedback(transcript, code, results),
+        action_taken: action_taken.to_string(),
+    });
+}
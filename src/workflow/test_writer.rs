@@ -1,4 +1,5 @@
 use super::{MAX_TEST_RETRIES, TaskStatus, WorkerJob, Workflow, make_context_summary};
+use crate::i18n::{self, Text};
 
 pub(crate) fn build_prompt(
     workflow: &Workflow,
@@ -71,9 +72,10 @@ pub(crate) fn on_completion(
     if success {
         if skip_test_runner_on_success {
             workflow.set_status(test_writer_id, TaskStatus::Done);
-            messages.push(format!(
-                "System: Task #{} removed failing tests after retries and proceeded.",
-                top_task_id
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::FailingTestsRemoved,
+                &[("task", &top_task_id)],
             ));
             workflow.try_mark_top_done(top_task_id, messages);
             return;
@@ -88,9 +90,10 @@ pub(crate) fn on_completion(
                     test_report: Some(transcript.join("\n")),
                 },
             });
-            messages.push(format!(
-                "System: Task #{} test-writer pass {} complete; resumed audit queued.",
-                top_task_id, pass
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestWriterResumedAudit,
+                &[("task", &top_task_id), ("pass", &pass)],
             ));
         } else {
             workflow.queue_test_writer_next_step(
@@ -105,9 +108,10 @@ pub(crate) fn on_completion(
     } else {
         if super::ENFORCE_TESTS_MODE_RUNTIME_GATING && !workflow.tests_mode_enabled() {
             workflow.set_status(test_writer_id, TaskStatus::Done);
-            messages.push(format!(
-                "System: Task #{} tests mode is OFF; skipping test-writer retries.",
-                top_task_id
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestsOffSkippingTestWriterRetries,
+                &[("task", &top_task_id)],
             ));
             workflow.try_mark_top_done(top_task_id, messages);
             return;
@@ -124,9 +128,14 @@ pub(crate) fn on_completion(
                 action_taken: "Test-writer retries exhausted; proceeded without adding tests."
                     .to_string(),
             });
-            messages.push(format!(
-                "System: Task #{} test-writer still failing at pass {}. Max retries ({}) reached; proceeding to next step.",
-                top_task_id, pass, MAX_TEST_RETRIES
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestWriterRetriesExhausted,
+                &[
+                    ("task", &top_task_id),
+                    ("pass", &pass),
+                    ("max", &MAX_TEST_RETRIES),
+                ],
             ));
             workflow.try_mark_top_done(top_task_id, messages);
         } else {
@@ -141,9 +150,10 @@ pub(crate) fn on_completion(
                     resume_audit_pass: None,
                 },
             });
-            messages.push(format!(
-                "System: Task #{} test-writer failed (code {}); retry queued.",
-                top_task_id, code
+            messages.push(i18n::system(
+                workflow.locale,
+                Text::TestWriterFailedRetry,
+                &[("task", &top_task_id), ("code", &code)],
             ));
         }
     }
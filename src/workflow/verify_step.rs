@@ -0,0 +1,129 @@
+use super::{TaskKind, TaskStatus, WorkerJob, WorkerJobKind, Workflow};
+use crate::i18n::{self, Text};
+
+/// Queues the verify gate for an implementation branch whose audits and tests are done.
+/// Returns false when neither the top-level task nor config declares a verify command, or
+/// when safe mode skips it.
+pub(crate) fn queue_if_configured(
+    workflow: &mut Workflow,
+    top_task_id: u64,
+    implementor_id: u64,
+    pass: u8,
+    messages: &mut Vec<String>,
+) -> bool {
+    if workflow.verify_command(top_task_id).is_none() {
+        return false;
+    }
+    if workflow.safe_mode {
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::VerifySkippedSafeMode,
+            &[("top_task_id", &top_task_id)],
+        ));
+        return false;
+    }
+    let attempt = workflow
+        .verify_attempts
+        .entry(implementor_id)
+        .and_modify(|attempt| *attempt = attempt.saturating_add(1))
+        .or_insert(1);
+    let attempt = *attempt;
+    workflow.queue.push_back(WorkerJob {
+        top_task_id,
+        kind: WorkerJobKind::Verify {
+            implementor_id,
+            pass,
+            attempt,
+        },
+    });
+    messages.push(i18n::system(
+        workflow.locale,
+        Text::VerifyQueued,
+        &[("top_task_id", &top_task_id), ("attempt", &attempt)],
+    ));
+    true
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn on_completion(
+    workflow: &mut Workflow,
+    top_task_id: u64,
+    implementor_id: u64,
+    pass: u8,
+    attempt: u8,
+    transcript: &[String],
+    success: bool,
+    code: i32,
+    messages: &mut Vec<String>,
+) {
+    if success {
+        workflow.set_status(implementor_id, TaskStatus::Done);
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::VerifyPassed,
+            &[("top_task_id", &top_task_id), ("attempt", &attempt)],
+        ));
+        workflow.try_mark_top_done(top_task_id, messages);
+        return;
+    }
+
+    let feedback = verify_feedback(transcript, code);
+    let max_retries = workflow.verify_gate.max_retries;
+    if attempt > max_retries {
+        workflow.recent_failures.push(super::WorkflowFailure {
+            kind: super::WorkflowFailureKind::Test,
+            top_task_id,
+            top_task_title: workflow.task_title(top_task_id),
+            attempts: attempt,
+            reason: feedback,
+            action_taken: "Verify command retries exhausted; continued to next step.".to_string(),
+        });
+        workflow.set_status(implementor_id, TaskStatus::Done);
+        messages.push(i18n::system(
+            workflow.locale,
+            Text::VerifyRetriesExhausted,
+            &[
+                ("top_task_id", &top_task_id),
+                ("attempt", &attempt),
+                ("max_retries", &max_retries),
+            ],
+        ));
+        workflow.try_mark_top_done(top_task_id, messages);
+        return;
+    }
+    // The fix goes through the existing-test runner again before the next verify run.
+    if let Some(test_runner_id) = workflow.find_child_kind(implementor_id, TaskKind::TestRunner) {
+        workflow.set_status(test_runner_id, TaskStatus::NeedsChanges);
+    }
+    workflow.set_status(implementor_id, TaskStatus::NeedsChanges);
+    workflow.queue.push_back(WorkerJob {
+        top_task_id,
+        kind: WorkerJobKind::Implementor {
+            implementor_id,
+            pass: pass.saturating_add(1),
+            feedback: Some(feedback),
+            resume_auditor_id: None,
+            resume_audit_pass: None,
+        },
+    });
+    messages.push(i18n::system(
+        workflow.locale,
+        Text::VerifyFailed,
+        &[
+            ("top_task_id", &top_task_id),
+            ("pass", &pass.saturating_add(1)),
+        ],
+    ));
+}
+
+fn verify_feedback(transcript: &[String], code: i32) -> String {
+    let merged = transcript.join("\n");
+    if merged.trim().is_empty() {
+        return format!("Verify command failed with code {code} and no output.");
+    }
+    format!("Verify command failed with code {code}. Output:\n{merged}")
+}
+
+#[cfg(test)]
+#[path = "../../tests/unit/verify_step_tests.rs"]
+mod tests;
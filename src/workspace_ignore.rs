@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::Path;
+
+use crate::protected_paths::{segment_matches, segments_match};
+
+/// Workspace-root file listing paths agents should never see, in `.gitignore` syntax.
+pub const METAAGENT_IGNORE_FILE_NAME: &str = ".metaagentignore";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IgnoreRule {
+    /// Path segments for an anchored rule; a single name glob otherwise.
+    segments: Vec<String>,
+    anchored: bool,
+    dir_only: bool,
+    negated: bool,
+}
+
+/// Rules from the workspace root's `.gitignore` and `.metaagentignore`, read in that order,
+/// so a `!` line in `.metaagentignore` can bring back a git-ignored path.
+///
+/// Follows `.gitignore` rules: a pattern without a `/` (other than a trailing one) matches
+/// a name at any depth, a trailing `/` matches only directories, `**` spans segments, and
+/// the last matching line wins. Everything below an ignored directory stays ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceIgnore {
+    rules: Vec<IgnoreRule>,
+    metaagent_patterns: Vec<String>,
+}
+
+impl WorkspaceIgnore {
+    /// Reads both files under `root`; a missing or unreadable file adds no rules.
+    pub fn load(root: &Path) -> Self {
+        let read = |name: &str| fs::read_to_string(root.join(name)).unwrap_or_default();
+        Self::from_texts(&read(".gitignore"), &read(METAAGENT_IGNORE_FILE_NAME))
+    }
+
+    pub fn from_texts(gitignore: &str, metaagentignore: &str) -> Self {
+        let metaagent_patterns = pattern_lines(metaagentignore);
+        let rules = pattern_lines(gitignore)
+            .iter()
+            .chain(&metaagent_patterns)
+            .filter_map(|line| parse_rule(line))
+            .collect();
+        Self {
+            rules,
+            metaagent_patterns,
+        }
+    }
+
+    /// The `.metaagentignore` lines, for prompts that ask an agent to skip those paths.
+    pub fn metaagent_patterns(&self) -> &[String] {
+        &self.metaagent_patterns
+    }
+
+    /// Whether the workspace-relative `path`, or a directory above it, is ignored.
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+        let path = path.trim().trim_start_matches("./");
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        (1..=segments.len()).any(|depth| {
+            let ancestor_is_dir = depth < segments.len() || is_dir;
+            self.matches(&segments[..depth], ancestor_is_dir)
+        })
+    }
+
+    fn matches(&self, segments: &[&str], is_dir: bool) -> bool {
+        let Some(name) = segments.last() else {
+            return false;
+        };
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let hit = if rule.anchored {
+                let pattern = rule.segments.iter().map(String::as_str).collect::<Vec<_>>();
+                segments_match(&pattern, segments)
+            } else {
+                segment_matches(&rule.segments[0], name)
+            };
+            if hit {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+fn pattern_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_rule(line: &str) -> Option<IgnoreRule> {
+    let (negated, pattern) = match line.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    let (dir_only, pattern) = match pattern.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    let anchored = pattern.contains('/');
+    let segments = pattern
+        .trim_start_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    (!segments.is_empty()).then_some(IgnoreRule {
+        segments,
+        anchored,
+        dir_only,
+        negated,
+    })
+}
+
+#[cfg(test)]
+#[path = "../tests/unit/workspace_ignore_tests.rs"]
+mod tests;
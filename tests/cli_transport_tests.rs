@@ -1,4 +1,4 @@
-use serde_json::Value;
+use serde_json::{Value, json};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
@@ -173,7 +173,7 @@ fn capability_list_human_output_lists_entries() {
     assert!(stdout.contains("Listed "));
     assert!(stdout.contains("app_prompt_preparation"));
     assert!(stdout.contains("session_planner_storage"));
-    assert_eq!(stdout.matches("\n  - ").count(), 12);
+    assert_eq!(stdout.matches("\n  - ").count(), 16);
     assert!(
         !stdout.trim_start().starts_with('{'),
         "human output should not be a JSON envelope"
@@ -216,6 +216,54 @@ fn workflow_validate_tasks_human_output_is_compact_by_default() {
     );
 }
 
+#[test]
+fn workflow_export_graph_prints_the_diagram_in_the_requested_format() {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let tasks_file = std::env::temp_dir().join(format!("metaagent-cli-export-graph-{now}.json"));
+    std::fs::write(
+        &tasks_file,
+        r#"[{"id":"1","title":"Task 1","details":"details","status":"done","parent_id":null,"order":0}]"#,
+    )
+    .expect("write test tasks");
+    let tasks_file_arg = tasks_file.to_string_lossy().to_string();
+
+    let mermaid = run_cli(&[
+        "api",
+        "workflow",
+        "export-graph",
+        "--tasks-file",
+        &tasks_file_arg,
+    ]);
+    let dot = run_cli(&[
+        "--output",
+        "json",
+        "api",
+        "workflow",
+        "export-graph",
+        "--tasks-file",
+        &tasks_file_arg,
+        "--format",
+        "dot",
+    ]);
+
+    let _ = std::fs::remove_file(tasks_file);
+    assert_eq!(mermaid.status.code(), Some(0));
+    let stdout = stdout_text(&mermaid);
+    assert!(stdout.contains("flowchart TD\n"), "{stdout}");
+    assert!(stdout.contains("  t0[\"Task 1<br/>task · done\"]:::done"));
+    assert_eq!(dot.status.code(), Some(0));
+    let body = stdout_json(&dot);
+    assert_eq!(body.pointer("/data/format"), Some(&Value::from("dot")));
+    assert!(
+        body.pointer("/data/graph")
+            .and_then(Value::as_str)
+            .is_some_and(|graph| graph.starts_with("digraph tasks {"))
+    );
+}
+
 #[test]
 fn workflow_validate_tasks_human_output_verbose_outputs_full_payload() {
     let now = std::time::SystemTime::now()
@@ -559,6 +607,213 @@ fn session_cli_command_chain_keeps_json_shape_stable_across_operations() {
     );
 }
 
+#[test]
+fn session_search_code_returns_ranked_workspace_chunks() {
+    let root = TempDirGuard::new("search-code");
+    let home = root.path().join("home");
+    let workspace = root.path().join("workspace");
+    std::fs::create_dir_all(home.join(".agentbob/sessions/s1")).expect("create session dir");
+    std::fs::create_dir_all(workspace.join("src")).expect("create workspace");
+    std::fs::write(
+        workspace.join("src/export.rs"),
+        "pub fn export_csv_rows() {\n    write_csv_header();\n}\n",
+    )
+    .expect("write export");
+    std::fs::write(workspace.join("src/ledger.rs"), "pub struct Ledger;\n").expect("write ledger");
+    let workspace_arg = workspace.display().to_string();
+    let session_dir = home.join(".agentbob/sessions/s1").display().to_string();
+
+    let search = run_cli_in_home(
+        &home,
+        &[
+            "--output",
+            "json",
+            "api",
+            "session",
+            "search-code",
+            "--cwd",
+            workspace_arg.as_str(),
+            "--session-dir",
+            session_dir.as_str(),
+            "--query",
+            "csv export",
+            "--limit",
+            "1",
+        ],
+    );
+    assert_eq!(search.status.code(), Some(0));
+    let body = stdout_json(&search);
+    let results = body
+        .pointer("/data/results")
+        .and_then(Value::as_array)
+        .expect("search results");
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        results[0].get("path").and_then(Value::as_str),
+        Some("src/export.rs")
+    );
+    assert!(
+        std::path::Path::new(&session_dir)
+            .join("code-index.json")
+            .is_file()
+    );
+}
+
+#[test]
+fn session_export_audit_trail_copies_the_trail_and_reports_a_broken_chain() {
+    let root = TempDirGuard::new("audit-trail");
+    let home = root.path().join("home");
+    let session = home.join(".agentbob/sessions/s1");
+    std::fs::create_dir_all(&session).expect("create session dir");
+    let session_dir = session.display().to_string();
+    let output = root.path().join("export.jsonl");
+    let output_arg = output.display().to_string();
+    let export = |home: &std::path::Path| {
+        run_cli_in_home(
+            home,
+            &[
+                "--output",
+                "json",
+                "api",
+                "session",
+                "export-audit-trail",
+                "--session-dir",
+                session_dir.as_str(),
+                "--output",
+                output_arg.as_str(),
+            ],
+        )
+    };
+
+    let empty = export(&home);
+    assert_eq!(empty.status.code(), Some(0));
+    let body = stdout_json(&empty);
+    assert_eq!(
+        body.pointer("/data/entries")
+            .and_then(Value::as_array)
+            .map(Vec::len),
+        Some(0)
+    );
+    assert_eq!(body.pointer("/data/broken_at"), Some(&Value::Null));
+    assert_eq!(std::fs::read_to_string(&output).expect("read export"), "");
+
+    let tampered = concat!(
+        r#"{"seq":1,"at_epoch_ms":1,"event":{"type":"approval_decision","payload":"#,
+        r#"{"subject":"review","decision":"approve","detail":"task #1 pass 1"}},"#,
+        r#""prev_hash":"0000000000000000000000000000000000000000000000000000000000000000","#,
+        r#""hash":"not-the-hash"}"#,
+        "\n"
+    );
+    std::fs::write(session.join("audit-trail.jsonl"), tampered).expect("write trail");
+    let broken = export(&home);
+    assert_eq!(broken.status.code(), Some(0));
+    let body = stdout_json(&broken);
+    assert_eq!(
+        body.pointer("/data/broken_at").and_then(Value::as_u64),
+        Some(1)
+    );
+    assert_eq!(
+        body.pointer("/data/entries/0/event/payload/decision")
+            .and_then(Value::as_str),
+        Some("approve")
+    );
+    assert_eq!(
+        std::fs::read_to_string(&output).expect("read export"),
+        tampered
+    );
+}
+
+#[test]
+fn session_task_commands_edit_tasks_json_with_reload_validation() {
+    let root = TempDirGuard::new("task-crud");
+    let home = root.path().join("home");
+    let workspace = root.path().join("workspace");
+    std::fs::create_dir_all(&home).expect("create home");
+    std::fs::create_dir_all(&workspace).expect("create workspace");
+    let workspace_arg = workspace.display().to_string();
+    let init = run_cli_in_home(
+        &home,
+        &[
+            "--output",
+            "json",
+            "api",
+            "session",
+            "init",
+            "--cwd",
+            workspace_arg.as_str(),
+        ],
+    );
+    assert_eq!(init.status.code(), Some(0));
+    let session_dir = stdout_json(&init)
+        .pointer("/data/session_dir")
+        .and_then(Value::as_str)
+        .expect("session dir")
+        .to_string();
+    let session_task = |args: &[&str]| {
+        let mut full = vec!["--output", "json", "api", "session"];
+        full.extend_from_slice(args);
+        full.extend_from_slice(&[
+            "--cwd",
+            workspace_arg.as_str(),
+            "--session-dir",
+            session_dir.as_str(),
+        ]);
+        run_cli_in_home(&home, &full)
+    };
+
+    for id in ["first", "second"] {
+        let task_file = root.path().join(format!("{id}.json"));
+        std::fs::write(
+            &task_file,
+            json!({
+                "id": id,
+                "title": format!("Task {id}"),
+                "details": format!("{id} details"),
+                "docs": [],
+                "kind": "task",
+                "status": "pending",
+                "parent_id": null,
+                "order": null
+            })
+            .to_string(),
+        )
+        .expect("write task file");
+        let task_arg = task_file.display().to_string();
+        let add = session_task(&["add-task", "--task-file", task_arg.as_str()]);
+        assert_eq!(add.status.code(), Some(0), "{}", stdout_text(&add));
+        let duplicate = session_task(&["add-task", "--task-file", task_arg.as_str()]);
+        assert_eq!(duplicate.status.code(), Some(11));
+    }
+
+    let reorder = session_task(&["reorder-task", "--task-id", "second", "--position", "0"]);
+    assert_eq!(reorder.status.code(), Some(0));
+    let reordered = stdout_json(&reorder);
+    let orders = reordered
+        .pointer("/data/tasks")
+        .and_then(Value::as_array)
+        .expect("tasks")
+        .iter()
+        .map(|task| (task["id"].as_str(), task["order"].as_u64()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        orders,
+        [(Some("first"), Some(1)), (Some("second"), Some(0))]
+    );
+
+    let missing = session_task(&["remove-task", "--task-id", "nope"]);
+    assert_eq!(missing.status.code(), Some(12));
+    let remove = session_task(&["remove-task", "--task-id", "first"]);
+    assert_eq!(remove.status.code(), Some(0));
+    let read = session_task(&["read-tasks"]);
+    let tasks = stdout_json(&read)
+        .pointer("/data/tasks")
+        .and_then(Value::as_array)
+        .cloned()
+        .expect("tasks");
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["id"], "second");
+}
+
 #[test]
 fn multi_step_planner_and_workflow_cli_commands_are_chainable_and_json_stable() {
     let root = TempDirGuard::new("workflow-chain");
@@ -686,3 +941,25 @@ fn multi_step_planner_and_workflow_cli_commands_are_chainable_and_json_stable()
             .is_some_and(|text| text.contains("Ship CLI parity"))
     }));
 }
+
+/// Guards prompt wording as the shipped binary renders it. After an intended prompt change, run
+/// `cargo run -- api prompts snapshot --golden-dir tests/golden/prompts --update` and review
+/// the golden file diff.
+#[test]
+fn prompt_snapshots_match_golden_files() {
+    let golden_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/prompts");
+    let output = run_cli(&[
+        "--output",
+        "json",
+        "api",
+        "prompts",
+        "snapshot",
+        "--golden-dir",
+        golden_dir.to_str().expect("utf8 golden dir"),
+    ]);
+    assert!(
+        output.status.success(),
+        "prompt snapshots drifted from golden files:\n{}",
+        stdout_text(&output)
+    );
+}
@@ -0,0 +1,109 @@
+use super::*;
+use crate::app::ExternalTasksEdit;
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn task(id: &str, kind: PlannerTaskKindFile, parent_id: Option<&str>) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("Task {id}"),
+        details: "d".to_string(),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    }
+}
+
+fn seeded_session(prefix: &str) -> (App, SessionStore, PathBuf) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let cwd = std::env::current_dir().expect("cwd");
+    let session_dir = std::env::temp_dir().join(format!("{prefix}-{now}"));
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing store");
+    let tasks = vec![
+        task("t", PlannerTaskKindFile::Task, None),
+        task("t-impl", PlannerTaskKindFile::Implementor, Some("t")),
+        task("t-audit", PlannerTaskKindFile::Auditor, Some("t-impl")),
+    ];
+    let mut app = App::default();
+    app.sync_planner_tasks_from_file(tasks.clone())
+        .expect("sync tasks");
+    store.write_tasks(&tasks).expect("write tasks");
+    (app, store, session_dir)
+}
+
+#[test]
+fn advisor_edits_to_tasks_file_are_rejected_but_app_writes_are_kept() {
+    let (app, store, session_dir) = seeded_session("metaagent-advisor-reject");
+    let snapshot = std::fs::read_to_string(store.tasks_file()).expect("read tasks");
+    assert!(!reject_tasks_file_edits(&app, &store, &snapshot).expect("unchanged"));
+
+    let compact = serde_json::to_string(&app.planner_tasks_for_file()).expect("serialize");
+    std::fs::write(store.tasks_file(), &compact).expect("app-equivalent write");
+    assert!(!reject_tasks_file_edits(&app, &store, &snapshot).expect("same tree"));
+    assert_eq!(
+        std::fs::read_to_string(store.tasks_file()).expect("read tasks"),
+        compact
+    );
+
+    let mut edited = app.planner_tasks_for_file();
+    edited[0].title = "Renamed by the advisor".to_string();
+    store.write_tasks(&edited).expect("advisor write");
+    assert!(reject_tasks_file_edits(&app, &store, &snapshot).expect("restore"));
+    let restored = store.read_tasks().expect("read tasks");
+    assert_eq!(restored[0].title, "Task t");
+    assert_eq!(restored.len(), 3);
+
+    let _ = std::fs::remove_dir_all(session_dir);
+}
+
+#[test]
+fn pending_user_edit_is_restored_from_the_snapshot() {
+    let (mut app, store, session_dir) = seeded_session("metaagent-advisor-user-edit");
+    let user_edit = "[]";
+    std::fs::write(store.tasks_file(), user_edit).expect("user edit");
+    app.set_external_tasks_edit(ExternalTasksEdit {
+        session_dir: session_dir.clone(),
+        text: user_edit.to_string(),
+        validation_error: None,
+    });
+
+    std::fs::write(store.tasks_file(), "{\"advisor\": true}").expect("advisor write");
+    assert!(reject_tasks_file_edits(&app, &store, user_edit).expect("restore"));
+    assert_eq!(
+        std::fs::read_to_string(store.tasks_file()).expect("read tasks"),
+        user_edit
+    );
+
+    let _ = std::fs::remove_dir_all(session_dir);
+}
+
+#[test]
+fn advisor_prompt_is_read_only_and_lists_queued_jobs() {
+    let prompt = crate::subagents::build_advisor_prompt(
+        "Why is the auth task slow?",
+        "/tmp/session",
+        "/tmp/session/tasks.json",
+        Some("Rust CLI"),
+        &["auditor task:auth-audit pass 2 (Auth)".to_string()],
+    );
+    assert!(
+        prompt.contains(
+            "Current task list with runtime statuses (read only): /tmp/session/tasks.json"
+        )
+    );
+    assert!(prompt.contains("Project context (project-info.md):\nRust CLI\n"));
+    assert!(prompt.contains("- auditor task:auth-audit pass 2 (Auth)\n"));
+    assert!(prompt.contains("do not create, edit, or delete files"));
+    assert!(prompt.ends_with("User question:\nWhy is the auth task slow?"));
+
+    let prompt =
+        crate::subagents::build_advisor_prompt("Status?", "/s", "/s/tasks.json", None, &[]);
+    assert!(!prompt.contains("Project context"));
+    assert!(prompt.contains("- (no jobs queued)"));
+}
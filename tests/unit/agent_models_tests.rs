@@ -79,6 +79,88 @@ fn toml_overrides_profiles_and_agent_assignments() {
     assert_eq!(task_check.thinking_effort.as_deref(), Some("medium"));
 }
 
+#[test]
+fn agent_workdirs_route_kinds_to_workspace_subdirectories() {
+    let routing = CodexAgentModelRouting::from_toml_str(
+        r#"
+        [codex.agent_workdirs]
+        worker_implementor = " backend/ "
+        project_info = ""
+        "#,
+    )
+    .expect("parse should succeed");
+
+    assert_eq!(
+        routing.workdir_for(CodexAgentKind::WorkerImplementor),
+        Some(std::path::PathBuf::from("backend/"))
+    );
+    assert_eq!(routing.workdir_for(CodexAgentKind::ProjectInfo), None);
+    assert_eq!(routing.workdir_for(CodexAgentKind::Master), None);
+
+    for bad in [
+        "[codex.agent_workdirs]\nmaster = \"../elsewhere\"\n",
+        "[codex.agent_workdirs]\nmaster = \"/tmp\"\n",
+        "[codex.agent_workdirs]\nplanner = \"backend\"\n",
+    ] {
+        let err = CodexAgentModelRouting::from_toml_str(bad).expect_err(bad);
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn agent_workdirs_resolve_against_a_resumed_sessions_workspace() {
+    let mut routing = CodexAgentModelRouting::from_toml_str(
+        "[codex.agent_workdirs]\nworker_implementor = \"backend\"\n",
+    )
+    .expect("parse should succeed");
+    routing.set_workspace(Some(std::path::PathBuf::from("/work/api")));
+
+    assert_eq!(
+        routing.workdir_for(CodexAgentKind::WorkerImplementor),
+        Some(std::path::PathBuf::from("/work/api/backend"))
+    );
+    assert_eq!(
+        routing.workdir_for(CodexAgentKind::Master),
+        Some(std::path::PathBuf::from("/work/api"))
+    );
+    assert_eq!(
+        routing.in_workspace(Some(std::path::PathBuf::from("web"))),
+        Some(std::path::PathBuf::from("/work/api/web"))
+    );
+}
+
+#[test]
+fn escalation_ladder_climbs_from_the_current_profile_and_stops_at_the_top() {
+    let routing = CodexAgentModelRouting::default();
+    assert_eq!(
+        routing.escalated_profile("large-smart", 1).as_deref(),
+        Some("large-genius")
+    );
+    assert_eq!(
+        routing.escalated_profile("LARGE-SMART", 5).as_deref(),
+        Some("large-supergenius")
+    );
+    assert_eq!(routing.escalated_profile("large-supergenius", 1), None);
+    assert_eq!(routing.escalated_profile("large-smart", 0), None);
+    assert_eq!(routing.escalated_profile("small-genius", 1), None);
+
+    let routing = CodexAgentModelRouting::from_toml_str(
+        r#"
+        [codex]
+        escalation_ladder = ["large-smart", "missing", "large-genius"]
+        "#,
+    )
+    .expect("parse should succeed");
+    assert_eq!(
+        routing.escalated_profile("large-smart", 1).as_deref(),
+        Some("large-genius")
+    );
+
+    let routing = CodexAgentModelRouting::from_toml_str("[codex]\nescalation_ladder = []\n")
+        .expect("parse should succeed");
+    assert_eq!(routing.escalated_profile("large-smart", 1), None);
+}
+
 #[test]
 fn unknown_profile_assignment_falls_back_to_large_smart() {
     let routing = CodexAgentModelRouting::from_toml_str(
@@ -224,3 +306,49 @@ fn load_from_metaagent_config_reports_invalid_data_for_malformed_file() {
     }
     let _ = fs::remove_dir_all(&temp_home);
 }
+
+#[test]
+fn configured_backends_list_selected_backend_first_with_overrides() {
+    let backends = configured_backends_from_toml_str(
+        r#"
+        [backend]
+        selected = "claude"
+
+        [backend.claude]
+        program = "/opt/bin/claude"
+        "#,
+    )
+    .expect("parse should succeed");
+
+    assert_eq!(backends.len(), 2);
+    assert_eq!(backends[0].kind, BackendKind::Claude);
+    assert!(backends[0].selected);
+    assert_eq!(backends[0].command.program, "/opt/bin/claude");
+    assert_eq!(backends[1].kind, BackendKind::Codex);
+    assert!(!backends[1].selected);
+    assert_eq!(backends[1].command.program, "codex");
+}
+
+#[test]
+fn pinned_models_override_the_configured_routing_and_drift_lists_the_changed_slots() {
+    let mut routing = CodexAgentModelRouting::from_toml_str(
+        "[codex.model_profiles.fast]\nmodel = \"gpt-5-mini\"\n\n[codex.agent_profiles]\nworker_auditor = \"fast\"\n",
+    )
+    .expect("parse should succeed");
+    let recorded = CodexAgentModelRouting::default().configured_models();
+    let configured = routing.configured_models();
+
+    let drift = model_drift(&recorded, &configured);
+    assert_eq!(drift.len(), 1);
+    assert_eq!(drift[0].agent, "worker_auditor");
+    assert_eq!(drift[0].recorded.describe(), "gpt-5.3-codex (medium)");
+    assert_eq!(drift[0].configured.describe(), "gpt-5-mini");
+
+    routing.set_pinned_models(recorded.clone());
+    assert_eq!(
+        routing.profile_for(CodexAgentKind::WorkerAuditor).model,
+        "gpt-5.3-codex"
+    );
+    assert_eq!(routing.configured_models(), configured);
+    assert!(model_drift(&recorded, &recorded).is_empty());
+}
@@ -36,6 +36,72 @@ fn default_claude_config_uses_claude_backend_defaults() {
     assert!(config.model_reasoning_effort.is_none());
 }
 
+#[test]
+fn read_only_configs_replace_the_bypass_flags_with_the_backend_sandbox() {
+    let mut codex = CodexCommandConfig::default();
+    codex.make_read_only();
+    assert_eq!(
+        codex.args_prefix,
+        vec![
+            "exec",
+            "--color",
+            "never",
+            "-c",
+            "sandbox_mode=\"read-only\""
+        ]
+    );
+    assert_eq!(
+        build_resume_prompt_args(&codex, "thread-1"),
+        vec![
+            "exec",
+            "resume",
+            "-c",
+            "sandbox_mode=\"read-only\"",
+            "thread-1"
+        ]
+    );
+
+    let mut claude = CodexCommandConfig::default_for_backend(BackendKind::Claude);
+    claude.make_read_only();
+    assert_eq!(claude.args_prefix, vec!["--permission-mode", "plan"]);
+}
+
+#[test]
+fn read_only_except_configs_add_the_session_dir_as_a_writable_path() {
+    let session = Path::new("/home/me/.agentbob/sessions/1-repo");
+    let mut codex = CodexCommandConfig {
+        workdir: Some(PathBuf::from("/work/repo")),
+        ..CodexCommandConfig::default()
+    };
+    codex.make_read_only_except(session);
+    assert_eq!(
+        codex.args_prefix,
+        vec![
+            "exec",
+            "--color",
+            "never",
+            "-c",
+            "sandbox_mode=\"workspace-write\"",
+            "-c",
+            "sandbox_workspace_write.writable_roots=[\"/home/me/.agentbob/sessions/1-repo\"]"
+        ]
+    );
+    assert_eq!(codex.workdir.as_deref(), Some(Path::new("/work/repo")));
+
+    let mut claude = CodexCommandConfig::default_for_backend(BackendKind::Claude);
+    claude.make_read_only_except(session);
+    assert_eq!(
+        claude.args_prefix,
+        vec![
+            "--add-dir",
+            "/home/me/.agentbob/sessions/1-repo",
+            "--allowedTools",
+            "Edit(//home/me/.agentbob/sessions/1-repo/**),MultiEdit(//home/me/.agentbob/sessions/1-repo/**),Write(//home/me/.agentbob/sessions/1-repo/**)"
+        ]
+    );
+    assert_eq!(claude.workdir, None);
+}
+
 #[test]
 fn backend_kind_detects_claude_from_program_name() {
     let mut config = CodexCommandConfig::default();
@@ -76,6 +142,33 @@ fn parses_session_id_from_jsonl_event() {
     );
 }
 
+#[test]
+fn usage_parser_reads_codex_turn_and_claude_result_totals() {
+    let codex = r#"{"type":"turn.completed","usage":{"input_tokens":2400,"cached_input_tokens":2000,"output_tokens":120}}"#;
+    assert_eq!(
+        parse_usage_from_jsonl_line(codex),
+        Some(TokenUsage {
+            input_tokens: 2400,
+            output_tokens: 120,
+            cost_usd: 0.0,
+        })
+    );
+    let claude = r#"{"type":"result","result":"done","total_cost_usd":0.25,"usage":{"input_tokens":4,"cache_read_input_tokens":96,"output_tokens":30}}"#;
+    assert_eq!(
+        parse_usage_from_jsonl_line(claude),
+        Some(TokenUsage {
+            input_tokens: 100,
+            output_tokens: 30,
+            cost_usd: 0.25,
+        })
+    );
+    assert_eq!(
+        parse_usage_from_jsonl_line(r#"{"type":"result","result":"done"}"#),
+        None
+    );
+    assert_eq!(parse_usage_from_jsonl_line("tokens used: 12"), None);
+}
+
 #[test]
 fn claude_session_parser_ignores_thread_id_without_session_id() {
     let line = r#"{"type":"system","thread_id":"thread-12345678"}"#;
@@ -264,6 +357,7 @@ fn adapter_streams_stdout_and_stderr() {
         skip_reader_join_after_wait: false,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     adapter.send_prompt("hello".to_string());
 
@@ -287,6 +381,47 @@ fn adapter_streams_stdout_and_stderr() {
     assert!(outputs.iter().any(|l| l == "err:hello"));
 }
 
+#[test]
+fn plain_text_adapter_reports_only_stderr_failure_lines_as_system() {
+    let adapter = CodexAdapter::with_config(CodexCommandConfig {
+        program: "bash".to_string(),
+        args_prefix: vec![
+            "-lc".to_string(),
+            "printf 'fixed the connection refused bug\\n'; printf 'ERROR: stream disconnected\\n' 1>&2; exit 1"
+                .to_string(),
+            "codex-test".to_string(),
+        ],
+        output_mode: AdapterOutputMode::PlainText,
+        persistent_session: false,
+        skip_reader_join_after_wait: false,
+        model: None,
+        model_reasoning_effort: None,
+        workdir: None,
+    });
+    adapter.send_prompt("hello".to_string());
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    let mut events = Vec::new();
+    while Instant::now() < deadline {
+        events.extend(adapter.drain_events());
+        if events.contains(&AgentEvent::System(
+            "ERROR: stream disconnected".to_string(),
+        )) && events.contains(&AgentEvent::Output(
+            "fixed the connection refused bug".to_string(),
+        )) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert!(events.contains(&AgentEvent::Output(
+        "fixed the connection refused bug".to_string()
+    )));
+    assert!(events.contains(&AgentEvent::System(
+        "ERROR: stream disconnected".to_string()
+    )));
+}
+
 #[test]
 fn adapter_emits_completed_event() {
     let adapter = CodexAdapter::with_config(CodexCommandConfig {
@@ -297,6 +432,7 @@ fn adapter_emits_completed_event() {
         skip_reader_join_after_wait: false,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     adapter.send_prompt("ignored".to_string());
 
@@ -319,6 +455,38 @@ fn adapter_emits_completed_event() {
     assert!(completed, "expected completed event");
 }
 
+#[test]
+fn adapter_runs_the_agent_in_its_configured_workdir() {
+    let workdir = std::env::temp_dir().join(format!("agentbob-workdir-{}", std::process::id()));
+    std::fs::create_dir_all(&workdir).expect("create workdir");
+    let adapter = CodexAdapter::with_config(CodexCommandConfig {
+        program: "bash".to_string(),
+        args_prefix: vec!["-c".to_string(), "pwd -P".to_string()],
+        output_mode: AdapterOutputMode::PlainText,
+        persistent_session: false,
+        skip_reader_join_after_wait: false,
+        model: None,
+        model_reasoning_effort: None,
+        workdir: Some(workdir.clone()),
+    });
+    adapter.send_prompt("ignored".to_string());
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    let mut outputs = Vec::new();
+    while Instant::now() < deadline && outputs.is_empty() {
+        for event in adapter.drain_events() {
+            if let AgentEvent::Output(line) = event {
+                outputs.push(line);
+            }
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let expected = workdir.canonicalize().expect("canonical workdir");
+    assert_eq!(outputs, vec![expected.display().to_string()]);
+    let _ = std::fs::remove_dir_all(&workdir);
+}
+
 #[test]
 fn adapter_emits_completed_after_output_is_drained() {
     let adapter = CodexAdapter::with_config(CodexCommandConfig {
@@ -332,6 +500,7 @@ fn adapter_emits_completed_after_output_is_drained() {
         skip_reader_join_after_wait: false,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     adapter.send_prompt("ignored".to_string());
 
@@ -392,6 +561,7 @@ fn adapter_reports_spawn_errors() {
         skip_reader_join_after_wait: false,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     adapter.send_prompt("hello".to_string());
 
@@ -427,6 +597,7 @@ fn adapter_spawn_error_still_emits_completed_event() {
         skip_reader_join_after_wait: false,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     adapter.send_prompt("hello".to_string());
 
@@ -468,6 +639,7 @@ fn claude_backend_spawn_error_still_emits_completed_event() {
         skip_reader_join_after_wait: false,
         model: Some("claude-sonnet-4.5".to_string()),
         model_reasoning_effort: Some("high".to_string()),
+        workdir: None,
     });
     adapter.send_prompt("hello".to_string());
 
@@ -512,6 +684,7 @@ fn persistent_plain_text_completes_without_waiting_for_background_descendants()
         skip_reader_join_after_wait: false,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     let started = Instant::now();
     adapter.send_prompt("ignored".to_string());
@@ -551,6 +724,7 @@ fn persistent_json_can_complete_without_waiting_for_background_descendants() {
         skip_reader_join_after_wait: true,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     let started = Instant::now();
     adapter.send_prompt("ignored".to_string());
@@ -637,6 +811,7 @@ fn json_assistant_mode_keeps_plaintext_stdout_lines() {
         skip_reader_join_after_wait: false,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     adapter.send_prompt("ignored".to_string());
 
@@ -692,3 +867,19 @@ fn drain_events_limited_respects_max_and_preserves_queue() {
     assert_eq!(second.len(), 3);
     assert!(second.iter().all(|e| matches!(e, AgentEvent::Output(_))));
 }
+
+#[test]
+fn transient_failure_lines_cover_start_network_and_provider_errors() {
+    assert!(is_transient_failure_line(
+        "Adapter (codex) failed to start: No such file or directory (os error 2)"
+    ));
+    assert!(is_transient_failure_line(
+        "ERROR: stream disconnected before completion: error sending request"
+    ));
+    assert!(is_transient_failure_line(
+        "API Error: 529 {\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}"
+    ));
+    assert!(is_transient_failure_line("Rate limit reached; retry later"));
+    assert!(!is_transient_failure_line("AUDIT_RESULT: FAIL"));
+    assert!(!is_transient_failure_line("error[E0308]: mismatched types"));
+}
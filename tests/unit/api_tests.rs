@@ -38,6 +38,47 @@ fn api_request_deserialization_reads_domain_and_action_tags() {
     );
 }
 
+#[test]
+fn events_subscribe_defaults_cursor_and_wait_and_batch_round_trips() {
+    let request: ApiRequestContract = serde_json::from_value(json!({
+        "domain": "events",
+        "request": { "action": "subscribe" }
+    }))
+    .expect("request should deserialize");
+    assert_eq!(
+        request,
+        ApiRequestContract::Events(EventsRequest::Subscribe {
+            after: 0,
+            limit: None,
+            wait_ms: 0,
+        })
+    );
+
+    let response = ApiResponseContract::Events(EventsResponse::Batch {
+        events: vec![SessionEventRecordContract {
+            seq: 4,
+            at_epoch_ms: 1_700_000_000_000,
+            event: SessionEventContract::TaskStatusChanged {
+                task_id: "t-1".to_string(),
+                title: "Build".to_string(),
+                status: PlannerTaskStatusContract::Done,
+            },
+        }],
+        next_cursor: 4,
+    });
+    let value = serde_json::to_value(&response).expect("response should serialize");
+    assert_eq!(
+        value["response"]["events"][0]["event"],
+        json!({
+            "type": "task_status_changed",
+            "payload": { "task_id": "t-1", "title": "Build", "status": "done" }
+        })
+    );
+    let decoded: ApiResponseContract =
+        serde_json::from_value(value).expect("response should deserialize");
+    assert_eq!(decoded, response);
+}
+
 #[test]
 fn api_response_deserialization_reads_domain_and_kind_tags() {
     let value = json!({
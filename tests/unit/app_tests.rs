@@ -3,6 +3,7 @@ use crate::agent::BackendKind;
 use crate::session_store::{PlannerTaskKindFile, PlannerTaskStatusFile};
 use crate::text_layout::wrap_word_with_positions;
 use std::sync::Arc;
+use std::time::Duration;
 
 const REMOVED_TEST_DECISION_QUESTIONS: [&str; 5] = [
     "Testing-decision flow before initial planning in a session:",
@@ -29,6 +30,7 @@ fn load_default_plan(app: &mut App, top_title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -39,6 +41,7 @@ fn load_default_plan(app: &mut App, top_title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -49,6 +52,7 @@ fn load_default_plan(app: &mut App, top_title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -59,6 +63,7 @@ fn load_default_plan(app: &mut App, top_title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -69,6 +74,7 @@ fn load_default_plan(app: &mut App, top_title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("seed plan should sync");
@@ -153,6 +159,48 @@ fn tick_and_quit_update_app_state() {
     assert!(!app.running);
 }
 
+#[test]
+fn quit_command_parses_optional_shutdown_mode() {
+    assert_eq!(App::parse_quit_command("/quit"), Some(None));
+    assert_eq!(
+        App::parse_quit_command("/QUIT wait"),
+        Some(Some(ShutdownMode::Wait))
+    );
+    assert_eq!(
+        App::parse_quit_command("/exit detach"),
+        Some(Some(ShutdownMode::Detach))
+    );
+    assert_eq!(App::parse_quit_command("/quit soon"), Some(None));
+    assert_eq!(App::parse_quit_command("/quitter"), None);
+}
+
+#[test]
+fn request_shutdown_prompts_once_while_a_job_runs_and_blocks_new_jobs() {
+    let mut app = App::default();
+    assert_eq!(app.request_shutdown(None), None);
+    assert!(!app.running);
+
+    let mut app = App::default();
+    load_default_plan(&mut app, "Top");
+    app.start_execution();
+    app.start_next_worker_job().expect("worker should start");
+
+    let prompt = app.request_shutdown(None).expect("should ask how to quit");
+    assert!(prompt.contains("/quit wait"), "{prompt}");
+    assert!(app.running);
+    assert_eq!(app.shutdown_mode(), None);
+    assert_eq!(app.request_shutdown(None), None);
+    assert_eq!(app.shutdown_mode(), Some(ShutdownMode::Abort));
+    assert!(app.running);
+
+    let interrupted = app
+        .interrupt_execution()
+        .expect("job should be interrupted");
+    assert!(interrupted.contains("task #"), "{interrupted}");
+    assert!(!app.has_active_worker_job());
+    assert!(app.start_next_worker_job().is_none());
+}
+
 #[test]
 fn pane_focus_cycles_forward_and_backward() {
     let mut app = App::default();
@@ -195,14 +243,14 @@ fn scrolling_is_bounded_for_each_pane() {
     assert_eq!(app.left_top_scroll(), 0);
 
     app.active_pane = Pane::LeftBottom;
-    let max_left_bottom_scroll = app.left_bottom_lines().len().saturating_sub(1) as u16;
+    let max_left_bottom_scroll = app.left_bottom_lines().len().saturating_sub(1);
 
     for _ in 0..500 {
         app.scroll_chat_down(max_left_bottom_scroll);
     }
     assert_eq!(
         app.left_bottom_scroll(),
-        app.left_bottom_lines().len().saturating_sub(1) as u16
+        app.left_bottom_lines().len().saturating_sub(1)
     );
 
     app.active_pane = Pane::Right;
@@ -235,6 +283,64 @@ fn chat_input_and_submit_flow() {
     );
 }
 
+#[test]
+fn system_messages_fill_the_system_log_and_only_important_ones_reach_chat() {
+    fn displayed(app: &App) -> Vec<String> {
+        let transcript = app.displayed_chat_transcript();
+        (0..transcript.len())
+            .filter_map(|index| transcript.line(index).map(|line| line.into_owned()))
+            .collect()
+    }
+    let mut app = App::default();
+    app.push_agent_message("Agent: Planning now.");
+    app.push_agent_message("System: Master prompt sent.");
+    app.push_agent_message("System: Failed to write tasks.json: disk full");
+    app.paste_input("/verbose");
+    app.submit_chat_message();
+    app.begin_command_reply();
+    app.push_agent_message("System: Verbose output on.");
+    app.end_command_reply();
+    app.push_agent_message("System: Session metadata write completed.");
+
+    assert_eq!(app.chat_view(), ChatView::Chat);
+    assert_eq!(
+        displayed(&app),
+        [
+            "Agent: Planning now.",
+            "System: Failed to write tasks.json: disk full",
+            "You: /verbose",
+            "System: Verbose output on.",
+        ]
+    );
+    assert_eq!(app.toggle_chat_view(), ChatView::SystemLog);
+    assert_eq!(
+        displayed(&app),
+        [
+            "System: Master prompt sent.",
+            "System: Failed to write tasks.json: disk full",
+            "System: Verbose output on.",
+            "System: Session metadata write completed.",
+        ]
+    );
+    assert_eq!(app.left_bottom_lines().len(), 6);
+}
+
+#[test]
+fn paste_input_keeps_newlines_literal_and_moves_cursor_past_pasted_text() {
+    let mut app = App::default();
+    app.input_char('[');
+    app.input_char(']');
+    app.move_cursor_left();
+    app.paste_input("line one\r\nline two\rline three");
+
+    assert_eq!(app.chat_input(), "[line one\nline two\nline three]");
+    assert_eq!(app.chat_cursor_line_col(200), (2, 10));
+    assert_eq!(
+        app.submit_chat_message(),
+        Some("[line one\nline two\nline three]".to_string())
+    );
+}
+
 #[test]
 fn ctrl_enter_newline_then_enter_submits_and_clears_chat_input() {
     let mut app = App::default();
@@ -470,6 +576,55 @@ fn worker_completion_updates_chat_and_tree() {
     assert!(tree.contains("Ship fix"));
 }
 
+#[test]
+fn finished_top_tasks_are_listed_in_the_summaries_pane() {
+    let mut app = App::default();
+    load_default_plan(&mut app, "Ship fix");
+    app.set_right_pane_mode(RightPaneMode::TaskSummaries);
+    assert!(app.right_block_lines(80)[0].contains("No top-level task is done yet"));
+
+    app.start_execution();
+    for output in [
+        "Implemented change",
+        "PASS",
+        "Added tests",
+        "all tests passed",
+    ] {
+        app.start_next_worker_job().expect("next job");
+        app.on_worker_output(output.to_string());
+        app.on_worker_completed(true, 0);
+    }
+    assert_eq!(
+        app.drain_completed_top_tasks(),
+        vec![CompletedTopTask {
+            key: "top".to_string(),
+            title: "Ship fix".to_string(),
+        }]
+    );
+    assert!(app.drain_completed_top_tasks().is_empty());
+    assert_eq!(
+        app.right_block_lines(80),
+        vec!["# Ship fix", "(no summary yet)"]
+    );
+
+    app.record_task_summary(TaskSummaryFileEntry {
+        top_task_id: "top".to_string(),
+        top_task_title: "Ship fix".to_string(),
+        summary: "Fixed the bug; the audit passed and a regression test covers it.".to_string(),
+        created_at_epoch_secs: 0,
+    });
+    assert_eq!(
+        app.right_block_lines(80),
+        vec![
+            "# Ship fix",
+            "Fixed the bug; the audit passed and a regression test covers it."
+        ]
+    );
+    assert_eq!(
+        app.right_pane_title(),
+        "Task Summaries (Backspace returns to tasks)"
+    );
+}
 #[test]
 fn worker_completion_reports_new_context_when_rolling_context_is_at_capacity() {
     let mut app = App::default();
@@ -530,6 +685,25 @@ fn start_command_detection_handles_aliases() {
     assert!(!App::is_remove_final_audit_command("/start"));
 }
 
+#[test]
+fn transient_failures_are_only_noted_from_system_lines() {
+    let mut app = App::default();
+    load_default_plan(&mut app, "Transient failures");
+    app.start_execution();
+    let _ = app
+        .start_next_worker_job()
+        .expect("worker job should start");
+    app.on_worker_output("Added a retry when the connection refused error comes back.".to_string());
+    app.on_worker_output("Rate limit handling now backs off.".to_string());
+    assert_eq!(app.take_active_job_transient_failure(), None);
+
+    app.on_worker_system_output("ERROR: stream disconnected before completion".to_string());
+    assert_eq!(
+        app.take_active_job_transient_failure().as_deref(),
+        Some("ERROR: stream disconnected before completion")
+    );
+}
+
 #[test]
 fn worker_output_streams_to_top_left_pane() {
     let mut app = App::default();
@@ -666,6 +840,7 @@ fn task_details_toggle_changes_block_rendering() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-a".to_string(),
@@ -675,11 +850,13 @@ fn task_details_toggle_changes_block_rendering() {
                 title: "Docs".to_string(),
                 url: "https://example.com/docs".to_string(),
                 summary: "Reference".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::Implementor,
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("task-a".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-a-audit".to_string(),
@@ -690,6 +867,7 @@ fn task_details_toggle_changes_block_rendering() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl-a".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -715,6 +893,32 @@ fn task_details_toggle_changes_block_rendering() {
     assert!(docs_expanded.contains("https://example.com/docs"));
 }
 
+#[test]
+fn session_ui_state_restores_positions_and_drops_stale_task_keys() {
+    let mut app = App::default();
+    load_default_plan(&mut app, "Top");
+    app.load_planner_markdown("# Plan\nship it".to_string());
+    app.set_planner_cursor(6);
+    app.toggle_task_details("top");
+    let mut saved = app.session_ui_state();
+    assert_eq!(saved.planner_cursor, 6);
+    assert!(saved.expanded_task_keys.contains("top"));
+
+    let mut other = App::default();
+    load_default_plan(&mut other, "Top");
+    other.load_planner_markdown("# P".to_string());
+    saved.expanded_task_keys.insert("removed-task".to_string());
+    saved.right_scroll = u16::MAX;
+    other.restore_session_ui_state(saved);
+    assert!(other.is_task_details_expanded("top"));
+    assert!(!other.is_task_details_expanded("removed-task"));
+    assert_eq!(other.session_ui_state().planner_cursor, 3);
+    assert!(other.right_scroll() < u16::MAX);
+
+    other.restore_session_ui_state(SessionUiStateFile::default());
+    assert!(!other.is_task_details_expanded("top"));
+}
+
 #[test]
 fn subagent_output_auto_follows_latest_lines() {
     let mut app = App::default();
@@ -778,8 +982,9 @@ fn command_index_matches_newmaster_prefix() {
     app.input_char('/');
     app.input_char('n');
     let suggestions = app.command_suggestions();
-    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions.len(), 2);
     assert_eq!(suggestions[0].command, "/newmaster");
+    assert_eq!(suggestions[1].command, "/new-from-template");
 }
 
 #[test]
@@ -876,6 +1081,34 @@ fn command_index_matches_split_and_merge_prefixes() {
     assert_eq!(suggestions[0].command, "/remove-final-audit");
 }
 
+#[test]
+fn chat_input_aliases_are_rewritten_and_macros_queued() {
+    let mut app = App::default();
+    app.set_command_registry(
+        CommandRegistry::from_toml_str(
+            "[commands.aliases]\nrt = \"/retry\"\n\n[commands.macros]\nship = [\"/convert\", \"/start\"]\n",
+        )
+        .expect("config should parse"),
+    );
+    for ch in "/rt parse".chars() {
+        app.input_char(ch);
+    }
+    assert!(!app.expand_chat_input_command());
+    assert_eq!(app.chat_input(), "/retry parse");
+    assert!(!app.expand_chat_input_command());
+
+    let _ = app.consume_chat_input_trimmed();
+    for ch in "/sh".chars() {
+        app.input_char(ch);
+    }
+    assert_eq!(app.command_suggestions()[0].command, "/ship");
+    app.input_char('i');
+    app.input_char('p');
+    assert!(app.expand_chat_input_command());
+    assert_eq!(app.chat_input(), "");
+    assert_eq!(app.queued_messages(), vec!["/convert", "/start"]);
+}
+
 #[test]
 fn consume_chat_input_trimmed_clears_input_and_cursor() {
     let mut app = App::default();
@@ -979,6 +1212,117 @@ fn backend_picker_navigation_and_selection_work() {
     assert!(!app.is_backend_picker_open());
 }
 
+#[test]
+fn resume_picker_filters_by_title_and_workspace_words() {
+    let mut app = App::default();
+    let option = |dir: &str, workspace: &str, title: Option<&str>| ResumeSessionOption {
+        session_dir: dir.to_string(),
+        workspace: workspace.to_string(),
+        title: title.map(str::to_string),
+        created_at_label: None,
+        last_used_epoch_secs: 1,
+    };
+    app.open_resume_picker(vec![
+        option("/tmp/s1", "/work/billing", Some("Fix invoice export")),
+        option("/tmp/s2", "/work/search", Some("Ranking tweaks")),
+        option("/tmp/s3", "/work/billing", None),
+    ]);
+
+    for ch in "BILL".chars() {
+        app.resume_picker_filter_push(ch);
+    }
+    assert_eq!(app.resume_picker_options().len(), 2);
+    app.resume_picker_move_down();
+    app.resume_picker_filter_push(' ');
+    app.resume_picker_filter_push('x');
+    assert_eq!(app.resume_picker_selected_index(), 0);
+    let (selected, preview) = app.resume_picker_preview().expect("a match stays selected");
+    assert_eq!(selected.session_dir, "/tmp/s1");
+    assert!(preview.is_none());
+
+    app.resume_picker_filter_push('z');
+    assert!(app.resume_picker_options().is_empty());
+    assert!(app.select_resume_session().is_none());
+    assert!(app.is_resume_picker_open());
+
+    while app.resume_picker_filter_pop() {}
+    assert_eq!(app.resume_picker_filter(), "");
+    assert_eq!(app.resume_picker_options().len(), 3);
+}
+
+#[test]
+fn resume_picker_lists_other_workspaces_on_tab_and_confirms_the_switch() {
+    let mut app = App::default();
+    app.set_workspace_root(PathBuf::from("/work/billing"));
+    let option = |dir: &str, workspace: &str| ResumeSessionOption {
+        session_dir: dir.to_string(),
+        workspace: workspace.to_string(),
+        title: None,
+        created_at_label: None,
+        last_used_epoch_secs: 1,
+    };
+    app.open_resume_picker_with_other_workspaces(
+        vec![option("/tmp/s1", "/work/billing")],
+        vec![option("/tmp/s2", "/work/search")],
+    );
+    assert!(!app.resume_picker_shows_all_workspaces());
+    assert_eq!(app.resume_picker_options().len(), 1);
+
+    assert!(app.toggle_resume_picker_workspaces());
+    assert_eq!(app.resume_picker_options().len(), 2);
+    app.resume_picker_move_down();
+    assert!(app.select_resume_session().is_none());
+    assert!(app.is_resume_picker_open());
+    assert_eq!(app.toggle_chat_view(), ChatView::SystemLog);
+    let log = app.displayed_chat_transcript();
+    assert!(
+        log.line(log.len() - 1)
+            .unwrap_or_default()
+            .contains("Press Enter again")
+    );
+    assert_eq!(
+        app.select_resume_session()
+            .map(|selection| selection.session_dir),
+        Some("/tmp/s2".to_string())
+    );
+
+    app.open_resume_picker_with_other_workspaces(
+        Vec::new(),
+        vec![option("/tmp/s2", "/work/search")],
+    );
+    assert!(app.resume_picker_shows_all_workspaces());
+    assert!(!app.toggle_resume_picker_workspaces());
+    assert_eq!(app.resume_picker_options().len(), 1);
+}
+#[test]
+fn recent_session_offer_holds_the_message_until_picked_or_declined() {
+    let mut app = App::default();
+    let option = ResumeSessionOption {
+        session_dir: "/tmp/recent".to_string(),
+        workspace: "/work/app".to_string(),
+        title: Some("Recent".to_string()),
+        created_at_label: None,
+        last_used_epoch_secs: 1,
+    };
+    app.set_offer_recent_session(true);
+    app.offer_recent_sessions(vec![option.clone()], "add login".to_string());
+    assert!(!app.offers_recent_session());
+    assert!(app.is_resume_picker_open());
+
+    assert_eq!(app.select_resume_session(), Some(option.clone()));
+    assert_eq!(app.chat_input(), "add login");
+    assert!(app.take_new_session_message().is_none());
+
+    let mut app = App::default();
+    app.offer_recent_sessions(vec![option.clone()], "add login".to_string());
+    assert_eq!(app.take_new_session_message().as_deref(), Some("add login"));
+
+    app.offer_recent_sessions(vec![option], "add login".to_string());
+    app.open_resume_picker(Vec::new());
+    assert!(app.take_new_session_message().is_none());
+    assert_eq!(app.chat_input(), "");
+}
+
 #[test]
 fn command_index_hides_while_resume_picker_open() {
     let mut app = App::default();
@@ -1039,3 +1383,944 @@ fn backend_and_resume_pickers_remain_mutually_exclusive() {
     assert!(app.is_resume_picker_open());
     assert!(!app.is_backend_picker_open());
 }
+
+#[test]
+fn resume_option_when_label_prefers_last_used_then_created_date() {
+    let mut app = App::default();
+    let option = |created_at_label: Option<&str>, last_used_epoch_secs| ResumeSessionOption {
+        session_dir: "/tmp/s".to_string(),
+        workspace: "/tmp/w".to_string(),
+        title: None,
+        created_at_label: created_at_label.map(str::to_string),
+        last_used_epoch_secs,
+    };
+    app.set_language_settings(LanguageSettings {
+        time_zone: TimeZone::parse("+01:00").expect("offset"),
+        ..LanguageSettings::default()
+    });
+    assert_eq!(
+        app.resume_option_when_label(&option(Some("2026-02-16T12:00:00Z"), 0)),
+        "Feb 16, 2026 13:00 UTC+01:00"
+    );
+    assert_eq!(
+        app.resume_option_when_label(&option(Some("last tuesday"), 0)),
+        "last tuesday"
+    );
+    assert_eq!(
+        app.resume_option_when_label(&option(None, 0)),
+        "unknown date"
+    );
+
+    let last_used = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs()
+        - 3 * 3_600;
+    app.open_resume_picker(vec![option(None, last_used)]);
+    assert_eq!(
+        app.resume_option_when_label(app.resume_picker_options()[0]),
+        "3h ago"
+    );
+}
+
+#[test]
+fn log_picker_opens_selected_log_in_right_pane_until_closed() {
+    let entry = |label: &str| WorkerLogEntry {
+        path: PathBuf::from(format!("/tmp/logs/{label}")),
+        label: label.to_string(),
+        size_bytes: 4,
+        modified: std::time::UNIX_EPOCH,
+    };
+    let mut app = App::default();
+    assert!(App::is_logs_command(" /LOGS "));
+    app.set_right_pane_mode(RightPaneMode::TaskList);
+    app.open_backend_picker(vec![BackendOption {
+        kind: BackendKind::Codex,
+        label: "Codex",
+        description: "Codex backend",
+    }]);
+    app.open_log_picker(vec![
+        entry("t1/implementor-pass2.log"),
+        entry("t1/implementor-pass1.log"),
+    ]);
+    assert!(app.is_log_picker_open());
+    assert!(!app.is_backend_picker_open());
+
+    app.log_picker_move_down();
+    app.log_picker_move_down();
+    assert_eq!(app.log_picker_selected_index(), 1);
+    let selected = app.select_worker_log().expect("selection should exist");
+    assert_eq!(selected.label, "t1/implementor-pass1.log");
+    assert!(!app.is_log_picker_open());
+
+    app.show_worker_log(selected.label, vec!["hello".to_string()]);
+    assert!(app.is_worker_log_mode());
+    assert!(!app.is_planner_mode());
+    assert_eq!(app.right_pane_title(), "Worker Log (read-only, Backspace closes)");
+    assert_eq!(
+        app.right_block_lines(80),
+        vec!["# t1/implementor-pass1.log".to_string(), "hello".to_string()]
+    );
+
+    app.close_worker_log();
+    assert!(!app.is_worker_log_mode());
+    assert_eq!(app.right_pane_title(), "Task List");
+}
+
+#[test]
+fn files_command_toggles_left_top_between_worker_output_and_file_tree() {
+    let root = std::env::temp_dir().join(format!(
+        "metaagent-app-file-tree-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(root.join("src")).expect("create workspace");
+    std::fs::write(root.join("src/lib.rs"), "pub fn f() {}\n").expect("write file");
+
+    let mut app = App::default();
+    app.set_workspace_root(root.clone());
+    assert!(App::is_files_command(" /FILES "));
+    assert_eq!(app.left_top_title(), "Worker Output");
+
+    assert!(app.toggle_file_tree_mode());
+    assert_eq!(app.left_top_title(), "Files (* changed this session)");
+    assert_eq!(app.file_tree_lines(), vec!["> [+] src/".to_string()]);
+
+    app.active_pane = Pane::LeftTop;
+    app.file_tree_activate();
+    app.scroll_left_top_down(0);
+    app.file_tree_activate();
+    assert!(app.is_file_viewer_open());
+    assert_eq!(app.left_top_title(), "src/lib.rs (read-only, Backspace closes)");
+    assert!(app.file_tree_back());
+    assert!(!app.is_file_viewer_open());
+
+    assert!(!app.toggle_file_tree_mode());
+    assert_eq!(app.left_top_title(), "Worker Output");
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn failed_audit_lists_its_citations_and_opens_them_in_the_file_viewer() {
+    let root = std::env::temp_dir().join(format!(
+        "metaagent-app-citations-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(root.join("src")).expect("create workspace");
+    std::fs::write(root.join("src/lib.rs"), "a\nb\nc\nd\n").expect("write file");
+
+    let mut app = App::default();
+    app.set_workspace_root(root.clone());
+    load_default_plan(&mut app, "Ship fix");
+    app.start_execution();
+    app.start_next_worker_job().expect("implementor");
+    app.on_worker_output("Implemented change".to_string());
+    app.on_worker_completed(true, 0);
+    app.start_next_worker_job().expect("auditor");
+    app.on_worker_output("FAIL".to_string());
+    app.on_worker_output("- src/lib.rs:2-3 returns early".to_string());
+    app.on_worker_output("- src/gone.rs:9 is never called".to_string());
+    app.on_worker_completed(true, 0);
+
+    let lines = app.left_top_lines().to_vec();
+    let cited = lines
+        .iter()
+        .position(|line| line == "Cited locations (click to open):")
+        .expect("citation header");
+    assert_eq!(
+        lines[cited + 1..],
+        [
+            "- src/lib.rs:2-3".to_string(),
+            "- src/gone.rs:9 (file not found in the workspace)".to_string(),
+        ]
+    );
+
+    let citations = crate::audit_citations::parse_line(&lines[cited + 2]);
+    assert!(!app.open_citation(&citations[0]));
+    let citations = crate::audit_citations::parse_line(&lines[cited + 1]);
+    assert!(app.open_citation(&citations[0]));
+    assert_eq!(app.active_pane, Pane::LeftTop);
+    assert_eq!(
+        app.left_top_title(),
+        "src/lib.rs (read-only, Backspace closes)"
+    );
+    assert_eq!(app.file_viewer_scroll(), 1);
+    assert_eq!(app.file_viewer_highlight(), Some((2, 3)));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn implementor_prompts_get_matching_workspace_excerpts_and_auditors_do_not() {
+    let root = std::env::temp_dir().join(format!(
+        "metaagent-app-code-context-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(root.join("src")).expect("create workspace");
+    std::fs::write(root.join("src/widget.rs"), "pub fn build_widget() {}\n").expect("write file");
+
+    let mut app = App::default();
+    app.set_workspace_root(root.clone());
+    load_default_plan(&mut app, "Polish widget rendering");
+    app.start_execution();
+    let started = app.start_next_worker_job().expect("implementor");
+    let JobRun::AgentPrompt(prompt) = started.run else {
+        panic!("implementor should get an agent prompt");
+    };
+    assert!(prompt.contains("\n\nRelevant workspace files"));
+    assert!(prompt.ends_with("--- src/widget.rs (lines 1-1) ---\npub fn build_widget() {}\n"));
+
+    app.on_worker_completed(true, 0);
+    let started = app.start_next_worker_job().expect("auditor");
+    assert_eq!(started.role, WorkerRole::Auditor);
+    let JobRun::AgentPrompt(prompt) = started.run else {
+        panic!("auditor should get an agent prompt");
+    };
+    assert!(!prompt.contains("Relevant workspace files"));
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+#[test]
+fn execution_status_text_summarizes_active_job_queue_backend_and_usage() {
+    let mut app = App::default();
+    let now = Instant::now();
+    assert_eq!(
+        app.execution_status_text(now),
+        "Job: idle | Queued: 0 | Tokens: 0 in / 0 out"
+    );
+
+    app.set_runtime_backend("codex");
+    load_default_plan(&mut app, "Polish widget rendering");
+    app.start_execution();
+    app.start_next_worker_job().expect("implementor");
+    app.set_active_job_model(Some("gpt-5-codex"));
+    assert!(!app.record_token_usage(TokenUsage::default()));
+    assert!(app.record_token_usage(TokenUsage {
+        input_tokens: 12_345,
+        output_tokens: 678,
+        cost_usd: 0.5,
+    }));
+    let status = app.execution_status_text(now + Duration::from_secs(125));
+    assert!(
+        status.starts_with("Job: Impl \"Polish widget rendering\" pass 1 2m 0"),
+        "{status}"
+    );
+    assert!(status.ends_with(
+        " | Queued: 0 | Backend: codex/gpt-5-codex | Tokens: 12.3k in / 678 out | Cost: $0.50"
+    ));
+
+    assert!(app.record_worker_token_usage(TokenUsage {
+        input_tokens: 1_000,
+        output_tokens: 100,
+        cost_usd: 0.25,
+    }));
+    app.on_worker_completed(true, 0);
+    assert!(
+        app.execution_status_text(now)
+            .starts_with("Job: idle | Queued: 1 | Backend: codex | ")
+    );
+    assert!(
+        app.execution_status_text(now)
+            .ends_with("Tokens: 13.3k in / 778 out | Cost: $0.75")
+    );
+    let stats = app.workflow.task_usage_stats("top").expect("task usage");
+    assert_eq!((stats.passes, stats.usage.input_tokens), (1, 1_000));
+}
+
+#[test]
+fn silent_worker_is_flagged_as_stalled_until_the_user_chooses_to_wait() {
+    let mut app = App::default();
+    app.set_stall_after(Some(Duration::from_secs(60)));
+    load_default_plan(&mut app, "Polish widget rendering");
+    app.start_execution();
+    app.start_next_worker_job().expect("implementor");
+    let later = Instant::now() + Duration::from_secs(90);
+    let stalled_at = |app: &App, secs| app.worker_stalled_for(later + Duration::from_secs(secs));
+
+    assert!(app.worker_stalled_for(Instant::now()).is_none());
+    assert!(app.worker_stalled_for(later).is_some());
+    let status = app.execution_status_text(later);
+    assert!(
+        status.contains(" | Stalled? 1m 3")
+            && status.contains("without output (w wait, n nudge, a abort) | Queued: 0"),
+        "{status}"
+    );
+    assert_eq!(
+        app.stall_action_for_key('n', later),
+        Some(StallAction::Nudge)
+    );
+    assert_eq!(app.stall_action_for_key('x', later), None);
+    app.chat_input = "w".to_string();
+    assert_eq!(app.stall_action_for_key('a', later), None);
+    app.chat_input.clear();
+
+    app.reset_stall_timer(later);
+    assert!(stalled_at(&app, 30).is_none());
+    assert!(stalled_at(&app, 60).is_some());
+
+    app.set_stall_after(None);
+    assert!(stalled_at(&app, 3600).is_none());
+    app.set_stall_after(Some(Duration::from_secs(60)));
+    app.on_worker_completed(false, 1);
+    assert!(stalled_at(&app, 3600).is_none());
+}
+
+#[test]
+fn stats_mode_shows_lines_in_left_top_and_hides_back_to_worker_output() {
+    let mut app = App::default();
+    assert!(App::is_stats_command(" /Stats "));
+    assert!(!app.is_stats_mode());
+
+    app.show_stats(vec!["line 1".to_string(), "line 2".to_string()]);
+    assert!(app.is_stats_mode());
+    assert_eq!(app.left_top_title(), "Failure Stats (all sessions)");
+    assert_eq!(app.stats_lines(), ["line 1".to_string(), "line 2".to_string()]);
+
+    app.active_pane = Pane::LeftTop;
+    app.scroll_left_top_down(1);
+    app.scroll_left_top_down(1);
+    assert_eq!(app.stats_scroll(), 1);
+    app.scroll_up();
+    assert_eq!(app.stats_scroll(), 0);
+
+    app.hide_stats();
+    assert!(!app.is_stats_mode());
+    assert_eq!(app.left_top_title(), "Worker Output");
+}
+
+#[test]
+fn new_from_template_name_parses_optional_template_argument() {
+    assert_eq!(
+        App::new_from_template_name("/new-from-template web-api"),
+        Some("web-api")
+    );
+    assert_eq!(
+        App::new_from_template_name(" /New-From-Template   cli "),
+        Some("cli")
+    );
+    assert_eq!(App::new_from_template_name("/new-from-template"), Some(""));
+    assert_eq!(App::new_from_template_name("/newmaster"), None);
+}
+
+#[test]
+fn external_tasks_edit_commands_parse_and_edit_clears_on_session_switch() {
+    assert!(App::is_reload_tasks_command(" /Reload-Tasks "));
+    assert!(App::is_keep_tasks_command("/keep-tasks"));
+    assert!(!App::is_keep_tasks_command("/keep-tasks now"));
+
+    let mut app = App::default();
+    app.set_external_tasks_edit(ExternalTasksEdit {
+        session_dir: PathBuf::from("/tmp/session"),
+        text: "[]".to_string(),
+        validation_error: None,
+    });
+    assert!(app.has_external_tasks_edit());
+    app.reset_execution_for_session_switch();
+    assert!(app.external_tasks_edit().is_none());
+}
+
+#[test]
+fn queued_chat_input_is_shown_as_queued_and_dequeued_in_order() {
+    assert!(App::is_clear_queue_command(" /Clear-Queue "));
+    let mut app = App::default();
+    assert_eq!(app.queue_chat_input(), None);
+    for message in ["first", "second"] {
+        message.chars().for_each(|c| app.input_char(c));
+        app.queue_chat_input();
+    }
+    assert_eq!(app.chat_input(), "");
+    assert_eq!(app.queued_message_count(), 2);
+    assert_eq!(
+        app.left_bottom_lines().last().map(String::as_str),
+        Some("You: [queued] second")
+    );
+    assert_eq!(app.next_queued_message(), Some("first"));
+    assert_eq!(app.dequeue_chat_message().as_deref(), Some("first"));
+    assert_eq!(app.clear_queued_messages(), 1);
+    assert_eq!(app.dequeue_chat_message(), None);
+}
+
+#[test]
+fn start_task_command_parses_task_id() {
+    assert_eq!(
+        App::parse_start_task_command(" /start task:impl-2 ").as_deref(),
+        Some("impl-2")
+    );
+    assert_eq!(
+        App::parse_start_task_command("/START Task: top1").as_deref(),
+        Some("top1")
+    );
+    assert_eq!(App::parse_start_task_command("/start"), None);
+    assert_eq!(App::parse_start_task_command("/start task:"), None);
+    assert_eq!(App::parse_start_task_command("/start top1"), None);
+    assert_eq!(App::parse_start_task_command("/start task:a b"), None);
+    assert!(!App::is_start_execution_command("/start task:top1"));
+}
+
+#[test]
+fn manual_task_commands_parse_action_and_task_id() {
+    assert_eq!(
+        App::parse_manual_task_command(" /skip impl-2 "),
+        Some((ManualTaskAction::Skip, "impl-2".to_string()))
+    );
+    assert_eq!(
+        App::parse_manual_task_command("/Mark-Done top1"),
+        Some((ManualTaskAction::MarkDone, "top1".to_string()))
+    );
+    assert_eq!(App::parse_manual_task_command("/skip"), None);
+    assert_eq!(App::parse_manual_task_command("/skip a b"), None);
+    assert_eq!(App::parse_manual_task_command("/skip-plan"), None);
+}
+
+#[test]
+fn review_commands_parse_toggle_decisions_and_fall_back_to_status() {
+    assert_eq!(
+        App::parse_review_command("/review ON"),
+        Some(ReviewCommand::Toggle(true))
+    );
+    assert_eq!(
+        App::parse_review_command(" /review off "),
+        Some(ReviewCommand::Toggle(false))
+    );
+    assert_eq!(
+        App::parse_review_command("/review approve"),
+        Some(ReviewCommand::Decide(ReviewDecision::Approve))
+    );
+    assert_eq!(
+        App::parse_review_command("/review revert"),
+        Some(ReviewCommand::Decide(ReviewDecision::Revert))
+    );
+    assert_eq!(
+        App::parse_review_command("/review changes  Keep the old API.\nAdd docs."),
+        Some(ReviewCommand::Decide(ReviewDecision::RequestChanges(
+            "Keep the old API.\nAdd docs.".to_string()
+        )))
+    );
+    for status in [
+        "/review",
+        "/review changes",
+        "/review on now",
+        "/review maybe",
+    ] {
+        assert_eq!(
+            App::parse_review_command(status),
+            Some(ReviewCommand::Status),
+            "{status}"
+        );
+    }
+    assert_eq!(App::parse_review_command("/reviewer on"), None);
+}
+
+#[test]
+fn task_action_commands_parse_task_arguments() {
+    assert_eq!(App::parse_logs_command("/logs"), Some(None));
+    assert_eq!(
+        App::parse_logs_command("/logs top"),
+        Some(Some("top".to_string()))
+    );
+    assert_eq!(App::parse_logs_command("/logs a b"), None);
+    assert_eq!(
+        App::parse_attach_docs_command("/attach-docs task:impl"),
+        Some(AttachDocsRequest {
+            task_id: Some("impl".to_string()),
+            snapshot: false,
+        })
+    );
+    assert_eq!(
+        App::parse_attach_docs_command("/attach-docs"),
+        Some(AttachDocsRequest::default())
+    );
+    assert_eq!(App::parse_attach_docs_command("/attach-docs impl"), None);
+    assert_eq!(
+        App::parse_attach_docs_command("/attach-docs task:impl SNAPSHOT"),
+        Some(AttachDocsRequest {
+            task_id: Some("impl".to_string()),
+            snapshot: true,
+        })
+    );
+    assert_eq!(
+        App::parse_attach_docs_command("/attach-docs snapshot snapshot"),
+        None
+    );
+    assert_eq!(
+        App::parse_retry_command("/retry impl"),
+        Some("impl".to_string())
+    );
+    assert_eq!(App::parse_retry_command("/retry"), None);
+    assert_eq!(
+        App::parse_rerun_command("/RERUN impl "),
+        Some("impl".to_string())
+    );
+    assert_eq!(App::parse_rerun_command("/rerun impl audit"), None);
+    assert_eq!(
+        App::direct_instruction("/direct  Fix the typo "),
+        Some("Fix the typo")
+    );
+    assert_eq!(App::direct_instruction("/DIRECT"), Some(""));
+    assert_eq!(App::direct_instruction("/directly fix"), None);
+    assert_eq!(
+        App::parse_task_model_command("/task-model top fast"),
+        Some(("top".to_string(), Some("fast".to_string())))
+    );
+    assert_eq!(
+        App::parse_task_model_command("/task-model top"),
+        Some(("top".to_string(), None))
+    );
+    assert_eq!(App::parse_task_model_command("/task-model"), None);
+}
+
+#[test]
+fn task_menu_offers_actions_for_the_clicked_task_as_commands() {
+    let mut app = App::default();
+    load_default_plan(&mut app, "Top");
+    assert!(!app.open_task_menu("missing"));
+
+    assert!(app.open_task_menu("impl"));
+    assert_eq!(
+        app.task_menu_entries(),
+        [
+            TaskMenuEntry::ToggleDetails,
+            TaskMenuEntry::ViewLogs,
+            TaskMenuEntry::ShowConversation,
+            TaskMenuEntry::Retry,
+            TaskMenuEntry::Skip,
+            TaskMenuEntry::ChangeModel,
+            TaskMenuEntry::AttachDocs,
+        ]
+    );
+    app.task_menu_move_down();
+    let (target, entry) = app.select_task_menu_entry().expect("entry selected");
+    assert!(!app.is_task_menu_open());
+    assert_eq!(entry.command(&target).as_deref(), Some("/logs top"));
+    assert_eq!(
+        TaskMenuEntry::AttachDocs.command(&target).as_deref(),
+        Some("/attach-docs task:impl")
+    );
+
+    app.set_task_model("top", Some("fast".to_string()));
+    app.open_task_model_menu(target.clone(), vec!["fast".to_string(), "deep".to_string()]);
+    assert_eq!(app.task_menu_selected_index(), 1);
+    app.task_menu_move_up();
+    let (_, entry) = app.select_task_menu_entry().expect("profile selected");
+    assert_eq!(
+        entry.command(&target).as_deref(),
+        Some("/task-model top default")
+    );
+
+    assert!(app.open_task_menu("tw-runner"));
+    assert!(!app.task_menu_entries().contains(&TaskMenuEntry::AttachDocs));
+}
+#[test]
+fn task_thread_view_shows_only_lines_about_that_task() {
+    let mut app = App::default();
+    load_default_plan(&mut app, "Top");
+    app.push_agent_message("Agent: I split task impl into two passes.");
+    app.push_agent_message("System: Saved planner.md.");
+    app.push_agent_message("You: is `top` done yet?");
+    app.push_agent_message("Agent: Nothing about tasks here.");
+
+    assert!(!app.show_task_thread("missing"));
+    assert_eq!(app.chat_view(), ChatView::Chat);
+    assert!(app.show_task_thread("impl"));
+    let ChatView::Task(top_task_id) = app.chat_view() else {
+        panic!("task thread should be shown");
+    };
+    assert_eq!(app.task_thread_label(top_task_id), "top");
+    assert_eq!(
+        app.displayed_chat_transcript().recent(),
+        [
+            "Agent: I split task impl into two passes.",
+            "You: is `top` done yet?"
+        ]
+    );
+    app.push_agent_message(format!("System: Task #{top_task_id} failed its audit."));
+    assert_eq!(app.displayed_chat_transcript().len(), 3);
+
+    assert_eq!(app.toggle_chat_view(), ChatView::Chat);
+}
+
+#[test]
+fn jobs_command_parses_one_based_positions() {
+    assert_eq!(App::parse_jobs_command("/jobs"), Some(JobsCommand::List));
+    assert_eq!(
+        App::parse_jobs_command("/JOBS move 3 1"),
+        Some(JobsCommand::Move { from: 2, to: 0 })
+    );
+    assert_eq!(
+        App::parse_jobs_command("/jobs drop 2"),
+        Some(JobsCommand::Drop(1))
+    );
+    assert_eq!(
+        App::parse_jobs_command("/jobs drop 0"),
+        Some(JobsCommand::List)
+    );
+    assert_eq!(
+        App::parse_jobs_command("/jobs move 1"),
+        Some(JobsCommand::List)
+    );
+    assert_eq!(App::parse_jobs_command("/jobsx"), None);
+}
+
+#[test]
+fn pin_models_command_pins_or_unpins() {
+    assert_eq!(
+        App::parse_pin_models_command("/pin-models"),
+        Some(PinModelsCommand::Pin)
+    );
+    assert_eq!(
+        App::parse_pin_models_command(" /PIN-MODELS Off "),
+        Some(PinModelsCommand::Unpin)
+    );
+    assert_eq!(
+        App::parse_pin_models_command("/pin-models now"),
+        Some(PinModelsCommand::Usage)
+    );
+    assert_eq!(App::parse_pin_models_command("/pin-modelsx"), None);
+}
+
+#[test]
+fn context_command_leaves_sections_out_of_the_next_master_prompt() {
+    assert_eq!(
+        App::parse_context_command("/context"),
+        Some(ContextCommand::Show)
+    );
+    assert_eq!(
+        App::parse_context_command("/CONTEXT Reset"),
+        Some(ContextCommand::Reset)
+    );
+    assert_eq!(
+        App::parse_context_command("/context everything"),
+        Some(ContextCommand::Usage)
+    );
+    assert_eq!(App::parse_context_command("/contextx"), None);
+
+    let mut app = App::default();
+    load_default_plan(&mut app, "Add feature Y");
+    let full = app.prepare_master_prompt("Add feature Y", "/tmp/tasks.json");
+    assert!(!full.contains(OMITTED_SECTION));
+
+    let reply = app.apply_context_command(
+        App::parse_context_command("/context tasks").expect("context command"),
+    );
+    assert!(reply.contains("`tasks` is now left out of the next master prompt"));
+    assert!(reply.contains("[ ] tasks"));
+    app.apply_context_command(ContextCommand::Toggle(ContextSection::ProjectInfo));
+    app.apply_context_command(ContextCommand::Toggle(ContextSection::Planner));
+
+    let lean = app.prepare_master_prompt("Add feature Y", "/tmp/tasks.json");
+    assert!(lean.contains(&format!("Current task tree:\n{OMITTED_SECTION}")));
+    assert!(!lean.contains("Rolling task context:\n(left out"));
+    assert_eq!(app.project_info_for_prompt(Some("# Project")), None);
+    let planner = app.prepare_planner_prompt(
+        "Plan this change",
+        "/tmp/session/planner.md",
+        "/tmp/session/project-info.md",
+    );
+    assert!(planner.contains("do not read it for this reply"));
+    assert!(!planner.contains("/tmp/session/project-info.md"));
+
+    app.reset_prompt_sections();
+    assert_eq!(
+        app.prepare_master_prompt("Add feature Y", "/tmp/tasks.json"),
+        full
+    );
+    assert_eq!(
+        app.project_info_for_prompt(Some("# Project")),
+        Some("# Project")
+    );
+}
+
+#[test]
+fn cache_command_shows_stats_or_purges() {
+    assert_eq!(
+        App::parse_cache_command("/cache"),
+        Some(CacheCommand::Stats)
+    );
+    assert_eq!(
+        App::parse_cache_command(" /CACHE Purge "),
+        Some(CacheCommand::Purge)
+    );
+    assert_eq!(
+        App::parse_cache_command("/cache clear"),
+        Some(CacheCommand::Usage)
+    );
+    assert_eq!(App::parse_cache_command("/cached"), None);
+}
+
+#[test]
+fn plugin_command_lists_or_runs_a_plugin_with_its_input() {
+    assert_eq!(
+        App::parse_plugin_command("/plugin"),
+        Some(PluginCommand::List)
+    );
+    assert_eq!(
+        App::parse_plugin_command(" /Plugin license-check  src/lib.rs  Cargo.toml "),
+        Some(PluginCommand::Run {
+            name: "license-check".to_string(),
+            input: "src/lib.rs  Cargo.toml".to_string(),
+        })
+    );
+    assert_eq!(App::parse_plugin_command("/plugins"), None);
+    assert!(
+        App::default()
+            .plugin_list_message()
+            .starts_with("System: No plugins are installed.")
+    );
+}
+
+#[test]
+fn cancel_command_names_the_sub_agent_to_stop() {
+    assert_eq!(
+        App::parse_cancel_command("/cancel docs"),
+        Some(CancelCommand::Docs)
+    );
+    assert_eq!(
+        App::parse_cancel_command("/cancel context"),
+        Some(CancelCommand::Context)
+    );
+    assert_eq!(
+        App::parse_cancel_command("/CANCEL TaskCheck"),
+        Some(CancelCommand::TaskCheck)
+    );
+    assert_eq!(
+        App::parse_cancel_command("/cancel"),
+        Some(CancelCommand::Usage)
+    );
+    assert_eq!(
+        App::parse_cancel_command("/cancel docs now"),
+        Some(CancelCommand::Usage)
+    );
+    assert_eq!(App::parse_cancel_command("/cancelled"), None);
+}
+
+#[test]
+fn tasks_command_expands_number_lists_and_ranges() {
+    assert_eq!(
+        App::parse_tasks_command("/tasks done 3,5-7"),
+        Some(TasksCommand::Done(vec![3, 5, 6, 7]))
+    );
+    assert_eq!(
+        App::parse_tasks_command("/TASKS Reset 4, 2-4"),
+        Some(TasksCommand::Reset(vec![4, 2, 3]))
+    );
+    assert_eq!(
+        App::parse_tasks_command("/tasks delete 9"),
+        Some(TasksCommand::Delete(vec![9]))
+    );
+    for malformed in [
+        "/tasks",
+        "/tasks done",
+        "/tasks done 0",
+        "/tasks done 7-5",
+        "/tasks drop 1",
+    ] {
+        assert_eq!(
+            App::parse_tasks_command(malformed),
+            Some(TasksCommand::Usage)
+        );
+    }
+    assert_eq!(App::parse_tasks_command("/taskset done 1"), None);
+}
+
+#[test]
+fn import_plan_command_seeds_the_planner_or_converts_to_tasks() {
+    assert_eq!(
+        App::parse_import_plan_command("/import-plan"),
+        Some(PlanImport::Planner)
+    );
+    assert_eq!(
+        App::parse_import_plan_command(" /import-plan TASKS "),
+        Some(PlanImport::Tasks)
+    );
+    assert_eq!(App::parse_import_plan_command("/import-plan later"), None);
+    assert_eq!(App::parse_import_plan_command("/import-planner"), None);
+}
+
+#[test]
+fn master_planner_write_keeps_unsaved_edits_or_opens_a_conflict() {
+    let mut app = App::default();
+    app.load_planner_markdown("# Plan\n".to_string());
+    app.reconcile_planner_markdown("# Plan\n- from master\n".to_string());
+    assert_eq!(app.planner_markdown(), "# Plan\n- from master\n");
+    assert_eq!(app.planner_saved_markdown(), "# Plan\n- from master\n");
+
+    app.set_planner_markdown("# Plan\n- from master\n- mine\n".to_string());
+    assert!(!app.reconcile_planner_markdown("# Plan\n- from master\n".to_string()));
+    assert_eq!(app.planner_markdown(), "# Plan\n- from master\n- mine\n");
+    assert!(app.edit_conflict().is_none());
+
+    assert!(app.reconcile_planner_markdown("# Plan\n- rewritten\n".to_string()));
+    assert_eq!(app.planner_markdown(), "# Plan\n- from master\n- mine\n");
+    let conflict = app.edit_conflict().expect("planner conflict");
+    assert_eq!(conflict.file, ConflictFile::Planner);
+    assert_eq!(conflict.base, "# Plan\n- from master\n");
+    assert_eq!(conflict.theirs, "# Plan\n- rewritten\n");
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .expect("conflict notice")
+            .contains("/conflict merge")
+    );
+}
+
+#[test]
+fn conflict_command_parses_choices() {
+    assert_eq!(
+        App::parse_conflict_command("/conflict Mine"),
+        Some(Some(ConflictChoice::Mine))
+    );
+    assert_eq!(
+        App::parse_conflict_command("/conflict theirs"),
+        Some(Some(ConflictChoice::Theirs))
+    );
+    assert_eq!(App::parse_conflict_command("/conflict"), Some(None));
+    assert_eq!(App::parse_conflict_command("/conflict both"), Some(None));
+    assert_eq!(App::parse_conflict_command("/conflicts"), None);
+}
+
+#[test]
+fn lock_command_parses_its_action() {
+    assert_eq!(
+        App::parse_lock_command("/lock Takeover"),
+        Some(Some(LockAction::Takeover))
+    );
+    assert_eq!(
+        App::parse_lock_command("/lock observe"),
+        Some(Some(LockAction::Observe))
+    );
+    assert_eq!(App::parse_lock_command("/lock"), Some(None));
+    assert_eq!(App::parse_lock_command("/locks"), None);
+}
+
+#[test]
+fn speed_command_parses_profiles_and_routes_worker_models() {
+    assert_eq!(
+        App::parse_speed_command("/speed ECO"),
+        Some(Some(SpeedProfile::Eco))
+    );
+    assert_eq!(App::parse_speed_command("/speed"), Some(None));
+    assert_eq!(App::parse_speed_command("/speed turbo"), Some(None));
+    assert_eq!(App::parse_speed_command("/speed max now"), Some(None));
+    assert_eq!(App::parse_speed_command("/speedy"), None);
+
+    let mut app = App::default();
+    load_default_plan(&mut app, "Top");
+    app.set_speed_profile(SpeedProfile::Eco);
+    app.start_execution();
+    let job = app.start_next_worker_job().expect("implementor job");
+    assert_eq!(job.model_profile.as_deref(), Some("small-smart"));
+}
+
+#[test]
+fn staged_chat_command_gives_the_draft_back_after_submit() {
+    let mut app = App::default();
+    for ch in "half typed".chars() {
+        app.input_char(ch);
+    }
+    app.stage_chat_command("/retry impl".to_string());
+    assert_eq!(app.chat_input(), "/retry impl");
+    assert_eq!(
+        app.consume_chat_input_trimmed().as_deref(),
+        Some("/retry impl")
+    );
+    app.restore_staged_chat_draft();
+    assert_eq!(app.chat_input(), "half typed");
+}
+
+#[test]
+fn subagent_pane_collapses_repeats_until_verbose_is_toggled_on() {
+    let mut app = App::default();
+    assert!(App::is_verbose_command(" /VERBOSE "));
+    let before = app.left_top_lines().len();
+    for line in ["warning: slow"; 3].into_iter().chain(["⠙ Thinking"]) {
+        app.push_filtered_subagent_output("docs", line, format!("Docs: {line}"));
+    }
+    assert_eq!(
+        &app.left_top_lines()[before..],
+        ["Docs: warning: slow (+2 similar)"]
+    );
+    assert_eq!(app.filtered_output_lines(), 3);
+
+    assert!(app.toggle_verbose_output());
+    app.push_filtered_subagent_output("docs", "⠙ Thinking", "Docs: ⠙ Thinking".to_string());
+    assert_eq!(
+        app.left_top_lines().last().map(String::as_str),
+        Some("Docs: ⠙ Thinking")
+    );
+    assert!(!app.toggle_verbose_output());
+}
+#[cfg(unix)]
+#[test]
+fn console_command_runs_in_the_pane_and_keeps_output_on_request() {
+    assert_eq!(
+        App::parse_console_command("/console"),
+        Some(ConsoleCommand::Toggle)
+    );
+    assert_eq!(
+        App::parse_console_command("/console run cargo test -q"),
+        Some(ConsoleCommand::Run("cargo test -q".to_string()))
+    );
+    assert_eq!(
+        App::parse_console_command("/console keep it"),
+        Some(ConsoleCommand::Usage)
+    );
+    assert_eq!(App::parse_console_command("/consoles"), None);
+
+    let mut app = App::default();
+    let cwd = std::env::temp_dir();
+    assert_eq!(
+        app.handle_console_command(ConsoleCommand::Keep, &cwd),
+        "System: No finished console command to keep."
+    );
+    app.handle_console_command(ConsoleCommand::Run("echo hello".to_string()), &cwd);
+    assert!(app.is_console_mode());
+    let deadline = std::time::Instant::now() + Duration::from_secs(10);
+    while !app.console_lines().contains(&"[exit code 0]".to_string()) {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "console command did not finish"
+        );
+        app.poll_console();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(
+        app.console_lines()[..3],
+        ["$ echo hello", "hello", "[exit code 0]"]
+    );
+    let chat = app.chat_transcript();
+    assert!(
+        chat.line(chat.len() - 1)
+            .unwrap_or_default()
+            .contains("`echo hello` finished (exit code 0). /console keep")
+    );
+    app.handle_console_command(ConsoleCommand::Keep, &cwd);
+    assert_eq!(
+        app.rolling_context_entries(),
+        vec!["User ran `echo hello` in the workspace console (exit code 0); output: hello"]
+    );
+    assert!(!app.toggle_console_mode());
+}
+
+#[test]
+fn console_commands_do_not_run_in_safe_mode() {
+    let mut app = App::default();
+    app.set_safe_mode(true);
+    let marker = std::env::temp_dir().join(format!("agentbob-safe-console-{}", std::process::id()));
+    let reply = app.handle_console_command(
+        ConsoleCommand::Run(format!("touch {}", marker.display())),
+        &std::env::temp_dir(),
+    );
+    assert_eq!(reply, "System: /console run is disabled in safe mode.");
+    assert!(app.console_lines().is_empty());
+    assert!(!marker.exists());
+}
@@ -207,3 +207,91 @@ fn runtime_storage_dir_prefers_agentbob_config_over_legacy_metaagent_config() {
         );
     });
 }
+
+#[test]
+fn project_config_overlays_global_config_with_provenance() {
+    with_temp_home("artifact-io-project-config", |home| {
+        let global_config = home.join(".agentbob/config.toml");
+        fs::create_dir_all(global_config.parent().expect("global config parent"))
+            .expect("create global config dir");
+        fs::write(
+            &global_config,
+            r#"
+            [backend]
+            selected = "claude"
+
+            [workflow]
+            final_audit = "auto"
+            "#,
+        )
+        .expect("write global config");
+        let project = home.join("project");
+        fs::create_dir_all(project.join(".metaagent")).expect("create project config dir");
+        fs::write(
+            project.join(".metaagent/config.toml"),
+            r#"
+            [workflow]
+            final_audit = "never"
+
+            [codex.agent_profiles]
+            master = "small-smart"
+            "#,
+        )
+        .expect("write project config");
+
+        let merged = parse_toml_table(
+            &load_merged_metaagent_config_text_in(&project).expect("load merged config"),
+        )
+        .expect("parse merged config");
+        assert_eq!(merged["workflow"]["final_audit"].as_str(), Some("never"));
+        assert_eq!(merged["backend"]["selected"].as_str(), Some("claude"));
+        assert_eq!(
+            merged["codex"]["agent_profiles"]["master"].as_str(),
+            Some("small-smart")
+        );
+        assert_eq!(
+            merged["codex"]["agent_profiles"]["worker_auditor"].as_str(),
+            Some("large-smart")
+        );
+
+        let effective = effective_metaagent_config(Some(&project)).expect("effective config");
+        assert_eq!(
+            effective.project_file,
+            Some(project.join(".metaagent/config.toml"))
+        );
+        let source = |key: &str| {
+            effective
+                .entries
+                .iter()
+                .find(|entry| entry.key == key)
+                .map(|entry| entry.source)
+        };
+        assert_eq!(source("workflow.final_audit"), Some(ConfigSource::Project));
+        assert_eq!(
+            source("codex.agent_profiles.master"),
+            Some(ConfigSource::Project)
+        );
+        assert_eq!(source("backend.selected"), Some(ConfigSource::Global));
+        assert_eq!(source("logs.retention_days"), Some(ConfigSource::Default));
+
+        let global_only = effective_metaagent_config(None).expect("global config");
+        assert_eq!(global_only.project_file, None);
+        assert!(
+            global_only
+                .entries
+                .iter()
+                .all(|entry| entry.source != ConfigSource::Project)
+        );
+    });
+}
+
+#[test]
+fn home_directory_has_no_project_config_overlay() {
+    with_temp_home("artifact-io-home-project-config", |home| {
+        assert_eq!(project_config_file_path(home), None);
+        assert_eq!(
+            project_config_file_path(&home.join("repo")),
+            Some(home.join("repo/.metaagent/config.toml"))
+        );
+    });
+}
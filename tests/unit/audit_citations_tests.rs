@@ -0,0 +1,67 @@
+use super::*;
+
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(str::to_string).collect()
+}
+
+#[test]
+fn parse_reads_line_and_range_citations_in_common_notations() {
+    let citations = parse(&lines(
+        "FAIL\n\
+         - `src/app.rs:120-134`: scroll is not clamped.\n\
+         - Off-by-one in (./src/ui.rs:42), see also web/main.ts#L7-L9\n\
+         - src/app.rs:120-134 again, pass:4 and 12:30 are not citations",
+    ));
+
+    let found = citations
+        .iter()
+        .map(|citation| {
+            (
+                citation.text.as_str(),
+                citation.path.as_str(),
+                citation.start_line,
+                citation.end_line,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        vec![
+            ("src/app.rs:120-134", "src/app.rs", 120, 134),
+            ("./src/ui.rs:42", "src/ui.rs", 42, 42),
+            ("web/main.ts#L7-L9", "web/main.ts", 7, 9),
+        ]
+    );
+}
+
+#[test]
+fn verify_marks_missing_files_and_paths_outside_the_workspace() {
+    let root = std::env::temp_dir().join(format!("agentbob-citations-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("src")).expect("create workspace");
+    std::fs::write(root.join("src/lib.rs"), "fn main() {}\n").expect("write file");
+
+    let absolute = format!("{}:1", root.join("src/lib.rs").display());
+    let mut citations = parse(&lines(&format!(
+        "src/lib.rs:1 src/gone.rs:3 ../outside.rs:2 {absolute}"
+    )));
+    verify(&mut citations, &root);
+
+    let exists = citations
+        .iter()
+        .map(|citation| (citation.path.as_str(), citation.exists))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exists,
+        vec![
+            ("src/lib.rs", true),
+            ("src/gone.rs", false),
+            ("../outside.rs", false),
+            ("src/lib.rs", true),
+        ]
+    );
+    assert_eq!(
+        citations[1].describe(),
+        "- src/gone.rs:3 (file not found in the workspace)"
+    );
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+fn prompt(text: &str) -> AuditEventFile {
+    AuditEventFile::PromptSent {
+        program: "codex".to_string(),
+        model: Some("gpt-5".to_string()),
+        prompt: text.to_string(),
+    }
+}
+
+#[test]
+fn sha256_matches_reference_digests() {
+    assert_eq!(
+        sha256_hex(b""),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        sha256_hex(b"abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+}
+
+#[test]
+fn appended_entries_chain_and_resume_after_reopening() {
+    let path =
+        std::env::temp_dir().join(format!("agentbob-audit-trail-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut open = None;
+    append_to(&mut open, &path, prompt("Implement the parser")).expect("append prompt");
+    append_to(
+        &mut open,
+        &path,
+        AuditEventFile::FilesChanged {
+            task_id: "parser".to_string(),
+            pass: 1,
+            files: vec!["src/parser.rs".to_string()],
+        },
+    )
+    .expect("append files");
+    // A new process picks the chain up where the file ends.
+    let mut reopened = None;
+    append_to(
+        &mut reopened,
+        &path,
+        AuditEventFile::ApprovalDecision {
+            subject: "review".to_string(),
+            decision: "approve".to_string(),
+            detail: "task #1 pass 1".to_string(),
+        },
+    )
+    .expect("append approval");
+
+    let entries = read_entries(&path).expect("read trail");
+    assert_eq!(
+        entries.iter().map(|entry| entry.seq).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(entries[0].prev_hash, GENESIS_HASH);
+    assert_eq!(entries[2].prev_hash, entries[1].hash);
+    let verification = verify(&entries);
+    assert_eq!(verification.broken_at, None);
+    assert_eq!(verification.head_hash, entries[2].hash);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn verify_reports_the_first_tampered_or_missing_entry() {
+    let first = chain_entry(1, 10, prompt("one"), GENESIS_HASH).expect("entry");
+    let second = chain_entry(2, 20, prompt("two"), &first.hash).expect("entry");
+    let third = chain_entry(3, 30, prompt("three"), &second.hash).expect("entry");
+    let entries = vec![first.clone(), second.clone(), third.clone()];
+    assert_eq!(verify(&entries).broken_at, None);
+    assert_eq!(verify(&[]).head_hash, GENESIS_HASH);
+
+    let mut edited = entries.clone();
+    edited[1].event = prompt("something else");
+    assert_eq!(verify(&edited).broken_at, Some(2));
+
+    assert_eq!(verify(&[first, third]).broken_at, Some(3));
+}
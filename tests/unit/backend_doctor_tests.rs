@@ -0,0 +1,166 @@
+use super::*;
+use crate::agent::CodexCommandConfig;
+
+fn backend(kind: BackendKind) -> ConfiguredBackend {
+    ConfiguredBackend {
+        kind,
+        selected: true,
+        command: CodexCommandConfig::default_for_backend(kind),
+    }
+}
+
+fn ok(text: &str) -> io::Result<ProbeOutput> {
+    Ok(ProbeOutput {
+        success: true,
+        timed_out: false,
+        text: text.to_string(),
+    })
+}
+
+fn failed(text: &str) -> io::Result<ProbeOutput> {
+    Ok(ProbeOutput {
+        success: false,
+        timed_out: false,
+        text: text.to_string(),
+    })
+}
+
+#[test]
+fn missing_program_is_reported_without_further_probes() {
+    let diagnosis = diagnose_backend(
+        &backend(BackendKind::Claude),
+        &CodexAgentModelRouting::default(),
+        DoctorOptions::default(),
+        &|_, args, _| {
+            assert_eq!(args, ["--version"], "only the version probe should run");
+            Err(io::Error::new(io::ErrorKind::NotFound, "missing"))
+        },
+    );
+
+    assert!(!diagnosis.installed);
+    assert_eq!(
+        diagnosis.errors,
+        vec!["`claude` is not installed or not on PATH".to_string()]
+    );
+}
+
+#[test]
+fn codex_probes_report_version_flags_and_unavailable_models() {
+    let routing = CodexAgentModelRouting::from_toml_str(
+        r#"
+        [codex.agent_profiles]
+        worker_auditor = "small-smart"
+        "#,
+    )
+    .expect("parse routing");
+    let diagnosis = diagnose_backend(
+        &backend(BackendKind::Codex),
+        &routing,
+        DoctorOptions::default(),
+        &|_, args, _| match args.first().map(String::as_str) {
+            Some("--version") => ok("codex-cli 0.50.0\n"),
+            Some("login") => ok("Logged in using ChatGPT"),
+            Some("exec") if args.iter().any(|arg| arg == "--help") => ok(
+                "Usage: codex exec [OPTIONS]\n\nCommands:\n  resume  Resume a session\n\nOptions:\n      --json\n",
+            ),
+            Some("exec") if args.iter().any(|arg| arg == "gpt-5.1-codex-mini") => failed(
+                "stream error: The model `gpt-5.1-codex-mini` does not exist or you do not have access to it.",
+            ),
+            Some("exec") => ok("OK"),
+            other => panic!("unexpected probe: {other:?}"),
+        },
+    );
+
+    assert!(diagnosis.installed);
+    assert_eq!(diagnosis.version.as_deref(), Some("codex-cli 0.50.0"));
+    assert_eq!(diagnosis.supports_json_mode, Some(true));
+    assert_eq!(diagnosis.supports_resume, Some(true));
+    assert_eq!(diagnosis.authenticated, Some(true));
+    assert_eq!(
+        diagnosis.models,
+        vec![
+            ModelDiagnosis {
+                model: "gpt-5.1-codex-mini".to_string(),
+                used_by: vec!["worker_auditor".to_string()],
+                available: Some(false),
+            },
+            ModelDiagnosis {
+                model: "gpt-5.3-codex".to_string(),
+                used_by: vec![
+                    "master".to_string(),
+                    "master_report".to_string(),
+                    "project_info".to_string(),
+                    "docs_attach".to_string(),
+                    "task_check".to_string(),
+                    "worker_implementor".to_string(),
+                    "worker_test_writer".to_string(),
+                    "worker_final_audit".to_string(),
+                ],
+                available: Some(true),
+            },
+        ]
+    );
+    assert!(diagnosis.errors.is_empty());
+    assert_eq!(
+        diagnosis.warnings,
+        vec![
+            "model `gpt-5.1-codex-mini` (used by worker_auditor) is not available to this account"
+                .to_string()
+        ]
+    );
+}
+
+#[test]
+fn quick_mode_skips_prompt_probes_and_flags_logged_out_codex() {
+    let diagnosis = diagnose_backend(
+        &backend(BackendKind::Codex),
+        &CodexAgentModelRouting::default(),
+        DoctorOptions { quick: true },
+        &|_, args, _| match args.first().map(String::as_str) {
+            Some("--version") => ok("codex-cli 0.50.0"),
+            Some("login") => failed("Not logged in"),
+            Some("exec") if args.iter().any(|arg| arg == "--help") => ok("Options:\n"),
+            other => panic!("prompt probes should be skipped: {other:?}"),
+        },
+    );
+
+    assert_eq!(diagnosis.authenticated, Some(false));
+    assert_eq!(
+        diagnosis.errors,
+        vec!["not logged in (run `codex login`)".to_string()]
+    );
+    assert_eq!(diagnosis.supports_json_mode, Some(false));
+    assert_eq!(diagnosis.supports_resume, Some(false));
+    assert!(
+        diagnosis
+            .models
+            .iter()
+            .all(|model| model.available.is_none())
+    );
+    let report = BackendDoctorReport {
+        backends: vec![diagnosis],
+    };
+    assert_eq!(report.error_count(), 1);
+    assert_eq!(report.warning_count(), 2);
+    assert!(
+        report
+            .render_lines()
+            .contains(&"  model gpt-5.3-codex (master, master_report, project_info, docs_attach, task_check, worker_implementor, worker_auditor, worker_test_writer, worker_final_audit): not checked".to_string())
+    );
+}
+
+#[test]
+fn probe_failures_are_classified_from_cli_output() {
+    assert_eq!(
+        classify_probe_failure("Invalid API key · Please run /login"),
+        ProbeFailure::Unauthenticated
+    );
+    assert_eq!(
+        classify_probe_failure("model: claude-x not found"),
+        ProbeFailure::ModelUnavailable
+    );
+    assert_eq!(
+        classify_probe_failure("connection reset"),
+        ProbeFailure::Other
+    );
+}
@@ -0,0 +1,81 @@
+use super::*;
+
+fn call(agent_kind: &str, backend: &str, latency_ms: u64, exit_code: i32) -> BackendCallFileEntry {
+    BackendCallFileEntry::new(agent_kind, backend, None, latency_ms, exit_code, false)
+}
+
+#[test]
+fn percentiles_use_the_nearest_rank() {
+    let sorted = (1..=20).map(|n| n * 100).collect::<Vec<u64>>();
+    assert_eq!(percentile(&sorted, 50), 1_000);
+    assert_eq!(percentile(&sorted, 95), 1_900);
+    assert_eq!(percentile(&[700], 95), 700);
+    assert_eq!(percentile(&[], 50), 0);
+}
+
+#[test]
+fn stats_group_calls_by_agent_kind_and_backend() {
+    let mut retried = call("master", "codex", 9_000, 0);
+    retried.retry = true;
+    let entries = vec![
+        call("master", "codex", 1_000, 0),
+        call("master", "codex", 3_000, 1),
+        retried,
+        call("worker_implementor", "claude", 400, 0),
+    ];
+    let stats = BackendStats::from_entries(&entries);
+    assert_eq!(stats.total_calls, 4);
+    assert_eq!(
+        stats.by_agent,
+        vec![
+            BackendCallStats {
+                agent_kind: "master".to_string(),
+                backend: "codex".to_string(),
+                calls: 3,
+                failures: 1,
+                retries: 1,
+                p50_latency_ms: 3_000,
+                p95_latency_ms: 9_000,
+            },
+            BackendCallStats {
+                agent_kind: "worker_implementor".to_string(),
+                backend: "claude".to_string(),
+                calls: 1,
+                failures: 0,
+                retries: 0,
+                p50_latency_ms: 400,
+                p95_latency_ms: 400,
+            },
+        ]
+    );
+    let lines = stats.render_lines();
+    assert!(lines[3].starts_with("master"));
+    assert!(lines[3].ends_with("3.0s      9.0s"));
+    assert!(lines[4].contains("400ms"));
+    assert_eq!(
+        BackendStats::default().render_lines(),
+        ["No backend calls recorded yet."]
+    );
+}
+
+#[test]
+fn entries_round_trip_through_the_metrics_file() {
+    let dir = std::env::temp_dir().join(format!("agentbob-backend-metrics-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join(BACKEND_METRICS_FILE_NAME);
+    assert!(read_entries(&path).expect("missing file").is_empty());
+
+    let entry = call("task_check", "codex", 1_200, 0);
+    append_entry(&path, &entry).expect("append");
+    fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(b"not json\n"))
+        .expect("garbage line");
+    append_entry(&path, &entry).expect("append");
+    assert_eq!(
+        read_entries(&path).expect("read"),
+        vec![entry.clone(), entry]
+    );
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,59 @@
+use super::*;
+
+fn git_workspace(prefix: &str) -> Option<PathBuf> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("{prefix}-{}-{now}", std::process::id()));
+    fs::create_dir_all(root.join("src")).unwrap();
+    git(&root, &["init", "--quiet"], None).ok()?;
+    fs::write(root.join("src/lib.rs"), "pub fn one() -> u8 {\n    1\n}\n").unwrap();
+    fs::write(root.join("notes.md"), "keep me\n").unwrap();
+    fs::write(root.join(".gitignore"), "target/\n").unwrap();
+    Some(root)
+}
+
+#[test]
+fn diff_and_revert_cover_edits_deletions_and_new_files_since_the_snapshot() {
+    let Some(root) = git_workspace("bob-change-review") else {
+        return;
+    };
+    let snapshot = snapshot_worktree(&root).expect("snapshot");
+    assert_eq!(
+        diff_since(&snapshot).expect("empty diff"),
+        vec!["No workspace changes since the pass started.".to_string()]
+    );
+
+    fs::write(root.join("src/lib.rs"), "pub fn one() -> u8 {\n    2\n}\n").unwrap();
+    fs::remove_file(root.join("notes.md")).unwrap();
+    fs::create_dir_all(root.join("src/extra")).unwrap();
+    fs::write(root.join("src/extra/new.rs"), "pub fn two() {}\n").unwrap();
+    fs::create_dir_all(root.join("target")).unwrap();
+    fs::write(root.join("target/build.log"), "ignored\n").unwrap();
+
+    let diff = diff_since(&snapshot).expect("diff");
+    assert!(diff.iter().any(|line| line == "-    1"), "{diff:?}");
+    assert!(diff.iter().any(|line| line == "+    2"));
+    assert!(diff.iter().any(|line| line == "+++ b/src/extra/new.rs"));
+    assert!(diff.iter().any(|line| line == "--- a/notes.md"));
+    assert!(!diff.iter().any(|line| line.contains("build.log")));
+    assert_eq!(
+        changed_paths_since(&snapshot).expect("changed paths"),
+        ["notes.md", "src/extra/new.rs", "src/lib.rs"]
+    );
+
+    assert_eq!(revert_to(&snapshot).expect("revert"), 3);
+    assert_eq!(
+        fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+        "pub fn one() -> u8 {\n    1\n}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("notes.md")).unwrap(),
+        "keep me\n"
+    );
+    assert!(!root.join("src/extra/new.rs").exists());
+    assert!(root.join("target/build.log").exists());
+
+    let _ = fs::remove_dir_all(root);
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+#[test]
+fn extracts_questions_from_every_block_without_list_markers() {
+    let reply = "A few things first.\n\
+                 QUESTIONS_BEGIN\n\
+                 1. Which database should the cache use?\n\
+                 \n\
+                 - Is a migration acceptable?\n\
+                 2) Who reviews the rollout?\n\
+                 QUESTIONS_END\n\
+                 Not a question block.\n\
+                 QUESTIONS_BEGIN\n\
+                 Keep the old endpoint?\n\
+                 QUESTIONS_END\n";
+    assert_eq!(
+        extract_questions(reply),
+        [
+            "Which database should the cache use?",
+            "Is a migration acceptable?",
+            "Who reviews the rollout?",
+            "Keep the old endpoint?",
+        ]
+    );
+    assert!(extract_questions("No block here.").is_empty());
+    assert!(QuestionForm::new(Vec::new()).is_none());
+}
+
+#[test]
+fn enter_walks_the_fields_and_submits_on_the_last_one() {
+    let questions = vec!["Target?".to_string(), "Deadline?".to_string()];
+    let mut form = QuestionForm::new(questions).expect("form");
+    assert!(form.is_blank());
+    form.insert_text("staging\nonly");
+    form.backspace();
+    assert_eq!(form.submit_field(), FormStep::Editing);
+    assert_eq!(form.selected(), 1);
+    form.select_next();
+    assert_eq!(form.selected(), 0);
+    form.select_previous();
+    assert_eq!(form.submit_field(), FormStep::Submitted);
+    assert!(!form.is_blank());
+    assert_eq!(
+        form.combined_message(),
+        "Answers to your clarification questions:\n\
+         1. Target?\n   Answer: staging onl\n\
+         2. Deadline?\n   Answer: (no answer)\n"
+    );
+}
+
+#[test]
+fn answers_are_appended_to_the_clarifications_file() {
+    let dir = std::env::temp_dir().join(format!("agentbob-clarify-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("clarifications.md");
+    let mut form = QuestionForm::new(vec!["Target?".to_string()]).expect("form");
+    form.insert_text("staging");
+    append_clarifications(&path, &form, 0).expect("first append");
+    append_clarifications(&path, &form, 60).expect("second append");
+    let text = std::fs::read_to_string(&path).expect("read clarifications");
+    assert!(text.starts_with("# Clarifications\n\n## Answered Jan 1, 1970 00:00 UTC\n"));
+    assert!(text.contains("## Answered Jan 1, 1970 00:01 UTC\n"));
+    assert_eq!(text.matches("- Q: Target?\n  A: staging\n").count(), 2);
+    let _ = std::fs::remove_dir_all(&dir);
+}
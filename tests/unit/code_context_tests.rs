@@ -0,0 +1,88 @@
+use super::*;
+use std::path::PathBuf;
+
+fn temp_workspace(prefix: &str) -> PathBuf {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("{prefix}-{}-{now}", std::process::id()));
+    for (path, text) in [
+        ("src/main.rs", "fn main() {\n    run();\n}\n"),
+        (
+            "src/export.rs",
+            "use std::io;\n\n/// Writes rows as CSV.\npub fn export_csv_rows() -> io::Result<()> {\n    Ok(())\n}\n",
+        ),
+        (
+            "src/ledger.rs",
+            "pub struct Ledger;\n// calls export_csv_rows\n",
+        ),
+        ("docs/notes.md", "Unrelated notes about styling.\n"),
+        ("target/debug/export.rs", "generated export copy\n"),
+        (".hidden/export.rs", "hidden export copy\n"),
+    ] {
+        let file = root.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, text).unwrap();
+    }
+    root
+}
+
+#[test]
+fn excerpts_rank_named_paths_and_keyword_matches_and_skip_ignored_dirs() {
+    let root = temp_workspace("bob-code-context");
+    let policy = CodeContextPolicy {
+        max_files: 3,
+        max_excerpt_lines: 2,
+    };
+
+    let excerpts = relevant_file_excerpts(
+        &root,
+        "CSV export\nAdd headers to export_csv_rows and wire it up in `src/main.rs`.",
+        &policy,
+    );
+    let paths = excerpts
+        .iter()
+        .map(|excerpt| excerpt.relative_path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["src/main.rs", "src/export.rs", "src/ledger.rs"]);
+    assert_eq!(
+        excerpts[1],
+        FileExcerpt {
+            relative_path: "src/export.rs".to_string(),
+            start_line: 3,
+            lines: vec![
+                "/// Writes rows as CSV.".to_string(),
+                "pub fn export_csv_rows() -> io::Result<()> {".to_string(),
+            ],
+        }
+    );
+
+    assert!(relevant_file_excerpts(&root, "the and with", &policy).is_empty());
+    let disabled = CodeContextPolicy {
+        max_files: 0,
+        ..policy
+    };
+    assert!(relevant_file_excerpts(&root, "export_csv_rows", &disabled).is_empty());
+
+    fs::write(root.join(".metaagentignore"), "ledger.rs\n").unwrap();
+    let paths = relevant_file_excerpts(&root, "export_csv_rows", &policy)
+        .into_iter()
+        .map(|excerpt| excerpt.relative_path)
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["src/export.rs"]);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn rendered_context_labels_each_excerpt_with_its_line_range() {
+    assert_eq!(render_code_context(&[]), "");
+    let rendered = render_code_context(&[FileExcerpt {
+        relative_path: "src/lib.rs".to_string(),
+        start_line: 10,
+        lines: vec!["pub mod a;".to_string(), "pub mod b;".to_string()],
+    }]);
+    assert!(rendered.starts_with("Relevant workspace files"));
+    assert!(rendered.ends_with("\n--- src/lib.rs (lines 10-11) ---\npub mod a;\npub mod b;\n"));
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+fn temp_dir(prefix: &str) -> PathBuf {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    std::env::temp_dir().join(format!("{prefix}-{}-{now}", std::process::id()))
+}
+
+fn write(root: &Path, path: &str, text: &str) {
+    let file = root.join(path);
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(file, text).unwrap();
+}
+
+fn workspace_and_store(prefix: &str) -> (PathBuf, SessionStore) {
+    let base = temp_dir(prefix);
+    let root = base.join("workspace");
+    write(
+        &root,
+        "src/export.rs",
+        "pub fn export_csv_rows(rows: &[Row]) {\n    write_csv_header();\n}\n",
+    );
+    write(
+        &root,
+        "src/ledger.rs",
+        "pub struct Ledger {\n    balance: i64,\n}\n",
+    );
+    write(&root, "target/debug/export.rs", "export_csv_rows copy\n");
+    let store = SessionStore::open_existing(&root, base.join("session")).expect("open store");
+    (root, store)
+}
+
+#[test]
+fn embedding_config_defaults_to_hashed_and_validates_command_backend() {
+    assert_eq!(
+        embedding_backend_from_toml("").expect("defaults"),
+        EmbeddingBackend::Hashed { dimensions: 256 }
+    );
+    assert_eq!(
+        embedding_backend_from_toml(
+            "[embeddings]\nbackend = \"command\"\ncommand = \"embed.sh\"\n"
+        )
+        .expect("command backend"),
+        EmbeddingBackend::Command {
+            command: "embed.sh".to_string()
+        }
+    );
+    for invalid in [
+        "[embeddings]\nbackend = \"command\"\n",
+        "[embeddings]\ndimensions = 0\n",
+        "[embeddings]\nbackend = \"remote\"\n",
+    ] {
+        assert_eq!(
+            embedding_backend_from_toml(invalid).unwrap_err().kind(),
+            io::ErrorKind::InvalidData,
+            "{invalid}"
+        );
+    }
+    assert_eq!(
+        embedding_backend_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+            .expect("embedded defaults"),
+        EmbeddingBackend::default()
+    );
+}
+
+#[test]
+fn refresh_reembeds_only_changed_files_and_drops_deleted_ones() {
+    let (root, store) = workspace_and_store("bob-code-index-refresh");
+    let backend = EmbeddingBackend::Hashed { dimensions: 64 };
+
+    let summary = refresh_code_index(&root, &store, &backend, None).expect("full refresh");
+    assert_eq!(
+        summary,
+        RefreshSummary {
+            indexed: 2,
+            removed: 0,
+            unchanged: 0
+        }
+    );
+    let index = read_code_index(&store)
+        .expect("read")
+        .expect("index exists");
+    assert_eq!(index.backend, "hashed-64");
+    assert_eq!(
+        index.files.keys().collect::<Vec<_>>(),
+        vec!["src/export.rs", "src/ledger.rs"]
+    );
+
+    write(&root, "src/ledger.rs", "pub struct Ledger;\n");
+    fs::remove_file(root.join("src/export.rs")).unwrap();
+    let changed = ["src/ledger.rs".to_string(), "src/export.rs".to_string()];
+    let summary =
+        refresh_code_index(&root, &store, &backend, Some(&changed)).expect("partial refresh");
+    assert_eq!(
+        summary,
+        RefreshSummary {
+            indexed: 1,
+            removed: 1,
+            unchanged: 0
+        }
+    );
+    let summary = refresh_code_index(&root, &store, &backend, None).expect("no-op refresh");
+    assert_eq!(summary.unchanged, 1);
+    assert_eq!(summary.indexed, 0);
+
+    // Files under a `.metaagentignore` entry leave the index, even when named as changed.
+    write(&root, ".metaagentignore", "src/ledger.rs\n");
+    let summary =
+        refresh_code_index(&root, &store, &backend, Some(&changed[..1])).expect("ignored refresh");
+    assert_eq!(summary.removed, 1);
+    fs::remove_file(root.join(".metaagentignore")).unwrap();
+
+    let other = EmbeddingBackend::Hashed { dimensions: 32 };
+    let summary = refresh_code_index(&root, &store, &other, Some(&[])).expect("rebuild");
+    assert_eq!(summary.indexed, 1);
+    assert_eq!(
+        read_code_index(&store).unwrap().unwrap().backend,
+        "hashed-32"
+    );
+
+    let _ = fs::remove_dir_all(root.parent().unwrap());
+}
+
+#[test]
+fn search_ranks_matching_chunks_first_and_returns_their_text() {
+    let (root, store) = workspace_and_store("bob-code-index-search");
+    let backend = EmbeddingBackend::default();
+
+    let hits = search_code(
+        &root,
+        &store,
+        &backend,
+        "where is the csv export written",
+        5,
+    )
+    .expect("search");
+    assert_eq!(hits[0].path, "src/export.rs");
+    assert_eq!((hits[0].start_line, hits[0].end_line), (1, 3));
+    assert!(hits[0].excerpt.contains("export_csv_rows"));
+    assert!(hits.iter().all(|hit| !hit.path.starts_with("target/")));
+
+    write(
+        &root,
+        "src/balance.rs",
+        "fn ledger_balance(ledger: &Ledger) -> i64 {\n    0\n}\n",
+    );
+    let hits = search_code(&root, &store, &backend, "ledger balance", 1).expect("search");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].path, "src/balance.rs");
+
+    let _ = fs::remove_dir_all(root.parent().unwrap());
+}
@@ -0,0 +1,93 @@
+use super::*;
+
+fn ids() -> Vec<String> {
+    vec!["parse".to_string(), "write-output".to_string()]
+}
+
+fn hint_for(input: &str) -> Option<ArgHint> {
+    let task_ids = ids();
+    hint(
+        input,
+        ArgContext {
+            task_ids: &task_ids,
+        },
+    )
+}
+
+#[test]
+fn hints_appear_once_a_known_command_is_followed_by_a_space() {
+    assert_eq!(hint_for("/speed"), None);
+    assert_eq!(hint_for("/planner "), None);
+    assert_eq!(hint_for("hello /speed "), None);
+
+    let speed = hint_for("/speed ").expect("hint");
+    assert_eq!(speed.usage, "/speed [<eco|standard|max>]");
+    assert_eq!(speed.candidates, vec!["eco", "standard", "max"]);
+    assert_eq!(speed.problem, None);
+
+    let narrowed = hint_for("/SPEED St").expect("hint");
+    assert_eq!(narrowed.candidates, vec!["standard"]);
+    assert_eq!(narrowed.problem, None);
+}
+
+#[test]
+fn invalid_arguments_are_flagged_while_typing() {
+    assert_eq!(
+        hint_for("/speed turbo").expect("hint").problem.as_deref(),
+        Some("`turbo` is not one of eco, standard, max")
+    );
+    assert_eq!(
+        hint_for("/speed max now").expect("hint").problem.as_deref(),
+        Some("unexpected `now`")
+    );
+    assert_eq!(
+        hint_for("/jobs drop x").expect("hint").problem.as_deref(),
+        Some("<n> must be a position like 1 or 2")
+    );
+}
+
+#[test]
+fn task_ids_are_listed_and_checked_against_the_plan() {
+    let start = hint_for("/start ").expect("hint");
+    assert_eq!(start.usage, "/start [task:<id>]");
+    assert_eq!(start.candidates, vec!["task:parse", "task:write-output"]);
+
+    let typed = hint_for("/start task:w").expect("hint");
+    assert_eq!(typed.candidates, vec!["task:write-output"]);
+    assert_eq!(typed.problem, None);
+
+    assert_eq!(
+        hint_for("/start task:missing")
+            .expect("hint")
+            .problem
+            .as_deref(),
+        Some("no task with id `missing`")
+    );
+    assert_eq!(
+        hint_for("/start parse ").expect("hint").problem.as_deref(),
+        Some("expected task:<id>, not `parse`")
+    );
+    assert_eq!(hint_for("/retry parse ").expect("hint").problem, None);
+}
+
+#[test]
+fn commands_with_several_forms_keep_the_ones_still_possible() {
+    let jobs = hint_for("/jobs ").expect("hint");
+    assert_eq!(
+        jobs.usage,
+        "/jobs | /jobs move <from> <to> | /jobs drop <n>"
+    );
+    assert_eq!(jobs.candidates, vec!["move", "drop"]);
+
+    let moving = hint_for("/jobs move 2 ").expect("hint");
+    assert_eq!(moving.usage, "/jobs move <from> <to>");
+    assert_eq!(moving.problem, None);
+
+    let review = hint_for("/review changes please split the parser").expect("hint");
+    assert_eq!(review.usage, "/review changes <feedback>");
+    assert_eq!(review.problem, None);
+
+    let docs = hint_for("/attach-docs task:parse ").expect("hint");
+    assert_eq!(docs.usage, "/attach-docs task:<id> snapshot");
+    assert_eq!(docs.candidates, vec!["snapshot"]);
+}
@@ -0,0 +1,103 @@
+use super::*;
+
+const CONFIG: &str = r#"
+[commands.aliases]
+s = "/start"
+"/rt" = "/retry"
+ship-one = "/start task:parse"
+
+[commands.macros]
+ship = ["/convert", "Keep the plan small.", "/start"]
+"#;
+
+#[test]
+fn aliases_keep_typed_arguments_and_macros_list_their_steps() {
+    let registry = CommandRegistry::from_toml_str(CONFIG).expect("config should parse");
+
+    assert_eq!(
+        registry.expand("/s"),
+        Some(UserCommand::Alias("/start".to_string()))
+    );
+    assert_eq!(
+        registry.expand(" /RT  write-output "),
+        Some(UserCommand::Alias("/retry  write-output".to_string()))
+    );
+    assert_eq!(registry.expand_alias("/rt "), Some("/retry ".to_string()));
+    assert_eq!(
+        registry.expand("/ship"),
+        Some(UserCommand::Macro(vec![
+            "/convert".to_string(),
+            "Keep the plan small.".to_string(),
+            "/start".to_string(),
+        ]))
+    );
+    assert_eq!(registry.expand("/ship now"), None);
+    assert_eq!(registry.expand("/start"), None);
+    assert_eq!(registry.expand("/sx"), None);
+    assert_eq!(registry.expand_alias("/ship"), None);
+}
+
+#[test]
+fn suggestions_list_user_commands_after_the_built_ins() {
+    let registry = CommandRegistry::from_toml_str(CONFIG).expect("config should parse");
+
+    let commands = registry
+        .suggestions("/s")
+        .into_iter()
+        .map(|suggestion| suggestion.command)
+        .collect::<Vec<_>>();
+    assert_eq!(commands.first().map(String::as_str), Some("/start"));
+    assert_eq!(commands[commands.len() - 3..], ["/s", "/ship", "/ship-one"]);
+    let ship = registry.suggestions("/ship");
+    assert_eq!(
+        ship[0].description,
+        "Macro: /convert; Keep the plan small.; /start"
+    );
+    assert_eq!(ship[1].description, "Alias for /start task:parse");
+    assert!(
+        CommandRegistry::default()
+            .suggestions("/s")
+            .iter()
+            .all(|suggestion| suggestion.command != "/s")
+    );
+}
+
+#[test]
+fn invalid_user_commands_are_rejected() {
+    for (config, message) in [
+        (
+            "[commands.aliases]\nstart = \"/speed eco\"\n",
+            "commands.aliases.start would hide the built-in /start",
+        ),
+        (
+            "[commands.aliases]\ns = \"start\"\n",
+            "commands.aliases.s must be a slash command",
+        ),
+        (
+            "[commands.macros]\nship = []\n",
+            "commands.macros.ship must be a non-empty list",
+        ),
+        (
+            "[commands.aliases]\nship = \"/start\"\n\n[commands.macros]\nship = [\"/convert\"]\n",
+            "/ship is defined both in commands.aliases and commands.macros",
+        ),
+        (
+            "[commands.aliases]\ns = \"/start\"\n\n[commands.macros]\nship = [\"/convert\", \"/s\"]\n",
+            "/ship uses /s, which is itself an alias or macro",
+        ),
+        (
+            "[commands.aliases]\n\"two words\" = \"/start\"\n",
+            "must be a single word",
+        ),
+    ] {
+        let err = CommandRegistry::from_toml_str(config).expect_err(config);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains(message), "{err}");
+    }
+    assert!(
+        CommandRegistry::from_toml_str("[ui]\nnarrow_width = 80\n")
+            .expect("no [commands] table")
+            .expand("/s")
+            .is_none()
+    );
+}
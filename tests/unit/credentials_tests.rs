@@ -0,0 +1,127 @@
+use super::*;
+
+fn store_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "agentbob-credentials-{name}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn file_store(dir: &Path, passphrase: Option<&str>) -> CredentialFile {
+    CredentialFile {
+        iterations: 2,
+        ..CredentialFile::new(dir, passphrase.map(str::to_string))
+    }
+}
+
+#[test]
+fn file_store_round_trips_secrets_without_writing_them_in_plain_text() {
+    let dir = store_dir("round-trip");
+    let store = file_store(&dir, Some("correct horse"));
+    assert_eq!(store.get(LINEAR_TOKEN), Ok(None));
+    let secret = "lin_api_0123456789abcdef0123456789abcdef-long-enough-for-two-blocks";
+    store.set(LINEAR_TOKEN, secret).expect("set");
+    store.set(SMTP_PASSWORD, "app-password").expect("set");
+    assert_eq!(store.get(LINEAR_TOKEN), Ok(Some(secret.to_string())));
+    assert_eq!(store.get(JIRA_TOKEN), Ok(None));
+
+    let text = std::fs::read_to_string(dir.join(STORE_FILE_NAME)).expect("read store");
+    assert!(!text.contains("lin_api") && !text.contains("app-password"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(dir.join(STORE_FILE_NAME))
+            .expect("metadata")
+            .permissions();
+        assert_eq!(mode.mode() & 0o777, 0o600);
+    }
+
+    assert_eq!(store.remove(LINEAR_TOKEN), Ok(true));
+    assert_eq!(store.remove(LINEAR_TOKEN), Ok(false));
+    assert_eq!(store.get(LINEAR_TOKEN), Ok(None));
+    assert_eq!(
+        store.get(SMTP_PASSWORD),
+        Ok(Some("app-password".to_string()))
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn tampered_entries_and_wrong_passphrases_are_reported() {
+    let dir = store_dir("tamper");
+    let store = file_store(&dir, Some("correct horse"));
+    store.set(JIRA_TOKEN, "jira-secret").expect("set");
+    let path = dir.join(STORE_FILE_NAME);
+    let mut file: StoreFile =
+        serde_json::from_str(&std::fs::read_to_string(&path).expect("read")).expect("parse");
+    let sealed = file.secrets.get_mut(JIRA_TOKEN).expect("entry");
+    let flipped = if sealed.ciphertext.starts_with('0') {
+        "1"
+    } else {
+        "0"
+    };
+    sealed.ciphertext.replace_range(..1, flipped);
+    std::fs::write(&path, serde_json::to_string(&file).expect("json")).expect("write");
+    assert_eq!(
+        store.get(JIRA_TOKEN),
+        Err(
+            "Failed to decrypt the jira credential: entry was modified or encrypted with another key"
+                .to_string()
+        )
+    );
+
+    let wrong = file_store(&dir, Some("battery staple"));
+    let expected = Err(format!(
+        "{PASSPHRASE_ENV} does not unlock {STORE_FILE_NAME}"
+    ));
+    assert_eq!(wrong.get(JIRA_TOKEN), expected);
+    assert_eq!(wrong.set(LINEAR_TOKEN, "token"), expected.map(|_| ()));
+
+    let mut file: StoreFile =
+        serde_json::from_str(&std::fs::read_to_string(&path).expect("read")).expect("parse");
+    file.iterations = u32::MAX;
+    std::fs::write(&path, serde_json::to_string(&file).expect("json")).expect("write");
+    assert_eq!(
+        store.get(JIRA_TOKEN),
+        Err(format!(
+            "{STORE_FILE_NAME} uses {} key derivation rounds; only 2 are supported",
+            u32::MAX
+        ))
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn file_store_fails_closed_without_a_passphrase() {
+    let dir = store_dir("no-passphrase");
+    let store = file_store(&dir, Some(""));
+    let err = store.set(LINEAR_TOKEN, "token").expect_err("no passphrase");
+    assert!(err.contains(PASSPHRASE_ENV), "{err}");
+    assert!(!dir.join(STORE_FILE_NAME).exists());
+    assert_eq!(store.get(LINEAR_TOKEN), Ok(None));
+    assert_eq!(store.remove(LINEAR_TOKEN), Ok(false));
+}
+
+#[test]
+fn derived_keys_match_pbkdf2_hmac_sha256() {
+    // RFC 7914 section 11 test vector for PBKDF2-HMAC-SHA256.
+    assert_eq!(
+        encode_hex(&derive_key("passwd", b"salt", 1)),
+        "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc"
+    );
+}
+
+#[test]
+fn service_names_are_validated() {
+    assert_eq!(validate_service("github-app_2"), Ok(()));
+    assert_eq!(
+        validate_service("Linear"),
+        Err(
+            "credential name \"Linear\" must use lower-case letters, digits, '-' or '_'"
+                .to_string()
+        )
+    );
+    assert!(validate_service("").is_err());
+}
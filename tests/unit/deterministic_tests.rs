@@ -196,6 +196,79 @@ fn deterministic_runner_fails_when_command_missing() {
     assert!(saw_completed);
 }
 
+/// Runs `command` with failure re-runs on and returns the completion and flaky verdict.
+fn run_with_rerun(command: &str) -> (bool, i32, bool) {
+    let mut runner = TestRunnerAdapter::new();
+    runner.set_rerun_failures(true);
+    runner.run_tests_with_command(Some(command));
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        for event in runner.drain_events() {
+            if let AgentEvent::Completed { success, code } = event {
+                return (success, code, runner.take_last_run_flaky());
+            }
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("expected completed event");
+}
+
+#[test]
+fn deterministic_runner_reruns_failures_and_flags_differing_results_as_flaky() {
+    let marker = std::env::temp_dir().join(format!(
+        "metaagent-flaky-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0)
+    ));
+    let flaky = format!(
+        "if [ -e '{0}' ]; then exit 0; fi; touch '{0}'; exit 3",
+        marker.display()
+    );
+    assert_eq!(run_with_rerun(&flaky), (false, 3, true));
+    let _ = std::fs::remove_file(&marker);
+
+    assert_eq!(run_with_rerun("exit 4"), (false, 4, false));
+    assert_eq!(run_with_rerun("true"), (true, 0, false));
+}
+
+#[test]
+fn deterministic_runner_keeps_the_test_cases_it_recognized() {
+    let runner = TestRunnerAdapter::new();
+    runner.run_tests_with_command(Some(
+        "printf 'test a ... ok\\ntest b ... FAILED\\n'; exit 101",
+    ));
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    let mut summary = None;
+    while Instant::now() < deadline {
+        for event in runner.drain_events() {
+            match event {
+                AgentEvent::System(line) if line.starts_with("Deterministic test results") => {
+                    summary = Some(line);
+                }
+                AgentEvent::Completed { success, code } => {
+                    assert!(!success);
+                    assert_eq!(code, 101);
+                    let report = runner.take_last_report().expect("report before completion");
+                    assert_eq!(report.summary(), "shell: 1 passed, 1 failed, 0 skipped");
+                    assert_eq!(runner.take_last_report(), None);
+                    assert_eq!(
+                        summary.as_deref(),
+                        Some("Deterministic test results (shell: 1 passed, 1 failed, 0 skipped).")
+                    );
+                    return;
+                }
+                AgentEvent::Output(_) | AgentEvent::System(_) => {}
+            }
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("expected completed event");
+}
+
 #[test]
 fn drain_events_limited_respects_max_and_preserves_queue() {
     let runner = TestRunnerAdapter::new();
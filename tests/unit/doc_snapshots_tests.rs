@@ -0,0 +1,97 @@
+use super::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::session_store::{PlannerTaskDocFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile};
+
+fn open_temp_store(prefix: &str) -> (SessionStore, PathBuf) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let cwd = std::env::current_dir().expect("cwd");
+    let session_dir = std::env::temp_dir().join(format!("{prefix}-{now}"));
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing store");
+    (store, session_dir)
+}
+
+fn task_with_snapshots(snapshots: &[&str]) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: "impl".to_string(),
+        title: "Implement".to_string(),
+        details: String::new(),
+        docs: snapshots
+            .iter()
+            .map(|snapshot| PlannerTaskDocFileEntry {
+                title: snapshot.to_string(),
+                url: "https://docs.example".to_string(),
+                summary: String::new(),
+                snapshot: Some(snapshot.to_string()),
+            })
+            .collect(),
+        kind: PlannerTaskKindFile::Implementor,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: None,
+        order: Some(0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn sanitize_strips_html_markup_scripts_and_terminal_escapes() {
+    let html = "<!DOCTYPE html><html><head><style>p { color: red }</style>\
+                <script>alert('x')</script></head><body><nav>Home | API</nav>\
+                <h1>Derive</h1><p>Use <code>#[derive(Serialize)]</code> &amp; friends.</p>\
+                <!-- tracking --><p>Fields &lt;T&gt;</p></body></html>";
+    assert_eq!(
+        sanitize_snapshot_text(html),
+        "Derive\n\nUse #[derive(Serialize)] & friends.\n\nFields <T>"
+    );
+    assert_eq!(
+        sanitize_snapshot_text("\u{1b}[31mred\u{1b}[0m\u{7}\n\n\n\nnext\t line  \n"),
+        "red\n\nnext\t line"
+    );
+}
+
+#[test]
+fn sweep_rewrites_snapshots_and_drops_paths_outside_the_docs_dir() {
+    let (store, session_dir) = open_temp_store("metaagent-doc-snapshots");
+    std::fs::create_dir_all(session_dir.join("docs/impl")).expect("docs dir");
+    std::fs::write(
+        session_dir.join("docs/impl/serde.txt"),
+        "<html><body><p>Serde</p></body></html>",
+    )
+    .expect("write snapshot");
+    std::fs::write(session_dir.join("tasks.json"), "[]").expect("write outside docs");
+    let mut tasks = vec![task_with_snapshots(&[
+        "docs/impl/serde.txt",
+        "docs/impl/missing.txt",
+        "tasks.json",
+        "docs/../tasks.json",
+    ])];
+
+    let sweep = sanitize_snapshots(&store, &mut tasks).expect("sweep");
+
+    assert_eq!(
+        sweep,
+        SnapshotSweep {
+            kept: 1,
+            dropped: 3
+        }
+    );
+    assert_eq!(
+        std::fs::read_to_string(session_dir.join("docs/impl/serde.txt")).expect("read"),
+        "Serde"
+    );
+    let kept = tasks[0]
+        .docs
+        .iter()
+        .filter_map(|doc| doc.snapshot.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(kept, vec!["docs/impl/serde.txt"]);
+    assert_eq!(
+        read_snapshots(&store, &tasks),
+        HashMap::from([("docs/impl/serde.txt".to_string(), "Serde".to_string())])
+    );
+
+    let _ = std::fs::remove_dir_all(session_dir);
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+const BASE: &str = "# Plan\n\n- auth\n- billing\n- reports\n";
+
+#[test]
+fn changes_to_different_lines_merge_cleanly() {
+    let mine = "# Plan\n\n- auth with SSO\n- billing\n- reports\n";
+    let theirs = "# Plan\n\n- auth\n- billing\n- reports\n- exports\n";
+    assert_eq!(
+        merge_three_way(BASE, mine, theirs),
+        (
+            "# Plan\n\n- auth with SSO\n- billing\n- reports\n- exports\n".to_string(),
+            false
+        )
+    );
+}
+
+#[test]
+fn identical_changes_are_kept_once() {
+    let both = "# Plan\n\n- auth\n- invoices\n- reports\n";
+    assert_eq!(merge_three_way(BASE, both, both), (both.to_string(), false));
+}
+
+#[test]
+fn overlapping_changes_get_conflict_markers() {
+    let mine = "# Plan\n\n- auth\n- billing (Stripe)\n- reports\n";
+    let theirs = "# Plan\n\n- auth\n- billing (Paddle)\n- reports\n";
+    let (merged, conflicted) = merge_three_way(BASE, mine, theirs);
+    assert!(conflicted);
+    assert_eq!(
+        merged,
+        "# Plan\n\n- auth\n<<<<<<< yours\n- billing (Stripe)\n=======\n- billing (Paddle)\n>>>>>>> master\n- reports\n"
+    );
+}
+
+#[test]
+fn resolve_picks_a_side_or_merges() {
+    let conflict = EditConflict {
+        file: ConflictFile::Planner,
+        base: BASE.to_string(),
+        mine: "# Plan\n\n- auth\n- billing\n".to_string(),
+        theirs: "# Plan\n\n- billing\n- reports\n".to_string(),
+    };
+    assert_eq!(
+        conflict.resolve(ConflictChoice::Mine),
+        (conflict.mine.clone(), false)
+    );
+    assert_eq!(
+        conflict.resolve(ConflictChoice::Theirs),
+        (conflict.theirs.clone(), false)
+    );
+    assert_eq!(
+        conflict.resolve(ConflictChoice::Merge),
+        ("# Plan\n\n- billing\n".to_string(), false)
+    );
+    assert_eq!(
+        ConflictChoice::parse(" MERGE "),
+        Some(ConflictChoice::Merge)
+    );
+    assert_eq!(ConflictChoice::parse("both"), None);
+    assert!(conflict.notice(Locale::En).contains("/conflict mine"));
+}
@@ -0,0 +1,137 @@
+use super::*;
+use crate::session_store::{PlannerTaskKindFile, TaskFailFileEntry};
+
+fn enabled_config() -> EmailDigestConfig {
+    email_digest_config_from_toml(
+        r#"
+[email_digest]
+smtp_url = "smtps://smtp.example.com:465"
+username = "bob@example.com"
+password = "app-password"
+from = "bob@example.com"
+to = ["lead@example.com"]
+interval_minutes = 30
+"#,
+    )
+    .expect("config should parse")
+}
+
+fn task(id: &str, status: PlannerTaskStatusFile) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("{id} title"),
+        details: String::new(),
+        docs: Vec::new(),
+        kind: PlannerTaskKindFile::Task,
+        status,
+        parent_id: None,
+        order: Some(0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn config_defaults_off_and_validates_enabled_settings() {
+    let config = email_digest_config_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+        .expect("embedded default config parses");
+    assert!(!config.is_enabled());
+    assert_eq!(config.interval_minutes, 60);
+
+    let config = enabled_config();
+    assert!(config.is_enabled());
+    assert_eq!(config.interval(), Duration::from_secs(30 * 60));
+
+    assert!(email_digest_config_from_toml("[email_digest]\ninterval_minutes = 0\n").is_err());
+    assert!(
+        email_digest_config_from_toml(
+            "[email_digest]\nsmtp_url = \"smtp://localhost\"\nto = [\"a@example.com\"]\n"
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn digests_cover_progress_since_the_last_one_and_end_with_a_final_report() {
+    let cwd = std::env::current_dir().expect("cwd");
+    let session_dir = std::env::temp_dir().join(format!(
+        "agentbob-email-digest-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos())
+    ));
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open store");
+    let start = Instant::now();
+    let mut digest = EmailDigest::new(enabled_config());
+    let mut tasks = vec![
+        task("parse", PlannerTaskStatusFile::InProgress),
+        task("export", PlannerTaskStatusFile::Pending),
+    ];
+
+    assert_eq!(digest.due_report(start, &store, &tasks, &[], true), None);
+    digest.note_summary("Parser implemented; auditor running.\n");
+    tasks[0].status = PlannerTaskStatusFile::Done;
+    store
+        .append_task_fails(&[TaskFailFileEntry {
+            kind: "audit".to_string(),
+            top_task_id: 2,
+            top_task_title: "export title".to_string(),
+            attempts: 1,
+            reason: "Missing header row\nmore detail".to_string(),
+            action_taken: "retry".to_string(),
+            created_at_epoch_secs: 0,
+        }])
+        .expect("append fail");
+    let minute = Duration::from_secs(60);
+    assert_eq!(
+        digest.due_report(start + 29 * minute, &store, &tasks, &[], true),
+        None
+    );
+
+    let progress = digest
+        .due_report(start + 30 * minute, &store, &tasks, &[], true)
+        .expect("progress digest is due");
+    assert_eq!(progress.kind, DigestKind::Progress);
+    assert!(progress.estimates.is_empty());
+    assert_eq!(progress.completed, vec!["parse title".to_string()]);
+    assert_eq!(
+        progress.failures,
+        vec!["export title (audit, attempt 1): Missing header row".to_string()]
+    );
+    assert_eq!(progress.remaining, vec!["export title".to_string()]);
+    assert_eq!(
+        progress.summaries,
+        vec!["Parser implemented; auditor running.".to_string()]
+    );
+    assert_eq!(progress.subject(), "[bob] Bob session: 1 done, 1 remaining");
+    assert!(
+        progress
+            .body()
+            .starts_with("Execution has been running for 0h 30m.\n\nCompleted (1):\n- parse title")
+    );
+
+    tasks[1].status = PlannerTaskStatusFile::Done;
+    let last = digest
+        .due_report(
+            start + 45 * minute,
+            &store,
+            &tasks,
+            &["export title: estimated 1h, took 45m 00s (-25%)".to_string()],
+            false,
+        )
+        .expect("final report when execution stops");
+    assert_eq!(last.kind, DigestKind::Final);
+    assert_eq!(last.completed, vec!["export title".to_string()]);
+    assert!(last.failures.is_empty());
+    assert!(last.summaries.is_empty());
+    assert_eq!(last.subject(), "[bob] Bob session: execution finished");
+    assert!(
+        last.body().ends_with(
+            "Estimates vs actuals (1):\n- export title: estimated 1h, took 45m 00s (-25%)"
+        )
+    );
+    assert_eq!(
+        digest.due_report(start + 90 * minute, &store, &tasks, &[], false),
+        None
+    );
+    let _ = fs::remove_dir_all(&session_dir);
+}
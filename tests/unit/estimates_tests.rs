@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn parse_accepts_sizes_and_hours_and_rejects_the_rest() {
+    assert_eq!(Estimate::parse("m"), Ok(Estimate::Medium));
+    assert_eq!(Estimate::parse(" L "), Ok(Estimate::Large));
+    assert_eq!(Estimate::parse("4h"), Ok(Estimate::Hours(40)));
+    assert_eq!(Estimate::parse("1.5 H"), Ok(Estimate::Hours(15)));
+    assert_eq!(Estimate::Hours(15).to_string(), "1.5h");
+    assert_eq!(Estimate::Small.to_string(), "S");
+    for invalid in ["XL", "4", "0h", "-2h", "h"] {
+        assert!(
+            Estimate::parse(invalid).is_err(),
+            "{invalid} should be rejected"
+        );
+    }
+    assert_eq!(
+        Estimate::parse("big"),
+        Err("estimate \"big\" must be S, M, L, or hours such as \"4h\"".to_string())
+    );
+}
+
+#[test]
+fn plan_total_counts_sizes_and_sums_hours() {
+    assert_eq!(plan_total(&[None, None]), None);
+    assert_eq!(
+        plan_total(&[
+            Some(Estimate::Small),
+            Some(Estimate::Hours(40)),
+            None,
+            Some(Estimate::Large),
+            Some(Estimate::Small),
+            Some(Estimate::Hours(25)),
+        ]),
+        Some("2 S, 1 L + 6.5h (1 task not estimated)".to_string())
+    );
+    assert_eq!(
+        plan_total(&[Some(Estimate::Hours(10))]),
+        Some("1h".to_string())
+    );
+}
+
+#[test]
+fn comparison_reports_the_overrun_of_hour_estimates() {
+    let three_hours = Duration::from_secs(3 * 3_600);
+    assert_eq!(
+        comparison(Some(Estimate::Hours(20)), three_hours),
+        "estimated 2h, took 3h 00m (+50%)"
+    );
+    assert_eq!(
+        comparison(Some(Estimate::Medium), three_hours),
+        "estimated M, took 3h 00m"
+    );
+    assert_eq!(
+        comparison(None, Duration::from_secs(90)),
+        "not estimated, took 1m 30s"
+    );
+}
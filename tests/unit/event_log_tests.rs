@@ -0,0 +1,272 @@
+use super::*;
+use crate::session_store::PlannerTaskKindFile;
+
+fn read_events_after(
+    path: &Path,
+    after: u64,
+    limit: usize,
+) -> io::Result<Vec<SessionEventFileEntry>> {
+    EventLogCursor::default().read_after(path, after, limit)
+}
+
+fn open_temp_store(prefix: &str) -> (SessionStore, PathBuf) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let cwd = std::env::current_dir().expect("cwd");
+    let session_dir = std::env::temp_dir().join(format!("{prefix}-{now}"));
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing store");
+    (store, session_dir)
+}
+
+fn task(id: &str, status: PlannerTaskStatusFile) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("{id} title"),
+        details: String::new(),
+        docs: Vec::new(),
+        kind: PlannerTaskKindFile::Task,
+        status,
+        parent_id: None,
+        order: Some(0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn events_get_increasing_seq_and_resume_after_cursor_across_recorders() {
+    let (store, session_dir) = open_temp_store("event-log-cursor");
+    let mut recorder = SessionEventRecorder::default();
+    recorder
+        .record_agent_event(&store, "master", &AgentEvent::Output("hello".to_string()))
+        .expect("record output");
+    recorder
+        .record_agent_event(
+            &store,
+            "master",
+            &AgentEvent::Completed {
+                success: true,
+                code: 0,
+            },
+        )
+        .expect("record completion");
+
+    // A restarted app continues the sequence instead of reusing cursors.
+    let mut restarted = SessionEventRecorder::default();
+    restarted
+        .record_agent_event(&store, "worker", &AgentEvent::System("started".to_string()))
+        .expect("record after restart");
+
+    let all = read_events_after(&store.events_file(), 0, usize::MAX).expect("read events");
+    assert_eq!(
+        all.iter().map(|entry| entry.seq).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        all[1].event,
+        SessionEventKindFile::AgentCompleted {
+            agent: "master".to_string(),
+            success: true,
+            exit_code: 0,
+        }
+    );
+
+    let resumed = read_events_after(&store.events_file(), 1, 1).expect("read after cursor");
+    assert_eq!(resumed.len(), 1);
+    assert_eq!(resumed[0].seq, 2);
+    assert!(
+        wait_for_events(&store.events_file(), 3, 10, Duration::from_millis(150))
+            .expect("wait")
+            .is_empty()
+    );
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn workflow_changes_record_status_and_execution_diffs_after_baseline() {
+    let (store, session_dir) = open_temp_store("event-log-workflow");
+    let mut recorder = SessionEventRecorder::default();
+    let pending = vec![task("a", PlannerTaskStatusFile::Pending)];
+    recorder
+        .record_workflow_changes(&store, &pending, false, false)
+        .expect("baseline");
+    recorder
+        .record_workflow_changes(&store, &pending, false, false)
+        .expect("unchanged");
+    assert!(
+        read_events_after(&store.events_file(), 0, usize::MAX)
+            .expect("read events")
+            .is_empty()
+    );
+
+    let started = vec![
+        task("a", PlannerTaskStatusFile::InProgress),
+        task("b", PlannerTaskStatusFile::Pending),
+    ];
+    recorder
+        .record_workflow_changes(&store, &started, true, true)
+        .expect("changes");
+    let events = read_events_after(&store.events_file(), 0, usize::MAX)
+        .expect("read events")
+        .into_iter()
+        .map(|entry| entry.event)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        vec![
+            SessionEventKindFile::TaskStatusChanged {
+                task_id: "a".to_string(),
+                title: "a title".to_string(),
+                status: PlannerTaskStatusFile::InProgress,
+            },
+            SessionEventKindFile::ExecutionChanged {
+                enabled: true,
+                busy: true,
+            },
+        ]
+    );
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn chat_lines_are_recorded_once_and_not_carried_into_a_switched_session() {
+    let (store, session_dir) = open_temp_store("event-log-chat");
+    let (other, other_dir) = open_temp_store("event-log-chat-other");
+    let mut recorder = SessionEventRecorder::default();
+    let mut lines = Transcript::default();
+    lines.push("You: build it".to_string());
+    recorder.record_chat_lines(&store, &lines).expect("first");
+    lines.push("System: Started execution".to_string());
+    recorder.record_chat_lines(&store, &lines).expect("second");
+    recorder
+        .record_chat_lines(&store, &lines)
+        .expect("unchanged");
+    let recorded = read_events_after(&store.events_file(), 0, usize::MAX)
+        .expect("read events")
+        .into_iter()
+        .map(|entry| entry.event)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        recorded,
+        lines
+            .recent()
+            .iter()
+            .map(|line| SessionEventKindFile::ChatMessage { line: line.clone() })
+            .collect::<Vec<_>>()
+    );
+
+    recorder.record_chat_lines(&other, &lines).expect("switch");
+    lines.push("Agent: Resumed.".to_string());
+    recorder
+        .record_chat_lines(&other, &lines)
+        .expect("after switch");
+    let recorded = read_events_after(&other.events_file(), 0, usize::MAX).expect("read events");
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(
+        recorded[0].event,
+        SessionEventKindFile::ChatMessage {
+            line: "Agent: Resumed.".to_string()
+        }
+    );
+
+    std::fs::remove_dir_all(session_dir).ok();
+    std::fs::remove_dir_all(other_dir).ok();
+}
+
+#[test]
+fn cursor_reads_only_appended_whole_lines() {
+    let (store, session_dir) = open_temp_store("event-log-tail");
+    let path = store.events_file();
+    let mut recorder = SessionEventRecorder::default();
+    recorder
+        .record_agent_event(&store, "master", &AgentEvent::Output("one".to_string()))
+        .expect("record first");
+
+    let mut cursor = EventLogCursor::default();
+    assert_eq!(
+        cursor
+            .read_after(&path, 0, usize::MAX)
+            .expect("first read")
+            .len(),
+        1
+    );
+    assert!(
+        cursor
+            .read_after(&path, 0, usize::MAX)
+            .expect("nothing new")
+            .is_empty()
+    );
+
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .expect("open log");
+    write!(file, "{{\"seq\":2,").expect("write partial line");
+    assert!(
+        cursor
+            .read_after(&path, 0, usize::MAX)
+            .expect("partial line")
+            .is_empty()
+    );
+    writeln!(
+        file,
+        "\"at_epoch_ms\":0,\"event\":{{\"type\":\"chat_message\",\"payload\":{{\"line\":\"hi\"}}}}}}"
+    )
+    .expect("finish line");
+    let appended = cursor.read_after(&path, 0, usize::MAX).expect("appended");
+    assert_eq!(appended.len(), 1);
+    assert_eq!(appended[0].seq, 2);
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn wait_for_events_returns_events_appended_while_waiting() {
+    let (store, session_dir) = open_temp_store("event-log-wait");
+    let path = store.events_file();
+    let mut recorder = SessionEventRecorder::default();
+    recorder
+        .record_agent_event(&store, "master", &AgentEvent::Output("one".to_string()))
+        .expect("record first");
+
+    let writer = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(250));
+        recorder
+            .record_agent_event(&store, "master", &AgentEvent::Output("two".to_string()))
+            .expect("record second");
+    });
+    let events = wait_for_events(&path, 1, 10, Duration::from_secs(5)).expect("wait");
+    writer.join().expect("writer");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].seq, 2);
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn last_event_seq_reads_the_tail_across_chunks_and_skips_a_partial_line() {
+    let (store, session_dir) = open_temp_store("event-log-last-seq");
+    let path = store.events_file();
+    assert_eq!(last_event_seq(&path).expect("missing log"), 0);
+
+    let mut recorder = SessionEventRecorder::default();
+    let long_line = "x".repeat(TAIL_CHUNK_BYTES as usize);
+    for _ in 0..3 {
+        recorder
+            .record_agent_event(&store, "master", &AgentEvent::Output(long_line.clone()))
+            .expect("record output");
+    }
+    assert_eq!(last_event_seq(&path).expect("whole lines"), 3);
+
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .expect("open log");
+    write!(file, "{{\"seq\":4,").expect("write partial line");
+    assert_eq!(last_event_seq(&path).expect("partial line"), 3);
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
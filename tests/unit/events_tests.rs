@@ -24,6 +24,10 @@ fn maps_navigation_and_quit_keys() {
         map_key_event(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
         AppEvent::Quit
     );
+    assert_eq!(
+        map_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::CONTROL)),
+        AppEvent::QuickSwitch
+    );
 }
 
 #[test]
@@ -98,6 +102,22 @@ fn maps_ctrl_u_d_to_right_pane_scroll_regardless_of_focus() {
     );
 }
 
+#[test]
+fn maps_ctrl_l_to_the_chat_view_toggle() {
+    assert_eq!(
+        map_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL)),
+        AppEvent::ToggleChatView
+    );
+    assert_eq!(
+        map_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE)),
+        AppEvent::InputChar('l')
+    );
+    assert_eq!(
+        map_key_event(KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE)),
+        AppEvent::ShowHelp
+    );
+}
+
 #[test]
 fn maps_text_editing_keys() {
     assert_eq!(
@@ -167,7 +187,7 @@ fn maps_left_with_ctrl_or_alt_to_word_left() {
 #[test]
 fn maps_unhandled_keys_to_tick() {
     assert_eq!(
-        map_key_event(KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE)),
+        map_key_event(KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE)),
         AppEvent::Tick
     );
 }
@@ -243,3 +263,34 @@ fn left_drag_sequence_does_not_emit_click() {
         AppEvent::Tick
     );
 }
+
+#[test]
+fn right_click_and_long_press_open_the_context_menu() {
+    assert_eq!(
+        map_mouse_event(mouse_event(
+            MouseEventKind::Down(crossterm::event::MouseButton::Right),
+            12,
+            3
+        )),
+        AppEvent::MouseContextMenu(12, 3)
+    );
+
+    reset_left_mouse_state_for_tests();
+    assert_eq!(
+        map_mouse_event(mouse_event(
+            MouseEventKind::Down(crossterm::event::MouseButton::Left),
+            8,
+            4
+        )),
+        AppEvent::Tick
+    );
+    backdate_left_press_for_tests(LONG_PRESS_THRESHOLD);
+    assert_eq!(
+        map_mouse_event(mouse_event(
+            MouseEventKind::Up(crossterm::event::MouseButton::Left),
+            8,
+            4
+        )),
+        AppEvent::MouseContextMenu(8, 4)
+    );
+}
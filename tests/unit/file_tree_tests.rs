@@ -0,0 +1,106 @@
+use super::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn temp_workspace(prefix: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "{prefix}-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    fs::create_dir_all(root.join("src/nested")).expect("create src");
+    fs::create_dir_all(root.join(".git")).expect("create .git");
+    fs::create_dir_all(root.join("target")).expect("create target");
+    fs::write(root.join("Cargo.toml"), "[package]\n").expect("write manifest");
+    fs::write(root.join("src/main.rs"), "fn main() {\n\tprintln!(\"hi\");\n}\n")
+        .expect("write main");
+    fs::write(root.join("src/nested/lib.rs"), "").expect("write lib");
+    root
+}
+
+#[test]
+fn parse_changed_file_paths_reads_bullets_and_skips_reason_lines() {
+    let summary = "- src/app.rs: added pane\n* `src/ui.rs`: render\n./README.md\n- No files changed because nothing needed";
+    assert_eq!(
+        parse_changed_file_paths(summary),
+        vec![
+            "src/app.rs".to_string(),
+            "src/ui.rs".to_string(),
+            "README.md".to_string()
+        ]
+    );
+}
+
+#[test]
+fn refresh_lists_dirs_first_and_skips_ignored_dirs() {
+    let root = temp_workspace("metaagent-file-tree-list");
+    let mut state = FileTreeState::default();
+    state.set_root(root.clone());
+    state.refresh(&BTreeSet::new());
+
+    let names = state
+        .entries()
+        .iter()
+        .map(|entry| entry.relative_path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["src", "Cargo.toml"]);
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn activate_expands_dirs_and_marks_changed_files() {
+    let root = temp_workspace("metaagent-file-tree-expand");
+    let changed = BTreeSet::from(["src/nested/lib.rs".to_string()]);
+    let mut state = FileTreeState::default();
+    state.set_root(root.clone());
+    state.refresh(&changed);
+
+    assert!(state.entries()[0].changed, "dir containing changes is marked");
+    state.activate(&changed);
+    let lines = state.render_lines();
+    assert_eq!(lines[0], "> [-] src/ *");
+    assert_eq!(lines[1], "    [+] nested/ *");
+    assert_eq!(lines[2], "        main.rs");
+
+    assert!(state.back(&changed), "back collapses the expanded dir");
+    assert_eq!(state.entries().len(), 2);
+    assert!(!state.back(&changed));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn activate_opens_read_only_viewer_and_back_closes_it() {
+    let root = temp_workspace("metaagent-file-tree-viewer");
+    let changed = BTreeSet::new();
+    let mut state = FileTreeState::default();
+    state.set_root(root.clone());
+    state.refresh(&changed);
+
+    state.activate(&changed);
+    state.move_down(0);
+    state.move_down(0);
+    state.activate(&changed);
+    let viewer = state.viewer().expect("viewer should open for files");
+    assert_eq!(viewer.relative_path, "src/main.rs");
+    assert_eq!(
+        state.render_lines(),
+        vec![
+            "1 │ fn main() {".to_string(),
+            "2 │     println!(\"hi\");".to_string(),
+            "3 │ }".to_string()
+        ]
+    );
+
+    state.move_down(1);
+    state.move_down(1);
+    assert_eq!(state.viewer().map(|viewer| viewer.scroll), Some(1));
+
+    assert!(state.back(&changed));
+    assert!(state.viewer().is_none());
+    assert_eq!(state.selected_index(), 2);
+
+    let _ = fs::remove_dir_all(root);
+}
@@ -0,0 +1,98 @@
+use super::*;
+
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut names = text
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+#[test]
+fn language_config_defaults_to_english_and_rejects_unknown_locales() {
+    assert_eq!(
+        language_settings_from_toml("").expect("defaults"),
+        LanguageSettings::default()
+    );
+    assert_eq!(
+        language_settings_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+            .expect("embedded defaults"),
+        LanguageSettings::default()
+    );
+    assert_eq!(
+        language_settings_from_toml("[ui]\nlocale = \"es-MX\"\nprompt_language = \" Spanish \"\n")
+            .expect("spanish"),
+        LanguageSettings {
+            locale: Locale::Es,
+            prompt_language: Some("Spanish".to_string()),
+            time_zone: TimeZone::default(),
+        }
+    );
+    assert_eq!(Locale::parse("en_US.UTF-8"), Some(Locale::En));
+    assert_eq!(
+        language_settings_from_toml("[ui]\nprompt_language = \"  \"\n")
+            .expect("blank language")
+            .prompt_language,
+        None
+    );
+
+    let err = language_settings_from_toml("[ui]\nlocale = \"fr\"\n").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("en, es"), "{err}");
+
+    assert_eq!(
+        language_settings_from_toml("[ui]\ntime_zone = \"-05:00\"\n")
+            .expect("offset")
+            .time_zone,
+        TimeZone::parse("-0500").expect("offset")
+    );
+    let err = language_settings_from_toml("[ui]\ntime_zone = \"Europe/Paris\"\n").unwrap_err();
+    assert!(err.to_string().contains("ui.time_zone"), "{err}");
+}
+
+#[test]
+fn every_locale_translates_every_key_with_the_same_placeholders() {
+    for key in Text::ALL {
+        let english = text(Locale::En, key);
+        for locale in Locale::ALL {
+            let translated = text(locale, key);
+            assert!(!translated.trim().is_empty(), "{key:?} in {locale:?}");
+            assert_eq!(
+                placeholders(translated),
+                placeholders(english),
+                "{key:?} in {locale:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn fill_substitutes_named_placeholders() {
+    assert_eq!(
+        fill(
+            Locale::En,
+            Text::StartingJob,
+            &[("role", &"Implementor"), ("task", &7)]
+        ),
+        "Starting Implementor for task #7."
+    );
+    assert_eq!(
+        fill(Locale::Es, Text::ChatQueued, &[("count", &2)]),
+        "2 en cola"
+    );
+    assert!(reply_language_instruction("Spanish").contains("in Spanish"));
+}
+
+#[test]
+fn system_keeps_the_english_prefix_in_every_locale() {
+    assert_eq!(
+        system(Locale::En, Text::FinalReportSent, &[("to", &"a@b.c")]),
+        "System: E-mailed the final report to a@b.c."
+    );
+    assert_eq!(
+        system(Locale::Es, Text::FinalReportSent, &[("to", &"a@b.c")]),
+        "System: Se envió por correo el informe final a a@b.c."
+    );
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+fn titles(overlay: &HelpOverlay) -> Vec<&'static str> {
+    overlay
+        .sections()
+        .into_iter()
+        .map(|(context, _)| context.title())
+        .collect()
+}
+
+#[test]
+fn focused_context_comes_first_and_every_context_has_bindings() {
+    let overlay = HelpOverlay::new(KeyContext::TaskList);
+    let sections = overlay.sections();
+    assert_eq!(sections.len(), KeyContext::ALL.len());
+    assert_eq!(sections[0].0, KeyContext::TaskList);
+    assert_eq!(sections[1].0, KeyContext::Global);
+    assert_eq!(
+        sections
+            .iter()
+            .map(|(_, bindings)| bindings.len())
+            .sum::<usize>(),
+        KEY_BINDINGS.len()
+    );
+}
+
+#[test]
+fn search_matches_every_word_in_keys_or_action() {
+    let mut overlay = HelpOverlay::new(KeyContext::ChatInput);
+    overlay.scroll_down(4);
+    overlay.push_query("CANCEL");
+    assert_eq!(overlay.scroll(), 0);
+    assert_eq!(
+        titles(&overlay),
+        [
+            "Resume, backend, and log pickers",
+            "Master prompt preview",
+            "Re-run editor"
+        ]
+    );
+    overlay.push_query(" shift");
+    let sections = overlay.sections();
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].1[0].keys, "Shift+Tab");
+
+    overlay.push_query(" nothing-matches");
+    assert!(overlay.sections().is_empty());
+    while overlay.pop_query() {}
+    assert_eq!(overlay.query(), "");
+    assert!(!overlay.pop_query());
+    assert_eq!(overlay.sections()[0].0, KeyContext::ChatInput);
+}
@@ -73,6 +73,7 @@ fn integration_plan_with_final() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -83,6 +84,7 @@ fn integration_plan_with_final() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -93,6 +95,7 @@ fn integration_plan_with_final() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -103,6 +106,7 @@ fn integration_plan_with_final() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-audit".to_string(),
@@ -113,6 +117,7 @@ fn integration_plan_with_final() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -123,6 +128,7 @@ fn integration_plan_with_final() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "final".to_string(),
@@ -133,6 +139,7 @@ fn integration_plan_with_final() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
     ]
 }
@@ -148,6 +155,7 @@ fn simple_execution_plan() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -158,6 +166,7 @@ fn simple_execution_plan() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -168,6 +177,7 @@ fn simple_execution_plan() -> Vec<PlannerTaskFileEntry> {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ]
 }
@@ -185,6 +195,42 @@ fn parse_launch_options_accepts_send_file() {
     );
 }
 
+#[test]
+fn parse_batch_steps_splits_a_send_file_script_on_delimiter_lines() {
+    assert_eq!(
+        parse_batch_steps("Plan a CLI that counts words.\nKeep it small.\n"),
+        vec!["Plan a CLI that counts words.\nKeep it small.".to_string()]
+    );
+    assert_eq!(
+        parse_batch_steps("Plan a word counter.\n---\n/convert\n  ---  \n\n---\n/start\n---\n"),
+        vec![
+            "Plan a word counter.".to_string(),
+            "/convert".to_string(),
+            "/start".to_string(),
+        ]
+    );
+    assert!(parse_batch_steps("---\n\n---").is_empty());
+}
+
+#[test]
+fn batch_steps_wait_until_every_stage_and_queued_message_is_done() {
+    assert!(batch_step_ready(false, false, false, false, false, 0));
+    assert!(!batch_step_ready(true, false, false, false, false, 0));
+    assert!(!batch_step_ready(false, true, false, false, false, 0));
+    assert!(!batch_step_ready(false, false, true, false, false, 0));
+    assert!(!batch_step_ready(false, false, false, true, false, 0));
+    assert!(!batch_step_ready(false, false, false, false, true, 0));
+    assert!(!batch_step_ready(false, false, false, false, false, 1));
+
+    let mut app = App::default();
+    app.load_batch_steps(vec!["/convert".to_string(), "/start".to_string()]);
+    assert_eq!(app.batch_step_total(), 3);
+    assert_eq!(app.take_batch_step(), Some((2, "/convert".to_string())));
+    assert_eq!(app.clear_batch_steps(), 1);
+    assert!(!app.has_batch_steps());
+    assert_eq!(app.take_batch_step(), None);
+}
+
 #[test]
 fn parse_launch_options_accepts_verbose_flag() {
     let options =
@@ -193,6 +239,255 @@ fn parse_launch_options_accepts_verbose_flag() {
     assert!(options.verbose);
 }
 
+#[test]
+fn parse_launch_options_accepts_safe_mode_flag() {
+    let options =
+        parse_launch_options(vec!["--safe-mode".to_string()]).expect("options should parse");
+    assert!(options.command.is_none());
+    assert!(options.safe_mode);
+    assert!(!parse_launch_options(Vec::new()).expect("options").safe_mode);
+}
+
+#[test]
+fn parse_launch_options_accepts_observe_session_dir() {
+    let options = parse_launch_options(vec![
+        "observe".to_string(),
+        "--session-dir".to_string(),
+        "/tmp/session-a".to_string(),
+    ])
+    .expect("options should parse");
+    match options.command {
+        Some(RootCommand::Observe(observe)) => {
+            assert_eq!(observe.session_dir, PathBuf::from("/tmp/session-a"));
+        }
+        other => panic!("expected observe command, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_launch_options_accepts_serve_ui_with_default_port() {
+    let options = parse_launch_options(vec![
+        "serve-ui".to_string(),
+        "--session-dir".to_string(),
+        "/tmp/session-a".to_string(),
+    ])
+    .expect("options should parse");
+    match options.command {
+        Some(RootCommand::ServeUi(serve)) => {
+            assert_eq!(serve.session_dir, PathBuf::from("/tmp/session-a"));
+            assert_eq!(serve.port, web_ui::DEFAULT_PORT);
+        }
+        other => panic!("expected serve-ui command, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_launch_options_accepts_stats_command() {
+    let options =
+        parse_launch_options(vec!["stats".to_string()]).expect("options should parse");
+    assert!(matches!(
+        options.command,
+        Some(RootCommand::Stats(StatsRootCommand { action: None }))
+    ));
+    let options = parse_launch_options(vec!["stats".to_string(), "backends".to_string()])
+        .expect("options should parse");
+    assert!(matches!(
+        options.command,
+        Some(RootCommand::Stats(StatsRootCommand {
+            action: Some(StatsCommand::Backends)
+        }))
+    ));
+}
+
+#[test]
+fn parse_launch_options_accepts_config_show_command() {
+    let options = parse_launch_options(vec![
+        "config".to_string(),
+        "show".to_string(),
+        "--effective".to_string(),
+    ])
+    .expect("options should parse");
+    assert!(matches!(
+        options.command,
+        Some(RootCommand::Config(ConfigRootCommand {
+            action: ConfigCommand::Show { effective: true },
+        }))
+    ));
+}
+
+#[test]
+fn parse_launch_options_accepts_events_subscribe_command() {
+    let options = parse_launch_options(
+        [
+            "api",
+            "events",
+            "subscribe",
+            "--session-dir",
+            "/tmp/session",
+            "--after",
+            "7",
+            "--wait-ms",
+            "2000",
+        ]
+        .map(str::to_string)
+        .to_vec(),
+    )
+    .expect("options should parse");
+    let Some(RootCommand::Api(ApiRootCommand {
+        resource:
+            ApiResourceCommand::Events {
+                action:
+                    EventsCommand::Subscribe {
+                        session_dir,
+                        after,
+                        limit,
+                        wait_ms,
+                        ..
+                    },
+            },
+    })) = options.command
+    else {
+        panic!("expected events subscribe command");
+    };
+    assert_eq!(session_dir, PathBuf::from("/tmp/session"));
+    assert_eq!((after, limit, wait_ms), (7, None, 2000));
+}
+
+#[test]
+fn parse_launch_options_accepts_auth_commands() {
+    let options = parse_launch_options(vec![
+        "auth".to_string(),
+        "set".to_string(),
+        "linear".to_string(),
+    ])
+    .expect("options should parse");
+    assert!(matches!(
+        options.command,
+        Some(RootCommand::Auth(AuthRootCommand {
+            action: AuthCommand::Set { ref service },
+        })) if service == "linear"
+    ));
+    let output = CliTransportAdapter
+        .execute(RootCommand::Auth(AuthRootCommand {
+            action: AuthCommand::Remove {
+                service: "Not Valid".to_string(),
+            },
+        }))
+        .expect_err("invalid service name");
+    assert_eq!(output.code, api::ApiErrorCode::InvalidRequest);
+}
+
+#[test]
+fn parse_launch_options_accepts_backend_doctor_command() {
+    let options = parse_launch_options(vec![
+        "api".to_string(),
+        "backend".to_string(),
+        "doctor".to_string(),
+        "--backend".to_string(),
+        "claude".to_string(),
+        "--quick".to_string(),
+    ])
+    .expect("options should parse");
+    let Some(RootCommand::Api(ApiRootCommand {
+        resource: ApiResourceCommand::Backend {
+            action: BackendCommand::Doctor { backend, quick },
+        },
+    })) = options.command
+    else {
+        panic!("expected backend doctor command");
+    };
+    assert_eq!(backend.as_deref(), Some("claude"));
+    assert!(quick);
+}
+
+#[test]
+fn parse_launch_options_accepts_prompts_snapshot_command() {
+    let options = parse_launch_options(
+        ["api", "prompts", "snapshot", "--golden-dir", "golden", "--update"]
+            .map(str::to_string)
+            .to_vec(),
+    )
+    .expect("options should parse");
+    let Some(RootCommand::Api(ApiRootCommand {
+        resource:
+            ApiResourceCommand::Prompts {
+                action:
+                    PromptsCommand::Snapshot {
+                        golden_dir,
+                        fixtures_dir,
+                        update,
+                    },
+            },
+    })) = options.command
+    else {
+        panic!("expected prompts snapshot command");
+    };
+    assert_eq!(golden_dir, PathBuf::from("golden"));
+    assert_eq!(fixtures_dir, None);
+    assert!(update);
+}
+
+#[test]
+fn parse_launch_options_accepts_repeated_sync_import_tickets() {
+    let options = parse_launch_options(
+        [
+            "api",
+            "sync",
+            "import",
+            "--session-dir",
+            "/tmp/session",
+            "--ticket",
+            "linear:ENG-1",
+            "--ticket",
+            "jira:PROJ-2",
+        ]
+        .map(str::to_string)
+        .to_vec(),
+    )
+    .expect("options should parse");
+    let Some(RootCommand::Api(ApiRootCommand {
+        resource:
+            ApiResourceCommand::Sync {
+                action: SyncCommand::Import { tickets, .. },
+            },
+    })) = options.command
+    else {
+        panic!("expected sync import command");
+    };
+    assert_eq!(tickets, vec!["linear:ENG-1", "jira:PROJ-2"]);
+    assert!(
+        parse_launch_options(
+            ["api", "sync", "import", "--session-dir", "/tmp/s"]
+                .map(str::to_string)
+                .to_vec()
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn observer_submissions_allow_view_commands_and_refuse_prompts() {
+    let mut app = App::default();
+    app.set_read_only(true);
+
+    submit_observer_message(&mut app, "please add a feature");
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .is_some_and(|line| line.contains("Observer mode is read-only"))
+    );
+    assert!(app.running);
+
+    submit_observer_message(&mut app, "/planner");
+    assert!(app.is_planner_mode());
+    submit_observer_message(&mut app, "/files");
+    assert!(app.is_file_tree_mode());
+    assert_eq!(app.active_pane, Pane::LeftTop);
+
+    submit_observer_message(&mut app, "/quit");
+    assert!(!app.running);
+}
+
 #[test]
 fn keyboard_enhancement_flags_enable_modified_key_reporting() {
     let flags = keyboard_enhancement_flags();
@@ -240,6 +535,10 @@ fn session_initialization_gate_only_triggers_for_non_slash_messages() {
     assert!(!should_initialize_session_for_message("   /start   "));
     assert!(should_initialize_session_for_message("Build this feature"));
     assert!(should_initialize_session_for_message("start execution"));
+    assert!(should_initialize_session_for_message(
+        "/direct fix the typo"
+    ));
+    assert!(!should_initialize_session_for_message("/direct"));
 }
 
 #[test]
@@ -251,6 +550,7 @@ fn active_session_gate_applies_only_to_session_bound_slash_commands() {
     assert!(command_requires_active_session("/attach-docs"));
     assert!(command_requires_active_session("/split-audits"));
     assert!(command_requires_active_session("/add-final-audit"));
+    assert!(command_requires_active_session("/direct fix the typo"));
     assert!(!command_requires_active_session("/resume"));
     assert!(!command_requires_active_session("/newmaster"));
     assert!(!command_requires_active_session("hello"));
@@ -266,6 +566,7 @@ fn known_slash_command_detection_is_strict() {
     assert!(is_known_slash_command("/run"));
     assert!(is_known_slash_command("/quit"));
     assert!(is_known_slash_command("/attach-docs"));
+    assert!(is_known_slash_command("/jobs drop 2"));
     assert!(!is_known_slash_command("/split-tests"));
     assert!(!is_known_slash_command("/merge-tests"));
     assert!(!is_known_slash_command("/unknown-cmd"));
@@ -283,6 +584,7 @@ fn resumed_right_pane_mode_uses_task_list_when_tasks_exist() {
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
     assert_eq!(resumed_right_pane_mode(&tasks), RightPaneMode::TaskList);
 }
@@ -330,6 +632,24 @@ fn submit_block_reason_prioritizes_project_info_and_respects_task_check_quit_esc
     );
 }
 
+#[test]
+fn queued_messages_wait_for_master_and_task_check_but_not_execution() {
+    assert_eq!(
+        submit_block_reason(true, true, true, true, "/clear-queue"),
+        None
+    );
+    assert_eq!(
+        submit_block_reason(false, false, false, true, "/jobs move 2 1"),
+        None
+    );
+    assert!(!conflicts_with_running_execution("/jobs drop 1"));
+    assert!(!queued_message_ready(false, true, false, "hello"));
+    assert!(!queued_message_ready(false, false, true, "/start"));
+    assert!(!queued_message_ready(true, false, false, "hello"));
+    assert!(queued_message_ready(false, false, false, "hello"));
+    assert!(queued_message_ready(false, false, true, "/quit"));
+}
+
 #[test]
 fn backend_command_is_not_blocked_while_other_flows_are_in_flight() {
     assert_eq!(
@@ -460,6 +780,7 @@ fn post_completion_tail_drain_collects_late_worker_output() {
         skip_reader_join_after_wait: false,
         model: None,
         model_reasoning_effort: None,
+        workdir: None,
     });
     adapter.send_prompt("ignored".to_string());
 
@@ -569,6 +890,46 @@ fn reset_master_report_runtime_clears_inflight_queue_and_transcript() {
     assert!(transcript.is_empty());
 }
 
+#[test]
+fn master_report_queue_round_trips_and_restores_in_flight_prompt_first() {
+    let (store, session_dir) = open_temp_store("bob-master-report-queue");
+    let mut app = App::default();
+    assert_eq!(
+        store.read_master_report_queue().expect("missing file"),
+        MasterReportQueueFile::default()
+    );
+
+    let pending = std::collections::VecDeque::from([String::from("second"), String::new()]);
+    persist_master_report_queue(&mut app, Some(&store), Some("first"), &pending);
+    let queue = store.read_master_report_queue().expect("read queue");
+    assert_eq!(queue.in_flight.as_deref(), Some("first"));
+
+    let mut restored = std::collections::VecDeque::from([String::from("already queued")]);
+    assert_eq!(restore_master_report_queue(queue, &mut restored), 2);
+    assert_eq!(
+        restored,
+        std::collections::VecDeque::from([
+            String::from("already queued"),
+            String::from("first"),
+            String::from("second"),
+        ])
+    );
+
+    let mut intro_needed = true;
+    let wrapped =
+        with_master_report_session_intro("report".to_string(), &store, None, &mut intro_needed);
+    assert!(wrapped.starts_with("Meta-agent session working directory:"));
+    assert!(!intro_needed);
+    intro_needed = true;
+    assert_eq!(
+        with_master_report_session_intro(wrapped.clone(), &store, None, &mut intro_needed),
+        wrapped
+    );
+    assert!(!intro_needed);
+
+    let _ = std::fs::remove_dir_all(session_dir);
+}
+
 #[test]
 fn reset_task_check_runtime_clears_state() {
     let mut in_flight = true;
@@ -584,7 +945,7 @@ fn reset_task_check_runtime_clears_state() {
 fn planner_paste_save_persists_immediately_without_waiting_for_debounce() {
     let (store, session_dir) = open_temp_store("planner-paste-immediate-save");
     let mut app = App::default();
-    app.set_planner_markdown("before".to_string());
+    app.load_planner_markdown("before".to_string());
     store
         .write_planner_markdown(app.planner_markdown())
         .expect("seed planner markdown");
@@ -594,7 +955,9 @@ fn planner_paste_save_persists_immediately_without_waiting_for_debounce() {
     let mut last_keystroke_at = Some(Instant::now());
 
     match persist_planner_markdown_if_changed(&mut app, Some(&store)) {
-        PlannerPersistResult::Persisted | PlannerPersistResult::Unchanged => {
+        PlannerPersistResult::Persisted
+        | PlannerPersistResult::Unchanged
+        | PlannerPersistResult::Conflicted => {
             dirty = false;
             last_keystroke_at = None;
         }
@@ -703,6 +1066,7 @@ fn planner_edit_before_session_initializes_and_notifies_adapters() {
             skip_reader_join_after_wait: false,
             model: None,
             model_reasoning_effort: None,
+            workdir: None,
         });
         let project_info_adapter = CodexAdapter::with_config(CodexCommandConfig {
             program: "bash".to_string(),
@@ -716,6 +1080,7 @@ fn planner_edit_before_session_initializes_and_notifies_adapters() {
             skip_reader_join_after_wait: false,
             model: None,
             model_reasoning_effort: None,
+            workdir: None,
         });
 
         let mut app = App::default();
@@ -730,6 +1095,7 @@ fn planner_edit_before_session_initializes_and_notifies_adapters() {
             &mut project_info_text,
             &project_info_adapter,
             &master_adapter,
+            &CodexAgentModelRouting::default(),
         )
         .expect("initialize planner prefill session");
 
@@ -760,6 +1126,7 @@ fn planner_edit_before_session_saves_via_debounce_after_initialization() {
             skip_reader_join_after_wait: false,
             model: None,
             model_reasoning_effort: None,
+            workdir: None,
         });
         let project_info_adapter = CodexAdapter::with_config(CodexCommandConfig {
             program: "bash".to_string(),
@@ -773,6 +1140,7 @@ fn planner_edit_before_session_saves_via_debounce_after_initialization() {
             skip_reader_join_after_wait: false,
             model: None,
             model_reasoning_effort: None,
+            workdir: None,
         });
 
         let mut app = App::default();
@@ -788,6 +1156,7 @@ fn planner_edit_before_session_saves_via_debounce_after_initialization() {
             &mut project_info_text,
             &project_info_adapter,
             &master_adapter,
+            &CodexAgentModelRouting::default(),
         )
         .expect("initialize planner prefill session");
 
@@ -884,6 +1253,7 @@ fn prepare_resumed_session_rejects_invalid_task_hierarchy() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -894,6 +1264,7 @@ fn prepare_resumed_session_rejects_invalid_task_hierarchy() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ];
     std::fs::write(
@@ -931,6 +1302,7 @@ fn resume_session_does_not_switch_when_target_tasks_are_invalid() {
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
     std::fs::write(
         current_store.tasks_file(),
@@ -1024,6 +1396,93 @@ fn resume_session_does_not_switch_when_target_tasks_are_invalid() {
     std::fs::remove_dir_all(target_dir).ok();
 }
 
+#[test]
+fn resume_session_offers_takeover_or_observe_when_another_instance_holds_the_lock() {
+    let (current_store, current_dir) = open_temp_store("resume-current-locked");
+    let (_target_store, target_dir) = open_temp_store("resume-target-locked");
+    let owner = session_lock::LockOwner {
+        host: format!("{}-elsewhere", session_lock::LockOwner::current().host),
+        ..session_lock::LockOwner::current()
+    };
+    session_lock::write_owner(&target_dir, &owner).expect("write target lock");
+
+    let mut app = App::default();
+    let mut session_store = Some(current_store);
+    let master_adapter = CodexAdapter::new();
+    let master_report_adapter = CodexAdapter::new();
+    let project_info_adapter = CodexAdapter::new();
+    let mut worker_agent_adapters: HashMap<String, CodexAdapter> = HashMap::new();
+    let mut active_worker_context_key = None;
+    let mut pending_task_write_baseline = None;
+    let mut docs_attach_in_flight = false;
+    let mut master_session_intro_needed = false;
+    let mut master_report_session_intro_needed = false;
+    let mut pending_master_message_after_project_info = None;
+    let mut project_info_in_flight = false;
+    let mut project_info_stage = None;
+    let mut project_info_text = None;
+    let mut master_report_in_flight = false;
+    let mut pending_master_report_prompts = std::collections::VecDeque::new();
+    let mut master_report_transcript = Vec::new();
+    let mut task_check_in_flight = false;
+    let mut task_check_baseline = None;
+    let backend = ratatui::backend::TestBackend::new(120, 40);
+    let mut terminal = Terminal::new(backend).expect("test terminal");
+
+    let selection = ResumeSessionOption {
+        session_dir: target_dir.display().to_string(),
+        workspace: "workspace".to_string(),
+        title: None,
+        created_at_label: None,
+        last_used_epoch_secs: 0,
+    };
+    resume_session(
+        &mut app,
+        &mut session_store,
+        selection.clone(),
+        &master_adapter,
+        &master_report_adapter,
+        &project_info_adapter,
+        &mut worker_agent_adapters,
+        &mut active_worker_context_key,
+        &mut pending_task_write_baseline,
+        &mut docs_attach_in_flight,
+        &mut master_session_intro_needed,
+        &mut master_report_session_intro_needed,
+        &mut pending_master_message_after_project_info,
+        &mut project_info_in_flight,
+        &mut project_info_stage,
+        &mut project_info_text,
+        &mut master_report_in_flight,
+        &mut pending_master_report_prompts,
+        &mut master_report_transcript,
+        &mut task_check_in_flight,
+        &mut task_check_baseline,
+        &mut terminal,
+    )
+    .expect("resume should not hard-fail");
+
+    assert_eq!(
+        session_store.as_ref().map(SessionStore::session_dir),
+        Some(current_dir.as_path()),
+        "a locked session must not be opened without /lock takeover"
+    );
+    let locked = app.locked_session().expect("locked session is offered");
+    assert_eq!(locked.selection, selection);
+    assert_eq!(locked.owner, owner);
+    let last = app.left_bottom_lines().last().cloned().unwrap_or_default();
+    assert!(last.contains("/lock takeover"), "{last}");
+    assert!(last.contains("on another host"), "{last}");
+    assert_eq!(
+        session_lock::read_owner(&target_dir),
+        Some(owner),
+        "the other instance's lock is left alone"
+    );
+
+    std::fs::remove_dir_all(current_dir).ok();
+    std::fs::remove_dir_all(target_dir).ok();
+}
+
 #[test]
 fn resume_session_resets_master_report_and_task_check_runtime_state_on_success() {
     let (current_store, current_dir) = open_temp_store("resume-current-state-reset");
@@ -1037,6 +1496,7 @@ fn resume_session_resets_master_report_and_task_check_runtime_state_on_success()
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
     std::fs::write(
         target_store.tasks_file(),
@@ -1200,11 +1660,13 @@ fn cli_task_contract_round_trip_preserves_all_fields() {
             title: "Rust tests".to_string(),
             url: "https://doc.rust-lang.org/stable/book/ch11-00-testing.html".to_string(),
             summary: "Testing chapter".to_string(),
+            snapshot: None,
         }],
         kind: PlannerTaskKindFile::TestWriter,
         status: PlannerTaskStatusFile::NeedsChanges,
         parent_id: Some("top".to_string()),
         order: Some(2),
+        ..Default::default()
     };
 
     let contract = file_task_to_contract_task(file_task.clone());
@@ -1258,6 +1720,7 @@ fn execute_core_workflow_validation_is_transport_agnostic() {
         status: api::PlannerTaskStatusContract::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
 
     let request_with_cli_transport = api::RequestEnvelope {
@@ -1330,6 +1793,145 @@ fn execute_core_unsupported_domain_is_transport_agnostic() {
     assert_eq!(err_cli.message, err_mock.message);
 }
 
+#[test]
+fn execute_core_events_subscribe_resumes_after_cursor() {
+    let (store, session_dir) = open_temp_store("metaagent-events-subscribe-contract");
+    let cwd = std::env::current_dir().expect("cwd");
+    let mut recorder = SessionEventRecorder::default();
+    for line in ["first", "second"] {
+        recorder
+            .record_agent_event(&store, "master", &AgentEvent::Output(line.to_string()))
+            .expect("record event");
+    }
+    let subscribe = |after| api::RequestEnvelope {
+        request_id: None,
+        capability: api::CapabilityId::EventSubscription,
+        metadata: api::RequestMetadata {
+            transport: Some("cli".to_string()),
+            actor: Some(json!({ "cwd": cwd, "session_dir": session_dir }).to_string()),
+        },
+        payload: api::ApiRequestContract::Events(api::EventsRequest::Subscribe {
+            after,
+            limit: None,
+            wait_ms: 0,
+        }),
+    };
+
+    let batch = |after| match execute_core_api_contract(subscribe(after))
+        .expect("subscribe should succeed")
+        .result
+    {
+        api::ApiResultEnvelope::Ok {
+            data:
+                api::ApiResponseContract::Events(api::EventsResponse::Batch {
+                    events,
+                    next_cursor,
+                }),
+        } => (events, next_cursor),
+        other => panic!("unexpected response: {other:?}"),
+    };
+    let (events, cursor) = batch(1);
+    assert_eq!(cursor, 2);
+    assert_eq!(
+        events
+            .into_iter()
+            .map(|record| record.event)
+            .collect::<Vec<_>>(),
+        vec![api::SessionEventContract::AgentOutput {
+            agent: "master".to_string(),
+            line: "second".to_string(),
+        }]
+    );
+    let (events, cursor) = batch(cursor);
+    assert!(events.is_empty());
+    assert_eq!(cursor, 2);
+
+    drop(store);
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn execute_core_subagent_requests_build_prompts_from_explicit_arguments() {
+    let build = |payload| {
+        execute_core_api_contract(api::RequestEnvelope {
+            request_id: None,
+            capability: api::CapabilityId::SubagentPromptGeneration,
+            metadata: api::RequestMetadata::default(),
+            payload: api::ApiRequestContract::Subagent(payload),
+        })
+    };
+    let prompt_text =
+        |response: api::ResponseEnvelope<api::ApiResponseContract>| match response.result {
+            api::ApiResultEnvelope::Ok {
+                data: api::ApiResponseContract::Subagent(api::SubagentResponse::Prompt { text }),
+            } => text,
+            other => panic!("unexpected response: {other:?}"),
+        };
+
+    let task_check = prompt_text(
+        build(api::SubagentRequest::BuildTaskCheckPrompt {
+            tasks_file: "/s/tasks.json".to_string(),
+            project_info_file: "/s/project-info.md".to_string(),
+            session_meta_file: "/s/meta.json".to_string(),
+        })
+        .expect("task check prompt"),
+    );
+    assert!(task_check.contains("Review the planner JSON file at: /s/tasks.json"));
+
+    let tasks = [
+        ("top", api::PlannerTaskKindContract::Task, None),
+        (
+            "impl",
+            api::PlannerTaskKindContract::Implementor,
+            Some("top"),
+        ),
+        ("audit", api::PlannerTaskKindContract::Auditor, Some("impl")),
+    ]
+    .into_iter()
+    .map(|(id, kind, parent_id)| api::PlannerTaskEntryContract {
+        id: id.to_string(),
+        title: format!("{id} title"),
+        details: format!("{id} details"),
+        docs: Vec::new(),
+        kind,
+        status: api::PlannerTaskStatusContract::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    })
+    .collect::<Vec<_>>();
+    let audit = prompt_text(
+        build(api::SubagentRequest::BuildAuditPrompt {
+            tasks: tasks.clone(),
+            task_id: "audit".to_string(),
+            worker_output: Some("report".to_string()),
+            changed_files_summary: None,
+            pass: None,
+            tests_mode_enabled: None,
+            audit_rubric: Some("Check logging".to_string()),
+            rolling_context: vec!["earlier work".to_string()],
+        })
+        .expect("audit prompt"),
+    );
+    assert!(audit.contains("Audit pass: 1 of"));
+    assert!(audit.contains("Implementation output to audit:\nreport"));
+    assert!(audit.contains("Check logging"));
+    assert!(audit.contains("earlier work"));
+
+    let err = build(api::SubagentRequest::BuildAuditPrompt {
+        tasks,
+        task_id: "impl".to_string(),
+        worker_output: None,
+        changed_files_summary: None,
+        pass: None,
+        tests_mode_enabled: None,
+        audit_rubric: None,
+        rolling_context: Vec::new(),
+    })
+    .expect_err("implementor is not an audit task");
+    assert_eq!(err.code, api::ApiErrorCode::InvalidRequest);
+}
+
 #[test]
 fn execute_core_session_project_info_write_then_read_round_trips() {
     let (store, session_dir) = open_temp_store("metaagent-session-project-info-contract");
@@ -1594,6 +2196,7 @@ fn convert_submit_persists_latest_planner_markdown_before_conversion() {
         "[]",
     )
     .expect("write baseline tasks");
+    app.load_planner_markdown("stale planner markdown".to_string());
     app.set_planner_markdown("latest planner markdown".to_string());
 
     let mut pending_task_write_baseline = None;
@@ -1831,6 +2434,7 @@ fn persist_runtime_tasks_snapshot_writes_updated_subtask_statuses() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -1841,6 +2445,7 @@ fn persist_runtime_tasks_snapshot_writes_updated_subtask_statuses() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -1851,6 +2456,7 @@ fn persist_runtime_tasks_snapshot_writes_updated_subtask_statuses() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -1861,6 +2467,7 @@ fn persist_runtime_tasks_snapshot_writes_updated_subtask_statuses() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -1871,6 +2478,7 @@ fn persist_runtime_tasks_snapshot_writes_updated_subtask_statuses() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -1919,6 +2527,7 @@ fn start_execution_claim_persists_first_job_status_immediately() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -1929,6 +2538,7 @@ fn start_execution_claim_persists_first_job_status_immediately() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -1939,6 +2549,7 @@ fn start_execution_claim_persists_first_job_status_immediately() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -1983,7 +2594,7 @@ fn integration_happy_path_persists_and_reloads_cleanly() {
                 }
                 let _ = app.on_worker_completed(true, 0);
             }
-            JobRun::DeterministicTestRun => {
+            JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
                 app.on_worker_output("all passed".to_string());
                 let _ = app.on_worker_completed(true, 0);
             }
@@ -2054,7 +2665,7 @@ fn integration_retry_paths_recover_without_exhausted_failures() {
                 }
                 let _ = app.on_worker_completed(true, 0);
             }
-            JobRun::DeterministicTestRun => {
+            JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
                 if runner_failures_left > 0 {
                     runner_failures_left = runner_failures_left.saturating_sub(1);
                     app.on_worker_output("tests failing".to_string());
@@ -2097,6 +2708,7 @@ fn integration_snapshot_does_not_write_illegal_children_for_done_or_final_roots(
             status: PlannerTaskStatusFile::Done,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "final".to_string(),
@@ -2107,6 +2719,7 @@ fn integration_snapshot_does_not_write_illegal_children_for_done_or_final_roots(
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2153,6 +2766,7 @@ fn integration_persisted_legacy_in_progress_impl_resumes_at_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -2163,6 +2777,7 @@ fn integration_persisted_legacy_in_progress_impl_resumes_at_auditor() {
             status: PlannerTaskStatusFile::InProgress,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -2173,6 +2788,7 @@ fn integration_persisted_legacy_in_progress_impl_resumes_at_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2193,7 +2809,9 @@ fn integration_persisted_legacy_in_progress_impl_resumes_at_auditor() {
         JobRun::AgentPrompt(prompt) => {
             assert!(prompt.contains("reviewing implementation output"));
         }
-        JobRun::DeterministicTestRun => panic!("expected auditor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
     }
 
     let _ = std::fs::remove_dir_all(&session_dir);
@@ -2364,6 +2982,79 @@ fn build_resume_options_filters_to_current_workspace() {
     assert_eq!(options[0].session_dir, "/tmp/current-1");
 }
 
+#[test]
+fn recent_session_options_keep_this_workspaces_sessions_from_the_last_week() {
+    let entry = |dir: &str, workspace: &str, last_used_epoch_secs: u64| SessionListEntry {
+        session_dir: std::path::PathBuf::from(dir),
+        workspace: workspace.to_string(),
+        title: None,
+        created_at_label: None,
+        created_at_epoch_secs: 0,
+        last_used_epoch_secs,
+    };
+    let now = 30 * 24 * 60 * 60;
+    let options = recent_session_options(
+        vec![
+            entry("/tmp/fresh", "/work/app", now - 60),
+            entry("/tmp/elsewhere", "/work/other", now - 60),
+            entry("/tmp/week", "/work/app", now - RECENT_SESSION_WINDOW_SECS),
+            entry(
+                "/tmp/stale",
+                "/work/app",
+                now - RECENT_SESSION_WINDOW_SECS - 1,
+            ),
+        ],
+        std::path::Path::new("/work/app"),
+        now,
+    );
+    assert_eq!(
+        options
+            .iter()
+            .map(|option| option.session_dir.as_str())
+            .collect::<Vec<_>>(),
+        ["/tmp/fresh", "/tmp/week"]
+    );
+}
+
+#[test]
+fn loop_wait_timeout_sleeps_until_the_earliest_deadline_or_the_idle_cap() {
+    let now = Instant::now();
+    assert_eq!(loop_wait_timeout(now, [None, None]), IDLE_WAIT_CAP);
+    assert_eq!(
+        loop_wait_timeout(
+            now,
+            [
+                Some(now + TASKS_WATCH_INTERVAL),
+                None,
+                Some(now + UI_TICK_INTERVAL)
+            ]
+        ),
+        UI_TICK_INTERVAL
+    );
+    assert_eq!(
+        loop_wait_timeout(now + UI_TICK_INTERVAL, [Some(now)]),
+        Duration::ZERO
+    );
+}
+#[test]
+fn user_presence_goes_away_on_focus_loss_or_idle_input_and_returns_on_input() {
+    let start = Instant::now();
+    let mut presence = UserPresence::new(start);
+    assert!(!presence.is_away(start));
+
+    assert!(!presence.on_event(&AppEvent::FocusLost, start));
+    assert!(presence.is_away(start));
+    assert!(!presence.on_event(&AppEvent::Tick, start));
+    assert!(presence.on_event(&AppEvent::FocusGained, start));
+    assert!(!presence.is_away(start));
+
+    let idle = start + INPUT_IDLE_AFTER;
+    assert!(presence.is_away(idle));
+    assert!(!presence.on_event(&AppEvent::Resize, idle));
+    assert!(presence.on_event(&AppEvent::InputChar('a'), idle));
+    assert!(!presence.is_away(idle));
+}
+
 #[test]
 fn sanitize_master_docs_fields_clears_docs_for_new_tasks() {
     let mut tasks = vec![PlannerTaskFileEntry {
@@ -2374,11 +3065,13 @@ fn sanitize_master_docs_fields_clears_docs_for_new_tasks() {
             title: "Doc".to_string(),
             url: "https://example.com".to_string(),
             summary: "sum".to_string(),
+            snapshot: None,
         }],
         kind: session_store::PlannerTaskKindFile::Task,
         status: session_store::PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
 
     let changed = sanitize_master_docs_fields(&mut tasks, Some("[]"));
@@ -2406,11 +3099,13 @@ fn sanitize_master_docs_fields_preserves_baseline_docs_for_existing_tasks() {
             title: "Wrong".to_string(),
             url: "https://wrong".to_string(),
             summary: String::new(),
+            snapshot: None,
         }],
         kind: session_store::PlannerTaskKindFile::Task,
         status: session_store::PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
 
     let changed = sanitize_master_docs_fields(&mut tasks, Some(baseline));
@@ -2429,11 +3124,13 @@ fn sanitize_master_docs_fields_ignores_missing_baseline() {
             title: "Keep".to_string(),
             url: "https://keep".to_string(),
             summary: "sum".to_string(),
+            snapshot: None,
         }],
         kind: session_store::PlannerTaskKindFile::Task,
         status: session_store::PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
 
     let changed = sanitize_master_docs_fields(&mut tasks, None);
@@ -2646,13 +3343,42 @@ fn mouse_left_click_focuses_pane_from_click_position() {
 }
 
 #[test]
-fn mouse_left_click_does_not_change_focus_when_picker_is_open() {
+fn question_mark_opens_help_only_where_it_is_not_typed() {
     let mut app = App::default();
+    let screen = Rect::new(0, 0, 120, 40);
     app.active_pane = Pane::LeftBottom;
-    app.open_resume_picker(vec![ResumeSessionOption {
-        session_dir: "/tmp/example".to_string(),
-        workspace: "workspace".to_string(),
-        title: Some("Session".to_string()),
+    app.input_char('x');
+    assert!(!help_requested(&app, &AppEvent::InputChar('?')));
+    assert!(help_requested(&app, &AppEvent::ShowHelp));
+    app.active_pane = Pane::Right;
+    assert!(app.is_planner_mode());
+    assert!(!help_requested(&app, &AppEvent::InputChar('?')));
+    app.active_pane = Pane::LeftTop;
+    assert!(help_requested(&app, &AppEvent::InputChar('?')));
+
+    app.open_help_overlay();
+    assert_eq!(
+        app.help_overlay().map(|help| help.focused()),
+        Some(keybindings::KeyContext::WorkerOutput)
+    );
+    handle_help_overlay_event(&mut app, screen, &AppEvent::InputChar('q'));
+    handle_help_overlay_event(&mut app, screen, &AppEvent::InputChar('?'));
+    assert_eq!(app.help_overlay().map(|help| help.query()), Some("q?"));
+    handle_help_overlay_event(&mut app, screen, &AppEvent::Backspace);
+    handle_help_overlay_event(&mut app, screen, &AppEvent::Backspace);
+    assert!(app.is_help_overlay_open());
+    handle_help_overlay_event(&mut app, screen, &AppEvent::Backspace);
+    assert!(!app.is_help_overlay_open());
+}
+
+#[test]
+fn mouse_left_click_does_not_change_focus_when_picker_is_open() {
+    let mut app = App::default();
+    app.active_pane = Pane::LeftBottom;
+    app.open_resume_picker(vec![ResumeSessionOption {
+        session_dir: "/tmp/example".to_string(),
+        workspace: "workspace".to_string(),
+        title: Some("Session".to_string()),
         created_at_label: Some("now".to_string()),
         last_used_epoch_secs: 1,
     }]);
@@ -2702,6 +3428,7 @@ fn mouse_left_click_scroll_button_scrolls_active_pane_down_by_half_page_in_narro
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(idx as u32),
+                ..Default::default()
             })
             .collect(),
     )
@@ -3188,6 +3915,8 @@ fn prepends_master_session_intro_once() {
         "/tmp/session-1",
         "/tmp/session-1/meta.json",
         Some("Project info"),
+        Some("Project memory (shared by every session in this workspace):\n- Uses tokio."),
+        Some("Be terse."),
         &mut intro_needed,
     );
     assert!(first.contains("Meta-agent session working directory: /tmp/session-1"));
@@ -3196,12 +3925,17 @@ fn prepends_master_session_intro_once() {
     assert!(first.contains("Never modify project workspace files directly."));
     assert!(first.contains("Project context (project-info.md):"));
     assert!(first.contains("Project info"));
+    assert!(first.find("- Uses tokio.") < first.find("Be terse."));
+    assert!(first.contains("Session persona (from meta.json"));
+    assert!(first.find("Be terse.") < first.find("Do work"));
     assert!(first.contains("Do work"));
     let second = subagents::build_session_intro_if_needed(
         "Do more",
         "/tmp/session-1",
         "/tmp/session-1/meta.json",
         Some("Project info"),
+        None,
+        None,
         &mut intro_needed,
     );
     assert_eq!(second, "Do more");
@@ -3213,6 +3947,8 @@ fn project_info_prompt_includes_question_and_output_path() {
         "/tmp/workspace",
         "How should we implement task batching?",
         "/tmp/session/project-info.md",
+        &[],
+        &project_info_scope::ProjectInfoScope::default(),
     );
     assert!(prompt.contains("Current working directory: /tmp/workspace"));
     assert!(prompt.contains("How should we implement task batching?"));
@@ -3225,6 +3961,18 @@ fn project_info_prompt_includes_question_and_output_path() {
     assert!(prompt.contains("\"Testing Setup\""));
     assert!(prompt.contains("best command to run the project's tests end-to-end"));
     assert!(prompt.contains("single verbatim shell command runnable in bash as-is"));
+    assert!(!prompt.contains(".metaagentignore"));
+
+    let prompt = subagents::build_project_info_prompt(
+        "/tmp/workspace",
+        "How should we implement task batching?",
+        "/tmp/session/project-info.md",
+        &["dist/".to_string(), "vendor/".to_string()],
+        &project_info_scope::ProjectInfoScope::default(),
+    );
+    assert!(prompt.contains(
+        "do not read, list, or summarize them: `dist/`, `vendor/`\n- Do not browse the web"
+    ));
 }
 
 #[test]
@@ -3315,6 +4063,7 @@ fn ensure_final_audit_task_adds_or_resets_final_task() {
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
     ensure_final_audit_task(&mut tasks);
     assert!(
@@ -3336,6 +4085,54 @@ fn ensure_final_audit_task_adds_or_resets_final_task() {
     assert!(matches!(existing.status, PlannerTaskStatusFile::Pending));
 }
 
+#[test]
+fn auto_final_audit_policy_appends_only_when_plan_first_materializes() {
+    let plan = || {
+        vec![PlannerTaskFileEntry {
+            id: "1".to_string(),
+            title: "Task".to_string(),
+            details: "d".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::Task,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: None,
+            order: Some(0),
+            ..Default::default()
+        }]
+    };
+
+    let mut tasks = plan();
+    assert!(append_auto_final_audit_if_needed(
+        FinalAuditPolicy::Auto,
+        true,
+        &mut tasks
+    ));
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[1].kind, PlannerTaskKindFile::FinalAudit);
+    assert!(
+        !append_auto_final_audit_if_needed(FinalAuditPolicy::Auto, true, &mut tasks),
+        "an existing final audit is not duplicated"
+    );
+
+    let mut tasks = plan();
+    assert!(!append_auto_final_audit_if_needed(
+        FinalAuditPolicy::Auto,
+        false,
+        &mut tasks
+    ));
+    assert!(!append_auto_final_audit_if_needed(
+        FinalAuditPolicy::Manual,
+        true,
+        &mut tasks
+    ));
+    assert!(!append_auto_final_audit_if_needed(
+        FinalAuditPolicy::Auto,
+        true,
+        &mut Vec::new()
+    ));
+    assert_eq!(tasks.len(), 1);
+}
+
 #[test]
 fn normalize_root_orders_with_final_last_places_final_at_end() {
     let mut tasks = vec![
@@ -3348,6 +4145,7 @@ fn normalize_root_orders_with_final_last_places_final_at_end() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "a".to_string(),
@@ -3358,6 +4156,7 @@ fn normalize_root_orders_with_final_last_places_final_at_end() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
     ];
     normalize_root_orders_with_final_last(&mut tasks);
@@ -3374,6 +4173,130 @@ fn normalize_root_orders_with_final_last_places_final_at_end() {
     assert!(final_order > task_order);
 }
 
+#[test]
+fn direct_task_is_saved_and_runs_one_implementor_with_audit_and_tests() {
+    let (store, session_dir) = open_temp_store("direct-task");
+    let mut app = App::default();
+
+    assert_eq!(add_direct_task(&mut app, "", &store), None);
+    assert_eq!(
+        add_direct_task(&mut app, "Rename the config flag", &store).as_deref(),
+        Some("direct-1")
+    );
+    let task_id = add_direct_task(&mut app, "Fix the typo in the README", &store)
+        .expect("second direct task");
+    assert_eq!(task_id, "direct-2");
+
+    let tasks = store.read_tasks().expect("read tasks");
+    assert_eq!(tasks.len(), 8);
+    let top = tasks.iter().find(|t| t.id == "direct-2").expect("top task");
+    assert_eq!(top.title, "Direct: Fix the typo in the README");
+    assert_eq!(top.order, Some(1));
+    let kinds = tasks
+        .iter()
+        .filter(|t| t.id.starts_with("direct-2-"))
+        .map(|t| (t.id.as_str(), t.kind, t.parent_id.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            (
+                "direct-2-impl",
+                PlannerTaskKindFile::Implementor,
+                Some("direct-2")
+            ),
+            (
+                "direct-2-audit",
+                PlannerTaskKindFile::Auditor,
+                Some("direct-2-impl")
+            ),
+            (
+                "direct-2-tests",
+                PlannerTaskKindFile::TestRunner,
+                Some("direct-2-impl")
+            ),
+        ]
+    );
+
+    app.start_execution_scoped(&task_id)
+        .expect("direct task should start");
+    let job = app.start_next_worker_job().expect("implementor job");
+    assert_eq!(job.role, crate::workflow::WorkerRole::Implementor);
+    let JobRun::AgentPrompt(prompt) = job.run else {
+        panic!("expected implementor prompt");
+    };
+    assert!(prompt.contains("Fix the typo in the README"));
+    assert!(!prompt.contains("Rename the config flag"));
+
+    let _ = std::fs::remove_dir_all(session_dir);
+}
+
+#[test]
+fn docs_update_task_is_appended_before_final_audit_and_prompts_with_session_changes() {
+    let entry = |id: &str, kind, parent_id: Option<&str>| PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("Task {id}"),
+        details: "d".to_string(),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    };
+    let mut tasks = vec![
+        entry("login", PlannerTaskKindFile::Task, None),
+        entry(
+            "login-impl",
+            PlannerTaskKindFile::Implementor,
+            Some("login"),
+        ),
+        entry(
+            "login-audit",
+            PlannerTaskKindFile::Auditor,
+            Some("login-impl"),
+        ),
+    ];
+    assert!(!append_docs_update_task_if_needed(false, true, &mut tasks));
+    assert!(!append_docs_update_task_if_needed(true, false, &mut tasks));
+    assert!(append_docs_update_task_if_needed(true, true, &mut tasks));
+    assert!(
+        !append_docs_update_task_if_needed(true, true, &mut tasks),
+        "an existing docs task is not duplicated"
+    );
+    assert!(append_auto_final_audit_if_needed(
+        FinalAuditPolicy::Auto,
+        true,
+        &mut tasks
+    ));
+    let roots = tasks
+        .iter()
+        .filter(|t| t.parent_id.is_none())
+        .map(|t| (t.id.as_str(), t.order))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        roots,
+        vec![
+            ("login", Some(0)),
+            ("docs-update", Some(1)),
+            ("final-audit", Some(2))
+        ]
+    );
+
+    let mut app = App::default();
+    app.sync_planner_tasks_from_file(tasks)
+        .expect("docs task should pass validation");
+    app.start_execution_scoped("docs-update")
+        .expect("docs task should start");
+    let job = app.start_next_worker_job().expect("implementor job");
+    let JobRun::AgentPrompt(prompt) = job.run else {
+        panic!("expected implementor prompt");
+    };
+    assert!(prompt.contains("CHANGELOG.md"));
+    assert!(prompt.contains("Session tasks:\n- Task login (not finished)\n"));
+    assert!(prompt.contains("Files changed this session:\n- (no changed files were reported)"));
+}
+
 #[test]
 fn add_final_audit_aborts_without_writing_when_tasks_read_fails() {
     let (store, session_dir) = open_temp_store("final-audit-read-fail-add");
@@ -3435,6 +4358,7 @@ fn remove_final_audit_reports_not_present_when_only_non_final_tasks_exist() {
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
     let tasks_json = serde_json::to_string_pretty(&tasks).expect("serialize tasks");
     std::fs::write(store.tasks_file(), tasks_json).expect("write tasks");
@@ -3453,6 +4377,56 @@ fn remove_final_audit_reports_not_present_when_only_non_final_tasks_exist() {
     std::fs::remove_dir_all(session_dir).ok();
 }
 
+#[test]
+fn a_run_finishing_while_away_still_sends_its_final_digest() {
+    let (store, session_dir) = open_temp_store("digest-while-away");
+    let mut app = App::default();
+    app.sync_planner_tasks_from_file(integration_plan_with_final())
+        .expect("sync tasks");
+    // Nothing listens on the discard port, so the send fails fast and reports back.
+    let mut email_digest = email_digest::EmailDigest::new(email_digest::EmailDigestConfig {
+        smtp_url: "smtp://127.0.0.1:9".to_string(),
+        from: "bob@example.com".to_string(),
+        to: vec!["lead@example.com".to_string()],
+        ..Default::default()
+    });
+    let mut code_index_refresher = code_index::CodeIndexRefresher::default();
+    let cwd = std::env::current_dir().expect("cwd");
+    let mut poll_away = |app: &App| {
+        poll_digest_and_code_index(
+            app,
+            true,
+            &cwd,
+            &store,
+            &mut email_digest,
+            &mut code_index_refresher,
+        )
+    };
+
+    app.start_execution();
+    let _ = app
+        .start_next_worker_job()
+        .expect("active worker should exist");
+    poll_away(&app);
+    let _ = app.interrupt_execution();
+    assert!(!app.is_execution_busy());
+    poll_away(&app);
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut messages = Vec::new();
+    while messages.is_empty() && Instant::now() < deadline {
+        messages.extend(email_digest.drain_messages());
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert!(
+        messages
+            .iter()
+            .any(|message| message.starts_with("System: Failed to e-mail the final report")),
+        "{messages:?}"
+    );
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
 #[test]
 fn final_audit_commands_do_not_write_while_execution_busy() {
     let (store, session_dir) = open_temp_store("final-audit-block-while-running");
@@ -3500,6 +4474,7 @@ fn add_final_audit_reports_write_failure_without_returning_error() {
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }];
     let tasks_json = serde_json::to_string_pretty(&tasks).expect("serialize tasks");
     std::fs::write(store.tasks_file(), tasks_json).expect("write tasks");
@@ -3575,3 +4550,586 @@ fn remove_final_audit_reports_write_failure_without_returning_error() {
     let _ = std::fs::set_permissions(store.tasks_file(), reset_perms);
     std::fs::remove_dir_all(session_dir).ok();
 }
+
+#[test]
+fn preview_holds_master_prompt_and_cancel_rearms_session_intro() {
+    let mut app = App::default();
+    let master_adapter = CodexAdapter::new();
+    assert!(App::is_preview_command(" /PREVIEW "));
+    assert_eq!(
+        submit_block_reason(true, true, true, true, "/preview"),
+        None
+    );
+
+    assert!(app.toggle_master_prompt_preview());
+    let sent = send_or_preview_master_prompt(
+        &mut app,
+        &master_adapter,
+        "assembled prompt".to_string(),
+        "build a parser",
+        true,
+    );
+    assert!(!sent);
+    assert!(!app.is_master_in_progress());
+    assert!(is_picker_open(&app));
+    assert_eq!(
+        app.master_prompt_preview().map(|preview| preview.prompt.as_str()),
+        Some("assembled prompt")
+    );
+
+    let mut master_session_intro_needed = false;
+    cancel_master_prompt_preview(&mut app, &mut master_session_intro_needed);
+    assert!(master_session_intro_needed);
+    assert!(!app.is_master_prompt_preview_open());
+    assert_eq!(app.chat_input(), "build a parser");
+}
+
+#[test]
+fn stall_nudge_needs_a_resumable_agent_session() {
+    let mut app = App::default();
+    let worker = CodexAdapter::new();
+    let test_runner = TestRunnerAdapter::new();
+    let mut pending_nudge = None;
+
+    handle_stall_action(
+        &mut app,
+        StallAction::Nudge,
+        Some(&worker),
+        &test_runner,
+        &mut pending_nudge,
+        Instant::now(),
+    );
+    assert_eq!(pending_nudge, None);
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .expect("nudge message")
+            .contains("cannot be nudged")
+    );
+
+    pending_nudge = Some(STALL_NUDGE_PROMPT.to_string());
+    handle_stall_action(
+        &mut app,
+        StallAction::Abort,
+        None,
+        &test_runner,
+        &mut pending_nudge,
+        Instant::now(),
+    );
+    assert_eq!(pending_nudge, None);
+    assert_eq!(
+        app.left_bottom_lines().last().map(String::as_str),
+        Some("System: The stalled job already stopped; waiting for it to finish.")
+    );
+}
+
+fn split_test_tasks(titles: &[&str]) -> Vec<PlannerTaskFileEntry> {
+    titles
+        .iter()
+        .enumerate()
+        .flat_map(|(index, title)| {
+            let id = format!("t{index}");
+            let entry = |id: String, title: String, kind, parent_id: Option<String>| {
+                PlannerTaskFileEntry {
+                    id,
+                    title,
+                    details: "d".to_string(),
+                    docs: Vec::new(),
+                    kind,
+                    status: PlannerTaskStatusFile::Pending,
+                    parent_id,
+                    order: Some(index as u32),
+                    ..Default::default()
+                }
+            };
+            vec![
+                entry(id.clone(), title.to_string(), PlannerTaskKindFile::Task, None),
+                entry(
+                    format!("{id}-impl"),
+                    format!("{title} implementor"),
+                    PlannerTaskKindFile::Implementor,
+                    Some(id.clone()),
+                ),
+                entry(
+                    format!("{id}-audit"),
+                    format!("{title} auditor"),
+                    PlannerTaskKindFile::Auditor,
+                    Some(format!("{id}-impl")),
+                ),
+            ]
+        })
+        .collect()
+}
+
+fn stage_task_split(prefix: &str, app: &mut App) -> (SessionStore, std::path::PathBuf) {
+    let (store, session_dir) = open_temp_store(prefix);
+    let before = split_test_tasks(&["Big"]);
+    store.write_tasks(&before).expect("write tasks");
+    let proposal = serde_json::to_string(&split_test_tasks(&["Part one", "Part two"]))
+        .expect("serialize proposal");
+    std::fs::write(store.task_split_proposal_file(), proposal).expect("write proposal");
+    let run = TaskSplitRun {
+        session_dir: session_dir.clone(),
+        tasks_baseline: std::fs::read_to_string(store.tasks_file()).expect("read baseline"),
+        tasks_before: before,
+    };
+    finish_task_split_run(app, &store, run);
+    (store, session_dir)
+}
+
+#[test]
+fn approved_task_split_rewrites_tasks_json() {
+    let mut app = App::default();
+    let (store, session_dir) = stage_task_split("task-split-approve", &mut app);
+    assert!(app.pending_task_split().is_some());
+    let message = app
+        .left_bottom_lines()
+        .last()
+        .expect("proposal message")
+        .clone();
+    assert!(message.contains("Split: \"Big\""));
+    assert!(message.contains("Into: \"Part one\", \"Part two\""));
+
+    handle_task_split_decision(&mut app, "/approve-split", Some(&store));
+
+    assert!(app.pending_task_split().is_none());
+    assert!(!store.task_split_proposal_file().exists());
+    let titles = store
+        .read_tasks()
+        .expect("read tasks")
+        .into_iter()
+        .filter(|task| task.parent_id.is_none())
+        .map(|task| task.title)
+        .collect::<Vec<_>>();
+    assert_eq!(titles, vec!["Part one".to_string(), "Part two".to_string()]);
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn rejected_or_stale_task_split_leaves_tasks_json_unchanged() {
+    let mut app = App::default();
+    let (store, session_dir) = stage_task_split("task-split-reject", &mut app);
+    let before = std::fs::read_to_string(store.tasks_file()).expect("read tasks");
+    handle_task_split_decision(&mut app, "/reject-split", Some(&store));
+    assert!(app.pending_task_split().is_none());
+    assert!(!store.task_split_proposal_file().exists());
+    assert_eq!(
+        std::fs::read_to_string(store.tasks_file()).expect("read tasks"),
+        before
+    );
+    std::fs::remove_dir_all(session_dir).ok();
+
+    let mut app = App::default();
+    let (store, session_dir) = stage_task_split("task-split-stale", &mut app);
+    let edited = split_test_tasks(&["Big", "Manual"]);
+    store.write_tasks(&edited).expect("edit tasks");
+    handle_task_split_decision(&mut app, "/approve-split", Some(&store));
+    assert!(app.pending_task_split().is_none());
+    assert_eq!(store.read_tasks().expect("read tasks").len(), edited.len());
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .expect("stale message")
+            .contains("tasks.json changed after the split was proposed")
+    );
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn master_task_changes_are_listed_and_rejecting_restores_the_oldest_baseline() {
+    let mut app = App::default();
+    let (store, session_dir) = open_temp_store("task-changes-reject");
+    let first = split_test_tasks(&["Big"]);
+    store.write_tasks(&first).expect("write tasks");
+    app.sync_planner_tasks_from_file(first.clone())
+        .expect("sync tasks");
+    let baseline = std::fs::read_to_string(store.tasks_file()).expect("read baseline");
+
+    store
+        .write_tasks(&split_test_tasks(&["Big", "Extra"]))
+        .expect("master write");
+    offer_task_changes(&mut app, &store, &baseline);
+    let second_baseline = std::fs::read_to_string(store.tasks_file()).expect("read tasks");
+    store
+        .write_tasks(&split_test_tasks(&["Big", "Extra", "More"]))
+        .expect("second master write");
+    offer_task_changes(&mut app, &store, &second_baseline);
+    let message = app.left_bottom_lines().last().expect("diff").clone();
+    assert!(message.contains("+ added t1: Extra"));
+    assert!(message.contains("+ added t2: More"));
+    assert!(message.contains("/reject-tasks"));
+    assert_eq!(
+        app.pending_task_changes()
+            .map(|pending| pending.baseline.as_str()),
+        Some(baseline.as_str())
+    );
+
+    handle_task_changes_decision(&mut app, "/reject-tasks", Some(&store));
+    assert!(app.pending_task_changes().is_none());
+    assert_eq!(store.read_tasks().expect("read tasks").len(), first.len());
+    assert_eq!(app.planner_tasks_for_file().len(), first.len());
+
+    handle_task_changes_decision(&mut app, "/accept-tasks", Some(&store));
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .expect("nothing pending")
+            .contains("No task changes are waiting")
+    );
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn safe_mode_master_edits_the_staged_tasks_until_they_are_accepted() {
+    let mut app = App::default();
+    app.set_safe_mode(true);
+    let (store, session_dir) = open_temp_store("task-changes-safe-mode");
+    let first = split_test_tasks(&["Big"]);
+    store.write_tasks(&first).expect("write tasks");
+    app.sync_planner_tasks_from_file(first.clone())
+        .expect("sync tasks");
+    let baseline = std::fs::read_to_string(store.tasks_file()).expect("read baseline");
+    let changed = split_test_tasks(&["Big", "Extra"]);
+
+    let master_file = services::master_tasks_file(&app, &store);
+    assert_eq!(master_file, store.staged_tasks_file());
+    assert_eq!(
+        std::fs::read_to_string(&master_file).expect("staged copy"),
+        baseline
+    );
+    std::fs::write(
+        &master_file,
+        serde_json::to_string_pretty(&changed).expect("json"),
+    )
+    .expect("master write");
+    let staged = read_master_tasks(&app, &store).expect("read staged");
+    load_master_tasks(&mut app, staged).expect("staged tasks validate");
+    offer_task_changes(&mut app, &store, &baseline);
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .expect("diff")
+            .contains("tasks.staged.json")
+    );
+    assert_eq!(
+        std::fs::read_to_string(store.tasks_file()).expect("read tasks"),
+        baseline
+    );
+    assert_eq!(app.planner_tasks_for_file().len(), first.len());
+
+    handle_task_changes_decision(&mut app, "/accept-tasks", Some(&store));
+    assert_eq!(store.read_tasks().expect("read tasks").len(), changed.len());
+    assert_eq!(app.planner_tasks_for_file().len(), changed.len());
+    assert!(!store.staged_tasks_file().exists());
+
+    let baseline = std::fs::read_to_string(store.tasks_file()).expect("read baseline");
+    store.write_tasks(&first).expect("master wrote tasks.json");
+    restore_tasks_after_safe_mode_master(&mut app, &store, Some(&baseline));
+    assert_eq!(
+        std::fs::read_to_string(store.tasks_file()).expect("read tasks"),
+        baseline
+    );
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .expect("restored")
+            .contains("restored the previous tasks")
+    );
+
+    let master_file = services::master_tasks_file(&app, &store);
+    std::fs::write(
+        &master_file,
+        serde_json::to_string_pretty(&first).expect("json"),
+    )
+    .expect("second master write");
+    offer_task_changes(&mut app, &store, &baseline);
+    handle_task_changes_decision(&mut app, "/reject-tasks", Some(&store));
+    assert_eq!(store.read_tasks().expect("read tasks").len(), changed.len());
+    assert!(!store.staged_tasks_file().exists());
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn safe_mode_session_agents_stay_in_the_workspace_and_write_only_the_open_session() {
+    let mut model_routing = CodexAgentModelRouting::default();
+    let workspace = std::env::temp_dir().join("agentbob-safe-mode-workspace");
+    model_routing.set_workspace(Some(workspace.clone()));
+    model_routing.set_safe_mode(Some(SafeModeSandbox::default()));
+    let (store, session_dir) = open_temp_store("safe-mode-sandbox");
+    let adapters = [
+        CodexAgentKind::Master,
+        CodexAgentKind::MasterReport,
+        CodexAgentKind::ProjectInfo,
+        CodexAgentKind::DocsAttach,
+        CodexAgentKind::TaskCheck,
+    ]
+    .map(|kind| build_plain_adapter(&model_routing, BackendKind::Codex, kind, false));
+
+    for adapter in &adapters {
+        let config = adapter.run_config();
+        assert_eq!(config.workdir.as_deref(), Some(workspace.as_path()));
+        assert!(
+            config
+                .args_prefix
+                .contains(&"sandbox_mode=\"read-only\"".to_string())
+        );
+    }
+
+    sync_safe_mode_session(&model_routing, Some(&store));
+    let writable_roots = format!(
+        "sandbox_workspace_write.writable_roots=[{}]",
+        toml::Value::String(session_dir.display().to_string())
+    );
+    for adapter in &adapters {
+        let config = adapter.run_config();
+        assert_eq!(config.workdir.as_deref(), Some(workspace.as_path()));
+        assert!(config.args_prefix.contains(&writable_roots), "{config:?}");
+    }
+
+    sync_safe_mode_session(&model_routing, None);
+    assert!(
+        adapters[0]
+            .run_config()
+            .args_prefix
+            .contains(&"sandbox_mode=\"read-only\"".to_string())
+    );
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn cancelling_a_task_check_restores_tasks_json_and_clears_the_run() {
+    let mut app = App::default();
+    let (store, session_dir) = open_temp_store("cancel-task-check");
+    let before = split_test_tasks(&["Big"]);
+    store.write_tasks(&before).expect("write tasks");
+    app.sync_planner_tasks_from_file(before.clone())
+        .expect("sync tasks");
+    let adapter = CodexAdapter::new();
+    let mut project_info_in_flight = false;
+    let mut project_info_stage = None;
+    let mut pending_master_message = None;
+    let mut docs_attach_in_flight = false;
+    let mut task_check_in_flight = true;
+    let mut task_check_baseline = std::fs::read_to_string(store.tasks_file()).ok();
+    let mut task_split_run = None;
+    app.set_task_check_in_progress(true);
+    store
+        .write_tasks(&split_test_tasks(&["Big", "Checker edit"]))
+        .expect("checker write");
+
+    let mut cancel = |app: &mut App, command| {
+        handle_cancel_command(
+            app,
+            command,
+            &adapter,
+            &adapter,
+            &adapter,
+            &mut project_info_in_flight,
+            &mut project_info_stage,
+            &mut pending_master_message,
+            &mut docs_attach_in_flight,
+            &mut task_check_in_flight,
+            &mut task_check_baseline,
+            &mut task_split_run,
+            Some(&store),
+        )
+    };
+    cancel(&mut app, CancelCommand::TaskCheck);
+    assert!(!app.is_task_check_in_progress());
+    assert_eq!(store.read_tasks().expect("read tasks").len(), before.len());
+    assert_eq!(app.planner_tasks_for_file().len(), before.len());
+    assert_eq!(
+        app.left_bottom_lines().last().map(String::as_str),
+        Some("System: Cancelled the task check and restored tasks.json from before it ran.")
+    );
+
+    cancel(&mut app, CancelCommand::Docs);
+    assert_eq!(
+        app.left_bottom_lines().last().map(String::as_str),
+        Some("System: No docs attach is running.")
+    );
+    assert!(!task_check_in_flight);
+    assert_eq!(task_check_baseline, None);
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn cancelling_context_gathering_hands_back_the_held_master_message() {
+    let mut app = App::default();
+    let adapter = CodexAdapter::new();
+    let mut project_info_in_flight = true;
+    let mut project_info_stage = Some(ProjectInfoStage::GatheringInfo);
+    let mut pending_master_message = Some("Build a CSV parser".to_string());
+    let mut cancel = |app: &mut App| {
+        handle_cancel_command(
+            app,
+            CancelCommand::Context,
+            &adapter,
+            &adapter,
+            &adapter,
+            &mut project_info_in_flight,
+            &mut project_info_stage,
+            &mut pending_master_message,
+            &mut false,
+            &mut false,
+            &mut None,
+            &mut None,
+            None,
+        )
+    };
+
+    assert_eq!(cancel(&mut app), Some("Build a CSV parser".to_string()));
+    assert_eq!(cancel(&mut app), None);
+    assert_eq!(
+        app.left_bottom_lines().last().map(String::as_str),
+        Some("System: No project context gather is running.")
+    );
+    assert!(!project_info_in_flight);
+    assert_eq!(project_info_stage, None);
+    assert_eq!(pending_master_message, None);
+}
+
+#[test]
+fn tasks_command_edits_numbered_tasks_and_saves_them_all_or_nothing() {
+    let mut app = App::default();
+    let (store, session_dir) = open_temp_store("tasks-bulk");
+    let tasks = split_test_tasks(&["Parse", "Write", "Ship"]);
+    store.write_tasks(&tasks).expect("write tasks");
+    app.sync_planner_tasks_from_file(tasks).expect("sync tasks");
+
+    let summary = apply_tasks_command(&mut app, TasksCommand::Done(vec![1, 3]), &store);
+    assert_eq!(
+        summary,
+        "System: Marked done 2 task(s) with their subtasks:\n- 1. Parse\n- 3. Ship"
+    );
+    let saved = store.read_tasks().expect("read tasks");
+    assert!(
+        saved
+            .iter()
+            .filter(|task| task.id.starts_with("t0") || task.id.starts_with("t2"))
+            .all(|task| task.status == PlannerTaskStatusFile::Done)
+    );
+
+    let summary = apply_tasks_command(&mut app, TasksCommand::Delete(vec![2, 4]), &store);
+    assert_eq!(
+        summary,
+        "System: No tasks were changed: No task #4; the task list has 3 top-level task(s)"
+    );
+    assert_eq!(store.read_tasks().expect("read tasks").len(), saved.len());
+
+    let summary = apply_tasks_command(&mut app, TasksCommand::Delete(vec![2]), &store);
+    assert_eq!(
+        summary,
+        "System: This deletes 1 task(s) with their subtasks:\n- 2. Write\nSend the same /tasks delete again to confirm."
+    );
+    assert_eq!(store.read_tasks().expect("read tasks").len(), saved.len());
+    assert_eq!(app.planner_tasks_for_file().len(), saved.len());
+
+    let summary = apply_tasks_command(&mut app, TasksCommand::Delete(vec![2]), &store);
+    assert_eq!(
+        summary,
+        "System: Deleted 1 task(s) with their subtasks:\n- 2. Write"
+    );
+    assert_eq!(
+        app.planner_tasks_for_file()
+            .iter()
+            .filter(|task| task.parent_id.is_none())
+            .map(|task| task.title.as_str())
+            .collect::<Vec<_>>(),
+        ["Parse", "Ship"]
+    );
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+fn stage_external_tasks_edit(prefix: &str, app: &mut App) -> (SessionStore, std::path::PathBuf) {
+    let (store, session_dir) = open_temp_store(prefix);
+    let before = split_test_tasks(&["First"]);
+    app.sync_planner_tasks_from_file(before.clone())
+        .expect("sync tasks");
+    store
+        .write_tasks(&split_test_tasks(&["First", "Second"]))
+        .expect("hand edit tasks");
+    app.set_external_tasks_edit(app::ExternalTasksEdit {
+        session_dir: session_dir.clone(),
+        text: std::fs::read_to_string(store.tasks_file()).expect("read tasks"),
+        validation_error: None,
+    });
+    (store, session_dir)
+}
+
+#[test]
+fn reload_tasks_loads_the_external_edit_and_resumes_enabled_execution() {
+    let mut app = App::default();
+    let (store, session_dir) = stage_external_tasks_edit("reload-tasks", &mut app);
+    assert!(!handle_external_tasks_decision(&mut app, "/reload-tasks", Some(&store)));
+    assert!(!app.has_external_tasks_edit());
+    assert_eq!(app.planner_tasks_for_file().len(), 6);
+    std::fs::remove_dir_all(session_dir).ok();
+
+    let mut app = App::default();
+    let (store, session_dir) = stage_external_tasks_edit("reload-tasks-idle", &mut app);
+    let mut finished = split_test_tasks(&["First"]);
+    for task in &mut finished {
+        task.status = PlannerTaskStatusFile::Done;
+    }
+    app.sync_planner_tasks_from_file(finished)
+        .expect("sync finished tasks");
+    app.start_execution();
+    assert!(app.is_execution_enabled() && !app.is_execution_busy());
+    assert!(handle_external_tasks_decision(&mut app, "/reload-tasks", Some(&store)));
+    assert!(!app.is_execution_enabled());
+    assert_eq!(app.planner_tasks_for_file().len(), 6);
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn master_write_over_pending_hand_edit_opens_conflict_until_resolved() {
+    let mut app = App::default();
+    let (store, session_dir) = stage_external_tasks_edit("tasks-edit-conflict", &mut app);
+    store
+        .write_tasks(&split_test_tasks(&["First", "Third"]))
+        .expect("master write");
+    detect_tasks_edit_conflict(&mut app, &store);
+    assert!(!app.has_external_tasks_edit());
+    assert_eq!(
+        app.edit_conflict().map(|conflict| conflict.file),
+        Some(ConflictFile::Tasks)
+    );
+
+    apply_conflict_command(&mut app, Some(ConflictChoice::Mine), Some(&store));
+    assert!(app.edit_conflict().is_none());
+    assert_eq!(store.read_tasks().expect("read tasks")[3].title, "Second");
+    assert!(app.has_external_tasks_edit());
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .expect("resolve message")
+            .contains("kept your tasks.json")
+    );
+
+    apply_conflict_command(&mut app, Some(ConflictChoice::Theirs), Some(&store));
+    assert_eq!(
+        app.left_bottom_lines().last().map(String::as_str),
+        Some("System: No edit conflict is open.")
+    );
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn keep_tasks_restores_file_and_invalid_reload_stays_pending() {
+    let mut app = App::default();
+    let (store, session_dir) = stage_external_tasks_edit("keep-tasks", &mut app);
+    assert!(!handle_external_tasks_decision(&mut app, "/keep-tasks", Some(&store)));
+    assert!(!app.has_external_tasks_edit());
+    assert_eq!(store.read_tasks().expect("read tasks").len(), 3);
+    std::fs::remove_dir_all(session_dir).ok();
+
+    let mut app = App::default();
+    let (store, session_dir) = stage_external_tasks_edit("reload-tasks-invalid", &mut app);
+    std::fs::write(store.tasks_file(), "[{").expect("corrupt tasks");
+    assert!(!handle_external_tasks_decision(&mut app, "/reload-tasks", Some(&store)));
+    assert!(app.has_external_tasks_edit());
+    assert_eq!(app.planner_tasks_for_file().len(), 3);
+    std::fs::remove_dir_all(session_dir).ok();
+}
@@ -0,0 +1,155 @@
+use super::*;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn temp_session_dir(prefix: &str) -> std::path::PathBuf {
+    let session_dir = std::env::temp_dir().join(format!(
+        "{prefix}-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&session_dir).expect("create session dir");
+    fs::write(session_dir.join("tasks.json"), "[]\n").expect("write tasks");
+    fs::write(session_dir.join("planner.md"), "").expect("write planner");
+    fs::write(session_dir.join("rolling_context.json"), "[]\n").expect("write context");
+    fs::write(session_dir.join("task-fails.json"), "[]\n").expect("write task fails");
+    session_dir
+}
+
+#[test]
+fn unseen_context_entries_follows_capped_window() {
+    let entries = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        unseen_context_entries(&[], &entries(&["a", "b"])),
+        entries(&["a", "b"]).as_slice()
+    );
+    assert!(unseen_context_entries(&entries(&["a", "b"]), &entries(&["a", "b"])).is_empty());
+    assert_eq!(
+        unseen_context_entries(&entries(&["a", "b", "c"]), &entries(&["b", "c", "d"])),
+        entries(&["d"]).as_slice()
+    );
+    assert_eq!(
+        unseen_context_entries(&entries(&["a"]), &entries(&["x", "y"])),
+        entries(&["x", "y"]).as_slice()
+    );
+}
+
+#[test]
+fn poll_mirrors_session_files_and_only_reports_changes() {
+    let session_dir = temp_session_dir("metaagent-observer-poll");
+    let store = SessionStore::open_read_only(&session_dir).expect("open read-only");
+    let mut observer = SessionObserver::new(store);
+    let mut app = App::default();
+
+    assert!(
+        observer.poll(&mut app),
+        "first poll applies the initial state"
+    );
+    assert!(
+        !observer.poll(&mut app),
+        "unchanged files are not re-applied"
+    );
+
+    fs::write(
+        session_dir.join("tasks.json"),
+        r#"[{"id":"t1","title":"Observed task","details":"Watch it","parent_id":null,"order":0}]"#,
+    )
+    .expect("write tasks");
+    fs::write(session_dir.join("planner.md"), "# Plan\n").expect("write planner");
+    fs::write(
+        session_dir.join("rolling_context.json"),
+        r#"["implementor finished"]"#,
+    )
+    .expect("write context");
+    fs::write(
+        session_dir.join("task-fails.json"),
+        r#"[{"kind":"audit","top_task_id":1,"top_task_title":"Observed task","attempts":4,"reason":"missing tests","action_taken":"continued","created_at_epoch_secs":0}]"#,
+    )
+    .expect("write task fails");
+
+    assert!(observer.poll(&mut app));
+    assert_eq!(app.planner_tasks_for_file()[0].title, "Observed task");
+    assert_eq!(app.planner_markdown(), "# Plan\n");
+    assert!(
+        app.left_top_lines()
+            .iter()
+            .any(|line| line.contains("Context: implementor finished"))
+    );
+    assert!(
+        app.left_bottom_lines()
+            .iter()
+            .any(|line| line.contains("audit failed for task #1 (Observed task)"))
+    );
+    assert!(!observer.poll(&mut app));
+
+    let _ = fs::remove_dir_all(&session_dir);
+}
+
+#[test]
+fn poll_tails_the_event_log_from_the_last_complete_line() {
+    let session_dir = temp_session_dir("metaagent-observer-events");
+    let store = SessionStore::open_read_only(&session_dir).expect("open read-only");
+    let mut observer = SessionObserver::new(store);
+    let mut app = App::default();
+    observer.poll(&mut app);
+
+    let line = |seq: u64, event: SessionEventKindFile| {
+        serde_json::to_string(&SessionEventFileEntry {
+            seq,
+            at_epoch_ms: 0,
+            event,
+        })
+        .expect("serialize event")
+    };
+    let first = line(
+        1,
+        SessionEventKindFile::ChatMessage {
+            line: "You: add a login page".to_string(),
+        },
+    );
+    let second = line(
+        2,
+        SessionEventKindFile::AgentOutput {
+            agent: "worker".to_string(),
+            line: "editing src/login.rs".to_string(),
+        },
+    );
+    let (written, pending) = second.split_at(10);
+    fs::write(
+        session_dir.join("events.jsonl"),
+        format!("{first}\n{written}"),
+    )
+    .expect("write events");
+
+    assert!(observer.poll(&mut app));
+    assert!(
+        app.left_bottom_lines()
+            .iter()
+            .any(|line| line.contains("You: add a login page"))
+    );
+    assert!(!observer.poll(&mut app), "a partial line waits");
+
+    let mut log = fs::OpenOptions::new()
+        .append(true)
+        .open(session_dir.join("events.jsonl"))
+        .expect("open events");
+    std::io::Write::write_all(&mut log, format!("{pending}\n").as_bytes()).expect("append");
+    assert!(observer.poll(&mut app));
+    assert!(
+        app.left_top_lines()
+            .iter()
+            .any(|line| line.contains("worker: editing src/login.rs"))
+    );
+    assert_eq!(
+        app.left_bottom_lines()
+            .iter()
+            .filter(|line| line.contains("You: add a login page"))
+            .count(),
+        1
+    );
+    assert!(!observer.poll(&mut app));
+
+    let _ = fs::remove_dir_all(&session_dir);
+}
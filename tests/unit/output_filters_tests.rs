@@ -0,0 +1,91 @@
+use super::*;
+
+fn rule(pattern: &str, roles: &[&str], action: FilterAction) -> OutputFilterRule {
+    OutputFilterRule {
+        pattern: pattern.to_string(),
+        roles: roles.iter().map(|role| role.to_string()).collect(),
+        action,
+    }
+}
+
+#[test]
+fn patterns_support_wildcards_and_anchors() {
+    assert!(pattern_matches("tool call", "[exec] tool call: rg -n foo"));
+    assert!(pattern_matches("^[exec]*rg", "[exec] tool call: rg -n foo"));
+    assert!(!pattern_matches("^tool", "[exec] tool call: rg -n foo"));
+    assert!(pattern_matches("foo$", "foo bar foo"));
+    assert!(pattern_matches(
+        "^warning:*unused*$",
+        "warning: unused import"
+    ));
+    assert!(!pattern_matches(
+        "^warning:*unused*x$",
+        "warning: unused import"
+    ));
+    assert!(pattern_matches("^exact$", "exact"));
+    assert!(!pattern_matches("^exact$", "exactly"));
+}
+
+#[test]
+fn spinner_frames_are_detected() {
+    assert!(is_spinner_frame("⠙ Thinking"));
+    assert!(is_spinner_frame("  /  "));
+    assert!(!is_spinner_frame("- item"));
+    assert!(!is_spinner_frame(""));
+}
+
+#[test]
+fn filter_hides_spinners_and_role_rules_and_collapses_repeats() {
+    let mut filter = OutputFilter::new(OutputFilterConfig {
+        rules: vec![
+            rule("^tokens used", &["implementor"], FilterAction::Hide),
+            rule("^[exec]", &[], FilterAction::Collapse),
+        ],
+        ..OutputFilterConfig::default()
+    });
+    let mut apply = |role: &str, line: &str, generation: u64| {
+        filter.apply(role, line, format!("{role}: {line}"), generation)
+    };
+
+    assert_eq!(apply("implementor", "⠹ Working", 0), PaneUpdate::Hide);
+    assert_eq!(
+        apply("implementor", "tokens used: 120", 0),
+        PaneUpdate::Hide
+    );
+    assert_eq!(
+        apply("auditor", "tokens used: 120", 0),
+        PaneUpdate::Append("auditor: tokens used: 120".to_string())
+    );
+    assert_eq!(
+        apply("implementor", "[exec] ls", 1),
+        PaneUpdate::Append("implementor: [exec] ls".to_string())
+    );
+    assert_eq!(
+        apply("implementor", "[exec] cat a.rs", 2),
+        PaneUpdate::ReplaceLast("implementor: [exec] ls (+1 similar)".to_string())
+    );
+    assert_eq!(
+        apply("implementor", "[exec] cat b.rs", 3),
+        PaneUpdate::ReplaceLast("implementor: [exec] ls (+2 similar)".to_string())
+    );
+    // Another line written to the pane in between ends the run.
+    assert_eq!(
+        apply("implementor", "[exec] cat c.rs", 5),
+        PaneUpdate::Append("implementor: [exec] cat c.rs".to_string())
+    );
+    assert_eq!(
+        apply("implementor", "warning: slow test", 6),
+        PaneUpdate::Append("implementor: warning: slow test".to_string())
+    );
+    assert_eq!(
+        apply("implementor", "warning: slow test", 7),
+        PaneUpdate::ReplaceLast("implementor: warning: slow test (+1 similar)".to_string())
+    );
+    assert_eq!(filter.hidden_lines(), 5);
+
+    filter.set_verbose(true);
+    assert_eq!(
+        filter.apply("implementor", "⠹ Working", "⠹ Working".to_string(), 8),
+        PaneUpdate::Append("⠹ Working".to_string())
+    );
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+fn temp_workspace(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "agentbob-plan-import-{name}-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("create workspace");
+    dir
+}
+
+#[test]
+fn find_prefers_plan_md_and_skips_empty_files() {
+    let dir = temp_workspace("find");
+    assert_eq!(find(&dir), None);
+
+    fs::write(dir.join("PLAN.md"), "  \n").expect("write PLAN.md");
+    fs::write(dir.join("TODO.md"), "- [ ] Parse input\n").expect("write TODO.md");
+    let plan = find(&dir).expect("TODO.md is found");
+    assert_eq!(plan.file_name, "TODO.md");
+    assert_eq!(plan.markdown, "- [ ] Parse input");
+
+    fs::write(dir.join("PLAN.md"), "# Plan\n").expect("write PLAN.md");
+    assert_eq!(find(&dir).map(|plan| plan.file_name), Some("PLAN.md"));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn merge_keeps_existing_planner_text_and_does_not_import_twice() {
+    let plan = WorkspacePlan {
+        file_name: "TODO.md",
+        path: PathBuf::from("TODO.md"),
+        markdown: "- [ ] Parse input".to_string(),
+    };
+
+    assert_eq!(merge_into_planner("\n", &plan), "- [ ] Parse input\n");
+    let merged = merge_into_planner("# Goals\n\nFast CSV tool\n", &plan);
+    assert_eq!(
+        merged,
+        "# Goals\n\nFast CSV tool\n\n## Imported from TODO.md\n\n- [ ] Parse input\n"
+    );
+    assert_eq!(merge_into_planner(&merged, &plan), merged);
+}
@@ -0,0 +1,224 @@
+use super::*;
+use crate::session_store::{PlannerTaskDocFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn open_temp_store(prefix: &str) -> (SessionStore, PathBuf) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let cwd = std::env::current_dir().expect("cwd");
+    let session_dir = std::env::temp_dir().join(format!("{prefix}-{now}"));
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing store");
+    (store, session_dir)
+}
+
+fn planned_tasks(docs: Vec<PlannerTaskDocFileEntry>) -> Vec<PlannerTaskFileEntry> {
+    let entry = |id: &str, kind, parent_id: Option<&str>| PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("{id} title"),
+        details: "d".to_string(),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    };
+    let mut top = entry("t", PlannerTaskKindFile::Task, None);
+    top.docs = docs;
+    vec![
+        top,
+        entry("t-impl", PlannerTaskKindFile::Implementor, Some("t")),
+        entry("t-audit", PlannerTaskKindFile::Auditor, Some("t-impl")),
+    ]
+}
+
+fn doc(title: &str) -> PlannerTaskDocFileEntry {
+    PlannerTaskDocFileEntry {
+        title: title.to_string(),
+        url: format!("https://example.com/{title}"),
+        summary: String::new(),
+        snapshot: None,
+    }
+}
+
+/// Records the stage order and lets each test script what the "agent" writes to disk.
+struct ScriptedRunner<F> {
+    stages: Vec<PlanStage>,
+    prompts: Vec<String>,
+    script: F,
+}
+
+impl<F: FnMut(PlanStage, usize) -> Result<Vec<String>, String>> PlanStageRunner
+    for ScriptedRunner<F>
+{
+    fn run(&mut self, stage: PlanStage, prompt: String) -> Result<Vec<String>, String> {
+        let runs = self.stages.iter().filter(|seen| **seen == stage).count();
+        self.stages.push(stage);
+        self.prompts.push(prompt);
+        (self.script)(stage, runs)
+    }
+}
+
+#[test]
+fn pipeline_runs_every_stage_and_keeps_only_attached_docs() {
+    let (store, session_dir) = open_temp_store("plan-pipeline-ok");
+    let tasks_file = store.tasks_file().to_path_buf();
+    let mut runner = ScriptedRunner {
+        stages: Vec::new(),
+        prompts: Vec::new(),
+        script: |stage, _| {
+            let write = |tasks: Vec<PlannerTaskFileEntry>| {
+                let text = serde_json::to_string_pretty(&tasks).expect("serialize tasks");
+                std::fs::write(&tasks_file, text).expect("write tasks");
+            };
+            match stage {
+                PlanStage::ProjectInfo => return Ok(vec!["# Project".to_string()]),
+                PlanStage::Master => write(planned_tasks(vec![doc("from-master")])),
+                PlanStage::DocsAttach => write(planned_tasks(vec![doc("from-docs")])),
+                PlanStage::SessionMeta | PlanStage::TaskCheck => {}
+            }
+            Ok(Vec::new())
+        },
+    };
+    let mut app = App::default();
+
+    let outcome = run_plan_pipeline(
+        &mut app,
+        &store,
+        Path::new("/repo"),
+        "Build a thing",
+        &mut runner,
+    )
+    .expect("pipeline succeeds");
+
+    assert_eq!(
+        runner.stages,
+        vec![
+            PlanStage::ProjectInfo,
+            PlanStage::SessionMeta,
+            PlanStage::Master,
+            PlanStage::TaskCheck,
+            PlanStage::DocsAttach,
+        ]
+    );
+    assert!(runner.prompts[2].contains("Build a thing"));
+    assert!(outcome.notes.is_empty(), "{:?}", outcome.notes);
+    assert_eq!(outcome.tasks.len(), 3);
+    assert_eq!(outcome.tasks[0].docs, vec![doc("from-docs")]);
+    assert_eq!(
+        store.read_project_info().expect("project info fallback"),
+        "# Project"
+    );
+    let changelog = crate::tasks_changelog::read_entries(&store.tasks_changelog_file())
+        .expect("read changelog");
+    assert_eq!(
+        changelog
+            .iter()
+            .map(|entry| entry.agent)
+            .collect::<Vec<_>>(),
+        vec![
+            crate::tasks_changelog::TasksChangeAgent::Master,
+            crate::tasks_changelog::TasksChangeAgent::DocsAttach,
+        ]
+    );
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn invalid_master_output_is_retried_and_invalid_later_edits_are_rolled_back() {
+    let (store, session_dir) = open_temp_store("plan-pipeline-retry");
+    let tasks_file = store.tasks_file().to_path_buf();
+    let mut runner = ScriptedRunner {
+        stages: Vec::new(),
+        prompts: Vec::new(),
+        script: |stage, runs| {
+            match (stage, runs) {
+                (PlanStage::Master, 0) | (PlanStage::TaskCheck, _) => {
+                    std::fs::write(&tasks_file, "[{").expect("write broken tasks");
+                }
+                (PlanStage::Master, _) => {
+                    let text =
+                        serde_json::to_string(&planned_tasks(Vec::new())).expect("serialize");
+                    std::fs::write(&tasks_file, text).expect("write tasks");
+                }
+                (PlanStage::DocsAttach, _) => return Err("agent exited with code 1".to_string()),
+                _ => {}
+            }
+            Ok(Vec::new())
+        },
+    };
+    let mut app = App::default();
+
+    let outcome = run_plan_pipeline(&mut app, &store, Path::new("/repo"), "spec", &mut runner)
+        .expect("pipeline succeeds after retry");
+
+    let master_prompts = runner
+        .stages
+        .iter()
+        .zip(&runner.prompts)
+        .filter(|(stage, _)| **stage == PlanStage::Master)
+        .map(|(_, prompt)| prompt)
+        .collect::<Vec<_>>();
+    assert_eq!(master_prompts.len(), 2);
+    assert!(master_prompts[1].contains("tasks.json failed to parse/validate"));
+    assert_eq!(outcome.tasks.len(), 3);
+    assert_eq!(store.read_tasks().expect("restored tasks").len(), 3);
+    let noted = outcome
+        .notes
+        .iter()
+        .map(|note| note.stage)
+        .collect::<Vec<_>>();
+    assert_eq!(noted, vec![PlanStage::TaskCheck, PlanStage::DocsAttach]);
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn master_failure_or_exhausted_retries_abort_the_pipeline() {
+    let (store, session_dir) = open_temp_store("plan-pipeline-fail");
+    let tasks_file = store.tasks_file().to_path_buf();
+    let mut runner = ScriptedRunner {
+        stages: Vec::new(),
+        prompts: Vec::new(),
+        script: |stage, _| {
+            if stage == PlanStage::Master {
+                std::fs::write(&tasks_file, "not json").expect("write broken tasks");
+            }
+            Ok(Vec::new())
+        },
+    };
+    let err = run_plan_pipeline(
+        &mut App::default(),
+        &store,
+        Path::new("/repo"),
+        "spec",
+        &mut runner,
+    )
+    .expect_err("retries exhausted");
+    assert!(err.contains("after 2 correction attempt(s)"), "{err}");
+    assert!(!runner.stages.contains(&PlanStage::TaskCheck));
+
+    let mut runner = ScriptedRunner {
+        stages: Vec::new(),
+        prompts: Vec::new(),
+        script: |stage, _| match stage {
+            PlanStage::Master => Err("agent exited with code 2".to_string()),
+            _ => Ok(Vec::new()),
+        },
+    };
+    let err = run_plan_pipeline(
+        &mut App::default(),
+        &store,
+        Path::new("/repo"),
+        "spec",
+        &mut runner,
+    )
+    .expect_err("master failed");
+    assert_eq!(err, "Master planning failed: agent exited with code 2");
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
@@ -0,0 +1,247 @@
+use super::*;
+use crate::plugins::PluginRegistry;
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile};
+use crate::workflow::{TaskNode, WorkerRole};
+
+fn entry(
+    id: &str,
+    parent_id: Option<&str>,
+    kind: PlannerTaskKindFile,
+    order: u32,
+) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("{id} title"),
+        details: format!("{id} details"),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(order),
+        ..Default::default()
+    }
+}
+
+/// A top-level task whose implementor has one audit followed by a plugin step per
+/// `(plugin, order)`, listed in the given order.
+fn seed(workflow: &mut Workflow, steps: &[(&str, u32)]) {
+    let mut tasks = vec![
+        entry("top", None, PlannerTaskKindFile::Task, 0),
+        entry("impl", Some("top"), PlannerTaskKindFile::Implementor, 0),
+        entry("audit", Some("impl"), PlannerTaskKindFile::Auditor, 0),
+    ];
+    for (plugin, order) in steps {
+        let mut step = entry(plugin, Some("impl"), PlannerTaskKindFile::Plugin, *order);
+        step.plugin = Some(plugin.to_string());
+        tasks.push(step);
+    }
+    workflow
+        .sync_planner_tasks_from_file(tasks)
+        .expect("sync should succeed");
+}
+
+fn node<'a>(nodes: &'a [TaskNode], external_id: &str) -> Option<&'a TaskNode> {
+    nodes.iter().find_map(|candidate| {
+        if candidate.external_id.as_deref() == Some(external_id) {
+            Some(candidate)
+        } else {
+            node(&candidate.children, external_id)
+        }
+    })
+}
+
+fn id_of(workflow: &Workflow, external_id: &str) -> u64 {
+    node(&workflow.tasks, external_id).expect("task").id
+}
+
+fn status_of(workflow: &Workflow, external_id: &str) -> TaskStatus {
+    node(&workflow.tasks, external_id).expect("task").status
+}
+
+#[cfg(unix)]
+fn install_step_plugins(names: &[&str]) -> PluginRegistry {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!(
+        "agentbob-plugin-step-{}-{}",
+        names.join("-"),
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create plugins dir");
+    for name in names {
+        let program = dir.join(name);
+        std::fs::write(
+            &program,
+            format!(
+                "#!/bin/sh\nread -r request\necho '{{\"name\":\"{name}\",\"description\":\"{name}\",\"capabilities\":[\"step\"]}}'\n"
+            ),
+        )
+        .expect("write plugin");
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+    }
+    let registry = PluginRegistry::discover(&dir);
+    let _ = std::fs::remove_dir_all(&dir);
+    registry
+}
+
+fn next_plugin(workflow: &mut Workflow) -> Option<String> {
+    let job = workflow.start_next_job()?;
+    assert_eq!(job.role, WorkerRole::Plugin);
+    match job.run {
+        JobRun::PluginStep { plugin, .. } => Some(plugin),
+        _ => panic!("expected plugin step"),
+    }
+}
+
+#[test]
+fn job_run_sends_the_step_details_and_the_implementation_it_checks() {
+    let mut workflow = Workflow::default();
+    seed(&mut workflow, &[("license-check", 1)]);
+    let run = job_run(
+        &workflow,
+        id_of(&workflow, "top"),
+        id_of(&workflow, "impl"),
+        id_of(&workflow, "license-check"),
+    );
+    match run {
+        JobRun::PluginStep {
+            plugin,
+            program,
+            input,
+            task,
+        } => {
+            assert_eq!(plugin, "license-check");
+            assert_eq!(program, None);
+            assert_eq!(input, "license-check details");
+            assert_eq!(task.id, "impl");
+            assert_eq!(task.title, "impl title");
+            assert_eq!(task.details, "impl details");
+            assert_eq!(task.top_task_title, "top title");
+        }
+        _ => panic!("expected plugin step"),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn plugin_steps_run_in_task_order_after_the_audits() {
+    let mut workflow = Workflow::default();
+    workflow.set_plugins(install_step_plugins(&["lint", "license-check"]));
+    seed(&mut workflow, &[("lint", 2), ("license-check", 1)]);
+    workflow.start_execution();
+    let _ = workflow.start_next_job().expect("implementor");
+    workflow.finish_active_job(true, 0);
+    let _ = workflow.start_next_job().expect("audit");
+    workflow.append_active_output("PASS".to_string());
+    workflow.finish_active_job(true, 0);
+
+    assert_eq!(next_plugin(&mut workflow).as_deref(), Some("license-check"));
+    let messages = workflow.finish_active_job(true, 0);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("plugin step license-check passed on run 1"))
+    );
+    assert_eq!(status_of(&workflow, "license-check"), TaskStatus::Done);
+    assert_eq!(status_of(&workflow, "lint"), TaskStatus::Pending);
+
+    assert_eq!(next_plugin(&mut workflow).as_deref(), Some("lint"));
+    workflow.finish_active_job(true, 0);
+    assert_eq!(status_of(&workflow, "lint"), TaskStatus::Done);
+    assert!(
+        workflow
+            .start_next_job()
+            .is_none_or(|job| job.role != WorkerRole::Plugin)
+    );
+}
+
+#[test]
+fn a_failed_step_sends_its_output_back_to_the_implementor() {
+    let mut workflow = Workflow::default();
+    seed(&mut workflow, &[("license-check", 1)]);
+    let (top, implementor, step) = (
+        id_of(&workflow, "top"),
+        id_of(&workflow, "impl"),
+        id_of(&workflow, "license-check"),
+    );
+    let mut messages = Vec::new();
+    on_completion(
+        &mut workflow,
+        top,
+        implementor,
+        step,
+        1,
+        &["GPL dependency".to_string()],
+        false,
+        2,
+        &mut messages,
+    );
+
+    assert_eq!(
+        status_of(&workflow, "license-check"),
+        TaskStatus::NeedsChanges
+    );
+    assert_eq!(status_of(&workflow, "impl"), TaskStatus::NeedsChanges);
+    assert!(messages[0].contains("plugin step license-check failed; implementor pass 2 queued"));
+    match &workflow.queue.back().expect("queued retry").kind {
+        WorkerJobKind::Implementor {
+            implementor_id,
+            pass,
+            feedback,
+            ..
+        } => {
+            assert_eq!(*implementor_id, implementor);
+            assert_eq!(*pass, 2);
+            assert_eq!(
+                feedback.as_deref(),
+                Some("Plugin license-check failed with code 2. Output:\nGPL dependency")
+            );
+        }
+        _ => panic!("expected an implementor retry"),
+    }
+
+    assert_eq!(
+        plugin_feedback("license-check", &[], 3),
+        "Plugin license-check failed with code 3 and no output."
+    );
+}
+
+#[test]
+fn a_step_that_keeps_failing_is_recorded_and_execution_moves_on() {
+    let mut workflow = Workflow::default();
+    seed(&mut workflow, &[("license-check", 1)]);
+    let (top, implementor, step) = (
+        id_of(&workflow, "top"),
+        id_of(&workflow, "impl"),
+        id_of(&workflow, "license-check"),
+    );
+    let mut messages = Vec::new();
+    on_completion(
+        &mut workflow,
+        top,
+        implementor,
+        step,
+        super::super::MAX_TEST_RETRIES,
+        &["still GPL".to_string()],
+        false,
+        1,
+        &mut messages,
+    );
+
+    assert_eq!(status_of(&workflow, "license-check"), TaskStatus::Done);
+    assert!(messages[0].contains("Max retries (5) reached; proceeding to next step."));
+    assert!(
+        !workflow
+            .queue
+            .iter()
+            .any(|job| matches!(job.kind, WorkerJobKind::Implementor { .. }))
+    );
+    let failure = workflow.recent_failures.last().expect("recorded failure");
+    assert_eq!(failure.top_task_id, top);
+    assert_eq!(failure.attempts, super::super::MAX_TEST_RETRIES);
+    assert!(failure.reason.contains("still GPL"));
+    assert_eq!(
+        failure.action_taken,
+        "Plugin license-check retries exhausted; continued to next step."
+    );
+}
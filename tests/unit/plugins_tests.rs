@@ -0,0 +1,132 @@
+use super::*;
+
+fn plugins_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("agentbob-plugins-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create plugins dir");
+    dir
+}
+
+#[cfg(unix)]
+fn write_plugin(dir: &Path, file_name: &str, script: &str) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+    let path = dir.join(file_name);
+    std::fs::write(&path, format!("#!/bin/sh\n{script}")).expect("write plugin");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn discovery_loads_described_executables_and_reports_the_rest() {
+    let dir = plugins_dir("discover");
+    write_plugin(
+        &dir,
+        "license",
+        "read -r request\n\
+         echo '{\"name\":\"license-check\",\"description\":\"Checks licenses\",\"capabilities\":[\"step\",\"command\"]}'\n",
+    );
+    write_plugin(
+        &dir,
+        "zz-copy",
+        "echo '{\"name\":\"license-check\",\"capabilities\":[\"command\"]}'\n",
+    );
+    write_plugin(&dir, "shouty", "echo '{\"name\":\"Shouty\"}'\n");
+    write_plugin(&dir, "broken", "echo 'not json'\n");
+    write_plugin(&dir, ".hidden", "exit 1\n");
+    std::fs::write(dir.join("README.md"), "not a plugin").expect("write readme");
+
+    let registry = PluginRegistry::discover(&dir);
+    let names = registry
+        .plugins()
+        .iter()
+        .map(Plugin::name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["license-check"]);
+    let plugin = registry.get("license-check").expect("plugin");
+    assert_eq!(plugin.program, dir.join("license"));
+    assert_eq!(plugin.manifest.description, "Checks licenses");
+    assert!(plugin.supports(PluginCapability::Step));
+    assert_eq!(registry.load_errors().len(), 3);
+    assert!(registry.load_errors()[0].starts_with("broken: invalid describe response"));
+    assert_eq!(
+        registry.load_errors()[1],
+        "shouty: plugin name \"Shouty\" must use lower-case letters, digits, '-' or '_'"
+    );
+    assert_eq!(
+        registry.load_errors()[2],
+        "zz-copy: plugin name \"license-check\" is already taken"
+    );
+    assert_eq!(
+        registry.get_for("nope", PluginCapability::Command),
+        Err("No plugin named \"nope\" is installed.".to_string())
+    );
+    assert!(
+        PluginRegistry::discover(&dir.join("missing"))
+            .plugins()
+            .is_empty()
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn run_sends_the_request_and_streams_output_before_the_response() {
+    let dir = plugins_dir("run");
+    let program = write_plugin(
+        &dir,
+        "echoer",
+        "read -r request\n\
+         echo \"$request\" > \"$(dirname \"$0\")/request.json\"\n\
+         pwd > \"$(dirname \"$0\")/cwd.txt\"\n\
+         echo 'checking 2 files'\n\
+         echo 'warning: slow' >&2\n\
+         echo '{\"status\":\"fail\",\"summary\":\"GPL dependency\",\"details\":[\"Cargo.toml: gpl-crate\"]}'\n",
+    );
+    let request = PluginRunRequest {
+        invocation: PluginCapability::Step,
+        input: "Check licenses".to_string(),
+        task: None,
+        workspace: dir.display().to_string(),
+        session_dir: None,
+    };
+    let mut lines = Vec::new();
+    let response = run(&program, &request, &Mutex::new(None), &mut |line| {
+        lines.push(line)
+    })
+    .expect("run plugin");
+    assert!(!response.passed());
+    lines.sort();
+    assert_eq!(lines, ["checking 2 files", "warning: slow"]);
+    assert_eq!(
+        response_message(Locale::En, "license-check", &response),
+        "Plugin license-check failed: GPL dependency\n- Cargo.toml: gpl-crate"
+    );
+    assert_eq!(
+        response_message(Locale::Es, "license-check", &response),
+        "El plugin license-check falló: GPL dependency\n- Cargo.toml: gpl-crate"
+    );
+    let sent: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(dir.join("request.json")).expect("read request"),
+    )
+    .expect("request json");
+    assert_eq!(sent["protocol"], 1);
+    assert_eq!(sent["request"], "run");
+    assert_eq!(sent["invocation"], "step");
+    assert_eq!(sent["input"], "Check licenses");
+    assert!(sent["task"].is_null());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("cwd.txt")).expect("read cwd"),
+        format!("{}\n", dir.display())
+    );
+
+    let failing = write_plugin(&dir, "failing", "echo 'partial'\nexit 3\n");
+    let mut lines = Vec::new();
+    let err = run(&failing, &request, &Mutex::new(None), &mut |line| {
+        lines.push(line)
+    })
+    .expect_err("non-zero exit");
+    assert_eq!(err, "exited with status code 3");
+    assert_eq!(lines, ["partial"]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
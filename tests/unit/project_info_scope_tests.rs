@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn default_scope_adds_no_prompt_requirements() {
+    assert_eq!(ProjectInfoScope::default().prompt_requirements(), "");
+    assert_eq!(ProjectInfoScope::default().fit_to_budget("# Brief\n"), None);
+}
+
+#[test]
+fn prompt_requirements_cover_depth_directories_budget_and_fast_mode() {
+    let scope = ProjectInfoScope {
+        max_depth: 2,
+        include: vec!["services/api".to_string()],
+        exclude: vec!["vendor".to_string(), "dist".to_string()],
+        max_tokens: 1500,
+        fast: true,
+    };
+    let lines = scope.prompt_requirements();
+    assert!(lines.starts_with("- Fast mode: read only these files"));
+    assert!(lines.contains("`Cargo.toml`, `package.json`"));
+    assert!(lines.contains("more than 2 directory level(s) below the repository root.\n"));
+    assert!(lines.contains("- Only explore these directories: `services/api`\n"));
+    assert!(lines.contains("- Do not explore these directories: `vendor`, `dist`\n"));
+    assert!(
+        lines.ends_with("- Keep the brief under about 1500 tokens; it is cut at that length.\n")
+    );
+}
+
+#[test]
+fn invalid_directory_finds_paths_outside_the_workspace() {
+    let mut scope = ProjectInfoScope {
+        include: vec!["src".to_string()],
+        exclude: vec!["../other".to_string()],
+        ..ProjectInfoScope::default()
+    };
+    assert_eq!(scope.invalid_directory(), Some("../other"));
+    scope.exclude = vec!["target".to_string()];
+    assert_eq!(scope.invalid_directory(), None);
+}
+
+#[test]
+fn fit_to_budget_keeps_whole_lines_and_notes_the_cut() {
+    let scope = ProjectInfoScope {
+        max_tokens: 25,
+        ..ProjectInfoScope::default()
+    };
+    let markdown = "# Project Overview\nA CLI.\n## File Structure\n- src/main.rs: entry point\n- src/app.rs: state\n- src/ui.rs: rendering\n";
+    let trimmed = scope.fit_to_budget(markdown).expect("over budget");
+    assert_eq!(
+        trimmed,
+        "# Project Overview\nA CLI.\n_Trimmed to the project_info.max_tokens budget of 25 tokens._\n"
+    );
+    assert!(estimated_tokens(trimmed.len()) <= 25);
+    assert_eq!(scope.fit_to_budget("# Short\n"), None);
+}
@@ -0,0 +1,81 @@
+use super::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn temp_dir(prefix: &str) -> PathBuf {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!("{prefix}-{now}"))
+}
+
+#[test]
+fn memory_file_is_keyed_by_workspace_path() {
+    let storage = Path::new("/home/dev/.agentbob");
+    let first = memory_file_in(storage, Path::new("/work/app"));
+    assert_eq!(first, memory_file_in(storage, Path::new("/work/app")));
+    assert_ne!(first, memory_file_in(storage, Path::new("/work/other")));
+    assert_eq!(first.parent(), Some(storage.join("memory").as_path()));
+    assert_eq!(first.extension().and_then(|ext| ext.to_str()), Some("md"));
+}
+
+#[test]
+fn extracts_facts_from_every_memory_block() {
+    let reply = "Updated tasks.json.\n\
+                 PROJECT_MEMORY_BEGIN\n\
+                 - The API crate owns all serde types.\n\
+                 \n\
+                 * Run migrations with `make db`.\n\
+                 PROJECT_MEMORY_END\n\
+                 Outside the block.\n\
+                 PROJECT_MEMORY_BEGIN\n\
+                 Never edit generated/ by hand.\n\
+                 PROJECT_MEMORY_END";
+    assert_eq!(
+        extract_memory_facts(reply),
+        vec![
+            "The API crate owns all serde types.",
+            "Run migrations with `make db`.",
+            "Never edit generated/ by hand.",
+        ]
+    );
+    assert!(extract_memory_facts("No block here.").is_empty());
+}
+
+#[test]
+fn append_skips_known_facts_and_prompt_section_lists_them() {
+    let dir = temp_dir("metaagent-project-memory");
+    let path = dir.join("memory").join("abc.md");
+    let workspace = Path::new("/work/app");
+    assert!(memory_prompt_section(&path).contains("(no facts recorded yet)"));
+
+    let facts = vec![
+        "Uses sqlx for queries.".to_string(),
+        "uses   SQLX for queries.".to_string(),
+        "Tests need Docker.".to_string(),
+    ];
+    assert_eq!(
+        append_memory_facts(&path, workspace, &facts).expect("append"),
+        2
+    );
+    assert_eq!(
+        append_memory_facts(&path, workspace, &["Tests need Docker.".to_string()])
+            .expect("append again"),
+        0
+    );
+
+    let text = std::fs::read_to_string(&path).expect("read memory");
+    assert!(text.starts_with("# Project memory\n\nWorkspace: /work/app\n"));
+    assert_eq!(
+        read_memory_facts(&path),
+        Some(vec![
+            "Uses sqlx for queries.".to_string(),
+            "Tests need Docker.".to_string(),
+        ])
+    );
+    let section = memory_prompt_section(&path);
+    assert!(section.contains("- Uses sqlx for queries.\n- Tests need Docker."));
+    assert!(section.contains("PROJECT_MEMORY_BEGIN and PROJECT_MEMORY_END"));
+
+    let _ = std::fs::remove_dir_all(dir);
+}
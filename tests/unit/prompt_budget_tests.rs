@@ -0,0 +1,34 @@
+use super::*;
+
+#[test]
+fn summary_lists_each_section_and_the_unclaimed_remainder() {
+    let prompt = format!("{}{}", "d".repeat(600), "t".repeat(400));
+    let budget = PromptBudget::new(&prompt, vec![PromptSection::new("docs", &prompt[..600])]);
+    assert_eq!(
+        budget.summary_line("implementor pass 2"),
+        "[prompt] implementor pass 2: 1.0k bytes (~250 tokens) | docs 600 (60%) | task 400 (40%)"
+    );
+    assert!(budget.warnings().is_empty());
+}
+
+#[test]
+fn warns_about_truncated_sections_and_sections_dominating_a_large_prompt() {
+    let prompt = "x".repeat(10_000);
+    let budget = PromptBudget::new(
+        &prompt,
+        vec![
+            PromptSection::new("docs", &prompt[..7_000]),
+            PromptSection::new("context", &prompt[..1_000]).truncated("3 older entries dropped"),
+        ],
+    );
+    assert_eq!(
+        budget.warnings(),
+        vec![
+            "[prompt] warning: context section was truncated: 3 older entries dropped",
+            "[prompt] warning: docs section is 70% of the prompt (~1.8k tokens)",
+        ]
+    );
+
+    let small = PromptBudget::new("x", vec![PromptSection::new("docs", "x")]);
+    assert!(small.warnings().is_empty());
+}
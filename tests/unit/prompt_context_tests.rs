@@ -0,0 +1,35 @@
+use super::*;
+
+#[test]
+fn sections_toggle_out_and_back_in() {
+    let mut sections = PromptSections::default();
+    assert!(sections.includes(ContextSection::TaskTree));
+
+    assert!(!sections.toggle(ContextSection::TaskTree));
+    assert!(!sections.includes(ContextSection::TaskTree));
+    assert!(sections.includes(ContextSection::RollingContext));
+
+    assert!(sections.toggle(ContextSection::TaskTree));
+    assert_eq!(sections, PromptSections::default());
+}
+
+#[test]
+fn sections_parse_by_key_and_render_as_checkboxes() {
+    assert_eq!(
+        ContextSection::parse("Rolling"),
+        Some(ContextSection::RollingContext)
+    );
+    assert_eq!(ContextSection::parse("planner.md"), None);
+
+    let mut sections = PromptSections::default();
+    sections.toggle(ContextSection::ProjectInfo);
+    assert_eq!(
+        sections.render_lines(),
+        vec![
+            "[ ] project  project info (project-info.md)",
+            "[x] rolling  rolling task context",
+            "[x] tasks    full task tree",
+            "[x] planner  planner.md (planner mode only)",
+        ]
+    );
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+fn editor(prompt: &str) -> PromptEditor {
+    PromptEditor::new(LastWorkerPrompt {
+        task_id: "T1".to_string(),
+        label: "Implementor pass 2".to_string(),
+        prompt: prompt.to_string(),
+    })
+}
+
+#[test]
+fn edits_at_the_cursor_and_keeps_the_goal_column_across_short_lines() {
+    let mut editor = editor("first line\nab\nthird line");
+    for _ in 0..6 {
+        editor.move_right();
+    }
+    editor.move_down(40);
+    assert_eq!(editor.cursor_line_col(40), (1, 2));
+    editor.move_down(40);
+    assert_eq!(editor.cursor_line_col(40), (2, 6));
+    editor.backspace();
+    editor.insert_text("é!");
+    editor.move_up(40);
+    editor.move_up(40);
+    editor.move_up(40);
+    assert_eq!(editor.cursor_line_col(40), (0, 7));
+    editor.move_left();
+    editor.insert_text("\n");
+    assert_eq!(editor.text(), "first \nline\nab\nthirdé!line");
+    let (task_id, prompt) = editor.into_parts();
+    assert_eq!((task_id.as_str(), prompt.len()), ("T1", 27));
+}
+
+#[test]
+fn scroll_follows_the_cursor_off_either_edge() {
+    let mut editor = editor("1\n2\n3\n4\n5\n6");
+    for _ in 0..5 {
+        editor.move_down(10);
+        editor.ensure_cursor_visible(10, 3);
+    }
+    assert_eq!((editor.cursor_line_col(10).0, editor.scroll()), (5, 3));
+    for _ in 0..4 {
+        editor.move_up(10);
+        editor.ensure_cursor_visible(10, 3);
+    }
+    assert_eq!((editor.cursor_line_col(10).0, editor.scroll()), (1, 1));
+    assert_eq!(editor.label(), "Implementor pass 2");
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+fn temp_dir(prefix: &str) -> PathBuf {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    std::env::temp_dir().join(format!("{prefix}-{}-{now}", std::process::id()))
+}
+
+fn snapshot(name: &str, prompt: &str) -> PromptSnapshot {
+    PromptSnapshot {
+        name: name.to_string(),
+        prompt: prompt.to_string(),
+    }
+}
+
+#[test]
+fn rendering_covers_subagents_and_every_agent_backed_fixture_task() {
+    let snapshots = render_prompt_snapshots(&builtin_fixtures()).expect("fixtures should render");
+    let names = snapshots
+        .iter()
+        .map(|snapshot| snapshot.name.as_str())
+        .collect::<Vec<_>>();
+    for expected in [
+        "subagents/task-check",
+        "export/tests-on/master",
+        "export/tests-on/export-impl",
+        "export/tests-on/export-tests",
+        "export/tests-off/export-tests-audit",
+        "export/tests-off/final-audit",
+    ] {
+        assert!(names.contains(&expected), "missing {expected}");
+    }
+    assert!(!names.iter().any(|name| name.ends_with("/export-tests-run")));
+    let implementor = snapshots
+        .iter()
+        .find(|snapshot| snapshot.name == "export/tests-on/export-impl")
+        .unwrap();
+    assert!(implementor.prompt.contains("https://docs.rs/csv"));
+
+    let mut invalid = builtin_fixtures();
+    invalid[0].tasks[1].parent_id = Some("missing".to_string());
+    let err = render_prompt_snapshots(&invalid).expect_err("invalid fixture");
+    assert!(err.contains("Fixture `export`"));
+}
+
+#[test]
+fn golden_comparison_reports_drift_and_update_rewrites_files() {
+    let dir = temp_dir("bob-prompt-golden");
+    fs::create_dir_all(dir.join("old")).unwrap();
+    fs::write(dir.join("same.txt"), "line one\nline two").unwrap();
+    fs::write(dir.join("edited.txt"), "keep\nold wording\nkeep too").unwrap();
+    fs::write(dir.join("old/gone.txt"), "removed prompt").unwrap();
+    let snapshots = [
+        snapshot("same", "line one\nline two"),
+        snapshot("edited", "keep\nnew wording\nkeep too"),
+        snapshot("nested/added", "brand new"),
+    ];
+
+    let report = compare_with_golden(&snapshots, &dir, false).expect("compare");
+    assert!(!report.is_clean());
+    assert_eq!(report.matched, vec!["same"]);
+    assert_eq!(
+        report.changed,
+        vec![SnapshotDiff {
+            name: "edited".to_string(),
+            lines: vec!["+new wording".to_string(), "-old wording".to_string()],
+        }]
+    );
+    assert_eq!(report.missing, vec!["nested/added"]);
+    assert_eq!(report.stale, vec!["old/gone"]);
+    assert!(dir.join("old/gone.txt").exists());
+
+    let report = compare_with_golden(&snapshots, &dir, true).expect("update");
+    assert!(report.is_clean());
+    assert_eq!(
+        fs::read_to_string(dir.join("nested/added.txt")).unwrap(),
+        "brand new"
+    );
+    assert!(!dir.join("old/gone.txt").exists());
+    let report = compare_with_golden(&snapshots, &dir, false).expect("recompare");
+    assert!(report.is_clean());
+    assert_eq!(report.matched.len(), 3);
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn fixtures_load_from_json_task_files_named_by_stem() {
+    let dir = temp_dir("bob-prompt-fixtures");
+    fs::create_dir_all(&dir).unwrap();
+    let tasks = serde_json::to_string(&builtin_fixtures()[0].tasks).unwrap();
+    fs::write(dir.join("b-export.json"), &tasks).unwrap();
+    fs::write(dir.join("a-copy.json"), &tasks).unwrap();
+    fs::write(dir.join("notes.md"), "ignored").unwrap();
+
+    let fixtures = load_fixtures(&dir).expect("load");
+    assert_eq!(
+        fixtures
+            .iter()
+            .map(|fixture| fixture.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["a-copy", "b-export"]
+    );
+    fs::write(dir.join("c-broken.json"), "{").unwrap();
+    assert_eq!(
+        load_fixtures(&dir).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
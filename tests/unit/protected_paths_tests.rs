@@ -0,0 +1,72 @@
+use super::*;
+
+fn protected(patterns: &[&str]) -> ProtectedPaths {
+    ProtectedPaths::new(patterns.iter().map(|pattern| pattern.to_string()))
+}
+
+fn violating_paths(protected: &ProtectedPaths, paths: &[&str]) -> Vec<String> {
+    protected
+        .violations(paths.iter().copied())
+        .into_iter()
+        .map(|violation| violation.path)
+        .collect()
+}
+
+#[test]
+fn double_star_spans_directories_and_single_star_stays_in_one() {
+    let protected = protected(&["migrations/**", "deploy/*.yml"]);
+    assert_eq!(
+        violating_paths(
+            &protected,
+            &[
+                "migrations/001_init.sql",
+                "migrations/2026/002_users.sql",
+                "src/migrations/readme.md",
+                "deploy/prod.yml",
+                "deploy/k8s/prod.yml",
+            ],
+        ),
+        [
+            "migrations/001_init.sql",
+            "migrations/2026/002_users.sql",
+            "deploy/prod.yml"
+        ]
+    );
+}
+
+#[test]
+fn patterns_without_a_slash_match_names_at_any_depth() {
+    let protected = protected(&["*.lock", "secrets"]);
+    assert_eq!(
+        violating_paths(
+            &protected,
+            &[
+                "Cargo.lock",
+                "web/yarn.lock",
+                "config/secrets/key.pem",
+                "src/lock.rs"
+            ],
+        ),
+        ["Cargo.lock", "web/yarn.lock", "config/secrets/key.pem"]
+    );
+}
+
+#[test]
+fn violations_name_the_pattern_and_skip_duplicate_paths() {
+    let protected = protected(&[" ./deploy/ ", "", "?.env"]);
+    assert_eq!(protected.patterns(), ["deploy/**", "?.env"]);
+    assert_eq!(
+        protected.violations(["./deploy/run.sh", "deploy/run.sh", "a.env", "ab.env"]),
+        [
+            Violation {
+                path: "deploy/run.sh".to_string(),
+                pattern: "deploy/**".to_string(),
+            },
+            Violation {
+                path: "a.env".to_string(),
+                pattern: "?.env".to_string(),
+            },
+        ]
+    );
+    assert!(ProtectedPaths::new(Vec::new()).is_empty());
+}
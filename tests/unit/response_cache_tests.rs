@@ -0,0 +1,84 @@
+use super::*;
+
+fn temp_cache_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "agentbob-response-cache-{name}-{}",
+        std::process::id()
+    ))
+}
+
+#[test]
+fn keys_depend_on_the_call_and_every_input() {
+    let key = ResponseCache::key(CachedCall::TaskCheck, &["prompt", "tasks"]);
+    assert_eq!(key.len(), 64);
+    assert_eq!(
+        key,
+        ResponseCache::key(CachedCall::TaskCheck, &["prompt", "tasks"])
+    );
+    assert_ne!(
+        key,
+        ResponseCache::key(CachedCall::DocsAttach, &["prompt", "tasks"])
+    );
+    assert_ne!(
+        key,
+        ResponseCache::key(CachedCall::TaskCheck, &["prompt", "tasks!"])
+    );
+    assert_ne!(
+        ResponseCache::key(CachedCall::TaskCheck, &["ab", "c"]),
+        ResponseCache::key(CachedCall::TaskCheck, &["a", "bc"])
+    );
+}
+
+#[test]
+fn a_completed_miss_is_served_on_the_next_lookup_until_purged() {
+    let dir = temp_cache_dir("roundtrip");
+    let _ = fs::remove_dir_all(&dir);
+    let mut cache = ResponseCache::new(dir.clone());
+    let key = ResponseCache::key(CachedCall::TaskCheck, &["tasks.json v1"]);
+
+    assert_eq!(cache.lookup(CachedCall::TaskCheck, key.clone()), None);
+    cache
+        .complete(CachedCall::TaskCheck, Some("fixed tasks"))
+        .expect("store response");
+    assert_eq!(
+        cache.lookup(CachedCall::TaskCheck, key.clone()),
+        Some("fixed tasks".to_string())
+    );
+    // The same key under another call is not a hit.
+    assert_eq!(cache.lookup(CachedCall::DocsAttach, key.clone()), None);
+    cache
+        .complete(CachedCall::DocsAttach, None)
+        .expect("failed run stores nothing");
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+    assert!(stats.bytes > 0);
+
+    assert_eq!(cache.purge().expect("purge"), 1);
+    assert_eq!(cache.stats().entries, 0);
+    assert_eq!(cache.lookup(CachedCall::TaskCheck, key), None);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn forgotten_or_disabled_calls_store_nothing() {
+    let dir = temp_cache_dir("forget");
+    let _ = fs::remove_dir_all(&dir);
+    let mut cache = ResponseCache::new(dir.clone());
+    let key = ResponseCache::key(CachedCall::DocsAttach, &["snapshot run"]);
+    assert_eq!(cache.lookup(CachedCall::DocsAttach, key), None);
+    cache.forget(CachedCall::DocsAttach);
+    cache
+        .complete(CachedCall::DocsAttach, Some("docs"))
+        .expect("nothing pending");
+    assert_eq!(cache.stats().entries, 0);
+
+    let mut disabled = ResponseCache::default();
+    assert_eq!(
+        disabled.lookup(CachedCall::TaskCheck, "key".to_string()),
+        None
+    );
+    assert_eq!(disabled.stats(), CacheStats::default());
+    assert_eq!(disabled.purge().expect("purge"), 0);
+    let _ = fs::remove_dir_all(&dir);
+}
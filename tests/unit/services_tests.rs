@@ -30,6 +30,7 @@ fn seed_simple_plan(app: &mut App) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -40,6 +41,7 @@ fn seed_simple_plan(app: &mut App) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -50,6 +52,7 @@ fn seed_simple_plan(app: &mut App) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -137,6 +140,9 @@ fn dispatch_agent_prompt_preserves_prior_worker_session_id() {
         role: WorkerRole::Implementor,
         top_task_id: 1,
         parent_context_key: Some("implementor:1".to_string()),
+        model_profile: None,
+        escalation: 0,
+        workdir: None,
     };
 
     service.dispatch_worker_job(
@@ -176,6 +182,9 @@ fn dispatch_agent_prompt_keeps_existing_context_and_creates_new_context_for_futu
         role: WorkerRole::Implementor,
         top_task_id: 1,
         parent_context_key: Some("implementor:1".to_string()),
+        model_profile: None,
+        escalation: 0,
+        workdir: None,
     };
     service.dispatch_worker_job(
         &first_job,
@@ -194,6 +203,9 @@ fn dispatch_agent_prompt_keeps_existing_context_and_creates_new_context_for_futu
         role: WorkerRole::Implementor,
         top_task_id: 2,
         parent_context_key: Some("implementor:2".to_string()),
+        model_profile: None,
+        escalation: 0,
+        workdir: None,
     };
     service.dispatch_worker_job(
         &second_job,
@@ -244,6 +256,9 @@ fn dispatch_agent_prompt_does_not_replace_in_flight_context_adapter_after_backen
         role: WorkerRole::Implementor,
         top_task_id: 1,
         parent_context_key: Some("implementor:1".to_string()),
+        model_profile: None,
+        escalation: 0,
+        workdir: None,
     };
     service.dispatch_worker_job(
         &job,
@@ -271,7 +286,7 @@ fn dispatch_agent_prompt_does_not_replace_in_flight_context_adapter_after_backen
 #[test]
 fn build_worker_adapter_for_codex_keeps_plain_text_persistent_behavior() {
     let routing = CodexAgentModelRouting::default();
-    let adapter = build_worker_adapter(&routing, WorkerRole::Implementor);
+    let adapter = build_worker_adapter(&routing, WorkerRole::Implementor, None, None);
     let config = adapter.config_snapshot();
 
     assert_eq!(config.backend_kind(), BackendKind::Codex);
@@ -284,7 +299,7 @@ fn build_worker_adapter_for_codex_keeps_plain_text_persistent_behavior() {
 fn build_worker_adapter_for_claude_uses_json_persistent_mode_for_resumption() {
     let routing = CodexAgentModelRouting::from_toml_str("[backend]\nselected = \"claude\"\n")
         .unwrap_or_default();
-    let adapter = build_worker_adapter(&routing, WorkerRole::Implementor);
+    let adapter = build_worker_adapter(&routing, WorkerRole::Implementor, None, None);
     let config = adapter.config_snapshot();
 
     assert_eq!(config.backend_kind(), BackendKind::Claude);
@@ -293,6 +308,21 @@ fn build_worker_adapter_for_claude_uses_json_persistent_mode_for_resumption() {
     assert!(config.skip_reader_join_after_wait);
 }
 
+#[test]
+fn build_worker_adapter_prefers_the_task_workdir_over_the_agent_kind_one() {
+    let routing = CodexAgentModelRouting::from_toml_str(
+        "[codex.agent_workdirs]\nworker_implementor = \"backend\"\n",
+    )
+    .expect("routing");
+
+    let by_kind = build_worker_adapter(&routing, WorkerRole::Implementor, None, None);
+    assert_eq!(by_kind.workdir(), Some(std::path::Path::new("backend")));
+    let by_task = build_worker_adapter(&routing, WorkerRole::Implementor, None, Some("api"));
+    assert_eq!(by_task.workdir(), Some(std::path::Path::new("api")));
+    let auditor = build_worker_adapter(&routing, WorkerRole::Auditor, None, None);
+    assert_eq!(auditor.workdir(), None);
+}
+
 #[test]
 fn dispatch_deterministic_test_run_uses_trimmed_meta_test_command() {
     let service = DefaultCoreOrchestrationService;
@@ -312,6 +342,9 @@ fn dispatch_deterministic_test_run_uses_trimmed_meta_test_command() {
         role: WorkerRole::TestRunner,
         top_task_id: 1,
         parent_context_key: Some("test_writer:1".to_string()),
+        model_profile: None,
+        escalation: 0,
+        workdir: None,
     };
 
     service.dispatch_worker_job(
@@ -452,3 +485,48 @@ fn normalize_test_command_trims_or_drops_empty_values() {
     assert!(normalize_test_command(Some("   ".to_string())).is_none());
     assert!(normalize_test_command(None).is_none());
 }
+
+#[test]
+fn backend_failover_tracker_fires_once_per_streak_until_rearmed() {
+    let mut tracker = BackendFailoverTracker::new(2);
+    assert_eq!(tracker.record_worker_result(false), None);
+    assert_eq!(tracker.record_worker_result(true), None);
+    assert_eq!(tracker.record_worker_result(false), None);
+    assert_eq!(tracker.record_worker_result(false), Some(2));
+    // The fallback backend failing too must not bounce the run back and forth.
+    assert_eq!(tracker.record_worker_result(false), None);
+    assert_eq!(tracker.record_worker_result(false), None);
+
+    tracker.rearm();
+    assert_eq!(tracker.record_worker_result(false), None);
+    assert_eq!(tracker.record_worker_result(false), Some(2));
+
+    let mut disabled = BackendFailoverTracker::new(0);
+    assert!((0..5).all(|_| disabled.record_worker_result(false).is_none()));
+}
+
+#[test]
+fn transient_retry_queue_doubles_the_delay_until_retries_run_out() {
+    let now = std::time::Instant::now();
+    let mut queue = TransientRetryQueue::default();
+    assert_eq!(queue.next_due(), None);
+
+    let delays = (0..TransientRetryQueue::MAX_RETRIES)
+        .map(|_| {
+            let scheduled = queue.schedule("top:1".to_string(), now);
+            assert_eq!(queue.take_due(now), None);
+            let delay = scheduled.expect("retry left").1;
+            assert_eq!(queue.take_due(now + delay).as_deref(), Some("top:1"));
+            delay.as_secs()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(delays, [2, 4, 8, 16]);
+
+    assert_eq!(queue.schedule("top:1".to_string(), now), None);
+    assert_eq!(
+        queue.schedule("top:1".to_string(), now),
+        Some((1, Duration::from_secs(2)))
+    );
+    queue.reset();
+    assert_eq!(queue.next_due(), None);
+}
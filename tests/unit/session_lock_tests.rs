@@ -0,0 +1,166 @@
+use super::*;
+
+fn temp_dir(prefix: &str) -> PathBuf {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("{prefix}-{}-{now}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+fn owner_with_pid(pid: u32) -> LockOwner {
+    LockOwner {
+        pid,
+        ..LockOwner::current()
+    }
+}
+
+#[test]
+fn missing_unreadable_and_own_locks_leave_the_session_free() {
+    let dir = temp_dir("session-lock-free");
+    assert_eq!(inspect(&dir), LockState::Free);
+
+    std::fs::write(lock_file(&dir), "not json").expect("write lock");
+    assert_eq!(inspect(&dir), LockState::Free);
+
+    write_owner(&dir, &LockOwner::current()).expect("write lock");
+    assert_eq!(inspect(&dir), LockState::Free);
+}
+
+#[cfg(unix)]
+#[test]
+fn a_running_owner_holds_the_lock_until_its_process_exits() {
+    let dir = temp_dir("session-lock-held");
+    let mut child = Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .expect("spawn sleep");
+    let owner = owner_with_pid(child.id());
+    write_owner(&dir, &owner).expect("write lock");
+    assert_eq!(owner.is_running(), Some(true));
+    assert_eq!(inspect(&dir), LockState::Held(owner.clone()));
+
+    child.kill().expect("kill sleep");
+    child.wait().expect("reap sleep");
+    assert_eq!(inspect(&dir), LockState::Stale(owner));
+}
+
+#[cfg(unix)]
+#[test]
+fn acquire_creates_the_lock_but_only_replaces_a_stale_one() {
+    let dir = temp_dir("session-lock-acquire");
+    assert_eq!(acquire(&dir).expect("acquire"), LockState::Free);
+    assert!(read_owner(&dir).is_some_and(|owner| owner.is_current_process()));
+    assert_eq!(acquire(&dir).expect("acquire again"), LockState::Free);
+
+    let mut child = Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .expect("spawn sleep");
+    let owner = owner_with_pid(child.id());
+    write_owner(&dir, &owner).expect("write lock");
+    assert_eq!(
+        acquire(&dir).expect("acquire held"),
+        LockState::Held(owner.clone())
+    );
+    assert_eq!(read_owner(&dir), Some(owner.clone()));
+
+    child.kill().expect("kill sleep");
+    child.wait().expect("reap sleep");
+    assert_eq!(
+        acquire(&dir).expect("acquire stale"),
+        LockState::Stale(owner)
+    );
+    assert!(read_owner(&dir).is_some_and(|owner| owner.is_current_process()));
+
+    std::fs::write(lock_file(&dir), "not json").expect("write lock");
+    assert_eq!(acquire(&dir).expect("acquire unreadable"), LockState::Free);
+    assert!(read_owner(&dir).is_some_and(|owner| owner.is_current_process()));
+}
+
+#[cfg(unix)]
+#[test]
+fn only_one_of_two_instances_replaces_the_same_stale_lock() {
+    let spawn_sleep = || {
+        Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep")
+    };
+    let mut exited = spawn_sleep();
+    let stale = owner_with_pid(exited.id());
+    exited.kill().expect("kill sleep");
+    exited.wait().expect("reap sleep");
+    let mut children = [spawn_sleep(), spawn_sleep()];
+    let instances = children
+        .iter()
+        .map(|child| owner_with_pid(child.id()))
+        .collect::<Vec<_>>();
+
+    for round in 0..200 {
+        let dir = temp_dir(&format!("session-lock-race-{round}"));
+        write_owner(&dir, &stale).expect("write stale lock");
+        let barrier = std::sync::Barrier::new(instances.len());
+        let results = std::thread::scope(|scope| {
+            let handles = instances
+                .iter()
+                .map(|me| {
+                    let (dir, barrier) = (&dir, &barrier);
+                    scope.spawn(move || {
+                        barrier.wait();
+                        acquire_as(dir, me)
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("join"))
+                .collect::<Vec<_>>()
+        });
+
+        let winners = instances
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| matches!(result, Ok(LockState::Free) | Ok(LockState::Stale(_))))
+            .map(|(me, _)| me.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(winners.len(), 1, "round {round}: {results:?}");
+        assert_eq!(read_owner(&dir), Some(winners[0].clone()), "round {round}");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    for child in &mut children {
+        child.kill().expect("kill sleep");
+        child.wait().expect("reap sleep");
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn processes_this_user_cannot_signal_still_count_as_running() {
+    assert!(process_is_running(1));
+}
+
+#[test]
+fn owners_on_another_host_are_never_treated_as_stale() {
+    let dir = temp_dir("session-lock-remote");
+    let owner = LockOwner {
+        pid: 1,
+        host: format!("{}-elsewhere", current_host()),
+        acquired_at_epoch_secs: 1_771_243_200,
+    };
+    write_owner(&dir, &owner).expect("write lock");
+    assert_eq!(owner.is_running(), None);
+    assert_eq!(inspect(&dir), LockState::Held(owner.clone()));
+    assert!(owner.describe().starts_with("pid 1 on "));
+}
+
+#[test]
+fn lock_actions_parse_case_insensitively() {
+    assert_eq!(LockAction::parse(" Takeover "), Some(LockAction::Takeover));
+    assert_eq!(LockAction::parse("observe"), Some(LockAction::Observe));
+    assert_eq!(LockAction::parse(""), None);
+    assert_eq!(LockAction::parse("steal"), None);
+}
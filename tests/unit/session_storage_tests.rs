@@ -0,0 +1,191 @@
+use std::sync::{Arc, Mutex};
+
+use super::*;
+
+fn temp_dir(prefix: &str) -> PathBuf {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("{prefix}-{}-{now}", std::process::id()));
+    fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+fn remote_config() -> RemoteStorageConfig {
+    RemoteStorageConfig {
+        endpoint: "https://storage.example.com/".to_string(),
+        bucket: "team-bob".to_string(),
+        prefix: "/dev/sessions/".to_string(),
+        ..RemoteStorageConfig::default()
+    }
+}
+
+fn recording_remote(responses: Vec<&str>) -> (RemoteObjectStorage, Arc<Mutex<Vec<ObjectRequest>>>) {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+    let responses = Mutex::new(
+        responses
+            .into_iter()
+            .map(|body| body.as_bytes().to_vec())
+            .collect::<Vec<_>>(),
+    );
+    let remote = RemoteObjectStorage::with_runner(
+        remote_config(),
+        Box::new(move |request| {
+            recorded.lock().unwrap().push(request.clone());
+            Ok(responses.lock().unwrap().remove(0))
+        }),
+    );
+    (remote, requests)
+}
+
+#[test]
+fn copy_session_mirrors_nested_files_but_not_the_lock() {
+    let source = LocalDiskStorage::new(temp_dir("bob-storage-from"));
+    let target = LocalDiskStorage::new(temp_dir("bob-storage-to"));
+    source.write_file("1-app", "tasks.json", b"[]\n").unwrap();
+    source
+        .write_file("1-app", "logs/t1/implementor-pass1.log", b"ok")
+        .unwrap();
+    source
+        .write_file("1-app", LOCK_FILE_NAME, b"{\"pid\":1}")
+        .unwrap();
+
+    assert_eq!(copy_session(&source, &target, "1-app").unwrap(), 2);
+    assert_eq!(target.list_sessions().unwrap(), vec!["1-app"]);
+    assert_eq!(
+        target.list_files("1-app").unwrap(),
+        vec!["logs/t1/implementor-pass1.log", "tasks.json"]
+    );
+    assert_eq!(
+        copy_session(&source, &target, "2-missing")
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::NotFound
+    );
+    for bad in ["../escape", "a//b", "/etc/passwd", "a\\b"] {
+        assert!(target.write_file("1-app", bad, b"x").is_err(), "{bad}");
+    }
+    assert!(target.list_files("..").is_err());
+
+    target
+        .replace_file("1-app", "tasks.json", b"[{}]\n")
+        .unwrap();
+    assert_eq!(target.read_file("1-app", "tasks.json").unwrap(), b"[{}]\n");
+    target.remove_file("1-app", "tasks.json").unwrap();
+    target.remove_file("1-app", "tasks.json").unwrap();
+    assert_eq!(
+        target.list_files("1-app").unwrap(),
+        vec!["logs/t1/implementor-pass1.log"]
+    );
+}
+
+#[test]
+fn remote_lists_sessions_across_pages_and_strips_the_prefix() {
+    let (remote, requests) = recording_remote(vec![
+        "<ListBucketResult><Prefix>dev/sessions/</Prefix><CommonPrefixes><Prefix>dev/sessions/2-api/</Prefix></CommonPrefixes><IsTruncated>true</IsTruncated><NextContinuationToken>a+b=</NextContinuationToken></ListBucketResult>",
+        "<ListBucketResult><CommonPrefixes><Prefix>dev/sessions/1-a&amp;b/</Prefix></CommonPrefixes><IsTruncated>false</IsTruncated></ListBucketResult>",
+    ]);
+
+    assert_eq!(remote.describe(), "s3://team-bob/dev/sessions");
+    assert_eq!(remote.list_sessions().unwrap(), vec!["1-a&b", "2-api"]);
+    let urls = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|request| request.url.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        urls,
+        vec![
+            "https://storage.example.com/team-bob?list-type=2&prefix=dev%2Fsessions%2F&delimiter=%2F",
+            "https://storage.example.com/team-bob?list-type=2&prefix=dev%2Fsessions%2F&delimiter=%2F&continuation-token=a%2Bb%3D",
+        ]
+    );
+}
+
+#[test]
+fn remote_round_trips_session_files_as_objects() {
+    let (remote, requests) = recording_remote(vec![
+        "",
+        "<ListBucketResult><Contents><Key>dev/sessions/1-app/logs/a b.log</Key></Contents><Contents><Key>dev/sessions/1-app/../x</Key></Contents></ListBucketResult>",
+        "log text",
+        "",
+        "",
+    ]);
+
+    remote
+        .write_file("1-app", "logs/a b.log", b"log text")
+        .unwrap();
+    assert_eq!(remote.list_files("1-app").unwrap(), vec!["logs/a b.log"]);
+    assert_eq!(
+        remote.read_file("1-app", "logs/a b.log").unwrap(),
+        b"log text"
+    );
+    remote.remove_file("1-app", "logs/a b.log").unwrap();
+    remote.replace_file("1-app", "tasks.json", b"[]").unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0].method, "PUT");
+    assert_eq!(
+        requests[0].url,
+        "https://storage.example.com/team-bob/dev/sessions/1-app/logs/a%20b.log"
+    );
+    assert_eq!(requests[0].body.as_deref(), Some(&b"log text"[..]));
+    assert_eq!(requests[2].method, "GET");
+    assert_eq!(requests[2].url, requests[0].url);
+    assert_eq!(requests[2].body, None);
+    assert_eq!(requests[3].method, "DELETE");
+    assert_eq!(requests[3].url, requests[0].url);
+    assert_eq!(requests[4].method, "PUT");
+    assert_eq!(requests[4].body.as_deref(), Some(&b"[]"[..]));
+}
+
+#[test]
+fn remote_debug_output_leaves_out_the_keys() {
+    let remote = RemoteObjectStorage::with_runner(
+        RemoteStorageConfig {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI".to_string(),
+            ..remote_config()
+        },
+        Box::new(|_| Ok(Vec::new())),
+    );
+
+    let debug = format!("{remote:?}");
+    assert!(debug.contains("s3://team-bob/dev/sessions"), "{debug}");
+    assert!(!debug.contains("AKIDEXAMPLE") && !debug.contains("wJalrXUtnFEMI"));
+}
+
+#[cfg(unix)]
+#[test]
+fn upload_bodies_go_to_an_owner_only_file_in_a_fresh_directory() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let first = write_upload_file(b"payload").unwrap();
+    let second = write_upload_file(b"payload").unwrap();
+    let file = first.join(UPLOAD_FILE_NAME);
+
+    assert_ne!(first, second);
+    assert_eq!(fs::read(&file).unwrap(), b"payload");
+    let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode(&first), 0o700);
+    assert_eq!(mode(&file), 0o600);
+    fs::remove_dir_all(first).unwrap();
+    fs::remove_dir_all(second).unwrap();
+}
+
+#[test]
+fn remote_storage_is_off_in_the_default_config() {
+    let parsed: crate::session_store::MetaAgentConfig =
+        toml::from_str(crate::default_config::DEFAULT_CONFIG_TOML).expect("default config");
+    assert_eq!(parsed.storage.remote, RemoteStorageConfig::default());
+    assert!(!parsed.storage.remote.is_enabled());
+    assert!(
+        toml::from_str::<crate::session_store::MetaAgentConfig>(
+            "[storage.remote]\nendpoint = \"https://s3.example.com\"\nbukcet = \"typo\"\n"
+        )
+        .is_err()
+    );
+}
@@ -52,6 +52,7 @@ fn embedded_default_config_includes_storage_section() {
     let parsed: MetaAgentConfig = toml::from_str(crate::default_config::DEFAULT_CONFIG_TOML)
         .expect("embedded default config should parse");
     assert_eq!(parsed.storage.root_dir, "~/.agentbob/sessions");
+    assert!(parsed.storage.offer_recent_session);
 }
 
 #[test]
@@ -82,6 +83,17 @@ fn metaagent_config_storage_falls_back_when_storage_section_missing() {
     )
     .expect("partial config should parse");
     assert_eq!(parsed.storage.root_dir, "~/.agentbob/sessions");
+    assert!(parsed.storage.offer_recent_session);
+
+    let parsed: MetaAgentConfig = toml::from_str(
+        r#"
+        [storage]
+        offer_recent_session = false
+        "#,
+    )
+    .expect("storage flag should parse");
+    assert_eq!(parsed.storage.root_dir, "~/.agentbob/sessions");
+    assert!(!parsed.storage.offer_recent_session);
 }
 
 #[test]
@@ -135,6 +147,382 @@ fn tests_mode_load_parses_explicit_enabled_flag() {
     );
 }
 
+#[test]
+fn final_audit_policy_parses_workflow_section_and_defaults_to_manual() {
+    assert_eq!(
+        final_audit_policy_from_toml("").expect("empty config should default"),
+        FinalAuditPolicy::Manual
+    );
+    assert_eq!(
+        final_audit_policy_from_toml("[workflow]\nfinal_audit = \"auto\"\n")
+            .expect("auto should parse"),
+        FinalAuditPolicy::Auto
+    );
+    assert_eq!(
+        final_audit_policy_from_toml("[workflow]\nfinal_audit = \"Never\"\n")
+            .expect("never should parse case-insensitively"),
+        FinalAuditPolicy::Never
+    );
+    let err = final_audit_policy_from_toml("[workflow]\nfinal_audit = \"sometimes\"\n")
+        .expect_err("unknown policy should fail");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let embedded = final_audit_policy_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+        .expect("embedded default config should parse");
+    assert_eq!(embedded, FinalAuditPolicy::Manual);
+}
+
+#[test]
+fn task_split_limits_parse_workflow_section_and_reject_negative_values() {
+    assert_eq!(
+        task_split_limits_from_toml("").expect("empty config should default"),
+        TaskSplitLimits::default()
+    );
+    assert_eq!(
+        task_split_limits_from_toml(
+            "[workflow]\ntask_split_max_details_chars = 0\ntask_split_max_subtasks = 4\n"
+        )
+        .expect("overrides should parse"),
+        TaskSplitLimits {
+            max_details_chars: 0,
+            max_subtasks: 4,
+        }
+    );
+    let err = task_split_limits_from_toml("[workflow]\ntask_split_max_subtasks = -1\n")
+        .expect_err("negative limit should fail");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("workflow.task_split_max_subtasks"));
+
+    let embedded = task_split_limits_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+        .expect("embedded default config should parse");
+    assert_eq!(embedded, TaskSplitLimits::default());
+}
+
+#[test]
+fn stall_after_defaults_to_five_minutes_and_zero_disables_it() {
+    let five_minutes = Some(std::time::Duration::from_secs(300));
+    assert_eq!(stall_after_from_toml("").expect("defaults"), five_minutes);
+    assert_eq!(
+        stall_after_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+            .expect("embedded default config should parse"),
+        five_minutes
+    );
+    assert_eq!(
+        stall_after_from_toml("[workflow]\nstall_after_secs = 0\n").expect("disabled"),
+        None
+    );
+    let err = stall_after_from_toml("[workflow]\nstall_after_secs = \"soon\"\n")
+        .expect_err("non-integer should fail");
+    assert!(err.to_string().contains("workflow.stall_after_secs"));
+}
+
+#[test]
+fn rerun_failed_tests_defaults_off_and_rejects_non_booleans() {
+    assert!(!rerun_failed_tests_from_toml("").expect("empty config should default"));
+    assert!(
+        rerun_failed_tests_from_toml("[workflow]\nrerun_failed_tests = true\n")
+            .expect("override should parse")
+    );
+    let err = rerun_failed_tests_from_toml("[workflow]\nrerun_failed_tests = \"yes\"\n")
+        .expect_err("string should fail");
+    assert!(err.to_string().contains("workflow.rerun_failed_tests"));
+    assert!(
+        !rerun_failed_tests_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+            .expect("embedded default config should parse")
+    );
+}
+
+#[test]
+fn docs_update_task_defaults_off_and_rejects_non_booleans() {
+    assert!(
+        !docs_update_task_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+            .expect("embedded default config should parse")
+    );
+    assert!(
+        docs_update_task_from_toml("[workflow]\ndocs_update_task = true\n")
+            .expect("override should parse")
+    );
+    let err = docs_update_task_from_toml("[workflow]\ndocs_update_task = 1\n")
+        .expect_err("integer should fail");
+    assert!(err.to_string().contains("workflow.docs_update_task"));
+}
+
+#[test]
+fn protected_paths_parse_a_list_of_globs() {
+    assert!(
+        protected_paths_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+            .expect("embedded default config should parse")
+            .is_empty()
+    );
+    assert_eq!(
+        protected_paths_from_toml(
+            "[workflow]\nprotected_paths = [\"migrations/**\", \"*.lock\"]\n"
+        )
+        .expect("list should parse")
+        .patterns(),
+        ["migrations/**", "*.lock"]
+    );
+    let err = protected_paths_from_toml("[workflow]\nprotected_paths = \"*.lock\"\n")
+        .expect_err("bare string should fail");
+    assert!(err.to_string().contains("workflow.protected_paths"));
+}
+
+#[test]
+fn verify_gate_parses_command_and_retry_budget() {
+    assert_eq!(
+        verify_gate_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+            .expect("embedded default config should parse"),
+        VerifyGate::default()
+    );
+    assert_eq!(
+        verify_gate_from_toml("[workflow]\nverify = \" make e2e \"\nverify_retries = 1\n")
+            .expect("override should parse"),
+        VerifyGate {
+            command: Some("make e2e".to_string()),
+            max_retries: 1,
+        }
+    );
+    let err =
+        verify_gate_from_toml("[workflow]\nverify = [\"make\"]\n").expect_err("list should fail");
+    assert!(
+        err.to_string()
+            .contains("workflow.verify must be a command string")
+    );
+    let err = verify_gate_from_toml("[workflow]\nverify_retries = 300\n")
+        .expect_err("retries over 255 should fail");
+    assert!(err.to_string().contains("workflow.verify_retries"));
+}
+#[test]
+fn project_info_scope_parses_section_and_rejects_outside_directories() {
+    assert_eq!(
+        project_info_scope_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+            .expect("embedded default config should parse"),
+        ProjectInfoScope::default()
+    );
+    assert_eq!(
+        project_info_scope_from_toml(
+            "[project_info]\nmax_depth = 3\nexclude = [\"vendor\"]\nmax_tokens = 2000\nfast = true\n"
+        )
+        .expect("override should parse"),
+        ProjectInfoScope {
+            max_depth: 3,
+            include: Vec::new(),
+            exclude: vec!["vendor".to_string()],
+            max_tokens: 2000,
+            fast: true,
+        }
+    );
+    let err = project_info_scope_from_toml("[project_info]\ninclude = [\"/etc\"]\n")
+        .expect_err("absolute path should fail");
+    assert!(err.to_string().contains("(got \"/etc\")"));
+    let err = project_info_scope_from_toml("[project_info]\ndepth = 3\n")
+        .expect_err("unknown key should fail");
+    assert!(err.to_string().contains("invalid [project_info]"));
+}
+#[test]
+fn code_context_policy_parses_workflow_section_and_defaults() {
+    assert_eq!(
+        code_context_policy_from_toml("").expect("empty config should default"),
+        CodeContextPolicy::default()
+    );
+    assert_eq!(
+        code_context_policy_from_toml("[workflow]\ncode_context_files = 0\n")
+            .expect("overrides should parse"),
+        CodeContextPolicy {
+            max_files: 0,
+            max_excerpt_lines: 40,
+        }
+    );
+    let err = code_context_policy_from_toml("[workflow]\ncode_context_excerpt_lines = -5\n")
+        .expect_err("negative limit should fail");
+    assert!(
+        err.to_string()
+            .contains("workflow.code_context_excerpt_lines")
+    );
+
+    let embedded = code_context_policy_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+        .expect("embedded default config should parse");
+    assert_eq!(embedded, CodeContextPolicy::default());
+}
+
+#[test]
+fn worker_log_policy_parses_logs_section_and_defaults() {
+    assert_eq!(
+        worker_log_policy_from_toml("").expect("empty config should default"),
+        WorkerLogPolicy::default()
+    );
+    assert_eq!(
+        worker_log_policy_from_toml("[logs]\nmax_file_bytes = 0\nretention_days = 3\n")
+            .expect("overrides should parse"),
+        WorkerLogPolicy {
+            max_file_bytes: 0,
+            max_rotated_files: 3,
+            retention_days: 3,
+        }
+    );
+    let err = worker_log_policy_from_toml("[logs]\nmax_rotated_files = \"many\"\n")
+        .expect_err("non-integer should fail");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("logs.max_rotated_files"));
+
+    let embedded = worker_log_policy_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+        .expect("embedded default config should parse");
+    assert_eq!(embedded, WorkerLogPolicy::default());
+}
+
+#[test]
+fn output_filters_parse_rules_and_reject_empty_patterns() {
+    assert_eq!(
+        output_filters_from_toml("").expect("empty config should default"),
+        OutputFilterConfig::default()
+    );
+    let config = output_filters_from_toml(
+        "[output_filters]\nhide_spinners = false\n\n[[output_filters.rules]]\npattern = \"^[exec]\"\nroles = [\"implementor\"]\naction = \"collapse\"\n",
+    )
+    .expect("rules should parse");
+    assert!(!config.hide_spinners);
+    assert!(config.collapse_repeats);
+    assert_eq!(
+        config.rules,
+        [crate::output_filters::OutputFilterRule {
+            pattern: "^[exec]".to_string(),
+            roles: vec!["implementor".to_string()],
+            action: crate::output_filters::FilterAction::Collapse,
+        }]
+    );
+
+    let err = output_filters_from_toml("[[output_filters.rules]]\npattern = \" \"\n")
+        .expect_err("empty pattern should fail");
+    assert!(err.to_string().contains("output_filters.rules[0].pattern"));
+    let err =
+        output_filters_from_toml("[[output_filters.rules]]\npattern = \"x\"\naction = \"drop\"\n")
+            .expect_err("unknown action should fail");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let embedded = output_filters_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+        .expect("embedded default config should parse");
+    assert_eq!(embedded, OutputFilterConfig::default());
+}
+
+#[test]
+fn backend_failover_threshold_parses_backend_section_and_defaults() {
+    assert_eq!(
+        backend_failover_threshold_from_toml("").expect("empty config should default"),
+        BackendFailoverTracker::DEFAULT_THRESHOLD
+    );
+    assert_eq!(
+        backend_failover_threshold_from_toml("[backend]\nfailover_after_failures = 0\n")
+            .expect("override should parse"),
+        0
+    );
+    let err = backend_failover_threshold_from_toml("[backend]\nfailover_after_failures = -2\n")
+        .expect_err("negative threshold should fail");
+    assert!(err.to_string().contains("backend.failover_after_failures"));
+
+    let embedded = backend_failover_threshold_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+        .expect("embedded default config should parse");
+    assert_eq!(embedded, BackendFailoverTracker::DEFAULT_THRESHOLD);
+}
+
+#[test]
+fn task_split_proposal_round_trips_and_clears() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-task-split-proposal-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let cwd = std::env::current_dir().expect("cwd");
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing");
+    assert!(
+        store
+            .read_task_split_proposal()
+            .expect("missing proposal should read")
+            .is_none()
+    );
+
+    fs::write(
+        store.task_split_proposal_file(),
+        r#"[{"id":"a","title":"A","details":"d","docs":[],"kind":"task","status":"pending","parent_id":null,"order":0}]"#,
+    )
+    .expect("write proposal");
+    let proposal = store
+        .read_task_split_proposal()
+        .expect("read proposal")
+        .expect("proposal should exist");
+    assert_eq!(proposal.len(), 1);
+    assert_eq!(proposal[0].title, "A");
+
+    store.clear_task_split_proposal().expect("clear proposal");
+    assert!(!store.task_split_proposal_file().exists());
+    store
+        .clear_task_split_proposal()
+        .expect("clearing a missing proposal should succeed");
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn session_files_go_through_the_storage_of_the_session_root() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-session-storage-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let cwd = std::env::current_dir().expect("cwd");
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing");
+    let storage = LocalDiskStorage::new(&base);
+
+    store
+        .write_planner_markdown("# Plan\n")
+        .expect("write planner");
+    assert_eq!(
+        storage
+            .read_file("session-a", "planner.md")
+            .expect("read planner"),
+        b"# Plan\n"
+    );
+    storage
+        .write_file("session-a", "project-info.md", b"Rust CLI")
+        .expect("write project info");
+    assert_eq!(
+        store.read_project_info().expect("read project info"),
+        "Rust CLI"
+    );
+    storage
+        .write_file("session-a", "project-info.md", &[0xff, 0xfe])
+        .expect("write bytes");
+    assert_eq!(
+        store.read_project_info().unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert!(
+        store
+            .storage_path(&base.join("session-b").join("tasks.json"))
+            .is_err()
+    );
+    store
+        .write_tasks_atomic(&[])
+        .expect("write tasks atomically");
+    assert_eq!(
+        storage
+            .read_file("session-a", "tasks.json")
+            .expect("read tasks"),
+        b"[]"
+    );
+    store.write_tasks_text("[]\n").expect("restore tasks text");
+    assert_eq!(store.read_tasks_text().expect("read tasks text"), "[]\n");
+
+    let _ = fs::remove_dir_all(&base);
+}
+
 #[test]
 fn persist_tests_mode_updates_config_without_clobbering_other_sections() {
     let updated = update_tests_mode_enabled_in_toml(
@@ -286,6 +674,15 @@ fn list_sessions_in_root_orders_by_last_used_desc() {
             created_at: "2026-02-16T12:00:00Z".to_string(),
             stack_description: "Rust + Ratatui terminal UI app".to_string(),
             test_command: Some("cargo test".to_string()),
+            languages: Vec::new(),
+            frameworks: Vec::new(),
+            package_manager: None,
+            build_command: None,
+            run_command: None,
+            test_runner: None,
+            personas: BTreeMap::new(),
+            models: BTreeMap::new(),
+            models_pinned: false,
         })
         .expect("serialize"),
     )
@@ -457,6 +854,43 @@ fn read_rolling_context_reports_invalid_json() {
     let _ = fs::remove_dir_all(&base);
 }
 
+#[test]
+fn task_summaries_are_replaced_per_task() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-session-summaries-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let cwd = std::env::current_dir().expect("cwd");
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing");
+    assert!(store.read_task_summaries().expect("no file").is_empty());
+
+    let summary = |id: &str, text: &str| TaskSummaryFileEntry {
+        top_task_id: id.to_string(),
+        top_task_title: format!("Task {id}"),
+        summary: text.to_string(),
+        created_at_epoch_secs: 1,
+    };
+    store
+        .upsert_task_summary(summary("a", "first run"))
+        .expect("write a");
+    store
+        .upsert_task_summary(summary("b", "done"))
+        .expect("write b");
+    store
+        .upsert_task_summary(summary("a", "after retry"))
+        .expect("rewrite a");
+    assert_eq!(
+        store.read_task_summaries().expect("read summaries"),
+        vec![summary("b", "done"), summary("a", "after retry")]
+    );
+
+    let _ = fs::remove_dir_all(&base);
+}
 #[test]
 fn append_task_fails_recovers_from_malformed_existing_file() {
     let base = std::env::temp_dir().join(format!(
@@ -544,3 +978,267 @@ fn session_meta_file_parses_test_command_as_string_or_null() {
         .expect("session meta with null command should parse");
     assert!(without_tests.test_command.is_none());
 }
+
+#[test]
+fn agent_persona_reads_the_trimmed_entry_for_each_agent_slot() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-session-personas-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let cwd = std::env::current_dir().expect("cwd");
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing");
+    assert_eq!(store.agent_persona(CodexAgentKind::Master), None);
+
+    fs::write(
+        store.session_meta_file(),
+        "{\"title\":\"Planner Session\",\"created_at\":\"2026-02-16T12:00:00Z\",\"personas\":{\"master\":\" Be terse. \",\"worker_implementor\":\"Target Rust 2021.\",\"worker_auditor\":\"  \"}}",
+    )
+    .expect("write meta");
+    assert_eq!(
+        store.agent_persona(CodexAgentKind::Master).as_deref(),
+        Some("Be terse.")
+    );
+    assert_eq!(
+        store
+            .agent_persona(CodexAgentKind::WorkerImplementor)
+            .as_deref(),
+        Some("Target Rust 2021.")
+    );
+    assert_eq!(store.agent_persona(CodexAgentKind::WorkerAuditor), None);
+    assert_eq!(store.agent_persona(CodexAgentKind::MasterReport), None);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn session_models_are_recorded_once_and_pinning_keeps_other_meta_fields() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-session-models-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let cwd = std::env::current_dir().expect("cwd");
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing");
+    let models = |model: &str| {
+        BTreeMap::from([(
+            "master".to_string(),
+            CodexModelProfile {
+                model: model.to_string(),
+                thinking_effort: Some("high".to_string()),
+            },
+        )])
+    };
+    assert!(
+        !store
+            .record_session_models(&models("gpt-5"))
+            .expect("missing meta is not an error")
+    );
+
+    fs::write(
+        store.session_meta_file(),
+        "{\"title\":\"Planner Session\",\"created_at\":\"2026-02-16T12:00:00Z\",\"notes\":\"kept\"}",
+    )
+    .expect("write meta");
+    assert!(
+        store
+            .record_session_models(&models("gpt-5"))
+            .expect("record")
+    );
+    assert!(
+        !store
+            .record_session_models(&models("gpt-5.1"))
+            .expect("second record")
+    );
+    store.set_session_models_pinned(true).expect("pin");
+
+    let meta = store.read_session_meta().expect("read meta");
+    assert_eq!(meta.models, models("gpt-5"));
+    assert!(meta.models_pinned);
+    let text = fs::read_to_string(store.session_meta_file()).expect("meta text");
+    assert!(text.contains("\"notes\": \"kept\""));
+
+    store.set_session_models_pinned(false).expect("unpin");
+    let text = fs::read_to_string(store.session_meta_file()).expect("meta text");
+    assert!(!text.contains("models_pinned"));
+    assert!(!store.read_session_meta().expect("read meta").models_pinned);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn project_stack_lists_the_structured_fields_meta_json_sets() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-session-stack-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let cwd = std::env::current_dir().expect("cwd");
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing");
+    assert_eq!(store.project_stack(), None);
+
+    fs::write(
+        store.session_meta_file(),
+        "{\"title\":\"Planner Session\",\"created_at\":\"2026-02-16T12:00:00Z\",\"stack_description\":\" \",\"languages\":[\"Rust\",\" \",\"SQL\"],\"frameworks\":[],\"package_manager\":\"cargo\",\"build_command\":\" cargo build \",\"run_command\":null,\"test_command\":\"cargo test\"}",
+    )
+    .expect("write meta");
+    let meta = store.read_session_meta().expect("read meta");
+    assert_eq!(meta.languages, ["Rust", " ", "SQL"]);
+    assert_eq!(meta.run_command, None);
+    assert_eq!(
+        store.project_stack().as_deref(),
+        Some(
+            "- Languages: Rust, SQL\n\
+             - Package manager: cargo\n\
+             - Build command: `cargo build`\n\
+             - Test command: `cargo test`"
+        )
+    );
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn interrupted_run_round_trips_and_clears() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-session-interrupted-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let cwd = std::env::current_dir().expect("cwd");
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing");
+    assert_eq!(store.read_interrupted_run().expect("read missing"), None);
+
+    let run = InterruptedRunFile {
+        execution_running: true,
+        interrupted_job: Some("Implementor pass 1 for task #1 (Top)".to_string()),
+        queued_messages: vec!["also fix the docs".to_string()],
+    };
+    store.write_interrupted_run(&run).expect("write");
+    assert_eq!(store.read_interrupted_run().expect("read"), Some(run));
+
+    store.clear_interrupted_run().expect("clear");
+    store.clear_interrupted_run().expect("clear missing");
+    assert_eq!(store.read_interrupted_run().expect("read cleared"), None);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn ui_state_round_trips_and_defaults_when_missing() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-session-ui-state-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let cwd = std::env::current_dir().expect("cwd");
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing");
+    assert_eq!(
+        store.read_ui_state().expect("read missing"),
+        SessionUiStateFile::default()
+    );
+
+    let state = SessionUiStateFile {
+        left_top_scroll: 3,
+        right_scroll: 12,
+        planner_cursor: 40,
+        expanded_task_keys: ["top".to_string()].into_iter().collect(),
+    };
+    store.write_ui_state(&state).expect("write");
+    assert_eq!(store.read_ui_state().expect("read"), state);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn open_read_only_requires_existing_session_and_never_writes() {
+    let base = std::env::temp_dir().join(format!(
+        "metaagent-session-read-only-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    let session_dir = base.join("session-a");
+
+    let missing = SessionStore::open_read_only(&session_dir).expect_err("missing dir should fail");
+    assert_eq!(missing.kind(), std::io::ErrorKind::NotFound);
+    assert!(!session_dir.exists());
+
+    fs::create_dir_all(&session_dir).expect("session dir");
+    let not_session =
+        SessionStore::open_read_only(&session_dir).expect_err("dir without tasks.json should fail");
+    assert_eq!(not_session.kind(), std::io::ErrorKind::InvalidInput);
+
+    fs::write(session_dir.join("tasks.json"), "[]\n").expect("write tasks");
+    let store = SessionStore::open_read_only(&session_dir).expect("open read-only");
+    assert_eq!(store.session_dir(), session_dir.as_path());
+    assert!(store.read_tasks().expect("read tasks").is_empty());
+    let names = fs::read_dir(&session_dir)
+        .expect("list session dir")
+        .map(|entry| entry.expect("entry").file_name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec![std::ffi::OsString::from("tasks.json")]);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn read_preview_summarizes_top_level_tasks_and_planner_head() {
+    let session_dir = std::env::temp_dir().join(format!(
+        "session-preview-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&session_dir).expect("create session dir");
+    assert_eq!(
+        SessionStore::read_preview(&session_dir, 3),
+        SessionPreview::default()
+    );
+
+    fs::write(
+        session_dir.join("tasks.json"),
+        r#"[
+            {"id": "a", "title": "A", "details": "a", "status": "done", "parent_id": null, "order": 0},
+            {"id": "a-impl", "title": "Impl", "details": "i", "kind": "implementor", "status": "done", "parent_id": "a", "order": 0},
+            {"id": "b", "title": "B", "details": "b", "status": "in_progress", "parent_id": null, "order": 1}
+        ]"#,
+    )
+    .expect("write tasks");
+    fs::write(
+        session_dir.join("planner.md"),
+        "# Plan\n\n- first step\n- second step\n- third step\n",
+    )
+    .expect("write planner");
+
+    let preview = SessionStore::read_preview(&session_dir, 3);
+    assert_eq!((preview.tasks_done, preview.tasks_total), (1, 2));
+    assert!(preview.last_activity_epoch_secs.is_some());
+    assert_eq!(
+        preview.planner_head,
+        ["# Plan", "- first step", "- second step"]
+    );
+    let _ = fs::remove_dir_all(&session_dir);
+}
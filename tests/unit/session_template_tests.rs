@@ -0,0 +1,107 @@
+use super::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const WEB_API_TEMPLATE: &str = r##"
+description = "HTTP service with a database"
+planner = "# Web API\n\n- [ ] Routes\n"
+test_command = " cargo test "
+audit_rubric = "Every handler validates its input."
+
+[[tasks]]
+id = "routes"
+title = "Routes"
+details = "Add the routing skeleton."
+
+[[tasks]]
+id = 2
+title = "Routes implementor"
+details = "Implement the router."
+kind = "implementor"
+parent_id = "routes"
+
+[[tasks]]
+id = "2-audit"
+title = "Routes auditor"
+details = "Review the router."
+kind = "auditor"
+parent_id = "2"
+"##;
+
+fn temp_dir(prefix: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "{prefix}-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+#[test]
+fn list_and_load_templates_from_dir() {
+    let dir = temp_dir("metaagent-templates-load");
+    fs::write(dir.join("web-api.toml"), WEB_API_TEMPLATE).expect("write template");
+    fs::write(dir.join("cli.toml"), "planner = \"# CLI\"\n").expect("write template");
+    fs::write(dir.join("notes.md"), "ignored").expect("write notes");
+
+    assert_eq!(
+        list_templates_in_dir(&dir).expect("list templates"),
+        vec!["cli".to_string(), "web-api".to_string()]
+    );
+    let template = load_template_from_dir(&dir, "web-api").expect("load template");
+    assert_eq!(template.description, "HTTP service with a database");
+    assert_eq!(template.tasks.len(), 3);
+    assert_eq!(template.tasks[1].id, "2");
+
+    let missing = load_template_from_dir(&dir, "mobile").expect_err("missing template");
+    assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    let invalid = load_template_from_dir(&dir, "../cli").expect_err("path-like name");
+    assert_eq!(invalid.kind(), io::ErrorKind::InvalidInput);
+    assert!(
+        list_templates_in_dir(&dir.join("absent"))
+            .expect("missing dir lists nothing")
+            .is_empty()
+    );
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn parse_template_rejects_invalid_task_graphs() {
+    let err = parse_template(
+        "[[tasks]]\nid = \"a\"\ntitle = \"A\"\ndetails = \"x\"\nparent_id = \"missing\"\n",
+    )
+    .expect_err("missing parent should be rejected");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("missing parent_id"));
+}
+
+#[test]
+fn apply_to_session_seeds_planner_tasks_and_template_settings() {
+    let session_dir = temp_dir("metaagent-templates-apply");
+    let store = SessionStore::open_existing(&session_dir, &session_dir).expect("open session");
+    assert_eq!(store.read_session_template().expect("read settings"), None);
+
+    let template = parse_template(WEB_API_TEMPLATE).expect("parse template");
+    template
+        .apply_to_session("web-api", &store)
+        .expect("apply template");
+
+    assert_eq!(
+        store.read_planner_markdown().expect("read planner"),
+        "# Web API\n\n- [ ] Routes\n"
+    );
+    assert_eq!(store.read_tasks().expect("read tasks").len(), 3);
+    assert_eq!(
+        store.read_session_template().expect("read settings"),
+        Some(SessionTemplateFile {
+            name: "web-api".to_string(),
+            test_command: Some("cargo test".to_string()),
+            audit_rubric: "Every handler validates its input.".to_string(),
+        })
+    );
+
+    let _ = fs::remove_dir_all(session_dir);
+}
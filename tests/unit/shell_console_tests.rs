@@ -0,0 +1,65 @@
+use super::*;
+
+fn wait_for_exit(console: &mut ShellConsole) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while console.is_running() && Instant::now() < deadline {
+        console.poll();
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(!console.is_running(), "console command did not finish");
+}
+
+#[cfg(unix)]
+#[test]
+fn captures_output_and_exit_code_in_the_working_directory() {
+    let dir = std::env::temp_dir().join(format!("agentbob-console-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("dir");
+    std::fs::write(dir.join("marker.txt"), "").expect("marker");
+
+    let mut console = ShellConsole::default();
+    assert!(console.keep_entry().is_none());
+    console.run("ls; echo oops >&2; exit 3", &dir).expect("run");
+    assert!(console.is_running());
+    assert!(
+        console
+            .run("true", &dir)
+            .expect_err("busy")
+            .contains("is still running")
+    );
+    wait_for_exit(&mut console);
+
+    let lines = console.lines();
+    assert_eq!(lines[0], "$ ls; echo oops >&2; exit 3");
+    assert!(lines.contains(&"marker.txt".to_string()));
+    assert!(lines.contains(&"oops".to_string()));
+    assert!(lines.contains(&"[exit code 3]".to_string()));
+    let run = console.last_run().expect("run");
+    assert_eq!(run.code, Some(3));
+    assert!(run.finished);
+    let entry = console.keep_entry().expect("entry");
+    assert!(entry.starts_with(
+        "User ran `ls; echo oops >&2; exit 3` in the workspace console (exit code 3); output: "
+    ));
+    assert!(!entry.contains('\n'));
+
+    console.clear();
+    assert!(console.lines().is_empty());
+    assert!(console.keep_entry().is_some());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn keep_entry_holds_only_the_tail_of_long_output() {
+    let mut console = ShellConsole::default();
+    console.run("seq 1 50", &std::env::temp_dir()).expect("run");
+    wait_for_exit(&mut console);
+    let entry = console.keep_entry().expect("entry");
+    assert!(entry.contains("(exit code 0); last 20 output lines: 31 | 32"));
+    assert!(entry.ends_with("| 50"));
+    assert_eq!(
+        console.run("  ", &std::env::temp_dir()),
+        Err("Usage: /console run <command>".to_string())
+    );
+}
@@ -0,0 +1,17 @@
+use super::*;
+
+#[test]
+fn shutdown_mode_parses_case_insensitively_and_rejects_unknown_modes() {
+    assert_eq!(ShutdownMode::parse("wait"), Some(ShutdownMode::Wait));
+    assert_eq!(ShutdownMode::parse(" ABORT "), Some(ShutdownMode::Abort));
+    assert_eq!(ShutdownMode::parse("Detach"), Some(ShutdownMode::Detach));
+    assert_eq!(ShutdownMode::parse("later"), None);
+    assert_eq!(ShutdownMode::parse(""), None);
+}
+
+#[test]
+fn take_sigterm_reports_each_signal_once() {
+    SIGTERM_RECEIVED.store(true, Ordering::SeqCst);
+    assert!(take_sigterm());
+    assert!(!take_sigterm());
+}
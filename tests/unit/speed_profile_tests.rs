@@ -0,0 +1,46 @@
+use super::*;
+
+#[test]
+fn profiles_parse_case_insensitively_and_scale_with_speed() {
+    assert_eq!(SpeedProfile::parse(" MAX "), Some(SpeedProfile::Max));
+    assert_eq!(SpeedProfile::parse("fast"), None);
+    for profile in SpeedProfile::ALL {
+        assert_eq!(SpeedProfile::parse(profile.label()), Some(profile));
+    }
+
+    assert_eq!(
+        SpeedProfile::Standard.worker_model_profile(WorkerRole::Implementor),
+        None
+    );
+    assert_eq!(
+        SpeedProfile::Eco.worker_model_profile(WorkerRole::Auditor),
+        Some("small-dumb")
+    );
+    assert_eq!(
+        SpeedProfile::Max.worker_model_profile(WorkerRole::Implementor),
+        Some("large-genius")
+    );
+    assert_eq!(
+        SpeedProfile::Max.worker_model_profile(WorkerRole::TestRunner),
+        None
+    );
+    assert!(SpeedProfile::Eco.audit_retries() < SpeedProfile::Standard.audit_retries());
+    assert!(SpeedProfile::Standard.audit_retries() < SpeedProfile::Max.audit_retries());
+    assert!(!SpeedProfile::Eco.test_writer_audits());
+    assert_eq!(
+        SpeedProfile::Eco.summary(),
+        "small models for workers, up to 2 audit passes, test-writer audits off"
+    );
+}
+
+#[test]
+fn speed_is_stored_in_session_settings_only_when_not_standard() {
+    let settings = crate::session_store::SessionSettingsFile::default();
+    assert_eq!(
+        serde_json::to_value(&settings).expect("json")["speed"],
+        serde_json::Value::Null
+    );
+    let parsed: crate::session_store::SessionSettingsFile =
+        serde_json::from_str(r#"{"review_changes":false,"speed":"eco"}"#).expect("parse");
+    assert_eq!(parsed.speed, SpeedProfile::Eco);
+}
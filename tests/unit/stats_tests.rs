@@ -0,0 +1,100 @@
+use super::*;
+use crate::session_store::PlannerTaskDocFileEntry;
+
+fn top_task(id: &str, title: &str, status: PlannerTaskStatusFile) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: title.to_string(),
+        details: "details".to_string(),
+        docs: Vec::<PlannerTaskDocFileEntry>::new(),
+        kind: PlannerTaskKindFile::Task,
+        status,
+        parent_id: None,
+        order: None,
+        ..Default::default()
+    }
+}
+
+fn fail(kind: &str, title: &str, attempts: u8) -> TaskFailFileEntry {
+    TaskFailFileEntry {
+        kind: kind.to_string(),
+        top_task_id: 1,
+        top_task_title: title.to_string(),
+        attempts,
+        reason: "reason".to_string(),
+        action_taken: "retry".to_string(),
+        created_at_epoch_secs: 0,
+    }
+}
+
+fn samples() -> Vec<SessionFailureSample> {
+    vec![
+        SessionFailureSample {
+            session_dir: PathBuf::from("/tmp/session-a"),
+            tasks: vec![
+                top_task("1", "Parser", PlannerTaskStatusFile::Done),
+                top_task("2", "Renderer", PlannerTaskStatusFile::Done),
+            ],
+            fails: vec![fail("audit", "Parser", 2), fail("test", "Parser", 3)],
+        },
+        SessionFailureSample {
+            session_dir: PathBuf::from("/tmp/session-b"),
+            tasks: vec![
+                top_task("1", "Parser", PlannerTaskStatusFile::InProgress),
+                top_task("2", "Docs", PlannerTaskStatusFile::Pending),
+            ],
+            fails: vec![fail("audit", "Parser", 4)],
+        },
+    ]
+}
+
+#[test]
+fn from_samples_aggregates_failures_by_kind_and_task() {
+    let stats = FailureStats::from_samples(&samples());
+
+    assert_eq!(stats.sessions_scanned, 2);
+    assert_eq!(stats.top_tasks, 4);
+    assert_eq!(stats.done_tasks, 2);
+    assert_eq!(stats.total_failures, 3);
+
+    let audit = &stats.by_kind[0];
+    assert_eq!(audit.kind, "audit");
+    assert_eq!(audit.failures, 2);
+    assert_eq!(
+        audit.tasks_affected, 2,
+        "same title in two sessions counts twice"
+    );
+    assert_eq!(audit.failure_rate, 0.5);
+    assert_eq!(audit.average_attempts, 3.0);
+    assert_eq!(stats.by_kind[1].kind, "test");
+
+    assert_eq!(stats.most_expensive_tasks[0].session_dir, "/tmp/session-a");
+    assert_eq!(stats.most_expensive_tasks[0].attempts, 5);
+    assert_eq!(stats.most_expensive_tasks[1].attempts, 4);
+    // Parser took 5 failed passes plus the passing one; Renderer passed first time.
+    assert_eq!(stats.average_passes_to_done, Some(3.5));
+}
+
+#[test]
+fn render_lines_reports_empty_history_without_averages() {
+    let stats = FailureStats::from_samples(&[]);
+    assert_eq!(
+        stats.render_lines(),
+        vec![
+            "Sessions: 0 | Top-level tasks: 0 (0 done) | Failures: 0".to_string(),
+            "Average passes to done: n/a".to_string(),
+            String::new(),
+            "Failures by kind:".to_string(),
+            "  (no recorded failures)".to_string(),
+            String::new(),
+            "Most expensive tasks:".to_string(),
+            "  (none)".to_string(),
+        ]
+    );
+
+    let lines = FailureStats::from_samples(&samples()).render_lines();
+    assert!(lines.contains(
+        &"  audit: 2 failure(s), 2 task(s) affected (50% of tasks), avg 3.0 attempt(s)".to_string()
+    ));
+    assert!(lines.contains(&"  1. Parser — 5 attempt(s) over 2 failure(s)".to_string()));
+}
@@ -90,3 +90,18 @@ fn audit_and_test_edit_command_prompts_require_isolated_context_details() {
         "keep details self-contained with files/modules, behavior expectations, constraints/non-goals, verification approach, and an explicit isolated-context rationale"
     ));
 }
+
+#[test]
+fn worker_prompt_gets_the_project_stack_before_the_persona() {
+    let stack = "- Languages: Rust\n- Test command: `cargo test`";
+    let prompt = with_worker_persona(
+        &with_project_stack("Implement task 1", Some(stack)),
+        Some("Be terse."),
+    );
+    assert!(prompt.starts_with("Implement task 1\n\nProject stack (from meta.json"));
+    assert!(prompt.find(stack) < prompt.find("Be terse."));
+    assert_eq!(
+        with_project_stack("Implement task 1", None),
+        "Implement task 1"
+    );
+}
@@ -0,0 +1,92 @@
+use super::*;
+use crate::session_store::PlannerTaskStatusFile;
+
+fn entry(id: &str, title: &str, details: &str, parent_id: Option<&str>) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: title.to_string(),
+        details: details.to_string(),
+        docs: Vec::new(),
+        kind: PlannerTaskKindFile::Task,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn oversized_top_tasks_are_flagged_by_details_then_subtask_count() {
+    let tasks = vec![
+        entry("a", "Long", &"x".repeat(11), None),
+        entry("b", "Wide", "short", None),
+        entry("b1", "Child", "c", Some("b")),
+        entry("b2", "Child", "c", Some("b")),
+        entry("b21", "Grandchild", "c", Some("b2")),
+        entry("c", "Small", "short", None),
+    ];
+    let limits = TaskSplitLimits {
+        max_details_chars: 10,
+        max_subtasks: 2,
+    };
+
+    let oversized = find_oversized_top_tasks(&tasks, limits, &HashSet::new());
+
+    assert_eq!(
+        oversized,
+        vec![
+            OversizedTask {
+                id: "a".to_string(),
+                title: "Long".to_string(),
+                reason: "details are 11 chars (limit 10)".to_string(),
+            },
+            OversizedTask {
+                id: "b".to_string(),
+                title: "Wide".to_string(),
+                reason: "3 subtasks (limit 2)".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn oversized_top_tasks_honor_skip_titles_and_disabled_limits() {
+    let tasks = vec![
+        entry("a", "Long", &"x".repeat(50), None),
+        entry("b", "Longer", &"x".repeat(60), None),
+    ];
+    let skip = HashSet::from(["Long".to_string()]);
+    let limits = TaskSplitLimits {
+        max_details_chars: 10,
+        max_subtasks: 0,
+    };
+
+    let oversized = find_oversized_top_tasks(&tasks, limits, &skip);
+    assert_eq!(oversized.len(), 1);
+    assert_eq!(oversized[0].title, "Longer");
+
+    let disabled = TaskSplitLimits {
+        max_details_chars: 0,
+        max_subtasks: 0,
+    };
+    assert!(find_oversized_top_tasks(&tasks, disabled, &HashSet::new()).is_empty());
+}
+
+#[test]
+fn split_summary_lists_removed_and_added_top_level_titles() {
+    let before = vec![entry("a", "Big", "d", None), entry("b", "Keep", "d", None)];
+    let after = vec![
+        entry("a1", "Part one", "d", None),
+        entry("a2", "Part two", "d", None),
+        entry("b", "Keep", "d", None),
+    ];
+
+    assert_eq!(
+        summarize_task_split(&before, &after),
+        vec![
+            "Split: \"Big\"".to_string(),
+            "Into: \"Part one\", \"Part two\"".to_string(),
+        ]
+    );
+    assert!(summarize_task_split(&before, &before).is_empty());
+}
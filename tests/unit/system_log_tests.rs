@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn failures_and_requests_for_the_user_are_important() {
+    assert!(is_system_line("System: Master prompt sent."));
+    assert!(!is_system_line("Agent: Updated tasks.json."));
+    assert!(!is_important_system_line("System: Master prompt sent."));
+    assert!(!is_important_system_line(
+        "System: Task checker applied fixes to tasks.json."
+    ));
+    assert!(is_important_system_line(
+        "System: Failed to write tasks.json: disk full"
+    ));
+    assert!(is_important_system_line(
+        "System: tasks.json was edited outside the app. Use /reload-tasks or /keep-tasks."
+    ));
+    assert!(is_important_system_line(
+        "System: Usage: /cache or /cache purge"
+    ));
+}
@@ -0,0 +1,61 @@
+use super::*;
+use crate::session_store::{PlannerTaskDocFileEntry, PlannerTaskKindFile};
+
+fn task(id: &str, title: &str, status: PlannerTaskStatusFile) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: title.to_string(),
+        details: format!("{id} details"),
+        docs: Vec::new(),
+        kind: PlannerTaskKindFile::Task,
+        status,
+        parent_id: None,
+        order: None,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn diff_reports_added_removed_retitled_status_details_and_docs_changes() {
+    let before = vec![
+        task("a", "Parse input", PlannerTaskStatusFile::Pending),
+        task("b", "Write output", PlannerTaskStatusFile::Pending),
+        task("c", "Old cleanup", PlannerTaskStatusFile::Done),
+    ];
+    let mut edited = task("b", "Write CSV output", PlannerTaskStatusFile::Done);
+    edited.details = "b details with headers".to_string();
+    let mut documented = task("a", "Parse input", PlannerTaskStatusFile::Pending);
+    documented.docs.push(PlannerTaskDocFileEntry {
+        title: "CSV spec".to_string(),
+        url: "https://example.com/csv".to_string(),
+        summary: String::new(),
+        snapshot: None,
+    });
+    let after = vec![
+        documented,
+        edited,
+        task("d", "Add docs", PlannerTaskStatusFile::Pending),
+    ];
+
+    let changes = diff(&before, &after);
+    assert_eq!(
+        render(&changes),
+        "- removed c: Old cleanup\n\
+         ~ changed docs of a: Parse input\n\
+         ~ retitled b: \"Write output\" -> \"Write CSV output\"\n\
+         * b (Write CSV output): pending -> done\n\
+         ~ edited details of b: Write CSV output\n\
+         + added d: Add docs"
+    );
+    assert!(diff(&after, &after).is_empty());
+}
+
+#[test]
+fn render_caps_long_diffs() {
+    let after = (0..25)
+        .map(|n| task(&n.to_string(), "Task", PlannerTaskStatusFile::Pending))
+        .collect::<Vec<_>>();
+    let rendered = render(&diff(&[], &after));
+    assert_eq!(rendered.lines().count(), 21);
+    assert!(rendered.ends_with("... and 5 more change(s)"));
+}
@@ -0,0 +1,206 @@
+use super::*;
+use crate::session_store::{PlannerTaskKindFile, PlannerTaskStatusFile};
+
+fn task(id: &str, parent_id: Option<&str>, order: Option<u32>) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("Task {id}"),
+        details: format!("{id} details"),
+        docs: Vec::new(),
+        kind: if parent_id.is_some() {
+            PlannerTaskKindFile::Implementor
+        } else {
+            PlannerTaskKindFile::Task
+        },
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order,
+        ..Default::default()
+    }
+}
+
+fn plan() -> Vec<PlannerTaskFileEntry> {
+    let mut audit = task("a-audit", Some("a-impl"), Some(0));
+    audit.kind = PlannerTaskKindFile::Auditor;
+    vec![
+        task("a", None, Some(0)),
+        task("a-impl", Some("a"), Some(0)),
+        audit,
+        task("b", None, Some(1)),
+        task("c", None, Some(2)),
+    ]
+}
+
+fn ids_by_order(tasks: &[PlannerTaskFileEntry]) -> Vec<&str> {
+    let mut roots = tasks
+        .iter()
+        .filter(|entry| entry.parent_id.is_none())
+        .collect::<Vec<_>>();
+    roots.sort_by_key(|entry| entry.order);
+    roots.into_iter().map(|entry| entry.id.as_str()).collect()
+}
+
+#[test]
+fn added_tasks_go_after_their_siblings_and_ids_must_be_new() {
+    let tasks = apply(
+        plan(),
+        TaskEdit::Add(task("d", None, None)),
+        FinalAuditPolicy::default(),
+    )
+    .expect("add");
+    assert_eq!(ids_by_order(&tasks), ["a", "b", "c", "d"]);
+    assert_eq!(tasks.last().and_then(|entry| entry.order), Some(3));
+
+    let err = apply(
+        plan(),
+        TaskEdit::Add(task("b", None, None)),
+        FinalAuditPolicy::default(),
+    )
+    .expect_err("duplicate");
+    assert_eq!(
+        err,
+        TaskEditError::Invalid("Planner task b already exists".to_string())
+    );
+}
+
+#[test]
+fn edits_are_checked_like_a_tasks_file_reload() {
+    let mut orphan = task("x", Some("missing"), None);
+    orphan.kind = PlannerTaskKindFile::Implementor;
+    let err = apply(plan(), TaskEdit::Add(orphan), FinalAuditPolicy::default())
+        .expect_err("missing parent");
+    assert!(
+        matches!(err, TaskEditError::Invalid(message) if message.contains("missing parent_id"))
+    );
+
+    let mut blank = task("b", None, Some(1));
+    blank.details = "  ".to_string();
+    let err = apply(plan(), TaskEdit::Update(blank), FinalAuditPolicy::default())
+        .expect_err("blank details");
+    assert!(
+        matches!(err, TaskEditError::Invalid(message) if message.contains("non-empty details"))
+    );
+
+    let mut final_audit = task("z", None, None);
+    final_audit.kind = PlannerTaskKindFile::FinalAudit;
+    apply(
+        plan(),
+        TaskEdit::Add(final_audit.clone()),
+        FinalAuditPolicy::Manual,
+    )
+    .expect("final audit allowed");
+    let err = apply(plan(), TaskEdit::Add(final_audit), FinalAuditPolicy::Never)
+        .expect_err("final audits disabled");
+    assert!(
+        matches!(err, TaskEditError::Invalid(message) if message.contains("final audits are disabled"))
+    );
+
+    let mut renamed = task("b", None, Some(1));
+    renamed.title = "Renamed".to_string();
+    let tasks = apply(
+        plan(),
+        TaskEdit::Update(renamed),
+        FinalAuditPolicy::default(),
+    )
+    .expect("update");
+    assert_eq!(tasks[3].title, "Renamed");
+}
+
+#[test]
+fn removing_a_task_drops_its_subtasks() {
+    let tasks = apply(
+        plan(),
+        TaskEdit::Remove {
+            task_id: "a".to_string(),
+        },
+        FinalAuditPolicy::default(),
+    )
+    .expect("remove");
+    assert_eq!(
+        tasks
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<Vec<_>>(),
+        ["b", "c"]
+    );
+
+    let err = apply(
+        plan(),
+        TaskEdit::Remove {
+            task_id: "zzz".to_string(),
+        },
+        FinalAuditPolicy::default(),
+    )
+    .expect_err("unknown id");
+    assert_eq!(
+        err,
+        TaskEditError::NotFound("Planner task zzz not found".to_string())
+    );
+}
+
+#[test]
+fn reordering_moves_a_task_among_its_siblings_and_renumbers_them() {
+    let tasks = apply(
+        plan(),
+        TaskEdit::Reorder {
+            task_id: "c".to_string(),
+            position: 0,
+        },
+        FinalAuditPolicy::default(),
+    )
+    .expect("reorder");
+    assert_eq!(ids_by_order(&tasks), ["c", "a", "b"]);
+    assert_eq!(
+        tasks
+            .iter()
+            .filter(|entry| entry.parent_id.is_none())
+            .map(|entry| entry.order)
+            .collect::<Vec<_>>(),
+        [Some(1), Some(2), Some(0)]
+    );
+
+    let err = apply(
+        plan(),
+        TaskEdit::Reorder {
+            task_id: "a".to_string(),
+            position: 3,
+        },
+        FinalAuditPolicy::default(),
+    )
+    .expect_err("out of range");
+    assert!(matches!(err, TaskEditError::Invalid(message) if message.contains("out of range")));
+}
+
+#[test]
+fn set_status_covers_subtasks_and_positions_follow_the_task_list() {
+    let tasks = apply(
+        plan(),
+        TaskEdit::SetStatus {
+            task_id: "a".to_string(),
+            status: PlannerTaskStatusFile::Done,
+        },
+        FinalAuditPolicy::default(),
+    )
+    .expect("set status");
+    assert_eq!(
+        tasks
+            .iter()
+            .filter(|entry| entry.status == PlannerTaskStatusFile::Done)
+            .map(|entry| entry.id.as_str())
+            .collect::<Vec<_>>(),
+        ["a", "a-impl", "a-audit"]
+    );
+
+    let mut reordered = plan();
+    reordered[3].order = Some(5);
+    assert_eq!(
+        top_level_ids(&reordered, &[3, 1]),
+        Ok(vec!["b".to_string(), "a".to_string()])
+    );
+    assert_eq!(
+        top_level_ids(&reordered, &[4]),
+        Err(TaskEditError::NotFound(
+            "No task #4; the task list has 3 top-level task(s)".to_string()
+        ))
+    );
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+fn task(
+    id: &str,
+    title: &str,
+    kind: PlannerTaskKindFile,
+    status: PlannerTaskStatusFile,
+    parent_id: Option<&str>,
+) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: title.to_string(),
+        details: format!("{id} details"),
+        docs: Vec::new(),
+        kind,
+        status,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    }
+}
+
+fn plan() -> Vec<PlannerTaskFileEntry> {
+    vec![
+        task(
+            "a",
+            "Parse \"input\"",
+            PlannerTaskKindFile::Task,
+            PlannerTaskStatusFile::Done,
+            None,
+        ),
+        task(
+            "a-impl",
+            "Implement",
+            PlannerTaskKindFile::Implementor,
+            PlannerTaskStatusFile::NeedsChanges,
+            Some("a"),
+        ),
+        task(
+            "b",
+            "Write output",
+            PlannerTaskKindFile::Task,
+            PlannerTaskStatusFile::Pending,
+            None,
+        ),
+    ]
+}
+
+#[test]
+fn mermaid_graph_links_subtasks_orders_top_level_tasks_and_colors_by_status() {
+    let graph = render(&plan(), GraphFormat::Mermaid);
+    let lines = graph.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "flowchart TD");
+    assert!(lines.contains(&"  classDef done fill:#d4f7d4,stroke:#3c9d3c"));
+    assert!(lines.contains(&"  t0[\"Parse #quot;input#quot;<br/>task · done\"]:::done"));
+    assert!(lines.contains(&"  t1[\"Implement<br/>implementor · needs changes\"]:::needs_changes"));
+    assert_eq!(&lines[lines.len() - 2..], ["  t0 --> t1", "  t0 -.-> t2"]);
+}
+
+#[test]
+fn dot_graph_escapes_labels_and_closes_the_digraph() {
+    let graph = render(&plan(), GraphFormat::Dot);
+    assert!(graph.starts_with("digraph tasks {\n  rankdir=TB;\n"));
+    assert!(graph.contains(
+        "  t0 [label=\"Parse \\\"input\\\"\\ntask · done\", fillcolor=\"#d4f7d4\", color=\"#3c9d3c\"];"
+    ));
+    assert!(graph.ends_with("  t0 -> t1;\n  t0 -> t2 [style=dashed];\n}"));
+}
@@ -0,0 +1,75 @@
+use super::*;
+use crate::session_store::{PlannerTaskKindFile, PlannerTaskStatusFile};
+
+fn task(id: &str, title: &str) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: title.to_string(),
+        details: String::new(),
+        docs: Vec::new(),
+        kind: PlannerTaskKindFile::Task,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: None,
+        order: None,
+        ..Default::default()
+    }
+}
+
+fn text(tasks: &[PlannerTaskFileEntry]) -> String {
+    serde_json::to_string_pretty(tasks).expect("serialize tasks")
+}
+
+#[test]
+fn records_each_agent_write_with_its_diff_and_resulting_tasks() {
+    let path = std::env::temp_dir()
+        .join(format!("agentbob-tasks-changelog-{}", std::process::id()))
+        .join("tasks-changelog.jsonl");
+    let _ = fs::remove_file(&path);
+    let planned = vec![task("a", "Parse input")];
+    let checked = vec![task("a", "Parse input"), task("b", "Write output")];
+
+    assert!(record(&path, TasksChangeAgent::Master, None, Some(&text(&planned))).expect("record"));
+    assert!(
+        record(
+            &path,
+            TasksChangeAgent::TaskCheck,
+            Some(&text(&planned)),
+            Some(&text(&checked))
+        )
+        .expect("record")
+    );
+    // Unchanged or unreadable results leave the changelog alone.
+    assert!(
+        !record(
+            &path,
+            TasksChangeAgent::DocsAttach,
+            Some(&text(&checked)),
+            Some(&text(&checked))
+        )
+        .expect("record")
+    );
+    assert!(!record(&path, TasksChangeAgent::Master, None, Some("not json")).expect("record"));
+
+    let entries = read_entries(&path).expect("read entries");
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].agent, TasksChangeAgent::Master);
+    assert_eq!(
+        entries[1].changes,
+        vec![TaskChange::Added {
+            id: "b".to_string(),
+            title: "Write output".to_string(),
+        }]
+    );
+    assert_eq!(
+        entries[1]
+            .tasks
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["a", "b"]
+    );
+    let line = fs::read_to_string(&path).expect("read changelog");
+    assert!(line.contains("\"agent\":\"task_check\""));
+    assert!(line.contains("\"change\":\"added\""));
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,126 @@
+use super::*;
+use crate::session_store::{PlannerTaskKindFile, PlannerTaskStatusFile};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn open_temp_store(prefix: &str) -> (SessionStore, PathBuf) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let cwd = std::env::current_dir().expect("cwd");
+    let session_dir = std::env::temp_dir().join(format!("{prefix}-{now}"));
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing store");
+    (store, session_dir)
+}
+
+fn task(
+    id: &str,
+    title: &str,
+    kind: PlannerTaskKindFile,
+    parent_id: Option<&str>,
+) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: title.to_string(),
+        details: "d".to_string(),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    }
+}
+
+fn watched_session(prefix: &str) -> (App, SessionStore, PathBuf, TasksFileWatcher) {
+    let (store, session_dir) = open_temp_store(prefix);
+    let tasks = vec![
+        task("t", "Build", PlannerTaskKindFile::Task, None),
+        task(
+            "t-impl",
+            "Implement",
+            PlannerTaskKindFile::Implementor,
+            Some("t"),
+        ),
+        task(
+            "t-audit",
+            "Audit",
+            PlannerTaskKindFile::Auditor,
+            Some("t-impl"),
+        ),
+    ];
+    let mut app = App::default();
+    app.sync_planner_tasks_from_file(tasks.clone())
+        .expect("sync tasks");
+    store.write_tasks(&tasks).expect("write tasks");
+    let mut watcher = TasksFileWatcher::default();
+    assert!(!watcher.poll(&mut app, &store, false));
+    (app, store, session_dir, watcher)
+}
+
+#[test]
+fn hand_edit_is_flagged_once_and_cleared_when_reverted() {
+    let (mut app, store, session_dir, mut watcher) = watched_session("tasks-watch-edit");
+    let original = std::fs::read_to_string(store.tasks_file()).expect("read tasks");
+    let mut edited = store.read_tasks().expect("read tasks");
+    edited[0].title = "Build it better".to_string();
+    store.write_tasks(&edited).expect("edit tasks");
+
+    assert!(watcher.poll(&mut app, &store, false));
+    assert_eq!(
+        app.external_tasks_edit(),
+        Some(&ExternalTasksEdit {
+            session_dir: session_dir.clone(),
+            text: std::fs::read_to_string(store.tasks_file()).expect("read tasks"),
+            validation_error: None,
+        })
+    );
+    assert!(!watcher.poll(&mut app, &store, false));
+
+    std::fs::write(store.tasks_file(), original).expect("revert tasks");
+    assert!(!watcher.poll(&mut app, &store, false));
+    assert!(!app.has_external_tasks_edit());
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn app_snapshots_and_agent_writes_are_not_flagged() {
+    let (mut app, store, session_dir, mut watcher) = watched_session("tasks-watch-app");
+    let snapshot = serde_json::to_string(&app.planner_tasks_for_file()).expect("serialize tasks");
+    std::fs::write(store.tasks_file(), snapshot).expect("write compact snapshot");
+    assert!(!watcher.poll(&mut app, &store, false));
+
+    let mut edited = store.read_tasks().expect("read tasks");
+    edited[0].title = "Written by master".to_string();
+    store.write_tasks(&edited).expect("master edit");
+    assert!(!watcher.poll(&mut app, &store, true));
+    edited[0].title = "Written as master finished".to_string();
+    store.write_tasks(&edited).expect("master edit");
+    assert!(!watcher.poll(&mut app, &store, false));
+    assert!(!app.has_external_tasks_edit());
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
+
+#[test]
+fn invalid_hand_edit_is_flagged_with_validation_error() {
+    let (mut app, store, session_dir, mut watcher) = watched_session("tasks-watch-invalid");
+    let mut edited = store.read_tasks().expect("read tasks");
+    edited[2].parent_id = Some("missing".to_string());
+    store.write_tasks(&edited).expect("edit tasks");
+
+    assert!(watcher.poll(&mut app, &store, false));
+    let error = app
+        .external_tasks_edit()
+        .and_then(|edit| edit.validation_error.clone())
+        .expect("validation error");
+    assert!(error.contains("missing"), "{error}");
+    assert!(
+        app.left_bottom_lines()
+            .last()
+            .is_some_and(|line| line.contains("/keep-tasks"))
+    );
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
@@ -0,0 +1,122 @@
+use super::*;
+
+#[test]
+fn config_defaults_off_and_builds_the_traces_url() {
+    let config = telemetry_config_from_toml(crate::default_config::DEFAULT_CONFIG_TOML)
+        .expect("embedded default config should parse");
+    assert_eq!(config, TelemetryConfig::default());
+    assert_eq!(config.traces_url(), None);
+
+    let config = telemetry_config_from_toml(
+        "[telemetry]\notlp_endpoint = \"http://localhost:4318/\"\nservice_name = \"bob-ci\"\n",
+    )
+    .expect("override should parse");
+    assert_eq!(
+        config.traces_url().as_deref(),
+        Some("http://localhost:4318/v1/traces")
+    );
+    assert_eq!(config.service_name, "bob-ci");
+    let config = TelemetryConfig {
+        otlp_endpoint: "https://otlp.example/v1/traces".to_string(),
+        ..TelemetryConfig::default()
+    };
+    assert_eq!(
+        config.traces_url().as_deref(),
+        Some("https://otlp.example/v1/traces")
+    );
+    assert!(telemetry_config_from_toml("[telemetry]\notlp_endpoint = 4318\n").is_err());
+}
+
+#[test]
+fn spans_record_parents_attributes_and_errors_when_they_end() {
+    let (tx, rx) = mpsc::channel();
+    let tracer = Tracer::new(tx);
+    let mut job = tracer.start_span("workflow.job", SpanKind::Internal, None);
+    job.set_attribute("workflow.role", "implementor");
+    job.set_attribute("workflow.pass", 2u8);
+    let job_context = job.context().expect("enabled span has a context");
+
+    let mut run = tracer.start_span("agent.run", SpanKind::Client, Some(job_context));
+    run.set_attribute("process.exit_code", 1);
+    run.set_error("exited with status code 1");
+    drop(run);
+    job.end();
+
+    let run = rx.try_recv().expect("child span recorded first");
+    let job = rx.try_recv().expect("job span recorded");
+    assert!(rx.try_recv().is_err());
+    assert_eq!(run.name, "agent.run");
+    assert_eq!(run.kind, SpanKind::Client);
+    assert_eq!(run.context.trace_id, job.context.trace_id);
+    assert_eq!(run.parent_span_id, Some(job.context.span_id));
+    assert_ne!(run.context.span_id, job.context.span_id);
+    assert_eq!(run.error.as_deref(), Some("exited with status code 1"));
+    assert_eq!(job.parent_span_id, None);
+    assert_eq!(
+        job.attributes,
+        vec![
+            (
+                "workflow.role".to_string(),
+                AttributeValue::from("implementor")
+            ),
+            ("workflow.pass".to_string(), AttributeValue::Int(2)),
+        ]
+    );
+    assert!(job.end_unix_nanos >= job.start_unix_nanos);
+
+    let mut disabled = Span::disabled();
+    disabled.set_attribute("ignored", true);
+    assert_eq!(disabled.context(), None);
+}
+
+#[test]
+fn otlp_json_encodes_ids_times_attributes_and_status() {
+    let span = |span_id, parent_span_id, error: Option<&str>| SpanRecord {
+        name: "session.write".to_string(),
+        context: SpanContext {
+            trace_id: 0xab,
+            span_id,
+        },
+        parent_span_id,
+        kind: SpanKind::Internal,
+        start_unix_nanos: 1_000,
+        end_unix_nanos: 2_500,
+        attributes: vec![
+            ("file.name".to_string(), AttributeValue::from("tasks.json")),
+            ("file.bytes".to_string(), AttributeValue::from(42usize)),
+            ("flaky".to_string(), AttributeValue::from(false)),
+        ],
+        error: error.map(str::to_string),
+    };
+    let body = otlp_traces_json(
+        "agentbob",
+        &[span(1, None, None), span(2, Some(1), Some("disk full"))],
+    );
+
+    let resource = &body["resourceSpans"][0];
+    assert_eq!(
+        resource["resource"]["attributes"][0],
+        json!({ "key": "service.name", "value": { "stringValue": "agentbob" } })
+    );
+    let spans = &resource["scopeSpans"][0]["spans"];
+    assert_eq!(spans[0]["traceId"], "000000000000000000000000000000ab");
+    assert_eq!(spans[0]["spanId"], "0000000000000001");
+    assert!(spans[0].get("parentSpanId").is_none());
+    assert_eq!(spans[0]["kind"], 1);
+    assert_eq!(spans[0]["startTimeUnixNano"], "1000");
+    assert_eq!(spans[0]["endTimeUnixNano"], "2500");
+    assert_eq!(spans[0]["status"], json!({ "code": 1 }));
+    assert_eq!(
+        spans[0]["attributes"],
+        json!([
+            { "key": "file.name", "value": { "stringValue": "tasks.json" } },
+            { "key": "file.bytes", "value": { "intValue": "42" } },
+            { "key": "flaky", "value": { "boolValue": false } },
+        ])
+    );
+    assert_eq!(spans[1]["parentSpanId"], "0000000000000001");
+    assert_eq!(
+        spans[1]["status"],
+        json!({ "code": 2, "message": "disk full" })
+    );
+}
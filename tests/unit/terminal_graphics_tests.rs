@@ -0,0 +1,187 @@
+use super::*;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+
+fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: Vec<(String, String)> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| {
+        vars.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    }
+}
+
+#[test]
+fn detects_kitty_and_iterm2_but_not_inside_tmux() {
+    assert_eq!(
+        GraphicsProtocol::detect(env_of(&[("TERM", "xterm-kitty")])),
+        Some(GraphicsProtocol::Kitty)
+    );
+    assert_eq!(
+        GraphicsProtocol::detect(env_of(&[("TERM_PROGRAM", "ghostty")])),
+        Some(GraphicsProtocol::Kitty)
+    );
+    assert_eq!(
+        GraphicsProtocol::detect(env_of(&[("TERM_PROGRAM", "iTerm.app")])),
+        Some(GraphicsProtocol::Iterm2)
+    );
+    assert_eq!(
+        GraphicsProtocol::detect(env_of(&[
+            ("TERM", "xterm-kitty"),
+            ("TMUX", "/tmp/tmux-1000/default,1,0")
+        ])),
+        None
+    );
+    assert_eq!(
+        GraphicsProtocol::detect(env_of(&[("TERM", "xterm-256color")])),
+        None
+    );
+}
+
+#[test]
+fn graphics_mode_defaults_to_auto_and_rejects_unknown_values() {
+    assert_eq!(
+        graphics_mode_from_toml("[ui]\nlocale = \"en\"\n").expect("mode"),
+        GraphicsMode::Auto
+    );
+    assert_eq!(
+        graphics_mode_from_toml("[ui]\ngraphics = \"OFF\"\n").expect("mode"),
+        GraphicsMode::Off
+    );
+    let err = graphics_mode_from_toml("[ui]\ngraphics = \"sixel\"\n").expect_err("invalid");
+    assert!(err.to_string().contains("ui.graphics"));
+}
+
+#[test]
+fn png_references_become_text_fallbacks() {
+    let text = "See ![Task graph](docs/graph.png) and ![logo](logo.svg), or [notes](notes.md).";
+    assert_eq!(
+        with_image_fallbacks(text),
+        "See [image: docs/graph.png] Task graph and ![logo](logo.svg), or [notes](notes.md)."
+    );
+    assert_eq!(with_image_fallbacks("![](out/a.PNG)"), "[image: out/a.PNG]");
+    assert!(matches!(
+        with_image_fallbacks("no images here"),
+        Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn base64_matches_reference_vectors() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"f"), "Zg==");
+    assert_eq!(base64(b"fo"), "Zm8=");
+    assert_eq!(base64(b"foo"), "Zm9v");
+    assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+}
+
+#[test]
+fn placements_use_the_blank_rows_below_each_fallback() {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 30, 8));
+    buffer.set_string(2, 1, "[image: a.png] Graph", Style::default());
+    buffer.set_string(0, 5, "next message", Style::default());
+    buffer.set_string(0, 6, "[image: cut", Style::default());
+    // A modal drawn over the right side, in another background, bounds the width.
+    buffer.set_style(Rect::new(20, 0, 10, 8), Style::default().bg(Color::Blue));
+
+    assert_eq!(
+        find_placements(&buffer, 12),
+        vec![ImagePlacement {
+            path: "a.png".to_string(),
+            x: 2,
+            y: 2,
+            cols: 18,
+            rows: 3,
+        }]
+    );
+    assert_eq!(find_placements(&buffer, 2)[0].rows, 2);
+}
+
+#[test]
+fn inline_images_redraw_only_when_placements_change() {
+    let dir =
+        std::env::temp_dir().join(format!("agentbob-terminal-graphics-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("dir");
+    std::fs::write(dir.join("a.png"), b"png").expect("image");
+    let placement = ImagePlacement {
+        path: "a.png".to_string(),
+        x: 4,
+        y: 2,
+        cols: 10,
+        rows: 3,
+    };
+
+    let mut kitty = InlineImages::default();
+    let mut out = Vec::new();
+    assert!(
+        !kitty
+            .update(
+                &mut out,
+                GraphicsProtocol::Kitty,
+                vec![placement.clone()],
+                &dir
+            )
+            .expect("update")
+    );
+    let written = String::from_utf8(out).expect("utf8");
+    assert!(written.starts_with("\x1b7\x1b[3;5H\x1b_Ga=T,f=100,t=f,c=10,r=3,C=1,q=2;"));
+    assert!(written.contains(&base64(dir.join("a.png").to_string_lossy().as_bytes())));
+    assert!(written.ends_with("\x1b\\\x1b8"));
+
+    let mut out = Vec::new();
+    kitty
+        .update(
+            &mut out,
+            GraphicsProtocol::Kitty,
+            vec![placement.clone()],
+            &dir,
+        )
+        .expect("update");
+    assert!(out.is_empty());
+    kitty
+        .update(&mut out, GraphicsProtocol::Kitty, Vec::new(), &dir)
+        .expect("update");
+    assert_eq!(
+        String::from_utf8(out).expect("utf8"),
+        format!("\x1b7{KITTY_DELETE_ALL}\x1b8")
+    );
+
+    let mut iterm2 = InlineImages::default();
+    let mut out = Vec::new();
+    iterm2
+        .update(
+            &mut out,
+            GraphicsProtocol::Iterm2,
+            vec![placement.clone()],
+            &dir,
+        )
+        .expect("update");
+    let written = String::from_utf8(out).expect("utf8");
+    assert!(written.contains("\x1b]1337;File=inline=1;size=3;width=10;height=3;"));
+    assert!(written.contains(&format!(":{}\x07", base64(b"png"))));
+    // Moving an iTerm2 image asks for a repaint first, then draws it on the clean screen.
+    let moved = ImagePlacement { y: 1, ..placement };
+    let mut out = Vec::new();
+    assert!(
+        iterm2
+            .update(
+                &mut out,
+                GraphicsProtocol::Iterm2,
+                vec![moved.clone()],
+                &dir
+            )
+            .expect("update")
+    );
+    assert!(out.is_empty());
+    assert!(
+        !iterm2
+            .update(&mut out, GraphicsProtocol::Iterm2, vec![moved], &dir)
+            .expect("update")
+    );
+    assert!(!out.is_empty());
+    let _ = std::fs::remove_dir_all(&dir);
+}
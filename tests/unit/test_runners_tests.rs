@@ -0,0 +1,231 @@
+use super::*;
+
+fn spec(json: serde_json::Value) -> TestRunnerSpec {
+    serde_json::from_value(json).expect("spec")
+}
+
+fn case(
+    name: &str,
+    outcome: TestOutcome,
+    location: Option<&str>,
+    message: Option<&str>,
+) -> TestCaseResult {
+    TestCaseResult {
+        name: name.to_string(),
+        outcome,
+        location: location.map(str::to_string),
+        message: message.map(str::to_string),
+    }
+}
+
+#[test]
+fn registry_builds_commands_per_kind_and_rejects_incomplete_specs() {
+    let registry = RunnerRegistry::register_defaults();
+    assert_eq!(
+        registry.kinds(),
+        ["shell", "cargo", "pytest", "docker_compose"]
+    );
+
+    let (runner, command) = registry
+        .resolve(&spec(
+            serde_json::json!({"kind": "Cargo", "args": ["--workspace"]}),
+        ))
+        .expect("cargo");
+    assert_eq!(runner.kind(), "cargo");
+    assert_eq!(
+        command.config.args,
+        [
+            "test",
+            "--workspace",
+            "--",
+            "-Z",
+            "unstable-options",
+            "--format",
+            "json"
+        ]
+    );
+    assert_eq!(
+        command.env,
+        [("RUSTC_BOOTSTRAP".to_string(), "1".to_string())]
+    );
+
+    let (_, command) = registry
+        .resolve(&spec(serde_json::json!({
+            "kind": "docker-compose",
+            "file": "compose.test.yml",
+            "service": "tests",
+            "args": ["-x"],
+        })))
+        .expect("docker compose");
+    assert_eq!(
+        command.config.args,
+        [
+            "compose",
+            "-f",
+            "compose.test.yml",
+            "run",
+            "--rm",
+            "--build",
+            "tests",
+            "-x"
+        ]
+    );
+    assert_eq!(
+        command.cleanup.expect("teardown").args,
+        [
+            "compose",
+            "-f",
+            "compose.test.yml",
+            "down",
+            "--remove-orphans"
+        ]
+    );
+
+    let (_, command) = registry
+        .resolve(&spec(serde_json::json!({"kind": "pytest"})))
+        .expect("pytest");
+    let report_file = command.report_file.expect("junit report");
+    assert_eq!(
+        command.config.args,
+        [format!("--junitxml={}", report_file.display())]
+    );
+
+    let err = |json| registry.resolve(&spec(json)).err().expect("rejected");
+    assert!(err(serde_json::json!({"kind": "make"})).contains("expected one of: shell, cargo"));
+    assert!(err(serde_json::json!({"kind": "shell", "command": " "})).contains("needs a command"));
+    assert!(err(serde_json::json!({"kind": "docker_compose"})).contains("needs a service"));
+}
+
+#[test]
+fn cargo_json_events_become_test_cases_and_readable_lines() {
+    let runner = RunnerRegistry::register_defaults()
+        .get("cargo")
+        .expect("cargo");
+    let mut report = TestRunReport::new("cargo");
+    let mut shown = Vec::new();
+    for line in [
+        r#"{ "type": "suite", "event": "started", "test_count": 3 }"#,
+        r#"{ "type": "test", "event": "started", "name": "math::adds" }"#,
+        r#"{ "type": "test", "name": "math::adds", "event": "ok" }"#,
+        r#"{ "type": "test", "name": "math::divides", "event": "failed", "stdout": "thread 'math::divides' panicked at src/math.rs:9:5:\nassertion failed\n" }"#,
+        r#"{ "type": "test", "name": "math::slow", "event": "ignored" }"#,
+        r#"{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1 }"#,
+        "   Compiling demo v0.1.0",
+    ] {
+        shown.extend(runner.parse_line(line, &mut report));
+    }
+    assert_eq!(
+        shown,
+        [
+            "test math::adds ... ok",
+            "test math::divides ... FAILED",
+            "thread 'math::divides' panicked at src/math.rs:9:5:",
+            "assertion failed",
+            "test math::slow ... ignored",
+            "test result: FAILED. 1 passed; 1 failed; 1 ignored",
+            "   Compiling demo v0.1.0",
+        ]
+    );
+    assert_eq!(report.summary(), "cargo: 1 passed, 1 failed, 1 skipped");
+    assert!(report.detailed);
+    assert_eq!(
+        report.failed().collect::<Vec<_>>(),
+        [&case(
+            "math::divides",
+            TestOutcome::Failed,
+            Some("src/math.rs:9:5"),
+            Some("assertion failed")
+        )]
+    );
+}
+
+#[test]
+fn panic_output_yields_location_and_message_in_both_formats() {
+    assert_eq!(
+        parse_panic(
+            "thread 'eq' panicked at src/lib.rs:4:9:\nassertion `left == right` failed\n  left: 1\n right: 2\nnote: run with `RUST_BACKTRACE=1`\n"
+        ),
+        (
+            Some("src/lib.rs:4:9".to_string()),
+            Some("assertion `left == right` failed\n  left: 1\n right: 2".to_string())
+        )
+    );
+    assert_eq!(
+        parse_panic("thread 'old' panicked at 'boom', src/lib.rs:7:5\n"),
+        (Some("src/lib.rs:7:5".to_string()), Some("boom".to_string()))
+    );
+    assert_eq!(
+        parse_panic("custom harness output"),
+        (None, Some("custom harness output".to_string()))
+    );
+    let long = (0..30)
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(cap_lines(&long).ends_with("19\n... 10 more line(s)"));
+}
+
+#[test]
+fn junit_xml_reports_failures_errors_and_skips() {
+    let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<testsuites><testsuite name="pytest" tests="4">
+<testcase classname="tests.test_api" name="test_ok" time="0.01" />
+<testcase classname="tests.test_api" name="test_bad" time="0.02"><failure message="assert 1 == 2&#10;+  where 1 = f()">def test_bad():
+&gt;       assert f() == 2
+E       assert 1 == 2
+
+tests/test_api.py:14: AssertionError</failure></testcase>
+<testcase classname="tests.test_api" name="test_boom" file="tests/test_api.py"><error message="fixture &quot;db&quot; failed" /></testcase>
+<testcase classname="tests.test_api" name="test_later"><skipped message="todo" /></testcase>
+</testsuite></testsuites>"#;
+    let mut report = TestRunReport::new("pytest");
+    parse_junit_xml(xml, &mut report);
+    assert_eq!(
+        report.cases,
+        [
+            case("tests.test_api::test_ok", TestOutcome::Passed, None, None),
+            case(
+                "tests.test_api::test_bad",
+                TestOutcome::Failed,
+                Some("tests/test_api.py:14"),
+                Some("assert 1 == 2\n+  where 1 = f()")
+            ),
+            case(
+                "tests.test_api::test_boom",
+                TestOutcome::Failed,
+                Some("tests/test_api.py"),
+                Some("fixture \"db\" failed")
+            ),
+            case(
+                "tests.test_api::test_later",
+                TestOutcome::Skipped,
+                None,
+                None
+            ),
+        ]
+    );
+}
+
+#[test]
+fn text_output_yields_libtest_and_pytest_cases_and_compose_falls_back_to_the_service() {
+    let mut report = TestRunReport::new("shell");
+    for line in [
+        "test parser::empty ... ok",
+        "test parser::nested ... FAILED",
+        "test parser::huge ... ignored, slow",
+        "tests/test_cli.py::test_help PASSED                [ 50%]",
+        "tests/test_cli.py::test_run FAILED                 [100%]",
+        "error[E0425]: cannot find value `x` in this scope",
+    ] {
+        parse_text_result_line(line, &mut report);
+    }
+    assert_eq!(report.summary(), "shell: 2 passed, 2 failed, 1 skipped");
+    assert!(!report.detailed);
+
+    let registry = RunnerRegistry::register_defaults();
+    let compose = spec(serde_json::json!({"kind": "docker_compose", "service": "it"}));
+    let (runner, command) = registry.resolve(&compose).expect("docker compose");
+    let mut report = TestRunReport::new("docker_compose");
+    runner.finish(&compose, &command, false, &mut report);
+    assert_eq!(report.cases, [case("it", TestOutcome::Failed, None, None)]);
+}
@@ -81,3 +81,49 @@ fn load_or_default_falls_back_for_malformed_theme_file() {
 
     let _ = fs::remove_file(path);
 }
+
+#[test]
+fn color_depth_follows_no_color_colorterm_and_term() {
+    let depth = |vars: &[(&str, &str)]| {
+        ColorDepth::from_env(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    };
+    assert_eq!(
+        depth(&[("COLORTERM", "truecolor"), ("NO_COLOR", "1")]),
+        ColorDepth::Monochrome
+    );
+    assert_eq!(
+        depth(&[("COLORTERM", "24bit"), ("NO_COLOR", "")]),
+        ColorDepth::TrueColor
+    );
+    assert_eq!(depth(&[("TERM", "xterm-256color")]), ColorDepth::Indexed256);
+    assert_eq!(depth(&[("TERM", "xterm")]), ColorDepth::Ansi16);
+    assert_eq!(depth(&[("TERM", "dumb")]), ColorDepth::Monochrome);
+}
+
+#[test]
+fn downgrade_picks_the_nearest_palette_color() {
+    let chat_bg = Theme::default().chat_bg;
+    assert_eq!(ColorDepth::TrueColor.downgrade(chat_bg), chat_bg);
+    assert_eq!(
+        ColorDepth::Indexed256.downgrade(chat_bg),
+        Color::Indexed(237)
+    );
+    assert_eq!(
+        ColorDepth::Indexed256.downgrade(Color::Rgb(90, 145, 200)),
+        Color::Indexed(68)
+    );
+    assert_eq!(ColorDepth::Ansi16.downgrade(chat_bg), Color::Black);
+    assert_eq!(
+        ColorDepth::Ansi16.downgrade(Color::Indexed(196)),
+        Color::LightRed
+    );
+    assert_eq!(ColorDepth::Ansi16.downgrade(Color::Yellow), Color::Yellow);
+    assert_eq!(
+        ColorDepth::Monochrome.downgrade(Color::Yellow),
+        Color::Reset
+    );
+}
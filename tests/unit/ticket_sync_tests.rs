@@ -0,0 +1,204 @@
+use std::sync::Mutex;
+
+use super::*;
+use crate::app::App;
+
+fn config() -> TicketSyncConfig {
+    ticket_sync_config_from_toml(
+        r#"
+[sync.linear]
+api_token = "lin_api_secret"
+
+[sync.jira]
+base_url = "https://example.atlassian.net/"
+email = "dev@example.com"
+api_token = "jira-secret"
+done_transition = "Resolved"
+"#,
+    )
+    .expect("config should parse")
+}
+
+fn recording_runner<'a>(
+    requests: &'a Mutex<Vec<HttpRequest>>,
+    responses: &'a Mutex<Vec<Value>>,
+) -> impl Fn(&HttpRequest) -> Result<Value, String> + 'a {
+    move |request| {
+        requests.lock().unwrap().push(request.clone());
+        Ok(responses.lock().unwrap().remove(0))
+    }
+}
+
+#[test]
+fn external_refs_parse_provider_and_normalize_key() {
+    assert_eq!(
+        ExternalRef::parse(" Linear:eng-123 ").expect("linear ref"),
+        ExternalRef {
+            provider: TicketProvider::Linear,
+            key: "ENG-123".to_string(),
+        }
+    );
+    assert_eq!(
+        ExternalRef::parse("jira:PROJ-42").unwrap().to_string(),
+        "jira:PROJ-42"
+    );
+    assert!(ExternalRef::parse("ENG-123").is_err());
+    assert!(ExternalRef::parse("github:1").is_err());
+    assert!(ExternalRef::parse("jira:PROJ-42/../x").is_err());
+}
+
+#[test]
+fn sync_config_defaults_state_names_and_treats_missing_tables_as_unconfigured() {
+    let config = config();
+    let linear = config.linear.as_ref().expect("linear configured");
+    assert_eq!(linear.in_progress_state, "In Progress");
+    assert_eq!(linear.done_state, "Done");
+    let jira = config.jira.as_ref().expect("jira configured");
+    assert_eq!(jira.done_transition, "Resolved");
+
+    let empty = ticket_sync_config_from_toml("[backend]\nselected = \"codex\"\n").unwrap();
+    assert_eq!(empty, TicketSyncConfig::default());
+    let err = fetch_ticket_with(
+        &empty,
+        &ExternalRef::parse("linear:ENG-1").unwrap(),
+        &|_: &HttpRequest| -> Result<Value, String> { panic!("no request expected") },
+    )
+    .expect_err("unconfigured provider should fail");
+    assert!(err.contains("[sync.linear]"));
+}
+
+#[test]
+fn linear_fetch_and_push_use_graphql_with_token_header() {
+    let requests = Mutex::new(Vec::new());
+    let responses = Mutex::new(vec![
+        json!({ "data": { "issue": {
+            "identifier": "ENG-7", "title": "Fix login", "description": "Steps...",
+            "url": "https://linear.app/acme/issue/ENG-7"
+        } } }),
+        json!({ "data": { "issue": { "id": "uuid-7", "team": { "states": { "nodes": [
+            { "id": "s-todo", "name": "Todo" },
+            { "id": "s-done", "name": "done" }
+        ] } } } } }),
+        json!({ "data": { "issueUpdate": { "success": true } } }),
+    ]);
+    let runner = recording_runner(&requests, &responses);
+    let config = config();
+    let reference = ExternalRef::parse("linear:ENG-7").unwrap();
+
+    let ticket = fetch_ticket_with(&config, &reference, &runner).expect("fetch");
+    assert_eq!(ticket.title, "Fix login");
+    assert_eq!(ticket.external_ref, "linear:ENG-7");
+    let state = push_ticket_status_with(&config, &reference, PlannerTaskStatusFile::Done, &runner)
+        .expect("push");
+    assert_eq!(state.as_deref(), Some("Done"));
+    assert_eq!(
+        push_ticket_status_with(
+            &config,
+            &reference,
+            PlannerTaskStatusFile::NeedsChanges,
+            &runner
+        ),
+        Ok(None)
+    );
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    assert!(
+        requests
+            .iter()
+            .all(|request| request.url == LINEAR_GRAPHQL_URL
+                && request.headers == vec!["Authorization: lin_api_secret".to_string()])
+    );
+    let update = requests[2].body.as_ref().unwrap();
+    assert_eq!(update["variables"]["id"], "uuid-7");
+    assert_eq!(update["variables"]["stateId"], "s-done");
+}
+
+#[test]
+fn jira_push_picks_configured_transition_and_reports_missing_one() {
+    let requests = Mutex::new(Vec::new());
+    let responses = Mutex::new(vec![
+        json!({ "transitions": [
+            { "id": "11", "name": "Start", "to": { "name": "In Progress" } },
+            { "id": "31", "name": "Resolve", "to": { "name": "Resolved" } }
+        ] }),
+        Value::Null,
+        json!({ "transitions": [] }),
+    ]);
+    let runner = recording_runner(&requests, &responses);
+    let config = config();
+    let reference = ExternalRef::parse("jira:PROJ-42").unwrap();
+
+    let state = push_ticket_status_with(&config, &reference, PlannerTaskStatusFile::Done, &runner)
+        .expect("push");
+    assert_eq!(state.as_deref(), Some("Resolved"));
+    let err = push_ticket_status_with(
+        &config,
+        &reference,
+        PlannerTaskStatusFile::InProgress,
+        &runner,
+    )
+    .expect_err("no transition available");
+    assert!(err.contains("In Progress"));
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(
+        requests[1].url,
+        "https://example.atlassian.net/rest/api/2/issue/PROJ-42/transitions"
+    );
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(
+        requests[1].user.as_deref(),
+        Some("dev@example.com:jira-secret")
+    );
+    assert_eq!(
+        requests[1].body,
+        Some(json!({ "transition": { "id": "31" } }))
+    );
+}
+
+#[test]
+fn imported_tickets_become_valid_task_trees_ahead_of_final_audit() {
+    let mut tasks = vec![PlannerTaskFileEntry {
+        id: "final-audit".to_string(),
+        title: "Final audit".to_string(),
+        details: "Audit everything".to_string(),
+        docs: Vec::new(),
+        kind: PlannerTaskKindFile::FinalAudit,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: None,
+        order: Some(5),
+        ..Default::default()
+    }];
+    let ticket = Ticket {
+        external_ref: "jira:PROJ-42".to_string(),
+        title: "Add export".to_string(),
+        description: String::new(),
+        url: "https://example.atlassian.net/browse/PROJ-42".to_string(),
+    };
+
+    assert_eq!(
+        append_ticket_tasks(&mut tasks, &[ticket.clone(), ticket.clone()]),
+        1
+    );
+    assert_eq!(append_ticket_tasks(&mut tasks, &[ticket]), 0);
+    assert_eq!(tasks.len(), 4);
+    let top = &tasks[1];
+    assert_eq!(top.id, "proj-42");
+    assert_eq!(top.title, "PROJ-42: Add export");
+    assert_eq!(top.order, Some(0));
+    assert_eq!(top.external_ref.as_deref(), Some("jira:PROJ-42"));
+    assert!(top.details.contains("Source ticket: https://"));
+
+    let mut app = App::default();
+    app.sync_planner_tasks_from_file(tasks.clone())
+        .expect("imported tasks should validate");
+    assert_eq!(
+        app.planner_tasks_for_file()
+            .iter()
+            .find(|task| task.id == "proj-42")
+            .and_then(|task| task.external_ref.clone())
+            .as_deref(),
+        Some("jira:PROJ-42")
+    );
+}
@@ -0,0 +1,90 @@
+use super::*;
+
+// 2026-02-16T12:00:00Z
+const NOON_FEB_16_2026: u64 = 1_771_243_200;
+
+#[test]
+fn relative_labels_step_from_seconds_to_days_then_fall_back_to_a_date() {
+    let now = NOON_FEB_16_2026;
+    let relative = |ago: u64, locale| format_relative(locale, TimeZone::UTC, now - ago, now);
+    assert_eq!(relative(30, Locale::En), "just now");
+    assert_eq!(relative(5 * 60 + 59, Locale::En), "5m ago");
+    assert_eq!(relative(2 * SECS_PER_HOUR, Locale::En), "2h ago");
+    assert_eq!(relative(3 * SECS_PER_DAY, Locale::En), "3d ago");
+    assert_eq!(relative(2 * SECS_PER_HOUR, Locale::Es), "hace 2 h");
+    assert_eq!(relative(10 * SECS_PER_DAY, Locale::En), "Feb 6, 2026");
+    assert_eq!(relative(10 * SECS_PER_DAY, Locale::Es), "6 feb 2026");
+    assert_eq!(
+        format_relative(Locale::En, TimeZone::UTC, now + 90, now),
+        "just now"
+    );
+}
+
+#[test]
+fn dates_honor_the_configured_offset() {
+    let zone = TimeZone::parse("+13:30").expect("offset");
+    assert_eq!(zone.label(), "UTC+13:30");
+    assert_eq!(
+        format_date_time(Locale::En, TimeZone::UTC, NOON_FEB_16_2026),
+        "Feb 16, 2026 12:00 UTC"
+    );
+    assert_eq!(
+        format_date_time(Locale::Es, zone, NOON_FEB_16_2026),
+        "17 feb 2026 01:30 UTC+13:30"
+    );
+    assert_eq!(
+        format_date(Locale::En, TimeZone::parse("-05").expect("offset"), 0),
+        "Dec 31, 1969"
+    );
+}
+
+#[test]
+fn time_zone_parsing_accepts_utc_and_offsets_only() {
+    assert_eq!(TimeZone::parse(" utc "), Some(TimeZone::UTC));
+    assert_eq!(TimeZone::parse("Z"), Some(TimeZone::UTC));
+    assert_eq!(
+        TimeZone::parse("UTC-0530").map(TimeZone::label),
+        Some("UTC-05:30".to_string())
+    );
+    assert_eq!(
+        TimeZone::parse("+9").map(TimeZone::label),
+        Some("UTC+09:00".to_string())
+    );
+    for invalid in ["Europe/Paris", "+", "++2", "+15:00", "+02:75", "2"] {
+        assert_eq!(TimeZone::parse(invalid), None, "{invalid}");
+    }
+}
+
+#[test]
+fn rfc3339_timestamps_round_trip_through_the_calendar() {
+    assert_eq!(
+        parse_rfc3339("2026-02-16T12:00:00Z"),
+        Some(NOON_FEB_16_2026)
+    );
+    assert_eq!(
+        parse_rfc3339("2026-02-16T14:00:00.250+02:00"),
+        Some(NOON_FEB_16_2026)
+    );
+    assert_eq!(
+        parse_rfc3339("2026-02-16"),
+        Some(NOON_FEB_16_2026 - 12 * SECS_PER_HOUR)
+    );
+    assert_eq!(parse_rfc3339("1970-01-01T00:00:00Z"), Some(0));
+    assert_eq!(
+        parse_rfc3339("2024-02-29T00:00:00Z").map(|secs| format_date(
+            Locale::En,
+            TimeZone::UTC,
+            secs
+        )),
+        Some("Feb 29, 2024".to_string())
+    );
+    assert_eq!(parse_rfc3339("yesterday"), None);
+    assert_eq!(parse_rfc3339("2026-13-01T00:00:00Z"), None);
+}
+
+#[test]
+fn durations_use_the_largest_two_units() {
+    assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+    assert_eq!(format_duration(Duration::from_secs(245)), "4m 05s");
+    assert_eq!(format_duration(Duration::from_secs(3_780)), "1h 03m");
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn old_lines_spill_to_disk_and_read_back_by_index() {
+    let mut transcript = Transcript::with_memory_lines(4);
+    for n in 0..11 {
+        transcript.push(format!("Agent: line {n}\nsecond row"));
+    }
+    assert_eq!(transcript.len(), 11);
+    assert_eq!(transcript.generation(), 11);
+    assert!(transcript.recent().len() < 8);
+    let spill_path = transcript.spill.as_ref().expect("spill file").path.clone();
+    assert!(spill_path.exists());
+    for n in 0..11 {
+        assert_eq!(
+            transcript.line(n).as_deref(),
+            Some(format!("Agent: line {n}\nsecond row").as_str())
+        );
+    }
+    assert_eq!(transcript.line(11), None);
+    assert_eq!(
+        transcript.recent().last().map(String::as_str),
+        Some("Agent: line 10\nsecond row")
+    );
+
+    drop(transcript);
+    assert!(!spill_path.exists());
+}
+
+#[test]
+fn short_transcripts_stay_in_memory() {
+    let mut transcript = Transcript::default();
+    transcript.push("You: hello".to_string());
+    assert!(transcript.spill.is_none());
+    assert_eq!(transcript.recent(), ["You: hello"]);
+    assert_eq!(transcript.line(0).as_deref(), Some("You: hello"));
+}
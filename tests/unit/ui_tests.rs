@@ -2,6 +2,7 @@ use super::*;
 use crate::agent::BackendKind;
 use crate::app::RightPaneMode;
 use crate::session_store::{PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile};
+use crate::terminal_graphics::{GraphicsProtocol, INLINE_IMAGE_ROWS};
 use ratatui::Terminal;
 use ratatui::backend::TestBackend;
 use ratatui::buffer::Buffer;
@@ -42,6 +43,7 @@ fn seed_execution_plan(app: &mut App) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-1".to_string(),
@@ -52,6 +54,7 @@ fn seed_execution_plan(app: &mut App) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top-1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-1-audit".to_string(),
@@ -62,6 +65,7 @@ fn seed_execution_plan(app: &mut App) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl-1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("seed plan should sync");
@@ -78,12 +82,90 @@ fn render_shows_three_panes_and_help_text() {
     assert!(text.contains("Tab/Shift+Tab"));
     assert!(text.contains("Ctrl+U/Ctrl+D"));
     assert!(text.contains("PgUp/PgDn"));
-    assert!(text.contains("Wheel"));
+    assert!(text.contains("F1/? keys"));
     assert!(text.contains("scroll"));
     assert!(text.contains("focus"));
     assert!(!text.contains("Click tabs at top to switch"));
 }
 
+#[test]
+fn help_overlay_lists_the_focused_context_first_and_filters_by_search() {
+    let mut app = App::default();
+    app.active_pane = Pane::Right;
+    app.set_right_pane_mode(RightPaneMode::TaskList);
+    app.open_help_overlay();
+    let text = render_text(&app, 120, 30);
+    assert!(text.contains("Key Bindings"));
+    assert!(text.contains("Task list (focused)"));
+    assert!(text.contains("Right-click, long press   Open the task menu"));
+
+    if let Some(help) = app.help_overlay_mut() {
+        help.push_query("zzz");
+    }
+    let text = render_text(&app, 120, 30);
+    assert!(text.contains("Search: zzz"));
+    assert!(text.contains("No key bindings match the search"));
+}
+
+#[test]
+fn cached_render_redraws_only_damaged_panes() {
+    let mut app = App::default();
+    let theme = Theme::default();
+    let mut cache = PaneRenderCache::default();
+    let mut draw = |app: &mut App| {
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).expect("test terminal");
+        let damage = app.take_pane_damage();
+        terminal
+            .draw(|frame| render_with_cache(frame, app, &theme, &mut cache, damage))
+            .expect("render should succeed");
+        buffer_to_string(terminal.backend().buffer())
+    };
+    let first = draw(&mut app);
+    assert_eq!(first, render_text(&app, 120, 30));
+
+    app.push_agent_message("Agent: kept out of the cached chat");
+    assert!(app.take_pane_damage().contains(Pane::LeftBottom));
+    app.push_subagent_output("Implementor#1: streamed line");
+    let mut left_top_only = PaneDamage::NONE;
+    left_top_only.insert(Pane::LeftTop);
+    let damage = app.take_pane_damage();
+    assert_eq!(damage, left_top_only);
+    app.mark_pane_damaged(Pane::LeftTop);
+    let partial = draw(&mut app);
+    assert!(partial.contains("streamed line"));
+    assert!(!partial.contains("kept out of the cached chat"));
+
+    app.mark_all_panes_damaged();
+    let full = draw(&mut app);
+    assert!(full.contains("kept out of the cached chat"));
+    assert_eq!(full, render_text(&app, 120, 30));
+}
+
+#[test]
+fn render_without_colors_marks_the_active_title_in_reverse_video() {
+    let app = App::default();
+    let backend = TestBackend::new(120, 30);
+    let mut terminal = Terminal::new(backend).expect("test terminal should initialize");
+    let theme = Theme::default().with_color_depth(ColorDepth::Monochrome);
+    terminal
+        .draw(|frame| render(frame, &app, &theme))
+        .expect("render should succeed");
+
+    let buffer = terminal.backend().buffer();
+    assert!(
+        buffer
+            .content
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+    );
+    assert!(
+        buffer
+            .content
+            .iter()
+            .any(|cell| cell.modifier.contains(Modifier::REVERSED))
+    );
+}
+
 #[test]
 fn render_shows_tabs_in_narrow_mode() {
     let app = App::default();
@@ -186,6 +268,20 @@ fn render_shows_resume_picker_overlay_when_open() {
     assert!(text.contains("Session A"));
 }
 
+#[test]
+fn render_resume_picker_fits_terminals_narrower_than_its_minimum_width() {
+    let mut app = App::default();
+    app.open_resume_picker(vec![crate::app::ResumeSessionOption {
+        session_dir: "/tmp/session-a".to_string(),
+        workspace: "/tmp/work-a".to_string(),
+        title: Some("Session A".to_string()),
+        created_at_label: None,
+        last_used_epoch_secs: 100,
+    }]);
+    let text = render_text(&app, 30, 20);
+    assert!(text.contains("Resume"));
+}
+
 #[test]
 fn render_shows_backend_picker_overlay_when_open() {
     let mut app = App::default();
@@ -307,6 +403,22 @@ fn render_status_line_shows_tests_off_across_modes() {
     }
 }
 
+#[test]
+fn render_status_bar_shows_execution_summary_above_help() {
+    let mut app = App::default();
+    app.set_runtime_backend("claude");
+    let text = render_text(&app, 120, 30);
+    let summary_row = text
+        .lines()
+        .position(|line| line.contains("Job: idle | Queued: 0 | Backend: claude | Tokens: 0 in"))
+        .expect("execution summary should render");
+    let help_row = text
+        .lines()
+        .position(|line| line.contains("TESTS: ON"))
+        .expect("help line should render");
+    assert_eq!(help_row, summary_row + 1);
+}
+
 #[test]
 fn render_shows_overlay_in_right_pane_on_wide_layout() {
     let mut app = App::default();
@@ -326,6 +438,54 @@ fn render_shows_docs_attach_overlay_when_running() {
     assert!(text.contains("Agent Chat | Working ["));
 }
 
+#[test]
+fn visible_chat_lines_match_the_full_layout_at_any_scroll() {
+    let mut app = App::default();
+    let messages = (0..40)
+        .map(|n| format!("Agent: message {n} {}", "word ".repeat(n % 7)))
+        .collect::<Vec<_>>();
+    let body = |lines: &[ChatDisplayLine]| {
+        lines
+            .iter()
+            .map(|line| line.body.clone())
+            .collect::<Vec<_>>()
+    };
+    for (n, message) in messages.iter().enumerate() {
+        app.push_agent_message(message.clone());
+        if n == 20 {
+            assert_eq!(
+                chat_row_count(&app, 24),
+                chat_display_lines(&messages[..21], 24).len()
+            );
+        }
+    }
+    let full = chat_display_lines(&messages, 24);
+    assert_eq!(chat_row_count(&app, 24), full.len());
+    for scroll in [0, 1, 17, full.len() - 5] {
+        assert_eq!(
+            body(&visible_chat_display_lines(&app, 24, scroll, 5)),
+            body(&full[scroll..scroll + 5])
+        );
+    }
+    assert_eq!(
+        body(&visible_chat_display_lines(&app, 30, 0, 3)),
+        body(&chat_display_lines(&messages, 30)[..3])
+    );
+}
+
+#[test]
+fn chat_reserves_rows_under_image_fallbacks_only_when_images_are_drawn() {
+    let mut app = App::default();
+    app.push_agent_message("Agent: Plan: ![Task graph](docs/graph.png)".to_string());
+    let lines = visible_chat_display_lines(&app, 60, 0, 40);
+    assert_eq!(lines[0].body, "Plan: [image: docs/graph.png] Task graph");
+    assert_eq!(chat_row_count(&app, 60), 1);
+
+    app.set_graphics_protocol(Some(GraphicsProtocol::Kitty));
+    assert_eq!(chat_row_count(&app, 60), 1 + INLINE_IMAGE_ROWS as usize);
+    let lines = visible_chat_display_lines(&app, 60, 0, 40);
+    assert!(lines[1..].iter().all(|line| line.body.is_empty()));
+}
 #[test]
 fn chat_render_shows_separators_and_agent_prefix() {
     let messages = vec!["You: hello".to_string(), "Codex: hi there".to_string()];
@@ -371,19 +531,30 @@ fn shared_word_wrap_grows_with_text_length() {
 
 #[test]
 fn input_box_metrics_caps_at_five_lines_and_scrolls_after() {
-    let (height, scroll) = input_box_metrics(3, 2, 20);
+    let (height, scroll) = input_box_metrics(3, 2, 10);
     assert_eq!(height, 5);
     assert_eq!(scroll, 0);
 
-    let (height, scroll) = input_box_metrics(5, 0, 20);
+    let (height, scroll) = input_box_metrics(5, 0, 10);
     assert_eq!(height, 7);
     assert_eq!(scroll, 0);
 
-    let (height, scroll) = input_box_metrics(8, 6, 20);
+    let (height, scroll) = input_box_metrics(8, 6, 10);
     assert_eq!(height, 7);
     assert_eq!(scroll, 3);
 }
 
+#[test]
+fn input_box_metrics_grows_to_half_the_chat_pane_for_long_input() {
+    let (height, scroll) = input_box_metrics(8, 6, 20);
+    assert_eq!(height, 10);
+    assert_eq!(scroll, 0);
+
+    let (height, scroll) = input_box_metrics(14, 12, 20);
+    assert_eq!(height, 12);
+    assert_eq!(scroll, 4);
+}
+
 #[test]
 fn input_box_metrics_respects_small_available_height() {
     let (height, scroll) = input_box_metrics(10, 9, 4);
@@ -599,6 +770,7 @@ fn right_pane_toggle_hit_test_returns_task_key_on_marker_click() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-1".to_string(),
@@ -609,6 +781,7 @@ fn right_pane_toggle_hit_test_returns_task_key_on_marker_click() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("task-1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-1-audit".to_string(),
@@ -619,6 +792,7 @@ fn right_pane_toggle_hit_test_returns_task_key_on_marker_click() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl-1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -661,6 +835,7 @@ fn right_pane_toggle_hit_test_returns_docs_toggle_key_on_docs_marker_click() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-1".to_string(),
@@ -670,11 +845,13 @@ fn right_pane_toggle_hit_test_returns_docs_toggle_key_on_docs_marker_click() {
                 title: "Doc".to_string(),
                 url: "https://example.com/doc".to_string(),
                 summary: "Summary".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::Implementor,
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("task-1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-1-audit".to_string(),
@@ -685,6 +862,7 @@ fn right_pane_toggle_hit_test_returns_docs_toggle_key_on_docs_marker_click() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl-1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -743,6 +921,36 @@ fn pane_hit_test_uses_tabs_in_narrow_layout() {
     assert_eq!(pane_hit_test(screen, 79, 1), None);
     assert_eq!(pane_hit_test(screen, 1, 10), None);
 }
+#[test]
+fn narrow_layout_threshold_and_compact_tabs_follow_config() {
+    assert_eq!(
+        narrow_width_from_toml("[ui]\nlocale = \"en\"\n").expect("width"),
+        DEFAULT_NARROW_SCREEN_WIDTH
+    );
+    assert_eq!(
+        narrow_width_from_toml("[ui]\nnarrow_width = 0\n").expect("width"),
+        0
+    );
+    let err = narrow_width_from_toml("[ui]\nnarrow_width = -5\n").expect_err("invalid");
+    assert!(err.to_string().contains("ui.narrow_width"));
+
+    let app = App::default();
+    let text = render_text(&app, 45, 16);
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].contains("Output"));
+    assert!(lines[0].contains("Chat"));
+    assert!(lines[0].contains("Tasks"));
+    assert!(!text.contains("Planner Markdown"));
+    assert_eq!(
+        pane_hit_test(Rect::new(0, 0, 45, 16), 30, 0),
+        Some(Pane::Right)
+    );
+
+    set_narrow_width(0);
+    assert!(!is_narrow_layout(Rect::new(0, 0, 80, 24)));
+    assert!(render_text(&app, 80, 24).contains("Sub-agent output stream."));
+    set_narrow_width(DEFAULT_NARROW_SCREEN_WIDTH);
+}
 
 fn find_scroll_button(screen: Rect, pane: Pane, target: ScrollButton) -> (u16, u16) {
     for y in screen.y..screen.y.saturating_add(screen.height) {
@@ -801,3 +1009,59 @@ fn pane_hit_test_ignores_status_area() {
     assert!(status.height > 0);
     assert_eq!(pane_hit_test(screen, status.x, status.y), None);
 }
+
+#[test]
+fn render_shows_file_tree_title_when_files_mode_enabled() {
+    let mut app = App::default();
+    app.toggle_file_tree_mode();
+    let text = render_text(&app, 120, 30);
+    assert!(text.contains("Files (* changed this session)"));
+    assert!(!text.contains("Worker Output"));
+}
+
+#[test]
+fn master_prompt_preview_max_scroll_counts_wrapped_prompt_lines() {
+    let mut app = App::default();
+    let screen = Rect::new(0, 0, 100, 20);
+    assert_eq!(master_prompt_preview_max_scroll(screen, &app), 0);
+
+    let prompt = (1..=40)
+        .map(|idx| format!("line {idx}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    app.open_master_prompt_preview(prompt, "msg".to_string(), false);
+    let (_, visible_lines) = master_prompt_preview_metrics(screen);
+    assert_eq!(master_prompt_preview_max_scroll(screen, &app), 40 - visible_lines);
+}
+
+#[test]
+fn render_resume_picker_previews_the_selected_session() {
+    let mut app = App::default();
+    app.open_resume_picker(vec![crate::app::ResumeSessionOption {
+        session_dir: "/tmp/session-a".to_string(),
+        workspace: "/tmp/work-a".to_string(),
+        title: Some("Session A".to_string()),
+        created_at_label: None,
+        last_used_epoch_secs: 100,
+    }]);
+    app.set_resume_picker_previews(std::collections::HashMap::from([(
+        "/tmp/session-a".to_string(),
+        crate::session_store::SessionPreview {
+            tasks_done: 2,
+            tasks_total: 5,
+            last_activity_epoch_secs: None,
+            planner_head: vec!["# Billing cleanup".to_string()],
+        },
+    )]));
+
+    let text = render_text(&app, 120, 30);
+    assert!(text.contains("Workspace: /tmp/work-a"));
+    assert!(text.contains("Tasks: 2/5 done"));
+    assert!(text.contains("# Billing cleanup"));
+
+    app.resume_picker_filter_push('q');
+    let filtered = render_text(&app, 120, 30);
+    assert!(filtered.contains("Filter: q"));
+    assert!(filtered.contains("No sessions match the filter"));
+    assert!(!filtered.contains("Tasks: 2/5 done"));
+}
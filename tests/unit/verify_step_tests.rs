@@ -0,0 +1,201 @@
+use super::*;
+use crate::session_store::{PlannerTaskFileEntry, PlannerTaskKindFile, PlannerTaskStatusFile};
+use crate::workflow::{TaskNode, VerifyGate};
+
+fn entry(id: &str, parent_id: Option<&str>, kind: PlannerTaskKindFile) -> PlannerTaskFileEntry {
+    PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("{id} title"),
+        details: format!("{id} details"),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent_id.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    }
+}
+
+/// A top-level task with one implementor that has an audit and an existing-test runner,
+/// checked by `make smoke` with a budget of one failed run.
+fn verified_workflow() -> Workflow {
+    let mut workflow = Workflow::default();
+    workflow.set_verify_gate(VerifyGate {
+        command: Some("make smoke".to_string()),
+        max_retries: 1,
+    });
+    workflow
+        .sync_planner_tasks_from_file(vec![
+            entry("top", None, PlannerTaskKindFile::Task),
+            entry("impl", Some("top"), PlannerTaskKindFile::Implementor),
+            entry("audit", Some("impl"), PlannerTaskKindFile::Auditor),
+            entry("tests", Some("impl"), PlannerTaskKindFile::TestRunner),
+        ])
+        .expect("sync should succeed");
+    workflow
+}
+
+fn node<'a>(nodes: &'a [TaskNode], external_id: &str) -> Option<&'a TaskNode> {
+    nodes.iter().find_map(|candidate| {
+        if candidate.external_id.as_deref() == Some(external_id) {
+            Some(candidate)
+        } else {
+            node(&candidate.children, external_id)
+        }
+    })
+}
+
+fn id_of(workflow: &Workflow, external_id: &str) -> u64 {
+    node(&workflow.tasks, external_id).expect("task").id
+}
+
+fn status_of(workflow: &Workflow, external_id: &str) -> TaskStatus {
+    node(&workflow.tasks, external_id).expect("task").status
+}
+
+#[test]
+fn the_gate_is_skipped_without_a_command_or_in_safe_mode() {
+    let mut workflow = verified_workflow();
+    workflow.set_verify_gate(VerifyGate::default());
+    let (top, implementor) = (id_of(&workflow, "top"), id_of(&workflow, "impl"));
+    let mut messages = Vec::new();
+    assert!(!queue_if_configured(
+        &mut workflow,
+        top,
+        implementor,
+        1,
+        &mut messages
+    ));
+    assert!(messages.is_empty());
+
+    let mut workflow = verified_workflow();
+    workflow.set_safe_mode(true);
+    let (top, implementor) = (id_of(&workflow, "top"), id_of(&workflow, "impl"));
+    assert!(!queue_if_configured(
+        &mut workflow,
+        top,
+        implementor,
+        1,
+        &mut messages
+    ));
+    assert_eq!(
+        messages,
+        [format!(
+            "System: Task #{top} tests complete; skipped its verify command in safe mode."
+        )]
+    );
+    assert!(workflow.queue.is_empty());
+    assert!(workflow.verify_attempts.is_empty());
+}
+
+#[test]
+fn each_queued_run_counts_as_a_new_attempt() {
+    let mut workflow = verified_workflow();
+    let (top, implementor) = (id_of(&workflow, "top"), id_of(&workflow, "impl"));
+    let mut messages = Vec::new();
+    for expected in [1, 2] {
+        assert!(queue_if_configured(
+            &mut workflow,
+            top,
+            implementor,
+            1,
+            &mut messages
+        ));
+        match workflow.queue.pop_back().expect("verify job").kind {
+            WorkerJobKind::Verify { attempt, .. } => assert_eq!(attempt, expected),
+            _ => panic!("expected a verify job"),
+        }
+    }
+    assert!(messages[1].contains("verify command queued (attempt 2)"));
+}
+
+#[test]
+fn a_passing_run_completes_the_branch() {
+    let mut workflow = verified_workflow();
+    let (top, implementor) = (id_of(&workflow, "top"), id_of(&workflow, "impl"));
+    for done in ["audit", "tests"] {
+        workflow.set_status(id_of(&workflow, done), TaskStatus::Done);
+    }
+    let mut messages = Vec::new();
+    on_completion(
+        &mut workflow,
+        top,
+        implementor,
+        1,
+        1,
+        &[],
+        true,
+        0,
+        &mut messages,
+    );
+
+    assert!(messages[0].contains("verify command passed on attempt 1"));
+    assert_eq!(status_of(&workflow, "impl"), TaskStatus::Done);
+    assert_eq!(status_of(&workflow, "top"), TaskStatus::Done);
+    assert!(workflow.queue.is_empty());
+}
+
+#[test]
+fn a_failing_run_reruns_the_tests_after_the_next_implementor_pass() {
+    let mut workflow = verified_workflow();
+    let (top, implementor) = (id_of(&workflow, "top"), id_of(&workflow, "impl"));
+    workflow.set_status(id_of(&workflow, "tests"), TaskStatus::Done);
+    let mut messages = Vec::new();
+    on_completion(
+        &mut workflow,
+        top,
+        implementor,
+        1,
+        1,
+        &["smoke: login page 500".to_string()],
+        false,
+        2,
+        &mut messages,
+    );
+
+    assert!(messages[0].contains("verify command failed; implementor pass 2 queued"));
+    assert_eq!(status_of(&workflow, "impl"), TaskStatus::NeedsChanges);
+    assert_eq!(status_of(&workflow, "tests"), TaskStatus::NeedsChanges);
+    match &workflow.queue.back().expect("queued retry").kind {
+        WorkerJobKind::Implementor { pass, feedback, .. } => {
+            assert_eq!(*pass, 2);
+            assert_eq!(
+                feedback.as_deref(),
+                Some("Verify command failed with code 2. Output:\nsmoke: login page 500")
+            );
+        }
+        _ => panic!("expected an implementor retry"),
+    }
+}
+
+#[test]
+fn a_run_past_the_retry_budget_is_recorded_and_execution_moves_on() {
+    let mut workflow = verified_workflow();
+    let (top, implementor) = (id_of(&workflow, "top"), id_of(&workflow, "impl"));
+    let mut messages = Vec::new();
+    on_completion(
+        &mut workflow,
+        top,
+        implementor,
+        2,
+        2,
+        &[],
+        false,
+        1,
+        &mut messages,
+    );
+
+    assert!(messages[0].contains("Max retries (1) reached; proceeding to next step."));
+    assert_eq!(status_of(&workflow, "impl"), TaskStatus::Done);
+    assert!(workflow.queue.is_empty());
+    let failure = workflow.recent_failures.last().expect("recorded failure");
+    assert_eq!(failure.attempts, 2);
+    assert_eq!(
+        failure.reason,
+        "Verify command failed with code 1 and no output."
+    );
+    assert_eq!(
+        failure.action_taken,
+        "Verify command retries exhausted; continued to next step."
+    );
+}
@@ -0,0 +1,50 @@
+use super::*;
+use std::time::Instant;
+
+#[test]
+fn a_notification_sent_before_waiting_ends_the_next_wait() {
+    notify();
+    let started = Instant::now();
+    assert!(wait(Duration::from_secs(5)));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn sending_on_a_channel_wakes_a_waiting_thread() {
+    let (tx, rx) = channel();
+    let sender = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        tx.send("line").expect("send");
+    });
+    let started = Instant::now();
+    while rx.try_recv().is_err() {
+        wait(Duration::from_secs(5));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+    sender.join().expect("sender thread");
+}
+
+#[test]
+fn file_watch_flags_a_watched_file_once_it_changes() {
+    let dir = std::env::temp_dir().join(format!(
+        "bob-file-watch-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("clock")
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    let path = dir.join("tasks.json");
+    let mut watch = FileWatch::default();
+    watch.watch(std::slice::from_ref(&path));
+    std::thread::sleep(FILE_WATCH_INTERVAL * 2);
+    assert!(!watch.take_changed());
+
+    std::fs::write(&path, "[]").expect("write watched file");
+    let started = Instant::now();
+    while !watch.take_changed() {
+        wait(FILE_WATCH_INTERVAL);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,129 @@
+use super::*;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::event_log::SessionEventRecorder;
+use crate::transcript::Transcript;
+
+fn open_temp_store(prefix: &str) -> (SessionStore, PathBuf) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let cwd = std::env::current_dir().expect("cwd");
+    let session_dir = std::env::temp_dir().join(format!("{prefix}-{now}"));
+    let store = SessionStore::open_existing(&cwd, &session_dir).expect("open existing store");
+    (store, session_dir)
+}
+
+/// Sends a GET and reads until the connection closes or the response contains `until`; the
+/// event stream never closes on its own.
+fn get(addr: SocketAddr, target: &str, until: &str) -> String {
+    request(addr, target, &format!("localhost:{}", addr.port()), until)
+}
+
+fn request(addr: SocketAddr, target: &str, host: &str, until: &str) -> String {
+    let mut stream = TcpStream::connect(addr).expect("connect");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .expect("read timeout");
+    write!(stream, "GET {target} HTTP/1.1\r\nHost: {host}\r\n\r\n").expect("send request");
+    let mut response = Vec::new();
+    let mut buffer = [0; 4096];
+    while !String::from_utf8_lossy(&response).contains(until) {
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(read) => response.extend_from_slice(&buffer[..read]),
+        }
+    }
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[test]
+fn parses_request_line_query_and_last_event_id() {
+    let head = parse_request_head(
+        "GET /api/events?after=7&x=1 HTTP/1.1\r\nHost: localhost\r\nlast-event-id: 12\r\n\r\n",
+    )
+    .expect("valid request");
+    assert_eq!(head.method, "GET");
+    assert_eq!(head.path, "/api/events");
+    assert_eq!(query_param(&head.query, "after"), Some("7"));
+    assert_eq!(query_param(&head.query, "missing"), None);
+    assert_eq!(head.last_event_id, Some(12));
+    assert_eq!(head.host.as_deref(), Some("localhost"));
+
+    assert_eq!(
+        parse_request_head("GET / HTTP/1.0\r\n\r\n")
+            .expect("no headers")
+            .last_event_id,
+        None
+    );
+    assert_eq!(parse_request_head("GET /\r\n\r\n"), None);
+    assert_eq!(parse_request_head(""), None);
+}
+
+#[test]
+fn only_the_loopback_host_and_port_are_accepted() {
+    assert!(is_dashboard_host(Some("127.0.0.1:8787"), 8787));
+    assert!(is_dashboard_host(Some("LOCALHOST:8787"), 8787));
+    assert!(!is_dashboard_host(Some("localhost:8788"), 8787));
+    assert!(!is_dashboard_host(Some("localhost"), 8787));
+    assert!(!is_dashboard_host(Some("attacker.example:8787"), 8787));
+    assert!(!is_dashboard_host(None, 8787));
+}
+
+#[test]
+fn server_sent_events_are_named_by_event_type_and_carry_their_seq() {
+    let entry = SessionEventFileEntry {
+        seq: 4,
+        at_epoch_ms: 10,
+        event: event_log::SessionEventKindFile::ChatMessage {
+            line: "You: hi".to_string(),
+        },
+    };
+    let formatted = format_server_sent_event(&entry);
+    assert!(formatted.starts_with("id: 4\nevent: chat_message\ndata: {"));
+    assert!(formatted.contains("\"line\":\"You: hi\""));
+    assert!(formatted.ends_with("}\n\n"));
+}
+
+#[test]
+fn serves_page_snapshot_and_event_stream_for_a_session() {
+    let (store, session_dir) = open_temp_store("web-ui-serve");
+    let mut recorder = SessionEventRecorder::default();
+    let mut transcript = Transcript::default();
+    transcript.push("You: build it".to_string());
+    recorder
+        .record_chat_lines(&store, &transcript)
+        .expect("record chat");
+    let server = DashboardServer::bind(&store, 0).expect("bind");
+    let addr = server.local_addr().expect("addr");
+    std::thread::spawn(move || server.serve());
+
+    let page = get(addr, "/", "</html>");
+    assert!(page.starts_with("HTTP/1.1 200 OK"));
+    assert!(page.contains("<title>agentbob dashboard</title>"));
+
+    let snapshot = get(addr, "/api/snapshot", "\"last_event_seq\":1}");
+    assert!(snapshot.starts_with("HTTP/1.1 200 OK"), "{snapshot}");
+    let body = snapshot.split_once("\r\n\r\n").expect("body").1;
+    let json: serde_json::Value = serde_json::from_str(body).expect("snapshot json");
+    assert_eq!(json["last_event_seq"], 1);
+    assert!(json["tasks"].is_array());
+
+    let stream = get(addr, "/api/events?after=0", "event: chat_message\n");
+    assert!(stream.contains("Content-Type: text/event-stream"));
+    assert!(stream.contains("id: 1\nevent: chat_message\n"), "{stream}");
+
+    assert!(get(addr, "/missing", "not found").starts_with("HTTP/1.1 404"));
+    let rebound = request(
+        addr,
+        "/api/snapshot",
+        &format!("attacker.example:{}", addr.port()),
+        "unknown host",
+    );
+    assert!(rebound.starts_with("HTTP/1.1 403"), "{rebound}");
+
+    std::fs::remove_dir_all(session_dir).ok();
+}
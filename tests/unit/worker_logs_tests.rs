@@ -0,0 +1,135 @@
+use super::*;
+use crate::workflow::WorkerRole;
+use std::time::UNIX_EPOCH;
+
+fn temp_dir(prefix: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "{prefix}-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should work")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+fn meta(top_task_key: &str, role: WorkerRole, pass: u8) -> ActiveJobMeta {
+    ActiveJobMeta {
+        role,
+        top_task_id: 1,
+        top_task_key: top_task_key.to_string(),
+        pass,
+    }
+}
+
+#[test]
+fn relative_path_uses_task_role_and_pass_with_safe_dir_name() {
+    assert_eq!(
+        worker_log_relative_path(&meta("task-1", WorkerRole::Implementor, 2)),
+        PathBuf::from("task-1/implementor-pass2.log")
+    );
+    assert_eq!(
+        worker_log_relative_path(&meta("../a b", WorkerRole::TestRunner, 1)),
+        PathBuf::from("___a_b/test_runner-pass1.log")
+    );
+}
+
+#[test]
+fn writer_rotates_past_size_limit_and_keeps_bounded_copies() {
+    let logs_dir = temp_dir("metaagent-worker-logs-rotate");
+    let relative = PathBuf::from("t1/auditor-pass1.log");
+    let mut writer = WorkerLogWriter::new(WorkerLogPolicy {
+        max_file_bytes: 10,
+        max_rotated_files: 2,
+        retention_days: 0,
+    });
+
+    for line in ["first-1", "second-2", "third-3", "fourth-4"] {
+        writer
+            .append(&logs_dir, &relative, line)
+            .expect("append line");
+    }
+    writer.close();
+
+    let path = logs_dir.join(&relative);
+    let read = |path: &Path| fs::read_to_string(path).expect("read log");
+    assert_eq!(read(&path), "fourth-4\n");
+    assert_eq!(read(&logs_dir.join("t1/auditor-pass1.log.1")), "third-3\n");
+    assert_eq!(read(&logs_dir.join("t1/auditor-pass1.log.2")), "second-2\n");
+    assert!(!logs_dir.join("t1/auditor-pass1.log.3").exists());
+
+    let labels = list_worker_logs(&logs_dir)
+        .expect("list logs")
+        .into_iter()
+        .map(|entry| entry.label)
+        .collect::<Vec<_>>();
+    assert_eq!(labels.len(), 3);
+    assert!(labels.contains(&"t1/auditor-pass1.log.2".to_string()));
+
+    fs::remove_dir_all(logs_dir).ok();
+}
+
+#[test]
+fn prune_removes_logs_past_retention_and_empty_task_dirs() {
+    let logs_dir = temp_dir("metaagent-worker-logs-prune");
+    fs::create_dir_all(logs_dir.join("t1")).expect("create task dir");
+    fs::write(logs_dir.join("t1/implementor-pass1.log"), "old\n").expect("write log");
+    fs::write(logs_dir.join("t1/notes.txt"), "kept\n").expect("write other file");
+    fs::create_dir_all(logs_dir.join("t2")).expect("create task dir");
+    fs::write(logs_dir.join("t2/auditor-pass1.log"), "old\n").expect("write log");
+
+    prune_expired_logs(
+        &logs_dir,
+        0,
+        SystemTime::now() + Duration::from_secs(365 * 86_400),
+    )
+    .expect("retention disabled");
+    assert_eq!(list_worker_logs(&logs_dir).expect("list logs").len(), 2);
+
+    prune_expired_logs(
+        &logs_dir,
+        1,
+        SystemTime::now() + Duration::from_secs(2 * 86_400),
+    )
+    .expect("prune");
+    assert!(list_worker_logs(&logs_dir).expect("list logs").is_empty());
+    assert!(logs_dir.join("t1/notes.txt").exists());
+    assert!(!logs_dir.join("t2").exists());
+
+    fs::remove_dir_all(logs_dir).ok();
+}
+
+#[test]
+fn final_pass_logs_keep_the_last_pass_of_each_role() {
+    let logs_dir = temp_dir("metaagent-worker-logs-final");
+    let task_dir = logs_dir.join("task-1");
+    fs::create_dir_all(&task_dir).expect("create task dir");
+    for name in [
+        "implementor-pass1.log",
+        "implementor-pass2.log",
+        "implementor-pass2.log.1",
+        "auditor-pass1.log",
+        "test_writer-pass10.log",
+        "test_writer-pass9.log",
+        "notes.txt",
+    ] {
+        fs::write(task_dir.join(name), "output\n").expect("write log");
+    }
+
+    assert_eq!(
+        final_pass_logs(&logs_dir, "task-1"),
+        vec![
+            task_dir.join("auditor-pass1.log"),
+            task_dir.join("implementor-pass2.log"),
+            task_dir.join("test_writer-pass10.log"),
+        ]
+    );
+    assert!(final_pass_logs(&logs_dir, "task-2").is_empty());
+    fs::remove_dir_all(logs_dir).ok();
+}
+#[test]
+fn listing_a_missing_logs_dir_is_empty() {
+    let logs_dir = std::env::temp_dir().join("metaagent-worker-logs-missing-dir");
+    assert!(list_worker_logs(&logs_dir).expect("list logs").is_empty());
+}
@@ -25,6 +25,7 @@ fn seed_single_default_task(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -35,6 +36,7 @@ fn seed_single_default_task(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -45,6 +47,7 @@ fn seed_single_default_task(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -55,6 +58,7 @@ fn seed_single_default_task(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -65,6 +69,7 @@ fn seed_single_default_task(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("seed plan should sync");
@@ -81,6 +86,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl1".to_string(),
@@ -91,6 +97,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl1-audit".to_string(),
@@ -101,6 +108,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw1".to_string(),
@@ -111,6 +119,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top1".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw1-runner".to_string(),
@@ -121,6 +130,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "top2".to_string(),
@@ -131,6 +141,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl2".to_string(),
@@ -141,6 +152,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top2".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl2-audit".to_string(),
@@ -151,6 +163,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl2".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw2".to_string(),
@@ -161,6 +174,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top2".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw2-runner".to_string(),
@@ -171,6 +185,7 @@ fn seed_two_default_tasks(wf: &mut Workflow, first_title: &str, second_title: &s
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw2".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("seed plan should sync");
@@ -187,6 +202,7 @@ fn seed_single_default_task_with_final_audit(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -197,6 +213,7 @@ fn seed_single_default_task_with_final_audit(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -207,6 +224,7 @@ fn seed_single_default_task_with_final_audit(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -217,6 +235,7 @@ fn seed_single_default_task_with_final_audit(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -227,6 +246,7 @@ fn seed_single_default_task_with_final_audit(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "fa".to_string(),
@@ -237,6 +257,7 @@ fn seed_single_default_task_with_final_audit(wf: &mut Workflow, title: &str) {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("seed plan should sync");
@@ -284,6 +305,7 @@ fn syncs_planner_tasks_from_file_entries() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "child".to_string(),
@@ -294,6 +316,7 @@ fn syncs_planner_tasks_from_file_entries() {
                 status: PlannerTaskStatusFile::InProgress,
                 parent_id: Some("parent".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "child-audit".to_string(),
@@ -304,6 +327,7 @@ fn syncs_planner_tasks_from_file_entries() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("child".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
         ])
         .expect("sync should succeed");
@@ -313,6 +337,33 @@ fn syncs_planner_tasks_from_file_entries() {
     assert!(lines.contains("Impl: Child"));
 }
 
+#[test]
+fn never_final_audit_policy_rejects_final_audit_tasks() {
+    let mut wf = Workflow::default();
+    wf.set_final_audit_policy(FinalAuditPolicy::Never);
+    let err = wf
+        .sync_planner_tasks_from_file(vec![PlannerTaskFileEntry {
+            id: "fa".to_string(),
+            title: "Final Audit".to_string(),
+            details: "final audit details".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::FinalAudit,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: None,
+            order: Some(0),
+            ..Default::default()
+        }])
+        .expect_err("final audit should be rejected");
+    assert!(err.contains("final_audit = \"never\""));
+    assert!(
+        wf.prepare_master_prompt("plan it", &PromptSections::default())
+            .contains("Final audit policy is never")
+    );
+
+    wf.set_final_audit_policy(FinalAuditPolicy::Manual);
+    seed_single_default_task_with_final_audit(&mut wf, "Task");
+}
+
 #[test]
 fn renders_nested_task_blocks_with_local_wrapping() {
     let mut wf = Workflow::default();
@@ -326,6 +377,7 @@ fn renders_nested_task_blocks_with_local_wrapping() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "c".to_string(),
@@ -336,6 +388,7 @@ fn renders_nested_task_blocks_with_local_wrapping() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("p".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "c-audit".to_string(),
@@ -346,6 +399,7 @@ fn renders_nested_task_blocks_with_local_wrapping() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("c".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -403,6 +457,140 @@ fn execution_queues_implementor_and_test_writer_jobs() {
     assert!(matches!(second.run, JobRun::AgentPrompt(_)));
 }
 
+#[test]
+fn audit_citations_are_passed_verbatim_to_the_implementor_fix_prompt() {
+    let mut wf = Workflow::default();
+    seed_single_default_task(&mut wf, "Do work");
+    wf.start_execution();
+    let _ = wf.start_next_job().expect("implementor");
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+
+    let audit = wf.start_next_job().expect("auditor");
+    match audit.run {
+        JobRun::AgentPrompt(prompt) => assert!(prompt.contains("`path:start-end`")),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
+    }
+    wf.append_active_output("FAIL".to_string());
+    wf.append_active_output("- `src/app.rs:10-12` drops the error".to_string());
+    let citations = crate::audit_citations::parse(wf.active_transcript());
+    wf.record_active_audit_citations(citations);
+    wf.finish_active_job(true, 0);
+
+    let retry = wf.start_next_job().expect("implementor retry");
+    match retry.run {
+        JobRun::AgentPrompt(prompt) => assert!(
+            prompt
+                .contains("Cited locations:\n- src/app.rs:10-12 (file not found in the workspace)")
+        ),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected implementor prompt")
+        }
+    }
+}
+
+#[test]
+fn top_task_workdir_is_validated_and_carried_by_its_jobs() {
+    let mut wf = Workflow::default();
+    let top = |workdir: &str| PlannerTaskFileEntry {
+        id: "top".to_string(),
+        title: "Backend work".to_string(),
+        details: "top details".to_string(),
+        docs: Vec::new(),
+        kind: PlannerTaskKindFile::Task,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: None,
+        order: Some(0),
+        workdir: Some(workdir.to_string()),
+        ..Default::default()
+    };
+    for bad in ["../outside", "/abs"] {
+        let err = wf
+            .sync_planner_tasks_from_file(vec![top(bad)])
+            .expect_err("workdir outside the workspace should be rejected");
+        assert!(err.contains("must be a relative path inside the workspace"));
+    }
+
+    seed_single_default_task(&mut wf, "Backend work");
+    let mut entries = wf.planner_tasks_for_file();
+    entries[0].workdir = Some(" backend ".to_string());
+    wf.sync_planner_tasks_from_file(entries)
+        .expect("workdir should sync");
+    assert_eq!(
+        wf.planner_tasks_for_file()[0].workdir.as_deref(),
+        Some("backend")
+    );
+
+    wf.start_execution();
+    let job = wf.start_next_job().expect("first job should start");
+    assert_eq!(job.workdir.as_deref(), Some("backend"));
+}
+
+#[test]
+fn audit_must_confirm_every_acceptance_criterion_before_passing() {
+    let mut wf = Workflow::default();
+    seed_single_default_task(&mut wf, "Export CSV");
+    let mut entries = wf.planner_tasks_for_file();
+    entries[1].acceptance_criteria = vec!["misplaced".to_string()];
+    let err = wf
+        .sync_planner_tasks_from_file(entries.clone())
+        .expect_err("criteria on a subtask should be rejected");
+    assert!(err.contains("only top-level tasks"));
+    entries[1].acceptance_criteria.clear();
+    entries[0].acceptance_criteria = vec![
+        "Rows are comma separated".to_string(),
+        " ".to_string(),
+        "Headers come first".to_string(),
+    ];
+    wf.sync_planner_tasks_from_file(entries)
+        .expect("criteria should sync");
+    assert_eq!(wf.planner_tasks_for_file()[0].acceptance_criteria.len(), 2);
+    wf.start_execution();
+
+    let prompt_of = |job: StartedJob| match job.run {
+        JobRun::AgentPrompt(prompt) => prompt,
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected an agent prompt")
+        }
+    };
+    let implementor = prompt_of(wf.start_next_job().expect("implementor"));
+    assert!(implementor.contains(
+        "Acceptance criteria (address every one; the audit checks each):\n\
+         AC1: Rows are comma separated\nAC2: Headers come first\n"
+    ));
+    wf.append_active_output("AC1: joined with commas".to_string());
+    wf.finish_active_job(true, 0);
+
+    let audit = prompt_of(wf.start_next_job().expect("auditor"));
+    assert!(audit.contains("`AC<n>: MET` or `AC<n>: NOT MET - <finding>`"));
+    wf.append_active_output("PASS".to_string());
+    wf.append_active_output("- AC1: MET".to_string());
+    wf.append_active_output("- AC2: NOT MET - header row is missing".to_string());
+    let messages = wf.finish_active_job(true, 0);
+    assert!(
+        messages
+            .iter()
+            .any(|message| message.contains("without confirming every acceptance criterion"))
+    );
+
+    let retry = prompt_of(wf.start_next_job().expect("implementor retry"));
+    assert!(retry.contains("Acceptance criteria not confirmed as met:\n- AC2: Headers come first"));
+    wf.finish_active_job(true, 0);
+    let _ = wf.start_next_job().expect("auditor retry");
+    wf.append_active_output("PASS".to_string());
+    wf.append_active_output("AC1: MET".to_string());
+    wf.append_active_output("AC2: met".to_string());
+    wf.finish_active_job(true, 0);
+    assert!(
+        wf.planner_tasks_for_file()
+            .iter()
+            .any(|entry| entry.kind == PlannerTaskKindFile::Auditor
+                && entry.status == PlannerTaskStatusFile::Done)
+    );
+}
+
 #[test]
 fn top_task_does_not_complete_until_all_implementor_branches_are_done() {
     let mut wf = Workflow::default();
@@ -416,6 +604,7 @@ fn top_task_does_not_complete_until_all_implementor_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-1".to_string(),
@@ -426,6 +615,7 @@ fn top_task_does_not_complete_until_all_implementor_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-1-audit".to_string(),
@@ -436,6 +626,7 @@ fn top_task_does_not_complete_until_all_implementor_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl-1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-2".to_string(),
@@ -446,6 +637,7 @@ fn top_task_does_not_complete_until_all_implementor_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-2-audit".to_string(),
@@ -456,6 +648,7 @@ fn top_task_does_not_complete_until_all_implementor_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl-2".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -504,6 +697,7 @@ fn top_task_does_not_complete_until_all_test_writer_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -514,6 +708,7 @@ fn top_task_does_not_complete_until_all_test_writer_branches_are_done() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -524,6 +719,7 @@ fn top_task_does_not_complete_until_all_test_writer_branches_are_done() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-1".to_string(),
@@ -534,6 +730,7 @@ fn top_task_does_not_complete_until_all_test_writer_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-1-runner".to_string(),
@@ -544,6 +741,7 @@ fn top_task_does_not_complete_until_all_test_writer_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw-1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-2".to_string(),
@@ -554,6 +752,7 @@ fn top_task_does_not_complete_until_all_test_writer_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(2),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-2-runner".to_string(),
@@ -564,6 +763,7 @@ fn top_task_does_not_complete_until_all_test_writer_branches_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw-2".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -632,6 +832,7 @@ fn top_level_tasks_run_sequentially_in_order() {
             }
             WorkerRole::TestRunner => wf.append_active_output("all passed".to_string()),
             WorkerRole::FinalAudit => wf.append_active_output("PASS".to_string()),
+            WorkerRole::Plugin => {}
         }
         let messages = wf.finish_active_job(true, 0);
         if messages
@@ -649,6 +850,344 @@ fn top_level_tasks_run_sequentially_in_order() {
     );
 }
 
+#[test]
+fn scoped_start_runs_only_the_selected_top_task_then_returns_to_planning() {
+    let mut wf = Workflow::default();
+    seed_two_default_tasks(&mut wf, "Task One", "Task Two");
+    assert!(wf.start_execution_scoped("missing").is_err());
+
+    let messages = wf
+        .start_execution_scoped("impl2")
+        .expect("subtask id scopes to its top-level task");
+    assert!(messages[0].contains("\"Task Two\" (contains impl2) only"));
+    assert_eq!(wf.execution_scope_task_id().as_deref(), Some("top2"));
+    assert!(
+        wf.right_pane_lines()
+            .contains(&"- status: running (task:top2 only)".to_string())
+    );
+
+    let mut finished = Vec::new();
+    for _ in 0..24 {
+        let Some(job) = wf.start_next_job() else {
+            break;
+        };
+        let top_key = wf.active_job_meta().expect("active job").top_task_key;
+        assert_eq!(top_key, "top2", "scoped run started work outside its task");
+        wf.append_active_output(match job.role {
+            WorkerRole::Auditor | WorkerRole::FinalAudit => "PASS".to_string(),
+            WorkerRole::TestRunner => "all passed".to_string(),
+            _ => "done".to_string(),
+        });
+        finished = wf.finish_active_job(true, 0);
+    }
+
+    assert!(
+        finished
+            .iter()
+            .any(|message| message.contains("Scoped run of \"Task Two\" is done")),
+        "{finished:?}"
+    );
+    assert!(!wf.execution_enabled());
+    assert_eq!(wf.execution_scope_task_id(), None);
+    let statuses = wf
+        .planner_tasks_for_file()
+        .into_iter()
+        .map(|task| (task.id, task.status))
+        .collect::<std::collections::HashMap<_, _>>();
+    assert_eq!(statuses["top2"], PlannerTaskStatusFile::Done);
+    assert_eq!(statuses["top1"], PlannerTaskStatusFile::Pending);
+    assert_eq!(statuses["impl1"], PlannerTaskStatusFile::Pending);
+    assert!(wf.start_execution_scoped("top2").is_err());
+}
+
+#[test]
+fn manual_skip_and_mark_done_close_subtrees_and_let_execution_move_on() {
+    let mut wf = Workflow::default();
+    seed_two_default_tasks(&mut wf, "Task One", "Task Two");
+    wf.start_execution();
+    assert!(
+        wf.resolve_task_manually("missing", ManualTaskAction::Skip)
+            .is_err()
+    );
+
+    let messages = wf
+        .resolve_task_manually("top1", ManualTaskAction::Skip)
+        .expect("queued task can be skipped");
+    assert_eq!(messages[0], "System: Skipped \"Task One\" (top1).");
+    let statuses = wf
+        .planner_tasks_for_file()
+        .into_iter()
+        .map(|task| (task.id, task.status))
+        .collect::<std::collections::HashMap<_, _>>();
+    for id in ["top1", "impl1", "impl1-audit", "tw1", "tw1-runner"] {
+        assert_eq!(statuses[id], PlannerTaskStatusFile::Done, "{id}");
+    }
+    assert!(
+        wf.rolling_context_entries()
+            .iter()
+            .any(|entry| entry.starts_with("User skipped task \"Task One\" (top1)"))
+    );
+    assert_eq!(
+        wf.drain_manual_interventions(),
+        vec![ManualIntervention {
+            task_id: "top1".to_string(),
+            title: "Task One".to_string(),
+            action: ManualTaskAction::Skip,
+        }]
+    );
+    assert!(
+        wf.resolve_task_manually("top1", ManualTaskAction::MarkDone)
+            .unwrap_err()
+            .contains("already done")
+    );
+
+    let job = wf
+        .start_next_job()
+        .expect("execution moved on to the next task");
+    assert_eq!(job.role, WorkerRole::Implementor);
+    assert_eq!(wf.active_job_meta().unwrap().top_task_key, "top2");
+    assert!(
+        wf.resolve_task_manually("impl2", ManualTaskAction::MarkDone)
+            .unwrap_err()
+            .contains("running job")
+    );
+    wf.resolve_task_manually("tw2", ManualTaskAction::MarkDone)
+        .expect("idle branch of a running task can be completed");
+    let statuses = wf
+        .planner_tasks_for_file()
+        .into_iter()
+        .map(|task| (task.id, task.status))
+        .collect::<std::collections::HashMap<_, _>>();
+    assert_eq!(statuses["tw2-runner"], PlannerTaskStatusFile::Done);
+    assert_ne!(statuses["top2"], PlannerTaskStatusFile::Done);
+    assert!(
+        wf.rolling_context_entries()
+            .last()
+            .unwrap()
+            .contains("handling it manually")
+    );
+}
+
+#[test]
+fn retry_resets_a_finished_task_and_runs_only_that_task() {
+    let mut wf = Workflow::default();
+    seed_two_default_tasks(&mut wf, "Task One", "Task Two");
+    wf.resolve_task_manually("top1", ManualTaskAction::Skip)
+        .expect("queued task can be skipped");
+    assert!(wf.retry_task("missing").is_err());
+
+    let messages = wf
+        .retry_task("impl1")
+        .expect("finished task can be retried");
+    assert_eq!(
+        messages[0],
+        "System: Reset \"Implementation\" (impl1) to pending for a retry."
+    );
+    let statuses = wf
+        .planner_tasks_for_file()
+        .into_iter()
+        .map(|task| (task.id, task.status))
+        .collect::<std::collections::HashMap<_, _>>();
+    for id in ["top1", "impl1", "impl1-audit"] {
+        assert_ne!(statuses[id], PlannerTaskStatusFile::Done, "{id}");
+    }
+    assert_eq!(statuses["tw1"], PlannerTaskStatusFile::Done);
+    assert_eq!(wf.execution_scope_task_id().as_deref(), Some("top1"));
+
+    let job = wf.start_next_job().expect("retried implementor starts");
+    assert_eq!(job.role, WorkerRole::Implementor);
+    assert_eq!(wf.active_job_meta().unwrap().top_task_key, "top1");
+    assert!(
+        wf.retry_task("top2")
+            .unwrap_err()
+            .contains("already running")
+    );
+}
+
+#[test]
+fn rerun_dispatches_the_last_recorded_job_with_the_edited_prompt() {
+    let mut wf = Workflow::default();
+    seed_two_default_tasks(&mut wf, "Task One", "Task Two");
+    wf.start_execution_scoped("top1")
+        .expect("scoped run starts");
+    let mut sent = 0;
+    while let Some(job) = wf.start_next_job() {
+        if matches!(job.run, JobRun::AgentPrompt(_)) {
+            sent += 1;
+            wf.record_active_prompt(&format!("prompt {sent}"));
+        }
+        let output = match job.role {
+            WorkerRole::Auditor => "PASS",
+            _ => "done",
+        };
+        wf.append_active_output(output.to_string());
+        wf.finish_active_job(true, 0);
+    }
+    assert!(
+        wf.last_prompt_for("top2")
+            .unwrap_err()
+            .contains("No worker prompt")
+    );
+    let last = wf
+        .last_prompt_for("impl1")
+        .expect("implementation prompts were recorded");
+    assert_eq!(
+        (last.label.as_str(), last.prompt.as_str()),
+        ("Auditor pass 1", "prompt 2")
+    );
+    assert!(wf.rerun_with_prompt("impl1", " ".to_string()).is_err());
+
+    let messages = wf
+        .rerun_with_prompt("impl1", "edited audit prompt".to_string())
+        .expect("idle workflow re-runs the job");
+    assert_eq!(
+        messages[0],
+        "System: Re-running the Auditor pass 1 of \"Task One\" (impl1) with the edited prompt."
+    );
+    assert_eq!(wf.execution_scope_task_id().as_deref(), Some("top1"));
+    let job = wf.start_next_job().expect("re-run job starts");
+    assert_eq!(job.role, WorkerRole::Auditor);
+    assert!(matches!(&job.run, JobRun::AgentPrompt(prompt) if prompt == "edited audit prompt"));
+    let statuses = wf
+        .planner_tasks_for_file()
+        .into_iter()
+        .map(|task| (task.id, task.status))
+        .collect::<std::collections::HashMap<_, _>>();
+    assert_eq!(statuses["tw1"], PlannerTaskStatusFile::Done);
+    assert!(
+        wf.rerun_with_prompt("impl1", "again".to_string())
+            .unwrap_err()
+            .contains("already running")
+    );
+}
+
+#[test]
+fn task_menu_targets_follow_right_pane_task_lines() {
+    let mut wf = Workflow::default();
+    seed_two_default_tasks(&mut wf, "Task One", "Task Two");
+
+    let view = wf.right_pane_block_view(60, &HashSet::new());
+    let title_line = view
+        .lines
+        .iter()
+        .position(|line| line == "  2. Task Two")
+        .expect("second top-level title should render");
+    assert_eq!(view.task_key_at(title_line), Some("top2"));
+    let nested = (0..view.lines.len())
+        .find(|index| view.task_key_at(*index) == Some("impl2"))
+        .expect("subtask box maps to its own task");
+    assert!(nested > title_line);
+
+    let target = wf
+        .task_menu_target("impl2")
+        .expect("planner task has a menu");
+    assert_eq!(target.top_task_id, "top2");
+    assert!(target.collapsible_details && target.accepts_docs && !target.done);
+    assert!(!wf.task_menu_target("top2").unwrap().collapsible_details);
+    assert!(!wf.task_menu_target("tw2-runner").unwrap().accepts_docs);
+    assert_eq!(wf.task_menu_target("missing"), None);
+}
+
+#[test]
+fn review_gate_holds_implementor_passes_until_the_user_decides() {
+    let mut wf = Workflow::default();
+    seed_single_default_task(&mut wf, "Do work");
+    wf.set_review_changes_enabled(true);
+    wf.start_execution();
+    assert_eq!(
+        wf.resolve_review(ReviewDecision::Approve).unwrap_err(),
+        "No implementor pass is awaiting review."
+    );
+
+    let job = wf.start_next_job().expect("implementor starts");
+    assert_eq!(job.role, WorkerRole::Implementor);
+    wf.append_active_output("done".to_string());
+    let messages = wf.finish_active_job(true, 0);
+    assert!(
+        messages[0].contains("pass 1 is ready for review"),
+        "{messages:?}"
+    );
+    assert_eq!(
+        wf.pending_review(),
+        Some(PendingReviewMeta {
+            top_task_id: job.top_task_id,
+            top_task_title: "Do work".to_string(),
+            pass: 1,
+        })
+    );
+    assert!(wf.execution_busy());
+    assert!(wf.start_next_job().is_none());
+
+    wf.resolve_review(ReviewDecision::RequestChanges("Use a trait.".to_string()))
+        .expect("pass sent back");
+    let retry = wf.start_next_job().expect("implementor retries");
+    assert_eq!(wf.active_job_meta().unwrap().pass, 2);
+    match retry.run {
+        JobRun::AgentPrompt(prompt) => {
+            assert!(prompt.contains("User review requested changes:\nUse a trait."));
+        }
+        other => panic!("unexpected run: {other:?}"),
+    }
+
+    wf.append_active_output("done again".to_string());
+    wf.finish_active_job(true, 0);
+    wf.resolve_review(ReviewDecision::Approve)
+        .expect("pass approved");
+    assert_eq!(wf.pending_review(), None);
+    let audit = wf.start_next_job().expect("audit follows approval");
+    assert_eq!(audit.role, WorkerRole::Auditor);
+}
+
+#[test]
+fn touching_protected_paths_fails_the_audit_without_running_the_auditor() {
+    let mut wf = Workflow::default();
+    seed_single_default_task(&mut wf, "Do work");
+    wf.set_protected_paths(ProtectedPaths::new([
+        "migrations/**".to_string(),
+        "*.lock".to_string(),
+    ]));
+    wf.start_execution();
+
+    let job = wf.start_next_job().expect("implementor");
+    match job.run {
+        JobRun::AgentPrompt(prompt) => {
+            assert!(prompt.contains("fails the audit): migrations/**, *.lock\n"));
+        }
+        other => panic!("unexpected run: {other:?}"),
+    }
+    for line in [
+        "FILES_CHANGED_BEGIN",
+        "- src/db.rs: added a column",
+        "- migrations/002_add_column.sql: new migration",
+        "FILES_CHANGED_END",
+    ] {
+        wf.append_active_output(line.to_string());
+    }
+    wf.record_active_worktree_changes(vec!["src/db.rs".to_string(), "Cargo.lock".to_string()]);
+    let messages = wf.finish_active_job(true, 0);
+    assert!(
+        messages.iter().any(|message| message.contains(
+            "modified protected paths: migrations/002_add_column.sql (migrations/**), Cargo.lock (*.lock)"
+        )),
+        "{messages:?}"
+    );
+
+    let retry = wf.start_next_job().expect("implementor retries");
+    assert_eq!(retry.role, WorkerRole::Implementor);
+    assert_eq!(wf.active_job_meta().unwrap().pass, 2);
+    match retry.run {
+        JobRun::AgentPrompt(prompt) => {
+            assert!(prompt.contains("Protected-path violation"), "{prompt}");
+        }
+        other => panic!("unexpected run: {other:?}"),
+    }
+
+    wf.append_active_output("- src/db.rs: reverted the migration".to_string());
+    wf.finish_active_job(true, 0);
+    let audit = wf.start_next_job().expect("clean pass goes to the auditor");
+    assert_eq!(audit.role, WorkerRole::Auditor);
+}
+
 #[test]
 fn deterministic_test_runner_loops_back_to_test_writer_on_failure() {
     let mut wf = Workflow::default();
@@ -682,10 +1221,49 @@ fn deterministic_test_runner_loops_back_to_test_writer_on_failure() {
             assert!(prompt.contains("Deterministic test run failed"));
             assert!(prompt.contains("test failure output"));
         }
-        JobRun::DeterministicTestRun => panic!("expected agent prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected agent prompt")
+        }
     }
 }
 
+#[test]
+fn test_runner_feedback_lists_failing_cases_from_structured_results() {
+    let transcript = vec!["test a ... FAILED".to_string()];
+    assert_eq!(
+        test_runner_feedback(&transcript, 101, None),
+        "Deterministic test run failed with code 101. Output:\ntest a ... FAILED"
+    );
+
+    let mut results = TestRunReport::new("cargo");
+    for (name, outcome) in [
+        ("a", crate::test_runners::TestOutcome::Failed),
+        ("b", crate::test_runners::TestOutcome::Passed),
+    ] {
+        results.cases.push(crate::test_runners::TestCaseResult {
+            name: name.to_string(),
+            outcome,
+            location: None,
+            message: None,
+        });
+    }
+    assert_eq!(
+        test_runner_feedback(&transcript, 101, Some(&results)),
+        "Deterministic test run failed with code 101 (cargo: 1 passed, 1 failed, 0 skipped).\n\
+         Failing tests:\n- a\n\
+         Output:\ntest a ... FAILED"
+    );
+
+    results.detailed = true;
+    results.cases[0].location = Some("src/lib.rs:4:9".to_string());
+    results.cases[0].message = Some("assertion failed\n  left: 1".to_string());
+    assert_eq!(
+        test_runner_feedback(&transcript, 101, Some(&results)),
+        "Deterministic test run failed with code 101 (cargo: 1 passed, 1 failed, 0 skipped).\n\
+         Failing tests:\n- a at src/lib.rs:4:9\n  assertion failed\n    left: 1"
+    );
+}
+
 #[test]
 fn tests_mode_off_sync_keeps_top_task_pending_until_runtime_children_exist() {
     let mut wf = Workflow::default();
@@ -700,6 +1278,7 @@ fn tests_mode_off_sync_keeps_top_task_pending_until_runtime_children_exist() {
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }])
     .expect("sync should succeed");
 
@@ -761,6 +1340,7 @@ fn test_writer_child_auditor_runs_before_test_runner_when_present() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -771,6 +1351,7 @@ fn test_writer_child_auditor_runs_before_test_runner_when_present() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -781,6 +1362,7 @@ fn test_writer_child_auditor_runs_before_test_runner_when_present() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -791,6 +1373,7 @@ fn test_writer_child_auditor_runs_before_test_runner_when_present() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-audit".to_string(),
@@ -801,6 +1384,7 @@ fn test_writer_child_auditor_runs_before_test_runner_when_present() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -811,6 +1395,7 @@ fn test_writer_child_auditor_runs_before_test_runner_when_present() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -836,7 +1421,9 @@ fn test_writer_child_auditor_runs_before_test_runner_when_present() {
             assert!(prompt.contains("do not run tests"));
             assert!(prompt.contains("do not execute/check shell commands"));
         }
-        JobRun::DeterministicTestRun => panic!("expected auditor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
     }
     wf.append_active_output("PASS".to_string());
     wf.finish_active_job(true, 0);
@@ -858,6 +1445,7 @@ fn failing_test_writer_child_auditor_queues_test_writer_retry() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -868,6 +1456,7 @@ fn failing_test_writer_child_auditor_queues_test_writer_retry() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -878,6 +1467,7 @@ fn failing_test_writer_child_auditor_queues_test_writer_retry() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -888,6 +1478,7 @@ fn failing_test_writer_child_auditor_queues_test_writer_retry() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-audit".to_string(),
@@ -898,6 +1489,7 @@ fn failing_test_writer_child_auditor_queues_test_writer_retry() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -908,6 +1500,7 @@ fn failing_test_writer_child_auditor_queues_test_writer_retry() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -934,7 +1527,9 @@ fn failing_test_writer_child_auditor_queues_test_writer_retry() {
             assert!(prompt.contains("Audit feedback"));
             assert!(prompt.contains("Missing edge-case assertions"));
         }
-        JobRun::DeterministicTestRun => panic!("expected test writer prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected test writer prompt")
+        }
     }
 }
 
@@ -951,6 +1546,7 @@ fn multiple_implementor_audits_run_sequentially() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -961,6 +1557,7 @@ fn multiple_implementor_audits_run_sequentially() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "audit-1".to_string(),
@@ -971,6 +1568,7 @@ fn multiple_implementor_audits_run_sequentially() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "audit-2".to_string(),
@@ -981,6 +1579,7 @@ fn multiple_implementor_audits_run_sequentially() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -1000,6 +1599,367 @@ fn multiple_implementor_audits_run_sequentially() {
     assert_eq!(second_audit.role, WorkerRole::Auditor);
 }
 
+fn seed_task_with_plugin_step(wf: &mut Workflow, plugin: &str) {
+    wf.sync_planner_tasks_from_file(vec![
+        PlannerTaskFileEntry {
+            id: "top".to_string(),
+            title: "Do work".to_string(),
+            details: "task details".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::Task,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: None,
+            order: Some(0),
+            ..Default::default()
+        },
+        PlannerTaskFileEntry {
+            id: "impl".to_string(),
+            title: "Implementation".to_string(),
+            details: "impl details".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::Implementor,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: Some("top".to_string()),
+            order: Some(0),
+            ..Default::default()
+        },
+        PlannerTaskFileEntry {
+            id: "audit".to_string(),
+            title: "Audit".to_string(),
+            details: "audit details".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::Auditor,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: Some("impl".to_string()),
+            order: Some(0),
+            ..Default::default()
+        },
+        PlannerTaskFileEntry {
+            id: "license".to_string(),
+            title: "License check".to_string(),
+            details: "Reject GPL dependencies".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::Plugin,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: Some("impl".to_string()),
+            order: Some(1),
+            plugin: Some(plugin.to_string()),
+            ..Default::default()
+        },
+    ])
+    .expect("sync should succeed");
+}
+
+fn run_implementor_and_audit(wf: &mut Workflow) {
+    let implementor = wf.start_next_job().expect("implementor");
+    assert_eq!(implementor.role, WorkerRole::Implementor);
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+    let audit = wf.start_next_job().expect("audit");
+    assert_eq!(audit.role, WorkerRole::Auditor);
+    wf.append_active_output("PASS".to_string());
+    wf.finish_active_job(true, 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn plugin_step_runs_after_audits_and_sends_failures_back_to_the_implementor() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("agentbob-wf-plugins-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create plugins dir");
+    let program = dir.join("license");
+    std::fs::write(
+        &program,
+        "#!/bin/sh\nread -r request\necho '{\"name\":\"license-check\",\"description\":\"Checks licenses\",\"capabilities\":[\"step\"]}'\n",
+    )
+    .expect("write plugin");
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+    let mut wf = Workflow::default();
+    wf.set_plugins(PluginRegistry::discover(&dir));
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(
+        wf.prepare_master_prompt("plan", &PromptSections::default())
+            .contains("Installed step plugins: license-check (Checks licenses).")
+    );
+    seed_task_with_plugin_step(&mut wf, "license-check");
+    wf.start_execution();
+    run_implementor_and_audit(&mut wf);
+
+    let step = wf.start_next_job().expect("plugin step");
+    assert_eq!(step.role, WorkerRole::Plugin);
+    match step.run {
+        JobRun::PluginStep {
+            plugin,
+            program: installed,
+            input,
+            task,
+        } => {
+            assert_eq!(plugin, "license-check");
+            assert_eq!(installed, Some(program));
+            assert_eq!(input, "Reject GPL dependencies");
+            assert_eq!(task.title, "Implementation");
+            assert_eq!(task.top_task_title, "Do work");
+        }
+        _ => panic!("expected plugin step"),
+    }
+    wf.append_active_output("Plugin license-check failed: GPL dependency".to_string());
+    let messages = wf.finish_active_job(false, 1);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("implementor pass 2 queued"))
+    );
+
+    let retry = wf.start_next_job().expect("implementor retry");
+    assert_eq!(retry.role, WorkerRole::Implementor);
+    match retry.run {
+        JobRun::AgentPrompt(prompt) => assert!(prompt.contains("GPL dependency")),
+        _ => panic!("expected agent prompt"),
+    }
+    wf.append_active_output("removed gpl crate".to_string());
+    wf.finish_active_job(true, 0);
+    let rerun = wf.start_next_job().expect("plugin step rerun");
+    assert_eq!(rerun.role, WorkerRole::Plugin);
+    let messages = wf.finish_active_job(true, 0);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("plugin step license-check passed on run 2"))
+    );
+}
+
+#[test]
+fn plugin_steps_for_missing_plugins_are_skipped_with_a_warning() {
+    let mut wf = Workflow::default();
+    assert!(
+        !wf.prepare_master_prompt("plan", &PromptSections::default())
+            .contains("step plugins")
+    );
+    seed_task_with_plugin_step(&mut wf, "license-check");
+    wf.start_execution();
+    let _ = wf.start_next_job().expect("implementor");
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+    let _ = wf.start_next_job().expect("audit");
+    wf.append_active_output("PASS".to_string());
+    let messages = wf.finish_active_job(true, 0);
+    assert!(messages.iter().any(|m| {
+        m.starts_with("System: Warning: skipped plugin step \"License check\".")
+            && m.contains("No plugin named \"license-check\" is installed.")
+    }));
+
+    let mut misplaced = Workflow::default();
+    let err = misplaced
+        .sync_planner_tasks_from_file(vec![PlannerTaskFileEntry {
+            id: "top".to_string(),
+            title: "Do work".to_string(),
+            details: "task details".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::Task,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: None,
+            order: Some(0),
+            plugin: Some("license-check".to_string()),
+            ..Default::default()
+        }])
+        .expect_err("only plugin tasks may name a plugin");
+    assert!(err.starts_with("Planner task top names a plugin"), "{err}");
+}
+
+#[test]
+fn verify_command_gates_the_branch_with_its_own_retry_budget() {
+    let entry = |id: &str, parent: Option<&str>, kind, verify: Option<&str>| PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("{id} title"),
+        details: "details".to_string(),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent.map(str::to_string),
+        order: Some(0),
+        verify: verify.map(str::to_string),
+        ..Default::default()
+    };
+    let mut wf = Workflow::default();
+    let err = wf
+        .sync_planner_tasks_from_file(vec![
+            entry("top", None, PlannerTaskKindFile::Task, None),
+            entry(
+                "impl",
+                Some("top"),
+                PlannerTaskKindFile::Implementor,
+                Some("make e2e"),
+            ),
+        ])
+        .expect_err("sub-task verify");
+    assert!(err.contains("Planner task impl has a verify command"));
+
+    wf.set_verify_gate(VerifyGate {
+        command: Some("make smoke".to_string()),
+        max_retries: 1,
+    });
+    wf.sync_planner_tasks_from_file(vec![
+        entry("top", None, PlannerTaskKindFile::Task, Some("make e2e")),
+        entry("impl", Some("top"), PlannerTaskKindFile::Implementor, None),
+        entry("audit", Some("impl"), PlannerTaskKindFile::Auditor, None),
+    ])
+    .expect("sync should succeed");
+    assert_eq!(
+        wf.planner_tasks_for_file()[0].verify.as_deref(),
+        Some("make e2e")
+    );
+    wf.start_execution();
+    run_implementor_and_audit(&mut wf);
+
+    let verify = wf.start_next_job().expect("verify");
+    assert_eq!(verify.role, WorkerRole::TestRunner);
+    match verify.run {
+        JobRun::Verify { command } => assert_eq!(command, "make e2e"),
+        _ => panic!("expected verify run"),
+    }
+    wf.append_active_output("e2e broke".to_string());
+    let messages = wf.finish_active_job(false, 2);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("verify command failed; implementor pass 2 queued"))
+    );
+
+    let retry = wf.start_next_job().expect("implementor retry");
+    assert_eq!(retry.role, WorkerRole::Implementor);
+    match retry.run {
+        JobRun::AgentPrompt(prompt) => {
+            assert!(prompt.contains("Verify command failed with code 2. Output:\ne2e broke"))
+        }
+        _ => panic!("expected agent prompt"),
+    }
+    wf.append_active_output("fixed".to_string());
+    let messages = wf.finish_active_job(true, 0);
+    assert!(messages.iter().any(|m| m.contains("(attempt 2)")));
+
+    let _ = wf.start_next_job().expect("verify rerun");
+    let messages = wf.finish_active_job(false, 2);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Max retries (1) reached; proceeding to next step."))
+    );
+    let failures = wf.drain_recent_failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].attempts, 2);
+    assert!(wf.start_next_job().is_none());
+}
+
+#[test]
+fn safe_mode_skips_plugin_steps_and_verify_commands() {
+    let mut wf = Workflow::default();
+    wf.set_safe_mode(true);
+    wf.set_verify_gate(VerifyGate {
+        command: Some("make smoke".to_string()),
+        max_retries: 1,
+    });
+    seed_task_with_plugin_step(&mut wf, "license-check");
+    wf.start_execution();
+    let _ = wf.start_next_job().expect("implementor");
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+    let _ = wf.start_next_job().expect("audit");
+    wf.append_active_output("PASS".to_string());
+    let messages = wf.finish_active_job(true, 0);
+    assert!(messages.iter().any(|m| {
+        m.starts_with("System: Warning: skipped plugin step \"License check\".")
+            && m.contains("off in safe mode")
+    }));
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("skipped its verify command in safe mode"))
+    );
+    assert!(wf.start_next_job().is_none());
+}
+#[test]
+fn estimates_are_validated_totalled_and_compared_with_worker_time() {
+    let entry =
+        |id: &str, parent: Option<&str>, kind, estimate: Option<&str>| PlannerTaskFileEntry {
+            id: id.to_string(),
+            title: format!("{id} title"),
+            details: "details".to_string(),
+            docs: Vec::new(),
+            kind,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: parent.map(str::to_string),
+            order: Some(0),
+            estimate: estimate.map(str::to_string),
+            ..Default::default()
+        };
+    let mut wf = Workflow::default();
+    let err = wf
+        .sync_planner_tasks_from_file(vec![entry(
+            "top",
+            None,
+            PlannerTaskKindFile::Task,
+            Some("XL"),
+        )])
+        .expect_err("invalid estimate");
+    assert_eq!(
+        err,
+        "Planner task top has an invalid estimate \"XL\" must be S, M, L, or hours such as \"4h\""
+    );
+    let err = wf
+        .sync_planner_tasks_from_file(vec![
+            entry("top", None, PlannerTaskKindFile::Task, None),
+            entry(
+                "impl",
+                Some("top"),
+                PlannerTaskKindFile::Implementor,
+                Some("2h"),
+            ),
+        ])
+        .expect_err("sub-task estimate");
+    assert!(
+        err.starts_with("Planner task impl has an estimate"),
+        "{err}"
+    );
+
+    let mut second = entry("second", None, PlannerTaskKindFile::Task, None);
+    second.order = Some(1);
+    wf.sync_planner_tasks_from_file(vec![
+        entry("top", None, PlannerTaskKindFile::Task, Some(" 2h ")),
+        entry("impl", Some("top"), PlannerTaskKindFile::Implementor, None),
+        entry("audit", Some("impl"), PlannerTaskKindFile::Auditor, None),
+        second,
+    ])
+    .expect("sync");
+    assert_eq!(
+        wf.planner_tasks_for_file()[0].estimate.as_deref(),
+        Some("2h")
+    );
+    assert_eq!(
+        wf.plan_estimate_total().as_deref(),
+        Some("2h (1 task not estimated)")
+    );
+    assert!(
+        wf.right_pane_lines()
+            .join("\n")
+            .contains("Estimates\n- total: 2h (1 task not estimated)")
+    );
+    let view = wf.right_pane_block_view(80, &HashSet::new());
+    assert!(
+        view.lines
+            .iter()
+            .any(|line| line == "  1. top title [est. 2h]")
+    );
+
+    wf.start_execution();
+    let _ = wf.start_next_job().expect("implementor");
+    wf.record_worker_elapsed(Duration::from_secs(3 * 3_600));
+    assert_eq!(
+        wf.estimate_comparisons(),
+        ["top title: estimated 2h, took 3h 00m (+50%)"]
+    );
+}
+
 #[test]
 fn exhausted_implementor_audit_moves_to_next_audit() {
     let mut wf = Workflow::default();
@@ -1013,6 +1973,7 @@ fn exhausted_implementor_audit_moves_to_next_audit() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -1023,6 +1984,7 @@ fn exhausted_implementor_audit_moves_to_next_audit() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "audit-1".to_string(),
@@ -1033,6 +1995,7 @@ fn exhausted_implementor_audit_moves_to_next_audit() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "audit-2".to_string(),
@@ -1043,6 +2006,7 @@ fn exhausted_implementor_audit_moves_to_next_audit() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -1167,7 +2131,9 @@ fn test_fix_loop_stops_after_five_failed_test_runs() {
         JobRun::AgentPrompt(prompt) => {
             assert!(prompt.contains("Remove the failing tests completely"));
         }
-        JobRun::DeterministicTestRun => panic!("expected cleanup writer prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected cleanup writer prompt")
+        }
     }
     wf.append_active_output("Removed failing tests".to_string());
     wf.finish_active_job(true, 0);
@@ -1180,6 +2146,44 @@ fn test_fix_loop_stops_after_five_failed_test_runs() {
     assert_eq!(failures[0].attempts, 5);
 }
 
+#[test]
+fn flaky_test_run_is_logged_and_sends_flaky_feedback_to_the_test_writer() {
+    let mut wf = Workflow::default();
+    seed_single_default_task(&mut wf, "Do work");
+    wf.start_execution();
+    for output in ["implemented", "wrote tests", "PASS"] {
+        let _ = wf.start_next_job().expect("pre-test job");
+        wf.append_active_output(output.to_string());
+        wf.finish_active_job(true, 0);
+    }
+
+    let runner = wf.start_next_job().expect("test runner");
+    assert_eq!(runner.role, WorkerRole::TestRunner);
+    wf.append_active_output("test timing ... FAILED".to_string());
+    wf.append_active_output("test timing ... ok".to_string());
+    wf.mark_active_test_run_flaky();
+    let messages = wf.finish_active_job(false, 101);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("tests are flaky; test-writer pass 2 queued"))
+    );
+
+    let failures = wf.drain_recent_failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].kind, WorkflowFailureKind::FlakyTest);
+    assert_eq!(failures[0].attempts, 1);
+    assert!(failures[0].reason.contains("test timing ... FAILED"));
+
+    let writer = wf.start_next_job().expect("test writer retry");
+    let JobRun::AgentPrompt(prompt) = writer.run else {
+        panic!("expected test writer prompt");
+    };
+    assert!(prompt.contains("so the tests are flaky"));
+    assert!(prompt.contains("Output of both runs:\ntest timing ... FAILED\ntest timing ... ok"));
+    assert!(!prompt.contains("Deterministic test run failed with code 101. Output:"));
+}
+
 #[test]
 fn auditor_output_is_forwarded_to_implementor_retry_prompt() {
     let mut wf = Workflow::default();
@@ -1196,15 +2200,74 @@ fn auditor_output_is_forwarded_to_implementor_retry_prompt() {
     let messages = wf.finish_active_job(true, 0);
     assert!(messages.iter().any(|m| m.contains("audit requested fixes")));
 
-    let retry = wf.start_next_job().expect("implementor retry");
-    assert_eq!(retry.role, WorkerRole::Implementor);
-    match retry.run {
-        JobRun::AgentPrompt(prompt) => {
-            assert!(prompt.contains("Audit feedback"));
-            assert!(prompt.contains("Issue: missing edge-case handling"));
+    let retry = wf.start_next_job().expect("implementor retry");
+    assert_eq!(retry.role, WorkerRole::Implementor);
+    match retry.run {
+        JobRun::AgentPrompt(prompt) => {
+            assert!(prompt.contains("Audit feedback"));
+            assert!(prompt.contains("Issue: missing edge-case handling"));
+        }
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected implementor prompt")
+        }
+    }
+}
+
+#[test]
+fn model_escalation_message_follows_the_locale() {
+    let mut wf = Workflow::default();
+    wf.set_locale(Locale::Es);
+    seed_single_default_task(&mut wf, "Do work");
+    wf.start_execution();
+    let _ = wf.start_next_job().expect("implementor");
+
+    let message = wf
+        .record_model_escalation("large-smart", "large-genius")
+        .expect("escalation message");
+    assert!(message.starts_with("System: La pasada 1 del implementador"));
+    assert!(message.contains("del perfil de modelo large-smart a large-genius"));
+}
+
+#[test]
+fn implementor_escalates_after_the_same_audit_fails_twice() {
+    let mut wf = Workflow::default();
+    seed_single_default_task(&mut wf, "Do work");
+    wf.start_execution();
+
+    let mut escalations = Vec::new();
+    for _ in 0..3 {
+        let implementor = wf.start_next_job().expect("implementor");
+        escalations.push(implementor.escalation);
+        if implementor.escalation > 0 {
+            let message = wf
+                .record_model_escalation("large-smart", "large-genius")
+                .expect("escalation message");
+            assert!(message.contains("escalated from model profile large-smart to large-genius"));
         }
-        JobRun::DeterministicTestRun => panic!("expected implementor prompt"),
+        wf.append_active_output("implemented".to_string());
+        wf.finish_active_job(true, 0);
+        let _ = wf.start_next_job().expect("auditor");
+        wf.append_active_output("FAIL\nIssue: still broken".to_string());
+        wf.finish_active_job(true, 0);
     }
+    let implementor = wf.start_next_job().expect("implementor");
+    escalations.push(implementor.escalation);
+    assert_eq!(escalations, vec![0, 0, 1, 2]);
+
+    assert!(wf.rolling_context_entries().iter().any(|entry| {
+        entry.contains("Task #1 implementor pass 3 escalated from model profile large-smart")
+    }));
+    assert_eq!(
+        wf.task_usage_stats("top").map(|stats| stats.escalations),
+        Some(1)
+    );
+
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+    let _ = wf.start_next_job().expect("auditor");
+    wf.append_active_output("PASS".to_string());
+    wf.finish_active_job(true, 0);
+    assert!(wf.implementor_escalations.is_empty());
 }
 
 #[test]
@@ -1235,7 +2298,9 @@ fn implementor_changed_files_summary_is_forwarded_to_auditor_prompt() {
             assert!(prompt.contains("- src/app.rs: added state transition for command handling"));
             assert!(prompt.contains("- src/ui.rs: updated rendering path for task block layout"));
         }
-        JobRun::DeterministicTestRun => panic!("expected auditor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
     }
 }
 
@@ -1329,7 +2394,9 @@ fn audit_retry_limit_stops_after_four_failed_audits() {
                     assert!(prompt.contains("truly critical blockers"));
                 }
             }
-            JobRun::DeterministicTestRun => panic!("expected auditor prompt"),
+            JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+                panic!("expected auditor prompt")
+            }
         }
         wf.append_active_output("FAIL".to_string());
         wf.append_active_output("- Critical blocker still present".to_string());
@@ -1385,6 +2452,7 @@ fn implementor_owned_test_runner_runs_after_audits_when_present() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl1".to_string(),
@@ -1395,6 +2463,7 @@ fn implementor_owned_test_runner_runs_after_audits_when_present() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("t1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "runner1".to_string(),
@@ -1405,6 +2474,7 @@ fn implementor_owned_test_runner_runs_after_audits_when_present() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl1".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "audit1".to_string(),
@@ -1415,6 +2485,7 @@ fn implementor_owned_test_runner_runs_after_audits_when_present() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -1453,6 +2524,7 @@ fn implementor_owned_test_runner_exhaustion_records_failure_and_continues_to_nex
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl1".to_string(),
@@ -1463,6 +2535,7 @@ fn implementor_owned_test_runner_exhaustion_records_failure_and_continues_to_nex
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("t1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "runner1".to_string(),
@@ -1473,6 +2546,7 @@ fn implementor_owned_test_runner_exhaustion_records_failure_and_continues_to_nex
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl1".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "audit1".to_string(),
@@ -1483,6 +2557,7 @@ fn implementor_owned_test_runner_exhaustion_records_failure_and_continues_to_nex
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl1".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -1567,6 +2642,7 @@ fn sync_rejects_tasks_missing_details() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         }])
         .expect_err("missing details should fail");
     assert!(err.contains("non-empty details"));
@@ -1589,6 +2665,7 @@ fn sync_rejects_reload_while_execution_has_active_or_queued_work() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         }])
         .expect_err("reload should be blocked while execution is busy");
     assert!(err.contains("Cannot reload planner tasks while execution is enabled"));
@@ -1611,6 +2688,7 @@ fn sync_allows_reload_when_execution_is_enabled_but_idle() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         }])
         .expect("reload should succeed when execution is idle");
     assert_eq!(count, 1);
@@ -1629,6 +2707,7 @@ fn auto_generated_subtasks_include_non_empty_details_for_reload() {
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }])
     .expect("seed plan should sync");
 
@@ -1666,6 +2745,7 @@ fn mid_run_snapshot_with_auto_generated_branches_is_reloadable() {
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }])
     .expect("seed plan should sync");
 
@@ -1704,6 +2784,7 @@ fn sync_rejects_implementor_without_auditor() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -1714,6 +2795,7 @@ fn sync_rejects_implementor_without_auditor() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
         ])
         .expect_err("should reject missing auditor");
@@ -1734,6 +2816,7 @@ fn sync_rejects_implementor_test_runner_before_audit() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -1744,6 +2827,7 @@ fn sync_rejects_implementor_test_runner_before_audit() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-runner".to_string(),
@@ -1754,6 +2838,7 @@ fn sync_rejects_implementor_test_runner_before_audit() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-audit".to_string(),
@@ -1764,6 +2849,7 @@ fn sync_rejects_implementor_test_runner_before_audit() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
         ])
         .expect_err("should reject runner before audit");
@@ -1784,6 +2870,7 @@ fn sync_rejects_test_writer_without_test_runner() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -1794,6 +2881,7 @@ fn sync_rejects_test_writer_without_test_runner() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-audit".to_string(),
@@ -1804,6 +2892,7 @@ fn sync_rejects_test_writer_without_test_runner() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "tw".to_string(),
@@ -1814,6 +2903,7 @@ fn sync_rejects_test_writer_without_test_runner() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
         ])
         .expect_err("should reject missing test runner");
@@ -1834,6 +2924,7 @@ fn sync_rejects_nested_test_writer_groups() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -1844,6 +2935,7 @@ fn sync_rejects_nested_test_writer_groups() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-audit".to_string(),
@@ -1854,6 +2946,7 @@ fn sync_rejects_nested_test_writer_groups() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "tests-parent".to_string(),
@@ -1864,6 +2957,7 @@ fn sync_rejects_nested_test_writer_groups() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "tests-parent-runner".to_string(),
@@ -1874,6 +2968,7 @@ fn sync_rejects_nested_test_writer_groups() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("tests-parent".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "tests-child".to_string(),
@@ -1884,6 +2979,7 @@ fn sync_rejects_nested_test_writer_groups() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("tests-parent".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "tests-child-runner".to_string(),
@@ -1894,6 +2990,7 @@ fn sync_rejects_nested_test_writer_groups() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("tests-child".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
         ])
         .expect_err("should reject nested test writer grouping");
@@ -1914,6 +3011,7 @@ fn sync_rejects_nested_implementor_branches() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-root".to_string(),
@@ -1924,6 +3022,7 @@ fn sync_rejects_nested_implementor_branches() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-root-audit".to_string(),
@@ -1934,6 +3033,7 @@ fn sync_rejects_nested_implementor_branches() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl-root".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-nested".to_string(),
@@ -1944,6 +3044,7 @@ fn sync_rejects_nested_implementor_branches() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl-root".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-nested-audit".to_string(),
@@ -1954,6 +3055,7 @@ fn sync_rejects_nested_implementor_branches() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl-nested".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
         ])
         .expect_err("should reject nested implementor branch");
@@ -1975,6 +3077,7 @@ fn sync_rejects_nested_final_audit_task() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -1985,6 +3088,7 @@ fn sync_rejects_nested_final_audit_task() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-audit".to_string(),
@@ -1995,6 +3099,7 @@ fn sync_rejects_nested_final_audit_task() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "nested-final".to_string(),
@@ -2005,6 +3110,7 @@ fn sync_rejects_nested_final_audit_task() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
         ])
         .expect_err("should reject nested final audit task");
@@ -2026,6 +3132,7 @@ fn sync_rejects_auditor_with_invalid_parent_kind() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -2036,6 +3143,7 @@ fn sync_rejects_auditor_with_invalid_parent_kind() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-audit".to_string(),
@@ -2046,6 +3154,7 @@ fn sync_rejects_auditor_with_invalid_parent_kind() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "orphan-audit".to_string(),
@@ -2056,6 +3165,7 @@ fn sync_rejects_auditor_with_invalid_parent_kind() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
         ])
         .expect_err("should reject auditor parent kind");
@@ -2077,6 +3187,7 @@ fn sync_rejects_test_runner_with_invalid_parent_kind() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -2087,6 +3198,7 @@ fn sync_rejects_test_runner_with_invalid_parent_kind() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-audit".to_string(),
@@ -2097,6 +3209,7 @@ fn sync_rejects_test_runner_with_invalid_parent_kind() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "orphan-runner".to_string(),
@@ -2107,6 +3220,7 @@ fn sync_rejects_test_runner_with_invalid_parent_kind() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
         ])
         .expect_err("should reject test-runner parent kind");
@@ -2128,6 +3242,7 @@ fn sync_rejects_multiple_test_runners_under_implementor() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -2138,6 +3253,7 @@ fn sync_rejects_multiple_test_runners_under_implementor() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-audit".to_string(),
@@ -2148,6 +3264,7 @@ fn sync_rejects_multiple_test_runners_under_implementor() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-runner-1".to_string(),
@@ -2158,6 +3275,7 @@ fn sync_rejects_multiple_test_runners_under_implementor() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-runner-2".to_string(),
@@ -2168,6 +3286,7 @@ fn sync_rejects_multiple_test_runners_under_implementor() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(2),
+                ..Default::default()
             },
         ])
         .expect_err("should reject multiple implementor test runners");
@@ -2189,6 +3308,7 @@ fn sync_rejects_multiple_test_runners_under_test_writer() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: None,
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl".to_string(),
@@ -2199,6 +3319,7 @@ fn sync_rejects_multiple_test_runners_under_test_writer() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "impl-audit".to_string(),
@@ -2209,6 +3330,7 @@ fn sync_rejects_multiple_test_runners_under_test_writer() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("impl".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "tw".to_string(),
@@ -2219,6 +3341,7 @@ fn sync_rejects_multiple_test_runners_under_test_writer() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("top".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "tw-runner-1".to_string(),
@@ -2229,6 +3352,7 @@ fn sync_rejects_multiple_test_runners_under_test_writer() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("tw".to_string()),
                 order: Some(0),
+                ..Default::default()
             },
             PlannerTaskFileEntry {
                 id: "tw-runner-2".to_string(),
@@ -2239,6 +3363,7 @@ fn sync_rejects_multiple_test_runners_under_test_writer() {
                 status: PlannerTaskStatusFile::Pending,
                 parent_id: Some("tw".to_string()),
                 order: Some(1),
+                ..Default::default()
             },
         ])
         .expect_err("should reject multiple test-writer test runners");
@@ -2258,7 +3383,9 @@ fn implementor_and_auditor_prompts_include_test_guardrails() {
             assert!(prompt.contains("Implementation details:"));
             assert!(prompt.contains("implementor details"));
         }
-        JobRun::DeterministicTestRun => panic!("expected implementor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected implementor prompt")
+        }
     }
 
     wf.append_active_output("implemented".to_string());
@@ -2273,8 +3400,42 @@ fn implementor_and_auditor_prompts_include_test_guardrails() {
             assert!(prompt.contains("do not run tests"));
             assert!(prompt.contains("do not execute/check shell commands"));
         }
-        JobRun::DeterministicTestRun => panic!("expected auditor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
+    }
+}
+
+#[test]
+fn auditor_prompts_include_session_audit_rubric_when_set() {
+    let mut wf = Workflow::default();
+    wf.set_audit_rubric(Some("  Every handler validates its input.  ".to_string()));
+    seed_single_default_task(&mut wf, "Do work");
+    wf.start_execution();
+    let implementor = wf.start_next_job().expect("implementor");
+    match implementor.run {
+        JobRun::AgentPrompt(prompt) => assert!(!prompt.contains("Project audit rubric")),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected implementor prompt")
+        }
+    }
+
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+    let auditor = wf.start_next_job().expect("auditor");
+    match auditor.run {
+        JobRun::AgentPrompt(prompt) => {
+            assert!(prompt.contains(
+                "Project audit rubric (apply in addition to the checks above):\nEvery handler validates its input.\n"
+            ));
+        }
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
     }
+
+    wf.set_audit_rubric(Some("   ".to_string()));
+    assert_eq!(wf.audit_rubric_block(), "");
 }
 
 #[test]
@@ -2290,6 +3451,7 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -2299,11 +3461,13 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
                 title: "Impl guide".to_string(),
                 url: "https://docs.example/impl".to_string(),
                 summary: "Implementation guidance".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::Implementor,
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -2313,11 +3477,13 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
                 title: "Audit guide".to_string(),
                 url: "https://docs.example/impl-audit".to_string(),
                 summary: "Audit guidance".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::Auditor,
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -2327,11 +3493,13 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
                 title: "Testing guide".to_string(),
                 url: "https://docs.example/test-writer".to_string(),
                 summary: "Test-writing guidance".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::TestWriter,
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-audit".to_string(),
@@ -2341,11 +3509,13 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
                 title: "Test audit guide".to_string(),
                 url: "https://docs.example/test-audit".to_string(),
                 summary: "Test audit guidance".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::Auditor,
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -2356,6 +3526,7 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "final".to_string(),
@@ -2365,11 +3536,13 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
                 title: "Final review guide".to_string(),
                 url: "https://docs.example/final-audit".to_string(),
                 summary: "Final audit guidance".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::FinalAudit,
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2408,7 +3581,7 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
                     panic!("unexpected prompt variant: {prompt}");
                 }
             }
-            JobRun::DeterministicTestRun => {}
+            JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {}
         }
 
         match job.role {
@@ -2436,6 +3609,154 @@ fn worker_prompts_prepend_task_docs_and_web_read_instruction() {
     assert!(saw_final_audit, "final-audit prompt was not observed");
 }
 
+#[test]
+fn worker_prompts_inline_loaded_doc_snapshots_instead_of_web_reads() {
+    let doc = |title: &str, snapshot: Option<&str>| PlannerTaskDocFileEntry {
+        title: title.to_string(),
+        url: format!("https://docs.example/{title}"),
+        summary: String::new(),
+        snapshot: snapshot.map(str::to_string),
+    };
+    let task = |id: &str, parent: Option<&str>, kind, docs| PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: id.to_string(),
+        details: format!("{id} details"),
+        docs,
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    };
+    let mut wf = Workflow::default();
+    wf.sync_planner_tasks_from_file(vec![
+        task("top", None, PlannerTaskKindFile::Task, Vec::new()),
+        task(
+            "impl",
+            Some("top"),
+            PlannerTaskKindFile::Implementor,
+            vec![
+                doc("serde", Some("docs/impl/serde.txt")),
+                doc("tokio", Some("docs/impl/missing.txt")),
+            ],
+        ),
+        task(
+            "audit",
+            Some("impl"),
+            PlannerTaskKindFile::Auditor,
+            vec![doc("rubric", Some("docs/audit/rubric.txt"))],
+        ),
+    ])
+    .expect("sync should succeed");
+    wf.set_doc_snapshots(HashMap::from([(
+        "docs/impl/serde.txt".to_string(),
+        "Derive Serialize with #[derive(Serialize)].".to_string(),
+    )]));
+    wf.start_execution();
+
+    let job = wf.start_next_job().expect("implementor job");
+    let JobRun::AgentPrompt(prompt) = job.run else {
+        panic!("implementor should get a prompt");
+    };
+    assert!(prompt.contains("the ones with a snapshot below are included verbatim"));
+    assert!(prompt.contains("<<<\nDerive Serialize with #[derive(Serialize)].\n>>>"));
+    assert_eq!(prompt.matches("Snapshot:").count(), 1, "{prompt}");
+
+    wf.set_doc_snapshots(HashMap::from([(
+        "docs/audit/rubric.txt".to_string(),
+        "Check error paths.".to_string(),
+    )]));
+    wf.append_active_output("completed".to_string());
+    wf.finish_active_job(true, 0);
+    let job = wf.start_next_job().expect("auditor job");
+    let JobRun::AgentPrompt(prompt) = job.run else {
+        panic!("auditor should get a prompt");
+    };
+    assert!(prompt.contains("Every linked document is included below as a local snapshot"));
+    assert!(!prompt.contains("from the web"));
+}
+
+#[test]
+fn active_prompt_sections_flag_cut_snapshots_dropped_context_and_the_audited_report() {
+    let mut wf = Workflow::default();
+    wf.sync_planner_tasks_from_file(vec![
+        PlannerTaskFileEntry {
+            id: "top".to_string(),
+            title: "Top".to_string(),
+            details: "top details".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::Task,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: None,
+            order: Some(0),
+            ..Default::default()
+        },
+        PlannerTaskFileEntry {
+            id: "impl".to_string(),
+            title: "Impl".to_string(),
+            details: "impl details".to_string(),
+            docs: vec![PlannerTaskDocFileEntry {
+                title: "serde".to_string(),
+                url: "https://docs.example/serde".to_string(),
+                summary: String::new(),
+                snapshot: Some("docs/impl/serde.txt".to_string()),
+            }],
+            kind: PlannerTaskKindFile::Implementor,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: Some("top".to_string()),
+            order: Some(0),
+            ..Default::default()
+        },
+        PlannerTaskFileEntry {
+            id: "audit".to_string(),
+            title: "Audit".to_string(),
+            details: "audit details".to_string(),
+            docs: Vec::new(),
+            kind: PlannerTaskKindFile::Auditor,
+            status: PlannerTaskStatusFile::Pending,
+            parent_id: Some("impl".to_string()),
+            order: Some(0),
+            ..Default::default()
+        },
+    ])
+    .expect("sync should succeed");
+    wf.set_doc_snapshots(HashMap::from([(
+        "docs/impl/serde.txt".to_string(),
+        format!(
+            "Derive Serialize.\n[snapshot truncated at {} characters]",
+            crate::doc_snapshots::MAX_SNAPSHOT_PROMPT_CHARS
+        ),
+    )]));
+    wf.replace_rolling_context_entries((0..20).map(|idx| format!("entry {idx}")).collect());
+    wf.start_execution();
+
+    wf.start_next_job().expect("implementor job");
+    let sections = wf.active_prompt_sections();
+    let names = sections
+        .iter()
+        .map(|section| section.name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["docs", "context"]);
+    assert_eq!(
+        sections[0].truncated.as_deref(),
+        Some("docs/impl/serde.txt cut at 20000 characters")
+    );
+    assert_eq!(
+        sections[1].truncated.as_deref(),
+        Some("4 older entries dropped (keeps the last 16)")
+    );
+
+    wf.append_active_output("implemented the serializer".to_string());
+    wf.finish_active_job(true, 0);
+    wf.start_next_job().expect("auditor job");
+    let sections = wf.active_prompt_sections();
+    let report = sections
+        .iter()
+        .find(|section| section.name == "report")
+        .expect("auditor prompt carries the implementor report");
+    assert!(report.bytes > 0);
+}
+
 #[test]
 fn start_execution_picks_unfinished_task_when_some_are_done() {
     let mut wf = Workflow::default();
@@ -2449,6 +3770,7 @@ fn start_execution_picks_unfinished_task_when_some_are_done() {
             status: PlannerTaskStatusFile::Done,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "todo-task".to_string(),
@@ -2459,6 +3781,7 @@ fn start_execution_picks_unfinished_task_when_some_are_done() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2468,7 +3791,9 @@ fn start_execution_picks_unfinished_task_when_some_are_done() {
     assert_eq!(first.role, WorkerRole::Implementor);
     match first.run {
         JobRun::AgentPrompt(prompt) => assert!(prompt.contains("Pending task")),
-        JobRun::DeterministicTestRun => panic!("expected implementor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected implementor prompt")
+        }
     }
 }
 
@@ -2485,6 +3810,7 @@ fn start_execution_resumes_with_pending_implementor_auditor_subtask() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -2495,6 +3821,7 @@ fn start_execution_resumes_with_pending_implementor_auditor_subtask() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -2505,6 +3832,7 @@ fn start_execution_resumes_with_pending_implementor_auditor_subtask() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -2515,6 +3843,7 @@ fn start_execution_resumes_with_pending_implementor_auditor_subtask() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -2525,6 +3854,7 @@ fn start_execution_resumes_with_pending_implementor_auditor_subtask() {
             status: PlannerTaskStatusFile::Done,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2536,7 +3866,9 @@ fn start_execution_resumes_with_pending_implementor_auditor_subtask() {
         JobRun::AgentPrompt(prompt) => {
             assert!(prompt.contains("reviewing implementation output"));
         }
-        JobRun::DeterministicTestRun => panic!("expected auditor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
     }
 }
 
@@ -2553,6 +3885,7 @@ fn start_execution_resumes_with_in_progress_implementor_and_pending_auditor_subt
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -2563,6 +3896,7 @@ fn start_execution_resumes_with_in_progress_implementor_and_pending_auditor_subt
             status: PlannerTaskStatusFile::InProgress,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -2573,6 +3907,7 @@ fn start_execution_resumes_with_in_progress_implementor_and_pending_auditor_subt
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2584,7 +3919,9 @@ fn start_execution_resumes_with_in_progress_implementor_and_pending_auditor_subt
         JobRun::AgentPrompt(prompt) => {
             assert!(prompt.contains("reviewing implementation output"));
         }
-        JobRun::DeterministicTestRun => panic!("expected auditor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
     }
 }
 
@@ -2601,6 +3938,7 @@ fn first_implementor_pass_enqueues_implementor_auditor_before_test_writer() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -2611,6 +3949,7 @@ fn first_implementor_pass_enqueues_implementor_auditor_before_test_writer() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -2621,6 +3960,7 @@ fn first_implementor_pass_enqueues_implementor_auditor_before_test_writer() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-runner".to_string(),
@@ -2631,6 +3971,7 @@ fn first_implementor_pass_enqueues_implementor_auditor_before_test_writer() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw".to_string(),
@@ -2641,6 +3982,7 @@ fn first_implementor_pass_enqueues_implementor_auditor_before_test_writer() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "tw-runner".to_string(),
@@ -2651,6 +3993,7 @@ fn first_implementor_pass_enqueues_implementor_auditor_before_test_writer() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2683,6 +4026,7 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "top-a-impl".to_string(),
@@ -2693,6 +4037,7 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top-a".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "top-a-audit".to_string(),
@@ -2703,6 +4048,7 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top-a-impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "top-a-tw".to_string(),
@@ -2713,6 +4059,7 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top-a".to_string()),
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "top-a-tw-runner".to_string(),
@@ -2723,6 +4070,7 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top-a-tw".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "top-b".to_string(),
@@ -2733,6 +4081,7 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "top-b-impl".to_string(),
@@ -2743,6 +4092,7 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top-b".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "top-b-audit".to_string(),
@@ -2753,6 +4103,7 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("top-b-impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2782,10 +4133,48 @@ fn resume_does_not_mark_task_done_with_pending_implementor_auditor() {
         JobRun::AgentPrompt(prompt) => {
             assert!(prompt.contains("reviewing implementation output"));
         }
-        JobRun::DeterministicTestRun => panic!("expected auditor prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected auditor prompt")
+        }
     }
 }
 
+#[test]
+fn interrupt_execution_resets_the_active_job_so_resume_restarts_it() {
+    let mut wf = Workflow::default();
+    seed_single_default_task(&mut wf, "Do work");
+    wf.start_execution();
+    let first = wf.start_next_job().expect("implementor should start");
+    assert_eq!(first.role, WorkerRole::Implementor);
+
+    let interrupted = wf
+        .interrupt_execution()
+        .expect("active job should be reported");
+    assert!(
+        interrupted.starts_with("Implementor pass 1"),
+        "{interrupted}"
+    );
+    assert!(interrupted.contains("(Do work)"), "{interrupted}");
+    assert!(!wf.execution_enabled());
+    assert!(wf.active_job_meta().is_none());
+    let snapshot = wf.planner_tasks_for_file();
+    assert!(
+        snapshot
+            .iter()
+            .all(|entry| entry.status != PlannerTaskStatusFile::InProgress),
+        "{snapshot:?}"
+    );
+    assert_eq!(wf.interrupt_execution(), None);
+
+    let mut resumed = Workflow::default();
+    resumed
+        .sync_planner_tasks_from_file(snapshot)
+        .expect("reload should succeed");
+    resumed.start_execution();
+    let next = resumed.start_next_job().expect("implementor should restart");
+    assert_eq!(next.role, WorkerRole::Implementor);
+}
+
 #[test]
 fn enqueue_ready_does_not_mutate_done_or_final_audit_roots() {
     let mut wf = Workflow::default();
@@ -2799,6 +4188,7 @@ fn enqueue_ready_does_not_mutate_done_or_final_audit_roots() {
             status: PlannerTaskStatusFile::Done,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "fa".to_string(),
@@ -2809,6 +4199,7 @@ fn enqueue_ready_does_not_mutate_done_or_final_audit_roots() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2867,6 +4258,7 @@ fn final_audit_runs_after_non_final_tasks_complete() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "fa".to_string(),
@@ -2877,6 +4269,7 @@ fn final_audit_runs_after_non_final_tasks_complete() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -2942,7 +4335,9 @@ fn final_audit_requires_explicit_pass_token_to_complete() {
                 !prompt.contains("If tests exist, ask whether to write new tests as part of this work.")
             );
         }
-        JobRun::DeterministicTestRun => panic!("expected final audit prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected final audit prompt")
+        }
     }
 
     wf.append_active_output("PASS".to_string());
@@ -2971,7 +4366,9 @@ fn final_audit_prompt_in_tests_mode_off_uses_off_policy_language() {
             );
             assert_prompt_omits_removed_test_decision_questions(&prompt);
         }
-        JobRun::DeterministicTestRun => panic!("expected final audit prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected final audit prompt")
+        }
     }
 }
 
@@ -2991,7 +4388,9 @@ fn final_audit_prompt_in_tests_mode_on_uses_on_policy_and_omits_master_decision_
             );
             assert_prompt_omits_removed_test_decision_questions(&prompt);
         }
-        JobRun::DeterministicTestRun => panic!("expected final audit prompt"),
+        JobRun::DeterministicTestRun | JobRun::PluginStep { .. } | JobRun::Verify { .. } => {
+            panic!("expected final audit prompt")
+        }
     }
 }
 
@@ -3001,7 +4400,7 @@ fn workflow_master_prompt_in_tests_mode_on_omits_test_decision_questions() {
     wf.set_tests_mode_enabled(true);
     seed_single_default_task(&mut wf, "Do work");
 
-    let prompt = wf.prepare_master_prompt("Plan next steps");
+    let prompt = wf.prepare_master_prompt("Plan next steps", &PromptSections::default());
     assert!(prompt.contains("Tests mode is currently ON."));
     assert!(!prompt.contains("Tests mode is currently OFF."));
     assert_prompt_omits_removed_test_decision_questions(&prompt);
@@ -3013,12 +4412,77 @@ fn workflow_master_prompt_in_tests_mode_off_omits_test_decision_questions() {
     wf.set_tests_mode_enabled(false);
     seed_single_default_task(&mut wf, "Do work");
 
-    let prompt = wf.prepare_master_prompt("Plan next steps");
+    let prompt = wf.prepare_master_prompt("Plan next steps", &PromptSections::default());
     assert!(prompt.contains("Tests mode is currently OFF."));
     assert!(!prompt.contains("Tests mode is currently ON."));
     assert_prompt_omits_removed_test_decision_questions(&prompt);
 }
 
+#[test]
+fn lowered_audit_limit_and_disabled_test_writer_audits_shorten_the_run() {
+    let task = |id: &str, parent: Option<&str>, kind| PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: id.to_string(),
+        details: format!("{id} details"),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent.map(str::to_string),
+        order: Some(0),
+        ..Default::default()
+    };
+    let mut wf = Workflow::default();
+    wf.sync_planner_tasks_from_file(vec![
+        task("top", None, PlannerTaskKindFile::Task),
+        task("impl", Some("top"), PlannerTaskKindFile::Implementor),
+        task("impl-audit", Some("impl"), PlannerTaskKindFile::Auditor),
+        task("tw", Some("top"), PlannerTaskKindFile::TestWriter),
+        task("tw-audit", Some("tw"), PlannerTaskKindFile::Auditor),
+        task("tw-runner", Some("tw"), PlannerTaskKindFile::TestRunner),
+    ])
+    .expect("sync should succeed");
+    wf.set_max_audit_retries(2);
+    wf.set_test_writer_audits_enabled(false);
+    wf.start_execution();
+
+    let _ = wf.start_next_job().expect("implementor");
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+    let mut last_messages = Vec::new();
+    for audit_pass in 1..=2 {
+        let auditor = wf.start_next_job().expect("auditor");
+        let JobRun::AgentPrompt(prompt) = auditor.run else {
+            panic!("expected auditor prompt");
+        };
+        assert!(prompt.contains(&format!("Audit pass: {audit_pass} of 2")));
+        wf.append_active_output("FAIL".to_string());
+        wf.append_active_output("- Still broken".to_string());
+        last_messages = wf.finish_active_job(true, 0);
+        if audit_pass < 2 {
+            let _ = wf.start_next_job().expect("implementor retry");
+            wf.append_active_output("fixed".to_string());
+            wf.finish_active_job(true, 0);
+        }
+    }
+    assert!(
+        last_messages
+            .iter()
+            .any(|m| m.contains("Max retries (2) reached"))
+    );
+
+    let writer = wf.start_next_job().expect("test writer");
+    assert_eq!(writer.role, WorkerRole::TestWriter);
+    wf.append_active_output("wrote tests".to_string());
+    let messages = wf.finish_active_job(true, 0);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("test-writer audit skipped"))
+    );
+    let runner = wf.start_next_job().expect("test runner");
+    assert_eq!(runner.role, WorkerRole::TestRunner);
+}
+
 #[test]
 fn final_audit_retry_limit_stops_requeue_and_records_failure() {
     let mut wf = Workflow::default();
@@ -3168,11 +4632,13 @@ fn right_pane_shows_docs_badge_for_non_test_runner_only() {
                 title: "Doc".to_string(),
                 url: "https://example.com".to_string(),
                 summary: "summary".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::Task,
             status: PlannerTaskStatusFile::Pending,
             parent_id: None,
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl".to_string(),
@@ -3183,6 +4649,7 @@ fn right_pane_shows_docs_badge_for_non_test_runner_only() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("task".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "impl-audit".to_string(),
@@ -3193,6 +4660,7 @@ fn right_pane_shows_docs_badge_for_non_test_runner_only() {
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(0),
+            ..Default::default()
         },
         PlannerTaskFileEntry {
             id: "runner".to_string(),
@@ -3202,11 +4670,13 @@ fn right_pane_shows_docs_badge_for_non_test_runner_only() {
                 title: "Should hide".to_string(),
                 url: "https://example.com".to_string(),
                 summary: "summary".to_string(),
+                snapshot: None,
             }],
             kind: PlannerTaskKindFile::TestRunner,
             status: PlannerTaskStatusFile::Pending,
             parent_id: Some("impl".to_string()),
             order: Some(1),
+            ..Default::default()
         },
     ])
     .expect("sync should succeed");
@@ -3229,11 +4699,13 @@ fn right_pane_docs_toggle_expands_full_docs_without_collapsed_preview() {
             title: "Doc Title".to_string(),
             url: "https://example.com/doc".to_string(),
             summary: "Doc summary".to_string(),
+            snapshot: None,
         }],
         kind: PlannerTaskKindFile::Task,
         status: PlannerTaskStatusFile::Pending,
         parent_id: None,
         order: Some(0),
+        ..Default::default()
     }])
     .expect("sync should succeed");
 
@@ -3254,3 +4726,266 @@ fn right_pane_docs_toggle_expands_full_docs_without_collapsed_preview() {
     assert!(expanded_text.contains("https://example.com/doc"));
     assert!(expanded_text.contains("Doc summary"));
 }
+
+#[test]
+fn worker_usage_and_time_are_charged_to_the_top_task_details() {
+    let mut wf = Workflow::default();
+    seed_single_default_task(&mut wf, "Ship feature");
+    assert!(!wf.record_worker_usage(TokenUsage {
+        input_tokens: 10,
+        output_tokens: 1,
+        cost_usd: 0.0,
+    }));
+    wf.start_execution();
+
+    wf.start_next_job().expect("implementor");
+    assert!(wf.record_worker_usage(TokenUsage {
+        input_tokens: 12_000,
+        output_tokens: 400,
+        cost_usd: 0.25,
+    }));
+    wf.record_worker_elapsed(Duration::from_secs(200));
+    wf.finish_active_job(true, 0);
+    // Usage drained after the worker exited still belongs to its task.
+    wf.record_worker_usage(TokenUsage {
+        input_tokens: 500,
+        output_tokens: 100,
+        cost_usd: 0.05,
+    });
+    wf.start_next_job().expect("auditor");
+    wf.record_worker_elapsed(Duration::from_secs(45));
+
+    assert_eq!(
+        wf.task_usage_stats("top"),
+        Some(TaskUsageStats {
+            elapsed: Duration::from_secs(245),
+            passes: 2,
+            escalations: 0,
+            usage: TokenUsage {
+                input_tokens: 12_500,
+                output_tokens: 500,
+                cost_usd: 0.3,
+            },
+        })
+    );
+
+    let collapsed = wf.right_pane_block_view(80, &HashSet::new());
+    let toggle = collapsed
+        .toggles
+        .iter()
+        .find(|toggle| toggle.task_key == "top")
+        .expect("usage toggle");
+    assert_eq!(
+        collapsed.lines[toggle.line_index],
+        "  [time & usage] 2 passes, 4m 05s [+]"
+    );
+    assert!(!collapsed.lines.join("\n").contains("est. cost"));
+
+    let expanded = wf
+        .right_pane_block_view(80, &HashSet::from(["top".to_string()]))
+        .lines
+        .join("\n");
+    assert!(expanded.contains("  [time & usage] 2 passes, 4m 05s [-]"));
+    assert!(expanded.contains("    - wall-clock: 4m 05s"));
+    assert!(expanded.contains("    - passes: 2"));
+    assert!(expanded.contains("    - tokens: 12.5k in / 500 out"));
+    assert!(expanded.contains("    - est. cost: $0.30"));
+
+    wf.clear_task_usage();
+    assert_eq!(wf.task_usage_stats("top"), None);
+}
+
+#[test]
+fn audit_prompt_for_planner_task_builds_auditor_and_final_audit_prompts() {
+    let mut wf = Workflow::default();
+    seed_single_default_task_with_final_audit(&mut wf, "Ship feature");
+
+    let audit = wf
+        .audit_prompt_for_planner_task(
+            "impl-audit",
+            Some("implemented the parser".to_string()),
+            Some("src/parser.rs".to_string()),
+            2,
+        )
+        .expect("auditor prompt");
+    assert!(audit.contains("You are an audit sub-agent reviewing implementation output."));
+    assert!(audit.contains("Parent implementor details:\nimplementor details"));
+    assert!(audit.contains("Audit pass: 2 of"));
+    assert!(audit.contains("Implementor changed-files summary:\nsrc/parser.rs"));
+    assert!(audit.contains("Implementation output to audit:\nimplemented the parser"));
+
+    let final_audit = wf
+        .audit_prompt_for_planner_task("fa", Some("missing error handling".to_string()), None, 1)
+        .expect("final audit prompt");
+    assert!(final_audit.contains("You are a final audit sub-agent."));
+    assert!(
+        final_audit.contains("Previous final-audit feedback to address:\nmissing error handling")
+    );
+
+    assert_eq!(
+        wf.audit_prompt_for_planner_task("tw-runner", None, None, 1),
+        Err("Planner task tw-runner is not an audit task".to_string())
+    );
+    assert_eq!(
+        wf.audit_prompt_for_planner_task("missing", None, None, 1),
+        Err("Planner task missing not found".to_string())
+    );
+}
+
+#[test]
+fn queued_jobs_can_be_listed_reordered_and_dropped() {
+    let mut wf = Workflow::default();
+    seed_two_default_tasks(&mut wf, "First", "Second");
+    let node_id = |wf: &Workflow, key: &str| {
+        find_path_by_external_id(&wf.tasks, key)
+            .and_then(|path| path.last().map(|node| node.id))
+            .expect("seeded node")
+    };
+    let (top1, top2) = (node_id(&wf, "top1"), node_id(&wf, "top2"));
+    let implementor = |top_task_id, implementor_id| WorkerJob {
+        top_task_id,
+        kind: WorkerJobKind::Implementor {
+            implementor_id,
+            pass: 1,
+            feedback: None,
+            resume_auditor_id: None,
+            resume_audit_pass: None,
+        },
+    };
+    wf.queue.extend([
+        implementor(top1, node_id(&wf, "impl1")),
+        WorkerJob {
+            top_task_id: top1,
+            kind: WorkerJobKind::TestWriter {
+                test_writer_id: node_id(&wf, "tw1"),
+                pass: 2,
+                feedback: None,
+                skip_test_runner_on_success: false,
+                resume_auditor_id: None,
+                resume_audit_pass: None,
+            },
+        },
+        implementor(top2, node_id(&wf, "impl2")),
+        WorkerJob {
+            top_task_id: top2,
+            kind: WorkerJobKind::FinalAudit {
+                final_audit_id: top2,
+                pass: 1,
+                feedback: None,
+            },
+        },
+    ]);
+    let labels = |wf: &Workflow| {
+        wf.queued_jobs()
+            .iter()
+            .map(|job| job.task_key.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        wf.queued_jobs()[1].label(),
+        "test_writer task:tw1 pass 2 (First)"
+    );
+
+    let moved = wf.move_queued_job(2, 0).expect("move second task first");
+    assert_eq!(moved.task_key, "impl2");
+    assert_eq!(labels(&wf), vec!["impl2", "impl1", "tw1", "top2"]);
+    assert!(wf.move_queued_job(0, 3).is_err());
+    assert!(wf.move_queued_job(3, 0).is_err());
+    assert!(wf.move_queued_job(0, 4).is_err());
+
+    let dropped = wf.drop_queued_job(2).expect("drop test writer job");
+    assert_eq!(dropped.role, WorkerRole::TestWriter);
+    assert_eq!(labels(&wf), vec!["impl2", "impl1", "top2"]);
+    assert!(wf.drop_queued_job(3).is_err());
+    let lines = wf.right_pane_lines();
+    let queue_at = lines
+        .iter()
+        .position(|line| line.starts_with("- queue: 3 job(s)"))
+        .expect("queue listed in the execution section");
+    assert_eq!(
+        lines[queue_at + 1],
+        "  1. implementor task:impl2 pass 1 (Second)"
+    );
+}
+
+#[test]
+fn blocked_audit_parks_the_task_until_unblock_answers_it() {
+    let entry = |id: &str, parent: Option<&str>, kind, order: u32| PlannerTaskFileEntry {
+        id: id.to_string(),
+        title: format!("{id} title"),
+        details: "details".to_string(),
+        docs: Vec::new(),
+        kind,
+        status: PlannerTaskStatusFile::Pending,
+        parent_id: parent.map(str::to_string),
+        order: Some(order),
+        ..Default::default()
+    };
+    let mut wf = Workflow::default();
+    let mut unexplained = entry("top1", None, PlannerTaskKindFile::Task, 0);
+    unexplained.status = PlannerTaskStatusFile::Blocked;
+    let err = wf
+        .sync_planner_tasks_from_file(vec![unexplained])
+        .expect_err("blocked without a reason");
+    assert!(err.contains("Planner task top1 is blocked"));
+
+    wf.sync_planner_tasks_from_file(vec![
+        entry("top1", None, PlannerTaskKindFile::Task, 0),
+        entry("impl1", Some("top1"), PlannerTaskKindFile::Implementor, 0),
+        entry("audit1", Some("impl1"), PlannerTaskKindFile::Auditor, 0),
+        entry("top2", None, PlannerTaskKindFile::Task, 1),
+        entry("impl2", Some("top2"), PlannerTaskKindFile::Implementor, 0),
+        entry("audit2", Some("impl2"), PlannerTaskKindFile::Auditor, 0),
+    ])
+    .expect("sync should succeed");
+    wf.start_execution();
+    let implementor = wf.start_next_job().expect("implementor");
+    let blocked_top = implementor.top_task_id;
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+    let _ = wf.start_next_job().expect("audit");
+    wf.append_active_output("BLOCKED".to_string());
+    wf.append_active_output("Which OAuth provider should login use?".to_string());
+    let messages = wf.finish_active_job(true, 0);
+    assert!(messages.iter().any(|m| m.contains(
+        "is blocked on you: Which OAuth provider should login use?\nAnswer with /unblock top1 <answer>"
+    )));
+    let file = wf.planner_tasks_for_file();
+    assert_eq!(file[0].status, PlannerTaskStatusFile::Blocked);
+    assert_eq!(
+        file[0].blocked_reason.as_deref(),
+        Some("Which OAuth provider should login use?")
+    );
+    assert!(
+        wf.right_pane_lines()
+            .iter()
+            .any(|line| line.contains("[?] Task: top1 title"))
+    );
+
+    // The scheduler moves on to the next task and then waits.
+    let next = wf.start_next_job().expect("next task");
+    assert_ne!(next.top_task_id, blocked_top);
+    wf.append_active_output("implemented".to_string());
+    wf.finish_active_job(true, 0);
+    let _ = wf.start_next_job().expect("audit");
+    wf.append_active_output("PASS".to_string());
+    wf.finish_active_job(true, 0);
+    assert!(wf.start_next_job().is_none());
+
+    assert_eq!(
+        wf.unblock_task("top2", "GitHub").expect_err("not blocked"),
+        "Task \"top2 title\" is not blocked."
+    );
+    wf.unblock_task("top1", "GitHub").expect("unblock");
+    let file = wf.planner_tasks_for_file();
+    assert_eq!(file[0].status, PlannerTaskStatusFile::Pending);
+    assert_eq!(file[0].blocked_reason, None);
+    let retry = wf.start_next_job().expect("re-queued implementor");
+    assert_eq!(retry.top_task_id, blocked_top);
+    match retry.run {
+        JobRun::AgentPrompt(prompt) => assert!(prompt.contains(
+            "User answers for this task (follow them):\n- Which OAuth provider should login use? -> GitHub\n"
+        )),
+        _ => panic!("expected agent prompt"),
+    }
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+#[test]
+fn names_match_at_any_depth_and_anchored_patterns_from_the_root() {
+    let ignore = WorkspaceIgnore::from_texts(
+        "# build output\ntarget/\n*.log\n",
+        "/dist\nvendor/**/generated\n",
+    );
+    assert!(ignore.is_ignored("target", true));
+    assert!(ignore.is_ignored("web/target/debug/app", false));
+    assert!(!ignore.is_ignored("src/target.rs", false));
+    assert!(ignore.is_ignored("logs/server.log", false));
+    assert!(ignore.is_ignored("dist/app.js", false));
+    assert!(!ignore.is_ignored("web/dist/app.js", false));
+    assert!(ignore.is_ignored("vendor/lib/generated/api.rs", false));
+    assert!(ignore.is_ignored("./vendor/generated", true));
+    assert!(!ignore.is_ignored("src/main.rs", false));
+    assert_eq!(
+        ignore.metaagent_patterns(),
+        ["/dist", "vendor/**/generated"]
+    );
+}
+
+#[test]
+fn later_negations_win_except_below_an_ignored_directory() {
+    let ignore = WorkspaceIgnore::from_texts("*.lock\nbuild/\n", "!Cargo.lock\n!build/keep.txt\n");
+    assert!(ignore.is_ignored("web/yarn.lock", false));
+    assert!(!ignore.is_ignored("Cargo.lock", false));
+    assert!(ignore.is_ignored("build/keep.txt", false));
+    assert!(!WorkspaceIgnore::default().is_ignored("build/keep.txt", false));
+}
+
+#[test]
+fn load_reads_both_files_from_the_workspace_root() {
+    let root =
+        std::env::temp_dir().join(format!("agentbob-workspace-ignore-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).expect("create workspace");
+    fs::write(root.join(".gitignore"), "node_modules/\n").expect("write .gitignore");
+    fs::write(root.join(METAAGENT_IGNORE_FILE_NAME), "third_party/\n").expect("write ignore");
+    let ignore = WorkspaceIgnore::load(&root);
+    assert!(ignore.is_ignored("web/node_modules/react/index.js", false));
+    assert!(ignore.is_ignored("third_party/zlib/zlib.h", false));
+    assert!(!ignore.is_ignored("src/lib.rs", false));
+    let _ = fs::remove_dir_all(&root);
+}